@@ -1,13 +1,40 @@
 //! Memory client implementation for the Rook hosted API.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+#[cfg(feature = "native")]
 use rook_core::error::{RookError, RookResult};
-use rook_core::types::MemoryItem;
+#[cfg(feature = "native")]
+use rook_core::sync::{Changeset, SyncReport, SyncStatus};
 
-use reqwest::Client;
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::AsyncRead;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::error::{retry_after, ClientError, ClientResult};
+use crate::offline::Operation;
+#[cfg(feature = "native")]
+use crate::offline::{OfflineQueue, QueueStatus, ReplayReport};
+use crate::types::{
+    ExportSection, FieldSelection, ImportDedup, ImportStats, MemoryItem, RestoreStats,
+};
+
+/// URL of a `rook-server` running locally with its default port.
+pub const DEFAULT_BASE_URL: &str = "http://localhost:8080";
+
+/// Header carrying the idempotency key of a write.
+const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
 
 /// Client for the Rook hosted API.
 pub struct MemoryClient {
@@ -16,6 +43,120 @@ pub struct MemoryClient {
     base_url: String,
     org_id: Option<String>,
     project_id: Option<String>,
+    #[cfg(feature = "native")]
+    offline: Option<OfflineQueue>,
+    retry: RetryPolicy,
+}
+
+/// How failed requests are retried.
+///
+/// Reads, deletes and other idempotent requests are retried when the API is
+/// unreachable, unavailable (502/503/504) or rate limited. So are `add` and
+/// `update`, which carry an idempotency key the server deduplicates on.
+/// Other writes, e.g. `smart_ingest`, are only retried when the server
+/// cannot have handled them: a refused connection or a rate limit. Streamed
+/// imports are never retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further one.
+    pub initial_backoff: Duration,
+    /// Longest delay between attempts. A `Retry-After` beyond it is not
+    /// waited out but returned as [`ClientError::RateLimit`].
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.max_backoff)
+    }
+}
+
+/// Which failures a request may be retried after.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Retry {
+    /// Sending the request again has no further effect.
+    Idempotent,
+    /// Only retry when the server cannot have handled the request.
+    Unapplied,
+}
+
+/// What happened to a write.
+enum WriteOutcome {
+    /// The API accepted the write.
+    Sent(Response),
+    /// The API was unreachable; the write is in the offline queue.
+    #[cfg_attr(not(feature = "native"), allow(dead_code))]
+    Queued,
+}
+
+/// Whether a request failed before the server could have received it.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_unsent(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+/// Whether a request failed because the API could not be reached.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_unreachable(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout()
+}
+
+/// Whether a request failed before the server could have received it.
+///
+/// The browser's fetch does not say, so no failure counts as unsent.
+#[cfg(target_arch = "wasm32")]
+fn is_unsent(_error: &reqwest::Error) -> bool {
+    false
+}
+
+/// Whether a request failed because the API could not be reached.
+///
+/// The browser's fetch reports network failures as request errors.
+#[cfg(target_arch = "wasm32")]
+fn is_unreachable(error: &reqwest::Error) -> bool {
+    error.is_request() || error.is_timeout()
+}
+
+/// Wait before a retry.
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await
+}
+
+/// Wait before a retry, on a browser timer.
+#[cfg(target_arch = "wasm32")]
+async fn sleep(delay: Duration) {
+    gloo_timers::future::sleep(delay).await
+}
+
+/// Whether a status means the API is temporarily unavailable.
+fn is_unavailable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    )
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,6 +189,11 @@ struct SearchResultItem {
     updated_at: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectedResponse {
+    results: Vec<serde_json::Value>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct GetResponse {
     results: Vec<MemoryItemResponse>,
@@ -65,23 +211,71 @@ struct MemoryItemResponse {
     updated_at: Option<String>,
 }
 
+impl MemoryItemResponse {
+    fn into_item(self) -> MemoryItem {
+        let mut item = MemoryItem::new(self.id, self.memory);
+        if let Some(meta) = self.metadata {
+            item = item.with_metadata(meta);
+        }
+        item.created_at = self.created_at;
+        item.updated_at = self.updated_at;
+        item
+    }
+}
+
+/// History, wrapped in an object or as a bare list.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum HistoryResponse {
+    Wrapped { history: Vec<serde_json::Value> },
+    Bare(Vec<serde_json::Value>),
+}
+
+/// Parse a successful response body.
+async fn parse<T: DeserializeOwned>(response: Response) -> ClientResult<T> {
+    Ok(response.json().await?)
+}
+
+/// Pass a successful response through, turning any other into its error.
+async fn check(response: Response) -> ClientResult<Response> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(ClientError::from_response(response).await)
+    }
+}
+
+/// A numeric or timestamp `x-rook-export-*` header.
+fn export_header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers
+        .get(format!("x-rook-export-{}", name))?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
 impl MemoryClient {
     /// Create a new memory client.
-    pub fn new(api_key: &str) -> RookResult<Self> {
+    pub fn new(api_key: &str) -> ClientResult<Self> {
         Self::with_options(api_key, None, None, None)
     }
 
     /// Create a new memory client with options.
+    ///
+    /// `base_url` is the root of a `rook-server`, e.g.
+    /// `https://rook.example.com`, and defaults to [`DEFAULT_BASE_URL`].
     pub fn with_options(
         api_key: &str,
         base_url: Option<&str>,
         org_id: Option<&str>,
         project_id: Option<&str>,
-    ) -> RookResult<Self> {
+    ) -> ClientResult<Self> {
         let client = Client::new();
         let base_url = base_url
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| "https://api.mem0.ai/v1".to_string());
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
 
         Ok(Self {
             client,
@@ -89,24 +283,124 @@ impl MemoryClient {
             base_url,
             org_id: org_id.map(|s| s.to_string()),
             project_id: project_id.map(|s| s.to_string()),
+            #[cfg(feature = "native")]
+            offline: None,
+            retry: RetryPolicy::default(),
         })
     }
 
+    /// Set how failed requests are retried.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Create a client from environment variables.
-    pub fn from_env() -> RookResult<Self> {
+    ///
+    /// `ROOK_OFFLINE_QUEUE`, the path of an offline queue, needs the
+    /// `native` feature.
+    pub fn from_env() -> ClientResult<Self> {
         let api_key = std::env::var("ROOK_API_KEY")
-            .map_err(|_| RookError::Configuration("ROOK_API_KEY not set".to_string()))?;
+            .map_err(|_| ClientError::Configuration("ROOK_API_KEY not set".to_string()))?;
 
         let base_url = std::env::var("ROOK_BASE_URL").ok();
         let org_id = std::env::var("ROOK_ORG_ID").ok();
         let project_id = std::env::var("ROOK_PROJECT_ID").ok();
 
-        Self::with_options(
+        let client = Self::with_options(
             &api_key,
             base_url.as_deref(),
             org_id.as_deref(),
             project_id.as_deref(),
-        )
+        )?;
+
+        #[cfg(feature = "native")]
+        if let Ok(path) = std::env::var("ROOK_OFFLINE_QUEUE") {
+            return Ok(client.with_offline_queue(OfflineQueue::open(path)?));
+        }
+        Ok(client)
+    }
+
+    fn write_request(&self, operation: &Operation, key: &str) -> RequestBuilder {
+        let request = match operation {
+            Operation::Add { body } => self
+                .client
+                .post(format!("{}/memories", self.base_url))
+                .json(body),
+            Operation::Update { memory_id, text } => self
+                .client
+                .put(format!("{}/memories/{}", self.base_url, memory_id))
+                .json(&json!({ "text": text })),
+            Operation::Delete { memory_id } => self
+                .client
+                .delete(format!("{}/memories/{}", self.base_url, memory_id)),
+        };
+        request
+            .headers(self.headers())
+            .header(IDEMPOTENCY_HEADER, key)
+    }
+
+    /// Send a request, retrying failures the retry policy allows.
+    ///
+    /// Returns the last response whatever its status.
+    async fn execute(&self, request: RequestBuilder, retry: Retry) -> reqwest::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let next = match request.try_clone() {
+                Some(next) if attempt < self.retry.max_retries => next,
+                _ => return request.send().await,
+            };
+            let delay = match next.send().await {
+                Ok(response) => match self.retry_delay(&response, retry, attempt) {
+                    Some(delay) => delay,
+                    None => return Ok(response),
+                },
+                Err(e) if is_unsent(&e) || (retry == Retry::Idempotent && is_unreachable(&e)) => {
+                    self.retry.backoff(attempt)
+                }
+                Err(e) => return Err(e),
+            };
+            attempt += 1;
+            tracing::debug!("Retrying request in {:?} (attempt {})", delay, attempt);
+            sleep(delay).await;
+        }
+    }
+
+    /// How long to wait before retrying after a response, if at all.
+    fn retry_delay(&self, response: &Response, retry: Retry, attempt: u32) -> Option<Duration> {
+        let status = response.status();
+        let retryable = status == StatusCode::TOO_MANY_REQUESTS
+            || (retry == Retry::Idempotent && is_unavailable(status));
+        if !retryable {
+            return None;
+        }
+        match retry_after(response) {
+            Some(wait) if wait > self.retry.max_backoff => None,
+            Some(wait) => Some(wait),
+            None => Some(self.retry.backoff(attempt)),
+        }
+    }
+
+    /// Send a request with retries, turning an unsuccessful response into
+    /// its [`ClientError`].
+    async fn send(&self, request: RequestBuilder, retry: Retry) -> ClientResult<Response> {
+        check(self.execute(request, retry).await?).await
+    }
+
+    /// Send a write, queueing it if the API is unreachable and a queue is set.
+    async fn write(&self, operation: Operation, action: &str) -> ClientResult<WriteOutcome> {
+        let key = Uuid::new_v4().to_string();
+        #[cfg(feature = "native")]
+        if let Some(ref queue) = self.offline {
+            return self.write_queued(queue, operation, &key, action).await;
+        }
+        #[cfg(not(feature = "native"))]
+        let _ = action;
+
+        let request = self.write_request(&operation, &key);
+        // The server answers a resent write from its idempotency cache
+        let response = self.send(request, Retry::Idempotent).await?;
+        Ok(WriteOutcome::Sent(response))
     }
 
     fn headers(&self) -> reqwest::header::HeaderMap {
@@ -136,7 +430,7 @@ impl MemoryClient {
         agent_id: Option<&str>,
         run_id: Option<&str>,
         metadata: Option<HashMap<String, serde_json::Value>>,
-    ) -> RookResult<Vec<MemoryItem>> {
+    ) -> ClientResult<Vec<MemoryItem>> {
         let mut body = json!({
             "messages": [{"role": "user", "content": messages}]
         });
@@ -154,24 +448,12 @@ impl MemoryClient {
             body["metadata"] = json!(meta);
         }
 
-        let response = self
-            .client
-            .post(format!("{}/memories/", self.base_url))
-            .headers(self.headers())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to add memory: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!("Failed to add memory: {}", error)));
-        }
+        let response = match self.write(Operation::Add { body }, "add memory").await? {
+            WriteOutcome::Sent(response) => response,
+            WriteOutcome::Queued => return Ok(Vec::new()),
+        };
 
-        let result: AddResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+        let result: AddResponse = parse(response).await?;
 
         Ok(result
             .results
@@ -188,7 +470,7 @@ impl MemoryClient {
         agent_id: Option<&str>,
         run_id: Option<&str>,
         limit: Option<usize>,
-    ) -> RookResult<Vec<MemoryItem>> {
+    ) -> ClientResult<Vec<MemoryItem>> {
         let mut body = json!({ "query": query });
 
         if let Some(uid) = user_id {
@@ -204,24 +486,12 @@ impl MemoryClient {
             body["limit"] = json!(l);
         }
 
-        let response = self
+        let request = self
             .client
-            .post(format!("{}/memories/search/", self.base_url))
+            .post(format!("{}/search", self.base_url))
             .headers(self.headers())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to search: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!("Failed to search: {}", error)));
-        }
-
-        let result: SearchResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+            .json(&body);
+        let result: SearchResponse = parse(self.send(request, Retry::Idempotent).await?).await?;
 
         Ok(result
             .results
@@ -237,37 +507,19 @@ impl MemoryClient {
     }
 
     /// Get a specific memory by ID.
-    pub async fn get(&self, memory_id: &str) -> RookResult<Option<MemoryItem>> {
-        let response = self
+    pub async fn get(&self, memory_id: &str) -> ClientResult<Option<MemoryItem>> {
+        let request = self
             .client
-            .get(format!("{}/memories/{}/", self.base_url, memory_id))
-            .headers(self.headers())
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to get memory: {}", e)))?;
-
-        if response.status().as_u16() == 404 {
-            return Ok(None);
-        }
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!("Failed to get memory: {}", error)));
-        }
-
-        let result: MemoryItemResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
-
-        let mut item = MemoryItem::new(result.id, result.memory);
-        if let Some(meta) = result.metadata {
-            item = item.with_metadata(meta);
-        }
-        item.created_at = result.created_at;
-        item.updated_at = result.updated_at;
+            .get(format!("{}/memories/{}", self.base_url, memory_id))
+            .headers(self.headers());
+        let response = match self.send(request, Retry::Idempotent).await {
+            Ok(response) => response,
+            Err(ClientError::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
 
-        Ok(Some(item))
+        let result: MemoryItemResponse = parse(response).await?;
+        Ok(Some(result.into_item()))
     }
 
     /// Get all memories for a user/agent/run.
@@ -276,112 +528,176 @@ impl MemoryClient {
         user_id: Option<&str>,
         agent_id: Option<&str>,
         run_id: Option<&str>,
-    ) -> RookResult<Vec<MemoryItem>> {
+    ) -> ClientResult<Vec<MemoryItem>> {
         let mut params = vec![];
 
         if let Some(uid) = user_id {
-            params.push(format!("user_id={}", uid));
+            params.push(("user_id", uid));
         }
         if let Some(aid) = agent_id {
-            params.push(format!("agent_id={}", aid));
+            params.push(("agent_id", aid));
         }
         if let Some(rid) = run_id {
-            params.push(format!("run_id={}", rid));
+            params.push(("run_id", rid));
         }
 
-        let query_string = if params.is_empty() {
-            String::new()
-        } else {
-            format!("?{}", params.join("&"))
-        };
-
-        let response = self
+        let request = self
             .client
-            .get(format!("{}/memories/{}", self.base_url, query_string))
+            .get(format!("{}/memories", self.base_url))
             .headers(self.headers())
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to get memories: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!("Failed to get memories: {}", error)));
-        }
-
-        let result: GetResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+            .query(&params);
+        let result: GetResponse = parse(self.send(request, Retry::Idempotent).await?).await?;
 
         Ok(result
             .results
             .into_iter()
-            .map(|r| {
-                let mut item = MemoryItem::new(r.id, r.memory);
-                if let Some(meta) = r.metadata {
-                    item = item.with_metadata(meta);
-                }
-                item.created_at = r.created_at;
-                item.updated_at = r.updated_at;
-                item
-            })
+            .map(MemoryItemResponse::into_item)
             .collect())
     }
 
-    /// Update a memory.
-    pub async fn update(&self, memory_id: &str, text: &str) -> RookResult<MemoryItem> {
-        let body = json!({ "text": text });
+    /// Get one page of the memories for a user/agent/run.
+    ///
+    /// Pass the page's `next_cursor` as [`PageOptions::cursor`] to get the
+    /// next one.
+    pub async fn get_page(
+        &self,
+        user_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: Option<&str>,
+        page: &PageOptions,
+    ) -> ClientResult<MemoryPage> {
+        let mut params = vec![];
 
-        let response = self
+        if let Some(uid) = user_id {
+            params.push(("user_id", uid.to_string()));
+        }
+        if let Some(aid) = agent_id {
+            params.push(("agent_id", aid.to_string()));
+        }
+        if let Some(rid) = run_id {
+            params.push(("run_id", rid.to_string()));
+        }
+        if let Some(limit) = page.limit {
+            params.push(("limit", limit.to_string()));
+        }
+        if let Some(offset) = page.offset {
+            params.push(("offset", offset.to_string()));
+        }
+        if let Some(ref cursor) = page.cursor {
+            params.push(("cursor", cursor.clone()));
+        }
+        if let Some(ref order_by) = page.order_by {
+            params.push(("order_by", order_by.clone()));
+        }
+
+        let request = self
             .client
-            .put(format!("{}/memories/{}/", self.base_url, memory_id))
+            .get(format!("{}/memories", self.base_url))
             .headers(self.headers())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to update memory: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!(
-                "Failed to update memory: {}",
-                error
-            )));
-        }
+            .query(&params);
+        parse(self.send(request, Retry::Idempotent).await?).await
+    }
 
-        let result: MemoryItemResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+    /// Search memories, returning only the selected fields of each result.
+    ///
+    /// Results are raw JSON objects; `id` is always included.
+    pub async fn search_fields(
+        &self,
+        query: &str,
+        user_id: Option<&str>,
+        limit: Option<usize>,
+        fields: &FieldSelection,
+    ) -> ClientResult<Vec<serde_json::Value>> {
+        let mut body = json!({ "query": query, "fields": fields });
 
-        let mut item = MemoryItem::new(result.id, result.memory);
-        if let Some(meta) = result.metadata {
-            item = item.with_metadata(meta);
+        if let Some(uid) = user_id {
+            body["user_id"] = json!(uid);
+        }
+        if let Some(l) = limit {
+            body["limit"] = json!(l);
         }
-        item.created_at = result.created_at;
-        item.updated_at = result.updated_at;
 
-        Ok(item)
+        let request = self
+            .client
+            .post(format!("{}/search", self.base_url))
+            .headers(self.headers())
+            .json(&body);
+        let result: ProjectedResponse = parse(self.send(request, Retry::Idempotent).await?).await?;
+
+        Ok(result.results)
     }
 
-    /// Delete a memory.
-    pub async fn delete(&self, memory_id: &str) -> RookResult<()> {
-        let response = self
+    /// Get a memory by ID, returning only the selected fields.
+    pub async fn get_fields(
+        &self,
+        memory_id: &str,
+        fields: &FieldSelection,
+    ) -> ClientResult<Option<serde_json::Value>> {
+        let request = self
             .client
-            .delete(format!("{}/memories/{}/", self.base_url, memory_id))
+            .get(format!("{}/memories/{}", self.base_url, memory_id))
             .headers(self.headers())
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to delete memory: {}", e)))?;
+            .query(&[("fields", fields.to_query())]);
+        let response = match self.send(request, Retry::Idempotent).await {
+            Ok(response) => response,
+            Err(ClientError::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Some(parse(response).await?))
+    }
+
+    /// Get all memories for a user/agent/run, returning only the selected fields.
+    pub async fn get_all_fields(
+        &self,
+        user_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: Option<&str>,
+        fields: &FieldSelection,
+    ) -> ClientResult<Vec<serde_json::Value>> {
+        let mut params = vec![("fields", fields.to_query())];
 
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!(
-                "Failed to delete memory: {}",
-                error
-            )));
+        if let Some(uid) = user_id {
+            params.push(("user_id", uid.to_string()));
+        }
+        if let Some(aid) = agent_id {
+            params.push(("agent_id", aid.to_string()));
+        }
+        if let Some(rid) = run_id {
+            params.push(("run_id", rid.to_string()));
         }
 
+        let request = self
+            .client
+            .get(format!("{}/memories", self.base_url))
+            .headers(self.headers())
+            .query(&params);
+        let result: ProjectedResponse = parse(self.send(request, Retry::Idempotent).await?).await?;
+
+        Ok(result.results)
+    }
+
+    /// Update a memory.
+    pub async fn update(&self, memory_id: &str, text: &str) -> ClientResult<MemoryItem> {
+        let operation = Operation::Update {
+            memory_id: memory_id.to_string(),
+            text: text.to_string(),
+        };
+        let response = match self.write(operation, "update memory").await? {
+            WriteOutcome::Sent(response) => response,
+            WriteOutcome::Queued => return Ok(MemoryItem::new(memory_id, text)),
+        };
+
+        let result: MemoryItemResponse = parse(response).await?;
+        Ok(result.into_item())
+    }
+
+    /// Delete a memory.
+    pub async fn delete(&self, memory_id: &str) -> ClientResult<()> {
+        let operation = Operation::Delete {
+            memory_id: memory_id.to_string(),
+        };
+        self.write(operation, "delete memory").await?;
         Ok(())
     }
 
@@ -391,7 +707,7 @@ impl MemoryClient {
         user_id: Option<&str>,
         agent_id: Option<&str>,
         run_id: Option<&str>,
-    ) -> RookResult<()> {
+    ) -> ClientResult<()> {
         let mut body = json!({});
 
         if let Some(uid) = user_id {
@@ -404,66 +720,172 @@ impl MemoryClient {
             body["run_id"] = json!(rid);
         }
 
-        let response = self
+        let request = self
             .client
-            .delete(format!("{}/memories/", self.base_url))
+            .delete(format!("{}/memories", self.base_url))
             .headers(self.headers())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to delete memories: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!(
-                "Failed to delete memories: {}",
-                error
-            )));
-        }
+            .json(&body);
+        self.send(request, Retry::Idempotent).await?;
 
         Ok(())
     }
 
     /// Get memory history.
-    pub async fn history(&self, memory_id: &str) -> RookResult<Vec<serde_json::Value>> {
-        let response = self
+    pub async fn history(&self, memory_id: &str) -> ClientResult<Vec<serde_json::Value>> {
+        let request = self
             .client
-            .get(format!("{}/memories/{}/history/", self.base_url, memory_id))
-            .headers(self.headers())
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to get history: {}", e)))?;
+            .get(format!("{}/memories/{}/history", self.base_url, memory_id))
+            .headers(self.headers());
 
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!(
-                "Failed to get history: {}",
-                error
-            )));
+        match parse(self.send(request, Retry::Idempotent).await?).await? {
+            HistoryResponse::Wrapped { history } | HistoryResponse::Bare(history) => Ok(history),
         }
+    }
 
-        let result: Vec<serde_json::Value> = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+    /// Ingest content through the prediction error gate.
+    ///
+    /// The server decides whether the content is skipped as redundant,
+    /// stored as a new memory, or updates or supersedes a stored one.
+    pub async fn smart_ingest(
+        &self,
+        content: &str,
+        user_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: Option<&str>,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> ClientResult<SmartIngestResult> {
+        let mut body = json!({ "content": content });
 
-        Ok(result)
+        if let Some(uid) = user_id {
+            body["user_id"] = json!(uid);
+        }
+        if let Some(aid) = agent_id {
+            body["agent_id"] = json!(aid);
+        }
+        if let Some(rid) = run_id {
+            body["run_id"] = json!(rid);
+        }
+        if let Some(meta) = metadata {
+            body["metadata"] = json!(meta);
+        }
+
+        let request = self
+            .client
+            .post(format!("{}/smart_ingest", self.base_url))
+            .headers(self.headers())
+            .json(&body);
+        parse(self.send(request, Retry::Unapplied).await?).await
     }
 
-    /// Reset all memories.
-    pub async fn reset(&self) -> RookResult<()> {
-        let response = self
+    /// Export the memories in a scope, streaming the file into `writer`.
+    ///
+    /// The summary comes from the response headers, so it is known before
+    /// the first byte is written. Pass its `high_water_mark` as
+    /// [`ExportOptions::updated_since`] for the next incremental export.
+    pub async fn export<W>(
+        &self,
+        options: &ExportOptions,
+        writer: &mut W,
+    ) -> ClientResult<ExportSummary>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let request = self
             .client
-            .post(format!("{}/memories/reset/", self.base_url))
+            .post(format!("{}/export", self.base_url))
             .headers(self.headers())
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to reset: {}", e)))?;
+            .json(options);
+        let response = self.send(request, Retry::Idempotent).await?;
+
+        let headers = response.headers();
+        let summary = ExportSummary {
+            total: export_header(headers, "total").unwrap_or_default(),
+            exported: export_header(headers, "exported").unwrap_or_default(),
+            errors: export_header(headers, "errors").unwrap_or_default(),
+            records: export_header(headers, "records").unwrap_or_default(),
+            tombstones: export_header(headers, "tombstones").unwrap_or_default(),
+            high_water_mark: export_header(headers, "high-water-mark"),
+        };
 
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!("Failed to reset: {}", error)));
+        let mut body = response.bytes_stream();
+        while let Some(chunk) = body.next().await {
+            writer.write_all(&chunk?).await?;
         }
+        writer.flush().await?;
+
+        Ok(summary)
+    }
+
+    /// Import a JSON Lines export or bundle, streaming it from `reader`.
+    ///
+    /// The file is sent as it is read, so it is never held in memory. Since
+    /// the stream cannot be replayed, the import is not retried. Browsers
+    /// cannot stream request bodies; use [`MemoryClient::import_body`] there.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn import<R>(&self, reader: R, options: &ImportOptions) -> ClientResult<ImportSummary>
+    where
+        R: AsyncRead + Send + Sync + 'static,
+    {
+        let body = reqwest::Body::wrap_stream(ReaderStream::new(reader));
+        self.import_body(body, options).await
+    }
+
+    /// Import a JSON Lines export or bundle held in memory.
+    pub async fn import_body(
+        &self,
+        body: impl Into<reqwest::Body>,
+        options: &ImportOptions,
+    ) -> ClientResult<ImportSummary> {
+        let mut params = vec![];
+
+        if let Some(batch_size) = options.batch_size {
+            params.push(("batch_size", batch_size.to_string()));
+        }
+        match options.dedup {
+            Some(ImportDedup::Id) => params.push(("dedup", "id".to_string())),
+            Some(ImportDedup::Hash) => params.push(("dedup", "hash".to_string())),
+            Some(ImportDedup::Embedding { threshold }) => {
+                params.push(("dedup", "embedding".to_string()));
+                params.push(("dedup_threshold", threshold.to_string()));
+            }
+            Some(ImportDedup::SmartIngest) => params.push(("dedup", "smart_ingest".to_string())),
+            None => {}
+        }
+
+        let mut headers = self.headers();
+        headers.insert(CONTENT_TYPE, "application/x-ndjson".parse().unwrap());
+        let request = self
+            .client
+            .post(format!("{}/import", self.base_url))
+            .headers(headers)
+            .query(&params)
+            .body(body);
+        parse(self.send(request, Retry::Unapplied).await?).await
+    }
+
+    /// Configure the memory behind the API.
+    ///
+    /// `config` is the server's configure request, e.g. `{"llm": {...},
+    /// "embedder": {...}, "vector_store": {...}}`.
+    pub async fn configure<C>(&self, config: &C) -> ClientResult<ConfigureResponse>
+    where
+        C: Serialize + ?Sized,
+    {
+        let request = self
+            .client
+            .post(format!("{}/configure", self.base_url))
+            .headers(self.headers())
+            .json(config);
+        parse(self.send(request, Retry::Idempotent).await?).await
+    }
+
+    /// Reset all memories.
+    pub async fn reset(&self) -> ClientResult<()> {
+        let request = self
+            .client
+            .post(format!("{}/reset", self.base_url))
+            .headers(self.headers());
+        self.send(request, Retry::Idempotent).await?;
 
         Ok(())
     }
@@ -477,37 +899,178 @@ impl MemoryClient {
     /// - `retrieved_not_used`: Retrieved but not used → Neutral
     /// - `marked_incorrect`: User marked as wrong → Again grade
     /// - `marked_important`: User marked as key → Key memory flag
-    pub async fn send_signals(&self, signals: Vec<SignalInput>) -> RookResult<SignalsResponse> {
+    pub async fn send_signals(&self, signals: Vec<SignalInput>) -> ClientResult<SignalsResponse> {
         let body = json!({ "signals": signals });
 
-        let response = self
+        let request = self
             .client
-            .post(format!("{}/signals/", self.base_url))
+            .post(format!("{}/signals", self.base_url))
             .headers(self.headers())
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to send signals: {}", e)))?;
+            .json(&body);
+        parse(self.send(request, Retry::Unapplied).await?).await
+    }
+
+    /// Send a single strength signal.
+    pub async fn send_signal(&self, signal: SignalInput) -> ClientResult<SignalsResponse> {
+        self.send_signals(vec![signal]).await
+    }
+}
+
+/// Offline queue and sync, with the `native` feature.
+#[cfg(feature = "native")]
+impl MemoryClient {
+    /// Queue writes locally while the API is unreachable.
+    ///
+    /// With a queue, `add`, `update` and `delete` succeed even when the API
+    /// cannot be reached (connection failure, timeout, or 502/503/504): the
+    /// write is queued and `add` returns no items, `update` echoes the new
+    /// text. Queued writes are replayed in order before the next write, or
+    /// explicitly with [`MemoryClient::replay_queue`].
+    ///
+    /// A write queued after a timeout or 5xx may already have been applied;
+    /// replaying it can then apply it again (see [`OfflineQueue`]).
+    pub fn with_offline_queue(mut self, queue: OfflineQueue) -> Self {
+        self.offline = Some(queue);
+        self
+    }
+
+    /// The offline queue, if enabled.
+    pub fn offline_queue(&self) -> Option<&OfflineQueue> {
+        self.offline.as_ref()
+    }
 
-        if !response.status().is_success() {
-            let error = response.text().await.unwrap_or_default();
-            return Err(RookError::api(format!(
-                "Failed to send signals: {}",
-                error
-            )));
+    /// Pending and failed write counts of the offline queue.
+    pub fn queue_status(&self) -> RookResult<QueueStatus> {
+        self.queue()?.status()
+    }
+
+    fn queue(&self) -> RookResult<&OfflineQueue> {
+        self.offline
+            .as_ref()
+            .ok_or_else(|| RookError::Configuration("Offline queue is not enabled".to_string()))
+    }
+
+    /// Send a write through the offline queue.
+    async fn write_queued(
+        &self,
+        queue: &OfflineQueue,
+        operation: Operation,
+        key: &str,
+        action: &str,
+    ) -> ClientResult<WriteOutcome> {
+        // Writes stay in order: a new write waits behind queued ones.
+        if queue.pending_count()? > 0 && self.replay_queue().await?.remaining > 0 {
+            queue.push(key, &operation)?;
+            return Ok(WriteOutcome::Queued);
         }
 
-        let result: SignalsResponse = response
-            .json()
-            .await
-            .map_err(|e| RookError::api(format!("Failed to parse response: {}", e)))?;
+        let request = self.write_request(&operation, key);
+        match self.execute(request, Retry::Idempotent).await {
+            Ok(response) if !is_unavailable(response.status()) => {
+                Ok(WriteOutcome::Sent(check(response).await?))
+            }
+            Ok(response) => {
+                tracing::warn!("API unavailable ({}), queueing {}", response.status(), action);
+                queue.push(key, &operation)?;
+                Ok(WriteOutcome::Queued)
+            }
+            Err(e) if is_unreachable(&e) => {
+                tracing::warn!("API unreachable ({}), queueing {}", e, action);
+                queue.push(key, &operation)?;
+                Ok(WriteOutcome::Queued)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        Ok(result)
+    /// Replay queued writes in order with their original idempotency keys.
+    ///
+    /// The server deduplicates on the keys, so a write it applied before it
+    /// was queued is not applied again, as long as the server has not
+    /// restarted or forgotten the key (after a day) in the meantime.
+    ///
+    /// Stops at the first write that still cannot reach the API. Writes the
+    /// server rejects are moved to the failed list (see [`OfflineQueue::failed`]).
+    pub async fn replay_queue(&self) -> RookResult<ReplayReport> {
+        let queue = self.queue()?;
+        let _guard = queue.lock_replay().await;
+
+        let pending = queue.pending()?;
+        let mut report = ReplayReport::default();
+        for (i, queued) in pending.iter().enumerate() {
+            let result = self.write_request(&queued.operation, &queued.key).send().await;
+            let error = match result {
+                Ok(response) if response.status().is_success() => None,
+                // Already gone: an earlier attempt reached the server.
+                Ok(response)
+                    if response.status() == StatusCode::NOT_FOUND
+                        && matches!(queued.operation, Operation::Delete { .. }) =>
+                {
+                    None
+                }
+                Ok(response) if is_unavailable(response.status()) => {
+                    Some((response.status().to_string(), false))
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    Some((format!("{}: {}", status, body), true))
+                }
+                Err(e) => Some((e.to_string(), !is_unreachable(&e))),
+            };
+
+            match error {
+                None => {
+                    queue.remove(&queued.key)?;
+                    report.replayed += 1;
+                }
+                Some((error, true)) => {
+                    tracing::warn!("Queued write {} rejected: {}", queued.key, error);
+                    queue.record_attempt(&queued.key, &error, true)?;
+                    report.failed += 1;
+                }
+                Some((error, false)) => {
+                    queue.record_attempt(&queued.key, &error, false)?;
+                    report.remaining = pending.len() - i;
+                    return Ok(report);
+                }
+            }
+        }
+
+        queue.mark_replayed()?;
+        Ok(report)
     }
 
-    /// Send a single strength signal.
-    pub async fn send_signal(&self, signal: SignalInput) -> RookResult<SignalsResponse> {
-        self.send_signals(vec![signal]).await
+    /// Get the hosted replica's node ID, change sequence and peer cursors.
+    pub async fn sync_status(&self) -> ClientResult<SyncStatus> {
+        let request = self
+            .client
+            .get(format!("{}/sync/status", self.base_url))
+            .headers(self.headers());
+        parse(self.send(request, Retry::Idempotent).await?).await
+    }
+
+    /// Pull a page of the changes made on the hosted replica after `since`.
+    pub async fn pull_changes(&self, since: u64, limit: usize) -> ClientResult<Changeset> {
+        let request = self
+            .client
+            .get(format!("{}/sync/changes", self.base_url))
+            .headers(self.headers())
+            .query(&[("since", since)])
+            .query(&[("limit", limit)]);
+        parse(self.send(request, Retry::Idempotent).await?).await
+    }
+
+    /// Push a local replica's changes to the hosted replica.
+    ///
+    /// Changes carry their versions, so pushing them again is a no-op.
+    pub async fn push_changes(&self, changeset: &Changeset) -> ClientResult<SyncReport> {
+        let request = self
+            .client
+            .post(format!("{}/sync/changes", self.base_url))
+            .headers(self.headers())
+            .json(changeset);
+        parse(self.send(request, Retry::Idempotent).await?).await
     }
 }
 
@@ -559,3 +1122,249 @@ pub struct PendingUpdate {
     pub memory_id: String,
     pub grade: String,
 }
+
+/// Paging of [`MemoryClient::get_page`].
+#[derive(Debug, Clone, Default)]
+pub struct PageOptions {
+    /// Maximum memories per page.
+    pub limit: Option<usize>,
+    /// Memories to skip, after the cursor if one is given.
+    pub offset: Option<usize>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Order by a typed metadata field: `field` or `field:asc|desc`. Cannot
+    /// be combined with `cursor`.
+    pub order_by: Option<String>,
+}
+
+/// A page of memories.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryPage {
+    pub results: Vec<MemoryItem>,
+    /// Memories in the scope, across all pages.
+    pub total: usize,
+    /// Cursor for the next page, if there is one.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Outcome of [`MemoryClient::smart_ingest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartIngestResult {
+    /// `skip`, `create`, `update` or `supersede`.
+    pub decision: String,
+    /// Prediction error, from 0.0 (fully expected) to 1.0 (novel).
+    pub surprise: f32,
+    /// Detection layer that decided.
+    pub layer: String,
+    /// Memory created or updated.
+    #[serde(default)]
+    pub memory_id: Option<String>,
+    /// Existing memory the content duplicates, refines or supersedes.
+    #[serde(default)]
+    pub related_memory_id: Option<String>,
+    /// Content of the memory before an update or supersede.
+    #[serde(default)]
+    pub previous_content: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// JSON Lines, one memory per line.
+    #[default]
+    Jsonl,
+    /// Apache Parquet, if the server was built with it.
+    Parquet,
+}
+
+/// What [`MemoryClient::export`] exports.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    /// State to bundle with the memories; any section makes the export a
+    /// versioned JSON Lines bundle.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<ExportSection>,
+    /// Only export changes after this cursor, plus tombstones for the
+    /// memories deleted since. JSON Lines only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+/// Statistics of an export, from its `x-rook-export-*` headers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportSummary {
+    /// Memories in the scope.
+    pub total: u64,
+    /// Memories written.
+    pub exported: u64,
+    /// Records that failed to export.
+    pub errors: u64,
+    /// Records written, including bundled state and tombstones.
+    pub records: u64,
+    /// Tombstones written for deleted memories.
+    pub tombstones: u64,
+    /// Cursor for the next incremental export.
+    pub high_water_mark: Option<DateTime<Utc>>,
+}
+
+/// How [`MemoryClient::import`] imports.
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Memories written per batch (server default: 100).
+    pub batch_size: Option<usize>,
+    /// How duplicates of stored memories are skipped (server default: by ID).
+    pub dedup: Option<ImportDedup>,
+}
+
+/// Statistics of an import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    #[serde(flatten)]
+    pub stats: ImportStats,
+    /// Bundled state restored after the memories, for bundle imports.
+    #[serde(default)]
+    pub restored: Option<RestoreStats>,
+    /// Memories deleted for the tombstones of an incremental export.
+    #[serde(default)]
+    pub deleted: Option<u64>,
+}
+
+/// Response from configuring the memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigureResponse {
+    pub message: String,
+    pub configured: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Serve `statuses` in turn, one per request, answering the last one to
+    /// any further requests. Returns the base URL and the request count.
+    async fn stub_api(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let count = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let n = count.fetch_add(1, Ordering::SeqCst);
+                let status = statuses[n.min(statuses.len() - 1)];
+                read_request(&mut socket).await;
+                let body = r#"{"results":[]}"#;
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (base_url, requests)
+    }
+
+    /// Read a request's headers and body.
+    async fn read_request(socket: &mut tokio::net::TcpStream) {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            if n == 0 {
+                return;
+            }
+            data.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&data);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if data.len() >= end + 4 + length {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn client(base_url: &str) -> MemoryClient {
+        MemoryClient::with_options("test-key", Some(base_url), None, None)
+            .unwrap()
+            .with_retry(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            })
+    }
+
+    #[tokio::test]
+    async fn test_add_is_resent_after_unavailable() {
+        let (base_url, requests) = stub_api(vec![503, 200]).await;
+
+        client(&base_url)
+            .add("I like tea", Some("alice"), None, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[cfg(feature = "native")]
+    #[tokio::test]
+    async fn test_replay_removes_accepted_writes_and_keeps_rejected() {
+        let (base_url, requests) = stub_api(vec![200, 400, 200]).await;
+        let queue = OfflineQueue::in_memory().unwrap();
+        for (key, memory_id) in [("k1", "a"), ("k2", "b"), ("k3", "c")] {
+            let operation = Operation::Delete {
+                memory_id: memory_id.to_string(),
+            };
+            queue.push(key, &operation).unwrap();
+        }
+        let client = client(&base_url).with_offline_queue(queue);
+
+        let report = client.replay_queue().await.unwrap();
+
+        assert_eq!(
+            (report.replayed, report.failed, report.remaining),
+            (2, 1, 0)
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+        let queue = client.offline_queue().unwrap();
+        assert!(queue.pending().unwrap().is_empty());
+        let failed = queue.failed().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].key, "k2");
+        assert!(queue.status().unwrap().last_replay_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_resent_after_unavailable() {
+        let (base_url, requests) = stub_api(vec![503, 200]).await;
+
+        client(&base_url).delete("mem-1").await.unwrap();
+
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,216 @@
+//! Error types for the hosted API client.
+//!
+//! Failed responses are mapped onto the server's error codes (`{"error":
+//! {"code", "message", "details"}}`), falling back to the HTTP status when
+//! the body is not an API error.
+
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[cfg(feature = "native")]
+use rook_core::error::{ErrorCode, RookError};
+
+/// Result type alias for client operations.
+pub type ClientResult<T> = Result<T, ClientError>;
+
+/// Error returned by [`MemoryClient`](crate::MemoryClient) operations.
+#[derive(Error, Debug)]
+pub enum ClientError {
+    /// The request was malformed (400, `BAD_REQUEST`).
+    #[error("Bad request: {message}")]
+    BadRequest {
+        message: String,
+        details: Option<serde_json::Value>,
+    },
+
+    /// The API key is missing or invalid (401, `UNAUTHORIZED`).
+    #[error("Unauthorized: {message}")]
+    Unauthorized { message: String },
+
+    /// The API key may not perform the operation (403, `FORBIDDEN`).
+    #[error("Forbidden: {message}")]
+    Forbidden { message: String },
+
+    /// The memory or resource does not exist (404, `NOT_FOUND`).
+    #[error("Not found: {message}")]
+    NotFound { message: String },
+
+    /// The request conflicts with the current state (409, `CONFLICT`).
+    #[error("Conflict: {message}")]
+    Conflict { message: String },
+
+    /// The input failed validation (422, `VALIDATION_ERROR`).
+    #[error("Validation error: {message}")]
+    Validation {
+        message: String,
+        details: Option<serde_json::Value>,
+    },
+
+    /// The API key's rate limit was hit (429, `RATE_LIMIT`).
+    #[error("Rate limit exceeded: {message}")]
+    RateLimit {
+        message: String,
+        /// Wait before retrying, from the `Retry-After` header.
+        retry_after: Option<Duration>,
+    },
+
+    /// The server failed to handle the request (500, `INTERNAL_ERROR`).
+    #[error("Server error: {message}")]
+    Internal { message: String },
+
+    /// Any other unsuccessful status, e.g. 503 from a proxy.
+    #[error("HTTP {status}: {message}")]
+    Status {
+        status: u16,
+        code: Option<String>,
+        message: String,
+    },
+
+    /// The request could not be sent or its body not read.
+    #[error("Request failed: {0}")]
+    Transport(#[source] reqwest::Error),
+
+    /// The response body was not what the endpoint returns.
+    #[error("Failed to parse response: {0}")]
+    Decode(String),
+
+    /// The client is misconfigured, e.g. no API key in the environment.
+    #[error("Configuration error: {0}")]
+    Configuration(String),
+
+    /// Reading or writing a local stream failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A local operation failed, e.g. the offline queue.
+    #[cfg(feature = "native")]
+    #[error(transparent)]
+    Local(#[from] RookError),
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: String,
+    message: String,
+    #[serde(default)]
+    details: Option<serde_json::Value>,
+}
+
+impl ClientError {
+    /// Build the error for an unsuccessful response.
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        let body = response.text().await.unwrap_or_default();
+
+        let (code, message, details) = match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(ErrorResponse { error }) => (Some(error.code), error.message, error.details),
+            Err(_) if body.is_empty() => (None, status.to_string(), None),
+            Err(_) => (None, body, None),
+        };
+
+        match (code.as_deref(), status) {
+            (Some("BAD_REQUEST"), _) | (None, StatusCode::BAD_REQUEST) => {
+                Self::BadRequest { message, details }
+            }
+            (Some("UNAUTHORIZED"), _) | (None, StatusCode::UNAUTHORIZED) => {
+                Self::Unauthorized { message }
+            }
+            (Some("FORBIDDEN"), _) | (None, StatusCode::FORBIDDEN) => Self::Forbidden { message },
+            (Some("NOT_FOUND"), _) | (None, StatusCode::NOT_FOUND) => Self::NotFound { message },
+            (Some("CONFLICT"), _) | (None, StatusCode::CONFLICT) => Self::Conflict { message },
+            (Some("VALIDATION_ERROR"), _) | (None, StatusCode::UNPROCESSABLE_ENTITY) => {
+                Self::Validation { message, details }
+            }
+            (Some("RATE_LIMIT"), _) | (None, StatusCode::TOO_MANY_REQUESTS) => Self::RateLimit {
+                message,
+                retry_after,
+            },
+            (Some("INTERNAL_ERROR"), _) | (None, StatusCode::INTERNAL_SERVER_ERROR) => {
+                Self::Internal { message }
+            }
+            _ => Self::Status {
+                status: status.as_u16(),
+                code,
+                message,
+            },
+        }
+    }
+
+    /// HTTP status of the response, for errors returned by the API.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::BadRequest { .. } => Some(400),
+            Self::Unauthorized { .. } => Some(401),
+            Self::Forbidden { .. } => Some(403),
+            Self::NotFound { .. } => Some(404),
+            Self::Conflict { .. } => Some(409),
+            Self::Validation { .. } => Some(422),
+            Self::RateLimit { .. } => Some(429),
+            Self::Internal { .. } => Some(500),
+            Self::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+/// The `Retry-After` delay of a response, in seconds.
+pub(crate) fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_decode() {
+            Self::Decode(error.to_string())
+        } else {
+            Self::Transport(error)
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl From<ClientError> for RookError {
+    fn from(error: ClientError) -> Self {
+        match error {
+            ClientError::BadRequest { message, .. } | ClientError::Validation { message, .. } => {
+                RookError::validation(message)
+            }
+            ClientError::Unauthorized { message } => RookError::authentication(message),
+            ClientError::Forbidden { message } => RookError::forbidden(message),
+            ClientError::NotFound { message } => RookError::NotFound {
+                message,
+                code: ErrorCode::MemNotFound,
+                memory_id: None,
+            },
+            ClientError::RateLimit {
+                message,
+                retry_after,
+            } => RookError::RateLimit {
+                message,
+                code: ErrorCode::RateLimitExceeded,
+                retry_after: retry_after.map(|d| d.as_secs()),
+            },
+            ClientError::Configuration(message) => RookError::Configuration(message),
+            ClientError::Io(e) => RookError::Io(e),
+            ClientError::Local(e) => e,
+            other => RookError::api(other.to_string()),
+        }
+    }
+}
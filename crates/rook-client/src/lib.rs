@@ -1,11 +1,14 @@
-//! rook-client - Client library for the Rook hosted API.
+//! rook-client - Client library for the Rook REST API.
 //!
-//! This crate provides a client for interacting with the Rook hosted API.
+//! This crate provides a client for a `rook-server`, at
+//! [`DEFAULT_BASE_URL`] unless given another URL.
 //!
 //! # Example
 //!
 //! ```ignore
-//! use rook_client::{MemoryClient, SignalInput};
+//! use rook_client::{
+//!     ClientError, ExportOptions, MemoryClient, OfflineQueue, PageOptions, SignalInput, SyncEngine,
+//! };
 //!
 //! let client = MemoryClient::new("your-api-key")?;
 //!
@@ -15,14 +18,75 @@
 //! // Search memories
 //! let memories = client.search("programming", "user-123", 10).await?;
 //!
+//! // Page through a user's memories
+//! let mut page = PageOptions { limit: Some(50), ..Default::default() };
+//! loop {
+//!     let memories = client.get_page(Some("user-123"), None, None, &page).await?;
+//!     page.cursor = memories.next_cursor;
+//!     if page.cursor.is_none() {
+//!         break;
+//!     }
+//! }
+//!
+//! // Stream an export to a file; API errors are typed
+//! let mut file = tokio::fs::File::create("memories.jsonl").await?;
+//! match client.export(&ExportOptions::default(), &mut file).await {
+//!     Ok(summary) => println!("Exported {} memories", summary.exported),
+//!     Err(ClientError::Forbidden { message }) => eprintln!("Not an admin key: {}", message),
+//!     Err(e) => return Err(e),
+//! }
+//!
 //! // Send a signal that a memory was used
 //! client.send_signal(SignalInput::UsedInResponse {
 //!     memory_id: "mem-123".to_string(),
 //!     context: Some("answered user question".to_string()),
 //! }).await?;
+//!
+//! // Queue writes locally while the API is unreachable
+//! let client = MemoryClient::new("your-api-key")?
+//!     .with_offline_queue(OfflineQueue::open("rook-queue.db")?);
+//! let status = client.queue_status()?;
+//! client.replay_queue().await?;
+//!
+//! // Keep a local embedded Memory in sync with the hosted API
+//! let engine = Arc::new(SyncEngine::new(local_memory, Arc::new(client)));
+//! let round = engine.sync().await?;
+//! engine.clone().start();
 //! ```
+//!
+//! # WebAssembly
+//!
+//! The default `native` feature adds the offline queue and `SyncEngine`,
+//! which need rook-core and SQLite. Without it the crate builds for
+//! `wasm32-unknown-unknown`, where requests go through the browser's fetch
+//! API, so browser-based agent UIs can talk to rook-server directly:
+//!
+//! ```text
+//! cargo build -p rook-client --target wasm32-unknown-unknown --no-default-features
+//! ```
+//!
+//! Imports there are sent from memory with [`MemoryClient::import_body`].
 
 mod client;
+mod error;
+mod offline;
+#[cfg(feature = "native")]
+mod sync;
+mod types;
 
-pub use client::{MemoryClient, PendingUpdate, SignalInput, SignalsResponse};
-pub use rook_core::types::MemoryItem;
+pub use client::{
+    ConfigureResponse, ExportFormat, ExportOptions, ExportSummary, ImportOptions, ImportSummary,
+    MemoryClient, MemoryPage, PageOptions, PendingUpdate, RetryPolicy, SignalInput,
+    SignalsResponse, SmartIngestResult, DEFAULT_BASE_URL,
+};
+pub use error::{ClientError, ClientResult};
+#[cfg(feature = "native")]
+pub use offline::OfflineQueue;
+pub use offline::{Operation, QueueStatus, QueuedOperation, ReplayReport};
+#[cfg(feature = "native")]
+pub use rook_core::sync::ConflictPolicy;
+#[cfg(feature = "native")]
+pub use sync::{SyncEngine, SyncRound};
+pub use types::{
+    ExportSection, FieldSelection, ImportDedup, ImportStats, MemoryItem, RestoreStats,
+};
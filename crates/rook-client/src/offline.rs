@@ -0,0 +1,344 @@
+//! Local queue for writes made while the API is unreachable.
+//!
+//! Each queued operation keeps the idempotency key it was first sent with.
+//! A write queued after a timeout or 502/503/504 may already have been
+//! applied; the server recognizes the key on replay and answers with the
+//! first response instead of applying it twice. The queue itself needs the
+//! `native` feature.
+
+#[cfg(feature = "native")]
+use std::path::Path;
+#[cfg(feature = "native")]
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "native")]
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "native")]
+use rook_core::error::{RookError, RookResult};
+
+/// A write operation that can be queued.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// Add memories; `body` is the request body.
+    Add { body: serde_json::Value },
+    /// Update a memory's text.
+    Update { memory_id: String, text: String },
+    /// Delete a memory.
+    Delete { memory_id: String },
+}
+
+/// A queued write.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperation {
+    /// Idempotency key, also the queue entry ID.
+    pub key: String,
+    /// The write.
+    pub operation: Operation,
+    /// When the write was queued.
+    pub queued_at: DateTime<Utc>,
+    /// Replay attempts so far.
+    pub attempts: u32,
+    /// Last replay error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Queue state for the application.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueStatus {
+    /// Writes waiting to be replayed.
+    pub pending: usize,
+    /// Writes the server rejected on replay.
+    pub failed: usize,
+    /// When the oldest pending write was queued.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+    /// When a replay last completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_replay_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of a replay.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Writes the server accepted.
+    pub replayed: usize,
+    /// Writes the server rejected (moved to the failed list).
+    pub failed: usize,
+    /// Writes still pending because the API is unreachable.
+    pub remaining: usize,
+}
+
+/// SQLite-backed offline write queue.
+///
+/// Replay relies on the server deduplicating on the idempotency key, which
+/// it remembers for a day and forgets on restart. A write replayed after
+/// that is applied again.
+#[cfg(feature = "native")]
+pub struct OfflineQueue {
+    conn: Mutex<Connection>,
+    replay_lock: tokio::sync::Mutex<()>,
+}
+
+#[cfg(feature = "native")]
+impl OfflineQueue {
+    /// Open (or create) a queue at the given path.
+    pub fn open(path: impl AsRef<Path>) -> RookResult<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Create an in-memory queue (lost when the process exits).
+    pub fn in_memory() -> RookResult<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> RookResult<Self> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS offline_queue (
+                seq        INTEGER PRIMARY KEY AUTOINCREMENT,
+                key        TEXT NOT NULL UNIQUE,
+                operation  TEXT NOT NULL,
+                queued_at  TEXT NOT NULL,
+                attempts   INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                failed     INTEGER NOT NULL DEFAULT 0
+            );
+
+            CREATE TABLE IF NOT EXISTS offline_meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            "#,
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            replay_lock: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Append a write to the queue.
+    pub fn push(&self, key: &str, operation: &Operation) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO offline_queue (key, operation, queued_at) VALUES (?1, ?2, ?3)",
+            params![key, serde_json::to_string(operation)?, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Pending writes, oldest first.
+    pub fn pending(&self) -> RookResult<Vec<QueuedOperation>> {
+        self.list(false)
+    }
+
+    /// Writes the server rejected on replay, oldest first.
+    pub fn failed(&self) -> RookResult<Vec<QueuedOperation>> {
+        self.list(true)
+    }
+
+    /// Number of pending writes.
+    pub fn pending_count(&self) -> RookResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM offline_queue WHERE failed = 0",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Queue counts and timestamps.
+    pub fn status(&self) -> RookResult<QueueStatus> {
+        let conn = self.conn.lock().unwrap();
+        let (pending, failed, oldest): (i64, i64, Option<String>) = conn.query_row(
+            "SELECT COALESCE(SUM(failed = 0), 0), COALESCE(SUM(failed = 1), 0),
+                    MIN(CASE WHEN failed = 0 THEN queued_at END)
+             FROM offline_queue",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        let last_replay: Option<String> = conn
+            .query_row(
+                "SELECT value FROM offline_meta WHERE key = 'last_replay_at'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(QueueStatus {
+            pending: pending as usize,
+            failed: failed as usize,
+            oldest_pending_at: oldest.as_deref().and_then(parse_timestamp),
+            last_replay_at: last_replay.as_deref().and_then(parse_timestamp),
+        })
+    }
+
+    /// Drop a write the server accepted.
+    pub fn remove(&self, key: &str) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM offline_queue WHERE key = ?1", [key])?;
+        Ok(())
+    }
+
+    /// Record a replay attempt; `failed` moves the write to the failed list.
+    pub fn record_attempt(&self, key: &str, error: &str, failed: bool) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE offline_queue SET attempts = attempts + 1, last_error = ?2, failed = ?3
+             WHERE key = ?1",
+            params![key, error, failed as i32],
+        )?;
+        Ok(())
+    }
+
+    /// Re-queue a failed write for the next replay.
+    pub fn retry(&self, key: &str) -> RookResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let rows = conn.execute(
+            "UPDATE offline_queue SET failed = 0 WHERE key = ?1 AND failed = 1",
+            [key],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Remove all failed writes, returning how many were removed.
+    pub fn clear_failed(&self) -> RookResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM offline_queue WHERE failed = 1", [])?)
+    }
+
+    pub(crate) fn mark_replayed(&self) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO offline_meta (key, value) VALUES ('last_replay_at', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            [Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub(crate) async fn lock_replay(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.replay_lock.lock().await
+    }
+
+    fn list(&self, failed: bool) -> RookResult<Vec<QueuedOperation>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT key, operation, queued_at, attempts, last_error
+             FROM offline_queue WHERE failed = ?1 ORDER BY seq ASC",
+        )?;
+        let rows = stmt
+            .query_map([failed as i32], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(key, operation, queued_at, attempts, last_error)| {
+                Ok(QueuedOperation {
+                    key,
+                    operation: serde_json::from_str(&operation)?,
+                    queued_at: parse_timestamp(&queued_at).ok_or_else(|| {
+                        RookError::database(format!("Invalid queue timestamp '{}'", queued_at))
+                    })?,
+                    attempts,
+                    last_error,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "native")]
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::*;
+
+    fn delete(memory_id: &str) -> Operation {
+        Operation::Delete {
+            memory_id: memory_id.to_string(),
+        }
+    }
+
+    fn memory_ids(queued: &[QueuedOperation]) -> Vec<&str> {
+        queued
+            .iter()
+            .map(|q| match &q.operation {
+                Operation::Delete { memory_id } => memory_id.as_str(),
+                _ => panic!("unexpected operation"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_push_keeps_order() {
+        let queue = OfflineQueue::in_memory().unwrap();
+        queue.push("k2", &delete("b")).unwrap();
+        queue.push("k1", &delete("a")).unwrap();
+        queue.push("k3", &delete("c")).unwrap();
+
+        let pending = queue.pending().unwrap();
+        assert_eq!(memory_ids(&pending), vec!["b", "a", "c"]);
+        assert_eq!(pending[0].key, "k2");
+        assert_eq!(pending[0].attempts, 0);
+        assert_eq!(queue.pending_count().unwrap(), 3);
+        assert!(queue.push("k1", &delete("d")).is_err());
+    }
+
+    #[test]
+    fn test_accepted_writes_are_removed() {
+        let queue = OfflineQueue::in_memory().unwrap();
+        queue.push("k1", &delete("a")).unwrap();
+        queue.push("k2", &delete("b")).unwrap();
+
+        queue.remove("k1").unwrap();
+
+        assert_eq!(memory_ids(&queue.pending().unwrap()), vec!["b"]);
+        assert!(queue.failed().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_failed_writes_are_kept() {
+        let queue = OfflineQueue::in_memory().unwrap();
+        queue.push("k1", &delete("a")).unwrap();
+        queue.push("k2", &delete("b")).unwrap();
+
+        queue.record_attempt("k1", "400: bad", true).unwrap();
+        queue.record_attempt("k2", "503", false).unwrap();
+
+        let failed = queue.failed().unwrap();
+        assert_eq!(memory_ids(&failed), vec!["a"]);
+        assert_eq!(failed[0].attempts, 1);
+        assert_eq!(failed[0].last_error.as_deref(), Some("400: bad"));
+        let pending = queue.pending().unwrap();
+        assert_eq!(memory_ids(&pending), vec!["b"]);
+        assert_eq!(pending[0].last_error.as_deref(), Some("503"));
+
+        let status = queue.status().unwrap();
+        assert_eq!((status.pending, status.failed), (1, 1));
+
+        assert!(queue.retry("k1").unwrap());
+        assert_eq!(memory_ids(&queue.pending().unwrap()), vec!["a", "b"]);
+        assert_eq!(queue.clear_failed().unwrap(), 0);
+    }
+}
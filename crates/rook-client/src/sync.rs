@@ -0,0 +1,154 @@
+//! Two-way sync between a local embedded Memory and the hosted API.
+//!
+//! Both sides must have sync enabled (`sync.enabled`). Each round pulls the
+//! hosted replica's changes since the local cursor for it, then pushes the
+//! local changes since the hosted cursor for this node. Changes carry version
+//! vectors, so causally newer versions win and concurrent ones are resolved
+//! latest-`updated_at`-wins on both sides; the [`ConflictPolicy`] decides
+//! whether the local side keeps the losing version. Cursors live in the
+//! replicas' sync state, so a round interrupted by going offline resumes
+//! where it stopped.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::sync::{Changeset, ConflictPolicy, SyncReport};
+use rook_core::Memory;
+
+use crate::client::MemoryClient;
+
+/// Outcome of one sync round.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncRound {
+    /// Hosted changes applied to the local memory.
+    pub pulled: SyncReport,
+    /// Local changes applied to the hosted memory.
+    pub pushed: SyncReport,
+}
+
+/// Reconciles a local [`Memory`] with the hosted API.
+pub struct SyncEngine {
+    local: Arc<Memory>,
+    remote: Arc<MemoryClient>,
+    policy: ConflictPolicy,
+    page_size: usize,
+    interval: Duration,
+}
+
+impl SyncEngine {
+    /// Sync `local` with `remote`, keeping conflict losers locally, in pages
+    /// of 100 changes, every 5 minutes once started.
+    pub fn new(local: Arc<Memory>, remote: Arc<MemoryClient>) -> Self {
+        Self {
+            local,
+            remote,
+            policy: ConflictPolicy::default(),
+            page_size: 100,
+            interval: Duration::from_secs(300),
+        }
+    }
+
+    /// What the local side keeps of concurrent versions that lost.
+    ///
+    /// The hosted side applies pushed changes with its own policy.
+    pub fn with_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Changes exchanged per request.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Time between rounds of the background task.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Run one sync round: pull, then push.
+    pub async fn sync(&self) -> RookResult<SyncRound> {
+        let local = self.local.sync_status().await?;
+        let remote = self.remote.sync_status().await?;
+        if local.node_id == remote.node_id {
+            return Err(RookError::validation(format!(
+                "Local and hosted replicas share node ID {}",
+                local.node_id
+            )));
+        }
+
+        let mut round = SyncRound::default();
+
+        let mut since = local.peers.get(&remote.node_id).copied().unwrap_or(0);
+        loop {
+            let changeset = self.remote.pull_changes(since, self.page_size).await?;
+            let report = self
+                .local
+                .apply_changeset_with(&changeset, self.policy)
+                .await?;
+            add_report(&mut round.pulled, report);
+            since = changeset.next_seq;
+            if !changeset.has_more {
+                break;
+            }
+        }
+
+        let mut since = remote.peers.get(&local.node_id).copied().unwrap_or(0);
+        loop {
+            let mut changeset = self.local.sync_changes(since, self.page_size).await?;
+            if changeset.changes.is_empty() {
+                break;
+            }
+            // Versions written by the hosted replica came from it
+            skip_writer(&mut changeset, &remote.node_id);
+            let report = self.remote.push_changes(&changeset).await?;
+            add_report(&mut round.pushed, report);
+            since = changeset.next_seq;
+            if !changeset.has_more {
+                break;
+            }
+        }
+
+        Ok(round)
+    }
+
+    /// Sync in the background, once per interval.
+    ///
+    /// Failed rounds, e.g. while offline, are logged and retried at the next
+    /// tick.
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.interval.max(Duration::from_secs(1)));
+            loop {
+                interval.tick().await;
+                match self.sync().await {
+                    Ok(round) => tracing::debug!(
+                        pulled = round.pulled.applied,
+                        pushed = round.pushed.applied,
+                        conflicts = round.pulled.conflicts + round.pushed.conflicts,
+                        "Sync round complete"
+                    ),
+                    Err(e) => tracing::warn!("Sync with the hosted API failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+fn add_report(total: &mut SyncReport, report: SyncReport) {
+    total.applied += report.applied;
+    total.skipped += report.skipped;
+    total.conflicts += report.conflicts;
+}
+
+/// Drop changes last written by `node_id` from a changeset for that node.
+fn skip_writer(changeset: &mut Changeset, node_id: &str) {
+    changeset
+        .changes
+        .retain(|change| change.version.writer != node_id);
+}
@@ -0,0 +1,172 @@
+//! API types shared with rook-core.
+//!
+//! Native builds re-export the rook-core types. Builds without the `native`
+//! feature, e.g. for wasm32-unknown-unknown, cannot link rook-core and get
+//! copies with the same wire format instead; cognitive state is kept as raw
+//! JSON there.
+
+#[cfg(feature = "native")]
+pub use rook_core::types::{FieldSelection, MemoryItem};
+#[cfg(feature = "native")]
+pub use rook_core::{ExportSection, ImportDedup, ImportStats, RestoreStats};
+
+#[cfg(not(feature = "native"))]
+pub use wire::{ExportSection, FieldSelection, ImportDedup, ImportStats, MemoryItem, RestoreStats};
+
+#[cfg(not(feature = "native"))]
+mod wire {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    /// A memory item stored in the system.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct MemoryItem {
+        pub id: String,
+        pub memory: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hash: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub score: Option<f32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub metadata: Option<HashMap<String, serde_json::Value>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub created_at: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub updated_at: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub category: Option<String>,
+        #[serde(default)]
+        pub is_key: bool,
+        #[serde(default)]
+        pub pinned: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub surfaced_by: Option<String>,
+        /// FSRS memory state.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub memory_state: Option<serde_json::Value>,
+        /// Storage and retrieval strength.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub dual_strength: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub retrievability: Option<f32>,
+        /// Why the memory surfaced, from searches with `explain` set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub explanation: Option<serde_json::Value>,
+    }
+
+    impl MemoryItem {
+        /// Create a new memory item.
+        pub fn new(id: impl Into<String>, memory: impl Into<String>) -> Self {
+            Self {
+                id: id.into(),
+                memory: memory.into(),
+                hash: None,
+                score: None,
+                metadata: None,
+                created_at: None,
+                updated_at: None,
+                category: None,
+                is_key: false,
+                pinned: false,
+                surfaced_by: None,
+                memory_state: None,
+                dual_strength: None,
+                retrievability: None,
+                explanation: None,
+            }
+        }
+
+        /// Set the score.
+        pub fn with_score(mut self, score: f32) -> Self {
+            self.score = Some(score);
+            self
+        }
+
+        /// Set the metadata.
+        pub fn with_metadata(mut self, metadata: HashMap<String, serde_json::Value>) -> Self {
+            self.metadata = Some(metadata);
+            self
+        }
+    }
+
+    /// Fields to keep in a response.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+    #[serde(transparent)]
+    pub struct FieldSelection {
+        fields: Vec<String>,
+    }
+
+    impl FieldSelection {
+        /// Parse a comma-separated field list. Blank entries are ignored.
+        pub fn parse(s: &str) -> Self {
+            Self::from_fields(s.split(','))
+        }
+
+        /// Build a selection from individual field names or dotted paths.
+        pub fn from_fields<I, S>(fields: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: AsRef<str>,
+        {
+            let mut selection = Vec::new();
+            for field in fields {
+                let field = field.as_ref().trim();
+                if !field.is_empty() && !selection.iter().any(|f| f == field) {
+                    selection.push(field.to_string());
+                }
+            }
+            Self { fields: selection }
+        }
+
+        /// The selection as a comma-separated list, for query strings.
+        pub fn to_query(&self) -> String {
+            self.fields.join(",")
+        }
+    }
+
+    /// A kind of state carried in an export bundle.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum ExportSection {
+        Memories,
+        Cognitive,
+        Intentions,
+        Graph,
+        History,
+    }
+
+    /// How imported memories are matched against existing ones.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "mode", rename_all = "snake_case")]
+    pub enum ImportDedup {
+        /// Only skip memories whose ID already exists.
+        #[default]
+        Id,
+        /// Also skip memories with the same content hash.
+        Hash,
+        /// Also skip memories at least `threshold` similar to a stored one.
+        Embedding { threshold: f32 },
+        /// Also skip memories the `smart_ingest` gate judges redundant.
+        SmartIngest,
+    }
+
+    /// Statistics from an import operation.
+    #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+    pub struct ImportStats {
+        pub total: u64,
+        pub imported: u64,
+        pub skipped: u64,
+        pub errors: Vec<String>,
+    }
+
+    /// Counts of restored non-memory records.
+    #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+    pub struct RestoreStats {
+        pub cognitive: u64,
+        pub intentions: u64,
+        pub entities: u64,
+        pub relationships: u64,
+        pub errors: Vec<String>,
+    }
+}
@@ -0,0 +1,228 @@
+//! Memory analytics.
+//!
+//! Counts memories across all tenants, grouped by a payload field. Callers
+//! limited to aggregates get an [`AnalyticsReport`] built under an
+//! [`AggregationPolicy`]: groups smaller than k are suppressed and released
+//! counts may carry Laplace noise, so no individual memory can be singled
+//! out.
+
+mod privacy;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+pub use privacy::{AggregationPolicy, DEFAULT_MIN_GROUP_SIZE};
+
+/// Group label for memories without a value for the dimension.
+pub const UNSET_GROUP: &str = "(none)";
+
+/// Payload field memories are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsDimension {
+    UserId,
+    AgentId,
+    RunId,
+    Category,
+    /// Day the memory was created (`YYYY-MM-DD`).
+    CreatedDay,
+}
+
+impl AnalyticsDimension {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserId => "user_id",
+            Self::AgentId => "agent_id",
+            Self::RunId => "run_id",
+            Self::Category => "category",
+            Self::CreatedDay => "created_day",
+        }
+    }
+
+    /// Parse from the string representation.
+    pub fn parse(s: &str) -> RookResult<Self> {
+        match s {
+            "user_id" => Ok(Self::UserId),
+            "agent_id" => Ok(Self::AgentId),
+            "run_id" => Ok(Self::RunId),
+            "category" => Ok(Self::Category),
+            "created_day" => Ok(Self::CreatedDay),
+            other => Err(RookError::validation(format!(
+                "Unknown analytics dimension '{}'. Supported: user_id, agent_id, run_id, \
+                 category, created_day",
+                other
+            ))),
+        }
+    }
+
+    /// The group a memory payload falls into.
+    pub fn group_of(&self, payload: &HashMap<String, serde_json::Value>) -> String {
+        let value = match self {
+            Self::CreatedDay => payload
+                .get("created_at")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.get(..10)),
+            _ => payload.get(self.as_str()).and_then(|v| v.as_str()),
+        };
+        value
+            .filter(|s| !s.is_empty())
+            .unwrap_or(UNSET_GROUP)
+            .to_string()
+    }
+}
+
+/// Count payloads per group of `dimension`.
+pub fn count_by<'a>(
+    payloads: impl IntoIterator<Item = &'a HashMap<String, serde_json::Value>>,
+    dimension: AnalyticsDimension,
+) -> BTreeMap<String, u64> {
+    let mut counts = BTreeMap::new();
+    for payload in payloads {
+        *counts.entry(dimension.group_of(payload)).or_default() += 1;
+    }
+    counts
+}
+
+/// Memory count of one group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupCount {
+    pub group: String,
+    pub count: u64,
+}
+
+/// Memory counts grouped by a dimension.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsReport {
+    pub dimension: AnalyticsDimension,
+    /// Total memories counted. `None` when the total itself was suppressed.
+    pub total: Option<u64>,
+    /// Released groups, largest first.
+    pub groups: Vec<GroupCount>,
+    /// Groups withheld because they were smaller than k.
+    pub suppressed_groups: usize,
+    /// The policy the report was built under, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<AggregationPolicy>,
+}
+
+impl AnalyticsReport {
+    /// Report with exact counts.
+    pub fn exact(dimension: AnalyticsDimension, counts: BTreeMap<String, u64>) -> Self {
+        let total = counts.values().sum();
+        let groups = counts
+            .into_iter()
+            .map(|(group, count)| GroupCount { group, count })
+            .collect();
+        Self::sorted(Self {
+            dimension,
+            total: Some(total),
+            groups,
+            suppressed_groups: 0,
+            policy: None,
+        })
+    }
+
+    /// Report releasing only what `policy` allows.
+    pub fn aggregated(
+        dimension: AnalyticsDimension,
+        counts: BTreeMap<String, u64>,
+        policy: AggregationPolicy,
+    ) -> Self {
+        let total = policy.release(counts.values().sum());
+        let mut groups = Vec::new();
+        let mut suppressed_groups = 0;
+        for (group, count) in counts {
+            match policy.release(count) {
+                Some(count) => groups.push(GroupCount { group, count }),
+                None => suppressed_groups += 1,
+            }
+        }
+        Self::sorted(Self {
+            dimension,
+            total,
+            groups,
+            suppressed_groups,
+            policy: Some(policy),
+        })
+    }
+
+    fn sorted(mut self) -> Self {
+        self.groups
+            .sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.group.cmp(&b.group)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload(user_id: &str, category: Option<&str>) -> HashMap<String, serde_json::Value> {
+        let mut payload = HashMap::new();
+        payload.insert("user_id".to_string(), json!(user_id));
+        payload.insert("created_at".to_string(), json!("2026-03-01T10:00:00+00:00"));
+        if let Some(category) = category {
+            payload.insert("category".to_string(), json!(category));
+        }
+        payload
+    }
+
+    #[test]
+    fn test_count_by_dimension() {
+        let payloads = vec![
+            payload("alice", Some("work")),
+            payload("alice", None),
+            payload("bob", Some("work")),
+        ];
+        let by_user = count_by(&payloads, AnalyticsDimension::UserId);
+        assert_eq!(by_user.get("alice"), Some(&2));
+        assert_eq!(by_user.get("bob"), Some(&1));
+
+        let by_category = count_by(&payloads, AnalyticsDimension::Category);
+        assert_eq!(by_category.get(UNSET_GROUP), Some(&1));
+
+        let by_day = count_by(&payloads, AnalyticsDimension::CreatedDay);
+        assert_eq!(by_day.get("2026-03-01"), Some(&3));
+    }
+
+    #[test]
+    fn test_aggregated_report_suppresses_small_groups() {
+        let counts = BTreeMap::from([
+            ("work".to_string(), 12),
+            ("health".to_string(), 2),
+            ("travel".to_string(), 7),
+        ]);
+
+        let exact = AnalyticsReport::exact(AnalyticsDimension::Category, counts.clone());
+        assert_eq!(exact.total, Some(21));
+        assert_eq!(exact.groups.len(), 3);
+        assert_eq!(exact.groups[0].group, "work");
+
+        let report = AnalyticsReport::aggregated(
+            AnalyticsDimension::Category,
+            counts,
+            AggregationPolicy::new(5),
+        );
+        assert_eq!(report.total, Some(21));
+        assert_eq!(report.suppressed_groups, 1);
+        let groups: Vec<&str> = report.groups.iter().map(|g| g.group.as_str()).collect();
+        assert_eq!(groups, vec!["work", "travel"]);
+    }
+
+    #[test]
+    fn test_aggregated_report_suppresses_small_total() {
+        let counts = BTreeMap::from([("alice".to_string(), 3)]);
+        let report = AnalyticsReport::aggregated(
+            AnalyticsDimension::UserId,
+            counts,
+            AggregationPolicy::new(5),
+        );
+        assert_eq!(report.total, None);
+        assert!(report.groups.is_empty());
+    }
+}
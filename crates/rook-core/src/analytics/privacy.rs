@@ -0,0 +1,150 @@
+//! k-anonymity and noise for aggregate-only analytics.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Default smallest group released in aggregate-only mode.
+pub const DEFAULT_MIN_GROUP_SIZE: u64 = 5;
+
+/// How counts are protected for aggregate-only callers.
+///
+/// Groups with fewer than `min_group_size` members are suppressed, so no
+/// released count describes fewer than k memories. With `noise_epsilon` set,
+/// released counts also get Laplace noise of scale `1 / epsilon` (the
+/// sensitivity of a count is 1); smaller epsilon means more noise.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AggregationPolicy {
+    /// k: groups smaller than this are suppressed.
+    pub min_group_size: u64,
+    /// Privacy budget per released count. No noise when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub noise_epsilon: Option<f64>,
+}
+
+impl Default for AggregationPolicy {
+    fn default() -> Self {
+        Self {
+            min_group_size: DEFAULT_MIN_GROUP_SIZE,
+            noise_epsilon: None,
+        }
+    }
+}
+
+impl AggregationPolicy {
+    /// Policy suppressing groups smaller than `min_group_size`, without noise.
+    pub fn new(min_group_size: u64) -> Self {
+        Self {
+            min_group_size,
+            noise_epsilon: None,
+        }
+    }
+
+    /// Add Laplace noise with the given privacy budget.
+    pub fn with_noise(mut self, epsilon: f64) -> Self {
+        self.noise_epsilon = Some(epsilon);
+        self
+    }
+
+    /// Load from environment variables.
+    ///
+    /// `ROOK_ANALYTICS_MIN_GROUP_SIZE` sets k (default 5) and
+    /// `ROOK_ANALYTICS_EPSILON` enables noise.
+    pub fn from_env() -> Self {
+        let mut policy = Self::default();
+        if let Some(k) = std::env::var("ROOK_ANALYTICS_MIN_GROUP_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            policy.min_group_size = k;
+        }
+        policy.noise_epsilon = std::env::var("ROOK_ANALYTICS_EPSILON")
+            .ok()
+            .and_then(|v| v.parse().ok());
+        if let Err(e) = policy.validate() {
+            tracing::warn!("Ignoring invalid analytics policy: {}", e);
+            policy = Self::default();
+        }
+        policy
+    }
+
+    /// Check the policy is usable.
+    pub fn validate(&self) -> RookResult<()> {
+        if self.min_group_size == 0 {
+            return Err(RookError::validation("min_group_size must be at least 1"));
+        }
+        if let Some(epsilon) = self.noise_epsilon {
+            if !(epsilon.is_finite() && epsilon > 0.0) {
+                return Err(RookError::validation(
+                    "noise_epsilon must be a positive number",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The count to release, or `None` if it must be suppressed.
+    pub fn release(&self, count: u64) -> Option<u64> {
+        self.release_with(count, &mut rand::thread_rng())
+    }
+
+    /// [`release`](Self::release) with a caller-provided random source.
+    pub fn release_with<R: Rng + ?Sized>(&self, count: u64, rng: &mut R) -> Option<u64> {
+        // Suppression uses the true count; noise only blurs released counts
+        if count < self.min_group_size {
+            return None;
+        }
+        match self.noise_epsilon {
+            Some(epsilon) => {
+                let noisy = count as f64 + laplace(1.0 / epsilon, rng);
+                Some(noisy.round().max(0.0) as u64)
+            }
+            None => Some(count),
+        }
+    }
+}
+
+/// Sample from a zero-centred Laplace distribution with the given scale.
+fn laplace<R: Rng + ?Sized>(scale: f64, rng: &mut R) -> f64 {
+    let u: f64 = rng.gen_range(-0.5..0.5);
+    -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_small_groups_suppressed() {
+        let policy = AggregationPolicy::new(5);
+        assert_eq!(policy.release(4), None);
+        assert_eq!(policy.release(5), Some(5));
+        assert_eq!(policy.release(120), Some(120));
+    }
+
+    #[test]
+    fn test_noise_is_centred_and_non_negative() {
+        let policy = AggregationPolicy::new(1).with_noise(0.5);
+        let mut rng = StdRng::seed_from_u64(7);
+        let samples: Vec<u64> = (0..2000)
+            .map(|_| policy.release_with(100, &mut rng).unwrap())
+            .collect();
+        let mean = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+        assert!((mean - 100.0).abs() < 1.0, "mean {}", mean);
+        assert!(samples.iter().any(|&c| c != 100));
+
+        // Noise never pushes a count below zero
+        assert!((0..200).all(|_| policy.release_with(1, &mut rng).is_some()));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(AggregationPolicy::default().validate().is_ok());
+        assert!(AggregationPolicy::new(0).validate().is_err());
+        assert!(AggregationPolicy::new(5).with_noise(0.0).validate().is_err());
+        assert!(AggregationPolicy::new(5).with_noise(f64::NAN).validate().is_err());
+    }
+}
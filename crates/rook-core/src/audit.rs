@@ -0,0 +1,444 @@
+//! Append-only audit log of API-visible mutations.
+//!
+//! Every change made through the API (memories added, updated or deleted,
+//! configuration resets, API keys issued or revoked, ...) is recorded with
+//! who made it, when, and the content hash before and after. The log lives
+//! in its own SQLite table, separate from memory history: history can be
+//! pruned to save space, while the audit log only grows. Updates and deletes
+//! are rejected by triggers.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row, ToSql};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Entries returned by a query when it sets no limit.
+pub const DEFAULT_AUDIT_QUERY_LIMIT: usize = 100;
+
+/// Kind of change recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    DeleteAll,
+    Rollback,
+    Import,
+    Configure,
+    Reset,
+    KeyCreate,
+    KeyRevoke,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::DeleteAll => "delete_all",
+            Self::Rollback => "rollback",
+            Self::Import => "import",
+            Self::Configure => "configure",
+            Self::Reset => "reset",
+            Self::KeyCreate => "key_create",
+            Self::KeyRevoke => "key_revoke",
+        }
+    }
+
+    pub fn parse(s: &str) -> RookResult<Self> {
+        Ok(match s {
+            "create" => Self::Create,
+            "update" => Self::Update,
+            "delete" => Self::Delete,
+            "delete_all" => Self::DeleteAll,
+            "rollback" => Self::Rollback,
+            "import" => Self::Import,
+            "configure" => Self::Configure,
+            "reset" => Self::Reset,
+            "key_create" => Self::KeyCreate,
+            "key_revoke" => Self::KeyRevoke,
+            other => {
+                return Err(RookError::validation(format!(
+                    "Unknown audit action '{}'",
+                    other
+                )))
+            }
+        })
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A change to record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewAuditEntry {
+    /// Subject that made the change.
+    pub actor: String,
+    pub action: AuditAction,
+    /// Kind of thing changed: `memory`, `memories`, `config` or `api_key`.
+    pub resource_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    /// Organization the change was made in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Content hash before the change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_hash: Option<String>,
+    /// Content hash after the change.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_hash: Option<String>,
+    /// Action-specific context, such as the scope of a bulk delete.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl NewAuditEntry {
+    pub fn new(
+        actor: impl Into<String>,
+        action: AuditAction,
+        resource_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            actor: actor.into(),
+            action,
+            resource_type: resource_type.into(),
+            resource_id: None,
+            org_id: None,
+            old_hash: None,
+            new_hash: None,
+            details: None,
+        }
+    }
+
+    /// Builder: set the changed resource.
+    pub fn with_resource_id(mut self, resource_id: impl Into<String>) -> Self {
+        self.resource_id = Some(resource_id.into());
+        self
+    }
+
+    /// Builder: set the organization.
+    pub fn with_org_id(mut self, org_id: Option<String>) -> Self {
+        self.org_id = org_id;
+        self
+    }
+
+    /// Builder: set the content before and after the change, stored as
+    /// hashes.
+    pub fn with_content(mut self, old: Option<&str>, new: Option<&str>) -> Self {
+        self.old_hash = old.map(content_hash);
+        self.new_hash = new.map(content_hash);
+        self
+    }
+
+    /// Builder: set action-specific context.
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// A recorded change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, increasing with each entry.
+    pub seq: i64,
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub entry: NewAuditEntry,
+}
+
+/// Filters for [`AuditLog::query`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub resource_id: Option<String>,
+    pub org_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// Only entries after this sequence number, for paging through the log.
+    pub after_seq: Option<i64>,
+    /// Maximum entries (default: [`DEFAULT_AUDIT_QUERY_LIMIT`]).
+    pub limit: Option<usize>,
+}
+
+/// Hash of content recorded in the audit log, the same MD5 hex digest
+/// memories store as their `hash`.
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", md5::compute(content.as_bytes()))
+}
+
+/// Fixed-width timestamps, so they compare correctly as text.
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// SQLite-backed append-only audit log.
+pub struct AuditLog {
+    conn: Mutex<Connection>,
+}
+
+impl AuditLog {
+    /// Open the log at `path` (`:memory:` for an in-memory log).
+    pub fn new(path: impl AsRef<Path>) -> RookResult<Self> {
+        let conn = if path.as_ref().to_str() == Some(":memory:") {
+            Connection::open_in_memory()?
+        } else {
+            if let Some(parent) = path.as_ref().parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Connection::open(path.as_ref())?
+        };
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                seq           INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp     TEXT NOT NULL,
+                actor         TEXT NOT NULL,
+                action        TEXT NOT NULL,
+                resource_type TEXT NOT NULL,
+                resource_id   TEXT,
+                org_id        TEXT,
+                old_hash      TEXT,
+                new_hash      TEXT,
+                details       TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log(actor);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_resource ON audit_log(resource_id);
+            CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON audit_log
+            BEGIN
+                SELECT RAISE(ABORT, 'audit log is append-only');
+            END;
+            CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON audit_log
+            BEGIN
+                SELECT RAISE(ABORT, 'audit log is append-only');
+            END;
+            "#,
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// An in-memory log.
+    pub fn in_memory() -> RookResult<Self> {
+        Self::new(":memory:")
+    }
+
+    /// Append an entry.
+    pub fn record(&self, entry: NewAuditEntry) -> RookResult<AuditEntry> {
+        // Stored to the microsecond
+        let timestamp = Utc::now().trunc_subsecs(6);
+        let details = entry
+            .details
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO audit_log
+                (timestamp, actor, action, resource_type, resource_id, org_id, old_hash,
+                 new_hash, details)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                format_time(timestamp),
+                entry.actor,
+                entry.action.as_str(),
+                entry.resource_type,
+                entry.resource_id,
+                entry.org_id,
+                entry.old_hash,
+                entry.new_hash,
+                details,
+            ],
+        )?;
+
+        Ok(AuditEntry {
+            seq: conn.last_insert_rowid(),
+            timestamp,
+            entry,
+        })
+    }
+
+    /// Entries matching `query`, oldest first.
+    pub fn query(&self, query: &AuditQuery) -> RookResult<Vec<AuditEntry>> {
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut values: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(ref actor) = query.actor {
+            conditions.push("actor = ?");
+            values.push(Box::new(actor.clone()));
+        }
+        if let Some(action) = query.action {
+            conditions.push("action = ?");
+            values.push(Box::new(action.as_str()));
+        }
+        if let Some(ref resource_id) = query.resource_id {
+            conditions.push("resource_id = ?");
+            values.push(Box::new(resource_id.clone()));
+        }
+        if let Some(ref org_id) = query.org_id {
+            conditions.push("org_id = ?");
+            values.push(Box::new(org_id.clone()));
+        }
+        if let Some(since) = query.since {
+            conditions.push("timestamp >= ?");
+            values.push(Box::new(format_time(since)));
+        }
+        if let Some(until) = query.until {
+            conditions.push("timestamp < ?");
+            values.push(Box::new(format_time(until)));
+        }
+        if let Some(after_seq) = query.after_seq {
+            conditions.push("seq > ?");
+            values.push(Box::new(after_seq));
+        }
+        let limit = query.limit.unwrap_or(DEFAULT_AUDIT_QUERY_LIMIT);
+        values.push(Box::new(limit as i64));
+
+        let sql = format!(
+            "SELECT seq, timestamp, actor, action, resource_type, resource_id, org_id, old_hash,
+                    new_hash, details
+             FROM audit_log{} ORDER BY seq LIMIT ?",
+            if conditions.is_empty() {
+                String::new()
+            } else {
+                format!(" WHERE {}", conditions.join(" AND "))
+            }
+        );
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql)?;
+        let entries = stmt
+            .query_map(
+                rusqlite::params_from_iter(values.iter().map(|v| v.as_ref())),
+                Self::read_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    fn read_row(row: &Row<'_>) -> rusqlite::Result<AuditEntry> {
+        let text = |idx: usize, e: RookError| {
+            rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e))
+        };
+        let timestamp: String = row.get(1)?;
+        let details: Option<String> = row.get(9)?;
+        Ok(AuditEntry {
+            seq: row.get(0)?,
+            timestamp: DateTime::parse_from_rfc3339(&timestamp)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| {
+                    text(
+                        1,
+                        RookError::internal(format!(
+                            "Invalid audit timestamp '{}': {}",
+                            timestamp, e
+                        )),
+                    )
+                })?,
+            entry: NewAuditEntry {
+                actor: row.get(2)?,
+                action: AuditAction::parse(&row.get::<_, String>(3)?).map_err(|e| text(3, e))?,
+                resource_type: row.get(4)?,
+                resource_id: row.get(5)?,
+                org_id: row.get(6)?,
+                old_hash: row.get(7)?,
+                new_hash: row.get(8)?,
+                details: details
+                    .map(|s| serde_json::from_str(&s))
+                    .transpose()
+                    .map_err(|e| text(9, RookError::internal(e.to_string())))?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_query() {
+        let log = AuditLog::in_memory().unwrap();
+        let created = log
+            .record(
+                NewAuditEntry::new("key:k1", AuditAction::Create, "memory")
+                    .with_resource_id("m1")
+                    .with_content(None, Some("Likes tea")),
+            )
+            .unwrap();
+        log.record(
+            NewAuditEntry::new("alice", AuditAction::Update, "memory")
+                .with_resource_id("m1")
+                .with_content(Some("Likes tea"), Some("Likes coffee")),
+        )
+        .unwrap();
+        log.record(
+            NewAuditEntry::new("alice", AuditAction::Reset, "config")
+                .with_org_id(Some("acme".to_string())),
+        )
+        .unwrap();
+
+        let all = log.query(&AuditQuery::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0], created);
+        assert_eq!(all[1].entry.old_hash, created.entry.new_hash);
+        assert_eq!(all[1].entry.new_hash.as_deref(), Some(content_hash("Likes coffee").as_str()));
+
+        let by_alice = log
+            .query(&AuditQuery {
+                actor: Some("alice".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_alice.len(), 2);
+
+        let page = log
+            .query(&AuditQuery {
+                resource_id: Some("m1".to_string()),
+                after_seq: Some(created.seq),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].entry.action, AuditAction::Update);
+
+        let in_org = log
+            .query(&AuditQuery {
+                org_id: Some("acme".to_string()),
+                action: Some(AuditAction::Reset),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(in_org.len(), 1);
+    }
+
+    #[test]
+    fn test_log_is_append_only() {
+        let log = AuditLog::in_memory().unwrap();
+        log.record(NewAuditEntry::new("alice", AuditAction::Delete, "memory"))
+            .unwrap();
+
+        let conn = log.conn.lock().unwrap();
+        assert!(conn
+            .execute("UPDATE audit_log SET actor = 'mallory'", [])
+            .is_err());
+        assert!(conn.execute("DELETE FROM audit_log", []).is_err());
+    }
+}
@@ -0,0 +1,527 @@
+//! API keys with role scopes, user binding and rate limits.
+//!
+//! Keys are stored in SQLite as SHA-256 hashes; the secret is returned once,
+//! when the key is created. A request authenticated with a key runs as the
+//! subject `key:<id>`, and [`ApiKeyAuthorizer`] checks the key's role and
+//! user binding before passing the request on to the wrapped authorizer.
+//! Keys may also belong to an organization, whose requests the server routes
+//! to that organization's isolated memory instance.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::{Authorizer, AuthzAction, AuthzDecision, AuthzRequest};
+use crate::config::validate_tenant_id;
+use crate::error::{RookError, RookResult};
+
+/// Prefix of subjects authenticated with an API key.
+pub const KEY_SUBJECT_PREFIX: &str = "key:";
+
+/// Prefix of generated secrets, so leaked keys are easy to spot.
+const SECRET_PREFIX: &str = "rook_";
+
+/// Random characters in a generated secret.
+const SECRET_LENGTH: usize = 40;
+
+/// Characters of the secret kept in the clear to tell keys apart.
+const DISPLAY_PREFIX_LENGTH: usize = SECRET_PREFIX.len() + 8;
+
+/// What a key may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyRole {
+    /// Search, get, list and history.
+    ReadOnly,
+    /// Everything except configuration, reset, admin operations and
+    /// cross-tenant analytics.
+    ReadWrite,
+    /// Aggregate analytics only, with small groups suppressed.
+    Analytics,
+    /// Everything.
+    Admin,
+}
+
+impl KeyRole {
+    /// Get the string representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::ReadWrite => "read_write",
+            Self::Analytics => "analytics",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Parse from the string representation.
+    pub fn parse(s: &str) -> RookResult<Self> {
+        match s {
+            "read_only" => Ok(Self::ReadOnly),
+            "read_write" => Ok(Self::ReadWrite),
+            "analytics" => Ok(Self::Analytics),
+            "admin" => Ok(Self::Admin),
+            other => Err(RookError::validation(format!(
+                "Unknown key role '{}'. Supported: read_only, read_write, analytics, admin",
+                other
+            ))),
+        }
+    }
+
+    /// Whether keys with this role only see aggregated analytics.
+    pub fn is_aggregate_only(&self) -> bool {
+        matches!(self, Self::Analytics)
+    }
+
+    /// Whether keys with this role may perform `action`.
+    pub fn allows(&self, action: AuthzAction) -> bool {
+        use AuthzAction::*;
+        match self {
+            Self::ReadOnly => matches!(action, Search | Get | List | History),
+            Self::ReadWrite => !matches!(action, Configure | Reset | Admin | Analytics),
+            Self::Analytics => matches!(action, Analytics),
+            Self::Admin => true,
+        }
+    }
+}
+
+impl std::fmt::Display for KeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A stored API key. The secret itself is never kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    /// Leading characters of the secret, to tell keys apart.
+    pub prefix: String,
+    pub role: KeyRole,
+    /// Requests made with the key must be scoped to this user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Organization (tenant) whose memories the key works on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    /// Requests allowed per minute. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_minute: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Subject that requests made with this key run as.
+    pub fn subject(&self) -> String {
+        format!("{}{}", KEY_SUBJECT_PREFIX, self.id)
+    }
+
+    /// Whether the key has been revoked.
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+/// Settings for a new key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewApiKey {
+    pub name: String,
+    pub role: KeyRole,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+impl NewApiKey {
+    /// A key with no user or organization binding or rate limit.
+    pub fn new(name: impl Into<String>, role: KeyRole) -> Self {
+        Self {
+            name: name.into(),
+            role,
+            user_id: None,
+            org_id: None,
+            rate_limit_per_minute: None,
+        }
+    }
+
+    /// Bind the key to a user.
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Make the key work on an organization's memories.
+    pub fn with_org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.org_id = Some(org_id.into());
+        self
+    }
+
+    /// Limit the key to `per_minute` requests per minute.
+    pub fn with_rate_limit(mut self, per_minute: u32) -> Self {
+        self.rate_limit_per_minute = Some(per_minute);
+        self
+    }
+}
+
+/// SQLite-backed API key store.
+pub struct ApiKeyStore {
+    conn: Mutex<Connection>,
+}
+
+impl ApiKeyStore {
+    /// Open the store at `path` (`:memory:` for an in-memory store).
+    pub fn new(path: impl AsRef<Path>) -> RookResult<Self> {
+        let conn = if path.as_ref().to_str() == Some(":memory:") {
+            Connection::open_in_memory()?
+        } else {
+            if let Some(parent) = path.as_ref().parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Connection::open(path.as_ref())?
+        };
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                id                    TEXT PRIMARY KEY,
+                name                  TEXT NOT NULL,
+                prefix                TEXT NOT NULL,
+                key_hash              TEXT NOT NULL UNIQUE,
+                role                  TEXT NOT NULL,
+                user_id               TEXT,
+                rate_limit_per_minute INTEGER,
+                created_at            TEXT NOT NULL,
+                revoked_at            TEXT
+            );
+            "#,
+        )?;
+
+        // Added after the first release of the table
+        let has_org_id: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('api_keys') WHERE name = 'org_id'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap_or(0)
+            > 0;
+        if !has_org_id {
+            conn.execute("ALTER TABLE api_keys ADD COLUMN org_id TEXT", [])?;
+        }
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// An in-memory store.
+    pub fn in_memory() -> RookResult<Self> {
+        Self::new(":memory:")
+    }
+
+    /// Create a key. Returns the key and its secret, which cannot be
+    /// recovered later.
+    pub fn create(&self, new: NewApiKey) -> RookResult<(ApiKey, String)> {
+        if new.name.trim().is_empty() {
+            return Err(RookError::validation("API key name must not be empty"));
+        }
+        if new.role.is_aggregate_only() && new.user_id.is_some() {
+            return Err(RookError::validation(
+                "analytics keys cover all users and cannot be bound to one",
+            ));
+        }
+        if let Some(ref org_id) = new.org_id {
+            validate_tenant_id(org_id)?;
+            if matches!(new.role, KeyRole::Admin | KeyRole::Analytics) {
+                return Err(RookError::validation(
+                    "organization keys cannot have server-wide roles (admin, analytics)",
+                ));
+            }
+        }
+        if new.rate_limit_per_minute == Some(0) {
+            return Err(RookError::validation(
+                "rate_limit_per_minute must be greater than 0",
+            ));
+        }
+
+        let random: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(SECRET_LENGTH)
+            .map(char::from)
+            .collect();
+        let secret = format!("{}{}", SECRET_PREFIX, random);
+        let key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            name: new.name,
+            prefix: secret[..DISPLAY_PREFIX_LENGTH].to_string(),
+            role: new.role,
+            user_id: new.user_id,
+            org_id: new.org_id,
+            rate_limit_per_minute: new.rate_limit_per_minute,
+            created_at: Utc::now(),
+            revoked_at: None,
+        };
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO api_keys
+                (id, name, prefix, key_hash, role, user_id, org_id, rate_limit_per_minute,
+                 created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                key.id,
+                key.name,
+                key.prefix,
+                hash_secret(&secret),
+                key.role.as_str(),
+                key.user_id,
+                key.org_id,
+                key.rate_limit_per_minute,
+                key.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok((key, secret))
+    }
+
+    /// The active key with this secret, if any.
+    pub fn authenticate(&self, secret: &str) -> RookResult<Option<ApiKey>> {
+        let conn = self.conn.lock().unwrap();
+        let key = conn
+            .query_row(
+                "SELECT id, name, prefix, role, user_id, org_id, rate_limit_per_minute, created_at,
+                        revoked_at
+                 FROM api_keys WHERE key_hash = ?1 AND revoked_at IS NULL",
+                [hash_secret(secret)],
+                Self::read_row,
+            )
+            .optional()?;
+        Ok(key)
+    }
+
+    /// A key by ID, including revoked keys.
+    pub fn get(&self, id: &str) -> RookResult<Option<ApiKey>> {
+        let conn = self.conn.lock().unwrap();
+        let key = conn
+            .query_row(
+                "SELECT id, name, prefix, role, user_id, org_id, rate_limit_per_minute, created_at,
+                        revoked_at
+                 FROM api_keys WHERE id = ?1",
+                [id],
+                Self::read_row,
+            )
+            .optional()?;
+        Ok(key)
+    }
+
+    /// All keys, newest first.
+    pub fn list(&self) -> RookResult<Vec<ApiKey>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, name, prefix, role, user_id, org_id, rate_limit_per_minute, created_at,
+                    revoked_at
+             FROM api_keys ORDER BY created_at DESC",
+        )?;
+        let keys = stmt
+            .query_map([], Self::read_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(keys)
+    }
+
+    /// Revoke a key. Returns false if it does not exist or was already
+    /// revoked.
+    pub fn revoke(&self, id: &str) -> RookResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let changed = conn.execute(
+            "UPDATE api_keys SET revoked_at = ?2 WHERE id = ?1 AND revoked_at IS NULL",
+            params![id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(changed > 0)
+    }
+
+    fn read_row(row: &Row<'_>) -> rusqlite::Result<ApiKey> {
+        let text = |idx: usize, e: RookError| {
+            rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e))
+        };
+        Ok(ApiKey {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            prefix: row.get(2)?,
+            role: KeyRole::parse(&row.get::<_, String>(3)?).map_err(|e| text(3, e))?,
+            user_id: row.get(4)?,
+            org_id: row.get(5)?,
+            rate_limit_per_minute: row.get(6)?,
+            created_at: parse_time(&row.get::<_, String>(7)?).map_err(|e| text(7, e))?,
+            revoked_at: row
+                .get::<_, Option<String>>(8)?
+                .map(|s| parse_time(&s))
+                .transpose()
+                .map_err(|e| text(8, e))?,
+        })
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+fn parse_time(s: &str) -> RookResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| RookError::internal(format!("Invalid API key timestamp '{}': {}", s, e)))
+}
+
+/// Authorizer enforcing API key roles and user bindings.
+///
+/// Requests from `key:<id>` subjects must be allowed by the key's role and,
+/// for user-bound keys, be scoped to that user. Everything that passes (and
+/// every request from other subjects) is decided by the wrapped authorizer.
+pub struct ApiKeyAuthorizer {
+    inner: Arc<dyn Authorizer>,
+    store: Arc<ApiKeyStore>,
+}
+
+impl ApiKeyAuthorizer {
+    /// Wrap `inner` with the key checks.
+    pub fn new(inner: Arc<dyn Authorizer>, store: Arc<ApiKeyStore>) -> Self {
+        Self { inner, store }
+    }
+}
+
+#[async_trait]
+impl Authorizer for ApiKeyAuthorizer {
+    async fn authorize(&self, request: &AuthzRequest) -> RookResult<AuthzDecision> {
+        if let Some(id) = request.subject.strip_prefix(KEY_SUBJECT_PREFIX) {
+            let key = match self.store.get(id)? {
+                Some(key) if !key.is_revoked() => key,
+                _ => return Ok(AuthzDecision::deny("unknown or revoked API key")),
+            };
+            if !key.role.allows(request.action) {
+                return Ok(AuthzDecision::deny(format!(
+                    "{} keys cannot {}",
+                    key.role, request.action
+                )));
+            }
+            if let Some(ref user_id) = key.user_id {
+                if request.user_id.as_ref() != Some(user_id) {
+                    return Ok(AuthzDecision::deny(format!(
+                        "key is bound to user '{}'",
+                        user_id
+                    )));
+                }
+            }
+        }
+
+        self.inner.authorize(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authz::AllowAll;
+
+    #[test]
+    fn test_key_store_roundtrip() {
+        let store = ApiKeyStore::in_memory().unwrap();
+        let (key, secret) = store
+            .create(NewApiKey::new("assistant", KeyRole::ReadWrite).with_rate_limit(60))
+            .unwrap();
+        assert!(secret.starts_with(SECRET_PREFIX));
+        assert!(secret.starts_with(&key.prefix));
+
+        assert_eq!(store.authenticate(&secret).unwrap(), Some(key.clone()));
+        assert!(store.authenticate("rook_wrong").unwrap().is_none());
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        assert!(store.revoke(&key.id).unwrap());
+        assert!(!store.revoke(&key.id).unwrap());
+        assert!(store.authenticate(&secret).unwrap().is_none());
+        assert!(store.get(&key.id).unwrap().unwrap().is_revoked());
+    }
+
+    #[test]
+    fn test_create_validates() {
+        let store = ApiKeyStore::in_memory().unwrap();
+        assert!(store.create(NewApiKey::new(" ", KeyRole::Admin)).is_err());
+        assert!(store
+            .create(NewApiKey::new("bot", KeyRole::Admin).with_rate_limit(0))
+            .is_err());
+        assert!(store
+            .create(NewApiKey::new("bi", KeyRole::Analytics).with_user_id("alice"))
+            .is_err());
+        assert!(store
+            .create(NewApiKey::new("ops", KeyRole::Admin).with_org_id("acme"))
+            .is_err());
+        assert!(store
+            .create(NewApiKey::new("app", KeyRole::ReadWrite).with_org_id("../acme"))
+            .is_err());
+        let (key, _) = store
+            .create(NewApiKey::new("app", KeyRole::ReadWrite).with_org_id("acme"))
+            .unwrap();
+        assert_eq!(key.org_id.as_deref(), Some("acme"));
+    }
+
+    #[test]
+    fn test_role_scopes() {
+        assert!(KeyRole::ReadOnly.allows(AuthzAction::Search));
+        assert!(!KeyRole::ReadOnly.allows(AuthzAction::Add));
+        assert!(KeyRole::ReadWrite.allows(AuthzAction::Delete));
+        assert!(!KeyRole::ReadWrite.allows(AuthzAction::Admin));
+        assert!(KeyRole::Analytics.allows(AuthzAction::Analytics));
+        assert!(!KeyRole::Analytics.allows(AuthzAction::Search));
+        assert!(KeyRole::Admin.allows(AuthzAction::Reset));
+    }
+
+    #[tokio::test]
+    async fn test_authorizer_enforces_role_and_user_binding() {
+        let store = Arc::new(ApiKeyStore::in_memory().unwrap());
+        let (key, _) = store
+            .create(NewApiKey::new("alice-bot", KeyRole::ReadOnly).with_user_id("alice"))
+            .unwrap();
+        let authorizer = ApiKeyAuthorizer::new(Arc::new(AllowAll), store.clone());
+        let request = |action, user_id: Option<&str>| {
+            AuthzRequest::new(key.subject(), action).with_scope(
+                user_id.map(str::to_string),
+                None,
+                None,
+            )
+        };
+
+        let allowed = authorizer
+            .authorize(&request(AuthzAction::Search, Some("alice")))
+            .await
+            .unwrap();
+        assert!(allowed.is_allowed());
+        for denied in [
+            request(AuthzAction::Add, Some("alice")),
+            request(AuthzAction::Search, Some("bob")),
+            request(AuthzAction::Search, None),
+        ] {
+            assert!(!authorizer.authorize(&denied).await.unwrap().is_allowed());
+        }
+
+        // Other subjects are left to the wrapped authorizer
+        let other = AuthzRequest::new("gateway-user", AuthzAction::Reset);
+        assert!(authorizer.authorize(&other).await.unwrap().is_allowed());
+
+        store.revoke(&key.id).unwrap();
+        let revoked = authorizer
+            .authorize(&request(AuthzAction::Search, Some("alice")))
+            .await
+            .unwrap();
+        assert!(!revoked.is_allowed());
+    }
+}
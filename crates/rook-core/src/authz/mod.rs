@@ -0,0 +1,318 @@
+//! Pluggable authorization hooks.
+//!
+//! An [`Authorizer`] is consulted before each memory operation with the
+//! calling subject, the action, the session scope and (where known) the
+//! target memory's metadata. Built-in implementations:
+//! - [`AllowAll`] - no authorization (default)
+//! - [`StaticPolicyAuthorizer`] - rules loaded from a policy file
+//! - [`OpaAuthorizer`] - delegates to an OPA or other HTTP policy endpoint
+//!
+//! [`ApiKeyAuthorizer`] wraps any of these to enforce API key roles.
+
+mod keys;
+mod opa;
+mod policy;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+pub use keys::{ApiKey, ApiKeyAuthorizer, ApiKeyStore, KeyRole, NewApiKey, KEY_SUBJECT_PREFIX};
+pub use opa::OpaAuthorizer;
+pub use policy::{PolicyEffect, PolicyFile, PolicyRule, StaticPolicyAuthorizer};
+
+/// Operation being authorized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthzAction {
+    Add,
+    Search,
+    Get,
+    List,
+    Update,
+    Delete,
+    DeleteAll,
+    History,
+    Signal,
+    Configure,
+    Reset,
+    Admin,
+    /// Read aggregate analytics across tenants.
+    Analytics,
+}
+
+impl AuthzAction {
+    /// Get the string representation used in policies.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Search => "search",
+            Self::Get => "get",
+            Self::List => "list",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::DeleteAll => "delete_all",
+            Self::History => "history",
+            Self::Signal => "signal",
+            Self::Configure => "configure",
+            Self::Reset => "reset",
+            Self::Admin => "admin",
+            Self::Analytics => "analytics",
+        }
+    }
+}
+
+impl std::fmt::Display for AuthzAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Input to an authorization decision.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthzRequest {
+    /// Who is performing the operation (user, service, API key id).
+    pub subject: String,
+    /// Operation being performed.
+    pub action: AuthzAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    /// Target memory, for single-memory operations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<String>,
+    /// Metadata of the target memory, or of the memory being created.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl AuthzRequest {
+    /// Create a request with no scope.
+    pub fn new(subject: impl Into<String>, action: AuthzAction) -> Self {
+        Self {
+            subject: subject.into(),
+            action,
+            user_id: None,
+            agent_id: None,
+            run_id: None,
+            memory_id: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Set the session scope.
+    pub fn with_scope(
+        mut self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> Self {
+        self.user_id = user_id;
+        self.agent_id = agent_id;
+        self.run_id = run_id;
+        self
+    }
+
+    /// Set the target memory id.
+    pub fn with_memory_id(mut self, memory_id: impl Into<String>) -> Self {
+        self.memory_id = Some(memory_id.into());
+        self
+    }
+
+    /// Set memory metadata. Scope fields present in the metadata fill in
+    /// any scope that has not been set explicitly.
+    pub fn with_metadata(mut self, metadata: HashMap<String, serde_json::Value>) -> Self {
+        let scope_value = |key: &str| {
+            metadata
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+        self.user_id = self.user_id.or_else(|| scope_value("user_id"));
+        self.agent_id = self.agent_id.or_else(|| scope_value("agent_id"));
+        self.run_id = self.run_id.or_else(|| scope_value("run_id"));
+        self.metadata = metadata;
+        self
+    }
+}
+
+/// Result of an authorization check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "lowercase")]
+pub enum AuthzDecision {
+    Allow,
+    Deny { reason: String },
+}
+
+impl AuthzDecision {
+    /// Create a deny decision.
+    pub fn deny(reason: impl Into<String>) -> Self {
+        Self::Deny {
+            reason: reason.into(),
+        }
+    }
+
+    /// Whether the operation is allowed.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allow)
+    }
+}
+
+/// Authorization hook invoked per memory operation.
+#[async_trait]
+pub trait Authorizer: Send + Sync {
+    /// Decide whether the request is allowed.
+    async fn authorize(&self, request: &AuthzRequest) -> RookResult<AuthzDecision>;
+
+    /// Authorize and convert a deny decision into a forbidden error.
+    async fn enforce(&self, request: &AuthzRequest) -> RookResult<()> {
+        match self.authorize(request).await? {
+            AuthzDecision::Allow => Ok(()),
+            AuthzDecision::Deny { reason } => Err(RookError::forbidden(format!(
+                "'{}' is not allowed to {}: {}",
+                request.subject, request.action, reason
+            ))),
+        }
+    }
+}
+
+/// Authorizer that allows every operation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+#[async_trait]
+impl Authorizer for AllowAll {
+    async fn authorize(&self, _request: &AuthzRequest) -> RookResult<AuthzDecision> {
+        Ok(AuthzDecision::Allow)
+    }
+}
+
+/// Authorizer selection.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum AuthzConfig {
+    /// No authorization.
+    #[default]
+    None,
+    /// Static policy file (TOML, JSON, or YAML).
+    Static { path: PathBuf },
+    /// External OPA/HTTP policy endpoint.
+    Opa {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+        #[serde(default = "default_opa_timeout_ms")]
+        timeout_ms: u64,
+    },
+}
+
+fn default_opa_timeout_ms() -> u64 {
+    2_000
+}
+
+impl AuthzConfig {
+    /// Load from environment variables.
+    ///
+    /// - `ROOK_AUTHZ_POLICY_FILE` - path to a static policy file
+    /// - `ROOK_AUTHZ_OPA_URL` - OPA decision endpoint, e.g.
+    ///   `http://localhost:8181/v1/data/rook/allow`
+    /// - `ROOK_AUTHZ_OPA_TOKEN` - optional bearer token for the endpoint
+    /// - `ROOK_AUTHZ_OPA_TIMEOUT_MS` - request timeout (default: 2000)
+    ///
+    /// When both are set the policy file wins.
+    pub fn from_env() -> Self {
+        if let Ok(path) = std::env::var("ROOK_AUTHZ_POLICY_FILE") {
+            return Self::Static {
+                path: PathBuf::from(path),
+            };
+        }
+
+        if let Ok(url) = std::env::var("ROOK_AUTHZ_OPA_URL") {
+            return Self::Opa {
+                url,
+                token: std::env::var("ROOK_AUTHZ_OPA_TOKEN").ok(),
+                timeout_ms: std::env::var("ROOK_AUTHZ_OPA_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or_else(default_opa_timeout_ms),
+            };
+        }
+
+        Self::None
+    }
+
+    /// Build the configured authorizer.
+    pub fn build(&self) -> RookResult<Arc<dyn Authorizer>> {
+        Ok(match self {
+            Self::None => Arc::new(AllowAll),
+            Self::Static { path } => Arc::new(StaticPolicyAuthorizer::from_file(path)?),
+            Self::Opa {
+                url,
+                token,
+                timeout_ms,
+            } => Arc::new(OpaAuthorizer::new(
+                url.clone(),
+                token.clone(),
+                std::time::Duration::from_millis(*timeout_ms),
+            )?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DenyAll;
+
+    #[async_trait]
+    impl Authorizer for DenyAll {
+        async fn authorize(&self, _request: &AuthzRequest) -> RookResult<AuthzDecision> {
+            Ok(AuthzDecision::deny("nope"))
+        }
+    }
+
+    #[test]
+    fn test_metadata_fills_missing_scope() {
+        let metadata = HashMap::from([
+            ("user_id".to_string(), serde_json::json!("alice")),
+            ("agent_id".to_string(), serde_json::json!("bot")),
+        ]);
+        let request = AuthzRequest::new("svc", AuthzAction::Get)
+            .with_scope(None, Some("other".to_string()), None)
+            .with_metadata(metadata);
+
+        assert_eq!(request.user_id.as_deref(), Some("alice"));
+        assert_eq!(request.agent_id.as_deref(), Some("other"));
+        assert!(request.run_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_enforce_maps_deny_to_forbidden() {
+        let request = AuthzRequest::new("svc", AuthzAction::Reset);
+        assert!(AllowAll.enforce(&request).await.is_ok());
+
+        let err = DenyAll.enforce(&request).await.unwrap_err();
+        assert_eq!(err.code(), crate::error::ErrorCode::AuthForbidden);
+        assert!(err.to_string().contains("reset"));
+    }
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: AuthzConfig =
+            serde_json::from_str(r#"{"provider": "opa", "url": "http://opa:8181/v1/data/rook"}"#)
+                .unwrap();
+        match config {
+            AuthzConfig::Opa { timeout_ms, .. } => assert_eq!(timeout_ms, 2_000),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! OPA / HTTP policy endpoint authorizer.
+//!
+//! Posts `{"input": <AuthzRequest>}` to the configured URL and accepts either
+//! OPA-style `{"result": true}` or `{"result": {"allow": bool, "reason": "..."}}`.
+//! An undefined result is treated as deny; transport errors fail closed.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use super::{AuthzDecision, AuthzRequest, Authorizer};
+use crate::error::{RookError, RookResult};
+
+/// Authorizer that delegates decisions to an external policy endpoint.
+#[derive(Debug, Clone)]
+pub struct OpaAuthorizer {
+    client: Client,
+    url: String,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OpaResult {
+    Bool(bool),
+    Object {
+        allow: bool,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct OpaResponse {
+    #[serde(default)]
+    result: Option<OpaResult>,
+}
+
+impl OpaAuthorizer {
+    /// Create an authorizer for the given decision endpoint.
+    pub fn new(url: impl Into<String>, token: Option<String>, timeout: Duration) -> RookResult<Self> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| RookError::Configuration(format!("Failed to build HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            url: url.into(),
+            token,
+        })
+    }
+
+    fn decision_from_body(body: &str) -> RookResult<AuthzDecision> {
+        let response: OpaResponse = serde_json::from_str(body)
+            .map_err(|e| RookError::parse(format!("Invalid policy response: {}", e)))?;
+
+        Ok(match response.result {
+            Some(OpaResult::Bool(true)) => AuthzDecision::Allow,
+            Some(OpaResult::Bool(false)) => AuthzDecision::deny("denied by policy endpoint"),
+            Some(OpaResult::Object { allow: true, .. }) => AuthzDecision::Allow,
+            Some(OpaResult::Object {
+                allow: false,
+                reason,
+            }) => AuthzDecision::deny(reason.unwrap_or_else(|| "denied by policy endpoint".to_string())),
+            None => AuthzDecision::deny("policy decision is undefined"),
+        })
+    }
+}
+
+#[async_trait]
+impl Authorizer for OpaAuthorizer {
+    async fn authorize(&self, request: &AuthzRequest) -> RookResult<AuthzDecision> {
+        let mut http = self
+            .client
+            .post(&self.url)
+            .json(&serde_json::json!({ "input": request }));
+        if let Some(ref token) = self.token {
+            http = http.bearer_auth(token);
+        }
+
+        let response = http
+            .send()
+            .await
+            .map_err(|e| RookError::api(format!("Policy endpoint request failed: {}", e)))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| RookError::api(format!("Failed to read policy response: {}", e)))?;
+
+        if !status.is_success() {
+            return Err(RookError::api(format!(
+                "Policy endpoint returned {}: {}",
+                status, body
+            )));
+        }
+
+        Self::decision_from_body(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decision_from_body() {
+        assert_eq!(
+            OpaAuthorizer::decision_from_body(r#"{"result": true}"#).unwrap(),
+            AuthzDecision::Allow
+        );
+        assert!(!OpaAuthorizer::decision_from_body(r#"{"result": false}"#)
+            .unwrap()
+            .is_allowed());
+        assert_eq!(
+            OpaAuthorizer::decision_from_body(r#"{"result": {"allow": false, "reason": "pii"}}"#)
+                .unwrap(),
+            AuthzDecision::deny("pii")
+        );
+        // Undefined decision: OPA returns an empty document.
+        assert!(!OpaAuthorizer::decision_from_body("{}").unwrap().is_allowed());
+        assert!(OpaAuthorizer::decision_from_body("not json").is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Static policy file authorizer.
+//!
+//! Example policy (TOML):
+//!
+//! ```toml
+//! default = "deny"
+//!
+//! [[rules]]
+//! subjects = ["*"]
+//! actions = ["add", "search", "get", "list", "update", "delete"]
+//! user_ids = ["$subject"]
+//!
+//! [[rules]]
+//! effect = "deny"
+//! subjects = ["*"]
+//! actions = ["*"]
+//! metadata = { classification = "restricted" }
+//!
+//! [[rules]]
+//! subjects = ["admin-*"]
+//! actions = ["*"]
+//! ```
+//!
+//! Deny rules take precedence over allow rules; when no rule matches the
+//! `default` effect applies.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{AuthzDecision, AuthzRequest, Authorizer};
+use crate::error::{RookError, RookResult};
+
+/// Placeholder in scope lists that matches the requesting subject.
+const SUBJECT_PLACEHOLDER: &str = "$subject";
+
+/// Effect of a matching rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyEffect {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// A single policy rule. Empty lists match anything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyRule {
+    /// Effect when the rule matches.
+    pub effect: PolicyEffect,
+    /// Subject patterns (`*` matches any, `prefix-*` matches a prefix).
+    pub subjects: Vec<String>,
+    /// Action names, or `*`.
+    pub actions: Vec<String>,
+    /// Allowed user ids (`$subject` matches the subject itself).
+    pub user_ids: Vec<String>,
+    /// Allowed agent ids.
+    pub agent_ids: Vec<String>,
+    /// Allowed run ids.
+    pub run_ids: Vec<String>,
+    /// Metadata fields that must equal the given values.
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// A policy document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicyFile {
+    /// Effect when no rule matches.
+    #[serde(default = "default_effect")]
+    pub default: PolicyEffect,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+fn default_effect() -> PolicyEffect {
+    PolicyEffect::Deny
+}
+
+/// Authorizer backed by a static policy document.
+#[derive(Debug, Clone)]
+pub struct StaticPolicyAuthorizer {
+    policy: PolicyFile,
+}
+
+impl StaticPolicyAuthorizer {
+    /// Create from an in-memory policy.
+    pub fn new(policy: PolicyFile) -> Self {
+        Self { policy }
+    }
+
+    /// Load a policy file (TOML, JSON, or YAML).
+    pub fn from_file(path: impl AsRef<Path>) -> RookResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let policy = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => {
+                toml::from_str(&content).map_err(|e| RookError::Configuration(e.to_string()))?
+            }
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            Some("yaml" | "yml") => serde_yaml::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            _ => {
+                return Err(RookError::Configuration(
+                    "Unsupported policy file format. Use .toml, .json, or .yaml".to_string(),
+                ))
+            }
+        };
+        Ok(Self::new(policy))
+    }
+
+    /// Evaluate the policy synchronously.
+    pub fn evaluate(&self, request: &AuthzRequest) -> AuthzDecision {
+        let mut allowed = false;
+        for (index, rule) in self.policy.rules.iter().enumerate() {
+            if !rule_matches(rule, request) {
+                continue;
+            }
+            match rule.effect {
+                PolicyEffect::Deny => {
+                    return AuthzDecision::deny(format!("denied by policy rule {}", index))
+                }
+                PolicyEffect::Allow => allowed = true,
+            }
+        }
+
+        if allowed || self.policy.default == PolicyEffect::Allow {
+            AuthzDecision::Allow
+        } else {
+            AuthzDecision::deny("no policy rule allows this operation")
+        }
+    }
+}
+
+#[async_trait]
+impl Authorizer for StaticPolicyAuthorizer {
+    async fn authorize(&self, request: &AuthzRequest) -> RookResult<AuthzDecision> {
+        Ok(self.evaluate(request))
+    }
+}
+
+fn rule_matches(rule: &PolicyRule, request: &AuthzRequest) -> bool {
+    let subject_ok = rule.subjects.is_empty()
+        || rule
+            .subjects
+            .iter()
+            .any(|pattern| pattern_matches(pattern, &request.subject));
+    let action_ok = rule.actions.is_empty()
+        || rule
+            .actions
+            .iter()
+            .any(|a| a == "*" || a == request.action.as_str());
+
+    subject_ok
+        && action_ok
+        && scope_matches(&rule.user_ids, request.user_id.as_deref(), &request.subject)
+        && scope_matches(&rule.agent_ids, request.agent_id.as_deref(), &request.subject)
+        && scope_matches(&rule.run_ids, request.run_id.as_deref(), &request.subject)
+        && rule
+            .metadata
+            .iter()
+            .all(|(key, expected)| request.metadata.get(key) == Some(expected))
+}
+
+fn pattern_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+fn scope_matches(allowed: &[String], value: Option<&str>, subject: &str) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    let Some(value) = value else {
+        return false;
+    };
+    allowed.iter().any(|pattern| {
+        if pattern == SUBJECT_PLACEHOLDER {
+            value == subject
+        } else {
+            pattern_matches(pattern, value)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authz::AuthzAction;
+
+    fn policy() -> StaticPolicyAuthorizer {
+        let policy: PolicyFile = toml::from_str(
+            r#"
+            [[rules]]
+            subjects = ["*"]
+            actions = ["add", "search", "get"]
+            user_ids = ["$subject"]
+
+            [[rules]]
+            effect = "deny"
+            actions = ["*"]
+            metadata = { classification = "restricted" }
+
+            [[rules]]
+            subjects = ["admin-*"]
+            actions = ["*"]
+            "#,
+        )
+        .unwrap();
+        StaticPolicyAuthorizer::new(policy)
+    }
+
+    #[test]
+    fn test_subject_scoped_access() {
+        let authz = policy();
+        let own = AuthzRequest::new("alice", AuthzAction::Search).with_scope(
+            Some("alice".to_string()),
+            None,
+            None,
+        );
+        assert!(authz.evaluate(&own).is_allowed());
+
+        let other = AuthzRequest::new("alice", AuthzAction::Search).with_scope(
+            Some("bob".to_string()),
+            None,
+            None,
+        );
+        assert!(!authz.evaluate(&other).is_allowed());
+    }
+
+    #[test]
+    fn test_default_is_deny() {
+        let authz = policy();
+        let request = AuthzRequest::new("alice", AuthzAction::Reset);
+        assert!(!authz.evaluate(&request).is_allowed());
+    }
+
+    #[test]
+    fn test_deny_takes_precedence() {
+        let authz = policy();
+        let metadata = HashMap::from([(
+            "classification".to_string(),
+            serde_json::json!("restricted"),
+        )]);
+        let request = AuthzRequest::new("admin-ops", AuthzAction::Get).with_metadata(metadata);
+        assert!(!authz.evaluate(&request).is_allowed());
+
+        let request = AuthzRequest::new("admin-ops", AuthzAction::Reset);
+        assert!(authz.evaluate(&request).is_allowed());
+    }
+}
@@ -0,0 +1,547 @@
+//! Snapshot backup and restore of the data directory.
+//!
+//! [`create_snapshot`] packs everything under a data directory (vector store,
+//! history, graph, FSRS and intention stores, versions, text index, blobs)
+//! into one `.tar.zst` archive. SQLite databases are copied with SQLite's
+//! online backup API, so each one is captured in a consistent state even
+//! while the server is writing to it; other files are copied as they are.
+//! The archive starts with a [`SnapshotManifest`] listing every entry.
+//!
+//! [`restore_snapshot`] unpacks an archive next to the target directory,
+//! checks it against the manifest and only then swaps it into place, keeping
+//! the previous contents. Stop anything using the directory first: open
+//! database connections keep writing to the replaced files.
+//!
+//! Snapshots kept in the data directory's `backups/` directory are not
+//! included in new snapshots.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Version of the archive layout written by [`create_snapshot`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest, the first entry of every snapshot.
+pub const MANIFEST_FILE: &str = "rook-snapshot.json";
+
+/// Directory under the data directory that snapshots leave out, where
+/// snapshots of the directory itself can be kept.
+pub const BACKUP_DIR: &str = "backups";
+
+/// Directory in the archive holding the data directory's contents.
+const DATA_PREFIX: &str = "data";
+
+/// Zstandard compression level of snapshots.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Files SQLite keeps next to a database, captured by the backup API.
+const SQLITE_SIDECARS: [&str; 3] = ["-wal", "-shm", "-journal"];
+
+/// What a snapshot contains.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of rook that created the snapshot
+    pub rook_version: String,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl SnapshotManifest {
+    /// Total size of the snapshotted files in bytes, uncompressed.
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+}
+
+/// A file in a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    /// Path relative to the data directory, `/`-separated
+    pub path: String,
+    pub kind: EntryKind,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// How an entry was captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    /// SQLite database, copied with the online backup API
+    Sqlite,
+    /// Any other file, copied as is
+    File,
+}
+
+/// Options for [`restore_snapshot`].
+#[derive(Debug, Clone, Default)]
+pub struct RestoreOptions {
+    /// Replace a data directory that is not empty. Its contents are moved
+    /// aside rather than deleted.
+    pub overwrite: bool,
+}
+
+impl RestoreOptions {
+    /// Replace a data directory that is not empty.
+    pub fn overwrite(mut self) -> Self {
+        self.overwrite = true;
+        self
+    }
+}
+
+/// Outcome of [`restore_snapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub manifest: SnapshotManifest,
+    /// Where the previous contents of the data directory were moved, if it
+    /// was not empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<PathBuf>,
+}
+
+/// Snapshot `data_dir` into a `.tar.zst` archive at `archive`.
+///
+/// The archive is written next to its destination and renamed into place
+/// once complete, so an interrupted snapshot never leaves a truncated archive
+/// behind. `archive` itself is skipped if it lies inside `data_dir`.
+pub fn create_snapshot(
+    data_dir: impl AsRef<Path>,
+    archive: impl AsRef<Path>,
+) -> RookResult<SnapshotManifest> {
+    let data_dir = data_dir.as_ref();
+    let archive = archive.as_ref();
+    if !data_dir.is_dir() {
+        return Err(RookError::validation(format!(
+            "Data directory '{}' does not exist",
+            data_dir.display()
+        )));
+    }
+
+    let partial = sibling(archive, "partial")?;
+    let staging = ScratchDir::create(sibling(archive, "staging")?)?;
+
+    // Copy databases first, so the manifest can record their sizes
+    let mut files = Vec::new();
+    collect_files(data_dir, data_dir, &[archive, &partial], &mut files)?;
+    let mut entries = Vec::with_capacity(files.len());
+    let mut sources = Vec::with_capacity(files.len());
+    for (index, (relative, path)) in files.into_iter().enumerate() {
+        let (kind, source) = if is_sqlite(&path)? {
+            let copy = staging.path().join(format!("{}.db", index));
+            backup_database(&path, &copy)?;
+            (EntryKind::Sqlite, copy)
+        } else {
+            (EntryKind::File, path)
+        };
+        entries.push(SnapshotEntry {
+            size: fs::metadata(&source)?.len(),
+            path: relative,
+            kind,
+        });
+        sources.push(source);
+    }
+
+    let manifest = SnapshotManifest {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        created_at: Utc::now(),
+        rook_version: env!("CARGO_PKG_VERSION").to_string(),
+        entries,
+    };
+
+    let written = write_archive(&partial, &manifest, &sources);
+    if let Err(e) = written {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, archive)?;
+
+    tracing::info!(
+        archive = %archive.display(),
+        entries = manifest.entries.len(),
+        bytes = manifest.total_bytes(),
+        "Created snapshot"
+    );
+    Ok(manifest)
+}
+
+/// Read the manifest of a snapshot without unpacking it.
+pub fn read_manifest(archive: impl AsRef<Path>) -> RookResult<SnapshotManifest> {
+    let mut tar = open_archive(archive.as_ref())?;
+    let mut entries = tar.entries()?;
+    read_manifest_entry(entries.next())
+}
+
+/// Restore a snapshot into `data_dir`.
+///
+/// Fails if `data_dir` is not empty, unless `options.overwrite` is set; its
+/// previous contents are then moved to a sibling directory named
+/// `<data_dir>.pre-restore-<timestamp>`, reported in
+/// [`RestoreReport::previous`].
+pub fn restore_snapshot(
+    archive: impl AsRef<Path>,
+    data_dir: impl AsRef<Path>,
+    options: &RestoreOptions,
+) -> RookResult<RestoreReport> {
+    let data_dir = data_dir.as_ref();
+    let occupied = data_dir.is_dir() && fs::read_dir(data_dir)?.next().is_some();
+    if occupied && !options.overwrite {
+        return Err(RookError::validation(format!(
+            "Data directory '{}' is not empty; restore with overwrite to replace it",
+            data_dir.display()
+        )));
+    }
+
+    let staging = ScratchDir::create(sibling(data_dir, "restore")?)?;
+    let manifest = unpack_archive(archive.as_ref(), staging.path())?;
+    let restored = staging.path().join(DATA_PREFIX);
+    verify_restored(&restored, &manifest)?;
+
+    let previous = if occupied {
+        let name = format!(
+            "{}.pre-restore-{}",
+            file_name(data_dir)?,
+            Utc::now().format("%Y%m%dT%H%M%SZ")
+        );
+        let previous = data_dir.with_file_name(name);
+        fs::rename(data_dir, &previous)?;
+        tracing::info!(previous = %previous.display(), "Moved aside previous data directory");
+        Some(previous)
+    } else {
+        if data_dir.exists() {
+            fs::remove_dir(data_dir)?;
+        }
+        None
+    };
+    fs::rename(&restored, data_dir)?;
+
+    tracing::info!(
+        data_dir = %data_dir.display(),
+        entries = manifest.entries.len(),
+        "Restored snapshot"
+    );
+    Ok(RestoreReport { manifest, previous })
+}
+
+/// Files under `dir`, as paths relative to `root` and absolute paths, in a
+/// stable order. Skips the backup directory, `exclude` and SQLite sidecars.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    exclude: &[&Path],
+    files: &mut Vec<(String, PathBuf)>,
+) -> RookResult<()> {
+    let mut children: Vec<_> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    children.sort();
+
+    for path in children {
+        if (dir == root && path.file_name().is_some_and(|n| n == BACKUP_DIR))
+            || exclude.iter().any(|excluded| same_file(&path, excluded))
+        {
+            continue;
+        }
+        let metadata = fs::metadata(&path)?;
+        if metadata.is_dir() {
+            collect_files(root, &path, exclude, files)?;
+        } else if metadata.is_file() && !is_sidecar(&path) {
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| RookError::internal(e.to_string()))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, path));
+        }
+    }
+    Ok(())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn is_sidecar(path: &Path) -> bool {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    SQLITE_SIDECARS.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Whether a file starts with the SQLite database header.
+fn is_sqlite(path: &Path) -> RookResult<bool> {
+    const HEADER: &[u8; 16] = b"SQLite format 3\0";
+    let mut header = [0u8; 16];
+    let mut file = File::open(path)?;
+    match file.read_exact(&mut header) {
+        Ok(()) => Ok(&header == HEADER),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Copy a live database with the online backup API.
+fn backup_database(source: &Path, destination: &Path) -> RookResult<()> {
+    let backup = || -> rusqlite::Result<()> {
+        let conn = Connection::open_with_flags(
+            source,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        conn.backup(DatabaseName::Main, destination, None)
+    };
+    backup().map_err(|e| {
+        RookError::database(format!("Failed to back up '{}': {}", source.display(), e))
+    })
+}
+
+fn write_archive(path: &Path, manifest: &SnapshotManifest, sources: &[PathBuf]) -> RookResult<()> {
+    let file = BufWriter::new(File::create(path)?);
+    let encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL)?;
+    let mut tar = tar::Builder::new(encoder);
+
+    let json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_FILE, json.as_slice())?;
+
+    for (entry, source) in manifest.entries.iter().zip(sources) {
+        let mut file = File::open(source)?;
+        tar.append_file(format!("{}/{}", DATA_PREFIX, entry.path), &mut file)?;
+    }
+
+    let mut file = tar.into_inner()?.finish()?;
+    file.flush()?;
+    file.get_ref().sync_all()?;
+    Ok(())
+}
+
+type SnapshotReader = tar::Archive<zstd::Decoder<'static, BufReader<File>>>;
+
+fn open_archive(path: &Path) -> RookResult<SnapshotReader> {
+    let file = File::open(path).map_err(|e| {
+        RookError::validation(format!("Cannot open snapshot '{}': {}", path.display(), e))
+    })?;
+    Ok(tar::Archive::new(zstd::Decoder::new(file)?))
+}
+
+fn read_manifest_entry<R: Read>(
+    entry: Option<std::io::Result<tar::Entry<'_, R>>>,
+) -> RookResult<SnapshotManifest> {
+    let mut entry = match entry {
+        Some(entry) => entry?,
+        None => return Err(RookError::validation("Snapshot is empty")),
+    };
+    if entry.path()?.as_ref() != Path::new(MANIFEST_FILE) {
+        return Err(RookError::validation(
+            "Not a rook snapshot: the manifest is missing",
+        ));
+    }
+    let mut json = Vec::new();
+    entry.read_to_end(&mut json)?;
+    let manifest: SnapshotManifest = serde_json::from_slice(&json)?;
+    if manifest.format_version > SNAPSHOT_FORMAT_VERSION {
+        return Err(RookError::validation(format!(
+            "Snapshot format {} is newer than the supported format {}",
+            manifest.format_version, SNAPSHOT_FORMAT_VERSION
+        )));
+    }
+    Ok(manifest)
+}
+
+/// Unpack a snapshot into `dir`, returning its manifest.
+fn unpack_archive(archive: &Path, dir: &Path) -> RookResult<SnapshotManifest> {
+    let mut tar = open_archive(archive)?;
+    let mut entries = tar.entries()?;
+    let manifest = read_manifest_entry(entries.next())?;
+
+    fs::create_dir_all(dir.join(DATA_PREFIX))?;
+    for entry in entries {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let inside = path.starts_with(DATA_PREFIX)
+            && path.components().all(|c| matches!(c, Component::Normal(_)));
+        if !inside || !entry.header().entry_type().is_file() {
+            return Err(RookError::validation(format!(
+                "Unexpected entry '{}' in snapshot",
+                path.display()
+            )));
+        }
+        entry.unpack_in(dir)?;
+    }
+    Ok(manifest)
+}
+
+/// Check that every file in the manifest was restored intact.
+fn verify_restored(dir: &Path, manifest: &SnapshotManifest) -> RookResult<()> {
+    for entry in &manifest.entries {
+        let path = dir.join(&entry.path);
+        let size = fs::metadata(&path)
+            .map(|m| m.len())
+            .map_err(|_| RookError::validation(format!("Snapshot is missing '{}'", entry.path)))?;
+        if size != entry.size {
+            return Err(RookError::validation(format!(
+                "'{}' is {} bytes, the manifest records {}",
+                entry.path, size, entry.size
+            )));
+        }
+        if entry.kind == EntryKind::Sqlite {
+            let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .map_err(|e| RookError::database(e.to_string()))?;
+            let result: String = conn
+                .query_row("PRAGMA quick_check", [], |row| row.get(0))
+                .map_err(|e| RookError::database(e.to_string()))?;
+            if result != "ok" {
+                return Err(RookError::validation(format!(
+                    "Database '{}' in snapshot is corrupt: {}",
+                    entry.path, result
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn file_name(path: &Path) -> RookResult<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| RookError::validation(format!("'{}' has no file name", path.display())))
+}
+
+/// A hidden path next to `path` for temporary use.
+fn sibling(path: &Path, purpose: &str) -> RookResult<PathBuf> {
+    let name = format!(".{}.{}-{}", file_name(path)?, purpose, uuid::Uuid::new_v4());
+    Ok(path.with_file_name(name))
+}
+
+/// A directory removed with its contents when dropped.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn create(path: PathBuf) -> RookResult<Self> {
+        fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_dir(root: &Path) -> PathBuf {
+        let dir = root.join("data");
+        fs::create_dir_all(dir.join("tantivy")).unwrap();
+        fs::create_dir_all(dir.join(BACKUP_DIR)).unwrap();
+        fs::write(dir.join("tantivy/meta.json"), b"{}").unwrap();
+        fs::write(dir.join(BACKUP_DIR).join("old.tar.zst"), b"old").unwrap();
+
+        let conn = Connection::open(dir.join("history.db")).unwrap();
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE history (id TEXT PRIMARY KEY, memory TEXT);
+             INSERT INTO history VALUES ('m1', 'likes tea');",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = data_dir(root.path());
+        let archive = root.path().join("snapshot.tar.zst");
+
+        let manifest = create_snapshot(&dir, &archive).unwrap();
+        let paths: Vec<_> = manifest.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["history.db", "tantivy/meta.json"]);
+        assert_eq!(manifest.entries[0].kind, EntryKind::Sqlite);
+        assert_eq!(read_manifest(&archive).unwrap(), manifest);
+
+        let target = root.path().join("restored");
+        let report = restore_snapshot(&archive, &target, &RestoreOptions::default()).unwrap();
+        assert!(report.previous.is_none());
+        assert_eq!(
+            fs::read(target.join("tantivy/meta.json")).unwrap(),
+            b"{}".to_vec()
+        );
+        let conn = Connection::open(target.join("history.db")).unwrap();
+        let memory: String = conn
+            .query_row("SELECT memory FROM history WHERE id = 'm1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(memory, "likes tea");
+
+        // No scratch files are left behind
+        let leftovers: Vec<_> = fs::read_dir(root.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.starts_with('.'))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    }
+
+    #[test]
+    fn test_restore_requires_overwrite() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = data_dir(root.path());
+        let archive = root.path().join("snapshot.tar.zst");
+        create_snapshot(&dir, &archive).unwrap();
+
+        fs::write(dir.join("tantivy/meta.json"), b"changed").unwrap();
+        assert!(restore_snapshot(&archive, &dir, &RestoreOptions::default()).is_err());
+
+        let report =
+            restore_snapshot(&archive, &dir, &RestoreOptions::default().overwrite()).unwrap();
+        let previous = report.previous.unwrap();
+        assert_eq!(
+            fs::read(dir.join("tantivy/meta.json")).unwrap(),
+            b"{}".to_vec()
+        );
+        assert_eq!(
+            fs::read(previous.join("tantivy/meta.json")).unwrap(),
+            b"changed".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_rejects_foreign_archive() {
+        let root = tempfile::tempdir().unwrap();
+        let archive = root.path().join("other.tar.zst");
+        let encoder = zstd::Encoder::new(File::create(&archive).unwrap(), 0).unwrap();
+        let mut tar = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_cksum();
+        tar.append_data(&mut header, "notes.txt", &b"hi"[..])
+            .unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        assert!(read_manifest(&archive).is_err());
+        let target = root.path().join("restored");
+        assert!(restore_snapshot(&archive, &target, &RestoreOptions::default()).is_err());
+        assert!(!target.exists());
+    }
+}
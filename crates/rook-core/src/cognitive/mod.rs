@@ -5,5 +5,324 @@
 mod scheduler;
 mod store;
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
 pub use scheduler::FsrsScheduler;
-pub use store::{ArchivalCandidate, CognitiveStore};
+pub use store::{ArchivalCandidate, CognitiveSnapshot, CognitiveStateSnapshot, CognitiveStore};
+
+/// FSRS tracking of memories as they are added and accessed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CognitiveConfig {
+    /// Create FSRS state on add, review it on access and return
+    /// retrievability with memories (default: false).
+    pub enabled: bool,
+    /// Cognitive state database. Defaults to `cognitive.db` next to the
+    /// history database; ignored when a shared store is set with
+    /// `Memory::with_cognitive_store`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_path: Option<PathBuf>,
+    /// FSRS parameters, with per-category overrides.
+    pub fsrs: FsrsConfig,
+    /// Weighting of search scores by memory strength.
+    pub decay_ranking: DecayRankingConfig,
+}
+
+/// FSRS parameters for memories.
+///
+/// Unset parameters use the FSRS-6 defaults. Memories whose category has an
+/// entry in `categories` use its parameters instead, falling back to these
+/// for the ones it leaves unset. Checked when the memory is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FsrsConfig {
+    /// The 21 FSRS-6 weights, e.g. from the FSRS optimizer. The first four
+    /// set the initial stability by grade, the next two the initial
+    /// difficulty and the last the decay exponent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f32>>,
+    /// Forgetting curve decay exponent, between 0 and 1 (default: the last
+    /// weight). Smaller values flatten the curve, so memories fade more
+    /// slowly once past their stability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay: Option<f32>,
+    /// Retrievability below which a memory is due for review (default: 0.9).
+    pub desired_retention: f32,
+    /// Overrides by memory category, e.g. `personal_details`.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub categories: HashMap<String, FsrsParams>,
+}
+
+impl Default for FsrsConfig {
+    fn default() -> Self {
+        Self {
+            weights: None,
+            decay: None,
+            desired_retention: 0.9,
+            categories: HashMap::new(),
+        }
+    }
+}
+
+/// FSRS parameters of one memory category. Unset ones are taken from
+/// [`FsrsConfig`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FsrsParams {
+    /// The 21 FSRS-6 weights. When set without `decay`, the last weight is
+    /// the decay exponent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f32>>,
+    /// Forgetting curve decay exponent, between 0 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay: Option<f32>,
+    /// Retrievability below which a memory is due for review.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desired_retention: Option<f32>,
+}
+
+impl FsrsConfig {
+    /// Check every parameter set, including category overrides.
+    pub fn validate(&self) -> RookResult<()> {
+        self.scheduler()?;
+        self.category_schedulers()?;
+        Ok(())
+    }
+
+    /// Scheduler for memories without a category override.
+    pub fn scheduler(&self) -> RookResult<FsrsScheduler> {
+        build_scheduler(
+            "cognitive.fsrs",
+            self.weights.as_deref(),
+            self.decay,
+            self.desired_retention,
+        )
+    }
+
+    /// Schedulers of the categories with overrides.
+    pub fn category_schedulers(&self) -> RookResult<HashMap<String, FsrsScheduler>> {
+        self.categories
+            .iter()
+            .map(|(category, params)| {
+                // Weights set for the category bring their own decay.
+                let decay = match (params.decay, &params.weights) {
+                    (Some(decay), _) => Some(decay),
+                    (None, Some(_)) => None,
+                    (None, None) => self.decay,
+                };
+                let scheduler = build_scheduler(
+                    &format!("cognitive.fsrs.categories.{}", category),
+                    params.weights.as_deref().or(self.weights.as_deref()),
+                    decay,
+                    params.desired_retention.unwrap_or(self.desired_retention),
+                )?;
+                Ok((category.clone(), scheduler))
+            })
+            .collect()
+    }
+}
+
+/// Build a scheduler from FSRS parameters, naming `label` in errors.
+fn build_scheduler(
+    label: &str,
+    weights: Option<&[f32]>,
+    decay: Option<f32>,
+    desired_retention: f32,
+) -> RookResult<FsrsScheduler> {
+    if !(desired_retention > 0.0 && desired_retention < 1.0) {
+        return Err(RookError::validation(format!(
+            "{}.desired_retention must be between 0 and 1, got {}",
+            label, desired_retention
+        )));
+    }
+    let mut scheduler = match weights {
+        None => FsrsScheduler::new(),
+        Some(w) => {
+            if w.len() != fsrs::DEFAULT_PARAMETERS.len() {
+                return Err(RookError::validation(format!(
+                    "{}.weights must have {} values, got {}",
+                    label,
+                    fsrs::DEFAULT_PARAMETERS.len(),
+                    w.len()
+                )));
+            }
+            if w.iter().any(|v| !v.is_finite()) || w[..4].iter().any(|v| *v <= 0.0) {
+                return Err(RookError::validation(format!(
+                    "{}.weights must be finite, with positive initial stabilities",
+                    label
+                )));
+            }
+            // FSRS initial difficulty: D0(G) = w4 - e^(w5 * (G - 1)) + 1
+            let difficulty =
+                |grade: f32| (w[4] - (w[5] * (grade - 1.0)).exp() + 1.0).clamp(1.0, 10.0);
+            FsrsScheduler::with_params(
+                w[20],
+                [w[0], w[1], w[2], w[3]],
+                [difficulty(1.0), difficulty(2.0), difficulty(3.0), difficulty(4.0)],
+            )
+        }
+    };
+    if let Some(decay) = decay {
+        if !(decay > 0.0 && decay <= 1.0) {
+            return Err(RookError::validation(format!(
+                "{}.decay must be between 0 and 1, got {}",
+                label, decay
+            )));
+        }
+        scheduler = scheduler.with_decay(decay);
+    }
+    if !(scheduler.decay() > 0.0 && scheduler.decay() <= 1.0) {
+        return Err(RookError::validation(format!(
+            "{}.weights decay (last weight) must be between 0 and 1, got {}",
+            label,
+            scheduler.decay()
+        )));
+    }
+    Ok(scheduler.with_desired_retention(desired_retention))
+}
+
+/// Strength measure search scores are weighted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecaySource {
+    /// FSRS probability of recall at search time.
+    #[default]
+    Retrievability,
+    /// Dual-strength retrieval strength as of the last review.
+    RetrievalStrength,
+}
+
+/// Decay-weighted ranking: similarity scores are multiplied by memory
+/// strength so stale memories rank lower.
+///
+/// A memory with strength `s` scores `score * (1 - weight + weight * s)`.
+/// Needs `cognitive.enabled`; memories without FSRS state keep their score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecayRankingConfig {
+    /// Whether search weights scores by strength (default: false).
+    pub enabled: bool,
+    /// How much strength counts, from 0 (not at all) to 1 (score scaled by
+    /// strength alone) (default: 0.5).
+    pub weight: f32,
+    /// Strength measure used (default: retrievability).
+    pub source: DecaySource,
+    /// Leave key memories' scores untouched (default: true).
+    pub exempt_key: bool,
+}
+
+impl Default for DecayRankingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weight: 0.5,
+            source: DecaySource::default(),
+            exempt_key: true,
+        }
+    }
+}
+
+impl DecayRankingConfig {
+    /// Weight a similarity score by a strength between 0 and 1.
+    pub fn apply(&self, score: f32, strength: f32) -> f32 {
+        let weight = self.weight.clamp(0.0, 1.0);
+        score * (1.0 - weight + weight * strength.clamp(0.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay_ranking_blends_strength() {
+        let config = DecayRankingConfig::default();
+        assert_eq!(config.apply(0.8, 1.0), 0.8);
+        assert_eq!(config.apply(0.8, 0.0), 0.4);
+        assert!((config.apply(0.8, 0.5) - 0.6).abs() < 1e-6);
+
+        let config = DecayRankingConfig {
+            weight: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(config.apply(0.8, 0.25), 0.2);
+
+        let config = DecayRankingConfig {
+            weight: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(config.apply(0.8, 0.0), 0.8);
+    }
+
+    #[test]
+    fn test_fsrs_config_category_overrides() {
+        let mut config = FsrsConfig::default();
+        config.categories.insert(
+            "personal_details".to_string(),
+            FsrsParams {
+                decay: Some(0.1),
+                desired_retention: Some(0.8),
+                ..Default::default()
+            },
+        );
+        config.validate().unwrap();
+
+        let default = config.scheduler().unwrap();
+        assert_eq!(default.decay(), fsrs::FSRS6_DEFAULT_DECAY);
+        assert_eq!(default.desired_retention(), 0.9);
+
+        let schedulers = config.category_schedulers().unwrap();
+        let personal = &schedulers["personal_details"];
+        assert_eq!(personal.decay(), 0.1);
+        assert_eq!(personal.desired_retention(), 0.8);
+        // Past its stability, a memory fades more slowly with less decay.
+        assert!(personal.retrievability(2.0, 20.0) > default.retrievability(2.0, 20.0));
+    }
+
+    #[test]
+    fn test_fsrs_config_weights() {
+        let mut weights = fsrs::DEFAULT_PARAMETERS.to_vec();
+        weights[2] = 10.0;
+        weights[20] = 0.3;
+        let config = FsrsConfig {
+            weights: Some(weights),
+            ..Default::default()
+        };
+        let scheduler = config.scheduler().unwrap();
+        assert_eq!(scheduler.decay(), 0.3);
+        let state = scheduler.initial_state(crate::types::Grade::Good);
+        assert_eq!(state.stability, 10.0);
+        assert!(state.difficulty >= 1.0 && state.difficulty <= 10.0);
+    }
+
+    #[test]
+    fn test_fsrs_config_rejects_invalid() {
+        let config = FsrsConfig {
+            weights: Some(vec![1.0; 4]),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = FsrsConfig {
+            desired_retention: 1.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let mut config = FsrsConfig::default();
+        config.categories.insert(
+            "personal_details".to_string(),
+            FsrsParams {
+                decay: Some(0.0),
+                ..Default::default()
+            },
+        );
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("personal_details"));
+    }
+}
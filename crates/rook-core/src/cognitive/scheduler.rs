@@ -17,6 +17,8 @@ pub struct FsrsScheduler {
     initial_stability: [f32; 4],
     /// Initial difficulty values for each grade.
     initial_difficulty: [f32; 4],
+    /// Retrievability below which a memory is due for review.
+    desired_retention: f32,
 }
 
 impl FsrsScheduler {
@@ -41,6 +43,7 @@ impl FsrsScheduler {
                 5.0, // Good: average
                 3.5, // Easy: below average
             ],
+            desired_retention: 0.9,
         }
     }
 
@@ -50,9 +53,22 @@ impl FsrsScheduler {
             decay,
             initial_stability,
             initial_difficulty,
+            desired_retention: 0.9,
         }
     }
 
+    /// Set the forgetting curve decay exponent.
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Set the retrievability below which memories are due for review.
+    pub fn with_desired_retention(mut self, desired_retention: f32) -> Self {
+        self.desired_retention = desired_retention;
+        self
+    }
+
     /// Calculate current retrievability (probability of recall).
     ///
     /// Uses the FSRS power forgetting curve formula:
@@ -130,6 +146,17 @@ impl FsrsScheduler {
         self.decay
     }
 
+    /// Get the desired retention.
+    pub fn desired_retention(&self) -> f32 {
+        self.desired_retention
+    }
+
+    /// Check whether a memory's retrievability has fallen below the desired
+    /// retention.
+    pub fn is_due(&self, state: &FsrsState, now: DateTime<Utc>) -> bool {
+        self.current_retrievability(state, now) < self.desired_retention
+    }
+
     /// Process a review and update memory state based on grade.
     ///
     /// This is the core FSRS algorithm: based on the grade (quality of recall),
@@ -8,9 +8,16 @@
 
 use crate::consolidation::{BehavioralTagger, ConsolidationPhase, NoveltyResult, SynapticTag};
 use crate::error::{RookError, RookResult};
+use crate::retrieval::{
+    spread_activation_by_id, ActivatedMemory, ActivationGraph, FsrsMemoryState, FsrsStateProvider,
+    SpreadingConfig,
+};
 use crate::types::{ArchivalConfig, FsrsState};
 use chrono::{DateTime, Utc};
+use petgraph::graph::DiGraph;
 use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
@@ -86,6 +93,24 @@ impl CognitiveStore {
 
             CREATE INDEX IF NOT EXISTS idx_synaptic_tags_tagged_at ON synaptic_tags(tagged_at);
             CREATE INDEX IF NOT EXISTS idx_synaptic_tags_prp_available ON synaptic_tags(prp_available);
+
+            -- Consolidated memories awaiting promotion to semantic memory
+            CREATE TABLE IF NOT EXISTS semantic_promotions (
+                memory_id TEXT PRIMARY KEY,
+                queued_at TEXT NOT NULL
+            );
+
+            -- Memory pairs activated together during replay (memory_a < memory_b)
+            CREATE TABLE IF NOT EXISTS coactivations (
+                memory_a TEXT NOT NULL,
+                memory_b TEXT NOT NULL,
+                weight REAL NOT NULL,
+                count INTEGER NOT NULL DEFAULT 1,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (memory_a, memory_b)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_coactivations_memory_b ON coactivations(memory_b);
             ",
         )?;
 
@@ -123,6 +148,20 @@ impl CognitiveStore {
             )?;
         }
 
+        // When a memory was last replayed
+        let has_replayed: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('fsrs_states') WHERE name = 'replayed_at'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .unwrap_or(0)
+            > 0;
+
+        if !has_replayed {
+            conn.execute("ALTER TABLE fsrs_states ADD COLUMN replayed_at TEXT", [])?;
+        }
+
         Ok(())
     }
 
@@ -174,7 +213,8 @@ impl CognitiveStore {
 
     /// Save the FSRS state for a memory.
     ///
-    /// Creates or updates the state for the given memory ID.
+    /// Creates or updates the state for the given memory ID. An existing
+    /// memory keeps its creation time, consolidation phase and dual strength.
     pub fn save_state(
         &self,
         memory_id: &str,
@@ -189,11 +229,17 @@ impl CognitiveStore {
         let last_review_str = state.last_review.map(|dt| dt.to_rfc3339());
 
         conn.execute(
-            "INSERT OR REPLACE INTO fsrs_states
+            "INSERT INTO fsrs_states
              (memory_id, stability, difficulty, last_review, reps, lapses, is_key, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7,
-                     COALESCE((SELECT created_at FROM fsrs_states WHERE memory_id = ?1), ?8),
-                     ?9)",
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(memory_id) DO UPDATE SET
+                 stability = excluded.stability,
+                 difficulty = excluded.difficulty,
+                 last_review = excluded.last_review,
+                 reps = excluded.reps,
+                 lapses = excluded.lapses,
+                 is_key = excluded.is_key,
+                 updated_at = excluded.updated_at",
             params![
                 memory_id,
                 state.stability,
@@ -218,6 +264,14 @@ impl CognitiveStore {
             "DELETE FROM fsrs_states WHERE memory_id = ?1",
             params![memory_id],
         )?;
+        conn.execute(
+            "DELETE FROM semantic_promotions WHERE memory_id = ?1",
+            params![memory_id],
+        )?;
+        conn.execute(
+            "DELETE FROM coactivations WHERE memory_a = ?1 OR memory_b = ?1",
+            params![memory_id],
+        )?;
 
         Ok(deleted > 0)
     }
@@ -667,6 +721,165 @@ impl CognitiveStore {
         Ok(counts)
     }
 
+    // =========================================================================
+    // Semantic Promotion Methods
+    // =========================================================================
+
+    /// Queue a consolidated memory for promotion to semantic memory.
+    ///
+    /// Queuing a memory already in the queue keeps its original position.
+    pub fn queue_promotion(&self, memory_id: &str) -> RookResult<()> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        conn.execute(
+            "INSERT OR IGNORE INTO semantic_promotions (memory_id, queued_at) VALUES (?1, ?2)",
+            params![memory_id, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get memories queued for promotion, oldest first.
+    pub fn pending_promotions(&self) -> RookResult<Vec<String>> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT memory_id FROM semantic_promotions ORDER BY queued_at ASC, memory_id ASC",
+        )?;
+
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// Remove a memory from the promotion queue.
+    pub fn remove_promotion(&self, memory_id: &str) -> RookResult<bool> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM semantic_promotions WHERE memory_id = ?1",
+            params![memory_id],
+        )?;
+
+        Ok(deleted > 0)
+    }
+
+    // =========================================================================
+    // Replay Methods
+    // =========================================================================
+
+    /// Get memories created since `since` that haven't been replayed yet,
+    /// oldest first.
+    pub fn replay_candidates(&self, since: DateTime<Utc>, limit: usize) -> RookResult<Vec<String>> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT memory_id FROM fsrs_states
+             WHERE created_at >= ?1 AND replayed_at IS NULL
+             ORDER BY created_at ASC
+             LIMIT ?2",
+        )?;
+
+        let ids = stmt
+            .query_map(params![since.to_rfc3339(), limit], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// Record that a memory was replayed.
+    pub fn mark_replayed(&self, memory_id: &str, at: DateTime<Utc>) -> RookResult<bool> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let updated = conn.execute(
+            "UPDATE fsrs_states SET replayed_at = ?1 WHERE memory_id = ?2",
+            params![at.to_rfc3339(), memory_id],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    /// Record that two memories were activated together, with the
+    /// similarity that linked them.
+    ///
+    /// Repeated co-activations raise the pair's count and keep the highest
+    /// weight.
+    pub fn record_coactivation(&self, memory_id: &str, related_id: &str, weight: f32) -> RookResult<()> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let (a, b) = if memory_id < related_id {
+            (memory_id, related_id)
+        } else {
+            (related_id, memory_id)
+        };
+        conn.execute(
+            "INSERT INTO coactivations (memory_a, memory_b, weight, count, updated_at)
+             VALUES (?1, ?2, ?3, 1, ?4)
+             ON CONFLICT(memory_a, memory_b) DO UPDATE SET
+                 weight = MAX(weight, excluded.weight),
+                 count = count + 1,
+                 updated_at = excluded.updated_at",
+            params![a, b, weight.clamp(0.0, 1.0), Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Total number of co-activations a memory took part in.
+    pub fn coactivation_count(&self, memory_id: &str) -> RookResult<usize> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let count: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(count), 0) FROM coactivations WHERE memory_a = ?1 OR memory_b = ?1",
+            params![memory_id],
+            |row| row.get(0),
+        )?;
+
+        Ok(count as usize)
+    }
+
+    /// Get the memories a memory was activated together with, and the
+    /// weight of each link.
+    pub fn coactivations(&self, memory_id: &str) -> RookResult<Vec<(String, f32)>> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT CASE WHEN memory_a = ?1 THEN memory_b ELSE memory_a END, weight
+             FROM coactivations
+             WHERE memory_a = ?1 OR memory_b = ?1
+             ORDER BY weight DESC",
+        )?;
+
+        let links = stmt
+            .query_map(params![memory_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(String, f32)>, _>>()?;
+
+        Ok(links)
+    }
+
+    /// Build the co-activation graph, with an edge each way per pair.
+    fn coactivation_graph(&self) -> RookResult<(DiGraph<String, f32>, HashMap<String, petgraph::graph::NodeIndex>)> {
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut stmt = conn.prepare("SELECT memory_a, memory_b, weight FROM coactivations")?;
+        let edges = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<(String, String, f32)>, _>>()?;
+
+        let mut graph = DiGraph::new();
+        let mut index = HashMap::new();
+        for (a, b, weight) in edges {
+            let a = *index.entry(a.clone()).or_insert_with(|| graph.add_node(a));
+            let b = *index.entry(b.clone()).or_insert_with(|| graph.add_node(b));
+            graph.add_edge(a, b, weight);
+            graph.add_edge(b, a, weight);
+        }
+
+        Ok((graph, index))
+    }
+
     // =========================================================================
     // Behavioral Tagging Integration
     // =========================================================================
@@ -776,6 +989,149 @@ impl CognitiveStore {
 
         Ok(results)
     }
+
+    // =========================================================================
+    // Snapshot and Restore
+    // =========================================================================
+
+    /// Get the full cognitive state of a memory for export.
+    ///
+    /// Returns None if the memory has neither an FSRS state nor a synaptic tag.
+    pub fn get_snapshot(&self, memory_id: &str) -> RookResult<Option<CognitiveSnapshot>> {
+        let tag = self.get_synaptic_tag(memory_id)?;
+        let conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+
+        let state = conn
+            .query_row(
+                "SELECT stability, difficulty, last_review, reps, lapses, is_key, consolidation_phase,
+                        storage_strength, retrieval_strength, created_at
+                 FROM fsrs_states WHERE memory_id = ?1",
+                params![memory_id],
+                |row| {
+                    let last_review: Option<String> = row.get(2)?;
+                    let is_key: i32 = row.get(5)?;
+                    let phase: String = row.get(6)?;
+                    let created_at: String = row.get(9)?;
+                    Ok(CognitiveStateSnapshot {
+                        state: FsrsState {
+                            stability: row.get(0)?,
+                            difficulty: row.get(1)?,
+                            last_review: last_review.and_then(|s| parse_timestamp(&s)),
+                            reps: row.get(3)?,
+                            lapses: row.get(4)?,
+                        },
+                        is_key: is_key != 0,
+                        consolidation_phase: ConsolidationPhase::from_str(&phase)
+                            .unwrap_or(ConsolidationPhase::Immediate),
+                        dual_strength: crate::types::DualStrength {
+                            storage_strength: row.get::<_, f32>(7).unwrap_or(0.5),
+                            retrieval_strength: row.get::<_, f32>(8).unwrap_or(1.0),
+                        },
+                        created_at: parse_timestamp(&created_at).unwrap_or_else(Utc::now),
+                    })
+                },
+            )
+            .optional()?;
+
+        if state.is_none() && tag.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(CognitiveSnapshot {
+            memory_id: memory_id.to_string(),
+            state,
+            synaptic_tag: tag,
+        }))
+    }
+
+    /// Restore exported cognitive state in a single transaction.
+    ///
+    /// Existing state for the same memories is replaced. Either every
+    /// snapshot is written or none is.
+    pub fn restore_snapshots(&self, snapshots: &[CognitiveSnapshot]) -> RookResult<usize> {
+        let mut conn = self.conn.lock().map_err(|e| RookError::database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+
+        for snapshot in snapshots {
+            if let Some(ref s) = snapshot.state {
+                tx.execute(
+                    "INSERT OR REPLACE INTO fsrs_states
+                     (memory_id, stability, difficulty, last_review, reps, lapses, is_key,
+                      consolidation_phase, storage_strength, retrieval_strength, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    params![
+                        snapshot.memory_id,
+                        s.state.stability,
+                        s.state.difficulty,
+                        s.state.last_review.map(|dt| dt.to_rfc3339()),
+                        s.state.reps,
+                        s.state.lapses,
+                        if s.is_key { 1 } else { 0 },
+                        s.consolidation_phase.to_string(),
+                        s.dual_strength.storage_strength,
+                        s.dual_strength.retrieval_strength,
+                        s.created_at.to_rfc3339(),
+                        now,
+                    ],
+                )?;
+            }
+            if let Some(ref tag) = snapshot.synaptic_tag {
+                tx.execute(
+                    "INSERT OR REPLACE INTO synaptic_tags
+                     (memory_id, initial_strength, tau, tagged_at, prp_available, prp_available_at,
+                      created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+                    params![
+                        snapshot.memory_id,
+                        tag.initial_strength,
+                        tag.tau,
+                        tag.tagged_at.to_rfc3339(),
+                        if tag.prp_available { 1 } else { 0 },
+                        tag.prp_available_at.map(|dt| dt.to_rfc3339()),
+                        now,
+                    ],
+                )?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(snapshots.len())
+    }
+}
+
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+/// Cognitive state of one memory, as carried in exports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CognitiveSnapshot {
+    /// Memory ID.
+    pub memory_id: String,
+    /// FSRS scheduling state, dual strength and consolidation phase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<CognitiveStateSnapshot>,
+    /// Synaptic tag for STC consolidation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synaptic_tag: Option<SynapticTag>,
+}
+
+/// The `fsrs_states` row of a [`CognitiveSnapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CognitiveStateSnapshot {
+    /// FSRS scheduling state.
+    pub state: FsrsState,
+    /// Whether this is a key memory.
+    #[serde(default)]
+    pub is_key: bool,
+    /// Consolidation phase.
+    pub consolidation_phase: ConsolidationPhase,
+    /// Dual-strength model state.
+    pub dual_strength: crate::types::DualStrength,
+    /// When the state was first saved.
+    pub created_at: DateTime<Utc>,
 }
 
 /// A candidate for archival with its state information.
@@ -789,6 +1145,47 @@ pub struct ArchivalCandidate {
     pub created_at: DateTime<Utc>,
 }
 
+/// Spreading activation over the links recorded by memory replay.
+impl ActivationGraph for CognitiveStore {
+    fn spread_from_seeds(
+        &self,
+        seeds: &[(String, f32)],
+        config: &SpreadingConfig,
+    ) -> Vec<ActivatedMemory> {
+        match self.coactivation_graph() {
+            Ok((graph, index)) => spread_activation_by_id(
+                &graph,
+                &index,
+                seeds,
+                config,
+                |node| node.clone(),
+                |weight| *weight,
+            ),
+            Err(e) => {
+                tracing::warn!("Failed to load co-activation graph: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// FSRS state for retrieval scoring.
+impl FsrsStateProvider for CognitiveStore {
+    fn get_state(&self, id: &str) -> Option<FsrsMemoryState> {
+        match CognitiveStore::get_state(self, id) {
+            Ok(state) => state.map(|(state, _, _)| FsrsMemoryState {
+                stability: state.stability,
+                difficulty: state.difficulty,
+                last_review: state.last_review,
+            }),
+            Err(e) => {
+                tracing::warn!("Failed to read FSRS state of memory {}: {}", id, e);
+                None
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -804,6 +1201,153 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_snapshot_restore_roundtrip() {
+        let source = CognitiveStore::in_memory().unwrap();
+        source
+            .save_state("mem1", &create_test_state(12.0, 2), true, None)
+            .unwrap();
+        source
+            .update_consolidation_phase("mem1", ConsolidationPhase::Late)
+            .unwrap();
+        source
+            .save_dual_strength(
+                "mem1",
+                &crate::types::DualStrength {
+                    storage_strength: 2.5,
+                    retrieval_strength: 0.4,
+                },
+            )
+            .unwrap();
+        source
+            .save_synaptic_tag(&SynapticTag::with_tau("mem1".to_string(), 0.8, 60.0))
+            .unwrap();
+        source
+            .save_synaptic_tag(&SynapticTag::with_tau("mem2".to_string(), 0.5, 30.0))
+            .unwrap();
+
+        let snapshots: Vec<_> = ["mem1", "mem2", "missing"]
+            .iter()
+            .filter_map(|id| source.get_snapshot(id).unwrap())
+            .collect();
+        assert_eq!(snapshots.len(), 2);
+        assert!(snapshots[1].state.is_none());
+
+        let target = CognitiveStore::in_memory().unwrap();
+        assert_eq!(target.restore_snapshots(&snapshots).unwrap(), 2);
+
+        let (state, is_key, _) = target.get_state("mem1").unwrap().unwrap();
+        assert!((state.stability - 12.0).abs() < 0.001);
+        assert!(is_key);
+        assert_eq!(
+            target.get_consolidation_phase("mem1").unwrap(),
+            Some(ConsolidationPhase::Late)
+        );
+        let dual = target.get_dual_strength("mem1").unwrap().unwrap();
+        assert!((dual.storage_strength - 2.5).abs() < 0.001);
+        assert!(target.get_synaptic_tag("mem2").unwrap().is_some());
+        assert!(target.get_state("mem2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_save_state_keeps_phase_and_strength() {
+        let store = CognitiveStore::in_memory().unwrap();
+        store
+            .save_state("mem1", &create_test_state(5.0, 1), false, None)
+            .unwrap();
+        store
+            .update_consolidation_phase("mem1", ConsolidationPhase::Late)
+            .unwrap();
+        store
+            .save_dual_strength(
+                "mem1",
+                &crate::types::DualStrength {
+                    storage_strength: 2.0,
+                    retrieval_strength: 0.5,
+                },
+            )
+            .unwrap();
+
+        store
+            .save_state("mem1", &create_test_state(8.0, 0), true, None)
+            .unwrap();
+
+        let (state, is_key, _) = store.get_state("mem1").unwrap().unwrap();
+        assert!((state.stability - 8.0).abs() < 0.001);
+        assert!(is_key);
+        assert_eq!(
+            store.get_consolidation_phase("mem1").unwrap(),
+            Some(ConsolidationPhase::Late)
+        );
+        let dual = store.get_dual_strength("mem1").unwrap().unwrap();
+        assert!((dual.storage_strength - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_promotion_queue() {
+        let store = CognitiveStore::in_memory().unwrap();
+        store
+            .save_state("mem1", &create_test_state(5.0, 1), false, None)
+            .unwrap();
+        store.queue_promotion("mem1").unwrap();
+        store.queue_promotion("mem2").unwrap();
+        store.queue_promotion("mem1").unwrap();
+        assert_eq!(store.pending_promotions().unwrap().len(), 2);
+
+        assert!(store.remove_promotion("mem2").unwrap());
+        assert!(!store.remove_promotion("mem2").unwrap());
+
+        // Deleting a memory's state drops it from the queue
+        store.delete_state("mem1").unwrap();
+        assert!(store.pending_promotions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replay_candidates_and_coactivations() {
+        let store = CognitiveStore::in_memory().unwrap();
+        let now = Utc::now();
+        store
+            .save_state("old", &create_test_state(5.0, 10), false, Some(now - Duration::days(10)))
+            .unwrap();
+        store
+            .save_state("new", &create_test_state(1.0, 0), false, Some(now))
+            .unwrap();
+
+        let since = now - Duration::hours(1);
+        assert_eq!(store.replay_candidates(since, 10).unwrap(), vec!["new".to_string()]);
+        store.mark_replayed("new", now).unwrap();
+        assert!(store.replay_candidates(since, 10).unwrap().is_empty());
+
+        store.record_coactivation("new", "old", 0.6).unwrap();
+        store.record_coactivation("old", "new", 0.8).unwrap();
+        store.record_coactivation("old", "other", 0.5).unwrap();
+        assert_eq!(store.coactivation_count("old").unwrap(), 3);
+        assert_eq!(store.coactivation_count("new").unwrap(), 2);
+        let links = store.coactivations("old").unwrap();
+        assert_eq!(links[0].0, "new");
+        assert!((links[0].1 - 0.8).abs() < 0.001);
+
+        // Activation spreads along co-activation links
+        let activated = store.spread_from_seeds(&[("new".to_string(), 1.0)], &SpreadingConfig::default());
+        assert!(activated.iter().any(|a| a.memory_id == "old"));
+
+        store.delete_state("old").unwrap();
+        assert!(store.coactivations("new").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fsrs_state_provider() {
+        let store = CognitiveStore::in_memory().unwrap();
+        store
+            .save_state("mem1", &create_test_state(12.0, 2), false, None)
+            .unwrap();
+
+        let state = FsrsStateProvider::get_state(&store, "mem1").unwrap();
+        assert_eq!(state.stability, 12.0);
+        assert!(state.last_review.is_some());
+        assert!(FsrsStateProvider::get_state(&store, "missing").is_none());
+    }
+
     #[test]
     fn test_store_creation() {
         let store = CognitiveStore::in_memory().unwrap();
@@ -1,13 +1,21 @@
 //! Configuration system for rook.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::cognitive::CognitiveConfig;
+use crate::encryption::{BlindIndexConfig, ContentEncryptionConfig};
+use crate::memory::{
+    ArchiveConfig, ClassificationCacheConfig, GlobalKnowledgeConfig, ReplayConfig, RunConfig,
+    SemanticPromotionConfig,
+};
+use crate::sync::SyncConfig;
+use crate::versioning::VersioningConfig;
 use crate::traits::{
-    EmbedderConfig, EmbedderProvider, GraphStoreConfig, LlmConfig, RerankerConfig,
-    VectorStoreConfig, VectorStoreProvider,
+    EmbedderConfig, EmbedderProvider, GraphSearchConfig, GraphStoreConfig, GraphStoreProvider,
+    LlmConfig, RerankerConfig, VectorStoreConfig, VectorStoreProvider,
 };
-use crate::types::{CategoryConfig, KeyMemoryConfig};
+use crate::types::{CategoryConfig, KeyMemoryConfig, MetadataSchema};
 
 /// LLM provider type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -81,6 +89,8 @@ pub struct MemoryConfig {
     /// Graph store configuration (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graph_store: Option<GraphStoreConfig>,
+    /// Graph-aware search settings (used when a graph store is configured).
+    pub graph_search: GraphSearchConfig,
     /// Reranker configuration (optional).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reranker: Option<RerankerConfig>,
@@ -88,8 +98,28 @@ pub struct MemoryConfig {
     pub category: CategoryConfig,
     /// Key memory handling configuration.
     pub key_memory: KeyMemoryConfig,
+    /// Reuse of classifications for near-identical memories.
+    pub classification_cache: ClassificationCacheConfig,
+    /// Global (cross-user) knowledge base settings.
+    pub global_knowledge: GlobalKnowledgeConfig,
+    /// End-of-run summarization, promotion and cleanup.
+    pub runs: RunConfig,
+    /// Declared metadata field types, for ordering listed memories.
+    pub metadata_schema: MetadataSchema,
     /// Path to history database.
     pub history_db_path: PathBuf,
+    /// Replicated sync with other rook instances.
+    pub sync: SyncConfig,
+    /// Memory version snapshots for history and rollback.
+    pub versioning: VersioningConfig,
+    /// FSRS state of memories, tracked on add and access.
+    pub cognitive: CognitiveConfig,
+    /// Cold-storage archive for decayed memories.
+    pub archive: ArchiveConfig,
+    /// Restatement of consolidated memories as semantic memories.
+    pub semantic_promotion: SemanticPromotionConfig,
+    /// Replay of recent memories against older related ones.
+    pub replay: ReplayConfig,
     /// API version.
     pub version: String,
     /// Custom fact extraction prompt.
@@ -98,6 +128,12 @@ pub struct MemoryConfig {
     /// Custom update memory prompt.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_update_memory_prompt: Option<String>,
+    /// Blind indexing of filterable fields (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blind_index: Option<BlindIndexConfig>,
+    /// Encryption of memory content at rest (optional).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encryption: Option<ContentEncryptionConfig>,
 }
 
 impl Default for MemoryConfig {
@@ -111,13 +147,26 @@ impl Default for MemoryConfig {
             llm: LlmProviderConfig::default(),
             embedder: EmbedderProviderConfig::default(),
             graph_store: None,
+            graph_search: GraphSearchConfig::default(),
             reranker: None,
             category: CategoryConfig::default(),
             key_memory: KeyMemoryConfig::default(),
+            classification_cache: ClassificationCacheConfig::default(),
+            global_knowledge: GlobalKnowledgeConfig::default(),
+            runs: RunConfig::default(),
+            metadata_schema: MetadataSchema::default(),
             history_db_path: rook_dir.join("history.db"),
+            sync: SyncConfig::default(),
+            versioning: VersioningConfig::default(),
+            cognitive: CognitiveConfig::default(),
+            archive: ArchiveConfig::default(),
+            semantic_promotion: SemanticPromotionConfig::default(),
+            replay: ReplayConfig::default(),
             version: "v1.1".to_string(),
             custom_fact_extraction_prompt: None,
             custom_update_memory_prompt: None,
+            blind_index: None,
+            content_encryption: None,
         }
     }
 }
@@ -174,10 +223,86 @@ impl MemoryConfig {
         config
     }
 
+    /// Vector store settings for the archive collection, when
+    /// `archive.enabled`. Create the store with the same factory as the
+    /// memory store and pass it to `Memory::with_archive_store`.
+    pub fn archive_vector_store(&self) -> Option<VectorStoreConfig> {
+        self.archive
+            .enabled
+            .then(|| self.archive.vector_store(&self.vector_store))
+    }
+
     /// Build configuration using builder pattern.
     pub fn builder() -> MemoryConfigBuilder {
         MemoryConfigBuilder::default()
     }
+
+    /// Configuration for an isolated tenant (organization).
+    ///
+    /// The tenant gets its own vector store collection (`<collection>_<org>`)
+    /// and its own history, version, sync and embedded graph databases under
+    /// `tenants/<org>/` next to the originals. In-memory databases stay in
+    /// memory. Remote graph stores are shared and only scoped by session IDs.
+    pub fn for_tenant(&self, org_id: &str) -> crate::error::RookResult<Self> {
+        validate_tenant_id(org_id)?;
+
+        let mut config = self.clone();
+        config.vector_store.collection_name = format!(
+            "{}_{}",
+            self.vector_store.collection_name,
+            org_id.replace('-', "_")
+        );
+        config.history_db_path = tenant_path(&self.history_db_path, org_id);
+        if let Some(ref path) = self.versioning.db_path {
+            config.versioning.db_path = Some(tenant_path(path, org_id));
+        }
+        if let Some(ref path) = self.sync.db_path {
+            config.sync.db_path = Some(tenant_path(path, org_id));
+        }
+        if let Some(ref path) = self.cognitive.db_path {
+            config.cognitive.db_path = Some(tenant_path(path, org_id));
+        }
+        if let Some(ref name) = self.archive.collection_name {
+            config.archive.collection_name = Some(format!("{}_{}", name, org_id.replace('-', "_")));
+        }
+        if let Some(ref mut graph) = config.graph_store {
+            if graph.provider == GraphStoreProvider::Embedded {
+                graph.url = tenant_path(Path::new(&graph.url), org_id)
+                    .to_string_lossy()
+                    .into_owned();
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Check a tenant ID is safe to use in collection names and paths.
+pub fn validate_tenant_id(org_id: &str) -> crate::error::RookResult<()> {
+    let valid = !org_id.is_empty()
+        && org_id.len() <= 64
+        && org_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(crate::error::RookError::validation(format!(
+            "Invalid organization ID '{}': use 1-64 letters, digits, '_' or '-'",
+            org_id
+        )))
+    }
+}
+
+/// `path` moved into `tenants/<org>/` next to it, unless it is in memory.
+fn tenant_path(path: &Path, org_id: &str) -> PathBuf {
+    if path.as_os_str() == ":memory:" {
+        return path.to_path_buf();
+    }
+    let parent = path.parent().unwrap_or_else(|| Path::new(""));
+    match path.file_name() {
+        Some(name) => parent.join("tenants").join(org_id).join(name),
+        None => path.join("tenants").join(org_id),
+    }
 }
 
 /// Builder for MemoryConfig.
@@ -247,8 +372,105 @@ impl MemoryConfigBuilder {
         self
     }
 
+    /// Set classification cache configuration.
+    pub fn classification_cache(mut self, config: ClassificationCacheConfig) -> Self {
+        self.config.classification_cache = config;
+        self
+    }
+
+    /// Set blind index configuration.
+    pub fn blind_index(mut self, config: BlindIndexConfig) -> Self {
+        self.config.blind_index = Some(config);
+        self
+    }
+
+    /// Set content encryption configuration.
+    pub fn content_encryption(mut self, config: ContentEncryptionConfig) -> Self {
+        self.config.content_encryption = Some(config);
+        self
+    }
+
+    /// Set archive configuration.
+    pub fn archive(mut self, config: ArchiveConfig) -> Self {
+        self.config.archive = config;
+        self
+    }
+
+    /// Set semantic promotion configuration.
+    pub fn semantic_promotion(mut self, config: SemanticPromotionConfig) -> Self {
+        self.config.semantic_promotion = config;
+        self
+    }
+
+    /// Set replay configuration.
+    pub fn replay(mut self, config: ReplayConfig) -> Self {
+        self.config.replay = config;
+        self
+    }
+
+    /// Set cognitive (FSRS) tracking configuration.
+    pub fn cognitive(mut self, config: CognitiveConfig) -> Self {
+        self.config.cognitive = config;
+        self
+    }
+
     /// Build the configuration.
     pub fn build(self) -> MemoryConfig {
         self.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_config_isolates_stores() {
+        let mut base = MemoryConfig {
+            history_db_path: PathBuf::from("/data/rook/history.db"),
+            graph_store: Some(GraphStoreConfig {
+                url: "/data/rook/graph.db".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        base.versioning.db_path = Some(PathBuf::from(":memory:"));
+        base.cognitive.db_path = Some(PathBuf::from("/data/rook/cognitive.db"));
+
+        let tenant = base.for_tenant("acme-corp").unwrap();
+        assert_eq!(
+            tenant.vector_store.collection_name,
+            format!("{}_acme_corp", base.vector_store.collection_name)
+        );
+        assert_eq!(
+            tenant.history_db_path,
+            PathBuf::from("/data/rook/tenants/acme-corp/history.db")
+        );
+        assert_eq!(tenant.versioning.db_path, Some(PathBuf::from(":memory:")));
+        base.archive.enabled = true;
+        assert_eq!(
+            base.for_tenant("acme-corp")
+                .unwrap()
+                .archive_vector_store()
+                .unwrap()
+                .collection_name,
+            format!("{}_acme_corp_archive", base.vector_store.collection_name)
+        );
+        assert_eq!(
+            tenant.cognitive.db_path,
+            Some(PathBuf::from("/data/rook/tenants/acme-corp/cognitive.db"))
+        );
+        assert_eq!(
+            tenant.graph_store.unwrap().url,
+            "/data/rook/tenants/acme-corp/graph.db"
+        );
+    }
+
+    #[test]
+    fn test_tenant_id_validated() {
+        assert!(validate_tenant_id("acme_42").is_ok());
+        assert!(validate_tenant_id("").is_err());
+        assert!(validate_tenant_id("../etc").is_err());
+        assert!(validate_tenant_id("a b").is_err());
+    }
+}
@@ -5,12 +5,15 @@
 //! - Early memories advance to Late after 24 hours
 //! - Late memories advance to Consolidated after 72 hours
 //! - Consolidated memories receive storage_strength boost
+//! - Memories reaching Consolidated are queued for promotion to semantic
+//!   memory, see [`Memory::promote_consolidated`](crate::memory::Memory::promote_consolidated)
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::cognitive::CognitiveStore;
+use crate::consolidation::phases::IMMEDIATE_HOURS;
 use crate::consolidation::ConsolidationPhase;
 use crate::error::RookError;
 
@@ -64,6 +67,9 @@ pub struct ConsolidationResult {
     pub advanced: usize,
     /// Number of memories skipped (not ready for transition)
     pub skipped: usize,
+    /// Number of memories that reached Consolidated and were queued for
+    /// promotion to semantic memory
+    pub queued_for_promotion: usize,
     /// Any errors encountered (non-fatal, processing continued)
     pub errors: Vec<String>,
     /// Timestamp when consolidation started
@@ -159,6 +165,7 @@ impl ConsolidationManager {
             match self.process_immediate_memory(memory_id, now) {
                 Ok(ProcessResult::Consolidated) => result.consolidated += 1,
                 Ok(ProcessResult::Unconsolidated) => result.unconsolidated += 1,
+                Ok(ProcessResult::Advanced) => result.advanced += 1,
                 Ok(ProcessResult::Skipped) => result.skipped += 1,
                 Err(e) => result.errors.push(format!("{}: {}", memory_id, e)),
             }
@@ -176,7 +183,19 @@ impl ConsolidationManager {
         // Get the synaptic tag for this memory
         let tag = match self.store.get_synaptic_tag(memory_id)? {
             Some(t) => t,
-            None => return Ok(ProcessResult::Skipped), // No tag = not a candidate
+            None => {
+                // Never tagged: advance by age alone, without boost or penalty
+                let advanced = self.process_time_based_advancement(
+                    memory_id,
+                    ConsolidationPhase::Immediate,
+                    now,
+                )?;
+                return Ok(if advanced {
+                    ProcessResult::Advanced
+                } else {
+                    ProcessResult::Skipped
+                });
+            }
         };
 
         // Check if tag can consolidate (valid + PRP available)
@@ -235,7 +254,13 @@ impl ConsolidationManager {
 
         for memory_id in memory_ids.iter().take(self.config.batch_size) {
             match self.process_time_based_advancement(memory_id, ConsolidationPhase::Late, now) {
-                Ok(true) => result.advanced += 1,
+                Ok(true) => {
+                    result.advanced += 1;
+                    match self.store.queue_promotion(memory_id) {
+                        Ok(()) => result.queued_for_promotion += 1,
+                        Err(e) => result.errors.push(format!("{}: {}", memory_id, e)),
+                    }
+                }
                 Ok(false) => result.skipped += 1,
                 Err(e) => result.errors.push(format!("{}: {}", memory_id, e)),
             }
@@ -263,6 +288,7 @@ impl ConsolidationManager {
         // Check if can advance based on time (tag/PRP not required for time-based phases)
         // For Early and Late phases, only time matters
         let can_advance = match current_phase {
+            ConsolidationPhase::Immediate => age.num_hours() >= IMMEDIATE_HOURS,
             ConsolidationPhase::Early => age.num_hours() >= 24,
             ConsolidationPhase::Late => age.num_hours() >= 72,
             _ => false,
@@ -303,6 +329,7 @@ impl ConsolidationManager {
 enum ProcessResult {
     Consolidated,
     Unconsolidated,
+    Advanced,
     Skipped,
 }
 
@@ -519,6 +546,42 @@ mod tests {
 
         let phase = store.get_consolidation_phase("mem-1").unwrap().unwrap();
         assert_eq!(phase, ConsolidationPhase::Consolidated);
+
+        // Queued for promotion to semantic memory
+        assert_eq!(result.queued_for_promotion, 1);
+        assert_eq!(store.pending_promotions().unwrap(), vec!["mem-1".to_string()]);
+    }
+
+    #[test]
+    fn test_untagged_memory_advances_by_age() {
+        let store = create_test_store();
+        let now = Utc::now();
+        let state = FsrsState {
+            stability: 10.0,
+            difficulty: 5.0,
+            last_review: Some(now),
+            reps: 1,
+            lapses: 0,
+        };
+        store.save_state("young", &state, false, Some(now - Duration::hours(1))).unwrap();
+        store.save_state("old", &state, false, Some(now - Duration::hours(8))).unwrap();
+
+        let manager = ConsolidationManager::with_defaults(store.clone());
+        let result = manager.consolidate().unwrap();
+
+        assert_eq!(result.advanced, 1);
+        assert_eq!(
+            store.get_consolidation_phase("old").unwrap(),
+            Some(ConsolidationPhase::Early)
+        );
+        assert_eq!(
+            store.get_consolidation_phase("young").unwrap(),
+            Some(ConsolidationPhase::Immediate)
+        );
+
+        // Neither boosted nor penalized
+        let (updated, _, _) = store.get_state("old").unwrap().unwrap();
+        assert_eq!(updated.stability, 10.0);
     }
 
     #[test]
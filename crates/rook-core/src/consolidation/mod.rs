@@ -14,6 +14,10 @@
 //!   - Late (24-72h): Systems consolidation begins
 //!   - Consolidated (72h+): Stable long-term storage
 //!
+//! - **Systems Consolidation**: Memories reaching the Consolidated phase are
+//!   queued for promotion; [`Memory::promote_consolidated`](crate::memory::Memory::promote_consolidated)
+//!   restates them as semantic memories linked to the episodic originals.
+//!
 //! # References
 //!
 //! - Frey & Morris (1997). Synaptic tagging and long-term potentiation.
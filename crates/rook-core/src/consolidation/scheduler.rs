@@ -10,6 +10,11 @@ use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
 use tracing::{debug, error, info};
 
 use super::manager::ConsolidationManager;
+use crate::events::{AlertEvent, EventBus, MemoryLifecycleEvent};
+use crate::observability::metrics;
+
+/// Job name used in failure alerts.
+const JOB_NAME: &str = "consolidation";
 
 /// Configuration for the consolidation scheduler.
 #[derive(Debug, Clone)]
@@ -73,6 +78,7 @@ pub struct ConsolidationScheduler {
     scheduler: JobScheduler,
     manager: Arc<ConsolidationManager>,
     config: SchedulerConfig,
+    event_bus: Option<EventBus>,
 }
 
 impl ConsolidationScheduler {
@@ -89,6 +95,7 @@ impl ConsolidationScheduler {
             scheduler,
             manager,
             config,
+            event_bus: None,
         })
     }
 
@@ -99,6 +106,12 @@ impl ConsolidationScheduler {
         Self::new(manager, SchedulerConfig::default()).await
     }
 
+    /// Report failed runs as `alert.job_failed` events on the bus.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// Get the scheduler configuration.
     pub fn config(&self) -> &SchedulerConfig {
         &self.config
@@ -109,6 +122,7 @@ impl ConsolidationScheduler {
     /// This begins periodic execution of consolidate() at the configured interval.
     pub async fn start(&self) -> Result<(), JobSchedulerError> {
         let manager = self.manager.clone();
+        let event_bus = self.event_bus.clone();
         let interval_secs = self.config.interval_minutes * 60;
 
         // Create the periodic job
@@ -116,9 +130,10 @@ impl ConsolidationScheduler {
             std::time::Duration::from_secs(interval_secs),
             move |_uuid, _lock| {
                 let manager = manager.clone();
+                let event_bus = event_bus.clone();
                 Box::pin(async move {
                     debug!("Starting periodic consolidation");
-                    match manager.consolidate() {
+                    match consolidate_recorded(&manager) {
                         Ok(result) => {
                             info!(
                                 consolidated = result.consolidated,
@@ -131,6 +146,7 @@ impl ConsolidationScheduler {
                         }
                         Err(e) => {
                             error!(error = %e, "Consolidation failed");
+                            report_failure(event_bus.as_ref(), &e);
                         }
                     }
                 })
@@ -142,8 +158,9 @@ impl ConsolidationScheduler {
         // Optionally run immediately
         if self.config.run_on_start {
             debug!("Running initial consolidation on start");
-            if let Err(e) = self.manager.consolidate() {
+            if let Err(e) = consolidate_recorded(&self.manager) {
                 error!(error = %e, "Initial consolidation failed");
+                report_failure(self.event_bus.as_ref(), &e);
             }
         }
 
@@ -171,7 +188,7 @@ impl ConsolidationScheduler {
     pub fn run_now(
         &self,
     ) -> Result<super::manager::ConsolidationResult, crate::error::RookError> {
-        self.manager.consolidate()
+        consolidate_recorded(&self.manager)
     }
 
     /// Get the underlying manager.
@@ -180,6 +197,26 @@ impl ConsolidationScheduler {
     }
 }
 
+/// Run one consolidation pass, recording it in the scheduler metrics.
+fn consolidate_recorded(
+    manager: &ConsolidationManager,
+) -> Result<super::manager::ConsolidationResult, crate::error::RookError> {
+    let start = std::time::Instant::now();
+    let result = manager.consolidate();
+    let changed = result
+        .as_ref()
+        .map(|r| r.consolidated + r.unconsolidated + r.advanced)
+        .unwrap_or(0);
+    metrics::record_scheduler_run(JOB_NAME, result.is_ok(), start.elapsed(), changed);
+    result
+}
+
+fn report_failure(event_bus: Option<&EventBus>, error: &crate::error::RookError) {
+    if let Some(event_bus) = event_bus {
+        event_bus.emit(MemoryLifecycleEvent::Alert(AlertEvent::job_failed(JOB_NAME, error)));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,458 @@
+//! Deterministic HMAC blind indexes for filterable payload fields.
+
+use std::collections::{HashMap, HashSet};
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::error::{ErrorCode, RookError, RookResult};
+use crate::types::{Filter, FilterCondition, FilterOperator};
+
+/// Payload fields that are blind-indexed when no explicit list is configured.
+pub const DEFAULT_INDEXED_FIELDS: &[&str] = &["user_id", "agent_id", "run_id", "category"];
+
+/// Separator between field name and value in the HMAC input, so that
+/// `("user_id", "x")` and `("agent_id", "x")` never produce the same token.
+const FIELD_SEPARATOR: u8 = 0x1f;
+
+fn default_indexed_fields() -> Vec<String> {
+    DEFAULT_INDEXED_FIELDS.iter().map(|f| f.to_string()).collect()
+}
+
+/// A single HMAC key used for blind indexing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlindIndexKey {
+    /// Stable identifier, embedded as a prefix in every token.
+    pub id: String,
+    /// HMAC secret.
+    pub secret: String,
+}
+
+/// Configuration for blind indexing of filterable fields.
+///
+/// Multiple keys may be listed to support rotation: new writes use the active
+/// key, while filters match tokens produced by any listed key until
+/// [`BlindIndexer::rotate_payload`] has re-indexed old records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlindIndexConfig {
+    /// Payload fields to replace with blind index tokens.
+    #[serde(default = "default_indexed_fields")]
+    pub fields: Vec<String>,
+    /// Known keys, oldest first.
+    pub keys: Vec<BlindIndexKey>,
+    /// Key used for new writes. Defaults to the last entry in `keys`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_key_id: Option<String>,
+}
+
+impl BlindIndexConfig {
+    /// Create a config with a single key and the default indexed fields.
+    pub fn with_key(id: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            fields: default_indexed_fields(),
+            keys: vec![BlindIndexKey {
+                id: id.into(),
+                secret: secret.into(),
+            }],
+            active_key_id: None,
+        }
+    }
+
+    /// Add a key and make it the active one.
+    pub fn rotate_to(mut self, id: impl Into<String>, secret: impl Into<String>) -> Self {
+        let id = id.into();
+        self.keys.push(BlindIndexKey {
+            id: id.clone(),
+            secret: secret.into(),
+        });
+        self.active_key_id = Some(id);
+        self
+    }
+
+    /// Override the set of indexed fields.
+    pub fn fields(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Computes blind index tokens and rewrites filters to match them.
+///
+/// Tokens have the form `"<key_id>:<hex hmac>"`.
+#[derive(Debug, Clone)]
+pub struct BlindIndexer {
+    fields: HashSet<String>,
+    keys: Vec<BlindIndexKey>,
+    active: usize,
+}
+
+impl BlindIndexer {
+    /// Build an indexer, validating the key set.
+    pub fn new(config: BlindIndexConfig) -> RookResult<Self> {
+        if config.keys.is_empty() {
+            return Err(RookError::Configuration(
+                "Blind index requires at least one key".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for key in &config.keys {
+            if key.id.is_empty() || key.id.contains(':') {
+                return Err(RookError::Configuration(format!(
+                    "Invalid blind index key id '{}': must be non-empty and contain no ':'",
+                    key.id
+                )));
+            }
+            if key.secret.is_empty() {
+                return Err(RookError::Configuration(format!(
+                    "Blind index key '{}' has an empty secret",
+                    key.id
+                )));
+            }
+            if !seen.insert(key.id.as_str()) {
+                return Err(RookError::Configuration(format!(
+                    "Duplicate blind index key id '{}'",
+                    key.id
+                )));
+            }
+        }
+
+        let active = match &config.active_key_id {
+            Some(id) => config
+                .keys
+                .iter()
+                .position(|k| &k.id == id)
+                .ok_or_else(|| {
+                    RookError::Configuration(format!("Active blind index key '{}' not found", id))
+                })?,
+            None => config.keys.len() - 1,
+        };
+
+        Ok(Self {
+            fields: config.fields.into_iter().collect(),
+            keys: config.keys,
+            active,
+        })
+    }
+
+    /// Whether the given payload field is blind-indexed.
+    pub fn is_indexed(&self, field: &str) -> bool {
+        self.fields.contains(field)
+    }
+
+    /// Identifier of the key used for new writes.
+    pub fn active_key_id(&self) -> &str {
+        &self.keys[self.active].id
+    }
+
+    /// Token for a field value under the active key.
+    pub fn token(&self, field: &str, value: &serde_json::Value) -> String {
+        Self::compute(&self.keys[self.active], field, value)
+    }
+
+    /// Tokens for a field value under every known key, active key first.
+    pub fn tokens(&self, field: &str, value: &serde_json::Value) -> Vec<serde_json::Value> {
+        let mut tokens = vec![serde_json::Value::String(self.token(field, value))];
+        tokens.extend(
+            self.keys
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != self.active)
+                .map(|(_, key)| serde_json::Value::String(Self::compute(key, field, value))),
+        );
+        tokens
+    }
+
+    /// Returns the key id a value was tokenized with, if it is a token
+    /// produced by one of the known keys.
+    pub fn token_key_id<'a>(&self, value: &'a serde_json::Value) -> Option<&'a str> {
+        let (key_id, digest) = value.as_str()?.split_once(':')?;
+        let known = self.keys.iter().any(|k| k.id == key_id);
+        let well_formed = digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit());
+        (known && well_formed).then_some(key_id)
+    }
+
+    /// Replace indexed fields in a payload with tokens under the active key.
+    ///
+    /// Null values and values that are already tokens are left untouched.
+    pub fn index_payload(&self, payload: &mut HashMap<String, serde_json::Value>) {
+        for field in &self.fields {
+            if let Some(value) = payload.get_mut(field) {
+                if value.is_null() || self.token_key_id(value).is_some() {
+                    continue;
+                }
+                *value = serde_json::Value::String(self.token(field, value));
+            }
+        }
+    }
+
+    /// Rewrite a filter so that conditions on indexed fields compare tokens.
+    ///
+    /// Only equality-style operators can be evaluated against tokens; range
+    /// and substring operators on indexed fields are rejected.
+    pub fn rewrite_filter(&self, filter: &Filter) -> RookResult<Filter> {
+        match filter {
+            Filter::Condition(cond) if self.is_indexed(&cond.field) => {
+                Ok(Filter::Condition(self.rewrite_condition(cond)?))
+            }
+            Filter::Condition(_) => Ok(filter.clone()),
+            Filter::And(filters) => Ok(Filter::And(
+                filters
+                    .iter()
+                    .map(|f| self.rewrite_filter(f))
+                    .collect::<RookResult<_>>()?,
+            )),
+            Filter::Or(filters) => Ok(Filter::Or(
+                filters
+                    .iter()
+                    .map(|f| self.rewrite_filter(f))
+                    .collect::<RookResult<_>>()?,
+            )),
+            Filter::Not(inner) => Ok(Filter::Not(Box::new(self.rewrite_filter(inner)?))),
+        }
+    }
+
+    /// Re-index a payload under the active key.
+    ///
+    /// Blind indexes are one-way, so the plaintext must be supplied:
+    /// `candidates` maps each field to the plaintext values it may hold.
+    /// Plaintext values left over from before indexing was enabled are indexed
+    /// directly. Returns `true` if the payload changed.
+    pub fn rotate_payload(
+        &self,
+        payload: &mut HashMap<String, serde_json::Value>,
+        candidates: &HashMap<String, Vec<serde_json::Value>>,
+    ) -> bool {
+        let mut changed = false;
+
+        for field in &self.fields {
+            let Some(value) = payload.get_mut(field) else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+
+            let replacement = match self.token_key_id(value) {
+                Some(key_id) if key_id == self.active_key_id() => None,
+                Some(key_id) => {
+                    let key = self.keys.iter().find(|k| k.id == key_id);
+                    let current = value.as_str();
+                    key.and_then(|key| {
+                        candidates.get(field)?.iter().find(|candidate| {
+                            current == Some(Self::compute(key, field, candidate).as_str())
+                        })
+                    })
+                    .map(|plaintext| self.token(field, plaintext))
+                }
+                None => Some(self.token(field, value)),
+            };
+
+            if let Some(token) = replacement {
+                *value = serde_json::Value::String(token);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn rewrite_condition(&self, cond: &FilterCondition) -> RookResult<FilterCondition> {
+        let field = cond.field.as_str();
+        let expand = |values: &[serde_json::Value]| -> Vec<serde_json::Value> {
+            values.iter().flat_map(|v| self.tokens(field, v)).collect()
+        };
+
+        let operator = match &cond.operator {
+            FilterOperator::Eq(value) if self.keys.len() == 1 => {
+                FilterOperator::Eq(serde_json::Value::String(self.token(field, value)))
+            }
+            FilterOperator::Eq(value) => FilterOperator::In(self.tokens(field, value)),
+            FilterOperator::Ne(value) if self.keys.len() == 1 => {
+                FilterOperator::Ne(serde_json::Value::String(self.token(field, value)))
+            }
+            FilterOperator::Ne(value) => FilterOperator::Nin(self.tokens(field, value)),
+            FilterOperator::In(values) => FilterOperator::In(expand(values)),
+            FilterOperator::Nin(values) => FilterOperator::Nin(expand(values)),
+            FilterOperator::IsNull
+            | FilterOperator::IsNotNull
+            | FilterOperator::Exists
+            | FilterOperator::NotExists
+            | FilterOperator::Wildcard => cond.operator.clone(),
+            _ => {
+                return Err(RookError::Validation {
+                    message: format!(
+                        "Field '{}' is blind-indexed and only supports equality filters",
+                        field
+                    ),
+                    code: ErrorCode::ValInvalidFilter,
+                    details: HashMap::new(),
+                    suggestion: Some(
+                        "Use eq, ne, in or nin, or remove the field from blind_index.fields"
+                            .to_string(),
+                    ),
+                })
+            }
+        };
+
+        Ok(FilterCondition {
+            field: cond.field.clone(),
+            operator,
+        })
+    }
+
+    fn compute(key: &BlindIndexKey, field: &str, value: &serde_json::Value) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(key.secret.as_bytes()).expect("HMAC accepts any key length");
+        mac.update(field.as_bytes());
+        mac.update(&[FIELD_SEPARATOR]);
+        match value {
+            serde_json::Value::String(s) => mac.update(s.as_bytes()),
+            other => mac.update(other.to_string().as_bytes()),
+        }
+        format!("{}:{}", key.id, hex::encode(mac.finalize().into_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn indexer() -> BlindIndexer {
+        BlindIndexer::new(BlindIndexConfig::with_key("k1", "secret-one")).unwrap()
+    }
+
+    #[test]
+    fn test_tokens_are_deterministic_and_field_scoped() {
+        let idx = indexer();
+        let a = idx.token("user_id", &json!("alice"));
+        assert_eq!(a, idx.token("user_id", &json!("alice")));
+        assert!(a.starts_with("k1:"));
+        assert_ne!(a, idx.token("agent_id", &json!("alice")));
+        assert_ne!(a, idx.token("user_id", &json!("bob")));
+    }
+
+    #[test]
+    fn test_index_payload_leaves_other_fields() {
+        let idx = indexer();
+        let mut payload = HashMap::from([
+            ("user_id".to_string(), json!("alice")),
+            ("data".to_string(), json!("likes tea")),
+            ("run_id".to_string(), serde_json::Value::Null),
+        ]);
+        idx.index_payload(&mut payload);
+
+        assert_eq!(payload["user_id"], json!(idx.token("user_id", &json!("alice"))));
+        assert_eq!(payload["data"], json!("likes tea"));
+        assert!(payload["run_id"].is_null());
+
+        // Indexing twice must not double-hash.
+        let before = payload.clone();
+        idx.index_payload(&mut payload);
+        assert_eq!(payload, before);
+    }
+
+    #[test]
+    fn test_rewrite_filter_single_key() {
+        let idx = indexer();
+        let filter = Filter::And(vec![
+            Filter::eq("user_id", "alice"),
+            Filter::eq("topic", "food"),
+        ]);
+
+        let Filter::And(rewritten) = idx.rewrite_filter(&filter).unwrap() else {
+            panic!("expected And");
+        };
+        match &rewritten[0] {
+            Filter::Condition(FilterCondition {
+                operator: FilterOperator::Eq(v),
+                ..
+            }) => assert_eq!(v, &json!(idx.token("user_id", &json!("alice")))),
+            other => panic!("unexpected {:?}", other),
+        }
+        match &rewritten[1] {
+            Filter::Condition(FilterCondition {
+                operator: FilterOperator::Eq(v),
+                ..
+            }) => assert_eq!(v, &json!("food")),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rewrite_filter_matches_all_keys_during_rotation() {
+        let config = BlindIndexConfig::with_key("k1", "secret-one").rotate_to("k2", "secret-two");
+        let idx = BlindIndexer::new(config).unwrap();
+        assert_eq!(idx.active_key_id(), "k2");
+
+        match idx.rewrite_filter(&Filter::eq("user_id", "alice")).unwrap() {
+            Filter::Condition(FilterCondition {
+                operator: FilterOperator::In(values),
+                ..
+            }) => {
+                assert_eq!(values.len(), 2);
+                assert!(values[0].as_str().unwrap().starts_with("k2:"));
+                assert!(values[1].as_str().unwrap().starts_with("k1:"));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rewrite_filter_rejects_range_on_indexed_field() {
+        let idx = indexer();
+        let filter = Filter::Condition(FilterCondition {
+            field: "category".to_string(),
+            operator: FilterOperator::Contains("pref".to_string()),
+        });
+        assert!(matches!(
+            idx.rewrite_filter(&filter),
+            Err(RookError::Validation {
+                code: ErrorCode::ValInvalidFilter,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rotate_payload() {
+        let old = indexer();
+        let mut payload = HashMap::from([
+            ("user_id".to_string(), json!("alice")),
+            ("category".to_string(), json!("preference")),
+        ]);
+        old.index_payload(&mut payload);
+
+        let config = BlindIndexConfig::with_key("k1", "secret-one").rotate_to("k2", "secret-two");
+        let new = BlindIndexer::new(config).unwrap();
+        let candidates = HashMap::from([
+            ("user_id".to_string(), vec![json!("bob"), json!("alice")]),
+            ("category".to_string(), vec![json!("preference")]),
+        ]);
+
+        assert!(new.rotate_payload(&mut payload, &candidates));
+        assert_eq!(payload["user_id"], json!(new.token("user_id", &json!("alice"))));
+        assert_eq!(
+            payload["category"],
+            json!(new.token("category", &json!("preference")))
+        );
+        assert!(!new.rotate_payload(&mut payload, &candidates));
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert!(BlindIndexer::new(BlindIndexConfig {
+            fields: default_indexed_fields(),
+            keys: vec![],
+            active_key_id: None,
+        })
+        .is_err());
+        assert!(BlindIndexer::new(BlindIndexConfig::with_key("a:b", "s")).is_err());
+
+        let mut config = BlindIndexConfig::with_key("k1", "s");
+        config.active_key_id = Some("missing".to_string());
+        assert!(BlindIndexer::new(config).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+//! AES-256-GCM encryption of memory content.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Payload field holding memory content, the one encrypted.
+pub const ENCRYPTED_FIELD: &str = "data";
+
+/// Prefix of encrypted values: `"enc:<key_id>:<base64 nonce and ciphertext>"`.
+const ENCRYPTED_PREFIX: &str = "enc:";
+
+/// Length of an AES-256 key in bytes.
+const KEY_LEN: usize = 32;
+
+/// A content encryption key, given inline or read from a file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ContentEncryptionKey {
+    /// Stable identifier, stored with every value encrypted under the key.
+    pub id: String,
+    /// Base64-encoded 32-byte key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// File holding the base64-encoded key, read when the cipher is built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<PathBuf>,
+}
+
+/// Configuration for encrypting memory content at rest.
+///
+/// Covers the memory store and the content copies kept in the history,
+/// version and sync databases.
+///
+/// Multiple keys may be listed to support rotation: new writes use the active
+/// key, while values encrypted under any listed key can still be read until
+/// [`crate::Memory::rotate_content_encryption`] has re-encrypted them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentEncryptionConfig {
+    /// Known keys, oldest first.
+    pub keys: Vec<ContentEncryptionKey>,
+    /// Key used for new writes. Defaults to the last entry in `keys`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_key_id: Option<String>,
+}
+
+impl ContentEncryptionConfig {
+    /// Create a config with a single base64-encoded key.
+    pub fn with_key(id: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            keys: vec![ContentEncryptionKey {
+                id: id.into(),
+                key: Some(key.into()),
+                key_file: None,
+            }],
+            active_key_id: None,
+        }
+    }
+
+    /// Create a config with a single key read from a file.
+    pub fn with_key_file(id: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            keys: vec![ContentEncryptionKey {
+                id: id.into(),
+                key: None,
+                key_file: Some(path.into()),
+            }],
+            active_key_id: None,
+        }
+    }
+
+    /// Add a base64-encoded key and make it the active one.
+    pub fn rotate_to(mut self, id: impl Into<String>, key: impl Into<String>) -> Self {
+        let id = id.into();
+        self.keys.push(ContentEncryptionKey {
+            id: id.clone(),
+            key: Some(key.into()),
+            key_file: None,
+        });
+        self.active_key_id = Some(id);
+        self
+    }
+}
+
+/// Generate a random base64-encoded key for [`ContentEncryptionConfig`].
+pub fn generate_content_key() -> RookResult<String> {
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| RookError::internal("Failed to generate an encryption key"))?;
+    Ok(STANDARD.encode(key))
+}
+
+/// Encrypts and decrypts memory content.
+///
+/// Values are bound to the ID of the record holding them, so ciphertext
+/// copied to another record fails to decrypt.
+pub struct ContentCipher {
+    keys: Vec<(String, LessSafeKey)>,
+    active: usize,
+    rng: SystemRandom,
+}
+
+impl std::fmt::Debug for ContentCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContentCipher")
+            .field("keys", &self.keys.iter().map(|(id, _)| id).collect::<Vec<_>>())
+            .field("active_key_id", &self.active_key_id())
+            .finish()
+    }
+}
+
+impl ContentCipher {
+    /// Build a cipher, loading and validating the keys.
+    pub fn new(config: ContentEncryptionConfig) -> RookResult<Self> {
+        if config.keys.is_empty() {
+            return Err(RookError::Configuration(
+                "Content encryption requires at least one key".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut keys = Vec::with_capacity(config.keys.len());
+        for key in config.keys {
+            if key.id.is_empty() || key.id.contains(':') {
+                return Err(RookError::Configuration(format!(
+                    "Invalid content encryption key id '{}': must be non-empty and contain no ':'",
+                    key.id
+                )));
+            }
+            if !seen.insert(key.id.clone()) {
+                return Err(RookError::Configuration(format!(
+                    "Duplicate content encryption key id '{}'",
+                    key.id
+                )));
+            }
+            let encoded = match (key.key, key.key_file) {
+                (Some(encoded), None) => encoded,
+                (None, Some(path)) => std::fs::read_to_string(&path).map_err(|e| {
+                    RookError::Configuration(format!(
+                        "Failed to read content encryption key file {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?,
+                _ => {
+                    return Err(RookError::Configuration(format!(
+                        "Content encryption key '{}' needs exactly one of key and key_file",
+                        key.id
+                    )))
+                }
+            };
+            let bytes = STANDARD
+                .decode(encoded.trim())
+                .ok()
+                .filter(|bytes| bytes.len() == KEY_LEN)
+                .ok_or_else(|| {
+                    RookError::Configuration(format!(
+                        "Content encryption key '{}' must be {} base64-encoded bytes",
+                        key.id, KEY_LEN
+                    ))
+                })?;
+            let unbound = UnboundKey::new(&AES_256_GCM, &bytes).map_err(|_| {
+                RookError::Configuration(format!("Invalid content encryption key '{}'", key.id))
+            })?;
+            keys.push((key.id, LessSafeKey::new(unbound)));
+        }
+
+        let active = match &config.active_key_id {
+            Some(id) => keys.iter().position(|(key_id, _)| key_id == id).ok_or_else(|| {
+                RookError::Configuration(format!("Active content encryption key '{}' not found", id))
+            })?,
+            None => keys.len() - 1,
+        };
+
+        Ok(Self {
+            keys,
+            active,
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Identifier of the key used for new writes.
+    pub fn active_key_id(&self) -> &str {
+        &self.keys[self.active].0
+    }
+
+    /// Returns the key id a value was encrypted with, if it is encrypted
+    /// under one of the known keys.
+    pub fn encrypted_key_id<'a>(&self, value: &'a str) -> Option<&'a str> {
+        self.parse(value).map(|(key_id, _, _)| key_id)
+    }
+
+    /// Split an encrypted value into its key id, key and encoded ciphertext.
+    fn parse<'a>(&self, value: &'a str) -> Option<(&'a str, &LessSafeKey, &'a str)> {
+        let (key_id, encoded) = value.strip_prefix(ENCRYPTED_PREFIX)?.split_once(':')?;
+        let (_, key) = self.keys.iter().find(|(id, _)| id == key_id)?;
+        Some((key_id, key, encoded))
+    }
+
+    /// Encrypt `plaintext` for the record `record_id` under the active key.
+    pub fn encrypt(&self, record_id: &str, plaintext: &str) -> RookResult<String> {
+        let (key_id, key) = &self.keys[self.active];
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| RookError::internal("Failed to generate a nonce"))?;
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(aad(key_id, record_id)),
+            &mut sealed,
+        )
+        .map_err(|_| RookError::internal("Failed to encrypt memory content"))?;
+
+        let mut encoded = nonce.to_vec();
+        encoded.extend(sealed);
+        Ok(format!("{}{}:{}", ENCRYPTED_PREFIX, key_id, STANDARD.encode(encoded)))
+    }
+
+    /// Decrypt a value of the record `record_id`. Values that aren't
+    /// encrypted, such as content written before encryption was enabled, are
+    /// returned as they are.
+    pub fn decrypt(&self, record_id: &str, value: &str) -> RookResult<String> {
+        let Some((key_id, key, encoded)) = self.parse(value) else {
+            return Ok(value.to_string());
+        };
+        let failed = || {
+            RookError::internal(format!("Failed to decrypt the content of memory {}", record_id))
+        };
+
+        let mut sealed = STANDARD.decode(encoded).map_err(|_| failed())?;
+        if sealed.len() < NONCE_LEN {
+            return Err(failed());
+        }
+        let mut ciphertext = sealed.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&sealed).map_err(|_| failed())?;
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(aad(key_id, record_id)), &mut ciphertext)
+            .map_err(|_| failed())?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| failed())
+    }
+
+    /// Encrypt the content field of a payload under the active key, unless
+    /// it is already encrypted.
+    pub fn encrypt_payload(
+        &self,
+        record_id: &str,
+        payload: &mut HashMap<String, serde_json::Value>,
+    ) -> RookResult<()> {
+        if let Some(serde_json::Value::String(value)) = payload.get_mut(ENCRYPTED_FIELD) {
+            if self.encrypted_key_id(value).is_none() {
+                *value = self.encrypt(record_id, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypt the content field of a payload.
+    pub fn decrypt_payload(
+        &self,
+        record_id: &str,
+        payload: &mut HashMap<String, serde_json::Value>,
+    ) -> RookResult<()> {
+        if let Some(serde_json::Value::String(value)) = payload.get_mut(ENCRYPTED_FIELD) {
+            *value = self.decrypt(record_id, value)?;
+        }
+        Ok(())
+    }
+
+    /// Re-encrypt the content field of a payload under the active key.
+    ///
+    /// Plaintext left over from before encryption was enabled is encrypted.
+    /// Returns `true` if the payload changed.
+    pub fn rotate_payload(
+        &self,
+        record_id: &str,
+        payload: &mut HashMap<String, serde_json::Value>,
+    ) -> RookResult<bool> {
+        let Some(serde_json::Value::String(value)) = payload.get_mut(ENCRYPTED_FIELD) else {
+            return Ok(false);
+        };
+        if self.encrypted_key_id(value) == Some(self.active_key_id()) {
+            return Ok(false);
+        }
+        let plaintext = self.decrypt(record_id, value)?;
+        *value = self.encrypt(record_id, &plaintext)?;
+        Ok(true)
+    }
+}
+
+/// Associated data binding a value to its key and record.
+fn aad(key_id: &str, record_id: &str) -> Vec<u8> {
+    let mut aad = key_id.as_bytes().to_vec();
+    aad.push(0x1f);
+    aad.extend_from_slice(record_id.as_bytes());
+    aad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher(config: ContentEncryptionConfig) -> ContentCipher {
+        ContentCipher::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let cipher = cipher(ContentEncryptionConfig::with_key("k1", generate_content_key().unwrap()));
+
+        let encrypted = cipher.encrypt("m1", "Uses Helix").unwrap();
+        assert!(encrypted.starts_with("enc:k1:"));
+        assert!(!encrypted.contains("Helix"));
+        assert_ne!(encrypted, cipher.encrypt("m1", "Uses Helix").unwrap());
+        assert_eq!(cipher.decrypt("m1", &encrypted).unwrap(), "Uses Helix");
+
+        // Bound to the record
+        assert!(cipher.decrypt("m2", &encrypted).is_err());
+        // Plaintext passes through
+        assert_eq!(cipher.decrypt("m1", "Uses Helix").unwrap(), "Uses Helix");
+    }
+
+    #[test]
+    fn test_rotate_payload() {
+        let old = generate_content_key().unwrap();
+        let before = cipher(ContentEncryptionConfig::with_key("k1", old.clone()));
+        let after = cipher(
+            ContentEncryptionConfig::with_key("k1", old).rotate_to("k2", generate_content_key().unwrap()),
+        );
+
+        let mut payload = HashMap::from([(
+            ENCRYPTED_FIELD.to_string(),
+            serde_json::Value::String(before.encrypt("m1", "Uses Helix").unwrap()),
+        )]);
+        assert!(after.rotate_payload("m1", &mut payload).unwrap());
+        assert_eq!(after.encrypted_key_id(payload[ENCRYPTED_FIELD].as_str().unwrap()), Some("k2"));
+        assert!(!after.rotate_payload("m1", &mut payload).unwrap());
+
+        after.decrypt_payload("m1", &mut payload).unwrap();
+        assert_eq!(payload[ENCRYPTED_FIELD], "Uses Helix");
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(ContentCipher::new(ContentEncryptionConfig::with_key("k1", "c2hvcnQ=")).is_err());
+        assert!(ContentCipher::new(ContentEncryptionConfig::with_key("a:b", generate_content_key().unwrap())).is_err());
+        assert!(ContentCipher::new(ContentEncryptionConfig::with_key_file("k1", "/nonexistent/key")).is_err());
+
+        let mut config = ContentEncryptionConfig::with_key("k1", generate_content_key().unwrap());
+        config.active_key_id = Some("k9".to_string());
+        assert!(ContentCipher::new(config).is_err());
+    }
+}
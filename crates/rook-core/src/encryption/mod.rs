@@ -0,0 +1,40 @@
+//! Encryption support for memory payloads at rest.
+//!
+//! Encrypted stores cannot evaluate filters against ciphertext, so scoping
+//! fields (`user_id`, `agent_id`, `run_id`, `category`) are replaced with
+//! deterministic HMAC "blind index" tokens. Equality filters are rewritten to
+//! compare tokens, which keeps scoped search, listing and deletion working
+//! while free-text fields stay fully opaque.
+//!
+//! Memory content itself can be encrypted with AES-256-GCM by wrapping the
+//! vector store in an [`EncryptedVectorStore`], configured through
+//! [`ContentEncryptionConfig`]. Embeddings stay plaintext for search. The
+//! same keys encrypt content kept in the history database (change history
+//! and promotion requests) and, through an [`EncryptedVersionStore`], in
+//! version snapshots.
+//!
+//! # Example
+//!
+//! ```
+//! use rook_core::encryption::{BlindIndexConfig, BlindIndexer};
+//! use rook_core::types::Filter;
+//!
+//! let config = BlindIndexConfig::with_key("k1", "super-secret");
+//! let indexer = BlindIndexer::new(config).unwrap();
+//!
+//! let filter = indexer.rewrite_filter(&Filter::eq("user_id", "alice")).unwrap();
+//! assert!(format!("{:?}", filter).contains("k1:"));
+//! ```
+
+mod blind_index;
+mod content;
+mod store;
+mod versions;
+
+pub use blind_index::{BlindIndexConfig, BlindIndexKey, BlindIndexer, DEFAULT_INDEXED_FIELDS};
+pub use content::{
+    generate_content_key, ContentCipher, ContentEncryptionConfig, ContentEncryptionKey,
+    ENCRYPTED_FIELD,
+};
+pub use store::EncryptedVectorStore;
+pub use versions::EncryptedVersionStore;
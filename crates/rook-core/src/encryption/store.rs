@@ -0,0 +1,312 @@
+//! Vector store wrapper encrypting memory content.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::ContentCipher;
+use crate::error::RookResult;
+use crate::traits::{CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore};
+use crate::types::{Filter, OrderBy, Page, PageRequest};
+
+/// Encrypts the `data` field of every payload written to the wrapped store
+/// and decrypts it on read.
+///
+/// Embeddings and the other payload fields stay plaintext, so search and
+/// filters keep working. Keyword search inside the wrapped store only sees
+/// ciphertext.
+pub struct EncryptedVectorStore {
+    inner: Arc<dyn VectorStore>,
+    cipher: ContentCipher,
+}
+
+impl EncryptedVectorStore {
+    pub fn new(inner: Arc<dyn VectorStore>, cipher: ContentCipher) -> Self {
+        Self { inner, cipher }
+    }
+
+    /// Re-encrypt stored content under the active key, encrypting content
+    /// written before encryption was enabled. Returns the number of records
+    /// rewritten.
+    pub async fn rotate(&self) -> RookResult<usize> {
+        let mut rotated = 0;
+        for mut record in self.inner.list(None, None).await? {
+            if self.cipher.rotate_payload(&record.id, &mut record.payload)? {
+                self.inner.update(&record.id, None, Some(record.payload)).await?;
+                rotated += 1;
+            }
+        }
+        Ok(rotated)
+    }
+
+    fn decrypt_record(&self, mut record: VectorRecord) -> RookResult<VectorRecord> {
+        self.cipher.decrypt_payload(&record.id, &mut record.payload)?;
+        Ok(record)
+    }
+}
+
+#[async_trait]
+impl VectorStore for EncryptedVectorStore {
+    async fn create_collection(
+        &self,
+        name: &str,
+        dimension: usize,
+        distance: DistanceMetric,
+    ) -> RookResult<()> {
+        self.inner.create_collection(name, dimension, distance).await
+    }
+
+    async fn insert(&self, mut records: Vec<VectorRecord>) -> RookResult<()> {
+        for record in &mut records {
+            self.cipher.encrypt_payload(&record.id, &mut record.payload)?;
+        }
+        self.inner.insert(records).await
+    }
+
+    async fn search(
+        &self,
+        query_vector: &[f32],
+        limit: usize,
+        filters: Option<Filter>,
+    ) -> RookResult<Vec<VectorSearchResult>> {
+        self.inner
+            .search(query_vector, limit, filters)
+            .await?
+            .into_iter()
+            .map(|mut result| {
+                self.cipher.decrypt_payload(&result.id, &mut result.payload)?;
+                Ok(result)
+            })
+            .collect()
+    }
+
+    async fn get(&self, id: &str) -> RookResult<Option<VectorRecord>> {
+        self.inner
+            .get(id)
+            .await?
+            .map(|record| self.decrypt_record(record))
+            .transpose()
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        vector: Option<Vec<f32>>,
+        mut payload: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<()> {
+        if let Some(ref mut payload) = payload {
+            self.cipher.encrypt_payload(id, payload)?;
+        }
+        self.inner.update(id, vector, payload).await
+    }
+
+    async fn delete(&self, id: &str) -> RookResult<()> {
+        self.inner.delete(id).await
+    }
+
+    async fn list(
+        &self,
+        filters: Option<Filter>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        self.inner
+            .list(filters, limit)
+            .await?
+            .into_iter()
+            .map(|record| self.decrypt_record(record))
+            .collect()
+    }
+
+    async fn list_ordered(
+        &self,
+        filters: Option<Filter>,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        self.inner
+            .list_ordered(filters, order_by, limit)
+            .await?
+            .into_iter()
+            .map(|record| self.decrypt_record(record))
+            .collect()
+    }
+
+    async fn list_page(
+        &self,
+        filters: Option<Filter>,
+        page: &PageRequest,
+    ) -> RookResult<Page<VectorRecord>> {
+        let page = self.inner.list_page(filters, page).await?;
+        let items = page
+            .items
+            .into_iter()
+            .map(|record| self.decrypt_record(record))
+            .collect::<RookResult<_>>()?;
+        Ok(Page { items, ..page })
+    }
+
+    async fn list_collections(&self) -> RookResult<Vec<String>> {
+        self.inner.list_collections().await
+    }
+
+    async fn delete_collection(&self, name: &str) -> RookResult<()> {
+        self.inner.delete_collection(name).await
+    }
+
+    async fn collection_info(&self, name: &str) -> RookResult<CollectionInfo> {
+        self.inner.collection_info(name).await
+    }
+
+    async fn reset(&self) -> RookResult<()> {
+        self.inner.reset().await
+    }
+
+    fn collection_name(&self) -> &str {
+        self.inner.collection_name()
+    }
+
+    fn supports_timestamp_filters(&self) -> bool {
+        self.inner.supports_timestamp_filters()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encryption::{generate_content_key, ContentEncryptionConfig, ENCRYPTED_FIELD};
+    use std::sync::Mutex;
+
+    /// Keeps records in memory, as the wrapped store.
+    #[derive(Default)]
+    struct MapStore(Mutex<HashMap<String, VectorRecord>>);
+
+    #[async_trait]
+    impl VectorStore for MapStore {
+        async fn create_collection(&self, _: &str, _: usize, _: DistanceMetric) -> RookResult<()> {
+            Ok(())
+        }
+
+        async fn insert(&self, records: Vec<VectorRecord>) -> RookResult<()> {
+            let mut map = self.0.lock().unwrap();
+            for record in records {
+                map.insert(record.id.clone(), record);
+            }
+            Ok(())
+        }
+
+        async fn search(
+            &self,
+            _: &[f32],
+            limit: usize,
+            _: Option<Filter>,
+        ) -> RookResult<Vec<VectorSearchResult>> {
+            let map = self.0.lock().unwrap();
+            Ok(map
+                .values()
+                .take(limit)
+                .map(|r| VectorSearchResult {
+                    id: r.id.clone(),
+                    score: 1.0,
+                    payload: r.payload.clone(),
+                })
+                .collect())
+        }
+
+        async fn get(&self, id: &str) -> RookResult<Option<VectorRecord>> {
+            Ok(self.0.lock().unwrap().get(id).cloned())
+        }
+
+        async fn update(
+            &self,
+            id: &str,
+            _: Option<Vec<f32>>,
+            payload: Option<HashMap<String, serde_json::Value>>,
+        ) -> RookResult<()> {
+            if let (Some(record), Some(payload)) = (self.0.lock().unwrap().get_mut(id), payload) {
+                record.payload.extend(payload);
+            }
+            Ok(())
+        }
+
+        async fn delete(&self, id: &str) -> RookResult<()> {
+            self.0.lock().unwrap().remove(id);
+            Ok(())
+        }
+
+        async fn list(&self, _: Option<Filter>, _: Option<usize>) -> RookResult<Vec<VectorRecord>> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn list_collections(&self) -> RookResult<Vec<String>> {
+            Ok(vec![])
+        }
+
+        async fn delete_collection(&self, _: &str) -> RookResult<()> {
+            Ok(())
+        }
+
+        async fn collection_info(&self, name: &str) -> RookResult<CollectionInfo> {
+            Ok(CollectionInfo {
+                name: name.to_string(),
+                vector_count: 0,
+                dimension: 0,
+                distance: DistanceMetric::Cosine,
+            })
+        }
+
+        async fn reset(&self) -> RookResult<()> {
+            self.0.lock().unwrap().clear();
+            Ok(())
+        }
+
+        fn collection_name(&self) -> &str {
+            "test"
+        }
+    }
+
+    fn payload(data: &str) -> HashMap<String, serde_json::Value> {
+        HashMap::from([
+            (ENCRYPTED_FIELD.to_string(), data.into()),
+            ("user_id".to_string(), "alice".into()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_vector_store() {
+        let inner = Arc::new(MapStore::default());
+        let old = generate_content_key().unwrap();
+        let config = ContentEncryptionConfig::with_key("k1", old.clone());
+        let store = EncryptedVectorStore::new(inner.clone(), ContentCipher::new(config).unwrap());
+
+        store
+            .insert(vec![VectorRecord::new("m1", vec![1.0], payload("Uses Helix"))])
+            .await
+            .unwrap();
+        let raw = inner.get("m1").await.unwrap().unwrap();
+        assert!(raw.get_data().unwrap().starts_with("enc:k1:"));
+        assert_eq!(raw.get_string("user_id"), Some("alice"));
+        assert_eq!(store.get("m1").await.unwrap().unwrap().get_data(), Some("Uses Helix"));
+
+        store.update("m1", None, Some(payload("Uses Zed"))).await.unwrap();
+        assert_eq!(store.list(None, None).await.unwrap()[0].get_data(), Some("Uses Zed"));
+        assert_eq!(store.search(&[1.0], 1, None).await.unwrap()[0].payload[ENCRYPTED_FIELD], "Uses Zed");
+
+        // Plaintext from before encryption and content under the old key are
+        // re-encrypted under the new key
+        inner
+            .insert(vec![VectorRecord::new("m2", vec![1.0], payload("Lives in Oslo"))])
+            .await
+            .unwrap();
+        let config = ContentEncryptionConfig::with_key("k1", old)
+            .rotate_to("k2", generate_content_key().unwrap());
+        let store = EncryptedVectorStore::new(inner.clone(), ContentCipher::new(config).unwrap());
+        assert_eq!(store.rotate().await.unwrap(), 2);
+        assert_eq!(store.rotate().await.unwrap(), 0);
+        for id in ["m1", "m2"] {
+            let raw = inner.get(id).await.unwrap().unwrap();
+            assert!(raw.get_data().unwrap().starts_with("enc:k2:"));
+        }
+        assert_eq!(store.get("m2").await.unwrap().unwrap().get_data(), Some("Lives in Oslo"));
+    }
+}
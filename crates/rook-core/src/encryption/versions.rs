@@ -0,0 +1,153 @@
+//! Version store wrapper encrypting memory content.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use super::ContentCipher;
+use crate::error::RookResult;
+use crate::versioning::{MemoryVersion, VersionStore, VersionSummary};
+
+/// Encrypts the content of every version written to the wrapped store, and
+/// the content field of its metadata snapshot, and decrypts them on read.
+///
+/// Values are bound to the memory ID, as in [`super::EncryptedVectorStore`].
+pub struct EncryptedVersionStore {
+    inner: Arc<dyn VersionStore>,
+    cipher: ContentCipher,
+}
+
+impl EncryptedVersionStore {
+    pub fn new(inner: Arc<dyn VersionStore>, cipher: ContentCipher) -> Self {
+        Self { inner, cipher }
+    }
+
+    fn decrypt_version(&self, mut version: MemoryVersion) -> RookResult<MemoryVersion> {
+        version.content = self.cipher.decrypt(&version.memory_id, &version.content)?;
+        self.cipher
+            .decrypt_payload(&version.memory_id, &mut version.metadata)?;
+        Ok(version)
+    }
+
+    fn decrypt_versions(&self, versions: Vec<MemoryVersion>) -> RookResult<Vec<MemoryVersion>> {
+        versions
+            .into_iter()
+            .map(|version| self.decrypt_version(version))
+            .collect()
+    }
+}
+
+impl VersionStore for EncryptedVersionStore {
+    fn add_version(&self, version: &MemoryVersion) -> RookResult<()> {
+        let mut version = version.clone();
+        version.content = self.cipher.encrypt(&version.memory_id, &version.content)?;
+        self.cipher
+            .encrypt_payload(&version.memory_id, &mut version.metadata)?;
+        self.inner.add_version(&version)
+    }
+
+    fn get_at_time(
+        &self,
+        memory_id: &str,
+        timestamp: DateTime<Utc>,
+    ) -> RookResult<Option<MemoryVersion>> {
+        self.inner
+            .get_at_time(memory_id, timestamp)?
+            .map(|version| self.decrypt_version(version))
+            .transpose()
+    }
+
+    fn get_version(
+        &self,
+        memory_id: &str,
+        version_number: u32,
+    ) -> RookResult<Option<MemoryVersion>> {
+        self.inner
+            .get_version(memory_id, version_number)?
+            .map(|version| self.decrypt_version(version))
+            .transpose()
+    }
+
+    fn get_latest(&self, memory_id: &str) -> RookResult<Option<MemoryVersion>> {
+        self.inner
+            .get_latest(memory_id)?
+            .map(|version| self.decrypt_version(version))
+            .transpose()
+    }
+
+    fn get_all_versions(&self, memory_id: &str) -> RookResult<Vec<MemoryVersion>> {
+        self.decrypt_versions(self.inner.get_all_versions(memory_id)?)
+    }
+
+    fn get_versions_in_range(
+        &self,
+        memory_id: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> RookResult<Vec<MemoryVersion>> {
+        self.decrypt_versions(self.inner.get_versions_in_range(memory_id, start, end)?)
+    }
+
+    fn get_summary(&self, memory_id: &str) -> RookResult<Option<VersionSummary>> {
+        self.inner.get_summary(memory_id)
+    }
+
+    fn get_next_version_number(&self, memory_id: &str) -> RookResult<u32> {
+        self.inner.get_next_version_number(memory_id)
+    }
+
+    fn delete_versions(&self, memory_id: &str) -> RookResult<usize> {
+        self.inner.delete_versions(memory_id)
+    }
+
+    fn prune_old_versions(&self, memory_id: &str, keep_count: usize) -> RookResult<usize> {
+        self.inner.prune_old_versions(memory_id, keep_count)
+    }
+
+    fn count_all(&self) -> RookResult<usize> {
+        self.inner.count_all()
+    }
+
+    fn find_memory_ids(&self, field: &str, values: &[String]) -> RookResult<Vec<String>> {
+        self.inner.find_memory_ids(field, values)
+    }
+
+    fn deleted_since(&self, since: Option<DateTime<Utc>>) -> RookResult<Vec<MemoryVersion>> {
+        self.decrypt_versions(self.inner.deleted_since(since)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encryption::{generate_content_key, ContentEncryptionConfig, ENCRYPTED_FIELD};
+    use crate::versioning::SqliteVersionStore;
+
+    #[test]
+    fn test_versions_encrypted_at_rest() {
+        let config = ContentEncryptionConfig::with_key("k1", generate_content_key().unwrap());
+        let inner = Arc::new(SqliteVersionStore::in_memory().unwrap());
+        let store = EncryptedVersionStore::new(inner.clone(), ContentCipher::new(config).unwrap());
+
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert(ENCRYPTED_FIELD.to_string(), "Uses Helix".into());
+        store
+            .add_version(&MemoryVersion::initial("m1", "Uses Helix").with_metadata(metadata))
+            .unwrap();
+
+        let stored = inner.get_latest("m1").unwrap().unwrap();
+        assert!(stored.content.starts_with("enc:k1:"));
+        assert!(!stored.metadata[ENCRYPTED_FIELD]
+            .as_str()
+            .unwrap()
+            .contains("Helix"));
+
+        let latest = store.get_latest("m1").unwrap().unwrap();
+        assert_eq!(latest.content, "Uses Helix");
+        assert_eq!(latest.metadata[ENCRYPTED_FIELD], "Uses Helix");
+        assert_eq!(
+            store.get_all_versions("m1").unwrap()[0].content,
+            "Uses Helix"
+        );
+    }
+}
@@ -149,6 +149,7 @@ pub enum ErrorCode {
     AuthInvalidKey,
     AuthExpiredToken,
     AuthMissingCredentials,
+    AuthForbidden,
 
     // Validation (VAL_xxx)
     ValInvalidInput,
@@ -209,6 +210,7 @@ impl ErrorCode {
             ErrorCode::AuthInvalidKey => "AUTH_001",
             ErrorCode::AuthExpiredToken => "AUTH_002",
             ErrorCode::AuthMissingCredentials => "AUTH_003",
+            ErrorCode::AuthForbidden => "AUTH_004",
             ErrorCode::ValInvalidInput => "VAL_001",
             ErrorCode::ValMissingField => "VAL_002",
             ErrorCode::ValInvalidFormat => "VAL_003",
@@ -347,6 +349,15 @@ impl RookError {
         }
     }
 
+    /// Create an authorization (forbidden) error.
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::Authentication {
+            message: message.into(),
+            code: ErrorCode::AuthForbidden,
+            source: None,
+        }
+    }
+
     /// Create a rate limit error.
     pub fn rate_limit(message: impl Into<String>) -> Self {
         Self::RateLimit {
@@ -389,6 +400,10 @@ impl RookError {
     /// Get a user-friendly suggestion for resolving this error.
     pub fn suggestion(&self) -> Option<&str> {
         match self {
+            Self::Authentication {
+                code: ErrorCode::AuthForbidden,
+                ..
+            } => Some("The operation was denied by the authorization policy"),
             Self::Authentication { .. } => Some("Please check your API key and authentication credentials"),
             Self::RateLimit { .. } => Some("Please wait before making more requests"),
             Self::NotFound { .. } => Some("Please check the memory ID and ensure it exists"),
@@ -409,11 +424,12 @@ impl RookError {
                 details: HashMap::new(),
                 suggestion: Some("Please check your request parameters".to_string()),
             },
-            401 | 403 => Self::Authentication {
+            401 => Self::Authentication {
                 message: body.to_string(),
                 code: ErrorCode::AuthInvalidKey,
                 source: None,
             },
+            403 => Self::forbidden(body),
             404 => Self::NotFound {
                 message: body.to_string(),
                 code: ErrorCode::MemNotFound,
@@ -0,0 +1,658 @@
+//! Operational alerts
+//!
+//! Alerts share the event bus with memory lifecycle events so deployments can
+//! learn about problems before users do:
+//! - Provider error rate above a threshold ([`ErrorRateMonitor`])
+//! - Webhook deliveries that exhausted their retries (dead letters)
+//! - Background job failures
+//! - Low disk space for the data directory ([`DiskSpaceMonitor`])
+//! - Data directory over its size budget ([`crate::storage::DataDirMonitor`])
+//!
+//! [`AlertSubscriber`] forwards alerts to a generic webhook or a Slack
+//! incoming webhook URL.
+
+use crate::error::{RookError, RookResult};
+use crate::events::{EventBus, MemoryLifecycleEvent, WebhookError};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Alert severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for AlertSeverity {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Self::Info),
+            "warning" | "warn" => Ok(Self::Warning),
+            "critical" => Ok(Self::Critical),
+            other => Err(RookError::Configuration(format!(
+                "Unknown alert severity: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// What an alert is about
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertKind {
+    /// A provider's recent error rate exceeded the threshold
+    ProviderErrorRate {
+        provider: String,
+        error_rate: f64,
+        threshold: f64,
+        samples: usize,
+    },
+    /// A webhook delivery failed after all retries
+    WebhookDeadLetter {
+        webhook_id: String,
+        url: String,
+        event_type: String,
+        error: String,
+    },
+    /// A background job failed
+    JobFailed { job: String, error: String },
+    /// Free disk space for a data directory fell below the threshold
+    DiskSpaceLow {
+        path: String,
+        available_bytes: u64,
+        total_bytes: u64,
+    },
+    /// The data directory is over its size budget
+    StorageBudgetExceeded {
+        path: String,
+        used_bytes: u64,
+        budget_bytes: u64,
+    },
+}
+
+/// Operational alert event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    /// Unique event ID
+    pub event_id: String,
+    /// Alert severity
+    pub severity: AlertSeverity,
+    /// Alert details
+    #[serde(flatten)]
+    pub kind: AlertKind,
+    /// Human-readable summary
+    pub message: String,
+    /// When the alert was raised
+    pub timestamp: DateTime<Utc>,
+}
+
+impl AlertEvent {
+    pub fn new(severity: AlertSeverity, kind: AlertKind) -> Self {
+        let message = match &kind {
+            AlertKind::ProviderErrorRate {
+                provider,
+                error_rate,
+                threshold,
+                samples,
+            } => format!(
+                "{} error rate {:.0}% over the last {} calls (threshold {:.0}%)",
+                provider,
+                error_rate * 100.0,
+                samples,
+                threshold * 100.0
+            ),
+            AlertKind::WebhookDeadLetter {
+                url,
+                event_type,
+                error,
+                ..
+            } => format!("Dropped {} delivery to {}: {}", event_type, url, error),
+            AlertKind::JobFailed { job, error } => format!("Job '{}' failed: {}", job, error),
+            AlertKind::DiskSpaceLow {
+                path,
+                available_bytes,
+                total_bytes,
+            } => format!(
+                "Low disk space for {}: {} MiB free of {} MiB",
+                path,
+                available_bytes / (1024 * 1024),
+                total_bytes / (1024 * 1024)
+            ),
+            AlertKind::StorageBudgetExceeded {
+                path,
+                used_bytes,
+                budget_bytes,
+            } => format!(
+                "{} uses {} MiB, over its {} MiB budget",
+                path,
+                used_bytes / (1024 * 1024),
+                budget_bytes / (1024 * 1024)
+            ),
+        };
+
+        Self {
+            event_id: uuid::Uuid::new_v4().to_string(),
+            severity,
+            kind,
+            message,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn job_failed(job: impl Into<String>, error: impl std::fmt::Display) -> Self {
+        Self::new(
+            AlertSeverity::Warning,
+            AlertKind::JobFailed {
+                job: job.into(),
+                error: error.to_string(),
+            },
+        )
+    }
+
+    /// Event type used for filtering, e.g. `alert.job_failed`
+    pub fn alert_type(&self) -> &'static str {
+        match self.kind {
+            AlertKind::ProviderErrorRate { .. } => "alert.provider_error_rate",
+            AlertKind::WebhookDeadLetter { .. } => "alert.webhook_dead_letter",
+            AlertKind::JobFailed { .. } => "alert.job_failed",
+            AlertKind::DiskSpaceLow { .. } => "alert.disk_space_low",
+            AlertKind::StorageBudgetExceeded { .. } => "alert.storage_budget_exceeded",
+        }
+    }
+
+    /// Key identifying repeats of the same problem, used for cooldowns
+    pub fn dedup_key(&self) -> String {
+        match &self.kind {
+            AlertKind::ProviderErrorRate { provider, .. } => {
+                format!("{}:{}", self.alert_type(), provider)
+            }
+            AlertKind::WebhookDeadLetter { webhook_id, .. } => {
+                format!("{}:{}", self.alert_type(), webhook_id)
+            }
+            AlertKind::JobFailed { job, .. } => format!("{}:{}", self.alert_type(), job),
+            AlertKind::DiskSpaceLow { path, .. }
+            | AlertKind::StorageBudgetExceeded { path, .. } => {
+                format!("{}:{}", self.alert_type(), path)
+            }
+        }
+    }
+}
+
+/// Error rate alerting thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ErrorRateConfig {
+    /// Error rate in `[0, 1]` above which an alert fires (default: 0.25)
+    pub threshold: f64,
+    /// Number of most recent calls considered per provider (default: 50)
+    pub window: usize,
+    /// Minimum calls in the window before alerting (default: 10)
+    pub min_samples: usize,
+    /// Minimum time between alerts for the same provider (default: 300s)
+    pub cooldown_secs: u64,
+}
+
+impl Default for ErrorRateConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.25,
+            window: 50,
+            min_samples: 10,
+            cooldown_secs: 300,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ProviderWindow {
+    outcomes: VecDeque<bool>,
+    last_alert: Option<Instant>,
+}
+
+/// Tracks recent call outcomes per provider and raises
+/// [`AlertKind::ProviderErrorRate`] alerts.
+pub struct ErrorRateMonitor {
+    config: ErrorRateConfig,
+    windows: Mutex<HashMap<String, ProviderWindow>>,
+    event_bus: EventBus,
+}
+
+impl ErrorRateMonitor {
+    pub fn new(config: ErrorRateConfig, event_bus: EventBus) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(HashMap::new()),
+            event_bus,
+        }
+    }
+
+    /// Record a call outcome. Returns the alert if one was emitted.
+    pub fn record(&self, provider: &str, success: bool) -> Option<AlertEvent> {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let window = windows.entry(provider.to_string()).or_default();
+
+        window.outcomes.push_back(success);
+        while window.outcomes.len() > self.config.window.max(1) {
+            window.outcomes.pop_front();
+        }
+
+        let samples = window.outcomes.len();
+        if samples < self.config.min_samples {
+            return None;
+        }
+
+        let errors = window.outcomes.iter().filter(|ok| !**ok).count();
+        let error_rate = errors as f64 / samples as f64;
+        if error_rate <= self.config.threshold {
+            return None;
+        }
+
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        if window.last_alert.is_some_and(|at| at.elapsed() < cooldown) {
+            return None;
+        }
+        window.last_alert = Some(Instant::now());
+        drop(windows);
+
+        let alert = AlertEvent::new(
+            AlertSeverity::Critical,
+            AlertKind::ProviderErrorRate {
+                provider: provider.to_string(),
+                error_rate,
+                threshold: self.config.threshold,
+                samples,
+            },
+        );
+        self.event_bus.emit(MemoryLifecycleEvent::Alert(alert.clone()));
+        Some(alert)
+    }
+}
+
+/// Disk space alerting for a data directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSpaceConfig {
+    /// Directory to watch
+    pub path: PathBuf,
+    /// Alert when free space falls below this percentage (default: 10)
+    #[serde(default = "default_min_free_percent")]
+    pub min_free_percent: f64,
+    /// Seconds between checks (default: 60)
+    #[serde(default = "default_check_interval")]
+    pub check_interval_secs: u64,
+}
+
+fn default_min_free_percent() -> f64 {
+    10.0
+}
+
+fn default_check_interval() -> u64 {
+    60
+}
+
+impl DiskSpaceConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            min_free_percent: default_min_free_percent(),
+            check_interval_secs: default_check_interval(),
+        }
+    }
+
+    /// Watch `ROOK_DATA_DIR`, if set.
+    ///
+    /// `ROOK_ALERT_DISK_MIN_FREE_PERCENT` overrides the threshold.
+    pub fn from_env() -> Option<Self> {
+        let mut config = Self::new(std::env::var("ROOK_DATA_DIR").ok()?);
+        if let Some(percent) = std::env::var("ROOK_ALERT_DISK_MIN_FREE_PERCENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.min_free_percent = percent;
+        }
+        Some(config)
+    }
+}
+
+/// Periodically checks free space and raises [`AlertKind::DiskSpaceLow`]
+/// once per low-space episode.
+pub struct DiskSpaceMonitor {
+    config: DiskSpaceConfig,
+    event_bus: EventBus,
+}
+
+impl DiskSpaceMonitor {
+    pub fn new(config: DiskSpaceConfig, event_bus: EventBus) -> Self {
+        Self { config, event_bus }
+    }
+
+    /// Check free space now. Returns an alert if space is below the threshold.
+    pub fn check(&self) -> RookResult<Option<AlertEvent>> {
+        let available = fs2::available_space(&self.config.path)?;
+        let total = fs2::total_space(&self.config.path)?;
+        Ok(low_space_alert(&self.config, available, total))
+    }
+
+    /// Start checking in the background
+    pub fn start(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+            let mut low = false;
+            loop {
+                interval.tick().await;
+                match self.check() {
+                    Ok(Some(alert)) => {
+                        if !low {
+                            self.event_bus.emit(MemoryLifecycleEvent::Alert(alert));
+                        }
+                        low = true;
+                    }
+                    Ok(None) => low = false,
+                    Err(e) => tracing::warn!(
+                        "Disk space check for {} failed: {}",
+                        self.config.path.display(),
+                        e
+                    ),
+                }
+            }
+        })
+    }
+}
+
+fn low_space_alert(config: &DiskSpaceConfig, available: u64, total: u64) -> Option<AlertEvent> {
+    if total == 0 {
+        return None;
+    }
+    let free_percent = available as f64 / total as f64 * 100.0;
+    if free_percent >= config.min_free_percent {
+        return None;
+    }
+    let severity = if free_percent < config.min_free_percent / 2.0 {
+        AlertSeverity::Critical
+    } else {
+        AlertSeverity::Warning
+    };
+    Some(AlertEvent::new(
+        severity,
+        AlertKind::DiskSpaceLow {
+            path: config.path.display().to_string(),
+            available_bytes: available,
+            total_bytes: total,
+        },
+    ))
+}
+
+/// Payload format for alert delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertFormat {
+    /// The alert event as JSON
+    #[default]
+    Json,
+    /// Slack incoming webhook message
+    Slack,
+}
+
+/// Alert forwarding configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSubscriberConfig {
+    /// Destination URL
+    pub url: String,
+    /// Payload format
+    #[serde(default)]
+    pub format: AlertFormat,
+    /// Alerts below this severity are not forwarded (default: warning)
+    #[serde(default)]
+    pub min_severity: AlertSeverity,
+    /// Minimum time between forwards of the same problem (default: 300s)
+    #[serde(default = "default_cooldown")]
+    pub cooldown_secs: u64,
+    /// Request timeout in seconds (default: 10)
+    #[serde(default = "default_alert_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_cooldown() -> u64 {
+    300
+}
+
+fn default_alert_timeout() -> u64 {
+    10
+}
+
+impl AlertSubscriberConfig {
+    pub fn new(url: impl Into<String>, format: AlertFormat) -> Self {
+        Self {
+            url: url.into(),
+            format,
+            min_severity: AlertSeverity::default(),
+            cooldown_secs: default_cooldown(),
+            timeout_secs: default_alert_timeout(),
+        }
+    }
+
+    pub fn with_min_severity(mut self, severity: AlertSeverity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Create config from environment variables.
+    ///
+    /// Reads:
+    /// - `ROOK_ALERT_SLACK_URL` - Slack incoming webhook URL
+    /// - `ROOK_ALERT_WEBHOOK_URL` - generic JSON webhook URL (used if no Slack URL)
+    /// - `ROOK_ALERT_MIN_SEVERITY` - `info`, `warning` or `critical` (default: warning)
+    pub fn from_env() -> Option<Self> {
+        let mut config = match std::env::var("ROOK_ALERT_SLACK_URL") {
+            Ok(url) => Self::new(url, AlertFormat::Slack),
+            Err(_) => Self::new(std::env::var("ROOK_ALERT_WEBHOOK_URL").ok()?, AlertFormat::Json),
+        };
+        if let Some(severity) = std::env::var("ROOK_ALERT_MIN_SEVERITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.min_severity = severity;
+        }
+        Some(config)
+    }
+}
+
+/// Forwards alert events from the bus to a webhook or Slack URL
+pub struct AlertSubscriber {
+    client: Client,
+    config: AlertSubscriberConfig,
+    last_sent: Mutex<HashMap<String, Instant>>,
+}
+
+impl AlertSubscriber {
+    pub fn new(config: AlertSubscriberConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            config,
+            last_sent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Start forwarding alerts from the event bus
+    pub fn start(self, event_bus: &EventBus) -> tokio::task::JoinHandle<()> {
+        let mut subscriber = event_bus.subscribe();
+        tokio::spawn(async move {
+            while let Some(event) = subscriber.recv().await {
+                let MemoryLifecycleEvent::Alert(alert) = event else {
+                    continue;
+                };
+                if !self.should_forward(&alert) {
+                    continue;
+                }
+                if let Err(e) = self.send(&alert).await {
+                    tracing::error!("Alert delivery to {} failed: {}", self.config.url, e);
+                }
+            }
+        })
+    }
+
+    /// Whether the alert passes the severity filter and cooldown.
+    /// Records the forward time when it does.
+    pub fn should_forward(&self, alert: &AlertEvent) -> bool {
+        if alert.severity < self.config.min_severity {
+            return false;
+        }
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        let key = alert.dedup_key();
+        let cooldown = Duration::from_secs(self.config.cooldown_secs);
+        if last_sent.get(&key).is_some_and(|at| at.elapsed() < cooldown) {
+            return false;
+        }
+        last_sent.insert(key, Instant::now());
+        true
+    }
+
+    /// Send one alert
+    pub async fn send(&self, alert: &AlertEvent) -> Result<(), WebhookError> {
+        let response = self
+            .client
+            .post(&self.config.url)
+            .json(&self.payload(alert))
+            .send()
+            .await
+            .map_err(|e| WebhookError::Transient(format!("Network error: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_server_error() {
+            Err(WebhookError::Transient(format!("Server error: {}", status)))
+        } else {
+            Err(WebhookError::Permanent(format!("Client error: {}", status)))
+        }
+    }
+
+    /// Request body for an alert in the configured format
+    pub fn payload(&self, alert: &AlertEvent) -> serde_json::Value {
+        match self.config.format {
+            AlertFormat::Json => serde_json::to_value(alert).unwrap_or_default(),
+            AlertFormat::Slack => serde_json::json!({
+                "text": format!(
+                    "[rook] {} {}: {}",
+                    alert.severity.as_str().to_uppercase(),
+                    alert.alert_type(),
+                    alert.message
+                ),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_error_rate_monitor_alerts_once_per_cooldown() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe();
+        let monitor = ErrorRateMonitor::new(
+            ErrorRateConfig {
+                threshold: 0.5,
+                window: 4,
+                min_samples: 4,
+                cooldown_secs: 300,
+            },
+            bus,
+        );
+
+        assert!(monitor.record("llm", true).is_none());
+        assert!(monitor.record("llm", false).is_none());
+        assert!(monitor.record("llm", false).is_none());
+        // 3 errors out of 4 exceeds 50%
+        assert!(monitor.record("llm", false).is_some());
+        // Still failing, but within cooldown
+        assert!(monitor.record("llm", false).is_none());
+        // Other providers are tracked separately
+        assert!(monitor.record("embedder", false).is_none());
+
+        match sub.recv().await.unwrap() {
+            MemoryLifecycleEvent::Alert(alert) => {
+                assert_eq!(alert.alert_type(), "alert.provider_error_rate");
+                assert_eq!(alert.severity, AlertSeverity::Critical);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_low_space_threshold() {
+        let config = DiskSpaceConfig::new("/data");
+        assert!(low_space_alert(&config, 50, 100).is_none());
+
+        let alert = low_space_alert(&config, 8, 100).unwrap();
+        assert_eq!(alert.severity, AlertSeverity::Warning);
+        assert_eq!(alert.alert_type(), "alert.disk_space_low");
+
+        let alert = low_space_alert(&config, 2, 100).unwrap();
+        assert_eq!(alert.severity, AlertSeverity::Critical);
+    }
+
+    #[test]
+    fn test_subscriber_filters_and_formats() {
+        let subscriber = AlertSubscriber::new(AlertSubscriberConfig::new(
+            "https://hooks.slack.com/services/x",
+            AlertFormat::Slack,
+        ));
+
+        let info = AlertEvent::new(
+            AlertSeverity::Info,
+            AlertKind::JobFailed {
+                job: "consolidation".to_string(),
+                error: "boom".to_string(),
+            },
+        );
+        assert!(!subscriber.should_forward(&info));
+
+        let alert = AlertEvent::job_failed("consolidation", "boom");
+        assert!(subscriber.should_forward(&alert));
+        // Same problem again within the cooldown
+        assert!(!subscriber.should_forward(&AlertEvent::job_failed("consolidation", "boom")));
+
+        let payload = subscriber.payload(&alert);
+        let text = payload["text"].as_str().unwrap();
+        assert!(text.contains("WARNING"));
+        assert!(text.contains("Job 'consolidation' failed: boom"));
+    }
+
+    #[test]
+    fn test_alert_serialization() {
+        let alert = AlertEvent::job_failed("intentions", "db locked");
+        let event = MemoryLifecycleEvent::Alert(alert);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "alert");
+        assert_eq!(json["kind"], "job_failed");
+        assert_eq!(json["job"], "intentions");
+    }
+}
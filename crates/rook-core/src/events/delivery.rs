@@ -0,0 +1,382 @@
+//! Persistent webhook delivery state.
+//!
+//! Every delivery is recorded before it is attempted, so deliveries pending
+//! when the process stopped (or held back by an open circuit) are resumed,
+//! and dead-lettered deliveries can be inspected and redelivered. Webhooks
+//! registered at runtime are kept alongside, so they survive a restart.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+use crate::events::webhook::WebhookConfig;
+
+/// State of a webhook delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Not yet delivered; retried by the manager.
+    Pending,
+    /// Accepted by the endpoint.
+    Delivered,
+    /// Failed after all retries; kept for redelivery.
+    DeadLetter,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::DeadLetter => "dead_letter",
+        }
+    }
+
+    fn parse(s: &str) -> RookResult<Self> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "delivered" => Ok(Self::Delivered),
+            "dead_letter" => Ok(Self::DeadLetter),
+            other => Err(RookError::internal(format!(
+                "Unknown delivery status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// One event delivered (or to be delivered) to one webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    /// Delivery ID, sent as `X-Rook-Delivery` so receivers can deduplicate.
+    pub id: String,
+    /// Target webhook.
+    pub webhook_id: String,
+    /// Event type, e.g. `memory.created`.
+    pub event_type: String,
+    /// Serialized event body.
+    pub payload: String,
+    /// Current state.
+    pub status: DeliveryStatus,
+    /// HTTP attempts made so far.
+    pub attempts: u32,
+    /// Error from the last failed attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DeliveryRecord {
+    /// A new pending delivery.
+    pub fn new(
+        webhook_id: impl Into<String>,
+        event_type: impl Into<String>,
+        payload: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            webhook_id: webhook_id.into(),
+            event_type: event_type.into(),
+            payload: payload.into(),
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Delivery counts for one webhook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryCounts {
+    pub pending: u64,
+    pub delivered: u64,
+    pub dead_letter: u64,
+}
+
+/// SQLite-backed webhook delivery log and webhook registry.
+pub struct WebhookDeliveryStore {
+    conn: Mutex<Connection>,
+}
+
+impl WebhookDeliveryStore {
+    /// Open the store at `path` (`:memory:` for an in-memory store).
+    pub fn new(path: impl AsRef<Path>) -> RookResult<Self> {
+        let conn = if path.as_ref().to_str() == Some(":memory:") {
+            Connection::open_in_memory()?
+        } else {
+            if let Some(parent) = path.as_ref().parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Connection::open(path.as_ref())?
+        };
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                id         TEXT PRIMARY KEY,
+                webhook_id TEXT NOT NULL,
+                event_type TEXT NOT NULL,
+                payload    TEXT NOT NULL,
+                status     TEXT NOT NULL,
+                attempts   INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status
+                ON webhook_deliveries(status, created_at);
+            CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook
+                ON webhook_deliveries(webhook_id, status);
+            CREATE TABLE IF NOT EXISTS webhooks (
+                id         TEXT PRIMARY KEY,
+                config     TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            "#,
+        )?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// An in-memory store.
+    pub fn in_memory() -> RookResult<Self> {
+        Self::new(":memory:")
+    }
+
+    /// Insert a delivery, or update the state of an existing one.
+    pub fn save(&self, record: &DeliveryRecord) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO webhook_deliveries
+                (id, webhook_id, event_type, payload, status, attempts, last_error,
+                 created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET
+                status = excluded.status,
+                attempts = excluded.attempts,
+                last_error = excluded.last_error,
+                updated_at = excluded.updated_at",
+            params![
+                record.id,
+                record.webhook_id,
+                record.event_type,
+                record.payload,
+                record.status.as_str(),
+                record.attempts,
+                record.last_error,
+                record.created_at.to_rfc3339(),
+                record.updated_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// A delivery by ID.
+    pub fn get(&self, id: &str) -> RookResult<Option<DeliveryRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                "SELECT id, webhook_id, event_type, payload, status, attempts, last_error,
+                        created_at, updated_at
+                 FROM webhook_deliveries WHERE id = ?1",
+                [id],
+                Self::read_row,
+            )
+            .optional()?;
+        Ok(row)
+    }
+
+    /// Deliveries in a state, optionally for one webhook.
+    ///
+    /// Pending deliveries are returned oldest first (delivery order); others
+    /// newest first.
+    pub fn list(
+        &self,
+        status: DeliveryStatus,
+        webhook_id: Option<&str>,
+        limit: usize,
+    ) -> RookResult<Vec<DeliveryRecord>> {
+        let order = if status == DeliveryStatus::Pending {
+            "ASC"
+        } else {
+            "DESC"
+        };
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, webhook_id, event_type, payload, status, attempts, last_error,
+                    created_at, updated_at
+             FROM webhook_deliveries
+             WHERE status = ?1 AND (?2 IS NULL OR webhook_id = ?2)
+             ORDER BY created_at {} LIMIT ?3",
+            order
+        ))?;
+        let rows = stmt
+            .query_map(
+                params![status.as_str(), webhook_id, limit as i64],
+                Self::read_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Delivery counts by state for one webhook.
+    pub fn counts(&self, webhook_id: &str) -> RookResult<DeliveryCounts> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT status, COUNT(*) FROM webhook_deliveries
+             WHERE webhook_id = ?1 GROUP BY status",
+        )?;
+        let rows = stmt
+            .query_map([webhook_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut counts = DeliveryCounts::default();
+        for (status, count) in rows {
+            let count = count as u64;
+            match DeliveryStatus::parse(&status)? {
+                DeliveryStatus::Pending => counts.pending = count,
+                DeliveryStatus::Delivered => counts.delivered = count,
+                DeliveryStatus::DeadLetter => counts.dead_letter = count,
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Delete delivered records last updated before `before`. Returns the
+    /// number deleted.
+    pub fn prune_delivered(&self, before: DateTime<Utc>) -> RookResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute(
+            "DELETE FROM webhook_deliveries WHERE status = 'delivered' AND updated_at < ?1",
+            [before.to_rfc3339()],
+        )?)
+    }
+
+    /// Insert a webhook, or replace the config of an existing one.
+    pub fn save_webhook(&self, config: &WebhookConfig) -> RookResult<()> {
+        let json = serde_json::to_string(config)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO webhooks (id, config, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(id) DO UPDATE SET config = excluded.config",
+            params![config.id, json, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Registered webhooks, oldest first.
+    pub fn list_webhooks(&self) -> RookResult<Vec<WebhookConfig>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT config FROM webhooks ORDER BY created_at, id")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.iter()
+            .map(|json| serde_json::from_str(json).map_err(RookError::from))
+            .collect()
+    }
+
+    /// Delete a webhook. Returns whether it existed.
+    ///
+    /// Its delivery records are kept; pending ones are dead-lettered by the
+    /// manager's next resume pass.
+    pub fn delete_webhook(&self, id: &str) -> RookResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        Ok(conn.execute("DELETE FROM webhooks WHERE id = ?1", [id])? > 0)
+    }
+
+    fn read_row(row: &Row<'_>) -> rusqlite::Result<DeliveryRecord> {
+        let text = |idx: usize, e: RookError| {
+            rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e))
+        };
+        Ok(DeliveryRecord {
+            id: row.get(0)?,
+            webhook_id: row.get(1)?,
+            event_type: row.get(2)?,
+            payload: row.get(3)?,
+            status: DeliveryStatus::parse(&row.get::<_, String>(4)?).map_err(|e| text(4, e))?,
+            attempts: row.get(5)?,
+            last_error: row.get(6)?,
+            created_at: parse_time(&row.get::<_, String>(7)?).map_err(|e| text(7, e))?,
+            updated_at: parse_time(&row.get::<_, String>(8)?).map_err(|e| text(8, e))?,
+        })
+    }
+}
+
+fn parse_time(s: &str) -> RookResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| RookError::internal(format!("Invalid delivery timestamp '{}': {}", s, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_store_roundtrip() {
+        let store = WebhookDeliveryStore::in_memory().unwrap();
+        let mut first = DeliveryRecord::new("hook-1", "memory.created", r#"{"a":1}"#);
+        let second = DeliveryRecord::new("hook-1", "memory.updated", r#"{"a":2}"#);
+        let other = DeliveryRecord::new("hook-2", "memory.created", r#"{"a":3}"#);
+        store.save(&first).unwrap();
+        store.save(&second).unwrap();
+        store.save(&other).unwrap();
+
+        let pending = store.list(DeliveryStatus::Pending, Some("hook-1"), 10).unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].id, first.id);
+
+        first.status = DeliveryStatus::DeadLetter;
+        first.attempts = 6;
+        first.last_error = Some("Server error: 503".to_string());
+        store.save(&first).unwrap();
+
+        let loaded = store.get(&first.id).unwrap().unwrap();
+        assert_eq!(loaded.status, DeliveryStatus::DeadLetter);
+        assert_eq!(loaded.attempts, 6);
+        assert_eq!(loaded.payload, r#"{"a":1}"#);
+
+        let counts = store.counts("hook-1").unwrap();
+        assert_eq!(counts.pending, 1);
+        assert_eq!(counts.dead_letter, 1);
+        assert_eq!(store.list(DeliveryStatus::DeadLetter, None, 10).unwrap().len(), 1);
+        assert!(store.get("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_webhook_registry_roundtrip() {
+        let store = WebhookDeliveryStore::in_memory().unwrap();
+        let first = WebhookConfig::new("https://example.com/a").with_secret("s3cret");
+        let second =
+            WebhookConfig::new("https://example.com/b").with_events(vec!["memory.created"]);
+        store.save_webhook(&first).unwrap();
+        store.save_webhook(&second).unwrap();
+
+        let mut updated = first.clone();
+        updated.enabled = false;
+        store.save_webhook(&updated).unwrap();
+
+        let webhooks = store.list_webhooks().unwrap();
+        assert_eq!(webhooks.len(), 2);
+        let loaded = webhooks.iter().find(|w| w.id == first.id).unwrap();
+        assert!(!loaded.enabled);
+        assert_eq!(loaded.secret.as_deref(), Some("s3cret"));
+
+        assert!(store.delete_webhook(&second.id).unwrap());
+        assert!(!store.delete_webhook(&second.id).unwrap());
+        assert_eq!(store.list_webhooks().unwrap().len(), 1);
+    }
+}
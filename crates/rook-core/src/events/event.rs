@@ -6,6 +6,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::alert::AlertEvent;
+
 /// Memory lifecycle events
 ///
 /// These events are emitted to the event bus when memory operations occur,
@@ -21,6 +23,8 @@ pub enum MemoryLifecycleEvent {
     Deleted(MemoryDeletedEvent),
     /// Memory was accessed (INT-13)
     Accessed(MemoryAccessedEvent),
+    /// Operational alert (not tied to a memory)
+    Alert(AlertEvent),
 }
 
 impl MemoryLifecycleEvent {
@@ -31,16 +35,29 @@ impl MemoryLifecycleEvent {
             Self::Updated(_) => "memory.updated",
             Self::Deleted(_) => "memory.deleted",
             Self::Accessed(_) => "memory.accessed",
+            Self::Alert(e) => e.alert_type(),
+        }
+    }
+
+    /// Get the unique ID of this event
+    pub fn event_id(&self) -> &str {
+        match self {
+            Self::Created(e) => &e.event_id,
+            Self::Updated(e) => &e.event_id,
+            Self::Deleted(e) => &e.event_id,
+            Self::Accessed(e) => &e.event_id,
+            Self::Alert(e) => &e.event_id,
         }
     }
 
-    /// Get the memory ID this event relates to
+    /// Get the memory ID this event relates to (empty for alerts)
     pub fn memory_id(&self) -> &str {
         match self {
             Self::Created(e) => &e.memory_id,
             Self::Updated(e) => &e.memory_id,
             Self::Deleted(e) => &e.memory_id,
             Self::Accessed(e) => &e.memory_id,
+            Self::Alert(_) => "",
         }
     }
 
@@ -51,6 +68,7 @@ impl MemoryLifecycleEvent {
             Self::Updated(e) => e.timestamp,
             Self::Deleted(e) => e.timestamp,
             Self::Accessed(e) => e.timestamp,
+            Self::Alert(e) => e.timestamp,
         }
     }
 
@@ -61,6 +79,7 @@ impl MemoryLifecycleEvent {
             Self::Updated(e) => e.user_id.as_deref(),
             Self::Deleted(e) => e.user_id.as_deref(),
             Self::Accessed(e) => e.user_id.as_deref(),
+            Self::Alert(_) => None,
         }
     }
 }
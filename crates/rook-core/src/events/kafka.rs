@@ -0,0 +1,134 @@
+//! Kafka event sink
+//!
+//! Publishes with an idempotent producer and `acks=all`, so an event is
+//! only acknowledged once every in-sync replica has it. Events of a memory
+//! share its ID as key and land on one partition, in order.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use serde::{Deserialize, Serialize};
+
+use super::sink::{EventSink, SinkError, SinkMessage};
+use crate::error::{RookError, RookResult};
+
+/// Kafka connection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaSinkConfig {
+    /// Comma-separated bootstrap servers (default: `localhost:9092`)
+    pub brokers: String,
+    /// How long to wait for a broker acknowledgement before retrying
+    /// (default: 30)
+    pub timeout_secs: u64,
+    /// Further librdkafka properties (e.g. `security.protocol`,
+    /// `sasl.username`), overriding the defaults
+    pub properties: HashMap<String, String>,
+}
+
+impl Default for KafkaSinkConfig {
+    fn default() -> Self {
+        Self {
+            brokers: "localhost:9092".to_string(),
+            timeout_secs: 30,
+            properties: HashMap::new(),
+        }
+    }
+}
+
+impl KafkaSinkConfig {
+    pub fn new(brokers: impl Into<String>) -> Self {
+        Self {
+            brokers: brokers.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set a librdkafka property
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Publishes events to Kafka topics
+pub struct KafkaSink {
+    producer: FutureProducer,
+    timeout: Duration,
+}
+
+impl KafkaSink {
+    pub fn new(config: &KafkaSinkConfig) -> RookResult<Self> {
+        let mut client = ClientConfig::new();
+        client
+            .set("bootstrap.servers", &config.brokers)
+            .set("client.id", "rook")
+            .set("acks", "all")
+            .set("enable.idempotence", "true")
+            .set(
+                "message.timeout.ms",
+                (config.timeout_secs * 1000).to_string(),
+            );
+        for (key, value) in &config.properties {
+            client.set(key, value);
+        }
+
+        let producer = client.create().map_err(|e| {
+            RookError::Configuration(format!("Failed to create Kafka producer: {}", e))
+        })?;
+        Ok(Self {
+            producer,
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, message: &SinkMessage) -> Result<(), SinkError> {
+        let headers =
+            message
+                .headers()
+                .into_iter()
+                .fold(OwnedHeaders::new(), |headers, (key, value)| {
+                    headers.insert(Header {
+                        key,
+                        value: Some(value),
+                    })
+                });
+        let record = FutureRecord::to(&message.topic)
+            .key(&message.key)
+            .payload(&message.payload)
+            .headers(headers);
+
+        self.producer
+            .send(record, self.timeout)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| classify(e))
+    }
+}
+
+/// Messages the broker will never accept are permanent failures
+fn classify(error: KafkaError) -> SinkError {
+    match error.rdkafka_error_code() {
+        Some(
+            RDKafkaErrorCode::MessageSizeTooLarge
+            | RDKafkaErrorCode::InvalidMessage
+            | RDKafkaErrorCode::InvalidMessageSize
+            | RDKafkaErrorCode::TopicAuthorizationFailed
+            | RDKafkaErrorCode::ClusterAuthorizationFailed
+            | RDKafkaErrorCode::TopicException,
+        ) => SinkError::Permanent(error.to_string()),
+        _ => SinkError::Transient(error.to_string()),
+    }
+}
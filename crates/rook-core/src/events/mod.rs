@@ -4,14 +4,39 @@
 //! - Event types for memory operations (created, updated, deleted, accessed)
 //! - Event bus for internal pub/sub
 //! - Webhook delivery for external integrations
+//! - Operational alerts and alert forwarding
+//! - Event forwarding to Kafka or NATS (`kafka` and `nats` features)
 
+mod alert;
 mod bus;
+mod delivery;
 mod event;
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+mod sink;
 mod webhook;
 
+pub use alert::{
+    AlertEvent, AlertFormat, AlertKind, AlertSeverity, AlertSubscriber, AlertSubscriberConfig,
+    DiskSpaceConfig, DiskSpaceMonitor, ErrorRateConfig, ErrorRateMonitor,
+};
 pub use bus::{EventBus, EventSubscriber};
 pub use event::{
     AccessType, MemoryAccessedEvent, MemoryCreatedEvent, MemoryDeletedEvent, MemoryLifecycleEvent,
     MemoryUpdatedEvent, UpdateType,
 };
-pub use webhook::{verify_signature, RetryPolicy, WebhookConfig, WebhookDelivery, WebhookError, WebhookManager};
+pub use delivery::{DeliveryCounts, DeliveryRecord, DeliveryStatus, WebhookDeliveryStore};
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaSink, KafkaSinkConfig};
+#[cfg(feature = "nats")]
+pub use nats::{NatsSink, NatsSinkConfig};
+pub use sink::{
+    EventFormat, EventForwarder, EventSink, EventSinkConfig, SinkError, SinkMessage, SinkMetrics,
+    EVENT_ID_HEADER, EVENT_TYPE_HEADER,
+};
+pub use webhook::{
+    verify_signature, CircuitBreakerConfig, CircuitState, RetryPolicy, WebhookConfig,
+    WebhookDelivery, WebhookError, WebhookManager, WebhookMetrics,
+};
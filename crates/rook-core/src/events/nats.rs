@@ -0,0 +1,106 @@
+//! NATS JetStream event sink
+//!
+//! Publishes to JetStream and waits for the stream's acknowledgement. The
+//! event ID is sent as `Nats-Msg-Id`, so the stream drops duplicates of a
+//! retried publish within its duplicate window.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_nats::jetstream::{self, context::Publish};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::sink::{EventSink, SinkError, SinkMessage};
+use crate::error::{RookError, RookResult};
+
+/// NATS connection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NatsSinkConfig {
+    /// Server URL (default: `nats://localhost:4222`)
+    pub url: String,
+    /// Credentials file for NKey/JWT authentication
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials_file: Option<PathBuf>,
+    /// How long to wait for a stream acknowledgement before retrying
+    /// (default: 30)
+    pub timeout_secs: u64,
+}
+
+impl Default for NatsSinkConfig {
+    fn default() -> Self {
+        Self {
+            url: "nats://localhost:4222".to_string(),
+            credentials_file: None,
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl NatsSinkConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_credentials_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.credentials_file = Some(path.into());
+        self
+    }
+}
+
+/// Publishes events to subjects of a JetStream stream
+///
+/// The stream capturing the subjects must exist; publishes to subjects no
+/// stream captures fail and are retried.
+pub struct NatsSink {
+    jetstream: jetstream::Context,
+}
+
+impl NatsSink {
+    /// Connect to the NATS server
+    pub async fn connect(config: &NatsSinkConfig) -> RookResult<Self> {
+        let mut options = async_nats::ConnectOptions::new().name("rook");
+        if let Some(ref path) = config.credentials_file {
+            options = options.credentials_file(path).await.map_err(|e| {
+                RookError::Configuration(format!("Failed to read NATS credentials: {}", e))
+            })?;
+        }
+        let client = options
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| RookError::api(format!("Failed to connect to NATS: {}", e)))?;
+
+        let mut jetstream = jetstream::new(client);
+        jetstream.set_timeout(Duration::from_secs(config.timeout_secs));
+        Ok(Self { jetstream })
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, message: &SinkMessage) -> Result<(), SinkError> {
+        let publish = message.headers().into_iter().fold(
+            Publish::build()
+                .payload(message.payload.clone().into())
+                .message_id(message.id.as_str()),
+            |publish, (key, value)| publish.header(key, value),
+        );
+
+        let ack = self
+            .jetstream
+            .send_publish(message.topic.clone(), publish)
+            .await
+            .map_err(|e| SinkError::Transient(e.to_string()))?;
+        ack.await
+            .map(|_| ())
+            .map_err(|e| SinkError::Transient(e.to_string()))
+    }
+}
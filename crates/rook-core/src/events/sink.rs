@@ -0,0 +1,475 @@
+//! Forwarding of lifecycle events to message brokers
+//!
+//! An [`EventForwarder`] subscribes to the event bus and publishes each
+//! event to an [`EventSink`]: Kafka with the `kafka` feature, NATS
+//! JetStream with the `nats` feature, or any other implementation.
+//!
+//! Delivery is at-least-once. Events are published one at a time, in the
+//! order they were emitted, and an event is only let go once the broker has
+//! acknowledged it; transient failures are retried with backoff until they
+//! succeed. Consumers should deduplicate by the event ID, sent as the
+//! `rook-event-id` header. Events emitted while the bus is down or while the
+//! forwarder is so far behind that the bus drops them are not recovered.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use serde::{Deserialize, Serialize};
+
+use super::{EventBus, MemoryLifecycleEvent, RetryPolicy};
+
+/// Header carrying the event ID
+pub const EVENT_ID_HEADER: &str = "rook-event-id";
+
+/// Header carrying the event type, e.g. `memory.created`
+pub const EVENT_TYPE_HEADER: &str = "rook-event-type";
+
+/// Error publishing to a sink
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SinkError {
+    /// Broker unavailable, timed out, ... - should retry
+    #[error("Transient error: {0}")]
+    Transient(String),
+    /// Message rejected (too large, not authorized, ...) - should not retry
+    #[error("Permanent error: {0}")]
+    Permanent(String),
+}
+
+/// A serialized event ready for publishing
+#[derive(Debug, Clone)]
+pub struct SinkMessage {
+    /// Kafka topic or NATS subject
+    pub topic: String,
+    /// Partition key: the memory ID, or the event type for alerts
+    pub key: String,
+    /// Unique event ID, for deduplication by consumers
+    pub id: String,
+    /// Event type, e.g. `memory.created`
+    pub event_type: String,
+    /// MIME type of the payload
+    pub content_type: &'static str,
+    /// Serialized event
+    pub payload: Vec<u8>,
+}
+
+impl SinkMessage {
+    /// Headers sent with the message
+    pub fn headers(&self) -> [(&'static str, &str); 3] {
+        [
+            (EVENT_ID_HEADER, self.id.as_str()),
+            (EVENT_TYPE_HEADER, self.event_type.as_str()),
+            ("content-type", self.content_type),
+        ]
+    }
+}
+
+/// Destination for forwarded events
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Short name used in logs, e.g. `kafka`
+    fn name(&self) -> &str;
+
+    /// Publish one message, returning once the broker has acknowledged it
+    async fn publish(&self, message: &SinkMessage) -> Result<(), SinkError>;
+}
+
+/// How events are serialized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventFormat {
+    /// The event as sent to webhooks
+    #[default]
+    Json,
+    /// A CloudEvents 1.0 envelope in structured JSON mode, with the event as
+    /// its `data`
+    CloudEvents,
+}
+
+/// Settings of an [`EventForwarder`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventSinkConfig {
+    /// Topic or subject events are published to. `{type}` is replaced by the
+    /// event type, e.g. `rook.{type}` publishes to `rook.memory.created`
+    /// (default: `rook.events`)
+    pub topic: String,
+    /// Event types to forward; empty forwards all events
+    pub events: Vec<String>,
+    /// How events are serialized (default: JSON)
+    pub format: EventFormat,
+    /// Backoff between attempts. After `max_retries` attempts the event is
+    /// retried every `max_delay_ms` until it is published
+    pub retry: RetryPolicy,
+}
+
+impl Default for EventSinkConfig {
+    fn default() -> Self {
+        Self {
+            topic: "rook.events".to_string(),
+            events: Vec::new(),
+            format: EventFormat::default(),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl EventSinkConfig {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Forward only these event types
+    pub fn with_events(mut self, events: Vec<&str>) -> Self {
+        self.events = events.into_iter().map(String::from).collect();
+        self
+    }
+
+    pub fn with_format(mut self, format: EventFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Check if an event type should be forwarded
+    pub fn should_forward(&self, event_type: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event_type)
+    }
+
+    /// Serialize an event for publishing
+    pub fn message(&self, event: &MemoryLifecycleEvent) -> Result<SinkMessage, SinkError> {
+        let event_type = event.event_type();
+        let (content_type, payload) = match self.format {
+            EventFormat::Json => ("application/json", serde_json::to_vec(event)),
+            EventFormat::CloudEvents => {
+                let mut envelope = serde_json::json!({
+                    "specversion": "1.0",
+                    "id": event.event_id(),
+                    "source": "rook",
+                    "type": event_type,
+                    "time": event.timestamp().to_rfc3339(),
+                    "datacontenttype": "application/json",
+                    "data": event,
+                });
+                if !event.memory_id().is_empty() {
+                    envelope["subject"] = event.memory_id().into();
+                }
+                (
+                    "application/cloudevents+json",
+                    serde_json::to_vec(&envelope),
+                )
+            }
+        };
+        let payload = payload.map_err(|e| SinkError::Permanent(e.to_string()))?;
+
+        let key = match event.memory_id() {
+            "" => event_type,
+            id => id,
+        };
+        Ok(SinkMessage {
+            topic: self.topic.replace("{type}", event_type),
+            key: key.to_string(),
+            id: event.event_id().to_string(),
+            event_type: event_type.to_string(),
+            content_type,
+            payload,
+        })
+    }
+}
+
+/// Counts of a forwarder's publishes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SinkMetrics {
+    /// Events published
+    pub published: u64,
+    /// Failed publish attempts that were retried
+    pub retried: u64,
+    /// Events dropped after a permanent error
+    pub dropped: u64,
+}
+
+#[derive(Default)]
+struct SinkCounters {
+    published: AtomicU64,
+    retried: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Forwards events from the event bus to an [`EventSink`]
+///
+/// # Example
+///
+/// ```ignore
+/// use rook_core::events::{EventForwarder, EventSinkConfig, KafkaSink, KafkaSinkConfig};
+///
+/// let sink = KafkaSink::new(&KafkaSinkConfig::new("localhost:9092"))?;
+/// let forwarder = EventForwarder::new(Arc::new(sink), EventSinkConfig::new("rook.{type}"));
+/// forwarder.start(&event_bus);
+/// ```
+#[derive(Clone)]
+pub struct EventForwarder {
+    sink: Arc<dyn EventSink>,
+    config: Arc<EventSinkConfig>,
+    counters: Arc<SinkCounters>,
+}
+
+impl EventForwarder {
+    pub fn new(sink: Arc<dyn EventSink>, config: EventSinkConfig) -> Self {
+        Self {
+            sink,
+            config: Arc::new(config),
+            counters: Arc::new(SinkCounters::default()),
+        }
+    }
+
+    /// Start forwarding events from the event bus
+    ///
+    /// Returns a handle that can be used to stop forwarding
+    pub fn start(&self, event_bus: &EventBus) -> tokio::task::JoinHandle<()> {
+        let forwarder = self.clone();
+        let mut subscriber = event_bus.subscribe();
+        tokio::spawn(async move {
+            while let Some(event) = subscriber.recv().await {
+                forwarder.forward(&event).await;
+            }
+        })
+    }
+
+    /// Publish one event, retrying transient failures until it is published
+    ///
+    /// Returns whether the event was published; filtered out events and
+    /// events dropped after a permanent error are not.
+    pub async fn forward(&self, event: &MemoryLifecycleEvent) -> bool {
+        if !self.config.should_forward(event.event_type()) {
+            return false;
+        }
+        let message = match self.config.message(event) {
+            Ok(message) => message,
+            Err(e) => return self.drop_event(&e, event),
+        };
+
+        let policy = &self.config.retry;
+        let max_delay = Duration::from_millis(policy.max_delay_ms);
+        loop {
+            let publish = || self.sink.publish(&message);
+            let result = publish
+                .retry(
+                    ExponentialBuilder::default()
+                        .with_max_times(policy.max_retries as usize)
+                        .with_min_delay(Duration::from_millis(policy.initial_delay_ms))
+                        .with_max_delay(max_delay)
+                        .with_factor(policy.multiplier),
+                )
+                .when(|e| matches!(e, SinkError::Transient(_)))
+                .notify(|err, dur| {
+                    self.counters.retried.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "Publishing {} event to {} failed, retrying in {:?}: {}",
+                        message.event_type,
+                        self.sink.name(),
+                        dur,
+                        err
+                    );
+                })
+                .await;
+
+            match result {
+                Ok(()) => {
+                    self.counters.published.fetch_add(1, Ordering::Relaxed);
+                    return true;
+                }
+                Err(SinkError::Transient(e)) => {
+                    self.counters.retried.fetch_add(1, Ordering::Relaxed);
+                    tracing::error!(
+                        "{} unavailable, retrying {} event every {:?}: {}",
+                        self.sink.name(),
+                        message.event_type,
+                        max_delay,
+                        e
+                    );
+                    tokio::time::sleep(max_delay).await;
+                }
+                Err(e) => return self.drop_event(&e, event),
+            }
+        }
+    }
+
+    /// Publish counts so far
+    pub fn metrics(&self) -> SinkMetrics {
+        SinkMetrics {
+            published: self.counters.published.load(Ordering::Relaxed),
+            retried: self.counters.retried.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    fn drop_event(&self, error: &SinkError, event: &MemoryLifecycleEvent) -> bool {
+        self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+        tracing::error!(
+            "Dropping {} event {} for {}: {}",
+            event.event_type(),
+            event.event_id(),
+            self.sink.name(),
+            error
+        );
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{AlertEvent, AlertKind, AlertSeverity, MemoryCreatedEvent};
+    use std::sync::Mutex;
+
+    /// Sink failing with `errors` before accepting messages
+    struct MockSink {
+        errors: Mutex<Vec<SinkError>>,
+        published: Mutex<Vec<SinkMessage>>,
+    }
+
+    impl MockSink {
+        fn new(errors: Vec<SinkError>) -> Arc<Self> {
+            Arc::new(Self {
+                errors: Mutex::new(errors),
+                published: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl EventSink for MockSink {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn publish(&self, message: &SinkMessage) -> Result<(), SinkError> {
+            if let Some(e) = self.errors.lock().unwrap().pop() {
+                return Err(e);
+            }
+            self.published.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 1,
+            initial_delay_ms: 1,
+            max_delay_ms: 1,
+            multiplier: 1.0,
+        }
+    }
+
+    fn created() -> MemoryLifecycleEvent {
+        MemoryLifecycleEvent::Created(MemoryCreatedEvent::new("mem-1", "Hello"))
+    }
+
+    #[test]
+    fn test_json_message() {
+        let config = EventSinkConfig::new("rook.{type}");
+        let event = created();
+        let message = config.message(&event).unwrap();
+
+        assert_eq!(message.topic, "rook.memory.created");
+        assert_eq!(message.key, "mem-1");
+        assert_eq!(message.id, event.event_id());
+        assert_eq!(message.content_type, "application/json");
+        let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(payload["type"], "created");
+        assert_eq!(payload["memory_id"], "mem-1");
+    }
+
+    #[test]
+    fn test_cloud_events_message() {
+        let config = EventSinkConfig::default().with_format(EventFormat::CloudEvents);
+        let event = created();
+        let message = config.message(&event).unwrap();
+
+        assert_eq!(message.topic, "rook.events");
+        assert_eq!(message.content_type, "application/cloudevents+json");
+        let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+        assert_eq!(payload["specversion"], "1.0");
+        assert_eq!(payload["id"], event.event_id());
+        assert_eq!(payload["type"], "memory.created");
+        assert_eq!(payload["subject"], "mem-1");
+        assert_eq!(payload["data"]["content"], "Hello");
+
+        // Alerts have no memory; they are keyed by type
+        let alert = MemoryLifecycleEvent::Alert(AlertEvent::new(
+            AlertSeverity::Warning,
+            AlertKind::JobFailed {
+                job: "replay".to_string(),
+                error: "boom".to_string(),
+            },
+        ));
+        let message = config.message(&alert).unwrap();
+        assert_eq!(message.key, "alert.job_failed");
+        let payload: serde_json::Value = serde_json::from_slice(&message.payload).unwrap();
+        assert!(payload.get("subject").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forward_retries_until_published() {
+        let sink = MockSink::new(vec![
+            SinkError::Transient("down".to_string()),
+            SinkError::Transient("down".to_string()),
+            SinkError::Transient("down".to_string()),
+        ]);
+        let forwarder = EventForwarder::new(
+            sink.clone(),
+            EventSinkConfig::default().with_retry(fast_retry()),
+        );
+
+        assert!(forwarder.forward(&created()).await);
+        assert_eq!(sink.published.lock().unwrap().len(), 1);
+        let metrics = forwarder.metrics();
+        assert_eq!(metrics.published, 1);
+        assert_eq!(metrics.retried, 3);
+        assert_eq!(metrics.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_forward_drops_on_permanent_error() {
+        let sink = MockSink::new(vec![SinkError::Permanent("too large".to_string())]);
+        let forwarder = EventForwarder::new(
+            sink.clone(),
+            EventSinkConfig::default().with_retry(fast_retry()),
+        );
+
+        assert!(!forwarder.forward(&created()).await);
+        assert!(sink.published.lock().unwrap().is_empty());
+        assert_eq!(forwarder.metrics().dropped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_forwarder_filters_events() {
+        let sink = MockSink::new(Vec::new());
+        let forwarder = EventForwarder::new(
+            sink.clone(),
+            EventSinkConfig::default().with_events(vec!["memory.deleted"]),
+        );
+        let bus = EventBus::new();
+        let handle = forwarder.start(&bus);
+
+        bus.emit(created());
+        bus.emit(MemoryLifecycleEvent::Deleted(
+            crate::events::MemoryDeletedEvent::new("mem-1", false),
+        ));
+        drop(bus);
+        handle.await.unwrap();
+
+        let published = sink.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].event_type, "memory.deleted");
+    }
+}
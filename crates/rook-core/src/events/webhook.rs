@@ -4,18 +4,33 @@
 //! - HMAC-SHA256 payload signing for verification
 //! - Exponential backoff retry on transient failures
 //! - Event type filtering
+//! - Per-endpoint circuit breaking and delivery metrics
+//! - Optional persistent delivery log, so pending deliveries resume after a
+//!   restart and dead-lettered ones can be redelivered
 
-use crate::events::{EventBus, MemoryLifecycleEvent};
+use crate::error::{RookError, RookResult};
+use crate::events::delivery::{
+    DeliveryCounts, DeliveryRecord, DeliveryStatus, WebhookDeliveryStore,
+};
+use crate::events::{AlertEvent, AlertKind, AlertSeverity, EventBus, MemoryLifecycleEvent};
 use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
 use hmac::{Hmac, Mac};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::collections::HashSet;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+/// Error recorded for deliveries held back by an open circuit
+const CIRCUIT_OPEN: &str = "Circuit open";
+
+/// Pending deliveries resumed per pass
+const RESUME_BATCH: usize = 100;
+
 /// Error type for webhook delivery
 #[derive(Debug, Clone)]
 pub enum WebhookError {
@@ -63,6 +78,135 @@ impl Default for RetryPolicy {
     }
 }
 
+/// Circuit breaker configuration
+///
+/// After `failure_threshold` consecutive failed deliveries the circuit opens
+/// and new deliveries are held back. Once `cooldown_secs` have passed, one
+/// trial delivery is let through: success closes the circuit, failure opens
+/// it again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed deliveries that open the circuit (0 disables it)
+    pub failure_threshold: u32,
+    /// Seconds to wait before a trial delivery
+    pub cooldown_secs: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown_secs: 60,
+        }
+    }
+}
+
+/// Circuit breaker state of an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Deliveries flow normally
+    Closed,
+    /// Deliveries are held back
+    Open,
+    /// Cooldown elapsed; the next delivery is a trial
+    HalfOpen,
+}
+
+/// Delivery metrics for one webhook endpoint
+///
+/// Counters cover deliveries made since the manager started.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookMetrics {
+    pub webhook_id: String,
+    pub url: String,
+    pub circuit: CircuitState,
+    pub consecutive_failures: u32,
+    /// Deliveries accepted by the endpoint
+    pub delivered: u64,
+    /// Deliveries that failed after all retries
+    pub failed: u64,
+    /// HTTP requests made, including retries
+    pub attempts: u64,
+    /// Mean time to a successful delivery, including retries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_latency_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_success_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_failure_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Persisted delivery counts, when a delivery store is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deliveries: Option<DeliveryCounts>,
+}
+
+/// Circuit and counters of one endpoint
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    trial_in_flight: bool,
+    delivered: u64,
+    failed: u64,
+    attempts: u64,
+    latency_ms_total: u64,
+    last_success_at: Option<DateTime<Utc>>,
+    last_failure_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+impl EndpointHealth {
+    fn state(&self, config: &CircuitBreakerConfig) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(at) if at.elapsed() < Duration::from_secs(config.cooldown_secs) => {
+                CircuitState::Open
+            }
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a delivery may be attempted now
+    fn try_acquire(&mut self, config: &CircuitBreakerConfig) -> bool {
+        match self.state(config) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen if self.trial_in_flight => false,
+            CircuitState::HalfOpen => {
+                self.trial_in_flight = true;
+                true
+            }
+        }
+    }
+
+    fn record_success(&mut self, attempts: u32, latency: Duration) {
+        self.attempts += attempts as u64;
+        self.delivered += 1;
+        self.latency_ms_total += latency.as_millis() as u64;
+        self.last_success_at = Some(Utc::now());
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+        self.trial_in_flight = false;
+    }
+
+    fn record_failure(&mut self, config: &CircuitBreakerConfig, attempts: u32, error: String) {
+        self.attempts += attempts as u64;
+        self.failed += 1;
+        self.last_failure_at = Some(Utc::now());
+        self.last_error = Some(error);
+        self.consecutive_failures += 1;
+
+        let tripped = config.failure_threshold > 0
+            && (self.trial_in_flight || self.consecutive_failures >= config.failure_threshold);
+        if tripped {
+            self.opened_at = Some(Instant::now());
+        }
+        self.trial_in_flight = false;
+    }
+}
+
 /// Webhook endpoint configuration (INT-14)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookConfig {
@@ -79,6 +223,9 @@ pub struct WebhookConfig {
     /// Retry policy
     #[serde(default)]
     pub retry_policy: RetryPolicy,
+    /// Circuit breaker
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
     /// Request timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_secs: u64,
@@ -104,6 +251,7 @@ impl WebhookConfig {
             secret: None,
             events: HashSet::new(),
             retry_policy: RetryPolicy::default(),
+            circuit_breaker: CircuitBreakerConfig::default(),
             timeout_secs: 30,
             enabled: true,
         }
@@ -127,6 +275,39 @@ impl WebhookConfig {
         self
     }
 
+    /// Builder: set circuit breaker
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = config;
+        self
+    }
+
+    /// Check that the endpoint and delivery settings are usable
+    pub fn validate(&self) -> RookResult<()> {
+        let url = reqwest::Url::parse(&self.url).map_err(|e| {
+            RookError::validation(format!("Invalid webhook URL '{}': {}", self.url, e))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(RookError::validation(format!(
+                "Webhook URL must be http or https, got '{}'",
+                url.scheme()
+            )));
+        }
+        if self.timeout_secs == 0 {
+            return Err(RookError::validation("Webhook timeout_secs must be positive"));
+        }
+        if self.retry_policy.multiplier < 1.0 {
+            return Err(RookError::validation(
+                "Webhook retry multiplier must be at least 1.0",
+            ));
+        }
+        if self.retry_policy.initial_delay_ms > self.retry_policy.max_delay_ms {
+            return Err(RookError::validation(
+                "Webhook retry initial_delay_ms must not exceed max_delay_ms",
+            ));
+        }
+        Ok(())
+    }
+
     /// Check if this webhook should receive the given event type
     pub fn should_receive(&self, event_type: &str) -> bool {
         self.enabled && (self.events.is_empty() || self.events.contains(event_type))
@@ -137,6 +318,7 @@ impl WebhookConfig {
 pub struct WebhookDelivery {
     client: Client,
     config: WebhookConfig,
+    health: Arc<Mutex<EndpointHealth>>,
 }
 
 impl WebhookDelivery {
@@ -147,7 +329,11 @@ impl WebhookDelivery {
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, config }
+        Self {
+            client,
+            config,
+            health: Arc::new(Mutex::new(EndpointHealth::default())),
+        }
     }
 
     /// Deliver an event to the webhook endpoint
@@ -162,18 +348,33 @@ impl WebhookDelivery {
         let payload = serde_json::to_string(event)
             .map_err(|e| WebhookError::Config(format!("Serialization error: {}", e)))?;
 
-        let signature = self.sign_payload(&payload);
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        self.send(&delivery_id, event_type, &payload).await.1
+    }
+
+    /// Send a serialized event with retries, recording the outcome in the
+    /// endpoint's circuit and metrics. Returns the number of HTTP attempts.
+    async fn send(
+        &self,
+        delivery_id: &str,
+        event_type: &str,
+        payload: &str,
+    ) -> (u32, Result<(), WebhookError>) {
+        let signature = self.sign_payload(payload);
+        let attempts = AtomicU32::new(0);
+        let started = Instant::now();
 
         // Create delivery closure for retry
         let deliver_once = || async {
+            attempts.fetch_add(1, Ordering::Relaxed);
             let response = self
                 .client
                 .post(&self.config.url)
                 .header("Content-Type", "application/json")
                 .header("X-Rook-Signature", &signature)
                 .header("X-Rook-Event", event_type)
-                .header("X-Rook-Delivery", uuid::Uuid::new_v4().to_string())
-                .body(payload.clone())
+                .header("X-Rook-Delivery", delivery_id)
+                .body(payload.to_string())
                 .send()
                 .await
                 .map_err(|e| WebhookError::Transient(format!("Network error: {}", e)))?;
@@ -196,7 +397,7 @@ impl WebhookDelivery {
 
         // Apply retry policy
         let policy = &self.config.retry_policy;
-        deliver_once
+        let result = deliver_once
             .retry(
                 ExponentialBuilder::default()
                     .with_max_times(policy.max_retries as usize)
@@ -213,7 +414,54 @@ impl WebhookDelivery {
                     err
                 );
             })
-            .await
+            .await;
+
+        let attempts = attempts.into_inner();
+        let mut health = self.health.lock().unwrap();
+        match result {
+            Ok(()) => health.record_success(attempts, started.elapsed()),
+            Err(ref e) => {
+                health.record_failure(&self.config.circuit_breaker, attempts, e.to_string());
+                if health.state(&self.config.circuit_breaker) == CircuitState::Open {
+                    tracing::warn!(
+                        "Webhook {} circuit open after {} consecutive failures",
+                        self.config.url,
+                        health.consecutive_failures
+                    );
+                }
+            }
+        }
+        (attempts, result)
+    }
+
+    /// Whether the circuit lets a delivery through now
+    fn try_acquire(&self) -> bool {
+        self.health.lock().unwrap().try_acquire(&self.config.circuit_breaker)
+    }
+
+    /// Current circuit state
+    pub fn circuit_state(&self) -> CircuitState {
+        self.health.lock().unwrap().state(&self.config.circuit_breaker)
+    }
+
+    /// Delivery metrics for this endpoint
+    pub fn metrics(&self) -> WebhookMetrics {
+        let health = self.health.lock().unwrap();
+        WebhookMetrics {
+            webhook_id: self.config.id.clone(),
+            url: self.config.url.clone(),
+            circuit: health.state(&self.config.circuit_breaker),
+            consecutive_failures: health.consecutive_failures,
+            delivered: health.delivered,
+            failed: health.failed,
+            attempts: health.attempts,
+            avg_latency_ms: (health.delivered > 0)
+                .then(|| health.latency_ms_total as f64 / health.delivered as f64),
+            last_success_at: health.last_success_at,
+            last_failure_at: health.last_failure_at,
+            last_error: health.last_error.clone(),
+            deliveries: None,
+        }
     }
 
     /// Sign payload with HMAC-SHA256
@@ -241,6 +489,155 @@ impl Clone for WebhookDelivery {
         Self {
             client: self.client.clone(),
             config: self.config.clone(),
+            health: self.health.clone(),
+        }
+    }
+}
+
+/// Delivery state shared by the background task and the manager's API
+#[derive(Clone)]
+struct Dispatcher {
+    webhooks: Arc<RwLock<Vec<WebhookDelivery>>>,
+    event_bus: EventBus,
+    store: Option<Arc<WebhookDeliveryStore>>,
+    in_flight: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Dispatcher {
+    /// Deliver an event to every subscribed webhook in parallel
+    async fn dispatch(&self, event: &MemoryLifecycleEvent) {
+        let event_type = event.event_type();
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize {} event for webhooks: {}", event_type, e);
+                return;
+            }
+        };
+
+        let webhooks: Vec<WebhookDelivery> = self
+            .webhooks
+            .read()
+            .await
+            .iter()
+            .filter(|w| w.config().should_receive(event_type))
+            .cloned()
+            .collect();
+
+        let futures: Vec<_> = webhooks
+            .iter()
+            .map(|webhook| {
+                let record = DeliveryRecord::new(&webhook.config().id, event_type, &payload);
+                self.save(&record);
+                self.attempt(webhook, record, false)
+            })
+            .collect();
+        futures::future::join_all(futures).await;
+    }
+
+    /// Retry pending deliveries from the store
+    async fn resume_pending(&self) {
+        let Some(ref store) = self.store else {
+            return;
+        };
+        let pending = match store.list(DeliveryStatus::Pending, None, RESUME_BATCH) {
+            Ok(pending) => pending,
+            Err(e) => {
+                tracing::error!("Failed to load pending webhook deliveries: {}", e);
+                return;
+            }
+        };
+        if pending.is_empty() {
+            return;
+        }
+
+        let webhooks = self.webhooks.read().await.clone();
+        let mut futures = Vec::new();
+        for mut record in pending {
+            match webhooks.iter().find(|w| w.config().id == record.webhook_id) {
+                Some(webhook) if webhook.config().enabled => {
+                    futures.push(self.attempt(webhook, record, false));
+                }
+                // Disabled webhooks keep their deliveries until re-enabled
+                Some(_) => {}
+                None => {
+                    record.status = DeliveryStatus::DeadLetter;
+                    record.last_error = Some("Webhook no longer configured".to_string());
+                    record.updated_at = Utc::now();
+                    self.save(&record);
+                }
+            }
+        }
+        futures::future::join_all(futures).await;
+    }
+
+    /// Attempt one delivery and record its outcome
+    ///
+    /// `force` bypasses an open circuit, for manual redelivery.
+    async fn attempt(&self, webhook: &WebhookDelivery, mut record: DeliveryRecord, force: bool) {
+        if !self.in_flight.lock().unwrap().insert(record.id.clone()) {
+            return;
+        }
+
+        if force || webhook.try_acquire() {
+            let (attempts, result) = webhook
+                .send(&record.id, &record.event_type, &record.payload)
+                .await;
+            record.attempts += attempts;
+            match result {
+                Ok(()) => {
+                    record.status = DeliveryStatus::Delivered;
+                    record.last_error = None;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Webhook delivery to {} failed: {:?}",
+                        webhook.config().url,
+                        e
+                    );
+                    record.status = DeliveryStatus::DeadLetter;
+                    record.last_error = Some(e.to_string());
+                }
+            }
+        } else {
+            record.last_error = Some(CIRCUIT_OPEN.to_string());
+            // Without a store the delivery cannot be resumed later
+            if self.store.is_none() {
+                record.status = DeliveryStatus::DeadLetter;
+            }
+        }
+        record.updated_at = Utc::now();
+
+        if record.status == DeliveryStatus::DeadLetter {
+            self.alert_dead_letter(webhook, &record);
+        }
+        self.save(&record);
+        self.in_flight.lock().unwrap().remove(&record.id);
+    }
+
+    /// Report a dead-lettered delivery on the bus
+    ///
+    /// Alert events themselves are not reported, to avoid feedback loops.
+    fn alert_dead_letter(&self, webhook: &WebhookDelivery, record: &DeliveryRecord) {
+        if record.event_type.starts_with("alert.") {
+            return;
+        }
+        self.event_bus.emit(MemoryLifecycleEvent::Alert(AlertEvent::new(
+            AlertSeverity::Warning,
+            AlertKind::WebhookDeadLetter {
+                webhook_id: webhook.config().id.clone(),
+                url: webhook.config().url.clone(),
+                event_type: record.event_type.clone(),
+                error: record.last_error.clone().unwrap_or_default(),
+            },
+        )));
+    }
+
+    fn save(&self, record: &DeliveryRecord) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.save(record) {
+                tracing::error!("Failed to record webhook delivery {}: {}", record.id, e);
+            }
         }
     }
 }
@@ -249,70 +646,203 @@ impl Clone for WebhookDelivery {
 ///
 /// Spawns background task to consume events and deliver to all webhooks
 pub struct WebhookManager {
-    webhooks: Arc<RwLock<Vec<WebhookDelivery>>>,
-    event_bus: EventBus,
+    dispatcher: Dispatcher,
+    resume_interval: Duration,
 }
 
 impl WebhookManager {
     /// Create a new webhook manager
     pub fn new(event_bus: EventBus) -> Self {
         Self {
-            webhooks: Arc::new(RwLock::new(Vec::new())),
-            event_bus,
+            dispatcher: Dispatcher {
+                webhooks: Arc::new(RwLock::new(Vec::new())),
+                event_bus,
+                store: None,
+                in_flight: Arc::new(Mutex::new(HashSet::new())),
+            },
+            resume_interval: Duration::from_secs(30),
         }
     }
 
+    /// Builder: record deliveries in a persistent store
+    ///
+    /// Enables resuming pending deliveries and redelivering dead letters.
+    pub fn with_store(mut self, store: Arc<WebhookDeliveryStore>) -> Self {
+        self.dispatcher.store = Some(store);
+        self
+    }
+
+    /// Builder: set how often pending deliveries are retried (default: 30s)
+    pub fn with_resume_interval(mut self, interval: Duration) -> Self {
+        self.resume_interval = interval;
+        self
+    }
+
     /// Add a webhook
     pub async fn add_webhook(&self, config: WebhookConfig) {
-        let mut webhooks = self.webhooks.write().await;
+        let mut webhooks = self.dispatcher.webhooks.write().await;
         webhooks.push(WebhookDelivery::new(config));
     }
 
     /// Remove a webhook by ID
     pub async fn remove_webhook(&self, id: &str) {
-        let mut webhooks = self.webhooks.write().await;
+        let mut webhooks = self.dispatcher.webhooks.write().await;
+        webhooks.retain(|w| w.config().id != id);
+    }
+
+    /// Load the webhooks registered in the store
+    ///
+    /// Returns the number loaded. Webhooks already added with the same ID are
+    /// replaced.
+    pub async fn load_webhooks(&self) -> RookResult<usize> {
+        let configs = self.store()?.list_webhooks()?;
+        let count = configs.len();
+        let mut webhooks = self.dispatcher.webhooks.write().await;
+        for config in configs {
+            webhooks.retain(|w| w.config().id != config.id);
+            webhooks.push(WebhookDelivery::new(config));
+        }
+        Ok(count)
+    }
+
+    /// Register a webhook, or replace the webhook with the same ID
+    ///
+    /// The config is validated and, with a store, persisted. A replaced
+    /// webhook starts over with a closed circuit and fresh metrics.
+    pub async fn save_webhook(&self, config: WebhookConfig) -> RookResult<()> {
+        config.validate()?;
+        if let Some(ref store) = self.dispatcher.store {
+            store.save_webhook(&config)?;
+        }
+        let mut webhooks = self.dispatcher.webhooks.write().await;
+        let delivery = WebhookDelivery::new(config);
+        match webhooks.iter_mut().find(|w| w.config().id == delivery.config().id) {
+            Some(existing) => *existing = delivery,
+            None => webhooks.push(delivery),
+        }
+        Ok(())
+    }
+
+    /// Unregister a webhook, removing it from the store too
+    ///
+    /// Returns whether it was registered.
+    pub async fn delete_webhook(&self, id: &str) -> RookResult<bool> {
+        let mut deleted = false;
+        if let Some(ref store) = self.dispatcher.store {
+            deleted = store.delete_webhook(id)?;
+        }
+        let mut webhooks = self.dispatcher.webhooks.write().await;
+        let before = webhooks.len();
         webhooks.retain(|w| w.config().id != id);
+        Ok(deleted || webhooks.len() < before)
+    }
+
+    /// A configured webhook by ID
+    pub async fn get_webhook(&self, id: &str) -> Option<WebhookConfig> {
+        let webhooks = self.dispatcher.webhooks.read().await;
+        webhooks
+            .iter()
+            .find(|w| w.config().id == id)
+            .map(|w| w.config().clone())
     }
 
     /// Start the delivery background task
     ///
+    /// Deliveries that fail after all retries are dead-lettered and reported
+    /// as `alert.webhook_dead_letter` events on the bus (except for alert
+    /// events themselves, to avoid feedback loops). With a store, pending
+    /// deliveries, including those held back by an open circuit, are retried
+    /// every resume interval, starting immediately.
+    ///
     /// Returns a handle that can be used to stop delivery
     pub fn start(&self) -> tokio::task::JoinHandle<()> {
-        let webhooks = self.webhooks.clone();
-        let mut subscriber = self.event_bus.subscribe();
+        let dispatcher = self.dispatcher.clone();
+        let mut subscriber = self.dispatcher.event_bus.subscribe();
+        let mut resume = tokio::time::interval(self.resume_interval);
+        resume.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let resumable = dispatcher.store.is_some();
 
         tokio::spawn(async move {
-            while let Some(event) = subscriber.recv().await {
-                let webhooks = webhooks.read().await;
-
-                // Deliver to all webhooks in parallel
-                let futures: Vec<_> = webhooks
-                    .iter()
-                    .map(|webhook| {
-                        let webhook = webhook.clone();
-                        let event = event.clone();
-                        async move {
-                            if let Err(e) = webhook.deliver(&event).await {
-                                tracing::error!(
-                                    "Webhook delivery to {} failed: {:?}",
-                                    webhook.config().url,
-                                    e
-                                );
-                            }
-                        }
-                    })
-                    .collect();
-
-                futures::future::join_all(futures).await;
+            loop {
+                tokio::select! {
+                    event = subscriber.recv() => match event {
+                        Some(event) => dispatcher.dispatch(&event).await,
+                        None => break,
+                    },
+                    _ = resume.tick(), if resumable => dispatcher.resume_pending().await,
+                }
             }
         })
     }
 
     /// List all configured webhooks
     pub async fn list_webhooks(&self) -> Vec<WebhookConfig> {
-        let webhooks = self.webhooks.read().await;
+        let webhooks = self.dispatcher.webhooks.read().await;
         webhooks.iter().map(|w| w.config().clone()).collect()
     }
+
+    /// Delivery metrics for every webhook
+    pub async fn metrics(&self) -> RookResult<Vec<WebhookMetrics>> {
+        let webhooks = self.dispatcher.webhooks.read().await;
+        webhooks
+            .iter()
+            .map(|webhook| {
+                let mut metrics = webhook.metrics();
+                if let Some(ref store) = self.dispatcher.store {
+                    metrics.deliveries = Some(store.counts(&webhook.config().id)?);
+                }
+                Ok(metrics)
+            })
+            .collect()
+    }
+
+    /// Dead-lettered deliveries, newest first, optionally for one webhook
+    pub fn dead_letters(
+        &self,
+        webhook_id: Option<&str>,
+        limit: usize,
+    ) -> RookResult<Vec<DeliveryRecord>> {
+        self.store()?.list(DeliveryStatus::DeadLetter, webhook_id, limit)
+    }
+
+    /// Redeliver a dead-lettered delivery now, bypassing an open circuit
+    ///
+    /// Returns the delivery with its new state.
+    pub async fn redeliver(&self, delivery_id: &str) -> RookResult<DeliveryRecord> {
+        let store = self.store()?;
+        let mut record = store
+            .get(delivery_id)?
+            .ok_or_else(|| RookError::not_found(format!("Webhook delivery {}", delivery_id)))?;
+        if record.status != DeliveryStatus::DeadLetter {
+            return Err(RookError::validation(format!(
+                "Delivery {} is {}; only dead-lettered deliveries can be redelivered",
+                delivery_id,
+                record.status.as_str()
+            )));
+        }
+
+        let webhook = self
+            .dispatcher
+            .webhooks
+            .read()
+            .await
+            .iter()
+            .find(|w| w.config().id == record.webhook_id)
+            .cloned()
+            .ok_or_else(|| RookError::not_found(format!("Webhook {}", record.webhook_id)))?;
+
+        record.status = DeliveryStatus::Pending;
+        self.dispatcher.attempt(&webhook, record, true).await;
+        store
+            .get(delivery_id)?
+            .ok_or_else(|| RookError::not_found(format!("Webhook delivery {}", delivery_id)))
+    }
+
+    fn store(&self) -> RookResult<&WebhookDeliveryStore> {
+        self.dispatcher.store.as_deref().ok_or_else(|| {
+            RookError::Configuration("No webhook delivery store configured".to_string())
+        })
+    }
 }
 
 /// Verify a webhook signature
@@ -368,6 +898,39 @@ mod tests {
         assert!(config.should_receive("memory.accessed"));
     }
 
+    #[test]
+    fn test_webhook_config_validate() {
+        assert!(WebhookConfig::new("https://example.com/webhook").validate().is_ok());
+        assert!(WebhookConfig::new("not a url").validate().is_err());
+        assert!(WebhookConfig::new("ftp://example.com").validate().is_err());
+
+        let mut config = WebhookConfig::new("http://localhost:9000/hook");
+        config.timeout_secs = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manager_persists_webhooks() {
+        let store = Arc::new(WebhookDeliveryStore::in_memory().unwrap());
+        let manager = WebhookManager::new(EventBus::new()).with_store(store.clone());
+
+        let config = WebhookConfig::new("https://example.com/hook");
+        manager.save_webhook(config.clone()).await.unwrap();
+        let mut disabled = config.clone();
+        disabled.enabled = false;
+        manager.save_webhook(disabled).await.unwrap();
+        assert!(manager.save_webhook(WebhookConfig::new("bad")).await.is_err());
+
+        // A new manager over the same store picks the webhook up
+        let restarted = WebhookManager::new(EventBus::new()).with_store(store);
+        assert_eq!(restarted.load_webhooks().await.unwrap(), 1);
+        assert!(!restarted.get_webhook(&config.id).await.unwrap().enabled);
+
+        assert!(restarted.delete_webhook(&config.id).await.unwrap());
+        assert!(!restarted.delete_webhook(&config.id).await.unwrap());
+        assert!(restarted.list_webhooks().await.is_empty());
+    }
+
     #[test]
     fn test_signature_verification() {
         let secret = "my-secret-key";
@@ -389,4 +952,83 @@ mod tests {
         assert_eq!(policy.initial_delay_ms, 100);
         assert_eq!(policy.max_delay_ms, 30_000);
     }
+
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown_secs: 3600,
+        };
+        let mut health = EndpointHealth::default();
+        assert!(health.try_acquire(&config));
+
+        health.record_failure(&config, 1, "Server error: 503".to_string());
+        assert_eq!(health.state(&config), CircuitState::Closed);
+        health.record_failure(&config, 1, "Server error: 503".to_string());
+        assert_eq!(health.state(&config), CircuitState::Open);
+        assert!(!health.try_acquire(&config));
+
+        // After the cooldown a single trial is let through
+        let elapsed = CircuitBreakerConfig {
+            cooldown_secs: 0,
+            ..config.clone()
+        };
+        assert_eq!(health.state(&elapsed), CircuitState::HalfOpen);
+        assert!(health.try_acquire(&elapsed));
+        assert!(!health.try_acquire(&elapsed));
+
+        // A failed trial reopens the circuit; a successful one closes it
+        health.record_failure(&config, 1, "Server error: 503".to_string());
+        assert_eq!(health.state(&config), CircuitState::Open);
+        assert!(health.try_acquire(&elapsed));
+        health.record_success(1, Duration::from_millis(40));
+        assert_eq!(health.state(&config), CircuitState::Closed);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!((health.delivered, health.failed, health.attempts), (1, 3, 4));
+    }
+
+    #[tokio::test]
+    async fn test_manager_dead_letters_and_circuit() {
+        let store = Arc::new(WebhookDeliveryStore::in_memory().unwrap());
+        let manager = WebhookManager::new(EventBus::new()).with_store(store.clone());
+        // Nothing listens on port 1, so every delivery fails
+        let config = WebhookConfig::new("http://127.0.0.1:1/hook")
+            .with_retry(RetryPolicy {
+                max_retries: 0,
+                ..RetryPolicy::default()
+            })
+            .with_circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown_secs: 3600,
+            });
+        let webhook_id = config.id.clone();
+        manager.add_webhook(config).await;
+
+        let event = MemoryLifecycleEvent::Created(crate::events::MemoryCreatedEvent::new(
+            "mem-1", "likes tea",
+        ));
+        manager.dispatcher.dispatch(&event).await;
+        manager.dispatcher.dispatch(&event).await;
+
+        let dead = manager.dead_letters(Some(&webhook_id), 10).unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, 1);
+
+        // The second delivery is held back by the open circuit
+        let pending = store.list(DeliveryStatus::Pending, None, 10).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].last_error.as_deref(), Some(CIRCUIT_OPEN));
+
+        let metrics = manager.metrics().await.unwrap();
+        assert_eq!(metrics[0].circuit, CircuitState::Open);
+        assert_eq!(metrics[0].failed, 1);
+        assert_eq!(metrics[0].deliveries.unwrap().pending, 1);
+
+        // Redelivery bypasses the circuit
+        let redelivered = manager.redeliver(&dead[0].id).await.unwrap();
+        assert_eq!(redelivered.status, DeliveryStatus::DeadLetter);
+        assert_eq!(redelivered.attempts, 2);
+        assert!(manager.redeliver(&pending[0].id).await.is_err());
+        assert!(manager.redeliver("missing").await.is_err());
+    }
 }
@@ -0,0 +1,347 @@
+//! Versioned export envelope for memories and the state around them.
+//!
+//! A plain export carries memories only, so restoring it loses FSRS state,
+//! synaptic tags, intentions and the knowledge graph. A bundle is still JSON
+//! Lines, but every line is tagged with a `type` and the first line is a
+//! header naming the format version, the sections present and the exported
+//! scope:
+//!
+//! ```text
+//! {"type":"header","version":2,"sections":["memories","cognitive","graph"],"user_id":"alice",...}
+//! {"type":"memory","id":"...","memory":"Works at Acme"}
+//! {"type":"cognitive","memory_id":"...","state":{...},"synaptic_tag":{...}}
+//! {"type":"intention","id":"...","name":"...",...}
+//! {"type":"entity","name":"alice","entity_type":"person","properties":{}}
+//! {"type":"relationship","source":"alice","target":"acme","relationship_type":"works_at","properties":{}}
+//! ```
+//!
+//! Untagged lines are version 1 memory records, so plain exports still import.
+//! Incremental exports add a `{"type":"tombstone","id":"...","deleted_at":"..."}`
+//! line per deleted memory (see [`super::export_jsonl_since`]).
+//!
+//! The `history` section (memory versions, history records, runs and
+//! promotions) completes the export of a user's data; it is not restored
+//! on import.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
+
+use super::jsonl::{ExportStats, ExportableMemory, Tombstone};
+use crate::cognitive::{CognitiveSnapshot, CognitiveStore};
+use crate::intentions::{Intention, IntentionStore};
+use crate::memory::{HistoryRecord, PromotionRecord, RunRecord};
+use crate::traits::{Entity, GraphFilters, GraphStore, Relationship};
+use crate::versioning::MemoryVersion;
+use crate::{MemoryItem, RookResult};
+
+/// Current bundle format version. Version 1 is the plain memory export.
+pub const BUNDLE_VERSION: u32 = 2;
+
+/// A kind of state carried in a bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSection {
+    /// Memory records.
+    Memories,
+    /// FSRS states, dual strengths, consolidation phases and synaptic tags.
+    Cognitive,
+    /// Intentions.
+    Intentions,
+    /// Knowledge graph entities and relationships.
+    Graph,
+    /// Memory versions, history records, runs and promotion records.
+    History,
+}
+
+/// First line of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleHeader {
+    /// Format version, see [`BUNDLE_VERSION`].
+    pub version: u32,
+    /// Sections present in the bundle.
+    pub sections: Vec<ExportSection>,
+    /// Scope the bundle was exported from. Graph records are restored into it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    /// When the bundle was written.
+    pub exported_at: DateTime<Utc>,
+}
+
+impl BundleHeader {
+    /// Header for a bundle of the given scope.
+    pub fn new(
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            sections: vec![ExportSection::Memories],
+            user_id,
+            agent_id,
+            run_id,
+            exported_at: Utc::now(),
+        }
+    }
+
+    /// Graph filters for the exported scope.
+    pub fn graph_filters(&self) -> GraphFilters {
+        GraphFilters {
+            user_id: self.user_id.clone(),
+            agent_id: self.agent_id.clone(),
+            run_id: self.run_id.clone(),
+        }
+    }
+}
+
+/// One line of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BundleRecord {
+    Header(BundleHeader),
+    Memory(ExportableMemory),
+    Cognitive(CognitiveSnapshot),
+    Intention(Intention),
+    Entity(Entity),
+    Relationship(Relationship),
+    Version(MemoryVersion),
+    History(HistoryRecord),
+    Run(RunRecord),
+    Promotion(PromotionRecord),
+    /// A memory deleted since an incremental export's cursor.
+    Tombstone(Tombstone),
+}
+
+/// Memories and the state around them, ready to be written.
+#[derive(Debug, Clone)]
+pub struct ExportBundle {
+    pub header: BundleHeader,
+    pub memories: Vec<MemoryItem>,
+    pub cognitive: Vec<CognitiveSnapshot>,
+    pub intentions: Vec<Intention>,
+    pub entities: Vec<Entity>,
+    pub relationships: Vec<Relationship>,
+    pub versions: Vec<MemoryVersion>,
+    pub history: Vec<HistoryRecord>,
+    pub runs: Vec<RunRecord>,
+    pub promotions: Vec<PromotionRecord>,
+}
+
+impl ExportBundle {
+    /// Bundle of the given memories.
+    pub fn new(header: BundleHeader, memories: Vec<MemoryItem>) -> Self {
+        Self {
+            header,
+            memories,
+            cognitive: Vec::new(),
+            intentions: Vec::new(),
+            entities: Vec::new(),
+            relationships: Vec::new(),
+            versions: Vec::new(),
+            history: Vec::new(),
+            runs: Vec::new(),
+            promotions: Vec::new(),
+        }
+    }
+
+    /// Add the cognitive state of every bundled memory.
+    pub fn collect_cognitive(&mut self, store: &CognitiveStore) -> RookResult<()> {
+        for memory in &self.memories {
+            if let Some(snapshot) = store.get_snapshot(&memory.id)? {
+                self.cognitive.push(snapshot);
+            }
+        }
+        self.add_section(ExportSection::Cognitive);
+        Ok(())
+    }
+
+    /// Add the intentions of the exported user, or every intention when the
+    /// bundle is not scoped to a user.
+    pub fn collect_intentions(&mut self, store: &dyn IntentionStore) -> RookResult<()> {
+        self.intentions = match self.header.user_id {
+            Some(ref user_id) => store.get_for_user(user_id)?,
+            None => store.get_all()?,
+        };
+        self.add_section(ExportSection::Intentions);
+        Ok(())
+    }
+
+    /// Add the current entities and relationships of the exported scope.
+    pub async fn collect_graph(&mut self, store: &dyn GraphStore) -> RookResult<()> {
+        let filters = self.header.graph_filters();
+        self.entities = store.get_all(&filters).await?;
+        self.relationships = store.get_all_relationships(&filters).await?;
+        self.add_section(ExportSection::Graph);
+        Ok(())
+    }
+
+    pub(crate) fn add_section(&mut self, section: ExportSection) {
+        if !self.header.sections.contains(&section) {
+            self.header.sections.push(section);
+        }
+    }
+
+    /// Records in write order, header first.
+    fn into_records(self) -> impl Iterator<Item = (Option<ExportSection>, BundleRecord)> {
+        std::iter::once((None, BundleRecord::Header(self.header)))
+            .chain(self.memories.into_iter().map(|m| {
+                (
+                    Some(ExportSection::Memories),
+                    BundleRecord::Memory(ExportableMemory::from(m)),
+                )
+            }))
+            .chain(
+                self.cognitive
+                    .into_iter()
+                    .map(|c| (Some(ExportSection::Cognitive), BundleRecord::Cognitive(c))),
+            )
+            .chain(
+                self.intentions
+                    .into_iter()
+                    .map(|i| (Some(ExportSection::Intentions), BundleRecord::Intention(i))),
+            )
+            .chain(
+                self.entities
+                    .into_iter()
+                    .map(|e| (Some(ExportSection::Graph), BundleRecord::Entity(e))),
+            )
+            .chain(
+                self.relationships
+                    .into_iter()
+                    .map(|r| (Some(ExportSection::Graph), BundleRecord::Relationship(r))),
+            )
+            .chain(
+                self.versions
+                    .into_iter()
+                    .map(|v| (Some(ExportSection::History), BundleRecord::Version(v))),
+            )
+            .chain(
+                self.history
+                    .into_iter()
+                    .map(|h| (Some(ExportSection::History), BundleRecord::History(h))),
+            )
+            .chain(
+                self.runs
+                    .into_iter()
+                    .map(|r| (Some(ExportSection::History), BundleRecord::Run(r))),
+            )
+            .chain(
+                self.promotions
+                    .into_iter()
+                    .map(|p| (Some(ExportSection::History), BundleRecord::Promotion(p))),
+            )
+    }
+}
+
+/// Write a bundle as JSON Lines.
+///
+/// `total` and `exported` count memories, as in [`super::export_jsonl`];
+/// other records are counted per section in `sections`.
+pub async fn export_bundle_jsonl<W>(bundle: ExportBundle, writer: W) -> RookResult<ExportStats>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut stats = ExportStats::new();
+    let mut writer = BufWriter::new(writer);
+    let mut sections: BTreeMap<ExportSection, u64> = BTreeMap::new();
+
+    for (section, record) in bundle.into_records() {
+        let is_memory = section == Some(ExportSection::Memories);
+        if is_memory {
+            stats.total += 1;
+        }
+
+        let mut line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(e) => {
+                stats.errors.push(format!("Serialization error: {}", e));
+                continue;
+            }
+        };
+        line.push('\n');
+        if let Err(e) = writer.write_all(line.as_bytes()).await {
+            stats.errors.push(format!("Write error: {}", e));
+            continue;
+        }
+
+        match section {
+            Some(ExportSection::Memories) => stats.exported += 1,
+            Some(section) => *sections.entry(section).or_default() += 1,
+            None => {}
+        }
+    }
+
+    if let Err(e) = writer.flush().await {
+        stats.errors.push(format!("Final flush error: {}", e));
+    }
+
+    stats.sections = sections;
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bundle_writes_header_first_and_counts_sections() {
+        let mut bundle = ExportBundle::new(
+            BundleHeader::new(Some("alice".to_string()), None, None),
+            vec![MemoryItem::new("m1", "Works at Acme")],
+        );
+        bundle.entities.push(Entity {
+            name: "alice".to_string(),
+            entity_type: "person".to_string(),
+            properties: serde_json::json!({}),
+        });
+        bundle.add_section(ExportSection::Graph);
+
+        let mut output = Vec::new();
+        let stats = export_bundle_jsonl(bundle, &mut output).await.unwrap();
+        assert_eq!(stats.exported, 1);
+        assert_eq!(stats.sections.get(&ExportSection::Graph), Some(&1));
+
+        let content = String::from_utf8(output).unwrap();
+        let records: Vec<BundleRecord> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        match &records[0] {
+            BundleRecord::Header(header) => {
+                assert_eq!(header.version, BUNDLE_VERSION);
+                assert_eq!(
+                    header.sections,
+                    vec![ExportSection::Memories, ExportSection::Graph]
+                );
+            }
+            other => panic!("expected header, got {:?}", other),
+        }
+        assert!(matches!(records[1], BundleRecord::Memory(ref m) if m.id == "m1"));
+        assert!(matches!(records[2], BundleRecord::Entity(ref e) if e.name == "alice"));
+    }
+
+    #[test]
+    fn test_collect_cognitive_only_for_bundled_memories() {
+        let store = CognitiveStore::in_memory().unwrap();
+        let state = crate::types::FsrsState::new();
+        store.save_state("m1", &state, false, None).unwrap();
+        store.save_state("other", &state, false, None).unwrap();
+
+        let mut bundle = ExportBundle::new(
+            BundleHeader::new(None, None, None),
+            vec![MemoryItem::new("m1", "First")],
+        );
+        bundle.collect_cognitive(&store).unwrap();
+
+        assert_eq!(bundle.cognitive.len(), 1);
+        assert_eq!(bundle.cognitive[0].memory_id, "m1");
+        assert!(bundle.header.sections.contains(&ExportSection::Cognitive));
+    }
+}
@@ -6,15 +6,21 @@
 //! - Human-readable debugging
 //! - Incremental processing and appending
 //! - Unix pipeline compatibility (grep, jq, etc.)
+//!
+//! Exports can be incremental: [`export_jsonl_since`] writes only memories
+//! changed after a cursor, plus a [`Tombstone`] line for each memory deleted
+//! since, and reports the cursor for the next export as the high-water mark.
 
+use super::bundle::{BundleRecord, ExportSection};
 use crate::{MemoryItem, RookResult};
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tokio::io::{AsyncWrite, AsyncWriteExt, BufWriter};
 
 /// Statistics from an export operation.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct ExportStats {
     /// Total memories processed.
     pub total: u64,
@@ -22,6 +28,20 @@ pub struct ExportStats {
     pub exported: u64,
     /// Error messages for failed exports.
     pub errors: Vec<String>,
+    /// Non-memory records exported per bundle section.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub sections: BTreeMap<ExportSection, u64>,
+    /// Tombstones written for deleted memories.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub tombstones: u64,
+    /// Latest change covered by the export, to pass as `updated_since` to
+    /// the next incremental export.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_water_mark: Option<DateTime<Utc>>,
+}
+
+fn is_zero(n: &u64) -> bool {
+    *n == 0
 }
 
 impl ExportStats {
@@ -34,6 +54,24 @@ impl ExportStats {
     pub fn is_success(&self) -> bool {
         self.errors.is_empty() && self.total == self.exported
     }
+
+    /// Move the high-water mark forward to `at`.
+    fn advance(&mut self, at: DateTime<Utc>) {
+        if self.high_water_mark < Some(at) {
+            self.high_water_mark = Some(at);
+        }
+    }
+}
+
+/// A memory deleted since the cursor of an incremental export.
+///
+/// Written as a `{"type": "tombstone", ...}` line so importers can tell it
+/// apart from memories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tombstone {
+    /// ID of the deleted memory.
+    pub id: String,
+    pub deleted_at: DateTime<Utc>,
 }
 
 /// Memory data in export format.
@@ -117,6 +155,38 @@ pub async fn export_jsonl<W, S>(
     memories: S,
     writer: W,
 ) -> RookResult<ExportStats>
+where
+    W: AsyncWrite + Unpin,
+    S: Stream<Item = MemoryItem>,
+{
+    export_jsonl_since(memories, Vec::new(), None, writer).await
+}
+
+/// Export the memories changed after a cursor, and tombstones for the
+/// memories deleted after it, to JSON Lines format.
+///
+/// A memory is exported if its `updated_at` (or, if it was never updated,
+/// `created_at`) is after `updated_since`; memories without a parseable
+/// timestamp are always exported. Tombstones are written after the memories.
+/// `total` counts the memories that passed the cursor. The returned
+/// [`ExportStats::high_water_mark`] is the latest change written, or
+/// `updated_since` if nothing changed, and is the cursor for the next export.
+///
+/// With `updated_since` of `None` every memory is exported, as with
+/// [`export_jsonl`].
+///
+/// # Example
+///
+/// ```ignore
+/// let stats = export_jsonl_since(memories, tombstones, Some(cursor), file).await?;
+/// let next_cursor = stats.high_water_mark;
+/// ```
+pub async fn export_jsonl_since<W, S>(
+    memories: S,
+    tombstones: Vec<Tombstone>,
+    updated_since: Option<DateTime<Utc>>,
+    writer: W,
+) -> RookResult<ExportStats>
 where
     W: AsyncWrite + Unpin,
     S: Stream<Item = MemoryItem>,
@@ -124,10 +194,17 @@ where
     use futures::StreamExt;
 
     let mut stats = ExportStats::new();
+    stats.high_water_mark = updated_since;
     let mut writer = BufWriter::new(writer);
     let mut memories = std::pin::pin!(memories);
 
     while let Some(memory) = memories.next().await {
+        let changed_at = changed_at(&memory);
+        if let (Some(since), Some(at)) = (updated_since, changed_at) {
+            if at <= since {
+                continue;
+            }
+        }
         stats.total += 1;
 
         let exportable = ExportableMemory::from(memory);
@@ -152,6 +229,9 @@ where
                 }
 
                 stats.exported += 1;
+                if let Some(at) = changed_at {
+                    stats.advance(at);
+                }
             }
             Err(e) => {
                 stats.errors.push(format!(
@@ -162,6 +242,32 @@ where
         }
     }
 
+    for tombstone in tombstones {
+        if updated_since.is_some_and(|since| tombstone.deleted_at <= since) {
+            continue;
+        }
+        let deleted_at = tombstone.deleted_at;
+        let id = tombstone.id.clone();
+        let mut line = match serde_json::to_string(&BundleRecord::Tombstone(tombstone)) {
+            Ok(json) => json,
+            Err(e) => {
+                stats
+                    .errors
+                    .push(format!("Serialization error for tombstone {}: {}", id, e));
+                continue;
+            }
+        };
+        line.push('\n');
+        if let Err(e) = writer.write_all(line.as_bytes()).await {
+            stats
+                .errors
+                .push(format!("Write error for tombstone {}: {}", id, e));
+            continue;
+        }
+        stats.tombstones += 1;
+        stats.advance(deleted_at);
+    }
+
     // Flush the buffer
     if let Err(e) = writer.flush().await {
         stats.errors.push(format!("Final flush error: {}", e));
@@ -170,6 +276,16 @@ where
     Ok(stats)
 }
 
+/// When a memory last changed, if its timestamps parse.
+fn changed_at(memory: &MemoryItem) -> Option<DateTime<Utc>> {
+    memory
+        .updated_at
+        .as_deref()
+        .or(memory.created_at.as_deref())
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +365,70 @@ mod tests {
         assert!(output.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_export_jsonl_since_cursor_and_tombstones() {
+        let since: DateTime<Utc> = "2024-01-02T00:00:00Z".parse().unwrap();
+        let memories = vec![
+            MemoryItem::new("old", "Unchanged").with_created_at("2024-01-01T00:00:00Z"),
+            MemoryItem::new("updated", "Updated")
+                .with_created_at("2024-01-01T00:00:00Z")
+                .with_updated_at("2024-01-03T00:00:00Z"),
+            MemoryItem::new("new", "Created").with_created_at("2024-01-02T12:00:00Z"),
+        ];
+        let tombstones = vec![
+            Tombstone {
+                id: "gone".to_string(),
+                deleted_at: "2024-01-04T00:00:00Z".parse().unwrap(),
+            },
+            Tombstone {
+                id: "long-gone".to_string(),
+                deleted_at: "2024-01-01T00:00:00Z".parse().unwrap(),
+            },
+        ];
+
+        let mut output = Vec::new();
+        let stats =
+            export_jsonl_since(stream::iter(memories), tombstones, Some(since), &mut output)
+                .await
+                .unwrap();
+
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.exported, 2);
+        assert_eq!(stats.tombstones, 1);
+        assert_eq!(
+            stats.high_water_mark,
+            Some("2024-01-04T00:00:00Z".parse().unwrap())
+        );
+
+        let content = String::from_utf8(output).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["id"], "updated");
+        assert_eq!(lines[1]["id"], "new");
+        assert_eq!(lines[2]["type"], "tombstone");
+        assert_eq!(lines[2]["id"], "gone");
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_since_without_changes_keeps_cursor() {
+        let since: DateTime<Utc> = "2024-01-02T00:00:00Z".parse().unwrap();
+        let memories =
+            vec![MemoryItem::new("old", "Unchanged").with_created_at("2024-01-01T00:00:00Z")];
+
+        let mut output = Vec::new();
+        let stats =
+            export_jsonl_since(stream::iter(memories), Vec::new(), Some(since), &mut output)
+                .await
+                .unwrap();
+
+        assert_eq!(stats.total, 0);
+        assert_eq!(stats.high_water_mark, Some(since));
+        assert!(output.is_empty());
+    }
+
     #[test]
     fn test_exportable_memory_from_memory_item() {
         let item = MemoryItem::new("test-id", "test content")
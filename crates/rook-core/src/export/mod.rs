@@ -1,6 +1,9 @@
 //! Export utilities for memory data.
 //!
 //! Supports JSON Lines (streaming, human-readable) and Parquet (columnar, compressed).
+//! JSON Lines bundles can also carry cognitive state, intentions and the graph.
+//! JSON Lines exports can be incremental, carrying the changes and deletions
+//! since a cursor.
 //!
 //! # Example
 //!
@@ -14,10 +17,14 @@
 //! println!("Exported {} memories", stats.exported);
 //! ```
 
+pub mod bundle;
 pub mod jsonl;
 #[cfg(feature = "export")]
 pub mod parquet;
 
-pub use jsonl::{export_jsonl, ExportStats, ExportableMemory};
+pub use bundle::{
+    export_bundle_jsonl, BundleHeader, BundleRecord, ExportBundle, ExportSection, BUNDLE_VERSION,
+};
+pub use jsonl::{export_jsonl, export_jsonl_since, ExportStats, ExportableMemory, Tombstone};
 #[cfg(feature = "export")]
 pub use self::parquet::export_parquet;
@@ -0,0 +1,410 @@
+//! Import of versioned export bundles.
+//!
+//! Memory records are streamed to the caller in batches exactly like
+//! [`super::import_jsonl`]. Cognitive state, intentions and graph records are
+//! collected into [`BundleContents`] and restored once the memories are in,
+//! so they never point at memories that failed to import mid-stream.
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use super::jsonl::{ImportStats, ImportableMemory};
+use crate::cognitive::{CognitiveSnapshot, CognitiveStore};
+use crate::error::RookError;
+use crate::export::{BundleHeader, BundleRecord, Tombstone, BUNDLE_VERSION};
+use crate::intentions::{Intention, IntentionStore};
+use crate::traits::{Entity, GraphStore, Relationship};
+use crate::RookResult;
+
+/// Non-memory records read from a bundle.
+#[derive(Debug, Clone, Default)]
+pub struct BundleContents {
+    /// Bundle header, absent for version 1 exports.
+    pub header: Option<BundleHeader>,
+    pub cognitive: Vec<CognitiveSnapshot>,
+    pub intentions: Vec<Intention>,
+    pub entities: Vec<Entity>,
+    pub relationships: Vec<Relationship>,
+    /// Memories deleted at the source of an incremental export. They are
+    /// not applied by [`Self::restore`].
+    pub tombstones: Vec<Tombstone>,
+    /// Section records that could not be parsed.
+    pub invalid: u64,
+}
+
+/// Counts of restored non-memory records.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RestoreStats {
+    pub cognitive: u64,
+    pub intentions: u64,
+    pub entities: u64,
+    pub relationships: u64,
+    /// Error messages for sections that were not restored.
+    pub errors: Vec<String>,
+}
+
+impl BundleContents {
+    /// Whether the bundle carried nothing besides memories.
+    pub fn is_empty(&self) -> bool {
+        self.cognitive.is_empty()
+            && self.intentions.is_empty()
+            && self.entities.is_empty()
+            && self.relationships.is_empty()
+    }
+
+    /// Restore the collected state into the given stores.
+    ///
+    /// Nothing is restored if any section record failed to parse. Cognitive
+    /// state and intentions are each written in a single transaction; a
+    /// section whose store is missing is reported in `errors` and skipped.
+    /// Graph records are restored into the scope named by the header.
+    pub async fn restore(
+        &self,
+        cognitive: Option<&CognitiveStore>,
+        intentions: Option<&dyn IntentionStore>,
+        graph: Option<&dyn GraphStore>,
+    ) -> RestoreStats {
+        let mut stats = RestoreStats::default();
+        if self.invalid > 0 {
+            stats.errors.push(format!(
+                "{} section records could not be parsed; nothing was restored",
+                self.invalid
+            ));
+            return stats;
+        }
+
+        if !self.cognitive.is_empty() {
+            match cognitive {
+                Some(store) => match store.restore_snapshots(&self.cognitive) {
+                    Ok(count) => stats.cognitive = count as u64,
+                    Err(e) => stats.errors.push(format!("Cognitive state restore failed: {}", e)),
+                },
+                None => stats.errors.push(missing_store("cognitive", self.cognitive.len())),
+            }
+        }
+
+        if !self.intentions.is_empty() {
+            match intentions {
+                Some(store) => match store.restore(&self.intentions) {
+                    Ok(count) => stats.intentions = count as u64,
+                    Err(e) => stats.errors.push(format!("Intention restore failed: {}", e)),
+                },
+                None => stats.errors.push(missing_store("intention", self.intentions.len())),
+            }
+        }
+
+        if !self.entities.is_empty() || !self.relationships.is_empty() {
+            match graph {
+                Some(store) => self.restore_graph(store, &mut stats).await,
+                None => stats.errors.push(missing_store(
+                    "graph",
+                    self.entities.len() + self.relationships.len(),
+                )),
+            }
+        }
+
+        stats
+    }
+
+    async fn restore_graph(&self, store: &dyn GraphStore, stats: &mut RestoreStats) {
+        let filters = self
+            .header
+            .as_ref()
+            .map(BundleHeader::graph_filters)
+            .unwrap_or_default();
+
+        for entity in &self.entities {
+            match store
+                .add_entity(&entity.name, &entity.entity_type, &entity.properties, &filters)
+                .await
+            {
+                Ok(_) => stats.entities += 1,
+                Err(e) => stats
+                    .errors
+                    .push(format!("Entity '{}' restore failed: {}", entity.name, e)),
+            }
+        }
+
+        for rel in &self.relationships {
+            match store
+                .add_relationship(
+                    &rel.source,
+                    &rel.target,
+                    &rel.relationship_type,
+                    &rel.properties,
+                    &filters,
+                )
+                .await
+            {
+                Ok(_) => stats.relationships += 1,
+                Err(e) => stats.errors.push(format!(
+                    "Relationship '{}' -[{}]-> '{}' restore failed: {}",
+                    rel.source, rel.relationship_type, rel.target, e
+                )),
+            }
+        }
+    }
+}
+
+fn missing_store(kind: &str, count: usize) -> String {
+    format!("No {} store configured; {} records were not restored", kind, count)
+}
+
+/// Import a bundle, or a plain memory export, from JSON Lines.
+///
+/// Memories are passed to `import_batch` in batches and counted in the
+/// returned [`ImportStats`]; everything else is returned in
+/// [`BundleContents`] for [`BundleContents::restore`]. Fails if the header
+/// names a newer format version.
+pub async fn import_bundle_jsonl<R, F, Fut>(
+    reader: R,
+    batch_size: usize,
+    mut import_batch: F,
+) -> RookResult<(ImportStats, BundleContents)>
+where
+    R: AsyncBufRead + Unpin,
+    F: FnMut(Vec<ImportableMemory>) -> Fut,
+    Fut: Future<Output = RookResult<usize>>,
+{
+    let mut stats = ImportStats::new();
+    let mut contents = BundleContents::default();
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut lines = reader.lines();
+    let mut line_number = 0u64;
+
+    while let Some(line_result) = lines.next_line().await? {
+        let line = line_result.trim();
+        if line.is_empty() {
+            continue;
+        }
+        line_number += 1;
+
+        let memory = match parse_line(line) {
+            Ok(Line::Memory(memory)) => memory,
+            Ok(Line::Record(BundleRecord::Header(header))) => {
+                if header.version > BUNDLE_VERSION {
+                    return Err(RookError::validation(format!(
+                        "Bundle version {} is newer than supported version {}",
+                        header.version, BUNDLE_VERSION
+                    )));
+                }
+                contents.header = Some(header);
+                continue;
+            }
+            Ok(Line::Record(BundleRecord::Memory(memory))) => memory.into(),
+            Ok(Line::Record(BundleRecord::Cognitive(snapshot))) => {
+                contents.cognitive.push(snapshot);
+                continue;
+            }
+            Ok(Line::Record(BundleRecord::Intention(intention))) => {
+                contents.intentions.push(intention);
+                continue;
+            }
+            Ok(Line::Record(BundleRecord::Entity(entity))) => {
+                contents.entities.push(entity);
+                continue;
+            }
+            Ok(Line::Record(BundleRecord::Relationship(rel))) => {
+                contents.relationships.push(rel);
+                continue;
+            }
+            Ok(Line::Record(BundleRecord::Tombstone(tombstone))) => {
+                contents.tombstones.push(tombstone);
+                continue;
+            }
+            // History is exported for the record and not restored
+            Ok(Line::Record(
+                BundleRecord::Version(_)
+                | BundleRecord::History(_)
+                | BundleRecord::Run(_)
+                | BundleRecord::Promotion(_),
+            )) => continue,
+            Err(LineError::Memory(e)) => {
+                stats.total += 1;
+                stats
+                    .errors
+                    .push(format!("Parse error at line {}: {}", line_number, e));
+                continue;
+            }
+            Err(LineError::Record(e)) => {
+                contents.invalid += 1;
+                stats
+                    .errors
+                    .push(format!("Parse error at line {}: {}", line_number, e));
+                continue;
+            }
+        };
+
+        stats.total += 1;
+        batch.push(memory);
+
+        if batch.len() >= batch_size {
+            let batch_count = batch.len();
+            match import_batch(std::mem::take(&mut batch)).await {
+                Ok(count) => {
+                    stats.imported += count as u64;
+                    stats.skipped += (batch_count - count) as u64;
+                }
+                Err(e) => {
+                    stats.errors.push(format!("Batch import error: {}", e));
+                }
+            }
+            batch = Vec::with_capacity(batch_size);
+        }
+    }
+
+    if !batch.is_empty() {
+        let batch_count = batch.len();
+        match import_batch(batch).await {
+            Ok(count) => {
+                stats.imported += count as u64;
+                stats.skipped += (batch_count - count) as u64;
+            }
+            Err(e) => {
+                stats.errors.push(format!("Final batch import error: {}", e));
+            }
+        }
+    }
+
+    Ok((stats, contents))
+}
+
+enum Line {
+    /// Untagged version 1 memory line.
+    Memory(ImportableMemory),
+    Record(BundleRecord),
+}
+
+enum LineError {
+    Memory(serde_json::Error),
+    Record(serde_json::Error),
+}
+
+fn parse_line(line: &str) -> Result<Line, LineError> {
+    let value: serde_json::Value = serde_json::from_str(line).map_err(LineError::Memory)?;
+    let tag = value.get("type").and_then(|t| t.as_str());
+    match tag {
+        None => serde_json::from_value(value)
+            .map(Line::Memory)
+            .map_err(LineError::Memory),
+        Some("memory") => serde_json::from_value(value)
+            .map(Line::Record)
+            .map_err(LineError::Memory),
+        Some(_) => serde_json::from_value(value)
+            .map(Line::Record)
+            .map_err(LineError::Record),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{export_bundle_jsonl, ExportBundle};
+    use crate::intentions::{IntentionAction, SqliteIntentionStore, TriggerCondition};
+    use crate::MemoryItem;
+    use std::io::Cursor;
+    use tokio::io::BufReader;
+
+    #[tokio::test]
+    async fn test_bundle_roundtrip() {
+        let source = CognitiveStore::in_memory().unwrap();
+        source
+            .save_state("m1", &crate::types::FsrsState::new(), true, None)
+            .unwrap();
+        let intentions = SqliteIntentionStore::in_memory().unwrap();
+        intentions
+            .add(&Intention::new(
+                "remind",
+                TriggerCondition::keyword(vec!["acme".to_string()]),
+                IntentionAction::default(),
+            ))
+            .unwrap();
+
+        let mut bundle = ExportBundle::new(
+            BundleHeader::new(None, None, None),
+            vec![MemoryItem::new("m1", "Works at Acme")],
+        );
+        bundle.collect_cognitive(&source).unwrap();
+        bundle.collect_intentions(&intentions).unwrap();
+        let mut output = Vec::new();
+        export_bundle_jsonl(bundle, &mut output).await.unwrap();
+
+        let reader = BufReader::new(Cursor::new(output));
+        let (stats, contents) =
+            import_bundle_jsonl(reader, 10, |batch| async move { Ok(batch.len()) })
+                .await
+                .unwrap();
+        assert_eq!(stats.imported, 1);
+        assert!(stats.errors.is_empty());
+        assert!(contents.header.is_some());
+
+        let cognitive = CognitiveStore::in_memory().unwrap();
+        let restored_intentions = SqliteIntentionStore::in_memory().unwrap();
+        let restored = contents
+            .restore(Some(&cognitive), Some(&restored_intentions), None)
+            .await;
+        assert!(restored.errors.is_empty());
+        assert_eq!(restored.cognitive, 1);
+        assert_eq!(restored.intentions, 1);
+        assert!(cognitive.get_state("m1").unwrap().unwrap().1);
+    }
+
+    #[tokio::test]
+    async fn test_version_one_lines_import_as_memories() {
+        let jsonl = r#"{"id":"1","memory":"First"}
+{"id":"2","memory":"Second"}"#;
+        let reader = BufReader::new(Cursor::new(jsonl));
+        let (stats, contents) =
+            import_bundle_jsonl(reader, 10, |batch| async move { Ok(batch.len()) })
+                .await
+                .unwrap();
+        assert_eq!(stats.imported, 2);
+        assert!(contents.header.is_none());
+        assert!(contents.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tombstones_collected() {
+        let jsonl = r#"{"id":"1","memory":"First"}
+{"type":"tombstone","id":"2","deleted_at":"2024-01-02T00:00:00Z"}"#;
+        let reader = BufReader::new(Cursor::new(jsonl));
+        let (stats, contents) =
+            import_bundle_jsonl(reader, 10, |batch| async move { Ok(batch.len()) })
+                .await
+                .unwrap();
+        assert_eq!(stats.total, 1);
+        assert!(stats.errors.is_empty());
+        assert_eq!(contents.tombstones.len(), 1);
+        assert_eq!(contents.tombstones[0].id, "2");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_section_record_blocks_restore() {
+        let jsonl = r#"{"type":"cognitive","memory_id":"m1","state":{"oops":true}}
+{"type":"entity","name":"alice","entity_type":"person","properties":{}}"#;
+        let reader = BufReader::new(Cursor::new(jsonl));
+        let (stats, contents) =
+            import_bundle_jsonl(reader, 10, |batch| async move { Ok(batch.len()) })
+                .await
+                .unwrap();
+        assert_eq!(stats.errors.len(), 1);
+        assert_eq!(contents.invalid, 1);
+
+        let restored = contents.restore(None, None, None).await;
+        assert_eq!(restored.entities, 0);
+        assert_eq!(restored.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_newer_bundle_version_rejected() {
+        let jsonl = format!(
+            r#"{{"type":"header","version":{},"sections":["memories"],"exported_at":"2024-01-01T00:00:00Z"}}"#,
+            BUNDLE_VERSION + 1
+        );
+        let reader = BufReader::new(Cursor::new(jsonl));
+        let result = import_bundle_jsonl(reader, 10, |batch| async move { Ok(batch.len()) }).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Deduplication of imported memories.
+//!
+//! Imports always skip memories whose ID already exists. [`ImportDedup`]
+//! also skips memories duplicating one already stored in the same scope
+//! (or earlier in the same batch), so re-importing a backup taken from
+//! another store doesn't double every memory.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Similarity above which an imported memory is a duplicate, for
+/// [`ImportDedup::Embedding`] without a threshold.
+pub const DEFAULT_IMPORT_DEDUP_THRESHOLD: f32 = 0.95;
+
+/// How imported memories are matched against existing ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ImportDedup {
+    /// Only skip memories whose ID already exists.
+    #[default]
+    Id,
+    /// Also skip memories with the same content hash as a memory in the
+    /// same scope.
+    Hash,
+    /// Also skip memories at least `threshold` similar (0.0-1.0) to a
+    /// memory in the same scope.
+    Embedding { threshold: f32 },
+    /// Also skip memories the prediction error gate of `smart_ingest`
+    /// judges redundant with stored memories in the same scope. Memories it
+    /// would update or supersede are imported as they are.
+    SmartIngest,
+}
+
+impl ImportDedup {
+    /// Parse a mode name (`id`, `hash`, `embedding`, `smart_ingest`), with
+    /// the similarity threshold for `embedding`.
+    pub fn parse(mode: &str, threshold: Option<f32>) -> RookResult<Self> {
+        let dedup = match mode.trim().to_lowercase().as_str() {
+            "id" | "none" => Self::Id,
+            "hash" => Self::Hash,
+            "embedding" => Self::Embedding {
+                threshold: threshold.unwrap_or(DEFAULT_IMPORT_DEDUP_THRESHOLD),
+            },
+            "smart_ingest" | "smart" => Self::SmartIngest,
+            other => {
+                return Err(RookError::validation(format!(
+                    "Unknown dedup mode '{}' (expected id, hash, embedding or smart_ingest)",
+                    other
+                )))
+            }
+        };
+        if let Self::Embedding { threshold } = dedup {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(RookError::validation(
+                    "The dedup threshold must be between 0.0 and 1.0",
+                ));
+            }
+        }
+        Ok(dedup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_import_dedup() {
+        assert_eq!(ImportDedup::parse("hash", None).unwrap(), ImportDedup::Hash);
+        assert_eq!(ImportDedup::parse("Smart", None).unwrap(), ImportDedup::SmartIngest);
+        assert_eq!(
+            ImportDedup::parse("embedding", None).unwrap(),
+            ImportDedup::Embedding {
+                threshold: DEFAULT_IMPORT_DEDUP_THRESHOLD
+            }
+        );
+        assert_eq!(
+            ImportDedup::parse("embedding", Some(0.8)).unwrap(),
+            ImportDedup::Embedding { threshold: 0.8 }
+        );
+        assert!(ImportDedup::parse("embedding", Some(1.5)).is_err());
+        assert!(ImportDedup::parse("fuzzy", None).is_err());
+    }
+}
@@ -3,14 +3,16 @@
 //! Provides streaming import from JSON Lines format with batched processing.
 //! This format allows line-by-line reading without loading the entire file.
 
+use super::bundle::import_bundle_jsonl;
+use crate::export::ExportableMemory;
 use crate::RookResult;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::future::Future;
-use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::io::AsyncBufRead;
 
 /// Statistics from an import operation.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ImportStats {
     /// Total lines processed.
     pub total: u64,
@@ -78,10 +80,29 @@ pub struct ImportableMemory {
     pub is_key: bool,
 }
 
+impl From<ExportableMemory> for ImportableMemory {
+    fn from(memory: ExportableMemory) -> Self {
+        Self {
+            id: memory.id,
+            memory: memory.memory,
+            hash: memory.hash,
+            metadata: memory.metadata,
+            created_at: memory.created_at,
+            updated_at: memory.updated_at,
+            fsrs_state: memory.fsrs_state,
+            dual_strength: memory.dual_strength,
+            category: memory.category,
+            is_key: memory.is_key,
+        }
+    }
+}
+
 /// Import memories from JSON Lines format.
 ///
 /// Reads lines from the input, parses each as JSON, and processes in batches.
-/// Malformed lines are logged as errors but don't abort the import.
+/// Malformed lines are logged as errors but don't abort the import. Memory
+/// records of an export bundle are imported too; use
+/// [`super::import_bundle_jsonl`] to also restore the other sections.
 ///
 /// # Arguments
 ///
@@ -109,68 +130,15 @@ pub struct ImportableMemory {
 pub async fn import_jsonl<R, F, Fut>(
     reader: R,
     batch_size: usize,
-    mut import_batch: F,
+    import_batch: F,
 ) -> RookResult<ImportStats>
 where
     R: AsyncBufRead + Unpin,
     F: FnMut(Vec<ImportableMemory>) -> Fut,
     Fut: Future<Output = RookResult<usize>>,
 {
-    let mut stats = ImportStats::new();
-    let mut batch = Vec::with_capacity(batch_size);
-    let mut lines = reader.lines();
-
-    while let Some(line_result) = lines.next_line().await? {
-        let line = line_result.trim();
-
-        // Skip empty lines
-        if line.is_empty() {
-            continue;
-        }
-
-        stats.total += 1;
-
-        // Parse the JSON line
-        match serde_json::from_str::<ImportableMemory>(line) {
-            Ok(memory) => {
-                batch.push(memory);
-
-                // Process batch when full
-                if batch.len() >= batch_size {
-                    match import_batch(std::mem::take(&mut batch)).await {
-                        Ok(count) => {
-                            stats.imported += count as u64;
-                        }
-                        Err(e) => {
-                            stats.errors.push(format!("Batch import error: {}", e));
-                        }
-                    }
-                    batch = Vec::with_capacity(batch_size);
-                }
-            }
-            Err(e) => {
-                stats.errors.push(format!(
-                    "Parse error at line {}: {}",
-                    stats.total, e
-                ));
-            }
-        }
-    }
-
-    // Process remaining batch
-    if !batch.is_empty() {
-        let batch_count = batch.len();
-        match import_batch(batch).await {
-            Ok(count) => {
-                stats.imported += count as u64;
-                stats.skipped += (batch_count - count) as u64;
-            }
-            Err(e) => {
-                stats.errors.push(format!("Final batch import error: {}", e));
-            }
-        }
-    }
-
+    // Bundle sections other than memories are dropped
+    let (stats, _) = import_bundle_jsonl(reader, batch_size, import_batch).await?;
     Ok(stats)
 }
 
@@ -226,6 +194,25 @@ mod tests {
         assert_eq!(batch_counts[2], 5);
     }
 
+    #[tokio::test]
+    async fn test_import_jsonl_counts_skipped_in_every_batch() {
+        let jsonl = (0..25)
+            .map(|i| format!(r#"{{"id":"{}","memory":"Memory {}"}}"#, i, i))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let reader = BufReader::new(Cursor::new(jsonl));
+
+        // Each batch skips one memory
+        let stats = import_jsonl(reader, 10, |batch| async move { Ok(batch.len() - 1) })
+            .await
+            .unwrap();
+
+        assert_eq!(stats.total, 25);
+        assert_eq!(stats.imported, 22);
+        assert_eq!(stats.skipped, 3);
+    }
+
     #[tokio::test]
     async fn test_import_jsonl_with_errors() {
         let jsonl = r#"{"id":"1","memory":"Valid memory"}
@@ -1,6 +1,7 @@
 //! Import utilities for memory data.
 //!
-//! Supports JSON Lines format with batched processing.
+//! Supports JSON Lines format with batched processing, including export
+//! bundles that carry cognitive state, intentions and the graph.
 //!
 //! # Example
 //!
@@ -16,8 +17,17 @@
 //!     memory.import_batch(batch).await
 //! }).await?;
 //! println!("Imported {}/{}", stats.imported, stats.total);
+//!
+//! // Re-importing a backup: also skip memories already stored with the same content
+//! let stats = import_jsonl(reader, 100, |batch| async {
+//!     memory.import_batch_with(batch, ImportDedup::Hash).await
+//! }).await?;
 //! ```
 
+pub mod bundle;
+pub mod dedup;
 pub mod jsonl;
 
+pub use bundle::{import_bundle_jsonl, BundleContents, RestoreStats};
+pub use dedup::{ImportDedup, DEFAULT_IMPORT_DEDUP_THRESHOLD};
 pub use jsonl::{import_jsonl, ImportStats, ImportableMemory};
@@ -52,6 +52,11 @@ pub enum StrengthSignal {
     /// User explicitly marked memory as important
     /// Effect: Mark as key memory (always retrieve)
     MarkedImportant { memory_id: String },
+
+    /// Memory was repeatedly activated together with newer memories
+    /// during replay
+    /// Effect: Promote with Good grade (rehearsed)
+    Replayed { memory_id: String },
 }
 
 impl StrengthSignal {
@@ -85,6 +90,9 @@ impl StrengthSignal {
                 // Key memory marking is handled separately, not via grades
                 vec![]
             }
+            StrengthSignal::Replayed { memory_id } => {
+                vec![(memory_id.clone(), Grade::Good)]
+            }
         }
     }
 }
@@ -136,6 +144,23 @@ impl StrengthSignalProcessor {
             .collect()
     }
 
+    /// Take the most recent pending grade of one memory, clearing its
+    /// pending updates
+    pub fn take_pending(&mut self, memory_id: &str) -> Option<Grade> {
+        self.pending_updates
+            .remove(memory_id)
+            .and_then(|grades| grades.last().copied())
+    }
+
+    /// Take the most recent pending grade of every memory, clearing all
+    /// pending grade updates. Key marks are kept.
+    pub fn take_all_pending(&mut self) -> HashMap<String, Grade> {
+        self.pending_updates
+            .drain()
+            .filter_map(|(id, grades)| grades.last().map(|g| (id, *g)))
+            .collect()
+    }
+
     /// Get memories to mark as key
     pub fn get_pending_key_marks(&self) -> &[String] {
         &self.pending_key_marks
@@ -243,6 +268,16 @@ mod tests {
         assert!(updates.is_empty());
     }
 
+    #[test]
+    fn test_replayed_gives_good() {
+        let signal = StrengthSignal::Replayed {
+            memory_id: "mem1".to_string(),
+        };
+
+        let updates = signal.to_grade_updates();
+        assert_eq!(updates, vec![("mem1".to_string(), Grade::Good)]);
+    }
+
     #[test]
     fn test_processor_collects_updates() {
         let mut processor = StrengthSignalProcessor::new();
@@ -261,6 +296,29 @@ mod tests {
         assert_eq!(updates.get("mem2"), Some(&Grade::Easy));
     }
 
+    #[test]
+    fn test_take_all_pending_drains_grades() {
+        let mut processor = StrengthSignalProcessor::new();
+
+        processor.process(StrengthSignal::UsedInResponse {
+            memory_id: "mem1".to_string(),
+            context: None,
+        });
+        processor.process(StrengthSignal::UserCorrection {
+            old_memory_id: "mem1".to_string(),
+            new_content: "Lives in Berlin".to_string(),
+        });
+        processor.process(StrengthSignal::MarkedImportant {
+            memory_id: "mem2".to_string(),
+        });
+
+        let updates = processor.take_all_pending();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates.get("mem1"), Some(&Grade::Again));
+        assert!(processor.take_all_pending().is_empty());
+        assert_eq!(processor.get_pending_key_marks(), &["mem2".to_string()]);
+    }
+
     #[test]
     fn test_processor_handles_key_marks() {
         let mut processor = StrengthSignalProcessor::new();
@@ -291,6 +349,20 @@ mod tests {
         assert_eq!(updates.get("mem1"), Some(&Grade::Easy));
     }
 
+    #[test]
+    fn test_processor_take_pending() {
+        let mut processor = StrengthSignalProcessor::new();
+
+        processor.process(StrengthSignal::UserCorrection {
+            old_memory_id: "mem1".to_string(),
+            new_content: "corrected info".to_string(),
+        });
+
+        assert_eq!(processor.take_pending("mem1"), Some(Grade::Again));
+        assert_eq!(processor.take_pending("mem1"), None);
+        assert!(processor.get_pending_updates().is_empty());
+    }
+
     #[test]
     fn test_processor_clear() {
         let mut processor = StrengthSignalProcessor::new();
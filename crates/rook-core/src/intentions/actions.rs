@@ -0,0 +1,533 @@
+//! Execution of intention actions.
+//!
+//! [`ActionExecutor`] carries out the action of a fired intention:
+//! - `CallWebhook` and `Notify` POST to an HTTP endpoint, retrying transient
+//!   failures (5xx, network errors) with exponential backoff
+//! - `NotifyMcp` sends a notification to connected MCP clients through an
+//!   [`IntentionNotifier`]
+//! - `SurfaceMemory` queues the intention's memory in [`SurfacedMemories`]
+//!   for the user's next searches
+//! - `Log` writes to the tracing log
+//! - `Callback` is left to the caller
+//!
+//! Webhook payload templates may use these placeholders, which are replaced
+//! with JSON-escaped values so the template stays valid JSON:
+//! `{{intention.id}}`, `{{intention.name}}`, `{{memory_id}}`, `{{user_id}}`,
+//! `{{fired_at}}`, `{{reason}}` and `{{context}}`.
+
+use crate::error::RookResult;
+use crate::events::{RetryPolicy, WebhookError};
+use crate::intentions::surfacing::{SurfacedMemories, SurfacedMemory};
+use crate::intentions::triggers::{ActionResult, TriggerReason};
+use crate::intentions::types::{Intention, IntentionAction};
+use async_trait::async_trait;
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Message used by `NotifyMcp` actions without one.
+const DEFAULT_MCP_MESSAGE: &str = "Intention \"{{intention.name}}\" fired: {{reason}}";
+
+/// Notification about a fired intention, as sent to MCP clients and as the
+/// default webhook payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentionNotification {
+    pub intention_id: String,
+    pub intention_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    pub fired_at: DateTime<Utc>,
+    pub reason: TriggerReason,
+    /// Human-readable summary of the fire
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Delivers `NotifyMcp` actions to MCP clients.
+///
+/// Implemented by the MCP server, which knows the connected clients.
+#[async_trait]
+pub trait IntentionNotifier: Send + Sync {
+    /// Send the notification, failing if no client could be reached.
+    async fn notify(&self, notification: &IntentionNotification) -> RookResult<()>;
+}
+
+/// Executes the actions of fired intentions.
+#[derive(Clone)]
+pub struct ActionExecutor {
+    client: Client,
+    retry_policy: RetryPolicy,
+    timeout: Duration,
+    notifier: Option<Arc<dyn IntentionNotifier>>,
+    surfaced: Option<Arc<SurfacedMemories>>,
+}
+
+impl Default for ActionExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActionExecutor {
+    /// Create an executor with the default retry policy, no MCP notifier and
+    /// no queue of surfaced memories.
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            retry_policy: RetryPolicy::default(),
+            timeout: Duration::from_secs(30),
+            notifier: None,
+            surfaced: None,
+        }
+    }
+
+    /// Builder: set the retry policy for webhooks and MCP notifications
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Builder: set the timeout of each webhook request
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builder: set the notifier for `NotifyMcp` actions
+    pub fn with_notifier(mut self, notifier: Arc<dyn IntentionNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Builder: set the queue `SurfaceMemory` actions surface memories in
+    pub fn with_surfaced_memories(mut self, surfaced: Arc<SurfacedMemories>) -> Self {
+        self.surfaced = Some(surfaced);
+        self
+    }
+
+    /// Execute the action of an intention that fired for `reason`.
+    pub async fn execute(&self, intention: &Intention, reason: &TriggerReason) -> ActionResult {
+        let fired_at = Utc::now();
+        match &intention.action {
+            IntentionAction::SurfaceMemory { boost, searches } => {
+                let (Some(surfaced), Some(memory_id)) = (&self.surfaced, &intention.memory_id)
+                else {
+                    return ActionResult::Skipped {
+                        reason: match intention.memory_id {
+                            Some(_) => "No surfaced memory queue configured".to_string(),
+                            None => "No memory attached to the intention".to_string(),
+                        },
+                    };
+                };
+                surfaced.surface(
+                    intention.user_id.as_deref(),
+                    SurfacedMemory {
+                        memory_id: memory_id.clone(),
+                        intention_id: intention.id,
+                        boost: *boost,
+                        remaining_searches: *searches,
+                    },
+                );
+                ActionResult::Success {
+                    details: Some(format!(
+                        "Surfacing memory {} in the next {} searches",
+                        memory_id, searches
+                    )),
+                }
+            }
+            IntentionAction::Log { message } => {
+                tracing::info!(
+                    intention_id = %intention.id,
+                    "{}",
+                    render_template(message, intention, reason, fired_at, false)
+                );
+                ActionResult::Success { details: None }
+            }
+            IntentionAction::CallWebhook {
+                url,
+                payload_template,
+            } => {
+                let payload = match payload_template {
+                    Some(template) => render_template(template, intention, reason, fired_at, true),
+                    None => self.default_payload(intention, reason, fired_at),
+                };
+                self.post(url, payload).await
+            }
+            IntentionAction::Notify {
+                webhook_url,
+                payload,
+            } => {
+                let payload = match payload {
+                    Some(payload) => payload.to_string(),
+                    None => self.default_payload(intention, reason, fired_at),
+                };
+                self.post(webhook_url, payload).await
+            }
+            IntentionAction::NotifyMcp { message } => {
+                let Some(notifier) = &self.notifier else {
+                    return ActionResult::Skipped {
+                        reason: "No MCP notifier configured".to_string(),
+                    };
+                };
+                let template = message.as_deref().unwrap_or(DEFAULT_MCP_MESSAGE);
+                let mut notification = notification(intention, reason, fired_at);
+                notification.message = Some(render_template(
+                    template, intention, reason, fired_at, false,
+                ));
+
+                let send = || async { notifier.notify(&notification).await };
+                match send
+                    .retry(self.backoff())
+                    .notify(|err, dur| {
+                        tracing::warn!(
+                            "MCP notification for intention {} failed, retrying in {:?}: {}",
+                            intention.id,
+                            dur,
+                            err
+                        );
+                    })
+                    .await
+                {
+                    Ok(()) => ActionResult::Success { details: None },
+                    Err(e) => ActionResult::Failed {
+                        error: e.to_string(),
+                    },
+                }
+            }
+            IntentionAction::Callback { callback_id, .. } => ActionResult::Skipped {
+                reason: format!("Callback {} is handled by the caller", callback_id),
+            },
+        }
+    }
+
+    fn default_payload(
+        &self,
+        intention: &Intention,
+        reason: &TriggerReason,
+        fired_at: DateTime<Utc>,
+    ) -> String {
+        let mut notification = notification(intention, reason, fired_at);
+        notification.message = Some(describe_reason(reason));
+        serde_json::to_string(&notification).unwrap_or_default()
+    }
+
+    /// POST a payload, retrying transient failures.
+    async fn post(&self, url: &str, payload: String) -> ActionResult {
+        let post_once = || async {
+            let response = self
+                .client
+                .post(url)
+                .timeout(self.timeout)
+                .header("Content-Type", "application/json")
+                .body(payload.clone())
+                .send()
+                .await
+                .map_err(|e| WebhookError::Transient(format!("Network error: {}", e)))?;
+
+            let status = response.status();
+            if status.is_success() {
+                Ok(status)
+            } else if status.is_server_error() {
+                Err(WebhookError::Transient(format!("Server error: {}", status)))
+            } else {
+                let body = response.text().await.unwrap_or_default();
+                Err(WebhookError::Permanent(format!(
+                    "Client error {}: {}",
+                    status, body
+                )))
+            }
+        };
+
+        let result = post_once
+            .retry(self.backoff())
+            .when(|e| matches!(e, WebhookError::Transient(_)))
+            .notify(|err, dur| {
+                tracing::warn!(
+                    "Intention webhook to {} failed, retrying in {:?}: {}",
+                    url,
+                    dur,
+                    err
+                );
+            })
+            .await;
+
+        match result {
+            Ok(status) => ActionResult::Success {
+                details: Some(format!("{} responded {}", url, status)),
+            },
+            Err(e) => ActionResult::Failed {
+                error: e.to_string(),
+            },
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBuilder {
+        let policy = &self.retry_policy;
+        ExponentialBuilder::default()
+            .with_max_times(policy.max_retries as usize)
+            .with_min_delay(Duration::from_millis(policy.initial_delay_ms))
+            .with_max_delay(Duration::from_millis(policy.max_delay_ms))
+            .with_factor(policy.multiplier)
+    }
+}
+
+fn notification(
+    intention: &Intention,
+    reason: &TriggerReason,
+    fired_at: DateTime<Utc>,
+) -> IntentionNotification {
+    IntentionNotification {
+        intention_id: intention.id.to_string(),
+        intention_name: intention.name.clone(),
+        memory_id: intention.memory_id.clone(),
+        user_id: intention.user_id.clone(),
+        fired_at,
+        reason: reason.clone(),
+        message: None,
+    }
+}
+
+/// Human-readable summary of why an intention fired.
+fn describe_reason(reason: &TriggerReason) -> String {
+    match reason {
+        TriggerReason::Keyword {
+            matched_keyword, ..
+        } => format!("\"{}\" was mentioned", matched_keyword),
+        TriggerReason::Topic { topic, similarity } => {
+            format!("\"{}\" was discussed (similarity {:.2})", topic, similarity)
+        }
+        TriggerReason::TimeElapsed { elapsed_secs } => {
+            format!("{} seconds elapsed", elapsed_secs)
+        }
+        TriggerReason::ScheduledTime { scheduled_at } => {
+            format!("scheduled for {}", scheduled_at.to_rfc3339())
+        }
+        TriggerReason::Composite { reasons } => reasons
+            .iter()
+            .map(describe_reason)
+            .collect::<Vec<_>>()
+            .join(" and "),
+    }
+}
+
+/// Where the fire happened in the conversation: the text around a keyword
+/// or the discussed topic.
+fn reason_context(reason: &TriggerReason) -> Option<String> {
+    match reason {
+        TriggerReason::Keyword { context, .. } => Some(context.clone()),
+        TriggerReason::Topic { topic, .. } => Some(topic.clone()),
+        TriggerReason::Composite { reasons } => reasons.iter().find_map(reason_context),
+        _ => None,
+    }
+}
+
+/// Replace the placeholders of a template. With `escape`, values are
+/// JSON-escaped for use inside JSON strings.
+fn render_template(
+    template: &str,
+    intention: &Intention,
+    reason: &TriggerReason,
+    fired_at: DateTime<Utc>,
+    escape: bool,
+) -> String {
+    let context = reason_context(reason).unwrap_or_default();
+    let values = [
+        ("intention.id", intention.id.to_string()),
+        ("intention.name", intention.name.clone()),
+        ("memory_id", intention.memory_id.clone().unwrap_or_default()),
+        ("user_id", intention.user_id.clone().unwrap_or_default()),
+        ("fired_at", fired_at.to_rfc3339()),
+        ("reason", describe_reason(reason)),
+        ("context", context),
+    ];
+
+    let mut rendered = template.to_string();
+    for (name, value) in values {
+        let value = if escape { json_escape(&value) } else { value };
+        rendered = rendered.replace(&format!("{{{{{}}}}}", name), &value);
+    }
+    rendered
+}
+
+/// Escape a value for use inside a JSON string, without the quotes.
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::String(value.to_string()).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RookError;
+    use crate::intentions::types::TriggerCondition;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    fn intention(action: IntentionAction) -> Intention {
+        Intention::new(
+            "Mentions of \"Rust\"",
+            TriggerCondition::keyword(vec!["rust".to_string()]),
+            action,
+        )
+        .with_memory("mem-1")
+    }
+
+    fn keyword_reason() -> TriggerReason {
+        TriggerReason::Keyword {
+            matched_keyword: "rust".to_string(),
+            context: "learning rust today".to_string(),
+        }
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 2,
+            initial_delay_ms: 1,
+            max_delay_ms: 1,
+            multiplier: 1.0,
+        }
+    }
+
+    /// Fails the first `failures` notifications, then records them.
+    struct MockNotifier {
+        failures: AtomicU32,
+        sent: Mutex<Vec<IntentionNotification>>,
+    }
+
+    impl MockNotifier {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures: AtomicU32::new(failures),
+                sent: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IntentionNotifier for MockNotifier {
+        async fn notify(&self, notification: &IntentionNotification) -> RookResult<()> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(RookError::api("client disconnected"));
+            }
+            self.sent.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_render_template_escapes_json() {
+        let intention = intention(IntentionAction::default());
+        let rendered = render_template(
+            r#"{"text": "{{intention.name}} ({{memory_id}}): {{context}}"}"#,
+            &intention,
+            &keyword_reason(),
+            Utc::now(),
+            true,
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(
+            json["text"],
+            "Mentions of \"Rust\" (mem-1): learning rust today"
+        );
+    }
+
+    #[test]
+    fn test_render_template_leaves_unknown_placeholders() {
+        let intention = intention(IntentionAction::default());
+        let rendered = render_template(
+            "{{reason}} {{unknown}}",
+            &intention,
+            &keyword_reason(),
+            Utc::now(),
+            false,
+        );
+        assert_eq!(rendered, "\"rust\" was mentioned {{unknown}}");
+    }
+
+    #[tokio::test]
+    async fn test_notify_mcp_retries_until_delivered() {
+        let notifier = Arc::new(MockNotifier::new(2));
+        let executor = ActionExecutor::new()
+            .with_retry(fast_retry())
+            .with_notifier(notifier.clone());
+        let intention = intention(IntentionAction::NotifyMcp { message: None });
+
+        let result = executor.execute(&intention, &keyword_reason()).await;
+
+        assert!(result.is_success());
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].memory_id.as_deref(), Some("mem-1"));
+        assert_eq!(
+            sent[0].message.as_deref(),
+            Some("Intention \"Mentions of \"Rust\"\" fired: \"rust\" was mentioned")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_mcp_fails_after_retries() {
+        let notifier = Arc::new(MockNotifier::new(10));
+        let executor = ActionExecutor::new()
+            .with_retry(fast_retry())
+            .with_notifier(notifier.clone());
+        let intention = intention(IntentionAction::NotifyMcp { message: None });
+
+        let result = executor.execute(&intention, &keyword_reason()).await;
+
+        assert!(result.is_failed());
+        // One attempt plus two retries
+        assert_eq!(notifier.failures.load(Ordering::SeqCst), 7);
+    }
+
+    #[tokio::test]
+    async fn test_notify_mcp_without_notifier_is_skipped() {
+        let executor = ActionExecutor::new();
+        let intention = intention(IntentionAction::NotifyMcp { message: None });
+
+        let result = executor.execute(&intention, &keyword_reason()).await;
+        assert!(matches!(result, ActionResult::Skipped { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_surface_memory_queues_memory() {
+        let surfaced = Arc::new(SurfacedMemories::new());
+        let executor = ActionExecutor::new().with_surfaced_memories(surfaced.clone());
+        let intention = intention(IntentionAction::default()).with_user("alice");
+
+        let result = executor.execute(&intention, &keyword_reason()).await;
+
+        assert!(result.is_success());
+        let pending = surfaced.pending(Some("alice"));
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].memory_id, "mem-1");
+        assert_eq!(pending[0].intention_id, intention.id);
+
+        // Nothing to surface without an attached memory
+        let mut detached = intention.clone();
+        detached.memory_id = None;
+        let result = executor.execute(&detached, &keyword_reason()).await;
+        assert!(matches!(result, ActionResult::Skipped { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_with_invalid_url_fails() {
+        let executor = ActionExecutor::new().with_retry(fast_retry());
+        let intention = intention(IntentionAction::CallWebhook {
+            url: "not a url".to_string(),
+            payload_template: None,
+        });
+
+        let result = executor.execute(&intention, &keyword_reason()).await;
+        assert!(result.is_failed());
+    }
+}
@@ -3,15 +3,26 @@
 //! Two-phase evaluation:
 //! 1. Fast bloom filter scan for keyword mentions (every message)
 //! 2. Expensive semantic similarity check (at configurable interval)
+//!
+//! Composite triggers (`All`, `Any`, `Not`) go through the same tiers: those
+//! requiring a keyword are skipped unless the bloom filter flags the message,
+//! and those with topics are only evaluated on semantic checks. Time-based
+//! conditions inside them hold once their time has come.
+//!
+//! The actions of fired intentions are run by an [`ActionExecutor`].
 
 use crate::error::RookResult;
 use crate::intentions::{
+    actions::ActionExecutor,
     bloom::KeywordBloomFilter,
+    cron::CronSchedule,
     store::IntentionStore,
-    triggers::{ActionResult, FiredIntention, TriggerReason},
+    triggers::{FiredIntention, TriggerReason},
     types::{Intention, TriggerCondition},
 };
 use crate::traits::Embedder;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -60,6 +71,10 @@ pub struct IntentionChecker<S: IntentionStore, E: Embedder> {
     keyword_intentions: RwLock<Vec<Intention>>,
     /// Cached topic intentions with embeddings.
     topic_intentions: RwLock<Vec<(Intention, Vec<f32>)>>,
+    /// Cached composite intentions.
+    composite_intentions: RwLock<Vec<CompositeIntention>>,
+    /// Runs the actions of fired intentions.
+    executor: ActionExecutor,
 }
 
 impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
@@ -73,9 +88,17 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
             message_counter: AtomicU32::new(0),
             keyword_intentions: RwLock::new(Vec::new()),
             topic_intentions: RwLock::new(Vec::new()),
+            composite_intentions: RwLock::new(Vec::new()),
+            executor: ActionExecutor::new(),
         }
     }
 
+    /// Builder: set the executor for actions of fired intentions
+    pub fn with_executor(mut self, executor: ActionExecutor) -> Self {
+        self.executor = executor;
+        self
+    }
+
     /// Load/refresh intentions from store.
     pub async fn refresh_intentions(&self) -> RookResult<()> {
         // Load keyword intentions and rebuild bloom filter
@@ -91,6 +114,35 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
 
         *self.keyword_intentions.write().await = keyword_intentions;
 
+        // Load composite intentions, adding required keywords to the bloom filter
+        let mut composites = Vec::new();
+        'composites: for intention in self.store.get_by_trigger_type("composite")? {
+            let required_keywords = intention.trigger.required_keywords();
+            if let Some(ref keywords) = required_keywords {
+                bloom.add_many(keywords);
+            }
+
+            let mut topic_embeddings = HashMap::new();
+            for topic in intention.trigger.topics() {
+                match self.embedder.embed(topic, None).await {
+                    Ok(emb) => {
+                        topic_embeddings.insert(topic.to_string(), emb);
+                    }
+                    Err(_) => continue 'composites, // Skip if embedding fails
+                }
+            }
+
+            composites.push(CompositeIntention {
+                has_topic: intention.trigger.has_topic(),
+                intention,
+                required_keywords,
+                topic_embeddings,
+            });
+        }
+        drop(bloom);
+
+        *self.composite_intentions.write().await = composites;
+
         // Load topic intentions and compute embeddings
         let topic_intentions = self.store.get_by_trigger_type("topic_discussed")?;
         let mut with_embeddings = Vec::new();
@@ -120,9 +172,9 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
         Ok(())
     }
 
-    /// Check message for triggered intentions.
+    /// Check message for triggered intentions and execute their actions.
     ///
-    /// Returns list of intentions that fired, with reasons.
+    /// Returns list of intentions that fired, with reasons and action results.
     pub async fn check(
         &self,
         message: &str,
@@ -135,11 +187,34 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
 
         // Tier 2: Semantic checking (at interval) - INT-07
         let count = self.message_counter.fetch_add(1, Ordering::Relaxed);
-        if count % self.config.semantic_check_interval == 0 {
-            fired.extend(self.check_topics(message, user_id).await?);
+        let semantic_check = count.checked_rem(self.config.semantic_check_interval) == Some(0);
+        let message_embedding = if semantic_check && self.needs_embedding().await {
+            Some(self.embedder.embed(message, None).await?)
+        } else {
+            None
+        };
+        if let Some(ref embedding) = message_embedding {
+            fired.extend(self.check_topics(embedding, user_id).await);
         }
 
-        Ok(fired)
+        // Composite triggers, screened by the same tiers
+        fired.extend(
+            self.check_composites(message, user_id, message_embedding.as_deref())
+                .await,
+        );
+
+        let results = futures::future::join_all(
+            fired
+                .iter()
+                .map(|(intention, reason)| self.executor.execute(intention, reason)),
+        )
+        .await;
+
+        Ok(fired
+            .into_iter()
+            .zip(results)
+            .map(|((intention, reason), result)| FiredIntention::new(intention.id, reason, result))
+            .collect())
     }
 
     /// Check keyword intentions using bloom filter pre-screening.
@@ -147,7 +222,7 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
         &self,
         message: &str,
         user_id: Option<&str>,
-    ) -> RookResult<Vec<FiredIntention>> {
+    ) -> RookResult<Vec<(Intention, TriggerReason)>> {
         let mut fired = Vec::new();
 
         // Phase 1: Bloom filter scan
@@ -177,34 +252,15 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
                 exact_match,
             } = &intention.trigger
             {
-                // Check if any keyword matches
-                for keyword in keywords {
-                    let normalized_keyword = keyword.to_lowercase();
-                    let matches = if *exact_match {
-                        // Exact word match
-                        message
-                            .to_lowercase()
-                            .split_whitespace()
-                            .any(|word| {
-                                word.trim_matches(|c: char| !c.is_alphanumeric())
-                                    == normalized_keyword
-                            })
-                    } else {
-                        // Substring match
-                        message.to_lowercase().contains(&normalized_keyword)
-                    };
-
-                    if matches {
-                        fired.push(FiredIntention::new(
-                            intention.id,
-                            TriggerReason::Keyword {
-                                matched_keyword: keyword.clone(),
-                                context: Self::extract_context(message, keyword),
-                            },
-                            ActionResult::Success { details: None },
-                        ));
-                        break; // Only fire once per intention
-                    }
+                // Only fire once per intention, for the first matching keyword
+                if let Some(keyword) = matched_keyword(message, keywords, *exact_match) {
+                    fired.push((
+                        intention.clone(),
+                        TriggerReason::Keyword {
+                            matched_keyword: keyword.clone(),
+                            context: Self::extract_context(message, keyword),
+                        },
+                    ));
                 }
             }
         }
@@ -212,17 +268,25 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
         Ok(fired)
     }
 
+    /// Whether any topic or composite intention needs the message embedding.
+    async fn needs_embedding(&self) -> bool {
+        !self.topic_intentions.read().await.is_empty()
+            || self
+                .composite_intentions
+                .read()
+                .await
+                .iter()
+                .any(|composite| composite.has_topic)
+    }
+
     /// Check topic intentions using semantic similarity.
     async fn check_topics(
         &self,
-        message: &str,
+        message_embedding: &[f32],
         user_id: Option<&str>,
-    ) -> RookResult<Vec<FiredIntention>> {
+    ) -> Vec<(Intention, TriggerReason)> {
         let mut fired = Vec::new();
 
-        // Get message embedding
-        let message_embedding = self.embedder.embed(message, None).await?;
-
         let intentions = self.topic_intentions.read().await;
         for (intention, topic_embedding) in intentions.iter() {
             // Skip if wrong user scope
@@ -240,45 +304,80 @@ impl<S: IntentionStore, E: Embedder> IntentionChecker<S, E> {
                 topic, threshold, ..
             } = &intention.trigger
             {
-                let similarity = cosine_similarity(&message_embedding, topic_embedding);
+                let similarity = cosine_similarity(message_embedding, topic_embedding);
 
                 if similarity >= *threshold {
-                    fired.push(FiredIntention::new(
-                        intention.id,
+                    fired.push((
+                        intention.clone(),
                         TriggerReason::Topic {
                             similarity,
                             topic: topic.clone(),
                         },
-                        ActionResult::Success { details: None },
                     ));
                 }
             }
         }
 
-        Ok(fired)
+        fired
     }
 
-    /// Extract context around matched keyword.
-    fn extract_context(message: &str, keyword: &str) -> String {
-        let lower_message = message.to_lowercase();
-        let lower_keyword = keyword.to_lowercase();
+    /// Check composite intentions. Those requiring a keyword are pre-screened
+    /// with the bloom filter, those with topics need the message embedding.
+    async fn check_composites(
+        &self,
+        message: &str,
+        user_id: Option<&str>,
+        message_embedding: Option<&[f32]>,
+    ) -> Vec<(Intention, TriggerReason)> {
+        let mut fired = Vec::new();
+
+        let composites = self.composite_intentions.read().await;
+        if composites.is_empty() {
+            return fired;
+        }
+        let keyword_hit = !self
+            .keyword_bloom
+            .read()
+            .await
+            .scan_message(message)
+            .is_empty();
+        let now = Utc::now();
 
-        if let Some(pos) = lower_message.find(&lower_keyword) {
-            let start = pos.saturating_sub(30);
-            let end = (pos + keyword.len() + 30).min(message.len());
+        for composite in composites.iter() {
+            let intention = &composite.intention;
 
-            let mut context = String::new();
-            if start > 0 {
-                context.push_str("...");
+            // Skip if wrong user scope
+            if let (Some(user), Some(intention_user)) = (user_id, &intention.user_id) {
+                if user != intention_user {
+                    continue;
+                }
             }
-            context.push_str(&message[start..end]);
-            if end < message.len() {
-                context.push_str("...");
+
+            if !intention.can_fire()
+                || (composite.required_keywords.is_some() && !keyword_hit)
+                || (composite.has_topic && message_embedding.is_none())
+            {
+                continue;
+            }
+
+            let evaluation = Evaluation {
+                message,
+                message_embedding,
+                topic_embeddings: &composite.topic_embeddings,
+                intention,
+                now,
+            };
+            if let Some(reasons) = evaluation.evaluate(&intention.trigger) {
+                fired.push((intention.clone(), TriggerReason::Composite { reasons }));
             }
-            context
-        } else {
-            message.chars().take(60).collect()
         }
+
+        fired
+    }
+
+    /// Extract context around matched keyword.
+    fn extract_context(message: &str, keyword: &str) -> String {
+        extract_context(message, keyword)
     }
 
     /// Get the current message count.
@@ -309,6 +408,159 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     }
 }
 
+/// The first keyword mentioned in the message, if any.
+fn matched_keyword<'a>(
+    message: &str,
+    keywords: &'a [String],
+    exact_match: bool,
+) -> Option<&'a String> {
+    let lower_message = message.to_lowercase();
+    keywords.iter().find(|keyword| {
+        let normalized_keyword = keyword.to_lowercase();
+        if exact_match {
+            // Exact word match
+            lower_message
+                .split_whitespace()
+                .any(|word| word.trim_matches(|c: char| !c.is_alphanumeric()) == normalized_keyword)
+        } else {
+            // Substring match
+            lower_message.contains(&normalized_keyword)
+        }
+    })
+}
+
+/// Extract context around matched keyword.
+fn extract_context(message: &str, keyword: &str) -> String {
+    let lower_message = message.to_lowercase();
+    let lower_keyword = keyword.to_lowercase();
+
+    if let Some(pos) = lower_message.find(&lower_keyword) {
+        let start = pos.saturating_sub(30);
+        let end = (pos + keyword.len() + 30).min(message.len());
+
+        let mut context = String::new();
+        if start > 0 {
+            context.push_str("...");
+        }
+        context.push_str(&message[start..end]);
+        if end < message.len() {
+            context.push_str("...");
+        }
+        context
+    } else {
+        message.chars().take(60).collect()
+    }
+}
+
+/// A composite intention with the embeddings of its topics.
+struct CompositeIntention {
+    intention: Intention,
+    /// Keywords one of which must be mentioned for the trigger to hold
+    required_keywords: Option<Vec<String>>,
+    /// Whether evaluation needs the message embedding
+    has_topic: bool,
+    topic_embeddings: HashMap<String, Vec<f32>>,
+}
+
+/// Evaluation of a (composite) trigger condition against a message.
+struct Evaluation<'a> {
+    message: &'a str,
+    message_embedding: Option<&'a [f32]>,
+    topic_embeddings: &'a HashMap<String, Vec<f32>>,
+    intention: &'a Intention,
+    now: DateTime<Utc>,
+}
+
+impl Evaluation<'_> {
+    /// The reasons the condition holds, or `None` if it does not.
+    ///
+    /// Time-based conditions hold once their time has come since the
+    /// intention last fired (or was created).
+    fn evaluate(&self, condition: &TriggerCondition) -> Option<Vec<TriggerReason>> {
+        match condition {
+            TriggerCondition::KeywordMention {
+                keywords,
+                exact_match,
+            } => matched_keyword(self.message, keywords, *exact_match).map(|keyword| {
+                vec![TriggerReason::Keyword {
+                    matched_keyword: keyword.clone(),
+                    context: extract_context(self.message, keyword),
+                }]
+            }),
+            TriggerCondition::TopicDiscussed {
+                topic,
+                topic_embedding,
+                threshold,
+            } => {
+                let topic_embedding = topic_embedding
+                    .as_ref()
+                    .or_else(|| self.topic_embeddings.get(topic))?;
+                let similarity = cosine_similarity(self.message_embedding?, topic_embedding);
+                (similarity >= *threshold).then(|| {
+                    vec![TriggerReason::Topic {
+                        similarity,
+                        topic: topic.clone(),
+                    }]
+                })
+            }
+            TriggerCondition::TimeElapsed {
+                duration_secs,
+                reference_time,
+                ..
+            } => {
+                let reference = self
+                    .intention
+                    .last_fired_at
+                    .or(*reference_time)
+                    .unwrap_or(self.intention.created_at);
+                let elapsed_secs = (self.now - reference).num_seconds().max(0) as u64;
+                (elapsed_secs >= *duration_secs)
+                    .then(|| vec![TriggerReason::TimeElapsed { elapsed_secs }])
+            }
+            TriggerCondition::ScheduledTime {
+                scheduled_at,
+                cron: Some(expression),
+                timezone,
+                ..
+            } => {
+                let schedule = CronSchedule::parse(expression, timezone.as_deref()).ok()?;
+                let since = self
+                    .intention
+                    .last_fired_at
+                    .unwrap_or(self.intention.created_at.max(*scheduled_at));
+                let due = schedule
+                    .next_after(since)
+                    .filter(|next| *next <= self.now)?;
+                Some(vec![TriggerReason::ScheduledTime { scheduled_at: due }])
+            }
+            TriggerCondition::ScheduledTime { scheduled_at, .. } => (*scheduled_at <= self.now)
+                .then(|| {
+                    vec![TriggerReason::ScheduledTime {
+                        scheduled_at: *scheduled_at,
+                    }]
+                }),
+            TriggerCondition::All { conditions } => {
+                let mut reasons = Vec::new();
+                for condition in conditions {
+                    reasons.extend(self.evaluate(condition)?);
+                }
+                Some(reasons)
+            }
+            TriggerCondition::Any { conditions } => {
+                let held: Vec<Vec<TriggerReason>> = conditions
+                    .iter()
+                    .filter_map(|condition| self.evaluate(condition))
+                    .collect();
+                (!held.is_empty()).then(|| held.into_iter().flatten().collect())
+            }
+            TriggerCondition::Not { condition } => match self.evaluate(condition) {
+                Some(_) => None,
+                None => Some(Vec::new()),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +612,70 @@ mod tests {
         assert_eq!(context_short, "Rust is great");
     }
 
+    #[test]
+    fn test_evaluate_composite() {
+        fn evaluation<'a>(
+            message: &'a str,
+            intention: &'a Intention,
+            topic_embeddings: &'a HashMap<String, Vec<f32>>,
+        ) -> Evaluation<'a> {
+            Evaluation {
+                message,
+                message_embedding: None,
+                topic_embeddings,
+                intention,
+                now: Utc::now(),
+            }
+        }
+
+        let mut intention = Intention::new(
+            "deploy reminder",
+            TriggerCondition::all(vec![
+                TriggerCondition::keyword(vec!["deploy".to_string()]),
+                TriggerCondition::time_elapsed(chrono::Duration::days(7)),
+            ]),
+            crate::intentions::IntentionAction::default(),
+        );
+        let topic_embeddings = HashMap::new();
+        // Keyword mentioned, but not 7 days since creation
+        let trigger = intention.trigger.clone();
+        assert!(evaluation("time to deploy", &intention, &topic_embeddings)
+            .evaluate(&trigger)
+            .is_none());
+
+        intention.created_at = Utc::now() - chrono::Duration::days(8);
+        let reasons = evaluation("time to deploy", &intention, &topic_embeddings)
+            .evaluate(&trigger)
+            .unwrap();
+        assert_eq!(reasons.len(), 2);
+        assert!(matches!(reasons[0], TriggerReason::Keyword { .. }));
+        assert!(evaluation("nothing to see", &intention, &topic_embeddings)
+            .evaluate(&trigger)
+            .is_none());
+
+        // Firing resets the elapsed time
+        intention.last_fired_at = Some(Utc::now());
+        assert!(evaluation("time to deploy", &intention, &topic_embeddings)
+            .evaluate(&trigger)
+            .is_none());
+
+        let any_not = TriggerCondition::any(vec![
+            TriggerCondition::keyword(vec!["rollback".to_string()]),
+            TriggerCondition::not(TriggerCondition::keyword(vec!["deploy".to_string()])),
+        ]);
+        assert!(evaluation("time to deploy", &intention, &topic_embeddings)
+            .evaluate(&any_not)
+            .is_none());
+        assert!(
+            evaluation("deploy and rollback", &intention, &topic_embeddings)
+                .evaluate(&any_not)
+                .is_some()
+        );
+        assert!(evaluation("hello", &intention, &topic_embeddings)
+            .evaluate(&any_not)
+            .is_some_and(|reasons| reasons.is_empty()));
+    }
+
     #[test]
     fn test_checker_config_default() {
         let config = CheckerConfig::default();
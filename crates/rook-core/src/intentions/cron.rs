@@ -0,0 +1,329 @@
+//! Cron schedules for scheduled-time intentions (INT-05).
+//!
+//! Expressions use the standard five fields (minute, hour, day of month,
+//! month, day of week) with an optional leading seconds field, plus the
+//! usual extensions: ranges, steps, lists, names (`MON`, `JAN`), `L` for the
+//! last day of the month, `#` for the nth weekday and aliases such as
+//! `@daily`. When both day of month and day of week are restricted, either
+//! matching is enough, as in Vixie cron.
+//!
+//! Schedules are evaluated in an IANA timezone (`Europe/Berlin`), UTC by
+//! default, so `0 9 * * MON-FRI` fires at 9:00 local time across daylight
+//! saving changes.
+
+use crate::error::{RookError, RookResult};
+use crate::intentions::types::{Intention, TriggerCondition};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+
+/// Most fires delivered for one intention when catching up after downtime.
+pub const MAX_CATCH_UP_FIRES: usize = 100;
+
+/// What to do with cron fires missed while the scheduler was not running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// Drop missed fires and wait for the next one
+    Skip,
+    /// Fire once for the most recent missed fire (default)
+    #[default]
+    FireOnce,
+    /// Fire for every missed fire, up to [`MAX_CATCH_UP_FIRES`]
+    FireAll,
+}
+
+impl MisfirePolicy {
+    /// The missed fire times to deliver, oldest first.
+    pub fn select(self, mut missed: Vec<DateTime<Utc>>) -> Vec<DateTime<Utc>> {
+        match self {
+            Self::Skip => Vec::new(),
+            Self::FireOnce => missed.pop().into_iter().collect(),
+            Self::FireAll => missed,
+        }
+    }
+}
+
+/// A parsed cron expression in a timezone.
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    cron: Cron,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    /// Parse a cron expression, evaluated in `timezone` (UTC if `None`).
+    pub fn parse(expression: &str, timezone: Option<&str>) -> RookResult<Self> {
+        let cron = Cron::new(expression.trim())
+            .with_seconds_optional()
+            .parse()
+            .map_err(|e| {
+                RookError::validation(format!("Invalid cron expression '{}': {}", expression, e))
+            })?;
+        let timezone = match timezone {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| RookError::validation(format!("Unknown timezone '{}'", name)))?,
+            None => Tz::UTC,
+        };
+        Ok(Self { cron, timezone })
+    }
+
+    /// The cron expression.
+    pub fn expression(&self) -> &str {
+        self.cron.as_str()
+    }
+
+    /// The timezone the expression is evaluated in.
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// The first fire strictly after `after`, if the expression ever matches
+    /// again.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.cron
+            .find_next_occurrence(&after.with_timezone(&self.timezone), false)
+            .ok()
+            .map(|next| next.with_timezone(&Utc))
+    }
+
+    /// The next `count` fires after `after`.
+    pub fn upcoming(&self, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        let mut fires = Vec::with_capacity(count);
+        let mut cursor = after;
+        while fires.len() < count {
+            match self.next_after(cursor) {
+                Some(next) => {
+                    fires.push(next);
+                    cursor = next;
+                }
+                None => break,
+            }
+        }
+        fires
+    }
+
+    /// Fires after `since` up to and including `until`, oldest first, at most
+    /// `limit` of the most recent ones.
+    pub fn between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: usize,
+    ) -> Vec<DateTime<Utc>> {
+        let mut fires = Vec::new();
+        let mut cursor = since;
+        while let Some(next) = self.next_after(cursor).filter(|next| *next <= until) {
+            if fires.len() == limit {
+                fires.remove(0);
+            }
+            fires.push(next);
+            cursor = next;
+        }
+        fires
+    }
+}
+
+/// A fire an intention is due for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingFire {
+    pub intention_id: uuid::Uuid,
+    pub intention_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    pub fire_at: DateTime<Utc>,
+}
+
+impl Intention {
+    /// The next `count` times this intention fires after `after`, fewer if
+    /// it expires or runs out of fires first. Only time-based triggers have
+    /// fire times.
+    pub fn next_fires(&self, after: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        if !self.can_fire() {
+            return Vec::new();
+        }
+        let remaining = self.max_fires.map_or(count, |max| {
+            count.min(max.saturating_sub(self.fire_count) as usize)
+        });
+
+        let fires = match &self.trigger {
+            TriggerCondition::ScheduledTime {
+                scheduled_at,
+                cron: Some(expression),
+                timezone,
+                ..
+            } => match CronSchedule::parse(expression, timezone.as_deref()) {
+                Ok(schedule) => schedule.upcoming(after.max(*scheduled_at), remaining),
+                Err(_) => Vec::new(),
+            },
+            TriggerCondition::ScheduledTime { scheduled_at, .. } => {
+                if self.fire_count == 0 && *scheduled_at > after {
+                    vec![*scheduled_at]
+                } else {
+                    Vec::new()
+                }
+            }
+            TriggerCondition::TimeElapsed {
+                duration_secs,
+                recurring,
+                reference_time,
+            } => {
+                if *duration_secs == 0 {
+                    return Vec::new();
+                }
+                let period = chrono::Duration::seconds(*duration_secs as i64);
+                let reference = self
+                    .last_fired_at
+                    .filter(|_| *recurring)
+                    .or(*reference_time)
+                    .unwrap_or(self.created_at);
+                if *recurring {
+                    let elapsed = (after - reference).num_seconds().max(0) as u64;
+                    let first = (elapsed / duration_secs + 1) as i32;
+                    (first..first + remaining as i32)
+                        .map(|i| reference + period * i)
+                        .collect()
+                } else if self.fire_count == 0 && reference + period > after {
+                    vec![reference + period]
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        };
+
+        fires
+            .into_iter()
+            .take(remaining)
+            .take_while(|fire| match self.expires_at {
+                Some(expires) => *fire <= expires,
+                None => true,
+            })
+            .collect()
+    }
+}
+
+/// The next `limit` fires across `intentions` after `after`, soonest first.
+pub fn upcoming_fires(
+    intentions: &[Intention],
+    after: DateTime<Utc>,
+    limit: usize,
+) -> Vec<UpcomingFire> {
+    let mut fires: Vec<UpcomingFire> = intentions
+        .iter()
+        .flat_map(|intention| {
+            intention
+                .next_fires(after, limit)
+                .into_iter()
+                .map(|fire_at| UpcomingFire {
+                    intention_id: intention.id,
+                    intention_name: intention.name.clone(),
+                    user_id: intention.user_id.clone(),
+                    fire_at,
+                })
+        })
+        .collect();
+    fires.sort_by_key(|fire| fire.fire_at);
+    fires.truncate(limit);
+    fires
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intentions::IntentionAction;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_input() {
+        assert!(CronSchedule::parse("0 9 * * *", None).is_ok());
+        assert!(CronSchedule::parse("30 0 9 * * MON-FRI", None).is_ok());
+        assert!(CronSchedule::parse("@daily", Some("Europe/Berlin")).is_ok());
+        assert!(CronSchedule::parse("61 * * * *", None).is_err());
+        assert!(CronSchedule::parse("not cron", None).is_err());
+        assert!(CronSchedule::parse("0 9 * * *", Some("Mars/Olympus")).is_err());
+    }
+
+    #[test]
+    fn test_next_after_in_timezone() {
+        let schedule = CronSchedule::parse("0 9 * * *", Some("America/New_York")).unwrap();
+
+        // 9:00 EST is 14:00 UTC in winter, 9:00 EDT is 13:00 UTC in summer
+        assert_eq!(
+            schedule.next_after(utc(2024, 1, 15, 12, 0)),
+            Some(utc(2024, 1, 15, 14, 0))
+        );
+        assert_eq!(
+            schedule.next_after(utc(2024, 7, 15, 13, 0)),
+            Some(utc(2024, 7, 16, 13, 0))
+        );
+    }
+
+    #[test]
+    fn test_upcoming_and_between() {
+        let schedule = CronSchedule::parse("0 */6 * * *", None).unwrap();
+        let start = utc(2024, 3, 1, 1, 0);
+
+        assert_eq!(
+            schedule.upcoming(start, 3),
+            vec![
+                utc(2024, 3, 1, 6, 0),
+                utc(2024, 3, 1, 12, 0),
+                utc(2024, 3, 1, 18, 0)
+            ]
+        );
+
+        let missed = schedule.between(start, utc(2024, 3, 2, 6, 0), 10);
+        assert_eq!(missed.len(), 5);
+        assert_eq!(missed.last(), Some(&utc(2024, 3, 2, 6, 0)));
+
+        // Only the most recent fires are kept past the limit
+        let capped = schedule.between(start, utc(2024, 3, 2, 6, 0), 2);
+        assert_eq!(capped, vec![utc(2024, 3, 2, 0, 0), utc(2024, 3, 2, 6, 0)]);
+    }
+
+    #[test]
+    fn test_misfire_policy_select() {
+        let missed = vec![utc(2024, 3, 1, 6, 0), utc(2024, 3, 1, 12, 0)];
+        assert!(MisfirePolicy::Skip.select(missed.clone()).is_empty());
+        assert_eq!(
+            MisfirePolicy::FireOnce.select(missed.clone()),
+            vec![utc(2024, 3, 1, 12, 0)]
+        );
+        assert_eq!(MisfirePolicy::FireAll.select(missed.clone()), missed);
+    }
+
+    #[test]
+    fn test_upcoming_fires_respects_max_fires() {
+        let now = Utc::now();
+        let hourly = Intention::new(
+            "hourly",
+            TriggerCondition::cron("0 * * * *", None),
+            IntentionAction::default(),
+        )
+        .max_fires(2);
+        let once = Intention::new(
+            "once",
+            TriggerCondition::scheduled_at(now + chrono::Duration::minutes(90)),
+            IntentionAction::default(),
+        );
+
+        let fires = upcoming_fires(&[hourly.clone(), once.clone()], now, 10);
+        assert_eq!(fires.len(), 3);
+        assert!(fires.windows(2).all(|w| w[0].fire_at <= w[1].fire_at));
+        assert_eq!(
+            fires.iter().filter(|f| f.intention_id == hourly.id).count(),
+            2
+        );
+        assert_eq!(
+            fires.iter().filter(|f| f.intention_id == once.id).count(),
+            1
+        );
+    }
+}
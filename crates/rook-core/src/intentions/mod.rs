@@ -4,12 +4,16 @@
 //! - `KeywordMention`: fires when keywords appear in conversation (INT-02)
 //! - `TopicDiscussed`: fires when semantically similar topics are discussed (INT-03)
 //! - `TimeElapsed`: fires after a duration since creation/last fire (INT-04)
-//! - `ScheduledTime`: fires at specific datetime or cron schedule (INT-05)
+//! - `ScheduledTime`: fires at specific datetime or cron schedule in a
+//!   timezone (INT-05, see [`CronSchedule`])
 //!
 //! The system uses tiered checking (INT-06, INT-07):
 //! - Tier 1: Bloom filter for fast keyword pre-screening (every message)
 //! - Tier 2: Embedding similarity for topics (at configurable interval)
 //!
+//! `SurfaceMemory` actions bridge intentions and retrieval: once one fires,
+//! its memory leads the user's next searches (see [`SurfacedMemories`]).
+//!
 //! # Example
 //!
 //! ```
@@ -19,7 +23,7 @@
 //! let intention = Intention::new(
 //!     "Rust mention alert",
 //!     TriggerCondition::keyword(vec!["rust".to_string(), "programming".to_string()]),
-//!     IntentionAction::SurfaceMemory { boost: 1.5 },
+//!     IntentionAction::SurfaceMemory { boost: 1.5, searches: 3 },
 //! );
 //!
 //! // Create an intention that fires 24 hours after creation
@@ -30,16 +34,22 @@
 //! );
 //! ```
 
+mod actions;
 mod bloom;
 mod checker;
+mod cron;
 mod scheduler;
 mod store;
+mod surfacing;
 mod triggers;
 mod types;
 
+pub use actions::{ActionExecutor, IntentionNotification, IntentionNotifier};
 pub use bloom::{BloomConfig, KeywordBloomFilter};
 pub use checker::{CheckerConfig, IntentionChecker};
+pub use cron::{upcoming_fires, CronSchedule, MisfirePolicy, UpcomingFire, MAX_CATCH_UP_FIRES};
 pub use scheduler::{FiredIntentionReceiver, IntentionScheduler};
 pub use store::{IntentionStore, SqliteIntentionStore};
+pub use surfacing::{SurfacedMemories, SurfacedMemory, DEFAULT_SURFACE_SEARCHES};
 pub use triggers::{ActionResult, FiredIntention, TriggerReason};
 pub use types::{Intention, IntentionAction, TriggerCondition};
@@ -1,15 +1,22 @@
 //! Scheduler for time-based intention triggers (INT-04, INT-05).
 //!
-//! Uses tokio-cron-scheduler for scheduled and elapsed-time triggers.
+//! Uses tokio-cron-scheduler for scheduled and elapsed-time triggers. Cron
+//! triggers run as a chain of one-shot jobs, each firing at the time
+//! computed by [`CronSchedule`] and scheduling the next, so fires carry their
+//! scheduled time and follow the trigger's timezone. Cron fires missed while
+//! the scheduler was down are delivered on scheduling according to the
+//! trigger's [`MisfirePolicy`].
 
 use crate::error::{RookError, RookResult};
 use crate::intentions::{
+    cron::{CronSchedule, MisfirePolicy, MAX_CATCH_UP_FIRES},
     store::IntentionStore,
     triggers::{ActionResult, FiredIntention, TriggerReason},
     types::{Intention, TriggerCondition},
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, RwLock};
 use tokio_cron_scheduler::{Job, JobScheduler};
@@ -23,7 +30,7 @@ pub struct IntentionScheduler {
     /// The job scheduler.
     scheduler: JobScheduler,
     /// Map of intention ID to job UUID.
-    job_map: RwLock<HashMap<Uuid, uuid::Uuid>>,
+    job_map: Arc<RwLock<HashMap<Uuid, uuid::Uuid>>>,
     /// Channel for sending fired intentions.
     fire_sender: mpsc::Sender<FiredIntention>,
     /// Whether scheduler is running.
@@ -44,7 +51,7 @@ impl IntentionScheduler {
         Ok((
             Self {
                 scheduler,
-                job_map: RwLock::new(HashMap::new()),
+                job_map: Arc::new(RwLock::new(HashMap::new())),
                 fire_sender: tx,
                 running: RwLock::new(false),
             },
@@ -97,10 +104,16 @@ impl IntentionScheduler {
             TriggerCondition::ScheduledTime {
                 scheduled_at,
                 cron,
-                ..
+                timezone,
+                misfire,
             } => {
                 if let Some(cron_expr) = cron {
-                    self.schedule_cron(intention.id, cron_expr).await
+                    let schedule = CronSchedule::parse(cron_expr, timezone.as_deref())?;
+                    let since = intention
+                        .last_fired_at
+                        .unwrap_or(intention.created_at.max(*scheduled_at));
+                    self.schedule_cron(intention.id, schedule, since, *misfire)
+                        .await
                 } else {
                     self.schedule_one_shot(intention.id, *scheduled_at).await
                 }
@@ -225,24 +238,43 @@ impl IntentionScheduler {
         Ok(())
     }
 
-    /// Schedule a cron-based recurring trigger.
-    async fn schedule_cron(&self, intention_id: Uuid, cron_expr: &str) -> RookResult<()> {
-        let sender = self.fire_sender.clone();
+    /// Schedule a cron-based recurring trigger, first delivering the fires
+    /// missed since `since` that `misfire` selects.
+    async fn schedule_cron(
+        &self,
+        intention_id: Uuid,
+        schedule: CronSchedule,
+        since: DateTime<Utc>,
+        misfire: MisfirePolicy,
+    ) -> RookResult<()> {
+        let now = Utc::now();
+        let catch_up = misfire.select(schedule.between(since, now, MAX_CATCH_UP_FIRES));
+        if !catch_up.is_empty() {
+            // Sent from a task, as the fires of many intentions may exceed
+            // the channel before its receiver runs
+            let sender = self.fire_sender.clone();
+            tokio::spawn(async move {
+                for scheduled_at in catch_up {
+                    let fired = FiredIntention::new(
+                        intention_id,
+                        TriggerReason::ScheduledTime { scheduled_at },
+                        ActionResult::Success { details: None },
+                    );
+                    let _ = sender.send(fired).await;
+                }
+            });
+        }
 
-        let job = Job::new_async(cron_expr, move |_uuid, _lock| {
-            let sender = sender.clone();
-            Box::pin(async move {
-                let fired = FiredIntention::new(
-                    intention_id,
-                    TriggerReason::ScheduledTime {
-                        scheduled_at: Utc::now(),
-                    },
-                    ActionResult::Success { details: None },
-                );
-                let _ = sender.send(fired).await;
-            })
-        })
-        .map_err(|e| RookError::internal(format!("Failed to create cron job: {}", e)))?;
+        let Some(next) = schedule.next_after(now) else {
+            return Ok(());
+        };
+        let chain = CronChain {
+            intention_id,
+            schedule: Arc::new(schedule),
+            sender: self.fire_sender.clone(),
+            job_map: self.job_map.clone(),
+        };
+        let job = chain.job(next)?;
 
         let job_id = job.guid();
         self.scheduler
@@ -280,6 +312,68 @@ impl IntentionScheduler {
     }
 }
 
+/// The one-shot jobs of a cron trigger, each scheduling the next.
+#[derive(Clone)]
+struct CronChain {
+    intention_id: Uuid,
+    schedule: Arc<CronSchedule>,
+    sender: mpsc::Sender<FiredIntention>,
+    job_map: Arc<RwLock<HashMap<Uuid, uuid::Uuid>>>,
+}
+
+impl CronChain {
+    /// A job firing at `scheduled_at`.
+    fn job(&self, scheduled_at: DateTime<Utc>) -> RookResult<Job> {
+        let delay = (scheduled_at - Utc::now()).to_std().unwrap_or_default();
+        let chain = self.clone();
+        Job::new_one_shot_async(delay, move |job_id, scheduler| {
+            let chain = chain.clone();
+            Box::pin(async move {
+                let fired = FiredIntention::new(
+                    chain.intention_id,
+                    TriggerReason::ScheduledTime { scheduled_at },
+                    ActionResult::Success { details: None },
+                );
+                let _ = chain.sender.send(fired).await;
+
+                if let Some(next) = chain.schedule.next_after(scheduled_at) {
+                    if let Err(e) = chain.add_next(job_id, next, &scheduler).await {
+                        tracing::error!(
+                            "Failed to schedule next fire of intention {}: {}",
+                            chain.intention_id,
+                            e
+                        );
+                    }
+                }
+            })
+        })
+        .map_err(|e| RookError::internal(format!("Failed to create cron job: {}", e)))
+    }
+
+    /// Replace the job `current` with one firing at `next`, unless the
+    /// intention was unscheduled or rescheduled meanwhile.
+    async fn add_next(
+        &self,
+        current: uuid::Uuid,
+        next: DateTime<Utc>,
+        scheduler: &JobScheduler,
+    ) -> RookResult<()> {
+        let mut job_map = self.job_map.write().await;
+        if job_map.get(&self.intention_id) != Some(&current) {
+            return Ok(());
+        }
+
+        let job = self.job(next)?;
+        let job_id = job.guid();
+        scheduler
+            .add(job)
+            .await
+            .map_err(|e| RookError::internal(format!("Failed to add job: {}", e)))?;
+        job_map.insert(self.intention_id, job_id);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,6 +429,7 @@ mod tests {
                 scheduled_at: past_time,
                 cron: None,
                 timezone: None,
+                misfire: MisfirePolicy::default(),
             },
             crate::intentions::IntentionAction::default(),
         );
@@ -350,6 +445,95 @@ mod tests {
         assert_eq!(fired.intention_id, intention.id);
     }
 
+    fn minutely_since(minutes_ago: i64, misfire: MisfirePolicy) -> Intention {
+        let mut intention = Intention::new(
+            "test",
+            TriggerCondition::ScheduledTime {
+                scheduled_at: Utc::now() - ChronoDuration::minutes(minutes_ago),
+                cron: Some("0 * * * * *".to_string()),
+                timezone: Some("Europe/Berlin".to_string()),
+                misfire,
+            },
+            crate::intentions::IntentionAction::default(),
+        );
+        intention.created_at = Utc::now() - ChronoDuration::minutes(minutes_ago);
+        intention
+    }
+
+    async fn drain(rx: &mut FiredIntentionReceiver) -> Vec<FiredIntention> {
+        let mut fired = Vec::new();
+        while let Ok(Some(f)) = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await {
+            fired.push(f);
+        }
+        fired
+    }
+
+    #[tokio::test]
+    async fn test_schedule_cron() {
+        let (scheduler, mut rx) = IntentionScheduler::new().await.unwrap();
+
+        let intention = Intention::new(
+            "test",
+            TriggerCondition::cron("0 9 * * MON-FRI", Some("America/New_York".to_string())),
+            crate::intentions::IntentionAction::default(),
+        );
+
+        scheduler.schedule(&intention).await.unwrap();
+        assert_eq!(scheduler.job_count().await, 1);
+        assert!(drain(&mut rx).await.is_empty());
+
+        scheduler.unschedule(intention.id).await.unwrap();
+        assert_eq!(scheduler.job_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_cron_invalid_expression() {
+        let (scheduler, _rx) = IntentionScheduler::new().await.unwrap();
+
+        let intention = Intention::new(
+            "test",
+            TriggerCondition::cron("every day", None),
+            crate::intentions::IntentionAction::default(),
+        );
+
+        assert!(scheduler.schedule(&intention).await.is_err());
+        assert_eq!(scheduler.job_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cron_misfires() {
+        let (scheduler, mut rx) = IntentionScheduler::new().await.unwrap();
+
+        // Missed fires every minute for the last ten minutes
+        let skip = minutely_since(10, MisfirePolicy::Skip);
+        scheduler.schedule(&skip).await.unwrap();
+        assert!(drain(&mut rx).await.is_empty());
+
+        let once = minutely_since(10, MisfirePolicy::FireOnce);
+        scheduler.schedule(&once).await.unwrap();
+        let fired = drain(&mut rx).await;
+        assert_eq!(fired.len(), 1);
+        match fired[0].reason {
+            TriggerReason::ScheduledTime { scheduled_at } => {
+                assert!(Utc::now() - scheduled_at < ChronoDuration::minutes(1));
+            }
+            _ => panic!("Wrong trigger reason"),
+        }
+
+        let all = minutely_since(10, MisfirePolicy::FireAll);
+        scheduler.schedule(&all).await.unwrap();
+        let fired = drain(&mut rx).await;
+        assert!((9..=10).contains(&fired.len()));
+
+        // Fires since the last one only
+        let mut resumed = minutely_since(10, MisfirePolicy::FireAll);
+        resumed.last_fired_at = Some(Utc::now() - ChronoDuration::seconds(150));
+        scheduler.schedule(&resumed).await.unwrap();
+        assert!((2..=3).contains(&drain(&mut rx).await.len()));
+
+        assert_eq!(scheduler.job_count().await, 4);
+    }
+
     #[tokio::test]
     async fn test_schedule_keyword_intention_is_noop() {
         let (scheduler, _rx) = IntentionScheduler::new().await.unwrap();
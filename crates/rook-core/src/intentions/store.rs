@@ -24,6 +24,9 @@ pub trait IntentionStore: Send + Sync {
     /// Delete an intention
     fn delete(&self, id: Uuid) -> RookResult<()>;
 
+    /// Get all intentions, active or not
+    fn get_all(&self) -> RookResult<Vec<Intention>>;
+
     /// Get all active intentions
     fn get_active(&self) -> RookResult<Vec<Intention>>;
 
@@ -42,10 +45,26 @@ pub trait IntentionStore: Send + Sync {
     /// Get fire history for an intention
     fn get_fire_history(&self, intention_id: Uuid, limit: usize) -> RookResult<Vec<FiredIntention>>;
 
+    /// Get recent fires of existing intentions, newest first, optionally for
+    /// one user and only after `since`
+    fn get_recent_fires(
+        &self,
+        user_id: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> RookResult<Vec<FiredIntention>>;
+
     /// Clean up expired intentions
     fn cleanup_expired(&self) -> RookResult<usize>;
+
+    /// Restore exported intentions, replacing any with the same ID. Either
+    /// every intention is written or none is
+    fn restore(&self, intentions: &[Intention]) -> RookResult<usize>;
 }
 
+/// Intention ID, fire time, reason and action result columns of a fire
+type FireRow = (String, String, String, String);
+
 /// SQLite-backed intention store
 pub struct SqliteIntentionStore {
     conn: Mutex<Connection>,
@@ -123,6 +142,9 @@ impl SqliteIntentionStore {
             TriggerCondition::TopicDiscussed { .. } => "topic_discussed",
             TriggerCondition::TimeElapsed { .. } => "time_elapsed",
             TriggerCondition::ScheduledTime { .. } => "scheduled_time",
+            TriggerCondition::All { .. }
+            | TriggerCondition::Any { .. }
+            | TriggerCondition::Not { .. } => "composite",
         }
     }
 
@@ -146,6 +168,8 @@ impl SqliteIntentionStore {
         match action {
             crate::intentions::IntentionAction::SurfaceMemory { .. } => "surface_memory",
             crate::intentions::IntentionAction::Notify { .. } => "notify",
+            crate::intentions::IntentionAction::CallWebhook { .. } => "call_webhook",
+            crate::intentions::IntentionAction::NotifyMcp { .. } => "notify_mcp",
             crate::intentions::IntentionAction::Callback { .. } => "callback",
             crate::intentions::IntentionAction::Log { .. } => "log",
         }
@@ -196,22 +220,44 @@ impl SqliteIntentionStore {
             metadata: serde_json::from_str(&metadata)?,
         })
     }
-}
 
-impl IntentionStore for SqliteIntentionStore {
-    fn add(&self, intention: &Intention) -> RookResult<()> {
-        let conn = self.conn.lock().unwrap();
+    /// Raw columns of an `intention_fires` row
+    fn read_fire_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<FireRow> {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+    }
+
+    fn fire_from_row(
+        (intention_id, fired_at, reason_data, action_result): FireRow,
+    ) -> RookResult<FiredIntention> {
+        Ok(FiredIntention {
+            intention_id: Uuid::parse_str(&intention_id)
+                .map_err(|e| crate::error::RookError::parse(e.to_string()))?,
+            fired_at: DateTime::parse_from_rfc3339(&fired_at)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| crate::error::RookError::parse(e.to_string()))?,
+            reason: serde_json::from_str(&reason_data)?,
+            action_result: serde_json::from_str(&action_result)?,
+        })
+    }
+
+    /// Insert an intention row, replacing any row with the same ID when
+    /// `replace` is set
+    fn insert(conn: &Connection, intention: &Intention, replace: bool) -> RookResult<()> {
         let trigger_type = Self::trigger_type_name(&intention.trigger);
         let trigger_data = Self::serialize_trigger(&intention.trigger)?;
         let action_type = Self::action_type_name(&intention.action);
         let action_data = Self::serialize_action(&intention.action)?;
         let metadata = serde_json::to_string(&intention.metadata)?;
 
+        let verb = if replace { "INSERT OR REPLACE" } else { "INSERT" };
         conn.execute(
-            r#"INSERT INTO intentions
+            &format!(
+                r#"{} INTO intentions
                (id, name, memory_id, user_id, trigger_type, trigger_data, action_type, action_data,
                 expires_at, active, created_at, last_fired_at, fire_count, max_fires, metadata)
                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"#,
+                verb
+            ),
             params![
                 intention.id.to_string(),
                 intention.name,
@@ -232,6 +278,13 @@ impl IntentionStore for SqliteIntentionStore {
         )?;
         Ok(())
     }
+}
+
+impl IntentionStore for SqliteIntentionStore {
+    fn add(&self, intention: &Intention) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::insert(&conn, intention, false)
+    }
 
     fn get(&self, id: Uuid) -> RookResult<Option<Intention>> {
         let conn = self.conn.lock().unwrap();
@@ -282,6 +335,11 @@ impl IntentionStore for SqliteIntentionStore {
 
     fn delete(&self, id: Uuid) -> RookResult<()> {
         let conn = self.conn.lock().unwrap();
+        // Fire history references the intention
+        conn.execute(
+            "DELETE FROM intention_fires WHERE intention_id = ?1",
+            params![id.to_string()],
+        )?;
         conn.execute(
             "DELETE FROM intentions WHERE id = ?1",
             params![id.to_string()],
@@ -289,6 +347,21 @@ impl IntentionStore for SqliteIntentionStore {
         Ok(())
     }
 
+    fn get_all(&self) -> RookResult<Vec<Intention>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"SELECT id, name, memory_id, user_id, trigger_data, action_data,
+                      expires_at, active, created_at, last_fired_at, fire_count, max_fires, metadata
+               FROM intentions"#,
+        )?;
+
+        let results = stmt.query_map([], |row| Ok(Self::row_to_intention(row)))?;
+
+        results
+            .map(|r| r.map_err(|e| e.into()).and_then(|inner| inner))
+            .collect()
+    }
+
     fn get_active(&self) -> RookResult<Vec<Intention>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
@@ -362,6 +435,7 @@ impl IntentionStore for SqliteIntentionStore {
             crate::intentions::TriggerReason::Topic { .. } => "topic",
             crate::intentions::TriggerReason::TimeElapsed { .. } => "time_elapsed",
             crate::intentions::TriggerReason::ScheduledTime { .. } => "scheduled_time",
+            crate::intentions::TriggerReason::Composite { .. } => "composite",
         };
         let reason_data = serde_json::to_string(&fired.reason)?;
         let action_result = serde_json::to_string(&fired.action_result)?;
@@ -397,29 +471,34 @@ impl IntentionStore for SqliteIntentionStore {
                LIMIT ?2"#,
         )?;
 
-        let results = stmt.query_map(params![intention_id.to_string(), limit as i64], |row| {
-            let intention_id: String = row.get(0)?;
-            let fired_at: String = row.get(1)?;
-            let reason_data: String = row.get(2)?;
-            let action_result: String = row.get(3)?;
+        let results = stmt.query_map(
+            params![intention_id.to_string(), limit as i64],
+            Self::read_fire_row,
+        )?;
+        results.map(|r| Self::fire_from_row(r?)).collect()
+    }
 
-            Ok((intention_id, fired_at, reason_data, action_result))
-        })?;
+    fn get_recent_fires(
+        &self,
+        user_id: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> RookResult<Vec<FiredIntention>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"SELECT f.intention_id, f.fired_at, f.reason_data, f.action_result
+               FROM intention_fires f
+               JOIN intentions i ON i.id = f.intention_id
+               WHERE (?1 IS NULL OR i.user_id = ?1)
+                 AND (?2 IS NULL OR f.fired_at > ?2)
+               ORDER BY f.fired_at DESC, f.id DESC
+               LIMIT ?3"#,
+        )?;
 
-        results
-            .map(|r| {
-                let (intention_id, fired_at, reason_data, action_result) = r?;
-                Ok(FiredIntention {
-                    intention_id: Uuid::parse_str(&intention_id)
-                        .map_err(|e| crate::error::RookError::parse(e.to_string()))?,
-                    fired_at: DateTime::parse_from_rfc3339(&fired_at)
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .map_err(|e| crate::error::RookError::parse(e.to_string()))?,
-                    reason: serde_json::from_str(&reason_data)?,
-                    action_result: serde_json::from_str(&action_result)?,
-                })
-            })
-            .collect()
+        let since = since.map(|dt| dt.to_rfc3339());
+        let results =
+            stmt.query_map(params![user_id, since, limit as i64], Self::read_fire_row)?;
+        results.map(|r| Self::fire_from_row(r?)).collect()
     }
 
     fn cleanup_expired(&self) -> RookResult<usize> {
@@ -434,6 +513,16 @@ impl IntentionStore for SqliteIntentionStore {
 
         Ok(count)
     }
+
+    fn restore(&self, intentions: &[Intention]) -> RookResult<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for intention in intentions {
+            Self::insert(&tx, intention, true)?;
+        }
+        tx.commit()?;
+        Ok(intentions.len())
+    }
 }
 
 #[cfg(test)]
@@ -470,6 +559,37 @@ mod tests {
         assert!(store.get(intention.id).unwrap().is_none());
     }
 
+    #[test]
+    fn test_restore_replaces_and_keeps_inactive() {
+        let source = SqliteIntentionStore::in_memory().unwrap();
+        let mut inactive = Intention::new(
+            "inactive",
+            TriggerCondition::keyword(vec!["old".to_string()]),
+            IntentionAction::default(),
+        );
+        inactive.active = false;
+        source.add(&inactive).unwrap();
+        source
+            .add(&Intention::new(
+                "active",
+                TriggerCondition::keyword(vec!["new".to_string()]),
+                IntentionAction::default(),
+            ))
+            .unwrap();
+
+        let exported = source.get_all().unwrap();
+        assert_eq!(exported.len(), 2);
+
+        let target = SqliteIntentionStore::in_memory().unwrap();
+        let mut stale = inactive.clone();
+        stale.name = "stale".to_string();
+        target.add(&stale).unwrap();
+
+        assert_eq!(target.restore(&exported).unwrap(), 2);
+        assert_eq!(target.get_all().unwrap().len(), 2);
+        assert_eq!(target.get(inactive.id).unwrap().unwrap().name, "inactive");
+    }
+
     #[test]
     fn test_get_active_intentions() {
         let store = SqliteIntentionStore::in_memory().unwrap();
@@ -630,4 +750,49 @@ mod tests {
         let active_retrieved = store.get(active.id).unwrap().unwrap();
         assert!(active_retrieved.active);
     }
+
+    #[test]
+    fn test_get_recent_fires() {
+        let store = SqliteIntentionStore::in_memory().unwrap();
+
+        let mine = Intention::new(
+            "mine",
+            TriggerCondition::keyword(vec!["test".to_string()]),
+            IntentionAction::default(),
+        )
+        .with_user("user1");
+        let theirs = Intention::new(
+            "theirs",
+            TriggerCondition::keyword(vec!["test".to_string()]),
+            IntentionAction::default(),
+        )
+        .with_user("user2");
+        store.add(&mine).unwrap();
+        store.add(&theirs).unwrap();
+
+        let reason = || crate::intentions::TriggerReason::TimeElapsed { elapsed_secs: 60 };
+        let mut first = FiredIntention::success(mine.id, reason());
+        first.fired_at = Utc::now() - chrono::Duration::minutes(5);
+        store.record_fire(mine.id, &first).unwrap();
+        store
+            .record_fire(mine.id, &FiredIntention::success(mine.id, reason()))
+            .unwrap();
+        store
+            .record_fire(theirs.id, &FiredIntention::success(theirs.id, reason()))
+            .unwrap();
+
+        assert_eq!(store.get_recent_fires(None, None, 10).unwrap().len(), 3);
+
+        let user1 = store.get_recent_fires(Some("user1"), None, 10).unwrap();
+        assert_eq!(user1.len(), 2);
+        assert!(user1[0].fired_at > user1[1].fired_at);
+
+        let since = Utc::now() - chrono::Duration::minutes(1);
+        let recent = store.get_recent_fires(Some("user1"), Some(since), 10).unwrap();
+        assert_eq!(recent.len(), 1);
+
+        // Deleting an intention removes its fires
+        store.delete(theirs.id).unwrap();
+        assert_eq!(store.get_recent_fires(None, None, 10).unwrap().len(), 2);
+    }
 }
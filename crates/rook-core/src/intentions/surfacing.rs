@@ -0,0 +1,158 @@
+//! Memories surfaced in search by fired intentions.
+//!
+//! When an intention with a `SurfaceMemory` action fires, its memory is queued
+//! in [`SurfacedMemories`] for the intention's user. The user's next searches
+//! return it ahead of the similarity results, its score multiplied by the
+//! action's boost, until the number of searches the action asked for ran out.
+//!
+//! The queue is kept in memory only; surfacings pending at shutdown are lost.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+/// Searches that surface a memory by default after its intention fired.
+pub const DEFAULT_SURFACE_SEARCHES: u32 = 3;
+
+/// A memory waiting to be surfaced in a user's searches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SurfacedMemory {
+    pub memory_id: String,
+    /// The intention that surfaced the memory
+    pub intention_id: Uuid,
+    /// Factor the memory's score is multiplied by
+    pub boost: f32,
+    /// Searches the memory is still surfaced in
+    pub remaining_searches: u32,
+}
+
+/// Memories to surface in upcoming searches, per user.
+///
+/// Shared between the [`ActionExecutor`](crate::intentions::ActionExecutor)
+/// running `SurfaceMemory` actions and the [`Memory`](crate::memory::Memory)
+/// searching.
+#[derive(Debug, Default)]
+pub struct SurfacedMemories {
+    by_user: Mutex<HashMap<Option<String>, Vec<SurfacedMemory>>>,
+}
+
+impl SurfacedMemories {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Surface a memory in the next `searches` searches of `user_id`.
+    ///
+    /// Surfacing a memory that is already waiting replaces its boost and
+    /// remaining searches.
+    pub fn surface(&self, user_id: Option<&str>, memory: SurfacedMemory) {
+        if memory.remaining_searches == 0 {
+            return;
+        }
+        let mut by_user = self.by_user.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = by_user.entry(user_id.map(String::from)).or_default();
+        pending.retain(|m| m.memory_id != memory.memory_id);
+        pending.push(memory);
+    }
+
+    /// Memories waiting to be surfaced for `user_id`, most boosted first.
+    pub fn pending(&self, user_id: Option<&str>) -> Vec<SurfacedMemory> {
+        let by_user = self.by_user.lock().unwrap_or_else(|e| e.into_inner());
+        let mut pending = by_user
+            .get(&user_id.map(String::from))
+            .cloned()
+            .unwrap_or_default();
+        sort_by_boost(&mut pending);
+        pending
+    }
+
+    /// The memories to surface in a search by `user_id`, most boosted first,
+    /// counting the search against each of them.
+    pub fn take_for_search(&self, user_id: Option<&str>) -> Vec<SurfacedMemory> {
+        let mut by_user = self.by_user.lock().unwrap_or_else(|e| e.into_inner());
+        let key = user_id.map(String::from);
+        let Some(pending) = by_user.get_mut(&key) else {
+            return Vec::new();
+        };
+
+        let mut surfaced = pending.clone();
+        for memory in pending.iter_mut() {
+            memory.remaining_searches -= 1;
+        }
+        pending.retain(|m| m.remaining_searches > 0);
+        if pending.is_empty() {
+            by_user.remove(&key);
+        }
+
+        sort_by_boost(&mut surfaced);
+        surfaced
+    }
+
+    /// Stop surfacing a memory, e.g. once it was deleted.
+    pub fn remove_memory(&self, memory_id: &str) {
+        let mut by_user = self.by_user.lock().unwrap_or_else(|e| e.into_inner());
+        for pending in by_user.values_mut() {
+            pending.retain(|m| m.memory_id != memory_id);
+        }
+        by_user.retain(|_, pending| !pending.is_empty());
+    }
+}
+
+fn sort_by_boost(memories: &mut [SurfacedMemory]) {
+    memories.sort_by(|a, b| b.boost.total_cmp(&a.boost));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surfaced(memory_id: &str, boost: f32, searches: u32) -> SurfacedMemory {
+        SurfacedMemory {
+            memory_id: memory_id.to_string(),
+            intention_id: Uuid::new_v4(),
+            boost,
+            remaining_searches: searches,
+        }
+    }
+
+    #[test]
+    fn test_surfaced_for_the_requested_searches() {
+        let queue = SurfacedMemories::new();
+        queue.surface(Some("alice"), surfaced("m1", 1.5, 2));
+        queue.surface(Some("alice"), surfaced("m2", 3.0, 1));
+        queue.surface(Some("bob"), surfaced("m3", 1.5, 1));
+
+        let first: Vec<_> = queue
+            .take_for_search(Some("alice"))
+            .into_iter()
+            .map(|m| m.memory_id)
+            .collect();
+        assert_eq!(first, vec!["m2", "m1"]);
+
+        let second = queue.take_for_search(Some("alice"));
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].memory_id, "m1");
+        assert_eq!(second[0].remaining_searches, 1);
+
+        assert!(queue.take_for_search(Some("alice")).is_empty());
+        assert!(queue.take_for_search(None).is_empty());
+        assert_eq!(queue.pending(Some("bob")).len(), 1);
+    }
+
+    #[test]
+    fn test_surface_again_replaces_and_remove() {
+        let queue = SurfacedMemories::new();
+        queue.surface(None, surfaced("m1", 1.5, 1));
+        queue.surface(None, surfaced("m1", 2.0, 5));
+        queue.surface(None, surfaced("m2", 2.0, 0));
+
+        let pending = queue.pending(None);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].remaining_searches, 5);
+
+        queue.remove_memory("m1");
+        assert!(queue.pending(None).is_empty());
+    }
+}
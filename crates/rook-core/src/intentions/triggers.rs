@@ -37,6 +37,11 @@ pub enum TriggerReason {
         /// The scheduled time that triggered
         scheduled_at: DateTime<Utc>,
     },
+    /// Fired due to a composite trigger
+    Composite {
+        /// Reasons of the conditions that held
+        reasons: Vec<TriggerReason>,
+    },
 }
 
 /// Record of an intention that fired
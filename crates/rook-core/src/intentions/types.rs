@@ -5,6 +5,9 @@
 //! - `TriggerCondition`: Conditions that cause an intention to fire
 //! - `IntentionAction`: Actions to perform when an intention fires
 
+use crate::error::{RookError, RookResult};
+use crate::intentions::cron::{CronSchedule, MisfirePolicy};
+use crate::intentions::surfacing::DEFAULT_SURFACE_SEARCHES;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -141,15 +144,26 @@ pub enum TriggerCondition {
     },
     /// Fire at a specific scheduled time (INT-05)
     ScheduledTime {
-        /// Scheduled datetime in UTC
+        /// Scheduled datetime in UTC; with a cron expression, the schedule
+        /// starts after this time
         scheduled_at: DateTime<Utc>,
-        /// Optional cron expression for recurring schedules
+        /// Optional cron expression for recurring schedules (see
+        /// [`CronSchedule`])
         #[serde(skip_serializing_if = "Option::is_none")]
         cron: Option<String>,
-        /// Timezone for cron interpretation
+        /// IANA timezone for cron interpretation (default: UTC)
         #[serde(skip_serializing_if = "Option::is_none")]
         timezone: Option<String>,
+        /// What to do with cron fires missed while the scheduler was down
+        #[serde(default)]
+        misfire: MisfirePolicy,
     },
+    /// Fire when every condition holds
+    All { conditions: Vec<TriggerCondition> },
+    /// Fire when any condition holds
+    Any { conditions: Vec<TriggerCondition> },
+    /// Fire when the condition does not hold
+    Not { condition: Box<TriggerCondition> },
 }
 
 fn default_topic_threshold() -> f32 {
@@ -189,8 +203,158 @@ impl TriggerCondition {
             scheduled_at: dt,
             cron: None,
             timezone: None,
+            misfire: MisfirePolicy::default(),
+        }
+    }
+
+    /// Create a recurring cron trigger, evaluated in `timezone` (UTC if
+    /// `None`), starting now
+    pub fn cron(expression: impl Into<String>, timezone: Option<String>) -> Self {
+        Self::ScheduledTime {
+            scheduled_at: Utc::now(),
+            cron: Some(expression.into()),
+            timezone,
+            misfire: MisfirePolicy::default(),
+        }
+    }
+
+    /// Create a trigger firing when every condition holds
+    ///
+    /// Composite triggers are evaluated by the `IntentionChecker` on each
+    /// message. Inside them, time-based conditions hold once their time has
+    /// come since the intention last fired, e.g. "`deploy` is mentioned and
+    /// at least 7 days passed since the last fire":
+    ///
+    /// ```
+    /// use rook_core::intentions::TriggerCondition;
+    ///
+    /// let trigger = TriggerCondition::all(vec![
+    ///     TriggerCondition::keyword(vec!["deploy".to_string()]),
+    ///     TriggerCondition::time_elapsed(chrono::Duration::days(7)),
+    /// ]);
+    /// assert!(trigger.validate().is_ok());
+    /// ```
+    pub fn all(conditions: Vec<TriggerCondition>) -> Self {
+        Self::All { conditions }
+    }
+
+    /// Create a trigger firing when any condition holds
+    pub fn any(conditions: Vec<TriggerCondition>) -> Self {
+        Self::Any { conditions }
+    }
+
+    /// Create a trigger firing when the condition does not hold
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(condition: TriggerCondition) -> Self {
+        Self::Not {
+            condition: Box::new(condition),
+        }
+    }
+
+    /// Whether this combines other conditions
+    pub fn is_composite(&self) -> bool {
+        matches!(self, Self::All { .. } | Self::Any { .. } | Self::Not { .. })
+    }
+
+    /// Keywords of which the message must mention one for the trigger to
+    /// hold, used for bloom filter pre-screening. `None` if the trigger can
+    /// hold without any keyword.
+    pub fn required_keywords(&self) -> Option<Vec<String>> {
+        match self {
+            Self::KeywordMention { keywords, .. } => Some(keywords.clone()),
+            Self::All { conditions } => conditions
+                .iter()
+                .filter_map(|c| c.required_keywords())
+                .min_by_key(|keywords| keywords.len()),
+            Self::Any { conditions } => conditions
+                .iter()
+                .map(|c| c.required_keywords())
+                .collect::<Option<Vec<_>>>()
+                .map(|keywords| keywords.concat()),
+            _ => None,
+        }
+    }
+
+    /// Whether evaluating the trigger needs the message embedding
+    pub fn has_topic(&self) -> bool {
+        match self {
+            Self::TopicDiscussed { .. } => true,
+            Self::All { conditions } | Self::Any { conditions } => {
+                conditions.iter().any(|c| c.has_topic())
+            }
+            Self::Not { condition } => condition.has_topic(),
+            _ => false,
+        }
+    }
+
+    /// Topics of the trigger and its nested conditions
+    pub fn topics(&self) -> Vec<&str> {
+        match self {
+            Self::TopicDiscussed { topic, .. } => vec![topic.as_str()],
+            Self::All { conditions } | Self::Any { conditions } => {
+                conditions.iter().flat_map(|c| c.topics()).collect()
+            }
+            Self::Not { condition } => condition.topics(),
+            _ => Vec::new(),
         }
     }
+
+    /// Check that the trigger can ever fire
+    pub fn validate(&self) -> RookResult<()> {
+        match self {
+            Self::KeywordMention { keywords, .. } => {
+                if keywords.iter().all(|k| k.trim().is_empty()) {
+                    return Err(RookError::validation("Keyword trigger needs at least one keyword"));
+                }
+            }
+            Self::TopicDiscussed {
+                topic, threshold, ..
+            } => {
+                if topic.trim().is_empty() {
+                    return Err(RookError::validation("Topic trigger needs a topic"));
+                }
+                if !(0.0..=1.0).contains(threshold) {
+                    return Err(RookError::validation(
+                        "Topic threshold must be between 0.0 and 1.0",
+                    ));
+                }
+            }
+            Self::TimeElapsed { duration_secs, .. } => {
+                if *duration_secs == 0 {
+                    return Err(RookError::validation(
+                        "Time elapsed trigger needs a duration of at least one second",
+                    ));
+                }
+            }
+            Self::ScheduledTime { cron, timezone, .. } => {
+                if let Some(cron) = cron {
+                    if cron.trim().is_empty() {
+                        return Err(RookError::validation("Cron expression is empty"));
+                    }
+                    CronSchedule::parse(cron, timezone.as_deref())?;
+                }
+            }
+            Self::All { conditions } | Self::Any { conditions } => {
+                if conditions.is_empty() {
+                    return Err(RookError::validation(
+                        "Composite trigger needs at least one condition",
+                    ));
+                }
+                for condition in conditions {
+                    condition.validate()?;
+                }
+            }
+            Self::Not { condition } => {
+                if matches!(**condition, Self::Not { .. }) {
+                    return Err(RookError::validation(
+                        "Nested negations cancel out; use the inner condition",
+                    ));
+                }
+                condition.validate()?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Action to perform when intention fires
@@ -201,6 +365,9 @@ pub enum IntentionAction {
     SurfaceMemory {
         /// Boost factor for the memory (1.0 = normal, >1.0 = higher)
         boost: f32,
+        /// Number of the user's searches to surface the memory in
+        #[serde(default = "default_surface_searches")]
+        searches: u32,
     },
     /// Send a notification via webhook
     Notify {
@@ -210,6 +377,23 @@ pub enum IntentionAction {
         #[serde(skip_serializing_if = "Option::is_none")]
         payload: Option<serde_json::Value>,
     },
+    /// POST to a webhook, retrying transient failures
+    CallWebhook {
+        /// Webhook URL to call
+        url: String,
+        /// JSON body with placeholders such as `{{intention.name}}`; the
+        /// fire is sent as JSON when unset (see [`ActionExecutor`])
+        ///
+        /// [`ActionExecutor`]: crate::intentions::ActionExecutor
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload_template: Option<String>,
+    },
+    /// Notify connected MCP clients
+    NotifyMcp {
+        /// Message with the same placeholders as webhook templates
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
     /// Execute a custom callback (for programmatic use)
     Callback {
         /// Callback identifier
@@ -224,12 +408,51 @@ pub enum IntentionAction {
     },
 }
 
+impl IntentionAction {
+    /// Check that the action can be executed
+    pub fn validate(&self) -> RookResult<()> {
+        match self {
+            Self::CallWebhook { url, .. }
+            | Self::Notify {
+                webhook_url: url, ..
+            } => {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(RookError::validation(
+                        "Webhook URL must start with http:// or https://",
+                    ));
+                }
+            }
+            Self::SurfaceMemory { boost, searches } => {
+                if !(boost.is_finite() && *boost > 0.0) {
+                    return Err(RookError::validation("Surface boost must be positive"));
+                }
+                if *searches == 0 {
+                    return Err(RookError::validation(
+                        "Memory must be surfaced in at least one search",
+                    ));
+                }
+            }
+            Self::NotifyMcp { .. }
+            | Self::Callback { .. }
+            | Self::Log { .. } => {}
+        }
+        Ok(())
+    }
+}
+
 impl Default for IntentionAction {
     fn default() -> Self {
-        Self::SurfaceMemory { boost: 1.5 }
+        Self::SurfaceMemory {
+            boost: 1.5,
+            searches: DEFAULT_SURFACE_SEARCHES,
+        }
     }
 }
 
+fn default_surface_searches() -> u32 {
+    DEFAULT_SURFACE_SEARCHES
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,15 +538,128 @@ mod tests {
 
     #[test]
     fn test_action_serialization() {
-        let action = IntentionAction::SurfaceMemory { boost: 2.0 };
+        let action = IntentionAction::SurfaceMemory {
+            boost: 2.0,
+            searches: 5,
+        };
         let json = serde_json::to_string(&action).unwrap();
         assert!(json.contains("surface_memory"));
         assert!(json.contains("2.0"));
 
         let deserialized: IntentionAction = serde_json::from_str(&json).unwrap();
         match deserialized {
-            IntentionAction::SurfaceMemory { boost } => assert!((boost - 2.0).abs() < f32::EPSILON),
+            IntentionAction::SurfaceMemory { boost, searches } => {
+                assert!((boost - 2.0).abs() < f32::EPSILON);
+                assert_eq!(searches, 5);
+            }
+            _ => panic!("Wrong action type"),
+        }
+
+        // Older intentions without a search count surface in the default number
+        let json = r#"{"type": "surface_memory", "boost": 1.5}"#;
+        match serde_json::from_str(json).unwrap() {
+            IntentionAction::SurfaceMemory { searches, .. } => {
+                assert_eq!(searches, DEFAULT_SURFACE_SEARCHES)
+            }
             _ => panic!("Wrong action type"),
         }
+        assert!(IntentionAction::SurfaceMemory {
+            boost: 1.5,
+            searches: 0
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_call_webhook_action_serialization() {
+        let json = r#"{"type": "call_webhook", "url": "https://example.com/hook"}"#;
+        let action: IntentionAction = serde_json::from_str(json).unwrap();
+        match &action {
+            IntentionAction::CallWebhook {
+                url,
+                payload_template,
+            } => {
+                assert_eq!(url, "https://example.com/hook");
+                assert!(payload_template.is_none());
+            }
+            _ => panic!("Wrong action type"),
+        }
+
+        let action: IntentionAction = serde_json::from_str(r#"{"type": "notify_mcp"}"#).unwrap();
+        assert!(matches!(
+            action,
+            IntentionAction::NotifyMcp { message: None }
+        ));
+
+        let action = IntentionAction::CallWebhook {
+            url: "example.com/hook".to_string(),
+            payload_template: None,
+        };
+        assert!(action.validate().is_err());
+    }
+
+    #[test]
+    fn test_composite_trigger() {
+        let deploy = TriggerCondition::keyword(vec!["deploy".to_string()]);
+        let release = TriggerCondition::keyword(vec!["release".to_string(), "ship".to_string()]);
+        let week = TriggerCondition::time_elapsed(Duration::days(7));
+
+        let all = TriggerCondition::all(vec![deploy.clone(), week.clone()]);
+        assert!(all.is_composite());
+        assert_eq!(all.required_keywords(), Some(vec!["deploy".to_string()]));
+        assert!(!all.has_topic());
+
+        let any = TriggerCondition::any(vec![deploy.clone(), release.clone()]);
+        assert_eq!(
+            any.required_keywords(),
+            Some(vec![
+                "deploy".to_string(),
+                "release".to_string(),
+                "ship".to_string()
+            ])
+        );
+        let any = TriggerCondition::any(vec![deploy.clone(), week.clone()]);
+        assert_eq!(any.required_keywords(), None);
+        assert_eq!(TriggerCondition::not(deploy.clone()).required_keywords(), None);
+
+        let nested = TriggerCondition::all(vec![
+            TriggerCondition::any(vec![TriggerCondition::topic("outages"), release]),
+            TriggerCondition::not(week),
+        ]);
+        assert!(nested.has_topic());
+        assert_eq!(nested.topics(), vec!["outages"]);
+
+        let json = serde_json::to_string(&nested).unwrap();
+        let parsed: TriggerCondition = serde_json::from_str(&json).unwrap();
+        assert!(matches!(parsed, TriggerCondition::All { ref conditions } if conditions.len() == 2));
+
+        assert!(nested.validate().is_ok());
+        assert!(TriggerCondition::all(vec![]).validate().is_err());
+        assert!(TriggerCondition::any(vec![TriggerCondition::topic("")]).validate().is_err());
+        assert!(TriggerCondition::not(TriggerCondition::not(deploy)).validate().is_err());
+    }
+
+    #[test]
+    fn test_trigger_condition_validate() {
+        assert!(TriggerCondition::keyword(vec!["rust".to_string()]).validate().is_ok());
+        assert!(TriggerCondition::keyword(vec![" ".to_string()]).validate().is_err());
+        assert!(TriggerCondition::topic("").validate().is_err());
+        assert!(TriggerCondition::time_elapsed(Duration::zero()).validate().is_err());
+        assert!(TriggerCondition::time_elapsed(Duration::hours(1)).validate().is_ok());
+        assert!(TriggerCondition::cron("0 9 * * MON-FRI", Some("Europe/Berlin".to_string()))
+            .validate()
+            .is_ok());
+        assert!(TriggerCondition::cron("0 9 * *", None).validate().is_err());
+        assert!(TriggerCondition::cron("0 9 * * *", Some("Nowhere".to_string()))
+            .validate()
+            .is_err());
+
+        let topic = TriggerCondition::TopicDiscussed {
+            topic: "rust".to_string(),
+            topic_embedding: None,
+            threshold: 1.5,
+        };
+        assert!(topic.validate().is_err());
     }
 }
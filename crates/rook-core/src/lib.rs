@@ -18,9 +18,14 @@
 //! let results = memory.search("food preferences", Some("user1".to_string()), None, None, 10, None, None, true).await?;
 //! ```
 
+pub mod analytics;
+pub mod audit;
+pub mod authz;
+pub mod backup;
 pub mod cognitive;
 pub mod config;
 pub mod consolidation;
+pub mod encryption;
 pub mod error;
 pub mod events;
 pub mod export;
@@ -31,14 +36,25 @@ pub mod memory;
 pub mod migration;
 #[cfg(feature = "multimodal")]
 pub mod multimodal;
+pub mod observability;
 pub mod retrieval;
 pub mod runtime;
+pub mod storage;
+pub mod sync;
 pub mod traits;
 pub mod types;
 pub mod versioning;
 
 // Re-export commonly used types
-pub use cognitive::{ArchivalCandidate, CognitiveStore, FsrsScheduler};
+pub use audit::{AuditAction, AuditEntry, AuditLog, AuditQuery, NewAuditEntry};
+pub use authz::{
+    ApiKey, ApiKeyAuthorizer, ApiKeyStore, Authorizer, AuthzAction, AuthzConfig, AuthzDecision,
+    AuthzRequest, KeyRole, NewApiKey,
+};
+pub use cognitive::{
+    ArchivalCandidate, CognitiveConfig, CognitiveStore, DecayRankingConfig, DecaySource, FsrsConfig,
+    FsrsParams, FsrsScheduler,
+};
 pub use config::MemoryConfig;
 pub use consolidation::{
     BehavioralTagConfig, BehavioralTagger, ConsolidationConfig, ConsolidationManager,
@@ -50,18 +66,19 @@ pub use ingestion::{
     DetectionLayer, GateResult, GatingThresholds, IngestDecision, IngestResult,
     PredictionErrorGate, StrengthSignal, StrengthSignalProcessor,
 };
-pub use memory::Memory;
+pub use memory::{ContextBlock, ContextBuilder, Memory, TokenCounter};
 pub use traits::{
     Embedder, EmbedderConfig, EmbeddingAction, Llm, LlmConfig, Reranker, VectorStore,
     VectorStoreConfig,
 };
 pub use retrieval::{
     spread_activation, spread_activation_by_id, ActivatedMemory, ActivationEdge, ActivationNode,
-    SpreadingConfig,
+    RelativeThreshold, SpreadingConfig, ThresholdCalibration, ThresholdSpec,
 };
 pub use types::{
-    AddResult, ArchivalConfig, DualStrength, Filter, FsrsState, Grade, MemoryEvent, MemoryItem,
-    MemoryResult, MemoryType, Message, MessageInput, MessageRole, SearchResult,
+    AddResult, ArchivalConfig, DualStrength, FieldSelection, Filter, FsrsState, Grade,
+    MemoryEvent, MemoryItem, MemoryResult, MemoryType, Message, MessageInput, MessageRole,
+    SearchResult,
 };
 pub use versioning::{
     FsrsStateSnapshot, MemoryVersion, SqliteVersionStore, VersionEventType, VersionStore,
@@ -70,24 +87,37 @@ pub use versioning::{
 pub use intentions::{
     ActionResult, BloomConfig, CheckerConfig, FiredIntention, FiredIntentionReceiver, Intention,
     IntentionAction, IntentionChecker, IntentionScheduler, IntentionStore, KeywordBloomFilter,
-    SqliteIntentionStore, TriggerCondition, TriggerReason,
+    SqliteIntentionStore, SurfacedMemories, TriggerCondition, TriggerReason,
 };
 pub use events::{
-    AccessType, EventBus, EventSubscriber, MemoryAccessedEvent, MemoryCreatedEvent,
+    AccessType, AlertEvent, AlertSeverity, AlertSubscriber, AlertSubscriberConfig, EventBus, EventSubscriber, MemoryAccessedEvent, MemoryCreatedEvent,
     MemoryDeletedEvent, MemoryLifecycleEvent, MemoryUpdatedEvent, RetryPolicy, UpdateType,
-    WebhookConfig, WebhookDelivery, WebhookError, WebhookManager, verify_signature,
+    WebhookConfig, WebhookDelivery, WebhookDeliveryStore, WebhookError, WebhookManager,
+    WebhookMetrics, verify_signature,
 };
-pub use runtime::{BackgroundRuntime, RuntimeConfig};
+pub use runtime::{BackgroundRuntime, MaintenanceJob, RuntimeConfig};
+pub use storage::{DataDirConfig, DataDirMonitor, DataUsage};
+pub use sync::{Changeset, ConflictPolicy, SyncConfig, SyncReport, SyncStatus};
 
 // Multimodal extraction (feature-gated)
 #[cfg(feature = "multimodal")]
-pub use multimodal::{MultimodalConfig, MultimodalIngester, MultimodalIngestResult, SourceProvenance};
+pub use multimodal::{
+    BulkIngestConfig, BulkIngestReport, BulkIngester, ChunkingStrategy, ImageMemoryConfig,
+    MultimodalConfig, MultimodalIngestResult, MultimodalIngester, SemanticChunkConfig,
+    SourceProvenance, ThumbnailConfig, ThumbnailStorage, UrlFetchConfig,
+};
 
 // Export/Import utilities
-pub use export::{export_jsonl, ExportStats, ExportableMemory};
+pub use export::{
+    export_bundle_jsonl, export_jsonl, export_jsonl_since, ExportBundle, ExportSection,
+    ExportStats, ExportableMemory, Tombstone,
+};
 #[cfg(feature = "export")]
 pub use export::export_parquet;
-pub use import::{import_jsonl, ImportStats, ImportableMemory};
+pub use import::{
+    import_bundle_jsonl, import_jsonl, BundleContents, ImportDedup, ImportStats, ImportableMemory,
+    RestoreStats,
+};
 
 // Migration utilities
 pub use migration::{migrate_from_mem0, Mem0Memory, MigrationStats};
@@ -0,0 +1,113 @@
+//! Cold-storage archive for decayed memories.
+//!
+//! [`Memory::archive_decayed`](super::Memory::archive_decayed) moves
+//! memories whose FSRS retrievability has fallen below the archival
+//! threshold out of the main vector store into a separate archive
+//! collection. Archived memories are left out of searches, listings and key
+//! memory injection unless a search sets `include_archived`, and return to
+//! the main store when accessed.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::Memory;
+use crate::error::RookResult;
+use crate::runtime::MaintenanceJob;
+use crate::traits::VectorStoreConfig;
+use crate::types::ArchivalConfig;
+
+/// Payload field recording when an archived memory was archived.
+pub const ARCHIVED_AT_FIELD: &str = "archived_at";
+
+/// Search filter that also searches the archive when `true`.
+pub const INCLUDE_ARCHIVED_FILTER: &str = "include_archived";
+
+/// Archive settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArchiveConfig {
+    /// Whether decayed memories are archived (default: false). Needs
+    /// `cognitive.enabled` and an archive store, see
+    /// [`MemoryConfig::archive_vector_store`](crate::config::MemoryConfig::archive_vector_store).
+    pub enabled: bool,
+    /// Archive collection (default: the memory collection with an
+    /// `_archive` suffix).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_name: Option<String>,
+    /// Move archived memories back to the main store when a search or get
+    /// returns them (default: true).
+    pub unarchive_on_access: bool,
+    /// Which memories are archived: retrievability threshold, minimum age
+    /// and batch size.
+    pub archival: ArchivalConfig,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            collection_name: None,
+            unarchive_on_access: true,
+            archival: ArchivalConfig::default(),
+        }
+    }
+}
+
+impl ArchiveConfig {
+    /// Vector store settings for the archive: the memory store's, with the
+    /// archive collection.
+    pub fn vector_store(&self, base: &VectorStoreConfig) -> VectorStoreConfig {
+        let mut config = base.clone();
+        config.collection_name = self
+            .collection_name
+            .clone()
+            .unwrap_or_else(|| format!("{}_archive", base.collection_name));
+        config
+    }
+}
+
+/// Maintenance job archiving decayed memories.
+pub struct ArchiveJob {
+    memory: Arc<Memory>,
+}
+
+impl ArchiveJob {
+    pub fn new(memory: Arc<Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for ArchiveJob {
+    fn name(&self) -> &str {
+        "memory_archive"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        self.memory.archive_decayed().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_collection_name() {
+        let base = VectorStoreConfig {
+            collection_name: "memories".to_string(),
+            ..Default::default()
+        };
+        let config = ArchiveConfig::default();
+        assert_eq!(config.vector_store(&base).collection_name, "memories_archive");
+
+        let config = ArchiveConfig {
+            collection_name: Some("cold".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(config.vector_store(&base).collection_name, "cold");
+        assert!(config.unarchive_on_access);
+    }
+}
@@ -0,0 +1,137 @@
+//! Point-in-time search over memory versions.
+//!
+//! [`Memory::search_as_of`](super::Memory::search_as_of) searches memories
+//! as the version store recorded them at a given time, answering questions
+//! like "what did I know before last Tuesday". A version whose content is
+//! still stored reuses the stored embedding; older contents are embedded
+//! when first searched and kept in a [`VersionEmbeddingCache`].
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+
+use super::expiry::is_expired;
+use crate::versioning::{MemoryVersion, VersionEventType};
+
+/// Most embeddings of past memory contents kept between searches.
+pub const VERSION_EMBEDDING_CACHE_SIZE: usize = 1000;
+
+/// Embeddings of past memory contents, keyed by content hash.
+///
+/// The oldest entry is dropped once [`VERSION_EMBEDDING_CACHE_SIZE`] are
+/// cached.
+#[derive(Default)]
+pub(crate) struct VersionEmbeddingCache {
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    embeddings: HashMap<String, Vec<f32>>,
+    /// Keys, oldest first.
+    order: VecDeque<String>,
+}
+
+impl VersionEmbeddingCache {
+    pub(crate) fn get(&self, content: &str) -> Option<Vec<f32>> {
+        let entries = self.entries.lock().ok()?;
+        entries.embeddings.get(&content_key(content)).cloned()
+    }
+
+    pub(crate) fn insert(&self, content: &str, embedding: Vec<f32>) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        let key = content_key(content);
+        if entries.embeddings.insert(key.clone(), embedding).is_none() {
+            entries.order.push_back(key);
+        }
+        while entries.order.len() > VERSION_EMBEDDING_CACHE_SIZE {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.embeddings.remove(&oldest);
+            }
+        }
+    }
+}
+
+fn content_key(content: &str) -> String {
+    format!("{:x}", md5::compute(content.as_bytes()))
+}
+
+/// Whether a version, the latest at `as_of`, means the memory existed then:
+/// it wasn't deleted and hadn't expired.
+pub(crate) fn existed_at(version: &MemoryVersion, as_of: DateTime<Utc>) -> bool {
+    version.event_type != VersionEventType::Deleted && !is_expired(&version.metadata, as_of)
+}
+
+/// Whether metadata matches a scope, given the accepted stored values of
+/// each scope field (plaintext and blind index tokens).
+pub(crate) fn in_scope(
+    metadata: &HashMap<String, serde_json::Value>,
+    scope_values: &[(&str, Vec<String>)],
+) -> bool {
+    scope_values.iter().all(|(field, values)| {
+        metadata
+            .get(*field)
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| values.iter().any(|value| value == v))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_version_embedding_cache() {
+        let cache = VersionEmbeddingCache::default();
+        assert!(cache.get("likes pizza").is_none());
+
+        cache.insert("likes pizza", vec![1.0, 0.0]);
+        assert_eq!(cache.get("likes pizza"), Some(vec![1.0, 0.0]));
+
+        for i in 0..VERSION_EMBEDDING_CACHE_SIZE {
+            cache.insert(&format!("fact {}", i), vec![i as f32]);
+        }
+        // The oldest entry made room
+        assert!(cache.get("likes pizza").is_none());
+        assert_eq!(cache.get("fact 0"), Some(vec![0.0]));
+    }
+
+    #[test]
+    fn test_existed_at() {
+        let as_of = Utc::now();
+        let mut version = MemoryVersion::initial("m1", "Likes pizza");
+        assert!(existed_at(&version, as_of));
+
+        version.metadata.insert(
+            "expires_at".to_string(),
+            json!((as_of - chrono::Duration::hours(1)).to_rfc3339()),
+        );
+        assert!(!existed_at(&version, as_of));
+        assert!(existed_at(&version, as_of - chrono::Duration::hours(2)));
+
+        let mut deleted = MemoryVersion::initial("m1", "Likes pizza");
+        deleted.event_type = VersionEventType::Deleted;
+        assert!(!existed_at(&deleted, as_of));
+    }
+
+    #[test]
+    fn test_in_scope() {
+        let metadata = HashMap::from([
+            ("user_id".to_string(), json!("token-alice")),
+            ("agent_id".to_string(), json!("bot")),
+        ]);
+        let alice = vec!["alice".to_string(), "token-alice".to_string()];
+
+        assert!(in_scope(&metadata, &[("user_id", alice.clone())]));
+        assert!(in_scope(
+            &metadata,
+            &[("user_id", alice.clone()), ("agent_id", vec!["bot".to_string()])]
+        ));
+        assert!(!in_scope(&metadata, &[("user_id", vec!["bob".to_string()])]));
+        assert!(!in_scope(&metadata, &[("user_id", alice), ("run_id", vec!["r1".to_string()])]));
+    }
+}
@@ -0,0 +1,333 @@
+//! Cache of memory classifications.
+//!
+//! Near-identical facts ("likes pizza") are added by many users and each one
+//! would otherwise cost an LLM classification call. The cache answers a
+//! classification from an earlier one when the normalized text matches
+//! exactly or the embeddings are at least
+//! [`ClassificationCacheConfig::similarity_threshold`] similar.
+//!
+//! Entries are kept per namespace: the vector store collection (so each
+//! tenant has its own) and the category taxonomy. Setting
+//! [`ClassificationCacheConfig::share_across_tenants`] drops the collection
+//! from the namespace, so one tenant's classifications can answer another's.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::prompts::{cosine_similarity, ClassificationResult};
+use crate::observability::metrics;
+
+/// Classification cache settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClassificationCacheConfig {
+    /// Answer classifications from the cache (default: false).
+    pub enabled: bool,
+    /// Minimum cosine similarity between embeddings for a cached
+    /// classification to be reused (default: 0.97).
+    pub similarity_threshold: f32,
+    /// Maximum cached classifications; the least recently used is dropped
+    /// beyond this (default: 10000).
+    pub max_entries: usize,
+    /// Reuse classifications across tenants (default: false). Only the
+    /// category and key flag are shared, but a tenant can infer that similar
+    /// text was classified before.
+    pub share_across_tenants: bool,
+}
+
+impl Default for ClassificationCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            similarity_threshold: 0.97,
+            max_entries: 10_000,
+            share_across_tenants: false,
+        }
+    }
+}
+
+/// Hit counters for a [`ClassificationCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassificationCacheStats {
+    /// Lookups answered by an entry with the same normalized text.
+    pub exact_hits: u64,
+    /// Lookups answered by an entry with a similar embedding.
+    pub similar_hits: u64,
+    /// Lookups that needed an LLM call.
+    pub misses: u64,
+    /// Classifications currently cached.
+    pub entries: usize,
+}
+
+impl ClassificationCacheStats {
+    /// Share of lookups answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let hits = self.exact_hits + self.similar_hits;
+        let total = hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            hits as f64 / total as f64
+        }
+    }
+}
+
+struct CacheEntry {
+    namespace: String,
+    text: String,
+    embedding: Vec<f32>,
+    result: ClassificationResult,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    entries: Vec<CacheEntry>,
+    clock: u64,
+}
+
+impl CacheEntries {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+/// Classifications keyed by normalized text and embedding.
+///
+/// Lookups scan the entries of their namespace, which is cheap next to the
+/// LLM call they replace at the default size.
+pub struct ClassificationCache {
+    config: ClassificationCacheConfig,
+    entries: Mutex<CacheEntries>,
+    exact_hits: AtomicU64,
+    similar_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ClassificationCache {
+    pub fn new(config: ClassificationCacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(CacheEntries::default()),
+            exact_hits: AtomicU64::new(0),
+            similar_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the settings.
+    pub fn config(&self) -> &ClassificationCacheConfig {
+        &self.config
+    }
+
+    /// Namespace for classifications in `collection` against `categories`.
+    pub fn namespace(&self, collection: &str, categories: &[String]) -> String {
+        let mut categories = categories.to_vec();
+        categories.sort();
+        let taxonomy = format!("{:x}", md5::compute(categories.join("\n").as_bytes()));
+        if self.config.share_across_tenants {
+            taxonomy
+        } else {
+            format!("{}:{}", collection, taxonomy)
+        }
+    }
+
+    /// Cached classification for `text`, if an entry matches.
+    pub fn get(
+        &self,
+        namespace: &str,
+        text: &str,
+        embedding: &[f32],
+    ) -> Option<ClassificationResult> {
+        let text = normalize(text);
+        let mut cache = self.entries.lock().unwrap();
+        let now = cache.tick();
+
+        let mut exact = false;
+        let mut best: Option<(usize, f32)> = None;
+        for (i, entry) in cache.entries.iter().enumerate() {
+            if entry.namespace != namespace {
+                continue;
+            }
+            if entry.text == text {
+                exact = true;
+                best = Some((i, 1.0));
+                break;
+            }
+            let similarity = cosine_similarity(embedding, &entry.embedding);
+            if similarity < self.config.similarity_threshold {
+                continue;
+            }
+            if !matches!(best, Some((_, s)) if s >= similarity) {
+                best = Some((i, similarity));
+            }
+        }
+
+        let Some((i, _)) = best else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            metrics::record_classification_cache_lookup("miss");
+            return None;
+        };
+        let counter = if exact {
+            &self.exact_hits
+        } else {
+            &self.similar_hits
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        metrics::record_classification_cache_lookup(if exact { "exact" } else { "similar" });
+
+        let entry = &mut cache.entries[i];
+        entry.last_used = now;
+        Some(entry.result.clone())
+    }
+
+    /// Cache a classification made by the LLM.
+    pub fn insert(
+        &self,
+        namespace: impl Into<String>,
+        text: &str,
+        embedding: Vec<f32>,
+        result: ClassificationResult,
+    ) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        let mut cache = self.entries.lock().unwrap();
+        let now = cache.tick();
+        while cache.entries.len() >= self.config.max_entries {
+            let Some(lru) = cache
+                .entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+            cache.entries.swap_remove(lru);
+        }
+        cache.entries.push(CacheEntry {
+            namespace: namespace.into(),
+            text: normalize(text),
+            embedding,
+            result,
+            last_used: now,
+        });
+    }
+
+    /// Drop every cached classification. Hit counters are kept.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().entries.clear();
+    }
+
+    /// Hit counters and size.
+    pub fn stats(&self) -> ClassificationCacheStats {
+        ClassificationCacheStats {
+            exact_hits: self.exact_hits.load(Ordering::Relaxed),
+            similar_hits: self.similar_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().entries.len(),
+        }
+    }
+}
+
+/// Lowercase, drop punctuation and collapse whitespace.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(category: &str) -> ClassificationResult {
+        ClassificationResult {
+            category: category.to_string(),
+            is_key: false,
+            confidence: 0.9,
+        }
+    }
+
+    fn cache(share_across_tenants: bool) -> ClassificationCache {
+        ClassificationCache::new(ClassificationCacheConfig {
+            enabled: true,
+            share_across_tenants,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_exact_and_similar_hits() {
+        let cache = cache(false);
+        let categories = vec!["preferences".to_string(), "misc".to_string()];
+        let ns = cache.namespace("memories", &categories);
+        cache.insert(ns.clone(), "Likes pizza.", vec![1.0, 0.0, 0.0], result("preferences"));
+
+        // Normalized text matches even with an unrelated embedding
+        let hit = cache.get(&ns, "  likes   PIZZA", &[0.0, 1.0, 0.0]).unwrap();
+        assert_eq!(hit.category, "preferences");
+
+        // Similar embedding
+        assert!(cache.get(&ns, "enjoys pizza", &[0.99, 0.05, 0.0]).is_some());
+        // Dissimilar embedding
+        assert!(cache.get(&ns, "works at Acme", &[0.0, 0.0, 1.0]).is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.exact_hits, 1);
+        assert_eq!(stats.similar_hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+        assert!((stats.hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_namespaces_isolate_tenants() {
+        let categories = vec!["misc".to_string(), "preferences".to_string()];
+
+        let isolated = cache(false);
+        let a = isolated.namespace("memories_a", &categories);
+        let b = isolated.namespace("memories_b", &categories);
+        assert_ne!(a, b);
+        isolated.insert(a, "likes pizza", vec![1.0, 0.0], result("preferences"));
+        assert!(isolated.get(&b, "likes pizza", &[1.0, 0.0]).is_none());
+
+        let shared = cache(true);
+        let a = shared.namespace("memories_a", &categories);
+        let b = shared.namespace("memories_b", &categories);
+        assert_eq!(a, b);
+
+        // Category order does not matter, the taxonomy does
+        let reordered = vec!["preferences".to_string(), "misc".to_string()];
+        assert_eq!(shared.namespace("memories_a", &reordered), a);
+        assert_ne!(shared.namespace("memories_a", &["misc".to_string()]), a);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let cache = ClassificationCache::new(ClassificationCacheConfig {
+            enabled: true,
+            max_entries: 2,
+            ..Default::default()
+        });
+        cache.insert("ns", "one", vec![1.0, 0.0, 0.0], result("a"));
+        cache.insert("ns", "two", vec![0.0, 1.0, 0.0], result("b"));
+        assert!(cache.get("ns", "one", &[1.0, 0.0, 0.0]).is_some());
+        cache.insert("ns", "three", vec![0.0, 0.0, 1.0], result("c"));
+
+        assert_eq!(cache.stats().entries, 2);
+        assert!(cache.get("ns", "one", &[1.0, 0.0, 0.0]).is_some());
+        assert!(cache.get("ns", "two", &[0.0, 1.0, 0.0]).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! Prompt-ready memory context within a token budget.
+//!
+//! [`ContextBuilder`] gathers key memories and search results for a query,
+//! drops duplicates and fills a token budget in priority order, truncating
+//! the memory that doesn't fit. The IDs of the memories used are returned so
+//! callers can report them as [`StrengthSignal::UsedInResponse`].
+//!
+//! [`StrengthSignal::UsedInResponse`]: crate::ingestion::StrengthSignal::UsedInResponse
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::Memory;
+use crate::error::RookResult;
+
+/// Search results considered when no limit is given.
+pub const DEFAULT_CONTEXT_SEARCH_LIMIT: usize = 20;
+
+/// Heading of the context block when none is given.
+pub const DEFAULT_CONTEXT_HEADER: &str = "Relevant memories:";
+
+/// A memory is only truncated if at least this many tokens of it fit.
+const MIN_TRUNCATED_TOKENS: usize = 8;
+
+/// Marks a truncated memory.
+const ELLIPSIS: &str = "…";
+
+/// Counts tokens in text.
+///
+/// Implement this with the target model's tokenizer for exact budgets;
+/// [`ApproxTokenCounter`] is used otherwise.
+pub trait TokenCounter: Send + Sync {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Approximates tokens as one per four characters, rounded up.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApproxTokenCounter;
+
+impl TokenCounter for ApproxTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+/// A memory block ready to place in a prompt.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextBlock {
+    /// The formatted block; empty when no memory fits.
+    pub text: String,
+    /// IDs of the memories included, in order.
+    pub memory_ids: Vec<String>,
+    /// Tokens in `text`, as counted by the builder's counter.
+    pub tokens: usize,
+    /// Whether the last memory was cut short to fit the budget.
+    pub truncated: bool,
+}
+
+/// Builds a [`ContextBlock`] for a query within a token budget.
+///
+/// Key memories come first, then search results by relevance.
+pub struct ContextBuilder {
+    token_budget: usize,
+    counter: Arc<dyn TokenCounter>,
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+    search_limit: usize,
+    include_key_memories: bool,
+    header: String,
+}
+
+impl ContextBuilder {
+    pub fn new(token_budget: usize) -> Self {
+        Self {
+            token_budget,
+            counter: Arc::new(ApproxTokenCounter),
+            user_id: None,
+            agent_id: None,
+            run_id: None,
+            search_limit: DEFAULT_CONTEXT_SEARCH_LIMIT,
+            include_key_memories: true,
+            header: DEFAULT_CONTEXT_HEADER.to_string(),
+        }
+    }
+
+    /// Count tokens with the model's tokenizer.
+    pub fn with_token_counter(mut self, counter: Arc<dyn TokenCounter>) -> Self {
+        self.counter = counter;
+        self
+    }
+
+    pub fn with_user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    pub fn with_agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    /// Search results considered (default 20).
+    pub fn with_search_limit(mut self, search_limit: usize) -> Self {
+        self.search_limit = search_limit;
+        self
+    }
+
+    /// Leave out key memories that search didn't return.
+    pub fn without_key_memories(mut self) -> Self {
+        self.include_key_memories = false;
+        self
+    }
+
+    /// Line placed above the memories; empty for none.
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// Build the context block for `query`.
+    pub async fn build(&self, memory: &Memory, query: &str) -> RookResult<ContextBlock> {
+        let mut candidates = Vec::new();
+        if self.include_key_memories {
+            let key_memories = memory
+                .get_key_memories(self.user_id.clone(), self.agent_id.clone(), self.run_id.clone())
+                .await?;
+            candidates.extend(key_memories.into_iter().map(|m| (m.id, m.memory)));
+        }
+        let found = memory
+            .search(
+                query,
+                self.user_id.clone(),
+                self.agent_id.clone(),
+                self.run_id.clone(),
+                self.search_limit,
+                None,
+                None,
+                false,
+            )
+            .await?;
+        candidates.extend(found.results.into_iter().map(|m| (m.id, m.memory)));
+
+        Ok(assemble(
+            &self.header,
+            &candidates,
+            self.token_budget,
+            self.counter.as_ref(),
+        ))
+    }
+}
+
+/// Fill `budget` with `candidates` (ID and text) in order, skipping
+/// duplicates and truncating the first memory that doesn't fit.
+fn assemble(
+    header: &str,
+    candidates: &[(String, String)],
+    budget: usize,
+    counter: &dyn TokenCounter,
+) -> ContextBlock {
+    let mut block = ContextBlock::default();
+    let mut text = header.to_string();
+    let mut seen_ids = HashSet::new();
+    let mut seen_texts = HashSet::new();
+
+    for (id, memory) in candidates {
+        let memory = memory.trim();
+        if memory.is_empty()
+            || !seen_ids.insert(id.as_str())
+            || !seen_texts.insert(normalize(memory))
+        {
+            continue;
+        }
+
+        let candidate = append_line(&text, memory);
+        let tokens = counter.count(&candidate);
+        if tokens <= budget {
+            text = candidate;
+            block.tokens = tokens;
+            block.memory_ids.push(id.clone());
+            continue;
+        }
+
+        if let Some((candidate, tokens)) = truncate_to_fit(&text, memory, budget, counter) {
+            text = candidate;
+            block.tokens = tokens;
+            block.memory_ids.push(id.clone());
+            block.truncated = true;
+        }
+        break;
+    }
+
+    if !block.memory_ids.is_empty() {
+        block.text = text;
+    }
+    block
+}
+
+fn append_line(text: &str, memory: &str) -> String {
+    if text.is_empty() {
+        format!("- {}", memory)
+    } else {
+        format!("{}\n- {}", text, memory)
+    }
+}
+
+/// Compare memories ignoring case and whitespace.
+fn normalize(memory: &str) -> String {
+    memory
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// The longest prefix of `memory`, cut at a word boundary where possible,
+/// that fits the budget when appended to `text`.
+fn truncate_to_fit(
+    text: &str,
+    memory: &str,
+    budget: usize,
+    counter: &dyn TokenCounter,
+) -> Option<(String, usize)> {
+    let remaining = budget.checked_sub(counter.count(text))?;
+    if remaining < MIN_TRUNCATED_TOKENS {
+        return None;
+    }
+
+    let boundaries: Vec<usize> = memory.char_indices().map(|(i, _)| i).skip(1).collect();
+    let fits = |end: usize| {
+        let candidate = append_line(text, &format!("{}{}", memory[..end].trim_end(), ELLIPSIS));
+        let tokens = counter.count(&candidate);
+        (tokens <= budget).then_some((candidate, tokens))
+    };
+
+    // Token counts grow with the prefix, so binary search the boundaries
+    let mut best = None;
+    let (mut low, mut high) = (0, boundaries.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if fits(boundaries[mid]).is_some() {
+            best = Some(boundaries[mid]);
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    let end = best?;
+    let end = match memory[..end].rfind(char::is_whitespace) {
+        Some(space) if space > end / 2 && !memory[end..].starts_with(char::is_whitespace) => {
+            space
+        }
+        _ => end,
+    };
+    let (candidate, tokens) = fits(end)?;
+    (counter.count(&memory[..end]) >= MIN_TRUNCATED_TOKENS).then_some((candidate, tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts words, to keep budgets readable.
+    struct WordCounter;
+
+    impl TokenCounter for WordCounter {
+        fn count(&self, text: &str) -> usize {
+            text.split_whitespace().count()
+        }
+    }
+
+    fn candidates(memories: &[(&str, &str)]) -> Vec<(String, String)> {
+        memories
+            .iter()
+            .map(|(id, memory)| (id.to_string(), memory.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_approx_token_counter() {
+        assert_eq!(ApproxTokenCounter.count(""), 0);
+        assert_eq!(ApproxTokenCounter.count("abcd"), 1);
+        assert_eq!(ApproxTokenCounter.count("abcde"), 2);
+    }
+
+    #[test]
+    fn test_assemble_in_order_and_deduplicated() {
+        let block = assemble(
+            "Memories:",
+            &candidates(&[
+                ("1", "Uses Helix"),
+                ("2", "Prefers dark themes"),
+                ("1", "Uses Helix"),
+                ("3", "  prefers   DARK themes "),
+            ]),
+            100,
+            &WordCounter,
+        );
+        assert_eq!(block.text, "Memories:\n- Uses Helix\n- Prefers dark themes");
+        assert_eq!(block.memory_ids, vec!["1", "2"]);
+        assert_eq!(block.tokens, 8);
+        assert!(!block.truncated);
+    }
+
+    #[test]
+    fn test_assemble_truncates_to_budget() {
+        let long = "one two three four five six seven eight nine ten eleven twelve";
+        let block = assemble(
+            "",
+            &candidates(&[("1", "Uses Helix"), ("2", long), ("3", "Lives in Oslo")]),
+            14,
+            &WordCounter,
+        );
+        assert_eq!(block.memory_ids, vec!["1", "2"]);
+        assert!(block.truncated);
+        assert!(block.tokens <= 14);
+        assert!(block.text.ends_with("\n- one two three four five six seven eight nine ten…"));
+
+        // Too little room left to be worth truncating
+        let block = assemble("", &candidates(&[("1", "Uses Helix"), ("2", long)]), 6, &WordCounter);
+        assert_eq!(block.memory_ids, vec!["1"]);
+        assert!(!block.truncated);
+    }
+
+    #[test]
+    fn test_assemble_nothing_fits() {
+        let block = assemble("Memories:", &candidates(&[("1", "Uses Helix")]), 1, &WordCounter);
+        assert_eq!(block, ContextBlock::default());
+    }
+}
@@ -0,0 +1,124 @@
+//! Documents stored as a parent record with chunk children.
+//!
+//! When multimodal ingestion splits a document into several memories, it
+//! also stores a parent record describing the whole document and links each
+//! chunk to it through [`DOCUMENT_ID_FIELD`]. [`Memory::get_document`]
+//! returns the chunks in order, and deleting the document record with
+//! [`Memory::delete`] deletes its chunks too.
+//!
+//! [`Memory::get_document`]: super::Memory::get_document
+//! [`Memory::delete`]: super::Memory::delete
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::MemoryItem;
+
+/// Payload field linking a chunk to its document record.
+pub const DOCUMENT_ID_FIELD: &str = "document_id";
+
+/// Payload field marking a memory as a document record or one of its chunks.
+pub const DOCUMENT_ROLE_FIELD: &str = "document_role";
+
+/// [`DOCUMENT_ROLE_FIELD`] value of document records.
+pub const DOCUMENT_ROLE: &str = "document";
+
+/// [`DOCUMENT_ROLE_FIELD`] value of chunks.
+pub const CHUNK_ROLE: &str = "chunk";
+
+/// Relationship from a document to each of its chunks in the graph store.
+pub const HAS_CHUNK_RELATIONSHIP: &str = "has_chunk";
+
+/// A document record with its chunks, from
+/// [`Memory::get_document`](super::Memory::get_document).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    pub document: MemoryItem,
+    /// Chunks in document order.
+    pub chunks: Vec<MemoryItem>,
+}
+
+impl Document {
+    pub(crate) fn new(document: MemoryItem, mut chunks: Vec<MemoryItem>) -> Self {
+        chunks.sort_by_key(|chunk| chunk_index(chunk).unwrap_or(usize::MAX));
+        Self { document, chunks }
+    }
+
+    /// The document's text, its chunks joined in order.
+    pub fn text(&self) -> String {
+        self.chunks
+            .iter()
+            .map(|chunk| chunk.memory.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Whether a memory payload is a document record.
+pub(crate) fn is_document(payload: &HashMap<String, serde_json::Value>) -> bool {
+    payload.get(DOCUMENT_ROLE_FIELD).and_then(|v| v.as_str()) == Some(DOCUMENT_ROLE)
+}
+
+/// Graph entity name of a document record or chunk, by its role.
+pub fn document_entity_name(role: &str, memory_id: &str) -> String {
+    format!("{}:{}", role, memory_id)
+}
+
+fn chunk_index(chunk: &MemoryItem) -> Option<usize> {
+    chunk
+        .metadata
+        .as_ref()?
+        .get("chunk_index")?
+        .as_u64()
+        .map(|i| i as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(id: &str, index: Option<usize>) -> MemoryItem {
+        let mut metadata = HashMap::new();
+        if let Some(index) = index {
+            metadata.insert("chunk_index".to_string(), serde_json::json!(index));
+        }
+        MemoryItem {
+            metadata: Some(metadata),
+            ..MemoryItem::new(id, format!("text {}", id))
+        }
+    }
+
+    #[test]
+    fn test_document_orders_chunks() {
+        let document = Document::new(
+            MemoryItem::new("doc", "report.pdf"),
+            vec![
+                chunk("c", Some(2)),
+                chunk("x", None),
+                chunk("a", Some(0)),
+                chunk("b", Some(1)),
+            ],
+        );
+
+        let ids: Vec<_> = document.chunks.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "c", "x"]);
+        assert_eq!(document.text(), "text a\n\ntext b\n\ntext c\n\ntext x");
+    }
+
+    #[test]
+    fn test_is_document() {
+        let mut payload = HashMap::new();
+        assert!(!is_document(&payload));
+        payload.insert(
+            DOCUMENT_ROLE_FIELD.to_string(),
+            serde_json::json!(CHUNK_ROLE),
+        );
+        assert!(!is_document(&payload));
+        payload.insert(
+            DOCUMENT_ROLE_FIELD.to_string(),
+            serde_json::json!(DOCUMENT_ROLE),
+        );
+        assert!(is_document(&payload));
+    }
+}
@@ -0,0 +1,93 @@
+//! Erasure of everything stored about a user.
+//!
+//! [`Memory::erase_user`](super::Memory::erase_user) removes a user's
+//! memories and the state kept about them in every store, then looks for
+//! what is left and reports it, so a data subject request can be closed on
+//! evidence rather than on trust.
+
+use serde::Serialize;
+
+/// Records about one user, per store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct UserDataCounts {
+    pub memories: usize,
+    pub entities: usize,
+    pub cognitive_states: usize,
+    pub synaptic_tags: usize,
+    pub versions: usize,
+    pub history: usize,
+    pub runs: usize,
+    pub promotions: usize,
+    pub intentions: usize,
+}
+
+impl UserDataCounts {
+    /// Records across all stores.
+    pub fn total(&self) -> usize {
+        self.memories
+            + self.entities
+            + self.cognitive_states
+            + self.synaptic_tags
+            + self.versions
+            + self.history
+            + self.runs
+            + self.promotions
+            + self.intentions
+    }
+}
+
+/// Outcome of erasing a user's data.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErasureReport {
+    pub user_id: String,
+    /// Records deleted.
+    pub erased: UserDataCounts,
+    /// Records still found after erasure.
+    pub remaining: UserDataCounts,
+    /// Stores not configured on this instance, neither erased nor checked.
+    pub skipped: Vec<String>,
+    /// Whether nothing remains in the checked stores.
+    pub verified: bool,
+}
+
+impl ErasureReport {
+    pub(crate) fn new(
+        user_id: &str,
+        erased: UserDataCounts,
+        remaining: UserDataCounts,
+        skipped: Vec<String>,
+    ) -> Self {
+        let verified = remaining.total() == 0;
+        Self {
+            user_id: user_id.to_string(),
+            erased,
+            remaining,
+            skipped,
+            verified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erasure_report_verified() {
+        let erased = UserDataCounts {
+            memories: 3,
+            versions: 5,
+            ..Default::default()
+        };
+        assert_eq!(erased.total(), 8);
+
+        let report = ErasureReport::new("alice", erased.clone(), UserDataCounts::default(), vec![]);
+        assert!(report.verified);
+
+        let remaining = UserDataCounts {
+            history: 1,
+            ..Default::default()
+        };
+        assert!(!ErasureReport::new("alice", erased, remaining, vec![]).verified);
+    }
+}
@@ -0,0 +1,162 @@
+//! Query expansion for memory search.
+//!
+//! A search with a `query_expansion` filter entry has the LLM rewrite the
+//! query before searching: `paraphrase` asks for a few alternative phrasings,
+//! `hyde` for a hypothetical memory that would answer it. The original query
+//! and its expansions are each embedded and searched, and the rankings are
+//! fused with reciprocal rank fusion, so terse queries like "food" can still
+//! reach "loves Neapolitan pizza".
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::json_parser::extract_json;
+use crate::error::{RookError, RookResult};
+
+/// Search filter entry selecting query expansion (`paraphrase` or `hyde`).
+pub const QUERY_EXPANSION_FILTER: &str = "query_expansion";
+
+/// Most paraphrases searched alongside the original query.
+pub const MAX_PARAPHRASES: usize = 3;
+
+/// How a search query is expanded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryExpansion {
+    /// Search with up to [`MAX_PARAPHRASES`] rephrasings of the query.
+    Paraphrase,
+    /// Search with a hypothetical memory answering the query (HyDE).
+    Hyde,
+}
+
+impl QueryExpansion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Paraphrase => "paraphrase",
+            Self::Hyde => "hyde",
+        }
+    }
+}
+
+impl fmt::Display for QueryExpansion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for QueryExpansion {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "paraphrase" => Ok(Self::Paraphrase),
+            "hyde" => Ok(Self::Hyde),
+            other => Err(RookError::validation(format!(
+                "Unknown query expansion '{}', expected paraphrase or hyde",
+                other
+            ))),
+        }
+    }
+}
+
+/// Parse the value of a `query_expansion` filter entry.
+pub fn parse_query_expansion(value: &serde_json::Value) -> RookResult<QueryExpansion> {
+    value
+        .as_str()
+        .ok_or_else(|| RookError::validation("query_expansion must be a string"))?
+        .parse()
+}
+
+/// Build the prompt asking the LLM to expand a search query.
+pub fn query_expansion_prompt(query: &str, expansion: QueryExpansion) -> String {
+    match expansion {
+        QueryExpansion::Paraphrase => format!(
+            r#"You help search a store of memories about a user. Rewrite the search query below in {MAX_PARAPHRASES} different ways that could match how a relevant memory is worded: use synonyms, more specific or more general terms, and spell out what a short query likely means.
+
+QUERY: {query}
+
+Respond ONLY with a JSON object, no other text:
+{{"queries": ["<rewritten query>", ...]}}"#
+        ),
+        QueryExpansion::Hyde => format!(
+            r#"You help search a store of memories about a user. Write one short memory, in the style of a stored fact about the user, that would answer the search query below. It doesn't need to be true; it is only used to find similar memories.
+
+QUERY: {query}
+
+Respond ONLY with a JSON object, no other text:
+{{"memory": "<hypothetical memory>"}}"#
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+struct RawExpansion {
+    #[serde(default)]
+    queries: Vec<String>,
+    #[serde(default)]
+    memory: Option<String>,
+}
+
+/// Parse the LLM response into the expanded queries.
+///
+/// Blank entries and repeats of the query are dropped, and at most
+/// [`MAX_PARAPHRASES`] are kept. An unparseable response yields none.
+pub fn parse_query_expansion_response(response: &str, query: &str) -> Vec<String> {
+    let json = extract_json(response).unwrap_or_default();
+    let raw: RawExpansion = match serde_json::from_str(&json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::debug!("Failed to parse query expansion: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut queries: Vec<String> = Vec::new();
+    for candidate in raw.queries.iter().chain(raw.memory.as_ref()) {
+        let candidate = candidate.trim();
+        if candidate.is_empty()
+            || candidate.eq_ignore_ascii_case(query.trim())
+            || queries.iter().any(|q| q == candidate)
+        {
+            continue;
+        }
+        queries.push(candidate.to_string());
+    }
+    queries.truncate(MAX_PARAPHRASES);
+    queries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_query_expansion() {
+        assert_eq!(
+            parse_query_expansion(&json!("hyde")).unwrap(),
+            QueryExpansion::Hyde
+        );
+        assert!(parse_query_expansion(&json!("synonyms")).is_err());
+        assert!(parse_query_expansion(&json!(true)).is_err());
+    }
+
+    #[test]
+    fn test_parse_query_expansion_response() {
+        let response = r#"```json
+{"queries": [" favorite dishes ", "food", "", "favorite dishes", "cuisine they enjoy", "meals"]}
+```"#;
+        assert_eq!(
+            parse_query_expansion_response(response, "Food"),
+            vec!["favorite dishes", "cuisine they enjoy", "meals"]
+        );
+
+        assert_eq!(
+            parse_query_expansion_response(r#"{"memory": "Loves Neapolitan pizza"}"#, "food"),
+            vec!["Loves Neapolitan pizza"]
+        );
+        assert!(parse_query_expansion_response("No idea.", "food").is_empty());
+    }
+}
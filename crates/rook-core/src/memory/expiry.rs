@@ -0,0 +1,153 @@
+//! Memory expiry.
+//!
+//! A memory whose `expires_at` metadata field (RFC 3339) has passed is
+//! soft-deleted by [`MemoryExpiryJob`]: it leaves the vector store, but its
+//! versions are kept so it can still be rolled back.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::Memory;
+use crate::error::RookResult;
+use crate::runtime::MaintenanceJob;
+
+/// Metadata field holding when a memory expires.
+pub const EXPIRES_AT_FIELD: &str = "expires_at";
+
+/// Deletion reason reported on Deleted events for expired memories.
+pub const EXPIRED_REASON: &str = "expired";
+
+/// When a memory expires, if it has a valid `expires_at`.
+pub fn expires_at(metadata: &HashMap<String, serde_json::Value>) -> Option<DateTime<Utc>> {
+    metadata
+        .get(EXPIRES_AT_FIELD)
+        .and_then(|v| v.as_str())
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Whether a memory's `expires_at` is at or before `now`.
+pub fn is_expired(metadata: &HashMap<String, serde_json::Value>, now: DateTime<Utc>) -> bool {
+    expires_at(metadata).is_some_and(|t| t <= now)
+}
+
+/// The time `ttl_secs` from now.
+pub fn expiry_after(ttl_secs: u64) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::seconds(ttl_secs.min(i32::MAX as u64) as i64)
+}
+
+/// The `expires_at` value for a memory living `ttl_secs` from now.
+pub fn expiry_from_ttl(ttl_secs: u64) -> serde_json::Value {
+    serde_json::Value::String(expiry_after(ttl_secs).to_rfc3339())
+}
+
+/// Maintenance job soft-deleting expired memories.
+pub struct MemoryExpiryJob {
+    memory: Arc<Memory>,
+}
+
+impl MemoryExpiryJob {
+    pub fn new(memory: Arc<Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for MemoryExpiryJob {
+    fn name(&self) -> &str {
+        "memory_expiry"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        self.memory.expire_memories().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+        let metadata = |value: serde_json::Value| {
+            HashMap::from([(EXPIRES_AT_FIELD.to_string(), value)])
+        };
+
+        let past = (now - chrono::Duration::minutes(1)).to_rfc3339();
+        let future = (now + chrono::Duration::minutes(1)).to_rfc3339();
+        assert!(is_expired(&metadata(json!(past)), now));
+        assert!(!is_expired(&metadata(json!(future)), now));
+        assert!(!is_expired(&metadata(json!(null)), now));
+        assert!(!is_expired(&metadata(json!("tomorrow")), now));
+        assert!(!is_expired(&HashMap::new(), now));
+    }
+
+    #[tokio::test]
+    async fn test_expire_memories_filters_in_store() {
+        use crate::memory::testing::{test_config, test_memory_with_store, InMemoryVectorStore};
+        use crate::traits::VectorStore;
+        use crate::types::{Filter, FilterOperator};
+
+        // Listed with an `expires_at` condition, or in full and checked here
+        let stores = [
+            InMemoryVectorStore::with_timestamp_filters(),
+            InMemoryVectorStore::default(),
+        ];
+        for store in stores {
+            let pushdown = store.supports_timestamp_filters();
+            let (memory, store) = test_memory_with_store(test_config(), store);
+            let mut ids = Vec::new();
+            for text in ["Likes tea", "Likes coffee", "Likes cocoa"] {
+                let result = memory
+                    .add(
+                        text,
+                        Some("alice".to_string()),
+                        None,
+                        None,
+                        None,
+                        false,
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                ids.push(result.results[0].id.clone());
+            }
+            let now = Utc::now();
+            memory
+                .set_expiry(&ids[0], Some(now - chrono::Duration::minutes(1)))
+                .await
+                .unwrap();
+            memory
+                .set_expiry(&ids[1], Some(now + chrono::Duration::minutes(1)))
+                .await
+                .unwrap();
+
+            assert_eq!(memory.expire_memories().await.unwrap(), 1);
+            let remaining: Vec<String> = store.records().into_iter().map(|r| r.id).collect();
+            assert_eq!(remaining, ids[1..]);
+
+            let filter = store.list_filters().pop().unwrap();
+            match filter {
+                Some(Filter::Condition(cond)) => {
+                    assert!(pushdown);
+                    assert_eq!(cond.field, EXPIRES_AT_FIELD);
+                    assert!(matches!(cond.operator, FilterOperator::Lte(_)));
+                }
+                None => assert!(!pushdown),
+                other => panic!("unexpected filter {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_expiry_from_ttl() {
+        let value = expiry_from_ttl(60);
+        let expires = expires_at(&HashMap::from([(EXPIRES_AT_FIELD.to_string(), value)])).unwrap();
+        assert!(expires > Utc::now() + chrono::Duration::seconds(50));
+    }
+}
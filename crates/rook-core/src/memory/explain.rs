@@ -0,0 +1,152 @@
+//! Score breakdowns for memory search.
+//!
+//! A search with an `explain: true` filter entry returns a
+//! [`RetrievalExplanation`] with each result: the signals the retrieval
+//! stage combined, how much each later ranking stage moved the score, and
+//! whether the memory was injected as a key or pinned memory. It is meant
+//! for debugging why a memory surfaced.
+
+use std::collections::HashMap;
+
+use crate::retrieval::RetrievalSignals;
+use crate::types::{MemoryItem, RetrievalExplanation};
+
+/// Search filter entry returning a score breakdown with each result.
+pub const EXPLAIN_FILTER: &str = "explain";
+
+/// Explanation from the retrieval engine's signals for a result.
+pub(crate) fn from_signals(signals: &RetrievalSignals) -> RetrievalExplanation {
+    RetrievalExplanation {
+        vector_score: signals.vector,
+        bm25: signals.bm25,
+        activation: signals.activation,
+        fsrs_weight: signals.fsrs,
+        importance: signals.importance,
+        ..Default::default()
+    }
+}
+
+/// Start explaining retrieved memories from their scores. The score is taken
+/// as the vector similarity too unless the results were `fused` or the
+/// retrieval engine already recorded it.
+pub(crate) fn begin(memories: &mut [MemoryItem], fused: bool) {
+    for memory in memories.iter_mut() {
+        let explanation = memory.explanation.get_or_insert_with(Default::default);
+        explanation.retrieval_score = memory.score;
+        if !fused && explanation.vector_score.is_none() {
+            explanation.vector_score = memory.score;
+        }
+    }
+}
+
+/// Scores by memory ID, to measure a ranking stage against.
+pub(crate) fn scores(memories: &[MemoryItem]) -> HashMap<String, f32> {
+    memories
+        .iter()
+        .filter_map(|m| Some((m.id.clone(), m.score?)))
+        .collect()
+}
+
+/// Record how much a ranking stage moved each explained memory's score
+/// from `before`. Unchanged scores aren't recorded.
+pub(crate) fn record_deltas(
+    memories: &mut [MemoryItem],
+    before: &HashMap<String, f32>,
+    delta: fn(&mut RetrievalExplanation) -> &mut Option<f32>,
+) {
+    for memory in memories.iter_mut() {
+        let (Some(score), Some(previous)) = (memory.score, before.get(&memory.id)) else {
+            continue;
+        };
+        if let Some(ref mut explanation) = memory.explanation {
+            if score != *previous {
+                *delta(explanation) = Some(score - previous);
+            }
+        }
+    }
+}
+
+/// Finish the explanations of the returned memories.
+///
+/// Explanations of search results are carried over to key and pinned
+/// memories that replaced them; memories only present as key or pinned
+/// memories are marked as injected.
+pub(crate) fn finish(
+    memories: &mut [MemoryItem],
+    mut explained: HashMap<String, RetrievalExplanation>,
+    key_ids: &[String],
+) {
+    for memory in memories.iter_mut() {
+        let explanation = match memory.explanation.take().or_else(|| explained.remove(&memory.id)) {
+            Some(explanation) => explanation,
+            None => RetrievalExplanation {
+                key_memory: !memory.pinned && key_ids.contains(&memory.id),
+                pinned: memory.pinned,
+                ..Default::default()
+            },
+        };
+        memory.explanation = Some(RetrievalExplanation {
+            final_score: memory.score,
+            ..explanation
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scored(id: &str, score: f32) -> MemoryItem {
+        MemoryItem::new(id, format!("memory {}", id)).with_score(score)
+    }
+
+    #[test]
+    fn test_begin_and_record_deltas() {
+        let mut memories = vec![scored("a", 0.9), scored("b", 0.5)];
+        begin(&mut memories, false);
+        let explanation = memories[0].explanation.as_ref().unwrap();
+        assert_eq!(explanation.vector_score, Some(0.9));
+        assert_eq!(explanation.retrieval_score, Some(0.9));
+
+        let before = scores(&memories);
+        memories[1].score = Some(0.75);
+        record_deltas(&mut memories, &before, |e| &mut e.rerank_delta);
+        assert_eq!(memories[0].explanation.as_ref().unwrap().rerank_delta, None);
+        let delta = memories[1].explanation.as_ref().unwrap().rerank_delta.unwrap();
+        assert!((delta - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_begin_fused() {
+        let mut memories = vec![scored("a", 0.03)];
+        begin(&mut memories, true);
+        let explanation = memories[0].explanation.as_ref().unwrap();
+        assert_eq!(explanation.vector_score, None);
+        assert_eq!(explanation.retrieval_score, Some(0.03));
+    }
+
+    #[test]
+    fn test_finish_marks_injected_memories() {
+        let mut searched = scored("a", 0.8);
+        searched.explanation = Some(RetrievalExplanation {
+            vector_score: Some(0.8),
+            ..Default::default()
+        });
+        let explained = HashMap::from([("a".to_string(), searched.explanation.clone().unwrap())]);
+
+        // "a" was found and is a key memory, "k" only a key memory
+        let mut pinned = MemoryItem::new("p", "pinned");
+        pinned.pinned = true;
+        let mut memories = vec![pinned, scored("a", 0.8), MemoryItem::new("k", "key")];
+        let key_ids = vec!["a".to_string(), "k".to_string()];
+        finish(&mut memories, explained, &key_ids);
+
+        let pinned = memories[0].explanation.as_ref().unwrap();
+        assert!(pinned.pinned && !pinned.key_memory);
+        let found = memories[1].explanation.as_ref().unwrap();
+        assert_eq!(found.vector_score, Some(0.8));
+        assert!(!found.key_memory);
+        assert_eq!(found.final_score, Some(0.8));
+        assert!(memories[2].explanation.as_ref().unwrap().key_memory);
+    }
+}
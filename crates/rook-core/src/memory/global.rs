@@ -0,0 +1,266 @@
+//! Global knowledge shared across users.
+//!
+//! Global memories carry `scope: "global"` in place of a user/agent/run scope.
+//! They are created by promoting an existing memory (normally after an admin
+//! review) and are searched read-only alongside the caller's own scope when
+//! [`GlobalKnowledgeConfig::enabled`] is set.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+use crate::types::MemoryItem;
+
+/// Payload field marking a memory's scope.
+pub const SCOPE_FIELD: &str = "scope";
+
+/// Value of [`SCOPE_FIELD`] for global memories.
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// Payload fields that tie a memory to its original owner.
+const OWNER_FIELDS: &[&str] = &["user_id", "agent_id", "run_id", "actor_id", "role"];
+
+/// Global knowledge settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalKnowledgeConfig {
+    /// Search global memories alongside the caller's scope (default: false).
+    pub enabled: bool,
+    /// Hold promotions for review (default: true). When false, proposals are
+    /// approved as soon as they are made.
+    pub require_review: bool,
+    /// Maximum global memories merged into each search (default: 5).
+    pub search_limit: usize,
+}
+
+impl Default for GlobalKnowledgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            require_review: true,
+            search_limit: 5,
+        }
+    }
+}
+
+/// State of a promotion request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromotionStatus {
+    /// Waiting for review.
+    Pending,
+    /// Approved; the global memory exists.
+    Approved,
+    /// Rejected by a reviewer.
+    Rejected,
+}
+
+impl PromotionStatus {
+    /// String form stored in the history database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromotionStatus::Pending => "pending",
+            PromotionStatus::Approved => "approved",
+            PromotionStatus::Rejected => "rejected",
+        }
+    }
+
+    /// Parse the stored string form.
+    pub fn parse(s: &str) -> RookResult<Self> {
+        match s {
+            "pending" => Ok(PromotionStatus::Pending),
+            "approved" => Ok(PromotionStatus::Approved),
+            "rejected" => Ok(PromotionStatus::Rejected),
+            other => Err(RookError::validation(format!(
+                "Unknown promotion status '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// A request to promote a memory into the global scope, with its review trail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotionRecord {
+    /// Promotion ID.
+    pub id: String,
+    /// Memory being promoted.
+    pub memory_id: String,
+    /// Memory content when the promotion was proposed.
+    pub content: String,
+    /// User that owned the original memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contributor_user_id: Option<String>,
+    /// Agent that owned the original memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contributor_agent_id: Option<String>,
+    /// Who proposed the promotion.
+    pub requested_by: String,
+    /// When the promotion was proposed.
+    pub requested_at: String,
+    /// Why the memory should be global.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Current state.
+    pub status: PromotionStatus,
+    /// Who approved or rejected the promotion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+    /// When the promotion was reviewed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<String>,
+    /// Reviewer's comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_note: Option<String>,
+    /// The global memory created on approval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_memory_id: Option<String>,
+}
+
+/// Whether a memory payload or metadata map belongs to the global scope.
+pub fn is_global(metadata: &HashMap<String, serde_json::Value>) -> bool {
+    metadata.get(SCOPE_FIELD).and_then(|v| v.as_str()) == Some(GLOBAL_SCOPE)
+}
+
+/// Filters selecting global memories, keeping any non-scope conditions from `filters`.
+pub(crate) fn global_filters(
+    filters: &HashMap<String, serde_json::Value>,
+) -> HashMap<String, serde_json::Value> {
+    let mut global: HashMap<_, _> = filters
+        .iter()
+        .filter(|(key, _)| !OWNER_FIELDS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    global.insert(SCOPE_FIELD.to_string(), GLOBAL_SCOPE.into());
+    global
+}
+
+/// Payload for the global copy of a promoted memory.
+///
+/// Owner fields are dropped and replaced by provenance pointing back at the
+/// original memory, its contributor and the promotion.
+pub(crate) fn global_payload(
+    source: &HashMap<String, serde_json::Value>,
+    promotion: &PromotionRecord,
+    promoted_at: &str,
+) -> HashMap<String, serde_json::Value> {
+    let mut payload: HashMap<_, _> = source
+        .iter()
+        .filter(|(key, _)| !OWNER_FIELDS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    payload.insert(SCOPE_FIELD.to_string(), GLOBAL_SCOPE.into());
+    payload.insert("promoted_from".to_string(), promotion.memory_id.clone().into());
+    payload.insert("promotion_id".to_string(), promotion.id.clone().into());
+    payload.insert("promoted_at".to_string(), promoted_at.into());
+    payload.insert("created_at".to_string(), promoted_at.into());
+    payload.remove("updated_at");
+    if let Some(ref reviewer) = promotion.reviewed_by {
+        payload.insert("promoted_by".to_string(), reviewer.clone().into());
+    }
+    if let Some(ref user_id) = promotion.contributor_user_id {
+        payload.insert("contributor_user_id".to_string(), user_id.clone().into());
+    }
+    if let Some(ref agent_id) = promotion.contributor_agent_id {
+        payload.insert("contributor_agent_id".to_string(), agent_id.clone().into());
+    }
+    payload
+}
+
+/// Merge global results into scoped results by score, keeping `limit` items.
+pub(crate) fn merge_results(
+    scoped: Vec<MemoryItem>,
+    global: Vec<MemoryItem>,
+    limit: usize,
+) -> Vec<MemoryItem> {
+    let mut merged = scoped;
+    for item in global {
+        if !merged.iter().any(|m| m.id == item.id) {
+            merged.push(item);
+        }
+    }
+    merged.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    merged.truncate(limit);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn promotion() -> PromotionRecord {
+        PromotionRecord {
+            id: "p1".to_string(),
+            memory_id: "m1".to_string(),
+            content: "API rate limit is 100 rps".to_string(),
+            contributor_user_id: Some("alice".to_string()),
+            contributor_agent_id: None,
+            requested_by: "alice".to_string(),
+            requested_at: "2024-01-01T00:00:00Z".to_string(),
+            note: None,
+            status: PromotionStatus::Approved,
+            reviewed_by: Some("admin".to_string()),
+            reviewed_at: None,
+            review_note: None,
+            global_memory_id: None,
+        }
+    }
+
+    #[test]
+    fn test_global_payload_replaces_owner_with_provenance() {
+        let source: HashMap<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "data": "API rate limit is 100 rps",
+            "user_id": "alice",
+            "run_id": "r1",
+            "category": "technical",
+            "updated_at": "2024-02-01T00:00:00Z",
+        }))
+        .unwrap();
+
+        let payload = global_payload(&source, &promotion(), "2024-03-01T00:00:00Z");
+        assert!(is_global(&payload));
+        assert!(!payload.contains_key("user_id"));
+        assert!(!payload.contains_key("run_id"));
+        assert!(!payload.contains_key("updated_at"));
+        assert_eq!(payload["category"], "technical");
+        assert_eq!(payload["promoted_from"], "m1");
+        assert_eq!(payload["promoted_by"], "admin");
+        assert_eq!(payload["contributor_user_id"], "alice");
+    }
+
+    #[test]
+    fn test_global_filters_drop_owner_scope() {
+        let mut filters = HashMap::new();
+        filters.insert("user_id".to_string(), serde_json::json!("alice"));
+        filters.insert("category".to_string(), serde_json::json!("technical"));
+
+        let global = global_filters(&filters);
+        assert!(is_global(&global));
+        assert!(!global.contains_key("user_id"));
+        assert_eq!(global["category"], "technical");
+    }
+
+    #[test]
+    fn test_merge_results_by_score() {
+        let scored = |id: &str, score: f32| {
+            let mut item = MemoryItem::new(id, id);
+            item.score = Some(score);
+            item
+        };
+
+        let merged = merge_results(
+            vec![scored("a", 0.9), scored("b", 0.5)],
+            vec![scored("g", 0.7), scored("a", 0.9)],
+            2,
+        );
+        let ids: Vec<_> = merged.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "g"]);
+    }
+}
@@ -0,0 +1,207 @@
+//! Graph-aware search helpers.
+//!
+//! Entities named in a query seed a breadth-first walk over the graph store.
+//! Search results that mention an entity in that neighborhood are boosted by
+//! how close the entity is to the query.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{GraphRelation, MemoryItem};
+
+/// Entities and relations reached from a query.
+#[derive(Debug, Default)]
+pub(crate) struct GraphNeighborhood {
+    /// Hop distance from the query, keyed by lowercase entity name.
+    distances: HashMap<String, usize>,
+    /// Traversed relations in discovery order, without duplicates.
+    relations: Vec<GraphRelation>,
+    seen: HashSet<(String, String, String)>,
+}
+
+impl GraphNeighborhood {
+    /// Start a neighborhood from the entities named in the query.
+    pub(crate) fn new(seeds: &[String]) -> Self {
+        let mut neighborhood = Self::default();
+        for seed in seeds {
+            neighborhood.distances.insert(seed.to_lowercase(), 0);
+        }
+        neighborhood
+    }
+
+    /// Record relations found `hop` hops from the query.
+    ///
+    /// Returns the entities reached for the first time, to expand next.
+    pub(crate) fn extend(&mut self, relations: Vec<GraphRelation>, hop: usize) -> Vec<String> {
+        let mut frontier = Vec::new();
+        for relation in relations {
+            for entity in [&relation.source, &relation.target] {
+                if let Entry::Vacant(slot) = self.distances.entry(entity.to_lowercase()) {
+                    slot.insert(hop);
+                    frontier.push(entity.clone());
+                }
+            }
+
+            let triple = (
+                relation.source.to_lowercase(),
+                relation.relationship.to_lowercase(),
+                relation.target.to_lowercase(),
+            );
+            if self.seen.insert(triple) {
+                self.relations.push(relation);
+            }
+        }
+        frontier
+    }
+
+    /// Whether the walk reached any entity.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Boost for a memory: the largest `graph_boost / (hops + 1)` over the
+    /// entities it mentions, or zero.
+    pub(crate) fn boost_for(&self, text: &str, graph_boost: f32) -> f32 {
+        let text = text.to_lowercase();
+        self.distances
+            .iter()
+            .filter(|(name, _)| mentions(&text, name))
+            .map(|(_, hops)| graph_boost / (*hops as f32 + 1.0))
+            .fold(0.0, f32::max)
+    }
+
+    /// Consume the neighborhood, keeping at most `limit` relations.
+    pub(crate) fn into_relations(mut self, limit: usize) -> Vec<GraphRelation> {
+        self.relations.truncate(limit);
+        self.relations
+    }
+}
+
+/// Known entity names that appear in the query as whole words.
+pub(crate) fn query_entities<'a>(
+    query: &str,
+    names: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let query = query.to_lowercase();
+    let mut seen = HashSet::new();
+    names
+        .into_iter()
+        .filter(|name| mentions(&query, &name.to_lowercase()))
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether lowercase `text` contains lowercase `name` on word boundaries.
+///
+/// Single-character names are ignored; they match almost anything.
+fn mentions(text: &str, name: &str) -> bool {
+    if name.chars().count() < 2 {
+        return false;
+    }
+
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Add graph boosts to search scores and re-sort by score.
+///
+/// Order is left untouched when no memory mentions a neighborhood entity.
+pub(crate) fn apply_graph_boost(
+    memories: &mut [MemoryItem],
+    neighborhood: &GraphNeighborhood,
+    graph_boost: f32,
+) {
+    if neighborhood.is_empty() || graph_boost <= 0.0 {
+        return;
+    }
+
+    let mut boosted = false;
+    for memory in memories.iter_mut() {
+        let boost = neighborhood.boost_for(&memory.memory, graph_boost);
+        if boost > 0.0 {
+            memory.score = Some(memory.score.unwrap_or(0.0) + boost);
+            boosted = true;
+        }
+    }
+
+    if boosted {
+        memories.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation(source: &str, relationship: &str, target: &str) -> GraphRelation {
+        GraphRelation {
+            source: source.to_string(),
+            relationship: relationship.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    fn scored(id: &str, text: &str, score: f32) -> MemoryItem {
+        let mut item = MemoryItem::new(id, text);
+        item.score = Some(score);
+        item
+    }
+
+    #[test]
+    fn test_query_entities_word_boundaries() {
+        let names = ["Alice", "Al", "Acme Corp", "alice", "x"];
+        let found = query_entities("Where does alice work? Acme Corp?", names);
+        assert_eq!(found, vec!["Alice".to_string(), "Acme Corp".to_string()]);
+
+        assert!(query_entities("Alicent", ["Alice"]).is_empty());
+    }
+
+    #[test]
+    fn test_neighborhood_extend() {
+        let mut neighborhood = GraphNeighborhood::new(&["Alice".to_string()]);
+
+        let frontier = neighborhood.extend(
+            vec![
+                relation("Alice", "works_at", "Acme"),
+                relation("alice", "WORKS_AT", "acme"),
+            ],
+            1,
+        );
+        assert_eq!(frontier, vec!["Acme".to_string()]);
+
+        let frontier = neighborhood.extend(vec![relation("Acme", "located_in", "Berlin")], 2);
+        assert_eq!(frontier, vec!["Berlin".to_string()]);
+
+        assert_eq!(neighborhood.boost_for("Alice likes tea", 0.3), 0.3);
+        assert!((neighborhood.boost_for("Trip to Berlin", 0.3) - 0.1).abs() < 1e-6);
+        assert_eq!(neighborhood.boost_for("Unrelated", 0.3), 0.0);
+        assert_eq!(neighborhood.into_relations(10).len(), 2);
+    }
+
+    #[test]
+    fn test_apply_graph_boost_reorders() {
+        let mut neighborhood = GraphNeighborhood::new(&["Acme".to_string()]);
+        neighborhood.extend(vec![relation("Acme", "located_in", "Berlin")], 1);
+
+        let mut memories = vec![
+            scored("a", "Likes coffee", 0.8),
+            scored("b", "Works at Acme", 0.75),
+            scored("c", "Lives near Berlin", 0.7),
+        ];
+        apply_graph_boost(&mut memories, &neighborhood, 0.1);
+
+        let ids: Vec<_> = memories.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a", "c"]);
+        assert!((memories[0].score.unwrap() - 0.85).abs() < 1e-6);
+        assert!((memories[2].score.unwrap() - 0.75).abs() < 1e-6);
+    }
+}
@@ -1,12 +1,20 @@
 //! History tracking using SQLite.
 
-use rusqlite::{params, Connection};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+use crate::encryption::ContentCipher;
 use crate::error::{RookError, RookResult};
+use crate::retrieval::ThresholdCalibration;
+use crate::storage::{RebuildProgress, RebuildTarget};
+
+use super::global::{PromotionRecord, PromotionStatus};
+use super::runs::RunRecord;
+use super::session::SessionScope;
 
 /// Event type for history records.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,9 +66,24 @@ pub struct HistoryRecord {
     pub role: Option<String>,
 }
 
+/// A memory pinned to a run.
+///
+/// Pinned memories are returned by every search in the run, ahead of
+/// similarity results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunPin {
+    pub run_id: String,
+    pub memory_id: String,
+    pub pinned_at: DateTime<Utc>,
+    /// When the pin lapses; `None` keeps it until the run's pins are cleared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
 /// SQLite-based history store.
 pub struct HistoryStore {
     conn: Arc<Mutex<Connection>>,
+    cipher: Option<ContentCipher>,
 }
 
 impl HistoryStore {
@@ -80,6 +103,7 @@ impl HistoryStore {
 
         let store = Self {
             conn: Arc::new(Mutex::new(conn)),
+            cipher: None,
         };
 
         store.create_table()?;
@@ -87,6 +111,34 @@ impl HistoryStore {
         Ok(store)
     }
 
+    /// Encrypt memory content (history entries and promotion requests) with
+    /// `cipher`, as the memory store does. Content written before is still
+    /// read as it is.
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn encrypt(&self, record_id: &str, content: Option<&str>) -> RookResult<Option<String>> {
+        match (&self.cipher, content) {
+            (Some(cipher), Some(content)) => cipher.encrypt(record_id, content).map(Some),
+            (_, content) => Ok(content.map(String::from)),
+        }
+    }
+
+    fn decrypt(&self, record_id: &str, content: Option<String>) -> RookResult<Option<String>> {
+        match (&self.cipher, content) {
+            (Some(cipher), Some(content)) => cipher.decrypt(record_id, &content).map(Some),
+            (_, content) => Ok(content),
+        }
+    }
+
+    fn decrypt_record(&self, mut record: HistoryRecord) -> RookResult<HistoryRecord> {
+        record.old_memory = self.decrypt(&record.memory_id, record.old_memory)?;
+        record.new_memory = self.decrypt(&record.memory_id, record.new_memory)?;
+        Ok(record)
+    }
+
     /// Create the history table if it doesn't exist.
     fn create_table(&self) -> RookResult<()> {
         let conn = self.conn.lock().unwrap();
@@ -116,6 +168,95 @@ impl HistoryStore {
         )
         .map_err(|e| RookError::database(e.to_string()))?;
 
+        // Audit trail for promotions into the global scope
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS promotions (
+                id                    TEXT PRIMARY KEY,
+                memory_id             TEXT NOT NULL,
+                content               TEXT NOT NULL,
+                contributor_user_id   TEXT,
+                contributor_agent_id  TEXT,
+                requested_by          TEXT NOT NULL,
+                requested_at          DATETIME NOT NULL,
+                note                  TEXT,
+                status                TEXT NOT NULL,
+                reviewed_by           TEXT,
+                reviewed_at           DATETIME,
+                review_note           TEXT,
+                global_memory_id      TEXT
+            )
+            "#,
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
+        // Score thresholds calibrated per embedder model
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS threshold_calibrations (
+                embedder       TEXT NOT NULL,
+                model          TEXT NOT NULL,
+                sample_size    INTEGER NOT NULL,
+                relevant_mean  REAL NOT NULL,
+                random_mean    REAL NOT NULL,
+                strict         REAL NOT NULL,
+                default_score  REAL NOT NULL,
+                loose          REAL NOT NULL,
+                calibrated_at  DATETIME NOT NULL,
+                PRIMARY KEY (embedder, model)
+            )
+            "#,
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
+        // Memories pinned to runs
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS run_pins (
+                run_id     TEXT NOT NULL,
+                memory_id  TEXT NOT NULL,
+                pinned_at  DATETIME NOT NULL,
+                expires_at DATETIME,
+                PRIMARY KEY (run_id, memory_id)
+            )
+            "#,
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_run_pins_memory_id ON run_pins(memory_id)",
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
+        // Run lifecycle records; the full record is kept as JSON
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS runs (
+                run_id      TEXT PRIMARY KEY,
+                expires_at  DATETIME,
+                expired_at  DATETIME,
+                record      TEXT NOT NULL
+            )
+            "#,
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
+        // Checkpoints of derived store rebuilds
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS rebuild_progress (
+                target    TEXT PRIMARY KEY,
+                progress  TEXT NOT NULL
+            )
+            "#,
+            [],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
         Ok(())
     }
 
@@ -131,6 +272,8 @@ impl HistoryStore {
         actor_id: Option<&str>,
         role: Option<&str>,
     ) -> RookResult<String> {
+        let old_memory = self.encrypt(memory_id, old_memory)?;
+        let new_memory = self.encrypt(memory_id, new_memory)?;
         let conn = self.conn.lock().unwrap();
         let id = Uuid::new_v4().to_string();
         let is_deleted = matches!(event, HistoryEvent::Delete);
@@ -194,22 +337,804 @@ impl HistoryStore {
             .map_err(|e| RookError::database(e.to_string()))?;
 
         records
+            .map(|record| {
+                self.decrypt_record(record.map_err(|e| RookError::database(e.to_string()))?)
+            })
+            .collect()
+    }
+
+    /// Records written after the row with the given rowid, oldest first.
+    ///
+    /// Returns each record with its rowid so callers can resume from the last one.
+    pub fn changes_since(&self, rowid: i64) -> RookResult<Vec<(i64, HistoryRecord)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                r#"
+            SELECT rowid, id, memory_id, old_memory, new_memory, event,
+                   created_at, updated_at, is_deleted, actor_id, role
+            FROM history
+            WHERE rowid > ?1
+            ORDER BY rowid ASC
+            "#,
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        let records = stmt
+            .query_map([rowid], |row| {
+                Ok((
+                    row.get(0)?,
+                    HistoryRecord {
+                        id: row.get(1)?,
+                        memory_id: row.get(2)?,
+                        old_memory: row.get(3)?,
+                        new_memory: row.get(4)?,
+                        event: row.get(5)?,
+                        created_at: row.get(6)?,
+                        updated_at: row.get(7)?,
+                        is_deleted: row.get::<_, i32>(8)? != 0,
+                        actor_id: row.get(9)?,
+                        role: row.get(10)?,
+                    },
+                ))
+            })
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        records
+            .map(|record| {
+                let (rowid, record) = record.map_err(|e| RookError::database(e.to_string()))?;
+                Ok((rowid, self.decrypt_record(record)?))
+            })
+            .collect()
+    }
+
+    /// Remove history that is no longer needed.
+    ///
+    /// Drops the full history of deleted memories, then every record last
+    /// touched before `before` (if given). Returns the number of records removed.
+    pub fn prune(&self, before: Option<DateTime<Utc>>) -> RookResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        let mut removed = conn
+            .execute(
+                "DELETE FROM history WHERE memory_id IN \
+                 (SELECT memory_id FROM history WHERE is_deleted = 1)",
+                [],
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        if let Some(cutoff) = before {
+            removed += conn
+                .execute(
+                    "DELETE FROM history WHERE COALESCE(updated_at, created_at) < ?1",
+                    [cutoff.to_rfc3339()],
+                )
+                .map_err(|e| RookError::database(e.to_string()))?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Insert or update a promotion record.
+    pub fn save_promotion(&self, record: &PromotionRecord) -> RookResult<()> {
+        let content = self.encrypt(&record.id, Some(&record.content))?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO promotions (
+                id, memory_id, content, contributor_user_id, contributor_agent_id,
+                requested_by, requested_at, note, status, reviewed_by, reviewed_at,
+                review_note, global_memory_id
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+            "#,
+            params![
+                record.id,
+                record.memory_id,
+                content,
+                record.contributor_user_id,
+                record.contributor_agent_id,
+                record.requested_by,
+                record.requested_at,
+                record.note,
+                record.status.as_str(),
+                record.reviewed_by,
+                record.reviewed_at,
+                record.review_note,
+                record.global_memory_id,
+            ],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Get a promotion record by ID.
+    pub fn get_promotion(&self, id: &str) -> RookResult<Option<PromotionRecord>> {
+        Ok(self
+            .query_promotions("WHERE id = ?1", params![id])?
+            .into_iter()
+            .next())
+    }
+
+    /// List promotion records, newest first, optionally by status.
+    pub fn list_promotions(
+        &self,
+        status: Option<PromotionStatus>,
+    ) -> RookResult<Vec<PromotionRecord>> {
+        match status {
+            Some(status) => self.query_promotions(
+                "WHERE status = ?1 ORDER BY requested_at DESC",
+                params![status.as_str()],
+            ),
+            None => self.query_promotions("ORDER BY requested_at DESC", params![]),
+        }
+    }
+
+    fn query_promotions(
+        &self,
+        clause: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> RookResult<Vec<PromotionRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let sql = format!(
+            "SELECT id, memory_id, content, contributor_user_id, contributor_agent_id, \
+             requested_by, requested_at, note, status, reviewed_by, reviewed_at, review_note, \
+             global_memory_id FROM promotions {}",
+            clause
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok((
+                    PromotionRecord {
+                        id: row.get(0)?,
+                        memory_id: row.get(1)?,
+                        content: row.get(2)?,
+                        contributor_user_id: row.get(3)?,
+                        contributor_agent_id: row.get(4)?,
+                        requested_by: row.get(5)?,
+                        requested_at: row.get(6)?,
+                        note: row.get(7)?,
+                        status: PromotionStatus::Pending,
+                        reviewed_by: row.get(9)?,
+                        reviewed_at: row.get(10)?,
+                        review_note: row.get(11)?,
+                        global_memory_id: row.get(12)?,
+                    },
+                    row.get::<_, String>(8)?,
+                ))
+            })
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        rows.map(|row| {
+            let (mut record, status) = row.map_err(|e| RookError::database(e.to_string()))?;
+            record.status = PromotionStatus::parse(&status)?;
+            if let Some(ref cipher) = self.cipher {
+                record.content = cipher.decrypt(&record.id, &record.content)?;
+            }
+            Ok(record)
+        })
+        .collect()
+    }
+
+    /// Insert or replace the calibration for an embedder model.
+    pub fn save_calibration(&self, calibration: &ThresholdCalibration) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO threshold_calibrations (
+                embedder, model, sample_size, relevant_mean, random_mean, strict,
+                default_score, loose, calibrated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            "#,
+            params![
+                calibration.embedder,
+                calibration.model,
+                calibration.sample_size as i64,
+                calibration.relevant_mean,
+                calibration.random_mean,
+                calibration.strict,
+                calibration.default,
+                calibration.loose,
+                calibration.calibrated_at.to_rfc3339(),
+            ],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Get the calibration for an embedder model.
+    pub fn get_calibration(
+        &self,
+        embedder: &str,
+        model: &str,
+    ) -> RookResult<Option<ThresholdCalibration>> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                "SELECT sample_size, relevant_mean, random_mean, strict, default_score, loose, \
+                 calibrated_at FROM threshold_calibrations WHERE embedder = ?1 AND model = ?2",
+                params![embedder, model],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, f32>(1)?,
+                        row.get::<_, f32>(2)?,
+                        row.get::<_, f32>(3)?,
+                        row.get::<_, f32>(4)?,
+                        row.get::<_, f32>(5)?,
+                        row.get::<_, String>(6)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        let Some((sample_size, relevant_mean, random_mean, strict, default, loose, at)) = row
+        else {
+            return Ok(None);
+        };
+        let calibrated_at = DateTime::parse_from_rfc3339(&at)
+            .map_err(|e| RookError::database(format!("Invalid calibration timestamp: {}", e)))?
+            .with_timezone(&Utc);
+
+        Ok(Some(ThresholdCalibration {
+            embedder: embedder.to_string(),
+            model: model.to_string(),
+            sample_size: sample_size as usize,
+            relevant_mean,
+            random_mean,
+            strict,
+            default,
+            loose,
+            calibrated_at,
+        }))
+    }
+
+    /// Record the progress of a rebuild, replacing the previous checkpoint.
+    pub fn save_rebuild_progress(&self, progress: &RebuildProgress) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO rebuild_progress (target, progress) VALUES (?1, ?2)",
+            params![progress.target.as_str(), serde_json::to_string(progress)?],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Get the last recorded progress of a rebuild.
+    pub fn get_rebuild_progress(
+        &self,
+        target: RebuildTarget,
+    ) -> RookResult<Option<RebuildProgress>> {
+        let conn = self.conn.lock().unwrap();
+        let progress: Option<String> = conn
+            .query_row(
+                "SELECT progress FROM rebuild_progress WHERE target = ?1",
+                params![target.as_str()],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        Ok(progress.map(|p| serde_json::from_str(&p)).transpose()?)
+    }
+
+    /// Insert a run pin, or update the expiry of an existing one.
+    pub fn save_run_pin(&self, pin: &RunPin) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            r#"
+            INSERT INTO run_pins (run_id, memory_id, pinned_at, expires_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(run_id, memory_id) DO UPDATE SET expires_at = excluded.expires_at
+            "#,
+            params![
+                pin.run_id,
+                pin.memory_id,
+                pin.pinned_at.to_rfc3339(),
+                pin.expires_at.map(|t| t.to_rfc3339()),
+            ],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Unexpired pins of a run, oldest first. Expired pins are deleted.
+    pub fn list_run_pins(&self, run_id: &str, now: DateTime<Utc>) -> RookResult<Vec<RunPin>> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM run_pins WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+            params![now.to_rfc3339()],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT run_id, memory_id, pinned_at, expires_at FROM run_pins
+                 WHERE run_id = ?1 ORDER BY pinned_at, memory_id",
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![run_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })
+            .map_err(|e| RookError::database(e.to_string()))?
             .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|(run_id, memory_id, pinned_at, expires_at)| {
+                Ok(RunPin {
+                    run_id,
+                    memory_id,
+                    pinned_at: parse_pin_time(&pinned_at)?,
+                    expires_at: expires_at.as_deref().map(parse_pin_time).transpose()?,
+                })
+            })
+            .collect()
+    }
+
+    /// Delete one pin. Returns whether it existed.
+    pub fn delete_run_pin(&self, run_id: &str, memory_id: &str) -> RookResult<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn
+            .execute(
+                "DELETE FROM run_pins WHERE run_id = ?1 AND memory_id = ?2",
+                params![run_id, memory_id],
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete every pin of a run. Returns the number deleted.
+    pub fn clear_run_pins(&self, run_id: &str) -> RookResult<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM run_pins WHERE run_id = ?1", params![run_id])
             .map_err(|e| RookError::database(e.to_string()))
     }
 
-    /// Reset (clear) all history.
+    /// Delete the pins of a memory in every run.
+    pub fn delete_memory_pins(&self, memory_id: &str) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM run_pins WHERE memory_id = ?1", params![memory_id])
+            .map_err(|e| RookError::database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Insert or replace a run record.
+    pub fn save_run(&self, run: &RunRecord) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        insert_run(&conn, run)
+    }
+
+    /// Get a run record.
+    pub fn get_run(&self, run_id: &str) -> RookResult<Option<RunRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let record: Option<String> = conn
+            .query_row(
+                "SELECT record FROM runs WHERE run_id = ?1",
+                params![run_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        Ok(record.map(|r| serde_json::from_str(&r)).transpose()?)
+    }
+
+    /// Ended runs whose memories are due for deletion at `now`.
+    pub fn runs_due_for_expiry(&self, now: DateTime<Utc>) -> RookResult<Vec<RunRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT record FROM runs
+                 WHERE expired_at IS NULL AND expires_at IS NOT NULL AND expires_at <= ?1
+                 ORDER BY expires_at",
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+        let records = stmt
+            .query_map(params![now.to_rfc3339()], |row| row.get::<_, String>(0))
+            .map_err(|e| RookError::database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        records
+            .iter()
+            .map(|r| Ok(serde_json::from_str(r)?))
+            .collect()
+    }
+
+    /// Move run records and promotion contributors of the `from` scope to
+    /// the `to` scope, in one transaction.
+    ///
+    /// A record is moved when every ID set in `from` equals its own; IDs set
+    /// in `to` replace the record's. A run whose ID changes takes its pins
+    /// along and replaces any record of the new run ID. Returns the numbers
+    /// of runs and promotions changed.
+    pub fn reassign_scope(
+        &self,
+        from: &SessionScope,
+        to: &SessionScope,
+    ) -> RookResult<(usize, usize)> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        let records = {
+            let mut stmt = tx
+                .prepare("SELECT record FROM runs")
+                .map_err(|e| RookError::database(e.to_string()))?;
+            let records = stmt
+                .query_map([], |row| row.get::<_, String>(0))
+                .map_err(|e| RookError::database(e.to_string()))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| RookError::database(e.to_string()))?;
+            records
+        };
+
+        let mut runs = 0;
+        for record in records {
+            let mut run: RunRecord = serde_json::from_str(&record)?;
+            let run_id = Some(run.run_id.clone());
+            if !(scope_matches(&run.user_id, &from.user_id)
+                && scope_matches(&run.agent_id, &from.agent_id)
+                && scope_matches(&run_id, &from.run_id))
+            {
+                continue;
+            }
+
+            let moved = SessionScope::new(
+                to.user_id.clone().or_else(|| run.user_id.clone()),
+                to.agent_id.clone().or_else(|| run.agent_id.clone()),
+                to.run_id.clone().or(run_id),
+            );
+            if moved.user_id == run.user_id
+                && moved.agent_id == run.agent_id
+                && moved.run_id.as_deref() == Some(run.run_id.as_str())
+            {
+                continue;
+            }
+
+            let old_run_id = std::mem::take(&mut run.run_id);
+            run.run_id = moved.run_id.unwrap_or_else(|| old_run_id.clone());
+            run.user_id = moved.user_id;
+            run.agent_id = moved.agent_id;
+            if run.run_id != old_run_id {
+                tx.execute(
+                    "UPDATE OR IGNORE run_pins SET run_id = ?1 WHERE run_id = ?2",
+                    params![run.run_id, old_run_id],
+                )
+                .map_err(|e| RookError::database(e.to_string()))?;
+                tx.execute("DELETE FROM run_pins WHERE run_id = ?1", params![old_run_id])
+                    .map_err(|e| RookError::database(e.to_string()))?;
+                tx.execute("DELETE FROM runs WHERE run_id = ?1", params![old_run_id])
+                    .map_err(|e| RookError::database(e.to_string()))?;
+            }
+            insert_run(&tx, &run)?;
+            runs += 1;
+        }
+
+        // Promotions have no run
+        let promotions = if from.run_id.is_none() && (to.user_id.is_some() || to.agent_id.is_some())
+        {
+            tx.execute(
+                r#"
+                UPDATE promotions
+                SET contributor_user_id = COALESCE(?1, contributor_user_id),
+                    contributor_agent_id = COALESCE(?2, contributor_agent_id)
+                WHERE (?3 IS NULL OR contributor_user_id = ?3)
+                  AND (?4 IS NULL OR contributor_agent_id = ?4)
+                "#,
+                params![to.user_id, to.agent_id, from.user_id, from.agent_id],
+            )
+            .map_err(|e| RookError::database(e.to_string()))?
+        } else {
+            0
+        };
+
+        tx.commit().map_err(|e| RookError::database(e.to_string()))?;
+        Ok((runs, promotions))
+    }
+
+    /// Run records of a user.
+    pub fn user_runs(&self, user_id: &str) -> RookResult<Vec<RunRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT record FROM runs WHERE json_extract(record, '$.user_id') = ?1
+                 ORDER BY run_id",
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+        let records = stmt
+            .query_map(params![user_id], |row| row.get::<_, String>(0))
+            .map_err(|e| RookError::database(e.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        records
+            .iter()
+            .map(|r| Ok(serde_json::from_str(r)?))
+            .collect()
+    }
+
+    /// Promotion records contributed by a user, newest first.
+    pub fn user_promotions(&self, user_id: &str) -> RookResult<Vec<PromotionRecord>> {
+        self.query_promotions(
+            "WHERE contributor_user_id = ?1 ORDER BY requested_at DESC",
+            params![user_id],
+        )
+    }
+
+    /// Delete the history of `memory_ids` and the runs and promotion records
+    /// of a user, in one transaction.
+    ///
+    /// Pins of the memories and of the user's runs go too. Returns the
+    /// numbers of history records, runs and promotions deleted.
+    pub fn erase_user(
+        &self,
+        user_id: &str,
+        memory_ids: &[String],
+    ) -> RookResult<(usize, usize, usize)> {
+        let conn = self.conn.lock().unwrap();
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        let mut history = 0;
+        for memory_id in memory_ids {
+            history += tx
+                .execute("DELETE FROM history WHERE memory_id = ?1", params![memory_id])
+                .map_err(|e| RookError::database(e.to_string()))?;
+            tx.execute("DELETE FROM run_pins WHERE memory_id = ?1", params![memory_id])
+                .map_err(|e| RookError::database(e.to_string()))?;
+        }
+
+        tx.execute(
+            "DELETE FROM run_pins WHERE run_id IN \
+             (SELECT run_id FROM runs WHERE json_extract(record, '$.user_id') = ?1)",
+            params![user_id],
+        )
+        .map_err(|e| RookError::database(e.to_string()))?;
+        let runs = tx
+            .execute(
+                "DELETE FROM runs WHERE json_extract(record, '$.user_id') = ?1",
+                params![user_id],
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+        let promotions = tx
+            .execute(
+                "DELETE FROM promotions WHERE contributor_user_id = ?1",
+                params![user_id],
+            )
+            .map_err(|e| RookError::database(e.to_string()))?;
+
+        tx.commit().map_err(|e| RookError::database(e.to_string()))?;
+        Ok((history, runs, promotions))
+    }
+
+    /// Reset (clear) all history, including promotion records, run pins and
+    /// run records.
     pub fn reset(&self) -> RookResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute("DELETE FROM history", [])
             .map_err(|e| RookError::database(e.to_string()))?;
+        conn.execute("DELETE FROM promotions", [])
+            .map_err(|e| RookError::database(e.to_string()))?;
+        conn.execute("DELETE FROM run_pins", [])
+            .map_err(|e| RookError::database(e.to_string()))?;
+        conn.execute("DELETE FROM runs", [])
+            .map_err(|e| RookError::database(e.to_string()))?;
         Ok(())
     }
 }
 
+fn parse_pin_time(s: &str) -> RookResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| RookError::database(format!("Invalid pin timestamp '{}': {}", s, e)))
+}
+
+fn insert_run(conn: &Connection, run: &RunRecord) -> RookResult<()> {
+    let record = serde_json::to_string(run)?;
+    conn.execute(
+        r#"
+        INSERT OR REPLACE INTO runs (run_id, expires_at, expired_at, record)
+        VALUES (?1, ?2, ?3, ?4)
+        "#,
+        params![
+            run.run_id,
+            run.expires_at.map(|t| t.to_rfc3339()),
+            run.expired_at.map(|t| t.to_rfc3339()),
+            record,
+        ],
+    )
+    .map_err(|e| RookError::database(e.to_string()))?;
+    Ok(())
+}
+
+/// Whether a record's scope ID matches `filter`, which matches any ID when unset.
+fn scope_matches(id: &Option<String>, filter: &Option<String>) -> bool {
+    filter.is_none() || id == filter
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encryption::{generate_content_key, ContentEncryptionConfig};
+
+    #[test]
+    fn test_run_pins() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let now = Utc::now();
+        let pin = |run: &str, memory: &str, expires_at| RunPin {
+            run_id: run.to_string(),
+            memory_id: memory.to_string(),
+            pinned_at: now,
+            expires_at,
+        };
+        store.save_run_pin(&pin("run1", "m1", None)).unwrap();
+        store
+            .save_run_pin(&pin("run1", "m2", Some(now + chrono::Duration::hours(1))))
+            .unwrap();
+        store
+            .save_run_pin(&pin("run1", "m3", Some(now - chrono::Duration::seconds(1))))
+            .unwrap();
+        store.save_run_pin(&pin("run2", "m1", None)).unwrap();
+
+        // The expired pin is dropped
+        let pins = store.list_run_pins("run1", now).unwrap();
+        let ids: Vec<_> = pins.iter().map(|p| p.memory_id.as_str()).collect();
+        assert_eq!(ids, vec!["m1", "m2"]);
+        assert_eq!(pins[1].expires_at, Some(now + chrono::Duration::hours(1)));
+
+        // Later, the other pin with an expiry lapses too
+        let later = now + chrono::Duration::hours(2);
+        assert_eq!(store.list_run_pins("run1", later).unwrap().len(), 1);
+
+        assert!(store.delete_run_pin("run1", "m1").unwrap());
+        assert!(!store.delete_run_pin("run1", "m1").unwrap());
+
+        store.delete_memory_pins("m1").unwrap();
+        assert!(store.list_run_pins("run2", now).unwrap().is_empty());
+        assert_eq!(store.clear_run_pins("run1").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_runs() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let now = Utc::now();
+        assert!(store.get_run("run1").unwrap().is_none());
+
+        let mut run = RunRecord::new("run1");
+        run.user_id = Some("alice".to_string());
+        store.save_run(&run).unwrap();
+        store.save_run(&RunRecord::new("run2")).unwrap();
+        assert_eq!(store.get_run("run1").unwrap(), Some(run.clone()));
+        assert!(store.runs_due_for_expiry(now).unwrap().is_empty());
+
+        run.ended_at = Some(now);
+        run.expires_at = Some(now + chrono::Duration::hours(1));
+        store.save_run(&run).unwrap();
+        assert!(store.runs_due_for_expiry(now).unwrap().is_empty());
+
+        let later = now + chrono::Duration::hours(2);
+        let due = store.runs_due_for_expiry(later).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].run_id, "run1");
+
+        run.expired_at = Some(later);
+        store.save_run(&run).unwrap();
+        assert!(store.runs_due_for_expiry(later).unwrap().is_empty());
+
+        store.reset().unwrap();
+        assert!(store.get_run("run2").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reassign_scope() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let now = Utc::now();
+        let mut run = RunRecord::new("run1");
+        run.user_id = Some("alice-2".to_string());
+        store.save_run(&run).unwrap();
+        let mut other = RunRecord::new("run2");
+        other.user_id = Some("bob".to_string());
+        store.save_run(&other).unwrap();
+        store
+            .save_run_pin(&RunPin {
+                run_id: "run1".to_string(),
+                memory_id: "m1".to_string(),
+                pinned_at: now,
+                expires_at: None,
+            })
+            .unwrap();
+        store
+            .save_promotion(&PromotionRecord {
+                id: "p1".to_string(),
+                memory_id: "m1".to_string(),
+                content: "Uses Helix".to_string(),
+                contributor_user_id: Some("alice-2".to_string()),
+                contributor_agent_id: None,
+                requested_by: "alice-2".to_string(),
+                requested_at: "2024-01-01T00:00:00Z".to_string(),
+                note: None,
+                status: PromotionStatus::Pending,
+                reviewed_by: None,
+                reviewed_at: None,
+                review_note: None,
+                global_memory_id: None,
+            })
+            .unwrap();
+
+        let counts = store
+            .reassign_scope(&SessionScope::user("alice-2"), &SessionScope::user("alice"))
+            .unwrap();
+        assert_eq!(counts, (1, 1));
+        assert_eq!(store.get_run("run1").unwrap().unwrap().user_id.as_deref(), Some("alice"));
+        assert_eq!(store.get_run("run2").unwrap().unwrap().user_id.as_deref(), Some("bob"));
+        let promotion = store.get_promotion("p1").unwrap().unwrap();
+        assert_eq!(promotion.contributor_user_id.as_deref(), Some("alice"));
+
+        // Renaming a run moves its pins
+        let counts = store
+            .reassign_scope(&SessionScope::run("run1"), &SessionScope::run("run3"))
+            .unwrap();
+        assert_eq!(counts, (1, 0));
+        assert!(store.get_run("run1").unwrap().is_none());
+        assert_eq!(store.get_run("run3").unwrap().unwrap().user_id.as_deref(), Some("alice"));
+        assert_eq!(store.list_run_pins("run3", now).unwrap().len(), 1);
+        assert!(store.list_run_pins("run1", now).unwrap().is_empty());
+
+        let counts = store
+            .reassign_scope(&SessionScope::user("alice-2"), &SessionScope::user("alice"))
+            .unwrap();
+        assert_eq!(counts, (0, 0));
+    }
+
+    #[test]
+    fn test_erase_user() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let now = Utc::now();
+        for (memory_id, actor) in [("m1", "alice"), ("m2", "bob")] {
+            store
+                .add(memory_id, None, Some("Uses Helix"), HistoryEvent::Add, None, None, Some(actor), None)
+                .unwrap();
+        }
+        let mut run = RunRecord::new("run1");
+        run.user_id = Some("alice".to_string());
+        store.save_run(&run).unwrap();
+        store.save_run(&RunRecord::new("run2")).unwrap();
+        for (run_id, memory_id) in [("run1", "m2"), ("run2", "m1")] {
+            store
+                .save_run_pin(&RunPin {
+                    run_id: run_id.to_string(),
+                    memory_id: memory_id.to_string(),
+                    pinned_at: now,
+                    expires_at: None,
+                })
+                .unwrap();
+        }
+        assert_eq!(store.user_runs("alice").unwrap(), vec![run]);
+
+        let counts = store.erase_user("alice", &["m1".to_string()]).unwrap();
+        assert_eq!(counts, (1, 1, 0));
+        assert!(store.get("m1").unwrap().is_empty());
+        assert_eq!(store.get("m2").unwrap().len(), 1);
+        assert!(store.user_runs("alice").unwrap().is_empty());
+        assert!(store.get_run("run2").unwrap().is_some());
+        assert!(store.list_run_pins("run1", now).unwrap().is_empty());
+        assert!(store.list_run_pins("run2", now).unwrap().is_empty());
+    }
 
     #[test]
     fn test_history_store() {
@@ -238,6 +1163,88 @@ mod tests {
         assert_eq!(history[0].new_memory, Some("new memory".to_string()));
     }
 
+    #[test]
+    fn test_history_changes_since() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        for memory_id in ["mem1", "mem2", "mem3"] {
+            store
+                .add(memory_id, None, Some("x"), HistoryEvent::Add, None, None, None, None)
+                .unwrap();
+        }
+
+        let all = store.changes_since(0).unwrap();
+        assert_eq!(all.len(), 3);
+        let rest = store.changes_since(all[0].0).unwrap();
+        let ids: Vec<_> = rest.iter().map(|(_, r)| r.memory_id.as_str()).collect();
+        assert_eq!(ids, vec!["mem2", "mem3"]);
+    }
+
+    #[test]
+    fn test_history_encrypted_at_rest() {
+        let config = ContentEncryptionConfig::with_key("k1", generate_content_key().unwrap());
+        let store = HistoryStore::new(":memory:")
+            .unwrap()
+            .with_cipher(ContentCipher::new(config).unwrap());
+        store
+            .add(
+                "mem1",
+                Some("Uses Vim"),
+                Some("Uses Helix"),
+                HistoryEvent::Update,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let mut promotion = PromotionRecord {
+            id: "p1".to_string(),
+            memory_id: "mem1".to_string(),
+            content: "Uses Helix".to_string(),
+            contributor_user_id: None,
+            contributor_agent_id: None,
+            requested_by: "alice".to_string(),
+            requested_at: "2024-01-01T00:00:00Z".to_string(),
+            note: None,
+            status: PromotionStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_note: None,
+            global_memory_id: None,
+        };
+        store.save_promotion(&promotion).unwrap();
+
+        let stored: Vec<String> = {
+            let conn = store.conn.lock().unwrap();
+            let (old, new): (String, String) = conn
+                .query_row("SELECT old_memory, new_memory FROM history", [], |row| {
+                    Ok((row.get(0)?, row.get(1)?))
+                })
+                .unwrap();
+            let content: String = conn
+                .query_row("SELECT content FROM promotions", [], |row| row.get(0))
+                .unwrap();
+            vec![old, new, content]
+        };
+        for value in stored {
+            assert!(value.starts_with("enc:k1:"));
+        }
+
+        let history = store.get("mem1").unwrap();
+        assert_eq!(history[0].old_memory.as_deref(), Some("Uses Vim"));
+        assert_eq!(history[0].new_memory.as_deref(), Some("Uses Helix"));
+        assert_eq!(
+            store.changes_since(0).unwrap()[0].1.new_memory.as_deref(),
+            Some("Uses Helix")
+        );
+        promotion.status = PromotionStatus::Approved;
+        store.save_promotion(&promotion).unwrap();
+        assert_eq!(
+            store.get_promotion("p1").unwrap().unwrap().content,
+            "Uses Helix"
+        );
+    }
+
     #[test]
     fn test_history_reset() {
         let store = HistoryStore::new(":memory:").unwrap();
@@ -254,4 +1261,100 @@ mod tests {
         let history = store.get("mem1").unwrap();
         assert!(history.is_empty());
     }
+
+    #[test]
+    fn test_promotion_records() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let mut record = PromotionRecord {
+            id: "p1".to_string(),
+            memory_id: "mem1".to_string(),
+            content: "API rate limit is 100 rps".to_string(),
+            contributor_user_id: Some("alice".to_string()),
+            contributor_agent_id: None,
+            requested_by: "alice".to_string(),
+            requested_at: "2024-01-01T00:00:00Z".to_string(),
+            note: Some("applies to everyone".to_string()),
+            status: PromotionStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_note: None,
+            global_memory_id: None,
+        };
+        store.save_promotion(&record).unwrap();
+
+        record.status = PromotionStatus::Approved;
+        record.reviewed_by = Some("admin".to_string());
+        record.global_memory_id = Some("g1".to_string());
+        store.save_promotion(&record).unwrap();
+
+        let loaded = store.get_promotion("p1").unwrap().unwrap();
+        assert_eq!(loaded.status, PromotionStatus::Approved);
+        assert_eq!(loaded.global_memory_id.as_deref(), Some("g1"));
+        assert_eq!(loaded.note.as_deref(), Some("applies to everyone"));
+
+        assert_eq!(store.list_promotions(None).unwrap().len(), 1);
+        assert!(store.list_promotions(Some(PromotionStatus::Pending)).unwrap().is_empty());
+        assert!(store.get_promotion("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_history_prune() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        let old = "2020-01-01T00:00:00+00:00";
+        let recent = Utc::now().to_rfc3339();
+
+        store
+            .add("old", None, Some("a"), HistoryEvent::Add, Some(old), None, None, None)
+            .unwrap();
+        store
+            .add("kept", None, Some("b"), HistoryEvent::Add, Some(&recent), None, None, None)
+            .unwrap();
+        store
+            .add("gone", None, Some("c"), HistoryEvent::Add, Some(&recent), None, None, None)
+            .unwrap();
+        store
+            .add("gone", Some("c"), None, HistoryEvent::Delete, None, None, None, None)
+            .unwrap();
+
+        // Without a cutoff only deleted memories lose their history
+        assert_eq!(store.prune(None).unwrap(), 2);
+        assert_eq!(store.get("old").unwrap().len(), 1);
+
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        assert_eq!(store.prune(Some(cutoff)).unwrap(), 1);
+        assert!(store.get("old").unwrap().is_empty());
+        assert_eq!(store.get("kept").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_threshold_calibrations() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        assert!(store.get_calibration("openai", "small").unwrap().is_none());
+
+        let calibration =
+            ThresholdCalibration::estimate("openai", "small", &[0.8, 0.9], &[0.1, 0.2]).unwrap();
+        store.save_calibration(&calibration).unwrap();
+
+        let loaded = store.get_calibration("openai", "small").unwrap().unwrap();
+        assert_eq!(loaded.strict, calibration.strict);
+        assert_eq!(loaded.loose, calibration.loose);
+        assert_eq!(loaded.sample_size, 2);
+        assert!(store.get_calibration("openai", "large").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rebuild_progress() {
+        let store = HistoryStore::new(":memory:").unwrap();
+        assert!(store.get_rebuild_progress(RebuildTarget::Graph).unwrap().is_none());
+
+        let mut progress = RebuildProgress::new(RebuildTarget::Graph);
+        progress.processed = 10;
+        progress.cursor = Some("m10".to_string());
+        store.save_rebuild_progress(&progress).unwrap();
+
+        let loaded = store.get_rebuild_progress(RebuildTarget::Graph).unwrap().unwrap();
+        assert_eq!(loaded.processed, 10);
+        assert_eq!(loaded.cursor.as_deref(), Some("m10"));
+        assert!(store.get_rebuild_progress(RebuildTarget::Fsrs).unwrap().is_none());
+    }
 }
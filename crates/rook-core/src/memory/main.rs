@@ -1,37 +1,107 @@
 //! Core Memory implementation.
 
+use async_trait::async_trait;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::io::AsyncWrite;
 use tokio::sync::RwLock;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::cognitive::{CognitiveStore, DecaySource, FsrsScheduler};
 use crate::config::MemoryConfig;
+use crate::encryption::{BlindIndexer, ContentCipher, EncryptedVectorStore, EncryptedVersionStore};
 use crate::error::{RookError, RookResult};
 use crate::events::{
-    AccessType, EventBus, MemoryAccessedEvent, MemoryCreatedEvent, MemoryDeletedEvent,
-    MemoryLifecycleEvent, MemoryUpdatedEvent, UpdateType,
+    AccessType, ErrorRateMonitor, EventBus, MemoryAccessedEvent, MemoryCreatedEvent,
+    MemoryDeletedEvent, MemoryLifecycleEvent, MemoryUpdatedEvent, UpdateType,
 };
 use crate::ingestion::{
     IngestDecision, IngestResult, PredictionErrorGate, StrengthSignal, StrengthSignalProcessor,
 };
+use crate::export::{
+    export_bundle_jsonl, BundleHeader, ExportBundle, ExportSection, ExportStats, ExportableMemory,
+    Tombstone,
+};
+use crate::import::{ImportDedup, ImportableMemory};
+use crate::intentions::{
+    Intention, IntentionAction, IntentionStore, SurfacedMemories, TriggerCondition,
+};
+use crate::observability::metrics;
+use crate::observability::sampling::child_span;
+use crate::retrieval::{
+    sample_pair_scores, ActivationGraph, CachedReranker, RetrievalConfig, RetrievalEngine,
+    RetrievalMode, RrfFusion, ThresholdCalibration, ThresholdSpec,
+};
+use crate::storage::{RebuildOptions, RebuildProgress, RebuildStatus, RebuildTarget, Rebuilder};
+use crate::sync::{
+    resolve, Change, Changeset, ConflictPolicy, Resolution, SupersedeRecord, SyncReport, SyncState,
+    SyncStatus, SyncStore, SYNC_ACTOR_PREFIX,
+};
 use crate::traits::{
-    Embedder, EmbeddingAction, GenerationOptions, GraphFilters, GraphStore, Llm, Reranker,
-    ResponseFormat, VectorRecord, VectorSearchResult, VectorStore,
+    Embedder, EmbeddingAction, GenerationOptions, GraphFilters, GraphStore, ImageEmbedder, Llm,
+    LlmResponse, Reranker, ResponseFormat, VectorRecord, VectorSearchResult, VectorStore,
 };
+use crate::versioning::{MemoryVersion, SqliteVersionStore, VersionEventType, VersionStore};
 use crate::types::{
-    AddResult, Filter, Grade, GraphRelation, MemoryEvent, MemoryItem, MemoryResult, MemoryType,
-    Message, MessageInput, MessageRole, SearchResult,
+    normalize_tag, parse_tag_filter, has_all_tags, tags_of, AddResult, Filter, Grade,
+    GraphRelation, MemoryEvent, MemoryItem, MemoryResult, MemoryType, Message, MessageInput,
+    MessageRole, Page, PageRequest, RetrievalExplanation, SearchResult, TagCount, TAGS_FIELD,
 };
-
-use super::history::{HistoryEvent, HistoryStore};
+use crate::types::{DualStrength, FsrsState};
+
+use super::archive::{ARCHIVED_AT_FIELD, INCLUDE_ARCHIVED_FILTER};
+use super::as_of::{existed_at, in_scope, VersionEmbeddingCache};
+use super::classification_cache::ClassificationCache;
+use super::document::{is_document, Document, DOCUMENT_ID_FIELD};
+use super::erasure::{ErasureReport, UserDataCounts};
+use super::expansion::{
+    parse_query_expansion, parse_query_expansion_response, query_expansion_prompt, QueryExpansion,
+    QUERY_EXPANSION_FILTER,
+};
+use super::explain::{self, EXPLAIN_FILTER};
+use super::expiry::{is_expired, EXPIRED_REASON, EXPIRES_AT_FIELD};
+use super::global::{
+    global_filters, global_payload, is_global, merge_results,
+    PromotionRecord, PromotionStatus,
+};
+use super::graph_search::{apply_graph_boost, query_entities, GraphNeighborhood};
+use super::history::{HistoryEvent, HistoryStore, RunPin};
 use super::json_parser::{parse_facts, parse_memory_actions};
+use super::merge::{
+    merge_prompt, parse_merged_memory, MergeStrategy, MERGED_REASON, SUPERSEDED_BY_FIELD,
+};
 use super::prompts::{
     agent_memory_extraction_prompt, build_update_memory_message, classification_prompt,
-    entity_extraction_prompt, find_entity_match, parse_classification, parse_entity_extraction,
-    procedural_memory_prompt, user_memory_extraction_prompt, ClassificationResult, MergeConfig,
+    cosine_similarity, entity_extraction_prompt, find_entity_match, parse_classification,
+    parse_entity_extraction, procedural_memory_prompt, user_memory_extraction_prompt,
+    ClassificationResult, MergeConfig,
 };
-use super::session::SessionScope;
+use super::reflection::{knowledge_gaps_prompt, parse_knowledge_gaps, KnowledgeGapReport};
+use super::runs::{
+    parse_run_summary, run_summary_prompt, RunEndOptions, RunRecord, SessionSummary,
+    RUN_SOURCE_FIELD, SUMMARIZED_REASON, SUMMARY_SOURCES_FIELD,
+};
+use super::retrieval::{
+    parse_mmr_lambda, parse_retrieval_mode, PooledSearcher, ACTIVATION_POOL, MMR_LAMBDA_FILTER,
+    RETRIEVAL_MODE_FILTER,
+};
+use super::semantic::{parse_semantic_promotion, semantic_promotion_prompt, SEMANTIC_MEMORY_TYPE};
+use super::session::{ScopeReassignment, SessionScope};
 use super::telemetry::{process_telemetry_filters, Telemetry};
+use super::temporal::{
+    apply_recency_boost, parse_recency_half_life, TimeRange, RECENCY_HALF_LIFE_FILTER,
+};
+
+/// Memories retrieved as context when looking for knowledge gaps.
+const KNOWLEDGE_GAP_CONTEXT: usize = 20;
+
+/// Candidates fetched per requested result when results are filtered after
+/// the vector search: by tag, or by time range in stores that can't compare
+/// timestamps.
+const POST_FILTER_OVERFETCH: usize = 5;
 
 /// Main Memory struct - the core of rook.
 pub struct Memory {
@@ -46,6 +116,35 @@ pub struct Memory {
     prediction_error_gate: PredictionErrorGate,
     strength_processor: Mutex<StrengthSignalProcessor>,
     event_bus: Option<EventBus>,
+    error_monitor: Option<Arc<ErrorRateMonitor>>,
+    blind_indexer: Option<BlindIndexer>,
+    /// The vector store, when memory content is encrypted at rest.
+    encrypted_store: Option<Arc<EncryptedVectorStore>>,
+    sync: Option<SyncStore>,
+    versions: Option<Arc<dyn VersionStore>>,
+    classification_cache: Option<Arc<ClassificationCache>>,
+    cognitive_store: Option<Arc<CognitiveStore>>,
+    fsrs: FsrsScheduler,
+    /// Schedulers of categories with FSRS parameter overrides.
+    category_fsrs: HashMap<String, FsrsScheduler>,
+    intention_store: Option<Arc<dyn IntentionStore>>,
+    /// Memories whose reminders fired, to lead their users' next searches.
+    surfaced_memories: Option<Arc<SurfacedMemories>>,
+    archive_store: Option<Arc<dyn VectorStore>>,
+    /// The archive store, when memory content is encrypted at rest.
+    encrypted_archive: Option<Arc<EncryptedVectorStore>>,
+    /// Graph spreading activation follows in retrieval modes that use it.
+    activation_graph: Option<Arc<dyn ActivationGraph>>,
+    /// Embeddings of past memory contents, for point-in-time search.
+    version_embeddings: VersionEmbeddingCache,
+    /// Embeddings of image memories, for searching images by what they show.
+    image_index: Option<ImageIndex>,
+}
+
+/// Image embeddings in a collection of their own, keyed by memory ID.
+struct ImageIndex {
+    embedder: Arc<dyn ImageEmbedder>,
+    store: Arc<dyn VectorStore>,
 }
 
 impl Memory {
@@ -54,6 +153,9 @@ impl Memory {
     /// Note: This method requires you to provide the provider implementations.
     /// Use the factory methods in rook-llm, rook-embeddings, and rook-vector-stores
     /// to create these.
+    ///
+    /// The reranker is wrapped in a [`CachedReranker`] when the reranker
+    /// config sets a cache TTL or timeout.
     pub fn new(
         config: MemoryConfig,
         llm: Arc<dyn Llm>,
@@ -62,12 +164,91 @@ impl Memory {
         graph_store: Option<Arc<dyn GraphStore>>,
         reranker: Option<Arc<dyn Reranker>>,
     ) -> RookResult<Self> {
-        let history = Arc::new(RwLock::new(HistoryStore::new(&config.history_db_path)?));
+        let fsrs = config.cognitive.fsrs.scheduler()?;
+        let category_fsrs = config.cognitive.fsrs.category_schedulers()?;
+        let mut history = HistoryStore::new(&config.history_db_path)?;
+        if let Some(ref encryption) = config.content_encryption {
+            history = history.with_cipher(ContentCipher::new(encryption.clone())?);
+        }
+        let history = Arc::new(RwLock::new(history));
         let telemetry = Telemetry::new(None);
 
         // Initialize prediction error gate with LLM for semantic layer
         let prediction_error_gate = PredictionErrorGate::new(Some(llm.clone()));
         let strength_processor = Mutex::new(StrengthSignalProcessor::new());
+        let blind_indexer = config
+            .blind_index
+            .clone()
+            .map(BlindIndexer::new)
+            .transpose()?;
+        let (vector_store, encrypted_store) = match config.content_encryption {
+            Some(ref encryption) => {
+                let cipher = ContentCipher::new(encryption.clone())?;
+                let store = Arc::new(EncryptedVectorStore::new(vector_store, cipher));
+                (store.clone() as Arc<dyn VectorStore>, Some(store))
+            }
+            None => (vector_store, None),
+        };
+        let sync = if config.sync.enabled {
+            let path = match config.sync.db_path {
+                Some(ref path) => path.clone(),
+                None if config.history_db_path.to_str() == Some(":memory:") => ":memory:".into(),
+                None => config.history_db_path.with_file_name("sync.db"),
+            };
+            let store = SyncStore::new(path, config.sync.node_id.clone())?;
+            Some(match config.content_encryption {
+                Some(ref encryption) => store.with_cipher(ContentCipher::new(encryption.clone())?),
+                None => store,
+            })
+        } else {
+            None
+        };
+        let versions: Option<Arc<dyn VersionStore>> = if config.versioning.enabled {
+            let path = match config.versioning.db_path {
+                Some(ref path) => path.clone(),
+                None if config.history_db_path.to_str() == Some(":memory:") => ":memory:".into(),
+                None => config.history_db_path.with_file_name("versions.db"),
+            };
+            let store = if path.to_str() == Some(":memory:") {
+                SqliteVersionStore::in_memory()?
+            } else {
+                SqliteVersionStore::new(path)?
+            };
+            match config.content_encryption {
+                Some(ref encryption) => Some(Arc::new(EncryptedVersionStore::new(
+                    Arc::new(store),
+                    ContentCipher::new(encryption.clone())?,
+                ))),
+                None => Some(Arc::new(store)),
+            }
+        } else {
+            None
+        };
+        let cognitive_store = if config.cognitive.enabled {
+            let path = match config.cognitive.db_path {
+                Some(ref path) => path.clone(),
+                None if config.history_db_path.to_str() == Some(":memory:") => ":memory:".into(),
+                None => config.history_db_path.with_file_name("cognitive.db"),
+            };
+            let store = if path.to_str() == Some(":memory:") {
+                CognitiveStore::in_memory()?
+            } else {
+                CognitiveStore::new(path)?
+            };
+            Some(Arc::new(store))
+        } else {
+            None
+        };
+        let reranker = match config.reranker {
+            Some(ref settings) if settings.cache.is_enabled() => reranker.map(|inner| {
+                Arc::new(CachedReranker::new(inner, settings.cache.clone())) as Arc<dyn Reranker>
+            }),
+            _ => reranker,
+        };
+        let classification_cache = config
+            .classification_cache
+            .enabled
+            .then(|| Arc::new(ClassificationCache::new(config.classification_cache.clone())));
 
         Ok(Self {
             config,
@@ -81,6 +262,22 @@ impl Memory {
             prediction_error_gate,
             strength_processor,
             event_bus: None,
+            error_monitor: None,
+            blind_indexer,
+            encrypted_store,
+            sync,
+            versions,
+            classification_cache,
+            cognitive_store,
+            fsrs,
+            category_fsrs,
+            intention_store: None,
+            surfaced_memories: None,
+            archive_store: None,
+            encrypted_archive: None,
+            activation_graph: None,
+            version_embeddings: VersionEmbeddingCache::default(),
+            image_index: None,
         })
     }
 
@@ -96,6 +293,147 @@ impl Memory {
         self
     }
 
+    /// Track provider call outcomes and raise error-rate alerts.
+    ///
+    /// Outcomes are recorded per component (`llm`, `embedder`,
+    /// `vector_store`, `reranker`).
+    pub fn with_error_monitor(mut self, monitor: Arc<ErrorRateMonitor>) -> Self {
+        self.error_monitor = Some(monitor);
+        self
+    }
+
+    /// Use a classification cache shared with other instances, e.g. one per
+    /// server rather than one per tenant. Whether entries are reused across
+    /// instances is up to the cache's own configuration.
+    pub fn with_classification_cache(mut self, cache: Arc<ClassificationCache>) -> Self {
+        self.classification_cache = Some(cache);
+        self
+    }
+
+    /// Use a cognitive state store shared with other components, e.g. the
+    /// background runtime's. FSRS state is tracked in it when
+    /// `cognitive.enabled`; either way it is included when erasing or
+    /// exporting a user.
+    pub fn with_cognitive_store(mut self, store: Arc<CognitiveStore>) -> Self {
+        self.cognitive_store = Some(store);
+        self
+    }
+
+    /// Graph to spread activation over in the Standard, Precise and
+    /// Cognitive retrieval modes, such as the embedded graph store. Without
+    /// one, the links recorded by memory replay in the cognitive store are
+    /// used when `cognitive.enabled`.
+    pub fn with_activation_graph(mut self, graph: Arc<dyn ActivationGraph>) -> Self {
+        self.activation_graph = Some(graph);
+        self
+    }
+
+    /// Intention store to include when erasing or exporting a user, and
+    /// to keep reminders attached with [`Memory::remind`] in.
+    pub fn with_intention_store(mut self, store: Arc<dyn IntentionStore>) -> Self {
+        self.intention_store = Some(store);
+        self
+    }
+
+    /// Lead searches with the memories queued in `surfaced` by fired
+    /// `SurfaceMemory` intentions, such as reminders from
+    /// [`Memory::remind`]. Share the queue with the
+    /// [`ActionExecutor`](crate::intentions::ActionExecutor) running the
+    /// intentions' actions.
+    pub fn with_surfaced_memories(mut self, surfaced: Arc<SurfacedMemories>) -> Self {
+        self.surfaced_memories = Some(surfaced);
+        self
+    }
+
+    /// Index embeddings of image memories from `embedder` in `store`, a
+    /// collection separate from the memory store with the embedder's
+    /// dimension.
+    ///
+    /// Multimodal ingestion then embeds each image it stores (see
+    /// [`Memory::index_image`]), and [`Memory::search_images`] finds images by
+    /// what they show rather than by their extracted text.
+    pub fn with_image_index(
+        mut self,
+        embedder: Arc<dyn ImageEmbedder>,
+        store: Arc<dyn VectorStore>,
+    ) -> Self {
+        self.image_index = Some(ImageIndex { embedder, store });
+        self
+    }
+
+    /// Whether image memories are indexed by their image embeddings.
+    pub fn has_image_index(&self) -> bool {
+        self.image_index.is_some()
+    }
+
+    /// Keep archived memories in `store`, a collection separate from the
+    /// memory store (see [`MemoryConfig::archive_vector_store`]). Content is
+    /// encrypted as in the memory store when `content_encryption` is set.
+    pub fn with_archive_store(mut self, store: Arc<dyn VectorStore>) -> RookResult<Self> {
+        let store = match self.config.content_encryption {
+            Some(ref encryption) => {
+                let cipher = ContentCipher::new(encryption.clone())?;
+                let store = Arc::new(EncryptedVectorStore::new(store, cipher));
+                self.encrypted_archive = Some(store.clone());
+                store as Arc<dyn VectorStore>
+            }
+            None => store,
+        };
+        self.archive_store = Some(store);
+        Ok(self)
+    }
+
+    /// The classification cache, if one is enabled.
+    pub fn classification_cache(&self) -> Option<Arc<ClassificationCache>> {
+        self.classification_cache.clone()
+    }
+
+    /// The vector store backing this memory.
+    pub fn vector_store(&self) -> Arc<dyn VectorStore> {
+        self.vector_store.clone()
+    }
+
+    /// The embedder used for memory vectors.
+    pub fn embedder(&self) -> Arc<dyn Embedder> {
+        self.embedder.clone()
+    }
+
+    /// The graph store backing this memory, if one is configured.
+    pub fn graph_store(&self) -> Option<Arc<dyn GraphStore>> {
+        self.graph_store.clone()
+    }
+
+    /// Run a provider call under a sampled child span, recording its outcome.
+    async fn observe<T>(
+        &self,
+        operation: &'static str,
+        call: impl Future<Output = RookResult<T>>,
+    ) -> RookResult<T> {
+        let start = Instant::now();
+        let result = call.instrument(child_span(operation)).await;
+        metrics::record_provider_call(operation, result.is_ok(), start.elapsed());
+        if let Some(ref monitor) = self.error_monitor {
+            let component = operation.split('.').next().unwrap_or(operation);
+            monitor.record(component, result.is_ok());
+        }
+        result
+    }
+
+    /// Call the LLM through [`Self::observe`], recording token usage.
+    async fn generate(
+        &self,
+        messages: &[Message],
+        options: Option<GenerationOptions>,
+    ) -> RookResult<LlmResponse> {
+        let response = self
+            .observe("llm.generate", self.llm.generate(messages, options))
+            .await?;
+        if let Some(ref usage) = response.usage {
+            metrics::record_token_usage(self.llm.model_name(), usage);
+        }
+        Ok(response)
+    }
+
     /// Add memories from messages.
     pub async fn add(
         &self,
@@ -158,6 +496,40 @@ impl Memory {
     /// If `key_memory.include_in_search` is enabled in config, key memories
     /// (is_key=true) are always included at the top of results, before
     /// similarity-ranked results. Duplicate key memories are deduplicated.
+    ///
+    /// A `tags` entry in `filters` (a tag or a list of tags) restricts results
+    /// to memories carrying all of those tags. An `include_archived: true`
+    /// entry searches the archive too; archived results are moved back to
+    /// the memory store when `archive.unarchive_on_access` is set. A
+    /// `retrieval_mode` entry (`quick`, `standard`, `precise` or
+    /// `cognitive`) ranks the memory store's results with the retrieval
+    /// engine, combining similarity with spreading activation and FSRS
+    /// retrievability as the mode prescribes; scores are then the engine's
+    /// combined scores. A `mmr_lambda` entry (0-1) has the engine diversify
+    /// the results with maximal marginal relevance, so near-identical
+    /// memories don't crowd out the rest, in `quick` mode if no
+    /// `retrieval_mode` is set. A `query_expansion` entry (`paraphrase` or `hyde`)
+    /// has the LLM rewrite the query and searches with the query and each
+    /// rewrite, fusing the rankings by reciprocal rank; scores are then the
+    /// fused scores. If the LLM fails, the query is searched as
+    /// is.
+    ///
+    /// `since` and `until` entries (RFC 3339 timestamps) restrict results to
+    /// memories created in that range, `until` exclusive; stores that
+    /// compare timestamps apply the range in the search itself. A
+    /// `recency_half_life_days` entry multiplies each result's score by
+    /// `0.5^(age_days / half_life_days)` after reranking and decay ranking,
+    /// so newer memories rank higher.
+    ///
+    /// An `explain: true` entry returns a [`RetrievalExplanation`] with each
+    /// result: its vector similarity and retrieval engine signals, how much
+    /// reranking, decay ranking, the recency and graph boosts moved its
+    /// score, and whether it was injected as a key or pinned memory.
+    ///
+    /// Memories whose reminder fired (see [`Memory::remind`]) come right
+    /// after pinned memories, ahead of key memories, in as many of the
+    /// user's searches as the reminder asked for; their scores are
+    /// multiplied by the reminder's boost.
     pub async fn search(
         &self,
         query: &str,
@@ -177,18 +549,151 @@ impl Memory {
             effective_filters.extend(additional);
         }
 
+        // Tags are matched after the vector search, so fetch extra candidates
+        let tags = effective_filters
+            .remove(TAGS_FIELD)
+            .map(|value| parse_tag_filter(&value))
+            .transpose()?
+            .filter(|tags| !tags.is_empty());
+        // So is a time range, unless the store filters timestamps itself
+        let time_range = TimeRange::take_from(&mut effective_filters)?;
+        time_range.insert_into(&mut effective_filters);
+        let post_filter_time = !time_range.is_unbounded()
+            && !self.vector_store.supports_timestamp_filters();
+        let fetch_limit = match tags.is_some() || post_filter_time {
+            true => limit.saturating_mul(POST_FILTER_OVERFETCH),
+            false => limit,
+        };
+        let recency_half_life = effective_filters
+            .remove(RECENCY_HALF_LIFE_FILTER)
+            .map(|value| parse_recency_half_life(&value))
+            .transpose()?;
+        let explain = effective_filters
+            .remove(EXPLAIN_FILTER)
+            .is_some_and(|value| value.as_bool() == Some(true));
+        let include_archived = effective_filters
+            .remove(INCLUDE_ARCHIVED_FILTER)
+            .is_some_and(|value| value.as_bool() == Some(true));
+        let retrieval_mode = effective_filters
+            .remove(RETRIEVAL_MODE_FILTER)
+            .map(|value| parse_retrieval_mode(&value))
+            .transpose()?;
+        let mmr_lambda = effective_filters
+            .remove(MMR_LAMBDA_FILTER)
+            .map(|value| parse_mmr_lambda(&value))
+            .transpose()?;
+        let retrieval_mode = retrieval_mode.or(mmr_lambda.map(|_| RetrievalMode::Quick));
+        let query_expansion = effective_filters
+            .remove(QUERY_EXPANSION_FILTER)
+            .map(|value| parse_query_expansion(&value))
+            .transpose()?;
+        let queries = match query_expansion {
+            Some(expansion) => self.expand_query(query, expansion).await,
+            None => vec![query.to_string()],
+        };
+
         // Search vector store for similarity-ranked results
-        let mut memories = self
-            .search_vector_store(query, &effective_filters, limit, threshold)
-            .await?;
+        let mut memories = match retrieval_mode {
+            Some(mode) => {
+                let filters = &effective_filters;
+                self.retrieve(&queries, mode, mmr_lambda, filters, fetch_limit, threshold)
+                    .await?
+            }
+            None => {
+                let store = self.vector_store.as_ref();
+                self.search_queries(store, &queries, &effective_filters, fetch_limit, threshold)
+                    .await?
+            }
+        };
+
+        // Merge read-only global knowledge unless the search already targets it
+        let global = &self.config.global_knowledge;
+        if global.enabled && global.search_limit > 0 && !is_global(&effective_filters) {
+            let global_memories = self
+                .search_queries(
+                    self.vector_store.as_ref(),
+                    &queries,
+                    &global_filters(&effective_filters),
+                    global.search_limit,
+                    threshold,
+                )
+                .await?;
+            memories = merge_results(memories, global_memories, fetch_limit);
+        }
+
+        if let (true, Some(archive)) = (include_archived, &self.archive_store) {
+            let archived = self
+                .search_queries(archive.as_ref(), &queries, &effective_filters, fetch_limit, threshold)
+                .await?;
+            memories = merge_results(memories, archived, fetch_limit);
+        }
+
+        if let Some(ref tags) = tags {
+            memories.retain(|m| m.metadata.as_ref().is_some_and(|md| has_all_tags(md, tags)));
+        }
+        memories.truncate(limit);
+
+        if explain {
+            explain::begin(&mut memories, queries.len() > 1);
+        } else {
+            memories.iter_mut().for_each(|m| m.explanation = None);
+        }
 
         // Apply reranking if enabled
         if rerank {
             if let Some(ref reranker) = self.reranker {
-                memories = reranker.rerank(query, memories, Some(limit)).await?;
+                let before = explain::scores(&memories);
+                memories = self
+                    .observe("reranker.rerank", reranker.rerank(query, memories, Some(limit)))
+                    .await?;
+                explain::record_deltas(&mut memories, &before, |e| &mut e.rerank_delta);
             }
         }
 
+        // Stale memories rank lower when decay ranking is enabled
+        let before = explain::scores(&memories);
+        self.apply_decay_ranking(&mut memories);
+        explain::record_deltas(&mut memories, &before, |e| &mut e.decay_delta);
+
+        if let Some(half_life_days) = recency_half_life {
+            let before = explain::scores(&memories);
+            apply_recency_boost(&mut memories, half_life_days, chrono::Utc::now());
+            explain::record_deltas(&mut memories, &before, |e| &mut e.recency_delta);
+        }
+
+        // Search graph store (if enabled) and boost memories linked to query entities
+        let relations = match self.graph_store {
+            Some(ref graph) if self.config.graph_search.enabled => {
+                match self.search_graph(graph.as_ref(), query, &effective_filters).await {
+                    Ok(neighborhood) => {
+                        let before = explain::scores(&memories);
+                        apply_graph_boost(
+                            &mut memories,
+                            &neighborhood,
+                            self.config.graph_search.graph_boost,
+                        );
+                        explain::record_deltas(&mut memories, &before, |e| {
+                            &mut e.graph_boost_delta
+                        });
+                        Some(neighborhood.into_relations(self.config.graph_search.relation_limit))
+                    }
+                    Err(e) => {
+                        tracing::warn!("Graph search failed: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        // Key and pinned memories replace the search results they duplicate,
+        // so keep the search results' explanations
+        let explained: HashMap<String, RetrievalExplanation> = memories
+            .iter()
+            .filter_map(|m| Some((m.id.clone(), m.explanation.clone()?)))
+            .collect();
+        let mut key_ids = Vec::new();
+
         // Inject key memories at top if enabled
         if self.config.key_memory.include_in_search {
             let key_memories = self
@@ -196,17 +701,44 @@ impl Memory {
                 .await?;
 
             if !key_memories.is_empty() {
+                key_ids = key_memories.iter().map(|m| m.id.clone()).collect();
                 memories = Self::merge_with_key_memories(key_memories, memories);
             }
         }
 
-        // Search graph store (if enabled)
-        let relations = if let Some(ref _graph) = self.graph_store {
-            // TODO: Implement graph search
-            None
-        } else {
-            None
-        };
+        // Memories whose reminder fired lead the user's next searches
+        if let Some(ref surfaced) = self.surfaced_memories {
+            let reminded = self
+                .reminded_memories(surfaced, user_id.as_deref(), &memories)
+                .await?;
+            if !reminded.is_empty() {
+                memories = Self::merge_with_key_memories(reminded, memories);
+            }
+        }
+
+        // Memories pinned to the run come first, whatever their similarity
+        if let Some(ref run_id) = run_id {
+            let pinned = self.pinned_memories(run_id).await?;
+            if !pinned.is_empty() {
+                memories = Self::merge_with_key_memories(pinned, memories);
+            }
+        }
+
+        // Key and pinned memories must carry the requested tags and fall in
+        // the time range too
+        if let Some(ref tags) = tags {
+            memories.retain(|m| m.metadata.as_ref().is_some_and(|md| has_all_tags(md, tags)));
+        }
+        if !time_range.is_unbounded() {
+            memories.retain(|m| m.metadata.as_ref().is_some_and(|md| time_range.contains(md)));
+        }
+
+        if explain {
+            explain::finish(&mut memories, explained, &key_ids);
+        }
+
+        self.unarchive_accessed(&mut memories).await;
+        self.track_access(&mut memories);
 
         // Emit accessed events for each memory in results
         if let Some(ref event_bus) = self.event_bus {
@@ -228,46 +760,183 @@ impl Memory {
         })
     }
 
-    /// Get a specific memory by ID.
-    pub async fn get(&self, memory_id: &str) -> RookResult<Option<MemoryItem>> {
-        let record = self.vector_store.get(memory_id).await?;
-        let result = record.map(|r| self.record_to_memory_item(r, None));
+    /// Reflect on what is and isn't known about a topic.
+    ///
+    /// Retrieves the memories most relevant to `topic` within `scope` and asks
+    /// the LLM which information is missing, stale, ambiguous or conflicting.
+    /// Each gap comes with a clarifying question the agent can put to the user.
+    pub async fn knowledge_gaps(
+        &self,
+        topic: &str,
+        scope: SessionScope,
+    ) -> RookResult<KnowledgeGapReport> {
+        scope.validate()?;
 
-        // Emit accessed event if memory was found
-        if let (Some(ref event_bus), Some(ref memory)) = (&self.event_bus, &result) {
-            let event = MemoryAccessedEvent::new(&memory.id, AccessType::DirectGet);
-            // Extract user_id from memory metadata if available
-            let event = if let Some(user_id) = memory
-                .metadata
-                .as_ref()
-                .and_then(|m| m.get("user_id"))
-                .and_then(|v| v.as_str())
-            {
-                event.with_user(user_id)
-            } else {
-                event
-            };
-            event_bus.emit(MemoryLifecycleEvent::Accessed(event));
+        let known = self
+            .search_vector_store(topic, &scope.to_filters(), KNOWLEDGE_GAP_CONTEXT, None)
+            .await?;
+
+        let messages = vec![Message::user(knowledge_gaps_prompt(topic, &known))];
+        let options = GenerationOptions {
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat::Json),
+            ..Default::default()
+        };
+        let response = self
+            .generate(&messages, Some(options))
+            .await?;
+        let (summary, gaps) = parse_knowledge_gaps(response.content_or_empty(), &known);
+
+        Ok(KnowledgeGapReport {
+            topic: topic.to_string(),
+            summary,
+            known,
+            gaps,
+        })
+    }
+
+    /// Propose promoting a memory into the global knowledge base.
+    ///
+    /// The proposal waits for [`Memory::review_promotion`] unless
+    /// `global_knowledge.require_review` is off, in which case it is approved
+    /// on behalf of `requested_by` straight away.
+    pub async fn propose_promotion(
+        &self,
+        memory_id: &str,
+        requested_by: &str,
+        note: Option<String>,
+    ) -> RookResult<PromotionRecord> {
+        let source = self
+            .get(memory_id)
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+        let metadata = source.metadata.unwrap_or_default();
+        if is_global(&metadata) {
+            return Err(RookError::validation(format!(
+                "Memory '{}' is already global",
+                memory_id
+            )));
         }
 
-        Ok(result)
+        let scope_value = |key: &str| metadata.get(key).and_then(|v| v.as_str()).map(String::from);
+        let record = PromotionRecord {
+            id: Uuid::new_v4().to_string(),
+            memory_id: memory_id.to_string(),
+            content: source.memory,
+            contributor_user_id: scope_value("user_id"),
+            contributor_agent_id: scope_value("agent_id"),
+            requested_by: requested_by.to_string(),
+            requested_at: chrono::Utc::now().to_rfc3339(),
+            note,
+            status: PromotionStatus::Pending,
+            reviewed_by: None,
+            reviewed_at: None,
+            review_note: None,
+            global_memory_id: None,
+        };
+        self.history.read().await.save_promotion(&record)?;
+
+        if self.config.global_knowledge.require_review {
+            Ok(record)
+        } else {
+            self.review_promotion(&record.id, requested_by, true, None).await
+        }
     }
 
-    /// Get all memories for a scope.
-    pub async fn get_all(
+    /// Approve or reject a pending promotion.
+    ///
+    /// Approval copies the memory into the global scope with provenance
+    /// (original memory, contributor, reviewer). The original is left as is.
+    pub async fn review_promotion(
         &self,
-        user_id: Option<String>,
-        agent_id: Option<String>,
-        run_id: Option<String>,
-        limit: Option<usize>,
-    ) -> RookResult<Vec<MemoryItem>> {
-        let scope = SessionScope::new(user_id, agent_id, run_id);
-        scope.validate()?;
+        promotion_id: &str,
+        reviewer: &str,
+        approve: bool,
+        note: Option<String>,
+    ) -> RookResult<PromotionRecord> {
+        let mut record = self
+            .history
+            .read()
+            .await
+            .get_promotion(promotion_id)?
+            .ok_or_else(|| RookError::not_found(promotion_id))?;
+        if record.status != PromotionStatus::Pending {
+            return Err(RookError::validation(format!(
+                "Promotion '{}' is already {}",
+                promotion_id,
+                record.status.as_str()
+            )));
+        }
 
-        let filters = scope.to_filters();
-        let filter = self.build_filter(&filters);
+        let now = chrono::Utc::now().to_rfc3339();
+        record.reviewed_by = Some(reviewer.to_string());
+        record.reviewed_at = Some(now.clone());
+        record.review_note = note;
+
+        if approve {
+            let source = self
+                .observe("vector_store.get", self.vector_store.get(&record.memory_id))
+                .await?
+                .ok_or_else(|| RookError::not_found(&record.memory_id))?;
+            let data = source
+                .payload
+                .get("data")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&record.content)
+                .to_string();
+
+            let embedding = self
+                .observe("embedder.embed", self.embedder.embed(&data, Some(EmbeddingAction::Add)))
+                .await?;
+            let global_id = Uuid::new_v4().to_string();
+            let payload = global_payload(&source.payload, &record, &now);
+
+            let mut stored_payload = payload.clone();
+            self.index_payload(&mut stored_payload);
+            let record_to_insert = VectorRecord::new(global_id.clone(), embedding, stored_payload);
+            self.observe("vector_store.insert", self.vector_store.insert(vec![record_to_insert]))
+                .await?;
+
+            if let Some(ref event_bus) = self.event_bus {
+                let event = MemoryCreatedEvent::new(&global_id, &data).with_metadata(payload);
+                event_bus.emit(MemoryLifecycleEvent::Created(event));
+            }
+
+            self.history.read().await.add(
+                &global_id,
+                None,
+                Some(&data),
+                HistoryEvent::Add,
+                Some(&now),
+                None,
+                Some(reviewer),
+                None,
+            )?;
+
+            record.status = PromotionStatus::Approved;
+            record.global_memory_id = Some(global_id);
+        } else {
+            record.status = PromotionStatus::Rejected;
+        }
+
+        self.history.read().await.save_promotion(&record)?;
+        Ok(record)
+    }
+
+    /// List promotions, newest first, optionally by status.
+    pub async fn list_promotions(
+        &self,
+        status: Option<PromotionStatus>,
+    ) -> RookResult<Vec<PromotionRecord>> {
+        self.history.read().await.list_promotions(status)
+    }
 
-        let records = self.vector_store.list(filter, limit).await?;
+    /// Get memories in the global knowledge base.
+    pub async fn get_global(&self, limit: Option<usize>) -> RookResult<Vec<MemoryItem>> {
+        let filter = self.build_filter(&global_filters(&HashMap::new()))?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, limit))
+            .await?;
 
         Ok(records
             .into_iter()
@@ -275,47 +944,796 @@ impl Memory {
             .collect())
     }
 
-    /// Update a memory.
-    pub async fn update(&self, memory_id: &str, data: &str) -> RookResult<MemoryItem> {
-        // Get existing memory
-        let existing = self.vector_store.get(memory_id).await?.ok_or_else(|| {
-            RookError::not_found(memory_id)
-        })?;
+    fn sync_store(&self) -> RookResult<&SyncStore> {
+        self.sync
+            .as_ref()
+            .ok_or_else(|| RookError::validation("Sync is not enabled (set sync.enabled)"))
+    }
 
-        let prev_data = existing.get_data().map(|s| s.to_string());
+    /// Assign versions to local writes recorded in history since the last pass.
+    ///
+    /// Writes applied from peers are skipped; they were versioned when applied.
+    async fn capture_local_changes(&self, sync: &SyncStore) -> RookResult<()> {
+        let changes = self.history.read().await.changes_since(sync.history_cursor()?)?;
+        let Some(&(last_rowid, _)) = changes.last() else {
+            return Ok(());
+        };
 
-        // Generate new embedding
-        let embedding = self
-            .embedder
-            .embed(data, Some(EmbeddingAction::Update))
-            .await?;
+        for (_, record) in changes {
+            let from_peer = record
+                .actor_id
+                .as_deref()
+                .is_some_and(|actor| actor.starts_with(SYNC_ACTOR_PREFIX));
+            if from_peer {
+                continue;
+            }
+            // Deletions carry no timestamp of their own.
+            let updated_at = match (record.is_deleted, record.updated_at, record.created_at) {
+                (false, Some(at), _) | (false, None, Some(at)) => at,
+                _ => chrono::Utc::now().to_rfc3339(),
+            };
+            sync.record_local(&record.memory_id, record.is_deleted, &updated_at)?;
+        }
 
-        // Update payload
-        let mut payload = existing.payload.clone();
-        payload.insert("data".to_string(), serde_json::Value::String(data.to_string()));
-        payload.insert(
-            "hash".to_string(),
-            serde_json::Value::String(format!("{:x}", md5::compute(data.as_bytes()))),
-        );
-        let updated_at = chrono::Utc::now().to_rfc3339();
-        payload.insert(
-            "updated_at".to_string(),
-            serde_json::Value::String(updated_at.clone()),
-        );
+        sync.set_history_cursor(last_rowid)
+    }
 
-        // Update in vector store
-        self.vector_store
-            .update(memory_id, Some(embedding), Some(payload.clone()))
-            .await?;
+    /// This replica's node ID, change sequence and peer cursors.
+    pub async fn sync_status(&self) -> RookResult<SyncStatus> {
+        let sync = self.sync_store()?;
+        let _guard = sync.lock().await;
+        self.capture_local_changes(sync).await?;
 
-        // Record history
-        {
-            let history = self.history.read().await;
-            history.add(
-                memory_id,
-                prev_data.as_deref(),
-                Some(data),
-                HistoryEvent::Update,
+        Ok(SyncStatus {
+            node_id: sync.node_id().to_string(),
+            seq: sync.seq()?,
+            peers: sync.peers()?,
+        })
+    }
+
+    /// Memories changed on this replica after sequence number `since`.
+    ///
+    /// Each change carries the memory in export format; pass the returned
+    /// `next_seq` as `since` to fetch the next page.
+    pub async fn sync_changes(&self, since: u64, limit: usize) -> RookResult<Changeset> {
+        let sync = self.sync_store()?;
+        let _guard = sync.lock().await;
+        self.capture_local_changes(sync).await?;
+
+        let limit = limit.max(1);
+        let mut states = sync.states_since(since, limit + 1)?;
+        let has_more = states.len() > limit;
+        states.truncate(limit);
+
+        let mut changes = Vec::with_capacity(states.len());
+        for state in states {
+            let memory = if state.deleted {
+                None
+            } else {
+                self.observe("vector_store.get", self.vector_store.get(&state.memory_id))
+                    .await?
+                    .map(|record| ExportableMemory::from(self.record_to_memory_item(record, None)))
+            };
+            changes.push(Change {
+                memory_id: state.memory_id,
+                seq: state.seq,
+                version: state.version,
+                deleted: state.deleted || memory.is_none(),
+                memory,
+            });
+        }
+
+        Ok(Changeset {
+            node_id: sync.node_id().to_string(),
+            since,
+            next_seq: changes.last().map_or(since, |c| c.seq),
+            has_more,
+            changes,
+        })
+    }
+
+    /// Apply a changeset pulled from (or pushed by) a peer.
+    ///
+    /// Causally newer changes overwrite local memories. Concurrent changes are
+    /// resolved latest-wins; the losing version is kept as a supersede record.
+    pub async fn apply_changeset(&self, changeset: &Changeset) -> RookResult<SyncReport> {
+        self.apply_changeset_with(changeset, ConflictPolicy::Supersede).await
+    }
+
+    /// Apply a changeset, keeping the losers of concurrent changes as
+    /// `policy` says.
+    pub async fn apply_changeset_with(
+        &self,
+        changeset: &Changeset,
+        policy: ConflictPolicy,
+    ) -> RookResult<SyncReport> {
+        let sync = self.sync_store()?;
+        if changeset.node_id == sync.node_id() {
+            return Err(RookError::validation(
+                "Changeset was produced by this node",
+            ));
+        }
+        let _guard = sync.lock().await;
+        self.capture_local_changes(sync).await?;
+
+        let mut report = SyncReport::default();
+        for change in &changeset.changes {
+            let local = sync.get_state(&change.memory_id)?;
+            let resolution = resolve(local.as_ref().map(|s| &s.version), &change.version);
+
+            if matches!(
+                resolution,
+                Resolution::ApplySuperseding | Resolution::KeepSuperseding
+            ) {
+                if let Some(ref local) = local {
+                    if policy == ConflictPolicy::Supersede {
+                        let record = self
+                            .supersede_record(
+                                local,
+                                change,
+                                resolution == Resolution::KeepSuperseding,
+                            )
+                            .await?;
+                        sync.save_supersede(&record)?;
+                    }
+                    report.conflicts += 1;
+                }
+            }
+
+            let (mut version, deleted) = match (resolution, &local) {
+                (Resolution::Skip, _) => {
+                    report.skipped += 1;
+                    continue;
+                }
+                (Resolution::KeepSuperseding, Some(local)) => {
+                    report.skipped += 1;
+                    (local.version.clone(), local.deleted)
+                }
+                _ => {
+                    self.apply_remote_change(&changeset.node_id, change).await?;
+                    report.applied += 1;
+                    (change.version.clone(), change.deleted)
+                }
+            };
+            // The merged clock dominates both sides, so neither replica sees
+            // this conflict again.
+            if let Some(ref local) = local {
+                version.clock.merge(&local.version.clock);
+            }
+            version.clock.merge(&change.version.clock);
+            sync.put_state(&change.memory_id, &version, deleted)?;
+        }
+
+        sync.set_peer_cursor(&changeset.node_id, changeset.next_seq)?;
+        Ok(report)
+    }
+
+    /// Conflict resolution records, newest first.
+    pub async fn sync_conflicts(&self, limit: usize) -> RookResult<Vec<SupersedeRecord>> {
+        self.sync_store()?.supersedes(limit)
+    }
+
+    async fn supersede_record(
+        &self,
+        local: &SyncState,
+        change: &Change,
+        local_wins: bool,
+    ) -> RookResult<SupersedeRecord> {
+        let (winner, loser) = if local_wins {
+            (&local.version, &change.version)
+        } else {
+            (&change.version, &local.version)
+        };
+        let (loser_content, loser_deleted) = if local_wins {
+            (change.memory.as_ref().map(|m| m.memory.clone()), change.deleted)
+        } else if local.deleted {
+            (None, true)
+        } else {
+            let current = self
+                .observe("vector_store.get", self.vector_store.get(&local.memory_id))
+                .await?;
+            (current.and_then(|r| r.get_data().map(String::from)), false)
+        };
+
+        Ok(SupersedeRecord {
+            id: Uuid::new_v4().to_string(),
+            memory_id: local.memory_id.clone(),
+            winner_node: winner.writer.clone(),
+            winner_updated_at: winner.updated_at.clone(),
+            loser_node: loser.writer.clone(),
+            loser_updated_at: loser.updated_at.clone(),
+            loser_content,
+            loser_deleted,
+            resolved_at: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Write a peer's version of a memory into the local stores.
+    async fn apply_remote_change(&self, peer: &str, change: &Change) -> RookResult<()> {
+        let actor = format!("{}{}", SYNC_ACTOR_PREFIX, peer);
+        let existing = self
+            .observe("vector_store.get", self.vector_store.get(&change.memory_id))
+            .await?;
+        let prev_data = existing.as_ref().and_then(|r| r.get_data().map(String::from));
+
+        let memory = match change.memory {
+            Some(ref memory) if !change.deleted => memory,
+            _ => {
+                if existing.is_some() {
+                    self.observe("vector_store.delete", self.vector_store.delete(&change.memory_id))
+                        .await?;
+                    self.history.read().await.add(
+                        &change.memory_id,
+                        prev_data.as_deref(),
+                        None,
+                        HistoryEvent::Delete,
+                        None,
+                        None,
+                        Some(&actor),
+                        None,
+                    )?;
+                }
+                return Ok(());
+            }
+        };
+
+        let embedding = self
+            .observe(
+                "embedder.embed",
+                self.embedder.embed(&memory.memory, Some(EmbeddingAction::Add)),
+            )
+            .await?;
+
+        // Metadata is the peer's stored payload; the top-level fields win.
+        let mut payload = memory.metadata.clone().unwrap_or_default();
+        payload.insert("data".to_string(), memory.memory.clone().into());
+        let hash = memory
+            .hash
+            .clone()
+            .unwrap_or_else(|| format!("{:x}", md5::compute(memory.memory.as_bytes())));
+        payload.insert("hash".to_string(), hash.into());
+        if let Some(ref created_at) = memory.created_at {
+            payload.insert("created_at".to_string(), created_at.clone().into());
+        }
+        if let Some(ref updated_at) = memory.updated_at {
+            payload.insert("updated_at".to_string(), updated_at.clone().into());
+        }
+        // Peers may not blind-index, or may carry plaintext scope fields.
+        self.index_payload(&mut payload);
+
+        let event = if existing.is_some() {
+            self.observe(
+                "vector_store.update",
+                self.vector_store
+                    .update(&change.memory_id, Some(embedding), Some(payload)),
+            )
+            .await?;
+            HistoryEvent::Update
+        } else {
+            let record = VectorRecord::new(change.memory_id.clone(), embedding, payload);
+            self.observe("vector_store.insert", self.vector_store.insert(vec![record]))
+                .await?;
+            HistoryEvent::Add
+        };
+
+        self.history.read().await.add(
+            &change.memory_id,
+            prev_data.as_deref(),
+            Some(&memory.memory),
+            event,
+            memory.created_at.as_deref(),
+            memory.updated_at.as_deref(),
+            Some(&actor),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Get a specific memory by ID.
+    ///
+    /// Archived memories are found too, and moved back to the memory store
+    /// when `archive.unarchive_on_access` is set.
+    pub async fn get(&self, memory_id: &str) -> RookResult<Option<MemoryItem>> {
+        let mut record = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?;
+        if record.is_none() {
+            record = self.get_archived(memory_id).await?;
+        }
+        let mut result = record.map(|r| self.record_to_memory_item(r, None));
+        if let Some(ref mut memory) = result {
+            self.track_access(std::slice::from_mut(memory));
+        }
+
+        // Emit accessed event if memory was found
+        if let (Some(ref event_bus), Some(ref memory)) = (&self.event_bus, &result) {
+            let event = MemoryAccessedEvent::new(&memory.id, AccessType::DirectGet);
+            // Extract user_id from memory metadata if available
+            let event = if let Some(user_id) = memory
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("user_id"))
+                .and_then(|v| v.as_str())
+            {
+                event.with_user(user_id)
+            } else {
+                event
+            };
+            event_bus.emit(MemoryLifecycleEvent::Accessed(event));
+        }
+
+        Ok(result)
+    }
+
+    /// Get all memories for a scope.
+    pub async fn get_all(
+        &self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        self.get_all_ordered(user_id, agent_id, run_id, None, limit).await
+    }
+
+    /// Get all memories for a scope, ordered by a metadata field.
+    ///
+    /// `order_by` is `field` or `field:asc|desc`, where the field is
+    /// `created_at`, `updated_at`, `importance` or a field declared in
+    /// `metadata_schema`. The limit applies after ordering.
+    pub async fn get_all_ordered(
+        &self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        order_by: Option<&str>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let filters = scope.to_filters();
+        let filter = self.build_filter(&filters)?;
+
+        let records = match order_by {
+            Some(spec) => {
+                let order_by = self.config.metadata_schema.order_by(spec)?;
+                self.observe(
+                    "vector_store.list_ordered",
+                    self.vector_store.list_ordered(filter, &order_by, limit),
+                )
+                .await?
+            }
+            None => {
+                self.observe("vector_store.list", self.vector_store.list(filter, limit))
+                    .await?
+            }
+        };
+
+        Ok(records
+            .into_iter()
+            .map(|r| self.record_to_memory_item(r, None))
+            .collect())
+    }
+
+    /// Get one page of the memories in a scope, with the total count.
+    ///
+    /// Pages follow `(created_at, id)` order and can be walked with offsets
+    /// or with each page's `next_cursor`. With `order_by`, memories follow
+    /// that field instead and only offsets are supported.
+    pub async fn get_page(
+        &self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        order_by: Option<&str>,
+        page: &PageRequest,
+    ) -> RookResult<Page<MemoryItem>> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let filters = scope.to_filters();
+        let filter = self.build_filter(&filters)?;
+
+        let records = match order_by {
+            Some(_) if page.cursor.is_some() => {
+                return Err(RookError::validation(
+                    "Cursors follow creation order and cannot be combined with order_by",
+                ));
+            }
+            Some(spec) => {
+                let order_by = self.config.metadata_schema.order_by(spec)?;
+                let records = self
+                    .observe(
+                        "vector_store.list_ordered",
+                        self.vector_store.list_ordered(filter, &order_by, None),
+                    )
+                    .await?;
+                let total = records.len();
+                let end = match page.limit {
+                    Some(limit) => page.offset.saturating_add(limit).min(total),
+                    None => total,
+                };
+                Page {
+                    items: records.into_iter().take(end).skip(page.offset).collect(),
+                    total,
+                    next_cursor: None,
+                }
+            }
+            None => {
+                self.observe(
+                    "vector_store.list_page",
+                    self.vector_store.list_page(filter, page),
+                )
+                .await?
+            }
+        };
+
+        Ok(records.map(|r| self.record_to_memory_item(r, None)))
+    }
+
+    /// Get the memories in a scope assigned to a category.
+    pub async fn get_category(
+        &self,
+        category: &str,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let mut filters = scope.to_filters();
+        filters.insert("category".to_string(), category.into());
+        let filter = self.build_filter(&filters)?;
+
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, limit))
+            .await?;
+        Ok(records
+            .into_iter()
+            .map(|r| self.record_to_memory_item(r, None))
+            .collect())
+    }
+
+    /// Category names memories can be classified into, sorted.
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> =
+            self.config.category.valid_categories().into_iter().collect();
+        categories.sort();
+        categories
+    }
+
+    /// Add free-form tags to a memory.
+    ///
+    /// Tags are trimmed and lowercased; tags the memory already has are
+    /// ignored. Returns the updated memory.
+    pub async fn add_tags(&self, memory_id: &str, tags: &[String]) -> RookResult<MemoryItem> {
+        self.change_tags(memory_id, |current| {
+            for tag in tags.iter().filter_map(|t| normalize_tag(t)) {
+                if !current.contains(&tag) {
+                    current.push(tag);
+                }
+            }
+        })
+        .await
+    }
+
+    /// Remove tags from a memory. Returns the updated memory.
+    pub async fn remove_tags(&self, memory_id: &str, tags: &[String]) -> RookResult<MemoryItem> {
+        let tags: Vec<String> = tags.iter().filter_map(|t| normalize_tag(t)).collect();
+        self.change_tags(memory_id, |current| current.retain(|t| !tags.contains(t)))
+            .await
+    }
+
+    /// Set when a memory expires, or clear its expiry with `None`.
+    ///
+    /// Expired memories are soft-deleted by [`Memory::expire_memories`].
+    /// Returns the updated memory.
+    pub async fn set_expiry(
+        &self,
+        memory_id: &str,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> RookResult<MemoryItem> {
+        self.change_metadata(memory_id, |payload| {
+            let current = payload.get(EXPIRES_AT_FIELD).filter(|v| !v.is_null());
+            let value = expires_at.map(|t| serde_json::Value::String(t.to_rfc3339()));
+            if current == value.as_ref() {
+                return None;
+            }
+            // Payload updates are merged, so null clears the expiry.
+            payload.insert(
+                EXPIRES_AT_FIELD.to_string(),
+                value.clone().unwrap_or(serde_json::Value::Null),
+            );
+            Some(match expires_at {
+                Some(t) => format!("Expires at {}", t.to_rfc3339()),
+                None => "Expiry cleared".to_string(),
+            })
+        })
+        .await
+    }
+
+    /// Tags used by the memories in a scope, most used first.
+    pub async fn tags(
+        &self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> RookResult<Vec<TagCount>> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let filter = self.build_filter(&scope.to_filters())?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for record in &records {
+            for tag in tags_of(&record.payload) {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut counts: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(counts)
+    }
+
+    /// Relations in the knowledge graph related to a query.
+    ///
+    /// Walks outward from entities named in the query, as search does; if
+    /// the query names no known entity, the graph store's own search is used.
+    /// Requires a graph store.
+    pub async fn graph_search(
+        &self,
+        query: &str,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        limit: usize,
+    ) -> RookResult<Vec<GraphRelation>> {
+        let graph = self
+            .graph_store
+            .as_ref()
+            .ok_or_else(|| RookError::graph_store("No graph store is configured"))?;
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let filters = scope.to_filters();
+        let neighborhood = self.search_graph(graph.as_ref(), query, &filters).await?;
+        if !neighborhood.is_empty() {
+            return Ok(neighborhood.into_relations(limit));
+        }
+
+        let graph_filters = GraphFilters {
+            user_id: scope.user_id,
+            agent_id: scope.agent_id,
+            run_id: scope.run_id,
+        };
+        self.observe("graph_store.search", graph.search(query, &graph_filters, limit))
+            .await
+    }
+
+    /// Find existing memories in a scope that nearly duplicate `content`.
+    ///
+    /// Returns the most similar memory if its score is at least `threshold`.
+    /// Unlike [`search`](Self::search) this records no access, so it can be
+    /// used as a cheap check before adding.
+    pub async fn find_duplicates(
+        &self,
+        content: &str,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+        threshold: f32,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        self.search_vector_store(content, &scope.to_filters(), 1, Some(threshold))
+            .await
+    }
+
+    /// Import exported memories, keeping their IDs, payloads and timestamps.
+    ///
+    /// Content is re-embedded with the configured embedder. Memories whose ID
+    /// already exists are skipped. Returns the number imported, so this can
+    /// serve as the batch callback of [`import_jsonl`](crate::import::import_jsonl).
+    pub async fn import_batch(&self, batch: Vec<ImportableMemory>) -> RookResult<usize> {
+        self.import_batch_with(batch, ImportDedup::Id).await
+    }
+
+    /// Import exported memories like [`Memory::import_batch`], also
+    /// skipping duplicates of stored memories as chosen by `dedup`.
+    pub async fn import_batch_with(
+        &self,
+        batch: Vec<ImportableMemory>,
+        dedup: ImportDedup,
+    ) -> RookResult<usize> {
+        let mut seen = std::collections::HashSet::new();
+        let mut memories: Vec<ImportableMemory> = Vec::with_capacity(batch.len());
+        for memory in batch {
+            if !seen.insert(memory.id.clone()) {
+                continue;
+            }
+            let existing = self
+                .observe("vector_store.get", self.vector_store.get(&memory.id))
+                .await?;
+            if existing.is_some() {
+                continue;
+            }
+            let duplicate = match dedup {
+                ImportDedup::Hash => {
+                    let hash = import_hash(&memory);
+                    memories.iter().any(|m| {
+                        import_scope(m) == import_scope(&memory) && import_hash(m) == hash
+                    }) || self.scope_has_hash(&memory, &hash).await?
+                }
+                ImportDedup::SmartIngest => {
+                    let filter = self.build_filter(&import_scope(&memory))?;
+                    let existing = self
+                        .observe("vector_store.list", self.vector_store.list(filter, None))
+                        .await?;
+                    let gate = self
+                        .prediction_error_gate
+                        .evaluate(&memory.memory, &existing, self.embedder.as_ref())
+                        .await?;
+                    gate.decision == IngestDecision::Skip
+                }
+                ImportDedup::Id | ImportDedup::Embedding { .. } => false,
+            };
+            if !duplicate {
+                memories.push(memory);
+            }
+        }
+        if memories.is_empty() {
+            return Ok(0);
+        }
+
+        let texts: Vec<String> = memories.iter().map(|m| m.memory.clone()).collect();
+        let embeddings = self
+            .observe(
+                "embedder.embed_batch",
+                self.embedder.embed_batch(&texts, Some(EmbeddingAction::Add)),
+            )
+            .await?;
+
+        let (memories, embeddings) = match dedup {
+            ImportDedup::Embedding { threshold } => {
+                let mut kept: Vec<(ImportableMemory, Vec<f32>)> = Vec::new();
+                for (memory, embedding) in memories.into_iter().zip(embeddings) {
+                    let scope = import_scope(&memory);
+                    let in_batch = kept.iter().any(|(m, e)| {
+                        import_scope(m) == scope && cosine_similarity(e, &embedding) >= threshold
+                    });
+                    let stored = !in_batch && {
+                        let filter = self.build_filter(&scope)?;
+                        self.observe(
+                            "vector_store.search",
+                            self.vector_store.search(&embedding, 1, filter),
+                        )
+                        .await?
+                        .first()
+                        .is_some_and(|r| r.score >= threshold)
+                    };
+                    if !in_batch && !stored {
+                        kept.push((memory, embedding));
+                    }
+                }
+                if kept.is_empty() {
+                    return Ok(0);
+                }
+                kept.into_iter().unzip()
+            }
+            _ => (memories, embeddings),
+        };
+
+        let mut records = Vec::with_capacity(memories.len());
+        for (memory, embedding) in memories.iter().zip(embeddings) {
+            // Metadata is the exported payload; the top-level fields win.
+            let mut payload = memory.metadata.clone().unwrap_or_default();
+            payload.insert("data".to_string(), memory.memory.clone().into());
+            let hash = memory
+                .hash
+                .clone()
+                .unwrap_or_else(|| format!("{:x}", md5::compute(memory.memory.as_bytes())));
+            payload.insert("hash".to_string(), hash.into());
+            if let Some(ref created_at) = memory.created_at {
+                payload.insert("created_at".to_string(), created_at.clone().into());
+            }
+            if let Some(ref updated_at) = memory.updated_at {
+                payload.insert("updated_at".to_string(), updated_at.clone().into());
+            }
+            if let Some(ref category) = memory.category {
+                payload.insert("category".to_string(), category.clone().into());
+            }
+            if memory.is_key {
+                payload.insert("is_key".to_string(), true.into());
+            }
+            // Exports may come from an unindexed store or carry plaintext scopes.
+            self.index_payload(&mut payload);
+            records.push(VectorRecord::new(memory.id.clone(), embedding, payload));
+        }
+
+        self.observe("vector_store.insert", self.vector_store.insert(records))
+            .await?;
+
+        let history = self.history.read().await;
+        for memory in &memories {
+            history.add(
+                &memory.id,
+                None,
+                Some(&memory.memory),
+                HistoryEvent::Add,
+                memory.created_at.as_deref(),
+                memory.updated_at.as_deref(),
+                None,
+                None,
+            )?;
+        }
+
+        Ok(memories.len())
+    }
+
+    /// Whether a memory in the imported memory's scope has `hash`.
+    async fn scope_has_hash(&self, memory: &ImportableMemory, hash: &str) -> RookResult<bool> {
+        let mut filters = import_scope(memory);
+        filters.insert("hash".to_string(), hash.into());
+        let filter = self.build_filter(&filters)?;
+        let found = self
+            .observe("vector_store.list", self.vector_store.list(filter, Some(1)))
+            .await?;
+        Ok(!found.is_empty())
+    }
+
+    /// Update a memory.
+    pub async fn update(&self, memory_id: &str, data: &str) -> RookResult<MemoryItem> {
+        // Get existing memory
+        let existing = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+
+        let prev_data = existing.get_data().map(|s| s.to_string());
+
+        // Generate new embedding
+        let embedding = self
+            .observe("embedder.embed", self.embedder.embed(data, Some(EmbeddingAction::Update)))
+            .await?;
+
+        // Update payload
+        let mut payload = existing.payload.clone();
+        payload.insert("data".to_string(), serde_json::Value::String(data.to_string()));
+        payload.insert(
+            "hash".to_string(),
+            serde_json::Value::String(format!("{:x}", md5::compute(data.as_bytes()))),
+        );
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        payload.insert(
+            "updated_at".to_string(),
+            serde_json::Value::String(updated_at.clone()),
+        );
+
+        // Update in vector store
+        self.observe(
+            "vector_store.update",
+            self.vector_store.update(memory_id, Some(embedding), Some(payload.clone())),
+        )
+        .await?;
+
+        // Record history
+        {
+            let history = self.history.read().await;
+            history.add(
+                memory_id,
+                prev_data.as_deref(),
+                Some(data),
+                HistoryEvent::Update,
                 payload
                     .get("created_at")
                     .and_then(|v| v.as_str()),
@@ -325,144 +1743,1404 @@ impl Memory {
             )?;
         }
 
-        // Emit updated event
+        let version = self.record_version(
+            memory_id,
+            Some(&existing),
+            data,
+            &payload,
+            VersionEventType::ContentUpdated,
+            None,
+        )?;
+
+        // Emit updated event
+        if let Some(ref event_bus) = self.event_bus {
+            let old_content = prev_data.clone().unwrap_or_default();
+            let event = MemoryUpdatedEvent::new(
+                memory_id,
+                &old_content,
+                data,
+                UpdateType::Content,
+                version.unwrap_or(1),
+            );
+            let event = if let Some(user_id) = payload.get("user_id").and_then(|v| v.as_str()) {
+                event.with_user(user_id)
+            } else {
+                event
+            };
+            event_bus.emit(MemoryLifecycleEvent::Updated(event));
+        }
+
+        Ok(MemoryItem {
+            id: memory_id.to_string(),
+            memory: data.to_string(),
+            hash: Some(format!("{:x}", md5::compute(data.as_bytes()))),
+            score: None,
+            metadata: Some(payload.clone()),
+            created_at: existing
+                .payload
+                .get("created_at")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            updated_at: Some(updated_at),
+            category: payload
+                .get("category")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            is_key: payload
+                .get("is_key")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            pinned: false,
+            surfaced_by: None,
+            memory_state: None,
+            dual_strength: None,
+            retrievability: None,
+            explanation: None,
+        })
+    }
+
+    /// Merge overlapping memories into one consolidated memory.
+    ///
+    /// The LLM combines the memories, oldest first, into a new memory in
+    /// their shared scope, which lists them under `source_memory_ids`,
+    /// carries their tags and stays a key memory if any of them was one.
+    /// The originals are then superseded or deleted per `strategy`. All
+    /// memories must exist and share the same user, agent and run.
+    pub async fn merge(&self, ids: &[String], strategy: MergeStrategy) -> RookResult<MemoryItem> {
+        let mut seen = Vec::new();
+        for id in ids {
+            if !seen.contains(id) {
+                seen.push(id.clone());
+            }
+        }
+        if seen.len() < 2 {
+            return Err(RookError::validation("Merging needs at least two memories"));
+        }
+
+        let mut records = Vec::new();
+        for id in &seen {
+            let record = self
+                .observe("vector_store.get", self.vector_store.get(id))
+                .await?
+                .ok_or_else(|| RookError::not_found(id))?;
+            records.push(record);
+        }
+        let scope_of = |record: &VectorRecord| {
+            ["user_id", "agent_id", "run_id"].map(|field| record.payload.get(field).cloned())
+        };
+        let scope = scope_of(&records[0]);
+        if records.iter().any(|r| scope_of(r) != scope) {
+            return Err(RookError::validation(
+                "Only memories with the same user_id, agent_id and run_id can be merged",
+            ));
+        }
+        records.sort_by(|a, b| a.get_string("created_at").cmp(&b.get_string("created_at")));
+
+        let memories: Vec<MemoryItem> = records
+            .iter()
+            .map(|r| self.record_to_memory_item(r.clone(), None))
+            .collect();
+        let messages = vec![Message::user(merge_prompt(&memories))];
+        let options = GenerationOptions {
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat::Json),
+            ..Default::default()
+        };
+        let response = self.generate(&messages, Some(options)).await?;
+        let content = parse_merged_memory(response.content_or_empty())
+            .ok_or_else(|| RookError::llm("No merged memory generated"))?;
+
+        let source_ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+        let mut metadata = HashMap::new();
+        for (field, value) in ["user_id", "agent_id", "run_id"].iter().zip(scope) {
+            if let Some(value) = value {
+                metadata.insert(field.to_string(), value);
+            }
+        }
+        let mut tags: Vec<String> = Vec::new();
+        for record in &records {
+            for tag in tags_of(&record.payload) {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        if !tags.is_empty() {
+            metadata.insert(TAGS_FIELD.to_string(), serde_json::json!(tags));
+        }
+        metadata.insert(SUMMARY_SOURCES_FIELD.to_string(), serde_json::json!(source_ids));
+        let merged_id = self.create_memory(&content, &metadata).await?;
+
+        let mut merged = self
+            .observe("vector_store.get", self.vector_store.get(&merged_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(&merged_id))?;
+        self.record_version(
+            &merged_id,
+            None,
+            &content,
+            &merged.payload,
+            VersionEventType::Merged,
+            Some(format!("Merged from {}", source_ids.join(", "))),
+        )?;
+        let merged_is_key = merged.payload.get("is_key").is_some_and(|v| v == true);
+        if memories.iter().any(|m| m.is_key) && !merged_is_key {
+            let item = self
+                .change_metadata(&merged_id, |payload| {
+                    payload.insert("is_key".to_string(), true.into());
+                    Some("Kept key status of merged memories".to_string())
+                })
+                .await?;
+            merged.payload = item.metadata.unwrap_or_default();
+        }
+
+        for record in &records {
+            match strategy {
+                MergeStrategy::Supersede => {
+                    let mut payload = record.payload.clone();
+                    payload.insert(SUPERSEDED_BY_FIELD.to_string(), serde_json::json!(merged_id));
+                    self.record_version(
+                        &record.id,
+                        Some(record),
+                        record.get_data().unwrap_or_default(),
+                        &payload,
+                        VersionEventType::Superseded,
+                        Some(format!("Merged into {}", merged_id)),
+                    )?;
+                    self.remove(&record.id, Some(MERGED_REASON)).await?;
+                }
+                MergeStrategy::Delete => self.remove(&record.id, None).await?,
+            }
+        }
+        tracing::debug!(
+            merged_id = %merged_id,
+            sources = source_ids.len(),
+            ?strategy,
+            "Merged memories"
+        );
+
+        Ok(self.record_to_memory_item(merged, None))
+    }
+
+    /// Delete a memory.
+    ///
+    /// Deleting a document record (see [`Memory::get_document`]) deletes its
+    /// chunks too.
+    pub async fn delete(&self, memory_id: &str) -> RookResult<()> {
+        let record = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?;
+        self.remove(memory_id, None).await?;
+        if let Some(ref surfaced) = self.surfaced_memories {
+            surfaced.remove_memory(memory_id);
+        }
+
+        if record.is_some_and(|r| is_document(&r.payload)) {
+            for chunk in self.document_chunks(memory_id).await? {
+                self.remove(&chunk.id, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get a document record and its chunks, in document order.
+    ///
+    /// Multimodal ingestion stores a document record when it splits a
+    /// document into several memories. Returns `None` if `document_id` is
+    /// not a document record.
+    pub async fn get_document(&self, document_id: &str) -> RookResult<Option<Document>> {
+        let record = self
+            .observe("vector_store.get", self.vector_store.get(document_id))
+            .await?;
+        let Some(record) = record.filter(|r| is_document(&r.payload)) else {
+            return Ok(None);
+        };
+
+        let document = self.record_to_memory_item(record, None);
+        let chunks = self.document_chunks(document_id).await?;
+        Ok(Some(Document::new(document, chunks)))
+    }
+
+    /// The chunks linked to a document record.
+    async fn document_chunks(&self, document_id: &str) -> RookResult<Vec<MemoryItem>> {
+        let filters = HashMap::from([(
+            DOCUMENT_ID_FIELD.to_string(),
+            serde_json::Value::String(document_id.to_string()),
+        )]);
+        let filter = self.build_filter(&filters)?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+        Ok(records
+            .into_iter()
+            .map(|r| self.record_to_memory_item(r, None))
+            .collect())
+    }
+
+    /// Remove a memory from the vector store.
+    ///
+    /// With a `soft_reason`, the Deleted event is marked as a soft delete;
+    /// either way the memory's versions are kept for rollback.
+    async fn remove(&self, memory_id: &str, soft_reason: Option<&str>) -> RookResult<()> {
+        // Get existing memory for history
+        let mut existing = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?;
+        if let Some(ref archive) = self.archive_store {
+            if existing.is_none() {
+                existing = self.observe("vector_store.get", archive.get(memory_id)).await?;
+            }
+            self.observe("vector_store.delete", archive.delete(memory_id)).await?;
+        }
+        let prev_data = existing.as_ref().and_then(|r| r.get_data().map(|s| s.to_string()));
+
+        // Delete from vector store
+        self.observe("vector_store.delete", self.vector_store.delete(memory_id)).await?;
+        if let Some(ref index) = self.image_index {
+            self.observe("image_store.delete", index.store.delete(memory_id)).await?;
+        }
+        if let Some(store) = self.fsrs_store() {
+            store.delete_state(memory_id)?;
+        }
+
+        // Record history
+        {
+            let history = self.history.read().await;
+            history.delete_memory_pins(memory_id)?;
+            history.add(
+                memory_id,
+                prev_data.as_deref(),
+                None,
+                HistoryEvent::Delete,
+                None,
+                None,
+                existing
+                    .as_ref()
+                    .and_then(|r| r.payload.get("actor_id"))
+                    .and_then(|v| v.as_str()),
+                existing
+                    .as_ref()
+                    .and_then(|r| r.payload.get("role"))
+                    .and_then(|v| v.as_str()),
+            )?;
+        }
+
+        if let (Some(ref record), Some(ref data)) = (&existing, &prev_data) {
+            self.record_version(
+                memory_id,
+                Some(record),
+                data,
+                &record.payload,
+                VersionEventType::Deleted,
+                soft_reason.map(|reason| format!("Deleted ({})", reason)),
+            )?;
+        }
+
+        // Emit deleted event
+        if let Some(ref event_bus) = self.event_bus {
+            let event = match soft_reason {
+                Some(reason) => MemoryDeletedEvent::new(memory_id, true).with_reason(reason),
+                None => MemoryDeletedEvent::new(memory_id, false),
+            };
+            let event = if let Some(user_id) = existing
+                .as_ref()
+                .and_then(|r| r.payload.get("user_id"))
+                .and_then(|v| v.as_str())
+            {
+                event.with_user(user_id)
+            } else {
+                event
+            };
+            event_bus.emit(MemoryLifecycleEvent::Deleted(event));
+        }
+
+        Ok(())
+    }
+
+    /// Delete all memories for a scope.
+    pub async fn delete_all(
+        &self,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> RookResult<()> {
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        // Get all memories for this scope
+        let memories = self
+            .get_all(
+                scope.user_id.clone(),
+                scope.agent_id.clone(),
+                scope.run_id.clone(),
+                None,
+            )
+            .await?;
+
+        // Delete each one
+        for memory in memories {
+            self.delete(&memory.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Erase everything stored about a user.
+    ///
+    /// Deletes the user's memories in every agent and run scope, with their
+    /// graph entities, cognitive state, versions and history, and the user's
+    /// runs, promotion records and intentions, then searches each store
+    /// again for what is left. Memories deleted earlier are found through
+    /// their versions; without versioning, their history can't be traced
+    /// back to the user. The erasure itself is not recorded in history or
+    /// versions, and no events are emitted.
+    pub async fn erase_user(&self, user_id: &str) -> RookResult<ErasureReport> {
+        SessionScope::user(user_id).validate()?;
+        let (memories, memory_ids) = self.user_memories(user_id).await?;
+        let mut erased = UserDataCounts::default();
+
+        for memory in &memories {
+            self.observe("vector_store.delete", self.vector_store.delete(&memory.id))
+                .await?;
+            if let Some(ref archive) = self.archive_store {
+                self.observe("vector_store.delete", archive.delete(&memory.id)).await?;
+            }
+            if let Some(ref index) = self.image_index {
+                self.observe("image_store.delete", index.store.delete(&memory.id))
+                    .await?;
+            }
+        }
+        erased.memories = memories.len();
+
+        if let Some(ref graph) = self.graph_store {
+            let filters = GraphFilters {
+                user_id: Some(user_id.to_string()),
+                ..Default::default()
+            };
+            erased.entities = graph.get_all(&filters).await?.len();
+            graph.delete_all(&filters).await?;
+        }
+
+        if let Some(ref store) = self.cognitive_store {
+            for memory_id in &memory_ids {
+                erased.cognitive_states += usize::from(store.delete_state(memory_id)?);
+                erased.synaptic_tags += usize::from(store.delete_synaptic_tag(memory_id)?);
+            }
+        }
+
+        if let Some(ref versions) = self.versions {
+            for memory_id in &memory_ids {
+                erased.versions += versions.delete_versions(memory_id)?;
+            }
+        }
+
+        (erased.history, erased.runs, erased.promotions) = self
+            .history
+            .read()
+            .await
+            .erase_user(user_id, &memory_ids)?;
+
+        if let Some(ref store) = self.intention_store {
+            for intention in user_intentions(store.as_ref(), user_id, &memory_ids)? {
+                store.delete(intention.id)?;
+                erased.intentions += 1;
+            }
+        }
+
+        let remaining = self.count_user_data(user_id, memory_ids).await?;
+        let report = ErasureReport::new(user_id, erased, remaining, self.unconfigured_stores());
+        tracing::info!(
+            user_id,
+            erased = report.erased.total(),
+            remaining = report.remaining.total(),
+            "Erased user data"
+        );
+        Ok(report)
+    }
+
+    /// Write everything stored about a user as a JSON Lines bundle.
+    ///
+    /// The bundle carries the user's memories in every agent and run scope,
+    /// their cognitive state, the user's intentions and graph, and a
+    /// `history` section with the versions and history of the user's
+    /// memories (including deleted ones, see [`Self::erase_user`]) and the
+    /// user's runs and promotion records. Sections whose store is not
+    /// configured are left out of the header.
+    pub async fn export_user<W>(&self, user_id: &str, writer: W) -> RookResult<ExportStats>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        SessionScope::user(user_id).validate()?;
+        let (memories, memory_ids) = self.user_memories(user_id).await?;
+        let header = BundleHeader::new(Some(user_id.to_string()), None, None);
+        let mut bundle = ExportBundle::new(header, memories);
+
+        if let Some(ref store) = self.cognitive_store {
+            bundle.collect_cognitive(store)?;
+        }
+        if let Some(ref store) = self.intention_store {
+            bundle.intentions = user_intentions(store.as_ref(), user_id, &memory_ids)?;
+            bundle.add_section(ExportSection::Intentions);
+        }
+        if let Some(ref graph) = self.graph_store {
+            bundle.collect_graph(graph.as_ref()).await?;
+        }
+
+        if let Some(ref versions) = self.versions {
+            for memory_id in &memory_ids {
+                bundle.versions.extend(versions.get_all_versions(memory_id)?);
+            }
+        }
+        {
+            let history = self.history.read().await;
+            for memory_id in &memory_ids {
+                bundle.history.extend(history.get(memory_id)?);
+            }
+            bundle.runs = history.user_runs(user_id)?;
+            bundle.promotions = history.user_promotions(user_id)?;
+        }
+        bundle.add_section(ExportSection::History);
+
+        export_bundle_jsonl(bundle, writer).await
+    }
+
+    /// Tombstones for the memories of a scope deleted after `since`, oldest
+    /// first, for an incremental export (see [`crate::export::export_jsonl_since`]).
+    ///
+    /// Deletions are read from the version store, so versioning must be
+    /// enabled. A memory restored after its deletion has no tombstone.
+    pub fn tombstones_since(
+        &self,
+        user_id: Option<&str>,
+        agent_id: Option<&str>,
+        run_id: Option<&str>,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> RookResult<Vec<Tombstone>> {
+        let versions = self.versions.as_ref().ok_or_else(|| {
+            RookError::validation("Deletions are only tracked with versioning enabled")
+        })?;
+        let scope: Vec<(&str, Vec<String>)> = [
+            ("user_id", user_id),
+            ("agent_id", agent_id),
+            ("run_id", run_id),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some((field, self.stored_scope_values(field, value?))))
+        .collect();
+
+        Ok(versions
+            .deleted_since(since)?
+            .into_iter()
+            .filter(|version| {
+                scope.iter().all(|(field, values)| {
+                    version
+                        .metadata
+                        .get(*field)
+                        .and_then(|v| v.as_str())
+                        .is_some_and(|v| values.iter().any(|value| value == v))
+                })
+            })
+            .map(|version| Tombstone {
+                id: version.memory_id,
+                deleted_at: version.created_at,
+            })
+            .collect())
+    }
+
+    /// The stored memories of a user, archived ones included, and the IDs of
+    /// every memory the user has had, including deleted memories that still
+    /// have versions.
+    async fn user_memories(&self, user_id: &str) -> RookResult<(Vec<MemoryItem>, Vec<String>)> {
+        let mut memories = self
+            .get_all(Some(user_id.to_string()), None, None, None)
+            .await?;
+        if let Some(ref archive) = self.archive_store {
+            let filter = self.build_filter(&SessionScope::user(user_id).to_filters())?;
+            let archived = self
+                .observe("vector_store.list", archive.list(filter, None))
+                .await?;
+            memories.extend(archived.into_iter().map(|r| self.record_to_memory_item(r, None)));
+        }
+        let mut memory_ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+
+        if let Some(ref versions) = self.versions {
+            let values = self.stored_scope_values("user_id", user_id);
+            for memory_id in versions.find_memory_ids("user_id", &values)? {
+                if !memory_ids.contains(&memory_id) {
+                    memory_ids.push(memory_id);
+                }
+            }
+        }
+
+        Ok((memories, memory_ids))
+    }
+
+    /// Replace the blind-indexed fields of a payload about to be stored with
+    /// their tokens, when blind indexing is on.
+    fn index_payload(&self, payload: &mut HashMap<String, serde_json::Value>) {
+        if let Some(ref indexer) = self.blind_indexer {
+            indexer.index_payload(payload);
+        }
+    }
+
+    /// The values a scope field may be stored with: the plaintext value and,
+    /// with a blind index, its tokens. Versions keep the payload as stored.
+    fn stored_scope_values(&self, field: &str, value: &str) -> Vec<String> {
+        let mut values = vec![value.to_string()];
+        if let Some(ref indexer) = self.blind_indexer {
+            values.extend(
+                indexer
+                    .tokens(field, &value.into())
+                    .into_iter()
+                    .filter_map(|token| token.as_str().map(String::from)),
+            );
+        }
+        values
+    }
+
+    /// Count what the configured stores still hold about a user.
+    async fn count_user_data(
+        &self,
+        user_id: &str,
+        mut memory_ids: Vec<String>,
+    ) -> RookResult<UserDataCounts> {
+        let mut counts = UserDataCounts::default();
+        let (memories, found) = self.user_memories(user_id).await?;
+        counts.memories = memories.len();
+        for memory_id in found {
+            if !memory_ids.contains(&memory_id) {
+                memory_ids.push(memory_id);
+            }
+        }
+
+        if let Some(ref graph) = self.graph_store {
+            let filters = GraphFilters {
+                user_id: Some(user_id.to_string()),
+                ..Default::default()
+            };
+            counts.entities = graph.get_all(&filters).await?.len();
+        }
+
+        if let Some(ref store) = self.cognitive_store {
+            for memory_id in &memory_ids {
+                counts.cognitive_states += usize::from(store.get_state(memory_id)?.is_some());
+                counts.synaptic_tags += usize::from(store.get_synaptic_tag(memory_id)?.is_some());
+            }
+        }
+
+        if let Some(ref versions) = self.versions {
+            for memory_id in &memory_ids {
+                counts.versions += versions.get_all_versions(memory_id)?.len();
+            }
+        }
+
+        {
+            let history = self.history.read().await;
+            for memory_id in &memory_ids {
+                counts.history += history.get(memory_id)?.len();
+            }
+            counts.runs = history.user_runs(user_id)?.len();
+            counts.promotions = history.user_promotions(user_id)?.len();
+        }
+
+        if let Some(ref store) = self.intention_store {
+            counts.intentions = user_intentions(store.as_ref(), user_id, &memory_ids)?.len();
+        }
+
+        Ok(counts)
+    }
+
+    /// Optional stores holding user data that this instance lacks.
+    fn unconfigured_stores(&self) -> Vec<String> {
+        [
+            ("graph", self.graph_store.is_some()),
+            ("cognitive", self.cognitive_store.is_some()),
+            ("versions", self.versions.is_some()),
+            ("intentions", self.intention_store.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, configured)| !configured)
+        .map(|(store, _)| store.to_string())
+        .collect()
+    }
+
+    /// Get history for a memory.
+    pub async fn history(
+        &self,
+        memory_id: &str,
+    ) -> RookResult<Vec<super::history::HistoryRecord>> {
+        let history = self.history.read().await;
+        history.get(memory_id)
+    }
+
+    /// Whether memory versions are recorded (`versioning.enabled`).
+    pub fn versioning_enabled(&self) -> bool {
+        self.versions.is_some()
+    }
+
+    /// Versions of a memory, oldest first.
+    pub async fn versions(&self, memory_id: &str) -> RookResult<Vec<MemoryVersion>> {
+        self.version_store()?.get_all_versions(memory_id)
+    }
+
+    /// One version of a memory.
+    pub async fn version(
+        &self,
+        memory_id: &str,
+        version_number: u32,
+    ) -> RookResult<Option<MemoryVersion>> {
+        self.version_store()?.get_version(memory_id, version_number)
+    }
+
+    /// Search memories as they were at a point in time.
+    ///
+    /// Each memory of `scope` is searched with its content and metadata in
+    /// the latest version recorded at or before `as_of`. Memories added
+    /// later, or deleted or expired by then, are left out; memories deleted
+    /// since are included. Memories unchanged since before versioning was
+    /// enabled are searched as stored if they already existed. Contents
+    /// still stored reuse their embedding; older ones are embedded when
+    /// first searched. Archived memories are searched too. Results are
+    /// ranked by cosine similarity to `query`.
+    pub async fn search_as_of(
+        &self,
+        query: &str,
+        as_of: chrono::DateTime<chrono::Utc>,
+        scope: SessionScope,
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        scope.validate()?;
+        let versions = self.version_store()?;
+
+        let scope_values: Vec<(&str, Vec<String>)> = [
+            ("user_id", &scope.user_id),
+            ("agent_id", &scope.agent_id),
+            ("run_id", &scope.run_id),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some((field, self.stored_scope_values(field, value.as_ref()?))))
+        .collect();
+
+        // Memories stored now, then those only versions remember
+        let filter = self.build_filter(&scope.to_filters())?;
+        let mut stored = self
+            .observe("vector_store.list", self.vector_store.list(filter.clone(), None))
+            .await?;
+        if let Some(ref archive) = self.archive_store {
+            stored.extend(
+                self.observe("vector_store.list", archive.list(filter, None))
+                    .await?,
+            );
+        }
+        let mut memory_ids: Vec<String> = stored.iter().map(|r| r.id.clone()).collect();
+        let mut stored: HashMap<String, VectorRecord> =
+            stored.into_iter().map(|r| (r.id.clone(), r)).collect();
+        let (field, values) = &scope_values[0];
+        for memory_id in versions.find_memory_ids(field, values)? {
+            if !stored.contains_key(&memory_id) && !memory_ids.contains(&memory_id) {
+                memory_ids.push(memory_id);
+            }
+        }
+
+        let query_embedding = self
+            .observe("embedder.embed", self.embedder.embed(query, Some(EmbeddingAction::Search)))
+            .await?;
+        let mut results = Vec::new();
+        for memory_id in memory_ids {
+            let record = stored.remove(&memory_id);
+            let (content, mut payload, vector) = match versions.get_at_time(&memory_id, as_of)? {
+                Some(version) => {
+                    if !existed_at(&version, as_of) || !in_scope(&version.metadata, &scope_values) {
+                        continue;
+                    }
+                    let vector = record
+                        .filter(|r| r.get_data() == Some(version.content.as_str()))
+                        .map(|r| r.vector)
+                        .unwrap_or_default();
+                    (version.content, version.metadata, vector)
+                }
+                None => {
+                    // Unversioned memories are searched as stored if they
+                    // haven't changed since as_of
+                    let Some(record) = record else {
+                        continue;
+                    };
+                    let changed_at = record
+                        .get_string("updated_at")
+                        .or_else(|| record.get_string("created_at"))
+                        .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+                    if changed_at.filter(|t| *t <= as_of).is_none()
+                        || versions.get_latest(&memory_id)?.is_some()
+                        || is_expired(&record.payload, as_of)
+                    {
+                        continue;
+                    }
+                    let content = record.get_data().unwrap_or_default().to_string();
+                    (content, record.payload, record.vector)
+                }
+            };
+
+            let vector = match vector.is_empty() {
+                false => vector,
+                true => match self.version_embeddings.get(&content) {
+                    Some(vector) => vector,
+                    None => {
+                        let vector = self
+                            .observe(
+                                "embedder.embed",
+                                self.embedder.embed(&content, Some(EmbeddingAction::Search)),
+                            )
+                            .await?;
+                        self.version_embeddings.insert(&content, vector.clone());
+                        vector
+                    }
+                },
+            };
+            let score = cosine_similarity(&query_embedding, &vector);
+            if threshold.is_some_and(|t| score < t) {
+                continue;
+            }
+            payload.insert("data".to_string(), content.into());
+            let record = VectorRecord::new(memory_id, vec![], payload);
+            results.push(self.record_to_memory_item(record, Some(score)));
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Restore a memory to an earlier version, recreating it if it was deleted.
+    ///
+    /// The restored state is recorded as a new version, so a rollback can
+    /// itself be rolled back.
+    pub async fn rollback(&self, memory_id: &str, version_number: u32) -> RookResult<MemoryItem> {
+        let target = self
+            .version_store()?
+            .get_version(memory_id, version_number)?
+            .ok_or_else(|| {
+                RookError::not_found(format!("Version {} of memory {}", version_number, memory_id))
+            })?;
+
+        let existing = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?;
+        let prev_data = existing.as_ref().and_then(|r| r.get_data().map(String::from));
+
+        let embedding = self
+            .observe(
+                "embedder.embed",
+                self.embedder.embed(&target.content, Some(EmbeddingAction::Update)),
+            )
+            .await?;
+
+        // The snapshot holds the full payload at that version; an expiry that
+        // has since passed is cleared so the restored memory isn't swept again,
+        // and a memory reassigned since stays in its current scope
+        let mut payload = target.metadata.clone();
+        if is_expired(&payload, chrono::Utc::now()) {
+            payload.insert(EXPIRES_AT_FIELD.to_string(), serde_json::Value::Null);
+        }
+        if let Some(ref existing) = existing {
+            for field in ["user_id", "agent_id", "run_id"] {
+                if let Some(id) = existing.payload.get(field) {
+                    payload.insert(field.to_string(), id.clone());
+                }
+            }
+        }
+        payload.insert("data".to_string(), target.content.clone().into());
+        payload.insert(
+            "hash".to_string(),
+            format!("{:x}", md5::compute(target.content.as_bytes())).into(),
+        );
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        payload.insert("updated_at".to_string(), updated_at.clone().into());
+
+        let event = if existing.is_some() {
+            self.observe(
+                "vector_store.update",
+                self.vector_store
+                    .update(memory_id, Some(embedding), Some(payload.clone())),
+            )
+            .await?;
+            HistoryEvent::Update
+        } else {
+            let record = VectorRecord::new(memory_id, embedding, payload.clone());
+            self.observe("vector_store.insert", self.vector_store.insert(vec![record]))
+                .await?;
+            HistoryEvent::Add
+        };
+
+        self.history.read().await.add(
+            memory_id,
+            prev_data.as_deref(),
+            Some(&target.content),
+            event,
+            payload.get("created_at").and_then(|v| v.as_str()),
+            Some(&updated_at),
+            payload.get("actor_id").and_then(|v| v.as_str()),
+            payload.get("role").and_then(|v| v.as_str()),
+        )?;
+
+        self.record_version(
+            memory_id,
+            existing.as_ref(),
+            &target.content,
+            &payload,
+            VersionEventType::Restored,
+            Some(format!("Rolled back to version {}", version_number)),
+        )?;
+
+        Ok(self.record_to_memory_item(VectorRecord::new(memory_id, vec![], payload), None))
+    }
+
+    fn version_store(&self) -> RookResult<&Arc<dyn VersionStore>> {
+        self.versions.as_ref().ok_or_else(|| {
+            RookError::validation("Versioning is not enabled (set versioning.enabled)")
+        })
+    }
+
+    /// Snapshot a change in the version store, if versioning is enabled.
+    ///
+    /// Memories written before versioning was enabled first get their previous
+    /// state recorded as version 1. Returns the new version number.
+    fn record_version(
+        &self,
+        memory_id: &str,
+        previous: Option<&VectorRecord>,
+        content: &str,
+        metadata: &HashMap<String, serde_json::Value>,
+        event_type: VersionEventType,
+        description: Option<String>,
+    ) -> RookResult<Option<u32>> {
+        let Some(ref versions) = self.versions else {
+            return Ok(None);
+        };
+
+        if let (Some(previous), None) = (previous, versions.get_latest(memory_id)?) {
+            let mut initial =
+                MemoryVersion::initial(memory_id, previous.get_data().unwrap_or_default())
+                    .with_metadata(previous.payload.clone());
+            if let Some(created_at) = previous
+                .get_string("created_at")
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            {
+                initial.created_at = created_at.with_timezone(&chrono::Utc);
+            }
+            versions.add_version(&initial)?;
+        }
+
+        let mut version =
+            MemoryVersion::initial(memory_id, content).with_metadata(metadata.clone());
+        version.version_number = versions.get_next_version_number(memory_id)?;
+        version.event_type = event_type;
+        version.change_description = description;
+        version.changed_by = metadata.get("actor_id").and_then(|v| v.as_str()).map(String::from);
+        versions.add_version(&version)?;
+
+        if let Some(max_versions) = self.config.versioning.max_versions {
+            versions.prune_old_versions(memory_id, max_versions)?;
+        }
+        Ok(Some(version.version_number))
+    }
+
+    /// Apply a change to a memory's tags, recording it as a metadata update.
+    async fn change_tags(
+        &self,
+        memory_id: &str,
+        change: impl FnOnce(&mut Vec<String>),
+    ) -> RookResult<MemoryItem> {
+        self.change_metadata(memory_id, |payload| {
+            let before = tags_of(payload);
+            let mut tags = before.clone();
+            change(&mut tags);
+            if tags == before {
+                return None;
+            }
+            // Payload updates are merged, so an empty list clears the tags.
+            payload.insert(TAGS_FIELD.to_string(), serde_json::json!(tags));
+            Some(format!("Tags set to [{}]", tags.join(", ")))
+        })
+        .await
+    }
+
+    /// Apply a change to a memory's metadata, recording it as a metadata
+    /// update. `change` returns a description of the change, or `None` if
+    /// it left the metadata unchanged.
+    async fn change_metadata(
+        &self,
+        memory_id: &str,
+        change: impl FnOnce(&mut HashMap<String, serde_json::Value>) -> Option<String>,
+    ) -> RookResult<MemoryItem> {
+        let existing = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+
+        let mut payload = existing.payload.clone();
+        let Some(description) = change(&mut payload) else {
+            return Ok(self.record_to_memory_item(existing, None));
+        };
+        payload.insert(
+            "updated_at".to_string(),
+            serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
+        );
+        self.observe(
+            "vector_store.update",
+            self.vector_store.update(memory_id, None, Some(payload.clone())),
+        )
+        .await?;
+
+        let content = existing.get_data().unwrap_or_default().to_string();
+        let version = self.record_version(
+            memory_id,
+            Some(&existing),
+            &content,
+            &payload,
+            VersionEventType::MetadataUpdated,
+            Some(description),
+        )?;
+
         if let Some(ref event_bus) = self.event_bus {
-            let old_content = prev_data.clone().unwrap_or_default();
             let event = MemoryUpdatedEvent::new(
                 memory_id,
-                &old_content,
-                data,
-                UpdateType::Content,
-                1, // Version tracking not yet integrated
+                &content,
+                &content,
+                UpdateType::Metadata,
+                version.unwrap_or(1),
             );
-            let event = if let Some(user_id) = payload.get("user_id").and_then(|v| v.as_str()) {
-                event.with_user(user_id)
-            } else {
-                event
+            let event = match payload.get("user_id").and_then(|v| v.as_str()) {
+                Some(user_id) => event.with_user(user_id),
+                None => event,
             };
             event_bus.emit(MemoryLifecycleEvent::Updated(event));
         }
 
-        Ok(MemoryItem {
-            id: memory_id.to_string(),
-            memory: data.to_string(),
-            hash: Some(format!("{:x}", md5::compute(data.as_bytes()))),
-            score: None,
-            metadata: Some(payload.clone()),
-            created_at: existing
-                .payload
-                .get("created_at")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            updated_at: Some(updated_at),
-            category: payload
-                .get("category")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string()),
-            is_key: payload
-                .get("is_key")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false),
-            memory_state: None,
-            dual_strength: None,
-        })
+        Ok(self.record_to_memory_item(
+            VectorRecord {
+                payload,
+                ..existing
+            },
+            None,
+        ))
+    }
+
+    /// Reset all memories.
+    pub async fn reset(&self) -> RookResult<()> {
+        self.observe("vector_store.reset", self.vector_store.reset()).await?;
+        if let Some(ref archive) = self.archive_store {
+            self.observe("vector_store.reset", archive.reset()).await?;
+        }
+        if let Some(ref index) = self.image_index {
+            self.observe("image_store.reset", index.store.reset()).await?;
+        }
+
+        {
+            let history = self.history.read().await;
+            history.reset()?;
+        }
+
+        Ok(())
+    }
+
+    /// Calibrate relative score thresholds for the configured embedder.
+    ///
+    /// Samples up to `sample_size` stored vectors, compares nearest-neighbor
+    /// and random pair scores, and saves the resulting thresholds. Later
+    /// [`ThresholdSpec::Relative`] thresholds resolve against them.
+    pub async fn calibrate_thresholds(
+        &self,
+        sample_size: usize,
+    ) -> RookResult<ThresholdCalibration> {
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(None, Some(sample_size)))
+            .await?;
+        let vectors: Vec<Vec<f32>> = records
+            .into_iter()
+            .map(|r| r.vector)
+            .filter(|v| !v.is_empty())
+            .collect();
+
+        let (relevant, random) = sample_pair_scores(&vectors);
+        let (embedder, model) = self.embedder_key();
+        let calibration = ThresholdCalibration::estimate(embedder, model, &relevant, &random)?;
+
+        let history = self.history.read().await;
+        history.save_calibration(&calibration)?;
+        Ok(calibration)
+    }
+
+    /// Saved threshold calibration for the configured embedder, if any.
+    pub async fn threshold_calibration(&self) -> RookResult<Option<ThresholdCalibration>> {
+        let (embedder, model) = self.embedder_key();
+        let history = self.history.read().await;
+        history.get_calibration(&embedder, &model)
+    }
+
+    /// Resolve a threshold to an absolute score.
+    ///
+    /// Relative levels use the embedder's calibration, falling back to
+    /// [`RelativeThreshold::uncalibrated`](crate::retrieval::RelativeThreshold::uncalibrated)
+    /// until one has been run.
+    pub async fn resolve_threshold(&self, spec: ThresholdSpec) -> RookResult<f32> {
+        match spec {
+            ThresholdSpec::Absolute(score) => Ok(score),
+            ThresholdSpec::Relative(level) => Ok(match self.threshold_calibration().await? {
+                Some(calibration) => calibration.threshold(level),
+                None => level.uncalibrated(),
+            }),
+        }
+    }
+
+    /// Provider and model of the configured embedder, as calibration keys.
+    fn embedder_key(&self) -> (String, String) {
+        let provider = serde_json::to_value(self.config.embedder.provider)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        (provider, self.config.embedder.config.model.clone())
     }
 
-    /// Delete a memory.
-    pub async fn delete(&self, memory_id: &str) -> RookResult<()> {
-        // Get existing memory for history
-        let existing = self.vector_store.get(memory_id).await?;
-        let prev_data = existing.as_ref().and_then(|r| r.get_data().map(|s| s.to_string()));
+    /// Regenerate a derived store from every record in the vector store.
+    ///
+    /// Records are processed in ID order, `options.batch_size` at a time, and
+    /// progress is checkpointed after each batch (see
+    /// [`Memory::rebuild_progress`]). With `options.resume`, an unfinished
+    /// rebuild continues after its checkpoint instead of clearing the store
+    /// and starting over. Records that fail are logged, counted and skipped.
+    pub async fn rebuild(
+        &self,
+        rebuilder: &dyn Rebuilder,
+        options: &RebuildOptions,
+    ) -> RookResult<RebuildProgress> {
+        let target = rebuilder.target();
+        let checkpoint = if options.resume {
+            self.rebuild_progress(target).await?
+        } else {
+            None
+        };
+        let mut progress = match checkpoint {
+            Some(checkpoint) if checkpoint.is_resumable() => RebuildProgress {
+                status: RebuildStatus::Running,
+                error: None,
+                ..checkpoint
+            },
+            _ => {
+                rebuilder.clear().await?;
+                RebuildProgress::new(target)
+            }
+        };
+
+        let result = self.run_rebuild(rebuilder, options, &mut progress).await;
+        progress.updated_at = chrono::Utc::now();
+        match result {
+            Ok(()) => progress.status = RebuildStatus::Completed,
+            Err(ref e) => {
+                progress.status = RebuildStatus::Failed;
+                progress.error = Some(e.to_string());
+            }
+        }
 
-        // Delete from vector store
-        self.vector_store.delete(memory_id).await?;
+        let history = self.history.read().await;
+        history.save_rebuild_progress(&progress)?;
+        result.map(|()| progress)
+    }
 
-        // Record history
-        {
-            let history = self.history.read().await;
-            history.add(
-                memory_id,
-                prev_data.as_deref(),
-                None,
-                HistoryEvent::Delete,
-                None,
-                None,
-                existing
-                    .as_ref()
-                    .and_then(|r| r.payload.get("actor_id"))
-                    .and_then(|v| v.as_str()),
-                existing
-                    .as_ref()
-                    .and_then(|r| r.payload.get("role"))
-                    .and_then(|v| v.as_str()),
-            )?;
+    /// Re-extract the knowledge graph from every memory.
+    ///
+    /// Entities and relationships are added to the existing graph, which
+    /// merges them with what is already there. Each memory costs one LLM
+    /// extraction call.
+    pub async fn rebuild_graph(&self, options: &RebuildOptions) -> RookResult<RebuildProgress> {
+        if self.graph_store.is_none() {
+            return Err(RookError::Configuration("No graph store configured".to_string()));
         }
+        self.rebuild(&GraphRebuilder { memory: self }, options).await
+    }
 
-        // Emit deleted event
-        if let Some(ref event_bus) = self.event_bus {
-            let event = MemoryDeletedEvent::new(memory_id, false); // hard delete
-            let event = if let Some(user_id) = existing
-                .as_ref()
-                .and_then(|r| r.payload.get("user_id"))
-                .and_then(|v| v.as_str())
-            {
-                event.with_user(user_id)
-            } else {
-                event
-            };
-            event_bus.emit(MemoryLifecycleEvent::Deleted(event));
+    /// Last recorded progress of a rebuild.
+    pub async fn rebuild_progress(
+        &self,
+        target: RebuildTarget,
+    ) -> RookResult<Option<RebuildProgress>> {
+        let history = self.history.read().await;
+        history.get_rebuild_progress(target)
+    }
+
+    async fn run_rebuild(
+        &self,
+        rebuilder: &dyn Rebuilder,
+        options: &RebuildOptions,
+        progress: &mut RebuildProgress,
+    ) -> RookResult<()> {
+        let mut records = self
+            .observe("vector_store.list", self.vector_store.list(None, None))
+            .await?;
+        records.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let start = progress.resume_position(&records);
+        progress.total = records.len();
+        progress.processed = start;
+
+        for batch in records[start..].chunks(options.batch_size.max(1)) {
+            for record in batch {
+                match rebuilder.rebuild(record).await {
+                    Ok(true) => progress.rebuilt += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to rebuild {} for memory {}: {}",
+                            progress.target,
+                            record.id,
+                            e
+                        );
+                        progress.failed += 1;
+                    }
+                }
+                progress.processed += 1;
+            }
+
+            rebuilder.flush().await?;
+            progress.cursor = batch.last().map(|r| r.id.clone());
+            progress.updated_at = chrono::Utc::now();
+            let history = self.history.read().await;
+            history.save_rebuild_progress(progress)?;
         }
 
         Ok(())
     }
 
-    /// Delete all memories for a scope.
-    pub async fn delete_all(
+    /// Embed an image and index it under the ID of the memory holding its
+    /// extracted text.
+    ///
+    /// The memory's payload, except its content, is copied to the index so
+    /// image searches are scoped like memory searches. Returns `false` if no
+    /// image index is configured.
+    pub async fn index_image(
+        &self,
+        memory_id: &str,
+        image: &[u8],
+        mime_type: &str,
+    ) -> RookResult<bool> {
+        let Some(ref index) = self.image_index else {
+            return Ok(false);
+        };
+        let record = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+
+        let embedding = self
+            .observe(
+                "image_embedder.embed_image",
+                index.embedder.embed_image(image, mime_type),
+            )
+            .await?;
+        let mut payload = record.payload;
+        payload.remove("data");
+        self.observe(
+            "image_store.insert",
+            index
+                .store
+                .insert(vec![VectorRecord::new(memory_id, embedding, payload)]),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Find image memories by what the images show.
+    ///
+    /// The query is embedded with the image index's embedder and matched
+    /// against the embeddings of ingested images, not their OCR text or
+    /// description, so "the whiteboard photo about the roadmap" finds the
+    /// photo even if its text was never extracted. Results are scoped like
+    /// [`Memory::search`] and scored by image similarity.
+    pub async fn search_images(
         &self,
+        query: &str,
         user_id: Option<String>,
         agent_id: Option<String>,
         run_id: Option<String>,
-    ) -> RookResult<()> {
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let Some(ref index) = self.image_index else {
+            return Err(RookError::Configuration(
+                "No image index configured".to_string(),
+            ));
+        };
         let scope = SessionScope::new(user_id, agent_id, run_id);
         scope.validate()?;
 
-        // Get all memories for this scope
-        let memories = self
-            .get_all(
-                scope.user_id.clone(),
-                scope.agent_id.clone(),
-                scope.run_id.clone(),
-                None,
+        let embedding = self
+            .observe("image_embedder.embed_text", index.embedder.embed_text(query))
+            .await?;
+        let results = self
+            .search_embeddings(
+                index.store.as_ref(),
+                &[embedding],
+                &scope.to_filters(),
+                limit,
+                threshold,
             )
             .await?;
 
-        // Delete each one
-        for memory in memories {
-            self.delete(&memory.id).await?;
+        let mut memories = Vec::with_capacity(results.len());
+        for result in results {
+            let record = self
+                .observe("vector_store.get", self.vector_store.get(&result.id))
+                .await?;
+            if let Some(record) = record {
+                memories.push(self.record_to_memory_item(record, Some(result.score)));
+            }
         }
-
-        Ok(())
+        self.track_access(&mut memories);
+        Ok(memories)
     }
 
-    /// Get history for a memory.
-    pub async fn history(
+    /// Re-index blind index tokens in a scope under the active key.
+    ///
+    /// Run after adding a new key to `blind_index.keys`. Scope identifiers and
+    /// the configured categories are used as plaintext candidates, since tokens
+    /// cannot be reversed. Returns the number of records rewritten.
+    pub async fn rotate_blind_index(
         &self,
-        memory_id: &str,
-    ) -> RookResult<Vec<super::history::HistoryRecord>> {
-        let history = self.history.read().await;
-        history.get(memory_id)
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        run_id: Option<String>,
+    ) -> RookResult<usize> {
+        let Some(ref indexer) = self.blind_indexer else {
+            return Err(RookError::Configuration(
+                "Blind indexing is not enabled".to_string(),
+            ));
+        };
+
+        let scope = SessionScope::new(user_id, agent_id, run_id);
+        scope.validate()?;
+
+        let filters = scope.to_filters();
+        let mut candidates: HashMap<String, Vec<serde_json::Value>> = filters
+            .iter()
+            .map(|(k, v)| (k.clone(), vec![v.clone()]))
+            .collect();
+        candidates.insert(
+            "category".to_string(),
+            self.config
+                .category
+                .valid_categories()
+                .into_iter()
+                .map(serde_json::Value::String)
+                .collect(),
+        );
+
+        let filter = self.build_filter(&filters)?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+
+        let mut rotated = 0;
+        for mut record in records {
+            if indexer.rotate_payload(&mut record.payload, &candidates) {
+                self.observe(
+                    "vector_store.update",
+                    self.vector_store.update(&record.id, None, Some(record.payload)),
+                )
+                .await?;
+                rotated += 1;
+            }
+        }
+
+        Ok(rotated)
     }
 
-    /// Reset all memories.
-    pub async fn reset(&self) -> RookResult<()> {
-        self.vector_store.reset().await?;
+    /// Re-encrypt memory content under the active content encryption key.
+    ///
+    /// Run after adding a new key to `content_encryption.keys`; content
+    /// stored before encryption was enabled is encrypted too. Returns the
+    /// number of records rewritten.
+    ///
+    /// History, version and sync conflict records are not rewritten; keep a
+    /// retired key listed for as long as records written under it are kept.
+    pub async fn rotate_content_encryption(&self) -> RookResult<usize> {
+        let Some(ref store) = self.encrypted_store else {
+            return Err(RookError::Configuration(
+                "Content encryption is not enabled".to_string(),
+            ));
+        };
+        let mut rotated = self.observe("vector_store.rotate", store.rotate()).await?;
+        if let Some(ref archive) = self.encrypted_archive {
+            rotated += self.observe("vector_store.rotate", archive.rotate()).await?;
+        }
+        Ok(rotated)
+    }
 
-        {
-            let history = self.history.read().await;
-            history.reset()?;
+    /// Move the memories of one scope to another, for when a user identifier
+    /// changes or two identities are merged.
+    ///
+    /// A memory is moved when every ID set in `from` equals its own. IDs set
+    /// in `to` replace the memory's; the others are kept. Graph entities, run
+    /// records and promotion requests of the scope move along. Each moved
+    /// memory gets a metadata version. Memory IDs don't change, so FSRS
+    /// state and earlier versions stay with their memories.
+    ///
+    /// The graph store and history are each rewritten in one transaction
+    /// before the memories are. A graph store that can't reassign scopes
+    /// fails the call before anything changes. If a later step fails,
+    /// calling again with the same scopes finishes the move.
+    pub async fn reassign_scope(
+        &self,
+        from: SessionScope,
+        to: SessionScope,
+    ) -> RookResult<ScopeReassignment> {
+        from.validate()?;
+        to.validate()?;
+
+        let mut reassignment = ScopeReassignment::default();
+        if let Some(ref graph) = self.graph_store {
+            let graph_filters = |scope: &SessionScope| GraphFilters {
+                user_id: scope.user_id.clone(),
+                agent_id: scope.agent_id.clone(),
+                run_id: scope.run_id.clone(),
+            };
+            reassignment.entities = self
+                .observe(
+                    "graph_store.reassign_scope",
+                    graph.reassign_scope(&graph_filters(&from), &graph_filters(&to)),
+                )
+                .await?;
         }
 
-        Ok(())
+        (reassignment.runs, reassignment.promotions) =
+            self.history.read().await.reassign_scope(&from, &to)?;
+
+        let filter = self.build_filter(&from.to_filters())?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+        let scope = to.to_filters();
+        let mut fields: Vec<String> = scope
+            .iter()
+            .map(|(field, id)| format!("{}={}", field, id.as_str().unwrap_or_default()))
+            .collect();
+        fields.sort();
+        let description = format!("Reassigned to {}", fields.join(", "));
+        for record in records {
+            self.change_metadata(&record.id, |payload| {
+                payload.extend(scope.clone());
+                self.index_payload(payload);
+                Some(description.clone())
+            })
+            .await?;
+            reassignment.memory_ids.push(record.id);
+        }
+
+        Ok(reassignment)
     }
 
     /// Intelligently ingest new content using prediction error gating.
@@ -488,10 +3166,12 @@ impl Memory {
         scope.validate()?;
 
         let filters = scope.to_filters();
-        let filter = self.build_filter(&filters);
+        let filter = self.build_filter(&filters)?;
 
         // Get existing memories for comparison (scoped to user/agent)
-        let existing_memories = self.vector_store.list(filter, None).await?;
+        let existing_memories = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
 
         // Run prediction error gating
         let gate_result = self
@@ -558,7 +3238,10 @@ impl Memory {
                 let new_memory_id = self.create_memory(content, &metadata).await?;
 
                 // Mark old memory as superseded (update metadata)
-                if let Some(mut record) = self.vector_store.get(&superseded_id).await? {
+                if let Some(mut record) = self
+                    .observe("vector_store.get", self.vector_store.get(&superseded_id))
+                    .await?
+                {
                     record.payload.insert(
                         "superseded_by".to_string(),
                         serde_json::Value::String(new_memory_id.clone()),
@@ -567,9 +3250,11 @@ impl Memory {
                         "superseded_at".to_string(),
                         serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
                     );
-                    self.vector_store
-                        .update(&superseded_id, None, Some(record.payload))
-                        .await?;
+                    self.observe(
+                        "vector_store.update",
+                        self.vector_store.update(&superseded_id, None, Some(record.payload)),
+                    )
+                    .await?;
                 }
 
                 // Process contradiction strength signal
@@ -622,28 +3307,501 @@ impl Memory {
             )
             .await;
 
-        Ok(result)
+        Ok(result)
+    }
+
+    /// Get pending strength signal updates
+    ///
+    /// Returns the updates collected since last clear. Used by
+    /// external FSRS integration to apply grade updates.
+    pub fn get_pending_strength_updates(&self) -> HashMap<String, Grade> {
+        let processor = self.strength_processor.lock().unwrap();
+        processor.get_pending_updates()
+    }
+
+    /// Clear pending strength signal updates
+    pub fn clear_strength_updates(&self) {
+        let mut processor = self.strength_processor.lock().unwrap();
+        processor.clear();
+    }
+
+    /// Process a strength signal
+    pub fn process_strength_signal(&self, signal: StrengthSignal) {
+        let mut processor = self.strength_processor.lock().unwrap();
+        processor.process(signal);
+    }
+
+    /// Apply all pending strength signal grades as reviews.
+    ///
+    /// Drains the grades collected from strength signals and reviews each
+    /// memory with the most recent one, as an access would, updating its
+    /// FSRS state and dual strength. Grades of memories no longer in the
+    /// vector store are dropped; key marks stay pending. Does nothing unless
+    /// `cognitive.enabled` is set. Returns the number of memories reviewed.
+    pub async fn apply_strength_updates(&self) -> RookResult<usize> {
+        let Some(store) = self.fsrs_store() else {
+            return Ok(0);
+        };
+        let updates = self.strength_processor.lock().unwrap().take_all_pending();
+
+        let mut applied = 0;
+        for (memory_id, grade) in updates {
+            let Some(record) = self
+                .observe("vector_store.get", self.vector_store.get(&memory_id))
+                .await?
+            else {
+                continue;
+            };
+            let memory = self.record_to_memory_item(record, None);
+            let (mut state, is_key) = match store.get_state(&memory_id)? {
+                Some((state, is_key, _)) => (state, is_key),
+                None => (
+                    self.init_cognitive_state(
+                        store,
+                        &memory_id,
+                        memory.category.as_deref(),
+                        memory.is_key,
+                    )?,
+                    memory.is_key,
+                ),
+            };
+            let mut strength = store.get_dual_strength(&memory_id)?.unwrap_or_default();
+            self.record_review(store, &memory, &mut state, is_key, &mut strength, grade)?;
+            metrics::record_strength_update(grade);
+            applied += 1;
+        }
+        if applied > 0 {
+            tracing::debug!(applied, "Applied strength signal grades");
+        }
+        Ok(applied)
+    }
+
+    /// The store FSRS state is tracked in, when `cognitive.enabled`.
+    fn fsrs_store(&self) -> Option<&CognitiveStore> {
+        self.cognitive_store
+            .as_deref()
+            .filter(|_| self.config.cognitive.enabled)
+    }
+
+    /// The FSRS scheduler of a memory category: its override from
+    /// `cognitive.fsrs.categories`, if any, or the default one.
+    fn scheduler(&self, category: Option<&str>) -> &FsrsScheduler {
+        category
+            .and_then(|category| self.category_fsrs.get(category))
+            .unwrap_or(&self.fsrs)
+    }
+
+    /// Create the FSRS state of a new memory. Key memories start as if
+    /// recalled easily, others as recalled normally.
+    fn init_cognitive_state(
+        &self,
+        store: &CognitiveStore,
+        memory_id: &str,
+        category: Option<&str>,
+        is_key: bool,
+    ) -> RookResult<FsrsState> {
+        let grade = if is_key { Grade::Easy } else { Grade::Good };
+        let state = self.scheduler(category).initial_state(grade);
+        store.save_state(memory_id, &state, is_key, None)?;
+        let mut strength = DualStrength::new();
+        strength.update_storage(grade, 0);
+        strength.retrieval_strength = 1.0;
+        store.save_dual_strength(memory_id, &strength)?;
+        Ok(state)
+    }
+
+    /// Weight search scores by memory strength and re-sort, when
+    /// `cognitive.decay_ranking` is enabled.
+    ///
+    /// Reads the stored FSRS state without reviewing it. Memories without
+    /// state, and key memories unless `exempt_key` is off, keep their score.
+    fn apply_decay_ranking(&self, memories: &mut [MemoryItem]) {
+        let config = &self.config.cognitive.decay_ranking;
+        let Some(store) = self.fsrs_store().filter(|_| config.enabled) else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        for memory in memories.iter_mut() {
+            let Some(score) = memory.score else {
+                continue;
+            };
+            let strength = match self.memory_strength(store, memory, now) {
+                Ok(Some(strength)) => strength,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!(memory_id = %memory.id, "Failed to read memory strength: {}", e);
+                    continue;
+                }
+            };
+            memory.score = Some(config.apply(score, strength));
+        }
+        memories.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    /// Strength of a memory for decay ranking, or `None` when it has no
+    /// state or is exempt.
+    fn memory_strength(
+        &self,
+        store: &CognitiveStore,
+        memory: &MemoryItem,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> RookResult<Option<f32>> {
+        let config = &self.config.cognitive.decay_ranking;
+        let Some((state, is_key, _)) = store.get_state(&memory.id)? else {
+            return Ok(None);
+        };
+        if config.exempt_key && (is_key || memory.is_key) {
+            return Ok(None);
+        }
+        Ok(match config.source {
+            DecaySource::Retrievability => Some(
+                self.scheduler(memory.category.as_deref())
+                    .current_retrievability(&state, now),
+            ),
+            DecaySource::RetrievalStrength => store
+                .get_dual_strength(&memory.id)?
+                .map(|strength| strength.retrieval_strength),
+        })
+    }
+
+    /// Review the FSRS state of accessed memories and attach it, with the
+    /// current retrievability.
+    ///
+    /// A pending strength signal grade for a memory is applied as a review
+    /// and consumed; access alone doesn't change the state. Memories added
+    /// before tracking was enabled get an initial state. Failures are logged
+    /// rather than failing the read.
+    fn track_access(&self, memories: &mut [MemoryItem]) {
+        let Some(store) = self.fsrs_store() else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        for memory in memories.iter_mut() {
+            if let Err(e) = self.review_on_access(store, memory, now) {
+                tracing::warn!(memory_id = %memory.id, "Failed to track memory access: {}", e);
+            }
+        }
+    }
+
+    fn review_on_access(
+        &self,
+        store: &CognitiveStore,
+        memory: &mut MemoryItem,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> RookResult<()> {
+        let (mut state, is_key) = match store.get_state(&memory.id)? {
+            Some((state, is_key, _)) => (state, is_key),
+            None => (
+                self.init_cognitive_state(
+                    store,
+                    &memory.id,
+                    memory.category.as_deref(),
+                    memory.is_key,
+                )?,
+                memory.is_key,
+            ),
+        };
+        let mut strength = store.get_dual_strength(&memory.id)?.unwrap_or_default();
+
+        let grade = self.strength_processor.lock().unwrap().take_pending(&memory.id);
+        if let Some(grade) = grade {
+            self.record_review(store, memory, &mut state, is_key, &mut strength, grade)?;
+        }
+
+        memory.retrievability = Some(
+            self.scheduler(memory.category.as_deref())
+                .current_retrievability(&state, now),
+        );
+        memory.memory_state = Some(state);
+        memory.dual_strength = Some(strength);
+        Ok(())
+    }
+
+    /// Apply a review grade to a memory's FSRS state and dual strength and
+    /// save both.
+    fn record_review(
+        &self,
+        store: &CognitiveStore,
+        memory: &MemoryItem,
+        state: &mut FsrsState,
+        is_key: bool,
+        strength: &mut DualStrength,
+        grade: Grade,
+    ) -> RookResult<()> {
+        let now = chrono::Utc::now();
+        let scheduler = self.scheduler(memory.category.as_deref());
+        let retrievability = scheduler.current_retrievability(state, now);
+        *state = scheduler.process_review(state, grade, now);
+        strength.update_storage(grade, state.reps);
+        strength.update_retrieval(retrievability, grade);
+        store.save_state(&memory.id, state, is_key, None)?;
+        store.save_dual_strength(&memory.id, strength)?;
+        Ok(())
+    }
+
+    fn review_store(&self) -> RookResult<&CognitiveStore> {
+        self.fsrs_store().ok_or_else(|| {
+            RookError::validation("Cognitive tracking is not enabled (set cognitive.enabled)")
+        })
+    }
+
+    /// Memories in a scope whose retrievability has fallen below the
+    /// desired retention of their category (`cognitive.fsrs`), least
+    /// retrievable first.
+    ///
+    /// Each memory carries its FSRS state and current retrievability.
+    /// Memories without FSRS state, added before tracking was enabled,
+    /// aren't due until first accessed.
+    pub async fn due_for_review(
+        &self,
+        scope: SessionScope,
+        limit: usize,
+    ) -> RookResult<Vec<MemoryItem>> {
+        scope.validate()?;
+        let store = self.review_store()?;
+
+        let filter = self.build_filter(&scope.to_filters())?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+
+        let now = chrono::Utc::now();
+        let mut due = Vec::new();
+        for record in records {
+            let Some((state, _, _)) = store.get_state(&record.id)? else {
+                continue;
+            };
+            let mut memory = self.record_to_memory_item(record, None);
+            let scheduler = self.scheduler(memory.category.as_deref());
+            let retrievability = scheduler.current_retrievability(&state, now);
+            if retrievability >= scheduler.desired_retention() {
+                continue;
+            }
+            memory.dual_strength = store.get_dual_strength(&memory.id)?;
+            memory.memory_state = Some(state);
+            memory.retrievability = Some(retrievability);
+            due.push(memory);
+        }
+
+        due.sort_by(|a, b| {
+            a.retrievability
+                .partial_cmp(&b.retrievability)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        due.truncate(limit);
+        Ok(due)
     }
 
-    /// Get pending strength signal updates
+    /// Record how well a memory was recalled and reschedule it.
     ///
-    /// Returns the updates collected since last clear. Used by
-    /// external FSRS integration to apply grade updates.
-    pub fn get_pending_strength_updates(&self) -> HashMap<String, Grade> {
-        let processor = self.strength_processor.lock().unwrap();
-        processor.get_pending_updates()
+    /// Returns the memory with its updated FSRS state and retrievability.
+    pub async fn review(&self, memory_id: &str, grade: Grade) -> RookResult<MemoryItem> {
+        let store = self.review_store()?;
+        let record = self
+            .observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+        let mut memory = self.record_to_memory_item(record, None);
+
+        let (mut state, is_key) = match store.get_state(memory_id)? {
+            Some((state, is_key, _)) => (state, is_key),
+            None => (
+                self.init_cognitive_state(
+                    store,
+                    memory_id,
+                    memory.category.as_deref(),
+                    memory.is_key,
+                )?,
+                memory.is_key,
+            ),
+        };
+        let mut strength = store.get_dual_strength(memory_id)?.unwrap_or_default();
+        self.record_review(store, &memory, &mut state, is_key, &mut strength, grade)?;
+
+        let now = chrono::Utc::now();
+        memory.retrievability = Some(
+            self.scheduler(memory.category.as_deref())
+                .current_retrievability(&state, now),
+        );
+        memory.memory_state = Some(state);
+        memory.dual_strength = Some(strength);
+        Ok(memory)
     }
 
-    /// Clear pending strength signal updates
-    pub fn clear_strength_updates(&self) {
-        let mut processor = self.strength_processor.lock().unwrap();
-        processor.clear();
+    /// Move memories whose retrievability has decayed below
+    /// `archive.archival.archive_threshold` to the archive store.
+    ///
+    /// Key memories and memories younger than `archive.archival.min_age_days`
+    /// stay; at most `archive.archival.archive_limit` are moved per call.
+    /// FSRS state is kept, so an archived memory resumes its schedule when
+    /// accessed. Does nothing unless `archive.enabled` and
+    /// `cognitive.enabled` are set and an archive store is configured.
+    /// Returns the number of memories archived.
+    pub async fn archive_decayed(&self) -> RookResult<usize> {
+        let (Some(store), Some(archive)) = (self.fsrs_store(), &self.archive_store) else {
+            return Ok(0);
+        };
+        if !self.config.archive.enabled {
+            return Ok(0);
+        }
+
+        let config = &self.config.archive.archival;
+        let now = chrono::Utc::now();
+        let mut archived = 0;
+        for candidate in store.get_archival_candidates(config, now)? {
+            // Categories with their own FSRS parameters decay differently.
+            let category = if self.category_fsrs.is_empty() {
+                None
+            } else {
+                self.observe("vector_store.get", self.vector_store.get(&candidate.memory_id))
+                    .await?
+                    .and_then(|record| {
+                        record
+                            .payload
+                            .get("category")
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string())
+                    })
+            };
+            if !self.scheduler(category.as_deref()).is_archival_candidate(
+                &candidate.state,
+                candidate.created_at,
+                false,
+                config,
+                now,
+            ) {
+                continue;
+            }
+            let moved = self
+                .move_record(
+                    self.vector_store.as_ref(),
+                    archive.as_ref(),
+                    &candidate.memory_id,
+                    Some(now.to_rfc3339()),
+                )
+                .await?;
+            archived += usize::from(moved.is_some());
+        }
+        if archived > 0 {
+            tracing::info!(archived, "Archived decayed memories");
+        }
+        Ok(archived)
     }
 
-    /// Process a strength signal
-    pub fn process_strength_signal(&self, signal: StrengthSignal) {
-        let mut processor = self.strength_processor.lock().unwrap();
-        processor.process(signal);
+    /// Move a memory to the archive store. Returns false when the memory
+    /// store doesn't hold it.
+    pub async fn archive(&self, memory_id: &str) -> RookResult<bool> {
+        let archive = self.archive_store()?;
+        let archived_at = chrono::Utc::now().to_rfc3339();
+        Ok(self
+            .move_record(self.vector_store.as_ref(), archive, memory_id, Some(archived_at))
+            .await?
+            .is_some())
+    }
+
+    /// Move an archived memory back to the memory store. Returns false when
+    /// the archive doesn't hold it.
+    pub async fn unarchive(&self, memory_id: &str) -> RookResult<bool> {
+        let archive = self.archive_store()?;
+        Ok(self
+            .move_record(archive, self.vector_store.as_ref(), memory_id, None)
+            .await?
+            .is_some())
+    }
+
+    fn archive_store(&self) -> RookResult<&dyn VectorStore> {
+        self.archive_store
+            .as_deref()
+            .ok_or_else(|| RookError::validation("No archive store is configured (set archive.enabled)"))
+    }
+
+    /// An archived memory, moved back to the memory store when
+    /// `archive.unarchive_on_access` is set.
+    async fn get_archived(&self, memory_id: &str) -> RookResult<Option<VectorRecord>> {
+        let Some(ref archive) = self.archive_store else {
+            return Ok(None);
+        };
+        if self.config.archive.unarchive_on_access {
+            self.move_record(archive.as_ref(), self.vector_store.as_ref(), memory_id, None)
+                .await
+        } else {
+            self.observe("vector_store.get", archive.get(memory_id)).await
+        }
+    }
+
+    /// Move archived search results back to the memory store, when
+    /// `archive.unarchive_on_access` is set. Failures are logged and leave
+    /// the memory archived.
+    async fn unarchive_accessed(&self, memories: &mut [MemoryItem]) {
+        let Some(ref archive) = self.archive_store else {
+            return;
+        };
+        if !self.config.archive.unarchive_on_access {
+            return;
+        }
+        for memory in memories.iter_mut() {
+            let archived = memory
+                .metadata
+                .as_ref()
+                .is_some_and(|m| m.contains_key(ARCHIVED_AT_FIELD));
+            if !archived {
+                continue;
+            }
+            match self
+                .move_record(archive.as_ref(), self.vector_store.as_ref(), &memory.id, None)
+                .await
+            {
+                Ok(_) => {
+                    if let Some(ref mut metadata) = memory.metadata {
+                        metadata.remove(ARCHIVED_AT_FIELD);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(memory_id = %memory.id, "Failed to unarchive memory: {}", e)
+                }
+            }
+        }
+    }
+
+    /// Move a record between the memory and archive stores, setting or
+    /// clearing its `archived_at` field. Returns the record as moved, or
+    /// `None` when `from` doesn't hold it.
+    async fn move_record(
+        &self,
+        from: &dyn VectorStore,
+        to: &dyn VectorStore,
+        memory_id: &str,
+        archived_at: Option<String>,
+    ) -> RookResult<Option<VectorRecord>> {
+        let Some(mut record) = self.observe("vector_store.get", from.get(memory_id)).await? else {
+            return Ok(None);
+        };
+        // Not every store returns vectors with records
+        if record.vector.is_empty() {
+            let data = record.get_data().unwrap_or_default().to_string();
+            record.vector = self
+                .observe("embedder.embed", self.embedder.embed(&data, Some(EmbeddingAction::Add)))
+                .await?;
+        }
+        match archived_at {
+            Some(at) => {
+                record.payload.insert(ARCHIVED_AT_FIELD.to_string(), at.into());
+            }
+            None => {
+                record.payload.remove(ARCHIVED_AT_FIELD);
+            }
+        }
+        record.score = None;
+
+        self.observe("vector_store.insert", to.insert(vec![record.clone()]))
+            .await?;
+        self.observe("vector_store.delete", from.delete(memory_id)).await?;
+        Ok(Some(record))
     }
 
     /// Get key memories for a scope.
@@ -664,10 +3822,12 @@ impl Memory {
         // Add is_key=true filter
         filters.insert("is_key".to_string(), serde_json::Value::Bool(true));
 
-        let filter = self.build_filter(&filters);
+        let filter = self.build_filter(&filters)?;
         let limit = Some(self.config.key_memory.max_key_memories);
 
-        let records = self.vector_store.list(filter, limit).await?;
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, limit))
+            .await?;
 
         Ok(records
             .into_iter()
@@ -703,6 +3863,607 @@ impl Memory {
         merged
     }
 
+    /// Pin a memory to a run.
+    ///
+    /// Every search in the run returns the memory ahead of similarity
+    /// results, until it is unpinned, `expires_at` passes or the run's pins
+    /// are cleared with [`clear_run_pins`](Self::clear_run_pins). Pinning an
+    /// already pinned memory updates its expiry.
+    pub async fn pin_to_run(
+        &self,
+        memory_id: &str,
+        run_id: &str,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> RookResult<RunPin> {
+        if run_id.is_empty() {
+            return Err(RookError::validation("run_id is required to pin a memory"));
+        }
+        let now = chrono::Utc::now();
+        if expires_at.is_some_and(|at| at <= now) {
+            return Err(RookError::validation("Pin expiry must be in the future"));
+        }
+        self.observe("vector_store.get", self.vector_store.get(memory_id))
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+
+        let pin = RunPin {
+            run_id: run_id.to_string(),
+            memory_id: memory_id.to_string(),
+            pinned_at: now,
+            expires_at,
+        };
+        self.history.read().await.save_run_pin(&pin)?;
+        Ok(pin)
+    }
+
+    /// Unpin a memory from a run. Returns whether it was pinned.
+    pub async fn unpin_from_run(&self, memory_id: &str, run_id: &str) -> RookResult<bool> {
+        self.history.read().await.delete_run_pin(run_id, memory_id)
+    }
+
+    /// Unexpired pins of a run, oldest first.
+    pub async fn run_pins(&self, run_id: &str) -> RookResult<Vec<RunPin>> {
+        self.history.read().await.list_run_pins(run_id, chrono::Utc::now())
+    }
+
+    /// Remove every pin of a run, e.g. when the run ends. Returns the number
+    /// removed.
+    pub async fn clear_run_pins(&self, run_id: &str) -> RookResult<usize> {
+        self.history.read().await.clear_run_pins(run_id)
+    }
+
+    /// Record the start of a run.
+    ///
+    /// `user_id` is the user salient memories are promoted to when the run
+    /// ends.
+    pub async fn start_run(
+        &self,
+        run_id: &str,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> RookResult<RunRecord> {
+        if run_id.is_empty() {
+            return Err(RookError::validation("run_id is required to start a run"));
+        }
+        let history = self.history.read().await;
+        if history.get_run(run_id)?.is_some() {
+            return Err(RookError::validation(format!(
+                "Run '{}' has already been started",
+                run_id
+            )));
+        }
+
+        let mut run = RunRecord::new(run_id);
+        run.user_id = user_id;
+        run.agent_id = agent_id;
+        run.metadata = metadata;
+        history.save_run(&run)?;
+        Ok(run)
+    }
+
+    /// Get a run's lifecycle record.
+    pub async fn get_run(&self, run_id: &str) -> RookResult<Option<RunRecord>> {
+        self.history.read().await.get_run(run_id)
+    }
+
+    /// End a run.
+    ///
+    /// Unless disabled in `runs` or `options`, the run's memories are
+    /// summarized and the salient ones (picked by the summarizer, key
+    /// memories and memories pinned to the run) are copied into the scope of
+    /// the run's user, along with the summary. The run's own memories and
+    /// pins are deleted once the retention period passes, by
+    /// [`expire_runs`](Self::expire_runs), or right away when it is zero.
+    pub async fn end_run(&self, run_id: &str, options: RunEndOptions) -> RookResult<RunRecord> {
+        let mut run = self
+            .get_run(run_id)
+            .await?
+            .ok_or_else(|| RookError::not_found(format!("Run '{}'", run_id)))?;
+        if !run.is_active() {
+            return Err(RookError::validation(format!("Run '{}' has already ended", run_id)));
+        }
+
+        let config = &self.config.runs;
+        let summarize = options.summarize.unwrap_or(config.summarize);
+        let promote = options.promote.unwrap_or(config.promote);
+        let retention_secs = options.retention_secs.unwrap_or(config.retention_secs);
+
+        // Most recent memories, oldest first
+        let mut memories = self
+            .get_all_ordered(
+                None,
+                None,
+                Some(run_id.to_string()),
+                Some("created_at:desc"),
+                Some(config.max_summary_memories),
+            )
+            .await?;
+        memories.reverse();
+
+        let mut salient: Vec<String> = Vec::new();
+        if summarize && !memories.is_empty() {
+            let messages = vec![Message::user(run_summary_prompt(&memories))];
+            let options = GenerationOptions {
+                temperature: Some(0.0),
+                response_format: Some(ResponseFormat::Json),
+                ..Default::default()
+            };
+            let response = self.generate(&messages, Some(options)).await?;
+            let (summary, picked) = parse_run_summary(response.content_or_empty(), &memories);
+            run.summary = Some(summary).filter(|s| !s.is_empty());
+            salient = picked;
+        }
+
+        // Promoted memories and the summary go to the run's user, without
+        // the run ID, so they outlive the run
+        let mut scope = HashMap::new();
+        if let Some(ref user_id) = run.user_id {
+            scope.insert("user_id".to_string(), serde_json::json!(user_id));
+        }
+        if let Some(ref agent_id) = run.agent_id {
+            scope.insert("agent_id".to_string(), serde_json::json!(agent_id));
+        }
+        scope.insert(RUN_SOURCE_FIELD.to_string(), serde_json::json!(run_id));
+
+        if promote && run.user_id.is_some() {
+            let pinned: Vec<String> = self
+                .run_pins(run_id)
+                .await?
+                .into_iter()
+                .map(|pin| pin.memory_id)
+                .collect();
+            for memory in &memories {
+                let keep =
+                    memory.is_key || pinned.contains(&memory.id) || salient.contains(&memory.id);
+                if !keep {
+                    continue;
+                }
+                let mut metadata = scope.clone();
+                metadata.insert("source_memory_id".to_string(), serde_json::json!(memory.id));
+                let id = self.create_memory(&memory.memory, &metadata).await?;
+                run.promoted_memory_ids.push(id);
+            }
+        }
+
+        let scoped = run.user_id.is_some() || run.agent_id.is_some();
+        if let (Some(ref summary), true) = (&run.summary, scoped) {
+            run.summary_memory_id = Some(self.create_memory(summary, &scope).await?);
+        }
+
+        let now = chrono::Utc::now();
+        run.ended_at = Some(now);
+        run.expires_at = Some(now + chrono::Duration::seconds(retention_secs as i64));
+        self.history.read().await.save_run(&run)?;
+
+        if retention_secs == 0 {
+            self.expire_run(&mut run).await?;
+        }
+        Ok(run)
+    }
+
+    /// Summarize a session's memories into one episodic memory.
+    ///
+    /// All memories of the run are summarized by the LLM, and the summary is
+    /// stored with `memory_type` `episodic_memory` and the IDs of its source
+    /// memories. It goes to the scope of the run's user or agent (from the
+    /// run record, or the memories themselves), or stays in the run when it
+    /// has neither. With `archive`, the source memories are then
+    /// soft-deleted; their versions are kept. Earlier summaries of the run
+    /// are not summarized again.
+    pub async fn summarize_session(&self, run_id: &str, archive: bool) -> RookResult<SessionSummary> {
+        let mut memories = self
+            .get_all_ordered(None, None, Some(run_id.to_string()), Some("created_at:asc"), None)
+            .await?;
+        memories.retain(|m| {
+            !m.metadata
+                .as_ref()
+                .is_some_and(|metadata| metadata.contains_key(SUMMARY_SOURCES_FIELD))
+        });
+        if memories.is_empty() {
+            return Err(RookError::validation(format!(
+                "Run '{}' has no memories to summarize",
+                run_id
+            )));
+        }
+
+        let messages = vec![Message::user(run_summary_prompt(&memories))];
+        let options = GenerationOptions {
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat::Json),
+            ..Default::default()
+        };
+        let response = self.generate(&messages, Some(options)).await?;
+        let (summary, _) = parse_run_summary(response.content_or_empty(), &memories);
+        if summary.is_empty() {
+            return Err(RookError::llm(format!("No summary generated for run '{}'", run_id)));
+        }
+
+        // Scope of the run's owner, so the summary outlives the run
+        let run = self.get_run(run_id).await?;
+        let owner = |field: &str| {
+            memories.iter().find_map(|m| {
+                m.metadata
+                    .as_ref()?
+                    .get(field)?
+                    .as_str()
+                    .map(str::to_string)
+            })
+        };
+        let user_id = run.as_ref().and_then(|r| r.user_id.clone()).or_else(|| owner("user_id"));
+        let agent_id = run.as_ref().and_then(|r| r.agent_id.clone()).or_else(|| owner("agent_id"));
+
+        let source_memory_ids: Vec<String> = memories.iter().map(|m| m.id.clone()).collect();
+        let mut metadata = HashMap::new();
+        if let Some(user_id) = user_id {
+            metadata.insert("user_id".to_string(), serde_json::json!(user_id));
+        }
+        if let Some(agent_id) = agent_id {
+            metadata.insert("agent_id".to_string(), serde_json::json!(agent_id));
+        }
+        if metadata.is_empty() {
+            metadata.insert("run_id".to_string(), serde_json::json!(run_id));
+        }
+        metadata.insert(RUN_SOURCE_FIELD.to_string(), serde_json::json!(run_id));
+        metadata.insert("memory_type".to_string(), serde_json::json!("episodic_memory"));
+        metadata.insert(
+            SUMMARY_SOURCES_FIELD.to_string(),
+            serde_json::json!(source_memory_ids),
+        );
+        let memory_id = self.create_memory(&summary, &metadata).await?;
+
+        if archive {
+            for id in &source_memory_ids {
+                self.remove(id, Some(SUMMARIZED_REASON)).await?;
+            }
+        }
+        tracing::debug!(run_id, sources = source_memory_ids.len(), archive, "Summarized session");
+
+        Ok(SessionSummary {
+            run_id: run_id.to_string(),
+            memory_id,
+            summary,
+            source_memory_ids,
+            archived: archive,
+        })
+    }
+
+    /// Restate memories that reached the Consolidated phase as semantic
+    /// memories.
+    ///
+    /// Consolidation queues memories as they reach the Consolidated phase.
+    /// Queued memories held by this instance are grouped by user, agent and
+    /// run, and the LLM generalizes each group into standalone facts, stored
+    /// with `memory_type` `semantic_memory` and the IDs of the episodic
+    /// memories they came from. The originals are kept. Memories of other
+    /// instances sharing the cognitive store stay queued. Does nothing
+    /// unless `semantic_promotion.enabled` and `cognitive.enabled` are set.
+    /// Returns the number of semantic memories created.
+    pub async fn promote_consolidated(&self) -> RookResult<usize> {
+        let Some(store) = self.fsrs_store() else {
+            return Ok(0);
+        };
+        let config = &self.config.semantic_promotion;
+        if !config.enabled {
+            return Ok(0);
+        }
+
+        type Scope = (Option<String>, Option<String>, Option<String>);
+        let mut groups: std::collections::BTreeMap<Scope, Vec<MemoryItem>> = Default::default();
+        let mut taken = 0;
+        for memory_id in store.pending_promotions()? {
+            if taken >= config.batch_size {
+                break;
+            }
+            let Some(record) = self
+                .observe("vector_store.get", self.vector_store.get(&memory_id))
+                .await?
+            else {
+                continue;
+            };
+            let memory = self.record_to_memory_item(record, None);
+            let field = |name: &str| {
+                memory
+                    .metadata
+                    .as_ref()?
+                    .get(name)?
+                    .as_str()
+                    .map(str::to_string)
+            };
+            // Semantic memories consolidate too; they're not restated again
+            if field("memory_type").as_deref() == Some(SEMANTIC_MEMORY_TYPE) {
+                store.remove_promotion(&memory_id)?;
+                continue;
+            }
+            let scope = (field("user_id"), field("agent_id"), field("run_id"));
+            groups.entry(scope).or_default().push(memory);
+            taken += 1;
+        }
+
+        let options = GenerationOptions {
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat::Json),
+            ..Default::default()
+        };
+        let mut created = 0;
+        for ((user_id, agent_id, run_id), memories) in groups {
+            let messages = vec![Message::user(semantic_promotion_prompt(&memories))];
+            let response = self.generate(&messages, Some(options.clone())).await?;
+
+            let mut metadata = HashMap::new();
+            for (key, value) in [("user_id", user_id), ("agent_id", agent_id), ("run_id", run_id)] {
+                if let Some(value) = value {
+                    metadata.insert(key.to_string(), serde_json::json!(value));
+                }
+            }
+            metadata.insert("memory_type".to_string(), serde_json::json!(SEMANTIC_MEMORY_TYPE));
+            for (fact, sources) in parse_semantic_promotion(response.content_or_empty(), &memories) {
+                let mut metadata = metadata.clone();
+                metadata.insert(SUMMARY_SOURCES_FIELD.to_string(), serde_json::json!(sources));
+                self.create_memory(&fact, &metadata).await?;
+                created += 1;
+            }
+
+            for memory in &memories {
+                store.remove_promotion(&memory.id)?;
+            }
+        }
+        if created > 0 {
+            tracing::info!(created, "Promoted consolidated memories to semantic memory");
+        }
+        Ok(created)
+    }
+
+    /// Replay recent memories against the older memories they relate to.
+    ///
+    /// Memories added within `replay.window_hours` are searched for again,
+    /// within their own user, agent and run. A memory whose closest older
+    /// memory is at least `replay.min_surprise` away from it is co-activated
+    /// with each older memory at `replay.min_similarity` or above, adding
+    /// links spreading activation follows. Older memories co-activated
+    /// `replay.strengthen_after` times or more get a PRP boost, so a valid
+    /// synaptic tag can consolidate, and a `Replayed` strength signal,
+    /// applied as a Good review with the other pending grades. Each memory is
+    /// replayed once. Does nothing unless `replay.enabled` and
+    /// `cognitive.enabled` are set. Returns the number of older memories
+    /// strengthened.
+    pub async fn replay(&self) -> RookResult<usize> {
+        let Some(store) = self.fsrs_store() else {
+            return Ok(0);
+        };
+        let config = &self.config.replay;
+        if !config.enabled {
+            return Ok(0);
+        }
+
+        let now = chrono::Utc::now();
+        let since = now - chrono::Duration::hours(config.window_hours as i64);
+        let mut strengthened = 0;
+        for memory_id in store.replay_candidates(since, config.batch_size)? {
+            let Some(mut record) = self
+                .observe("vector_store.get", self.vector_store.get(&memory_id))
+                .await?
+            else {
+                continue;
+            };
+            if record.vector.is_empty() {
+                let data = record.get_data().unwrap_or_default().to_string();
+                record.vector = self
+                    .observe("embedder.embed", self.embedder.embed(&data, Some(EmbeddingAction::Search)))
+                    .await?;
+            }
+
+            // Stored scope values are already blind-indexed tokens
+            let conditions: Vec<Filter> = ["user_id", "agent_id", "run_id"]
+                .into_iter()
+                .filter_map(|field| {
+                    let value = record.payload.get(field).filter(|v| !v.is_null())?;
+                    Some(Filter::eq(field, value.clone()))
+                })
+                .collect();
+            let filter = match conditions.len() {
+                0 => None,
+                1 => conditions.into_iter().next(),
+                _ => Some(Filter::And(conditions)),
+            };
+            let results = self
+                .observe(
+                    "vector_store.search",
+                    self.vector_store.search(&record.vector, config.neighbors + 1, filter),
+                )
+                .await?;
+
+            let created_at = |payload: &HashMap<String, serde_json::Value>| {
+                payload
+                    .get("created_at")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            };
+            let own_created_at = created_at(&record.payload);
+            let older: Vec<_> = results
+                .into_iter()
+                .filter(|r| r.id != memory_id)
+                .filter(|r| match (created_at(&r.payload), own_created_at) {
+                    (Some(theirs), Some(ours)) => theirs < ours,
+                    _ => false,
+                })
+                .take(config.neighbors)
+                .collect();
+
+            let closest = older.iter().map(|r| r.score).fold(0.0_f32, f32::max);
+            if 1.0 - closest >= config.min_surprise {
+                for related in older.iter().filter(|r| r.score >= config.min_similarity) {
+                    store.record_coactivation(&memory_id, &related.id, related.score)?;
+                    if store.coactivation_count(&related.id)? < config.strengthen_after {
+                        continue;
+                    }
+                    if let Some(mut tag) = store.get_synaptic_tag(&related.id)? {
+                        if tag.is_valid() && !tag.prp_available {
+                            tag.set_prp_available_at(now);
+                            store.save_synaptic_tag(&tag)?;
+                        }
+                    }
+                    self.process_strength_signal(StrengthSignal::Replayed {
+                        memory_id: related.id.clone(),
+                    });
+                    strengthened += 1;
+                }
+            }
+            store.mark_replayed(&memory_id, now)?;
+        }
+        if strengthened > 0 {
+            tracing::debug!(strengthened, "Strengthened memories by replay");
+        }
+        Ok(strengthened)
+    }
+
+    /// Delete the memories and pins of ended runs whose retention period
+    /// has passed. Returns the number of memories deleted.
+    pub async fn expire_runs(&self) -> RookResult<usize> {
+        let due = self
+            .history
+            .read()
+            .await
+            .runs_due_for_expiry(chrono::Utc::now())?;
+        let mut deleted = 0;
+        for mut run in due {
+            deleted += self.expire_run(&mut run).await?;
+        }
+        Ok(deleted)
+    }
+
+    /// Soft-delete memories whose `expires_at` has passed, emitting a
+    /// Deleted event for each. Returns the number of memories deleted.
+    ///
+    /// Stores that compare timestamps list only the expired memories; other
+    /// stores are scanned in full.
+    pub async fn expire_memories(&self) -> RookResult<usize> {
+        let now = chrono::Utc::now();
+        let filter = self
+            .vector_store
+            .supports_timestamp_filters()
+            .then(|| Filter::lte(EXPIRES_AT_FIELD, now.to_rfc3339()));
+        let records = self
+            .observe("vector_store.list", self.vector_store.list(filter, None))
+            .await?;
+        let mut deleted = 0;
+        for record in records.iter().filter(|r| is_expired(&r.payload, now)) {
+            self.remove(&record.id, Some(EXPIRED_REASON)).await?;
+            deleted += 1;
+        }
+        if deleted > 0 {
+            tracing::debug!(deleted, "Expired memories");
+        }
+        Ok(deleted)
+    }
+
+    async fn expire_run(&self, run: &mut RunRecord) -> RookResult<usize> {
+        let memories = self
+            .get_all(None, None, Some(run.run_id.clone()), None)
+            .await?;
+        for memory in &memories {
+            self.delete(&memory.id).await?;
+        }
+        self.clear_run_pins(&run.run_id).await?;
+
+        run.expired_at = Some(chrono::Utc::now());
+        self.history.read().await.save_run(run)?;
+        tracing::debug!(run_id = %run.run_id, deleted = memories.len(), "Expired run memories");
+        Ok(memories.len())
+    }
+
+    /// The memories pinned to a run, marked as pinned.
+    async fn pinned_memories(&self, run_id: &str) -> RookResult<Vec<MemoryItem>> {
+        let mut memories = Vec::new();
+        for pin in self.run_pins(run_id).await? {
+            let record = self
+                .observe("vector_store.get", self.vector_store.get(&pin.memory_id))
+                .await?;
+            if let Some(record) = record {
+                let mut item = self.record_to_memory_item(record, None);
+                item.pinned = true;
+                memories.push(item);
+            }
+        }
+        Ok(memories)
+    }
+
+    /// The memories to surface in a search by `user_id`, boost applied, most
+    /// boosted first. Memories already among `results` keep their score and
+    /// explanation; memories deleted since their reminder fired are skipped.
+    async fn reminded_memories(
+        &self,
+        surfaced: &SurfacedMemories,
+        user_id: Option<&str>,
+        results: &[MemoryItem],
+    ) -> RookResult<Vec<MemoryItem>> {
+        let mut memories = Vec::new();
+        for reminder in surfaced.take_for_search(user_id) {
+            let item = match results.iter().find(|m| m.id == reminder.memory_id) {
+                Some(item) => Some(item.clone()),
+                None => self
+                    .observe("vector_store.get", self.vector_store.get(&reminder.memory_id))
+                    .await?
+                    .map(|record| self.record_to_memory_item(record, None)),
+            };
+            if let Some(mut item) = item {
+                item.score = item.score.map(|score| score * reminder.boost);
+                item.surfaced_by = Some(reminder.intention_id.to_string());
+                memories.push(item);
+            }
+        }
+        Ok(memories)
+    }
+
+    /// Attach a reminder to a memory.
+    ///
+    /// Stores an intention that, once `condition` fires, surfaces the memory
+    /// in the next searches of its user (see [`Memory::search`]). The
+    /// intention's action is [`IntentionAction::default`]; update it in the
+    /// intention store to change the boost or number of searches.
+    ///
+    /// Needs an intention store ([`Memory::with_intention_store`]), and the
+    /// queue of surfaced memories shared with the executor of fired
+    /// intentions ([`Memory::with_surfaced_memories`]) for fires to reach
+    /// searches. Keyword and topic conditions fire as an
+    /// [`IntentionChecker`](crate::intentions::IntentionChecker) checks the
+    /// user's messages; time-based ones once the returned intention is
+    /// scheduled with an
+    /// [`IntentionScheduler`](crate::intentions::IntentionScheduler).
+    pub async fn remind(
+        &self,
+        memory_id: &str,
+        condition: TriggerCondition,
+    ) -> RookResult<Intention> {
+        let store = self.intention_store.as_ref().ok_or_else(|| {
+            RookError::validation("Reminders need an intention store (see with_intention_store)")
+        })?;
+        condition.validate()?;
+        let memory = self
+            .get(memory_id)
+            .await?
+            .ok_or_else(|| RookError::not_found(memory_id))?;
+
+        let name: String = memory.memory.chars().take(60).collect();
+        let mut intention = Intention::new(
+            format!("Reminder: {}", name),
+            condition,
+            IntentionAction::default(),
+        )
+        .with_memory(memory_id);
+        let user_id = memory
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.get("user_id"))
+            .and_then(|v| v.as_str());
+        if let Some(user_id) = user_id {
+            intention = intention.with_user(user_id);
+        }
+
+        store.add(&intention)?;
+        Ok(intention)
+    }
+
     /// Classify a memory using LLM.
     ///
     /// Uses the category configuration to generate a prompt with valid categories,
@@ -710,7 +4471,13 @@ impl Memory {
     /// with the category, is_key flag, and confidence score.
     ///
     /// If the LLM returns an invalid category, it falls back to "misc".
-    async fn classify_memory(&self, content: &str) -> RookResult<ClassificationResult> {
+    /// With a classification cache, a previous classification of the same or
+    /// a similar memory is reused instead of calling the LLM.
+    async fn classify_memory(
+        &self,
+        content: &str,
+        embedding: &[f32],
+    ) -> RookResult<ClassificationResult> {
         let valid_categories: Vec<String> = self
             .config
             .category
@@ -718,12 +4485,28 @@ impl Memory {
             .into_iter()
             .collect();
 
+        let cached = self.classification_cache.as_ref().map(|cache| {
+            let namespace =
+                cache.namespace(&self.config.vector_store.collection_name, &valid_categories);
+            (cache, namespace)
+        });
+        if let Some((cache, ref namespace)) = cached {
+            if let Some(result) = cache.get(namespace, content, embedding) {
+                return Ok(result);
+            }
+        }
+
         let prompt = classification_prompt(&valid_categories);
         let messages = vec![Message::system(prompt), Message::user(content)];
 
-        let response = self.llm.generate(&messages, None).await?;
+        let response = self
+            .generate(&messages, None)
+            .await?;
         let result = parse_classification(response.content_or_empty(), &valid_categories);
 
+        if let Some((cache, namespace)) = cached {
+            cache.insert(namespace, content, embedding.to_vec(), result.clone());
+        }
         Ok(result)
     }
 
@@ -865,7 +4648,9 @@ impl Memory {
             Message::user(formatted_messages),
         ];
 
-        let response = self.llm.generate(&llm_messages, None).await?;
+        let response = self
+            .generate(&llm_messages, None)
+            .await?;
         parse_facts(response.content_or_empty())
     }
 
@@ -883,7 +4668,9 @@ impl Memory {
             Message::user(formatted_messages),
         ];
 
-        let response = self.llm.generate(&llm_messages, None).await?;
+        let response = self
+            .generate(&llm_messages, None)
+            .await?;
         parse_facts(response.content_or_empty())
     }
 
@@ -893,11 +4680,18 @@ impl Memory {
         new_facts: &[String],
     ) -> RookResult<Vec<(String, String)>> {
         let mut existing = HashMap::new();
-        let filter = self.build_filter(filters);
+        let filter = self.build_filter(filters)?;
 
         for fact in new_facts {
-            let embedding = self.embedder.embed(fact, Some(EmbeddingAction::Search)).await?;
-            let results = self.vector_store.search(&embedding, 5, filter.clone()).await?;
+            let embedding = self
+                .observe("embedder.embed", self.embedder.embed(fact, Some(EmbeddingAction::Search)))
+                .await?;
+            let results = self
+                .observe(
+                    "vector_store.search",
+                    self.vector_store.search(&embedding, 5, filter.clone()),
+                )
+                .await?;
 
             for result in results {
                 if let Some(data) = result.payload.get("data").and_then(|v| v.as_str()) {
@@ -928,7 +4722,9 @@ impl Memory {
         );
 
         let llm_messages = vec![Message::user(prompt)];
-        let response = self.llm.generate(&llm_messages, None).await?;
+        let response = self
+            .generate(&llm_messages, None)
+            .await?;
 
         let mut actions = parse_memory_actions(response.content_or_empty())?;
 
@@ -949,13 +4745,15 @@ impl Memory {
         data: &str,
         metadata: &HashMap<String, serde_json::Value>,
     ) -> RookResult<String> {
-        let embedding = self.embedder.embed(data, Some(EmbeddingAction::Add)).await?;
+        let embedding = self
+            .observe("embedder.embed", self.embedder.embed(data, Some(EmbeddingAction::Add)))
+            .await?;
         let memory_id = Uuid::new_v4().to_string();
         let hash = format!("{:x}", md5::compute(data.as_bytes()));
         let created_at = chrono::Utc::now().to_rfc3339();
 
         // Classify the memory using LLM
-        let classification = self.classify_memory(data).await?;
+        let classification = self.classify_memory(data, &embedding).await?;
 
         let mut payload = metadata.clone();
         payload.insert("data".to_string(), serde_json::Value::String(data.to_string()));
@@ -976,8 +4774,18 @@ impl Memory {
             serde_json::json!(classification.confidence),
         );
 
-        let record = VectorRecord::new(memory_id.clone(), embedding, payload.clone());
-        self.vector_store.insert(vec![record]).await?;
+        // Scoping fields are stored as blind index tokens; events and history
+        // keep the plaintext values.
+        let mut stored_payload = payload.clone();
+        self.index_payload(&mut stored_payload);
+
+        let record = VectorRecord::new(memory_id.clone(), embedding, stored_payload);
+        self.observe("vector_store.insert", self.vector_store.insert(vec![record])).await?;
+        if let Some(store) = self.fsrs_store() {
+            let is_key = payload.get("is_key").and_then(|v| v.as_bool()).unwrap_or(false);
+            let category = payload.get("category").and_then(|v| v.as_str());
+            self.init_cognitive_state(store, &memory_id, category, is_key)?;
+        }
 
         // Emit created event
         if let Some(ref event_bus) = self.event_bus {
@@ -1028,6 +4836,78 @@ impl Memory {
         Ok(results)
     }
 
+    /// Rank memories with the retrieval engine in the given mode.
+    ///
+    /// Candidates are the memory store's best matches for the filters,
+    /// [`ACTIVATION_POOL`] times more than the engine fetches, and
+    /// `threshold` applies to their similarity. Memories the engine surfaces
+    /// from outside the candidates are dropped, since they may not match
+    /// the filters. With several queries, the candidates are the fused
+    /// matches for all of them, and the first query drives the engine.
+    /// With `mmr_lambda`, the engine diversifies its results by MMR.
+    async fn retrieve(
+        &self,
+        queries: &[String],
+        mode: RetrievalMode,
+        mmr_lambda: Option<f32>,
+        filters: &HashMap<String, serde_json::Value>,
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let config = match mode {
+            RetrievalMode::Quick => RetrievalConfig::quick(limit),
+            RetrievalMode::Standard => RetrievalConfig::standard(limit),
+            RetrievalMode::Precise => RetrievalConfig::precise(limit),
+            RetrievalMode::Cognitive => RetrievalConfig::cognitive(limit),
+        };
+        let config = match mmr_lambda {
+            Some(lambda) => config.with_mmr(lambda),
+            None => config,
+        };
+        let pool_size = match mode.uses_activation() {
+            true => limit * config.oversample_factor * ACTIVATION_POOL,
+            false => limit * config.oversample_factor,
+        };
+
+        let embeddings = self.embed_queries(queries).await?;
+        let pool = self
+            .search_embeddings(self.vector_store.as_ref(), &embeddings, filters, pool_size, threshold)
+            .await?;
+        let mut candidates: HashMap<String, VectorSearchResult> =
+            pool.iter().map(|r| (r.id.clone(), r.clone())).collect();
+
+        let cognitive_store = self
+            .cognitive_store
+            .clone()
+            .filter(|_| self.config.cognitive.enabled);
+        let searcher = PooledSearcher::new(self.vector_store.clone(), pool);
+        let mut engine: RetrievalEngine<PooledSearcher, dyn ActivationGraph, CognitiveStore> =
+            RetrievalEngine::new(Arc::new(searcher));
+        let graph = self.activation_graph.clone().or_else(|| {
+            cognitive_store
+                .clone()
+                .map(|store| store as Arc<dyn ActivationGraph>)
+        });
+        if let Some(graph) = graph {
+            engine = engine.with_graph(graph);
+        }
+        if let Some(store) = cognitive_store {
+            engine = engine.with_fsrs(store);
+        }
+
+        let results = engine.retrieve(&queries[0], &embeddings[0], &config).await?;
+        Ok(results
+            .into_iter()
+            .filter_map(|result| {
+                let mut candidate = candidates.remove(&result.id)?;
+                candidate.score = result.score;
+                let mut memory = self.search_result_to_memory_item(candidate);
+                memory.explanation = Some(explain::from_signals(&result.signals));
+                Some(memory)
+            })
+            .collect())
+    }
+
     async fn search_vector_store(
         &self,
         query: &str,
@@ -1035,21 +4915,181 @@ impl Memory {
         limit: usize,
         threshold: Option<f32>,
     ) -> RookResult<Vec<MemoryItem>> {
-        let embedding = self
-            .embedder
-            .embed(query, Some(EmbeddingAction::Search))
-            .await?;
+        let store = self.vector_store.as_ref();
+        self.search_queries(store, &[query.to_string()], filters, limit, threshold)
+            .await
+    }
 
-        let filter = self.build_filter(filters);
-        let results = self.vector_store.search(&embedding, limit, filter).await?;
+    /// Search a store with each query, fusing the rankings when there are
+    /// several.
+    async fn search_queries(
+        &self,
+        store: &dyn VectorStore,
+        queries: &[String],
+        filters: &HashMap<String, serde_json::Value>,
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let embeddings = self.embed_queries(queries).await?;
+        let results = self
+            .search_embeddings(store, &embeddings, filters, limit, threshold)
+            .await?;
 
-        let memories: Vec<MemoryItem> = results
+        Ok(results
             .into_iter()
-            .filter(|r| threshold.map_or(true, |t| r.score >= t))
             .map(|r| self.search_result_to_memory_item(r))
+            .collect())
+    }
+
+    async fn embed_queries(&self, queries: &[String]) -> RookResult<Vec<Vec<f32>>> {
+        match queries {
+            [query] => Ok(vec![self
+                .observe("embedder.embed", self.embedder.embed(query, Some(EmbeddingAction::Search)))
+                .await?]),
+            _ => {
+                self.observe(
+                    "embedder.embed_batch",
+                    self.embedder.embed_batch(queries, Some(EmbeddingAction::Search)),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Search a store with each embedding, `threshold` applying to each
+    /// result's similarity.
+    ///
+    /// `since`/`until` entries in `filters` become timestamp conditions when
+    /// the store supports them, and are checked against the results
+    /// otherwise. Several embeddings' rankings are fused by reciprocal rank,
+    /// and each result's score becomes its fused score.
+    async fn search_embeddings(
+        &self,
+        store: &dyn VectorStore,
+        embeddings: &[Vec<f32>],
+        filters: &HashMap<String, serde_json::Value>,
+        limit: usize,
+        threshold: Option<f32>,
+    ) -> RookResult<Vec<VectorSearchResult>> {
+        let mut filters = filters.clone();
+        let time_range = TimeRange::take_from(&mut filters)?;
+        let pushdown = store.supports_timestamp_filters();
+        let filter = match (self.build_filter(&filters)?, time_range.to_filter()) {
+            (Some(filter), Some(range)) if pushdown => Some(Filter::And(vec![filter, range])),
+            (None, Some(range)) if pushdown => Some(range),
+            (filter, _) => filter,
+        };
+
+        let mut rankings = Vec::with_capacity(embeddings.len());
+        for embedding in embeddings {
+            let results: Vec<VectorSearchResult> = self
+                .observe("vector_store.search", store.search(embedding, limit, filter.clone()))
+                .await?
+                .into_iter()
+                .filter(|r| !threshold.is_some_and(|t| r.score < t))
+                .filter(|r| pushdown || time_range.contains(&r.payload))
+                .collect();
+            rankings.push(results);
+        }
+        if rankings.len() == 1 {
+            return Ok(rankings.pop().unwrap_or_default());
+        }
+
+        let fused = RrfFusion::default().fuse(
+            rankings
+                .iter()
+                .map(|results| results.iter().map(|r| (r.id.clone(), r.score)).collect())
+                .collect(),
+        );
+        let mut results: HashMap<String, VectorSearchResult> = rankings
+            .into_iter()
+            .flatten()
+            .map(|r| (r.id.clone(), r))
             .collect();
+        Ok(fused
+            .into_iter()
+            .take(limit)
+            .filter_map(|(id, score)| {
+                let mut result = results.remove(&id)?;
+                result.score = score;
+                Some(result)
+            })
+            .collect())
+    }
 
-        Ok(memories)
+    /// The query followed by the LLM's expansions of it.
+    ///
+    /// Falls back to the query alone if the LLM fails.
+    async fn expand_query(&self, query: &str, expansion: QueryExpansion) -> Vec<String> {
+        let options = GenerationOptions {
+            temperature: Some(0.0),
+            response_format: Some(ResponseFormat::Json),
+            ..Default::default()
+        };
+        let messages = vec![Message::user(query_expansion_prompt(query, expansion))];
+        let mut queries = vec![query.to_string()];
+        match self.generate(&messages, Some(options)).await {
+            Ok(response) => queries.extend(parse_query_expansion_response(
+                response.content_or_empty(),
+                query,
+            )),
+            Err(e) => tracing::warn!("Query expansion failed, searching the query as is: {}", e),
+        }
+        queries
+    }
+
+    /// Walk the graph neighborhood of the entities named in a search query.
+    ///
+    /// Query entities are matched against entity names already in the graph;
+    /// the LLM is only consulted when none match and `llm_extraction` is set.
+    async fn search_graph(
+        &self,
+        graph: &dyn GraphStore,
+        query: &str,
+        filters: &HashMap<String, serde_json::Value>,
+    ) -> RookResult<GraphNeighborhood> {
+        let settings = &self.config.graph_search;
+        let graph_filters = GraphFilters {
+            user_id: filters.get("user_id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            agent_id: filters.get("agent_id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+            run_id: filters.get("run_id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        };
+
+        let known = self
+            .observe("graph_store.get_all", graph.get_all(&graph_filters))
+            .await?;
+        let mut seeds = query_entities(query, known.iter().map(|e| e.name.as_str()));
+
+        if seeds.is_empty() && settings.llm_extraction {
+            seeds = self
+                .extract_entities(query)
+                .await?
+                .entities
+                .into_iter()
+                .map(|e| e.name)
+                .collect();
+        }
+
+        let mut neighborhood = GraphNeighborhood::new(&seeds);
+        let mut frontier = seeds;
+        for hop in 1..=settings.max_hops.clamp(1, 2) {
+            let mut found = Vec::new();
+            for entity in &frontier {
+                found.extend(
+                    self.observe(
+                        "graph_store.search",
+                        graph.search(entity, &graph_filters, settings.relation_limit),
+                    )
+                    .await?,
+                );
+            }
+            frontier = neighborhood.extend(found, hop);
+            if frontier.is_empty() {
+                break;
+            }
+        }
+
+        Ok(neighborhood)
     }
 
     /// Add entities and relationships to the graph store.
@@ -1122,8 +5162,10 @@ impl Memory {
         for entity in &extraction_result.entities {
             // Generate embedding for the entity name
             let embedding = match self
-                .embedder
-                .embed(&entity.name, Some(EmbeddingAction::Add))
+                .observe(
+                    "embedder.embed",
+                    self.embedder.embed(&entity.name, Some(EmbeddingAction::Add)),
+                )
                 .await
             {
                 Ok(emb) => emb,
@@ -1189,16 +5231,30 @@ impl Memory {
                 "context": rel.context,
             });
 
-            match graph_store
-                .add_relationship(
-                    &rel.source,
-                    &rel.target,
-                    rel.relationship_type.as_str(),
-                    &properties,
-                    &graph_filters,
-                )
-                .await
-            {
+            // A new single-valued fact (e.g. works_at) ends the previous one
+            let result = if rel.relationship_type.is_single_valued() {
+                graph_store
+                    .supersede_relationship(
+                        &rel.source,
+                        &rel.target,
+                        rel.relationship_type.as_str(),
+                        &properties,
+                        &graph_filters,
+                    )
+                    .await
+            } else {
+                graph_store
+                    .add_relationship(
+                        &rel.source,
+                        &rel.target,
+                        rel.relationship_type.as_str(),
+                        &properties,
+                        &graph_filters,
+                    )
+                    .await
+            };
+
+            match result {
                 Ok(_) => {
                     tracing::debug!(
                         "Created relationship: {} --[{}]--> {}",
@@ -1244,7 +5300,9 @@ impl Memory {
             ..Default::default()
         };
 
-        let response = self.llm.generate(&messages, Some(options)).await?;
+        let response = self
+            .generate(&messages, Some(options))
+            .await?;
         let content = response.content.unwrap_or_default();
 
         Ok(parse_entity_extraction(&content))
@@ -1261,7 +5319,9 @@ impl Memory {
             "Create procedural memory of the above conversation.",
         ));
 
-        let response = self.llm.generate(&prompt_messages, None).await?;
+        let response = self
+            .generate(&prompt_messages, None)
+            .await?;
         let procedural_memory =
             super::json_parser::remove_code_blocks(response.content_or_empty());
 
@@ -1284,9 +5344,12 @@ impl Memory {
         })
     }
 
-    fn build_filter(&self, filters: &HashMap<String, serde_json::Value>) -> Option<Filter> {
+    fn build_filter(
+        &self,
+        filters: &HashMap<String, serde_json::Value>,
+    ) -> RookResult<Option<Filter>> {
         if filters.is_empty() {
-            return None;
+            return Ok(None);
         }
 
         let conditions: Vec<Filter> = filters
@@ -1294,10 +5357,15 @@ impl Memory {
             .map(|(k, v)| Filter::eq(k.clone(), v.clone()))
             .collect();
 
-        if conditions.len() == 1 {
-            Some(conditions.into_iter().next().unwrap())
+        let filter = if conditions.len() == 1 {
+            conditions.into_iter().next().unwrap()
         } else {
-            Some(Filter::And(conditions))
+            Filter::And(conditions)
+        };
+
+        match self.blind_indexer {
+            Some(ref indexer) => indexer.rewrite_filter(&filter).map(Some),
+            None => Ok(Some(filter)),
         }
     }
 
@@ -1337,8 +5405,12 @@ impl Memory {
                 .get("is_key")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false),
+            pinned: false,
+            surfaced_by: None,
             memory_state: None,
             dual_strength: None,
+            retrievability: None,
+            explanation: None,
         }
     }
 
@@ -1378,8 +5450,12 @@ impl Memory {
                 .get("is_key")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false),
+            pinned: false,
+            surfaced_by: None,
             memory_state: None,
             dual_strength: None,
+            retrievability: None,
+            explanation: None,
         }
     }
 
@@ -1475,6 +5551,133 @@ impl Memory {
             .ingest(self, content, mime_type, filename, user_id, additional_metadata)
             .await
     }
+
+    /// Fetch a URL and ingest its content as memories.
+    ///
+    /// The content type is taken from the response, or sniffed from the
+    /// content and URL when the server doesn't say, and routed through the
+    /// extraction pipeline like [`Memory::ingest_content`]. Memories carry
+    /// `source_url` and `source_fetched_at` metadata.
+    ///
+    /// Downloads are limited to 25 MB and 30 seconds and respect the site's
+    /// robots.txt; use [`Memory::ingest_url_with_config`] to change this.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let result = memory.ingest_url(
+    ///     "https://example.com/handbook.pdf",
+    ///     "user_123",
+    ///     None,
+    /// ).await?;
+    /// ```
+    #[cfg(feature = "multimodal")]
+    pub async fn ingest_url(
+        &self,
+        url: &str,
+        user_id: &str,
+        additional_metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+    ) -> crate::error::RookResult<crate::multimodal::MultimodalIngestResult> {
+        let ingester = crate::multimodal::MultimodalIngester::new();
+        ingester
+            .ingest_url(self, url, user_id, additional_metadata)
+            .await
+    }
+
+    /// Fetch a URL and ingest its content with custom configuration.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use rook_core::{MultimodalConfig, UrlFetchConfig};
+    ///
+    /// let config = MultimodalConfig {
+    ///     url_fetch: UrlFetchConfig {
+    ///         max_bytes: 100 * 1024 * 1024,
+    ///         respect_robots: false,
+    ///         ..Default::default()
+    ///     },
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let result = memory.ingest_url_with_config(
+    ///     "https://intranet.example.com/report.docx",
+    ///     "user_123",
+    ///     None,
+    ///     config,
+    /// ).await?;
+    /// ```
+    #[cfg(feature = "multimodal")]
+    pub async fn ingest_url_with_config(
+        &self,
+        url: &str,
+        user_id: &str,
+        additional_metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+        config: crate::multimodal::MultimodalConfig,
+    ) -> crate::error::RookResult<crate::multimodal::MultimodalIngestResult> {
+        let ingester = crate::multimodal::MultimodalIngester::with_config(config);
+        ingester
+            .ingest_url(self, url, user_id, additional_metadata)
+            .await
+    }
+
+    /// Ingest every supported file in a directory tree as memories.
+    ///
+    /// Files are filtered by the config's include and exclude globs and
+    /// ingested `concurrency` at a time. Content hashes are recorded in a
+    /// manifest (`.rook-manifest.json` in `root` by default), so re-running
+    /// on the same directory only ingests new and changed files. Memories
+    /// carry a `source_path` relative to `root`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use rook_core::BulkIngestConfig;
+    ///
+    /// let config = BulkIngestConfig::default().include("**/*.{pdf,md}");
+    /// let report = memory.ingest_directory("./docs", "user_123", None, config).await?;
+    ///
+    /// println!("{} ingested, {} unchanged, {} failed", report.ingested, report.unchanged, report.failed);
+    /// ```
+    #[cfg(feature = "multimodal")]
+    pub async fn ingest_directory(
+        &self,
+        root: impl AsRef<std::path::Path>,
+        user_id: &str,
+        additional_metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+        config: crate::multimodal::BulkIngestConfig,
+    ) -> crate::error::RookResult<crate::multimodal::BulkIngestReport> {
+        let ingester = crate::multimodal::BulkIngester::new(
+            crate::multimodal::MultimodalIngester::new(),
+            config,
+        );
+        ingester
+            .ingest_dir(self, root, user_id, additional_metadata)
+            .await
+    }
+}
+
+/// Rebuilds the graph by re-running entity extraction on memory text.
+struct GraphRebuilder<'a> {
+    memory: &'a Memory,
+}
+
+#[async_trait]
+impl Rebuilder for GraphRebuilder<'_> {
+    fn target(&self) -> RebuildTarget {
+        RebuildTarget::Graph
+    }
+
+    async fn rebuild(&self, record: &VectorRecord) -> RookResult<bool> {
+        let Some(data) = record.get_data() else {
+            return Ok(false);
+        };
+        let relations = self
+            .memory
+            .add_to_graph(&[Message::user(data)], &record.payload)
+            .await?;
+        Ok(!relations.is_empty())
+    }
 }
 
 #[cfg(test)]
@@ -1574,6 +5777,43 @@ mod event_wiring_tests {
     }
 }
 
+/// Intentions of a user or attached to one of their memories.
+fn user_intentions(
+    store: &dyn IntentionStore,
+    user_id: &str,
+    memory_ids: &[String],
+) -> RookResult<Vec<Intention>> {
+    let mut intentions = store.get_for_user(user_id)?;
+    for memory_id in memory_ids {
+        for intention in store.get_for_memory(memory_id)? {
+            if !intentions.iter().any(|i| i.id == intention.id) {
+                intentions.push(intention);
+            }
+        }
+    }
+    Ok(intentions)
+}
+
+/// Scope fields of an imported memory, as filters.
+fn import_scope(memory: &ImportableMemory) -> HashMap<String, serde_json::Value> {
+    let metadata = memory.metadata.as_ref();
+    ["user_id", "agent_id", "run_id"]
+        .into_iter()
+        .filter_map(|field| {
+            let value = metadata?.get(field).filter(|v| !v.is_null())?;
+            Some((field.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Content hash of an imported memory, computed when the export has none.
+fn import_hash(memory: &ImportableMemory) -> String {
+    memory
+        .hash
+        .clone()
+        .unwrap_or_else(|| format!("{:x}", md5::compute(memory.memory.as_bytes())))
+}
+
 /// Tests for add_to_graph entity extraction flow
 #[cfg(test)]
 mod add_to_graph_tests {
@@ -1772,3 +6012,172 @@ mod add_to_graph_tests {
         assert!(text.trim().is_empty());
     }
 }
+
+#[cfg(test)]
+mod blind_index_tests {
+    use super::*;
+    use crate::encryption::BlindIndexConfig;
+    use crate::memory::testing::{test_config, test_memory};
+    use crate::memory::{GLOBAL_SCOPE, SCOPE_FIELD};
+
+    fn blind_config(fields: &[&str]) -> MemoryConfig {
+        let mut config = test_config();
+        config.blind_index =
+            Some(BlindIndexConfig::with_key("k1", "secret").fields(fields.iter().copied()));
+        config
+    }
+
+    async fn add(memory: &Memory, text: &str, user_id: &str) -> String {
+        let result = memory
+            .add(
+                text,
+                Some(user_id.to_string()),
+                None,
+                None,
+                None,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+        result.results[0].id.clone()
+    }
+
+    #[tokio::test]
+    async fn test_promoted_memory_is_blind_indexed() {
+        let mut config = blind_config(&["user_id", "agent_id", "run_id", "category", SCOPE_FIELD]);
+        config.global_knowledge.require_review = false;
+        let (memory, store) = test_memory(config);
+        let id = add(&memory, "Deploys go out on Tuesdays", "alice").await;
+
+        let promotion = memory.propose_promotion(&id, "admin", None).await.unwrap();
+        let global_id = promotion.global_memory_id.unwrap();
+
+        let stored = store
+            .records()
+            .into_iter()
+            .find(|r| r.id == global_id)
+            .unwrap();
+        assert_ne!(stored.payload[SCOPE_FIELD], GLOBAL_SCOPE);
+        let global = memory.get_global(None).await.unwrap();
+        assert_eq!(global.len(), 1);
+        assert_eq!(global[0].id, global_id);
+        assert_eq!(global[0].memory, "Deploys go out on Tuesdays");
+    }
+
+    #[tokio::test]
+    async fn test_synced_memory_is_blind_indexed() {
+        // The peer doesn't blind-index, so its changes carry plaintext scopes.
+        let mut peer_config = test_config();
+        peer_config.sync.enabled = true;
+        let (peer, _) = test_memory(peer_config);
+        let id = add(&peer, "Prefers dark roast coffee", "alice").await;
+
+        let mut config = blind_config(&["user_id"]);
+        config.sync.enabled = true;
+        let (memory, store) = test_memory(config);
+        let changeset = peer.sync_changes(0, 10).await.unwrap();
+        let report = memory.apply_changeset(&changeset).await.unwrap();
+        assert_eq!(report.applied, 1);
+
+        let stored = store.records().into_iter().find(|r| r.id == id).unwrap();
+        assert_ne!(stored.payload["user_id"], "alice");
+        let found = memory
+            .get_all(Some("alice".to_string()), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].memory, "Prefers dark roast coffee");
+    }
+
+    async fn export_import(from: &Memory, to: &Memory, user_id: &str) -> u64 {
+        let mut exported = Vec::new();
+        from.export_user(user_id, &mut exported).await.unwrap();
+        let stats =
+            crate::import::import_jsonl(exported.as_slice(), 100, |batch| to.import_batch(batch))
+                .await
+                .unwrap();
+        stats.imported
+    }
+
+    #[tokio::test]
+    async fn test_imported_memory_is_blind_indexed() {
+        // The source doesn't blind-index, so its export carries plaintext scopes.
+        let (source, _) = test_memory(test_config());
+        let id = add(&source, "Allergic to peanuts", "alice").await;
+
+        let (memory, store) = test_memory(blind_config(&["user_id"]));
+        assert_eq!(export_import(&source, &memory, "alice").await, 1);
+        let stored = store.records().into_iter().find(|r| r.id == id).unwrap();
+        assert_ne!(stored.payload["user_id"], "alice");
+
+        // A blind-indexed export imports into another store with the same key.
+        let (copy, _) = test_memory(blind_config(&["user_id"]));
+        assert_eq!(export_import(&memory, &copy, "alice").await, 1);
+        for memory in [&memory, &copy] {
+            let found = memory
+                .get_all(Some("alice".to_string()), None, None, None)
+                .await
+                .unwrap();
+            assert_eq!(found.len(), 1);
+            assert_eq!(found[0].memory, "Allergic to peanuts");
+        }
+    }
+}
+
+#[cfg(test)]
+mod find_duplicates_tests {
+    use super::*;
+    use crate::memory::testing::{test_config, test_memory};
+
+    async fn add(memory: &Memory, text: &str, user_id: &str) -> String {
+        let result = memory
+            .add(
+                text,
+                Some(user_id.to_string()),
+                None,
+                None,
+                None,
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+        result.results[0].id.clone()
+    }
+
+    async fn duplicates(memory: &Memory, text: &str, user_id: &str) -> Vec<MemoryItem> {
+        memory
+            .find_duplicates(text, Some(user_id.to_string()), None, None, 0.9)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_finds_near_duplicate_in_scope() {
+        let (memory, _) = test_memory(test_config());
+        let id = add(&memory, "I like green tea", "alice").await;
+        add(&memory, "Deploys go out on Tuesdays", "alice").await;
+
+        let found = duplicates(&memory, "I like green tea", "alice").await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, id);
+        assert!(found[0].score.unwrap() >= 0.9);
+
+        assert!(duplicates(&memory, "I like green tea", "bob")
+            .await
+            .is_empty());
+        assert!(duplicates(&memory, "The office closes at six", "alice")
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_requires_a_scope() {
+        let (memory, _) = test_memory(test_config());
+        let result = memory
+            .find_duplicates("I like green tea", None, None, None, 0.9)
+            .await;
+        assert!(result.is_err());
+    }
+}
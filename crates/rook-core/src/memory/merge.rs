@@ -0,0 +1,112 @@
+//! Merging overlapping memories into one.
+//!
+//! [`crate::Memory::merge`] asks the LLM to combine several memories into a
+//! single consolidated memory, which lists its sources under
+//! [`super::SUMMARY_SOURCES_FIELD`]. The originals are then superseded or
+//! deleted, as chosen by [`MergeStrategy`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::RookError;
+use crate::types::MemoryItem;
+
+use super::json_parser::extract_json;
+
+/// Payload field on a superseded memory naming the memory it was merged into.
+pub const SUPERSEDED_BY_FIELD: &str = "superseded_by";
+
+/// Deletion reason reported for memories merged into another.
+pub const MERGED_REASON: &str = "merged";
+
+/// What happens to the memories merged into a new one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Record each original as superseded by the merged memory, then
+    /// soft-delete it. Originals can be restored with a rollback.
+    #[default]
+    Supersede,
+    /// Delete the originals.
+    Delete,
+}
+
+impl std::str::FromStr for MergeStrategy {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "supersede" => Ok(Self::Supersede),
+            "delete" => Ok(Self::Delete),
+            other => Err(RookError::validation(format!(
+                "Unknown merge strategy '{}' (expected supersede or delete)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Prompt asking the LLM to combine memories into one.
+pub fn merge_prompt(memories: &[MemoryItem]) -> String {
+    let listing = memories
+        .iter()
+        .enumerate()
+        .map(|(i, m)| format!("{}. {}", i + 1, m.memory))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"The following memories about the same user overlap. Combine them into a single memory.
+
+MEMORIES:
+{listing}
+
+Keep every distinct fact, drop repetition, and when memories conflict prefer the later one in the list. Write one concise, self-contained statement.
+
+Respond ONLY with a JSON object, no other text:
+{{"memory": "<combined memory>"}}"#
+    )
+}
+
+#[derive(Deserialize)]
+struct RawMerge {
+    #[serde(default)]
+    memory: String,
+}
+
+/// Parse the combined memory from the LLM response.
+pub fn parse_merged_memory(response: &str) -> Option<String> {
+    let json = extract_json(response).unwrap_or_default();
+    let raw: RawMerge = match serde_json::from_str(&json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::debug!("Failed to parse merged memory: {}", e);
+            return None;
+        }
+    };
+    Some(raw.memory.trim().to_string()).filter(|m| !m.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_strategy_from_str() {
+        assert_eq!("supersede".parse::<MergeStrategy>().unwrap(), MergeStrategy::Supersede);
+        assert_eq!(" Delete ".parse::<MergeStrategy>().unwrap(), MergeStrategy::Delete);
+        assert!("squash".parse::<MergeStrategy>().is_err());
+        assert_eq!(MergeStrategy::default(), MergeStrategy::Supersede);
+    }
+
+    #[test]
+    fn test_parse_merged_memory() {
+        assert_eq!(
+            parse_merged_memory(r#"```json
+{"memory": " Uses Helix with a dark theme "}
+```"#),
+            Some("Uses Helix with a dark theme".to_string())
+        );
+        assert_eq!(parse_merged_memory(r#"{"memory": ""}"#), None);
+        assert_eq!(parse_merged_memory("not json"), None);
+    }
+}
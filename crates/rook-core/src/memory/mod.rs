@@ -1,15 +1,79 @@
 //! Memory module - core memory implementation.
 
+mod archive;
+mod as_of;
+mod classification_cache;
+mod context;
+mod document;
+mod erasure;
+mod expansion;
+mod explain;
+mod expiry;
+mod global;
+mod graph_search;
 mod history;
 mod json_parser;
 mod main;
+mod merge;
 mod prompts;
+mod reflection;
+mod replay;
+mod retrieval;
+mod runs;
+mod semantic;
 mod session;
+mod strength;
 mod telemetry;
+mod temporal;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 
-pub use history::{HistoryEvent, HistoryRecord, HistoryStore};
+pub use archive::{ArchiveConfig, ArchiveJob, ARCHIVED_AT_FIELD, INCLUDE_ARCHIVED_FILTER};
+pub use as_of::VERSION_EMBEDDING_CACHE_SIZE;
+pub use classification_cache::{
+    ClassificationCache, ClassificationCacheConfig, ClassificationCacheStats,
+};
+pub use context::{
+    ApproxTokenCounter, ContextBlock, ContextBuilder, TokenCounter, DEFAULT_CONTEXT_HEADER,
+    DEFAULT_CONTEXT_SEARCH_LIMIT,
+};
+pub use document::{
+    document_entity_name, Document, CHUNK_ROLE, DOCUMENT_ID_FIELD, DOCUMENT_ROLE, DOCUMENT_ROLE_FIELD, HAS_CHUNK_RELATIONSHIP,
+};
+pub use erasure::{ErasureReport, UserDataCounts};
+pub use expansion::{
+    parse_query_expansion, parse_query_expansion_response, query_expansion_prompt, QueryExpansion,
+    MAX_PARAPHRASES, QUERY_EXPANSION_FILTER,
+};
+pub use explain::EXPLAIN_FILTER;
+pub use expiry::{
+    expires_at, expiry_after, expiry_from_ttl, is_expired, MemoryExpiryJob, EXPIRED_REASON, EXPIRES_AT_FIELD,
+};
+pub use global::{
+    is_global, GlobalKnowledgeConfig, PromotionRecord, PromotionStatus, GLOBAL_SCOPE, SCOPE_FIELD,
+};
+pub use history::{HistoryEvent, HistoryRecord, HistoryStore, RunPin};
 pub use json_parser::{extract_json, parse_facts, parse_memory_actions, remove_code_blocks};
 pub use main::Memory;
+pub use merge::{MergeStrategy, MERGED_REASON, SUPERSEDED_BY_FIELD};
 pub use prompts::*;
-pub use session::{build_filters_and_metadata, SessionScope};
+pub use reflection::{GapKind, KnowledgeGap, KnowledgeGapReport};
+pub use replay::{ReplayConfig, ReplayJob};
+pub use retrieval::{
+    parse_mmr_lambda, parse_retrieval_mode, ACTIVATION_POOL, MMR_LAMBDA_FILTER, RETRIEVAL_MODE_FILTER,
+};
+pub use runs::{
+    RunConfig, RunEndOptions, RunRecord, SessionSummary, RUN_SOURCE_FIELD, SUMMARIZED_REASON,
+    SUMMARY_SOURCES_FIELD,
+};
+pub use semantic::{
+    parse_semantic_promotion, semantic_promotion_prompt, SemanticPromotionConfig,
+    SemanticPromotionJob, SEMANTIC_MEMORY_TYPE,
+};
+pub use session::{build_filters_and_metadata, ScopeReassignment, SessionScope};
+pub use strength::StrengthUpdateJob;
 pub use telemetry::{process_telemetry_filters, Telemetry};
+pub use temporal::{
+    apply_recency_boost, parse_recency_half_life, recency_weight, TimeRange,
+    RECENCY_HALF_LIFE_FILTER, SINCE_FILTER, UNTIL_FILTER,
+};
@@ -120,6 +120,12 @@ impl RelationshipType {
         }
     }
 
+    /// Whether an entity holds at most one current relationship of this type,
+    /// so a new one supersedes the old (a person works at one place at a time).
+    pub fn is_single_valued(&self) -> bool {
+        matches!(self, RelationshipType::WorksAt | RelationshipType::LocatedIn)
+    }
+
     /// Parse from string with flexible matching.
     pub fn from_str_flexible(s: &str) -> Option<Self> {
         let lower = s.to_lowercase().replace(['-', ' '], "_");
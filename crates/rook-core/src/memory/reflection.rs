@@ -0,0 +1,227 @@
+//! Self-reflection: what the memory store knows and doesn't know about a topic.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::types::MemoryItem;
+
+use super::json_parser::extract_json;
+
+/// Kind of knowledge gap reported by [`crate::Memory::knowledge_gaps`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapKind {
+    /// Nothing is stored about this aspect of the topic.
+    Missing,
+    /// Stored information is likely outdated.
+    Stale,
+    /// Stored information is too vague to act on.
+    Ambiguous,
+    /// Stored memories contradict each other.
+    Conflicting,
+}
+
+impl GapKind {
+    /// Parse a gap kind, accepting common synonyms. Unknown kinds map to `Missing`.
+    pub fn from_str_flexible(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "stale" | "outdated" | "old" => GapKind::Stale,
+            "ambiguous" | "vague" | "unclear" => GapKind::Ambiguous,
+            "conflicting" | "conflict" | "contradictory" | "contradiction" => GapKind::Conflicting,
+            _ => GapKind::Missing,
+        }
+    }
+}
+
+/// A single gap in stored knowledge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeGap {
+    /// What kind of gap this is.
+    pub kind: GapKind,
+    /// What is missing or wrong.
+    pub description: String,
+    /// A clarifying question the agent could ask the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    /// IDs of the memories this gap refers to (empty for missing information).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memory_ids: Vec<String>,
+    /// How much filling this gap matters for the topic (0.0 to 1.0).
+    pub importance: f32,
+}
+
+/// What the memory store knows and doesn't know about a topic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeGapReport {
+    /// The topic that was reflected on.
+    pub topic: String,
+    /// Short summary of what is known.
+    pub summary: String,
+    /// Memories retrieved for the topic.
+    pub known: Vec<MemoryItem>,
+    /// Gaps, most important first.
+    pub gaps: Vec<KnowledgeGap>,
+}
+
+/// Build the prompt asking the LLM to find gaps in the given memories.
+///
+/// Memories are numbered from 1; the LLM refers to them by number.
+pub fn knowledge_gaps_prompt(topic: &str, memories: &[MemoryItem]) -> String {
+    let date = Local::now().format("%Y-%m-%d");
+    let listing = if memories.is_empty() {
+        "(no memories stored)".to_string()
+    } else {
+        memories
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let when = m.updated_at.as_deref().or(m.created_at.as_deref()).unwrap_or("unknown");
+                format!("{}. {} (last updated: {})", i + 1, m.memory, when)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(
+        r#"You are reviewing what an assistant remembers about a user, to find gaps before it answers.
+
+Today's date is {date}.
+
+TOPIC: {topic}
+
+STORED MEMORIES:
+{listing}
+
+Identify what the assistant would need to know to help with this topic but does not reliably know.
+Gap kinds:
+- "missing": relevant information that is not stored at all
+- "stale": stored information that is likely out of date
+- "ambiguous": stored information too vague to act on
+- "conflicting": stored memories that contradict each other
+
+For each gap, suggest one short clarifying question the assistant could ask the user.
+Only report gaps that matter for the topic. If nothing important is missing, return no gaps.
+
+Respond ONLY with a JSON object, no other text:
+{{"summary": "<one or two sentences on what is known>", "gaps": [{{"kind": "<kind>", "description": "<gap>", "question": "<question>", "memories": [<memory numbers>], "importance": <0.0-1.0>}}]}}"#
+    )
+}
+
+#[derive(Deserialize)]
+struct RawReport {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    gaps: Vec<RawGap>,
+}
+
+#[derive(Deserialize)]
+struct RawGap {
+    #[serde(default)]
+    kind: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    question: Option<String>,
+    #[serde(default)]
+    memories: Vec<serde_json::Value>,
+    #[serde(default)]
+    importance: Option<f32>,
+}
+
+/// Parse the LLM response into a summary and gaps, sorted by importance.
+///
+/// Memory numbers are resolved against `memories`; unknown numbers are dropped.
+/// An unparseable response yields an empty summary and no gaps.
+pub fn parse_knowledge_gaps(
+    response: &str,
+    memories: &[MemoryItem],
+) -> (String, Vec<KnowledgeGap>) {
+    let json = extract_json(response).unwrap_or_default();
+    let raw: RawReport = match serde_json::from_str(&json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::debug!("Failed to parse knowledge gaps: {}", e);
+            return (String::new(), Vec::new());
+        }
+    };
+
+    let mut gaps: Vec<KnowledgeGap> = raw
+        .gaps
+        .into_iter()
+        .filter(|g| !g.description.trim().is_empty())
+        .map(|g| KnowledgeGap {
+            kind: GapKind::from_str_flexible(&g.kind),
+            description: g.description.trim().to_string(),
+            question: g.question.map(|q| q.trim().to_string()).filter(|q| !q.is_empty()),
+            memory_ids: g
+                .memories
+                .iter()
+                .filter_map(|n| match n {
+                    serde_json::Value::Number(n) => n.as_u64(),
+                    serde_json::Value::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                })
+                .filter_map(|n| memories.get((n as usize).checked_sub(1)?))
+                .map(|m| m.id.clone())
+                .collect(),
+            importance: g.importance.unwrap_or(0.5).clamp(0.0, 1.0),
+        })
+        .collect();
+
+    gaps.sort_by(|a, b| {
+        b.importance
+            .partial_cmp(&a.importance)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    (raw.summary.trim().to_string(), gaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_knowledge_gaps() {
+        let memories = vec![
+            MemoryItem::new("m1", "Lives in Berlin"),
+            MemoryItem::new("m2", "Lives in Munich"),
+        ];
+        let response = r#"```json
+{"summary": "User location is recorded twice.",
+ "gaps": [
+   {"kind": "missing", "description": "Dietary restrictions", "question": "Any allergies?",
+    "memories": [], "importance": 0.4},
+   {"kind": "contradiction", "description": "Two different cities", "question": " ",
+    "memories": [1, "2", 7], "importance": 0.9},
+   {"kind": "stale", "description": ""}
+ ]}
+```"#;
+
+        let (summary, gaps) = parse_knowledge_gaps(response, &memories);
+        assert_eq!(summary, "User location is recorded twice.");
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0].kind, GapKind::Conflicting);
+        assert_eq!(gaps[0].memory_ids, vec!["m1", "m2"]);
+        assert!(gaps[0].question.is_none());
+        assert_eq!(gaps[1].kind, GapKind::Missing);
+        assert_eq!(gaps[1].question.as_deref(), Some("Any allergies?"));
+    }
+
+    #[test]
+    fn test_parse_knowledge_gaps_invalid() {
+        let (summary, gaps) = parse_knowledge_gaps("I cannot help with that.", &[]);
+        assert!(summary.is_empty());
+        assert!(gaps.is_empty());
+    }
+
+    #[test]
+    fn test_knowledge_gaps_prompt_numbers_memories() {
+        let mut item = MemoryItem::new("m1", "Prefers window seats");
+        item.updated_at = Some("2024-03-01".to_string());
+        let prompt = knowledge_gaps_prompt("booking a flight", &[item]);
+        assert!(prompt.contains("1. Prefers window seats (last updated: 2024-03-01)"));
+        assert!(knowledge_gaps_prompt("travel", &[]).contains("(no memories stored)"));
+    }
+}
@@ -0,0 +1,76 @@
+//! Replay of recent memories against older related ones.
+//!
+//! A sleep-replay analogue: [`Memory::replay`](super::Memory::replay) runs
+//! recently added, surprising memories through the embedder and vector
+//! search again to find the older memories they relate to. Each related pair
+//! is recorded as a co-activation in the cognitive store, whose links
+//! spreading activation follows, and older memories activated together with
+//! new ones repeatedly are strengthened.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::Memory;
+use crate::error::RookResult;
+use crate::runtime::MaintenanceJob;
+
+/// Replay settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayConfig {
+    /// Whether memories are replayed (default: false). Needs
+    /// `cognitive.enabled`.
+    pub enabled: bool,
+    /// Memories added within this many hours are replayed (default: 24).
+    pub window_hours: u64,
+    /// Surprise, one minus the similarity to the closest older memory, a
+    /// memory needs to be replayed (default: 0.3).
+    pub min_surprise: f32,
+    /// Older memories considered per replayed memory (default: 5).
+    pub neighbors: usize,
+    /// Similarity an older memory needs to be co-activated (default: 0.5).
+    pub min_similarity: f32,
+    /// Co-activations after which an older memory is strengthened
+    /// (default: 2).
+    pub strengthen_after: usize,
+    /// Most memories replayed per call (default: 50).
+    pub batch_size: usize,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_hours: 24,
+            min_surprise: 0.3,
+            neighbors: 5,
+            min_similarity: 0.5,
+            strengthen_after: 2,
+            batch_size: 50,
+        }
+    }
+}
+
+/// Maintenance job replaying recent memories.
+pub struct ReplayJob {
+    memory: Arc<Memory>,
+}
+
+impl ReplayJob {
+    pub fn new(memory: Arc<Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for ReplayJob {
+    fn name(&self) -> &str {
+        "memory_replay"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        self.memory.replay().await
+    }
+}
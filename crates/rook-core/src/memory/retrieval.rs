@@ -0,0 +1,117 @@
+//! Multi-signal retrieval for memory search.
+//!
+//! A search with a `retrieval_mode` filter entry goes through the
+//! [`RetrievalEngine`](crate::retrieval::RetrievalEngine) instead of plain
+//! vector search: Standard and Precise add spreading activation, Precise and
+//! Cognitive add FSRS retrievability. Candidates come from one filtered
+//! vector search, [`ACTIVATION_POOL`] times larger than needed, so memories
+//! reached only through the activation graph are still held to the search's
+//! scope and filters. A `mmr_lambda` entry diversifies the results with
+//! maximal marginal relevance, in Quick mode unless a mode is set.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::error::{RookError, RookResult};
+use crate::retrieval::{RetrievalMode, VectorSearcher};
+use crate::traits::{VectorSearchResult, VectorStore};
+
+/// Search filter entry selecting the retrieval mode (`quick`, `standard`,
+/// `precise` or `cognitive`).
+pub const RETRIEVAL_MODE_FILTER: &str = "retrieval_mode";
+
+/// Search filter entry diversifying results with MMR at the given lambda
+/// (0-1, higher favors relevance over diversity).
+pub const MMR_LAMBDA_FILTER: &str = "mmr_lambda";
+
+/// How many times the requested number of results is fetched as
+/// candidates for activation to pick from.
+pub const ACTIVATION_POOL: usize = 5;
+
+/// Parse the value of a `retrieval_mode` filter entry.
+pub fn parse_retrieval_mode(value: &serde_json::Value) -> RookResult<RetrievalMode> {
+    value
+        .as_str()
+        .ok_or_else(|| RookError::validation("retrieval_mode must be a string"))?
+        .parse()
+}
+
+/// Parse the value of a `mmr_lambda` filter entry.
+pub fn parse_mmr_lambda(value: &serde_json::Value) -> RookResult<f32> {
+    value
+        .as_f64()
+        .map(|lambda| lambda as f32)
+        .filter(|lambda| (0.0..=1.0).contains(lambda))
+        .ok_or_else(|| RookError::validation("mmr_lambda must be a number between 0 and 1"))
+}
+
+/// Vector searcher serving the engine from a pool of filtered search
+/// results.
+pub(crate) struct PooledSearcher {
+    store: Arc<dyn VectorStore>,
+    pool: Vec<VectorSearchResult>,
+}
+
+impl PooledSearcher {
+    /// `pool` must be sorted by score, highest first.
+    pub(crate) fn new(store: Arc<dyn VectorStore>, pool: Vec<VectorSearchResult>) -> Self {
+        Self { store, pool }
+    }
+}
+
+#[async_trait]
+impl VectorSearcher for PooledSearcher {
+    async fn search(&self, _query_embedding: &[f32], limit: usize) -> RookResult<Vec<(String, f32)>> {
+        Ok(self
+            .pool
+            .iter()
+            .take(limit)
+            .map(|r| (r.id.clone(), r.score))
+            .collect())
+    }
+
+    async fn get_embedding(&self, id: &str) -> RookResult<Option<Vec<f32>>> {
+        Ok(self
+            .store
+            .get(id)
+            .await?
+            .map(|record| record.vector)
+            .filter(|vector| !vector.is_empty()))
+    }
+
+    async fn get_embeddings(&self, ids: &[String]) -> RookResult<HashMap<String, Vec<f32>>> {
+        let mut embeddings = HashMap::new();
+        for id in ids {
+            if let Some(vector) = self.get_embedding(id).await? {
+                embeddings.insert(id.clone(), vector);
+            }
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_retrieval_mode() {
+        assert_eq!(
+            parse_retrieval_mode(&json!("cognitive")).unwrap(),
+            RetrievalMode::Cognitive
+        );
+        assert!(parse_retrieval_mode(&json!("fast")).is_err());
+        assert!(parse_retrieval_mode(&json!(3)).is_err());
+    }
+
+    #[test]
+    fn test_parse_mmr_lambda() {
+        assert_eq!(parse_mmr_lambda(&json!(0.5)).unwrap(), 0.5);
+        assert_eq!(parse_mmr_lambda(&json!(1)).unwrap(), 1.0);
+        assert!(parse_mmr_lambda(&json!(1.5)).is_err());
+        assert!(parse_mmr_lambda(&json!("0.5")).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+//! Run lifecycle: start, end-of-run summarization and cleanup.
+//!
+//! A run is started with [`crate::Memory::start_run`], which records who it
+//! belongs to. Ending it with [`crate::Memory::end_run`] summarizes the run's
+//! memories, copies the salient ones into the user's scope and schedules the
+//! rest for deletion after [`RunConfig::retention_secs`]. Deletion happens in
+//! [`crate::Memory::expire_runs`], which embedders call periodically.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::MemoryItem;
+
+use super::json_parser::extract_json;
+
+/// Payload field linking a promoted memory or run summary to its run.
+pub const RUN_SOURCE_FIELD: &str = "source_run_id";
+
+/// Payload field listing the memories a session summary was made from.
+pub const SUMMARY_SOURCES_FIELD: &str = "source_memory_ids";
+
+/// Deletion reason reported for memories archived into a session summary.
+pub const SUMMARIZED_REASON: &str = "summarized";
+
+/// Run lifecycle settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunConfig {
+    /// Summarize a run's memories when it ends (default: true).
+    pub summarize: bool,
+    /// Copy salient run memories into the user's scope when a run ends
+    /// (default: true).
+    pub promote: bool,
+    /// How long a run's memories are kept after it ends, in seconds
+    /// (default: 86400). Zero deletes them when the run ends.
+    pub retention_secs: u64,
+    /// Most recent run memories given to the summarizer (default: 50).
+    pub max_summary_memories: usize,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            summarize: true,
+            promote: true,
+            retention_secs: 24 * 60 * 60,
+            max_summary_memories: 50,
+        }
+    }
+}
+
+/// Overrides of [`RunConfig`] for ending one run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunEndOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summarize: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promote: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention_secs: Option<u64>,
+}
+
+/// The episodic memory summarizing a session, from
+/// [`crate::Memory::summarize_session`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub run_id: String,
+    /// The stored summary memory.
+    pub memory_id: String,
+    pub summary: String,
+    /// Memories the summary was made from, oldest first.
+    pub source_memory_ids: Vec<String>,
+    /// Whether the source memories were archived.
+    pub archived: bool,
+}
+
+/// A run's lifecycle record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub run_id: String,
+    /// User the run belongs to; salient memories are promoted to this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// End-of-run summary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// The summary stored as a memory in the user's scope.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_memory_id: Option<String>,
+    /// Memories created in the user's scope from salient run memories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub promoted_memory_ids: Vec<String>,
+    /// When the run's remaining memories are deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When they were deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expired_at: Option<DateTime<Utc>>,
+}
+
+impl RunRecord {
+    /// A run started now.
+    pub fn new(run_id: impl Into<String>) -> Self {
+        Self {
+            run_id: run_id.into(),
+            user_id: None,
+            agent_id: None,
+            metadata: HashMap::new(),
+            started_at: Utc::now(),
+            ended_at: None,
+            summary: None,
+            summary_memory_id: None,
+            promoted_memory_ids: Vec::new(),
+            expires_at: None,
+            expired_at: None,
+        }
+    }
+
+    /// Whether the run has not ended yet.
+    pub fn is_active(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
+
+/// Build the prompt asking the LLM to summarize a run and pick the memories
+/// worth keeping beyond it.
+///
+/// Memories are numbered from 1; the LLM refers to them by number.
+pub fn run_summary_prompt(memories: &[MemoryItem]) -> String {
+    let listing = memories
+        .iter()
+        .enumerate()
+        .map(|(i, m)| format!("{}. {}", i + 1, m.memory))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"You are closing a session between an assistant and a user. Below are the memories recorded during the session.
+
+SESSION MEMORIES:
+{listing}
+
+1. Summarize the session in two or three sentences.
+2. Pick the memories that stay useful after the session: lasting facts, preferences, decisions and commitments about the user. Skip details that only mattered within the session.
+
+Respond ONLY with a JSON object, no other text:
+{{"summary": "<summary>", "salient": [<memory numbers>]}}"#
+    )
+}
+
+#[derive(Deserialize)]
+struct RawRunSummary {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    salient: Vec<serde_json::Value>,
+}
+
+/// Parse the LLM response into a summary and the IDs of salient memories.
+///
+/// Memory numbers are resolved against `memories`; unknown numbers are dropped.
+/// An unparseable response yields an empty summary and no salient memories.
+pub fn parse_run_summary(response: &str, memories: &[MemoryItem]) -> (String, Vec<String>) {
+    let json = extract_json(response).unwrap_or_default();
+    let raw: RawRunSummary = match serde_json::from_str(&json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::debug!("Failed to parse run summary: {}", e);
+            return (String::new(), Vec::new());
+        }
+    };
+
+    let mut salient: Vec<String> = Vec::new();
+    for n in &raw.salient {
+        let n = match n {
+            serde_json::Value::Number(n) => n.as_u64(),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        };
+        let Some(memory) = n.and_then(|n| memories.get((n as usize).checked_sub(1)?)) else {
+            continue;
+        };
+        if !salient.contains(&memory.id) {
+            salient.push(memory.id.clone());
+        }
+    }
+
+    (raw.summary.trim().to_string(), salient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run_summary() {
+        let memories = vec![
+            MemoryItem::new("m1", "Is vegetarian"),
+            MemoryItem::new("m2", "Asked for a table at 8pm"),
+        ];
+        let response = r#"```json
+{"summary": " Booked dinner for a vegetarian user. ", "salient": [1, "1", "3", null]}
+```"#;
+
+        let (summary, salient) = parse_run_summary(response, &memories);
+        assert_eq!(summary, "Booked dinner for a vegetarian user.");
+        assert_eq!(salient, vec!["m1"]);
+
+        let (summary, salient) = parse_run_summary("No.", &memories);
+        assert!(summary.is_empty());
+        assert!(salient.is_empty());
+    }
+
+    #[test]
+    fn test_run_summary_prompt_numbers_memories() {
+        let prompt = run_summary_prompt(&[
+            MemoryItem::new("m1", "Is vegetarian"),
+            MemoryItem::new("m2", "Asked for a table at 8pm"),
+        ]);
+        assert!(prompt.contains("1. Is vegetarian\n2. Asked for a table at 8pm"));
+    }
+
+    #[test]
+    fn test_run_record_serialization() {
+        let mut record = RunRecord::new("run1");
+        assert!(record.is_active());
+        let json = serde_json::to_value(&record).unwrap();
+        assert!(json.get("ended_at").is_none());
+        assert!(json.get("promoted_memory_ids").is_none());
+
+        record.ended_at = Some(Utc::now());
+        record.promoted_memory_ids = vec!["m1".to_string()];
+        assert!(!record.is_active());
+        let back: RunRecord = serde_json::from_value(serde_json::to_value(&record).unwrap()).unwrap();
+        assert_eq!(back, record);
+    }
+}
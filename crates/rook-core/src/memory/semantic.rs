@@ -0,0 +1,186 @@
+//! Promotion of consolidated episodic memories to semantic memory.
+//!
+//! The [`ConsolidationManager`](crate::consolidation::ConsolidationManager)
+//! queues memories as they reach the Consolidated phase.
+//! [`Memory::promote_consolidated`](super::Memory::promote_consolidated)
+//! then has the LLM restate each scope's queued memories as general facts,
+//! stored as semantic memories that link back to the episodic originals
+//! through [`SUMMARY_SOURCES_FIELD`](super::SUMMARY_SOURCES_FIELD).
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::json_parser::extract_json;
+use super::Memory;
+use crate::error::RookResult;
+use crate::runtime::MaintenanceJob;
+use crate::types::MemoryItem;
+
+/// `memory_type` of memories created by promotion.
+pub const SEMANTIC_MEMORY_TYPE: &str = "semantic_memory";
+
+/// Semantic promotion settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticPromotionConfig {
+    /// Whether consolidated memories are promoted (default: false). Needs
+    /// `cognitive.enabled` and consolidation running on the same cognitive
+    /// store, e.g. the background runtime's.
+    pub enabled: bool,
+    /// Most queued memories restated per call (default: 50).
+    pub batch_size: usize,
+}
+
+impl Default for SemanticPromotionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            batch_size: 50,
+        }
+    }
+}
+
+/// Build the prompt asking the LLM to generalize episodic memories into
+/// semantic facts.
+///
+/// Memories are numbered from 1; the LLM refers to them by number.
+pub fn semantic_promotion_prompt(memories: &[MemoryItem]) -> String {
+    let listing = memories
+        .iter()
+        .enumerate()
+        .map(|(i, m)| format!("{}. {}", i + 1, m.memory))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"You are consolidating long-term memory. Below are episodic memories: specific things that were said or happened.
+
+EPISODIC MEMORIES:
+{listing}
+
+Restate what they reveal as general, lasting knowledge: facts, preferences, habits and relationships that hold beyond the individual events. Merge memories that say the same thing and leave out one-off details. Each statement must stand on its own; don't add anything the memories don't support. Cite the numbers of the memories each statement is based on.
+
+Respond ONLY with a JSON object, no other text:
+{{"facts": [{{"fact": "<general statement>", "sources": [<memory numbers>]}}]}}"#
+    )
+}
+
+#[derive(Deserialize)]
+struct RawPromotion {
+    #[serde(default)]
+    facts: Vec<RawFact>,
+}
+
+#[derive(Deserialize)]
+struct RawFact {
+    #[serde(default)]
+    fact: String,
+    #[serde(default)]
+    sources: Vec<serde_json::Value>,
+}
+
+/// Parse the LLM response into facts and the IDs of the memories each is
+/// based on.
+///
+/// Memory numbers are resolved against `memories`; unknown numbers are
+/// dropped, and so are facts left without sources. An unparseable response
+/// yields no facts.
+pub fn parse_semantic_promotion(response: &str, memories: &[MemoryItem]) -> Vec<(String, Vec<String>)> {
+    let json = extract_json(response).unwrap_or_default();
+    let raw: RawPromotion = match serde_json::from_str(&json) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::debug!("Failed to parse semantic promotion: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut facts = Vec::new();
+    for raw_fact in raw.facts {
+        let fact = raw_fact.fact.trim();
+        if fact.is_empty() {
+            continue;
+        }
+        let mut sources: Vec<String> = Vec::new();
+        for n in &raw_fact.sources {
+            let n = match n {
+                serde_json::Value::Number(n) => n.as_u64(),
+                serde_json::Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            };
+            let Some(memory) = n.and_then(|n| memories.get((n as usize).checked_sub(1)?)) else {
+                continue;
+            };
+            if !sources.contains(&memory.id) {
+                sources.push(memory.id.clone());
+            }
+        }
+        if !sources.is_empty() {
+            facts.push((fact.to_string(), sources));
+        }
+    }
+    facts
+}
+
+/// Maintenance job promoting consolidated memories to semantic memory.
+pub struct SemanticPromotionJob {
+    memory: Arc<Memory>,
+}
+
+impl SemanticPromotionJob {
+    pub fn new(memory: Arc<Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for SemanticPromotionJob {
+    fn name(&self) -> &str {
+        "semantic_promotion"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        self.memory.promote_consolidated().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_semantic_promotion() {
+        let memories = vec![
+            MemoryItem::new("m1", "Ordered a vegetarian pizza on Friday"),
+            MemoryItem::new("m2", "Asked for a meat-free option at lunch"),
+        ];
+        let response = r#"```json
+{"facts": [
+  {"fact": " Is vegetarian. ", "sources": [1, "2", 2, 7]},
+  {"fact": "Likes pizza", "sources": [9]},
+  {"fact": "", "sources": [1]}
+]}
+```"#;
+
+        let facts = parse_semantic_promotion(response, &memories);
+        assert_eq!(
+            facts,
+            vec![("Is vegetarian.".to_string(), vec!["m1".to_string(), "m2".to_string()])]
+        );
+
+        assert!(parse_semantic_promotion("Nothing to add.", &memories).is_empty());
+    }
+
+    #[test]
+    fn test_semantic_promotion_prompt_numbers_memories() {
+        let prompt = semantic_promotion_prompt(&[
+            MemoryItem::new("m1", "Ordered a vegetarian pizza on Friday"),
+            MemoryItem::new("m2", "Asked for a meat-free option at lunch"),
+        ]);
+        assert!(prompt.contains(
+            "1. Ordered a vegetarian pizza on Friday\n2. Asked for a meat-free option at lunch"
+        ));
+    }
+}
@@ -131,6 +131,20 @@ impl SessionScope {
     }
 }
 
+/// Records moved by [`crate::Memory::reassign_scope`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScopeReassignment {
+    /// IDs of the memories moved.
+    pub memory_ids: Vec<String>,
+    /// Graph entities moved, including those merged into an entity of the
+    /// same name in the new scope.
+    pub entities: usize,
+    /// Run records moved.
+    pub runs: usize,
+    /// Promotion requests whose contributor changed.
+    pub promotions: usize,
+}
+
 /// Build filters and metadata from session scope and input.
 pub fn build_filters_and_metadata(
     user_id: Option<String>,
@@ -0,0 +1,36 @@
+//! Background application of strength signals.
+//!
+//! Strength signals (a memory used in a response, confirmed, corrected, ...)
+//! only queue a grade for the memory. [`StrengthUpdateJob`] applies the
+//! queued grades as reviews on an interval, so memories are strengthened or
+//! weakened without waiting for their next access.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::Memory;
+use crate::error::RookResult;
+use crate::runtime::MaintenanceJob;
+
+/// Maintenance job applying pending strength signal grades.
+pub struct StrengthUpdateJob {
+    memory: Arc<Memory>,
+}
+
+impl StrengthUpdateJob {
+    pub fn new(memory: Arc<Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for StrengthUpdateJob {
+    fn name(&self) -> &str {
+        "strength_updates"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        self.memory.apply_strength_updates().await
+    }
+}
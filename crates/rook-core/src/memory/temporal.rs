@@ -0,0 +1,258 @@
+//! Time ranges and recency boosting for memory search.
+//!
+//! A search with `since` and/or `until` filter entries (RFC 3339
+//! timestamps) only returns memories created in that range, so agents can
+//! ask for "memories from this week". Stores that compare timestamps
+//! themselves ([`VectorStore::supports_timestamp_filters`]) get the range as
+//! part of the search filter; results from other stores are filtered after
+//! the search. A `recency_half_life_days` entry multiplies each result's
+//! score by `0.5^(age_days / half_life_days)`, so newer memories rank higher.
+//!
+//! [`VectorStore::supports_timestamp_filters`]: crate::traits::VectorStore::supports_timestamp_filters
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{RookError, RookResult};
+use crate::types::{Filter, MemoryItem};
+
+/// Search filter entry keeping memories created at or after a time.
+pub const SINCE_FILTER: &str = "since";
+
+/// Search filter entry keeping memories created before a time.
+pub const UNTIL_FILTER: &str = "until";
+
+/// Search filter entry boosting recent memories, halving a memory's score
+/// for every this many days of age.
+pub const RECENCY_HALF_LIFE_FILTER: &str = "recency_half_life_days";
+
+/// Payload field the time range applies to.
+const CREATED_AT_FIELD: &str = "created_at";
+
+/// Range of creation times accepted by a search. `since` is inclusive,
+/// `until` exclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Remove the `since` and `until` entries from search filters and parse
+    /// them.
+    pub fn take_from(filters: &mut HashMap<String, serde_json::Value>) -> RookResult<Self> {
+        let range = Self {
+            since: filters
+                .remove(SINCE_FILTER)
+                .map(|value| parse_timestamp(SINCE_FILTER, &value))
+                .transpose()?,
+            until: filters
+                .remove(UNTIL_FILTER)
+                .map(|value| parse_timestamp(UNTIL_FILTER, &value))
+                .transpose()?,
+        };
+        if let (Some(since), Some(until)) = (range.since, range.until) {
+            if since >= until {
+                return Err(RookError::validation("since must be before until"));
+            }
+        }
+        Ok(range)
+    }
+
+    /// Put the range back into search filters as RFC 3339 UTC timestamps.
+    pub fn insert_into(&self, filters: &mut HashMap<String, serde_json::Value>) {
+        if let Some(since) = self.since {
+            filters.insert(SINCE_FILTER.to_string(), since.to_rfc3339().into());
+        }
+        if let Some(until) = self.until {
+            filters.insert(UNTIL_FILTER.to_string(), until.to_rfc3339().into());
+        }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// Store filter conditions for the range, if it is bounded.
+    pub fn to_filter(&self) -> Option<Filter> {
+        let mut conditions = Vec::new();
+        if let Some(since) = self.since {
+            conditions.push(Filter::gte(CREATED_AT_FIELD, since.to_rfc3339()));
+        }
+        if let Some(until) = self.until {
+            conditions.push(Filter::lt(CREATED_AT_FIELD, until.to_rfc3339()));
+        }
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(Filter::And(conditions)),
+        }
+    }
+
+    /// Whether a payload's `created_at` falls in the range. Payloads without
+    /// a readable `created_at` only match an unbounded range.
+    pub fn contains(&self, payload: &HashMap<String, serde_json::Value>) -> bool {
+        if self.is_unbounded() {
+            return true;
+        }
+        payload
+            .get(CREATED_AT_FIELD)
+            .and_then(|v| v.as_str())
+            .and_then(parse_created_at)
+            .is_some_and(|created_at| {
+                self.since.filter(|since| created_at < *since).is_none()
+                    && self.until.filter(|until| created_at >= *until).is_none()
+            })
+    }
+}
+
+fn parse_timestamp(name: &str, value: &serde_json::Value) -> RookResult<DateTime<Utc>> {
+    value
+        .as_str()
+        .and_then(parse_created_at)
+        .ok_or_else(|| RookError::validation(format!("{} must be an RFC 3339 timestamp", name)))
+}
+
+fn parse_created_at(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Parse the value of a `recency_half_life_days` filter entry.
+pub fn parse_recency_half_life(value: &serde_json::Value) -> RookResult<f32> {
+    value
+        .as_f64()
+        .map(|days| days as f32)
+        .filter(|days| days.is_finite() && *days > 0.0)
+        .ok_or_else(|| {
+            RookError::validation("recency_half_life_days must be a positive number")
+        })
+}
+
+/// Score multiplier for a memory created at `created_at`: 1.0 for a new
+/// memory, halving every `half_life_days`. Memories without a readable
+/// creation time aren't boosted or penalized.
+pub fn recency_weight(created_at: Option<&str>, half_life_days: f32, now: DateTime<Utc>) -> f32 {
+    let Some(created_at) = created_at.and_then(parse_created_at) else {
+        return 1.0;
+    };
+    let age_days = (now - created_at).num_seconds().max(0) as f32 / 86_400.0;
+    0.5f32.powf(age_days / half_life_days)
+}
+
+/// Multiply each scored memory's score by its recency weight and re-sort
+/// by score.
+pub fn apply_recency_boost(memories: &mut [MemoryItem], half_life_days: f32, now: DateTime<Utc>) {
+    for memory in memories.iter_mut() {
+        if let Some(score) = memory.score {
+            let weight = recency_weight(memory.created_at.as_deref(), half_life_days, now);
+            memory.score = Some(score * weight);
+        }
+    }
+    memories.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+
+    fn payload(created_at: DateTime<Utc>) -> HashMap<String, serde_json::Value> {
+        HashMap::from([(CREATED_AT_FIELD.to_string(), json!(created_at.to_rfc3339()))])
+    }
+
+    #[test]
+    fn test_take_from() {
+        let mut filters = HashMap::from([
+            ("user_id".to_string(), json!("alice")),
+            (SINCE_FILTER.to_string(), json!("2024-03-01T00:00:00+02:00")),
+        ]);
+        let range = TimeRange::take_from(&mut filters).unwrap();
+        assert_eq!(range.since.unwrap().to_rfc3339(), "2024-02-29T22:00:00+00:00");
+        assert!(range.until.is_none());
+        assert_eq!(filters.len(), 1);
+
+        range.insert_into(&mut filters);
+        assert_eq!(filters[SINCE_FILTER], json!("2024-02-29T22:00:00+00:00"));
+
+        let mut bad = HashMap::from([(UNTIL_FILTER.to_string(), json!("last week"))]);
+        assert!(TimeRange::take_from(&mut bad).is_err());
+
+        let mut reversed = HashMap::from([
+            (SINCE_FILTER.to_string(), json!("2024-03-02T00:00:00Z")),
+            (UNTIL_FILTER.to_string(), json!("2024-03-01T00:00:00Z")),
+        ]);
+        assert!(TimeRange::take_from(&mut reversed).is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        let now = Utc::now();
+        let range = TimeRange {
+            since: Some(now - Duration::days(7)),
+            until: Some(now),
+        };
+
+        assert!(range.contains(&payload(now - Duration::days(7))));
+        assert!(range.contains(&payload(now - Duration::days(1))));
+        assert!(!range.contains(&payload(now)));
+        assert!(!range.contains(&payload(now - Duration::days(8))));
+        assert!(!range.contains(&HashMap::new()));
+        assert!(TimeRange::default().contains(&HashMap::new()));
+    }
+
+    #[test]
+    fn test_to_filter() {
+        assert!(TimeRange::default().to_filter().is_none());
+
+        let since = Utc::now();
+        let range = TimeRange {
+            since: Some(since),
+            until: None,
+        };
+        match range.to_filter() {
+            Some(Filter::Condition(cond)) => assert_eq!(cond.field, CREATED_AT_FIELD),
+            other => panic!("unexpected filter: {:?}", other),
+        }
+        let range = TimeRange {
+            since: Some(since),
+            until: Some(since + Duration::days(1)),
+        };
+        assert!(matches!(range.to_filter(), Some(Filter::And(conditions)) if conditions.len() == 2));
+    }
+
+    #[test]
+    fn test_parse_recency_half_life() {
+        assert_eq!(parse_recency_half_life(&json!(7)).unwrap(), 7.0);
+        assert!(parse_recency_half_life(&json!(0)).is_err());
+        assert!(parse_recency_half_life(&json!("7")).is_err());
+    }
+
+    #[test]
+    fn test_recency_boost() {
+        let now = Utc::now();
+        let week_old = (now - Duration::days(7)).to_rfc3339();
+        assert!((recency_weight(Some(&week_old), 7.0, now) - 0.5).abs() < 1e-3);
+        assert_eq!(recency_weight(None, 7.0, now), 1.0);
+
+        let mut old = MemoryItem::new("old", "Lived in Paris");
+        old.score = Some(0.9);
+        old.created_at = Some((now - Duration::days(30)).to_rfc3339());
+        let mut recent = MemoryItem::new("recent", "Moved to Lisbon");
+        recent.score = Some(0.6);
+        recent.created_at = Some(now.to_rfc3339());
+
+        let mut memories = vec![old, recent];
+        apply_recency_boost(&mut memories, 7.0, now);
+        assert_eq!(memories[0].id, "recent");
+        assert!(memories[1].score.unwrap() < 0.1);
+    }
+}
@@ -0,0 +1,328 @@
+//! In-process providers for testing [`Memory`] without external services.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::config::MemoryConfig;
+use crate::error::RookResult;
+use crate::memory::Memory;
+use crate::traits::{
+    CollectionInfo, DistanceMetric, Embedder, EmbeddingAction, GenerationOptions, Llm, LlmResponse,
+    LlmStream, Tool, ToolChoice, VectorRecord, VectorSearchResult, VectorStore,
+};
+use crate::types::{Filter, FilterOperator, Message};
+
+/// Dimension of [`TestEmbedder`] vectors.
+pub const TEST_DIMENSION: usize = 32;
+
+/// LLM answering every request with an empty response, so classification
+/// falls back to its defaults.
+pub struct TestLlm;
+
+#[async_trait]
+impl Llm for TestLlm {
+    async fn generate(
+        &self,
+        _messages: &[Message],
+        _options: Option<GenerationOptions>,
+    ) -> RookResult<LlmResponse> {
+        Ok(LlmResponse::default())
+    }
+
+    async fn generate_with_tools(
+        &self,
+        _messages: &[Message],
+        _tools: &[Tool],
+        _tool_choice: ToolChoice,
+        _options: Option<GenerationOptions>,
+    ) -> RookResult<LlmResponse> {
+        Ok(LlmResponse::default())
+    }
+
+    async fn generate_stream(
+        &self,
+        _messages: &[Message],
+        _options: Option<GenerationOptions>,
+    ) -> RookResult<LlmStream> {
+        unimplemented!()
+    }
+
+    fn model_name(&self) -> &str {
+        "test"
+    }
+}
+
+/// Bag-of-words embedder: texts sharing words have similar vectors.
+pub struct TestEmbedder;
+
+#[async_trait]
+impl Embedder for TestEmbedder {
+    async fn embed(&self, text: &str, _action: Option<EmbeddingAction>) -> RookResult<Vec<f32>> {
+        let mut vector = vec![0.0f32; TEST_DIMENSION];
+        for word in text.split_whitespace() {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            let bucket = md5::compute(word.as_bytes()).0[0] as usize % TEST_DIMENSION;
+            vector[bucket] += 1.0;
+        }
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        Ok(vector)
+    }
+
+    fn dimension(&self) -> usize {
+        TEST_DIMENSION
+    }
+
+    fn model_name(&self) -> &str {
+        "test"
+    }
+}
+
+/// Vector store keeping records in memory, in insertion order.
+#[derive(Default)]
+pub struct InMemoryVectorStore {
+    records: Mutex<Vec<VectorRecord>>,
+    timestamp_filters: bool,
+    list_filters: Mutex<Vec<Option<Filter>>>,
+}
+
+impl InMemoryVectorStore {
+    /// A store claiming to filter timestamps itself, comparing UTC
+    /// timestamps as text.
+    pub fn with_timestamp_filters() -> Self {
+        Self {
+            timestamp_filters: true,
+            ..Self::default()
+        }
+    }
+
+    /// Records as stored, without the decoding `Memory` applies.
+    pub fn records(&self) -> Vec<VectorRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// Filters passed to `list`, in call order.
+    pub fn list_filters(&self) -> Vec<Option<Filter>> {
+        self.list_filters.lock().unwrap().clone()
+    }
+}
+
+fn matches(payload: &HashMap<String, Value>, filter: &Filter) -> bool {
+    match filter {
+        Filter::Condition(cond) => {
+            let value = payload.get(&cond.field).filter(|v| !v.is_null());
+            match &cond.operator {
+                FilterOperator::Eq(v) => value == Some(v),
+                FilterOperator::Ne(v) => value != Some(v),
+                FilterOperator::In(values) => value.is_some_and(|v| values.contains(v)),
+                FilterOperator::Nin(values) => !value.is_some_and(|v| values.contains(v)),
+                FilterOperator::Contains(s) => {
+                    value.and_then(Value::as_str).is_some_and(|v| v.contains(s))
+                }
+                FilterOperator::Icontains(s) => value
+                    .and_then(Value::as_str)
+                    .is_some_and(|v| v.to_lowercase().contains(&s.to_lowercase())),
+                FilterOperator::Gt(v) => compare(value, v).is_some_and(|o| o.is_gt()),
+                FilterOperator::Gte(v) => compare(value, v).is_some_and(|o| o.is_ge()),
+                FilterOperator::Lt(v) => compare(value, v).is_some_and(|o| o.is_lt()),
+                FilterOperator::Lte(v) => compare(value, v).is_some_and(|o| o.is_le()),
+                FilterOperator::Between { min, max } => {
+                    compare(value, min).is_some_and(|o| o.is_ge())
+                        && compare(value, max).is_some_and(|o| o.is_le())
+                }
+                FilterOperator::IsNull => value.is_none(),
+                FilterOperator::IsNotNull => value.is_some(),
+                FilterOperator::Exists => payload.contains_key(&cond.field),
+                FilterOperator::NotExists => !payload.contains_key(&cond.field),
+                FilterOperator::Wildcard => true,
+            }
+        }
+        Filter::And(filters) => filters.iter().all(|f| matches(payload, f)),
+        Filter::Or(filters) => filters.iter().any(|f| matches(payload, f)),
+        Filter::Not(filter) => !matches(payload, filter),
+    }
+}
+
+/// Order numbers by value and strings (RFC 3339 timestamps included) as text.
+fn compare(value: Option<&Value>, other: &Value) -> Option<std::cmp::Ordering> {
+    match (value?, other) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+#[async_trait]
+impl VectorStore for InMemoryVectorStore {
+    async fn create_collection(
+        &self,
+        _name: &str,
+        _dimension: usize,
+        _distance: DistanceMetric,
+    ) -> RookResult<()> {
+        Ok(())
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> RookResult<()> {
+        let mut stored = self.records.lock().unwrap();
+        for record in records {
+            stored.retain(|r| r.id != record.id);
+            stored.push(record);
+        }
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        query_vector: &[f32],
+        limit: usize,
+        filters: Option<Filter>,
+    ) -> RookResult<Vec<VectorSearchResult>> {
+        let mut results: Vec<VectorSearchResult> = self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| filters.as_ref().map_or(true, |f| matches(&r.payload, f)))
+            .map(|r| VectorSearchResult {
+                id: r.id.clone(),
+                score: cosine(query_vector, &r.vector),
+                payload: r.payload.clone(),
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get(&self, id: &str) -> RookResult<Option<VectorRecord>> {
+        Ok(self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|r| r.id == id)
+            .cloned())
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        vector: Option<Vec<f32>>,
+        payload: Option<HashMap<String, Value>>,
+    ) -> RookResult<()> {
+        let mut stored = self.records.lock().unwrap();
+        if let Some(record) = stored.iter_mut().find(|r| r.id == id) {
+            if let Some(vector) = vector {
+                record.vector = vector;
+            }
+            if let Some(payload) = payload {
+                record.payload = payload;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> RookResult<()> {
+        self.records.lock().unwrap().retain(|r| r.id != id);
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        filters: Option<Filter>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        self.list_filters.lock().unwrap().push(filters.clone());
+        Ok(self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| filters.as_ref().map_or(true, |f| matches(&r.payload, f)))
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+
+    async fn list_collections(&self) -> RookResult<Vec<String>> {
+        Ok(vec!["test".to_string()])
+    }
+
+    async fn delete_collection(&self, _name: &str) -> RookResult<()> {
+        self.records.lock().unwrap().clear();
+        Ok(())
+    }
+
+    async fn collection_info(&self, name: &str) -> RookResult<CollectionInfo> {
+        Ok(CollectionInfo {
+            name: name.to_string(),
+            vector_count: self.records.lock().unwrap().len() as u64,
+            dimension: TEST_DIMENSION,
+            distance: DistanceMetric::Cosine,
+        })
+    }
+
+    async fn reset(&self) -> RookResult<()> {
+        self.records.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn collection_name(&self) -> &str {
+        "test"
+    }
+
+    fn supports_timestamp_filters(&self) -> bool {
+        self.timestamp_filters
+    }
+}
+
+/// Config keeping every database in memory.
+pub fn test_config() -> MemoryConfig {
+    MemoryConfig {
+        history_db_path: ":memory:".into(),
+        ..MemoryConfig::default()
+    }
+}
+
+/// A memory over [`TestLlm`], [`TestEmbedder`] and an in-memory store, with
+/// the store for inspecting what was written.
+pub fn test_memory(config: MemoryConfig) -> (Memory, Arc<InMemoryVectorStore>) {
+    test_memory_with_store(config, InMemoryVectorStore::default())
+}
+
+/// A memory as from [`test_memory`], over the given store.
+pub fn test_memory_with_store(
+    config: MemoryConfig,
+    store: InMemoryVectorStore,
+) -> (Memory, Arc<InMemoryVectorStore>) {
+    let store = Arc::new(store);
+    let memory = Memory::new(
+        config,
+        Arc::new(TestLlm),
+        Arc::new(TestEmbedder),
+        store.clone(),
+        None,
+        None,
+    )
+    .unwrap();
+    (memory, store)
+}
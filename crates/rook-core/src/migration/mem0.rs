@@ -157,8 +157,12 @@ fn convert_mem0_to_rook(mem0: Mem0Memory) -> MemoryItem {
         updated_at: mem0.updated_at,
         category: None, // Will be classified during import
         is_key: false,
+        pinned: false,
+        surfaced_by: None,
         memory_state: None,
         dual_strength: None,
+        retrievability: None,
+        explanation: None,
     }
 }
 
@@ -0,0 +1,715 @@
+//! Bulk ingestion of a directory tree.
+//!
+//! Walks a directory, filters files by glob, and ingests each one through a
+//! [`MultimodalIngester`] with bounded concurrency. A manifest of content
+//! hashes is written next to the files (or wherever configured) after every
+//! file, so an interrupted run resumes where it stopped and a re-run only
+//! ingests files that are new or have changed.
+
+use crate::error::{RookError, RookResult};
+use crate::memory::Memory;
+use crate::multimodal::fetch::{mime_from_extension, sniff_content};
+use crate::multimodal::ingest::MultimodalIngester;
+use crate::multimodal::types::BulkIngestConfig;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use walkdir::WalkDir;
+
+/// Default manifest file name, in the ingested directory.
+pub const MANIFEST_FILE: &str = ".rook-manifest.json";
+
+/// Outcome of ingesting one file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    /// Ingested as new memories.
+    Ingested,
+    /// Content matches the manifest; not ingested again.
+    Unchanged,
+    /// No extractor handles the file's type.
+    Unsupported,
+    /// Larger than `max_file_bytes`.
+    TooLarge,
+    /// Reading, extraction or storage failed.
+    Failed,
+}
+
+impl FileStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ingested => "ingested",
+            Self::Unchanged => "unchanged",
+            Self::Unsupported => "unsupported",
+            Self::TooLarge => "too_large",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What happened to one file of a bulk ingestion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileReport {
+    /// Path relative to the ingested directory, with `/` separators
+    pub path: String,
+    /// Outcome
+    pub status: FileStatus,
+    /// File size in bytes
+    pub size: u64,
+    /// Detected MIME type, if the file was read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Memories holding the file's content, including those from an earlier
+    /// run for unchanged files
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub memory_ids: Vec<String>,
+    /// Chunks created by this run
+    pub chunks_created: usize,
+    /// Time spent on the file, in milliseconds
+    pub duration_ms: u64,
+    /// Why the file failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Warnings from extraction
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub warnings: Vec<String>,
+}
+
+impl FileReport {
+    fn new(path: String, status: FileStatus, size: u64) -> Self {
+        Self {
+            path,
+            status,
+            size,
+            mime_type: None,
+            memory_ids: Vec::new(),
+            chunks_created: 0,
+            duration_ms: 0,
+            error: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Summary of a bulk ingestion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkIngestReport {
+    /// Directory that was ingested
+    pub root: PathBuf,
+    /// Manifest the content hashes were recorded in
+    pub manifest_path: PathBuf,
+    /// Files matching the filters
+    pub total: usize,
+    /// Files ingested by this run
+    pub ingested: usize,
+    /// Files skipped because they were unchanged
+    pub unchanged: usize,
+    /// Files skipped because they were unsupported or too large
+    pub skipped: usize,
+    /// Files that failed
+    pub failed: usize,
+    /// Chunks created by this run
+    pub chunks_created: usize,
+    /// Bytes of content ingested by this run
+    pub bytes_ingested: u64,
+    /// Per-file outcomes, in path order
+    pub files: Vec<FileReport>,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// When the run finished
+    pub finished_at: DateTime<Utc>,
+}
+
+impl BulkIngestReport {
+    fn new(root: PathBuf, manifest_path: PathBuf, total: usize) -> Self {
+        let now = Utc::now();
+        Self {
+            root,
+            manifest_path,
+            total,
+            ingested: 0,
+            unchanged: 0,
+            skipped: 0,
+            failed: 0,
+            chunks_created: 0,
+            bytes_ingested: 0,
+            files: Vec::with_capacity(total),
+            started_at: now,
+            finished_at: now,
+        }
+    }
+
+    fn record(&mut self, file: FileReport) {
+        match file.status {
+            FileStatus::Ingested => {
+                self.ingested += 1;
+                self.bytes_ingested += file.size;
+            }
+            FileStatus::Unchanged => self.unchanged += 1,
+            FileStatus::Unsupported | FileStatus::TooLarge => self.skipped += 1,
+            FileStatus::Failed => self.failed += 1,
+        }
+        self.chunks_created += file.chunks_created;
+        self.files.push(file);
+    }
+}
+
+/// Progress of a bulk ingestion, reported after each file.
+#[derive(Debug, Clone, Copy)]
+pub struct BulkProgress<'a> {
+    /// Files finished so far
+    pub completed: usize,
+    /// Files matching the filters
+    pub total: usize,
+    /// The file just finished
+    pub file: &'a FileReport,
+}
+
+type ProgressCallback = Arc<dyn Fn(&BulkProgress<'_>) + Send + Sync>;
+
+/// Content hashes of ingested files, keyed by relative path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestManifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+/// A file recorded in the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Hex SHA-256 of the file's content
+    pub sha256: String,
+    /// File size in bytes
+    pub size: u64,
+    /// Memories created from the file
+    pub memory_ids: Vec<String>,
+    /// Document record linking the memories, when the file was split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    /// When the file was ingested
+    pub ingested_at: DateTime<Utc>,
+}
+
+impl IngestManifest {
+    /// Load a manifest, or start an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> RookResult<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                RookError::validation(format!("Invalid ingest manifest {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the manifest, replacing the previous one atomically.
+    pub fn save(&self, path: &Path) -> RookResult<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// A file found by the directory walk.
+#[derive(Debug, Clone)]
+struct FoundFile {
+    path: PathBuf,
+    relative: String,
+    size: u64,
+}
+
+/// Ingests every matching file in a directory tree.
+///
+/// # Example
+///
+/// ```ignore
+/// use rook_core::multimodal::{BulkIngestConfig, BulkIngester, MultimodalIngester};
+///
+/// let config = BulkIngestConfig::default()
+///     .include("**/*.pdf")
+///     .include("**/*.md")
+///     .exclude("**/archive")
+///     .with_concurrency(8);
+///
+/// let report = BulkIngester::new(MultimodalIngester::new(), config)
+///     .on_progress(|p| println!("[{}/{}] {} {}", p.completed, p.total, p.file.status, p.file.path))
+///     .ingest_dir(&memory, "./docs", "user_123", None)
+///     .await?;
+///
+/// println!("{} ingested, {} unchanged", report.ingested, report.unchanged);
+/// ```
+pub struct BulkIngester {
+    ingester: MultimodalIngester,
+    config: BulkIngestConfig,
+    progress: Option<ProgressCallback>,
+}
+
+impl BulkIngester {
+    /// Create a bulk ingester that ingests files through `ingester`
+    pub fn new(ingester: MultimodalIngester, config: BulkIngestConfig) -> Self {
+        Self {
+            ingester,
+            config,
+            progress: None,
+        }
+    }
+
+    /// Call `callback` after each file is finished
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&BulkProgress<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Ingest every matching file under `root`
+    ///
+    /// Files whose content hash matches the manifest are skipped. A changed
+    /// file is ingested again and the memories from its previous version are
+    /// deleted. Memories of files that have since been removed are kept.
+    /// Every memory carries a `source_path` with the file's path relative to
+    /// `root`, alongside the usual provenance metadata.
+    ///
+    /// Failures of individual files are recorded in the report; only an
+    /// unreadable root or manifest, or invalid glob patterns, fail the run.
+    pub async fn ingest_dir(
+        &self,
+        memory: &Memory,
+        root: impl AsRef<Path>,
+        user_id: &str,
+        additional_metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<BulkIngestReport> {
+        let root = root.as_ref();
+        if !root.is_dir() {
+            return Err(RookError::validation(format!(
+                "{} is not a directory",
+                root.display()
+            )));
+        }
+        let manifest_path = self
+            .config
+            .manifest_path
+            .clone()
+            .unwrap_or_else(|| root.join(MANIFEST_FILE));
+        let mut manifest = IngestManifest::load(&manifest_path)?;
+
+        let files = {
+            let root = root.to_path_buf();
+            let config = self.config.clone();
+            let manifest_path = manifest_path.clone();
+            tokio::task::spawn_blocking(move || find_files(&root, &config, &manifest_path))
+                .await
+                .map_err(|e| RookError::internal(format!("Directory walk panicked: {}", e)))??
+        };
+
+        let mut report =
+            BulkIngestReport::new(root.to_path_buf(), manifest_path.clone(), files.len());
+        // Pair files with their manifest entries up front, as the manifest is
+        // updated while files are still being ingested
+        let files: Vec<_> = files
+            .into_iter()
+            .map(|file| {
+                let previous = manifest.files.get(&file.relative).cloned();
+                (file, previous)
+            })
+            .collect();
+        let jobs = files.into_iter().map(|(file, previous)| {
+            self.ingest_file(
+                memory,
+                file,
+                previous,
+                user_id,
+                additional_metadata.as_ref(),
+            )
+        });
+        let mut results = stream::iter(jobs).buffer_unordered(self.config.concurrency.max(1));
+
+        while let Some((file, entry)) = results.next().await {
+            if let Some(entry) = entry {
+                manifest.files.insert(file.path.clone(), entry);
+                manifest.save(&manifest_path)?;
+            }
+            if let Some(ref callback) = self.progress {
+                callback(&BulkProgress {
+                    completed: report.files.len() + 1,
+                    total: report.total,
+                    file: &file,
+                });
+            }
+            report.record(file);
+        }
+
+        report.files.sort_by(|a, b| a.path.cmp(&b.path));
+        report.finished_at = Utc::now();
+        Ok(report)
+    }
+
+    /// Ingest one file, returning its report and, if it was ingested, its
+    /// new manifest entry
+    async fn ingest_file(
+        &self,
+        memory: &Memory,
+        file: FoundFile,
+        previous: Option<ManifestEntry>,
+        user_id: &str,
+        additional_metadata: Option<&HashMap<String, serde_json::Value>>,
+    ) -> (FileReport, Option<ManifestEntry>) {
+        let started = Instant::now();
+        let mut report = FileReport::new(file.relative.clone(), FileStatus::Failed, file.size);
+        let entry = self
+            .try_ingest_file(
+                memory,
+                &file,
+                previous,
+                user_id,
+                additional_metadata,
+                &mut report,
+            )
+            .await;
+
+        report.duration_ms = started.elapsed().as_millis() as u64;
+        if report.status == FileStatus::Failed {
+            tracing::warn!(
+                "Failed to ingest {}: {}",
+                file.relative,
+                report.error.as_deref().unwrap_or_default()
+            );
+        }
+        (report, entry)
+    }
+
+    async fn try_ingest_file(
+        &self,
+        memory: &Memory,
+        file: &FoundFile,
+        previous: Option<ManifestEntry>,
+        user_id: &str,
+        additional_metadata: Option<&HashMap<String, serde_json::Value>>,
+        report: &mut FileReport,
+    ) -> Option<ManifestEntry> {
+        if file.size > self.config.max_file_bytes {
+            report.status = FileStatus::TooLarge;
+            return None;
+        }
+        let content = match tokio::fs::read(&file.path).await {
+            Ok(content) => content,
+            Err(e) => {
+                report.error = Some(format!("Failed to read file: {}", e));
+                return None;
+            }
+        };
+
+        let sha256 = hex::encode(Sha256::digest(&content));
+        if let Some(previous) = previous.as_ref().filter(|p| p.sha256 == sha256) {
+            report.status = FileStatus::Unchanged;
+            report.memory_ids = previous.memory_ids.clone();
+            return None;
+        }
+
+        let mime_type = match detect_file_type(&content, &file.relative) {
+            Some(mime) if self.ingester.supports(&mime) => mime,
+            mime => {
+                report.status = FileStatus::Unsupported;
+                report.mime_type = mime;
+                return None;
+            }
+        };
+        report.mime_type = Some(mime_type.clone());
+
+        let mut metadata = additional_metadata.cloned().unwrap_or_default();
+        metadata.insert(
+            "source_path".to_string(),
+            serde_json::Value::String(file.relative.clone()),
+        );
+        let filename = file.path.file_name().map(|n| n.to_string_lossy());
+        let result = self
+            .ingester
+            .ingest(
+                memory,
+                &content,
+                &mime_type,
+                filename.as_deref(),
+                user_id,
+                Some(metadata),
+            )
+            .await;
+
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                report.error = Some(e.to_string());
+                return None;
+            }
+        };
+
+        // The file changed; its new content replaces the old memories.
+        // Deleting a document record deletes its chunks
+        let outdated = previous.iter().flat_map(|p| match p.document_id {
+            Some(ref id) => std::slice::from_ref(id),
+            None => p.memory_ids.as_slice(),
+        });
+        for id in outdated {
+            if let Err(e) = memory.delete(id).await {
+                tracing::warn!(
+                    "Failed to delete outdated memory {} of {}: {}",
+                    id,
+                    file.relative,
+                    e
+                );
+            }
+        }
+
+        report.status = FileStatus::Ingested;
+        report.memory_ids = result.memory_ids.clone();
+        report.chunks_created = result.chunks_created;
+        report.warnings = result.warnings;
+        Some(ManifestEntry {
+            sha256,
+            size: file.size,
+            memory_ids: result.memory_ids,
+            document_id: result.document_id,
+            ingested_at: Utc::now(),
+        })
+    }
+}
+
+/// MIME type of a file from its content and extension
+///
+/// Files of unknown type are ingested as plain text if they are valid
+/// UTF-8, and unsupported otherwise.
+fn detect_file_type(content: &[u8], path: &str) -> Option<String> {
+    if let Some(mime) = sniff_content(content).or_else(|| mime_from_extension(path)) {
+        return Some(mime.to_string());
+    }
+    match std::str::from_utf8(content) {
+        Ok(text) if !text.contains('\0') => Some("text/plain".to_string()),
+        _ => None,
+    }
+}
+
+/// Compile glob patterns, or `None` if there are none
+fn build_globs(patterns: &[String]) -> RookResult<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| {
+            RookError::validation(format!("Invalid glob pattern '{}': {}", pattern, e))
+        })?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| RookError::validation(format!("Invalid glob patterns: {}", e)))
+}
+
+/// Files under `root` that pass the configured filters, in path order
+fn find_files(
+    root: &Path,
+    config: &BulkIngestConfig,
+    manifest_path: &Path,
+) -> RookResult<Vec<FoundFile>> {
+    let include = build_globs(&config.include)?;
+    let exclude = build_globs(&config.exclude)?;
+
+    let relative_path = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let walker = WalkDir::new(root)
+        .follow_links(config.follow_links)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let excluded = exclude
+                .as_ref()
+                .is_some_and(|globs| globs.is_match(relative_path(entry.path())));
+            !(excluded || (config.skip_hidden && hidden))
+        });
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::warn!("Skipping unreadable path: {}", e);
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() || entry.path() == manifest_path {
+            continue;
+        }
+        let relative = relative_path(entry.path());
+        if include
+            .as_ref()
+            .is_some_and(|globs| !globs.is_match(&relative))
+        {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+        files.push(FoundFile {
+            path: entry.into_path(),
+            relative,
+            size,
+        });
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in [
+            ("notes.md", "# Notes"),
+            ("docs/guide.md", "# Guide"),
+            ("docs/drafts/idea.md", "# Idea"),
+            ("docs/report.pdf", "%PDF-1.4"),
+            (".git/config", "[core]"),
+            (MANIFEST_FILE, "{}"),
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn found(root: &Path, config: &BulkIngestConfig) -> Vec<String> {
+        find_files(root, config, &root.join(MANIFEST_FILE))
+            .unwrap()
+            .into_iter()
+            .map(|f| f.relative)
+            .collect()
+    }
+
+    #[test]
+    fn test_find_files_filters() {
+        let dir = tree();
+        let root = dir.path();
+
+        assert_eq!(
+            found(root, &BulkIngestConfig::default()),
+            vec![
+                "docs/drafts/idea.md",
+                "docs/guide.md",
+                "docs/report.pdf",
+                "notes.md"
+            ]
+        );
+
+        let config = BulkIngestConfig::default()
+            .include("**/*.md")
+            .exclude("docs/drafts");
+        assert_eq!(found(root, &config), vec!["docs/guide.md", "notes.md"]);
+
+        let config = BulkIngestConfig {
+            skip_hidden: false,
+            ..BulkIngestConfig::default().include("*config")
+        };
+        assert_eq!(found(root, &config), vec![".git/config"]);
+
+        let config = BulkIngestConfig::default().include("[");
+        assert!(find_files(root, &config, &root.join(MANIFEST_FILE)).is_err());
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(MANIFEST_FILE);
+        assert!(IngestManifest::load(&path).unwrap().files.is_empty());
+
+        let mut manifest = IngestManifest::default();
+        manifest.files.insert(
+            "notes.md".to_string(),
+            ManifestEntry {
+                sha256: hex::encode(Sha256::digest(b"# Notes")),
+                size: 7,
+                memory_ids: vec!["mem-1".to_string()],
+                document_id: None,
+                ingested_at: Utc::now(),
+            },
+        );
+        manifest.save(&path).unwrap();
+
+        let loaded = IngestManifest::load(&path).unwrap();
+        assert_eq!(loaded.files["notes.md"].memory_ids, vec!["mem-1"]);
+        assert_eq!(
+            loaded.files["notes.md"].sha256,
+            manifest.files["notes.md"].sha256
+        );
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(IngestManifest::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_detect_file_type() {
+        assert_eq!(
+            detect_file_type(b"%PDF-1.7", "a.bin").as_deref(),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            detect_file_type(b"# Title", "README.md").as_deref(),
+            Some("text/markdown")
+        );
+        assert_eq!(
+            detect_file_type(b"plain words", "notes.txt").as_deref(),
+            Some("text/plain")
+        );
+        assert_eq!(detect_file_type(&[0, 159, 146, 150], "blob.dat"), None);
+    }
+
+    #[test]
+    fn test_report_counts() {
+        let mut report = BulkIngestReport::new(PathBuf::from("."), PathBuf::from(MANIFEST_FILE), 3);
+        let mut ingested = FileReport::new("a.md".to_string(), FileStatus::Ingested, 10);
+        ingested.chunks_created = 2;
+        report.record(ingested);
+        report.record(FileReport::new(
+            "b.md".to_string(),
+            FileStatus::Unchanged,
+            5,
+        ));
+        report.record(FileReport::new(
+            "c.bin".to_string(),
+            FileStatus::Unsupported,
+            5,
+        ));
+
+        assert_eq!(report.ingested, 1);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.failed, 0);
+        assert_eq!(report.chunks_created, 2);
+        assert_eq!(report.bytes_ingested, 10);
+    }
+}
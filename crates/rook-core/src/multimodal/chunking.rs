@@ -0,0 +1,536 @@
+//! Heading-aware and semantic chunking of extracted text.
+//!
+//! Fixed-size chunks cut long documents mid-topic, so a retrieved chunk
+//! often holds the end of one subject and the start of the next. These
+//! chunkers cut at the document's own boundaries instead: Markdown headings
+//! (which the HTML, DOCX and EPUB extractors emit), and within a section the
+//! points between sentences where the embedding of the text shifts most.
+
+use crate::error::RookResult;
+use crate::multimodal::types::SemanticChunkConfig;
+use crate::traits::{Embedder, EmbeddingAction};
+
+/// Sentences embedded per request.
+const EMBED_BATCH_SIZE: usize = 64;
+
+/// Words that end with a period without ending a sentence.
+const ABBREVIATIONS: &[&str] = &[
+    "e.g", "i.e", "etc", "vs", "cf", "al", "mr", "mrs", "ms", "dr", "prof", "st", "jr", "sr",
+    "fig", "figs", "no", "vol", "pp", "approx", "inc", "ltd", "co", "corp",
+];
+
+/// A chunk of extracted content, stored as one memory.
+#[derive(Debug)]
+pub(crate) struct ContentChunk {
+    pub(crate) text: String,
+    pub(crate) page_number: Option<usize>,
+    /// Heading path of the section the chunk came from
+    pub(crate) section: Option<String>,
+}
+
+impl ContentChunk {
+    pub(crate) fn new(text: String) -> Self {
+        Self {
+            text,
+            page_number: None,
+            section: None,
+        }
+    }
+}
+
+/// Text under one heading.
+#[derive(Debug)]
+struct Section<'a> {
+    /// Enclosing headings, outermost first, joined with " > "
+    path: Option<String>,
+    /// Heading lines, including those of empty sections before this one
+    heading: String,
+    /// Text after the heading
+    body: &'a str,
+}
+
+/// Split Markdown text at its headings.
+///
+/// Headings without text of their own (a chapter title directly followed
+/// by its first section's heading) are kept with the next section.
+fn split_sections(text: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut heading = String::new();
+    let mut body_start = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if let Some((level, title)) = parse_heading(line) {
+            let body = &text[body_start..offset];
+            if !body.trim().is_empty() {
+                sections.push(Section {
+                    path: heading_path(&stack),
+                    heading: std::mem::take(&mut heading),
+                    body: body.trim(),
+                });
+            }
+            stack.retain(|(l, _)| *l < level);
+            stack.push((level, title.to_string()));
+            heading.push_str(line.trim());
+            heading.push('\n');
+            body_start = offset + line.len();
+        }
+        offset += line.len();
+    }
+
+    let body = &text[body_start..];
+    if !body.trim().is_empty() || !heading.is_empty() {
+        sections.push(Section {
+            path: heading_path(&stack),
+            heading,
+            body: body.trim(),
+        });
+    }
+    sections
+}
+
+/// Titles of the enclosing headings, joined with " > "
+fn heading_path(stack: &[(usize, String)]) -> Option<String> {
+    (!stack.is_empty()).then(|| {
+        stack
+            .iter()
+            .map(|(_, title)| title.as_str())
+            .collect::<Vec<_>>()
+            .join(" > ")
+    })
+}
+
+/// Level and title of a Markdown heading line
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim();
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let title = line[level..].strip_prefix(' ')?.trim();
+    (!title.is_empty()).then_some((level, title))
+}
+
+/// Split text into sentences.
+///
+/// Sentences end at `.`, `!` or `?` followed by whitespace, except after
+/// common abbreviations and initials, and at blank lines. Single line
+/// breaks, which PDF text has at every wrapped line, don't end sentences.
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '.' | '!' | '?' => {
+                // Closing quotes and brackets belong to the sentence
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, next)) = chars.peek() {
+                    if matches!(next, '"' | '\'' | ')' | ']' | '”' | '’') {
+                        end = j + next.len_utf8();
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let followed_by_space = match chars.peek() {
+                    Some(&(_, next)) => next.is_whitespace(),
+                    None => true,
+                };
+                if followed_by_space && (c != '.' || !is_abbreviation(&text[start..i])) {
+                    Some(end)
+                } else {
+                    None
+                }
+            }
+            '\n' if text[i + 1..]
+                .trim_start_matches([' ', '\t', '\r'])
+                .starts_with('\n') =>
+            {
+                Some(i)
+            }
+            _ => None,
+        };
+        if let Some(end) = end {
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+    }
+
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        sentences.push(rest);
+    }
+    sentences
+}
+
+/// Whether the word at the end of `text` is an abbreviation or initial
+fn is_abbreviation(text: &str) -> bool {
+    let word = text
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(|c: char| !c.is_alphanumeric());
+    let mut letters = word.chars();
+    if let (Some(c), None) = (letters.next(), letters.next()) {
+        return c.is_uppercase();
+    }
+    ABBREVIATIONS.contains(&word.to_lowercase().as_str())
+}
+
+/// Join pieces into chunks of at most `max_size` characters.
+///
+/// Pieces longer than `max_size` are split into sentences, and sentences
+/// that are still too long at whitespace.
+fn pack<'a>(
+    pieces: impl IntoIterator<Item = &'a str>,
+    separator: &str,
+    max_size: usize,
+) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in pieces {
+        if piece.len() > max_size {
+            let parts = if separator == " " {
+                split_words(piece, max_size)
+            } else {
+                pack(split_sentences(piece), " ", max_size)
+            };
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            chunks.extend(parts);
+            continue;
+        }
+        if !current.is_empty() && current.len() + separator.len() + piece.len() > max_size {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+        }
+        current.push_str(piece);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split text at whitespace into parts of at most `max_size` characters
+/// (or single words, if longer)
+fn split_words(text: &str, max_size: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > max_size {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Chunks of a section's packed parts, with the heading on the first
+fn section_chunks(section: &Section<'_>, parts: Vec<String>) -> Vec<ContentChunk> {
+    let mut chunks: Vec<ContentChunk> = parts
+        .into_iter()
+        .map(|text| ContentChunk {
+            text,
+            page_number: None,
+            section: section.path.clone(),
+        })
+        .collect();
+    if !section.heading.is_empty() {
+        match chunks.first_mut() {
+            Some(first) => first.text.insert_str(0, &format!("{}\n", section.heading)),
+            None => chunks.push(ContentChunk {
+                text: section.heading.trim_end().to_string(),
+                page_number: None,
+                section: section.path.clone(),
+            }),
+        }
+    }
+    chunks
+}
+
+/// Split text into its heading sections, and sections longer than
+/// `max_size` at paragraphs.
+pub(crate) fn heading_chunks(text: &str, max_size: usize) -> Vec<ContentChunk> {
+    split_sections(text)
+        .iter()
+        .flat_map(|section| {
+            let paragraphs = section
+                .body
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|p| !p.is_empty());
+            let budget = max_size.saturating_sub(section.heading.len() + 1).max(1);
+            section_chunks(section, pack(paragraphs, "\n\n", budget))
+        })
+        .collect()
+}
+
+/// Split text where its topic changes.
+///
+/// Each sentence is embedded together with `config.window` sentences on
+/// either side, and the text is split between neighbouring sentences whose
+/// embeddings are further apart than `config.breakpoint_percentile` of all
+/// such distances. Heading boundaries are always kept. Groups longer than
+/// `max_size` are split further, and groups shorter than
+/// `config.min_chunk_size` merged into the group before them.
+pub(crate) async fn semantic_chunks(
+    text: &str,
+    embedder: &dyn Embedder,
+    config: &SemanticChunkConfig,
+    max_size: usize,
+) -> RookResult<Vec<ContentChunk>> {
+    let sections = split_sections(text);
+    let sentences: Vec<Vec<&str>> = sections.iter().map(|s| split_sentences(s.body)).collect();
+
+    let windows: Vec<String> = sentences
+        .iter()
+        .flat_map(|sentences| {
+            (0..sentences.len()).map(|i| {
+                let start = i.saturating_sub(config.window);
+                let end = (i + config.window + 1).min(sentences.len());
+                sentences[start..end].join(" ")
+            })
+        })
+        .collect();
+    let mut embeddings = Vec::with_capacity(windows.len());
+    for batch in windows.chunks(EMBED_BATCH_SIZE) {
+        embeddings.extend(
+            embedder
+                .embed_batch(batch, Some(EmbeddingAction::Add))
+                .await?,
+        );
+    }
+
+    // Distances between neighbouring sentences, per section
+    let mut offset = 0;
+    let distances: Vec<Vec<f32>> = sentences
+        .iter()
+        .map(|sentences| {
+            let section = &embeddings[offset..offset + sentences.len()];
+            offset += sentences.len();
+            section
+                .windows(2)
+                .map(|pair| 1.0 - cosine_similarity(&pair[0], &pair[1]))
+                .collect()
+        })
+        .collect();
+    let all: Vec<f32> = distances.iter().flatten().copied().collect();
+    let threshold = percentile(&all, config.breakpoint_percentile);
+
+    let mut chunks = Vec::new();
+    for ((section, sentences), distances) in sections.iter().zip(&sentences).zip(&distances) {
+        let budget = max_size.saturating_sub(section.heading.len() + 1).max(1);
+        let mut parts: Vec<String> = Vec::new();
+        for group in group_sentences(sentences, distances, threshold) {
+            for part in pack(group.iter().copied(), " ", budget) {
+                match parts.last_mut() {
+                    Some(last)
+                        if part.len() < config.min_chunk_size
+                            && last.len() + 1 + part.len() <= budget =>
+                    {
+                        last.push(' ');
+                        last.push_str(&part);
+                    }
+                    _ => parts.push(part),
+                }
+            }
+        }
+        chunks.extend(section_chunks(section, parts));
+    }
+    Ok(chunks)
+}
+
+/// Split sentences before each one whose distance from the previous
+/// sentence exceeds `threshold`
+fn group_sentences<'a>(
+    sentences: &[&'a str],
+    distances: &[f32],
+    threshold: f32,
+) -> Vec<Vec<&'a str>> {
+    let mut groups: Vec<Vec<&str>> = Vec::new();
+    for (i, sentence) in sentences.iter().enumerate() {
+        let breakpoint = i > 0 && distances.get(i - 1).is_some_and(|&d| d > threshold);
+        match groups.last_mut() {
+            Some(group) if !breakpoint => group.push(sentence),
+            _ => groups.push(vec![sentence]),
+        }
+    }
+    groups
+}
+
+/// Calculate cosine similarity between two vectors
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+/// The `p`th percentile of `values`, by linear interpolation
+fn percentile(values: &[f32], p: f32) -> f32 {
+    if values.is_empty() {
+        return f32::INFINITY;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    /// Embeds text by which of a few topics it mentions.
+    struct TopicEmbedder;
+
+    #[async_trait]
+    impl Embedder for TopicEmbedder {
+        async fn embed(&self, text: &str, _: Option<EmbeddingAction>) -> RookResult<Vec<f32>> {
+            Ok(["apple", "engine", "river"]
+                .iter()
+                .map(|topic| text.matches(topic).count() as f32)
+                .collect())
+        }
+
+        fn dimension(&self) -> usize {
+            3
+        }
+
+        fn model_name(&self) -> &str {
+            "topics"
+        }
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let text = "Dr. Smith met J. Doe at 3.30 today. It rained!\nThey left (quickly.) \
+                    Then what?\n\nA new paragraph without a stop";
+        assert_eq!(
+            split_sentences(text),
+            vec![
+                "Dr. Smith met J. Doe at 3.30 today.",
+                "It rained!",
+                "They left (quickly.)",
+                "Then what?",
+                "A new paragraph without a stop",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let text =
+            "Preface text.\n# Book\n## Part one\nFirst.\n\n### Detail\nDeep.\n## Part two\nSecond.";
+        let sections = split_sections(text);
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].path, None);
+        assert_eq!(sections[0].body, "Preface text.");
+        assert_eq!(sections[1].path.as_deref(), Some("Book > Part one"));
+        assert_eq!(sections[1].heading, "# Book\n## Part one\n");
+        assert_eq!(sections[1].body, "First.");
+        assert_eq!(
+            sections[2].path.as_deref(),
+            Some("Book > Part one > Detail")
+        );
+        assert_eq!(sections[3].path.as_deref(), Some("Book > Part two"));
+    }
+
+    #[test]
+    fn test_heading_chunks() {
+        let long = "word ".repeat(30);
+        let text = format!(
+            "# Intro\nShort intro.\n# Body\n{}\n\n{}",
+            long.trim(),
+            long.trim()
+        );
+        let chunks = heading_chunks(&text, 200);
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].text, "# Intro\n\nShort intro.");
+        assert_eq!(chunks[0].section.as_deref(), Some("Intro"));
+        assert!(chunks[1].text.starts_with("# Body\n\nword"));
+        assert_eq!(chunks[2].section.as_deref(), Some("Body"));
+        assert!(chunks.iter().all(|c| c.text.len() <= 200));
+    }
+
+    #[test]
+    fn test_pack_splits_long_pieces() {
+        let chunks = pack(["One. Two. Three.", "Four."], "\n\n", 10);
+        assert_eq!(chunks, vec!["One. Two.", "Three.", "Four."]);
+        assert_eq!(split_words("aaaa bbbb cccc", 9), vec!["aaaa bbbb", "cccc"]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[1.0, 2.0, 3.0, 4.0, 5.0], 50.0), 3.0);
+        assert_eq!(percentile(&[1.0, 2.0], 100.0), 2.0);
+        assert_eq!(percentile(&[], 90.0), f32::INFINITY);
+    }
+
+    #[tokio::test]
+    async fn test_semantic_chunks_split_on_topic_change() {
+        let text = "The apple tree grew. Each apple was red. The apple harvest was good. \
+                    The engine started. The engine was loud. The engine overheated.";
+        let config = SemanticChunkConfig {
+            breakpoint_percentile: 75.0,
+            window: 0,
+            min_chunk_size: 0,
+        };
+        let chunks = semantic_chunks(text, &TopicEmbedder, &config, 1000)
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunks[0].text,
+            "The apple tree grew. Each apple was red. The apple harvest was good."
+        );
+        assert!(chunks[1].text.starts_with("The engine started."));
+    }
+
+    #[tokio::test]
+    async fn test_semantic_chunks_merge_short_groups() {
+        let text = "# Rivers\n\nThe river ran. An apple fell. The river rose.";
+        let config = SemanticChunkConfig {
+            breakpoint_percentile: 0.0,
+            window: 0,
+            min_chunk_size: 50,
+        };
+        let chunks = semantic_chunks(text, &TopicEmbedder, &config, 1000)
+            .await
+            .unwrap();
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(
+            chunks[0].text,
+            "# Rivers\n\nThe river ran. An apple fell. The river rose."
+        );
+        assert_eq!(chunks[0].section.as_deref(), Some("Rivers"));
+    }
+}
@@ -0,0 +1,420 @@
+//! URL fetching for multimodal ingestion.
+//!
+//! Downloads a URL within size and time limits, optionally checking the
+//! site's robots.txt first, and works out the content's MIME type so it can
+//! be routed through the extraction pipeline.
+
+use crate::error::{RookError, RookResult};
+use crate::multimodal::types::UrlFetchConfig;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode, Url};
+use std::time::Duration;
+
+/// Content types that say nothing about the format.
+const GENERIC_TYPES: &[&str] = &[
+    "application/octet-stream",
+    "binary/octet-stream",
+    "application/download",
+    "application/x-download",
+    "text/plain",
+];
+
+/// Content downloaded from a URL.
+#[derive(Debug, Clone)]
+pub struct FetchedContent {
+    /// Response body
+    pub bytes: Vec<u8>,
+    /// MIME type from the response headers, content or URL extension
+    pub mime_type: String,
+    /// URL after redirects
+    pub url: String,
+    /// Last path segment of the URL, if any
+    pub filename: Option<String>,
+    /// When the response was received
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Downloads URLs for ingestion.
+pub struct UrlFetcher {
+    client: Client,
+    config: UrlFetchConfig,
+}
+
+impl UrlFetcher {
+    /// Create a fetcher with the given limits
+    pub fn new(config: UrlFetchConfig) -> RookResult<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent.clone())
+            .build()
+            .map_err(|e| RookError::internal(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self { client, config })
+    }
+
+    /// Fetch a URL
+    ///
+    /// Fails if the URL isn't http(s), robots.txt disallows it (when
+    /// `respect_robots` is set), the server returns an error status, or the
+    /// body is larger than `max_bytes`.
+    pub async fn fetch(&self, url: &str) -> RookResult<FetchedContent> {
+        let parsed = Url::parse(url)
+            .map_err(|e| RookError::validation(format!("Invalid URL '{}': {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(RookError::validation(format!(
+                "URL must be http or https, got '{}'",
+                parsed.scheme()
+            )));
+        }
+
+        if self.config.respect_robots && !self.robots_allow(&parsed).await {
+            return Err(RookError::extraction(format!(
+                "Fetching {} is disallowed by robots.txt",
+                url
+            )));
+        }
+
+        let mut response = self
+            .client
+            .get(parsed)
+            .send()
+            .await
+            .map_err(|e| RookError::extraction(format!("Failed to fetch {}: {}", url, e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(RookError::extraction(format!(
+                "Failed to fetch {}: HTTP {}",
+                url, status
+            )));
+        }
+
+        let too_large = || {
+            RookError::extraction(format!(
+                "Content at {} exceeds the {} byte limit",
+                url, self.config.max_bytes
+            ))
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > self.config.max_bytes as u64)
+        {
+            return Err(too_large());
+        }
+
+        let final_url = response.url().clone();
+        let header_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        // Servers may omit or misstate Content-Length, so count as we read
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| RookError::extraction(format!("Failed to read {}: {}", url, e)))?
+        {
+            if bytes.len() + chunk.len() > self.config.max_bytes {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        let fetched_at = Utc::now();
+
+        let mime_type = detect_mime_type(header_type.as_deref(), &bytes, final_url.path());
+        let filename = final_url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string);
+
+        Ok(FetchedContent {
+            bytes,
+            mime_type,
+            url: final_url.to_string(),
+            filename,
+            fetched_at,
+        })
+    }
+
+    /// Whether the site's robots.txt lets us fetch `url`
+    ///
+    /// A missing or unreachable robots.txt allows everything; one that
+    /// fails with a server error disallows everything, as the site may be
+    /// unable to say what it permits.
+    async fn robots_allow(&self, url: &Url) -> bool {
+        let Ok(robots_url) = url.join("/robots.txt") else {
+            return true;
+        };
+        let Ok(response) = self.client.get(robots_url).send().await else {
+            return true;
+        };
+        match response.status() {
+            status if status.is_server_error() => false,
+            StatusCode::OK => match response.text().await {
+                Ok(robots) => {
+                    let target = match url.query() {
+                        Some(query) => format!("{}?{}", url.path(), query),
+                        None => url.path().to_string(),
+                    };
+                    robots_allows(&robots, &self.config.user_agent, &target)
+                }
+                Err(_) => true,
+            },
+            _ => true,
+        }
+    }
+}
+
+/// MIME type of fetched content
+///
+/// A specific Content-Type header wins. Otherwise the content's magic bytes
+/// are checked, then the URL's file extension; unrecognised text falls
+/// back to the header, or `text/plain`.
+pub(crate) fn detect_mime_type(header: Option<&str>, content: &[u8], path: &str) -> String {
+    let header = header
+        .and_then(|h| h.split(';').next())
+        .map(|h| h.trim().to_ascii_lowercase())
+        .filter(|h| !h.is_empty());
+    if let Some(ref header) = header {
+        if !GENERIC_TYPES.contains(&header.as_str()) {
+            return header.clone();
+        }
+    }
+
+    sniff_content(content)
+        .or_else(|| mime_from_extension(path))
+        .map(str::to_string)
+        .or(header)
+        .unwrap_or_else(|| "text/plain".to_string())
+}
+
+/// MIME type from the content's leading bytes, for unambiguous formats
+pub(crate) fn sniff_content(content: &[u8]) -> Option<&'static str> {
+    let head = &content[..content.len().min(512)];
+    let mime = match head {
+        [b'%', b'P', b'D', b'F', ..] => "application/pdf",
+        [0x89, b'P', b'N', b'G', ..] => "image/png",
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [b'G', b'I', b'F', b'8', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "image/webp",
+        // EPUBs store their MIME type uncompressed as the first entry
+        [b'P', b'K', 3, 4, ..] if contains(head, b"mimetypeapplication/epub+zip") => {
+            "application/epub+zip"
+        }
+        _ => {
+            let text = String::from_utf8_lossy(head)
+                .trim_start()
+                .to_ascii_lowercase();
+            if text.starts_with("<!doctype html") || text.starts_with("<html") {
+                "text/html"
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(mime)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// MIME type from a URL or file path's file extension
+pub(crate) fn mime_from_extension(path: &str) -> Option<&'static str> {
+    let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    let mime = match extension.as_str() {
+        "pdf" => "application/pdf",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "xls" => "application/vnd.ms-excel",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "epub" => "application/epub+zip",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "html" | "htm" => "text/html",
+        "xhtml" => "application/xhtml+xml",
+        "md" | "markdown" => "text/markdown",
+        "eml" => "message/rfc822",
+        "mbox" => "application/mbox",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "rs" => "text/x-rust",
+        "py" => "text/x-python",
+        "js" | "mjs" => "text/javascript",
+        "ts" => "text/typescript",
+        "go" => "text/x-go",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// Whether robots.txt rules let `user_agent` fetch `path`
+///
+/// Uses the group for the most specific matching user agent, falling back
+/// to `*`. Within the group the longest matching rule wins, with `Allow`
+/// winning ties. Rules support the `*` wildcard and `$` end anchor.
+pub(crate) fn robots_allows(robots: &str, user_agent: &str, path: &str) -> bool {
+    // Product token, e.g. "rook" for "rook/0.1.1"
+    let agent = user_agent
+        .split(['/', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let mut groups: Vec<RobotsGroup> = Vec::new();
+    let mut in_agent_lines = false;
+    for line in robots.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match field.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agent_lines {
+                    groups.push(RobotsGroup::default());
+                }
+                in_agent_lines = true;
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+            }
+            field @ ("allow" | "disallow") => {
+                in_agent_lines = false;
+                if let Some(group) = groups.last_mut() {
+                    // An empty Disallow allows everything
+                    if !value.is_empty() {
+                        group.rules.push((field == "allow", value.to_string()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let group = groups
+        .iter()
+        .filter(|g| {
+            g.agents
+                .iter()
+                .any(|a| a != "*" && agent.starts_with(a.as_str()))
+        })
+        .max_by_key(|g| g.agents.iter().map(String::len).max())
+        .or_else(|| groups.iter().find(|g| g.agents.iter().any(|a| a == "*")));
+    let Some(group) = group else {
+        return true;
+    };
+
+    let rule = group
+        .rules
+        .iter()
+        .filter(|(_, pattern)| robots_pattern_matches(pattern, path))
+        .max_by_key(|(allow, pattern)| (pattern.len(), *allow));
+    match rule {
+        Some((allow, _)) => *allow,
+        None => true,
+    }
+}
+
+/// A robots.txt group: user agents and the rules that apply to them
+#[derive(Default)]
+struct RobotsGroup {
+    agents: Vec<String>,
+    /// (allow, path pattern)
+    rules: Vec<(bool, String)>,
+}
+
+/// Match a robots.txt path pattern against a path
+fn robots_pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let last = i == parts.len() - 1;
+        if last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_mime_type() {
+        assert_eq!(
+            detect_mime_type(Some("text/html; charset=utf-8"), b"", "/"),
+            "text/html"
+        );
+        assert_eq!(
+            detect_mime_type(Some("application/octet-stream"), b"%PDF-1.7", "/file"),
+            "application/pdf"
+        );
+        assert_eq!(
+            detect_mime_type(None, b"# Notes", "/docs/notes.md"),
+            "text/markdown"
+        );
+        assert_eq!(
+            detect_mime_type(Some("text/plain"), b"  <!DOCTYPE html><html>", "/"),
+            "text/html"
+        );
+        assert_eq!(
+            detect_mime_type(Some("text/plain"), b"hello", "/"),
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn test_robots_groups_and_precedence() {
+        let robots = "User-agent: *\n\
+                      Disallow: /private/\n\
+                      Allow: /private/public-*.html$\n\
+                      \n\
+                      User-agent: rook\n\
+                      User-agent: other\n\
+                      Disallow: /\n\
+                      Allow: /docs\n";
+
+        // rook's own group applies instead of the * group
+        assert!(!robots_allows(robots, "rook/0.1.1", "/blog"));
+        assert!(robots_allows(robots, "rook/0.1.1", "/docs/intro"));
+
+        assert!(robots_allows(robots, "crawler", "/blog"));
+        assert!(!robots_allows(robots, "crawler", "/private/report.pdf"));
+        assert!(robots_allows(robots, "crawler", "/private/public-faq.html"));
+        assert!(!robots_allows(
+            robots,
+            "crawler",
+            "/private/public-faq.html?x=1"
+        ));
+    }
+
+    #[test]
+    fn test_robots_empty_disallow_allows_all() {
+        assert!(robots_allows(
+            "User-agent: *\nDisallow:\n",
+            "rook",
+            "/anything"
+        ));
+        assert!(robots_allows("", "rook", "/anything"));
+    }
+}
@@ -4,9 +4,17 @@
 //! documents and images.
 
 use crate::error::{RookError, RookResult};
-use crate::memory::Memory;
-use crate::multimodal::types::{MultimodalConfig, MultimodalIngestResult, SourceProvenance};
-use rook_extractors::{ExtractedContent, ExtractionPipeline, Modality};
+use crate::memory::{
+    document_entity_name, Memory, CHUNK_ROLE, DOCUMENT_ID_FIELD, DOCUMENT_ROLE,
+    DOCUMENT_ROLE_FIELD, HAS_CHUNK_RELATIONSHIP,
+};
+use crate::multimodal::chunking::{heading_chunks, semantic_chunks, ContentChunk};
+use crate::multimodal::fetch::UrlFetcher;
+use crate::multimodal::types::{
+    ChunkingStrategy, MultimodalConfig, MultimodalIngestResult, SourceProvenance,
+};
+use crate::traits::GraphFilters;
+use rook_extractors::{ContentSource, ExtractedContent, ExtractionPipeline, Modality};
 use std::collections::HashMap;
 
 /// Orchestrates multimodal content ingestion.
@@ -14,7 +22,8 @@ use std::collections::HashMap;
 /// Workflow:
 /// 1. Detect content type (MIME type)
 /// 2. Extract text via appropriate extractor
-/// 3. Optionally chunk large documents
+/// 3. Optionally chunk large documents, by size, page, heading or topic
+///    (see [`ChunkingStrategy`])
 /// 4. Store as memory with provenance metadata
 ///
 /// # Cross-Modal Retrieval
@@ -90,16 +99,100 @@ impl MultimodalIngester {
         filename: Option<&str>,
         user_id: &str,
         additional_metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<MultimodalIngestResult> {
+        let mut source = SourceProvenance::new("");
+        if let Some(name) = filename {
+            source = source.with_filename(name);
+        }
+        self.ingest_source(
+            memory,
+            content,
+            mime_type,
+            source,
+            user_id,
+            additional_metadata,
+        )
+        .await
+    }
+
+    /// Fetch a URL and ingest its content into memory system
+    ///
+    /// The download is bounded by the configured `url_fetch` limits and,
+    /// unless disabled, checked against the site's robots.txt. The content
+    /// type comes from the response headers, falling back to the content's
+    /// magic bytes and the URL's extension. Provenance records the URL
+    /// (after redirects) and the fetch time.
+    ///
+    /// # Arguments
+    /// * `memory` - Memory instance for storage
+    /// * `url` - http(s) URL to fetch
+    /// * `user_id` - User ID for memory scoping
+    /// * `additional_metadata` - Extra metadata to include
+    pub async fn ingest_url(
+        &self,
+        memory: &Memory,
+        url: &str,
+        user_id: &str,
+        additional_metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<MultimodalIngestResult> {
+        let fetched = UrlFetcher::new(self.config.url_fetch.clone())?
+            .fetch(url)
+            .await?;
+
+        let mut source = SourceProvenance::new("").with_url(&fetched.url, fetched.fetched_at);
+        if let Some(ref name) = fetched.filename {
+            source = source.with_filename(name);
+        }
+        self.ingest_source(
+            memory,
+            &fetched.bytes,
+            &fetched.mime_type,
+            source,
+            user_id,
+            additional_metadata,
+        )
+        .await
+    }
+
+    /// Ingest content whose origin is described by `source`
+    ///
+    /// The modality, size, MIME type and extraction method of `source` are
+    /// filled in from the extraction.
+    async fn ingest_source(
+        &self,
+        memory: &Memory,
+        content: &[u8],
+        mime_type: &str,
+        source: SourceProvenance,
+        user_id: &str,
+        additional_metadata: Option<HashMap<String, serde_json::Value>>,
     ) -> RookResult<MultimodalIngestResult> {
         let content_len = content.len();
         let mut warnings = Vec::new();
 
-        // Extract content
-        let extracted = self
-            .pipeline
-            .extract(content, mime_type)
-            .await
-            .map_err(|e| RookError::extraction(format!("Failed to extract {}: {}", mime_type, e)))?;
+        // Extract content; an image nothing can describe is still worth
+        // keeping when it can be found by its image embedding
+        let extracted = match self.pipeline.extract(content, mime_type).await {
+            Ok(extracted) => extracted,
+            Err(e) if self.embeds_images(memory, mime_type) => {
+                warnings.push(format!("No text extracted from image: {}", e));
+                let label = source.filename.as_deref().unwrap_or(mime_type);
+                ExtractedContent::new(
+                    format!("Image file: {}", label),
+                    Modality::Image {
+                        format: mime_type.trim_start_matches("image/").to_string(),
+                    },
+                    ContentSource::Bytes,
+                )
+                .with_metadata("extraction_method", "image_embedding")
+            }
+            Err(e) => {
+                return Err(RookError::extraction(format!(
+                    "Failed to extract {}: {}",
+                    mime_type, e
+                )))
+            }
+        };
 
         // Check minimum text length
         if extracted.text.len() < self.config.min_text_length {
@@ -115,16 +208,22 @@ impl MultimodalIngester {
             Modality::Text => "text",
             Modality::Pdf => "pdf",
             Modality::Docx => "docx",
+            Modality::Epub => "epub",
+            Modality::Pptx => "pptx",
+            Modality::Html => "html",
+            Modality::Markdown => "markdown",
+            Modality::Email => "email",
+            Modality::Spreadsheet => "spreadsheet",
+            Modality::Code => "code",
             Modality::Image { format } => format.as_str(),
         };
 
-        let mut provenance = SourceProvenance::new(modality_str)
-            .with_size(content_len)
-            .with_mime_type(mime_type);
-
-        if let Some(name) = filename {
-            provenance = provenance.with_filename(name);
+        let mut provenance = SourceProvenance {
+            modality: modality_str.to_string(),
+            ..source
         }
+        .with_size(content_len)
+        .with_mime_type(mime_type);
 
         // Get extraction method from metadata if available
         if let Some(method) = extracted.metadata.get("extraction_method") {
@@ -133,8 +232,84 @@ impl MultimodalIngester {
             }
         }
 
+        // Emails carry per-message headers; a single message's IDs apply to
+        // every chunk, a mailbox's to the chunks of each message
+        let email_messages = extracted
+            .metadata
+            .get("messages")
+            .and_then(|v| v.as_array());
+        if let Some([message]) = email_messages.map(Vec::as_slice) {
+            if let Some(id) = message.get("message_id").and_then(|v| v.as_str()) {
+                provenance = provenance.with_message_id(id);
+            }
+            if let Some(id) = message.get("thread_id").and_then(|v| v.as_str()) {
+                provenance = provenance.with_thread_id(id);
+            }
+        }
+
+        if let Some(language) = extracted.metadata.get("language").and_then(|v| v.as_str()) {
+            provenance = provenance.with_language(language);
+        }
+
+        let is_image = matches!(extracted.modality, Modality::Image { .. });
+        if let (true, Some(thumbnail)) = (is_image, &self.config.image.thumbnail) {
+            #[cfg(feature = "image")]
+            match crate::multimodal::thumbnail::store_thumbnail(content, thumbnail).await {
+                Ok(thumbnail) => provenance = provenance.with_thumbnail(thumbnail),
+                Err(e) => warnings.push(format!("Failed to create thumbnail: {}", e)),
+            }
+            #[cfg(not(feature = "image"))]
+            {
+                let _ = thumbnail;
+                warnings.push("Thumbnails require the `image` feature".to_string());
+            }
+        }
+
+        // Source code pages are definitions, each with its symbol
+        let code_symbols = extracted.metadata.get("symbols").and_then(|v| v.as_array());
+
         // Determine chunking strategy
-        let chunks = self.chunk_content(&extracted, &mut warnings);
+        let strategy = self.config.chunking_for(mime_type);
+        let chunks = self
+            .chunk(memory, &extracted, strategy, &mut warnings)
+            .await;
+
+        // A document split into several memories gets a record of its own
+        let document_id = if chunks.len() > 1 && self.config.document_records {
+            let mut metadata = provenance.to_metadata();
+            metadata.insert(
+                DOCUMENT_ROLE_FIELD.to_string(),
+                serde_json::Value::String(DOCUMENT_ROLE.to_string()),
+            );
+            metadata.insert(
+                "chunk_total".to_string(),
+                serde_json::Value::Number(chunks.len().into()),
+            );
+            if let Some(ref extra) = additional_metadata {
+                metadata.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+
+            let title = extracted
+                .metadata
+                .get("title")
+                .and_then(|v| v.as_str())
+                .or(provenance.filename.as_deref())
+                .unwrap_or(provenance.modality.as_str());
+            let result = memory
+                .add(
+                    document_outline(title, &chunks).as_str(),
+                    Some(user_id.to_string()),
+                    None,
+                    None,
+                    Some(metadata),
+                    false,
+                    None,
+                )
+                .await?;
+            result.results.first().map(|r| r.id.clone())
+        } else {
+            None
+        };
 
         // Store memories
         let mut memory_ids = Vec::new();
@@ -162,6 +337,49 @@ impl MultimodalIngester {
                     "source_page".to_string(),
                     serde_json::Value::Number(page.into()),
                 );
+
+                // Pages of a mailbox are its messages
+                let message = email_messages.and_then(|messages| messages.get(page - 1));
+                for (field, key) in [
+                    ("message_id", "source_message_id"),
+                    ("thread_id", "source_thread_id"),
+                ] {
+                    if let Some(id) = message.and_then(|m| m.get(field)) {
+                        metadata.insert(key.to_string(), id.clone());
+                    }
+                }
+
+                let symbol = code_symbols.and_then(|symbols| symbols.get(page - 1));
+                for (field, key) in [
+                    ("name", "source_symbol"),
+                    ("kind", "source_symbol_kind"),
+                    ("start_line", "source_start_line"),
+                    ("end_line", "source_end_line"),
+                ] {
+                    let value = symbol.and_then(|s| s.get(field)).filter(|v| !v.is_null());
+                    if let Some(value) = value {
+                        metadata.insert(key.to_string(), value.clone());
+                    }
+                }
+            }
+
+            if let Some(ref id) = document_id {
+                metadata.insert(
+                    DOCUMENT_ID_FIELD.to_string(),
+                    serde_json::Value::String(id.clone()),
+                );
+                metadata.insert(
+                    DOCUMENT_ROLE_FIELD.to_string(),
+                    serde_json::Value::String(CHUNK_ROLE.to_string()),
+                );
+            }
+
+            // Add the heading path of heading-aware and semantic chunks
+            if let Some(ref section) = chunk.section {
+                metadata.insert(
+                    "source_section".to_string(),
+                    serde_json::Value::String(section.clone()),
+                );
             }
 
             // Merge additional metadata
@@ -191,6 +409,21 @@ impl MultimodalIngester {
             }
         }
 
+        if let Some(ref id) = document_id {
+            if let Err(e) = link_graph(memory, id, &memory_ids, user_id).await {
+                warnings.push(format!("Failed to link document in graph: {}", e));
+            }
+        }
+
+        // Index the image itself under its first memory
+        if let (true, Some(id)) = (is_image, memory_ids.first()) {
+            if self.embeds_images(memory, mime_type) {
+                if let Err(e) = memory.index_image(id, content, mime_type).await {
+                    warnings.push(format!("Failed to index image: {}", e));
+                }
+            }
+        }
+
         // Check for fallback usage
         let used_fallback = extracted
             .metadata
@@ -201,6 +434,7 @@ impl MultimodalIngester {
 
         Ok(MultimodalIngestResult {
             memory_ids,
+            document_id,
             provenance,
             chunks_created: chunks.len(),
             text_length,
@@ -209,24 +443,69 @@ impl MultimodalIngester {
         })
     }
 
-    /// Chunk extracted content based on configuration
+    /// Whether images of this type get an image embedding
+    fn embeds_images(&self, memory: &Memory, mime_type: &str) -> bool {
+        self.config.image.embed && memory.has_image_index() && mime_type.starts_with("image/")
+    }
+
+    /// Chunk extracted content, embedding its sentences with the memory's
+    /// embedder for semantic chunking
+    ///
+    /// If embedding fails, semantic chunking falls back to heading-aware
+    /// chunking with a warning.
+    async fn chunk(
+        &self,
+        memory: &Memory,
+        extracted: &ExtractedContent,
+        strategy: ChunkingStrategy,
+        warnings: &mut Vec<String>,
+    ) -> Vec<ContentChunk> {
+        let fits = extracted.text.len() <= self.config.max_chunk_size;
+        if strategy != ChunkingStrategy::Semantic || is_structured(extracted) || fits {
+            return self.chunk_content(extracted, strategy, warnings);
+        }
+
+        let embedder = memory.embedder();
+        let chunks = semantic_chunks(
+            &extracted.text,
+            embedder.as_ref(),
+            &self.config.semantic,
+            self.config.max_chunk_size,
+        )
+        .await;
+        match chunks {
+            Ok(chunks) if !chunks.is_empty() => chunks,
+            Ok(_) => self.chunk_content(extracted, ChunkingStrategy::Fixed, warnings),
+            Err(e) => {
+                warnings.push(format!(
+                    "Semantic chunking failed, splitting at headings instead: {}",
+                    e
+                ));
+                self.chunk_content(extracted, ChunkingStrategy::Heading, warnings)
+            }
+        }
+    }
+
+    /// Chunk extracted content with a strategy that needs no embeddings
     fn chunk_content(
         &self,
         extracted: &ExtractedContent,
+        strategy: ChunkingStrategy,
         warnings: &mut Vec<String>,
     ) -> Vec<ContentChunk> {
         let mut chunks = Vec::new();
 
         // Check if we should split by pages
-        if self.config.split_by_page {
+        let split_by_page = strategy == ChunkingStrategy::Page;
+        if split_by_page || is_structured(extracted) {
             if let Some(ref structure) = extracted.structure {
                 if !structure.pages.is_empty() {
                     // Create chunk per page
                     for (i, page_text) in structure.pages.iter().enumerate() {
                         if !page_text.trim().is_empty() {
                             chunks.push(ContentChunk {
-                                text: page_text.clone(),
                                 page_number: Some(i + 1),
+                                ..ContentChunk::new(page_text.clone())
                             });
                         }
                     }
@@ -236,18 +515,31 @@ impl MultimodalIngester {
                     }
                 }
             }
-            warnings.push("Page splitting requested but no page structure available".to_string());
+            if split_by_page {
+                warnings
+                    .push("Page splitting requested but no page structure available".to_string());
+            }
         }
 
-        // Fall back to character-based chunking
         let text = &extracted.text;
 
+        // Split at headings, keeping semantic chunking's heading boundaries
+        // when it couldn't embed the text
+        let by_heading = matches!(
+            strategy,
+            ChunkingStrategy::Heading | ChunkingStrategy::Semantic
+        );
+        if by_heading && text.len() > self.config.max_chunk_size {
+            chunks = heading_chunks(text, self.config.max_chunk_size);
+            if !chunks.is_empty() {
+                return chunks;
+            }
+        }
+
+        // Fall back to character-based chunking
         if text.len() <= self.config.max_chunk_size {
             // Small enough to be single chunk
-            chunks.push(ContentChunk {
-                text: text.clone(),
-                page_number: None,
-            });
+            chunks.push(ContentChunk::new(text.clone()));
         } else {
             // Split into overlapping chunks
             let mut start = 0;
@@ -261,10 +553,7 @@ impl MultimodalIngester {
 
                 let chunk_text = text[start..chunk_end].trim().to_string();
                 if !chunk_text.is_empty() {
-                    chunks.push(ContentChunk {
-                        text: chunk_text,
-                        page_number: None,
-                    });
+                    chunks.push(ContentChunk::new(chunk_text));
                     chunk_num += 1;
                 }
 
@@ -324,11 +613,78 @@ impl Default for MultimodalIngester {
     }
 }
 
-/// Internal chunk representation
-#[derive(Debug)]
-struct ContentChunk {
-    text: String,
-    page_number: Option<usize>,
+/// Whether the content's pages must be kept as chunks
+///
+/// Presentations, spreadsheets and source code are always split into
+/// pages: their pages are slides with their speaker notes, row groups that
+/// carry the column headers, or definitions that carry their symbol, which
+/// other chunking would lose.
+fn is_structured(extracted: &ExtractedContent) -> bool {
+    matches!(
+        extracted.modality,
+        Modality::Pptx | Modality::Spreadsheet | Modality::Code
+    )
+}
+
+/// Text of a document record: the document's title and the sections its
+/// chunks come from
+fn document_outline(title: &str, chunks: &[ContentChunk]) -> String {
+    let mut sections: Vec<&str> = Vec::new();
+    for section in chunks.iter().filter_map(|c| c.section.as_deref()) {
+        if !sections.contains(&section) {
+            sections.push(section);
+        }
+    }
+
+    let mut outline = format!("Document: {} ({} parts)", title, chunks.len());
+    if !sections.is_empty() {
+        outline.push_str("\n\nSections:");
+        for section in sections {
+            outline.push_str("\n- ");
+            outline.push_str(section);
+        }
+    }
+    outline
+}
+
+/// Link a document record to its chunks in the memory's graph store, if any
+async fn link_graph(
+    memory: &Memory,
+    document_id: &str,
+    chunk_ids: &[String],
+    user_id: &str,
+) -> RookResult<()> {
+    let Some(graph) = memory.graph_store() else {
+        return Ok(());
+    };
+    let filters = GraphFilters {
+        user_id: Some(user_id.to_string()),
+        ..Default::default()
+    };
+
+    let document = document_entity_name(DOCUMENT_ROLE, document_id);
+    let properties = serde_json::json!({ "memory_id": document_id });
+    graph
+        .add_entity(&document, DOCUMENT_ROLE, &properties, &filters)
+        .await?;
+    for (i, id) in chunk_ids.iter().enumerate() {
+        let chunk = document_entity_name(CHUNK_ROLE, id);
+        let properties = serde_json::json!({ "memory_id": id });
+        graph
+            .add_entity(&chunk, CHUNK_ROLE, &properties, &filters)
+            .await?;
+        let properties = serde_json::json!({ "chunk_index": i });
+        graph
+            .add_relationship(
+                &document,
+                &chunk,
+                HAS_CHUNK_RELATIONSHIP,
+                &properties,
+                &filters,
+            )
+            .await?;
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -366,6 +722,77 @@ mod tests {
         assert_eq!(boundary, 30);
     }
 
+    #[test]
+    fn test_spreadsheets_keep_row_groups() {
+        let ingester = MultimodalIngester::new();
+        let pages = vec![
+            "Rows 1-1 of 2\n| a |\n| --- |\n| 1 |".to_string(),
+            "Rows 2-2 of 2\n| a |\n| --- |\n| 2 |".to_string(),
+        ];
+        let extracted = ExtractedContent::new(
+            pages.join("\n\n"),
+            Modality::Spreadsheet,
+            rook_extractors::ContentSource::Bytes,
+        )
+        .with_structure(rook_extractors::DocumentStructure {
+            page_count: Some(2),
+            sections: Vec::new(),
+            pages,
+        });
+
+        let mut warnings = Vec::new();
+        let chunks = ingester.chunk_content(&extracted, ChunkingStrategy::Fixed, &mut warnings);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].page_number, Some(2));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_source_code_keeps_definitions() {
+        let ingester = MultimodalIngester::new();
+        let pages = vec![
+            "rust function parse (lines 1-3)\nfn parse() {}".to_string(),
+            "rust struct Config (lines 5-7)\nstruct Config;".to_string(),
+        ];
+        let extracted = ExtractedContent::new(
+            pages.join("\n\n"),
+            Modality::Code,
+            rook_extractors::ContentSource::Bytes,
+        )
+        .with_structure(rook_extractors::DocumentStructure {
+            page_count: Some(2),
+            sections: vec!["parse".to_string(), "Config".to_string()],
+            pages,
+        });
+
+        let mut warnings = Vec::new();
+        let chunks = ingester.chunk_content(&extracted, ChunkingStrategy::Fixed, &mut warnings);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].text.starts_with("rust struct Config"));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_heading_chunking_keeps_sections() {
+        let ingester = MultimodalIngester::with_config(MultimodalConfig {
+            max_chunk_size: 60,
+            ..Default::default()
+        });
+        let text = "# Setup\nInstall the package and run the setup script.\n\
+                    # Usage\nCall the client from your application code.";
+        let extracted = ExtractedContent::new(
+            text.to_string(),
+            Modality::Markdown,
+            rook_extractors::ContentSource::Bytes,
+        );
+
+        let mut warnings = Vec::new();
+        let chunks = ingester.chunk_content(&extracted, ChunkingStrategy::Heading, &mut warnings);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].text.starts_with("# Usage\n\n"));
+        assert_eq!(chunks[1].section.as_deref(), Some("Usage"));
+    }
+
     #[test]
     fn test_config_presets() {
         let page_config = MultimodalConfig::with_page_splitting();
@@ -383,5 +810,29 @@ mod tests {
         assert!(!config.split_by_page);
         assert!(config.preserve_structure);
         assert_eq!(config.min_text_length, 10);
+        assert!(config.document_records);
+    }
+
+    #[test]
+    fn test_document_outline() {
+        let section = |text: &str, section: Option<&str>| ContentChunk {
+            section: section.map(String::from),
+            ..ContentChunk::new(text.to_string())
+        };
+        let chunks = vec![
+            section("a", Some("Intro")),
+            section("b", Some("Intro")),
+            section("c", Some("Results")),
+            section("d", None),
+        ];
+
+        assert_eq!(
+            document_outline("report.pdf", &chunks),
+            "Document: report.pdf (4 parts)\n\nSections:\n- Intro\n- Results"
+        );
+        assert_eq!(
+            document_outline("notes.txt", &[section("x", None), section("y", None)]),
+            "Document: notes.txt (2 parts)"
+        );
     }
 }
@@ -1,10 +1,25 @@
 //! Multimodal content ingestion for rook memory system.
 //!
 //! Provides unified ingestion API for documents (PDF, DOCX) and images,
-//! storing extracted content as searchable memories with provenance tracking.
+//! local, fetched from a URL or walked from a directory tree, storing
+//! extracted content as searchable memories with provenance tracking.
 
+mod bulk;
+mod chunking;
+mod fetch;
 mod ingest;
+#[cfg(feature = "image")]
+mod thumbnail;
 mod types;
 
+pub use bulk::{
+    BulkIngestReport, BulkIngester, BulkProgress, FileReport, FileStatus, IngestManifest,
+    ManifestEntry, MANIFEST_FILE,
+};
+pub use fetch::{FetchedContent, UrlFetcher};
 pub use ingest::MultimodalIngester;
-pub use types::{MultimodalConfig, MultimodalIngestResult, SourceProvenance};
+pub use types::{
+    BulkIngestConfig, ChunkingStrategy, ImageMemoryConfig, MultimodalConfig,
+    MultimodalIngestResult, SemanticChunkConfig, SourceProvenance, ThumbnailConfig,
+    ThumbnailStorage, UrlFetchConfig,
+};
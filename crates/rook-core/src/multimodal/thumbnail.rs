@@ -0,0 +1,74 @@
+//! Image thumbnails stored with image memories.
+
+use crate::error::{RookError, RookResult};
+use crate::multimodal::types::{ThumbnailConfig, ThumbnailStorage};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+/// Make a JPEG thumbnail of an image and store it as configured, returning
+/// its `data:` URI or file path.
+pub(crate) async fn store_thumbnail(
+    content: &[u8],
+    config: &ThumbnailConfig,
+) -> RookResult<String> {
+    let image = content.to_vec();
+    let max_dimension = config.max_dimension;
+    let jpeg = tokio::task::spawn_blocking(move || {
+        rook_extractors::image::thumbnail(&image, max_dimension)
+    })
+    .await
+    .map_err(|e| RookError::internal(format!("Thumbnail task panicked: {}", e)))?
+    .map_err(|e| RookError::extraction(e.to_string()))?;
+
+    match config.storage {
+        ThumbnailStorage::Inline => Ok(format!(
+            "data:image/jpeg;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(&jpeg)
+        )),
+        ThumbnailStorage::Directory(ref dir) => {
+            // Named by content, so re-ingesting an image reuses its thumbnail
+            let path = dir.join(format!("{}.jpg", hex::encode(Sha256::digest(content))));
+            tokio::fs::create_dir_all(dir).await?;
+            tokio::fs::write(&path, jpeg).await?;
+            Ok(path.to_string_lossy().into_owned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 1x1 red PNG
+    const PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC";
+
+    fn png() -> Vec<u8> {
+        base64::engine::general_purpose::STANDARD
+            .decode(PNG)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_inline_thumbnail() {
+        let thumbnail = store_thumbnail(&png(), &ThumbnailConfig::default())
+            .await
+            .unwrap();
+        assert!(thumbnail.starts_with("data:image/jpeg;base64,/9j/"));
+    }
+
+    #[tokio::test]
+    async fn test_directory_thumbnail() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = ThumbnailConfig {
+            max_dimension: 64,
+            storage: ThumbnailStorage::Directory(dir.path().join("thumbs")),
+        };
+        let path = store_thumbnail(&png(), &config).await.unwrap();
+
+        assert!(path.ends_with(".jpg"));
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.starts_with(&[0xFF, 0xD8, 0xFF]));
+
+        assert!(store_thumbnail(b"not an image", &config).await.is_err());
+    }
+}
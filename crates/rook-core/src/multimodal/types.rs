@@ -2,11 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Source provenance - tracks where memory content originated
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourceProvenance {
-    /// Original content modality (pdf, docx, image, text)
+    /// Original content modality (pdf, docx, epub, pptx, html, markdown, email, code, image, text)
     pub modality: String,
 
     /// Original file name if known
@@ -33,6 +34,30 @@ pub struct SourceProvenance {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub section: Option<String>,
 
+    /// Message-ID of the source email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+
+    /// Message-ID of the root of the source email's thread
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+
+    /// Programming language of source code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// URL the content was fetched from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Timestamp of the fetch, for content from a URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Thumbnail of an image source: a file path or a `data:` URI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+
     /// Timestamp of extraction
     pub extracted_at: chrono::DateTime<chrono::Utc>,
 }
@@ -48,6 +73,12 @@ impl SourceProvenance {
             extraction_method: None,
             page_number: None,
             section: None,
+            message_id: None,
+            thread_id: None,
+            language: None,
+            url: None,
+            fetched_at: None,
+            thumbnail: None,
             extracted_at: chrono::Utc::now(),
         }
     }
@@ -88,6 +119,41 @@ impl SourceProvenance {
         self
     }
 
+    /// Set email message ID
+    pub fn with_message_id(mut self, message_id: impl Into<String>) -> Self {
+        self.message_id = Some(message_id.into());
+        self
+    }
+
+    /// Set email thread ID
+    pub fn with_thread_id(mut self, thread_id: impl Into<String>) -> Self {
+        self.thread_id = Some(thread_id.into());
+        self
+    }
+
+    /// Set source code language
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set source URL and fetch timestamp
+    pub fn with_url(
+        mut self,
+        url: impl Into<String>,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+    ) -> Self {
+        self.url = Some(url.into());
+        self.fetched_at = Some(fetched_at);
+        self
+    }
+
+    /// Set image thumbnail (file path or `data:` URI)
+    pub fn with_thumbnail(mut self, thumbnail: impl Into<String>) -> Self {
+        self.thumbnail = Some(thumbnail.into());
+        self
+    }
+
     /// Convert to metadata HashMap for memory storage
     pub fn to_metadata(&self) -> HashMap<String, serde_json::Value> {
         let mut metadata = HashMap::new();
@@ -139,6 +205,48 @@ impl SourceProvenance {
             );
         }
 
+        if let Some(ref message_id) = self.message_id {
+            metadata.insert(
+                "source_message_id".to_string(),
+                serde_json::Value::String(message_id.clone()),
+            );
+        }
+
+        if let Some(ref thread_id) = self.thread_id {
+            metadata.insert(
+                "source_thread_id".to_string(),
+                serde_json::Value::String(thread_id.clone()),
+            );
+        }
+
+        if let Some(ref language) = self.language {
+            metadata.insert(
+                "source_language".to_string(),
+                serde_json::Value::String(language.clone()),
+            );
+        }
+
+        if let Some(ref url) = self.url {
+            metadata.insert(
+                "source_url".to_string(),
+                serde_json::Value::String(url.clone()),
+            );
+        }
+
+        if let Some(fetched_at) = self.fetched_at {
+            metadata.insert(
+                "source_fetched_at".to_string(),
+                serde_json::Value::String(fetched_at.to_rfc3339()),
+            );
+        }
+
+        if let Some(ref thumbnail) = self.thumbnail {
+            metadata.insert(
+                "source_thumbnail".to_string(),
+                serde_json::Value::String(thumbnail.clone()),
+            );
+        }
+
         metadata.insert(
             "extracted_at".to_string(),
             serde_json::Value::String(self.extracted_at.to_rfc3339()),
@@ -154,6 +262,11 @@ pub struct MultimodalIngestResult {
     /// IDs of created memories
     pub memory_ids: Vec<String>,
 
+    /// ID of the document record linking the memories, when the content
+    /// was split into several
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub document_id: Option<String>,
+
     /// Source provenance for tracking
     pub provenance: SourceProvenance,
 
@@ -180,14 +293,34 @@ pub struct MultimodalConfig {
     /// Overlap between chunks (in characters)
     pub chunk_overlap: usize,
 
-    /// Whether to create separate memories per page (for PDFs)
+    /// Whether to create separate memories per page (for PDFs). Overrides
+    /// `chunking` for MIME types without an entry in `mime_chunking`
     pub split_by_page: bool,
 
+    /// How to split extracted text into memories
+    pub chunking: ChunkingStrategy,
+
+    /// Chunking strategy by MIME type, overriding `chunking`
+    pub mime_chunking: HashMap<String, ChunkingStrategy>,
+
+    /// Settings for semantic chunking
+    pub semantic: SemanticChunkConfig,
+
+    /// Thumbnails and embeddings of images
+    pub image: ImageMemoryConfig,
+
+    /// Whether content split into several memories also gets a document
+    /// record linking them (see [`Memory::get_document`](crate::Memory::get_document))
+    pub document_records: bool,
+
     /// Whether to preserve document structure in metadata
     pub preserve_structure: bool,
 
     /// Minimum extracted text length to consider successful
     pub min_text_length: usize,
+
+    /// Limits for fetching content from URLs
+    pub url_fetch: UrlFetchConfig,
 }
 
 impl Default for MultimodalConfig {
@@ -196,8 +329,14 @@ impl Default for MultimodalConfig {
             max_chunk_size: 2000,
             chunk_overlap: 200,
             split_by_page: false,
+            chunking: ChunkingStrategy::default(),
+            mime_chunking: HashMap::new(),
+            semantic: SemanticChunkConfig::default(),
+            image: ImageMemoryConfig::default(),
+            document_records: true,
             preserve_structure: true,
             min_text_length: 10,
+            url_fetch: UrlFetchConfig::default(),
         }
     }
 }
@@ -219,6 +358,221 @@ impl MultimodalConfig {
             ..Default::default()
         }
     }
+
+    /// Create config that splits text where its topic changes
+    pub fn with_semantic_chunking() -> Self {
+        Self {
+            chunking: ChunkingStrategy::Semantic,
+            ..Default::default()
+        }
+    }
+
+    /// Use `strategy` for content of one MIME type
+    pub fn with_chunking_for(
+        mut self,
+        mime_type: impl Into<String>,
+        strategy: ChunkingStrategy,
+    ) -> Self {
+        self.mime_chunking.insert(mime_type.into(), strategy);
+        self
+    }
+
+    /// Chunking strategy for content of a MIME type
+    pub fn chunking_for(&self, mime_type: &str) -> ChunkingStrategy {
+        match self.mime_chunking.get(mime_type) {
+            Some(strategy) => *strategy,
+            None if self.split_by_page => ChunkingStrategy::Page,
+            None => self.chunking,
+        }
+    }
+}
+
+/// How extracted text is split into memories
+///
+/// Presentations, spreadsheets and source code are always split into their
+/// slides, row groups and definitions, whatever the strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkingStrategy {
+    /// Fixed-size chunks with overlap, cut at sentence ends where possible
+    #[default]
+    Fixed,
+    /// One chunk per page, for documents with page structure
+    Page,
+    /// Sections under Markdown headings, split at paragraphs when too long
+    Heading,
+    /// Sentences grouped where their embeddings stay similar, within
+    /// heading sections. Costs one embedding call per sentence
+    Semantic,
+}
+
+/// Settings for semantic chunking
+#[derive(Debug, Clone)]
+pub struct SemanticChunkConfig {
+    /// Percentile of the embedding distances between neighbouring sentences
+    /// above which the text is split (0-100)
+    pub breakpoint_percentile: f32,
+
+    /// Sentences on each side embedded along with a sentence, smoothing out
+    /// short sentences
+    pub window: usize,
+
+    /// Chunks shorter than this are merged into the one before (in characters)
+    pub min_chunk_size: usize,
+}
+
+impl Default for SemanticChunkConfig {
+    fn default() -> Self {
+        Self {
+            breakpoint_percentile: 90.0,
+            window: 1,
+            min_chunk_size: 200,
+        }
+    }
+}
+
+/// Configuration for image memories
+#[derive(Debug, Clone)]
+pub struct ImageMemoryConfig {
+    /// Store a thumbnail of each image in its provenance metadata (requires
+    /// the `image` feature)
+    pub thumbnail: Option<ThumbnailConfig>,
+
+    /// Whether to embed images into the memory's image index, when one is
+    /// configured. Images no extractor can describe are then still stored,
+    /// to be found by their embedding
+    pub embed: bool,
+}
+
+impl Default for ImageMemoryConfig {
+    fn default() -> Self {
+        Self {
+            thumbnail: None,
+            embed: true,
+        }
+    }
+}
+
+/// How image thumbnails are made and stored
+#[derive(Debug, Clone)]
+pub struct ThumbnailConfig {
+    /// Longest side of the thumbnail (in pixels)
+    pub max_dimension: u32,
+
+    /// Where the thumbnail is kept
+    pub storage: ThumbnailStorage,
+}
+
+impl Default for ThumbnailConfig {
+    fn default() -> Self {
+        Self {
+            max_dimension: 256,
+            storage: ThumbnailStorage::Inline,
+        }
+    }
+}
+
+/// Where image thumbnails are kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThumbnailStorage {
+    /// Base64 `data:` URI in the memory's metadata
+    Inline,
+    /// JPEG file in a directory, named by the image's content hash, with its
+    /// path in the memory's metadata
+    Directory(PathBuf),
+}
+
+/// Limits for fetching content from URLs
+#[derive(Debug, Clone)]
+pub struct UrlFetchConfig {
+    /// Request timeout, covering connection and download (in seconds)
+    pub timeout_secs: u64,
+
+    /// Largest response body accepted (in bytes)
+    pub max_bytes: usize,
+
+    /// Whether to check the site's robots.txt before fetching
+    pub respect_robots: bool,
+
+    /// User-Agent header, whose product token is matched against robots.txt
+    pub user_agent: String,
+}
+
+impl Default for UrlFetchConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            max_bytes: 25 * 1024 * 1024,
+            respect_robots: true,
+            user_agent: format!("rook/{}", env!("CARGO_PKG_VERSION")),
+        }
+    }
+}
+
+/// Configuration for ingesting a directory tree
+#[derive(Debug, Clone)]
+pub struct BulkIngestConfig {
+    /// Glob patterns of files to ingest, relative to the root (default: all files)
+    pub include: Vec<String>,
+
+    /// Glob patterns of files and directories to skip, relative to the root
+    pub exclude: Vec<String>,
+
+    /// Number of files ingested at once
+    pub concurrency: usize,
+
+    /// Where to record content hashes of ingested files (default:
+    /// `.rook-manifest.json` in the root)
+    pub manifest_path: Option<PathBuf>,
+
+    /// Whether to skip files and directories whose names start with a dot
+    pub skip_hidden: bool,
+
+    /// Whether to follow symbolic links
+    pub follow_links: bool,
+
+    /// Largest file ingested (in bytes)
+    pub max_file_bytes: u64,
+}
+
+impl Default for BulkIngestConfig {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            concurrency: 4,
+            manifest_path: None,
+            skip_hidden: true,
+            follow_links: false,
+            max_file_bytes: 100 * 1024 * 1024,
+        }
+    }
+}
+
+impl BulkIngestConfig {
+    /// Only ingest files matching a glob pattern, e.g. `**/*.pdf`
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Skip files or directories matching a glob pattern, e.g. `**/drafts`
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Set the number of files ingested at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Record content hashes at `path` instead of in the root
+    pub fn with_manifest_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.manifest_path = Some(path.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -260,6 +614,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_provenance_thread_metadata() {
+        let metadata = SourceProvenance::new("email")
+            .with_message_id("reply@example.com")
+            .with_thread_id("root@example.com")
+            .to_metadata();
+
+        assert_eq!(
+            metadata.get("source_message_id"),
+            Some(&serde_json::Value::String("reply@example.com".to_string()))
+        );
+        assert_eq!(
+            metadata.get("source_thread_id"),
+            Some(&serde_json::Value::String("root@example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_provenance_url_metadata() {
+        let fetched_at = chrono::Utc::now();
+        let metadata = SourceProvenance::new("html")
+            .with_url("https://example.com/post", fetched_at)
+            .to_metadata();
+
+        assert_eq!(
+            metadata.get("source_url"),
+            Some(&serde_json::Value::String(
+                "https://example.com/post".to_string()
+            ))
+        );
+        assert_eq!(
+            metadata.get("source_fetched_at"),
+            Some(&serde_json::Value::String(fetched_at.to_rfc3339()))
+        );
+    }
+
+    #[test]
+    fn test_chunking_for_mime_type() {
+        let config = MultimodalConfig::with_semantic_chunking()
+            .with_chunking_for("text/markdown", ChunkingStrategy::Heading);
+        assert_eq!(
+            config.chunking_for("text/markdown"),
+            ChunkingStrategy::Heading
+        );
+        assert_eq!(
+            config.chunking_for("application/pdf"),
+            ChunkingStrategy::Semantic
+        );
+
+        let config = MultimodalConfig::with_page_splitting();
+        assert_eq!(
+            config.chunking_for("application/pdf"),
+            ChunkingStrategy::Page
+        );
+    }
+
     #[test]
     fn test_config_presets() {
         let page_config = MultimodalConfig::with_page_splitting();
@@ -0,0 +1,197 @@
+//! Operational metrics.
+//!
+//! Metrics are recorded through the [`metrics`] facade. rook-core never
+//! installs a recorder, so every call here is a no-op until the embedding
+//! binary installs one (rook-server exports them in Prometheus format at
+//! `GET /metrics`).
+//!
+//! Provider calls are recorded under the operation names used for tracing
+//! (`llm.generate`, `embedder.embed`, `vector_store.search`, ...), so the
+//! `component` label is the part before the dot.
+
+use std::time::Duration;
+
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit};
+
+use crate::traits::TokenUsage;
+use crate::types::Grade;
+
+/// HTTP requests served, by method, route and status.
+pub const HTTP_REQUESTS_TOTAL: &str = "rook_http_requests_total";
+/// HTTP request latency, by method and route.
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "rook_http_request_duration_seconds";
+/// Memories stored in the configured vector store.
+pub const MEMORIES: &str = "rook_memories";
+/// Provider calls (LLM, embedder, vector store, reranker), by operation and outcome.
+pub const PROVIDER_CALLS_TOTAL: &str = "rook_provider_calls_total";
+/// Provider call latency, by operation.
+pub const PROVIDER_CALL_DURATION_SECONDS: &str = "rook_provider_call_duration_seconds";
+/// LLM tokens used, by model and kind (`prompt` or `completion`).
+pub const LLM_TOKENS_TOTAL: &str = "rook_llm_tokens_total";
+/// Classification cache lookups, by outcome (`exact`, `similar` or `miss`).
+pub const CLASSIFICATION_CACHE_LOOKUPS_TOTAL: &str = "rook_classification_cache_lookups_total";
+/// Background job runs, by job and outcome.
+pub const SCHEDULER_RUNS_TOTAL: &str = "rook_scheduler_runs_total";
+/// Background job run duration, by job.
+pub const SCHEDULER_RUN_DURATION_SECONDS: &str = "rook_scheduler_run_duration_seconds";
+/// Items changed by background job runs, by job.
+pub const SCHEDULER_ITEMS_TOTAL: &str = "rook_scheduler_items_total";
+/// Unix time of the last background job run, by job.
+pub const SCHEDULER_LAST_RUN_TIMESTAMP_SECONDS: &str = "rook_scheduler_last_run_timestamp_seconds";
+
+/// Pending strength signal grades applied to memories, by grade.
+pub const STRENGTH_UPDATES_TOTAL: &str = "rook_strength_updates_total";
+
+/// Latency buckets in seconds, shared by every `*_seconds` histogram.
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Register help text and units for every rook metric.
+///
+/// Call once after installing a recorder.
+pub fn describe() {
+    describe_counter!(HTTP_REQUESTS_TOTAL, "HTTP requests served");
+    describe_histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        Unit::Seconds,
+        "HTTP request latency"
+    );
+    describe_gauge!(MEMORIES, "Memories stored in the vector store");
+    describe_counter!(PROVIDER_CALLS_TOTAL, "LLM, embedder, vector store and reranker calls");
+    describe_histogram!(
+        PROVIDER_CALL_DURATION_SECONDS,
+        Unit::Seconds,
+        "LLM, embedder, vector store and reranker call latency"
+    );
+    describe_counter!(LLM_TOKENS_TOTAL, "LLM tokens used");
+    describe_counter!(
+        CLASSIFICATION_CACHE_LOOKUPS_TOTAL,
+        "Memory classifications looked up in the cache"
+    );
+    describe_counter!(SCHEDULER_RUNS_TOTAL, "Background job runs");
+    describe_histogram!(
+        SCHEDULER_RUN_DURATION_SECONDS,
+        Unit::Seconds,
+        "Background job run duration"
+    );
+    describe_counter!(SCHEDULER_ITEMS_TOTAL, "Items changed by background job runs");
+    describe_gauge!(
+        SCHEDULER_LAST_RUN_TIMESTAMP_SECONDS,
+        Unit::Seconds,
+        "Unix time of the last background job run"
+    );
+    describe_counter!(
+        STRENGTH_UPDATES_TOTAL,
+        "Strength signal grades applied to memories"
+    );
+}
+
+/// Record a served HTTP request. `route` should be the matched route
+/// template (e.g. `/memories/:id`) to keep label cardinality bounded.
+pub fn record_http_request(method: &str, route: &str, status: u16, duration: Duration) {
+    counter!(
+        HTTP_REQUESTS_TOTAL,
+        "method" => method.to_string(),
+        "route" => route.to_string(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+    histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        "method" => method.to_string(),
+        "route" => route.to_string()
+    )
+    .record(duration.as_secs_f64());
+}
+
+/// Record a provider call such as `llm.generate` or `vector_store.search`.
+pub fn record_provider_call(operation: &str, ok: bool, duration: Duration) {
+    let component = operation.split('.').next().unwrap_or(operation);
+    counter!(
+        PROVIDER_CALLS_TOTAL,
+        "component" => component.to_string(),
+        "operation" => operation.to_string(),
+        "outcome" => outcome(ok)
+    )
+    .increment(1);
+    histogram!(
+        PROVIDER_CALL_DURATION_SECONDS,
+        "component" => component.to_string(),
+        "operation" => operation.to_string()
+    )
+    .record(duration.as_secs_f64());
+}
+
+/// Record tokens reported by an LLM response.
+pub fn record_token_usage(model: &str, usage: &TokenUsage) {
+    counter!(LLM_TOKENS_TOTAL, "model" => model.to_string(), "kind" => "prompt")
+        .increment(u64::from(usage.prompt_tokens));
+    counter!(LLM_TOKENS_TOTAL, "model" => model.to_string(), "kind" => "completion")
+        .increment(u64::from(usage.completion_tokens));
+}
+
+/// Record a classification cache lookup: `exact`, `similar` or `miss`.
+pub fn record_classification_cache_lookup(outcome: &'static str) {
+    counter!(CLASSIFICATION_CACHE_LOOKUPS_TOTAL, "outcome" => outcome).increment(1);
+}
+
+/// Record a background job run (consolidation or a maintenance job).
+pub fn record_scheduler_run(job: &str, ok: bool, duration: Duration, changed: usize) {
+    counter!(SCHEDULER_RUNS_TOTAL, "job" => job.to_string(), "outcome" => outcome(ok)).increment(1);
+    histogram!(SCHEDULER_RUN_DURATION_SECONDS, "job" => job.to_string())
+        .record(duration.as_secs_f64());
+    counter!(SCHEDULER_ITEMS_TOTAL, "job" => job.to_string()).increment(changed as u64);
+    gauge!(SCHEDULER_LAST_RUN_TIMESTAMP_SECONDS, "job" => job.to_string())
+        .set(chrono::Utc::now().timestamp() as f64);
+}
+
+/// Record a strength signal grade applied to a memory.
+pub fn record_strength_update(grade: Grade) {
+    let grade = match grade {
+        Grade::Again => "again",
+        Grade::Hard => "hard",
+        Grade::Good => "good",
+        Grade::Easy => "easy",
+    };
+    counter!(STRENGTH_UPDATES_TOTAL, "grade" => grade).increment(1);
+}
+
+/// Set the number of stored memories.
+pub fn set_memory_count(count: u64) {
+    gauge!(MEMORIES).set(count as f64);
+}
+
+fn outcome(ok: bool) -> &'static str {
+    if ok {
+        "success"
+    } else {
+        "error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_without_recorder_is_noop() {
+        record_provider_call("vector_store.search", true, Duration::from_millis(3));
+        record_token_usage(
+            "gpt-4o-mini",
+            &TokenUsage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+            },
+        );
+        record_classification_cache_lookup("miss");
+        record_scheduler_run("consolidation", false, Duration::from_secs(1), 0);
+        set_memory_count(42);
+    }
+
+    #[test]
+    fn test_latency_buckets_sorted() {
+        assert!(LATENCY_BUCKETS.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -0,0 +1,11 @@
+//! Observability controls for tracing and metrics.
+//!
+//! This module provides:
+//! - Head-based trace sampling with per-operation rates
+//! - Propagation of sampling decisions into child spans
+//! - Request, provider and scheduler metrics
+
+pub mod metrics;
+pub mod sampling;
+
+pub use sampling::{Sampler, SamplingConfig, SamplingDecision};
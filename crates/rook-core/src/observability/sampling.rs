@@ -0,0 +1,316 @@
+//! Head-based trace sampling.
+//!
+//! A sampling decision is made once per request or top-level operation and
+//! stored in a task-local, so child spans for LLM, embedding and vector store
+//! calls follow the root decision instead of sampling independently.
+//!
+//! Rates are looked up by operation name (e.g. `"POST /search"` or
+//! `"memory.search"`). Exact names win over prefix patterns ending in `*`;
+//! anything unmatched uses `default_rate`.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+use crate::error::{RookError, RookResult};
+
+tokio::task_local! {
+    static DECISION: SamplingDecision;
+}
+
+static GLOBAL: Lazy<Sampler> = Lazy::new(|| {
+    let config = SamplingConfig::from_env().unwrap_or_else(|e| {
+        tracing::warn!("Invalid trace sampling config, sampling everything: {}", e);
+        SamplingConfig::default()
+    });
+    Sampler::new(config)
+});
+
+/// Whether a request's spans are recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingDecision {
+    Sampled,
+    NotSampled,
+}
+
+impl SamplingDecision {
+    /// Whether spans should be recorded.
+    pub fn is_sampled(self) -> bool {
+        matches!(self, Self::Sampled)
+    }
+}
+
+/// Sampling configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SamplingConfig {
+    /// Rate for operations without a specific rate, in `[0, 1]` (default: 1.0).
+    pub default_rate: f64,
+    /// Per-route or per-operation rates.
+    pub rates: HashMap<String, f64>,
+    /// Report failed requests even when they were not sampled (default: true).
+    pub always_sample_errors: bool,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            default_rate: 1.0,
+            rates: HashMap::new(),
+            always_sample_errors: true,
+        }
+    }
+}
+
+impl SamplingConfig {
+    /// Load from environment variables.
+    ///
+    /// - `ROOK_TRACE_SAMPLING_FILE` - TOML, JSON, or YAML file with the full config
+    /// - `ROOK_TRACE_SAMPLE_RATE` - default rate (default: 1.0)
+    /// - `ROOK_TRACE_SAMPLE_RATES` - per-operation rates, e.g.
+    ///   `POST /search=0.1,memory.add=0.5`
+    /// - `ROOK_TRACE_SAMPLE_ERRORS` - set to `false` to drop unsampled errors
+    pub fn from_env() -> RookResult<Self> {
+        if let Ok(path) = std::env::var("ROOK_TRACE_SAMPLING_FILE") {
+            return Self::from_file(path);
+        }
+
+        let mut config = Self::default();
+
+        if let Ok(rate) = std::env::var("ROOK_TRACE_SAMPLE_RATE") {
+            config.default_rate = rate.trim().parse().map_err(|_| {
+                RookError::Configuration(format!("Invalid ROOK_TRACE_SAMPLE_RATE: {}", rate))
+            })?;
+        }
+
+        if let Ok(rates) = std::env::var("ROOK_TRACE_SAMPLE_RATES") {
+            for entry in rates.split(',').filter(|e| !e.trim().is_empty()) {
+                let (operation, rate) = entry.rsplit_once('=').ok_or_else(|| {
+                    RookError::Configuration(format!("Invalid sampling rate entry: {}", entry))
+                })?;
+                let rate: f64 = rate.trim().parse().map_err(|_| {
+                    RookError::Configuration(format!("Invalid sampling rate entry: {}", entry))
+                })?;
+                config.rates.insert(operation.trim().to_string(), rate);
+            }
+        }
+
+        if let Ok(value) = std::env::var("ROOK_TRACE_SAMPLE_ERRORS") {
+            config.always_sample_errors =
+                !matches!(value.to_lowercase().as_str(), "false" | "0" | "no");
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load from a file (TOML, JSON, or YAML).
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> RookResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let config: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => {
+                toml::from_str(&content).map_err(|e| RookError::Configuration(e.to_string()))?
+            }
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            Some("yaml" | "yml") => serde_yaml::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            _ => {
+                return Err(RookError::Configuration(
+                    "Unsupported sampling config format. Use .toml, .json, or .yaml".to_string(),
+                ))
+            }
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that all rates are within `[0, 1]`.
+    pub fn validate(&self) -> RookResult<()> {
+        let valid = |rate: f64| (0.0..=1.0).contains(&rate);
+        if !valid(self.default_rate) {
+            return Err(RookError::validation(format!(
+                "default_rate must be between 0 and 1, got {}",
+                self.default_rate
+            )));
+        }
+        if let Some((operation, rate)) = self.rates.iter().find(|(_, r)| !valid(**r)) {
+            return Err(RookError::validation(format!(
+                "Sampling rate for '{}' must be between 0 and 1, got {}",
+                operation, rate
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rate applied to an operation.
+    pub fn rate_for(&self, operation: &str) -> f64 {
+        if let Some(rate) = self.rates.get(operation) {
+            return *rate;
+        }
+
+        self.rates
+            .iter()
+            .filter_map(|(pattern, rate)| {
+                let prefix = pattern.strip_suffix('*')?;
+                operation.starts_with(prefix).then_some((prefix.len(), *rate))
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, rate)| rate)
+            .unwrap_or(self.default_rate)
+    }
+}
+
+/// Makes head-based sampling decisions from a reloadable config.
+#[derive(Debug)]
+pub struct Sampler {
+    config: RwLock<SamplingConfig>,
+}
+
+impl Sampler {
+    /// Create a sampler.
+    pub fn new(config: SamplingConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+        }
+    }
+
+    /// Current configuration.
+    pub fn config(&self) -> SamplingConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the configuration at runtime.
+    pub fn reload(&self, config: SamplingConfig) -> RookResult<()> {
+        config.validate()?;
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        Ok(())
+    }
+
+    /// Whether unsampled failures should still be reported.
+    pub fn always_sample_errors(&self) -> bool {
+        self.config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .always_sample_errors
+    }
+
+    /// Decide whether to sample an operation.
+    pub fn decide(&self, operation: &str) -> SamplingDecision {
+        let rate = self
+            .config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .rate_for(operation);
+
+        let sampled = if rate >= 1.0 {
+            true
+        } else if rate <= 0.0 {
+            false
+        } else {
+            rand::random::<f64>() < rate
+        };
+
+        if sampled {
+            SamplingDecision::Sampled
+        } else {
+            SamplingDecision::NotSampled
+        }
+    }
+}
+
+/// Process-wide sampler, initialized from the environment.
+pub fn global() -> &'static Sampler {
+    &GLOBAL
+}
+
+/// Sampling decision of the current task, if one has been made.
+pub fn current_decision() -> Option<SamplingDecision> {
+    DECISION.try_with(|d| *d).ok()
+}
+
+/// Run a future under an explicit sampling decision.
+pub async fn with_decision<F: Future>(decision: SamplingDecision, future: F) -> F::Output {
+    DECISION.scope(decision, future).await
+}
+
+/// Run a top-level operation, sampling it with the global sampler unless a
+/// decision has already been made by a caller.
+pub async fn sampled<F: Future>(operation: &str, future: F) -> F::Output {
+    match current_decision() {
+        Some(_) => future.await,
+        None => with_decision(global().decide(operation), future).await,
+    }
+}
+
+/// Span for a child call (LLM, embedding, vector store) that follows the
+/// current sampling decision. Without a decision, the span is recorded.
+pub fn child_span(operation: &'static str) -> Span {
+    match current_decision() {
+        Some(SamplingDecision::NotSampled) => Span::none(),
+        _ => tracing::info_span!("rook.call", operation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_lookup() {
+        let config = SamplingConfig {
+            default_rate: 0.5,
+            rates: HashMap::from([
+                ("POST /search".to_string(), 0.1),
+                ("memory.*".to_string(), 0.2),
+                ("memory.add*".to_string(), 0.9),
+            ]),
+            always_sample_errors: true,
+        };
+
+        assert_eq!(config.rate_for("POST /search"), 0.1);
+        assert_eq!(config.rate_for("memory.search"), 0.2);
+        assert_eq!(config.rate_for("memory.add"), 0.9);
+        assert_eq!(config.rate_for("GET /health"), 0.5);
+    }
+
+    #[test]
+    fn test_decide_extremes_and_reload() {
+        let sampler = Sampler::new(SamplingConfig {
+            default_rate: 0.0,
+            ..Default::default()
+        });
+        assert_eq!(sampler.decide("anything"), SamplingDecision::NotSampled);
+
+        sampler.reload(SamplingConfig::default()).unwrap();
+        assert_eq!(sampler.decide("anything"), SamplingDecision::Sampled);
+
+        let invalid = SamplingConfig {
+            default_rate: 1.5,
+            ..Default::default()
+        };
+        assert!(sampler.reload(invalid).is_err());
+        assert_eq!(sampler.config(), SamplingConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_decision_propagates_to_children() {
+        assert!(current_decision().is_none());
+
+        with_decision(SamplingDecision::NotSampled, async {
+            assert!(child_span("llm.generate").is_none());
+
+            // Nested operations keep the outer decision.
+            sampled("memory.search", async {
+                assert_eq!(current_decision(), Some(SamplingDecision::NotSampled));
+            })
+            .await;
+        })
+        .await;
+    }
+}
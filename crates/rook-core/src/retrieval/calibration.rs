@@ -0,0 +1,293 @@
+//! Score threshold calibration per embedder.
+//!
+//! Similarity scores are not comparable across embedding models: one model
+//! may score unrelated texts around 0.1 and another around 0.7. Calibration
+//! samples stored vectors, compares the similarity of nearest-neighbor pairs
+//! (a proxy for relevant pairs) with that of random pairs, and derives
+//! thresholds for each [`RelativeThreshold`] level.
+//!
+//! Callers then pass a [`ThresholdSpec`] that is either an absolute score or a
+//! level such as `"strict"`, resolved against the calibration for the
+//! configured embedder.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::{RookError, RookResult};
+use crate::memory::cosine_similarity;
+
+/// A named threshold level, resolved per embedder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeThreshold {
+    /// Keep only results about as similar as a typical nearest neighbor.
+    Strict,
+    /// Balance between strict and loose.
+    Default,
+    /// Drop only results indistinguishable from random pairs.
+    Loose,
+}
+
+impl RelativeThreshold {
+    /// Threshold used when the embedder has not been calibrated.
+    ///
+    /// These suit cosine scores from common text embedding models.
+    pub fn uncalibrated(self) -> f32 {
+        match self {
+            Self::Strict => 0.7,
+            Self::Default => 0.5,
+            Self::Loose => 0.3,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Default => "default",
+            Self::Loose => "loose",
+        }
+    }
+}
+
+impl fmt::Display for RelativeThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RelativeThreshold {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "default" => Ok(Self::Default),
+            "loose" => Ok(Self::Loose),
+            other => Err(RookError::validation(format!(
+                "Unknown threshold '{}'; expected a number, 'strict', 'default' or 'loose'",
+                other
+            ))),
+        }
+    }
+}
+
+/// A score threshold: absolute, or relative to the embedder's calibration.
+///
+/// Deserializes from a number (`0.42`) or a level name (`"strict"`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ThresholdSpec {
+    /// Raw similarity score.
+    Absolute(f32),
+    /// Named level.
+    Relative(RelativeThreshold),
+}
+
+impl From<f32> for ThresholdSpec {
+    fn from(score: f32) -> Self {
+        Self::Absolute(score)
+    }
+}
+
+impl From<RelativeThreshold> for ThresholdSpec {
+    fn from(level: RelativeThreshold) -> Self {
+        Self::Relative(level)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThresholdSpec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f32),
+            Name(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(score) => Ok(Self::Absolute(score)),
+            Raw::Name(name) => match name.trim().parse::<f32>() {
+                Ok(score) => Ok(Self::Absolute(score)),
+                Err(_) => name
+                    .parse()
+                    .map(Self::Relative)
+                    .map_err(serde::de::Error::custom),
+            },
+        }
+    }
+}
+
+/// Calibrated thresholds for one embedder model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdCalibration {
+    /// Embedder provider, e.g. `openai`.
+    pub embedder: String,
+    /// Embedding model.
+    pub model: String,
+    /// Number of vectors sampled.
+    pub sample_size: usize,
+    /// Mean nearest-neighbor similarity.
+    pub relevant_mean: f32,
+    /// Mean random-pair similarity.
+    pub random_mean: f32,
+    /// Threshold for [`RelativeThreshold::Strict`].
+    pub strict: f32,
+    /// Threshold for [`RelativeThreshold::Default`].
+    pub default: f32,
+    /// Threshold for [`RelativeThreshold::Loose`].
+    pub loose: f32,
+    /// When the calibration ran.
+    pub calibrated_at: DateTime<Utc>,
+}
+
+impl ThresholdCalibration {
+    /// Estimate thresholds from relevant and random pair scores.
+    ///
+    /// - `loose` is the 95th percentile of random pairs: anything below it
+    ///   looks like noise.
+    /// - `strict` is the median relevant pair.
+    /// - `default` is halfway between `loose` and the 25th percentile of
+    ///   relevant pairs.
+    ///
+    /// Levels are kept in order, so `loose <= default <= strict`.
+    pub fn estimate(
+        embedder: impl Into<String>,
+        model: impl Into<String>,
+        relevant: &[f32],
+        random: &[f32],
+    ) -> RookResult<Self> {
+        if relevant.is_empty() || random.is_empty() {
+            return Err(RookError::validation(
+                "Calibration needs at least two stored vectors",
+            ));
+        }
+
+        let mut relevant = relevant.to_vec();
+        let mut random = random.to_vec();
+        relevant.sort_by(f32::total_cmp);
+        random.sort_by(f32::total_cmp);
+
+        let loose = percentile(&random, 0.95);
+        let strict = percentile(&relevant, 0.5).max(loose);
+        let default = ((loose + percentile(&relevant, 0.25)) / 2.0).clamp(loose, strict);
+
+        Ok(Self {
+            embedder: embedder.into(),
+            model: model.into(),
+            sample_size: relevant.len(),
+            relevant_mean: mean(&relevant),
+            random_mean: mean(&random),
+            strict,
+            default,
+            loose,
+            calibrated_at: Utc::now(),
+        })
+    }
+
+    /// Threshold for a level.
+    pub fn threshold(&self, level: RelativeThreshold) -> f32 {
+        match level {
+            RelativeThreshold::Strict => self.strict,
+            RelativeThreshold::Default => self.default,
+            RelativeThreshold::Loose => self.loose,
+        }
+    }
+}
+
+/// Score nearest-neighbor and random pairs among sampled vectors.
+///
+/// Returns `(relevant, random)` cosine scores, one of each per vector. Fewer
+/// than two vectors yield no pairs.
+pub fn sample_pair_scores(vectors: &[Vec<f32>]) -> (Vec<f32>, Vec<f32>) {
+    if vectors.len() < 2 {
+        return (vec![], vec![]);
+    }
+
+    let relevant = vectors
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            vectors
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, b)| cosine_similarity(a, b))
+                .fold(f32::MIN, f32::max)
+        })
+        .collect();
+
+    // Pair each vector with a random other one
+    let mut partners: Vec<usize> = (0..vectors.len()).collect();
+    partners.shuffle(&mut rand::thread_rng());
+    let random = partners
+        .iter()
+        .enumerate()
+        .map(|(i, &j)| {
+            let j = if i == j { (j + 1) % vectors.len() } else { j };
+            cosine_similarity(&vectors[i], &vectors[j])
+        })
+        .collect();
+
+    (relevant, random)
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let rank = (p * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_orders_levels() {
+        let relevant: Vec<f32> = (0..20).map(|i| 0.6 + i as f32 * 0.01).collect();
+        let random: Vec<f32> = (0..20).map(|i| 0.1 + i as f32 * 0.01).collect();
+
+        let calibration =
+            ThresholdCalibration::estimate("openai", "text-embedding-3-small", &relevant, &random)
+                .unwrap();
+        assert!((calibration.loose - 0.28).abs() < 1e-6);
+        assert!((calibration.strict - 0.69).abs() < 1e-6);
+        assert!((calibration.default - 0.46).abs() < 1e-6);
+        assert_eq!(calibration.threshold(RelativeThreshold::Strict), calibration.strict);
+
+        // Overlapping distributions still keep the levels in order
+        let overlap = ThresholdCalibration::estimate("ollama", "m", &random, &relevant).unwrap();
+        assert!(overlap.loose <= overlap.default && overlap.default <= overlap.strict);
+
+        assert!(ThresholdCalibration::estimate("openai", "m", &[], &random).is_err());
+    }
+
+    #[test]
+    fn test_sample_pair_scores() {
+        let vectors = vec![vec![1.0, 0.0], vec![0.9, 0.1], vec![0.0, 1.0], vec![0.1, 0.9]];
+        let (relevant, random) = sample_pair_scores(&vectors);
+        assert_eq!(relevant.len(), 4);
+        assert_eq!(random.len(), 4);
+        assert!(relevant.iter().all(|&s| s > 0.9));
+        assert!(random.iter().all(|&s| s <= 1.0));
+        assert_eq!(sample_pair_scores(&vectors[..1]), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_threshold_spec_deserialization() {
+        let spec: ThresholdSpec = serde_json::from_str("0.42").unwrap();
+        assert_eq!(spec, ThresholdSpec::Absolute(0.42));
+        let spec: ThresholdSpec = serde_json::from_str(r#""Strict""#).unwrap();
+        assert_eq!(spec, ThresholdSpec::Relative(RelativeThreshold::Strict));
+        let spec: ThresholdSpec = serde_json::from_str(r#""0.5""#).unwrap();
+        assert_eq!(spec, ThresholdSpec::Absolute(0.5));
+        assert!(serde_json::from_str::<ThresholdSpec>(r#""medium""#).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Result diversification using maximal marginal relevance (MMR).
+//!
+//! After long usage a store often holds several restatements of the same
+//! fact, which all match a query equally well. MMR picks results one at a
+//! time, trading each candidate's relevance against its similarity to the
+//! results already picked, so the top results cover different facts.
+//!
+//! Reference: Carbonell & Goldstein (1998)
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::dedup::cosine_similarity;
+
+/// Configuration for MMR diversification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MmrConfig {
+    /// Weight of relevance against diversity.
+    /// Range: 0.0-1.0. 1.0 = relevance only, 0.0 = diversity only.
+    /// Default: 0.7
+    pub lambda: f32,
+}
+
+impl Default for MmrConfig {
+    fn default() -> Self {
+        Self { lambda: 0.7 }
+    }
+}
+
+impl MmrConfig {
+    /// Create config with custom lambda.
+    pub fn with_lambda(lambda: f32) -> Self {
+        Self {
+            lambda: lambda.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Diversifier reorders results by maximal marginal relevance.
+pub struct Diversifier {
+    config: MmrConfig,
+}
+
+impl Diversifier {
+    /// Create a new diversifier with the given configuration.
+    pub fn new(config: MmrConfig) -> Self {
+        Self { config }
+    }
+
+    /// Pick up to `limit` results by maximal marginal relevance.
+    ///
+    /// Each step picks the candidate maximizing
+    /// `lambda * relevance - (1 - lambda) * max_similarity`, where relevance
+    /// is the score relative to the top score and max_similarity the cosine
+    /// similarity to the closest result already picked.
+    /// Candidates without an embedding count as dissimilar to everything.
+    ///
+    /// # Arguments
+    /// * `results` - (id, score) pairs, pre-sorted by score descending
+    /// * `embeddings` - Map of id -> embedding
+    /// * `limit` - Maximum number of results to pick
+    ///
+    /// # Returns
+    /// Picked (id, score) pairs in pick order, with their original scores.
+    pub fn diversify_with_lookup(
+        &self,
+        results: Vec<(String, f32)>,
+        embeddings: &HashMap<String, Vec<f32>>,
+        limit: usize,
+    ) -> Vec<(String, f32)> {
+        let lambda = self.config.lambda.clamp(0.0, 1.0);
+        let max_score = results.iter().map(|(_, s)| *s).fold(0.0, f32::max);
+        let relevance = |score: f32| {
+            if max_score > f32::EPSILON {
+                (score / max_score).max(0.0)
+            } else {
+                0.0
+            }
+        };
+
+        let mut remaining = results;
+        // Closest similarity of each remaining candidate to the picked results
+        let mut max_similarity = vec![0.0f32; remaining.len()];
+        let mut picked = Vec::with_capacity(limit.min(remaining.len()));
+
+        while picked.len() < limit && !remaining.is_empty() {
+            let mut best = 0;
+            let mut best_mmr = f32::MIN;
+            for (i, (_, score)) in remaining.iter().enumerate() {
+                let mmr = lambda * relevance(*score) - (1.0 - lambda) * max_similarity[i];
+                if mmr > best_mmr {
+                    best = i;
+                    best_mmr = mmr;
+                }
+            }
+
+            let (id, score) = remaining.remove(best);
+            max_similarity.remove(best);
+            if let Some(picked_embedding) = embeddings.get(&id) {
+                for (i, (other, _)) in remaining.iter().enumerate() {
+                    if let Some(embedding) = embeddings.get(other) {
+                        let similarity = cosine_similarity(embedding, picked_embedding);
+                        max_similarity[i] = max_similarity[i].max(similarity);
+                    }
+                }
+            }
+            picked.push((id, score));
+        }
+
+        picked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restatements() -> Vec<(String, f32)> {
+        vec![
+            ("pizza".to_string(), 0.95),
+            ("pizza-again".to_string(), 0.94),
+            ("pizza-third".to_string(), 0.93),
+            ("pasta".to_string(), 0.80),
+        ]
+    }
+
+    fn restatement_embeddings() -> HashMap<String, Vec<f32>> {
+        let mut embeddings = HashMap::new();
+        embeddings.insert("pizza".to_string(), vec![1.0, 0.0]);
+        embeddings.insert("pizza-again".to_string(), vec![0.99, 0.05]);
+        embeddings.insert("pizza-third".to_string(), vec![0.98, 0.1]);
+        embeddings.insert("pasta".to_string(), vec![0.3, 1.0]);
+        embeddings
+    }
+
+    #[test]
+    fn test_diversify_promotes_distinct_results() {
+        let (results, embeddings) = (restatements(), restatement_embeddings());
+        let diversifier = Diversifier::new(MmrConfig::default());
+
+        let picked = diversifier.diversify_with_lookup(results, &embeddings, 2);
+        let ids: Vec<_> = picked.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["pizza", "pasta"]);
+        // Original scores are kept
+        assert_eq!(picked[1].1, 0.80);
+    }
+
+    #[test]
+    fn test_lambda_one_keeps_relevance_order() {
+        let (results, embeddings) = (restatements(), restatement_embeddings());
+        let diversifier = Diversifier::new(MmrConfig::with_lambda(1.0));
+
+        let picked = diversifier.diversify_with_lookup(results.clone(), &embeddings, 4);
+        assert_eq!(picked, results);
+    }
+
+    #[test]
+    fn test_without_embeddings() {
+        let diversifier = Diversifier::new(MmrConfig::default());
+        let results = vec![("a".to_string(), 0.9), ("b".to_string(), 0.9)];
+
+        let picked = diversifier.diversify_with_lookup(results.clone(), &HashMap::new(), 5);
+        assert_eq!(picked, results);
+        assert!(diversifier
+            .diversify_with_lookup(Vec::new(), &HashMap::new(), 5)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_with_lambda_clamps() {
+        assert_eq!(MmrConfig::with_lambda(1.5).lambda, 1.0);
+        assert_eq!(MmrConfig::with_lambda(-0.2).lambda, 0.0);
+    }
+}
@@ -15,6 +15,7 @@ use crate::error::RookResult;
 use super::activation::ActivatedMemory;
 use super::config::SpreadingConfig;
 use super::dedup::{DeduplicatableResult, Deduplicator};
+use super::diversity::{Diversifier, MmrConfig};
 use super::fusion::{FusionInputs, LinearFusion};
 use super::modes::{RetrievalConfig, RetrievalMode};
 use super::tantivy_search::{TantivySearcher, TextSearchResult};
@@ -41,6 +42,9 @@ pub struct RetrievalSignals {
     pub activation: Option<f32>,
     /// FSRS retrievability score (0-1).
     pub fsrs: Option<f32>,
+    /// Importance of the most central graph entity the memory mentions (0-1).
+    #[serde(default)]
+    pub importance: Option<f32>,
 }
 
 /// FSRS memory state for retrievability calculation.
@@ -88,23 +92,30 @@ pub trait FsrsStateProvider: Send + Sync {
     fn get_state(&self, id: &str) -> Option<FsrsMemoryState>;
 }
 
+/// Trait for graph entity importance (e.g. PageRank) backend.
+pub trait ImportanceProvider: Send + Sync {
+    /// Importance (0-1) of the most central entity a memory mentions.
+    fn importance(&self, memory_id: &str) -> Option<f32>;
+}
+
 /// Main retrieval engine combining all search methods.
 pub struct RetrievalEngine<V, G, F>
 where
     V: VectorSearcher,
-    G: ActivationGraph,
+    G: ActivationGraph + ?Sized,
     F: FsrsStateProvider,
 {
     vector_searcher: Arc<V>,
     text_searcher: Option<Arc<TantivySearcher>>,
     graph: Option<Arc<G>>,
     fsrs_provider: Option<Arc<F>>,
+    importance_provider: Option<Arc<dyn ImportanceProvider>>,
 }
 
 impl<V, G, F> RetrievalEngine<V, G, F>
 where
     V: VectorSearcher,
-    G: ActivationGraph,
+    G: ActivationGraph + ?Sized,
     F: FsrsStateProvider,
 {
     /// Create a new retrieval engine with vector search only.
@@ -114,6 +125,7 @@ where
             text_searcher: None,
             graph: None,
             fsrs_provider: None,
+            importance_provider: None,
         }
     }
 
@@ -135,6 +147,12 @@ where
         self
     }
 
+    /// Add entity importance provider (used by Precise mode).
+    pub fn with_importance(mut self, provider: Arc<dyn ImportanceProvider>) -> Self {
+        self.importance_provider = Some(provider);
+        self
+    }
+
     /// Retrieve memories matching a query.
     ///
     /// # Arguments
@@ -143,7 +161,8 @@ where
     /// * `config` - Retrieval configuration (mode, limits, etc.)
     ///
     /// # Returns
-    /// Vector of RetrievalResult sorted by score descending.
+    /// Vector of RetrievalResult sorted by score descending, or in MMR pick
+    /// order when `config.enable_mmr` is set.
     pub async fn retrieve(
         &self,
         query: &str,
@@ -152,6 +171,28 @@ where
     ) -> RookResult<Vec<RetrievalResult>> {
         let fetch_limit = config.limit * config.oversample_factor;
 
+        if !config.enable_mmr {
+            return self.retrieve_mode(query, query_embedding, config, fetch_limit).await;
+        }
+
+        // Rank every fetched candidate, then let MMR pick the final results
+        let candidates_config = RetrievalConfig {
+            limit: fetch_limit,
+            ..config.clone()
+        };
+        let results = self
+            .retrieve_mode(query, query_embedding, &candidates_config, fetch_limit)
+            .await?;
+        self.diversify_results(results, &config.mmr, config.limit).await
+    }
+
+    async fn retrieve_mode(
+        &self,
+        query: &str,
+        query_embedding: &[f32],
+        config: &RetrievalConfig,
+        fetch_limit: usize,
+    ) -> RookResult<Vec<RetrievalResult>> {
         match config.mode {
             RetrievalMode::Quick => self.retrieve_quick(query_embedding, config.limit).await,
             RetrievalMode::Standard => {
@@ -280,12 +321,21 @@ where
         // Collect FSRS scores
         let fsrs_scores = self.collect_fsrs_scores(&vector_results);
 
+        // Entity importance for every candidate
+        let candidates = vector_results
+            .iter()
+            .map(|(id, _)| id)
+            .chain(text_results.iter().map(|r| &r.id))
+            .chain(activation_results.iter().map(|r| &r.memory_id));
+        let importance_scores = self.collect_importance_scores(candidates);
+
         // Linear fusion
         let mut results = self.linear_fuse_all(
             &vector_results,
             &text_results,
             &activation_results,
             &fsrs_scores,
+            &importance_scores,
             &config.linear,
         );
 
@@ -329,6 +379,7 @@ where
             &[], // No BM25 in cognitive mode
             &activation_results,
             &fsrs_scores,
+            &HashMap::new(),
             &config.linear,
         );
 
@@ -366,6 +417,27 @@ where
         scores
     }
 
+    /// Collect entity importance scores for memories.
+    fn collect_importance_scores<'a>(
+        &self,
+        ids: impl Iterator<Item = &'a String>,
+    ) -> HashMap<String, f32> {
+        let mut scores = HashMap::new();
+
+        if let Some(provider) = &self.importance_provider {
+            for id in ids {
+                if scores.contains_key(id) {
+                    continue;
+                }
+                if let Some(importance) = provider.importance(id) {
+                    scores.insert(id.clone(), importance.clamp(0.0, 1.0));
+                }
+            }
+        }
+
+        scores
+    }
+
     /// Build result structs with signal information.
     fn build_results_with_signals(
         &self,
@@ -393,6 +465,7 @@ where
                     bm25: text_map.get(&id).copied(),
                     activation: activation_map.get(&id).copied(),
                     fsrs: fsrs_scores.and_then(|m| m.get(&id).copied()),
+                    importance: None,
                 },
                 id,
                 score,
@@ -407,6 +480,7 @@ where
         text_results: &[TextSearchResult],
         activation_results: &[ActivatedMemory],
         fsrs_scores: &HashMap<String, f32>,
+        importance_scores: &HashMap<String, f32>,
         weights: &LinearFusion,
     ) -> Vec<RetrievalResult> {
         // Collect all unique IDs
@@ -437,6 +511,11 @@ where
                 .fsrs_retrievability = *score;
         }
 
+        // Importance only adjusts memories another signal already found
+        for (id, inputs) in all_ids.iter_mut() {
+            inputs.importance = importance_scores.get(id).copied().unwrap_or(0.0);
+        }
+
         // Fuse and sort
         let mut results: Vec<_> = all_ids
             .into_iter()
@@ -466,6 +545,11 @@ where
                         } else {
                             None
                         },
+                        importance: if inputs.importance > 0.0 {
+                            Some(inputs.importance)
+                        } else {
+                            None
+                        },
                     },
                 }
             })
@@ -475,6 +559,34 @@ where
         results
     }
 
+    /// Reorder results by maximal marginal relevance, keeping `limit`.
+    async fn diversify_results(
+        &self,
+        results: Vec<RetrievalResult>,
+        config: &MmrConfig,
+        limit: usize,
+    ) -> RookResult<Vec<RetrievalResult>> {
+        let ids: Vec<_> = results.iter().map(|r| r.id.clone()).collect();
+        let embeddings = self.vector_searcher.get_embeddings(&ids).await?;
+
+        let mut signal_map: HashMap<_, _> = results
+            .iter()
+            .map(|r| (r.id.clone(), r.signals.clone()))
+            .collect();
+        let ranked: Vec<_> = results.into_iter().map(|r| (r.id, r.score)).collect();
+
+        let diversifier = Diversifier::new(config.clone());
+        Ok(diversifier
+            .diversify_with_lookup(ranked, &embeddings, limit)
+            .into_iter()
+            .map(|(id, score)| RetrievalResult {
+                signals: signal_map.remove(&id).unwrap_or_default(),
+                id,
+                score,
+            })
+            .collect())
+    }
+
     /// Apply deduplication to results.
     async fn deduplicate_results(
         &self,
@@ -647,6 +759,64 @@ mod tests {
         assert!(!results.is_empty());
     }
 
+    struct MockImportance;
+    impl ImportanceProvider for MockImportance {
+        fn importance(&self, id: &str) -> Option<f32> {
+            (id == "b").then_some(1.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_precise_mode_weights_importance() {
+        let searcher = MockVectorSearcher {
+            results: vec![("a".to_string(), 0.8), ("b".to_string(), 0.75)],
+            embeddings: HashMap::new(),
+        };
+
+        let engine: RetrievalEngine<MockVectorSearcher, MockGraph, MockFsrs> =
+            RetrievalEngine::new(Arc::new(searcher)).with_importance(Arc::new(MockImportance));
+
+        let config = RetrievalConfig::precise(10);
+        let results = engine.retrieve("test", &[1.0], &config).await.unwrap();
+        assert_eq!(results[0].id, "b");
+        assert_eq!(results[0].signals.importance, Some(1.0));
+        assert!(results[1].signals.importance.is_none());
+
+        // Importance is not a signal outside Precise mode
+        let config = RetrievalConfig::standard(10);
+        let results = engine.retrieve("test", &[1.0], &config).await.unwrap();
+        assert!(results.iter().all(|r| r.signals.importance.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_mmr_diversifies_results() {
+        let searcher = MockVectorSearcher {
+            results: vec![
+                ("a".to_string(), 0.9),
+                ("a2".to_string(), 0.89),
+                ("b".to_string(), 0.7),
+            ],
+            embeddings: HashMap::from([
+                ("a".to_string(), vec![1.0, 0.0]),
+                ("a2".to_string(), vec![1.0, 0.01]),
+                ("b".to_string(), vec![0.0, 1.0]),
+            ]),
+        };
+
+        let engine: RetrievalEngine<MockVectorSearcher, MockGraph, MockFsrs> =
+            RetrievalEngine::new(Arc::new(searcher));
+
+        let config = RetrievalConfig::quick(2).with_mmr(0.5);
+        let results = engine.retrieve("test", &[1.0], &config).await.unwrap();
+        let ids: Vec<_> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(results[1].signals.vector, Some(0.7));
+
+        let config = RetrievalConfig::quick(2);
+        let results = engine.retrieve("test", &[1.0], &config).await.unwrap();
+        assert_eq!(results[1].id, "a2");
+    }
+
     #[test]
     fn test_retrieval_mode_helpers() {
         assert!(RetrievalMode::Quick.uses_vector());
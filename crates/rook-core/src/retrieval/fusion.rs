@@ -20,6 +20,8 @@ pub struct FusionInputs {
     pub activation: f32,
     /// BM25 score (must be normalized before fusion).
     pub bm25_normalized: f32,
+    /// Importance of the entities the memory mentions (already 0-1).
+    pub importance: f32,
 }
 
 /// Reciprocal Rank Fusion for combining ranked lists.
@@ -88,6 +90,9 @@ pub struct LinearFusion {
     pub activation_weight: f32,
     /// Weight for BM25 text search score.
     pub bm25_weight: f32,
+    /// Weight for graph entity importance.
+    #[serde(default)]
+    pub importance_weight: f32,
 }
 
 impl Default for LinearFusion {
@@ -98,6 +103,7 @@ impl Default for LinearFusion {
             fsrs_weight: 0.2,
             activation_weight: 0.2,
             bm25_weight: 0.2,
+            importance_weight: 0.0,
         }
     }
 }
@@ -112,18 +118,21 @@ impl LinearFusion {
             fsrs_weight: 0.4,
             activation_weight: 0.2,
             bm25_weight: 0.0,
+            importance_weight: 0.0,
         }
     }
 
     /// Create fusion weights optimized for Precise mode (all signals).
     ///
-    /// Balances all retrieval signals for maximum accuracy.
+    /// Balances all retrieval signals for maximum accuracy, including the
+    /// importance of the graph entities a memory mentions.
     pub fn precise() -> Self {
         Self {
-            vector_weight: 0.35,
+            vector_weight: 0.3,
             fsrs_weight: 0.2,
-            activation_weight: 0.2,
+            activation_weight: 0.15,
             bm25_weight: 0.25,
+            importance_weight: 0.1,
         }
     }
 
@@ -138,7 +147,8 @@ impl LinearFusion {
         let score = inputs.vector * self.vector_weight
             + inputs.fsrs_retrievability * self.fsrs_weight
             + inputs.activation * self.activation_weight
-            + inputs.bm25_normalized * self.bm25_weight;
+            + inputs.bm25_normalized * self.bm25_weight
+            + inputs.importance * self.importance_weight;
         score.clamp(0.0, 1.0)
     }
 
@@ -161,8 +171,11 @@ impl LinearFusion {
 
     /// Validate that weights sum to approximately 1.0.
     pub fn validate(&self) -> Result<(), &'static str> {
-        let sum =
-            self.vector_weight + self.fsrs_weight + self.activation_weight + self.bm25_weight;
+        let sum = self.vector_weight
+            + self.fsrs_weight
+            + self.activation_weight
+            + self.bm25_weight
+            + self.importance_weight;
         if (sum - 1.0).abs() > 0.01 {
             return Err("Fusion weights should sum to 1.0");
         }
@@ -170,6 +183,7 @@ impl LinearFusion {
             || self.fsrs_weight < 0.0
             || self.activation_weight < 0.0
             || self.bm25_weight < 0.0
+            || self.importance_weight < 0.0
         {
             return Err("Fusion weights must be non-negative");
         }
@@ -233,6 +247,7 @@ mod tests {
             fsrs_retrievability: 0.6,
             activation: 0.5,
             bm25_normalized: 0.7,
+            importance: 0.0,
         };
 
         let score = fusion.fuse(&inputs);
@@ -252,6 +267,23 @@ mod tests {
         assert!(fusion.bm25_weight < 0.01);
     }
 
+    #[test]
+    fn test_precise_weights_use_importance() {
+        let fusion = LinearFusion::precise();
+        fusion.validate().unwrap();
+
+        let plain = FusionInputs {
+            vector: 0.8,
+            ..Default::default()
+        };
+        let central = FusionInputs {
+            importance: 1.0,
+            ..plain.clone()
+        };
+        assert!(fusion.fuse(&central) > fusion.fuse(&plain));
+        assert_eq!(LinearFusion::default().fuse(&central), LinearFusion::default().fuse(&plain));
+    }
+
     #[test]
     fn test_linear_batch_fusion() {
         let fusion = LinearFusion::default();
@@ -7,22 +7,30 @@
 //! - Cognitive: Activation + FSRS weighting
 
 mod activation;
+mod calibration;
 mod config;
 mod dedup;
+mod diversity;
 mod engine;
 mod fusion;
 mod modes;
+mod rerank_cache;
 mod tantivy_search;
 
 pub use activation::{
     spread_activation, spread_activation_by_id, ActivatedMemory, ActivationEdge, ActivationNode,
 };
+pub use calibration::{
+    sample_pair_scores, RelativeThreshold, ThresholdCalibration, ThresholdSpec,
+};
 pub use config::SpreadingConfig;
 pub use dedup::{DeduplicatableResult, DeduplicationConfig, Deduplicator};
+pub use diversity::{Diversifier, MmrConfig};
 pub use engine::{
-    ActivationGraph, FsrsMemoryState, FsrsStateProvider, RetrievalEngine, RetrievalResult,
-    RetrievalSignals, VectorSearcher,
+    ActivationGraph, FsrsMemoryState, FsrsStateProvider, ImportanceProvider, RetrievalEngine,
+    RetrievalResult, RetrievalSignals, VectorSearcher,
 };
 pub use fusion::{FusionInputs, LinearFusion, RrfFusion};
-pub use modes::{RetrievalConfig, RetrievalMode};
+pub use modes::{RetrievalConfig, RetrievalMode, MMR_MIN_OVERSAMPLE};
+pub use rerank_cache::{CachedReranker, RerankCacheConfig};
 pub use tantivy_search::{TantivySearcher, TextSearchResult};
@@ -6,14 +6,21 @@
 //! - Precise: All signals with linear fusion
 //! - Cognitive: Spreading activation + FSRS weighting
 
+use std::fmt;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::RookError;
+
 use super::config::SpreadingConfig;
 use super::dedup::DeduplicationConfig;
+use super::diversity::MmrConfig;
 use super::fusion::{LinearFusion, RrfFusion};
 
 /// Retrieval mode determines which signals are combined and how.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
 pub enum RetrievalMode {
     /// Vector search only - fastest retrieval.
     /// Use when: Speed is critical, query is well-formed for embedding.
@@ -34,6 +41,15 @@ pub enum RetrievalMode {
 }
 
 impl RetrievalMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Quick => "quick",
+            Self::Standard => "standard",
+            Self::Precise => "precise",
+            Self::Cognitive => "cognitive",
+        }
+    }
+
     /// Check if this mode uses vector search.
     pub fn uses_vector(&self) -> bool {
         true // All modes use vector search
@@ -65,6 +81,32 @@ impl RetrievalMode {
     }
 }
 
+impl fmt::Display for RetrievalMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RetrievalMode {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "quick" => Ok(Self::Quick),
+            "standard" => Ok(Self::Standard),
+            "precise" => Ok(Self::Precise),
+            "cognitive" => Ok(Self::Cognitive),
+            other => Err(RookError::validation(format!(
+                "Unknown retrieval mode '{}'; expected 'quick', 'standard', 'precise' or 'cognitive'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Smallest oversample factor used with MMR diversification.
+pub const MMR_MIN_OVERSAMPLE: usize = 3;
+
 /// Configuration for retrieval operations.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RetrievalConfig {
@@ -82,6 +124,12 @@ pub struct RetrievalConfig {
     pub dedup: DeduplicationConfig,
     /// Whether to deduplicate results.
     pub enable_dedup: bool,
+    /// MMR diversification configuration.
+    #[serde(default)]
+    pub mmr: MmrConfig,
+    /// Whether to diversify results with MMR.
+    #[serde(default)]
+    pub enable_mmr: bool,
     /// Oversample factor for fusion (fetch more results before fusion).
     pub oversample_factor: usize,
 }
@@ -96,6 +144,8 @@ impl Default for RetrievalConfig {
             linear: LinearFusion::default(),
             dedup: DeduplicationConfig::default(),
             enable_dedup: true,
+            mmr: MmrConfig::default(),
+            enable_mmr: false,
             oversample_factor: 2,
         }
     }
@@ -155,6 +205,17 @@ impl RetrievalConfig {
         self.enable_dedup = enable;
         self
     }
+
+    /// Diversify results with MMR at the given lambda.
+    ///
+    /// MMR picks from `limit * oversample_factor` candidates, so the
+    /// oversample factor is raised to at least [`MMR_MIN_OVERSAMPLE`].
+    pub fn with_mmr(mut self, lambda: f32) -> Self {
+        self.mmr = MmrConfig::with_lambda(lambda);
+        self.enable_mmr = true;
+        self.oversample_factor = self.oversample_factor.max(MMR_MIN_OVERSAMPLE);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -213,6 +274,39 @@ mod tests {
 
         let cognitive = RetrievalConfig::cognitive(10);
         assert_eq!(cognitive.mode, RetrievalMode::Cognitive);
+        assert!(!cognitive.enable_mmr);
+    }
+
+    #[test]
+    fn test_with_mmr() {
+        let quick = RetrievalConfig::quick(10).with_mmr(0.5);
+        assert!(quick.enable_mmr);
+        assert_eq!(quick.mmr.lambda, 0.5);
+        assert_eq!(quick.oversample_factor, MMR_MIN_OVERSAMPLE);
+
+        let precise = RetrievalConfig::precise(10).with_mmr(2.0);
+        assert_eq!(precise.mmr.lambda, 1.0);
+        assert_eq!(precise.oversample_factor, 3);
+    }
+
+    #[test]
+    fn test_retrieval_mode_from_str() {
+        assert_eq!("Cognitive".parse::<RetrievalMode>().unwrap(), RetrievalMode::Cognitive);
+        assert_eq!(" quick ".parse::<RetrievalMode>().unwrap(), RetrievalMode::Quick);
+        assert!("fast".parse::<RetrievalMode>().is_err());
+
+        for mode in [
+            RetrievalMode::Quick,
+            RetrievalMode::Standard,
+            RetrievalMode::Precise,
+            RetrievalMode::Cognitive,
+        ] {
+            assert_eq!(mode.to_string().parse::<RetrievalMode>().unwrap(), mode);
+            assert_eq!(
+                serde_json::to_value(mode).unwrap(),
+                serde_json::json!(mode.as_str())
+            );
+        }
     }
 
     #[test]
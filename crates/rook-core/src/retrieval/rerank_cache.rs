@@ -0,0 +1,319 @@
+//! Caching and deadlines for rerankers.
+//!
+//! Reranking is usually a remote API call, often the slowest stage of a
+//! search. [`CachedReranker`] wraps a reranker so a repeated search (the same
+//! query over the same candidates) reuses the earlier ranking within
+//! [`RerankCacheConfig::ttl_secs`], and a reranker slower than
+//! [`RerankCacheConfig::timeout_ms`] doesn't fail the search: the candidates
+//! are returned in their original order instead.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::RookResult;
+use crate::traits::Reranker;
+use crate::types::MemoryItem;
+
+/// Reranker caching and deadline settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RerankCacheConfig {
+    /// How long a ranking is reused for the same query and candidates, in
+    /// seconds. 0 disables caching (default: 0).
+    pub ttl_secs: u64,
+    /// Maximum cached rankings; the oldest is dropped beyond this
+    /// (default: 1000).
+    pub max_entries: usize,
+    /// Deadline for the reranker in milliseconds, after which the
+    /// candidates are returned in their original order (default: none).
+    pub timeout_ms: Option<u64>,
+}
+
+impl Default for RerankCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 0,
+            max_entries: 1000,
+            timeout_ms: None,
+        }
+    }
+}
+
+impl RerankCacheConfig {
+    /// Whether caching or a deadline is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.ttl_secs > 0 || self.timeout_ms.is_some()
+    }
+}
+
+/// A cached ranking: (memory id, score) pairs in ranked order.
+struct CachedRanking {
+    stored_at: Instant,
+    ranking: Vec<(String, Option<f32>)>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    rankings: HashMap<String, CachedRanking>,
+    /// Keys, oldest first.
+    order: VecDeque<String>,
+}
+
+/// Reranker wrapper reusing recent rankings and bounding rerank time.
+pub struct CachedReranker {
+    inner: Arc<dyn Reranker>,
+    config: RerankCacheConfig,
+    entries: Mutex<CacheEntries>,
+}
+
+impl CachedReranker {
+    pub fn new(inner: Arc<dyn Reranker>, config: RerankCacheConfig) -> Self {
+        Self {
+            inner,
+            config,
+            entries: Mutex::new(CacheEntries::default()),
+        }
+    }
+
+    /// Get the settings.
+    pub fn config(&self) -> &RerankCacheConfig {
+        &self.config
+    }
+
+    fn get(&self, key: &str) -> Option<Vec<(String, Option<f32>)>> {
+        let ttl = Duration::from_secs(self.config.ttl_secs);
+        let entries = self.entries.lock().ok()?;
+        entries
+            .rankings
+            .get(key)
+            .filter(|cached| cached.stored_at.elapsed() < ttl)
+            .map(|cached| cached.ranking.clone())
+    }
+
+    fn insert(&self, key: String, ranking: Vec<(String, Option<f32>)>) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        let cached = CachedRanking {
+            stored_at: Instant::now(),
+            ranking,
+        };
+        if entries.rankings.insert(key.clone(), cached).is_none() {
+            entries.order.push_back(key);
+        }
+        while entries.order.len() > self.config.max_entries {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.rankings.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Cache key for reranking `memories` against `query`. Candidates are
+/// hashed as a set, by id and content.
+fn cache_key(query: &str, memories: &[MemoryItem], limit: Option<usize>) -> String {
+    let mut candidates: Vec<String> = memories
+        .iter()
+        .map(|m| format!("{}\u{1f}{}", m.id, m.memory))
+        .collect();
+    candidates.sort();
+    let limit = limit.map(|l| l.to_string()).unwrap_or_default();
+    let key = format!("{}\u{1e}{}\u{1e}{}", query, limit, candidates.join("\u{1e}"));
+    format!("{:x}", md5::compute(key.as_bytes()))
+}
+
+/// Rebuild a cached ranking from the candidates it was computed for.
+fn apply_ranking(
+    memories: Vec<MemoryItem>,
+    ranking: &[(String, Option<f32>)],
+) -> Vec<MemoryItem> {
+    let mut by_id: HashMap<String, MemoryItem> =
+        memories.into_iter().map(|m| (m.id.clone(), m)).collect();
+    ranking
+        .iter()
+        .filter_map(|(id, score)| {
+            let mut memory = by_id.remove(id)?;
+            memory.score = *score;
+            Some(memory)
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Reranker for CachedReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        memories: Vec<MemoryItem>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<MemoryItem>> {
+        let key = (self.config.ttl_secs > 0).then(|| cache_key(query, &memories, limit));
+        if let Some(ranking) = key.as_deref().and_then(|key| self.get(key)) {
+            return Ok(apply_ranking(memories, &ranking));
+        }
+
+        let reranked = match self.config.timeout_ms {
+            Some(timeout_ms) => {
+                let deadline = Duration::from_millis(timeout_ms);
+                let rerank = self.inner.rerank(query, memories.clone(), limit);
+                match tokio::time::timeout(deadline, rerank).await {
+                    Ok(result) => result?,
+                    Err(_) => {
+                        tracing::warn!(
+                            "Reranker {} exceeded {}ms, keeping the original order",
+                            self.inner.model_name(),
+                            timeout_ms
+                        );
+                        let mut memories = memories;
+                        memories.truncate(limit.unwrap_or(usize::MAX));
+                        return Ok(memories);
+                    }
+                }
+            }
+            None => self.inner.rerank(query, memories, limit).await?,
+        };
+
+        if let Some(key) = key {
+            let ranking = reranked.iter().map(|m| (m.id.clone(), m.score)).collect();
+            self.insert(key, ranking);
+        }
+        Ok(reranked)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reverses the candidates, counting calls, after an optional delay.
+    struct ReversingReranker {
+        calls: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl ReversingReranker {
+        fn new(delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                calls: AtomicUsize::new(0),
+                delay,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Reranker for ReversingReranker {
+        async fn rerank(
+            &self,
+            _query: &str,
+            memories: Vec<MemoryItem>,
+            limit: Option<usize>,
+        ) -> RookResult<Vec<MemoryItem>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            let mut reranked: Vec<MemoryItem> = memories
+                .into_iter()
+                .rev()
+                .enumerate()
+                .map(|(i, m)| m.with_score(1.0 - i as f32 * 0.1))
+                .collect();
+            reranked.truncate(limit.unwrap_or(usize::MAX));
+            Ok(reranked)
+        }
+
+        fn model_name(&self) -> &str {
+            "reversing"
+        }
+    }
+
+    fn candidates() -> Vec<MemoryItem> {
+        vec![
+            MemoryItem::new("a", "Likes tea").with_score(0.9),
+            MemoryItem::new("b", "Drinks coffee").with_score(0.8),
+            MemoryItem::new("c", "Lives in Lisbon").with_score(0.7),
+        ]
+    }
+
+    fn ids(memories: &[MemoryItem]) -> Vec<&str> {
+        memories.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_reuses_ranking_for_same_candidates() {
+        let inner = ReversingReranker::new(Duration::ZERO);
+        let config = RerankCacheConfig {
+            ttl_secs: 60,
+            ..Default::default()
+        };
+        let reranker = CachedReranker::new(inner.clone(), config);
+
+        let first = reranker.rerank("drinks", candidates(), Some(2)).await.unwrap();
+        assert_eq!(ids(&first), vec!["c", "b"]);
+
+        // Same candidate set in another order hits the cache
+        let mut shuffled = candidates();
+        shuffled.swap(0, 2);
+        let second = reranker.rerank("drinks", shuffled, Some(2)).await.unwrap();
+        assert_eq!(ids(&second), vec!["c", "b"]);
+        assert_eq!(second[0].score, Some(1.0));
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // Another query or candidate set doesn't
+        reranker.rerank("food", candidates(), Some(2)).await.unwrap();
+        reranker.rerank("drinks", candidates()[..2].to_vec(), Some(2)).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_without_ttl_always_reranks() {
+        let inner = ReversingReranker::new(Duration::ZERO);
+        let config = RerankCacheConfig {
+            timeout_ms: Some(1000),
+            ..Default::default()
+        };
+        let reranker = CachedReranker::new(inner.clone(), config);
+
+        reranker.rerank("drinks", candidates(), None).await.unwrap();
+        reranker.rerank("drinks", candidates(), None).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_keeps_original_order() {
+        let inner = ReversingReranker::new(Duration::from_secs(5));
+        let config = RerankCacheConfig {
+            ttl_secs: 60,
+            timeout_ms: Some(20),
+            ..Default::default()
+        };
+        let reranker = CachedReranker::new(inner, config);
+
+        let results = reranker.rerank("drinks", candidates(), Some(2)).await.unwrap();
+        assert_eq!(ids(&results), vec!["a", "b"]);
+        assert_eq!(results[0].score, Some(0.9));
+    }
+
+    #[tokio::test]
+    async fn test_evicts_oldest_ranking() {
+        let inner = ReversingReranker::new(Duration::ZERO);
+        let config = RerankCacheConfig {
+            ttl_secs: 60,
+            max_entries: 1,
+            ..Default::default()
+        };
+        let reranker = CachedReranker::new(inner.clone(), config);
+
+        reranker.rerank("drinks", candidates(), None).await.unwrap();
+        reranker.rerank("food", candidates(), None).await.unwrap();
+        reranker.rerank("drinks", candidates(), None).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -190,6 +190,23 @@ impl TantivySearcher {
         Ok(())
     }
 
+    /// Delete every document.
+    ///
+    /// # Note
+    /// Call `commit()` after clearing to persist changes.
+    pub fn clear(&self) -> RookResult<()> {
+        let writer = self
+            .writer
+            .lock()
+            .map_err(|_| RookError::Configuration("Failed to acquire writer lock".to_string()))?;
+
+        writer
+            .delete_all_documents()
+            .map_err(|e| RookError::Configuration(format!("Failed to clear index: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Commit pending changes to make them searchable.
     ///
     /// # Important
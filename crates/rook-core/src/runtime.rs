@@ -1,18 +1,26 @@
 //! Background runtime for memory schedulers.
 //!
-//! Manages the lifecycle of ConsolidationScheduler and IntentionScheduler
-//! as background tasks, providing unified startup and graceful shutdown.
+//! Manages the lifecycle of ConsolidationScheduler, IntentionScheduler,
+//! strength signal application and registered maintenance jobs as background
+//! tasks, providing unified startup and graceful shutdown.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use tracing::{debug, info};
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
 
 use crate::cognitive::CognitiveStore;
 use crate::consolidation::{ConsolidationManager, ConsolidationScheduler, SchedulerConfig};
 use crate::error::{RookError, RookResult};
+use crate::events::{AlertEvent, EventBus, MemoryLifecycleEvent};
 use crate::intentions::{
     FiredIntentionReceiver, IntentionScheduler, IntentionStore, SqliteIntentionStore,
+    SurfacedMemories,
 };
+use crate::memory::{Memory, StrengthUpdateJob};
+use crate::observability::metrics;
 
 /// Configuration for the BackgroundRuntime.
 #[derive(Debug, Clone)]
@@ -29,6 +37,12 @@ pub struct RuntimeConfig {
     pub cognitive_db_path: Option<String>,
     /// Path to intention store SQLite database (default: None = in-memory).
     pub intention_db_path: Option<String>,
+    /// Whether pending strength signal grades are applied in the background
+    /// (default: true).
+    pub enable_strength_updates: bool,
+    /// Interval between strength signal applications in minutes
+    /// (default: 1).
+    pub strength_update_interval_minutes: u64,
 }
 
 impl Default for RuntimeConfig {
@@ -40,6 +54,8 @@ impl Default for RuntimeConfig {
             enable_intentions: true,
             cognitive_db_path: None,
             intention_db_path: None,
+            enable_strength_updates: true,
+            strength_update_interval_minutes: 1,
         }
     }
 }
@@ -69,6 +85,18 @@ impl RuntimeConfig {
         self
     }
 
+    /// Disable background application of strength signals.
+    pub fn without_strength_updates(mut self) -> Self {
+        self.enable_strength_updates = false;
+        self
+    }
+
+    /// Set the interval between strength signal applications.
+    pub fn with_strength_update_interval(mut self, minutes: u64) -> Self {
+        self.strength_update_interval_minutes = minutes.max(1);
+        self
+    }
+
     /// Set path for cognitive store database.
     pub fn with_cognitive_db_path(mut self, path: impl Into<String>) -> Self {
         self.cognitive_db_path = Some(path.into());
@@ -90,6 +118,8 @@ impl RuntimeConfig {
     /// - `ROOK_ENABLE_INTENTIONS` (default: true)
     /// - `ROOK_COGNITIVE_DB_PATH` (default: None = in-memory)
     /// - `ROOK_INTENTION_DB_PATH` (default: None = in-memory)
+    /// - `ROOK_DISABLE_STRENGTH_UPDATES` (default: unset)
+    /// - `ROOK_STRENGTH_UPDATE_INTERVAL_MINUTES` (default: 1)
     pub fn from_env() -> Self {
         let mut config = Self::default();
 
@@ -119,15 +149,40 @@ impl RuntimeConfig {
             config.intention_db_path = Some(path);
         }
 
+        if std::env::var("ROOK_DISABLE_STRENGTH_UPDATES").is_ok() {
+            config.enable_strength_updates = false;
+        }
+
+        if let Ok(interval) = std::env::var("ROOK_STRENGTH_UPDATE_INTERVAL_MINUTES") {
+            if let Ok(minutes) = interval.parse() {
+                config.strength_update_interval_minutes = minutes;
+            }
+        }
+
         config
     }
 }
 
+/// A periodic maintenance task run by the [`BackgroundRuntime`].
+///
+/// Lets crates downstream of rook-core (graph stores, vector stores) schedule
+/// their own housekeeping alongside consolidation.
+#[async_trait]
+pub trait MaintenanceJob: Send + Sync {
+    /// Job name, unique per runtime. Used in logs and failure alerts.
+    fn name(&self) -> &str;
+
+    /// Run the job once. Returns the number of items changed.
+    async fn run(&self) -> RookResult<usize>;
+}
+
 /// Background runtime managing scheduler lifecycle.
 ///
 /// Provides unified startup and shutdown for:
 /// - ConsolidationScheduler (periodic memory consolidation)
 /// - IntentionScheduler (time-based intention triggers)
+/// - Strength signal application (see [`BackgroundRuntime::with_strength_updates`])
+/// - Registered [`MaintenanceJob`]s (e.g. graph entity deduplication)
 ///
 /// # Example
 ///
@@ -160,6 +215,14 @@ pub struct BackgroundRuntime {
     cognitive_store: Arc<CognitiveStore>,
     /// Intention store (shared with intention scheduler).
     intention_store: Arc<dyn IntentionStore>,
+    /// Memories surfaced in search by fired intentions.
+    surfaced_memories: Arc<SurfacedMemories>,
+    /// Maintenance jobs and their intervals.
+    maintenance_jobs: Vec<(Arc<dyn MaintenanceJob>, Duration)>,
+    /// Running maintenance tasks (populated by `start()`).
+    maintenance_tasks: Mutex<Vec<JoinHandle<()>>>,
+    /// Event bus for job failure alerts.
+    event_bus: Option<EventBus>,
     /// Runtime configuration.
     config: RuntimeConfig,
 }
@@ -235,10 +298,81 @@ impl BackgroundRuntime {
             fired_intentions_rx,
             cognitive_store,
             intention_store,
+            surfaced_memories: Arc::new(SurfacedMemories::new()),
+            maintenance_jobs: Vec::new(),
+            maintenance_tasks: Mutex::new(Vec::new()),
+            event_bus: None,
             config,
         })
     }
 
+    /// Report scheduler job failures as alerts on the event bus.
+    ///
+    /// Must be called before `start()`.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.consolidation_scheduler = self
+            .consolidation_scheduler
+            .map(|scheduler| scheduler.with_event_bus(event_bus.clone()));
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Run a maintenance job every `interval_minutes` (minimum 1), replacing
+    /// any job with the same name.
+    ///
+    /// Must be called before `start()`. The first run happens one interval
+    /// after start; use [`BackgroundRuntime::run_maintenance_job`] to run it
+    /// on demand.
+    pub fn with_maintenance_job(
+        mut self,
+        job: Arc<dyn MaintenanceJob>,
+        interval_minutes: u64,
+    ) -> Self {
+        let interval = Duration::from_secs(interval_minutes.max(1) * 60);
+        self.maintenance_jobs.retain(|(existing, _)| existing.name() != job.name());
+        self.maintenance_jobs.push((job, interval));
+        self
+    }
+
+    /// Apply the strength signal grades `memory` collects as reviews every
+    /// `strength_update_interval_minutes`, unless strength updates are
+    /// disabled.
+    ///
+    /// Must be called before `start()`. Runs as the `strength_updates`
+    /// maintenance job, so it can also be run on demand.
+    pub fn with_strength_updates(self, memory: Arc<Memory>) -> Self {
+        if !self.config.enable_strength_updates {
+            return self;
+        }
+        let interval = self.config.strength_update_interval_minutes;
+        self.with_maintenance_job(Arc::new(StrengthUpdateJob::new(memory)), interval)
+    }
+
+    /// Start a job applying strength signal grades on a runtime that is
+    /// already running, every `strength_update_interval_minutes`, unless
+    /// strength updates are disabled.
+    ///
+    /// For jobs covering memories configured at runtime; see
+    /// [`BackgroundRuntime::spawn_maintenance_job`].
+    pub fn spawn_strength_updates(&self, job: Arc<dyn MaintenanceJob>) {
+        if self.config.enable_strength_updates {
+            self.spawn_maintenance_job(job, self.config.strength_update_interval_minutes);
+        }
+    }
+
+    /// Start a maintenance job on a runtime that is already running.
+    ///
+    /// For jobs whose dependencies only exist after start, such as a memory
+    /// configured at runtime. The first run happens one interval from now.
+    /// The job is stopped by `shutdown()` but, unlike jobs registered with
+    /// [`BackgroundRuntime::with_maintenance_job`], cannot be run on demand.
+    pub fn spawn_maintenance_job(&self, job: Arc<dyn MaintenanceJob>, interval_minutes: u64) {
+        let interval = Duration::from_secs(interval_minutes.max(1) * 60);
+        let mut tasks = self.maintenance_tasks.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.push(spawn_maintenance(job.clone(), interval, self.event_bus.clone()));
+        info!(job = job.name(), interval_secs = interval.as_secs(), "Maintenance job started");
+    }
+
     /// Start the background schedulers.
     ///
     /// Begins periodic execution of:
@@ -264,6 +398,14 @@ impl BackgroundRuntime {
             info!("Intention scheduler started");
         }
 
+        // Start maintenance jobs
+        let mut tasks = self.maintenance_tasks.lock().unwrap_or_else(|e| e.into_inner());
+        for (job, period) in &self.maintenance_jobs {
+            tasks.push(spawn_maintenance(job.clone(), *period, self.event_bus.clone()));
+            info!(job = job.name(), interval_secs = period.as_secs(), "Maintenance job started");
+        }
+        drop(tasks);
+
         info!("Background schedulers started");
         Ok(())
     }
@@ -288,6 +430,17 @@ impl BackgroundRuntime {
             debug!("Intention scheduler stopped");
         }
 
+        // Stop maintenance jobs
+        let tasks: Vec<_> = self
+            .maintenance_tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain(..)
+            .collect();
+        for task in tasks {
+            task.abort();
+        }
+
         info!("Background schedulers stopped");
         Ok(())
     }
@@ -310,6 +463,12 @@ impl BackgroundRuntime {
         self.intention_store.clone()
     }
 
+    /// Get a reference to the queue of memories surfaced by fired intentions,
+    /// to share between the action executor and memory instances.
+    pub fn surfaced_memories(&self) -> Arc<SurfacedMemories> {
+        self.surfaced_memories.clone()
+    }
+
     /// Get a reference to the consolidation scheduler.
     pub fn consolidation_scheduler(&self) -> Option<&ConsolidationScheduler> {
         self.consolidation_scheduler.as_ref()
@@ -320,12 +479,67 @@ impl BackgroundRuntime {
         self.intention_scheduler.as_ref()
     }
 
+    /// Names of the registered maintenance jobs.
+    pub fn maintenance_job_names(&self) -> Vec<String> {
+        self.maintenance_jobs
+            .iter()
+            .map(|(job, _)| job.name().to_string())
+            .collect()
+    }
+
+    /// Run a registered maintenance job now, outside its schedule.
+    pub async fn run_maintenance_job(&self, name: &str) -> RookResult<usize> {
+        let job = self
+            .maintenance_jobs
+            .iter()
+            .find(|(job, _)| job.name() == name)
+            .map(|(job, _)| job.clone())
+            .ok_or_else(|| RookError::validation(format!("Unknown maintenance job '{}'", name)))?;
+        run_recorded(job.as_ref()).await
+    }
+
     /// Get the runtime configuration.
     pub fn config(&self) -> &RuntimeConfig {
         &self.config
     }
 }
 
+/// Run `job` every `period` until aborted, reporting failures on the bus.
+/// Run a maintenance job once, recording it in the scheduler metrics.
+async fn run_recorded(job: &dyn MaintenanceJob) -> RookResult<usize> {
+    let start = Instant::now();
+    let result = job.run().await;
+    let changed = *result.as_ref().unwrap_or(&0);
+    metrics::record_scheduler_run(job.name(), result.is_ok(), start.elapsed(), changed);
+    result
+}
+
+fn spawn_maintenance(
+    job: Arc<dyn MaintenanceJob>,
+    period: Duration,
+    event_bus: Option<EventBus>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        loop {
+            interval.tick().await;
+            debug!(job = job.name(), "Running maintenance job");
+            match run_recorded(job.as_ref()).await {
+                Ok(changed) => info!(job = job.name(), changed, "Maintenance job complete"),
+                Err(e) => {
+                    error!(job = job.name(), error = %e, "Maintenance job failed");
+                    if let Some(ref event_bus) = event_bus {
+                        event_bus.emit(MemoryLifecycleEvent::Alert(AlertEvent::job_failed(
+                            job.name(),
+                            &e,
+                        )));
+                    }
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +553,8 @@ mod tests {
         assert!(config.enable_intentions);
         assert!(config.cognitive_db_path.is_none());
         assert!(config.intention_db_path.is_none());
+        assert!(config.enable_strength_updates);
+        assert_eq!(config.strength_update_interval_minutes, 1);
     }
 
     #[test]
@@ -370,6 +586,15 @@ mod tests {
         assert_eq!(config.consolidation_interval_minutes, 1);
     }
 
+    #[test]
+    fn test_runtime_config_strength_updates() {
+        let config = RuntimeConfig::default().with_strength_update_interval(0);
+        assert_eq!(config.strength_update_interval_minutes, 1);
+
+        let config = RuntimeConfig::default().without_strength_updates();
+        assert!(!config.enable_strength_updates);
+    }
+
     #[tokio::test]
     async fn test_runtime_creation_default() {
         let config = RuntimeConfig::default();
@@ -404,6 +629,52 @@ mod tests {
         runtime.shutdown().await.unwrap();
     }
 
+    struct CountingJob {
+        runs: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl MaintenanceJob for CountingJob {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn run(&self) -> RookResult<usize> {
+            Ok(self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runtime_maintenance_jobs() {
+        let config = RuntimeConfig::default()
+            .without_consolidation()
+            .without_intentions();
+        let job = Arc::new(CountingJob {
+            runs: Default::default(),
+        });
+        let mut runtime = BackgroundRuntime::new(config)
+            .await
+            .unwrap()
+            .with_maintenance_job(job.clone(), 60)
+            .with_maintenance_job(job, 30);
+
+        assert_eq!(runtime.maintenance_job_names(), vec!["counting".to_string()]);
+        assert_eq!(runtime.run_maintenance_job("counting").await.unwrap(), 1);
+        assert!(runtime.run_maintenance_job("missing").await.is_err());
+
+        runtime.start().await.unwrap();
+        assert_eq!(runtime.maintenance_tasks.lock().unwrap().len(), 1);
+        runtime.spawn_maintenance_job(
+            Arc::new(CountingJob {
+                runs: Default::default(),
+            }),
+            5,
+        );
+        assert_eq!(runtime.maintenance_tasks.lock().unwrap().len(), 2);
+        runtime.shutdown().await.unwrap();
+        assert!(runtime.maintenance_tasks.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_runtime_take_fired_intentions_rx() {
         let config = RuntimeConfig::default();
@@ -0,0 +1,18 @@
+//! Local data directory management.
+//!
+//! Tracks how much space each store under `ROOK_DATA_DIR` uses and keeps the
+//! directory within a configured budget by running pruning jobs. Derived
+//! stores can be regenerated from the vector store with a [`Rebuilder`].
+
+mod quota;
+mod rebuild;
+
+pub use quota::{
+    parse_size, ArchivalJob, CompactionJob, ComponentUsage, DataComponent, DataDirConfig,
+    DataDirMonitor, DataUsage, EnforcementReport, HistoryRetentionJob, PruneJob, PrunePhase,
+    PruneReport,
+};
+pub use rebuild::{
+    FsrsRebuilder, RebuildOptions, RebuildProgress, RebuildStatus, RebuildTarget, Rebuilder,
+    TextIndexRebuilder,
+};
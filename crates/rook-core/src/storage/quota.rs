@@ -0,0 +1,806 @@
+//! Data directory budgets and automatic pruning.
+//!
+//! [`DataDirMonitor`] periodically measures the stores in the data directory.
+//! When the total exceeds the budget it runs the registered [`PruneJob`]s in
+//! phase order (retention, then archival, then compaction), re-measuring
+//! after each one and stopping as soon as usage is back under budget.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cognitive::{CognitiveStore, FsrsScheduler};
+use crate::error::{RookError, RookResult};
+use crate::events::{AlertEvent, AlertKind, AlertSeverity, EventBus, MemoryLifecycleEvent};
+use crate::memory::HistoryStore;
+use crate::traits::VectorStore;
+use crate::types::ArchivalConfig;
+
+/// A store kept in the data directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataComponent {
+    /// Vector store database (`vectors.db`)
+    Vectors,
+    /// Memory history database (`history.db`)
+    History,
+    /// FSRS cognitive state database (`cognitive.db`)
+    Cognitive,
+    /// Tantivy full-text index (`tantivy/`)
+    TextIndex,
+    /// Stored source documents and media (`blobs/`)
+    Blobs,
+}
+
+impl DataComponent {
+    /// Every component, in reporting order.
+    pub const ALL: [DataComponent; 5] = [
+        Self::Vectors,
+        Self::History,
+        Self::Cognitive,
+        Self::TextIndex,
+        Self::Blobs,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Vectors => "vectors",
+            Self::History => "history",
+            Self::Cognitive => "cognitive",
+            Self::TextIndex => "text_index",
+            Self::Blobs => "blobs",
+        }
+    }
+
+    /// Default location relative to the data directory.
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            Self::Vectors => "vectors.db",
+            Self::History => "history.db",
+            Self::Cognitive => "cognitive.db",
+            Self::TextIndex => "tantivy",
+            Self::Blobs => "blobs",
+        }
+    }
+}
+
+/// Data directory budget configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataDirConfig {
+    /// The data directory
+    pub path: PathBuf,
+    /// Maximum total size in bytes. No pruning happens without a budget.
+    #[serde(default)]
+    pub budget_bytes: Option<u64>,
+    /// Log a warning once usage reaches this percentage of the budget (default: 90)
+    #[serde(default = "default_warn_percent")]
+    pub warn_percent: f64,
+    /// Seconds between checks (default: 300)
+    #[serde(default = "default_check_interval")]
+    pub check_interval_secs: u64,
+    /// Age after which history records are pruned. Without it, only the
+    /// history of deleted memories is pruned.
+    #[serde(default)]
+    pub history_retention_days: Option<u32>,
+    /// Allow the archival job to remove decayed memories when over budget
+    /// (default: false)
+    #[serde(default)]
+    pub archive_over_budget: bool,
+    /// Locations that differ from [`DataComponent::default_path`]
+    #[serde(default)]
+    pub component_paths: HashMap<DataComponent, PathBuf>,
+}
+
+fn default_warn_percent() -> f64 {
+    90.0
+}
+
+fn default_check_interval() -> u64 {
+    300
+}
+
+impl DataDirConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            budget_bytes: None,
+            warn_percent: default_warn_percent(),
+            check_interval_secs: default_check_interval(),
+            history_retention_days: None,
+            archive_over_budget: false,
+            component_paths: HashMap::new(),
+        }
+    }
+
+    /// Set the size budget in bytes.
+    pub fn with_budget(mut self, bytes: u64) -> Self {
+        self.budget_bytes = Some(bytes);
+        self
+    }
+
+    /// Prune history records older than this many days when over budget.
+    pub fn with_history_retention_days(mut self, days: u32) -> Self {
+        self.history_retention_days = Some(days);
+        self
+    }
+
+    /// Override where a component lives.
+    pub fn with_component_path(
+        mut self,
+        component: DataComponent,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.component_paths.insert(component, path.into());
+        self
+    }
+
+    /// Where a component lives.
+    pub fn component_path(&self, component: DataComponent) -> PathBuf {
+        self.component_paths
+            .get(&component)
+            .cloned()
+            .unwrap_or_else(|| self.path.join(component.default_path()))
+    }
+
+    /// Budget `ROOK_DATA_DIR`, if set.
+    ///
+    /// Reads:
+    /// - `ROOK_DATA_DIR_BUDGET` - size budget, e.g. `10GB` or `512MiB`
+    /// - `ROOK_DATA_DIR_WARN_PERCENT` (default: 90)
+    /// - `ROOK_DATA_DIR_CHECK_INTERVAL_SECS` (default: 300)
+    /// - `ROOK_HISTORY_RETENTION_DAYS` (default: unset)
+    /// - `ROOK_DATA_DIR_ARCHIVE` - set to `true` to allow archival pruning
+    /// - `ROOK_COGNITIVE_DB_PATH` - cognitive store location
+    pub fn from_env() -> Option<Self> {
+        let mut config = Self::new(std::env::var("ROOK_DATA_DIR").ok()?);
+
+        if let Ok(budget) = std::env::var("ROOK_DATA_DIR_BUDGET") {
+            match parse_size(&budget) {
+                Ok(bytes) => config.budget_bytes = Some(bytes),
+                Err(e) => tracing::warn!("Ignoring ROOK_DATA_DIR_BUDGET: {}", e),
+            }
+        }
+        if let Some(percent) = std::env::var("ROOK_DATA_DIR_WARN_PERCENT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.warn_percent = percent;
+        }
+        if let Some(secs) = std::env::var("ROOK_DATA_DIR_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.check_interval_secs = secs;
+        }
+        config.history_retention_days = std::env::var("ROOK_HISTORY_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok());
+        config.archive_over_budget = std::env::var("ROOK_DATA_DIR_ARCHIVE")
+            .map(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"))
+            .unwrap_or(false);
+        if let Ok(path) = std::env::var("ROOK_COGNITIVE_DB_PATH") {
+            config
+                .component_paths
+                .insert(DataComponent::Cognitive, PathBuf::from(path));
+        }
+
+        Some(config)
+    }
+}
+
+/// Parse a human-readable size such as `512MB`, `1.5GiB` or `1048576`.
+///
+/// Suffixes are binary multiples, so `1GB` and `1GiB` are both 1024^3 bytes.
+pub fn parse_size(value: &str) -> RookResult<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| RookError::Configuration(format!("Invalid size: {}", value)))?;
+    let multiplier: u64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        other => {
+            return Err(RookError::Configuration(format!(
+                "Unknown size unit '{}' in {}",
+                other, value
+            )))
+        }
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// Space used by one component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentUsage {
+    pub component: DataComponent,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Space used by the data directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataUsage {
+    /// The data directory
+    pub path: PathBuf,
+    /// Total bytes, including components stored outside the directory
+    pub total_bytes: u64,
+    /// Configured budget
+    pub budget_bytes: Option<u64>,
+    /// Whether usage exceeds the budget
+    pub over_budget: bool,
+    /// Per-component usage
+    pub components: Vec<ComponentUsage>,
+    /// Bytes in the directory not attributed to any component
+    pub other_bytes: u64,
+    /// When usage was measured
+    pub measured_at: DateTime<Utc>,
+}
+
+impl DataUsage {
+    /// Measure current usage.
+    pub fn measure(config: &DataDirConfig) -> RookResult<Self> {
+        let dir_bytes = path_size(&config.path)?;
+
+        let mut components = Vec::with_capacity(DataComponent::ALL.len());
+        let mut inside = 0;
+        let mut outside = 0;
+        for component in DataComponent::ALL {
+            let path = config.component_path(component);
+            let bytes = component_size(&path)?;
+            if path.starts_with(&config.path) {
+                inside += bytes;
+            } else {
+                outside += bytes;
+            }
+            components.push(ComponentUsage {
+                component,
+                path,
+                bytes,
+            });
+        }
+
+        let total_bytes = dir_bytes + outside;
+        Ok(Self {
+            path: config.path.clone(),
+            total_bytes,
+            budget_bytes: config.budget_bytes,
+            over_budget: config.budget_bytes.is_some_and(|b| total_bytes > b),
+            components,
+            other_bytes: dir_bytes.saturating_sub(inside),
+            measured_at: Utc::now(),
+        })
+    }
+
+    /// Usage as a percentage of the budget.
+    pub fn budget_percent(&self) -> Option<f64> {
+        self.budget_bytes
+            .filter(|b| *b > 0)
+            .map(|b| self.total_bytes as f64 / b as f64 * 100.0)
+    }
+}
+
+/// Size of a component, including SQLite sidecar files.
+fn component_size(path: &Path) -> RookResult<u64> {
+    let mut bytes = path_size(path)?;
+    if path.is_file() {
+        for suffix in ["-wal", "-shm", "-journal"] {
+            let mut sidecar = path.as_os_str().to_owned();
+            sidecar.push(suffix);
+            bytes += path_size(Path::new(&sidecar))?;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Size of a file, or of everything under a directory. Missing paths are empty.
+fn path_size(path: &Path) -> RookResult<u64> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path)? {
+        total += path_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// When a pruning job runs relative to the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrunePhase {
+    /// Drop data past its retention period
+    Retention,
+    /// Remove decayed memories
+    Archival,
+    /// Reclaim space freed by the earlier phases
+    Compaction,
+}
+
+/// A job that frees space in the data directory.
+#[async_trait]
+pub trait PruneJob: Send + Sync {
+    /// Job name, unique per monitor.
+    fn name(&self) -> &str;
+
+    fn phase(&self) -> PrunePhase;
+
+    /// Run the job. Returns the number of items removed or compacted.
+    async fn run(&self) -> RookResult<usize>;
+}
+
+/// Outcome of one job run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PruneReport {
+    pub job: String,
+    pub removed: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a budget check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnforcementReport {
+    pub before: DataUsage,
+    pub after: DataUsage,
+    pub jobs: Vec<PruneReport>,
+}
+
+/// Prunes the history database.
+pub struct HistoryRetentionJob {
+    path: PathBuf,
+    retention_days: Option<u32>,
+}
+
+impl HistoryRetentionJob {
+    pub fn new(path: impl Into<PathBuf>, retention_days: Option<u32>) -> Self {
+        Self {
+            path: path.into(),
+            retention_days,
+        }
+    }
+}
+
+#[async_trait]
+impl PruneJob for HistoryRetentionJob {
+    fn name(&self) -> &str {
+        "history_retention"
+    }
+
+    fn phase(&self) -> PrunePhase {
+        PrunePhase::Retention
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        if !self.path.exists() {
+            return Ok(0);
+        }
+        let path = self.path.clone();
+        let cutoff = self
+            .retention_days
+            .map(|days| Utc::now() - chrono::Duration::days(days as i64));
+        tokio::task::spawn_blocking(move || HistoryStore::new(&path)?.prune(cutoff))
+            .await
+            .map_err(|e| RookError::internal(format!("History retention task failed: {}", e)))?
+    }
+}
+
+/// Removes memories whose recall probability has decayed below the archive
+/// threshold from the vector store, along with their FSRS state.
+pub struct ArchivalJob {
+    cognitive_store: Arc<CognitiveStore>,
+    vector_store: Arc<dyn VectorStore>,
+    config: ArchivalConfig,
+    scheduler: FsrsScheduler,
+}
+
+impl ArchivalJob {
+    pub fn new(
+        cognitive_store: Arc<CognitiveStore>,
+        vector_store: Arc<dyn VectorStore>,
+        config: ArchivalConfig,
+    ) -> Self {
+        Self {
+            cognitive_store,
+            vector_store,
+            config,
+            scheduler: FsrsScheduler::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl PruneJob for ArchivalJob {
+    fn name(&self) -> &str {
+        "archival"
+    }
+
+    fn phase(&self) -> PrunePhase {
+        PrunePhase::Archival
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        let now = Utc::now();
+        let candidates = self
+            .cognitive_store
+            .get_archival_candidates(&self.config, now)?;
+
+        let mut removed = 0;
+        for candidate in candidates {
+            if !self.scheduler.is_archival_candidate(
+                &candidate.state,
+                candidate.created_at,
+                false,
+                &self.config,
+                now,
+            ) {
+                continue;
+            }
+            self.vector_store.delete(&candidate.memory_id).await?;
+            self.cognitive_store.delete_state(&candidate.memory_id)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Checkpoints and vacuums SQLite databases.
+pub struct CompactionJob {
+    paths: Vec<PathBuf>,
+}
+
+impl CompactionJob {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self { paths }
+    }
+}
+
+#[async_trait]
+impl PruneJob for CompactionJob {
+    fn name(&self) -> &str {
+        "compaction"
+    }
+
+    fn phase(&self) -> PrunePhase {
+        PrunePhase::Compaction
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        let paths: Vec<PathBuf> = self.paths.iter().filter(|p| p.is_file()).cloned().collect();
+        tokio::task::spawn_blocking(move || {
+            let mut compacted = 0;
+            for path in paths {
+                let result = rusqlite::Connection::open(&path).and_then(|conn| {
+                    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+                    conn.execute_batch("VACUUM")
+                });
+                match result {
+                    Ok(()) => compacted += 1,
+                    Err(e) => tracing::warn!("Failed to compact {}: {}", path.display(), e),
+                }
+            }
+            compacted
+        })
+        .await
+        .map_err(|e| RookError::internal(format!("Compaction task failed: {}", e)))
+    }
+}
+
+/// Keeps a data directory within its budget.
+pub struct DataDirMonitor {
+    config: DataDirConfig,
+    jobs: RwLock<Vec<Arc<dyn PruneJob>>>,
+    event_bus: Option<EventBus>,
+    last_usage: RwLock<Option<DataUsage>>,
+    enforcing: tokio::sync::Mutex<()>,
+}
+
+impl DataDirMonitor {
+    pub fn new(config: DataDirConfig) -> Self {
+        Self {
+            config,
+            jobs: RwLock::new(Vec::new()),
+            event_bus: None,
+            last_usage: RwLock::new(None),
+            enforcing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Raise [`AlertKind::StorageBudgetExceeded`] alerts on the event bus.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Add a pruning job.
+    pub fn with_job(self, job: Arc<dyn PruneJob>) -> Self {
+        self.register(job);
+        self
+    }
+
+    /// Register history retention and SQLite compaction for the default
+    /// component locations.
+    pub fn with_default_jobs(self) -> Self {
+        let history = HistoryRetentionJob::new(
+            self.config.component_path(DataComponent::History),
+            self.config.history_retention_days,
+        );
+        let compaction = CompactionJob::new(vec![
+            self.config.component_path(DataComponent::Vectors),
+            self.config.component_path(DataComponent::History),
+            self.config.component_path(DataComponent::Cognitive),
+        ]);
+        self.with_job(Arc::new(history)).with_job(Arc::new(compaction))
+    }
+
+    /// Add a pruning job, replacing any job with the same name.
+    ///
+    /// Jobs run in [`PrunePhase`] order, then in registration order.
+    pub fn register(&self, job: Arc<dyn PruneJob>) {
+        let mut jobs = self.jobs.write().unwrap_or_else(|e| e.into_inner());
+        match jobs.iter().position(|j| j.name() == job.name()) {
+            Some(index) => jobs[index] = job,
+            None => jobs.push(job),
+        }
+        jobs.sort_by_key(|j| j.phase());
+    }
+
+    pub fn config(&self) -> &DataDirConfig {
+        &self.config
+    }
+
+    /// Names of the registered jobs, in run order.
+    pub fn job_names(&self) -> Vec<String> {
+        self.jobs
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|j| j.name().to_string())
+            .collect()
+    }
+
+    /// Measure usage now.
+    pub fn usage(&self) -> RookResult<DataUsage> {
+        let usage = DataUsage::measure(&self.config)?;
+        *self.last_usage.write().unwrap_or_else(|e| e.into_inner()) = Some(usage.clone());
+        Ok(usage)
+    }
+
+    /// Usage from the most recent measurement.
+    pub fn last_usage(&self) -> Option<DataUsage> {
+        self.last_usage
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Check usage and prune if over budget.
+    pub async fn enforce(&self) -> RookResult<EnforcementReport> {
+        let _guard = self.enforcing.lock().await;
+
+        let before = self.usage()?;
+        let mut report = EnforcementReport {
+            before: before.clone(),
+            after: before.clone(),
+            jobs: Vec::new(),
+        };
+        let Some(budget) = self.config.budget_bytes else {
+            return Ok(report);
+        };
+
+        if !before.over_budget {
+            if before.budget_percent().unwrap_or(0.0) >= self.config.warn_percent {
+                tracing::warn!(
+                    used_bytes = before.total_bytes,
+                    budget_bytes = budget,
+                    "Data directory {} is nearing its budget",
+                    self.config.path.display()
+                );
+            }
+            return Ok(report);
+        }
+
+        tracing::warn!(
+            used_bytes = before.total_bytes,
+            budget_bytes = budget,
+            "Data directory {} is over budget, pruning",
+            self.config.path.display()
+        );
+
+        let jobs = self.jobs.read().unwrap_or_else(|e| e.into_inner()).clone();
+        for job in jobs {
+            let prune = match job.run().await {
+                Ok(removed) => PruneReport {
+                    job: job.name().to_string(),
+                    removed,
+                    error: None,
+                },
+                Err(e) => {
+                    tracing::warn!("Pruning job '{}' failed: {}", job.name(), e);
+                    PruneReport {
+                        job: job.name().to_string(),
+                        removed: 0,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+            tracing::info!(job = %prune.job, removed = prune.removed, "Pruning job finished");
+            report.jobs.push(prune);
+
+            report.after = self.usage()?;
+            if !report.after.over_budget {
+                break;
+            }
+        }
+
+        let severity = if report.after.over_budget {
+            tracing::warn!(
+                used_bytes = report.after.total_bytes,
+                budget_bytes = budget,
+                "Data directory {} is still over budget after pruning",
+                self.config.path.display()
+            );
+            AlertSeverity::Critical
+        } else {
+            AlertSeverity::Warning
+        };
+        if let Some(ref event_bus) = self.event_bus {
+            event_bus.emit(MemoryLifecycleEvent::Alert(AlertEvent::new(
+                severity,
+                AlertKind::StorageBudgetExceeded {
+                    path: self.config.path.display().to_string(),
+                    used_bytes: before.total_bytes,
+                    budget_bytes: budget,
+                },
+            )));
+        }
+
+        Ok(report)
+    }
+
+    /// Start checking in the background.
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.check_interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.enforce().await {
+                    tracing::warn!(
+                        "Data directory check for {} failed: {}",
+                        self.config.path.display(),
+                        e
+                    );
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RemoveFile {
+        path: PathBuf,
+        phase: PrunePhase,
+    }
+
+    #[async_trait]
+    impl PruneJob for RemoveFile {
+        fn name(&self) -> &str {
+            self.path.to_str().unwrap()
+        }
+
+        fn phase(&self) -> PrunePhase {
+            self.phase
+        }
+
+        async fn run(&self) -> RookResult<usize> {
+            std::fs::remove_file(&self.path)?;
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("10GB").unwrap(), 10 << 30);
+        assert_eq!(parse_size("1.5 MiB").unwrap(), 3 << 19);
+        assert!(parse_size("ten GB").is_err());
+        assert!(parse_size("5 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_measure_components() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("vectors.db"), vec![0u8; 100]).unwrap();
+        std::fs::write(dir.path().join("vectors.db-wal"), vec![0u8; 20]).unwrap();
+        std::fs::create_dir(dir.path().join("tantivy")).unwrap();
+        std::fs::write(dir.path().join("tantivy").join("seg"), vec![0u8; 30]).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), vec![0u8; 5]).unwrap();
+
+        let config = DataDirConfig::new(dir.path()).with_budget(100);
+        let usage = DataUsage::measure(&config).unwrap();
+
+        let bytes = |c: DataComponent| {
+            usage
+                .components
+                .iter()
+                .find(|u| u.component == c)
+                .unwrap()
+                .bytes
+        };
+        assert_eq!(bytes(DataComponent::Vectors), 120);
+        assert_eq!(bytes(DataComponent::TextIndex), 30);
+        assert_eq!(bytes(DataComponent::Blobs), 0);
+        assert_eq!(usage.other_bytes, 5);
+        assert_eq!(usage.total_bytes, 155);
+        assert!(usage.over_budget);
+    }
+
+    #[tokio::test]
+    async fn test_enforce_runs_jobs_in_phase_order_until_under_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small");
+        let large = dir.path().join("large");
+        let spare = dir.path().join("spare");
+        std::fs::write(&small, vec![0u8; 10]).unwrap();
+        std::fs::write(&large, vec![0u8; 100]).unwrap();
+        std::fs::write(&spare, vec![0u8; 10]).unwrap();
+
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        let monitor = DataDirMonitor::new(DataDirConfig::new(dir.path()).with_budget(50))
+            .with_event_bus(bus)
+            .with_job(Arc::new(RemoveFile {
+                path: spare.clone(),
+                phase: PrunePhase::Compaction,
+            }))
+            .with_job(Arc::new(RemoveFile {
+                path: small.clone(),
+                phase: PrunePhase::Retention,
+            }))
+            .with_job(Arc::new(RemoveFile {
+                path: large.clone(),
+                phase: PrunePhase::Archival,
+            }));
+
+        let report = monitor.enforce().await.unwrap();
+        let jobs: Vec<_> = report.jobs.iter().map(|j| j.job.as_str()).collect();
+        assert_eq!(jobs, vec![small.to_str().unwrap(), large.to_str().unwrap()]);
+        assert!(!report.after.over_budget);
+        assert!(spare.exists());
+
+        match rx.recv().await.unwrap() {
+            MemoryLifecycleEvent::Alert(alert) => {
+                assert_eq!(alert.severity, AlertSeverity::Warning);
+                assert_eq!(alert.alert_type(), "alert.storage_budget_exceeded");
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // Under budget: nothing runs
+        let report = monitor.enforce().await.unwrap();
+        assert!(report.jobs.is_empty());
+    }
+}
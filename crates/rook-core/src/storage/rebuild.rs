@@ -0,0 +1,309 @@
+//! Rebuilding derived stores from the vector store.
+//!
+//! The vector store holds every memory's text and payload, so the stores
+//! derived from it (FSRS state, the full-text index, the knowledge graph) can
+//! be regenerated if they are lost or corrupted. A [`Rebuilder`] regenerates
+//! one target record by record; [`Memory::rebuild`] walks the vector store in
+//! ID order and records a checkpoint after every batch so an interrupted
+//! rebuild can resume where it stopped.
+//!
+//! [`Memory::rebuild`]: crate::memory::Memory::rebuild
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cognitive::CognitiveStore;
+use crate::error::{RookError, RookResult};
+use crate::retrieval::TantivySearcher;
+use crate::traits::VectorRecord;
+use crate::types::FsrsState;
+
+/// A store that can be rebuilt from the vector store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RebuildTarget {
+    /// FSRS memory state in the cognitive store.
+    Fsrs,
+    /// Tantivy full-text index.
+    TextIndex,
+    /// Knowledge graph.
+    Graph,
+}
+
+impl RebuildTarget {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fsrs => "fsrs",
+            Self::TextIndex => "text-index",
+            Self::Graph => "graph",
+        }
+    }
+}
+
+impl fmt::Display for RebuildTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RebuildTarget {
+    type Err = RookError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fsrs" => Ok(Self::Fsrs),
+            "text-index" => Ok(Self::TextIndex),
+            "graph" => Ok(Self::Graph),
+            other => Err(RookError::validation(format!(
+                "Unknown rebuild target '{}'; expected 'fsrs', 'text-index' or 'graph'",
+                other
+            ))),
+        }
+    }
+}
+
+/// State of a rebuild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebuildStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Progress of a rebuild, checkpointed after every batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebuildProgress {
+    /// Store being rebuilt.
+    pub target: RebuildTarget,
+    /// Current state.
+    pub status: RebuildStatus,
+    /// Vector records to process.
+    pub total: usize,
+    /// Records processed so far, including those done before a resume.
+    pub processed: usize,
+    /// Records that changed the derived store.
+    pub rebuilt: usize,
+    /// Records that could not be rebuilt.
+    pub failed: usize,
+    /// ID of the last record in the last completed batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    /// When the rebuild started.
+    pub started_at: DateTime<Utc>,
+    /// When progress was last recorded.
+    pub updated_at: DateTime<Utc>,
+    /// Error that stopped the rebuild.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RebuildProgress {
+    /// Fresh progress for a target.
+    pub fn new(target: RebuildTarget) -> Self {
+        let now = Utc::now();
+        Self {
+            target,
+            status: RebuildStatus::Running,
+            total: 0,
+            processed: 0,
+            rebuilt: 0,
+            failed: 0,
+            cursor: None,
+            started_at: now,
+            updated_at: now,
+            error: None,
+        }
+    }
+
+    /// Whether a rebuild with this progress can be resumed.
+    pub fn is_resumable(&self) -> bool {
+        self.status != RebuildStatus::Completed
+    }
+
+    /// Index of the first record after the cursor in ID-sorted records.
+    pub fn resume_position(&self, records: &[VectorRecord]) -> usize {
+        match self.cursor {
+            Some(ref cursor) => records.partition_point(|r| r.id.as_str() <= cursor.as_str()),
+            None => 0,
+        }
+    }
+}
+
+/// Options for [`Memory::rebuild`](crate::memory::Memory::rebuild).
+#[derive(Debug, Clone)]
+pub struct RebuildOptions {
+    /// Continue an unfinished rebuild from its checkpoint instead of starting over.
+    pub resume: bool,
+    /// Records processed between checkpoints (default: 100).
+    pub batch_size: usize,
+}
+
+impl Default for RebuildOptions {
+    fn default() -> Self {
+        Self {
+            resume: false,
+            batch_size: 100,
+        }
+    }
+}
+
+impl RebuildOptions {
+    /// Resume from the last checkpoint, if any.
+    pub fn resume(mut self) -> Self {
+        self.resume = true;
+        self
+    }
+
+    /// Set the number of records between checkpoints.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+}
+
+/// Regenerates one derived store from vector records.
+#[async_trait]
+pub trait Rebuilder: Send + Sync {
+    /// Store this rebuilder writes.
+    fn target(&self) -> RebuildTarget;
+
+    /// Clear the store before a rebuild that is not resumed.
+    async fn clear(&self) -> RookResult<()> {
+        Ok(())
+    }
+
+    /// Regenerate the derived state of one record. Returns whether the store
+    /// changed.
+    async fn rebuild(&self, record: &VectorRecord) -> RookResult<bool>;
+
+    /// Persist pending writes. Called before each checkpoint.
+    async fn flush(&self) -> RookResult<()> {
+        Ok(())
+    }
+}
+
+/// Recreates missing FSRS state in the cognitive store.
+///
+/// Existing state is kept, since review history cannot be recovered from the
+/// vector store. Missing memories start as new cards, keeping the key flag
+/// and creation time from their payload.
+pub struct FsrsRebuilder {
+    store: Arc<CognitiveStore>,
+}
+
+impl FsrsRebuilder {
+    pub fn new(store: Arc<CognitiveStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl Rebuilder for FsrsRebuilder {
+    fn target(&self) -> RebuildTarget {
+        RebuildTarget::Fsrs
+    }
+
+    async fn rebuild(&self, record: &VectorRecord) -> RookResult<bool> {
+        if self.store.get_state(&record.id)?.is_some() {
+            return Ok(false);
+        }
+
+        let is_key = record.payload.get("is_key").and_then(|v| v.as_bool()).unwrap_or(false);
+        let created_at = record
+            .get_string("created_at")
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+        self.store.save_state(&record.id, &FsrsState::new(), is_key, created_at)?;
+        Ok(true)
+    }
+}
+
+/// Reindexes memory text in a Tantivy index.
+pub struct TextIndexRebuilder {
+    searcher: Arc<TantivySearcher>,
+}
+
+impl TextIndexRebuilder {
+    pub fn new(searcher: Arc<TantivySearcher>) -> Self {
+        Self { searcher }
+    }
+}
+
+#[async_trait]
+impl Rebuilder for TextIndexRebuilder {
+    fn target(&self) -> RebuildTarget {
+        RebuildTarget::TextIndex
+    }
+
+    async fn clear(&self) -> RookResult<()> {
+        self.searcher.clear()?;
+        self.searcher.commit()
+    }
+
+    async fn rebuild(&self, record: &VectorRecord) -> RookResult<bool> {
+        let Some(data) = record.get_data() else {
+            return Ok(false);
+        };
+        let metadata = serde_json::to_string(&record.payload)?;
+        self.searcher.update(&record.id, data, Some(&metadata))?;
+        Ok(true)
+    }
+
+    async fn flush(&self) -> RookResult<()> {
+        self.searcher.commit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn record(id: &str, data: &str) -> VectorRecord {
+        let payload = HashMap::from([
+            ("data".to_string(), serde_json::json!(data)),
+            ("is_key".to_string(), serde_json::json!(true)),
+        ]);
+        VectorRecord::new(id, vec![1.0], payload)
+    }
+
+    #[test]
+    fn test_target_parsing_and_resume_position() {
+        assert_eq!("text-index".parse::<RebuildTarget>().unwrap(), RebuildTarget::TextIndex);
+        assert_eq!(serde_json::to_value(RebuildTarget::TextIndex).unwrap(), "text-index");
+        assert!("vectors".parse::<RebuildTarget>().is_err());
+
+        let records = vec![record("a", "x"), record("b", "y"), record("c", "z")];
+        let mut progress = RebuildProgress::new(RebuildTarget::Fsrs);
+        assert_eq!(progress.resume_position(&records), 0);
+        progress.cursor = Some("b".to_string());
+        assert_eq!(progress.resume_position(&records), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rebuilders() {
+        let store = Arc::new(CognitiveStore::in_memory().unwrap());
+        let fsrs = FsrsRebuilder::new(store.clone());
+        assert!(fsrs.rebuild(&record("m1", "likes tea")).await.unwrap());
+        assert!(!fsrs.rebuild(&record("m1", "likes tea")).await.unwrap());
+        let (_, is_key, _) = store.get_state("m1").unwrap().unwrap();
+        assert!(is_key);
+
+        let searcher = Arc::new(TantivySearcher::in_memory().unwrap());
+        searcher.add("stale", "old document", None).unwrap();
+        searcher.commit().unwrap();
+
+        let text = TextIndexRebuilder::new(searcher.clone());
+        text.clear().await.unwrap();
+        assert!(text.rebuild(&record("m1", "likes green tea")).await.unwrap());
+        text.flush().await.unwrap();
+        assert_eq!(searcher.num_docs(), 1);
+        assert_eq!(searcher.search_ids("tea", 10).unwrap(), vec!["m1".to_string()]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Version vectors for tracking causality between replicas.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// Causal relationship of one version vector to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Causality {
+    /// Strictly older: the other side has seen everything this one has.
+    Before,
+    /// Strictly newer.
+    After,
+    /// Identical histories.
+    Equal,
+    /// Each side has writes the other has not seen.
+    Concurrent,
+}
+
+/// Per-node write counters for a single memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VersionVector(BTreeMap<String, u64>);
+
+impl VersionVector {
+    /// Create an empty version vector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counter for `node` (0 if the node never wrote).
+    pub fn get(&self, node: &str) -> u64 {
+        self.0.get(node).copied().unwrap_or(0)
+    }
+
+    /// Record a write by `node`, returning its new counter.
+    pub fn increment(&mut self, node: &str) -> u64 {
+        let counter = self.0.entry(node.to_string()).or_insert(0);
+        *counter += 1;
+        *counter
+    }
+
+    /// Take the element-wise maximum with `other`.
+    pub fn merge(&mut self, other: &VersionVector) {
+        for (node, &counter) in &other.0 {
+            let entry = self.0.entry(node.clone()).or_insert(0);
+            *entry = (*entry).max(counter);
+        }
+    }
+
+    /// How this vector relates to `other`.
+    pub fn compare(&self, other: &VersionVector) -> Causality {
+        let mut less = false;
+        let mut greater = false;
+        for node in self.0.keys().chain(other.0.keys()) {
+            match self.get(node).cmp(&other.get(node)) {
+                Ordering::Less => less = true,
+                Ordering::Greater => greater = true,
+                Ordering::Equal => {}
+            }
+        }
+        match (less, greater) {
+            (false, false) => Causality::Equal,
+            (true, false) => Causality::Before,
+            (false, true) => Causality::After,
+            (true, true) => Causality::Concurrent,
+        }
+    }
+}
+
+/// The version of a memory as known to one replica.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncVersion {
+    /// Causal history of the memory.
+    pub clock: VersionVector,
+    /// Node that made the latest write.
+    pub writer: String,
+    /// When the latest write happened (RFC 3339).
+    pub updated_at: String,
+}
+
+impl SyncVersion {
+    /// Latest-wins tie break for concurrent versions.
+    ///
+    /// The later `updated_at` wins; equal timestamps fall back to the writer's
+    /// node ID so every replica picks the same winner.
+    pub fn wins_over(&self, other: &SyncVersion) -> bool {
+        let ordering = match (
+            DateTime::parse_from_rfc3339(&self.updated_at),
+            DateTime::parse_from_rfc3339(&other.updated_at),
+        ) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => self.updated_at.cmp(&other.updated_at),
+        };
+        ordering.then_with(|| self.writer.cmp(&other.writer)) == Ordering::Greater
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vv(entries: &[(&str, u64)]) -> VersionVector {
+        VersionVector(entries.iter().map(|(n, c)| (n.to_string(), *c)).collect())
+    }
+
+    #[test]
+    fn test_version_vector_compare() {
+        let a = vv(&[("laptop", 2), ("desktop", 1)]);
+        assert_eq!(a.compare(&a.clone()), Causality::Equal);
+        assert_eq!(vv(&[("laptop", 1)]).compare(&a), Causality::Before);
+        assert_eq!(a.compare(&vv(&[("laptop", 1)])), Causality::After);
+        assert_eq!(
+            a.compare(&vv(&[("laptop", 1), ("desktop", 2)])),
+            Causality::Concurrent
+        );
+
+        let mut merged = a.clone();
+        merged.merge(&vv(&[("laptop", 1), ("desktop", 3)]));
+        assert_eq!(merged, vv(&[("laptop", 2), ("desktop", 3)]));
+        assert_eq!(merged.increment("phone"), 1);
+    }
+
+    #[test]
+    fn test_wins_over_is_deterministic() {
+        let version = |writer: &str, at: &str| SyncVersion {
+            clock: VersionVector::new(),
+            writer: writer.to_string(),
+            updated_at: at.to_string(),
+        };
+
+        let older = version("zeta", "2024-01-01T10:00:00+00:00");
+        let newer = version("alpha", "2024-01-01T12:00:00+02:00");
+        assert!(older.wins_over(&newer), "10:00Z is later than 12:00+02:00");
+        assert!(!newer.wins_over(&older));
+
+        let a = version("alpha", "2024-01-01T10:00:00Z");
+        let b = version("beta", "2024-01-01T10:00:00+00:00");
+        assert!(b.wins_over(&a));
+        assert!(!a.wins_over(&b));
+    }
+}
@@ -0,0 +1,203 @@
+//! Replicated sync between rook instances.
+//!
+//! Every local write recorded in history is assigned a per-memory version
+//! vector. Replicas exchange [`Changeset`]s of memories whose version changed
+//! since a peer's last pull, each carrying the memory in the export format.
+//! Causally newer versions are applied; concurrent versions are resolved
+//! latest-wins (see [`SyncVersion::wins_over`]); under the default
+//! [`ConflictPolicy::Supersede`] the losing side is kept as a
+//! [`SupersedeRecord`].
+
+mod clock;
+mod store;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::export::ExportableMemory;
+
+pub use clock::{Causality, SyncVersion, VersionVector};
+pub use store::{SyncState, SyncStore};
+
+/// History `actor_id` prefix for writes applied from a peer.
+///
+/// Such writes already carry a version and are not captured as local changes.
+pub const SYNC_ACTOR_PREFIX: &str = "sync:";
+
+/// Replicated sync settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Track versions and accept changesets (default: false).
+    pub enabled: bool,
+    /// Stable ID of this replica. Generated and persisted on first use if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    /// Sync state database. Defaults to `sync.db` next to the history database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_path: Option<PathBuf>,
+}
+
+/// One changed memory in a changeset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Change {
+    /// Memory ID (shared by all replicas).
+    pub memory_id: String,
+    /// Position in the sender's change sequence.
+    pub seq: u64,
+    /// Version of the memory on the sender.
+    pub version: SyncVersion,
+    /// Whether the memory is deleted.
+    pub deleted: bool,
+    /// Memory contents, absent for deletions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<ExportableMemory>,
+}
+
+/// Changes made on one replica after a sequence number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Changeset {
+    /// Replica that produced the changes.
+    pub node_id: String,
+    /// Sequence number the changes follow.
+    pub since: u64,
+    /// Sequence number to pass as `since` for the next page.
+    pub next_seq: u64,
+    /// Whether more changes follow `next_seq`.
+    pub has_more: bool,
+    /// Changes in sequence order.
+    pub changes: Vec<Change>,
+}
+
+/// Outcome of applying a changeset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Changes written locally.
+    pub applied: usize,
+    /// Changes already seen or lost to a local version.
+    pub skipped: usize,
+    /// Concurrent versions resolved by latest-wins.
+    pub conflicts: usize,
+}
+
+/// A concurrent version that lost conflict resolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupersedeRecord {
+    /// Record ID.
+    pub id: String,
+    /// Memory in conflict.
+    pub memory_id: String,
+    /// Node whose version was kept.
+    pub winner_node: String,
+    /// Timestamp of the kept version.
+    pub winner_updated_at: String,
+    /// Node whose version was discarded.
+    pub loser_node: String,
+    /// Timestamp of the discarded version.
+    pub loser_updated_at: String,
+    /// Content of the discarded version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loser_content: Option<String>,
+    /// Whether the discarded version was a deletion.
+    pub loser_deleted: bool,
+    /// When the conflict was resolved.
+    pub resolved_at: String,
+}
+
+/// Sync position of a replica.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    /// This replica's node ID.
+    pub node_id: String,
+    /// Latest local change sequence number.
+    pub seq: u64,
+    /// Last sequence number received from each peer.
+    pub peers: BTreeMap<String, u64>,
+}
+
+/// What happens to the losing side of a concurrent change.
+///
+/// Both policies pick the same winner, so replicas with different policies
+/// still converge; they differ only in what is kept of the loser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Discard the losing version.
+    LastWriteWins,
+    /// Keep the losing version as a [`SupersedeRecord`].
+    #[default]
+    Supersede,
+}
+
+/// What to do with an incoming change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Remote is newer: apply it.
+    Apply,
+    /// Remote is already known: ignore it.
+    Skip,
+    /// Concurrent and remote wins: apply it and supersede the local version.
+    ApplySuperseding,
+    /// Concurrent and local wins: keep it and supersede the remote version.
+    KeepSuperseding,
+}
+
+/// Decide how an incoming version relates to the local one.
+pub fn resolve(local: Option<&SyncVersion>, remote: &SyncVersion) -> Resolution {
+    let Some(local) = local else {
+        return Resolution::Apply;
+    };
+    match remote.clock.compare(&local.clock) {
+        Causality::After => Resolution::Apply,
+        Causality::Before | Causality::Equal => Resolution::Skip,
+        Causality::Concurrent if remote.wins_over(local) => Resolution::ApplySuperseding,
+        Causality::Concurrent => Resolution::KeepSuperseding,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(writes: &[(&str, u64)], writer: &str, at: &str) -> SyncVersion {
+        let mut clock = VersionVector::new();
+        for (node, count) in writes {
+            for _ in 0..*count {
+                clock.increment(node);
+            }
+        }
+        SyncVersion {
+            clock,
+            writer: writer.to_string(),
+            updated_at: at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let local = version(&[("laptop", 1)], "laptop", "2024-01-01T10:00:00Z");
+        let newer = version(&[("laptop", 1), ("desktop", 1)], "desktop", "2024-01-01T09:00:00Z");
+        let concurrent_later = version(&[("desktop", 1)], "desktop", "2024-01-01T11:00:00Z");
+        let concurrent_earlier = version(&[("desktop", 1)], "desktop", "2024-01-01T09:00:00Z");
+
+        assert_eq!(resolve(None, &local), Resolution::Apply);
+        assert_eq!(resolve(Some(&local), &newer), Resolution::Apply);
+        assert_eq!(resolve(Some(&newer), &local), Resolution::Skip);
+        assert_eq!(resolve(Some(&local), &local), Resolution::Skip);
+        assert_eq!(
+            resolve(Some(&local), &concurrent_later),
+            Resolution::ApplySuperseding
+        );
+        assert_eq!(
+            resolve(Some(&local), &concurrent_earlier),
+            Resolution::KeepSuperseding
+        );
+        // Both replicas agree on the winner.
+        assert_eq!(
+            resolve(Some(&concurrent_later), &local),
+            Resolution::KeepSuperseding
+        );
+    }
+}
@@ -0,0 +1,470 @@
+//! SQLite persistence for sync versions, peer cursors and conflict records.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use uuid::Uuid;
+
+use crate::encryption::ContentCipher;
+use crate::error::{RookError, RookResult};
+
+use super::{SupersedeRecord, SyncVersion, VersionVector};
+
+/// Sync version of one memory on this replica.
+#[derive(Debug, Clone)]
+pub struct SyncState {
+    /// Memory ID.
+    pub memory_id: String,
+    /// Current version.
+    pub version: SyncVersion,
+    /// Whether the memory is deleted.
+    pub deleted: bool,
+    /// Local change sequence number of the latest version.
+    pub seq: u64,
+}
+
+/// SQLite-backed sync state.
+pub struct SyncStore {
+    conn: Mutex<Connection>,
+    node_id: String,
+    exclusive: tokio::sync::Mutex<()>,
+    cipher: Option<ContentCipher>,
+}
+
+impl SyncStore {
+    /// Open the store at `path` (`:memory:` for an in-memory store).
+    ///
+    /// `node_id` overrides the persisted node ID; without one, the persisted
+    /// ID is used or a new one is generated.
+    pub fn new(path: impl AsRef<Path>, node_id: Option<String>) -> RookResult<Self> {
+        let conn = if path.as_ref().to_str() == Some(":memory:") {
+            Connection::open_in_memory()?
+        } else {
+            if let Some(parent) = path.as_ref().parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Connection::open(path.as_ref())?
+        };
+        Self::init_schema(&conn)?;
+
+        let persisted: Option<String> = conn
+            .query_row("SELECT value FROM sync_meta WHERE key = 'node_id'", [], |row| {
+                row.get(0)
+            })
+            .optional()?;
+        let node_id = match (node_id, persisted) {
+            (Some(id), _) => id,
+            (None, Some(id)) => id,
+            (None, None) => Uuid::new_v4().to_string(),
+        };
+        if node_id.is_empty() || node_id.starts_with(super::SYNC_ACTOR_PREFIX) {
+            return Err(RookError::Configuration(format!(
+                "Invalid sync node ID '{}'",
+                node_id
+            )));
+        }
+        Self::set_meta(&conn, "node_id", &node_id)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            node_id,
+            exclusive: tokio::sync::Mutex::new(()),
+            cipher: None,
+        })
+    }
+
+    /// Encrypt the content of conflict records with `cipher`, as the memory
+    /// store does.
+    pub fn with_cipher(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self
+    }
+
+    fn init_schema(conn: &Connection) -> RookResult<()> {
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS sync_state (
+                memory_id  TEXT PRIMARY KEY,
+                clock      TEXT NOT NULL,
+                writer     TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                deleted    INTEGER NOT NULL DEFAULT 0,
+                seq        INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_sync_state_seq ON sync_state(seq);
+
+            CREATE TABLE IF NOT EXISTS sync_peers (
+                node_id      TEXT PRIMARY KEY,
+                received_seq INTEGER NOT NULL,
+                last_sync    TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS sync_supersedes (
+                id                TEXT PRIMARY KEY,
+                memory_id         TEXT NOT NULL,
+                winner_node       TEXT NOT NULL,
+                winner_updated_at TEXT NOT NULL,
+                loser_node        TEXT NOT NULL,
+                loser_updated_at  TEXT NOT NULL,
+                loser_content     TEXT,
+                loser_deleted     INTEGER NOT NULL DEFAULT 0,
+                resolved_at       TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_sync_supersedes_memory
+                ON sync_supersedes(memory_id);
+            "#,
+        )?;
+        Ok(())
+    }
+
+    fn get_meta(conn: &Connection, key: &str) -> RookResult<Option<String>> {
+        Ok(conn
+            .query_row("SELECT value FROM sync_meta WHERE key = ?1", [key], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn set_meta(conn: &Connection, key: &str, value: &str) -> RookResult<()> {
+        conn.execute(
+            "INSERT INTO sync_meta (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    fn next_seq(conn: &Connection) -> RookResult<u64> {
+        let seq = Self::get_meta(conn, "seq")?
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(0)
+            + 1;
+        Self::set_meta(conn, "seq", &seq.to_string())?;
+        Ok(seq)
+    }
+
+    /// This replica's node ID.
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Serialize capture and apply passes.
+    pub async fn lock(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.exclusive.lock().await
+    }
+
+    /// Latest local change sequence number.
+    pub fn seq(&self) -> RookResult<u64> {
+        let conn = self.conn.lock().unwrap();
+        Ok(Self::get_meta(&conn, "seq")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// Last history row already assigned a version.
+    pub fn history_cursor(&self) -> RookResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        Ok(Self::get_meta(&conn, "history_cursor")?
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0))
+    }
+
+    /// Advance the history cursor.
+    pub fn set_history_cursor(&self, rowid: i64) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::set_meta(&conn, "history_cursor", &rowid.to_string())
+    }
+
+    /// Current version of a memory, if it was ever versioned.
+    pub fn get_state(&self, memory_id: &str) -> RookResult<Option<SyncState>> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                "SELECT memory_id, clock, writer, updated_at, deleted, seq
+                 FROM sync_state WHERE memory_id = ?1",
+                [memory_id],
+                Self::row_to_state,
+            )
+            .optional()?;
+        row.map(Self::decode_state).transpose()
+    }
+
+    /// Record a local write, bumping this node's counter for the memory.
+    pub fn record_local(
+        &self,
+        memory_id: &str,
+        deleted: bool,
+        updated_at: &str,
+    ) -> RookResult<SyncState> {
+        let mut clock = self
+            .get_state(memory_id)?
+            .map(|state| state.version.clock)
+            .unwrap_or_default();
+        clock.increment(&self.node_id);
+        let version = SyncVersion {
+            clock,
+            writer: self.node_id.clone(),
+            updated_at: updated_at.to_string(),
+        };
+        let seq = self.put_state(memory_id, &version, deleted)?;
+        Ok(SyncState {
+            memory_id: memory_id.to_string(),
+            version,
+            deleted,
+            seq,
+        })
+    }
+
+    /// Store a memory's version under a new sequence number, returning it.
+    pub fn put_state(
+        &self,
+        memory_id: &str,
+        version: &SyncVersion,
+        deleted: bool,
+    ) -> RookResult<u64> {
+        let conn = self.conn.lock().unwrap();
+        let seq = Self::next_seq(&conn)?;
+        let clock = serde_json::to_string(&version.clock)?;
+        conn.execute(
+            "INSERT INTO sync_state (memory_id, clock, writer, updated_at, deleted, seq)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(memory_id) DO UPDATE SET
+                clock = excluded.clock, writer = excluded.writer,
+                updated_at = excluded.updated_at, deleted = excluded.deleted,
+                seq = excluded.seq",
+            params![
+                memory_id,
+                clock,
+                version.writer,
+                version.updated_at,
+                deleted as i32,
+                seq as i64,
+            ],
+        )?;
+        Ok(seq)
+    }
+
+    /// Versions changed after `since`, in sequence order.
+    pub fn states_since(&self, since: u64, limit: usize) -> RookResult<Vec<SyncState>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT memory_id, clock, writer, updated_at, deleted, seq
+             FROM sync_state WHERE seq > ?1 ORDER BY seq ASC LIMIT ?2",
+        )?;
+        let rows = stmt
+            .query_map(params![since as i64, limit as i64], Self::row_to_state)?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter().map(Self::decode_state).collect()
+    }
+
+    /// Last sequence number received from `peer`.
+    pub fn peer_cursor(&self, peer: &str) -> RookResult<u64> {
+        let conn = self.conn.lock().unwrap();
+        let seq: Option<i64> = conn
+            .query_row(
+                "SELECT received_seq FROM sync_peers WHERE node_id = ?1",
+                [peer],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(seq.unwrap_or(0) as u64)
+    }
+
+    /// Record the last sequence number received from `peer`.
+    pub fn set_peer_cursor(&self, peer: &str, seq: u64) -> RookResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sync_peers (node_id, received_seq, last_sync) VALUES (?1, ?2, ?3)
+             ON CONFLICT(node_id) DO UPDATE SET
+                received_seq = MAX(received_seq, excluded.received_seq),
+                last_sync = excluded.last_sync",
+            params![peer, seq as i64, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Cursors of every known peer.
+    pub fn peers(&self) -> RookResult<BTreeMap<String, u64>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT node_id, received_seq FROM sync_peers")?;
+        let peers = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)))?
+            .collect::<Result<_, _>>()?;
+        Ok(peers)
+    }
+
+    /// Save a conflict resolution record.
+    pub fn save_supersede(&self, record: &SupersedeRecord) -> RookResult<()> {
+        let loser_content = match (&self.cipher, &record.loser_content) {
+            (Some(cipher), Some(content)) => Some(cipher.encrypt(&record.memory_id, content)?),
+            (_, content) => content.clone(),
+        };
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sync_supersedes (
+                id, memory_id, winner_node, winner_updated_at, loser_node,
+                loser_updated_at, loser_content, loser_deleted, resolved_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.id,
+                record.memory_id,
+                record.winner_node,
+                record.winner_updated_at,
+                record.loser_node,
+                record.loser_updated_at,
+                loser_content,
+                record.loser_deleted as i32,
+                record.resolved_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Conflict resolution records, newest first.
+    pub fn supersedes(&self, limit: usize) -> RookResult<Vec<SupersedeRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, memory_id, winner_node, winner_updated_at, loser_node,
+                    loser_updated_at, loser_content, loser_deleted, resolved_at
+             FROM sync_supersedes ORDER BY resolved_at DESC LIMIT ?1",
+        )?;
+        let records: Vec<SupersedeRecord> = stmt
+            .query_map([limit as i64], |row| {
+                Ok(SupersedeRecord {
+                    id: row.get(0)?,
+                    memory_id: row.get(1)?,
+                    winner_node: row.get(2)?,
+                    winner_updated_at: row.get(3)?,
+                    loser_node: row.get(4)?,
+                    loser_updated_at: row.get(5)?,
+                    loser_content: row.get(6)?,
+                    loser_deleted: row.get::<_, i32>(7)? != 0,
+                    resolved_at: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        records
+            .into_iter()
+            .map(|mut record| {
+                if let (Some(cipher), Some(content)) = (&self.cipher, &record.loser_content) {
+                    record.loser_content = Some(cipher.decrypt(&record.memory_id, content)?);
+                }
+                Ok(record)
+            })
+            .collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn row_to_state(
+        row: &rusqlite::Row,
+    ) -> rusqlite::Result<(String, String, String, String, bool, u64)> {
+        Ok((
+            row.get(0)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            row.get::<_, i32>(4)? != 0,
+            row.get::<_, i64>(5)? as u64,
+        ))
+    }
+
+    fn decode_state(
+        (memory_id, clock, writer, updated_at, deleted, seq): (
+            String,
+            String,
+            String,
+            String,
+            bool,
+            u64,
+        ),
+    ) -> RookResult<SyncState> {
+        let clock: VersionVector = serde_json::from_str(&clock)?;
+        Ok(SyncState {
+            memory_id,
+            version: SyncVersion {
+                clock,
+                writer,
+                updated_at,
+            },
+            deleted,
+            seq,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encryption::{generate_content_key, ContentEncryptionConfig};
+
+    #[test]
+    fn test_sync_store_round_trip() {
+        let store = SyncStore::new(":memory:", Some("laptop".to_string())).unwrap();
+        assert_eq!(store.node_id(), "laptop");
+        assert_eq!(store.seq().unwrap(), 0);
+
+        store.record_local("m1", false, "2024-01-01T00:00:00Z").unwrap();
+        let state = store.record_local("m1", false, "2024-01-02T00:00:00Z").unwrap();
+        assert_eq!(state.version.clock.get("laptop"), 2);
+        assert_eq!(state.seq, 2);
+        store.record_local("m2", true, "2024-01-03T00:00:00Z").unwrap();
+
+        let changed = store.states_since(1, 10).unwrap();
+        let ids: Vec<_> = changed.iter().map(|s| s.memory_id.as_str()).collect();
+        assert_eq!(ids, vec!["m1", "m2"]);
+        assert!(changed[1].deleted);
+
+        store.set_peer_cursor("desktop", 7).unwrap();
+        store.set_peer_cursor("desktop", 3).unwrap();
+        assert_eq!(store.peer_cursor("desktop").unwrap(), 7);
+        assert_eq!(store.peer_cursor("phone").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_sync_store_persists_node_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync.db");
+        let generated = SyncStore::new(&path, None).unwrap().node_id().to_string();
+        assert_eq!(SyncStore::new(&path, None).unwrap().node_id(), generated);
+        assert!(SyncStore::new(":memory:", Some("sync:x".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_supersede_content_encrypted_at_rest() {
+        let config = ContentEncryptionConfig::with_key("k1", generate_content_key().unwrap());
+        let store = SyncStore::new(":memory:", Some("laptop".to_string()))
+            .unwrap()
+            .with_cipher(ContentCipher::new(config).unwrap());
+        store
+            .save_supersede(&SupersedeRecord {
+                id: "s1".to_string(),
+                memory_id: "m1".to_string(),
+                winner_node: "laptop".to_string(),
+                winner_updated_at: "2024-01-02T00:00:00Z".to_string(),
+                loser_node: "desktop".to_string(),
+                loser_updated_at: "2024-01-01T00:00:00Z".to_string(),
+                loser_content: Some("Uses Helix".to_string()),
+                loser_deleted: false,
+                resolved_at: "2024-01-02T00:00:00Z".to_string(),
+            })
+            .unwrap();
+
+        let stored: String = store
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT loser_content FROM sync_supersedes", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert!(stored.starts_with("enc:k1:"));
+        let records = store.supersedes(10).unwrap();
+        assert_eq!(records[0].loser_content.as_deref(), Some("Uses Helix"));
+    }
+}
@@ -44,6 +44,27 @@ pub trait Embedder: Send + Sync {
     fn model_name(&self) -> &str;
 }
 
+/// Embeds images and text into one vector space (CLIP-style), so a text
+/// query can find an image by what it shows.
+///
+/// Image embeddings live in their own index (see
+/// [`Memory::with_image_index`](crate::memory::Memory::with_image_index)),
+/// as they are not comparable with the text embedder's vectors.
+#[async_trait]
+pub trait ImageEmbedder: Send + Sync {
+    /// Generate embedding for an image's bytes.
+    async fn embed_image(&self, image: &[u8], mime_type: &str) -> RookResult<Vec<f32>>;
+
+    /// Generate embedding for a text query, in the same space as images.
+    async fn embed_text(&self, text: &str) -> RookResult<Vec<f32>>;
+
+    /// Get the dimension of the embeddings.
+    fn dimension(&self) -> usize;
+
+    /// Get the model name.
+    fn model_name(&self) -> &str;
+}
+
 /// Embedder configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbedderConfig {
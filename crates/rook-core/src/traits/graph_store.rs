@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::error::RookResult;
+use crate::error::{RookError, RookResult};
 use crate::types::{GraphRelation, Message};
 
 /// Entity in a knowledge graph.
@@ -30,6 +30,51 @@ pub struct Relationship {
     pub properties: serde_json::Value,
 }
 
+/// Deepest traversal any backend will perform, regardless of the requested depth.
+pub const MAX_TRAVERSAL_DEPTH: usize = 6;
+
+/// A path through the graph found by [`GraphStore::traverse`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphPath {
+    /// Entity the traversal started from.
+    pub start: String,
+    /// Relationships followed from `start`, in order.
+    pub hops: Vec<GraphRelation>,
+}
+
+impl GraphPath {
+    /// Build a path from its entity names and the relationship types between them.
+    ///
+    /// Returns `None` unless there is exactly one more node than relationship.
+    pub fn from_parts(nodes: Vec<String>, relationships: Vec<String>) -> Option<Self> {
+        if nodes.len() != relationships.len() + 1 {
+            return None;
+        }
+
+        let start = nodes[0].clone();
+        let hops = nodes
+            .windows(2)
+            .zip(relationships)
+            .map(|(pair, relationship)| GraphRelation {
+                source: pair[0].clone(),
+                relationship,
+                target: pair[1].clone(),
+            })
+            .collect();
+        Some(Self { start, hops })
+    }
+
+    /// Number of relationships in the path.
+    pub fn depth(&self) -> usize {
+        self.hops.len()
+    }
+
+    /// Entity the path ends at.
+    pub fn end(&self) -> &str {
+        self.hops.last().map(|hop| hop.target.as_str()).unwrap_or(&self.start)
+    }
+}
+
 /// Search filters for graph operations.
 #[derive(Debug, Clone, Default)]
 pub struct GraphFilters {
@@ -41,6 +86,37 @@ pub struct GraphFilters {
     pub run_id: Option<String>,
 }
 
+/// Graph-aware search settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphSearchConfig {
+    /// Whether search consults the graph store (default: true).
+    pub enabled: bool,
+    /// Hops to follow from entities named in the query, 1 or 2 (default: 2).
+    pub max_hops: usize,
+    /// Score added to memories that mention an entity named in the query
+    /// (default: 0.1). Memories mentioning an entity `n` hops away get
+    /// `graph_boost / (n + 1)`.
+    pub graph_boost: f32,
+    /// Maximum relations returned with search results (default: 20).
+    pub relation_limit: usize,
+    /// Use the LLM to extract query entities when the query names no known
+    /// entity (default: false).
+    pub llm_extraction: bool,
+}
+
+impl Default for GraphSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_hops: 2,
+            graph_boost: 0.1,
+            relation_limit: 20,
+            llm_extraction: false,
+        }
+    }
+}
+
 /// Core GraphStore trait - all graph store backends implement this.
 #[async_trait]
 pub trait GraphStore: Send + Sync {
@@ -65,6 +141,17 @@ pub trait GraphStore: Send + Sync {
     /// Get all entities for the given filters.
     async fn get_all(&self, filters: &GraphFilters) -> RookResult<Vec<Entity>>;
 
+    /// Get all current relationships between entities within the given filters.
+    ///
+    /// Invalidated or superseded relationships are left out. Used to carry
+    /// the graph in exports.
+    async fn get_all_relationships(&self, filters: &GraphFilters) -> RookResult<Vec<Relationship>> {
+        let _ = filters;
+        Err(RookError::graph_store(
+            "Listing relationships is not supported by this graph store",
+        ))
+    }
+
     /// Add an entity directly to the graph store.
     ///
     /// This method is used for LLM-extracted entities. The entity extraction
@@ -104,6 +191,68 @@ pub trait GraphStore: Send + Sync {
         Ok(0)
     }
 
+    /// Add a relationship that replaces every other current relationship of
+    /// the same type from `source_name` (e.g. a new `works_at`).
+    ///
+    /// Stores that track temporal validity end the replaced relationships
+    /// instead of deleting them. The default only adds the new relationship.
+    async fn supersede_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        self.add_relationship(source_name, target_name, relationship_type, properties, filters)
+            .await
+    }
+
+    /// Mark a relationship as no longer valid.
+    ///
+    /// Returns whether a currently valid relationship was found. Stores without
+    /// temporal validity return `false`.
+    async fn invalidate_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<bool> {
+        let _ = (source_name, target_name, relationship_type, filters);
+        Ok(false)
+    }
+
+    /// Follow outgoing relationships from `start_entity` up to `max_depth` hops.
+    ///
+    /// Returns one shortest path per reachable entity, ordered by depth. When
+    /// `relationship_filter` is given, only relationships of those types
+    /// (case-insensitive) are followed. Depth is capped at
+    /// [`MAX_TRAVERSAL_DEPTH`].
+    async fn traverse(
+        &self,
+        start_entity: &str,
+        max_depth: usize,
+        relationship_filter: Option<&[String]>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphPath>> {
+        let _ = (start_entity, max_depth, relationship_filter, filters);
+        Err(RookError::graph_store("Traversal is not supported by this graph store"))
+    }
+
+    /// Move the entities of one scope to another.
+    ///
+    /// An entity is moved when every ID set in `from` equals its own. IDs set
+    /// in `to` replace the entity's; the others are kept. An entity whose
+    /// name already exists in its new scope is merged into that entity.
+    /// Returns the number of entities moved.
+    async fn reassign_scope(&self, from: &GraphFilters, to: &GraphFilters) -> RookResult<usize> {
+        let _ = (from, to);
+        Err(RookError::graph_store(
+            "Reassigning scopes is not supported by this graph store",
+        ))
+    }
+
     /// Get entities with their embeddings for entity merging.
     ///
     /// This is used by the entity merger to find similar entities.
@@ -199,4 +348,6 @@ pub enum GraphStoreProvider {
     Memgraph,
     /// AWS Neptune.
     Neptune,
+    /// Kuzu embedded graph database.
+    Kuzu,
 }
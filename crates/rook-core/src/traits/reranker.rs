@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use crate::error::RookResult;
+use crate::retrieval::RerankCacheConfig;
 use crate::types::MemoryItem;
 
 /// Core Reranker trait - all reranker providers implement this.
@@ -34,6 +35,9 @@ pub struct RerankerConfig {
     /// Top-N results to rerank.
     #[serde(default = "default_top_n")]
     pub top_n: usize,
+    /// Reuse of recent rankings and a deadline for reranking.
+    #[serde(default)]
+    pub cache: RerankCacheConfig,
 }
 
 fn default_top_n() -> usize {
@@ -47,6 +51,7 @@ impl Default for RerankerConfig {
             model: "rerank-english-v2.0".to_string(),
             api_key: None,
             top_n: default_top_n(),
+            cache: RerankCacheConfig::default(),
         }
     }
 }
@@ -61,4 +66,6 @@ pub enum RerankerProvider {
     Llm,
     SentenceTransformer,
     ZeroEntropy,
+    Jina,
+    Voyage,
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::RookResult;
-use crate::types::Filter;
+use crate::types::{Cursor, Filter, OrderBy, Page, PageRequest};
 
 /// Distance metric for vector similarity.
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -125,6 +125,38 @@ pub trait VectorStore: Send + Sync {
         limit: Option<usize>,
     ) -> RookResult<Vec<VectorRecord>>;
 
+    /// List vectors ordered by a typed payload field.
+    ///
+    /// The default implementation lists every match and sorts in memory;
+    /// SQL-backed stores order in the query.
+    async fn list_ordered(
+        &self,
+        filters: Option<Filter>,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        let mut records = self.list(filters, None).await?;
+        order_by.sort(&mut records, |r| &r.payload);
+        if let Some(limit) = limit {
+            records.truncate(limit);
+        }
+        Ok(records)
+    }
+
+    /// List one page of vectors in `(created_at, id)` order, with the total
+    /// number of matches.
+    ///
+    /// The default implementation lists every match and pages in memory;
+    /// the sqlite-vec and Qdrant stores count and window in the store.
+    async fn list_page(
+        &self,
+        filters: Option<Filter>,
+        page: &PageRequest,
+    ) -> RookResult<Page<VectorRecord>> {
+        let records = self.list(filters, None).await?;
+        Ok(page.apply(records, |r| Cursor::of_record(&r.id, &r.payload)))
+    }
+
     /// List all collections.
     async fn list_collections(&self) -> RookResult<Vec<String>>;
 
@@ -139,6 +171,14 @@ pub trait VectorStore: Send + Sync {
 
     /// Get the collection name.
     fn collection_name(&self) -> &str;
+
+    /// Whether `search` and `list` apply `Gte`/`Lt`/`Lte` conditions to RFC
+    /// 3339 timestamp fields such as `created_at` and `expires_at`, so time
+    /// ranges can be filtered in the store. Memory filters results itself
+    /// otherwise.
+    fn supports_timestamp_filters(&self) -> bool {
+        false
+    }
 }
 
 /// Batch operations for high-throughput scenarios.
@@ -245,6 +285,10 @@ pub struct VectorStoreConfig {
     /// Only used when provider is pgvector or pgvector_pooled.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pool: Option<PostgresPoolConfig>,
+    /// Vector quantization (optional).
+    /// Only used by stores that support quantized indexes (sqlite_vec).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<QuantizationConfig>,
     /// Provider-specific configuration.
     #[serde(flatten)]
     pub config: serde_json::Value,
@@ -261,6 +305,7 @@ impl Default for VectorStoreConfig {
             collection_name: "rook".to_string(),
             embedding_model_dims: default_embedding_dims(),
             pool: None,
+            quantization: None,
             config: serde_json::json!({}),
         }
     }
@@ -298,4 +343,67 @@ pub enum VectorStoreProvider {
     AzureMysql,
     /// SQLite with sqlite-vec extension for embedded vector search.
     SqliteVec,
+    Turbopuffer,
+    Typesense,
+}
+
+/// Vector quantization scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuantizationType {
+    /// Full-precision float32 vectors.
+    #[default]
+    None,
+    /// Scalar quantization to one signed byte per dimension (4x smaller index).
+    Int8,
+    /// Binary quantization to one bit per dimension (32x smaller index).
+    Binary,
+}
+
+/// Vector quantization configuration.
+///
+/// Search runs in two stages: a coarse scan over the quantized index, then
+/// exact rescoring of the top `limit * rescore_multiplier` candidates using
+/// the full-precision vectors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantizationConfig {
+    /// Quantization scheme.
+    #[serde(rename = "type", default)]
+    pub kind: QuantizationType,
+    /// Candidate oversampling factor for rescoring
+    /// (default: 4 for int8, 10 for binary).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rescore_multiplier: Option<usize>,
+}
+
+impl QuantizationConfig {
+    /// Create a config for the given scheme with the default multiplier.
+    pub fn new(kind: QuantizationType) -> Self {
+        Self {
+            kind,
+            rescore_multiplier: None,
+        }
+    }
+
+    /// Set the rescoring oversampling factor.
+    pub fn with_rescore_multiplier(mut self, multiplier: usize) -> Self {
+        self.rescore_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Whether quantization is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.kind != QuantizationType::None
+    }
+
+    /// Effective oversampling factor (at least 1).
+    pub fn rescore_multiplier(&self) -> usize {
+        self.rescore_multiplier
+            .unwrap_or(match self.kind {
+                QuantizationType::Binary => 10,
+                QuantizationType::Int8 => 4,
+                QuantizationType::None => 1,
+            })
+            .max(1)
+    }
 }
@@ -0,0 +1,55 @@
+//! Search result explanations.
+
+use serde::{Deserialize, Serialize};
+
+/// Why a search result surfaced: the signals and ranking stages behind its
+/// score.
+///
+/// Returned per result by searches with `explain` set. Signals a search
+/// didn't use are `None`; deltas are only recorded for stages that changed
+/// the score.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetrievalExplanation {
+    /// Vector similarity to the query. Not recorded when the rankings of
+    /// several expanded queries were fused.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_score: Option<f32>,
+    /// BM25 keyword score (normalized 0-1), from the retrieval engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25: Option<f32>,
+    /// Spreading activation (0-1), from the retrieval engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation: Option<f32>,
+    /// FSRS retrievability weight (0-1), from the retrieval engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fsrs_weight: Option<f32>,
+    /// Importance of the most central graph entity the memory mentions
+    /// (0-1), from the retrieval engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f32>,
+    /// Score after retrieval: the similarity, the fused score of expanded
+    /// queries or the retrieval engine's combined score.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retrieval_score: Option<f32>,
+    /// Score change from reranking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_delta: Option<f32>,
+    /// Score change from decay ranking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_delta: Option<f32>,
+    /// Score change from the recency boost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recency_delta: Option<f32>,
+    /// Score change from the graph boost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph_boost_delta: Option<f32>,
+    /// Injected ahead of the search results as a key memory.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub key_memory: bool,
+    /// Injected ahead of the search results as pinned to the searched run.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Score returned with the result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_score: Option<f32>,
+}
@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// Defines thresholds for identifying memories that should be archived
 /// (moved to cold storage) due to low retrievability and age.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ArchivalConfig {
     /// Retrievability threshold below which memories become archival candidates.
     /// Default: 0.1 (10% recall probability)
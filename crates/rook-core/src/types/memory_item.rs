@@ -1,5 +1,6 @@
 //! Memory item types.
 
+use super::explanation::RetrievalExplanation;
 use super::fsrs::{DualStrength, FsrsState};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -35,6 +36,12 @@ pub struct MemoryItem {
     /// Whether this is a key/important memory (higher priority in retrieval).
     #[serde(default, skip_serializing_if = "is_false")]
     pub is_key: bool,
+    /// Whether this memory was included because it is pinned to the searched run.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub pinned: bool,
+    /// ID of the intention whose fired reminder surfaced this memory in the search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surfaced_by: Option<String>,
 
     // FSRS-6 memory dynamics fields
 
@@ -44,6 +51,13 @@ pub struct MemoryItem {
     /// Dual-strength model (storage and retrieval strength).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dual_strength: Option<DualStrength>,
+    /// Current probability of recall, from the FSRS state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retrievability: Option<f32>,
+
+    /// Why the memory surfaced, from searches with `explain` set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<RetrievalExplanation>,
 }
 
 /// Helper function to skip serializing false booleans.
@@ -64,8 +78,12 @@ impl MemoryItem {
             updated_at: None,
             category: None,
             is_key: false,
+            pinned: false,
+            surfaced_by: None,
             memory_state: None,
             dual_strength: None,
+            retrievability: None,
+            explanation: None,
         }
     }
 
@@ -99,6 +117,12 @@ impl MemoryItem {
         self
     }
 
+    /// Set the current retrievability.
+    pub fn with_retrievability(mut self, retrievability: f32) -> Self {
+        self.retrievability = Some(retrievability);
+        self
+    }
+
     /// Set the category for this memory.
     pub fn with_category(mut self, category: impl Into<String>) -> Self {
         self.category = Some(category.into());
@@ -122,6 +146,11 @@ impl MemoryItem {
         self.updated_at = Some(updated_at.into());
         self
     }
+
+    /// Free-form tags set on this memory.
+    pub fn tags(&self) -> Vec<String> {
+        self.metadata.as_ref().map(super::tags_of).unwrap_or_default()
+    }
 }
 
 /// Event type for memory operations.
@@ -1,13 +1,23 @@
 //! Core types for rook.
 
 mod category;
+mod explanation;
 mod filter;
 mod fsrs;
 mod memory_item;
 mod message;
+mod ordering;
+mod pagination;
+mod projection;
+mod tags;
 
 pub use category::{CategoryConfig, DefaultCategory, KeyMemoryConfig};
+pub use explanation::RetrievalExplanation;
 pub use filter::*;
 pub use fsrs::{ArchivalConfig, DualStrength, FsrsState, Grade};
 pub use memory_item::*;
 pub use message::*;
+pub use ordering::{MetadataFieldType, MetadataSchema, OrderBy, SortDirection};
+pub use pagination::{Cursor, Page, PageRequest};
+pub use projection::FieldSelection;
+pub use tags::{has_all_tags, normalize_tag, parse_tag_filter, tags_of, TagCount, TAGS_FIELD};
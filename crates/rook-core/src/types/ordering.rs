@@ -0,0 +1,252 @@
+//! Typed metadata fields and result ordering.
+//!
+//! Payload values are untyped JSON, so ordering by a metadata field needs to
+//! know how to compare it: `"2024-01-01T13:00:00+02:00"` sorts after
+//! `"2024-01-01T12:00:00Z"` as a string but before it as a date. A [`MetadataSchema`] declares the type of each sortable field
+//! for a memory store; [`OrderBy`] orders records by one declared field.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Type of a metadata field, used to compare its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataFieldType {
+    String,
+    Number,
+    /// RFC 3339 timestamp.
+    Date,
+    Boolean,
+}
+
+/// Sort direction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Declared metadata field types for one memory store.
+///
+/// `created_at` and `updated_at` (dates) and `importance` (number) are always
+/// declared; `fields` adds or overrides declarations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetadataSchema {
+    #[serde(default)]
+    pub fields: HashMap<String, MetadataFieldType>,
+}
+
+impl MetadataSchema {
+    /// Builder: declare a field's type.
+    pub fn with_field(mut self, name: impl Into<String>, field_type: MetadataFieldType) -> Self {
+        self.fields.insert(name.into(), field_type);
+        self
+    }
+
+    /// The declared type of a field.
+    pub fn field_type(&self, name: &str) -> Option<MetadataFieldType> {
+        self.fields.get(name).copied().or(match name {
+            "created_at" | "updated_at" => Some(MetadataFieldType::Date),
+            "importance" => Some(MetadataFieldType::Number),
+            _ => None,
+        })
+    }
+
+    /// Parse an `order_by` spec (`field` or `field:asc|desc`) against the
+    /// declared fields.
+    pub fn order_by(&self, spec: &str) -> RookResult<OrderBy> {
+        let (field, direction) = match spec.trim().split_once(':') {
+            Some((field, "asc")) => (field, SortDirection::Asc),
+            Some((field, "desc")) => (field, SortDirection::Desc),
+            Some((_, other)) => {
+                return Err(RookError::validation(format!(
+                    "Unknown sort direction '{}' (expected asc or desc)",
+                    other
+                )))
+            }
+            None => (spec.trim(), SortDirection::Asc),
+        };
+        let field_type = self.field_type(field).ok_or_else(|| {
+            RookError::validation(format!(
+                "Cannot order by '{}': field has no declared type in the metadata schema",
+                field
+            ))
+        })?;
+        OrderBy::new(field, field_type, direction)
+    }
+}
+
+/// Order records by a typed payload field.
+///
+/// Records without a comparable value for the field sort last in either
+/// direction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderBy {
+    field: String,
+    field_type: MetadataFieldType,
+    direction: SortDirection,
+}
+
+impl OrderBy {
+    /// Order by `field`. Field names are limited to ASCII letters, digits
+    /// and underscores, so stores can embed them in queries.
+    pub fn new(
+        field: impl Into<String>,
+        field_type: MetadataFieldType,
+        direction: SortDirection,
+    ) -> RookResult<Self> {
+        let field = field.into();
+        if field.is_empty() || !field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(RookError::validation(format!(
+                "Invalid order_by field '{}'",
+                field
+            )));
+        }
+        Ok(Self {
+            field,
+            field_type,
+            direction,
+        })
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn field_type(&self) -> MetadataFieldType {
+        self.field_type
+    }
+
+    pub fn direction(&self) -> SortDirection {
+        self.direction
+    }
+
+    /// Compare two payloads by the field.
+    pub fn compare(
+        &self,
+        a: &HashMap<String, serde_json::Value>,
+        b: &HashMap<String, serde_json::Value>,
+    ) -> Ordering {
+        let key = |payload: &HashMap<String, serde_json::Value>| {
+            SortKey::from_value(payload.get(&self.field), self.field_type)
+        };
+        match (key(a), key(b)) {
+            (Some(a), Some(b)) => {
+                let ordering = a.compare(&b);
+                match self.direction {
+                    SortDirection::Asc => ordering,
+                    SortDirection::Desc => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    /// Sort `items` in place, reading each item's payload with `payload`.
+    pub fn sort<T>(
+        &self,
+        items: &mut [T],
+        payload: impl Fn(&T) -> &HashMap<String, serde_json::Value>,
+    ) {
+        items.sort_by(|a, b| self.compare(payload(a), payload(b)));
+    }
+}
+
+/// A field value converted to its declared type.
+#[derive(Debug, PartialEq, PartialOrd)]
+enum SortKey {
+    String(String),
+    Number(f64),
+    Date(DateTime<Utc>),
+    Boolean(bool),
+}
+
+impl SortKey {
+    fn from_value(
+        value: Option<&serde_json::Value>,
+        field_type: MetadataFieldType,
+    ) -> Option<Self> {
+        let value = value?;
+        match field_type {
+            MetadataFieldType::String => value.as_str().map(|s| Self::String(s.to_string())),
+            MetadataFieldType::Number => value.as_f64().map(Self::Number),
+            MetadataFieldType::Date => value
+                .as_str()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| Self::Date(dt.with_timezone(&Utc))),
+            MetadataFieldType::Boolean => value.as_bool().map(Self::Boolean),
+        }
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        // Keys of one field share a variant, and JSON numbers are finite
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload(key: &str, value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        HashMap::from([(key.to_string(), value)])
+    }
+
+    #[test]
+    fn test_order_by_number_missing_last() {
+        let order = MetadataSchema::default().order_by("importance:desc").unwrap();
+        let mut payloads = vec![
+            payload("importance", json!(9)),
+            payload("other", json!(1)),
+            payload("importance", json!(10)),
+            payload("importance", json!(0.5)),
+        ];
+        order.sort(&mut payloads, |p| p);
+
+        let values: Vec<_> = payloads.iter().map(|p| p.get("importance").cloned()).collect();
+        assert_eq!(
+            values,
+            vec![Some(json!(10)), Some(json!(9)), Some(json!(0.5)), None]
+        );
+    }
+
+    #[test]
+    fn test_order_by_date_across_offsets() {
+        let order = MetadataSchema::default().order_by("created_at").unwrap();
+        let mut payloads = vec![
+            payload("created_at", json!("2024-01-01T12:00:00+00:00")),
+            // Earlier instant despite the later wall-clock time
+            payload("created_at", json!("2024-01-01T13:00:00+02:00")),
+        ];
+        order.sort(&mut payloads, |p| p);
+        assert_eq!(payloads[0]["created_at"], json!("2024-01-01T13:00:00+02:00"));
+    }
+
+    #[test]
+    fn test_order_by_requires_declared_field() {
+        let schema = MetadataSchema::default();
+        assert!(schema.order_by("priority").is_err());
+        assert!(schema.order_by("created_at:sideways").is_err());
+
+        let schema = schema.with_field("priority", MetadataFieldType::Number);
+        let order = schema.order_by("priority:desc").unwrap();
+        assert_eq!(order.field_type(), MetadataFieldType::Number);
+        assert_eq!(order.direction(), SortDirection::Desc);
+    }
+
+    #[test]
+    fn test_order_by_rejects_unsafe_field_names() {
+        let schema = MetadataSchema::default().with_field("a'b", MetadataFieldType::String);
+        assert!(schema.order_by("a'b").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Offset and cursor pagination for listings.
+//!
+//! Pages follow a stable order, `created_at` then `id`. A [`Cursor`] marks
+//! the last record of a page, so the next page resumes right after it even
+//! if records are added or deleted in between; offsets are simpler but shift
+//! when earlier records change.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RookError, RookResult};
+
+/// Position of a record in `(created_at, id)` order.
+///
+/// Records without a parseable `created_at` sort first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    id: String,
+}
+
+impl Cursor {
+    pub fn new(created_at: Option<&str>, id: impl Into<String>) -> Self {
+        Self {
+            created_at: created_at.map(str::to_string),
+            id: id.into(),
+        }
+    }
+
+    /// The position of a record with this ID and payload.
+    pub fn of_record(id: &str, payload: &HashMap<String, serde_json::Value>) -> Self {
+        Self::new(payload.get("created_at").and_then(|v| v.as_str()), id)
+    }
+
+    /// The record's `created_at`, as stored.
+    pub fn created_at(&self) -> Option<&str> {
+        self.created_at.as_deref()
+    }
+
+    /// Opaque string form, safe in query strings.
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Parse a cursor produced by [`encode`](Self::encode).
+    pub fn decode(cursor: &str) -> RookResult<Self> {
+        hex::decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| RookError::validation(format!("Invalid cursor '{}'", cursor)))
+    }
+
+    /// Compare positions, by creation time then ID.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        let created_at = |cursor: &Self| {
+            cursor
+                .created_at
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc))
+        };
+        created_at(self)
+            .cmp(&created_at(other))
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+/// Which page of a listing to return.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Maximum records in the page; `None` returns every remaining record.
+    pub limit: Option<usize>,
+    /// Records to skip, after the cursor if one is given.
+    pub offset: usize,
+    /// Start after this position.
+    pub cursor: Option<Cursor>,
+}
+
+impl PageRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: set the page size.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Builder: skip records.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Builder: start after a cursor.
+    pub fn with_cursor(mut self, cursor: Cursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Sort `items` by their position and cut out this page.
+    pub fn apply<T>(&self, items: Vec<T>, position: impl Fn(&T) -> Cursor) -> Page<T> {
+        let mut keyed: Vec<(Cursor, T)> = items
+            .into_iter()
+            .map(|item| (position(&item), item))
+            .collect();
+        keyed.sort_by(|a, b| a.0.compare(&b.0));
+        let total = keyed.len();
+
+        let after_cursor = match self.cursor {
+            Some(ref cursor) => keyed.partition_point(|(key, _)| key.compare(cursor).is_le()),
+            None => 0,
+        };
+        let start = after_cursor.saturating_add(self.offset).min(total);
+        let end = match self.limit {
+            Some(limit) => start.saturating_add(limit).min(total),
+            None => total,
+        };
+
+        // A cursor is only useful when the page stopped short of the end
+        let next_cursor = (end > start && end < total).then(|| keyed[end - 1].0.encode());
+        let items = keyed.drain(start..end).map(|(_, item)| item).collect();
+
+        Page {
+            items,
+            total,
+            next_cursor,
+        }
+    }
+}
+
+/// One page of a listing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Records in the whole listing.
+    pub total: usize,
+    /// Cursor for the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Convert the items, keeping the total and cursor.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<(&'static str, Option<&'static str>)> {
+        vec![
+            ("c", Some("2024-01-02T00:00:00Z")),
+            ("b", Some("2024-01-01T00:00:00Z")),
+            ("a", Some("2024-01-02T00:00:00+00:00")),
+            ("z", None),
+        ]
+    }
+
+    fn ids<T: Copy>(page: &Page<(&'static str, T)>) -> Vec<&'static str> {
+        page.items.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn test_pages_follow_created_at_then_id() {
+        let position = |r: &(&str, Option<&str>)| Cursor::new(r.1, r.0);
+
+        let first = PageRequest::new().with_limit(2).apply(records(), position);
+        assert_eq!(ids(&first), vec!["z", "b"]);
+        assert_eq!(first.total, 4);
+
+        let cursor = Cursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+        let second = PageRequest::new()
+            .with_limit(2)
+            .with_cursor(cursor)
+            .apply(records(), position);
+        // Equal timestamps in different offsets fall back to the ID
+        assert_eq!(ids(&second), vec!["a", "c"]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_offset_and_cursor_survive_inserts() {
+        let position = |r: &(&str, Option<&str>)| Cursor::new(r.1, r.0);
+        let first = PageRequest::new().with_limit(2).apply(records(), position);
+        let cursor = Cursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+
+        // A record created before the cursor doesn't shift the next page
+        let mut grown = records();
+        grown.push(("y", Some("2023-12-31T00:00:00Z")));
+        let second = PageRequest::new()
+            .with_cursor(cursor)
+            .apply(grown.clone(), position);
+        assert_eq!(ids(&second), vec!["a", "c"]);
+        assert_eq!(second.total, 5);
+
+        let offset = PageRequest::new().with_offset(4).apply(grown, position);
+        assert_eq!(ids(&offset), vec!["c"]);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(Cursor::decode("not-a-cursor").is_err());
+        let cursor = Cursor::new(Some("2024-01-01T00:00:00Z"), "m1");
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+}
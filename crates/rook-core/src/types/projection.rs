@@ -0,0 +1,170 @@
+//! Response field selection.
+//!
+//! A [`FieldSelection`] names the fields a caller wants back, e.g.
+//! `id,memory,metadata.user_id`. Dotted paths select keys inside nested
+//! objects. The `id` field is always kept so results stay addressable.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Fields to keep in a response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct FieldSelection {
+    fields: Vec<String>,
+}
+
+impl FieldSelection {
+    /// Parse a comma-separated field list. Blank entries are ignored.
+    pub fn parse(s: &str) -> Self {
+        Self::from_fields(s.split(','))
+    }
+
+    /// Build a selection from individual field names or dotted paths.
+    pub fn from_fields<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut selection = Vec::new();
+        for field in fields {
+            let field = field.as_ref().trim();
+            if !field.is_empty() && !selection.iter().any(|f| f == field) {
+                selection.push(field.to_string());
+            }
+        }
+        Self { fields: selection }
+    }
+
+    /// Whether no fields were requested (the full response is returned).
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The requested fields, in request order.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// The selection as a comma-separated list, for query strings.
+    pub fn to_query(&self) -> String {
+        self.fields.join(",")
+    }
+
+    /// Keep only the selected fields of a JSON object.
+    ///
+    /// Non-object values and empty selections are returned unchanged.
+    /// Requested fields the value does not have are skipped.
+    pub fn apply(&self, value: serde_json::Value) -> serde_json::Value {
+        let serde_json::Value::Object(source) = value else {
+            return value;
+        };
+        if self.is_empty() {
+            return serde_json::Value::Object(source);
+        }
+
+        let mut projected = serde_json::Map::new();
+        if let Some(id) = source.get("id") {
+            projected.insert("id".to_string(), id.clone());
+        }
+        for field in &self.fields {
+            let path: Vec<&str> = field.split('.').collect();
+            copy_path(&source, &mut projected, &path);
+        }
+        serde_json::Value::Object(projected)
+    }
+
+    /// Serialize `item` and keep only the selected fields.
+    pub fn project<T: Serialize>(&self, item: &T) -> serde_json::Result<serde_json::Value> {
+        Ok(self.apply(serde_json::to_value(item)?))
+    }
+}
+
+/// Copy `path` from `source` into `target`, creating intermediate objects.
+fn copy_path(
+    source: &serde_json::Map<String, serde_json::Value>,
+    target: &mut serde_json::Map<String, serde_json::Value>,
+    path: &[&str],
+) {
+    let Some((&key, rest)) = path.split_first() else {
+        return;
+    };
+    let Some(value) = source.get(key) else {
+        return;
+    };
+
+    if rest.is_empty() {
+        target.insert(key.to_string(), value.clone());
+        return;
+    }
+    let serde_json::Value::Object(nested) = value else {
+        return;
+    };
+    let entry = target
+        .entry(key.to_string())
+        .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+    if let serde_json::Value::Object(entry) = entry {
+        copy_path(nested, entry, rest);
+    }
+}
+
+impl<'de> Deserialize<'de> for FieldSelection {
+    /// Accepts either a comma-separated string or a list of field names.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Csv(String),
+            List(Vec<String>),
+        }
+
+        Ok(match Raw::deserialize(deserializer)? {
+            Raw::Csv(s) => Self::parse(&s),
+            Raw::List(fields) => Self::from_fields(fields),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MemoryItem;
+    use std::collections::HashMap;
+
+    fn item() -> MemoryItem {
+        let metadata = HashMap::from([
+            ("user_id".to_string(), serde_json::json!("alice")),
+            ("provenance".to_string(), serde_json::json!({"source": "chat", "turn": 3})),
+        ]);
+        MemoryItem::new("m1", "Likes tea").with_score(0.9).with_metadata(metadata)
+    }
+
+    #[test]
+    fn test_projection_keeps_id_and_selected_paths() {
+        let selection = FieldSelection::parse("memory, metadata.user_id,,missing");
+        assert_eq!(selection.fields(), ["memory", "metadata.user_id", "missing"]);
+
+        let projected = selection.project(&item()).unwrap();
+        assert_eq!(
+            projected,
+            serde_json::json!({
+                "id": "m1",
+                "memory": "Likes tea",
+                "metadata": {"user_id": "alice"},
+            })
+        );
+
+        let nested = FieldSelection::parse("metadata.provenance.source").project(&item()).unwrap();
+        assert_eq!(nested["metadata"], serde_json::json!({"provenance": {"source": "chat"}}));
+    }
+
+    #[test]
+    fn test_empty_selection_and_deserialization() {
+        let full = FieldSelection::default().project(&item()).unwrap();
+        assert_eq!(full["score"], serde_json::json!(0.9f32));
+
+        let from_list: FieldSelection = serde_json::from_str(r#"["id", "score"]"#).unwrap();
+        let from_csv: FieldSelection = serde_json::from_str(r#""id,score""#).unwrap();
+        assert_eq!(from_list, from_csv);
+        assert_eq!(from_csv.to_query(), "id,score");
+    }
+}
@@ -0,0 +1,96 @@
+//! Free-form memory tags.
+//!
+//! Unlike categories, which are single-valued and assigned by the LLM, tags
+//! are set by users and a memory can carry any number of them. Tags are
+//! stored as a list under the `tags` metadata field, trimmed and lowercased.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{RookError, RookResult};
+
+/// Metadata field holding a memory's tags.
+pub const TAGS_FIELD: &str = "tags";
+
+/// How often a tag is used within a scope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagCount {
+    /// The tag.
+    pub tag: String,
+    /// Memories carrying the tag.
+    pub count: usize,
+}
+
+/// Normalize a tag: trimmed and lowercased. Empty tags are dropped.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Tags stored in a memory's metadata.
+pub fn tags_of(metadata: &HashMap<String, serde_json::Value>) -> Vec<String> {
+    metadata
+        .get(TAGS_FIELD)
+        .and_then(|v| v.as_array())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|t| t.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a memory's metadata carries every one of `tags`.
+pub fn has_all_tags(metadata: &HashMap<String, serde_json::Value>, tags: &[String]) -> bool {
+    let stored = tags_of(metadata);
+    tags.iter().all(|tag| stored.contains(tag))
+}
+
+/// Parse a `tags` filter value: a tag or a list of tags, normalized.
+pub fn parse_tag_filter(value: &serde_json::Value) -> RookResult<Vec<String>> {
+    let invalid = || RookError::validation("The tags filter must be a string or a list of strings");
+    let tags: Vec<&str> = match value {
+        serde_json::Value::String(tag) => vec![tag.as_str()],
+        serde_json::Value::Array(tags) => tags
+            .iter()
+            .map(|t| t.as_str().ok_or_else(invalid))
+            .collect::<RookResult<_>>()?,
+        _ => return Err(invalid()),
+    };
+    Ok(tags.into_iter().filter_map(normalize_tag).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_tag() {
+        assert_eq!(normalize_tag("  Rust "), Some("rust".to_string()));
+        assert_eq!(normalize_tag("   "), None);
+    }
+
+    #[test]
+    fn test_has_all_tags() {
+        let mut metadata = HashMap::new();
+        metadata.insert(TAGS_FIELD.to_string(), json!(["rust", "work"]));
+
+        assert!(has_all_tags(&metadata, &["rust".to_string()]));
+        assert!(has_all_tags(&metadata, &["rust".to_string(), "work".to_string()]));
+        assert!(!has_all_tags(&metadata, &["home".to_string()]));
+        assert!(!has_all_tags(&HashMap::new(), &["rust".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_tag_filter() {
+        assert_eq!(parse_tag_filter(&json!("Rust")).unwrap(), vec!["rust"]);
+        assert_eq!(
+            parse_tag_filter(&json!(["rust", " Work ", ""])).unwrap(),
+            vec!["rust", "work"]
+        );
+        assert!(parse_tag_filter(&json!(3)).is_err());
+        assert!(parse_tag_filter(&json!(["rust", 3])).is_err());
+    }
+}
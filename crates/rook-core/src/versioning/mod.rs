@@ -6,5 +6,23 @@
 mod store;
 mod version;
 
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
 pub use store::{SqliteVersionStore, VersionStore};
 pub use version::{FsrsStateSnapshot, MemoryVersion, VersionEventType, VersionSummary};
+
+/// Version tracking settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersioningConfig {
+    /// Snapshot memories on update and delete (default: false).
+    pub enabled: bool,
+    /// Version database. Defaults to `versions.db` next to the history database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_path: Option<PathBuf>,
+    /// Versions kept per memory, oldest pruned first (default: unlimited).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_versions: Option<usize>,
+}
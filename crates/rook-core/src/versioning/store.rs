@@ -55,6 +55,14 @@ pub trait VersionStore: Send + Sync {
 
     /// Count total versions in store
     fn count_all(&self) -> RookResult<usize>;
+
+    /// IDs of memories with a version whose metadata `field` is one of
+    /// `values`, including memories deleted since
+    fn find_memory_ids(&self, field: &str, values: &[String]) -> RookResult<Vec<String>>;
+
+    /// Deletion versions of memories whose latest version is a deletion made
+    /// after `since` (or at any time, without it), oldest first
+    fn deleted_since(&self, since: Option<DateTime<Utc>>) -> RookResult<Vec<MemoryVersion>>;
 }
 
 /// SQLite-backed version store
@@ -65,6 +73,11 @@ pub struct SqliteVersionStore {
 impl SqliteVersionStore {
     /// Create a new store at the given path
     pub fn new(path: impl AsRef<Path>) -> RookResult<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
         let conn = Connection::open(path)?;
         let store = Self {
             conn: Mutex::new(conn),
@@ -393,6 +406,50 @@ impl VersionStore for SqliteVersionStore {
         })?;
         Ok(count as usize)
     }
+
+    fn find_memory_ids(&self, field: &str, values: &[String]) -> RookResult<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"SELECT DISTINCT memory_id FROM memory_versions
+               WHERE json_extract(metadata, '$."' || ?1 || '"') = ?2"#,
+        )?;
+        let mut ids = Vec::new();
+        for value in values {
+            for id in stmt.query_map(params![field, value], |row| row.get::<_, String>(0))? {
+                let id = id?;
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    fn deleted_since(&self, since: Option<DateTime<Utc>>) -> RookResult<Vec<MemoryVersion>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            r#"SELECT version_id, memory_id, version_number, content, metadata, fsrs_state,
+                      created_at, event_type, change_description, changed_by
+               FROM memory_versions v
+               WHERE event_type = ?1
+                 AND version_number = (SELECT MAX(version_number) FROM memory_versions
+                                       WHERE memory_id = v.memory_id)"#,
+        )?;
+
+        let results = stmt.query_map(params![VersionEventType::Deleted.as_str()], |row| {
+            Ok(Self::row_to_version(row))
+        })?;
+
+        let mut deleted = results
+            .map(|r| r.map_err(|e| e.into()).and_then(|inner| inner))
+            .collect::<RookResult<Vec<_>>>()?;
+        // Compared parsed; stored RFC 3339 strings vary in precision
+        if let Some(since) = since {
+            deleted.retain(|v| v.created_at > since);
+        }
+        deleted.sort_by_key(|v| v.created_at);
+        Ok(deleted)
+    }
 }
 
 #[cfg(test)]
@@ -541,6 +598,72 @@ mod tests {
         assert_eq!(store.count_all().unwrap(), 0);
     }
 
+    #[test]
+    fn test_find_memory_ids() {
+        let store = SqliteVersionStore::in_memory().unwrap();
+        let metadata = |user_id: &str| HashMap::from([("user_id".to_string(), user_id.into())]);
+        store
+            .add_version(&MemoryVersion::initial("mem-1", "First").with_metadata(metadata("alice")))
+            .unwrap();
+        store
+            .add_version(&MemoryVersion::initial("mem-2", "Second").with_metadata(metadata("bob")))
+            .unwrap();
+        store
+            .add_version(&MemoryVersion::initial("mem-3", "Third").with_metadata(metadata("tok")))
+            .unwrap();
+
+        let ids = store
+            .find_memory_ids("user_id", &["alice".to_string(), "tok".to_string()])
+            .unwrap();
+        assert_eq!(ids, vec!["mem-1".to_string(), "mem-3".to_string()]);
+        assert!(store.find_memory_ids("user_id", &["carol".to_string()]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deleted_since() {
+        let store = SqliteVersionStore::in_memory().unwrap();
+        let deleted = |v: &MemoryVersion, hours_ago: i64| {
+            let mut d = MemoryVersion::from_content_update(v, v.content.clone());
+            d.event_type = VersionEventType::Deleted;
+            d.created_at = Utc::now() - chrono::Duration::hours(hours_ago);
+            d
+        };
+
+        let gone = MemoryVersion::initial("mem-1", "Gone");
+        store.add_version(&gone).unwrap();
+        store.add_version(&deleted(&gone, 1)).unwrap();
+
+        let long_gone = MemoryVersion::initial("mem-2", "Long gone");
+        store.add_version(&long_gone).unwrap();
+        store.add_version(&deleted(&long_gone, 5)).unwrap();
+
+        // Deleted, then restored
+        let restored = MemoryVersion::initial("mem-3", "Back");
+        store.add_version(&restored).unwrap();
+        let restored_deletion = deleted(&restored, 2);
+        store.add_version(&restored_deletion).unwrap();
+        store
+            .add_version(&MemoryVersion::from_content_update(
+                &restored_deletion,
+                "Back",
+            ))
+            .unwrap();
+
+        let all: Vec<_> = store
+            .deleted_since(None)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.memory_id)
+            .collect();
+        assert_eq!(all, vec!["mem-2".to_string(), "mem-1".to_string()]);
+
+        let recent = store
+            .deleted_since(Some(Utc::now() - chrono::Duration::hours(3)))
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].memory_id, "mem-1");
+    }
+
     #[test]
     fn test_versions_in_range() {
         let store = SqliteVersionStore::in_memory().unwrap();
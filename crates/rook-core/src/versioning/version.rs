@@ -24,8 +24,10 @@ pub enum VersionEventType {
     Superseded,
     /// Memory was merged with another
     Merged,
-    /// Memory was restored from archive
+    /// Memory was restored from archive or rolled back to an earlier version
     Restored,
+    /// Memory was deleted; the version holds its last content
+    Deleted,
 }
 
 impl VersionEventType {
@@ -39,6 +41,7 @@ impl VersionEventType {
             Self::Superseded => "superseded",
             Self::Merged => "merged",
             Self::Restored => "restored",
+            Self::Deleted => "deleted",
         }
     }
 
@@ -52,6 +55,7 @@ impl VersionEventType {
             "superseded" => Some(Self::Superseded),
             "merged" => Some(Self::Merged),
             "restored" => Some(Self::Restored),
+            "deleted" => Some(Self::Deleted),
             _ => None,
         }
     }
@@ -223,6 +227,7 @@ mod tests {
             VersionEventType::Superseded,
             VersionEventType::Merged,
             VersionEventType::Restored,
+            VersionEventType::Deleted,
         ];
 
         for event_type in types {
@@ -0,0 +1,107 @@
+//! ZIP container access shared by the EPUB and PPTX extractors.
+//!
+//! Both formats are ZIP archives of XML parts that refer to each other by
+//! relative paths.
+
+use std::io::{Cursor, Read};
+
+use roxmltree::{Document, ParsingOptions};
+use zip::ZipArchive;
+
+/// A ZIP archive held in memory.
+pub(crate) struct Archive {
+    zip: ZipArchive<Cursor<Vec<u8>>>,
+}
+
+impl Archive {
+    /// Open an archive, describing failures with `format` (e.g. "EPUB").
+    pub(crate) fn open(content: Vec<u8>, format: &str) -> Result<Self, String> {
+        ZipArchive::new(Cursor::new(content))
+            .map(|zip| Self { zip })
+            .map_err(|e| format!("Failed to open {} archive: {}", format, e))
+    }
+
+    /// Contents of a part as text, or `None` if the archive lacks it.
+    pub(crate) fn read(&mut self, path: &str) -> Option<String> {
+        let mut file = self.zip.by_name(path).ok()?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// Parse an XML part. DTDs are allowed, as EPUB 2 navigation files
+/// declare one.
+pub(crate) fn parse_xml(xml: &str) -> Result<Document<'_>, String> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    Document::parse_with_options(xml, options).map_err(|e| format!("Invalid XML: {}", e))
+}
+
+/// Resolve `target`, relative to the part at `base`, to an archive path.
+///
+/// `resolve("OEBPS/content.opf", "text/ch1.xhtml")` is
+/// `OEBPS/text/ch1.xhtml`, and `resolve("ppt/slides/slide1.xml",
+/// "../notesSlides/notesSlide1.xml")` is `ppt/notesSlides/notesSlide1.xml`.
+pub(crate) fn resolve(base: &str, target: &str) -> String {
+    // Fragments and URL escapes don't name parts
+    let target = target
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .replace("%20", " ");
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut parts: Vec<&str> = base.split('/').collect();
+    parts.pop();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            segment => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Build an archive from (path, contents) pairs, for tests.
+#[cfg(test)]
+pub(crate) fn build_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, contents) in files {
+        writer
+            .start_file(*path, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("OEBPS/content.opf", "text/ch1.xhtml#top"),
+            "OEBPS/text/ch1.xhtml"
+        );
+        assert_eq!(
+            resolve("ppt/slides/slide1.xml", "../notesSlides/notesSlide1.xml"),
+            "ppt/notesSlides/notesSlide1.xml"
+        );
+        assert_eq!(resolve("content.opf", "ch%201.html"), "ch 1.html");
+        assert_eq!(
+            resolve("ppt/presentation.xml", "/ppt/slides/slide2.xml"),
+            "ppt/slides/slide2.xml"
+        );
+    }
+}
@@ -0,0 +1,900 @@
+//! Source code extraction using tree-sitter.
+//!
+//! Character-based chunking cuts functions in half and leaves chunks that
+//! can't be found by the symbol they define. This extractor parses the file
+//! and emits one chunk per top-level function, type or class, with code
+//! between definitions (imports, constants, scripts) grouped into their own
+//! chunks. Classes, impls and traits that are too long to keep whole are
+//! split into an outline, where member bodies are elided, and one chunk per
+//! member. Each chunk is one entry of `structure.pages`, with its symbol,
+//! kind and line range at the same index of the `symbols` metadata.
+
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tree_sitter::{Node, Parser, Tree};
+
+/// Languages the code extractor can parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeLanguage {
+    /// Rust (`.rs`).
+    Rust,
+    /// Python (`.py`).
+    Python,
+    /// JavaScript (`.js`, `.mjs`, `.cjs`, `.jsx`).
+    JavaScript,
+    /// TypeScript (`.ts`, `.mts`, `.cts`, `.tsx`).
+    TypeScript,
+    /// Go (`.go`).
+    Go,
+}
+
+/// MIME types of all supported languages.
+const ALL_MIME_TYPES: &[&str] = &[
+    "text/x-rust",
+    "text/x-python",
+    "text/x-script.python",
+    "text/javascript",
+    "application/javascript",
+    "text/typescript",
+    "application/typescript",
+    "text/x-go",
+];
+
+impl CodeLanguage {
+    /// All supported languages, in auto-detection preference order.
+    ///
+    /// JavaScript comes before TypeScript so that plain JavaScript, which
+    /// also parses as TypeScript, is reported as JavaScript.
+    pub const ALL: [CodeLanguage; 5] = [
+        CodeLanguage::Rust,
+        CodeLanguage::Python,
+        CodeLanguage::JavaScript,
+        CodeLanguage::TypeScript,
+        CodeLanguage::Go,
+    ];
+
+    /// Lowercase language name, as stored in metadata.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeLanguage::Rust => "rust",
+            CodeLanguage::Python => "python",
+            CodeLanguage::JavaScript => "javascript",
+            CodeLanguage::TypeScript => "typescript",
+            CodeLanguage::Go => "go",
+        }
+    }
+
+    /// MIME types for this language.
+    pub fn mime_types(&self) -> &'static [&'static str] {
+        match self {
+            CodeLanguage::Rust => &ALL_MIME_TYPES[0..1],
+            CodeLanguage::Python => &ALL_MIME_TYPES[1..3],
+            CodeLanguage::JavaScript => &ALL_MIME_TYPES[3..5],
+            CodeLanguage::TypeScript => &ALL_MIME_TYPES[5..7],
+            CodeLanguage::Go => &ALL_MIME_TYPES[7..8],
+        }
+    }
+
+    /// Language for a MIME type.
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.mime_types().contains(&mime_type))
+    }
+
+    /// Language for a file extension, with or without the leading dot.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension
+            .trim_start_matches('.')
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "rs" => Some(CodeLanguage::Rust),
+            "py" | "pyi" => Some(CodeLanguage::Python),
+            "js" | "mjs" | "cjs" | "jsx" => Some(CodeLanguage::JavaScript),
+            "ts" | "mts" | "cts" | "tsx" => Some(CodeLanguage::TypeScript),
+            "go" => Some(CodeLanguage::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        match self {
+            CodeLanguage::Rust => tree_sitter_rust::LANGUAGE.into(),
+            CodeLanguage::Python => tree_sitter_python::LANGUAGE.into(),
+            CodeLanguage::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            // The TSX grammar also accepts JSX in .tsx files
+            CodeLanguage::TypeScript => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            CodeLanguage::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+
+    /// Separator between a container and member in qualified symbol names.
+    fn separator(&self) -> &'static str {
+        match self {
+            CodeLanguage::Rust => "::",
+            _ => ".",
+        }
+    }
+}
+
+impl std::fmt::Display for CodeLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Configuration for source code extraction.
+#[derive(Debug, Clone)]
+pub struct CodeConfig {
+    /// Language of the source. Detected by parsing with every grammar and
+    /// keeping the one with the fewest syntax errors when unset (default:
+    /// none).
+    pub language: Option<CodeLanguage>,
+    /// Definitions longer than this are split: containers into an outline
+    /// and their members, anything else on line boundaries
+    /// (default: 4000 characters).
+    pub max_chunk_chars: usize,
+}
+
+impl Default for CodeConfig {
+    fn default() -> Self {
+        Self {
+            language: None,
+            max_chunk_chars: 4000,
+        }
+    }
+}
+
+/// Code extractor splitting source files into function and class chunks.
+pub struct CodeExtractor {
+    config: CodeConfig,
+}
+
+/// A chunk of source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CodeChunk {
+    /// Qualified symbol name. `None` for code between definitions.
+    symbol: Option<String>,
+    /// Definition kind (function, method, struct, class, ...), or `code`.
+    kind: &'static str,
+    /// First line, 1-based.
+    start_line: usize,
+    /// Last line, 1-based.
+    end_line: usize,
+    text: String,
+}
+
+/// A definition found in the syntax tree.
+struct Definition<'tree> {
+    kind: &'static str,
+    name: String,
+    /// Member list of a container (impl, trait, class, module).
+    body: Option<Node<'tree>>,
+}
+
+impl CodeExtractor {
+    /// Create code extractor with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: CodeConfig::default(),
+        }
+    }
+
+    /// Create code extractor for a single language.
+    pub fn for_language(language: CodeLanguage) -> Self {
+        Self::with_config(CodeConfig {
+            language: Some(language),
+            ..Default::default()
+        })
+    }
+
+    /// Create code extractor with custom configuration.
+    pub fn with_config(config: CodeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &CodeConfig {
+        &self.config
+    }
+
+    /// Parse and chunk source synchronously (called within spawn_blocking).
+    fn chunk_source(
+        source: &str,
+        config: &CodeConfig,
+    ) -> ExtractResult<(CodeLanguage, Vec<CodeChunk>)> {
+        let (language, tree) = match config.language {
+            Some(language) => (language, parse(source, language)?),
+            None => detect(source)?,
+        };
+
+        let mut chunker = Chunker {
+            source,
+            language,
+            max_chars: config.max_chunk_chars.max(1),
+            chunks: Vec::new(),
+        };
+        chunker.collect(tree.root_node(), None, true);
+        Ok((language, chunker.chunks))
+    }
+}
+
+impl Default for CodeExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse(source: &str, language: CodeLanguage) -> ExtractResult<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).map_err(|e| {
+        ExtractError::ExtractionFailed(format!("Failed to load {} grammar: {}", language, e))
+    })?;
+    parser.parse(source, None).ok_or_else(|| {
+        ExtractError::ExtractionFailed(format!("Failed to parse {} source", language))
+    })
+}
+
+/// Parse with every grammar, keeping the tree with the fewest errors.
+fn detect(source: &str) -> ExtractResult<(CodeLanguage, Tree)> {
+    let mut best: Option<(usize, CodeLanguage, Tree)> = None;
+    for language in CodeLanguage::ALL {
+        let tree = parse(source, language)?;
+        let errors = count_errors(tree.root_node());
+        let better = match &best {
+            Some((fewest, _, _)) => errors < *fewest,
+            None => true,
+        };
+        if better {
+            best = Some((errors, language, tree));
+        }
+        if errors == 0 {
+            break;
+        }
+    }
+    best.map(|(_, language, tree)| (language, tree))
+        .ok_or_else(|| ExtractError::ExtractionFailed("No grammar available".to_string()))
+}
+
+fn count_errors(node: Node) -> usize {
+    if !node.has_error() {
+        return 0;
+    }
+    if node.is_error() || node.is_missing() {
+        return 1;
+    }
+    let mut cursor = node.walk();
+    node.children(&mut cursor).map(count_errors).sum()
+}
+
+/// Whether a node documents or annotates the definition that follows it.
+fn is_leading(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "comment" | "line_comment" | "block_comment" | "attribute_item" | "decorator"
+    )
+}
+
+/// Text of a node's named field.
+fn field_text(node: Node, field: &str, source: &str) -> Option<String> {
+    node.child_by_field_name(field)
+        .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+        .map(str::to_string)
+}
+
+/// Classify a node as a definition, if it is one.
+///
+/// `in_type` is set for members of impls, traits and classes, whose
+/// functions are methods.
+fn classify<'tree>(
+    node: Node<'tree>,
+    language: CodeLanguage,
+    source: &str,
+    in_type: bool,
+) -> Option<Definition<'tree>> {
+    let named = |kind: &'static str| {
+        field_text(node, "name", source).map(|name| Definition {
+            kind,
+            name,
+            body: None,
+        })
+    };
+    let container = |kind: &'static str, name: Option<String>| {
+        name.map(|name| Definition {
+            kind,
+            name,
+            body: node.child_by_field_name("body"),
+        })
+    };
+    let function = if in_type { "method" } else { "function" };
+
+    match (language, node.kind()) {
+        (CodeLanguage::Rust, "function_item" | "function_signature_item") => named(function),
+        (CodeLanguage::Rust, "struct_item") => named("struct"),
+        (CodeLanguage::Rust, "enum_item") => named("enum"),
+        (CodeLanguage::Rust, "union_item") => named("union"),
+        (CodeLanguage::Rust, "type_item") => named("type"),
+        (CodeLanguage::Rust, "const_item") => named("const"),
+        (CodeLanguage::Rust, "static_item") => named("static"),
+        (CodeLanguage::Rust, "macro_definition") => named("macro"),
+        (CodeLanguage::Rust, "trait_item") => container("trait", field_text(node, "name", source)),
+        (CodeLanguage::Rust, "mod_item") => container("module", field_text(node, "name", source)),
+        (CodeLanguage::Rust, "impl_item") => container("impl", field_text(node, "type", source)),
+
+        (CodeLanguage::Python, "function_definition") => named(function),
+        (CodeLanguage::Python, "class_definition") => {
+            container("class", field_text(node, "name", source))
+        }
+        (CodeLanguage::Python, "decorated_definition") => node
+            .child_by_field_name("definition")
+            .and_then(|definition| classify(definition, language, source, in_type)),
+
+        (
+            CodeLanguage::JavaScript | CodeLanguage::TypeScript,
+            "function_declaration" | "generator_function_declaration" | "function_signature",
+        ) => named("function"),
+        (CodeLanguage::JavaScript | CodeLanguage::TypeScript, "method_definition") => {
+            named("method")
+        }
+        (
+            CodeLanguage::JavaScript | CodeLanguage::TypeScript,
+            "class_declaration" | "abstract_class_declaration",
+        ) => container("class", field_text(node, "name", source)),
+        (CodeLanguage::JavaScript | CodeLanguage::TypeScript, "export_statement") => node
+            .child_by_field_name("declaration")
+            .and_then(|declaration| classify(declaration, language, source, in_type)),
+        (
+            CodeLanguage::JavaScript | CodeLanguage::TypeScript,
+            "lexical_declaration" | "variable_declaration",
+        ) => {
+            // `const handler = (...) => {...}` defines a function
+            let mut cursor = node.walk();
+            let declarators: Vec<Node> = node.named_children(&mut cursor).collect();
+            match declarators.as_slice() {
+                [declarator]
+                    if declarator
+                        .child_by_field_name("value")
+                        .is_some_and(|value| {
+                            matches!(
+                                value.kind(),
+                                "arrow_function" | "function_expression" | "function"
+                            )
+                        }) =>
+                {
+                    field_text(*declarator, "name", source).map(|name| Definition {
+                        kind: "function",
+                        name,
+                        body: None,
+                    })
+                }
+                _ => None,
+            }
+        }
+        (CodeLanguage::TypeScript, "interface_declaration") => named("interface"),
+        (CodeLanguage::TypeScript, "type_alias_declaration") => named("type"),
+        (CodeLanguage::TypeScript, "enum_declaration") => named("enum"),
+
+        (CodeLanguage::Go, "function_declaration") => named("function"),
+        (CodeLanguage::Go, "method_declaration") => {
+            let name = field_text(node, "name", source)?;
+            let name = match field_text(node, "receiver", source).and_then(|r| receiver_type(&r)) {
+                Some(receiver) => format!("{}.{}", receiver, name),
+                None => name,
+            };
+            Some(Definition {
+                kind: "method",
+                name,
+                body: None,
+            })
+        }
+        (CodeLanguage::Go, "type_declaration") => {
+            let mut cursor = node.walk();
+            let specs: Vec<Node> = node
+                .named_children(&mut cursor)
+                .filter(|n| matches!(n.kind(), "type_spec" | "type_alias"))
+                .collect();
+            let names: Vec<String> = specs
+                .iter()
+                .filter_map(|spec| field_text(*spec, "name", source))
+                .collect();
+            let kind = match specs.as_slice() {
+                [spec] => match spec.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("struct_type") => "struct",
+                    Some("interface_type") => "interface",
+                    _ => "type",
+                },
+                _ => "type",
+            };
+            (!names.is_empty()).then(|| Definition {
+                kind,
+                name: names.join(", "),
+                body: None,
+            })
+        }
+
+        _ => None,
+    }
+}
+
+/// Type name from a Go method receiver such as `(s *Server)` or
+/// `(l *List[T])`.
+fn receiver_type(receiver: &str) -> Option<String> {
+    let last = receiver.split_whitespace().last()?;
+    let name = last
+        .trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+        .split('[')
+        .next()?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// 1-based line number of a byte offset.
+fn line_at(source: &str, byte: usize) -> usize {
+    source[..byte].matches('\n').count() + 1
+}
+
+/// Walks definitions and collects chunks.
+struct Chunker<'src> {
+    source: &'src str,
+    language: CodeLanguage,
+    max_chars: usize,
+    chunks: Vec<CodeChunk>,
+}
+
+impl Chunker<'_> {
+    /// Collect chunks for the children of `parent`.
+    ///
+    /// `prefix` qualifies symbol names inside a container. Code between
+    /// definitions becomes its own chunk only when `emit_loose` is set;
+    /// inside split containers it is already part of the outline.
+    fn collect(&mut self, parent: Node, prefix: Option<&str>, emit_loose: bool) {
+        let mut leading: Option<usize> = None;
+        let mut loose: Option<(usize, usize)> = None;
+
+        let mut cursor = parent.walk();
+        for child in parent.named_children(&mut cursor) {
+            if is_leading(child) {
+                leading.get_or_insert(child.start_byte());
+                continue;
+            }
+            let start = leading.take().unwrap_or(child.start_byte());
+            match classify(child, self.language, self.source, prefix.is_some()) {
+                Some(definition) => {
+                    if let Some((from, to)) = loose.take() {
+                        self.push_loose(from, to, prefix, emit_loose);
+                    }
+                    self.push_definition(child, start, definition, prefix);
+                }
+                None => {
+                    let from = loose.map_or(start, |(from, _)| from);
+                    loose = Some((from, child.end_byte()));
+                }
+            }
+        }
+
+        // Trailing comments belong with the code before them
+        if let Some(start) = leading {
+            let from = loose.map_or(start, |(from, _)| from);
+            loose = Some((from, parent.end_byte().min(self.source.len())));
+        }
+        if let Some((from, to)) = loose {
+            self.push_loose(from, to, prefix, emit_loose);
+        }
+    }
+
+    fn push_loose(&mut self, from: usize, to: usize, prefix: Option<&str>, emit: bool) {
+        if emit {
+            self.push(prefix.map(str::to_string), "code", from, to);
+        }
+    }
+
+    fn push_definition(
+        &mut self,
+        node: Node,
+        start: usize,
+        definition: Definition,
+        prefix: Option<&str>,
+    ) {
+        let name = match prefix {
+            Some(prefix) => format!("{}{}{}", prefix, self.language.separator(), definition.name),
+            None => definition.name,
+        };
+        let end = node.end_byte();
+
+        match definition.body {
+            Some(body) if end - start > self.max_chars && self.has_members(body) => {
+                self.push_outline(&name, definition.kind, start, end, body);
+                self.collect(body, Some(&name), false);
+            }
+            _ => self.push(Some(name), definition.kind, start, end),
+        }
+    }
+
+    /// Whether a container body has member definitions to split it into.
+    fn has_members(&self, body: Node) -> bool {
+        let mut cursor = body.walk();
+        let has_members = body
+            .named_children(&mut cursor)
+            .any(|child| classify(child, self.language, self.source, true).is_some());
+        has_members
+    }
+
+    /// Container text with the body of each member replaced by `...`.
+    fn push_outline(
+        &mut self,
+        name: &str,
+        kind: &'static str,
+        start: usize,
+        end: usize,
+        body: Node,
+    ) {
+        let mut text = String::new();
+        let mut from = start;
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            if classify(member, self.language, self.source, true).is_none() {
+                continue;
+            }
+            let signature_end = member
+                .child_by_field_name("body")
+                .or_else(|| {
+                    member
+                        .child_by_field_name("definition")
+                        .and_then(|d| d.child_by_field_name("body"))
+                })
+                .map_or(member.end_byte(), |b| b.start_byte());
+            text.push_str(&self.source[from..signature_end]);
+            text.push_str(if self.language == CodeLanguage::Python {
+                "..."
+            } else {
+                "{ ... }"
+            });
+            from = member.end_byte();
+        }
+        text.push_str(&self.source[from..end]);
+
+        self.chunks.push(CodeChunk {
+            symbol: Some(name.to_string()),
+            kind,
+            start_line: line_at(self.source, start),
+            end_line: line_at(self.source, end),
+            text: text.trim().to_string(),
+        });
+    }
+
+    /// Push the source between two offsets, split on line boundaries when
+    /// longer than the limit.
+    fn push(&mut self, symbol: Option<String>, kind: &'static str, from: usize, to: usize) {
+        let text = &self.source[from..to];
+        if text.trim().is_empty() {
+            return;
+        }
+        let first_line = line_at(self.source, from);
+
+        let mut part = String::new();
+        let mut part_start = first_line;
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            if !part.is_empty() && part.len() + line.len() > self.max_chars {
+                self.push_part(symbol.clone(), kind, part_start, first_line + i - 1, &part);
+                part.clear();
+                part_start = first_line + i;
+            }
+            part.push_str(line);
+        }
+        let last_line = first_line + text.trim_end().matches('\n').count();
+        self.push_part(symbol, kind, part_start, last_line, &part);
+    }
+
+    fn push_part(
+        &mut self,
+        symbol: Option<String>,
+        kind: &'static str,
+        start_line: usize,
+        end_line: usize,
+        text: &str,
+    ) {
+        let trimmed = text.trim_start();
+        if trimmed.trim_end().is_empty() {
+            return;
+        }
+        // Trimming leading blank lines moves the start
+        let start_line = start_line + text[..text.len() - trimmed.len()].matches('\n').count();
+        let text = trimmed.trim_end();
+        self.chunks.push(CodeChunk {
+            symbol,
+            kind,
+            start_line,
+            end_line: end_line.max(start_line),
+            text: text.to_string(),
+        });
+    }
+}
+
+impl CodeChunk {
+    /// Chunk text with a header naming the symbol, so the symbol is part of
+    /// what gets embedded.
+    fn page(&self, language: CodeLanguage) -> String {
+        let subject = match &self.symbol {
+            Some(symbol) => format!("{} {} {}", language, self.kind, symbol),
+            None => format!("{} {}", language, self.kind),
+        };
+        format!(
+            "{} (lines {}-{})\n{}",
+            subject, self.start_line, self.end_line, self.text
+        )
+    }
+}
+
+#[async_trait]
+impl Extractor for CodeExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let source = String::from_utf8(content.to_vec()).map_err(|e| {
+            ExtractError::ExtractionFailed(format!("Source is not valid UTF-8: {}", e))
+        })?;
+        let content_len = content.len();
+
+        // Check for empty extraction
+        if source.trim().is_empty() {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        // Detection parses the file once per grammar, so run in a blocking task
+        let config = self.config.clone();
+        let (language, chunks) =
+            tokio::task::spawn_blocking(move || Self::chunk_source(&source, &config)).await??;
+
+        let pages: Vec<String> = chunks.iter().map(|chunk| chunk.page(language)).collect();
+        let text = pages.join("\n\n");
+
+        let structure = DocumentStructure {
+            page_count: Some(pages.len()),
+            sections: chunks.iter().filter_map(|c| c.symbol.clone()).collect(),
+            pages,
+        };
+        let symbols: Vec<serde_json::Value> = chunks
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "name": c.symbol,
+                    "kind": c.kind,
+                    "start_line": c.start_line,
+                    "end_line": c.end_line,
+                })
+            })
+            .collect();
+
+        Ok(
+            ExtractedContent::new(text, Modality::Code, ContentSource::Bytes)
+                .with_structure(structure)
+                .with_metadata("original_size", content_len)
+                .with_metadata("language", language.as_str())
+                .with_metadata("symbols", symbols),
+        )
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        match self.config.language {
+            Some(language) => language.mime_types(),
+            None => ALL_MIME_TYPES,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "tree-sitter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST: &str = r#"use std::fmt;
+
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+}
+
+impl Point {
+    /// Create a point.
+    pub fn new(x: i32) -> Self {
+        Self { x }
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+}
+
+fn main() {
+    println!("{:?}", Point::new(1));
+}
+"#;
+
+    fn symbols(result: &ExtractedContent) -> Vec<(Option<String>, String)> {
+        result.metadata["symbols"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| {
+                (
+                    s["name"].as_str().map(str::to_string),
+                    s["kind"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rust_definitions_with_doc_comments() {
+        let result = CodeExtractor::new().extract(RUST.as_bytes()).await.unwrap();
+        assert_eq!(result.modality, Modality::Code);
+        assert_eq!(result.metadata["language"], "rust");
+        assert_eq!(
+            symbols(&result),
+            vec![
+                (None, "code".to_string()),
+                (Some("Point".to_string()), "struct".to_string()),
+                (Some("Point".to_string()), "impl".to_string()),
+                (Some("main".to_string()), "function".to_string()),
+            ]
+        );
+
+        let pages = result.structure.unwrap().pages;
+        assert_eq!(pages[0], "rust code (lines 1-1)\nuse std::fmt;");
+        assert!(
+            pages[1].starts_with("rust struct Point (lines 3-7)\n/// A point.\n#[derive(Debug)]")
+        );
+        assert_eq!(result.metadata["symbols"][2]["start_line"], 9);
+        assert_eq!(result.metadata["symbols"][2]["end_line"], 18);
+    }
+
+    #[tokio::test]
+    async fn test_large_impl_split_into_outline_and_methods() {
+        let extractor = CodeExtractor::with_config(CodeConfig {
+            language: Some(CodeLanguage::Rust),
+            max_chunk_chars: 100,
+        });
+        let result = extractor.extract(RUST.as_bytes()).await.unwrap();
+        let symbols = symbols(&result);
+        assert!(symbols.contains(&(Some("Point::new".to_string()), "method".to_string())));
+        assert!(symbols.contains(&(Some("Point::x".to_string()), "method".to_string())));
+
+        let pages = result.structure.unwrap().pages;
+        let outline = pages
+            .iter()
+            .find(|p| p.starts_with("rust impl Point"))
+            .unwrap();
+        assert!(outline.contains("pub fn new(x: i32) -> Self { ... }"));
+        assert!(!outline.contains("Self { x }"));
+        let method = pages
+            .iter()
+            .find(|p| p.starts_with("rust method Point::new"))
+            .unwrap();
+        assert!(method.contains("/// Create a point."));
+    }
+
+    #[tokio::test]
+    async fn test_python_classes_and_decorators() {
+        let source = "import os\n\n\
+                      @dataclass\n\
+                      class User:\n    name: str\n\n\
+                      def greet(user):\n    return 'hi ' + user.name\n";
+        let result = CodeExtractor::new()
+            .extract(source.as_bytes())
+            .await
+            .unwrap();
+        assert_eq!(result.metadata["language"], "python");
+        assert_eq!(
+            symbols(&result)[1..],
+            [
+                (Some("User".to_string()), "class".to_string()),
+                (Some("greet".to_string()), "function".to_string()),
+            ]
+        );
+        assert!(result
+            .text
+            .contains("python class User (lines 3-5)\n@dataclass"));
+    }
+
+    #[tokio::test]
+    async fn test_javascript_and_typescript_detection() {
+        let js = "export const handler = async (event) => {\n  return event;\n};\n";
+        let result = CodeExtractor::new().extract(js.as_bytes()).await.unwrap();
+        assert_eq!(result.metadata["language"], "javascript");
+        assert_eq!(
+            symbols(&result),
+            vec![(Some("handler".to_string()), "function".to_string())]
+        );
+
+        let ts = "interface Shape {\n  area(): number;\n}\n\n\
+                  function total(shapes: Shape[]): number {\n  return 0;\n}\n";
+        let result = CodeExtractor::new().extract(ts.as_bytes()).await.unwrap();
+        assert_eq!(result.metadata["language"], "typescript");
+        assert_eq!(
+            symbols(&result),
+            vec![
+                (Some("Shape".to_string()), "interface".to_string()),
+                (Some("total".to_string()), "function".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_go_methods_named_by_receiver() {
+        let source = "package main\n\n\
+                      type Server struct {\n\tport int\n}\n\n\
+                      func (s *Server) Start() error {\n\treturn nil\n}\n";
+        let extractor = CodeExtractor::for_language(CodeLanguage::Go);
+        let result = extractor.extract(source.as_bytes()).await.unwrap();
+        assert_eq!(
+            symbols(&result)[1..],
+            [
+                (Some("Server".to_string()), "struct".to_string()),
+                (Some("Server.Start".to_string()), "method".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_definitions_split_on_lines() {
+        let body: String = (0..20)
+            .map(|i| format!("    let v{} = {};\n", i, i))
+            .collect();
+        let source = format!("fn long() {{\n{}}}\n", body);
+        let config = CodeConfig {
+            language: Some(CodeLanguage::Rust),
+            max_chunk_chars: 200,
+        };
+        let (_, chunks) = CodeExtractor::chunk_source(&source, &config).unwrap();
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.symbol.as_deref() == Some("long")));
+        assert!(chunks.iter().all(|c| c.text.len() <= 200));
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks.last().unwrap().end_line, 22);
+    }
+
+    #[test]
+    fn test_language_lookup() {
+        assert_eq!(
+            CodeLanguage::from_extension(".rs"),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_extension("TSX"),
+            Some(CodeLanguage::TypeScript)
+        );
+        assert_eq!(CodeLanguage::from_extension("c"), None);
+        assert_eq!(
+            CodeLanguage::from_mime_type("text/x-script.python"),
+            Some(CodeLanguage::Python)
+        );
+        assert_eq!(receiver_type("(l *List[T])").as_deref(), Some("List"));
+    }
+
+    #[tokio::test]
+    async fn test_code_extractor_empty_content() {
+        let extractor = CodeExtractor::new();
+        assert!(extractor.extract(b"  \n\n").await.is_err());
+        assert!(extractor.extract(&[0xff, 0xfe]).await.is_err());
+    }
+
+    #[test]
+    fn test_code_extractor_creation() {
+        let extractor = CodeExtractor::new();
+        assert_eq!(extractor.name(), "tree-sitter");
+        assert_eq!(extractor.config().max_chunk_chars, 4000);
+        assert!(extractor.supports("text/x-rust"));
+        assert!(extractor.supports("application/typescript"));
+        assert!(!extractor.supports("application/pdf"));
+
+        let go = CodeExtractor::for_language(CodeLanguage::Go);
+        assert!(go.supports("text/x-go"));
+        assert!(!go.supports("text/x-rust"));
+    }
+}
@@ -0,0 +1,459 @@
+//! Email content extraction using mailparse.
+//!
+//! Handles single messages (`.eml`) and mailboxes (`.mbox`). Each message
+//! is rendered as its main headers followed by the plain-text body, with
+//! quoted replies and signatures removed so a thread doesn't repeat itself.
+//! Message and thread IDs are returned in the `messages` metadata entry so
+//! ingested memories can be traced back to their thread.
+
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use chrono::DateTime;
+use mailparse::{DispositionType, MailHeaderMap, ParsedMail};
+use serde::Serialize;
+
+/// MIME type of mailboxes.
+const MBOX_MIME_TYPE: &str = "application/mbox";
+
+/// Email content extractor using the mailparse library.
+///
+/// Returns one message per `structure.pages` entry and subjects as
+/// `structure.sections`.
+#[derive(Debug, Clone, Default)]
+pub struct EmailExtractor {
+    /// Whether to remove quoted replies.
+    strip_quotes: bool,
+    /// Whether to remove signatures.
+    strip_signatures: bool,
+}
+
+/// Headers of an extracted message, returned in metadata.
+#[derive(Debug, Default, Serialize)]
+struct EmailMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_id: Option<String>,
+    /// Root message of the thread: the first `References` entry, else
+    /// `In-Reply-To`, else the message itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_reply_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cc: Option<String>,
+    /// Sent date as RFC 3339, or as written if it couldn't be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(skip)]
+    body: String,
+}
+
+impl EmailMessage {
+    /// Headers and body as text.
+    fn render(&self) -> String {
+        let mut text = String::new();
+        for (name, value) in [
+            ("Subject", &self.subject),
+            ("From", &self.from),
+            ("To", &self.to),
+            ("Cc", &self.cc),
+            ("Date", &self.date),
+        ] {
+            if let Some(value) = value {
+                text.push_str(&format!("{}: {}\n", name, value));
+            }
+        }
+        if !self.body.is_empty() {
+            text.push('\n');
+            text.push_str(&self.body);
+        }
+        text.trim().to_string()
+    }
+}
+
+impl EmailExtractor {
+    /// Create new email extractor with default settings.
+    pub fn new() -> Self {
+        Self {
+            strip_quotes: true,
+            strip_signatures: true,
+        }
+    }
+
+    /// Configure whether to remove quoted replies.
+    pub fn with_quote_stripping(mut self, strip: bool) -> Self {
+        self.strip_quotes = strip;
+        self
+    }
+
+    /// Configure whether to remove signatures.
+    pub fn with_signature_stripping(mut self, strip: bool) -> Self {
+        self.strip_signatures = strip;
+        self
+    }
+
+    /// Extract messages synchronously (called within spawn_blocking).
+    fn extract_sync(
+        content: Vec<u8>,
+        strip_quotes: bool,
+        strip_signatures: bool,
+    ) -> ExtractResult<Vec<EmailMessage>> {
+        let raw_messages = if is_mbox(&content) {
+            split_mbox(&content)
+        } else {
+            vec![content]
+        };
+
+        raw_messages
+            .iter()
+            .map(|raw| {
+                let mail = mailparse::parse_mail(raw).map_err(|e| {
+                    ExtractError::ExtractionFailed(format!("Failed to parse email: {}", e))
+                })?;
+                let mut message = parse_headers(&mail);
+                let mut body = body_text(&mail).unwrap_or_default();
+                if strip_quotes {
+                    body = remove_quotes(&body);
+                }
+                if strip_signatures {
+                    body = remove_signature(&body);
+                }
+                message.body = body.trim().to_string();
+                Ok(message)
+            })
+            .collect()
+    }
+}
+
+/// Whether content is a mailbox: messages each starting with a `From `
+/// separator line.
+fn is_mbox(content: &[u8]) -> bool {
+    content.starts_with(b"From ")
+}
+
+/// Split a mailbox into raw messages, unescaping `>From ` body lines.
+fn split_mbox(content: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut current: Option<Vec<u8>> = None;
+    let mut previous_blank = true;
+
+    for line in content.split_inclusive(|b| *b == b'\n') {
+        if previous_blank && line.starts_with(b"From ") {
+            messages.extend(current.take());
+            current = Some(Vec::new());
+        } else if let Some(message) = current.as_mut() {
+            let unescaped = line
+                .strip_prefix(b">")
+                .filter(|rest| trim_quote_markers(rest).starts_with(b"From "));
+            message.extend_from_slice(unescaped.unwrap_or(line));
+        }
+        previous_blank = line.iter().all(u8::is_ascii_whitespace);
+    }
+    messages.extend(current);
+    messages
+}
+
+/// Strip leading `>`, as `>From ` escapes may be nested (`>>From `).
+fn trim_quote_markers(line: &[u8]) -> &[u8] {
+    let start = line.iter().take_while(|b| **b == b'>').count();
+    &line[start..]
+}
+
+fn parse_headers(mail: &ParsedMail<'_>) -> EmailMessage {
+    let headers = &mail.headers;
+    let header = |name: &str| {
+        headers
+            .get_first_value(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let first_id = |name: &str| {
+        header(name)
+            .and_then(|v| mailparse::msgidparse(&v).ok())
+            .and_then(|ids| ids.first().cloned())
+    };
+
+    let message_id = first_id("Message-ID");
+    let in_reply_to = first_id("In-Reply-To");
+    let thread_id = first_id("References")
+        .or_else(|| in_reply_to.clone())
+        .or_else(|| message_id.clone());
+    let date = header("Date").map(|date| {
+        mailparse::dateparse(&date)
+            .ok()
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .map(|ts| ts.to_rfc3339())
+            .unwrap_or(date)
+    });
+
+    EmailMessage {
+        message_id,
+        thread_id,
+        in_reply_to,
+        from: header("From"),
+        to: header("To"),
+        cc: header("Cc"),
+        date,
+        subject: header("Subject"),
+        body: String::new(),
+    }
+}
+
+/// Text of the message body: the first plain-text part, else the first
+/// HTML part with tags removed. Attachments are skipped.
+fn body_text(mail: &ParsedMail<'_>) -> Option<String> {
+    let parts: Vec<&ParsedMail<'_>> = mail
+        .parts()
+        .filter(|part| part.subparts.is_empty())
+        .filter(|part| part.get_content_disposition().disposition != DispositionType::Attachment)
+        .collect();
+
+    let plain = parts.iter().find(|p| p.ctype.mimetype == "text/plain");
+    if let Some(body) = plain.and_then(|p| p.get_body().ok()) {
+        return Some(body);
+    }
+    parts
+        .iter()
+        .find(|p| p.ctype.mimetype == "text/html")
+        .and_then(|p| p.get_body().ok())
+        .map(|html| strip_tags(&html))
+}
+
+/// Crude HTML-to-text for HTML-only mail bodies.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut tag = String::new();
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                let name = tag
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                if matches!(name.as_str(), "br" | "p" | "div" | "li" | "tr") {
+                    text.push('\n');
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Whether a line introduces quoted text, e.g. "On Mon, Ann wrote:" or
+/// Outlook's "-----Original Message-----".
+fn is_quote_header(line: &str) -> bool {
+    let line = line.trim();
+    (line.starts_with("On ") && line.ends_with("wrote:"))
+        || (line.starts_with("-----") && line.to_lowercase().contains("original message"))
+        || (line.starts_with("-----") && line.to_lowercase().contains("forwarded message"))
+}
+
+/// Remove quoted replies: `>` lines and everything from a quote header on.
+fn remove_quotes(body: &str) -> String {
+    let mut kept = Vec::new();
+    for line in body.lines() {
+        if is_quote_header(line) {
+            break;
+        }
+        if !line.trim_start().starts_with('>') {
+            kept.push(line);
+        }
+    }
+    kept.join("\n")
+}
+
+/// Remove the signature: everything from the `-- ` delimiter, or a trailing
+/// "Sent from my ..." line.
+fn remove_signature(body: &str) -> String {
+    let mut kept = Vec::new();
+    for line in body.lines() {
+        if line == "-- " || line == "--" {
+            break;
+        }
+        kept.push(line);
+    }
+    while kept.last().is_some_and(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with("Sent from my ")
+    }) {
+        kept.pop();
+    }
+    kept.join("\n")
+}
+
+#[async_trait]
+impl Extractor for EmailExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let content = content.to_vec();
+        let content_len = content.len();
+        let strip_quotes = self.strip_quotes;
+        let strip_signatures = self.strip_signatures;
+
+        // Mailboxes can be large, so parse in a blocking task
+        let messages = tokio::task::spawn_blocking(move || {
+            Self::extract_sync(content, strip_quotes, strip_signatures)
+        })
+        .await??;
+
+        let pages: Vec<String> = messages.iter().map(EmailMessage::render).collect();
+        let text = pages
+            .iter()
+            .filter(|page| !page.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        // Check for empty extraction
+        if text.trim().is_empty() {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let structure = DocumentStructure {
+            page_count: Some(messages.len()),
+            sections: messages.iter().filter_map(|m| m.subject.clone()).collect(),
+            pages,
+        };
+        let metadata = serde_json::to_value(&messages).map_err(|e| {
+            ExtractError::ExtractionFailed(format!("Failed to serialize headers: {}", e))
+        })?;
+
+        Ok(
+            ExtractedContent::new(text, Modality::Email, ContentSource::Bytes)
+                .with_structure(structure)
+                .with_metadata("original_size", content_len)
+                .with_metadata("message_count", messages.len())
+                .with_metadata("messages", metadata),
+        )
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &["message/rfc822", MBOX_MIME_TYPE]
+    }
+
+    fn name(&self) -> &str {
+        "mailparse"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "Message-ID: <reply-1@example.com>\r
+In-Reply-To: <root@example.com>\r
+References: <root@example.com> <second@example.com>\r
+From: Ann <ann@example.com>\r
+To: Bob <bob@example.com>\r
+Date: Tue, 5 Mar 2024 09:30:00 +0100\r
+Subject: Re: Offsite dates\r
+\r
+The 14th works for me.\r
+\r
+On Mon, 4 Mar 2024, Bob wrote:\r
+> Does the 14th or 21st suit you?\r
+\r
+-- \r
+Ann\r
+Head of Ops\r
+";
+
+    #[tokio::test]
+    async fn test_email_headers_and_stripping() {
+        let extractor = EmailExtractor::new();
+        let result = extractor.extract(REPLY.as_bytes()).await.unwrap();
+
+        assert_eq!(result.modality, Modality::Email);
+        assert!(result.text.starts_with("Subject: Re: Offsite dates\nFrom: Ann <ann@example.com>"));
+        assert!(result.text.contains("Date: 2024-03-05T08:30:00+00:00"));
+        assert!(result.text.ends_with("The 14th works for me."));
+
+        let message = &result.metadata["messages"][0];
+        assert_eq!(message["message_id"], "reply-1@example.com");
+        assert_eq!(message["in_reply_to"], "root@example.com");
+        assert_eq!(message["thread_id"], "root@example.com");
+        assert_eq!(result.metadata["message_count"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_email_without_stripping() {
+        let extractor = EmailExtractor::new()
+            .with_quote_stripping(false)
+            .with_signature_stripping(false);
+        let result = extractor.extract(REPLY.as_bytes()).await.unwrap();
+        assert!(result.text.contains("> Does the 14th"));
+        assert!(result.text.contains("Head of Ops"));
+    }
+
+    #[tokio::test]
+    async fn test_mbox_messages() {
+        let mbox = "From ann@example.com Mon Mar  4 10:00:00 2024
+Message-ID: <root@example.com>
+Subject: Offsite dates
+Content-Type: multipart/alternative; boundary=\"b\"
+
+--b
+Content-Type: text/html
+
+<p>Does the 14th or 21st suit you?</p>
+--b--
+
+From bob@example.com Tue Mar  5 09:30:00 2024
+Message-ID: <reply@example.com>
+In-Reply-To: <root@example.com>
+Subject: Re: Offsite dates
+
+>From what I hear, the 14th.
+
+Sent from my phone
+";
+        let result = EmailExtractor::new().extract(mbox.as_bytes()).await.unwrap();
+
+        let structure = result.structure.unwrap();
+        assert_eq!(structure.page_count, Some(2));
+        assert_eq!(structure.sections, vec!["Offsite dates", "Re: Offsite dates"]);
+        assert!(structure.pages[0].ends_with("Does the 14th or 21st suit you?"));
+        assert!(structure.pages[1].ends_with("From what I hear, the 14th."));
+
+        let messages = &result.metadata["messages"];
+        assert_eq!(messages[0]["thread_id"], "root@example.com");
+        assert_eq!(messages[1]["thread_id"], "root@example.com");
+        assert_eq!(messages[1]["message_id"], "reply@example.com");
+    }
+
+    #[tokio::test]
+    async fn test_email_extractor_empty_content() {
+        let extractor = EmailExtractor::new();
+        assert!(extractor.extract(&[]).await.is_err());
+    }
+
+    #[test]
+    fn test_email_extractor_creation() {
+        let extractor = EmailExtractor::new();
+        assert_eq!(extractor.name(), "mailparse");
+        assert!(extractor.supports("message/rfc822"));
+        assert!(extractor.supports("application/mbox"));
+        assert!(!extractor.supports("text/plain"));
+    }
+}
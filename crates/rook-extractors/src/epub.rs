@@ -0,0 +1,357 @@
+//! EPUB content extraction.
+//!
+//! Reads the book's package document for its metadata and reading order,
+//! then renders each chapter in the spine as text. Chapters become entries
+//! of `structure.pages` and their titles, taken from the table of contents
+//! or the chapter's first heading, entries of `structure.sections`.
+
+use std::collections::HashMap;
+
+use crate::archive::{parse_xml, resolve, Archive};
+use crate::error::{ExtractError, ExtractResult};
+use crate::html::render_document;
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use roxmltree::Node;
+
+/// Media types of spine items rendered as chapters.
+const CHAPTER_TYPES: &[&str] = &["application/xhtml+xml", "text/html"];
+
+/// EPUB content extractor.
+///
+/// Extracts chapter text in reading order, skipping spine items marked as
+/// non-linear (footnote pages, answer keys) and chapters without text
+/// (covers, image plates).
+#[derive(Debug, Clone, Default)]
+pub struct EpubExtractor;
+
+/// A book read from an EPUB archive.
+#[derive(Debug, Default)]
+struct Book {
+    title: Option<String>,
+    author: Option<String>,
+    language: Option<String>,
+    chapters: Vec<Chapter>,
+}
+
+#[derive(Debug)]
+struct Chapter {
+    title: String,
+    text: String,
+}
+
+/// A package document item.
+struct ManifestItem {
+    path: String,
+    media_type: String,
+    properties: String,
+}
+
+impl EpubExtractor {
+    /// Create new EPUB extractor.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read the book synchronously (called within spawn_blocking).
+    fn extract_sync(content: Vec<u8>) -> ExtractResult<Book> {
+        let mut archive = Archive::open(content, "EPUB").map_err(ExtractError::Epub)?;
+
+        let container = archive
+            .read("META-INF/container.xml")
+            .ok_or_else(|| ExtractError::Epub("Missing META-INF/container.xml".to_string()))?;
+        let container = parse_xml(&container).map_err(ExtractError::Epub)?;
+        let package_path = container
+            .descendants()
+            .find(|n| n.has_tag_name("rootfile"))
+            .and_then(|n| n.attribute("full-path"))
+            .ok_or_else(|| ExtractError::Epub("No package document in container".to_string()))?
+            .to_string();
+
+        let package = archive
+            .read(&package_path)
+            .ok_or_else(|| ExtractError::Epub(format!("Missing {}", package_path)))?;
+        let package = parse_xml(&package).map_err(ExtractError::Epub)?;
+
+        let mut book = Book {
+            title: metadata_text(&package, "title"),
+            author: metadata_text(&package, "creator"),
+            language: metadata_text(&package, "language"),
+            chapters: Vec::new(),
+        };
+
+        let manifest: HashMap<&str, ManifestItem> = package
+            .descendants()
+            .filter(|n| n.has_tag_name("item"))
+            .filter_map(|n| {
+                let item = ManifestItem {
+                    path: resolve(&package_path, n.attribute("href")?),
+                    media_type: n.attribute("media-type").unwrap_or_default().to_string(),
+                    properties: n.attribute("properties").unwrap_or_default().to_string(),
+                };
+                Some((n.attribute("id")?, item))
+            })
+            .collect();
+
+        let toc = table_of_contents(&mut archive, &package, &manifest);
+
+        let spine = package
+            .descendants()
+            .find(|n| n.has_tag_name("spine"))
+            .ok_or_else(|| ExtractError::Epub("Package document has no spine".to_string()))?;
+        let chapter_paths = spine
+            .children()
+            .filter(|n| n.has_tag_name("itemref") && n.attribute("linear") != Some("no"))
+            .filter_map(|n| manifest.get(n.attribute("idref")?))
+            .filter(|item| CHAPTER_TYPES.contains(&item.media_type.as_str()))
+            .map(|item| item.path.clone());
+
+        for path in chapter_paths {
+            let Some(html) = archive.read(&path) else {
+                continue;
+            };
+            let (text, headings, html_title) = render_document(&html);
+            if text.trim().is_empty() {
+                continue;
+            }
+            let title = toc
+                .get(&path)
+                .cloned()
+                .or_else(|| headings.into_iter().next())
+                .or(html_title)
+                .unwrap_or_else(|| format!("Chapter {}", book.chapters.len() + 1));
+            book.chapters.push(Chapter { title, text });
+        }
+
+        Ok(book)
+    }
+}
+
+/// Text of the first Dublin Core element named `name` in the package
+/// metadata.
+fn metadata_text(package: &roxmltree::Document, name: &str) -> Option<String> {
+    package
+        .descendants()
+        .filter(|n| n.tag_name().name() == name)
+        .filter_map(|n| n.text())
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|t| !t.is_empty())
+}
+
+/// Chapter titles by archive path, from the EPUB 3 navigation document or
+/// the EPUB 2 NCX. Empty if the book has neither or it can't be parsed.
+fn table_of_contents(
+    archive: &mut Archive,
+    package: &roxmltree::Document,
+    manifest: &HashMap<&str, ManifestItem>,
+) -> HashMap<String, String> {
+    let nav = manifest
+        .values()
+        .find(|item| item.properties.split_whitespace().any(|p| p == "nav"));
+    let ncx = package
+        .descendants()
+        .find(|n| n.has_tag_name("spine"))
+        .and_then(|spine| spine.attribute("toc"))
+        .and_then(|id| manifest.get(id));
+
+    let mut titles = HashMap::new();
+    let Some(item) = nav.or(ncx) else {
+        return titles;
+    };
+    let Some(xml) = archive.read(&item.path) else {
+        return titles;
+    };
+    let Ok(document) = parse_xml(&xml) else {
+        return titles;
+    };
+
+    // Navigation documents list chapters as links, NCX files as navPoints
+    let entries = document.descendants().filter_map(|n| {
+        if n.has_tag_name("a") {
+            Some((n.attribute("href")?, node_text(n)))
+        } else if n.has_tag_name("navPoint") {
+            let src = n
+                .children()
+                .find(|c| c.has_tag_name("content"))?
+                .attribute("src")?;
+            let label = n.children().find(|c| c.has_tag_name("navLabel"))?;
+            Some((src, node_text(label)))
+        } else {
+            None
+        }
+    });
+    for (href, title) in entries {
+        if !title.is_empty() {
+            // The first entry for a file names the chapter; later ones
+            // point at sections within it
+            titles.entry(resolve(&item.path, href)).or_insert(title);
+        }
+    }
+    titles
+}
+
+/// Collapsed text of an element and its descendants.
+fn node_text(node: Node) -> String {
+    let text: Vec<&str> = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    text.join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[async_trait]
+impl Extractor for EpubExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let content = content.to_vec();
+        let content_len = content.len();
+
+        // Decompressing and rendering chapters is CPU-bound, so run it in a
+        // blocking task
+        let book = tokio::task::spawn_blocking(move || Self::extract_sync(content)).await??;
+
+        // Check for empty extraction
+        if book.chapters.is_empty() {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let pages: Vec<String> = book.chapters.iter().map(|c| c.text.clone()).collect();
+        let structure = DocumentStructure {
+            page_count: Some(pages.len()),
+            sections: book.chapters.iter().map(|c| c.title.clone()).collect(),
+            pages,
+        };
+        let text = book
+            .chapters
+            .iter()
+            .map(|c| c.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let mut result = ExtractedContent::new(text, Modality::Epub, ContentSource::Bytes)
+            .with_structure(structure)
+            .with_metadata("original_size", content_len);
+        for (key, value) in [
+            ("title", book.title),
+            ("author", book.author),
+            ("language", book.language),
+        ] {
+            if let Some(value) = value {
+                result = result.with_metadata(key, value);
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &["application/epub+zip"]
+    }
+
+    fn name(&self) -> &str {
+        "epub"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::build_zip as zip;
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+
+    const PACKAGE: &str = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:title>The Orchard</dc:title>
+    <dc:creator>A. Grower</dc:creator>
+    <dc:language>en</dc:language>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="cover" href="cover.xhtml" media-type="application/xhtml+xml"/>
+    <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+    <item id="ch2" href="text/ch2.xhtml" media-type="application/xhtml+xml"/>
+    <item id="notes" href="text/notes.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="cover"/>
+    <itemref idref="ch1"/>
+    <itemref idref="ch2"/>
+    <itemref idref="notes" linear="no"/>
+  </spine>
+</package>"#;
+
+    const NAV: &str = r#"<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+<body><nav epub:type="toc"><ol>
+  <li><a href="text/ch1.xhtml">1. Planting</a></li>
+  <li><a href="text/ch1.xhtml#spacing">Spacing</a></li>
+</ol></nav></body></html>"#;
+
+    fn book() -> Vec<u8> {
+        zip(&[
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", CONTAINER),
+            ("OEBPS/content.opf", PACKAGE),
+            ("OEBPS/nav.xhtml", NAV),
+            ("OEBPS/cover.xhtml", "<html><body><img src=\"cover.jpg\"/></body></html>"),
+            (
+                "OEBPS/text/ch1.xhtml",
+                "<html><body><h1>Planting</h1><p>Dig a wide hole.</p>\
+                 <h2 id=\"spacing\">Spacing</h2><p>Leave room to grow.</p></body></html>",
+            ),
+            (
+                "OEBPS/text/ch2.xhtml",
+                "<html><head><title>Pruning</title></head><body><p>Cut in winter.</p></body></html>",
+            ),
+            ("OEBPS/text/notes.xhtml", "<html><body><p>Footnotes.</p></body></html>"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_epub_chapters() {
+        let result = EpubExtractor::new().extract(&book()).await.unwrap();
+        assert_eq!(result.modality, Modality::Epub);
+
+        let structure = result.structure.unwrap();
+        assert_eq!(structure.page_count, Some(2));
+        assert_eq!(structure.sections, vec!["1. Planting", "Pruning"]);
+        assert_eq!(
+            structure.pages[0],
+            "# Planting\n\nDig a wide hole.\n\n## Spacing\n\nLeave room to grow."
+        );
+        assert_eq!(structure.pages[1], "Cut in winter.");
+        assert!(!result.text.contains("Footnotes"));
+
+        assert_eq!(result.metadata["title"], "The Orchard");
+        assert_eq!(result.metadata["author"], "A. Grower");
+        assert_eq!(result.metadata["language"], "en");
+    }
+
+    #[tokio::test]
+    async fn test_epub_extractor_invalid_content() {
+        let extractor = EpubExtractor::new();
+        assert!(extractor.extract(b"not a zip").await.is_err());
+        assert!(extractor
+            .extract(&zip(&[("mimetype", "application/epub+zip")]))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_epub_extractor_creation() {
+        let extractor = EpubExtractor::new();
+        assert_eq!(extractor.name(), "epub");
+        assert!(extractor.supports("application/epub+zip"));
+        assert!(!extractor.supports("application/zip"));
+    }
+}
@@ -31,6 +31,16 @@ pub enum ExtractError {
     #[error("DOCX extraction error: {0}")]
     Docx(String),
 
+    /// EPUB-specific extraction error.
+    #[cfg(feature = "epub")]
+    #[error("EPUB extraction error: {0}")]
+    Epub(String),
+
+    /// PPTX-specific extraction error.
+    #[cfg(feature = "pptx")]
+    #[error("PPTX extraction error: {0}")]
+    Pptx(String),
+
     /// Image processing error.
     #[cfg(feature = "image")]
     #[error("Image processing error: {0}")]
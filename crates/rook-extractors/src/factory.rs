@@ -11,6 +11,27 @@ use crate::PdfExtractor;
 #[cfg(feature = "docx")]
 use crate::DocxExtractor;
 
+#[cfg(feature = "html")]
+use crate::HtmlExtractor;
+
+#[cfg(feature = "epub")]
+use crate::EpubExtractor;
+
+#[cfg(feature = "pptx")]
+use crate::PptxExtractor;
+
+#[cfg(feature = "markdown")]
+use crate::MarkdownExtractor;
+
+#[cfg(feature = "email")]
+use crate::EmailExtractor;
+
+#[cfg(feature = "spreadsheet")]
+use crate::{SpreadsheetConfig, SpreadsheetExtractor};
+
+#[cfg(feature = "code")]
+use crate::{CodeConfig, CodeExtractor, CodeLanguage};
+
 #[cfg(feature = "image")]
 use crate::image::{ImageExtractionConfig, ImageExtractor};
 
@@ -49,6 +70,72 @@ impl ExtractorFactory {
         )
     }
 
+    /// Create an HTML extractor.
+    #[cfg(feature = "html")]
+    pub fn html() -> Arc<dyn Extractor> {
+        Arc::new(HtmlExtractor::new())
+    }
+
+    /// Create an EPUB extractor.
+    #[cfg(feature = "epub")]
+    pub fn epub() -> Arc<dyn Extractor> {
+        Arc::new(EpubExtractor::new())
+    }
+
+    /// Create a PPTX extractor.
+    #[cfg(feature = "pptx")]
+    pub fn pptx() -> Arc<dyn Extractor> {
+        Arc::new(PptxExtractor::new())
+    }
+
+    /// Create a PPTX extractor, optionally without speaker notes.
+    #[cfg(feature = "pptx")]
+    pub fn pptx_configured(include_notes: bool) -> Arc<dyn Extractor> {
+        Arc::new(PptxExtractor::new().with_notes(include_notes))
+    }
+
+    /// Create a Markdown extractor.
+    #[cfg(feature = "markdown")]
+    pub fn markdown() -> Arc<dyn Extractor> {
+        Arc::new(MarkdownExtractor::new())
+    }
+
+    /// Create an email extractor.
+    #[cfg(feature = "email")]
+    pub fn email() -> Arc<dyn Extractor> {
+        Arc::new(EmailExtractor::new())
+    }
+
+    /// Create a spreadsheet extractor.
+    #[cfg(feature = "spreadsheet")]
+    pub fn spreadsheet() -> Arc<dyn Extractor> {
+        Arc::new(SpreadsheetExtractor::new())
+    }
+
+    /// Create a spreadsheet extractor with custom configuration.
+    #[cfg(feature = "spreadsheet")]
+    pub fn spreadsheet_with_config(config: SpreadsheetConfig) -> Arc<dyn Extractor> {
+        Arc::new(SpreadsheetExtractor::with_config(config))
+    }
+
+    /// Create a source code extractor that detects the language.
+    #[cfg(feature = "code")]
+    pub fn code() -> Arc<dyn Extractor> {
+        Arc::new(CodeExtractor::new())
+    }
+
+    /// Create a source code extractor for a single language.
+    #[cfg(feature = "code")]
+    pub fn code_for_language(language: CodeLanguage) -> Arc<dyn Extractor> {
+        Arc::new(CodeExtractor::for_language(language))
+    }
+
+    /// Create a source code extractor with custom configuration.
+    #[cfg(feature = "code")]
+    pub fn code_with_config(config: CodeConfig) -> Arc<dyn Extractor> {
+        Arc::new(CodeExtractor::with_config(config))
+    }
+
     /// Create an image extractor (OCR + Vision combined).
     #[cfg(feature = "image")]
     pub fn image() -> Arc<dyn Extractor> {
@@ -75,6 +162,11 @@ impl ExtractorFactory {
 
     /// Create extractor for a given MIME type.
     pub fn for_mime_type(mime_type: &str) -> ExtractResult<Arc<dyn Extractor>> {
+        #[cfg(feature = "code")]
+        if let Some(language) = CodeLanguage::from_mime_type(mime_type) {
+            return Ok(Self::code_for_language(language));
+        }
+
         match mime_type {
             #[cfg(feature = "pdf")]
             "application/pdf" => Ok(Self::pdf()),
@@ -83,6 +175,29 @@ impl ExtractorFactory {
             "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
             | "application/docx" => Ok(Self::docx()),
 
+            #[cfg(feature = "html")]
+            "text/html" | "application/xhtml+xml" => Ok(Self::html()),
+
+            #[cfg(feature = "epub")]
+            "application/epub+zip" => Ok(Self::epub()),
+
+            #[cfg(feature = "pptx")]
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+            | "application/pptx" => Ok(Self::pptx()),
+
+            #[cfg(feature = "markdown")]
+            "text/markdown" | "text/x-markdown" => Ok(Self::markdown()),
+
+            #[cfg(feature = "email")]
+            "message/rfc822" | "application/mbox" => Ok(Self::email()),
+
+            #[cfg(feature = "spreadsheet")]
+            "text/csv"
+            | "text/tab-separated-values"
+            | "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            | "application/vnd.ms-excel"
+            | "application/vnd.oasis.opendocument.spreadsheet" => Ok(Self::spreadsheet()),
+
             #[cfg(feature = "image")]
             "image/png" | "image/jpeg" | "image/gif" | "image/webp" => Ok(Self::image()),
 
@@ -101,6 +216,27 @@ impl ExtractorFactory {
         #[cfg(feature = "docx")]
         extractors.push(Self::docx());
 
+        #[cfg(feature = "html")]
+        extractors.push(Self::html());
+
+        #[cfg(feature = "epub")]
+        extractors.push(Self::epub());
+
+        #[cfg(feature = "pptx")]
+        extractors.push(Self::pptx());
+
+        #[cfg(feature = "markdown")]
+        extractors.push(Self::markdown());
+
+        #[cfg(feature = "email")]
+        extractors.push(Self::email());
+
+        #[cfg(feature = "spreadsheet")]
+        extractors.push(Self::spreadsheet());
+
+        #[cfg(feature = "code")]
+        extractors.push(Self::code());
+
         #[cfg(feature = "image")]
         extractors.push(Self::image());
 
@@ -116,17 +252,17 @@ mod tests {
     fn test_factory_all_extractors() {
         let extractors = ExtractorFactory::all();
 
-        #[cfg(all(feature = "pdf", feature = "docx", not(feature = "image")))]
-        assert_eq!(extractors.len(), 2);
-
-        #[cfg(all(feature = "pdf", feature = "docx", feature = "image"))]
-        assert_eq!(extractors.len(), 3);
-
-        #[cfg(all(feature = "pdf", not(feature = "docx"), not(feature = "image")))]
-        assert_eq!(extractors.len(), 1);
-
-        #[cfg(all(feature = "docx", not(feature = "pdf"), not(feature = "image")))]
-        assert_eq!(extractors.len(), 1);
+        let expected = cfg!(feature = "pdf") as usize
+            + cfg!(feature = "docx") as usize
+            + cfg!(feature = "html") as usize
+            + cfg!(feature = "epub") as usize
+            + cfg!(feature = "pptx") as usize
+            + cfg!(feature = "markdown") as usize
+            + cfg!(feature = "email") as usize
+            + cfg!(feature = "spreadsheet") as usize
+            + cfg!(feature = "code") as usize
+            + cfg!(feature = "image") as usize;
+        assert_eq!(extractors.len(), expected);
     }
 
     #[cfg(feature = "docx")]
@@ -145,6 +281,54 @@ mod tests {
         assert!(extractor.is_ok());
     }
 
+    #[cfg(all(feature = "html", feature = "markdown"))]
+    #[test]
+    fn test_factory_for_mime_type_html_markdown() {
+        let html = ExtractorFactory::for_mime_type("text/html").unwrap();
+        assert_eq!(html.name(), "scraper");
+
+        let markdown = ExtractorFactory::for_mime_type("text/markdown").unwrap();
+        assert_eq!(markdown.name(), "markdown");
+    }
+
+    #[cfg(all(feature = "epub", feature = "pptx"))]
+    #[test]
+    fn test_factory_for_mime_type_epub_pptx() {
+        let epub = ExtractorFactory::for_mime_type("application/epub+zip").unwrap();
+        assert_eq!(epub.name(), "epub");
+
+        let pptx = ExtractorFactory::for_mime_type(
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        )
+        .unwrap();
+        assert_eq!(pptx.name(), "pptx");
+    }
+
+    #[cfg(feature = "email")]
+    #[test]
+    fn test_factory_for_mime_type_email() {
+        let eml = ExtractorFactory::for_mime_type("message/rfc822").unwrap();
+        assert_eq!(eml.name(), "mailparse");
+        assert!(eml.supports("application/mbox"));
+    }
+
+    #[cfg(feature = "spreadsheet")]
+    #[test]
+    fn test_factory_for_mime_type_spreadsheet() {
+        let csv = ExtractorFactory::for_mime_type("text/csv").unwrap();
+        assert_eq!(csv.name(), "calamine");
+        assert!(csv.supports("application/vnd.ms-excel"));
+    }
+
+    #[cfg(feature = "code")]
+    #[test]
+    fn test_factory_for_mime_type_code() {
+        let rust = ExtractorFactory::for_mime_type("text/x-rust").unwrap();
+        assert_eq!(rust.name(), "tree-sitter");
+        assert!(rust.supports("text/x-rust"));
+        assert!(!rust.supports("text/x-python"));
+    }
+
     #[cfg(feature = "image")]
     #[test]
     fn test_factory_image() {
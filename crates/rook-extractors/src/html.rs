@@ -0,0 +1,468 @@
+//! HTML content extraction using scraper.
+//!
+//! Extracts the main content of web pages, readability-style: the page's
+//! `<article>` or `<main>` if it has one, otherwise the element holding the
+//! most paragraph text. Navigation, sidebars, scripts and similar
+//! boilerplate are dropped. Headings are kept as Markdown-style `#` lines
+//! and links as `[text](url)`.
+
+use std::collections::HashMap;
+
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use scraper::{ElementRef, Html, Node, Selector};
+
+/// Elements whose content is never part of the main text.
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "canvas", "iframe", "form", "button",
+    "select", "nav", "header", "footer", "aside", "head",
+];
+
+/// Elements that start a new block of text.
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "section", "article", "main", "blockquote", "ul", "ol", "dl", "dt", "dd",
+    "table", "tr", "figure", "figcaption", "hr", "address", "details", "summary", "body",
+];
+
+/// Class and id fragments marking boilerplate containers.
+const BOILERPLATE_HINTS: &[&str] = &[
+    "comment", "sidebar", "footer", "navbar", "menu", "share", "social", "promo", "advert",
+    "related", "cookie", "newsletter", "breadcrumb", "popup",
+];
+
+/// Minimum text length for `<article>`/`<main>` to count as the main content.
+const MIN_MAIN_TEXT: usize = 140;
+
+/// Minimum length for a paragraph to count when scoring candidates.
+const MIN_PARAGRAPH_TEXT: usize = 25;
+
+/// HTML content extractor using the scraper library.
+///
+/// Picks the main content of a page and renders it as plain text with
+/// Markdown-style headings and links. The page title and description are
+/// returned as metadata.
+#[derive(Debug, Clone, Default)]
+pub struct HtmlExtractor {
+    /// Whether to keep link targets as `[text](url)`.
+    preserve_links: bool,
+    /// Whether to extract headings as sections.
+    extract_headings: bool,
+}
+
+impl HtmlExtractor {
+    /// Create new HTML extractor with default settings.
+    pub fn new() -> Self {
+        Self {
+            preserve_links: true,
+            extract_headings: true,
+        }
+    }
+
+    /// Configure whether to keep link targets.
+    pub fn with_links(mut self, preserve: bool) -> Self {
+        self.preserve_links = preserve;
+        self
+    }
+
+    /// Configure whether to extract headings as sections.
+    pub fn with_headings(mut self, extract: bool) -> Self {
+        self.extract_headings = extract;
+        self
+    }
+
+    /// Extract text synchronously (called within spawn_blocking).
+    fn extract_sync(
+        content: Vec<u8>,
+        preserve_links: bool,
+        extract_headings: bool,
+    ) -> (String, Vec<String>, HashMap<String, String>) {
+        let html = String::from_utf8_lossy(&content);
+        let document = Html::parse_document(&html);
+
+        let mut metadata = HashMap::new();
+        if let Some(title) = first_text(&document, "title") {
+            metadata.insert("title".to_string(), title);
+        }
+        if let Some(description) = meta_content(&document) {
+            metadata.insert("description".to_string(), description);
+        }
+
+        let mut renderer = Renderer {
+            preserve_links,
+            extract_headings,
+            blocks: Vec::new(),
+            current: String::new(),
+            headings: Vec::new(),
+        };
+        if let Some(root) = main_content(&document) {
+            renderer.render(root);
+        }
+        renderer.flush();
+
+        (renderer.blocks.join("\n\n"), renderer.headings, metadata)
+    }
+}
+
+/// Render a whole document body, skipping main-content detection, as text
+/// with its headings and `<title>`.
+///
+/// EPUB chapters have no navigation or sidebars to drop, and picking one
+/// container would lose the rest of the chapter.
+#[cfg(feature = "epub")]
+pub(crate) fn render_document(html: &str) -> (String, Vec<String>, Option<String>) {
+    let document = Html::parse_document(html);
+    let mut renderer = Renderer {
+        preserve_links: true,
+        extract_headings: true,
+        blocks: Vec::new(),
+        current: String::new(),
+        headings: Vec::new(),
+    };
+    let body = Selector::parse("body").ok().and_then(|body| document.select(&body).next());
+    renderer.render(body.unwrap_or_else(|| document.root_element()));
+    renderer.flush();
+
+    let title = first_text(&document, "title");
+    (renderer.blocks.join("\n\n"), renderer.headings, title)
+}
+
+/// Collapsed text of the first element matching `selector`.
+fn first_text(document: &Html, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).ok()?;
+    let element = document.select(&selector).next()?;
+    Some(collapse_whitespace(&element.text().collect::<String>())).filter(|t| !t.is_empty())
+}
+
+/// Page description from `<meta name="description">` or Open Graph.
+fn meta_content(document: &Html) -> Option<String> {
+    let selector =
+        Selector::parse(r#"meta[name="description"], meta[property="og:description"]"#).ok()?;
+    document
+        .select(&selector)
+        .filter_map(|meta| meta.value().attr("content"))
+        .map(collapse_whitespace)
+        .find(|content| !content.is_empty())
+}
+
+/// The element holding the page's main content.
+fn main_content(document: &Html) -> Option<ElementRef<'_>> {
+    for selector in ["article", "main", r#"[role="main"]"#] {
+        let Ok(selector) = Selector::parse(selector) else {
+            continue;
+        };
+        let main = document
+            .select(&selector)
+            .max_by_key(|element| text_length(*element));
+        if let Some(main) = main.filter(|m| text_length(*m) >= MIN_MAIN_TEXT) {
+            return Some(main);
+        }
+    }
+
+    best_scored_container(document).or_else(|| {
+        let body = Selector::parse("body").ok()?;
+        document.select(&body).next()
+    })
+}
+
+/// The container whose paragraphs carry the most text, discounting
+/// link-heavy containers like navigation lists.
+fn best_scored_container(document: &Html) -> Option<ElementRef<'_>> {
+    let paragraphs = Selector::parse("p").ok()?;
+    let mut scores: HashMap<_, (ElementRef<'_>, f32)> = HashMap::new();
+
+    for paragraph in document.select(&paragraphs) {
+        let text = collapse_whitespace(&paragraph.text().collect::<String>());
+        if text.len() < MIN_PARAGRAPH_TEXT {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f32 + (text.len() as f32 / 100.0).min(3.0);
+
+        let parent = paragraph.parent().and_then(ElementRef::wrap);
+        let grandparent = parent.and_then(|p| p.parent()).and_then(ElementRef::wrap);
+        for (ancestor, share) in [(parent, 1.0), (grandparent, 0.5)] {
+            if let Some(ancestor) = ancestor.filter(|a| !is_boilerplate(a)) {
+                scores.entry(ancestor.id()).or_insert((ancestor, 0.0)).1 += score * share;
+            }
+        }
+    }
+
+    scores
+        .into_values()
+        .map(|(element, score)| (element, score * (1.0 - link_density(element))))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(element, _)| element)
+}
+
+fn text_length(element: ElementRef<'_>) -> usize {
+    element.text().map(|t| t.trim().len()).sum()
+}
+
+/// Share of an element's text inside links.
+fn link_density(element: ElementRef<'_>) -> f32 {
+    let total = text_length(element);
+    if total == 0 {
+        return 0.0;
+    }
+    let Ok(links) = Selector::parse("a") else {
+        return 0.0;
+    };
+    let linked: usize = element.select(&links).map(text_length).sum();
+    linked as f32 / total as f32
+}
+
+fn is_boilerplate(element: &ElementRef<'_>) -> bool {
+    let value = element.value();
+    if SKIPPED_TAGS.contains(&value.name()) {
+        return true;
+    }
+    let hints = format!(
+        "{} {}",
+        value.attr("class").unwrap_or_default(),
+        value.attr("id").unwrap_or_default()
+    )
+    .to_lowercase();
+    BOILERPLATE_HINTS.iter().any(|hint| hints.contains(hint))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Renders an element tree to text blocks.
+struct Renderer {
+    preserve_links: bool,
+    extract_headings: bool,
+    blocks: Vec<String>,
+    /// Text of the block being built.
+    current: String,
+    headings: Vec<String>,
+}
+
+impl Renderer {
+    fn render(&mut self, element: ElementRef<'_>) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.render_element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn render_element(&mut self, element: ElementRef<'_>) {
+        if is_boilerplate(&element) {
+            return;
+        }
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                let text = collapse_whitespace(&element.text().collect::<String>());
+                if text.is_empty() {
+                    return;
+                }
+                if self.extract_headings {
+                    self.headings.push(text.clone());
+                }
+                let level = name[1..].parse().unwrap_or(1);
+                self.blocks.push(format!("{} {}", "#".repeat(level), text));
+            }
+            "a" => {
+                let text = collapse_whitespace(&element.text().collect::<String>());
+                let href = element
+                    .value()
+                    .attr("href")
+                    .filter(|href| href.starts_with("http://") || href.starts_with("https://"));
+                match href {
+                    Some(href) if self.preserve_links && !text.is_empty() => {
+                        self.push_text(&format!("[{}]({})", text, href));
+                    }
+                    _ => self.push_text(&text),
+                }
+            }
+            "br" => self.current.push('\n'),
+            "pre" => {
+                self.flush();
+                let text: String = element.text().collect();
+                if !text.trim().is_empty() {
+                    self.blocks.push(text.trim_end().to_string());
+                }
+            }
+            "li" => {
+                self.flush();
+                self.current.push_str("- ");
+                self.render(element);
+                self.flush();
+            }
+            "td" | "th" => {
+                self.render(element);
+                self.push_text(" ");
+            }
+            _ if BLOCK_TAGS.contains(&name) => {
+                self.flush();
+                self.render(element);
+                self.flush();
+            }
+            _ => self.render(element),
+        }
+    }
+
+    /// Append text to the current block, collapsing whitespace.
+    fn push_text(&mut self, text: &str) {
+        if text.starts_with(char::is_whitespace) && !self.current.ends_with(char::is_whitespace) {
+            self.current.push(' ');
+        }
+        self.current.push_str(&collapse_whitespace(text));
+        if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+            self.current.push(' ');
+        }
+    }
+
+    fn flush(&mut self) {
+        let block = self
+            .current
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let block = block.trim();
+        if !block.is_empty() && block != "-" {
+            self.blocks.push(block.to_string());
+        }
+        self.current.clear();
+    }
+}
+
+#[async_trait]
+impl Extractor for HtmlExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let content = content.to_vec();
+        let content_len = content.len();
+        let preserve_links = self.preserve_links;
+        let extract_headings = self.extract_headings;
+
+        // Parsing large pages is CPU-bound, so run it in a blocking task
+        let (text, headings, page_metadata) = tokio::task::spawn_blocking(move || {
+            Self::extract_sync(content, preserve_links, extract_headings)
+        })
+        .await?;
+
+        // Check for empty extraction
+        if text.trim().is_empty() {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let structure = DocumentStructure {
+            page_count: None,
+            sections: headings,
+            pages: Vec::new(),
+        };
+
+        let mut result = ExtractedContent::new(text, Modality::Html, ContentSource::Bytes);
+        result = result.with_structure(structure);
+        result = result.with_metadata("original_size", content_len);
+        for (key, value) in page_metadata {
+            result = result.with_metadata(key, value);
+        }
+
+        Ok(result)
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &["text/html", "application/xhtml+xml"]
+    }
+
+    fn name(&self) -> &str {
+        "scraper"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+  <title>Sourdough basics</title>
+  <meta name="description" content="How to keep a starter alive.">
+  <style>body { color: red; }</style>
+</head>
+<body>
+  <nav><a href="/">Home</a> <a href="/recipes">Recipes</a></nav>
+  <div class="sidebar"><p>Subscribe to our newsletter for weekly recipes and tips, delivered free.</p></div>
+  <div id="content">
+    <h1>Keeping a starter</h1>
+    <p>Feed the starter once a day with equal weights of flour and water, and keep it
+       somewhere warm. See <a href="https://example.com/hydration">hydration</a> for ratios.</p>
+    <h2>Storage</h2>
+    <p>A starter kept in the fridge only needs feeding once a week, which suits occasional bakers.</p>
+    <ul><li>Flour</li><li>Water</li></ul>
+    <script>track();</script>
+  </div>
+  <footer><p>Copyright 2024, all rights reserved by the example bakery company.</p></footer>
+</body>
+</html>"#;
+
+    #[tokio::test]
+    async fn test_html_extracts_main_content() {
+        let extractor = HtmlExtractor::new();
+        let result = extractor.extract(PAGE.as_bytes()).await.unwrap();
+
+        assert_eq!(result.modality, Modality::Html);
+        assert!(result.text.starts_with("# Keeping a starter\n\nFeed the starter"));
+        assert!(result.text.contains("## Storage"));
+        assert!(result.text.contains("See [hydration](https://example.com/hydration) for ratios."));
+        assert!(result.text.contains("- Flour\n\n- Water"));
+        for boilerplate in ["Recipes", "newsletter", "Copyright", "track()", "color: red"] {
+            assert!(!result.text.contains(boilerplate), "kept {}", boilerplate);
+        }
+
+        let structure = result.structure.unwrap();
+        assert_eq!(structure.sections, vec!["Keeping a starter", "Storage"]);
+        assert_eq!(result.metadata["title"], "Sourdough basics");
+        assert_eq!(result.metadata["description"], "How to keep a starter alive.");
+    }
+
+    #[tokio::test]
+    async fn test_html_prefers_article() {
+        let page = format!(
+            "<body><div><p>{}</p></div><article><p>{}</p></article></body>",
+            "Unrelated teaser text that goes on for a while, with commas, and more. ".repeat(3),
+            "The article body is the part worth remembering, by a wide margin. ".repeat(3),
+        );
+        let result = HtmlExtractor::new().extract(page.as_bytes()).await.unwrap();
+        assert!(result.text.starts_with("The article body"));
+        assert!(!result.text.contains("Unrelated"));
+    }
+
+    #[tokio::test]
+    async fn test_html_without_links() {
+        let extractor = HtmlExtractor::new().with_links(false).with_headings(false);
+        let result = extractor.extract(PAGE.as_bytes()).await.unwrap();
+        assert!(result.text.contains("See hydration for ratios."));
+        assert!(result.structure.unwrap().sections.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_html_extractor_empty_content() {
+        let extractor = HtmlExtractor::new();
+        assert!(extractor.extract(b"<html><body><nav>Home</nav></body></html>").await.is_err());
+        assert!(extractor.extract(&[]).await.is_err());
+    }
+
+    #[test]
+    fn test_html_extractor_creation() {
+        let extractor = HtmlExtractor::new();
+        assert_eq!(extractor.name(), "scraper");
+        assert!(extractor.supports("text/html"));
+        assert!(extractor.supports("application/xhtml+xml"));
+        assert!(!extractor.supports("text/markdown"));
+    }
+}
@@ -1,7 +1,8 @@
 //! Image content extraction with OCR and vision LLM strategies.
 //!
 //! Provides ImageExtractor that combines OCR (for text-heavy images)
-//! and vision LLM (for scene understanding) with configurable fallback.
+//! and vision LLM (for scene understanding) with configurable fallback,
+//! and [`thumbnail`] for small previews of stored images.
 
 use crate::error::{ExtractError, ExtractResult};
 use crate::types::{ContentSource, ExtractedContent, Modality};
@@ -153,6 +154,31 @@ impl ImageExtractor {
     }
 }
 
+/// Encode a JPEG thumbnail of an image, scaled to fit within
+/// `max_dimension` pixels on its longer side.
+///
+/// Images already that small are re-encoded at their own size. Decoding
+/// and resizing are CPU-bound, so call this from a blocking task.
+pub fn thumbnail(content: &[u8], max_dimension: u32) -> ExtractResult<Vec<u8>> {
+    use image::codecs::jpeg::JpegEncoder;
+
+    let img = image::load_from_memory(content)
+        .map_err(|e| ExtractError::Image(format!("Failed to decode image: {}", e)))?;
+    let max_dimension = max_dimension.max(1);
+    let img = if img.width() > max_dimension || img.height() > max_dimension {
+        img.thumbnail(max_dimension, max_dimension)
+    } else {
+        img
+    };
+
+    // JPEG has no alpha channel
+    let rgb = image::DynamicImage::ImageRgb8(img.to_rgb8());
+    let mut jpeg = Vec::new();
+    rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, 80))
+        .map_err(|e| ExtractError::Image(format!("Failed to encode thumbnail: {}", e)))?;
+    Ok(jpeg)
+}
+
 impl Default for ImageExtractor {
     fn default() -> Self {
         Self::new()
@@ -335,6 +361,22 @@ mod tests {
         assert_eq!(extractor.name(), "image");
     }
 
+    #[test]
+    fn test_thumbnail() {
+        let img = image::RgbaImage::from_pixel(640, 320, image::Rgba([200, 40, 40, 128]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgba8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let jpeg = thumbnail(&png, 128).unwrap();
+        assert_eq!(ImageExtractor::detect_format(&jpeg).unwrap(), "jpeg");
+        let small = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!((small.width(), small.height()), (128, 64));
+
+        assert!(thumbnail(b"not an image", 128).is_err());
+    }
+
     #[test]
     fn test_config_custom() {
         let config = ImageExtractionConfig {
@@ -1,12 +1,21 @@
 //! rook-extractors - Content extraction for multimodal memory ingestion.
 //!
-//! Provides extractors for PDF, DOCX, and image content with a unified
+//! Provides extractors for PDF, DOCX, HTML, EPUB, PPTX, Markdown, email,
+//! spreadsheet, source code, and image content with a unified
 //! trait-based interface following codebase patterns.
 //!
 //! # Features
 //!
 //! - `pdf` (default) - PDF text extraction via pdf-extract
 //! - `docx` (default) - DOCX text extraction via docx-rs
+//! - `html` (default) - HTML main-content extraction via scraper
+//! - `epub` (default) - EPUB chapters in reading order
+//! - `pptx` (default) - PPTX slides with speaker notes
+//! - `markdown` (default) - Markdown with YAML frontmatter and sections
+//! - `email` (default) - Email (.eml) and mailbox (.mbox) extraction via mailparse
+//! - `spreadsheet` (default) - CSV and XLSX/XLS/ODS tables via csv and calamine
+//! - `code` - Rust, Python, JavaScript, TypeScript and Go source split into
+//!   function/class chunks via tree-sitter
 //! - `image` - Image format detection and loading
 //! - `ocr` - Image OCR via tesseract (requires tesseract installed)
 //! - `vision` - Image description via vision LLM (GPT-4o)
@@ -24,6 +33,13 @@
 //! // Document extractors
 //! let pdf = ExtractorFactory::pdf();
 //! let docx = ExtractorFactory::docx();
+//! let html = ExtractorFactory::html();
+//! let epub = ExtractorFactory::epub();
+//! let pptx = ExtractorFactory::pptx();
+//! let markdown = ExtractorFactory::markdown();
+//! let email = ExtractorFactory::email();
+//! let spreadsheet = ExtractorFactory::spreadsheet();
+//! let code = ExtractorFactory::code();
 //!
 //! // Image extraction (OCR + Vision combined)
 //! let image = ExtractorFactory::image();
@@ -41,6 +57,30 @@ mod pdf;
 #[cfg(feature = "docx")]
 mod docx;
 
+#[cfg(feature = "html")]
+mod html;
+
+#[cfg(any(feature = "epub", feature = "pptx"))]
+mod archive;
+
+#[cfg(feature = "epub")]
+mod epub;
+
+#[cfg(feature = "pptx")]
+mod pptx;
+
+#[cfg(feature = "markdown")]
+mod markdown;
+
+#[cfg(feature = "email")]
+mod email;
+
+#[cfg(feature = "spreadsheet")]
+mod spreadsheet;
+
+#[cfg(feature = "code")]
+mod code;
+
 #[cfg(feature = "image")]
 pub mod image;
 
@@ -58,6 +98,27 @@ pub use pdf::PdfExtractor;
 #[cfg(feature = "docx")]
 pub use docx::DocxExtractor;
 
+#[cfg(feature = "html")]
+pub use html::HtmlExtractor;
+
+#[cfg(feature = "epub")]
+pub use epub::EpubExtractor;
+
+#[cfg(feature = "pptx")]
+pub use pptx::PptxExtractor;
+
+#[cfg(feature = "markdown")]
+pub use markdown::MarkdownExtractor;
+
+#[cfg(feature = "email")]
+pub use email::EmailExtractor;
+
+#[cfg(feature = "spreadsheet")]
+pub use spreadsheet::{SpreadsheetConfig, SpreadsheetExtractor, TableMode};
+
+#[cfg(feature = "code")]
+pub use code::{CodeConfig, CodeExtractor, CodeLanguage};
+
 #[cfg(feature = "image")]
 pub use image::{ImageExtractionConfig, ImageExtractor};
 
@@ -0,0 +1,321 @@
+//! Markdown content extraction.
+//!
+//! YAML frontmatter (between `---` lines at the top of the file) is parsed
+//! into the `frontmatter` metadata entry and removed from the text. The body
+//! is split into sections at ATX (`# Title`) and setext (`Title` underlined
+//! with `===`/`---`) headings, ignoring `#` lines inside fenced code blocks.
+
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+
+/// Markdown content extractor.
+///
+/// Returns the body as text, with headings as `structure.sections` and each
+/// section's text (heading included) as `structure.pages`.
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownExtractor {
+    /// Whether to parse YAML frontmatter into metadata.
+    parse_frontmatter: bool,
+    /// Whether to split the body into sections at headings.
+    extract_sections: bool,
+}
+
+/// A section of a Markdown document.
+#[derive(Debug, Default, PartialEq)]
+struct Section {
+    /// Heading text, without markers. `None` for text before the first heading.
+    heading: Option<String>,
+    text: String,
+}
+
+impl MarkdownExtractor {
+    /// Create new Markdown extractor with default settings.
+    pub fn new() -> Self {
+        Self {
+            parse_frontmatter: true,
+            extract_sections: true,
+        }
+    }
+
+    /// Configure whether to parse YAML frontmatter.
+    pub fn with_frontmatter(mut self, parse: bool) -> Self {
+        self.parse_frontmatter = parse;
+        self
+    }
+
+    /// Configure whether to split the body into sections.
+    pub fn with_sections(mut self, extract: bool) -> Self {
+        self.extract_sections = extract;
+        self
+    }
+}
+
+/// Split YAML frontmatter from the body. Returns `(frontmatter, body)`.
+fn split_frontmatter(source: &str) -> (Option<&str>, &str) {
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    let Some(rest) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return (None, source);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    // Unterminated: treat as ordinary text
+    (None, source)
+}
+
+/// Parse YAML frontmatter into JSON.
+fn parse_frontmatter(yaml: &str) -> ExtractResult<serde_json::Value> {
+    if yaml.trim().is_empty() {
+        return Ok(serde_json::Value::Object(Default::default()));
+    }
+    serde_yaml::from_str(yaml)
+        .map_err(|e| ExtractError::ExtractionFailed(format!("Invalid frontmatter: {}", e)))
+}
+
+/// Text of an ATX heading line (`## Title ##`), if it is one.
+fn atx_heading(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    // Closing hashes are only stripped when preceded by a space
+    let mut text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        text = without_closing.trim_end();
+    }
+    Some(text.to_string())
+}
+
+/// Whether a line underlines the previous one as a setext heading.
+fn is_setext_underline(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && (trimmed.chars().all(|c| c == '=') || trimmed.chars().all(|c| c == '-'))
+}
+
+/// Opening or closing marker of a fenced code block.
+fn fence_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    ["```", "~~~"]
+        .into_iter()
+        .find(|fence| trimmed.starts_with(fence))
+}
+
+/// Split a Markdown body into sections at headings.
+fn split_sections(body: &str) -> Vec<Section> {
+    let mut sections = vec![Section::default()];
+    let mut fence: Option<&str> = None;
+    // Plain line that could be turned into a setext heading by the next line
+    let mut previous_plain = false;
+
+    for line in body.lines() {
+        if let Some(open) = fence {
+            if fence_marker(line) == Some(open) {
+                fence = None;
+            }
+            push_line(sections.last_mut(), line);
+            previous_plain = false;
+            continue;
+        }
+        if let Some(marker) = fence_marker(line) {
+            fence = Some(marker);
+            push_line(sections.last_mut(), line);
+            previous_plain = false;
+            continue;
+        }
+
+        if let Some(heading) = atx_heading(line) {
+            sections.push(Section {
+                heading: Some(heading),
+                text: line.to_string(),
+            });
+            previous_plain = false;
+        } else if previous_plain && is_setext_underline(line) {
+            let section = sections.last_mut().expect("sections is never empty");
+            let (before, heading) = match section.text.rsplit_once('\n') {
+                Some((before, heading)) => (before.to_string(), heading.to_string()),
+                None => (String::new(), std::mem::take(&mut section.text)),
+            };
+            section.text = before;
+            sections.push(Section {
+                heading: Some(heading.trim().to_string()),
+                text: format!("{}\n{}", heading, line),
+            });
+            previous_plain = false;
+        } else {
+            push_line(sections.last_mut(), line);
+            previous_plain = !line.trim().is_empty();
+        }
+    }
+
+    for section in sections.iter_mut() {
+        section.text = section.text.trim().to_string();
+    }
+    sections.retain(|s| s.heading.is_some() || !s.text.is_empty());
+    sections
+}
+
+fn push_line(section: Option<&mut Section>, line: &str) {
+    if let Some(section) = section {
+        if !section.text.is_empty() {
+            section.text.push('\n');
+        }
+        section.text.push_str(line);
+    }
+}
+
+#[async_trait]
+impl Extractor for MarkdownExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let source = String::from_utf8_lossy(content);
+
+        let (frontmatter, body) = if self.parse_frontmatter {
+            split_frontmatter(&source)
+        } else {
+            (None, source.as_ref())
+        };
+        let text = body.trim();
+
+        // Check for empty extraction
+        if text.is_empty() {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let mut result =
+            ExtractedContent::new(text.to_string(), Modality::Markdown, ContentSource::Bytes);
+
+        if self.extract_sections {
+            let sections = split_sections(text);
+            result = result.with_structure(DocumentStructure {
+                page_count: None,
+                sections: sections.iter().filter_map(|s| s.heading.clone()).collect(),
+                pages: sections.into_iter().map(|s| s.text).collect(),
+            });
+        }
+
+        result = result.with_metadata("original_size", content.len());
+        if let Some(frontmatter) = frontmatter {
+            result = result.with_metadata("frontmatter", parse_frontmatter(frontmatter)?);
+        }
+
+        Ok(result)
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &["text/markdown", "text/x-markdown"]
+    }
+
+    fn name(&self) -> &str {
+        "markdown"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "---
+title: Trip planning
+tags: [travel, lisbon]
+---
+Notes for the spring trip.
+
+# Flights
+
+Book the early flight.
+
+Hotels
+------
+
+Near the river.
+
+```sh
+# not a heading
+```
+";
+
+    #[tokio::test]
+    async fn test_markdown_frontmatter_and_sections() {
+        let extractor = MarkdownExtractor::new();
+        let result = extractor.extract(NOTE.as_bytes()).await.unwrap();
+
+        assert_eq!(result.modality, Modality::Markdown);
+        assert!(result.text.starts_with("Notes for the spring trip."));
+        assert!(!result.text.contains("tags:"));
+        assert_eq!(
+            result.metadata["frontmatter"],
+            serde_json::json!({"title": "Trip planning", "tags": ["travel", "lisbon"]})
+        );
+
+        let structure = result.structure.unwrap();
+        assert_eq!(structure.sections, vec!["Flights", "Hotels"]);
+        assert_eq!(structure.pages.len(), 3);
+        assert_eq!(structure.pages[0], "Notes for the spring trip.");
+        assert_eq!(structure.pages[1], "# Flights\n\nBook the early flight.");
+        assert!(structure.pages[2].starts_with("Hotels\n------\n\nNear the river."));
+        assert!(structure.pages[2].contains("# not a heading"));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_invalid_frontmatter() {
+        let extractor = MarkdownExtractor::new();
+        let result = extractor.extract(b"---\ntitle: [unclosed\n---\nBody").await;
+        assert!(result.is_err());
+
+        // Ignored when frontmatter parsing is off
+        let extractor = MarkdownExtractor::new().with_frontmatter(false);
+        let result = extractor.extract(b"---\ntitle: [unclosed\n---\nBody").await.unwrap();
+        assert!(result.text.contains("title: [unclosed"));
+        assert!(!result.metadata.contains_key("frontmatter"));
+    }
+
+    #[tokio::test]
+    async fn test_markdown_extractor_empty_content() {
+        let extractor = MarkdownExtractor::new();
+        assert!(extractor.extract(b"---\ntitle: Empty\n---\n\n").await.is_err());
+        assert!(extractor.extract(&[]).await.is_err());
+    }
+
+    #[test]
+    fn test_atx_heading() {
+        assert_eq!(atx_heading("## Title ##"), Some("Title".to_string()));
+        assert_eq!(atx_heading("# C#"), Some("C#".to_string()));
+        assert_eq!(atx_heading("#hashtag"), None);
+        assert_eq!(atx_heading("####### too deep"), None);
+    }
+
+    #[test]
+    fn test_split_frontmatter_unterminated() {
+        let (frontmatter, body) = split_frontmatter("---\nno end");
+        assert!(frontmatter.is_none());
+        assert_eq!(body, "---\nno end");
+    }
+
+    #[test]
+    fn test_markdown_extractor_creation() {
+        let extractor = MarkdownExtractor::new().with_sections(false);
+        assert_eq!(extractor.name(), "markdown");
+        assert!(extractor.supports("text/markdown"));
+        assert!(extractor.supports("text/x-markdown"));
+        assert!(!extractor.supports("text/html"));
+    }
+}
@@ -93,6 +93,27 @@ mod tests {
 
         #[cfg(feature = "docx")]
         assert!(pipeline.supports("application/docx"));
+
+        #[cfg(feature = "html")]
+        assert!(pipeline.supports("text/html"));
+
+        #[cfg(feature = "epub")]
+        assert!(pipeline.supports("application/epub+zip"));
+
+        #[cfg(feature = "pptx")]
+        assert!(pipeline.supports("application/pptx"));
+
+        #[cfg(feature = "markdown")]
+        assert!(pipeline.supports("text/markdown"));
+
+        #[cfg(feature = "email")]
+        assert!(pipeline.supports("message/rfc822"));
+
+        #[cfg(feature = "spreadsheet")]
+        assert!(pipeline.supports("text/csv"));
+
+        #[cfg(feature = "code")]
+        assert!(pipeline.supports("text/x-rust"));
     }
 
     #[test]
@@ -112,14 +133,17 @@ mod tests {
     fn test_pipeline_with_defaults() {
         let pipeline = ExtractionPipeline::with_defaults();
 
-        #[cfg(all(feature = "pdf", feature = "docx", feature = "image"))]
-        assert_eq!(pipeline.len(), 3);
-
-        #[cfg(all(feature = "pdf", feature = "docx", not(feature = "image")))]
-        assert_eq!(pipeline.len(), 2);
-
-        #[cfg(all(feature = "pdf", not(feature = "docx"), not(feature = "image")))]
-        assert_eq!(pipeline.len(), 1);
+        let expected = cfg!(feature = "pdf") as usize
+            + cfg!(feature = "docx") as usize
+            + cfg!(feature = "html") as usize
+            + cfg!(feature = "epub") as usize
+            + cfg!(feature = "pptx") as usize
+            + cfg!(feature = "markdown") as usize
+            + cfg!(feature = "email") as usize
+            + cfg!(feature = "spreadsheet") as usize
+            + cfg!(feature = "code") as usize
+            + cfg!(feature = "image") as usize;
+        assert_eq!(pipeline.len(), expected);
     }
 
     #[tokio::test]
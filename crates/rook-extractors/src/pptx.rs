@@ -0,0 +1,396 @@
+//! PPTX content extraction.
+//!
+//! Reads slides in presentation order, following the relationships from
+//! the presentation to each slide and from each slide to its notes page.
+//! Every slide is one entry of `structure.pages`: its title, body text and
+//! tables, then its speaker notes. Slide titles are the
+//! `structure.sections`.
+
+use std::collections::HashMap;
+
+use crate::archive::{parse_xml, resolve, Archive};
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use roxmltree::Node;
+
+/// Namespace of relationship IDs in Office documents.
+const RELATIONSHIPS_NS: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Namespace of text paragraphs.
+const DRAWINGML_NS: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+
+const PRESENTATION: &str = "ppt/presentation.xml";
+
+/// PPTX content extractor.
+///
+/// Produces one page per slide, so presentations are stored slide by slide
+/// with each slide's speaker notes alongside its content.
+#[derive(Debug, Clone)]
+pub struct PptxExtractor {
+    /// Whether to include speaker notes.
+    include_notes: bool,
+}
+
+/// Text read from a slide.
+#[derive(Debug, Default)]
+struct Slide {
+    title: Option<String>,
+    /// Body paragraphs and table rows.
+    body: Vec<String>,
+    notes: Vec<String>,
+}
+
+impl PptxExtractor {
+    /// Create new PPTX extractor with default settings.
+    pub fn new() -> Self {
+        Self {
+            include_notes: true,
+        }
+    }
+
+    /// Configure whether to include speaker notes.
+    pub fn with_notes(mut self, include: bool) -> Self {
+        self.include_notes = include;
+        self
+    }
+
+    /// Read slides synchronously (called within spawn_blocking).
+    fn extract_sync(content: Vec<u8>, include_notes: bool) -> ExtractResult<Vec<Slide>> {
+        let mut archive = Archive::open(content, "PPTX").map_err(ExtractError::Pptx)?;
+
+        let presentation = archive
+            .read(PRESENTATION)
+            .ok_or_else(|| ExtractError::Pptx(format!("Missing {}", PRESENTATION)))?;
+        let presentation = parse_xml(&presentation).map_err(ExtractError::Pptx)?;
+        let targets = relationships(&mut archive, PRESENTATION);
+
+        let slide_paths: Vec<String> = presentation
+            .descendants()
+            .filter(|n| n.has_tag_name("sldId"))
+            .filter_map(|n| n.attribute((RELATIONSHIPS_NS, "id")))
+            .filter_map(|id| targets.get(id))
+            .map(|(_, target)| resolve(PRESENTATION, target))
+            .collect();
+
+        let mut slides = Vec::new();
+        for path in slide_paths {
+            let Some(xml) = archive.read(&path) else {
+                continue;
+            };
+            let document = parse_xml(&xml).map_err(ExtractError::Pptx)?;
+            let mut slide = Slide::default();
+            read_shapes(document.root_element(), &mut slide.title, &mut slide.body);
+
+            if include_notes {
+                let notes_path = relationships(&mut archive, &path)
+                    .into_values()
+                    .find(|(kind, _)| kind.ends_with("/notesSlide"))
+                    .map(|(_, target)| resolve(&path, &target));
+                if let Some(xml) = notes_path.and_then(|p| archive.read(&p)) {
+                    let document = parse_xml(&xml).map_err(ExtractError::Pptx)?;
+                    read_shapes(document.root_element(), &mut None, &mut slide.notes);
+                }
+            }
+            slides.push(slide);
+        }
+
+        Ok(slides)
+    }
+}
+
+impl Default for PptxExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Relationships of a part, as (type, target) by ID. Empty if the part
+/// has no relationships.
+fn relationships(archive: &mut Archive, part: &str) -> HashMap<String, (String, String)> {
+    let (dir, file) = part.rsplit_once('/').unwrap_or(("", part));
+    let Some(xml) = archive.read(&format!("{}/_rels/{}.rels", dir, file)) else {
+        return HashMap::new();
+    };
+    let Ok(document) = parse_xml(&xml) else {
+        return HashMap::new();
+    };
+    document
+        .descendants()
+        .filter(|n| n.has_tag_name("Relationship"))
+        .filter_map(|n| {
+            Some((
+                n.attribute("Id")?.to_string(),
+                (
+                    n.attribute("Type").unwrap_or_default().to_string(),
+                    n.attribute("Target")?.to_string(),
+                ),
+            ))
+        })
+        .collect()
+}
+
+/// Collect text from the shapes and tables under `node`, in document order.
+///
+/// Text of title placeholders goes to `title` when given. Slide numbers,
+/// dates, footers and slide images are skipped; on notes pages these
+/// repeat the slide rather than adding to it.
+fn read_shapes(node: Node, title: &mut Option<String>, lines: &mut Vec<String>) {
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "sp" => {
+                let placeholder = child
+                    .descendants()
+                    .find(|n| n.has_tag_name("ph"))
+                    .map(|ph| ph.attribute("type").unwrap_or("body"));
+                let paragraphs = paragraphs(child);
+                match placeholder {
+                    Some("sldNum" | "dt" | "ftr" | "hdr" | "sldImg") => {}
+                    Some("title" | "ctrTitle") if title.is_none() && !paragraphs.is_empty() => {
+                        *title = Some(paragraphs.join(" "));
+                    }
+                    _ => lines.extend(paragraphs),
+                }
+            }
+            "tbl" => {
+                for row in child.children().filter(|n| n.has_tag_name("tr")) {
+                    let cells: Vec<String> = row
+                        .children()
+                        .filter(|n| n.has_tag_name("tc"))
+                        .map(|cell| paragraphs(cell).join(" "))
+                        .collect();
+                    if cells.iter().any(|c| !c.is_empty()) {
+                        lines.push(cells.join(" | "));
+                    }
+                }
+            }
+            _ => read_shapes(child, title, lines),
+        }
+    }
+}
+
+/// Non-empty paragraphs of a shape or table cell.
+fn paragraphs(node: Node) -> Vec<String> {
+    node.descendants()
+        .filter(|n| n.has_tag_name((DRAWINGML_NS, "p")))
+        .map(|p| {
+            p.descendants()
+                .filter_map(|n| match n.tag_name().name() {
+                    "t" => n.text(),
+                    "br" => Some("\n"),
+                    _ => None,
+                })
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+impl Slide {
+    /// Slide text: title, body and speaker notes.
+    fn page(&self, number: usize) -> String {
+        let mut text = match &self.title {
+            Some(title) => format!("Slide {}: {}", number, title),
+            None => format!("Slide {}", number),
+        };
+        if !self.body.is_empty() {
+            text.push('\n');
+            text.push_str(&self.body.join("\n"));
+        }
+        if !self.notes.is_empty() {
+            text.push_str("\n\nSpeaker notes:\n");
+            text.push_str(&self.notes.join("\n"));
+        }
+        text
+    }
+
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.body.is_empty() && self.notes.is_empty()
+    }
+}
+
+#[async_trait]
+impl Extractor for PptxExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let content = content.to_vec();
+        let content_len = content.len();
+        let include_notes = self.include_notes;
+
+        // Decompressing and parsing slides is CPU-bound, so run it in a
+        // blocking task
+        let slides =
+            tokio::task::spawn_blocking(move || Self::extract_sync(content, include_notes))
+                .await??;
+
+        // Check for empty extraction
+        if slides.iter().all(Slide::is_empty) {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let pages: Vec<String> = slides
+            .iter()
+            .enumerate()
+            .map(|(i, slide)| slide.page(i + 1))
+            .collect();
+        let text = pages.join("\n\n");
+        let structure = DocumentStructure {
+            page_count: Some(pages.len()),
+            sections: slides.iter().filter_map(|s| s.title.clone()).collect(),
+            pages,
+        };
+        let slide_metadata: Vec<serde_json::Value> = slides
+            .iter()
+            .enumerate()
+            .map(|(i, slide)| {
+                serde_json::json!({
+                    "number": i + 1,
+                    "title": slide.title,
+                    "has_notes": !slide.notes.is_empty(),
+                })
+            })
+            .collect();
+
+        Ok(
+            ExtractedContent::new(text, Modality::Pptx, ContentSource::Bytes)
+                .with_structure(structure)
+                .with_metadata("original_size", content_len)
+                .with_metadata("slides", slide_metadata),
+        )
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &[
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "application/pptx",
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "pptx"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive::build_zip;
+
+    const NS: &str = r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main""#;
+
+    fn shape(placeholder: &str, paragraphs: &[&str]) -> String {
+        let paragraphs: String = paragraphs
+            .iter()
+            .map(|p| format!("<a:p><a:r><a:t>{}</a:t></a:r></a:p>", p))
+            .collect();
+        format!(
+            "<p:sp><p:nvSpPr><p:nvPr>{}</p:nvPr></p:nvSpPr><p:txBody>{}</p:txBody></p:sp>",
+            placeholder, paragraphs
+        )
+    }
+
+    fn part(root: &str, shapes: &str) -> String {
+        format!(
+            "<p:{root} {NS}><p:cSld><p:spTree>{shapes}</p:spTree></p:cSld></p:{root}>",
+            root = root,
+            NS = NS,
+            shapes = shapes
+        )
+    }
+
+    fn deck() -> Vec<u8> {
+        let presentation = format!(
+            "<p:presentation {}><p:sldIdLst><p:sldId id=\"256\" r:id=\"rId3\"/>\
+             <p:sldId id=\"257\" r:id=\"rId2\"/></p:sldIdLst></p:presentation>",
+            NS
+        );
+        let presentation_rels = r#"<Relationships>
+            <Relationship Id="rId2" Type=".../slide" Target="slides/slide1.xml"/>
+            <Relationship Id="rId3" Type=".../slide" Target="slides/slide2.xml"/>
+        </Relationships>"#;
+        let first = part(
+            "sld",
+            &(shape(r#"<p:ph type="ctrTitle"/>"#, &["Quarterly review"])
+                + &shape(r#"<p:ph type="sldNum"/>"#, &["1"])),
+        );
+        let table = "<p:graphicFrame><a:graphic><a:graphicData><a:tbl>\
+             <a:tr><a:tc><a:txBody><a:p><a:r><a:t>Region</a:t></a:r></a:p></a:txBody></a:tc>\
+             <a:tc><a:txBody><a:p><a:r><a:t>Sales</a:t></a:r></a:p></a:txBody></a:tc></a:tr>\
+             <a:tr><a:tc><a:txBody><a:p><a:r><a:t>North</a:t></a:r></a:p></a:txBody></a:tc>\
+             <a:tc><a:txBody><a:p><a:r><a:t>120</a:t></a:r></a:p></a:txBody></a:tc></a:tr>\
+             </a:tbl></a:graphicData></a:graphic></p:graphicFrame>";
+        let second = part(
+            "sld",
+            &(shape(r#"<p:ph type="title"/>"#, &["Results"])
+                + &shape(r#"<p:ph idx="1"/>"#, &["Revenue up", "Costs flat"])
+                + table),
+        );
+        let second_rels = r#"<Relationships>
+            <Relationship Id="rId1" Type=".../slideLayout" Target="../slideLayouts/slideLayout2.xml"/>
+            <Relationship Id="rId2" Type=".../notesSlide" Target="../notesSlides/notesSlide1.xml"/>
+        </Relationships>"#;
+        let notes = part(
+            "notes",
+            &(shape(r#"<p:ph type="sldImg"/>"#, &[])
+                + &shape(
+                    r#"<p:ph type="body" idx="1"/>"#,
+                    &["Mention the new hires."],
+                )),
+        );
+
+        build_zip(&[
+            ("ppt/presentation.xml", &presentation),
+            ("ppt/_rels/presentation.xml.rels", presentation_rels),
+            ("ppt/slides/slide2.xml", &first),
+            ("ppt/slides/slide1.xml", &second),
+            ("ppt/slides/_rels/slide1.xml.rels", second_rels),
+            ("ppt/notesSlides/notesSlide1.xml", &notes),
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_pptx_slides_in_presentation_order() {
+        let result = PptxExtractor::new().extract(&deck()).await.unwrap();
+        assert_eq!(result.modality, Modality::Pptx);
+
+        let structure = result.structure.unwrap();
+        assert_eq!(structure.sections, vec!["Quarterly review", "Results"]);
+        assert_eq!(structure.pages[0], "Slide 1: Quarterly review");
+        assert_eq!(
+            structure.pages[1],
+            "Slide 2: Results\nRevenue up\nCosts flat\nRegion | Sales\nNorth | 120\n\n\
+             Speaker notes:\nMention the new hires."
+        );
+        assert_eq!(result.metadata["slides"][1]["has_notes"], true);
+    }
+
+    #[tokio::test]
+    async fn test_pptx_without_notes() {
+        let extractor = PptxExtractor::new().with_notes(false);
+        let result = extractor.extract(&deck()).await.unwrap();
+        assert!(!result.text.contains("Speaker notes"));
+        assert_eq!(result.metadata["slides"][1]["has_notes"], false);
+    }
+
+    #[tokio::test]
+    async fn test_pptx_extractor_invalid_content() {
+        let extractor = PptxExtractor::new();
+        assert!(extractor.extract(b"not a zip").await.is_err());
+        assert!(extractor
+            .extract(&build_zip(&[("a.txt", "a")]))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_pptx_extractor_creation() {
+        let extractor = PptxExtractor::new();
+        assert_eq!(extractor.name(), "pptx");
+        assert!(extractor
+            .supports("application/vnd.openxmlformats-officedocument.presentationml.presentation"));
+        assert!(!extractor.supports("application/pdf"));
+    }
+}
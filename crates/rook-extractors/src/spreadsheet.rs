@@ -0,0 +1,454 @@
+//! Spreadsheet and CSV content extraction using csv and calamine.
+//!
+//! Tables don't survive being flattened into one block of text: once split
+//! into chunks, rows lose the headers that give their values meaning. This
+//! extractor instead emits either row groups, each a Markdown table that
+//! repeats the column headers, or a summary of each table with column
+//! profiles and sample rows. Each row group or summary is one entry of
+//! `structure.pages`, ready to be stored as its own memory.
+
+use std::collections::BTreeSet;
+use std::io::Cursor;
+
+use crate::error::{ExtractError, ExtractResult};
+use crate::types::{ContentSource, DocumentStructure, ExtractedContent, Modality};
+use crate::Extractor;
+use async_trait::async_trait;
+use calamine::{Data, Reader};
+use serde::{Deserialize, Serialize};
+
+/// How tables are turned into text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableMode {
+    /// Groups of rows, each with the column headers.
+    #[default]
+    RowGroups,
+    /// One summary per table: size, column profiles and sample rows.
+    Summary,
+}
+
+/// Configuration for spreadsheet extraction.
+#[derive(Debug, Clone)]
+pub struct SpreadsheetConfig {
+    /// Row groups or summaries (default: row groups).
+    pub mode: TableMode,
+    /// Rows per row group (default: 50).
+    pub rows_per_chunk: usize,
+    /// Rows shown in a summary (default: 5).
+    pub summary_sample_rows: usize,
+    /// CSV field delimiter. Detected from the first line when unset
+    /// (default: none).
+    pub delimiter: Option<u8>,
+}
+
+impl Default for SpreadsheetConfig {
+    fn default() -> Self {
+        Self {
+            mode: TableMode::RowGroups,
+            rows_per_chunk: 50,
+            summary_sample_rows: 5,
+            delimiter: None,
+        }
+    }
+}
+
+/// Spreadsheet extractor for CSV/TSV files and XLSX, XLS and ODS workbooks.
+pub struct SpreadsheetExtractor {
+    config: SpreadsheetConfig,
+}
+
+/// A table: a CSV file or a workbook sheet.
+#[derive(Debug, Default)]
+struct Table {
+    /// Sheet name. `None` for CSV files.
+    name: Option<String>,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl SpreadsheetExtractor {
+    /// Create spreadsheet extractor with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: SpreadsheetConfig::default(),
+        }
+    }
+
+    /// Create spreadsheet extractor with custom configuration.
+    pub fn with_config(config: SpreadsheetConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &SpreadsheetConfig {
+        &self.config
+    }
+
+    /// Read tables synchronously (called within spawn_blocking).
+    fn read_tables(content: Vec<u8>, delimiter: Option<u8>) -> ExtractResult<Vec<Table>> {
+        if is_workbook(&content) {
+            read_workbook(content)
+        } else {
+            read_csv(&content, delimiter).map(|table| vec![table])
+        }
+    }
+}
+
+impl Default for SpreadsheetExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether content is a workbook: a ZIP archive (XLSX, ODS) or an OLE
+/// compound file (XLS).
+fn is_workbook(content: &[u8]) -> bool {
+    content.starts_with(b"PK\x03\x04") || content.starts_with(&[0xD0, 0xCF, 0x11, 0xE0])
+}
+
+/// The most frequent of `,`, tab and `;` in the first line.
+fn detect_delimiter(content: &[u8]) -> u8 {
+    let first_line = content.split(|b| *b == b'\n').next().unwrap_or_default();
+    [b',', b'\t', b';']
+        .into_iter()
+        .max_by_key(|d| first_line.iter().filter(|b| *b == d).count())
+        .filter(|d| first_line.contains(d))
+        .unwrap_or(b',')
+}
+
+fn read_csv(content: &[u8], delimiter: Option<u8>) -> ExtractResult<Table> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter.unwrap_or_else(|| detect_delimiter(content)))
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content);
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record
+            .map_err(|e| ExtractError::ExtractionFailed(format!("Failed to parse CSV: {}", e)))?;
+        rows.push(record.iter().map(|cell| cell.trim().to_string()).collect());
+    }
+    Ok(Table::from_rows(None, rows))
+}
+
+fn read_workbook(content: Vec<u8>) -> ExtractResult<Vec<Table>> {
+    let mut workbook = calamine::open_workbook_auto_from_rs(Cursor::new(content))
+        .map_err(|e| ExtractError::ExtractionFailed(format!("Failed to open workbook: {}", e)))?;
+
+    let mut tables = Vec::new();
+    for name in workbook.sheet_names() {
+        let range = workbook.worksheet_range(&name).map_err(|e| {
+            ExtractError::ExtractionFailed(format!("Failed to read sheet {}: {}", name, e))
+        })?;
+        let rows = range
+            .rows()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+        let table = Table::from_rows(Some(name), rows);
+        if !table.rows.is_empty() || !table.headers.is_empty() {
+            tables.push(table);
+        }
+    }
+    Ok(tables)
+}
+
+/// Cell value as text, with dates as ISO 8601.
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(value) => match value.as_datetime() {
+            Some(datetime) if datetime.time() == chrono::NaiveTime::MIN => {
+                datetime.date().to_string()
+            }
+            Some(datetime) => datetime.to_string(),
+            None => value.to_string(),
+        },
+        other => other.to_string().trim().to_string(),
+    }
+}
+
+impl Table {
+    /// Table from rows, taking the first non-empty row as headers. Blank
+    /// rows are dropped, short rows padded and unnamed columns numbered.
+    fn from_rows(name: Option<String>, rows: Vec<Vec<String>>) -> Self {
+        let mut rows = rows
+            .into_iter()
+            .filter(|row| row.iter().any(|cell| !cell.is_empty()));
+        let mut headers = rows.next().unwrap_or_default();
+        let rows: Vec<Vec<String>> = rows.collect();
+
+        let width = rows.iter().map(Vec::len).chain([headers.len()]).max().unwrap_or(0);
+        headers.resize(width, String::new());
+        for (i, header) in headers.iter_mut().enumerate() {
+            if header.is_empty() {
+                *header = format!("column_{}", i + 1);
+            }
+        }
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                row.resize(width, String::new());
+                row
+            })
+            .collect();
+
+        Self {
+            name,
+            headers,
+            rows,
+        }
+    }
+
+    /// Title line naming the sheet, if the table has one.
+    fn title(&self) -> Option<String> {
+        self.name.as_ref().map(|name| format!("Sheet: {}", name))
+    }
+
+    /// Rows as Markdown table lines under the headers.
+    fn markdown(&self, rows: &[Vec<String>]) -> String {
+        let mut lines = vec![
+            markdown_row(&self.headers),
+            markdown_row(&vec!["---".to_string(); self.headers.len()]),
+        ];
+        lines.extend(rows.iter().map(|row| markdown_row(row)));
+        lines.join("\n")
+    }
+
+    /// Row groups of at most `rows_per_chunk` rows.
+    fn row_groups(&self, rows_per_chunk: usize) -> Vec<String> {
+        let total = self.rows.len();
+        if total == 0 {
+            return vec![self.with_title(format!("No rows\n{}", self.markdown(&[])))];
+        }
+        self.rows
+            .chunks(rows_per_chunk.max(1))
+            .enumerate()
+            .map(|(i, rows)| {
+                let first = i * rows_per_chunk.max(1) + 1;
+                let last = first + rows.len() - 1;
+                self.with_title(format!(
+                    "Rows {}-{} of {}\n{}",
+                    first,
+                    last,
+                    total,
+                    self.markdown(rows)
+                ))
+            })
+            .collect()
+    }
+
+    /// Size, column profiles and the first `sample_rows` rows.
+    fn summary(&self, sample_rows: usize) -> String {
+        let mut lines = vec![format!(
+            "{} rows, {} columns",
+            self.rows.len(),
+            self.headers.len()
+        )];
+        lines.push("Columns:".to_string());
+        for (i, header) in self.headers.iter().enumerate() {
+            let values: Vec<&str> = self
+                .rows
+                .iter()
+                .map(|row| row[i].as_str())
+                .filter(|v| !v.is_empty())
+                .collect();
+            lines.push(format!("- {}: {}", header, profile_column(&values)));
+        }
+        if !self.rows.is_empty() && sample_rows > 0 {
+            let sample = &self.rows[..sample_rows.min(self.rows.len())];
+            lines.push("Sample rows:".to_string());
+            lines.push(self.markdown(sample));
+        }
+        self.with_title(lines.join("\n"))
+    }
+
+    fn with_title(&self, body: String) -> String {
+        match self.title() {
+            Some(title) => format!("{}\n{}", title, body),
+            None => body,
+        }
+    }
+}
+
+fn markdown_row(cells: &[String]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| cell.replace('|', "\\|").replace(['\r', '\n'], " "))
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Short description of a column's non-empty values.
+fn profile_column(values: &[&str]) -> String {
+    if values.is_empty() {
+        return "empty".to_string();
+    }
+    let numbers: Vec<f64> = values.iter().filter_map(|v| v.parse().ok()).collect();
+    if numbers.len() == values.len() {
+        let min = numbers.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+        return format!("number, min {}, max {}, mean {:.2}", min, max, mean);
+    }
+    let distinct: BTreeSet<&str> = values.iter().copied().collect();
+    let examples: Vec<&str> = distinct.iter().copied().take(3).collect();
+    format!(
+        "text, {} distinct (e.g. {})",
+        distinct.len(),
+        examples.join(", ")
+    )
+}
+
+#[async_trait]
+impl Extractor for SpreadsheetExtractor {
+    async fn extract(&self, content: &[u8]) -> ExtractResult<ExtractedContent> {
+        let content = content.to_vec();
+        let content_len = content.len();
+        let delimiter = self.config.delimiter;
+
+        // Workbooks can be large, so parse in a blocking task
+        let tables =
+            tokio::task::spawn_blocking(move || Self::read_tables(content, delimiter)).await??;
+
+        // Check for empty extraction
+        if tables.iter().all(|t| t.headers.is_empty()) {
+            return Err(ExtractError::EmptyContent);
+        }
+
+        let pages: Vec<String> = tables
+            .iter()
+            .flat_map(|table| match self.config.mode {
+                TableMode::RowGroups => table.row_groups(self.config.rows_per_chunk),
+                TableMode::Summary => vec![table.summary(self.config.summary_sample_rows)],
+            })
+            .collect();
+        let text = pages.join("\n\n");
+
+        let structure = DocumentStructure {
+            page_count: Some(pages.len()),
+            sections: tables.iter().filter_map(|t| t.name.clone()).collect(),
+            pages,
+        };
+        let table_metadata: Vec<serde_json::Value> = tables
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "name": t.name,
+                    "rows": t.rows.len(),
+                    "columns": t.headers,
+                })
+            })
+            .collect();
+
+        Ok(
+            ExtractedContent::new(text, Modality::Spreadsheet, ContentSource::Bytes)
+                .with_structure(structure)
+                .with_metadata("original_size", content_len)
+                .with_metadata("tables", table_metadata)
+                .with_metadata(
+                    "table_mode",
+                    serde_json::to_value(self.config.mode).unwrap_or_default(),
+                ),
+        )
+    }
+
+    fn supported_types(&self) -> &[&str] {
+        &[
+            "text/csv",
+            "text/tab-separated-values",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "application/vnd.ms-excel",
+            "application/vnd.oasis.opendocument.spreadsheet",
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "calamine"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "region,amount,note\n\
+                       North,120,first order\n\
+                       South,80,\"has, comma\"\n\
+                       \n\
+                       East,300,\n\
+                       West,5,last | one\n";
+
+    #[tokio::test]
+    async fn test_csv_row_groups_repeat_headers() {
+        let extractor = SpreadsheetExtractor::with_config(SpreadsheetConfig {
+            rows_per_chunk: 3,
+            ..Default::default()
+        });
+        let result = extractor.extract(CSV.as_bytes()).await.unwrap();
+        assert_eq!(result.modality, Modality::Spreadsheet);
+
+        let pages = result.structure.unwrap().pages;
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].starts_with("Rows 1-3 of 4\n| region | amount | note |\n| --- | --- | --- |"));
+        assert!(pages[0].contains("| South | 80 | has, comma |"));
+        assert!(pages[1].starts_with("Rows 4-4 of 4\n| region | amount | note |"));
+        assert!(pages[1].contains("| West | 5 | last \\| one |"));
+
+        assert_eq!(result.metadata["tables"][0]["rows"], 4);
+        assert_eq!(result.metadata["table_mode"], "row_groups");
+    }
+
+    #[tokio::test]
+    async fn test_csv_summary() {
+        let extractor = SpreadsheetExtractor::with_config(SpreadsheetConfig {
+            mode: TableMode::Summary,
+            summary_sample_rows: 1,
+            ..Default::default()
+        });
+        let result = extractor.extract(CSV.as_bytes()).await.unwrap();
+
+        assert!(result.text.starts_with("4 rows, 3 columns"));
+        assert!(result.text.contains("- region: text, 4 distinct (e.g. East, North, South)"));
+        assert!(result.text.contains("- amount: number, min 5, max 300, mean 126.25"));
+        assert!(result.text.contains("| North | 120 | first order |"));
+        assert!(!result.text.contains("| South |"));
+        assert_eq!(result.structure.unwrap().pages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tsv_delimiter_detection() {
+        let tsv = "name\tcity\nAnn\tLisbon\n";
+        let result = SpreadsheetExtractor::new().extract(tsv.as_bytes()).await.unwrap();
+        assert!(result.text.contains("| Ann | Lisbon |"));
+    }
+
+    #[test]
+    fn test_table_pads_rows_and_names_columns() {
+        let rows = vec![
+            vec!["a".to_string(), String::new()],
+            vec!["1".to_string(), "2".to_string(), "3".to_string()],
+        ];
+        let table = Table::from_rows(None, rows);
+        assert_eq!(table.headers, vec!["a", "column_2", "column_3"]);
+        assert_eq!(table.rows[0].len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_spreadsheet_extractor_empty_content() {
+        let extractor = SpreadsheetExtractor::new();
+        assert!(extractor.extract(b"\n,,\n").await.is_err());
+        assert!(extractor.extract(&[]).await.is_err());
+    }
+
+    #[test]
+    fn test_spreadsheet_extractor_creation() {
+        let extractor = SpreadsheetExtractor::new();
+        assert_eq!(extractor.name(), "calamine");
+        assert_eq!(extractor.config().rows_per_chunk, 50);
+        assert!(extractor.supports("text/csv"));
+        assert!(extractor
+            .supports("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"));
+        assert!(!extractor.supports("application/pdf"));
+    }
+}
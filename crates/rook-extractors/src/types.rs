@@ -14,6 +14,20 @@ pub enum Modality {
     Pdf,
     /// Microsoft Word document.
     Docx,
+    /// EPUB e-book.
+    Epub,
+    /// Microsoft PowerPoint presentation.
+    Pptx,
+    /// HTML web page.
+    Html,
+    /// Markdown document.
+    Markdown,
+    /// Email message or mailbox.
+    Email,
+    /// Spreadsheet or CSV table.
+    Spreadsheet,
+    /// Source code file.
+    Code,
     /// Image with specified format.
     Image {
         /// Image format (e.g., "png", "jpeg").
@@ -36,7 +50,9 @@ pub enum ContentSource {
 /// Document structure metadata (optional, for structured documents).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DocumentStructure {
-    /// Total page count (for PDFs).
+    /// Total page count (for PDFs), chapter count (for EPUBs), slide count
+    /// (for presentations), message count (for mailboxes), or chunk count
+    /// (for spreadsheets and source code).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_count: Option<usize>,
 
@@ -44,7 +60,8 @@ pub struct DocumentStructure {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sections: Vec<String>,
 
-    /// Per-page text (for page-level retrieval).
+    /// Per-page text (for page-level retrieval), or per-section text for
+    /// documents without pages.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub pages: Vec<String>,
 }
@@ -0,0 +1,67 @@
+//! Cypher helpers shared by the Neo4j and Memgraph stores.
+
+use std::collections::HashSet;
+
+use neo4rs::{query, Query, Row};
+
+use rook_core::traits::{GraphFilters, GraphPath, MAX_TRAVERSAL_DEPTH};
+
+/// Upper bound on raw paths fetched per traversal before deduplication.
+const TRAVERSAL_PATH_LIMIT: i64 = 1000;
+
+/// Build the variable-length match used by `GraphStore::traverse`.
+///
+/// Nodes are identified by `name`, falling back to the `content`/`id`
+/// properties the Memory and User nodes carry. Path length cannot be a query
+/// parameter, so the (capped) depth is formatted into the pattern.
+pub(crate) fn traversal_query(
+    start_entity: &str,
+    max_depth: usize,
+    relationship_filter: Option<&[String]>,
+    filters: &GraphFilters,
+) -> Query {
+    let depth = max_depth.clamp(1, MAX_TRAVERSAL_DEPTH);
+    let types: Vec<String> = relationship_filter
+        .unwrap_or_default()
+        .iter()
+        .map(|t| t.to_lowercase())
+        .collect();
+
+    let cypher = format!(
+        r#"
+        MATCH p = (s)-[*1..{depth}]->(t)
+        WHERE toLower(coalesce(s.name, s.content, s.id)) = toLower($start)
+          AND s <> t
+          AND (size($types) = 0
+               OR all(r IN relationships(p) WHERE toLower(type(r)) IN $types))
+          AND ($user_id = ''
+               OR all(n IN nodes(p) WHERE n.user_id IS NULL OR n.user_id = $user_id))
+        RETURN [n IN nodes(p) | coalesce(n.name, n.content, n.id)] AS nodes,
+               [r IN relationships(p) | type(r)] AS relationships
+        ORDER BY length(p)
+        LIMIT $limit
+        "#
+    );
+
+    query(&cypher)
+        .param("start", start_entity.to_string())
+        .param("types", types)
+        .param("user_id", filters.user_id.clone().unwrap_or_default())
+        .param("limit", TRAVERSAL_PATH_LIMIT)
+}
+
+/// Convert a row returned by [`traversal_query`] into a path.
+pub(crate) fn row_to_path(row: &Row) -> Option<GraphPath> {
+    let nodes: Vec<String> = row.get("nodes").unwrap_or_default();
+    let relationships: Vec<String> = row.get("relationships").unwrap_or_default();
+    GraphPath::from_parts(nodes, relationships)
+}
+
+/// Keep the first (shortest) path to each end entity.
+pub(crate) fn shortest_per_end(paths: Vec<GraphPath>) -> Vec<GraphPath> {
+    let mut seen = HashSet::new();
+    paths
+        .into_iter()
+        .filter(|path| seen.insert(path.end().to_string()))
+        .collect()
+}
@@ -0,0 +1,148 @@
+//! Spreading activation over the embedded graph store.
+//!
+//! Memories are linked to the entities they mention. Activation starts at
+//! the entities of the seed memories, spreads along current relationships,
+//! and flows back to the other memories linked to the entities it reaches.
+//! The store serves this to retrieval as an [`ActivationGraph`].
+
+use std::collections::{HashMap, HashSet};
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::retrieval::{
+    spread_activation_by_id, ActivatedMemory, ActivationGraph, SpreadingConfig,
+};
+
+use super::{sync, EmbeddedGraphStore};
+
+impl EmbeddedGraphStore {
+    /// Spread activation from seed memories to related memories.
+    ///
+    /// A memory reached through an entity gets the entity's activation,
+    /// decayed by one more hop and divided by the fan-out penalty of the
+    /// memories linked to that entity. Seed memories are left out.
+    pub fn spread_from_memories(
+        &self,
+        seeds: &[(String, f32)],
+        config: &SpreadingConfig,
+    ) -> RookResult<Vec<ActivatedMemory>> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        // Seed each entity with the strongest memory mentioning it
+        let mut entity_seeds: HashMap<String, f32> = HashMap::new();
+        let mut node_index = HashMap::new();
+        for (memory_id, activation) in seeds {
+            for entity_id in sync::get_entities_for_memory(&conn, memory_id)? {
+                let Some(&idx) = db_id_index.get(&entity_id) else {
+                    continue;
+                };
+                let key = entity_id.to_string();
+                node_index.insert(key.clone(), idx);
+                let seed = entity_seeds.entry(key).or_insert(0.0);
+                *seed = seed.max(*activation);
+            }
+        }
+        if entity_seeds.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entity_seeds: Vec<_> = entity_seeds.into_iter().collect();
+        let entities = spread_activation_by_id(
+            &graph,
+            &node_index,
+            &entity_seeds,
+            config,
+            |node| node.db_id.to_string(),
+            |edge| if edge.is_current() { edge.weight as f32 } else { 0.0 },
+        );
+
+        let seed_ids: HashSet<&str> = seeds.iter().map(|(id, _)| id.as_str()).collect();
+        let mut memories: HashMap<String, ActivatedMemory> = HashMap::new();
+        for entity in entities {
+            let Ok(entity_id) = entity.memory_id.parse::<i64>() else {
+                continue;
+            };
+            let linked = sync::get_memories_for_entity(&conn, entity_id)?;
+            let fan_out_factor = 1.0 / (1.0 + config.fan_out_penalty * linked.len() as f32);
+            let activation = entity.activation * config.decay_factor * fan_out_factor;
+            if activation < config.firing_threshold {
+                continue;
+            }
+            for memory_id in linked {
+                if seed_ids.contains(memory_id.as_str()) {
+                    continue;
+                }
+                let memory = memories.entry(memory_id.clone()).or_insert(ActivatedMemory {
+                    memory_id,
+                    activation: 0.0,
+                    depth: entity.depth + 1,
+                });
+                if activation > memory.activation {
+                    memory.activation = activation;
+                    memory.depth = entity.depth + 1;
+                }
+            }
+        }
+
+        let mut memories: Vec<_> = memories.into_values().collect();
+        memories.sort_by(|a, b| b.activation.total_cmp(&a.activation));
+        Ok(memories)
+    }
+}
+
+impl ActivationGraph for EmbeddedGraphStore {
+    fn spread_from_seeds(
+        &self,
+        seeds: &[(String, f32)],
+        config: &SpreadingConfig,
+    ) -> Vec<ActivatedMemory> {
+        match self.spread_from_memories(seeds, config) {
+            Ok(memories) => memories,
+            Err(e) => {
+                tracing::warn!("Failed to spread activation over the graph: {}", e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rook_core::traits::GraphFilters;
+
+    #[test]
+    fn test_spread_from_memories() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let filters = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::json!({});
+        store.add_relationship("Alice", "Acme", "works_at", &json, &filters).unwrap();
+
+        let alice = store.add_entity("Alice", "person", &json, &filters).unwrap();
+        let acme = store.add_entity("Acme", "organization", &json, &filters).unwrap();
+        let chess = store.add_entity("Chess", "hobby", &json, &filters).unwrap();
+        {
+            let conn = store.conn.lock().unwrap();
+            sync::link_memory_to_entity(&conn, "mem-1", alice, "subject").unwrap();
+            sync::link_memory_to_entity(&conn, "mem-2", alice, "subject").unwrap();
+            sync::link_memory_to_entity(&conn, "mem-3", acme, "mentioned").unwrap();
+            sync::link_memory_to_entity(&conn, "mem-4", chess, "mentioned").unwrap();
+        }
+
+        let activated = store.spread_from_seeds(&[("mem-1".to_string(), 1.0)], &SpreadingConfig::default());
+        let ids: Vec<_> = activated.iter().map(|m| m.memory_id.as_str()).collect();
+
+        // Same entity first, then one relationship away; unrelated and seed left out
+        assert_eq!(ids, vec!["mem-2", "mem-3"]);
+        assert!(activated[0].activation > activated[1].activation);
+        assert_eq!(activated[1].depth, 2);
+
+        assert!(store
+            .spread_from_seeds(&[("mem-9".to_string(), 1.0)], &SpreadingConfig::default())
+            .is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! Topic clusters for the embedded graph store.
+//!
+//! [`EmbeddedGraphStore::detect_communities`] clusters the entities of a
+//! scope; [`EmbeddedGraphStore::categorize_communities`] turns each cluster
+//! into an auto-generated `topic: ...` category and links every memory that
+//! mentions a member entity to it.
+
+use std::collections::{BTreeSet, HashMap};
+
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::GraphFilters;
+
+use super::{sync, EmbeddedGraphStore};
+use crate::category::CategoryNode;
+use crate::graph_analytics::{detect_communities, modularity, CommunityConfig, WeightedGraph};
+
+/// Name prefix of auto-generated topic categories.
+pub const TOPIC_CATEGORY_PREFIX: &str = "topic: ";
+
+/// Members named in a topic category's name.
+const TOPIC_NAME_MEMBERS: usize = 3;
+
+/// Entity types that are bookkeeping rather than knowledge.
+const EXCLUDED_TYPES: &[&str] = &["category", "memory"];
+
+/// A cluster of closely related entities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCluster {
+    /// Generated name, from the best-connected members.
+    pub name: String,
+    /// Member entity IDs, best-connected first.
+    pub entity_ids: Vec<i64>,
+    /// Member entity names, in the same order.
+    pub entity_names: Vec<String>,
+    /// Memories mentioning any member.
+    pub memory_ids: Vec<String>,
+    /// Modularity of the whole partition (same for every cluster of a run).
+    pub modularity: f64,
+}
+
+impl EmbeddedGraphStore {
+    /// Cluster the entities within `filters` into topics.
+    ///
+    /// Only current relationships count, weighted by their edge weight.
+    /// Category and memory nodes are left out. Clusters are returned largest first.
+    pub fn detect_communities(
+        &self,
+        filters: &GraphFilters,
+        config: &CommunityConfig,
+    ) -> RookResult<Vec<TopicCluster>> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let nodes: Vec<NodeIndex> = graph
+            .node_indices()
+            .filter(|&idx| {
+                let node = &graph[idx];
+                !EXCLUDED_TYPES.contains(&node.entity_type.as_str())
+                    && node.matches_filters(
+                        filters.user_id.as_deref(),
+                        filters.agent_id.as_deref(),
+                        filters.run_id.as_deref(),
+                    )
+            })
+            .collect();
+        let positions: HashMap<NodeIndex, usize> =
+            nodes.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+
+        let mut weighted = WeightedGraph::new(nodes.len());
+        for edge_idx in graph.edge_indices() {
+            let edge = &graph[edge_idx];
+            let Some((source, target)) = graph.edge_endpoints(edge_idx) else {
+                continue;
+            };
+            if let (true, Some(&a), Some(&b)) =
+                (edge.is_current(), positions.get(&source), positions.get(&target))
+            {
+                weighted.add_edge(a, b, edge.weight.max(0.0));
+            }
+        }
+
+        let groups = detect_communities(&weighted, config);
+        let mut assignment: Vec<usize> = (0..nodes.len()).map(|i| groups.len() + i).collect();
+        for (community, members) in groups.iter().enumerate() {
+            for &member in members {
+                assignment[member] = community;
+            }
+        }
+        let score = modularity(&weighted, &assignment, config.resolution);
+
+        let mut clusters = Vec::with_capacity(groups.len());
+        for mut members in groups {
+            members.sort_by(|&a, &b| {
+                weighted
+                    .degree(b)
+                    .partial_cmp(&weighted.degree(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| graph[nodes[a]].name.cmp(&graph[nodes[b]].name))
+            });
+
+            let entity_ids: Vec<i64> = members.iter().map(|&m| graph[nodes[m]].db_id).collect();
+            let entity_names: Vec<String> =
+                members.iter().map(|&m| graph[nodes[m]].name.clone()).collect();
+            let mut memory_ids = BTreeSet::new();
+            for &entity_id in &entity_ids {
+                memory_ids.extend(sync::get_memories_for_entity(&conn, entity_id)?);
+            }
+
+            clusters.push(TopicCluster {
+                name: format!(
+                    "{}{}",
+                    TOPIC_CATEGORY_PREFIX,
+                    entity_names[..entity_names.len().min(TOPIC_NAME_MEMBERS)].join(", ")
+                ),
+                entity_ids,
+                entity_names,
+                memory_ids: memory_ids.into_iter().collect(),
+                modularity: score,
+            });
+        }
+
+        Ok(clusters)
+    }
+
+    /// Detect topic clusters and store them as categories.
+    ///
+    /// Topic categories from earlier runs in the same scope are replaced.
+    /// Each memory mentioning a member entity is linked to its topic.
+    pub fn categorize_communities(
+        &self,
+        filters: &GraphFilters,
+        config: &CommunityConfig,
+    ) -> RookResult<Vec<TopicCluster>> {
+        self.remove_topic_categories(filters)?;
+        let clusters = self.detect_communities(filters, config)?;
+
+        for cluster in &clusters {
+            let description = format!("Auto-generated topic: {}", cluster.entity_names.join(", "));
+            self.add_category(&CategoryNode::new(&cluster.name, description), filters)?;
+            for memory_id in &cluster.memory_ids {
+                self.link_memory_to_category(memory_id, &cluster.name, filters)?;
+            }
+        }
+
+        Ok(clusters)
+    }
+
+    /// Delete auto-generated topic categories in exactly this scope.
+    fn remove_topic_categories(&self, filters: &GraphFilters) -> RookResult<()> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let stale: Vec<i64> = graph
+            .node_weights()
+            .filter(|node| {
+                node.entity_type == "category"
+                    && node.name.starts_with(TOPIC_CATEGORY_PREFIX)
+                    && node.user_id == filters.user_id
+                    && node.agent_id == filters.agent_id
+                    && node.run_id == filters.run_id
+            })
+            .map(|node| node.db_id)
+            .collect();
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        for entity_id in stale {
+            sync::delete_entity(&tx, entity_id)?;
+        }
+        tx.commit()?;
+
+        sync::load_graph(&conn, &mut graph, &mut db_id_index, &mut name_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categorize_communities() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let filters = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::json!({});
+        let acme = store.add_entity("Acme", "organization", &json, &filters).unwrap();
+
+        // Work cluster and hobby cluster, joined by one relationship
+        for (source, target, rel) in [
+            ("Alice", "Acme", "works_at"),
+            ("Alice", "Rust", "uses"),
+            ("Acme", "Rust", "uses"),
+            ("Acme", "Bob", "employs"),
+            ("Bob", "Rust", "uses"),
+            ("Tennis", "Club", "played_at"),
+            ("Carol", "Tennis", "plays"),
+            ("Carol", "Club", "member_of"),
+            ("Dave", "Tennis", "plays"),
+            ("Dave", "Club", "member_of"),
+            ("Bob", "Carol", "knows"),
+        ] {
+            store.add_relationship(source, target, rel, &json, &filters).unwrap();
+        }
+        {
+            let conn = store.conn.lock().unwrap();
+            sync::link_memory_to_entity(&conn, "mem-1", acme, "mentioned").unwrap();
+        }
+
+        let clusters = store.categorize_communities(&filters, &CommunityConfig::default()).unwrap();
+        assert_eq!(clusters.len(), 2);
+        let work = clusters.iter().find(|c| c.entity_names.contains(&"Acme".to_string())).unwrap();
+        assert!(work.entity_names.contains(&"Rust".to_string()));
+        assert!(!work.entity_names.contains(&"Tennis".to_string()));
+        assert!(work.name.starts_with(TOPIC_CATEGORY_PREFIX));
+        assert_eq!(work.memory_ids, vec!["mem-1"]);
+        assert!(work.modularity > 0.0);
+
+        assert_eq!(store.get_memories_in_category(&work.name, &filters).unwrap(), vec!["mem-1"]);
+        assert_eq!(
+            store.get_categories_for_memory("mem-1", &filters).unwrap(),
+            vec![work.name.clone()]
+        );
+
+        // Re-running replaces the previous topics instead of adding more
+        store.categorize_communities(&filters, &CommunityConfig::default()).unwrap();
+        let topics = store
+            .get_all_categories(&filters)
+            .unwrap()
+            .into_iter()
+            .filter(|c| c.starts_with(TOPIC_CATEGORY_PREFIX))
+            .count();
+        assert_eq!(topics, 2);
+    }
+}
@@ -0,0 +1,379 @@
+//! Entity deduplication for the embedded graph store.
+//!
+//! Extraction merges new entities against existing ones as they arrive, but
+//! near-duplicates ("Bob", "Bob Smith", "bob smith") still accumulate: the
+//! entity may be created from a relationship without an embedding, or two
+//! variants may arrive in the same batch. [`EmbeddedGraphStore::deduplicate_entities`]
+//! sweeps each user/agent/run scope, folds duplicates into one canonical
+//! entity and records every merge in `entity_merges`.
+//!
+//! [`EntityDedupJob`] runs the sweep as a [`MaintenanceJob`] on the
+//! `BackgroundRuntime`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::MaintenanceJob;
+
+use super::{sync, EmbeddedGraphStore};
+use crate::entity::{cosine_similarity, MergeConfig};
+
+/// Entity type given to entities created implicitly from a relationship.
+/// It matches any type when `require_same_type` is set.
+const GENERIC_ENTITY_TYPE: &str = "entity";
+
+/// A duplicate entity folded into a canonical one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMerge {
+    /// Merge history ID.
+    pub id: i64,
+    /// Entity that was kept.
+    pub canonical_id: i64,
+    pub canonical_name: String,
+    /// Entity that was merged and deleted.
+    pub merged_id: i64,
+    pub merged_name: String,
+    /// Embedding similarity (1.0 for names equal up to case and spacing).
+    pub similarity: f32,
+    /// Relationships rewired onto the canonical entity.
+    pub relationships_moved: usize,
+    pub merged_at: DateTime<Utc>,
+}
+
+/// An entity considered for deduplication.
+#[derive(Debug, Clone)]
+pub(crate) struct DedupCandidate {
+    pub id: i64,
+    pub name: String,
+    pub entity_type: String,
+    pub embedding: Option<Vec<f32>>,
+    /// Number of relationships; better-connected entities are kept.
+    pub degree: usize,
+}
+
+/// Pairs `(canonical, duplicate, similarity)` of indexes into `candidates`,
+/// which must all belong to the same scope.
+///
+/// Candidates are visited most connected first (then longest name, then
+/// oldest), and each one not yet absorbed absorbs the later candidates that
+/// match it. Names equal up to case and spacing always match; otherwise both
+/// entities need embeddings at or above the similarity threshold.
+pub(crate) fn find_duplicates(
+    candidates: &[DedupCandidate],
+    config: &MergeConfig,
+) -> Vec<(usize, usize, f32)> {
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&candidates[a], &candidates[b]);
+        b.degree
+            .cmp(&a.degree)
+            .then(b.name.len().cmp(&a.name.len()))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let names: Vec<String> = candidates.iter().map(|c| normalize_name(&c.name)).collect();
+    let mut absorbed = vec![false; candidates.len()];
+    let mut pairs = Vec::new();
+
+    for (position, &keep) in order.iter().enumerate() {
+        if absorbed[keep] {
+            continue;
+        }
+        for &other in &order[position + 1..] {
+            if absorbed[other] || !types_compatible(&candidates[keep], &candidates[other], config) {
+                continue;
+            }
+
+            let similarity = if names[keep] == names[other] {
+                Some(1.0)
+            } else {
+                match (&candidates[keep].embedding, &candidates[other].embedding) {
+                    (Some(a), Some(b)) => Some(cosine_similarity(a, b)),
+                    _ => None,
+                }
+            };
+
+            if let Some(similarity) = similarity {
+                if similarity >= config.similarity_threshold {
+                    absorbed[other] = true;
+                    pairs.push((keep, other, similarity));
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+fn types_compatible(a: &DedupCandidate, b: &DedupCandidate, config: &MergeConfig) -> bool {
+    !config.require_same_type
+        || a.entity_type.eq_ignore_ascii_case(&b.entity_type)
+        || a.entity_type.eq_ignore_ascii_case(GENERIC_ENTITY_TYPE)
+        || b.entity_type.eq_ignore_ascii_case(GENERIC_ENTITY_TYPE)
+}
+
+/// Lowercase, with underscores as spaces and runs of whitespace collapsed.
+fn normalize_name(name: &str) -> String {
+    name.replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Record `merged_name` (and its own aliases) as aliases of the canonical
+/// entity, and fill in an embedding or description it lacks.
+fn absorb_properties(
+    canonical: &mut serde_json::Value,
+    merged_name: &str,
+    merged: &serde_json::Value,
+) {
+    if !canonical.is_object() {
+        *canonical = serde_json::json!({});
+    }
+    let Some(canonical) = canonical.as_object_mut() else {
+        return;
+    };
+
+    let mut aliases: Vec<serde_json::Value> = canonical
+        .get("aliases")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let merged_aliases = merged
+        .get("aliases")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for alias in std::iter::once(serde_json::Value::from(merged_name)).chain(merged_aliases) {
+        if !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+    canonical.insert("aliases".to_string(), aliases.into());
+
+    for key in ["embedding", "description"] {
+        let missing = matches!(canonical.get(key), None | Some(serde_json::Value::Null));
+        if let Some(value) = merged.get(key).filter(|v| !v.is_null()) {
+            if missing {
+                canonical.insert(key.to_string(), value.clone());
+            }
+        }
+    }
+}
+
+type Scope = (Option<String>, Option<String>, Option<String>);
+
+impl EmbeddedGraphStore {
+    /// Merge near-duplicate entities within each user/agent/run scope.
+    ///
+    /// Relationships and memory links of a duplicate move to the entity it is
+    /// merged into, and its name is kept as an alias so later relationships
+    /// naming it resolve to the canonical entity. Returns the merges made.
+    pub fn deduplicate_entities(&self, config: &MergeConfig) -> RookResult<Vec<EntityMerge>> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let mut scopes: HashMap<Scope, Vec<DedupCandidate>> = HashMap::new();
+        let mut properties: HashMap<i64, serde_json::Value> = HashMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            let degree = graph.edges_directed(idx, Direction::Outgoing).count()
+                + graph.edges_directed(idx, Direction::Incoming).count();
+            let embedding = node
+                .properties
+                .get("embedding")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_f64().map(|f| f as f32)).collect());
+
+            properties.insert(node.db_id, node.properties.clone());
+            scopes
+                .entry((node.user_id.clone(), node.agent_id.clone(), node.run_id.clone()))
+                .or_default()
+                .push(DedupCandidate {
+                    id: node.db_id,
+                    name: node.name.clone(),
+                    entity_type: node.entity_type.clone(),
+                    embedding,
+                    degree,
+                });
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        let mut merges = Vec::new();
+        for candidates in scopes.values() {
+            for (keep, duplicate, similarity) in find_duplicates(candidates, config) {
+                let (canonical, merged) = (&candidates[keep], &candidates[duplicate]);
+                let merged_properties = properties.remove(&merged.id).unwrap_or_default();
+                let canonical_properties = properties.entry(canonical.id).or_default();
+                absorb_properties(canonical_properties, &merged.name, &merged_properties);
+
+                let relationships_moved =
+                    sync::merge_entity(&tx, canonical.id, merged.id, canonical_properties)?;
+                let mut merge = EntityMerge {
+                    id: 0,
+                    canonical_id: canonical.id,
+                    canonical_name: canonical.name.clone(),
+                    merged_id: merged.id,
+                    merged_name: merged.name.clone(),
+                    similarity,
+                    relationships_moved,
+                    merged_at: Utc::now(),
+                };
+                merge.id = sync::save_entity_merge(&tx, &merge)?;
+                tracing::debug!(
+                    "Merged entity '{}' into '{}' (similarity: {:.3})",
+                    merge.merged_name,
+                    merge.canonical_name,
+                    similarity
+                );
+                merges.push(merge);
+            }
+        }
+        tx.commit()?;
+
+        if !merges.is_empty() {
+            sync::load_graph(&conn, &mut graph, &mut db_id_index, &mut name_index)?;
+        }
+
+        Ok(merges)
+    }
+
+    /// Most recent entity merges, newest first.
+    pub fn merge_history(&self, limit: usize) -> RookResult<Vec<EntityMerge>> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        sync::get_entity_merges(&conn, limit)
+    }
+}
+
+/// Periodically merges duplicate entities in an [`EmbeddedGraphStore`].
+///
+/// # Example
+///
+/// ```ignore
+/// let job = EntityDedupJob::new(store.clone(), MergeConfig::default());
+/// let runtime = BackgroundRuntime::new(config)
+///     .await?
+///     .with_maintenance_job(Arc::new(job), 60);
+/// ```
+pub struct EntityDedupJob {
+    store: Arc<EmbeddedGraphStore>,
+    config: MergeConfig,
+}
+
+impl EntityDedupJob {
+    pub fn new(store: Arc<EmbeddedGraphStore>, config: MergeConfig) -> Self {
+        Self { store, config }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for EntityDedupJob {
+    fn name(&self) -> &str {
+        "entity_dedup"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        let store = self.store.clone();
+        let config = self.config.clone();
+        let merges = tokio::task::spawn_blocking(move || store.deduplicate_entities(&config))
+            .await
+            .map_err(|e| RookError::internal(format!("Entity dedup task failed: {}", e)))??;
+        Ok(merges.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rook_core::traits::GraphFilters;
+
+    fn candidate(id: i64, name: &str, embedding: Option<Vec<f32>>, degree: usize) -> DedupCandidate {
+        DedupCandidate {
+            id,
+            name: name.to_string(),
+            entity_type: "person".to_string(),
+            embedding,
+            degree,
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_prefers_connected_entity() {
+        let candidates = vec![
+            candidate(1, "Bob", Some(vec![1.0, 0.1, 0.0]), 1),
+            candidate(2, "Bob Smith", Some(vec![1.0, 0.0, 0.0]), 3),
+            candidate(3, "bob_smith", None, 0),
+            candidate(4, "Alice", Some(vec![0.0, 1.0, 0.0]), 5),
+        ];
+
+        let pairs = find_duplicates(&candidates, &MergeConfig::default());
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.iter().all(|&(keep, _, _)| keep == 1));
+        let merged: Vec<i64> = pairs.iter().map(|&(_, dup, _)| candidates[dup].id).collect();
+        assert!(merged.contains(&1) && merged.contains(&3));
+    }
+
+    #[test]
+    fn test_find_duplicates_respects_types() {
+        let mut acme_person = candidate(1, "Acme", None, 0);
+        acme_person.entity_type = "person".to_string();
+        let mut acme_org = candidate(2, "acme", None, 0);
+        acme_org.entity_type = "organization".to_string();
+        let mut acme_generic = candidate(3, "ACME", None, 0);
+        acme_generic.entity_type = GENERIC_ENTITY_TYPE.to_string();
+
+        let pairs = find_duplicates(&[acme_person.clone(), acme_org.clone()], &MergeConfig::default());
+        assert!(pairs.is_empty());
+
+        let pairs = find_duplicates(&[acme_org, acme_generic], &MergeConfig::default());
+        assert_eq!(pairs.len(), 1);
+    }
+
+    #[test]
+    fn test_deduplicate_entities() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let alice = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let bob = GraphFilters {
+            user_id: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::json!({});
+
+        store.add_relationship("Bob Smith", "Acme", "works_at", &json, &alice).unwrap();
+        store.add_relationship("Bob Smith", "Tennis", "likes", &json, &alice).unwrap();
+        store.add_relationship("bob smith", "Berlin", "lives_in", &json, &alice).unwrap();
+        // Same name in another scope is left alone
+        store.add_relationship("bob smith", "Paris", "lives_in", &json, &bob).unwrap();
+
+        let merges = store.deduplicate_entities(&MergeConfig::default()).unwrap();
+        assert_eq!(merges.len(), 1);
+        assert_eq!(merges[0].canonical_name, "Bob Smith");
+        assert_eq!(merges[0].merged_name, "bob smith");
+        assert_eq!(merges[0].relationships_moved, 1);
+
+        let neighbors = store.get_neighbors("Bob Smith", &alice).unwrap();
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(store.get_neighbors("bob smith", &bob).unwrap().len(), 1);
+
+        // The alias resolves to the canonical entity
+        store.add_relationship("bob smith", "Chess", "likes", &json, &alice).unwrap();
+        assert_eq!(store.get_neighbors("Bob Smith", &alice).unwrap().len(), 4);
+
+        let history = store.merge_history(10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, merges[0].id);
+        assert!(store.deduplicate_entities(&MergeConfig::default()).unwrap().is_empty());
+    }
+}
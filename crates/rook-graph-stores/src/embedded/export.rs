@@ -0,0 +1,319 @@
+//! Graph export for visualization tools.
+//!
+//! The current entity/relationship graph of a user/agent/run scope can be
+//! written as GraphML (Gephi, yEd, Cytoscape), DOT (Graphviz) or a JSON
+//! node-link document (D3's `forceSimulation`, NetworkX `node_link_graph`).
+//! Invalidated relationships and entity embeddings are left out.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::GraphFilters;
+
+use super::EmbeddedGraphStore;
+
+/// Output format for [`EmbeddedGraphStore::export_graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphExportFormat {
+    /// GraphML XML.
+    GraphMl,
+    /// Graphviz DOT.
+    Dot,
+    /// JSON node-link document.
+    Json,
+}
+
+impl GraphExportFormat {
+    /// Parse a format name (`graphml`, `dot`/`gv`, `json`).
+    pub fn parse(s: &str) -> RookResult<Self> {
+        match s.to_lowercase().as_str() {
+            "graphml" => Ok(GraphExportFormat::GraphMl),
+            "dot" | "gv" => Ok(GraphExportFormat::Dot),
+            "json" => Ok(GraphExportFormat::Json),
+            other => Err(RookError::validation(format!(
+                "Unknown graph export format '{}'",
+                other
+            ))),
+        }
+    }
+
+    /// MIME type of the exported document.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            GraphExportFormat::GraphMl => "application/graphml+xml",
+            GraphExportFormat::Dot => "text/vnd.graphviz",
+            GraphExportFormat::Json => "application/json",
+        }
+    }
+}
+
+/// An entity in a JSON node-link export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportNode {
+    /// Entity database ID.
+    pub id: i64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub entity_type: String,
+    /// Entity properties without the embedding.
+    pub properties: serde_json::Value,
+}
+
+/// A relationship in a JSON node-link export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportLink {
+    /// Source entity ID.
+    pub source: i64,
+    /// Target entity ID.
+    pub target: i64,
+    pub relationship: String,
+    pub weight: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<DateTime<Utc>>,
+}
+
+/// A JSON node-link graph document.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeLinkGraph {
+    pub directed: bool,
+    pub nodes: Vec<ExportNode>,
+    pub links: Vec<ExportLink>,
+}
+
+impl EmbeddedGraphStore {
+    /// Snapshot of the current graph within `filters` as a node-link document.
+    pub fn export_node_link(&self, filters: &GraphFilters) -> RookResult<NodeLinkGraph> {
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let mut node_ids = HashMap::new();
+        let mut nodes = Vec::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            if !node.matches_filters(
+                filters.user_id.as_deref(),
+                filters.agent_id.as_deref(),
+                filters.run_id.as_deref(),
+            ) {
+                continue;
+            }
+
+            let mut properties = node.properties.clone();
+            if let Some(map) = properties.as_object_mut() {
+                map.remove("embedding");
+            }
+            node_ids.insert(idx, node.db_id);
+            nodes.push(ExportNode {
+                id: node.db_id,
+                name: node.name.clone(),
+                entity_type: node.entity_type.clone(),
+                properties,
+            });
+        }
+
+        let links = graph
+            .edge_indices()
+            .filter_map(|edge_idx| {
+                let edge = &graph[edge_idx];
+                let (source, target) = graph.edge_endpoints(edge_idx)?;
+                if !edge.is_current() {
+                    return None;
+                }
+                Some(ExportLink {
+                    source: *node_ids.get(&source)?,
+                    target: *node_ids.get(&target)?,
+                    relationship: edge.relationship_type.clone(),
+                    weight: edge.weight,
+                    valid_from: edge.valid_from,
+                })
+            })
+            .collect();
+
+        Ok(NodeLinkGraph {
+            directed: true,
+            nodes,
+            links,
+        })
+    }
+
+    /// Serialize the current graph within `filters` in the given format.
+    pub fn export_graph(
+        &self,
+        filters: &GraphFilters,
+        format: GraphExportFormat,
+    ) -> RookResult<String> {
+        let graph = self.export_node_link(filters)?;
+        match format {
+            GraphExportFormat::GraphMl => Ok(to_graphml(&graph)),
+            GraphExportFormat::Dot => Ok(to_dot(&graph)),
+            GraphExportFormat::Json => Ok(serde_json::to_string_pretty(&graph)?),
+        }
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn to_graphml(graph: &NodeLinkGraph) -> String {
+    let mut out = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="name" for="node" attr.name="name" attr.type="string"/>
+  <key id="type" for="node" attr.name="type" attr.type="string"/>
+  <key id="properties" for="node" attr.name="properties" attr.type="string"/>
+  <key id="relationship" for="edge" attr.name="relationship" attr.type="string"/>
+  <key id="weight" for="edge" attr.name="weight" attr.type="double"/>
+  <key id="valid_from" for="edge" attr.name="valid_from" attr.type="string"/>
+  <graph id="rook" edgedefault="directed">
+"#,
+    );
+
+    for node in &graph.nodes {
+        let _ = writeln!(out, r#"    <node id="n{}">"#, node.id);
+        let _ = writeln!(out, r#"      <data key="name">{}</data>"#, escape_xml(&node.name));
+        let _ = writeln!(
+            out,
+            r#"      <data key="type">{}</data>"#,
+            escape_xml(&node.entity_type)
+        );
+        if node.properties.as_object().is_some_and(|m| !m.is_empty()) {
+            let _ = writeln!(
+                out,
+                r#"      <data key="properties">{}</data>"#,
+                escape_xml(&node.properties.to_string())
+            );
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for (i, link) in graph.links.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            r#"    <edge id="e{}" source="n{}" target="n{}">"#,
+            i, link.source, link.target
+        );
+        let _ = writeln!(
+            out,
+            r#"      <data key="relationship">{}</data>"#,
+            escape_xml(&link.relationship)
+        );
+        let _ = writeln!(out, r#"      <data key="weight">{}</data>"#, link.weight);
+        if let Some(valid_from) = link.valid_from {
+            let _ = writeln!(
+                out,
+                r#"      <data key="valid_from">{}</data>"#,
+                valid_from.to_rfc3339()
+            );
+        }
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn to_dot(graph: &NodeLinkGraph) -> String {
+    let mut out = String::from("digraph rook {\n");
+    for node in &graph.nodes {
+        let _ = writeln!(
+            out,
+            "  n{} [label=\"{}\", type=\"{}\"];",
+            node.id,
+            escape_dot(&node.name),
+            escape_dot(&node.entity_type)
+        );
+    }
+    for link in &graph.links {
+        let _ = writeln!(
+            out,
+            "  n{} -> n{} [label=\"{}\", weight={}];",
+            link.source,
+            link.target,
+            escape_dot(&link.relationship),
+            link.weight
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> (EmbeddedGraphStore, GraphFilters) {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let alice = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let bob = GraphFilters {
+            user_id: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::json!({});
+
+        store
+            .add_entity(
+                "Tom & \"Jerry\"",
+                "person",
+                &serde_json::json!({"embedding": [0.1, 0.2], "role": "cat"}),
+                &alice,
+            )
+            .unwrap();
+        store.add_relationship("Tom & \"Jerry\"", "Acme", "works_at", &json, &alice).unwrap();
+        store.add_relationship("Tom & \"Jerry\"", "Berlin", "lives_in", &json, &alice).unwrap();
+        store.invalidate_relationship("Tom & \"Jerry\"", "Berlin", "lives_in", &alice).unwrap();
+        store.add_relationship("Bob", "Paris", "lives_in", &json, &bob).unwrap();
+        (store, alice)
+    }
+
+    #[test]
+    fn test_export_node_link_is_scoped() {
+        let (store, alice) = store();
+        let graph = store.export_node_link(&alice).unwrap();
+
+        let names: Vec<_> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names.len(), 3);
+        assert!(!names.contains(&"Bob"));
+        assert_eq!(graph.links.len(), 1, "invalidated relationships are left out");
+        assert_eq!(graph.links[0].relationship, "works_at");
+
+        let tom = graph.nodes.iter().find(|n| n.entity_type == "person").unwrap();
+        assert!(tom.properties.get("embedding").is_none());
+        assert_eq!(tom.properties["role"], "cat");
+    }
+
+    #[test]
+    fn test_export_graphml_and_dot_escape_names() {
+        let (store, alice) = store();
+
+        let graphml = store.export_graph(&alice, GraphExportFormat::GraphMl).unwrap();
+        assert!(graphml.contains("Tom &amp; &quot;Jerry&quot;"));
+        assert_eq!(graphml.matches("<edge ").count(), 1);
+
+        let dot = store.export_graph(&alice, GraphExportFormat::Dot).unwrap();
+        assert!(dot.starts_with("digraph rook {"));
+        assert!(dot.contains(r#"label="Tom & \"Jerry\"""#));
+        assert!(dot.contains("[label=\"works_at\", weight=1]"));
+
+        let json = store.export_graph(&alice, GraphExportFormat::Json).unwrap();
+        let parsed: NodeLinkGraph = serde_json::from_str(&json).unwrap();
+        assert!(parsed.directed);
+        assert_eq!(GraphExportFormat::parse("GV").unwrap(), GraphExportFormat::Dot);
+        assert!(GraphExportFormat::parse("csv").is_err());
+    }
+}
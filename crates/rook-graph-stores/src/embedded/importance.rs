@@ -0,0 +1,222 @@
+//! Entity importance for the embedded graph store.
+//!
+//! [`EmbeddedGraphStore::compute_importance`] scores every entity by its
+//! centrality within its user/agent/run scope and stores the score in the
+//! entity's `importance` property. A memory's importance is that of the most
+//! central entity it mentions; the store serves it to Precise retrieval as an
+//! [`ImportanceProvider`].
+//!
+//! [`EntityImportanceJob`] recomputes the scores as a [`MaintenanceJob`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use petgraph::graph::NodeIndex;
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::retrieval::ImportanceProvider;
+use rook_core::traits::GraphFilters;
+use rook_core::MaintenanceJob;
+
+use super::{sync, EmbeddedGraphStore};
+use crate::graph_analytics::{importance_scores, ImportanceConfig, WeightedGraph};
+
+/// Entity property holding the importance score.
+pub const IMPORTANCE_PROPERTY: &str = "importance";
+
+/// Entity types that are bookkeeping rather than knowledge.
+const EXCLUDED_TYPES: &[&str] = &["category", "memory"];
+
+type Scope = (Option<String>, Option<String>, Option<String>);
+
+impl EmbeddedGraphStore {
+    /// Score every entity by centrality within its scope and store the score.
+    ///
+    /// Only current relationships count. Category and memory nodes are not
+    /// scored. Returns the number of entities scored.
+    pub fn compute_importance(&self, config: &ImportanceConfig) -> RookResult<usize> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let mut scopes: HashMap<Scope, Vec<NodeIndex>> = HashMap::new();
+        for idx in graph.node_indices() {
+            let node = &graph[idx];
+            if !EXCLUDED_TYPES.contains(&node.entity_type.as_str()) {
+                scopes
+                    .entry((node.user_id.clone(), node.agent_id.clone(), node.run_id.clone()))
+                    .or_default()
+                    .push(idx);
+            }
+        }
+
+        let mut scored = Vec::new();
+        for nodes in scopes.values() {
+            let positions: HashMap<NodeIndex, usize> =
+                nodes.iter().enumerate().map(|(i, &idx)| (idx, i)).collect();
+            let mut weighted = WeightedGraph::new(nodes.len());
+            for edge_idx in graph.edge_indices() {
+                let edge = &graph[edge_idx];
+                let Some((source, target)) = graph.edge_endpoints(edge_idx) else {
+                    continue;
+                };
+                if let (true, Some(&a), Some(&b)) =
+                    (edge.is_current(), positions.get(&source), positions.get(&target))
+                {
+                    weighted.add_edge(a, b, edge.weight.max(0.0));
+                }
+            }
+
+            let scores = importance_scores(&weighted, config);
+            scored.extend(nodes.iter().copied().zip(scores));
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        for &(idx, score) in &scored {
+            let node = &mut graph[idx];
+            if !node.properties.is_object() {
+                node.properties = serde_json::json!({});
+            }
+            node.properties[IMPORTANCE_PROPERTY] = serde_json::json!(score);
+            sync::update_entity_properties(&tx, node.db_id, &node.properties)?;
+        }
+        tx.commit()?;
+
+        Ok(scored.len())
+    }
+
+    /// Stored importance of an entity, if it has been scored.
+    pub fn entity_importance(&self, name: &str, filters: &GraphFilters) -> RookResult<Option<f32>> {
+        let name_key = format!(
+            "{}:{}:{}:{}",
+            name,
+            filters.user_id.as_deref().unwrap_or(""),
+            filters.agent_id.as_deref().unwrap_or(""),
+            filters.run_id.as_deref().unwrap_or("")
+        );
+
+        let name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        Ok(name_index
+            .get(&name_key)
+            .and_then(|&idx| graph[idx].properties.get(IMPORTANCE_PROPERTY))
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32))
+    }
+
+    /// Importance of the most central scored entity each memory mentions.
+    ///
+    /// Memories that mention no scored entity are left out.
+    pub fn memory_importance(&self, memory_ids: &[String]) -> RookResult<HashMap<String, f32>> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let mut importance = HashMap::new();
+        for memory_id in memory_ids {
+            let best = sync::get_entities_for_memory(&conn, memory_id)?
+                .into_iter()
+                .filter_map(|entity_id| db_id_index.get(&entity_id))
+                .filter_map(|&idx| graph[idx].properties.get(IMPORTANCE_PROPERTY))
+                .filter_map(|v| v.as_f64())
+                .reduce(f64::max);
+            if let Some(best) = best {
+                importance.insert(memory_id.clone(), best as f32);
+            }
+        }
+
+        Ok(importance)
+    }
+}
+
+impl ImportanceProvider for EmbeddedGraphStore {
+    fn importance(&self, memory_id: &str) -> Option<f32> {
+        match self.memory_importance(&[memory_id.to_string()]) {
+            Ok(mut scores) => scores.remove(memory_id),
+            Err(e) => {
+                tracing::warn!("Failed to read importance of memory {}: {}", memory_id, e);
+                None
+            }
+        }
+    }
+}
+
+/// Periodically recomputes entity importance in an [`EmbeddedGraphStore`].
+///
+/// # Example
+///
+/// ```ignore
+/// let job = EntityImportanceJob::new(store.clone(), ImportanceConfig::default());
+/// let runtime = BackgroundRuntime::new(config)
+///     .await?
+///     .with_maintenance_job(Arc::new(job), 360);
+/// ```
+pub struct EntityImportanceJob {
+    store: Arc<EmbeddedGraphStore>,
+    config: ImportanceConfig,
+}
+
+impl EntityImportanceJob {
+    pub fn new(store: Arc<EmbeddedGraphStore>, config: ImportanceConfig) -> Self {
+        Self { store, config }
+    }
+}
+
+#[async_trait]
+impl MaintenanceJob for EntityImportanceJob {
+    fn name(&self) -> &str {
+        "entity_importance"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        let store = self.store.clone();
+        let config = self.config.clone();
+        tokio::task::spawn_blocking(move || store.compute_importance(&config))
+            .await
+            .map_err(|e| RookError::internal(format!("Entity importance task failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_importance() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let filters = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::json!({});
+        for person in ["Alice", "Bob", "Carol", "Dave"] {
+            store.add_relationship(person, "Acme", "works_at", &json, &filters).unwrap();
+        }
+        store.add_relationship("Dave", "Chess", "likes", &json, &filters).unwrap();
+        store.link_memory_to_category("mem-1", "professional", &filters).unwrap();
+
+        let acme = store.add_entity("Acme", "organization", &json, &filters).unwrap();
+        let chess = store.add_entity("Chess", "hobby", &json, &filters).unwrap();
+        {
+            let conn = store.conn.lock().unwrap();
+            sync::link_memory_to_entity(&conn, "mem-1", acme, "mentioned").unwrap();
+            sync::link_memory_to_entity(&conn, "mem-1", chess, "mentioned").unwrap();
+            sync::link_memory_to_entity(&conn, "mem-2", chess, "mentioned").unwrap();
+        }
+
+        assert_eq!(store.compute_importance(&ImportanceConfig::default()).unwrap(), 6);
+        assert_eq!(store.entity_importance("Acme", &filters).unwrap(), Some(1.0));
+        let chess_score = store.entity_importance("Chess", &filters).unwrap().unwrap();
+        assert!(chess_score < 1.0);
+        assert!(store.entity_importance("professional", &filters).unwrap().is_none());
+
+        let scores = store
+            .memory_importance(&["mem-1".to_string(), "mem-2".to_string(), "mem-3".to_string()])
+            .unwrap();
+        assert_eq!(scores["mem-1"], 1.0);
+        assert_eq!(scores["mem-2"], chess_score);
+        assert!(!scores.contains_key("mem-3"));
+        assert_eq!(store.importance("mem-2"), Some(chess_score));
+    }
+}
@@ -19,25 +19,60 @@
 //! └─────────────────────────────────────────┘
 //! ```
 
+mod activation;
+mod communities;
+mod dedup;
+mod export;
+mod importance;
 pub mod petgraph_ops;
+mod reassign;
 pub mod schema;
 pub mod sync;
 
-use std::collections::HashMap;
+pub use communities::{TopicCluster, TOPIC_CATEGORY_PREFIX};
+pub use dedup::{EntityDedupJob, EntityMerge};
+pub use export::{ExportLink, ExportNode, GraphExportFormat, NodeLinkGraph};
+pub use importance::{EntityImportanceJob, IMPORTANCE_PROPERTY};
+
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 use std::sync::Mutex;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use petgraph::graph::DiGraph;
 use petgraph::visit::EdgeRef;
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 
 use rook_core::error::{RookError, RookResult};
-use rook_core::traits::{Entity, GraphFilters, GraphStore, GraphStoreConfig};
+use rook_core::traits::{
+    Entity, GraphFilters, GraphPath, GraphStore, GraphStoreConfig, Relationship,
+    MAX_TRAVERSAL_DEPTH,
+};
 use rook_core::types::{GraphRelation, Message};
 
 use petgraph_ops::{DbIdIndex, EntityNode, NameIndex, RelationshipEdge};
 
+/// A relationship together with the interval during which it held.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporalRelation {
+    /// The relationship.
+    pub relation: GraphRelation,
+    /// When it became true (`None` = since always).
+    pub valid_from: Option<DateTime<Utc>>,
+    /// When it stopped being true (`None` = still valid).
+    pub valid_to: Option<DateTime<Utc>>,
+}
+
+/// Whether an edge held at `at`, or is still current if `at` is `None`.
+fn edge_valid(edge: &RelationshipEdge, at: Option<DateTime<Utc>>) -> bool {
+    match at {
+        Some(at) => edge.is_valid_at(at),
+        None => edge.is_current(),
+    }
+}
+
 /// Embedded graph store using petgraph + SQLite.
 ///
 /// Thread-safe via Mutex on the connection and graph.
@@ -155,8 +190,9 @@ impl EmbeddedGraphStore {
 
         // Save to SQLite
         let db_id = sync::save_relationship(&conn, source_id, target_id, relationship_type, properties, 1.0)?;
+        let (valid_from, valid_to) = sync::get_relationship_validity(&conn, db_id)?;
 
-        // Update in-memory graph
+        // Update in-memory graph, replacing the edge if the relationship already existed
         let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
         let db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
 
@@ -164,13 +200,113 @@ impl EmbeddedGraphStore {
             (db_id_index.get(&source_id), db_id_index.get(&target_id))
         {
             let edge = RelationshipEdge::new(db_id, relationship_type)
-                .with_properties(properties.clone());
-            graph.add_edge(source_idx, target_idx, edge);
+                .with_properties(properties.clone())
+                .with_validity(valid_from, valid_to);
+            let existing = graph
+                .edges_connecting(source_idx, target_idx)
+                .find(|e| e.weight().db_id == db_id)
+                .map(|e| e.id());
+            match existing {
+                Some(edge_idx) => graph[edge_idx] = edge,
+                None => {
+                    graph.add_edge(source_idx, target_idx, edge);
+                }
+            }
         }
 
         Ok(db_id)
     }
 
+    /// End the validity of `source --[relationship_type]--> target` as of now.
+    ///
+    /// The relationship is kept for point-in-time queries but no longer shows
+    /// up in current searches. Returns false if no current relationship matched.
+    pub fn invalidate_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<bool> {
+        let ended = self.invalidate_outgoing(source_name, filters, |target, relationship| {
+            relationship.eq_ignore_ascii_case(relationship_type)
+                && target.eq_ignore_ascii_case(target_name)
+        })?;
+        Ok(ended > 0)
+    }
+
+    /// Record `source --[relationship_type]--> target` as replacing every other
+    /// current relationship of that type from `source`.
+    ///
+    /// Use for single-valued facts: "works_at Initech" ends "works_at Acme".
+    pub fn supersede_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        let ended = self.invalidate_outgoing(source_name, filters, |target, relationship| {
+            relationship.eq_ignore_ascii_case(relationship_type)
+                && !target.eq_ignore_ascii_case(target_name)
+        })?;
+        if ended > 0 {
+            tracing::debug!(
+                "Superseded {} '{}' relationship(s) from '{}' with '{}'",
+                ended,
+                relationship_type,
+                source_name,
+                target_name
+            );
+        }
+
+        self.add_relationship(source_name, target_name, relationship_type, properties, filters)
+    }
+
+    /// Invalidate current outgoing edges of `source_name` for which
+    /// `matches(target_name, relationship_type)` holds. Returns how many ended.
+    fn invalidate_outgoing(
+        &self,
+        source_name: &str,
+        filters: &GraphFilters,
+        matches: impl Fn(&str, &str) -> bool,
+    ) -> RookResult<usize> {
+        let name_key = format!(
+            "{}:{}:{}:{}",
+            source_name,
+            filters.user_id.as_deref().unwrap_or(""),
+            filters.agent_id.as_deref().unwrap_or(""),
+            filters.run_id.as_deref().unwrap_or("")
+        );
+
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let node_idx = match name_index.get(&name_key) {
+            Some(&idx) => idx,
+            None => return Ok(0),
+        };
+
+        let ended: Vec<_> = graph
+            .edges(node_idx)
+            .filter(|e| {
+                e.weight().is_current()
+                    && matches(&graph[e.target()].name, &e.weight().relationship_type)
+            })
+            .map(|e| e.id())
+            .collect();
+
+        let now = Utc::now();
+        for &edge_idx in &ended {
+            sync::invalidate_relationship(&conn, graph[edge_idx].db_id, now)?;
+            graph[edge_idx].valid_to = Some(now);
+        }
+
+        Ok(ended.len())
+    }
+
     /// Get or create an entity by name.
     fn get_or_create_entity(
         &self,
@@ -203,8 +339,68 @@ impl EmbeddedGraphStore {
         self.add_entity(name, entity_type, &serde_json::json!({}), filters)
     }
 
-    /// Get neighbors of an entity.
+    /// Get neighbors of an entity over currently valid relationships.
     pub fn get_neighbors(&self, entity_name: &str, filters: &GraphFilters) -> RookResult<Vec<GraphRelation>> {
+        self.neighbors(entity_name, None, filters)
+    }
+
+    /// Get neighbors of an entity over relationships that were valid at `at`.
+    pub fn get_neighbors_at(
+        &self,
+        entity_name: &str,
+        at: DateTime<Utc>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphRelation>> {
+        self.neighbors(entity_name, Some(at), filters)
+    }
+
+    /// Every outgoing relationship of an entity, including invalidated ones,
+    /// oldest first.
+    pub fn relationship_history(
+        &self,
+        entity_name: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<TemporalRelation>> {
+        let name_key = format!(
+            "{}:{}:{}:{}",
+            entity_name,
+            filters.user_id.as_deref().unwrap_or(""),
+            filters.agent_id.as_deref().unwrap_or(""),
+            filters.run_id.as_deref().unwrap_or("")
+        );
+
+        let name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let node_idx = match name_index.get(&name_key) {
+            Some(&idx) => idx,
+            None => return Ok(vec![]),
+        };
+
+        let mut history: Vec<TemporalRelation> = graph
+            .edges(node_idx)
+            .map(|edge| TemporalRelation {
+                relation: GraphRelation {
+                    source: entity_name.to_string(),
+                    relationship: edge.weight().relationship_type.clone(),
+                    target: graph[edge.target()].name.clone(),
+                },
+                valid_from: edge.weight().valid_from,
+                valid_to: edge.weight().valid_to,
+            })
+            .collect();
+        history.sort_by_key(|r| r.valid_from);
+
+        Ok(history)
+    }
+
+    /// Neighbors over relationships valid at `at`, or currently valid if `None`.
+    fn neighbors(
+        &self,
+        entity_name: &str,
+        at: Option<DateTime<Utc>>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphRelation>> {
         let name_key = format!(
             "{}:{}:{}:{}",
             entity_name,
@@ -224,7 +420,7 @@ impl EmbeddedGraphStore {
         let mut relations = Vec::new();
 
         // Get outgoing edges
-        for edge in graph.edges(node_idx) {
+        for edge in graph.edges(node_idx).filter(|e| edge_valid(e.weight(), at)) {
             let target_node = graph.node_weight(edge.target());
             if let Some(target) = target_node {
                 relations.push(GraphRelation {
@@ -236,7 +432,10 @@ impl EmbeddedGraphStore {
         }
 
         // Get incoming edges
-        for edge in graph.edges_directed(node_idx, petgraph::Direction::Incoming) {
+        for edge in graph
+            .edges_directed(node_idx, petgraph::Direction::Incoming)
+            .filter(|e| edge_valid(e.weight(), at))
+        {
             let source_node = graph.node_weight(edge.source());
             if let Some(source) = source_node {
                 relations.push(GraphRelation {
@@ -250,6 +449,154 @@ impl EmbeddedGraphStore {
         Ok(relations)
     }
 
+    /// Breadth-first traversal over outgoing edges (see [`GraphStore::traverse`]).
+    pub fn traverse_paths(
+        &self,
+        start_entity: &str,
+        max_depth: usize,
+        relationship_filter: Option<&[String]>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphPath>> {
+        let name_key = format!(
+            "{}:{}:{}:{}",
+            start_entity,
+            filters.user_id.as_deref().unwrap_or(""),
+            filters.agent_id.as_deref().unwrap_or(""),
+            filters.run_id.as_deref().unwrap_or("")
+        );
+
+        let name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        let in_scope = |node: &EntityNode| {
+            node.matches_filters(
+                filters.user_id.as_deref(),
+                filters.agent_id.as_deref(),
+                filters.run_id.as_deref(),
+            )
+        };
+
+        // Exact key first, then a case-insensitive name match within scope
+        let start_idx = name_index.get(&name_key).copied().or_else(|| {
+            graph.node_indices().find(|&idx| {
+                graph[idx].name.eq_ignore_ascii_case(start_entity) && in_scope(&graph[idx])
+            })
+        });
+        let start_idx = match start_idx {
+            Some(idx) => idx,
+            None => return Ok(vec![]),
+        };
+
+        let allowed: Option<HashSet<String>> =
+            relationship_filter.map(|types| types.iter().map(|t| t.to_lowercase()).collect());
+        let max_depth = max_depth.min(MAX_TRAVERSAL_DEPTH);
+        let start = graph[start_idx].name.clone();
+
+        let mut visited = HashSet::from([start_idx]);
+        let mut queue = VecDeque::from([(start_idx, Vec::<GraphRelation>::new())]);
+        let mut paths = Vec::new();
+
+        while let Some((node_idx, hops)) = queue.pop_front() {
+            if hops.len() >= max_depth {
+                continue;
+            }
+
+            for edge in graph.edges(node_idx).filter(|e| e.weight().is_current()) {
+                let relationship = &edge.weight().relationship_type;
+                if let Some(ref allowed) = allowed {
+                    if !allowed.contains(&relationship.to_lowercase()) {
+                        continue;
+                    }
+                }
+
+                let target_idx = edge.target();
+                if !in_scope(&graph[target_idx]) || !visited.insert(target_idx) {
+                    continue;
+                }
+
+                let mut next = hops.clone();
+                next.push(GraphRelation {
+                    source: graph[node_idx].name.clone(),
+                    relationship: relationship.clone(),
+                    target: graph[target_idx].name.clone(),
+                });
+                paths.push(GraphPath {
+                    start: start.clone(),
+                    hops: next.clone(),
+                });
+                queue.push_back((target_idx, next));
+            }
+        }
+
+        Ok(paths)
+    }
+
+    /// Search over relationships that were valid at `at`.
+    pub fn search_at(
+        &self,
+        query: &str,
+        at: DateTime<Utc>,
+        filters: &GraphFilters,
+        limit: usize,
+    ) -> RookResult<Vec<GraphRelation>> {
+        self.search_relations(query, Some(at), filters, limit)
+    }
+
+    /// Name search over relationships valid at `at`, or currently valid if `None`.
+    fn search_relations(
+        &self,
+        query: &str,
+        at: Option<DateTime<Utc>>,
+        filters: &GraphFilters,
+        limit: usize,
+    ) -> RookResult<Vec<GraphRelation>> {
+        // Search for entities matching the query
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut relations = Vec::new();
+
+        let query_lower = query.to_lowercase();
+
+        // Simple text match search
+        for node_idx in graph.node_indices() {
+            if relations.len() >= limit {
+                break;
+            }
+
+            let node = match graph.node_weight(node_idx) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            // Check filters
+            if !node.matches_filters(
+                filters.user_id.as_deref(),
+                filters.agent_id.as_deref(),
+                filters.run_id.as_deref(),
+            ) {
+                continue;
+            }
+
+            // Check if name contains query
+            if node.name.to_lowercase().contains(&query_lower) {
+                // Get all relationships for this entity
+                for edge in graph.edges(node_idx).filter(|e| edge_valid(e.weight(), at)) {
+                    if relations.len() >= limit {
+                        break;
+                    }
+                    if let Some(target) = graph.node_weight(edge.target()) {
+                        relations.push(GraphRelation {
+                            source: node.name.clone(),
+                            relationship: edge.weight().relationship_type.clone(),
+                            target: target.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(relations)
+    }
+
     /// Get entity count.
     pub fn entity_count(&self) -> RookResult<usize> {
         let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
@@ -511,58 +858,14 @@ impl GraphStore for EmbeddedGraphStore {
         Ok(relations)
     }
 
-    /// Search for related entities.
+    /// Search for related entities over currently valid relationships.
     async fn search(
         &self,
         query: &str,
         filters: &GraphFilters,
         limit: usize,
     ) -> RookResult<Vec<GraphRelation>> {
-        // Search for entities matching the query
-        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
-        let mut relations = Vec::new();
-
-        let query_lower = query.to_lowercase();
-
-        // Simple text match search
-        for node_idx in graph.node_indices() {
-            if relations.len() >= limit {
-                break;
-            }
-
-            let node = match graph.node_weight(node_idx) {
-                Some(n) => n,
-                None => continue,
-            };
-
-            // Check filters
-            if !node.matches_filters(
-                filters.user_id.as_deref(),
-                filters.agent_id.as_deref(),
-                filters.run_id.as_deref(),
-            ) {
-                continue;
-            }
-
-            // Check if name contains query
-            if node.name.to_lowercase().contains(&query_lower) {
-                // Get all relationships for this entity
-                for edge in graph.edges(node_idx) {
-                    if relations.len() >= limit {
-                        break;
-                    }
-                    if let Some(target) = graph.node_weight(edge.target()) {
-                        relations.push(GraphRelation {
-                            source: node.name.clone(),
-                            relationship: edge.weight().relationship_type.clone(),
-                            target: target.name.clone(),
-                        });
-                    }
-                }
-            }
-        }
-
-        Ok(relations)
+        self.search_relations(query, None, filters, limit)
     }
 
     /// Delete all data for the given filters.
@@ -627,6 +930,37 @@ impl GraphStore for EmbeddedGraphStore {
         Ok(entities)
     }
 
+    /// Get all current relationships between entities within the filters.
+    async fn get_all_relationships(&self, filters: &GraphFilters) -> RookResult<Vec<Relationship>> {
+        let graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let in_scope = |idx| {
+            graph.node_weight(idx).filter(|node| {
+                node.matches_filters(
+                    filters.user_id.as_deref(),
+                    filters.agent_id.as_deref(),
+                    filters.run_id.as_deref(),
+                )
+            })
+        };
+
+        Ok(graph
+            .edge_indices()
+            .filter_map(|edge_idx| {
+                let edge = &graph[edge_idx];
+                if !edge.is_current() {
+                    return None;
+                }
+                let (source, target) = graph.edge_endpoints(edge_idx)?;
+                Some(Relationship {
+                    source: in_scope(source)?.name.clone(),
+                    target: in_scope(target)?.name.clone(),
+                    relationship_type: edge.relationship_type.clone(),
+                    properties: edge.properties.clone(),
+                })
+            })
+            .collect())
+    }
+
     /// Add an entity directly (async wrapper for sync method).
     async fn add_entity(
         &self,
@@ -652,6 +986,58 @@ impl GraphStore for EmbeddedGraphStore {
         EmbeddedGraphStore::add_relationship(self, source_name, target_name, relationship_type, properties, filters)
     }
 
+    /// Supersede a relationship (async wrapper for sync method).
+    async fn supersede_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        EmbeddedGraphStore::supersede_relationship(
+            self,
+            source_name,
+            target_name,
+            relationship_type,
+            properties,
+            filters,
+        )
+    }
+
+    /// Invalidate a relationship (async wrapper for sync method).
+    async fn invalidate_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<bool> {
+        EmbeddedGraphStore::invalidate_relationship(
+            self,
+            source_name,
+            target_name,
+            relationship_type,
+            filters,
+        )
+    }
+
+    /// Multi-hop traversal (async wrapper for sync method).
+    async fn traverse(
+        &self,
+        start_entity: &str,
+        max_depth: usize,
+        relationship_filter: Option<&[String]>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphPath>> {
+        self.traverse_paths(start_entity, max_depth, relationship_filter, filters)
+    }
+
+    /// Move entities between scopes (async wrapper for sync method).
+    async fn reassign_scope(&self, from: &GraphFilters, to: &GraphFilters) -> RookResult<usize> {
+        self.reassign_entities(from, to)
+    }
+
     /// Get entities for merge operations.
     async fn get_entities_for_merge(
         &self,
@@ -783,6 +1169,97 @@ mod tests {
         assert_eq!(results[0].source, "Alice Smith");
     }
 
+    #[tokio::test]
+    async fn test_embedded_store_traverse() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let filters = GraphFilters::default();
+        let props = serde_json::json!({});
+
+        store.add_relationship("Alice", "Acme", "works_at", &props, &filters).unwrap();
+        store.add_relationship("Acme", "Berlin", "located_in", &props, &filters).unwrap();
+        store.add_relationship("Berlin", "Germany", "located_in", &props, &filters).unwrap();
+        store.add_relationship("Alice", "Bob", "knows", &props, &filters).unwrap();
+
+        let paths = store.traverse("Alice", 2, None, &filters).await.unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[..2].iter().all(|p| p.depth() == 1));
+        assert_eq!(paths[2].end(), "Berlin");
+        assert_eq!(paths[2].hops[0].relationship, "works_at");
+        assert_eq!(paths[2].hops[1].source, "Acme");
+
+        let located = vec!["LOCATED_IN".to_string()];
+        let paths = store.traverse("acme", 5, Some(&located), &filters).await.unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[1].end(), "Germany");
+        assert_eq!(paths[1].start, "Acme");
+
+        assert!(store.traverse("Nobody", 3, None, &filters).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_embedded_store_temporal_validity() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let filters = GraphFilters::default();
+        let props = serde_json::json!({});
+
+        store.add_relationship("Alice", "Acme", "works_at", &props, &filters).unwrap();
+        store.add_relationship("Alice", "Bob", "knows", &props, &filters).unwrap();
+        let before = Utc::now();
+
+        store
+            .supersede_relationship("Alice", "Initech", "works_at", &props, &filters)
+            .unwrap();
+
+        let current = store.search("Alice", &filters, 10).await.unwrap();
+        let targets: Vec<_> = current.iter().map(|r| r.target.as_str()).collect();
+        assert!(targets.contains(&"Initech"));
+        assert!(!targets.contains(&"Acme"));
+
+        let past = store.get_neighbors_at("Alice", before, &filters).unwrap();
+        let targets: Vec<_> = past.iter().map(|r| r.target.as_str()).collect();
+        assert!(targets.contains(&"Acme"));
+        assert!(!targets.contains(&"Initech"));
+
+        let history = store.relationship_history("Alice", &filters).unwrap();
+        assert_eq!(history.len(), 3);
+        let acme = history.iter().find(|r| r.relation.target == "Acme").unwrap();
+        assert!(acme.valid_to.is_some());
+
+        assert!(store.invalidate_relationship("Alice", "bob", "KNOWS", &filters).unwrap());
+        assert!(!store.invalidate_relationship("Alice", "Bob", "knows", &filters).unwrap());
+        assert_eq!(store.get_neighbors("Alice", &filters).unwrap().len(), 1);
+
+        // Re-asserting an ended fact makes it current again without a duplicate edge
+        store.add_relationship("Alice", "Bob", "knows", &props, &filters).unwrap();
+        assert_eq!(store.get_neighbors("Alice", &filters).unwrap().len(), 2);
+        assert_eq!(store.relationship_count().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_embedded_store_get_all_relationships() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let alice = GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let bob = GraphFilters {
+            user_id: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let props = serde_json::json!({"since": 2020});
+
+        store.add_relationship("Alice", "Acme", "works_at", &props, &alice).unwrap();
+        store.add_relationship("Alice", "Berlin", "lives_in", &props, &alice).unwrap();
+        store.invalidate_relationship("Alice", "Berlin", "lives_in", &alice).unwrap();
+        store.add_relationship("Bob", "Initech", "works_at", &props, &bob).unwrap();
+
+        let relationships = store.get_all_relationships(&alice).await.unwrap();
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].source, "Alice");
+        assert_eq!(relationships[0].target, "Acme");
+        assert_eq!(relationships[0].properties["since"], 2020);
+    }
+
     #[tokio::test]
     async fn test_embedded_store_delete_all() {
         let store = EmbeddedGraphStore::in_memory().unwrap();
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use serde::{Deserialize, Serialize};
@@ -105,6 +106,10 @@ pub struct RelationshipEdge {
     pub weight: f64,
     /// Additional properties as JSON.
     pub properties: serde_json::Value,
+    /// When the relationship became true (`None` = since always).
+    pub valid_from: Option<DateTime<Utc>>,
+    /// When the relationship stopped being true (`None` = still valid).
+    pub valid_to: Option<DateTime<Utc>>,
 }
 
 impl RelationshipEdge {
@@ -115,9 +120,37 @@ impl RelationshipEdge {
             relationship_type: relationship_type.into(),
             weight: 1.0,
             properties: serde_json::Value::Object(serde_json::Map::new()),
+            valid_from: None,
+            valid_to: None,
         }
     }
 
+    /// Set the validity interval.
+    pub fn with_validity(
+        mut self,
+        valid_from: Option<DateTime<Utc>>,
+        valid_to: Option<DateTime<Utc>>,
+    ) -> Self {
+        self.valid_from = valid_from;
+        self.valid_to = valid_to;
+        self
+    }
+
+    /// Whether the relationship held at the given instant.
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        let started = match self.valid_from {
+            Some(from) => from <= at,
+            None => true,
+        };
+        let ended = matches!(self.valid_to, Some(to) if to <= at);
+        started && !ended
+    }
+
+    /// Whether the relationship has not been invalidated.
+    pub fn is_current(&self) -> bool {
+        self.valid_to.is_none()
+    }
+
     /// Set edge weight.
     pub fn with_weight(mut self, weight: f64) -> Self {
         self.weight = weight;
@@ -381,6 +414,22 @@ mod tests {
         assert_eq!(neighbors[0].1.relationship_type, "knows");
     }
 
+    #[test]
+    fn test_relationship_validity() {
+        let start = Utc::now() - chrono::Duration::days(10);
+        let end = Utc::now() - chrono::Duration::days(2);
+
+        let edge = RelationshipEdge::new(1, "works_at").with_validity(Some(start), Some(end));
+        assert!(!edge.is_current());
+        assert!(edge.is_valid_at(start + chrono::Duration::days(1)));
+        assert!(!edge.is_valid_at(start - chrono::Duration::days(1)));
+        assert!(!edge.is_valid_at(end));
+
+        let open = RelationshipEdge::new(2, "works_at");
+        assert!(open.is_current());
+        assert!(open.is_valid_at(Utc::now()));
+    }
+
     #[test]
     fn test_graph_remove_entity() {
         let mut graph = MemoryGraph::new();
@@ -0,0 +1,131 @@
+//! Moving entities between user/agent/run scopes.
+//!
+//! Used when an identifier changes or two identities are merged: entities
+//! of the old scope move to the new one, and entities the new scope already
+//! has absorb their namesakes.
+
+use std::collections::HashMap;
+
+use rusqlite::params;
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::GraphFilters;
+
+use super::{sync, EmbeddedGraphStore};
+
+type Scope = (Option<String>, Option<String>, Option<String>);
+
+/// Whether `scope` has every ID set in `filters`.
+fn in_scope(scope: &Scope, filters: &GraphFilters) -> bool {
+    let matches = |id: &Option<String>, filter: &Option<String>| filter.is_none() || id == filter;
+    matches(&scope.0, &filters.user_id)
+        && matches(&scope.1, &filters.agent_id)
+        && matches(&scope.2, &filters.run_id)
+}
+
+/// `scope` with the IDs set in `filters` replaced.
+fn retarget(scope: &Scope, filters: &GraphFilters) -> Scope {
+    (
+        filters.user_id.clone().or_else(|| scope.0.clone()),
+        filters.agent_id.clone().or_else(|| scope.1.clone()),
+        filters.run_id.clone().or_else(|| scope.2.clone()),
+    )
+}
+
+/// Fill in properties the kept entity lacks from the one merged into it.
+fn fill_properties(kept: &mut serde_json::Value, merged: &serde_json::Value) {
+    let (Some(kept), Some(merged)) = (kept.as_object_mut(), merged.as_object()) else {
+        return;
+    };
+    for (key, value) in merged {
+        if matches!(kept.get(key), None | Some(serde_json::Value::Null)) {
+            kept.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+impl EmbeddedGraphStore {
+    /// Move the entities of the `from` scope to the `to` scope.
+    ///
+    /// Entities match `from` exactly; unscoped entities are left alone.
+    /// Returns the number of entities moved, including those merged into an
+    /// entity of the same name in the new scope.
+    pub fn reassign_entities(&self, from: &GraphFilters, to: &GraphFilters) -> RookResult<usize> {
+        let conn = self.conn.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut graph = self.graph.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut db_id_index = self.db_id_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+        let mut name_index = self.name_index.lock().map_err(|e| RookError::internal(e.to_string()))?;
+
+        // Entities staying where they are, by name and scope
+        let mut occupied: HashMap<(String, Scope), (i64, serde_json::Value)> = HashMap::new();
+        let mut moving = Vec::new();
+        for node in graph.node_weights() {
+            let scope = (node.user_id.clone(), node.agent_id.clone(), node.run_id.clone());
+            let target = retarget(&scope, to);
+            if in_scope(&scope, from) && target != scope {
+                moving.push((node.db_id, node.name.clone(), target, node.properties.clone()));
+            } else {
+                occupied.insert((node.name.clone(), scope), (node.db_id, node.properties.clone()));
+            }
+        }
+        if moving.is_empty() {
+            return Ok(0);
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        for (id, name, target, properties) in &moving {
+            match occupied.get_mut(&(name.clone(), target.clone())) {
+                Some((kept_id, kept_properties)) => {
+                    fill_properties(kept_properties, properties);
+                    sync::merge_entity(&tx, *kept_id, *id, kept_properties)?;
+                }
+                None => {
+                    tx.execute(
+                        r#"
+                        UPDATE entities
+                        SET user_id = ?1, agent_id = ?2, run_id = ?3, updated_at = datetime('now')
+                        WHERE id = ?4
+                        "#,
+                        params![target.0, target.1, target.2, id],
+                    )?;
+                    occupied.insert((name.clone(), target.clone()), (*id, properties.clone()));
+                }
+            }
+        }
+        tx.commit()?;
+
+        sync::load_graph(&conn, &mut graph, &mut db_id_index, &mut name_index)?;
+        Ok(moving.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(user_id: &str) -> GraphFilters {
+        GraphFilters {
+            user_id: Some(user_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reassign_entities() {
+        let store = EmbeddedGraphStore::in_memory().unwrap();
+        let json = serde_json::json!({});
+        store.add_relationship("Alice", "Acme", "works_at", &json, &user("alice")).unwrap();
+        store.add_relationship("Alice", "Tennis", "likes", &json, &user("alice")).unwrap();
+        store.add_relationship("Alice", "Berlin", "lives_in", &json, &user("alice-2")).unwrap();
+        store.add_relationship("Carol", "Paris", "lives_in", &json, &user("carol")).unwrap();
+
+        let moved = store.reassign_entities(&user("alice-2"), &user("alice")).unwrap();
+        assert_eq!(moved, 2);
+        // "Alice" merged into the existing entity, "Berlin" moved
+        assert_eq!(store.get_neighbors("Alice", &user("alice")).unwrap().len(), 3);
+        assert!(store.get_neighbors("Alice", &user("alice-2")).unwrap().is_empty());
+        assert_eq!(store.get_neighbors("Carol", &user("carol")).unwrap().len(), 1);
+
+        assert_eq!(store.reassign_entities(&user("alice-2"), &user("alice")).unwrap(), 0);
+    }
+}
@@ -1,10 +1,11 @@
 //! SQLite schema for embedded graph store.
 //!
-//! Provides persistent storage for the knowledge graph with four tables:
+//! Provides persistent storage for the knowledge graph with five tables:
 //! - `entities`: Node data (name, type, properties)
 //! - `relationships`: Edge data (source, target, type, properties)
 //! - `memory_entities`: Links memories to entities
 //! - `entity_access_log`: Tracks entity access patterns for spreading activation
+//! - `entity_merges`: History of duplicate entities merged by deduplication
 
 use rusqlite::Connection;
 
@@ -50,6 +51,8 @@ CREATE TABLE IF NOT EXISTS relationships (
     relationship_type TEXT NOT NULL,
     properties TEXT NOT NULL DEFAULT '{}',
     weight REAL NOT NULL DEFAULT 1.0,
+    valid_from TEXT,
+    valid_to TEXT,
     created_at TEXT NOT NULL DEFAULT (datetime('now')),
     updated_at TEXT NOT NULL DEFAULT (datetime('now')),
     UNIQUE(source_id, target_id, relationship_type)
@@ -71,6 +74,11 @@ pub const CREATE_RELATIONSHIPS_TYPE_INDEX: &str = r#"
 CREATE INDEX IF NOT EXISTS idx_relationships_type ON relationships(relationship_type)
 "#;
 
+/// Columns added to `relationships` after its first release, with their types.
+///
+/// Databases created before a column existed get it via `ALTER TABLE`.
+const RELATIONSHIP_MIGRATIONS: &[(&str, &str)] = &[("valid_from", "TEXT"), ("valid_to", "TEXT")];
+
 /// SQL for memory-entity links.
 pub const CREATE_MEMORY_ENTITIES_TABLE: &str = r#"
 CREATE TABLE IF NOT EXISTS memory_entities (
@@ -114,6 +122,22 @@ pub const CREATE_ACCESS_LOG_TIME_INDEX: &str = r#"
 CREATE INDEX IF NOT EXISTS idx_access_log_time ON entity_access_log(accessed_at)
 "#;
 
+/// SQL for the entity merge history.
+///
+/// No foreign keys: the merged entity no longer exists.
+pub const CREATE_ENTITY_MERGES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS entity_merges (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    canonical_id INTEGER NOT NULL,
+    canonical_name TEXT NOT NULL,
+    merged_id INTEGER NOT NULL,
+    merged_name TEXT NOT NULL,
+    similarity REAL NOT NULL,
+    relationships_moved INTEGER NOT NULL DEFAULT 0,
+    merged_at TEXT NOT NULL
+)
+"#;
+
 /// Initialize the graph schema in the given database connection.
 ///
 /// Creates all tables and indexes if they don't exist.
@@ -127,6 +151,8 @@ pub fn init_schema(conn: &Connection) -> RookResult<()> {
     conn.execute(CREATE_RELATIONSHIPS_TABLE, [])?;
     conn.execute(CREATE_MEMORY_ENTITIES_TABLE, [])?;
     conn.execute(CREATE_ENTITY_ACCESS_LOG_TABLE, [])?;
+    conn.execute(CREATE_ENTITY_MERGES_TABLE, [])?;
+    migrate_relationships(conn)?;
 
     // Create indexes for entities
     conn.execute(CREATE_ENTITIES_NAME_INDEX, [])?;
@@ -149,6 +175,25 @@ pub fn init_schema(conn: &Connection) -> RookResult<()> {
     Ok(())
 }
 
+/// Add any relationship columns missing from an older database.
+fn migrate_relationships(conn: &Connection) -> RookResult<()> {
+    let existing: Vec<String> = conn
+        .prepare("SELECT name FROM pragma_table_info('relationships')")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    for (column, sql_type) in RELATIONSHIP_MIGRATIONS {
+        if !existing.iter().any(|name| name == column) {
+            conn.execute(
+                &format!("ALTER TABLE relationships ADD COLUMN {} {}", column, sql_type),
+                [],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,6 +216,7 @@ mod tests {
         assert!(tables.contains(&"relationships".to_string()));
         assert!(tables.contains(&"memory_entities".to_string()));
         assert!(tables.contains(&"entity_access_log".to_string()));
+        assert!(tables.contains(&"entity_merges".to_string()));
     }
 
     #[test]
@@ -193,6 +239,26 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn test_init_schema_migrates_relationships() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE relationships (id INTEGER PRIMARY KEY, source_id INTEGER, \
+             target_id INTEGER, relationship_type TEXT)",
+            [],
+        )
+        .unwrap();
+
+        init_schema(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO relationships (source_id, target_id, relationship_type, valid_to) \
+             VALUES (1, 2, 'works_at', '2024-01-01T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+    }
+
     #[test]
     fn test_entity_unique_constraint() {
         let conn = Connection::open_in_memory().unwrap();
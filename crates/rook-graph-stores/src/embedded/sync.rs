@@ -3,11 +3,13 @@
 //! Provides functions to load graph data from SQLite into petgraph
 //! and persist changes back to SQLite.
 
+use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 
 use rook_core::error::RookResult;
 use rook_core::traits::GraphFilters;
 
+use super::dedup::EntityMerge;
 use super::petgraph_ops::{DbIdIndex, EntityNode, MemoryGraph, NameIndex, RelationshipEdge};
 
 /// Load the entire graph from SQLite into petgraph.
@@ -61,14 +63,37 @@ pub fn load_graph(
             entity.run_id.as_deref(),
         );
 
+        let alias_keys: Vec<String> = entity
+            .properties
+            .get("aliases")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|alias| alias.as_str())
+            .map(|alias| {
+                make_name_key(
+                    alias,
+                    entity.user_id.as_deref(),
+                    entity.agent_id.as_deref(),
+                    entity.run_id.as_deref(),
+                )
+            })
+            .collect();
+
         let idx = graph.add_node(entity);
         db_id_index.insert(db_id, idx);
         name_index.insert(name_key, idx);
+
+        // Names merged into this entity resolve to it unless an entity still uses them
+        for alias_key in alias_keys {
+            name_index.entry(alias_key).or_insert(idx);
+        }
     }
 
     // Load all relationships
     let mut stmt = conn.prepare(
-        "SELECT id, source_id, target_id, relationship_type, properties, weight FROM relationships",
+        "SELECT id, source_id, target_id, relationship_type, properties, weight, valid_from, \
+         valid_to FROM relationships",
     )?;
 
     let relationship_iter = stmt.query_map([], |row| {
@@ -78,6 +103,8 @@ pub fn load_graph(
         let relationship_type: String = row.get(3)?;
         let properties_str: String = row.get(4)?;
         let weight: f64 = row.get(5)?;
+        let valid_from: Option<String> = row.get(6)?;
+        let valid_to: Option<String> = row.get(7)?;
 
         let properties = serde_json::from_str(&properties_str).unwrap_or_default();
 
@@ -89,6 +116,8 @@ pub fn load_graph(
                 relationship_type,
                 weight,
                 properties,
+                valid_from: parse_timestamp(valid_from),
+                valid_to: parse_timestamp(valid_to),
             },
         ))
     })?;
@@ -151,6 +180,9 @@ pub fn save_entity(
 
 /// Save a relationship to SQLite.
 ///
+/// A new relationship is valid from now. Saving a relationship that was
+/// invalidated makes it valid again from now.
+///
 /// Returns the database ID of the relationship.
 pub fn save_relationship(
     conn: &Connection,
@@ -164,14 +196,26 @@ pub fn save_relationship(
 
     conn.execute(
         r#"
-        INSERT INTO relationships (source_id, target_id, relationship_type, properties, weight, updated_at)
-        VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))
+        INSERT INTO relationships (
+            source_id, target_id, relationship_type, properties, weight, valid_from, updated_at
+        )
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))
         ON CONFLICT(source_id, target_id, relationship_type) DO UPDATE SET
             properties = excluded.properties,
             weight = excluded.weight,
+            valid_from = CASE WHEN relationships.valid_to IS NULL
+                THEN relationships.valid_from ELSE excluded.valid_from END,
+            valid_to = NULL,
             updated_at = datetime('now')
         "#,
-        params![source_id, target_id, relationship_type, properties_str, weight],
+        params![
+            source_id,
+            target_id,
+            relationship_type,
+            properties_str,
+            weight,
+            Utc::now().to_rfc3339()
+        ],
     )?;
 
     let id: i64 = conn.query_row(
@@ -183,6 +227,33 @@ pub fn save_relationship(
     Ok(id)
 }
 
+/// Validity interval of a relationship: `(valid_from, valid_to)`.
+pub type Validity = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Get the validity interval of a relationship.
+pub fn get_relationship_validity(conn: &Connection, relationship_id: i64) -> RookResult<Validity> {
+    let (valid_from, valid_to): (Option<String>, Option<String>) = conn.query_row(
+        "SELECT valid_from, valid_to FROM relationships WHERE id = ?1",
+        params![relationship_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    Ok((parse_timestamp(valid_from), parse_timestamp(valid_to)))
+}
+
+/// End the validity of a relationship at `valid_to`.
+pub fn invalidate_relationship(
+    conn: &Connection,
+    relationship_id: i64,
+    valid_to: DateTime<Utc>,
+) -> RookResult<bool> {
+    let rows = conn.execute(
+        "UPDATE relationships SET valid_to = ?2, updated_at = datetime('now') \
+         WHERE id = ?1 AND valid_to IS NULL",
+        params![relationship_id, valid_to.to_rfc3339()],
+    )?;
+    Ok(rows > 0)
+}
+
 /// Delete an entity from SQLite.
 ///
 /// Also deletes all related relationships (via CASCADE).
@@ -216,6 +287,115 @@ pub fn get_entity_ids_by_filters(conn: &Connection, filters: &GraphFilters) -> R
     Ok(ids)
 }
 
+/// Replace an entity's properties.
+pub fn update_entity_properties(
+    conn: &Connection,
+    entity_id: i64,
+    properties: &serde_json::Value,
+) -> RookResult<()> {
+    conn.execute(
+        "UPDATE entities SET properties = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![properties.to_string(), entity_id],
+    )?;
+    Ok(())
+}
+
+/// Fold `merged_id` into `canonical_id` and delete it.
+///
+/// Relationships, memory links and access history move to the canonical
+/// entity. Relationships the canonical entity already has, and edges that
+/// would become self-loops, are dropped. Returns how many relationships moved.
+pub fn merge_entity(
+    conn: &Connection,
+    canonical_id: i64,
+    merged_id: i64,
+    canonical_properties: &serde_json::Value,
+) -> RookResult<usize> {
+    let moved = conn.execute(
+        r#"
+        UPDATE OR IGNORE relationships SET source_id = ?1
+        WHERE source_id = ?2 AND target_id != ?1
+        "#,
+        params![canonical_id, merged_id],
+    )? + conn.execute(
+        r#"
+        UPDATE OR IGNORE relationships SET target_id = ?1
+        WHERE target_id = ?2 AND source_id != ?1
+        "#,
+        params![canonical_id, merged_id],
+    )?;
+    conn.execute(
+        "UPDATE OR IGNORE memory_entities SET entity_id = ?1 WHERE entity_id = ?2",
+        params![canonical_id, merged_id],
+    )?;
+    conn.execute(
+        "UPDATE entity_access_log SET entity_id = ?1 WHERE entity_id = ?2",
+        params![canonical_id, merged_id],
+    )?;
+    conn.execute(
+        "UPDATE entities SET properties = ?1, updated_at = datetime('now') WHERE id = ?2",
+        params![canonical_properties.to_string(), canonical_id],
+    )?;
+    // Cascades to whatever could not be moved
+    conn.execute("DELETE FROM entities WHERE id = ?1", params![merged_id])?;
+
+    Ok(moved)
+}
+
+/// Record a merge in the merge history.
+pub fn save_entity_merge(conn: &Connection, merge: &EntityMerge) -> RookResult<i64> {
+    conn.execute(
+        r#"
+        INSERT INTO entity_merges
+            (canonical_id, canonical_name, merged_id, merged_name, similarity,
+             relationships_moved, merged_at)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        "#,
+        params![
+            merge.canonical_id,
+            merge.canonical_name,
+            merge.merged_id,
+            merge.merged_name,
+            merge.similarity,
+            merge.relationships_moved as i64,
+            merge.merged_at.to_rfc3339(),
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Get the most recent merges, newest first.
+pub fn get_entity_merges(conn: &Connection, limit: usize) -> RookResult<Vec<EntityMerge>> {
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT id, canonical_id, canonical_name, merged_id, merged_name, similarity,
+               relationships_moved, merged_at
+        FROM entity_merges
+        ORDER BY id DESC
+        LIMIT ?1
+        "#,
+    )?;
+
+    let merges = stmt
+        .query_map(params![limit as i64], |row| {
+            let relationships_moved: i64 = row.get(6)?;
+            let merged_at: String = row.get(7)?;
+            Ok(EntityMerge {
+                id: row.get(0)?,
+                canonical_id: row.get(1)?,
+                canonical_name: row.get(2)?,
+                merged_id: row.get(3)?,
+                merged_name: row.get(4)?,
+                similarity: row.get(5)?,
+                relationships_moved: relationships_moved as usize,
+                merged_at: parse_timestamp(Some(merged_at)).unwrap_or_else(Utc::now),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(merges)
+}
+
 /// Log an entity access for spreading activation.
 pub fn log_entity_access(
     conn: &Connection,
@@ -287,6 +467,13 @@ fn make_name_key(
     )
 }
 
+/// Parse a stored RFC 3339 timestamp, ignoring malformed values.
+fn parse_timestamp(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -362,6 +549,39 @@ mod tests {
         assert_eq!(graph.edge_count(), 1);
     }
 
+    #[test]
+    fn test_relationship_validity_roundtrip() {
+        let conn = setup_test_db();
+        let filters = GraphFilters::default();
+
+        let alice_id = save_entity(&conn, "Alice", "person", &serde_json::json!({}), &filters).unwrap();
+        let acme_id = save_entity(&conn, "Acme", "company", &serde_json::json!({}), &filters).unwrap();
+        let rel_id =
+            save_relationship(&conn, alice_id, acme_id, "works_at", &serde_json::json!({}), 1.0)
+                .unwrap();
+
+        let (from, to) = get_relationship_validity(&conn, rel_id).unwrap();
+        assert!(from.is_some());
+        assert!(to.is_none());
+
+        assert!(invalidate_relationship(&conn, rel_id, Utc::now()).unwrap());
+        assert!(!invalidate_relationship(&conn, rel_id, Utc::now()).unwrap());
+
+        let mut graph = MemoryGraph::new();
+        let mut db_id_index = DbIdIndex::new();
+        let mut name_index = NameIndex::new();
+        load_graph(&conn, &mut graph, &mut db_id_index, &mut name_index).unwrap();
+        let edge = graph.edge_weights().next().unwrap();
+        assert!(!edge.is_current());
+
+        // Asserting the fact again reopens it
+        save_relationship(&conn, alice_id, acme_id, "works_at", &serde_json::json!({}), 1.0)
+            .unwrap();
+        let (reopened, to) = get_relationship_validity(&conn, rel_id).unwrap();
+        assert!(to.is_none());
+        assert!(reopened >= from);
+    }
+
     #[test]
     fn test_delete_entity_cascades() {
         let conn = setup_test_db();
@@ -382,6 +602,52 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[test]
+    fn test_merge_entity_rewires() {
+        let conn = setup_test_db();
+        let filters = GraphFilters::default();
+        let json = serde_json::json!({});
+
+        let bob = save_entity(&conn, "Bob Smith", "person", &json, &filters).unwrap();
+        let dup = save_entity(&conn, "bob", "person", &json, &filters).unwrap();
+        let acme = save_entity(&conn, "Acme", "organization", &json, &filters).unwrap();
+        let berlin = save_entity(&conn, "Berlin", "location", &json, &filters).unwrap();
+
+        save_relationship(&conn, bob, acme, "works_at", &json, 1.0).unwrap();
+        save_relationship(&conn, dup, acme, "works_at", &json, 1.0).unwrap();
+        save_relationship(&conn, dup, berlin, "lives_in", &json, 1.0).unwrap();
+        save_relationship(&conn, dup, bob, "same_as", &json, 1.0).unwrap();
+        link_memory_to_entity(&conn, "m1", dup, "mentioned").unwrap();
+
+        let properties = serde_json::json!({"aliases": ["bob"]});
+        let moved = merge_entity(&conn, bob, dup, &properties).unwrap();
+        assert_eq!(moved, 1);
+
+        let edges: Vec<(i64, i64, String)> = conn
+            .prepare("SELECT source_id, target_id, relationship_type FROM relationships ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            edges,
+            vec![
+                (bob, acme, "works_at".to_string()),
+                (bob, berlin, "lives_in".to_string()),
+            ]
+        );
+        assert_eq!(get_entities_for_memory(&conn, "m1").unwrap(), vec![bob]);
+
+        // The merged name resolves to the canonical entity after reload
+        let mut graph = MemoryGraph::new();
+        let mut db_id_index = DbIdIndex::new();
+        let mut name_index = NameIndex::new();
+        load_graph(&conn, &mut graph, &mut db_id_index, &mut name_index).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(name_index.get("bob:::"), db_id_index.get(&bob));
+    }
+
     #[test]
     fn test_memory_entity_linking() {
         let conn = setup_test_db();
@@ -39,6 +39,12 @@ impl GraphStoreFactory {
                 Ok(Arc::new(store))
             }
 
+            // `KuzuGraphStore` keeps its graph in memory until it is backed by
+            // the native bindings, so it isn't offered as a provider.
+            GraphStoreProvider::Kuzu => Err(RookError::Configuration(
+                "The kuzu graph store provider is not available yet; use embedded".to_string(),
+            )),
+
             #[allow(unreachable_patterns)]
             _ => Err(RookError::UnsupportedProvider {
                 provider: format!("{:?}", provider),
@@ -82,6 +88,19 @@ mod tests {
     use super::*;
     use rook_core::traits::GraphFilters;
 
+    #[tokio::test]
+    async fn test_factory_rejects_kuzu() {
+        let config = GraphStoreConfig {
+            provider: GraphStoreProvider::Kuzu,
+            url: String::new(),
+            username: None,
+            password: None,
+            database: None,
+        };
+        let result = GraphStoreFactory::create(GraphStoreProvider::Kuzu, config).await;
+        assert!(matches!(result, Err(RookError::Configuration(_))));
+    }
+
     #[tokio::test]
     #[cfg(feature = "embedded")]
     async fn test_factory_create_embedded() {
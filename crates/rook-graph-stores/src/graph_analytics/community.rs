@@ -0,0 +1,308 @@
+//! Community detection: Louvain modularity optimization and label propagation.
+//!
+//! Both algorithms are deterministic: nodes are visited in index order and
+//! ties go to the current community, then to the lowest community ID, so the
+//! same graph always yields the same clusters.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::WeightedGraph;
+
+/// Community detection algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommunityAlgorithm {
+    /// Multi-level modularity optimization (better clusters, slower).
+    #[default]
+    Louvain,
+    /// Label propagation (fast, coarser clusters).
+    LabelPropagation,
+}
+
+/// Community detection settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommunityConfig {
+    /// Algorithm to run (default: Louvain).
+    pub algorithm: CommunityAlgorithm,
+    /// Louvain resolution; higher values give smaller communities (default: 1.0).
+    pub resolution: f64,
+    /// Maximum passes over the nodes per level (default: 20).
+    pub max_iterations: usize,
+    /// Communities with fewer members are dropped (default: 3).
+    pub min_size: usize,
+}
+
+impl Default for CommunityConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: CommunityAlgorithm::Louvain,
+            resolution: 1.0,
+            max_iterations: 20,
+            min_size: 3,
+        }
+    }
+}
+
+/// Modularity of a partition (`communities[node]` is the node's community).
+pub fn modularity(graph: &WeightedGraph, communities: &[usize], resolution: f64) -> f64 {
+    let m2 = graph.total_degree();
+    if m2 == 0.0 {
+        return 0.0;
+    }
+
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut totals: HashMap<usize, f64> = HashMap::new();
+    for node in 0..graph.len() {
+        let community = communities[node];
+        *totals.entry(community).or_insert(0.0) += graph.degree(node);
+        let mut inside = 2.0 * graph.self_loop(node);
+        for (neighbor, weight) in graph.neighbors(node) {
+            if communities[neighbor] == community {
+                inside += weight;
+            }
+        }
+        *internal.entry(community).or_insert(0.0) += inside;
+    }
+
+    totals
+        .iter()
+        .map(|(community, total)| {
+            internal.get(community).copied().unwrap_or(0.0) / m2
+                - resolution * (total / m2).powi(2)
+        })
+        .sum()
+}
+
+/// Louvain community detection. Returns the community of each node.
+pub fn louvain(graph: &WeightedGraph, resolution: f64, max_iterations: usize) -> Vec<usize> {
+    let mut assignment: Vec<usize> = (0..graph.len()).collect();
+    let mut level = graph.clone();
+
+    loop {
+        let (communities, moved) = louvain_level(&level, resolution, max_iterations);
+        if !moved {
+            break;
+        }
+        let (renumbered, count) = renumber(&communities);
+        for community in assignment.iter_mut() {
+            *community = renumbered[*community];
+        }
+        if count == level.len() {
+            break;
+        }
+        level = aggregate(&level, &renumbered, count);
+    }
+
+    renumber(&assignment).0
+}
+
+/// One level of local moves. Returns each node's community and whether any node moved.
+fn louvain_level(
+    graph: &WeightedGraph,
+    resolution: f64,
+    max_iterations: usize,
+) -> (Vec<usize>, bool) {
+    let len = graph.len();
+    let mut communities: Vec<usize> = (0..len).collect();
+    let m2 = graph.total_degree();
+    if m2 == 0.0 {
+        return (communities, false);
+    }
+
+    let degrees: Vec<f64> = (0..len).map(|n| graph.degree(n)).collect();
+    let mut totals = degrees.clone();
+    let mut moved_any = false;
+
+    for _ in 0..max_iterations.max(1) {
+        let mut moved = false;
+        for node in 0..len {
+            let current = communities[node];
+            totals[current] -= degrees[node];
+
+            // Edge weight from `node` into each neighboring community
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            links.insert(current, 0.0);
+            for (neighbor, weight) in graph.neighbors(node) {
+                *links.entry(communities[neighbor]).or_insert(0.0) += weight;
+            }
+
+            let gain = |community: usize, weight: f64| {
+                weight - resolution * totals[community] * degrees[node] / m2
+            };
+            let mut best = current;
+            let mut best_gain = gain(current, links[&current]);
+            for (&community, &weight) in &links {
+                let g = gain(community, weight);
+                if g > best_gain + 1e-12 {
+                    best = community;
+                    best_gain = g;
+                }
+            }
+
+            totals[best] += degrees[node];
+            if best != current {
+                communities[node] = best;
+                moved = true;
+                moved_any = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    (communities, moved_any)
+}
+
+/// Collapse each community into a single node.
+fn aggregate(graph: &WeightedGraph, communities: &[usize], count: usize) -> WeightedGraph {
+    let mut collapsed = WeightedGraph::new(count);
+    for node in 0..graph.len() {
+        let community = communities[node];
+        collapsed.add_edge(community, community, graph.self_loop(node));
+        for (neighbor, weight) in graph.neighbors(node) {
+            if node < neighbor {
+                collapsed.add_edge(community, communities[neighbor], weight);
+            }
+        }
+    }
+    collapsed
+}
+
+/// Map community IDs to `0..count` in order of first appearance.
+fn renumber(communities: &[usize]) -> (Vec<usize>, usize) {
+    let mut ids = HashMap::new();
+    let renumbered = communities
+        .iter()
+        .map(|c| {
+            let next = ids.len();
+            *ids.entry(*c).or_insert(next)
+        })
+        .collect();
+    (renumbered, ids.len())
+}
+
+/// Label propagation. Returns the community of each node.
+///
+/// Labels are updated synchronously from the previous round, so a single
+/// bridge between two dense groups cannot drag one group's label across.
+pub fn label_propagation(graph: &WeightedGraph, max_iterations: usize) -> Vec<usize> {
+    let mut labels: Vec<usize> = (0..graph.len()).collect();
+
+    for _ in 0..max_iterations.max(1) {
+        let next: Vec<usize> = (0..graph.len())
+            .map(|node| {
+                let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+                for (neighbor, weight) in graph.neighbors(node) {
+                    *weights.entry(labels[neighbor]).or_insert(0.0) += weight;
+                }
+                let current = labels[node];
+                let Some(max) = weights.values().copied().reduce(f64::max) else {
+                    return current;
+                };
+                let is_max = |w: f64| (w - max).abs() < 1e-12;
+                if weights.get(&current).is_some_and(|&w| is_max(w)) {
+                    return current;
+                }
+                // BTreeMap iterates in label order: the first maximum is the lowest label.
+                weights
+                    .iter()
+                    .find(|(_, &w)| is_max(w))
+                    .map_or(current, |(&label, _)| label)
+            })
+            .collect();
+
+        if next == labels {
+            break;
+        }
+        labels = next;
+    }
+
+    renumber(&labels).0
+}
+
+/// Run the configured algorithm and group nodes into communities.
+///
+/// Communities smaller than `min_size` are dropped; the rest are returned
+/// largest first, each with its members in node order.
+pub fn detect_communities(graph: &WeightedGraph, config: &CommunityConfig) -> Vec<Vec<usize>> {
+    let assignment = match config.algorithm {
+        CommunityAlgorithm::Louvain => louvain(graph, config.resolution, config.max_iterations),
+        CommunityAlgorithm::LabelPropagation => label_propagation(graph, config.max_iterations),
+    };
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (node, community) in assignment.into_iter().enumerate() {
+        groups.entry(community).or_default().push(node);
+    }
+
+    let mut communities: Vec<Vec<usize>> = groups
+        .into_values()
+        .filter(|members| members.len() >= config.min_size.max(1))
+        .collect();
+    communities.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+    communities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two 4-cliques joined by a single edge.
+    fn two_cliques() -> WeightedGraph {
+        let mut graph = WeightedGraph::new(8);
+        for group in [0..4, 4..8] {
+            for a in group.clone() {
+                for b in group.clone().filter(|&b| b > a) {
+                    graph.add_edge(a, b, 1.0);
+                }
+            }
+        }
+        graph.add_edge(3, 4, 1.0);
+        graph
+    }
+
+    #[test]
+    fn test_louvain_separates_cliques() {
+        let graph = two_cliques();
+        let communities = louvain(&graph, 1.0, 20);
+        assert_eq!(communities[0..4], [communities[0]; 4]);
+        assert_eq!(communities[4..8], [communities[4]; 4]);
+        assert_ne!(communities[0], communities[4]);
+
+        let single = vec![0; 8];
+        assert!(modularity(&graph, &communities, 1.0) > modularity(&graph, &single, 1.0));
+        assert_eq!(louvain(&graph, 1.0, 20), communities, "deterministic");
+    }
+
+    #[test]
+    fn test_label_propagation_separates_cliques() {
+        let graph = two_cliques();
+        let labels = label_propagation(&graph, 20);
+        assert_eq!(labels[0..4], [labels[0]; 4]);
+        assert_eq!(labels[4..8], [labels[4]; 4]);
+        assert_ne!(labels[0], labels[4]);
+    }
+
+    #[test]
+    fn test_detect_communities_drops_small_groups() {
+        let mut graph = two_cliques();
+        graph.adjacency.push(Default::default());
+        graph.self_loops.push(0.0);
+
+        let config = CommunityConfig::default();
+        let communities = detect_communities(&graph, &config);
+        assert_eq!(communities, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+
+        let config = CommunityConfig {
+            algorithm: CommunityAlgorithm::LabelPropagation,
+            min_size: 1,
+            ..Default::default()
+        };
+        assert_eq!(detect_communities(&graph, &config).len(), 3);
+        assert!(detect_communities(&WeightedGraph::new(0), &config).is_empty());
+    }
+}
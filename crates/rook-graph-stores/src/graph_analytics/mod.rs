@@ -0,0 +1,82 @@
+//! Graph analytics over the in-memory entity graph.
+//!
+//! Algorithms work on a [`WeightedGraph`]: an undirected, weighted view of
+//! the petgraph structure in which parallel relationships between two
+//! entities are summed into one edge. The embedded store builds it for a
+//! user/agent/run scope; see [`EmbeddedGraphStore::detect_communities`] and
+//! [`EmbeddedGraphStore::compute_importance`].
+//!
+//! [`EmbeddedGraphStore::detect_communities`]: crate::EmbeddedGraphStore::detect_communities
+//! [`EmbeddedGraphStore::compute_importance`]: crate::EmbeddedGraphStore::compute_importance
+
+pub mod centrality;
+pub mod community;
+
+use std::collections::BTreeMap;
+
+pub use centrality::{
+    degree_centrality, importance_scores, pagerank, CentralityAlgorithm, ImportanceConfig,
+};
+pub use community::{
+    detect_communities, label_propagation, louvain, modularity, CommunityAlgorithm,
+    CommunityConfig,
+};
+
+/// Undirected weighted graph over nodes `0..len`.
+#[derive(Debug, Clone, Default)]
+pub struct WeightedGraph {
+    /// Neighbors of each node with the summed edge weight (self loops excluded).
+    adjacency: Vec<BTreeMap<usize, f64>>,
+    /// Self-loop weight of each node.
+    self_loops: Vec<f64>,
+}
+
+impl WeightedGraph {
+    /// Create a graph with `len` nodes and no edges.
+    pub fn new(len: usize) -> Self {
+        Self {
+            adjacency: vec![BTreeMap::new(); len],
+            self_loops: vec![0.0; len],
+        }
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Whether the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.adjacency.is_empty()
+    }
+
+    /// Add `weight` to the undirected edge between `a` and `b`.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: f64) {
+        if a == b {
+            self.self_loops[a] += weight;
+        } else {
+            *self.adjacency[a].entry(b).or_insert(0.0) += weight;
+            *self.adjacency[b].entry(a).or_insert(0.0) += weight;
+        }
+    }
+
+    /// Neighbors of `node` with edge weights, in node order.
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.adjacency[node].iter().map(|(&n, &w)| (n, w))
+    }
+
+    /// Weighted degree of `node` (self loops count twice).
+    pub fn degree(&self, node: usize) -> f64 {
+        self.adjacency[node].values().sum::<f64>() + 2.0 * self.self_loops[node]
+    }
+
+    /// Self-loop weight of `node`.
+    pub fn self_loop(&self, node: usize) -> f64 {
+        self.self_loops[node]
+    }
+
+    /// Twice the total edge weight.
+    pub fn total_degree(&self) -> f64 {
+        (0..self.len()).map(|n| self.degree(n)).sum()
+    }
+}
@@ -1,22 +1,210 @@
 //! Kuzu graph store implementation.
 //! Kuzu is an embedded graph database.
+//!
+//! Until the native bindings stabilize this store keeps its property graph in
+//! memory. It mirrors the embedded store's behavior: entities are scoped by
+//! user/agent/run, relationships can be invalidated or superseded, memories
+//! link to entities and categories, and categories nest via `subcategory_of`.
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use rook_core::error::RookResult;
-use rook_core::traits::{Entity, GraphFilters, GraphStore, GraphStoreConfig};
+use rook_core::traits::{
+    Entity, EntityWithEmbedding, GraphFilters, GraphPath, GraphStore, GraphStoreConfig,
+    Relationship, MAX_TRAVERSAL_DEPTH,
+};
 use rook_core::types::{GraphRelation, Message};
 
+use crate::category::CategoryNode;
+
+/// An entity stored in the graph.
+#[derive(Debug, Clone)]
+struct NodeRecord {
+    id: i64,
+    name: String,
+    entity_type: String,
+    properties: serde_json::Value,
+    user_id: Option<String>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+}
+
+impl NodeRecord {
+    /// Same scoping rule as the embedded store: an unset field matches any filter.
+    fn matches_filters(&self, filters: &GraphFilters) -> bool {
+        let matches = |field: &Option<String>, filter: &Option<String>| match filter {
+            Some(value) => field.as_deref() == Some(value.as_str()) || field.is_none(),
+            None => true,
+        };
+        matches(&self.user_id, &filters.user_id)
+            && matches(&self.agent_id, &filters.agent_id)
+            && matches(&self.run_id, &filters.run_id)
+    }
+
+    fn in_exact_scope(&self, filters: &GraphFilters) -> bool {
+        self.user_id == filters.user_id
+            && self.agent_id == filters.agent_id
+            && self.run_id == filters.run_id
+    }
+}
+
+/// A relationship stored in the graph.
+#[derive(Debug, Clone)]
+struct EdgeRecord {
+    id: i64,
+    source: i64,
+    target: i64,
+    relationship_type: String,
+    properties: serde_json::Value,
+    /// False once the relationship was invalidated or superseded.
+    current: bool,
+}
+
+/// The whole property graph, guarded by one lock.
+#[derive(Debug, Default)]
+struct KuzuGraph {
+    next_id: i64,
+    nodes: BTreeMap<i64, NodeRecord>,
+    name_index: HashMap<String, i64>,
+    edges: Vec<EdgeRecord>,
+    /// `(memory_id, entity_id)` -> role.
+    memory_links: BTreeMap<(String, i64), String>,
+}
+
+impl KuzuGraph {
+    fn allocate_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn find(&self, name: &str, filters: &GraphFilters) -> Option<i64> {
+        self.name_index.get(&name_key(name, filters)).copied()
+    }
+
+    fn upsert_entity(
+        &mut self,
+        name: &str,
+        entity_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> i64 {
+        if let Some(id) = self.find(name, filters) {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.entity_type = entity_type.to_string();
+                node.properties = properties.clone();
+            }
+            return id;
+        }
+
+        let id = self.allocate_id();
+        self.nodes.insert(
+            id,
+            NodeRecord {
+                id,
+                name: name.to_string(),
+                entity_type: entity_type.to_string(),
+                properties: properties.clone(),
+                user_id: filters.user_id.clone(),
+                agent_id: filters.agent_id.clone(),
+                run_id: filters.run_id.clone(),
+            },
+        );
+        self.name_index.insert(name_key(name, filters), id);
+        id
+    }
+
+    fn get_or_create(&mut self, name: &str, entity_type: &str, filters: &GraphFilters) -> i64 {
+        match self.find(name, filters) {
+            Some(id) => id,
+            None => self.upsert_entity(name, entity_type, &serde_json::json!({}), filters),
+        }
+    }
+
+    /// Add `source --[relationship_type]--> target`, reviving or updating an
+    /// existing edge between the same pair instead of duplicating it.
+    fn upsert_edge(
+        &mut self,
+        source: i64,
+        target: i64,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+    ) -> i64 {
+        if let Some(edge) = self.edges.iter_mut().find(|e| {
+            e.source == source && e.target == target && e.relationship_type == relationship_type
+        }) {
+            edge.properties = properties.clone();
+            edge.current = true;
+            return edge.id;
+        }
+
+        let id = self.allocate_id();
+        self.edges.push(EdgeRecord {
+            id,
+            source,
+            target,
+            relationship_type: relationship_type.to_string(),
+            properties: properties.clone(),
+            current: true,
+        });
+        id
+    }
+
+    /// End current outgoing edges of `source` for which
+    /// `matches(target_name, relationship_type)` holds. Returns how many ended.
+    fn invalidate_outgoing(&mut self, source: i64, matches: impl Fn(&str, &str) -> bool) -> usize {
+        let mut ended = 0;
+        for edge in self.edges.iter_mut().filter(|e| e.source == source && e.current) {
+            let target = self.nodes.get(&edge.target).map(|n| n.name.as_str()).unwrap_or("");
+            if matches(target, &edge.relationship_type) {
+                edge.current = false;
+                ended += 1;
+            }
+        }
+        ended
+    }
+
+    fn relation(&self, edge: &EdgeRecord) -> Option<GraphRelation> {
+        Some(GraphRelation {
+            source: self.nodes.get(&edge.source)?.name.clone(),
+            relationship: edge.relationship_type.clone(),
+            target: self.nodes.get(&edge.target)?.name.clone(),
+        })
+    }
+
+    fn remove_nodes(&mut self, ids: &HashSet<i64>) {
+        for id in ids {
+            if let Some(node) = self.nodes.remove(id) {
+                let scope = GraphFilters {
+                    user_id: node.user_id,
+                    agent_id: node.agent_id,
+                    run_id: node.run_id,
+                };
+                self.name_index.remove(&name_key(&node.name, &scope));
+            }
+        }
+        self.edges
+            .retain(|e| !ids.contains(&e.source) && !ids.contains(&e.target));
+        self.memory_links.retain(|(_, entity_id), _| !ids.contains(entity_id));
+    }
+}
+
+fn name_key(name: &str, filters: &GraphFilters) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        name,
+        filters.user_id.as_deref().unwrap_or(""),
+        filters.agent_id.as_deref().unwrap_or(""),
+        filters.run_id.as_deref().unwrap_or("")
+    )
+}
+
 /// In-memory graph store (placeholder for Kuzu).
 /// Kuzu requires native bindings which are not yet stable.
 pub struct KuzuGraphStore {
-    /// In-memory storage of relations.
-    relations: Arc<RwLock<HashMap<String, Vec<GraphRelation>>>>,
-    /// In-memory storage of entities.
-    entities: Arc<RwLock<HashMap<String, Vec<Entity>>>>,
+    graph: Arc<RwLock<KuzuGraph>>,
     #[allow(dead_code)]
     config: GraphStoreConfig,
 }
@@ -25,11 +213,221 @@ impl KuzuGraphStore {
     /// Create a new Kuzu graph store.
     pub async fn new(config: GraphStoreConfig) -> RookResult<Self> {
         Ok(Self {
-            relations: Arc::new(RwLock::new(HashMap::new())),
-            entities: Arc::new(RwLock::new(HashMap::new())),
+            graph: Arc::new(RwLock::new(KuzuGraph::default())),
             config,
         })
     }
+
+    /// Get neighbors of an entity over currently valid relationships,
+    /// both outgoing and incoming.
+    pub async fn get_neighbors(
+        &self,
+        entity_name: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphRelation>> {
+        let graph = self.graph.read().await;
+        let Some(id) = graph.find(entity_name, filters) else {
+            return Ok(vec![]);
+        };
+
+        let outgoing = graph.edges.iter().filter(|e| e.current && e.source == id);
+        let incoming = graph.edges.iter().filter(|e| e.current && e.target == id);
+        Ok(outgoing
+            .chain(incoming)
+            .filter_map(|edge| graph.relation(edge))
+            .collect())
+    }
+
+    /// Get entity count.
+    pub async fn entity_count(&self) -> RookResult<usize> {
+        Ok(self.graph.read().await.nodes.len())
+    }
+
+    /// Get relationship count.
+    pub async fn relationship_count(&self) -> RookResult<usize> {
+        Ok(self.graph.read().await.edges.len())
+    }
+
+    // ==================== Memory Links ====================
+
+    /// Record that a memory mentions an entity, creating the entity if needed.
+    pub async fn link_memory_to_entity(
+        &self,
+        memory_id: &str,
+        entity_name: &str,
+        role: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<()> {
+        let mut graph = self.graph.write().await;
+        let entity_id = graph.get_or_create(entity_name, "entity", filters);
+        graph
+            .memory_links
+            .insert((memory_id.to_string(), entity_id), role.to_string());
+        Ok(())
+    }
+
+    /// Get the IDs of memories that mention an entity.
+    pub async fn get_memories_for_entity(
+        &self,
+        entity_name: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<String>> {
+        let graph = self.graph.read().await;
+        let Some(entity_id) = graph.find(entity_name, filters) else {
+            return Ok(vec![]);
+        };
+
+        Ok(graph
+            .memory_links
+            .keys()
+            .filter(|(_, id)| *id == entity_id)
+            .map(|(memory_id, _)| memory_id.clone())
+            .collect())
+    }
+
+    /// Get the entities a memory mentions, within `filters`.
+    pub async fn get_entities_for_memory(
+        &self,
+        memory_id: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<Entity>> {
+        let graph = self.graph.read().await;
+
+        Ok(graph
+            .memory_links
+            .keys()
+            .filter(|(id, _)| id == memory_id)
+            .filter_map(|(_, entity_id)| graph.nodes.get(entity_id))
+            .filter(|node| node.matches_filters(filters))
+            .map(|node| Entity {
+                name: node.name.clone(),
+                entity_type: node.entity_type.clone(),
+                properties: node.properties.clone(),
+            })
+            .collect())
+    }
+
+    // ==================== Category Operations ====================
+
+    /// Add a category node to the graph.
+    ///
+    /// Categories are stored as entities with entity_type="category".
+    pub async fn add_category(
+        &self,
+        category: &CategoryNode,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        let properties = serde_json::json!({
+            "description": category.description,
+            "is_system": category.is_system,
+            "parent_category": category.parent_category,
+        });
+
+        let mut graph = self.graph.write().await;
+        let entity_id = graph.upsert_entity(&category.name, "category", &properties, filters);
+
+        if let Some(ref parent) = category.parent_category {
+            let parent_id = graph.get_or_create(parent, "category", filters);
+            graph.upsert_edge(entity_id, parent_id, "subcategory_of", &serde_json::json!({}));
+        }
+
+        Ok(entity_id)
+    }
+
+    /// Link a memory to a category.
+    ///
+    /// Creates a "belongs_to_category" relationship from memory to category.
+    pub async fn link_memory_to_category(
+        &self,
+        memory_id: &str,
+        category_name: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<()> {
+        let mut graph = self.graph.write().await;
+        let memory_entity_id =
+            graph.get_or_create(&format!("memory:{}", memory_id), "memory", filters);
+        let category_id = graph.get_or_create(category_name, "category", filters);
+
+        graph.upsert_edge(
+            memory_entity_id,
+            category_id,
+            "belongs_to_category",
+            &serde_json::json!({}),
+        );
+        graph
+            .memory_links
+            .insert((memory_id.to_string(), category_id), "category".to_string());
+
+        Ok(())
+    }
+
+    /// Get all memory IDs in a category.
+    ///
+    /// Returns memory IDs (without the "memory:" prefix) that belong to the given category.
+    pub async fn get_memories_in_category(
+        &self,
+        category_name: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<String>> {
+        let graph = self.graph.read().await;
+        let Some(category_id) = graph.find(category_name, filters) else {
+            return Ok(vec![]);
+        };
+
+        Ok(graph
+            .edges
+            .iter()
+            .filter(|e| e.target == category_id && e.relationship_type == "belongs_to_category")
+            .filter_map(|e| graph.nodes.get(&e.source))
+            .filter_map(|node| node.name.strip_prefix("memory:"))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Get all categories that a memory belongs to.
+    pub async fn get_categories_for_memory(
+        &self,
+        memory_id: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<String>> {
+        let graph = self.graph.read().await;
+        let Some(memory_entity_id) = graph.find(&format!("memory:{}", memory_id), filters) else {
+            return Ok(vec![]);
+        };
+
+        Ok(graph
+            .edges
+            .iter()
+            .filter(|e| {
+                e.source == memory_entity_id && e.relationship_type == "belongs_to_category"
+            })
+            .filter_map(|e| graph.nodes.get(&e.target))
+            .filter(|node| node.entity_type == "category")
+            .map(|node| node.name.clone())
+            .collect())
+    }
+
+    /// Initialize default categories in the graph.
+    ///
+    /// Safe to call multiple times (idempotent).
+    pub async fn initialize_default_categories(&self, filters: &GraphFilters) -> RookResult<()> {
+        for category in crate::category::default_categories() {
+            self.add_category(&category, filters).await?;
+        }
+        Ok(())
+    }
+
+    /// Get all category names in the graph.
+    pub async fn get_all_categories(&self, filters: &GraphFilters) -> RookResult<Vec<String>> {
+        let graph = self.graph.read().await;
+
+        Ok(graph
+            .nodes
+            .values()
+            .filter(|node| node.entity_type == "category" && node.matches_filters(filters))
+            .map(|node| node.name.clone())
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -40,79 +438,343 @@ impl GraphStore for KuzuGraphStore {
         filters: &GraphFilters,
     ) -> RookResult<Vec<GraphRelation>> {
         let user_id = filters.user_id.clone().unwrap_or_else(|| "default".to_string());
+        let mut graph = self.graph.write().await;
         let mut new_relations = Vec::new();
 
+        let user = graph.get_or_create(&user_id, "user", filters);
         for message in messages {
-            let relation = GraphRelation {
+            let memory =
+                graph.upsert_entity(&message.content, "Memory", &serde_json::json!({}), filters);
+            graph.upsert_edge(user, memory, "HAS_MEMORY", &serde_json::json!({}));
+
+            new_relations.push(GraphRelation {
                 source: user_id.clone(),
                 relationship: "HAS_MEMORY".to_string(),
                 target: message.content.clone(),
-            };
-            new_relations.push(relation.clone());
-
-            // Store entity
-            let entity = Entity {
-                name: message.content.clone(),
-                entity_type: "Memory".to_string(),
-                properties: serde_json::json!({}),
-            };
-
-            let mut entities = self.entities.write().await;
-            entities
-                .entry(user_id.clone())
-                .or_default()
-                .push(entity);
+            });
         }
 
-        // Store relations
-        let mut relations = self.relations.write().await;
-        relations
-            .entry(user_id)
-            .or_default()
-            .extend(new_relations.clone());
-
         Ok(new_relations)
     }
 
+    /// Search current relationships whose source or target name contains the
+    /// query (case-insensitive).
     async fn search(
         &self,
         query: &str,
         filters: &GraphFilters,
         limit: usize,
     ) -> RookResult<Vec<GraphRelation>> {
-        let user_id = filters.user_id.clone().unwrap_or_else(|| "default".to_string());
-        let relations = self.relations.read().await;
-
-        let results: Vec<GraphRelation> = relations
-            .get(&user_id)
-            .map(|rels| {
-                rels.iter()
-                    .filter(|r| r.target.contains(query))
-                    .take(limit)
-                    .cloned()
-                    .collect()
+        let graph = self.graph.read().await;
+        let query = query.to_lowercase();
+        let matches = |id: &i64| {
+            graph.nodes.get(id).is_some_and(|node| {
+                node.matches_filters(filters) && node.name.to_lowercase().contains(&query)
             })
-            .unwrap_or_default();
+        };
 
-        Ok(results)
+        Ok(graph
+            .edges
+            .iter()
+            .filter(|e| e.current && (matches(&e.source) || matches(&e.target)))
+            .filter_map(|edge| graph.relation(edge))
+            .take(limit)
+            .collect())
     }
 
+    /// Delete every entity in exactly this scope, with its relationships and
+    /// memory links.
     async fn delete_all(&self, filters: &GraphFilters) -> RookResult<()> {
-        let user_id = filters.user_id.clone().unwrap_or_else(|| "default".to_string());
+        let mut graph = self.graph.write().await;
+        let ids: HashSet<i64> = graph
+            .nodes
+            .values()
+            .filter(|node| node.in_exact_scope(filters))
+            .map(|node| node.id)
+            .collect();
+        graph.remove_nodes(&ids);
+        Ok(())
+    }
 
-        let mut relations = self.relations.write().await;
-        relations.remove(&user_id);
+    async fn get_all(&self, filters: &GraphFilters) -> RookResult<Vec<Entity>> {
+        let graph = self.graph.read().await;
 
-        let mut entities = self.entities.write().await;
-        entities.remove(&user_id);
+        Ok(graph
+            .nodes
+            .values()
+            .filter(|node| node.matches_filters(filters))
+            .map(|node| Entity {
+                name: node.name.clone(),
+                entity_type: node.entity_type.clone(),
+                properties: node.properties.clone(),
+            })
+            .collect())
+    }
 
-        Ok(())
+    async fn get_all_relationships(&self, filters: &GraphFilters) -> RookResult<Vec<Relationship>> {
+        let graph = self.graph.read().await;
+        let in_scope = |id: &i64| graph.nodes.get(id).filter(|node| node.matches_filters(filters));
+
+        Ok(graph
+            .edges
+            .iter()
+            .filter(|edge| edge.current)
+            .filter_map(|edge| {
+                Some(Relationship {
+                    source: in_scope(&edge.source)?.name.clone(),
+                    target: in_scope(&edge.target)?.name.clone(),
+                    relationship_type: edge.relationship_type.clone(),
+                    properties: edge.properties.clone(),
+                })
+            })
+            .collect())
     }
 
-    async fn get_all(&self, filters: &GraphFilters) -> RookResult<Vec<Entity>> {
-        let user_id = filters.user_id.clone().unwrap_or_else(|| "default".to_string());
-        let entities = self.entities.read().await;
+    async fn add_entity(
+        &self,
+        name: &str,
+        entity_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        let mut graph = self.graph.write().await;
+        Ok(graph.upsert_entity(name, entity_type, properties, filters))
+    }
+
+    async fn add_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        let mut graph = self.graph.write().await;
+        let source = graph.get_or_create(source_name, "entity", filters);
+        let target = graph.get_or_create(target_name, "entity", filters);
+        Ok(graph.upsert_edge(source, target, relationship_type, properties))
+    }
+
+    async fn supersede_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        properties: &serde_json::Value,
+        filters: &GraphFilters,
+    ) -> RookResult<i64> {
+        let mut graph = self.graph.write().await;
+        let source = graph.get_or_create(source_name, "entity", filters);
+        let target = graph.get_or_create(target_name, "entity", filters);
+        graph.invalidate_outgoing(source, |target, relationship| {
+            relationship.eq_ignore_ascii_case(relationship_type)
+                && !target.eq_ignore_ascii_case(target_name)
+        });
+        Ok(graph.upsert_edge(source, target, relationship_type, properties))
+    }
+
+    async fn invalidate_relationship(
+        &self,
+        source_name: &str,
+        target_name: &str,
+        relationship_type: &str,
+        filters: &GraphFilters,
+    ) -> RookResult<bool> {
+        let mut graph = self.graph.write().await;
+        let Some(source) = graph.find(source_name, filters) else {
+            return Ok(false);
+        };
+        let ended = graph.invalidate_outgoing(source, |target, relationship| {
+            relationship.eq_ignore_ascii_case(relationship_type)
+                && target.eq_ignore_ascii_case(target_name)
+        });
+        Ok(ended > 0)
+    }
+
+    async fn traverse(
+        &self,
+        start_entity: &str,
+        max_depth: usize,
+        relationship_filter: Option<&[String]>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphPath>> {
+        let graph = self.graph.read().await;
+
+        // Exact key first, then a case-insensitive name match within scope
+        let start_id = graph.find(start_entity, filters).or_else(|| {
+            graph
+                .nodes
+                .values()
+                .find(|n| n.name.eq_ignore_ascii_case(start_entity) && n.matches_filters(filters))
+                .map(|n| n.id)
+        });
+        let Some(start_id) = start_id else {
+            return Ok(vec![]);
+        };
+
+        let allowed: Option<HashSet<String>> =
+            relationship_filter.map(|types| types.iter().map(|t| t.to_lowercase()).collect());
+        let max_depth = max_depth.min(MAX_TRAVERSAL_DEPTH);
+        let start = graph.nodes[&start_id].name.clone();
+
+        let mut visited = HashSet::from([start_id]);
+        let mut queue = VecDeque::from([(start_id, Vec::<GraphRelation>::new())]);
+        let mut paths = Vec::new();
+
+        while let Some((node_id, hops)) = queue.pop_front() {
+            if hops.len() >= max_depth {
+                continue;
+            }
+
+            for edge in graph.edges.iter().filter(|e| e.current && e.source == node_id) {
+                if let Some(ref allowed) = allowed {
+                    if !allowed.contains(&edge.relationship_type.to_lowercase()) {
+                        continue;
+                    }
+                }
+                let Some(target) = graph.nodes.get(&edge.target) else {
+                    continue;
+                };
+                if !target.matches_filters(filters) || !visited.insert(target.id) {
+                    continue;
+                }
+
+                let mut next = hops.clone();
+                next.push(GraphRelation {
+                    source: graph.nodes[&node_id].name.clone(),
+                    relationship: edge.relationship_type.clone(),
+                    target: target.name.clone(),
+                });
+                paths.push(GraphPath {
+                    start: start.clone(),
+                    hops: next.clone(),
+                });
+                queue.push_back((target.id, next));
+            }
+        }
+
+        Ok(paths)
+    }
+
+    async fn get_entities_for_merge(
+        &self,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<EntityWithEmbedding>> {
+        let graph = self.graph.read().await;
+
+        Ok(graph
+            .nodes
+            .values()
+            .filter(|node| node.matches_filters(filters))
+            .map(|node| EntityWithEmbedding {
+                id: node.id,
+                name: node.name.clone(),
+                entity_type: node.entity_type.clone(),
+                embedding: node
+                    .properties
+                    .get("embedding")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| arr.iter().filter_map(|v| v.as_f64().map(|f| f as f32)).collect()),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rook_core::traits::GraphStoreProvider;
+
+    async fn store() -> KuzuGraphStore {
+        let config = GraphStoreConfig {
+            provider: GraphStoreProvider::Kuzu,
+            ..Default::default()
+        };
+        KuzuGraphStore::new(config).await.unwrap()
+    }
+
+    fn alice() -> GraphFilters {
+        GraphFilters {
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relationships_neighbors_and_traversal() {
+        let store = store().await;
+        let filters = alice();
+        let json = serde_json::json!({});
+
+        store.add_relationship("Alice", "Acme", "works_at", &json, &filters).await.unwrap();
+        store.add_relationship("Acme", "Berlin", "located_in", &json, &filters).await.unwrap();
+        store.add_relationship("Bob", "Alice", "knows", &json, &filters).await.unwrap();
+
+        let neighbors = store.get_neighbors("Alice", &filters).await.unwrap();
+        assert_eq!(neighbors.len(), 2);
+        assert!(neighbors.iter().any(|r| r.source == "Bob" && r.relationship == "knows"));
+
+        let paths = store.traverse("alice", 2, None, &filters).await.unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[1].end(), "Berlin");
+
+        store
+            .supersede_relationship("Alice", "Initech", "works_at", &json, &filters)
+            .await
+            .unwrap();
+        let current = store.search("alice", &filters, 10).await.unwrap();
+        assert!(current.iter().all(|r| r.target != "Acme"));
+        assert!(current.iter().any(|r| r.target == "Initech"));
+        assert!(store
+            .invalidate_relationship("Alice", "Initech", "WORKS_AT", &filters)
+            .await
+            .unwrap());
+
+        // Another user's graph is invisible
+        let bob = GraphFilters {
+            user_id: Some("bob".to_string()),
+            ..Default::default()
+        };
+        assert!(store.get_neighbors("Alice", &bob).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_categories_and_memory_links() {
+        let store = store().await;
+        let filters = alice();
+
+        store.initialize_default_categories(&filters).await.unwrap();
+        store.initialize_default_categories(&filters).await.unwrap();
+        store
+            .add_category(
+                &CategoryNode::new("q4_launch", "Q4 launch").with_parent("professional"),
+                &filters,
+            )
+            .await
+            .unwrap();
+        assert_eq!(store.get_all_categories(&filters).await.unwrap().len(), 11);
+
+        store.link_memory_to_category("mem-1", "q4_launch", &filters).await.unwrap();
+        assert_eq!(
+            store.get_memories_in_category("q4_launch", &filters).await.unwrap(),
+            vec!["mem-1"]
+        );
+        assert_eq!(
+            store.get_categories_for_memory("mem-1", &filters).await.unwrap(),
+            vec!["q4_launch"]
+        );
+
+        store.link_memory_to_entity("mem-1", "Acme", "mentioned", &filters).await.unwrap();
+        assert_eq!(
+            store.get_memories_for_entity("Acme", &filters).await.unwrap(),
+            vec!["mem-1"]
+        );
+        let entities = store.get_entities_for_memory("mem-1", &filters).await.unwrap();
+        assert!(entities.iter().any(|e| e.name == "Acme"));
 
-        Ok(entities.get(&user_id).cloned().unwrap_or_default())
+        store.delete_all(&filters).await.unwrap();
+        assert!(store.get_all(&filters).await.unwrap().is_empty());
+        assert!(store.get_memories_for_entity("Acme", &filters).await.unwrap().is_empty());
+        assert_eq!(store.relationship_count().await.unwrap(), 0);
     }
 }
@@ -9,6 +9,8 @@
 //! - **Neo4j** (feature: `neo4j`) - Neo4j graph database
 //! - **Memgraph** (feature: `memgraph`) - Memgraph (Neo4j-compatible)
 //! - **Neptune** (feature: `neptune`) - AWS Neptune
+//! - **Kuzu** (feature: `kuzu`) - in-memory preview of a Kuzu store, not yet
+//!   selectable through [`GraphStoreFactory`]
 //!
 //! # Architecture
 //!
@@ -21,15 +23,21 @@
 
 mod factory;
 
+#[cfg(any(feature = "neo4j", feature = "memgraph"))]
+mod cypher;
+
 #[cfg(feature = "embedded")]
 pub mod activation;
 
-#[cfg(feature = "embedded")]
+#[cfg(any(feature = "embedded", feature = "kuzu"))]
 pub mod category;
 
 #[cfg(feature = "embedded")]
 pub mod embedded;
 
+#[cfg(feature = "embedded")]
+pub mod graph_analytics;
+
 #[cfg(feature = "embedded")]
 pub mod entity;
 
@@ -48,7 +56,15 @@ mod neptune;
 pub use factory::GraphStoreFactory;
 
 #[cfg(feature = "embedded")]
-pub use embedded::EmbeddedGraphStore;
+pub use embedded::{
+    EmbeddedGraphStore, EntityDedupJob, EntityImportanceJob, EntityMerge, GraphExportFormat,
+    NodeLinkGraph, TemporalRelation, TopicCluster,
+};
+
+#[cfg(feature = "embedded")]
+pub use graph_analytics::{
+    CentralityAlgorithm, CommunityAlgorithm, CommunityConfig, ImportanceConfig,
+};
 
 #[cfg(feature = "embedded")]
 pub use entity::{
@@ -70,4 +86,4 @@ pub use kuzu::KuzuGraphStore;
 pub use neptune::NeptuneGraphStore;
 
 // Re-export core types
-pub use rook_core::traits::{GraphStore, GraphStoreConfig, GraphStoreProvider};
+pub use rook_core::traits::{GraphPath, GraphStore, GraphStoreConfig, GraphStoreProvider};
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use neo4rs::{query, Graph};
 
 use rook_core::error::{RookError, RookResult};
-use rook_core::traits::{Entity, GraphFilters, GraphStore, GraphStoreConfig};
+use rook_core::traits::{Entity, GraphFilters, GraphPath, GraphStore, GraphStoreConfig};
+
+use crate::cypher::{row_to_path, shortest_per_end, traversal_query};
 use rook_core::types::{GraphRelation, Message};
 
 /// Neo4j graph store implementation.
@@ -172,4 +174,34 @@ impl GraphStore for Neo4jGraphStore {
 
         Ok(entities)
     }
-}
\ No newline at end of file
+
+    async fn traverse(
+        &self,
+        start_entity: &str,
+        max_depth: usize,
+        relationship_filter: Option<&[String]>,
+        filters: &GraphFilters,
+    ) -> RookResult<Vec<GraphPath>> {
+        if max_depth == 0 {
+            return Ok(vec![]);
+        }
+
+        let q = traversal_query(start_entity, max_depth, relationship_filter, filters);
+        let mut result = self
+            .graph
+            .execute(q)
+            .await
+            .map_err(|e| RookError::graph_store(format!("Failed to traverse graph: {}", e)))?;
+
+        let mut paths = Vec::new();
+        while let Some(row) = result
+            .next()
+            .await
+            .map_err(|e| RookError::graph_store(format!("Failed to fetch row: {}", e)))?
+        {
+            paths.extend(row_to_path(&row));
+        }
+
+        Ok(shortest_per_end(paths))
+    }
+}
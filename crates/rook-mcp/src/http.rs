@@ -0,0 +1,127 @@
+//! Streamable HTTP transport.
+//!
+//! Serves the MCP endpoint at `/mcp` over HTTP (with SSE for server
+//! messages), so several MCP clients, possibly on other machines, can share
+//! one Rook memory. Every client session talks to the same [`MemoryServer`]
+//! state, so resource subscribers are notified of changes made by any client.
+//!
+//! When a token is configured, requests must carry it as
+//! `Authorization: Bearer <token>`.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::{StreamableHttpServerConfig, StreamableHttpService};
+use subtle::ConstantTimeEq;
+
+use crate::server::MemoryServer;
+
+/// Address the HTTP transport binds to by default.
+pub const DEFAULT_BIND: &str = "127.0.0.1:8765";
+
+/// Path of the MCP endpoint.
+pub const MCP_PATH: &str = "/mcp";
+
+/// Build the HTTP router for the MCP endpoint.
+///
+/// Without a token, every request is let through.
+pub fn router(server: MemoryServer, token: Option<String>) -> Router {
+    let service = StreamableHttpService::new(
+        move || Ok(server.clone()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    let router = Router::new().nest_service(MCP_PATH, service);
+    match token {
+        Some(token) => router.layer(middleware::from_fn_with_state(Arc::new(token), bearer_auth)),
+        None => router,
+    }
+}
+
+/// Serve the MCP endpoint on `bind` until the process is stopped.
+pub async fn serve(
+    server: MemoryServer,
+    bind: SocketAddr,
+    token: Option<String>,
+) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    tracing::info!("MCP server listening on http://{}{}", bind, MCP_PATH);
+    axum::serve(listener, router(server, token)).await?;
+    Ok(())
+}
+
+/// Reject requests without the configured bearer token.
+async fn bearer_auth(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let provided = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|header| header.strip_prefix("Bearer "));
+
+    match provided {
+        Some(provided) if bool::from(provided.as_bytes().ct_eq(token.as_bytes())) => {
+            next.run(request).await
+        }
+        Some(_) => (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
+        None => (StatusCode::UNAUTHORIZED, "Missing bearer token").into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        Router::new()
+            .route(MCP_PATH, get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(
+                Arc::new("secret".to_string()),
+                bearer_auth,
+            ))
+    }
+
+    async fn status(authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(MCP_PATH);
+        if let Some(value) = authorization {
+            request = request.header(AUTHORIZATION, value);
+        }
+        app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_rejected() {
+        assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(Some("secret")).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_token_is_rejected() {
+        assert_eq!(status(Some("Bearer wrong")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(Some("Bearer secre")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(Some("Bearer secret2")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_correct_token_is_accepted() {
+        assert_eq!(status(Some("Bearer secret")).await, StatusCode::OK);
+    }
+}
@@ -0,0 +1,188 @@
+//! Time-based intentions run by the MCP server.
+//!
+//! [`start`] schedules the time-based intentions of a store and runs their
+//! actions as they fire. `notify_mcp` actions reach the clients connected to
+//! the server when the executor comes from
+//! [`MemoryServer::intention_executor`](crate::server::MemoryServer::intention_executor)
+//! (see [`notifications`](crate::notifications)).
+//!
+//! The store is `ROOK_INTENTION_DB_PATH`, as for rook-server, so both can
+//! share one; `ROOK_DISABLE_INTENTIONS` turns the scheduler off.
+
+use std::sync::Arc;
+
+use rook_core::error::RookResult;
+use rook_core::intentions::{
+    ActionExecutor, ActionResult, FiredIntention, IntentionScheduler, IntentionStore,
+};
+
+/// Environment variable naming the intention store database.
+pub const INTENTION_DB_ENV: &str = "ROOK_INTENTION_DB_PATH";
+
+/// Environment variable turning the intention scheduler off.
+pub const DISABLE_INTENTIONS_ENV: &str = "ROOK_DISABLE_INTENTIONS";
+
+/// Schedule the time-based intentions of `store` and run their actions with
+/// `executor` as they fire.
+///
+/// Returns the number of intentions scheduled. The scheduler runs for the
+/// rest of the process.
+pub async fn start(executor: ActionExecutor, store: Arc<dyn IntentionStore>) -> RookResult<usize> {
+    let (scheduler, mut fired_rx) = IntentionScheduler::new().await?;
+    let count = scheduler.load_from_store(store.as_ref()).await?;
+    scheduler.start().await?;
+
+    tokio::spawn(async move {
+        while let Some(fired) = fired_rx.recv().await {
+            let intention_id = fired.intention_id;
+            if run_fired(&executor, store.as_ref(), fired).await {
+                if let Err(e) = scheduler.unschedule(intention_id).await {
+                    tracing::error!("Failed to unschedule intention {}: {}", intention_id, e);
+                }
+            }
+        }
+    });
+    Ok(count)
+}
+
+/// Execute the action of a fired intention and record the fire.
+///
+/// Fires of intentions that were deactivated, expired or used up are
+/// dropped. Returns whether the intention can no longer fire, so its
+/// schedule should be removed.
+pub async fn run_fired(
+    executor: &ActionExecutor,
+    store: &dyn IntentionStore,
+    mut fired: FiredIntention,
+) -> bool {
+    match store.get(fired.intention_id) {
+        Ok(Some(mut intention)) if intention.can_fire() => {
+            fired.action_result = executor.execute(&intention, &fired.reason).await;
+            if let ActionResult::Failed { ref error } = fired.action_result {
+                tracing::warn!("Action of intention {} failed: {}", intention.id, error);
+            }
+            if let Err(e) = store.record_fire(intention.id, &fired) {
+                tracing::error!("Failed to record fire of intention {}: {}", intention.id, e);
+            }
+            intention.fire_count += 1;
+            !intention.can_fire()
+        }
+        Ok(_) => true,
+        Err(e) => {
+            tracing::error!("Failed to load intention {}: {}", fired.intention_id, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use rmcp::model::LoggingMessageNotificationParam;
+    use rmcp::service::NotificationContext;
+    use rmcp::{ClientHandler, RoleClient, ServiceExt};
+    use rook_core::intentions::{
+        Intention, IntentionAction, SqliteIntentionStore, TriggerCondition, TriggerReason,
+    };
+    use tokio::sync::{mpsc, RwLock};
+
+    use super::*;
+    use crate::notifications::INTENTIONS_LOGGER;
+    use crate::profiles::ProfileConfig;
+    use crate::providers;
+    use crate::server::MemoryServer;
+
+    /// Client passing the log messages it receives to a channel.
+    struct LogRecorder(mpsc::UnboundedSender<LoggingMessageNotificationParam>);
+
+    impl ClientHandler for LogRecorder {
+        async fn on_logging_message(
+            &self,
+            params: LoggingMessageNotificationParam,
+            _context: NotificationContext<RoleClient>,
+        ) {
+            let _ = self.0.send(params);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fired_notify_mcp_intention_reaches_connected_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let config =
+            providers::memory_config(None, dir.path(), &ProfileConfig::default(), |name| {
+                (name == "OPENAI_API_KEY").then(|| "test-key".to_string())
+            })
+            .unwrap();
+        let memory = providers::create_memory(config).await.unwrap();
+        let server = MemoryServer::new(Arc::new(RwLock::new(memory)));
+        let executor = server.intention_executor();
+
+        // The server registers the client's peer once it is initialized
+        let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(async move {
+            if let Ok(service) = server.serve(server_io).await {
+                let _ = service.waiting().await;
+            }
+        });
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _client = LogRecorder(tx).serve(client_io).await.unwrap();
+
+        let store = SqliteIntentionStore::in_memory().unwrap();
+        let intention = Intention::new(
+            "Standup",
+            TriggerCondition::scheduled_at(Utc::now()),
+            IntentionAction::NotifyMcp { message: None },
+        )
+        .max_fires(1);
+        store.add(&intention).unwrap();
+
+        let fired = FiredIntention::success(
+            intention.id,
+            TriggerReason::ScheduledTime {
+                scheduled_at: Utc::now(),
+            },
+        );
+        let exhausted = run_fired(&executor, &store, fired).await;
+        assert!(exhausted);
+
+        let message = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.logger.as_deref(), Some(INTENTIONS_LOGGER));
+        assert_eq!(message.data["intention_id"], intention.id.to_string());
+        assert_eq!(message.data["intention_name"], "Standup");
+
+        let history = store.get_fire_history(intention.id, 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(matches!(
+            history[0].action_result,
+            ActionResult::Success { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fire_of_inactive_intention_is_dropped() {
+        let store = SqliteIntentionStore::in_memory().unwrap();
+        let mut intention = Intention::new(
+            "Paused",
+            TriggerCondition::scheduled_at(Utc::now()),
+            IntentionAction::Log {
+                message: "never".to_string(),
+            },
+        );
+        intention.active = false;
+        store.add(&intention).unwrap();
+
+        let fired = FiredIntention::success(
+            intention.id,
+            TriggerReason::ScheduledTime {
+                scheduled_at: Utc::now(),
+            },
+        );
+        assert!(run_fired(&ActionExecutor::new(), &store, fired).await);
+        assert!(store.get_fire_history(intention.id, 10).unwrap().is_empty());
+    }
+}
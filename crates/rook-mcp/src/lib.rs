@@ -6,16 +6,51 @@
 //! # Tools
 //!
 //! - `memory_add` - Add a new memory to the store
+//! - `memory_smart_ingest` - Store content only if it isn't already known,
+//!   reporting the decision and why
 //! - `memory_search` - Search memories by semantic similarity
+//! - `memory_graph_search` - Find related entities and relationships in the knowledge graph
 //! - `memory_get` - Get a specific memory by ID
+//! - `memory_update` - Replace the content of a memory by ID, or change when it expires
+//! - `memory_merge` - Combine overlapping memories into one
+//! - `memory_tag` - Add or remove free-form tags on a memory
+//! - `memory_tags` - List the tags used in a scope
+//! - `memory_history` - Show how a memory changed over time, with its versions
+//! - `memory_list` - List memories for a user, a page at a time
+//! - `memory_due_for_review` - List memories whose recall has decayed
+//! - `memory_review` - Record how well a memory held up and reschedule it
 //! - `memory_delete` - Delete a memory by ID
 //!
+//! # Prompts
+//!
+//! - `recall_context` - Recall memories about a topic as a system preamble
+//! - `end_of_session_summary` - Propose memories worth storing from the session
+//!
+//! # Resources
+//!
+//! Memories can also be read as `rook://` resources (see [`resources`]).
+//!
+//! # Notifications
+//!
+//! Time-based intentions are scheduled from the intention store (see
+//! [`intentions`]). Fired intentions with a `notify_mcp` action reach
+//! connected clients as log messages (see [`notifications`]).
+//!
 //! # Configuration
 //!
 //! The server reads configuration from environment variables:
 //!
 //! - `ROOK_DATA_DIR` - Directory for data storage (default: ~/.rook)
-//! - `OPENAI_API_KEY` - API key for embeddings and LLM
+//! - `OPENAI_API_KEY` - API key for the default OpenAI embeddings and LLM
+//! - `ROOK_CONFIG` and `ROOK_*_PROVIDER` - Choose other providers, e.g. Ollama
+//!   to run fully local (see [`providers`])
+//! - `ROOK_MCP_PROFILES_FILE` - Named profiles with separate stores (see [`profiles`])
+//! - `ROOK_MCP_PROJECT_SCOPE` - Keep each project's memories apart via `agent_id`
+//!   or `run_id` (see [`projects`])
+//! - `ROOK_MCP_TRANSPORT`, `ROOK_MCP_BIND`, `ROOK_MCP_TOKEN` - Serve over
+//!   streamable HTTP instead of stdio (see [`http`])
+//! - `ROOK_INTENTION_DB_PATH`, `ROOK_DISABLE_INTENTIONS` - Where intentions are
+//!   stored, or turn their scheduler off (see [`intentions`])
 //!
 //! # Usage with Claude Code
 //!
@@ -31,7 +66,16 @@
 //! }
 //! ```
 
+pub mod http;
+pub mod intentions;
+pub mod notifications;
+pub mod profiles;
+pub mod projects;
+pub mod prompts;
+pub mod providers;
+pub mod resources;
 pub mod server;
 pub mod tools;
 
+pub use profiles::{ProfileConfig, Profiles, ProfilesConfig};
 pub use server::MemoryServer;
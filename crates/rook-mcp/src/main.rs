@@ -2,14 +2,39 @@
 //!
 //! This binary provides an MCP server that exposes Rook's memory capabilities
 //! as MCP tools. It communicates via stdio transport, which is the standard
-//! for local MCP servers.
+//! for local MCP servers, or with `--transport http` over streamable HTTP so
+//! several clients can share one memory:
+//!
+//! ```text
+//! rook-mcp --transport http --bind 0.0.0.0:8765
+//! ```
 //!
 //! # Configuration
 //!
 //! Set these environment variables before running:
 //!
-//! - `OPENAI_API_KEY` - Required for embeddings and LLM operations
+//! - `OPENAI_API_KEY` - Required for the default OpenAI LLM and embeddings
+//! - `ROOK_CONFIG` - Optional TOML/JSON/YAML `MemoryConfig` file choosing providers
+//! - `ROOK_LLM_PROVIDER`, `ROOK_EMBEDDER_PROVIDER`, `ROOK_VECTOR_STORE_PROVIDER`,
+//!   `ROOK_GRAPH_STORE_PROVIDER`, `ROOK_RERANKER_PROVIDER` and related settings -
+//!   Optional provider overrides (see `providers`), e.g. `ollama` to run fully local
 //! - `ROOK_DATA_DIR` - Optional, defaults to `~/.rook`
+//! - `ROOK_DATA_DIR_BUDGET` - Optional size budget for `ROOK_DATA_DIR` (e.g. `5GB`);
+//!   history retention and compaction run when it is exceeded
+//! - `ROOK_MCP_SUBJECT` - Optional subject passed to authorization hooks, defaults to `mcp`
+//! - `ROOK_MCP_PROFILES_FILE` - Optional TOML/JSON/YAML file defining named profiles
+//!   (e.g. `work` and `personal`), each with its own data directory
+//! - `ROOK_MCP_PROJECT_SCOPE` - `agent_id` (default), `run_id` or `off`; which scope the
+//!   `project` argument or the client's workspace root is stored under
+//! - `ROOK_MEMORY_EXPIRY_INTERVAL_MINUTES` - How often memories past their `expires_at`
+//!   metadata are deleted, defaults to 1
+//! - `ROOK_AUTHZ_POLICY_FILE` / `ROOK_AUTHZ_OPA_URL` - Optional authorization hook
+//! - `ROOK_MCP_TRANSPORT` - `stdio` (default) or `http`; overridden by `--transport`
+//! - `ROOK_MCP_BIND` - HTTP bind address, defaults to `127.0.0.1:8765`; overridden by `--bind`
+//! - `ROOK_MCP_TOKEN` - Bearer token HTTP clients must send; unset allows any client
+//! - `ROOK_INTENTION_DB_PATH` - Optional intention store, defaults to `intentions.db` in
+//!   `ROOK_DATA_DIR`; time-based intentions in it fire while the server runs
+//! - `ROOK_DISABLE_INTENTIONS` - Optional, set to not run intentions
 //!
 //! # Usage with Claude Code
 //!
@@ -25,6 +50,9 @@
 //! }
 //! ```
 
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -32,9 +60,18 @@ use rmcp::{transport::stdio, ServiceExt};
 use tokio::sync::RwLock;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
+mod http;
+mod intentions;
+mod notifications;
+mod profiles;
+mod projects;
+mod prompts;
+mod resources;
 mod server;
+mod providers;
 mod tools;
 
+use profiles::{ProfileConfig, Profiles, ProfilesConfig};
 use server::MemoryServer;
 
 #[tokio::main]
@@ -49,98 +86,206 @@ async fn main() -> Result<()> {
         .with(EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into()))
         .init();
 
+    let options = Options::parse()?;
     tracing::info!("Starting Rook MCP server");
 
-    // Initialize memory system
-    let memory = initialize_memory().await?;
+    // Initialize memory system, one instance per profile
+    let data_dir = data_dir();
+    let base = providers::config_from_env()?;
+    let server = match ProfilesConfig::from_env()? {
+        Some(config) => MemoryServer::with_profiles(
+            initialize_profiles(&config, &data_dir, base.as_ref()).await?,
+        ),
+        None => MemoryServer::new(Arc::new(RwLock::new(
+            initialize_memory(&data_dir, base.as_ref(), &ProfileConfig::default()).await?,
+        ))),
+    };
+
+    // Data directory budget
+    if let Some(config) = rook_core::DataDirConfig::from_env().filter(|c| c.budget_bytes.is_some())
+    {
+        Arc::new(rook_core::DataDirMonitor::new(config).with_default_jobs()).start();
+    }
+
+    // Authorization hook (policy file or OPA endpoint, if configured)
+    let authorizer = rook_core::AuthzConfig::from_env().build()?;
+    let subject =
+        std::env::var("ROOK_MCP_SUBJECT").unwrap_or_else(|_| server::DEFAULT_SUBJECT.to_string());
+
+    // Project scoping (agent_id by default)
+    let project_scope = match std::env::var(projects::PROJECT_SCOPE_ENV) {
+        Ok(scope) => scope.parse().map_err(anyhow::Error::msg)?,
+        Err(_) => projects::ProjectScope::default(),
+    };
 
     // Create MCP server
-    let server = MemoryServer::new(Arc::new(RwLock::new(memory)));
+    let server = server
+        .with_authorizer(authorizer, subject)
+        .with_project_scope(project_scope);
+
+    // Memories past their `expires_at` (every minute by default)
+    let expiry_minutes = std::env::var("ROOK_MEMORY_EXPIRY_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(1);
+    server.start_memory_expiry(std::time::Duration::from_secs(expiry_minutes.max(1) * 60));
 
-    // Serve via stdio transport
-    let service = server.serve(stdio()).await.inspect_err(|e| {
-        tracing::error!("Server error: {:?}", e);
-    })?;
+    // Decayed memories to the archive, when enabled (hourly by default)
+    let archive_minutes = std::env::var("ROOK_MEMORY_ARCHIVE_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(60);
+    server.start_memory_archive(std::time::Duration::from_secs(archive_minutes.max(1) * 60));
 
-    tracing::info!("MCP server running on stdio");
+    // Time-based intentions, with notify_mcp actions sent to connected clients
+    if std::env::var(intentions::DISABLE_INTENTIONS_ENV).is_err() {
+        let path = std::env::var(intentions::INTENTION_DB_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| data_dir.join("intentions.db"));
+        let store = Arc::new(rook_core::intentions::SqliteIntentionStore::new(&path)?);
+        let count = server.start_intentions(store).await?;
+        tracing::info!("Scheduled {} intentions from {}", count, path.display());
+    }
 
-    service.waiting().await?;
+    match options.transport {
+        Transport::Stdio => {
+            let service = server.serve(stdio()).await.map_err(|e| {
+                tracing::error!("Server error: {:?}", e);
+                e
+            })?;
+
+            tracing::info!("MCP server running on stdio");
+
+            service.waiting().await?;
+        }
+        Transport::Http => {
+            if options.token.is_none() && !options.bind.ip().is_loopback() {
+                tracing::warn!(
+                    "ROOK_MCP_TOKEN is not set; any client that can reach {} can use this memory",
+                    options.bind
+                );
+            }
+            http::serve(server, options.bind, options.token).await?;
+        }
+    }
     Ok(())
 }
 
-/// Initialize the Memory instance with providers.
-///
-/// Reads configuration from environment and sets up:
-/// - OpenAI LLM for fact extraction
-/// - OpenAI embeddings
-/// - SQLite vector store (local)
-async fn initialize_memory() -> Result<rook_core::Memory> {
-    use std::path::PathBuf;
-
-    // Determine data directory
-    let data_dir = std::env::var("ROOK_DATA_DIR")
+/// How clients connect to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    Stdio,
+    Http,
+}
+
+impl std::str::FromStr for Transport {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "stdio" => Ok(Self::Stdio),
+            "http" => Ok(Self::Http),
+            other => Err(anyhow::anyhow!(
+                "Unknown transport '{}' (expected stdio or http)",
+                other
+            )),
+        }
+    }
+}
+
+/// Command-line options, falling back to environment variables.
+struct Options {
+    transport: Transport,
+    bind: SocketAddr,
+    token: Option<String>,
+}
+
+impl Options {
+    fn parse() -> Result<Self> {
+        let mut transport = std::env::var("ROOK_MCP_TRANSPORT").ok();
+        let mut bind = std::env::var("ROOK_MCP_BIND").ok();
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let target = match name.as_str() {
+                "--transport" => &mut transport,
+                "--bind" => &mut bind,
+                other => return Err(anyhow::anyhow!("Unknown argument '{}'", other)),
+            };
+            let value = inline
+                .or_else(|| args.next())
+                .ok_or_else(|| anyhow::anyhow!("{} needs a value", name))?;
+            *target = Some(value);
+        }
+
+        let bind = bind.as_deref().unwrap_or(http::DEFAULT_BIND);
+        Ok(Self {
+            transport: transport.as_deref().unwrap_or("stdio").parse()?,
+            bind: bind
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid bind address '{}': {}", bind, e))?,
+            token: std::env::var("ROOK_MCP_TOKEN").ok().filter(|t| !t.is_empty()),
+        })
+    }
+}
+
+/// Base data directory: `ROOK_DATA_DIR`, or `~/.rook`.
+fn data_dir() -> PathBuf {
+    std::env::var("ROOK_DATA_DIR")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
             dirs::home_dir()
                 .unwrap_or_else(|| PathBuf::from("."))
                 .join(".rook")
-        });
+        })
+}
 
-    // Ensure data directory exists
-    std::fs::create_dir_all(&data_dir)?;
+/// Initialize a Memory instance for every configured profile.
+async fn initialize_profiles(
+    config: &ProfilesConfig,
+    base_dir: &Path,
+    base: Option<&rook_core::MemoryConfig>,
+) -> Result<Profiles> {
+    let mut memories = BTreeMap::new();
+    for (name, profile) in &config.profiles {
+        tracing::info!("Loading profile '{}'", name);
+        let data_dir = profile.resolve_data_dir(name, base_dir);
+        let memory = initialize_memory(&data_dir, base, profile).await?;
+        memories.insert(name.clone(), Arc::new(RwLock::new(memory)));
+    }
 
-    tracing::info!("Data directory: {}", data_dir.display());
+    let default = config
+        .default_profile()
+        .ok_or_else(|| anyhow::anyhow!("Profiles file defines no profiles"))?;
+    let mut profiles = Profiles::new(default, memories)?;
+    for (name, profile) in &config.profiles {
+        if let Some(ref description) = profile.description {
+            profiles = profiles.with_description(name, description.clone());
+        }
+    }
+    Ok(profiles)
+}
 
-    // Get OpenAI API key from environment
-    let api_key = std::env::var("OPENAI_API_KEY")
-        .map_err(|_| anyhow::anyhow!("OPENAI_API_KEY environment variable not set"))?;
+/// Initialize the Memory instance for a profile.
+///
+/// Providers come from `ROOK_CONFIG` and the `ROOK_*_PROVIDER` variables
+/// (see [`providers`]), defaulting to OpenAI and a sqlite-vec store under
+/// `data_dir`.
+async fn initialize_memory(
+    data_dir: &Path,
+    base: Option<&rook_core::MemoryConfig>,
+    profile: &ProfileConfig,
+) -> Result<rook_core::Memory> {
+    // Ensure data directory exists
+    std::fs::create_dir_all(data_dir)?;
 
-    // Initialize LLM using core config types
-    let llm_config = rook_core::LlmConfig {
-        model: "gpt-4o-mini".to_string(),
-        api_key: Some(api_key.clone()),
-        ..Default::default()
-    };
-    let llm: Arc<dyn rook_core::Llm> =
-        Arc::new(rook_llm::OpenAIProvider::new(llm_config)?);
-
-    // Initialize embedder using core config types
-    let embedder_config = rook_core::EmbedderConfig {
-        model: "text-embedding-3-small".to_string(),
-        embedding_dims: 1536,
-        api_key: Some(api_key),
-        base_url: None,
-    };
-    let embedder: Arc<dyn rook_core::Embedder> =
-        Arc::new(rook_embeddings::OpenAIEmbedder::new(embedder_config)?);
-
-    // Initialize SQLite vector store
-    let vector_db_path = data_dir.join("vectors.db");
-    let history_db_path = data_dir.join("history.db");
-
-    let vector_store: Arc<dyn rook_core::VectorStore> = Arc::new(
-        rook_vector_stores::SqliteVecStore::new(
-            &vector_db_path.to_string_lossy(),
-            "rook",
-            1536, // text-embedding-3-small dimension
-        )?,
-    );
-
-    // Create memory config
-    let memory_config = rook_core::MemoryConfig {
-        history_db_path,
-        ..Default::default()
-    };
+    tracing::info!("Data directory: {}", data_dir.display());
 
-    // Create Memory instance
-    let memory = rook_core::Memory::new(
-        memory_config,
-        llm,
-        embedder,
-        vector_store,
-        None, // graph_store
-        None, // reranker
-    )?;
-
-    Ok(memory)
+    let config =
+        providers::memory_config(base, data_dir, profile, |name| std::env::var(name).ok())?;
+    Ok(providers::create_memory(config).await?)
 }
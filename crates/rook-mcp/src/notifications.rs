@@ -0,0 +1,83 @@
+//! Intention notifications to connected MCP clients.
+//!
+//! Intentions with a `notify_mcp` action reach clients as `notifications/message`
+//! log messages from the `rook.intentions` logger, with the fired intention as
+//! data. Clients are registered once initialized, and
+//! [`MemoryServer::intention_executor`] builds an
+//! [`ActionExecutor`](rook_core::intentions::ActionExecutor) delivering to
+//! them, as used for the server's time-based intentions (see
+//! [`intentions`](crate::intentions)).
+//!
+//! [`MemoryServer::intention_executor`]: crate::server::MemoryServer::intention_executor
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::{Peer, RoleServer};
+use rook_core::error::{RookError, RookResult};
+use rook_core::intentions::{IntentionNotification, IntentionNotifier};
+
+/// Logger name of intention notifications.
+pub const INTENTIONS_LOGGER: &str = "rook.intentions";
+
+/// Clients that receive intention notifications.
+#[derive(Default)]
+pub struct ClientNotifier {
+    peers: Mutex<Vec<Peer<RoleServer>>>,
+}
+
+impl ClientNotifier {
+    /// Register a client once it finished initialization.
+    pub fn register(&self, peer: Peer<RoleServer>) {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.retain(|peer| !peer.is_transport_closed());
+        peers.push(peer);
+    }
+
+    /// Connected clients, dropping those that went away.
+    fn connected(&self) -> Vec<Peer<RoleServer>> {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.retain(|peer| !peer.is_transport_closed());
+        peers.clone()
+    }
+}
+
+#[async_trait]
+impl IntentionNotifier for ClientNotifier {
+    /// Notify every connected client. Succeeds if at least one received it.
+    async fn notify(&self, notification: &IntentionNotification) -> RookResult<()> {
+        let peers = self.connected();
+        if peers.is_empty() {
+            return Err(RookError::api("No MCP clients connected"));
+        }
+
+        let data = serde_json::to_value(notification)?;
+        let mut last_error = None;
+        let mut delivered = false;
+        for peer in peers {
+            match peer
+                .notify_logging_message(LoggingMessageNotificationParam {
+                    level: LoggingLevel::Notice,
+                    logger: Some(INTENTIONS_LOGGER.to_string()),
+                    data: data.clone(),
+                })
+                .await
+            {
+                Ok(()) => delivered = true,
+                Err(e) => {
+                    tracing::debug!(error = %e, "Failed to notify MCP client of intention");
+                    last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        match (delivered, last_error) {
+            (false, Some(e)) => Err(RookError::api(format!(
+                "Failed to notify MCP clients: {}",
+                e
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Named memory profiles.
+//!
+//! One server can serve several independent memory stores, e.g. `work` and
+//! `personal`. Each profile has its own [`Memory`] instance and data
+//! directory; tools pick one with their `profile` parameter and fall back to
+//! the default profile when it is omitted.
+//!
+//! Profiles are read from the file named by `ROOK_MCP_PROFILES_FILE`
+//! (TOML, JSON, or YAML):
+//!
+//! ```toml
+//! default = "work"
+//!
+//! [profiles.work]
+//! data_dir = "~/.rook/work"
+//!
+//! [profiles.personal]
+//! description = "Personal notes and preferences"
+//! llm_model = "gpt-4o"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::Memory;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+/// Name of the profile used when no profiles file is configured.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Settings for one profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// Data directory (default: `<ROOK_DATA_DIR>/profiles/<name>`).
+    /// A leading `~` is expanded to the home directory.
+    pub data_dir: Option<PathBuf>,
+    /// LLM model, overriding the configured provider's model
+    /// (default: `gpt-4o-mini`).
+    pub llm_model: Option<String>,
+    /// Embedding model, overriding the configured provider's model
+    /// (default: `text-embedding-3-small`).
+    pub embedding_model: Option<String>,
+    /// Embedding dimensions (default: the embedder's, 1536 for OpenAI).
+    pub embedding_dims: Option<usize>,
+    /// Shown to clients in the server instructions.
+    pub description: Option<String>,
+}
+
+impl ProfileConfig {
+    /// Data directory for the profile, relative to the base data directory.
+    pub fn resolve_data_dir(&self, name: &str, base: &Path) -> PathBuf {
+        match self.data_dir {
+            Some(ref dir) => match dir.strip_prefix("~") {
+                Ok(rest) => dirs::home_dir().unwrap_or_else(|| PathBuf::from(".")).join(rest),
+                Err(_) => dir.clone(),
+            },
+            None => base.join("profiles").join(name),
+        }
+    }
+}
+
+/// The profiles file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfilesConfig {
+    /// Profile used when a tool call names none (default: the first profile).
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Profiles by name.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+impl ProfilesConfig {
+    /// Load from `ROOK_MCP_PROFILES_FILE`, if set.
+    pub fn from_env() -> RookResult<Option<Self>> {
+        match std::env::var("ROOK_MCP_PROFILES_FILE") {
+            Ok(path) => Self::from_file(path).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Load from a file (TOML, JSON, or YAML).
+    pub fn from_file(path: impl AsRef<Path>) -> RookResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let config: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => {
+                toml::from_str(&content).map_err(|e| RookError::Configuration(e.to_string()))?
+            }
+            Some("json") => serde_json::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            Some("yaml" | "yml") => serde_yaml::from_str(&content)
+                .map_err(|e| RookError::Configuration(e.to_string()))?,
+            _ => {
+                return Err(RookError::Configuration(
+                    "Unsupported profiles file format. Use .toml, .json, or .yaml".to_string(),
+                ))
+            }
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that there is at least one profile, names are usable as tool
+    /// arguments and directory names, and the default exists.
+    pub fn validate(&self) -> RookResult<()> {
+        if self.profiles.is_empty() {
+            return Err(RookError::Configuration(
+                "Profiles file defines no profiles".to_string(),
+            ));
+        }
+        for name in self.profiles.keys() {
+            let valid = !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(RookError::Configuration(format!(
+                    "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                    name
+                )));
+            }
+        }
+        if let Some(ref default) = self.default {
+            if !self.profiles.contains_key(default) {
+                return Err(RookError::Configuration(format!(
+                    "Default profile '{}' is not defined",
+                    default
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of the default profile.
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default
+            .as_deref()
+            .or_else(|| self.profiles.keys().next().map(String::as_str))
+    }
+}
+
+/// Memory instances by profile name.
+pub struct Profiles {
+    default: String,
+    memories: BTreeMap<String, Arc<RwLock<Memory>>>,
+    descriptions: BTreeMap<String, String>,
+}
+
+impl Profiles {
+    /// A single memory served as the default profile.
+    pub fn single(memory: Arc<RwLock<Memory>>) -> Self {
+        Self {
+            default: DEFAULT_PROFILE.to_string(),
+            memories: BTreeMap::from([(DEFAULT_PROFILE.to_string(), memory)]),
+            descriptions: BTreeMap::new(),
+        }
+    }
+
+    /// Profiles with the given default. The default must be among `memories`.
+    pub fn new(
+        default: impl Into<String>,
+        memories: BTreeMap<String, Arc<RwLock<Memory>>>,
+    ) -> RookResult<Self> {
+        let default = default.into();
+        if !memories.contains_key(&default) {
+            return Err(RookError::Configuration(format!(
+                "Default profile '{}' is not defined",
+                default
+            )));
+        }
+        Ok(Self {
+            default,
+            memories,
+            descriptions: BTreeMap::new(),
+        })
+    }
+
+    /// Describe a profile to clients.
+    pub fn with_description(mut self, name: &str, description: impl Into<String>) -> Self {
+        self.descriptions.insert(name.to_string(), description.into());
+        self
+    }
+
+    /// Memory for a profile, or for the default profile when `name` is `None`.
+    pub fn get(&self, name: Option<&str>) -> RookResult<&Arc<RwLock<Memory>>> {
+        let name = name.unwrap_or(&self.default);
+        self.memories.get(name).ok_or_else(|| {
+            RookError::validation(format!(
+                "Unknown profile '{}'; available profiles: {}",
+                name,
+                self.names().join(", ")
+            ))
+        })
+    }
+
+    /// Name of the default profile.
+    pub fn default_name(&self) -> &str {
+        &self.default
+    }
+
+    /// Profile names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        self.memories.keys().map(String::as_str).collect()
+    }
+
+    /// Whether more than one profile is served.
+    pub fn is_multi(&self) -> bool {
+        self.memories.len() > 1
+    }
+
+    /// Profile description, if configured.
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.descriptions.get(name).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_config_parsing() {
+        let config: ProfilesConfig = toml::from_str(
+            r#"
+            default = "work"
+
+            [profiles.work]
+            data_dir = "/tmp/rook-work"
+
+            [profiles.personal]
+            llm_model = "gpt-4o"
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.default_profile(), Some("work"));
+
+        let base = Path::new("/data");
+        assert_eq!(
+            config.profiles["work"].resolve_data_dir("work", base),
+            PathBuf::from("/tmp/rook-work")
+        );
+        assert_eq!(
+            config.profiles["personal"].resolve_data_dir("personal", base),
+            PathBuf::from("/data/profiles/personal")
+        );
+
+        let no_default = ProfilesConfig {
+            default: None,
+            ..config.clone()
+        };
+        assert_eq!(no_default.default_profile(), Some("personal"));
+    }
+
+    #[test]
+    fn test_profiles_config_validation() {
+        let mut config = ProfilesConfig::default();
+        assert!(config.validate().is_err());
+
+        config.profiles.insert("work".to_string(), ProfileConfig::default());
+        config.validate().unwrap();
+
+        config.default = Some("home".to_string());
+        assert!(config.validate().is_err());
+
+        config.default = None;
+        config.profiles.insert("my profile".to_string(), ProfileConfig::default());
+        assert!(config.validate().is_err());
+    }
+}
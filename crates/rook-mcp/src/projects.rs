@@ -0,0 +1,117 @@
+//! Per-project scoping.
+//!
+//! Coding assistants often run one MCP server per checked-out repository
+//! against a shared store. To keep memories from different repositories
+//! apart, tool calls are scoped to a project, taken from the `project`
+//! argument or, when it is omitted, from the first workspace root the client
+//! reports (the root directory's name). The project is stored as the
+//! memory's `agent_id` or `run_id`, depending on `ROOK_MCP_PROJECT_SCOPE`:
+//!
+//! - `agent_id` (default) - Scope memories to the project via `agent_id`
+//! - `run_id` - Scope memories to the project via `run_id`
+//! - `off` - Don't scope memories to projects
+//!
+//! Passing an empty `project` skips project scoping for that call, e.g. to
+//! search across all projects.
+
+use std::str::FromStr;
+
+/// Environment variable choosing how projects are scoped.
+pub const PROJECT_SCOPE_ENV: &str = "ROOK_MCP_PROJECT_SCOPE";
+
+/// Which memory scope a project maps to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProjectScope {
+    #[default]
+    Agent,
+    Run,
+    Off,
+}
+
+impl FromStr for ProjectScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "agent" | "agent_id" => Ok(Self::Agent),
+            "run" | "run_id" => Ok(Self::Run),
+            "off" | "none" => Ok(Self::Off),
+            other => Err(format!(
+                "Unknown project scope '{}' (expected agent_id, run_id or off)",
+                other
+            )),
+        }
+    }
+}
+
+/// `agent_id` and `run_id` a call is scoped to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectIds {
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+impl ProjectScope {
+    /// Map a project to the `agent_id`/`run_id` it is stored under.
+    pub fn ids(self, project: Option<&str>) -> ProjectIds {
+        let project = project.filter(|p| !p.is_empty()).map(str::to_string);
+        match self {
+            Self::Agent => ProjectIds {
+                agent_id: project,
+                run_id: None,
+            },
+            Self::Run => ProjectIds {
+                agent_id: None,
+                run_id: project,
+            },
+            Self::Off => ProjectIds::default(),
+        }
+    }
+}
+
+/// Project name of a workspace root URI: the name of its directory.
+pub fn project_from_root(uri: &str) -> Option<String> {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    path.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_scope_ids() {
+        assert_eq!(
+            ProjectScope::Agent.ids(Some("rook")).agent_id.as_deref(),
+            Some("rook")
+        );
+        assert_eq!(
+            ProjectScope::Run.ids(Some("rook")),
+            ProjectIds {
+                agent_id: None,
+                run_id: Some("rook".to_string())
+            }
+        );
+        assert_eq!(ProjectScope::Off.ids(Some("rook")), ProjectIds::default());
+        assert_eq!(ProjectScope::Agent.ids(Some("")), ProjectIds::default());
+        assert_eq!("run_id".parse::<ProjectScope>().unwrap(), ProjectScope::Run);
+        assert!("repo".parse::<ProjectScope>().is_err());
+    }
+
+    #[test]
+    fn test_project_from_root() {
+        assert_eq!(
+            project_from_root("file:///home/alice/src/rook/").as_deref(),
+            Some("rook")
+        );
+        assert_eq!(
+            project_from_root("file:///C:/work/api").as_deref(),
+            Some("api")
+        );
+        assert_eq!(project_from_root("file:///"), None);
+    }
+}
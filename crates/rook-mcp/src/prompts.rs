@@ -0,0 +1,179 @@
+//! MCP prompts for memory-augmented workflows.
+//!
+//! Prompts make the memory workflow discoverable to clients that list them,
+//! e.g. as slash commands:
+//!
+//! - `recall_context` - Search memories for a topic and format them as a
+//!   system preamble for the conversation
+//! - `end_of_session_summary` - Ask the assistant to propose memories worth
+//!   storing from the session
+//!
+//! Prompt arguments are passed as strings by MCP clients.
+
+use rmcp::schemars::{self, JsonSchema};
+use serde::{Deserialize, Serialize};
+
+/// Memories included by `recall_context` when no limit is given.
+pub const DEFAULT_RECALL_LIMIT: usize = 10;
+
+/// Recent memories shown to `end_of_session_summary` to avoid duplicates.
+pub const SUMMARY_KNOWN_LIMIT: usize = 20;
+
+/// Arguments for the recall_context prompt.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct RecallContextArgs {
+    /// Topic of the conversation, used as the search query.
+    pub topic: String,
+
+    /// User whose memories to recall.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Maximum memories to include (default: 10).
+    #[serde(default)]
+    pub limit: Option<String>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+impl RecallContextArgs {
+    /// Parsed `limit`, or the default.
+    pub fn limit(&self) -> Result<usize, String> {
+        match self.limit.as_deref().map(str::trim) {
+            None | Some("") => Ok(DEFAULT_RECALL_LIMIT),
+            Some(limit) => limit
+                .parse()
+                .map_err(|_| format!("Invalid limit '{}'", limit)),
+        }
+    }
+}
+
+/// Arguments for the end_of_session_summary prompt.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SessionSummaryArgs {
+    /// User the proposed memories belong to.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Format recalled memories as a system preamble.
+pub fn recall_preamble(topic: &str, memories: &[String]) -> String {
+    if memories.is_empty() {
+        return format!(
+            "No stored memories relate to \"{}\". Proceed without prior context, \
+             and use memory_add to store anything worth remembering.",
+            topic
+        );
+    }
+
+    let mut preamble = format!(
+        "The following memories are relevant to \"{}\". Treat them as background \
+         context about the user; prefer newer information when they conflict with \
+         the conversation.\n\nRelevant memories:",
+        topic
+    );
+    for memory in memories {
+        preamble.push_str(&format!("\n- {}", memory));
+    }
+    preamble
+}
+
+/// Ask the assistant to propose memories to store from the session.
+pub fn session_summary_request(
+    user_id: Option<&str>,
+    project: Option<&str>,
+    known: &[String],
+) -> String {
+    let mut request = String::from(
+        "The session is ending. Review the conversation and propose memories worth \
+         keeping for future sessions: stable preferences, facts about the user and \
+         their projects, decisions made, and open follow-ups. Skip small talk and \
+         anything only relevant to this session.\n\n\
+         List each proposed memory as one short, self-contained sentence. After I \
+         confirm, store each one with memory_smart_ingest",
+    );
+    let scope: Vec<String> = [("user_id", user_id), ("project", project)]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| format!("{} \"{}\"", name, value)))
+        .collect();
+    if !scope.is_empty() {
+        request.push_str(&format!(" using {}", scope.join(" and ")));
+    }
+    request.push('.');
+
+    if !known.is_empty() {
+        request.push_str(
+            "\n\nThese memories are already stored; only propose them again if the \
+             session changed them:",
+        );
+        for memory in known {
+            request.push_str(&format!("\n- {}", memory));
+        }
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recall_limit() {
+        let args = |limit: Option<&str>| RecallContextArgs {
+            topic: "rust".to_string(),
+            user_id: None,
+            limit: limit.map(str::to_string),
+            project: None,
+            profile: None,
+        };
+        assert_eq!(args(None).limit().unwrap(), DEFAULT_RECALL_LIMIT);
+        assert_eq!(args(Some(" 5 ")).limit().unwrap(), 5);
+        assert!(args(Some("many")).limit().is_err());
+    }
+
+    #[test]
+    fn test_recall_preamble() {
+        let preamble = recall_preamble(
+            "editor setup",
+            &["Uses Helix".to_string(), "Prefers dark themes".to_string()],
+        );
+        assert!(preamble.contains("relevant to \"editor setup\""));
+        assert!(preamble.ends_with("Relevant memories:\n- Uses Helix\n- Prefers dark themes"));
+
+        assert!(recall_preamble("taxes", &[]).starts_with("No stored memories"));
+    }
+
+    #[test]
+    fn test_session_summary_request() {
+        let request = session_summary_request(Some("alice"), None, &["Uses Helix".to_string()]);
+        assert!(request.contains("memory_smart_ingest using user_id \"alice\"."));
+        assert!(request.ends_with("\n- Uses Helix"));
+
+        let request = session_summary_request(None, Some("rook"), &[]);
+        assert!(request.ends_with("memory_smart_ingest using project \"rook\"."));
+
+        let request = session_summary_request(Some("alice"), Some("rook"), &[]);
+        assert!(request.ends_with("using user_id \"alice\" and project \"rook\"."));
+
+        let request = session_summary_request(None, None, &[]);
+        assert!(request.ends_with("memory_smart_ingest."));
+    }
+}
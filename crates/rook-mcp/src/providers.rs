@@ -0,0 +1,352 @@
+//! Provider selection for the MCP server.
+//!
+//! By default each profile uses OpenAI for the LLM and embeddings and a
+//! sqlite-vec store in its data directory. A full [`MemoryConfig`] file named
+//! by `ROOK_CONFIG` (TOML, JSON, or YAML) replaces those defaults, and these
+//! environment variables override single settings on top of either:
+//!
+//! | Variable | Setting |
+//! |----------|---------|
+//! | `ROOK_LLM_PROVIDER` | `openai`, `anthropic` or `ollama` |
+//! | `ROOK_LLM_MODEL` | LLM model |
+//! | `ROOK_LLM_BASE_URL` | LLM API base URL, e.g. a remote Ollama |
+//! | `ROOK_EMBEDDER_PROVIDER` | `openai` or `ollama` |
+//! | `ROOK_EMBEDDER_MODEL` | Embedding model |
+//! | `ROOK_EMBEDDING_DIMS` | Embedding dimensions |
+//! | `ROOK_EMBEDDER_BASE_URL` | Embedding API base URL |
+//! | `ROOK_VECTOR_STORE_PROVIDER` | e.g. `sqlite_vec`, `qdrant`, `pgvector` |
+//! | `ROOK_VECTOR_STORE_URL` | Vector store connection URL |
+//! | `ROOK_GRAPH_STORE_PROVIDER` | e.g. `embedded`, `neo4j` |
+//! | `ROOK_GRAPH_STORE_URL` | Graph store URL or embedded database path |
+//! | `ROOK_RERANKER_PROVIDER` | e.g. `cohere` |
+//!
+//! Profile settings (`llm_model`, `embedding_model`, `embedding_dims`) win
+//! over both. The history database, and sqlite-vec or embedded graph stores
+//! without a path, are always kept in the profile's data directory so
+//! profiles never share files.
+//!
+//! With `ROOK_LLM_PROVIDER=ollama` and `ROOK_EMBEDDER_PROVIDER=ollama` the
+//! server runs fully local.
+
+use std::path::Path;
+
+use rook_core::config::{LlmProvider, MemoryConfig};
+use rook_core::encryption::ContentEncryptionConfig;
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::{
+    EmbedderProvider, GraphStoreConfig, GraphStoreProvider, RerankerConfig, VectorStoreProvider,
+};
+use rook_core::Memory;
+use serde::de::DeserializeOwned;
+
+use crate::profiles::ProfileConfig;
+
+/// Environment variable naming a `MemoryConfig` file.
+pub const CONFIG_ENV: &str = "ROOK_CONFIG";
+
+/// Load the `MemoryConfig` file named by `ROOK_CONFIG`, if set.
+pub fn config_from_env() -> RookResult<Option<MemoryConfig>> {
+    match std::env::var(CONFIG_ENV) {
+        Ok(path) if !path.is_empty() => {
+            tracing::info!("Loading configuration from {}", path);
+            MemoryConfig::from_file(path).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Settings used without a configuration file.
+pub fn default_config() -> MemoryConfig {
+    let mut config = MemoryConfig::default();
+    config.llm.config.model = "gpt-4o-mini".to_string();
+    config.embedder.config.model = "text-embedding-3-small".to_string();
+    config.embedder.config.embedding_dims = 1536;
+    config.vector_store.provider = VectorStoreProvider::SqliteVec;
+    config.vector_store.collection_name = "rook".to_string();
+    config
+}
+
+/// Resolve the configuration for one profile.
+///
+/// `base` is the `ROOK_CONFIG` file, if any; `env` looks up environment
+/// variables.
+pub fn memory_config(
+    base: Option<&MemoryConfig>,
+    data_dir: &Path,
+    profile: &ProfileConfig,
+    env: impl Fn(&str) -> Option<String>,
+) -> RookResult<MemoryConfig> {
+    let mut config = base.cloned().unwrap_or_else(default_config);
+
+    // Environment overrides
+    if let Some(provider) = env("ROOK_LLM_PROVIDER") {
+        let provider: LlmProvider = parse_provider("ROOK_LLM_PROVIDER", &provider)?;
+        if provider != config.llm.provider && provider == LlmProvider::Ollama {
+            config.llm.config.model = "llama3.2".to_string();
+        }
+        config.llm.provider = provider;
+    }
+    if let Some(model) = env("ROOK_LLM_MODEL") {
+        config.llm.config.model = model;
+    }
+    if let Some(url) = env("ROOK_LLM_BASE_URL") {
+        config.llm.config.base_url = Some(url);
+    }
+
+    if let Some(provider) = env("ROOK_EMBEDDER_PROVIDER") {
+        let provider: EmbedderProvider = parse_provider("ROOK_EMBEDDER_PROVIDER", &provider)?;
+        if provider != config.embedder.provider && provider == EmbedderProvider::Ollama {
+            config.embedder.config.model = "nomic-embed-text".to_string();
+            config.embedder.config.embedding_dims = 768;
+        }
+        config.embedder.provider = provider;
+    }
+    if let Some(model) = env("ROOK_EMBEDDER_MODEL") {
+        config.embedder.config.model = model;
+    }
+    if let Some(dims) = env("ROOK_EMBEDDING_DIMS") {
+        config.embedder.config.embedding_dims = dims.parse().map_err(|_| {
+            RookError::Configuration(format!("Invalid ROOK_EMBEDDING_DIMS '{}'", dims))
+        })?;
+    }
+    if let Some(url) = env("ROOK_EMBEDDER_BASE_URL") {
+        config.embedder.config.base_url = Some(url);
+    }
+
+    if let Some(provider) = env("ROOK_VECTOR_STORE_PROVIDER") {
+        config.vector_store.provider = parse_provider("ROOK_VECTOR_STORE_PROVIDER", &provider)?;
+    }
+    if let Some(url) = env("ROOK_VECTOR_STORE_URL") {
+        set_config_value(&mut config.vector_store.config, "url", url);
+    }
+
+    if let Some(provider) = env("ROOK_GRAPH_STORE_PROVIDER") {
+        let provider: GraphStoreProvider =
+            parse_provider("ROOK_GRAPH_STORE_PROVIDER", &provider)?;
+        let graph = config.graph_store.get_or_insert_with(|| GraphStoreConfig {
+            url: String::new(),
+            ..Default::default()
+        });
+        graph.provider = provider;
+    }
+    if let Some(url) = env("ROOK_GRAPH_STORE_URL") {
+        config.graph_store.get_or_insert_with(GraphStoreConfig::default).url = url;
+    }
+
+    if let Some(provider) = env("ROOK_RERANKER_PROVIDER") {
+        config
+            .reranker
+            .get_or_insert_with(RerankerConfig::default)
+            .provider = parse_provider("ROOK_RERANKER_PROVIDER", &provider)?;
+    }
+
+    if let Some(path) = env("ROOK_CONTENT_KEY_FILE") {
+        let id = env("ROOK_CONTENT_KEY_ID").unwrap_or_else(|| "default".to_string());
+        config.content_encryption = Some(ContentEncryptionConfig::with_key_file(id, path));
+    }
+
+    if let Some(enabled) = env("ROOK_COGNITIVE") {
+        config.cognitive.enabled = enabled.parse().map_err(|_| {
+            RookError::Configuration(format!("Invalid ROOK_COGNITIVE '{}'", enabled))
+        })?;
+    }
+    if let Some(enabled) = env("ROOK_ARCHIVE") {
+        config.archive.enabled = enabled.parse().map_err(|_| {
+            RookError::Configuration(format!("Invalid ROOK_ARCHIVE '{}'", enabled))
+        })?;
+    }
+
+    // Profile overrides
+    if let Some(ref model) = profile.llm_model {
+        config.llm.config.model = model.clone();
+    }
+    if let Some(ref model) = profile.embedding_model {
+        config.embedder.config.model = model.clone();
+    }
+    if let Some(dims) = profile.embedding_dims {
+        config.embedder.config.embedding_dims = dims;
+    }
+
+    // The OpenAI key applies to whichever OpenAI providers are used
+    if let Some(api_key) = env("OPENAI_API_KEY") {
+        if config.llm.provider == LlmProvider::OpenAI && config.llm.config.api_key.is_none() {
+            config.llm.config.api_key = Some(api_key.clone());
+        }
+        if config.embedder.provider == EmbedderProvider::OpenAI
+            && config.embedder.config.api_key.is_none()
+        {
+            config.embedder.config.api_key = Some(api_key);
+        }
+    }
+
+    // Files that belong to the profile
+    config.history_db_path = data_dir.join("history.db");
+    if config.vector_store.provider == VectorStoreProvider::SqliteVec
+        && config.vector_store.config.get("path").is_none()
+    {
+        let path = data_dir.join("vectors.db").to_string_lossy().into_owned();
+        set_config_value(&mut config.vector_store.config, "path", path);
+    }
+    if let Some(ref mut graph) = config.graph_store {
+        if graph.provider == GraphStoreProvider::Embedded && graph.url.is_empty() {
+            graph.url = data_dir.join("graph.db").to_string_lossy().into_owned();
+        }
+    }
+
+    // The store's vectors must match the embedder
+    config.vector_store.embedding_model_dims = config.embedder.config.embedding_dims;
+
+    Ok(config)
+}
+
+/// Create a Memory instance with the configured providers.
+pub async fn create_memory(config: MemoryConfig) -> RookResult<Memory> {
+    tracing::info!(
+        llm = ?config.llm.provider,
+        embedder = ?config.embedder.provider,
+        vector_store = ?config.vector_store.provider,
+        "Creating memory providers"
+    );
+
+    let llm = rook_llm::LlmFactory::create(config.llm.provider, config.llm.config.clone())?;
+    let embedder = rook_embeddings::EmbedderFactory::create(
+        config.embedder.provider,
+        config.embedder.config.clone(),
+    )?;
+    let vector_store = rook_vector_stores::VectorStoreFactory::create(
+        config.vector_store.provider,
+        config.vector_store.clone(),
+    )
+    .await?;
+    let graph_store = match config.graph_store {
+        Some(ref graph) => Some(
+            rook_graph_stores::GraphStoreFactory::create(graph.provider, graph.clone()).await?,
+        ),
+        None => None,
+    };
+    let reranker = match config.reranker {
+        Some(ref reranker) => Some(
+            rook_rerankers::RerankerFactory::create(reranker.provider, reranker.clone()).await?,
+        ),
+        None => None,
+    };
+    let archive_store = match config.archive_vector_store() {
+        Some(archive) => {
+            Some(rook_vector_stores::VectorStoreFactory::create(archive.provider, archive).await?)
+        }
+        None => None,
+    };
+
+    let memory = Memory::new(config, llm, embedder, vector_store, graph_store, reranker)?;
+    match archive_store {
+        Some(store) => memory.with_archive_store(store),
+        None => Ok(memory),
+    }
+}
+
+fn parse_provider<T: DeserializeOwned>(variable: &str, value: &str) -> RookResult<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase())).map_err(|_| {
+        RookError::Configuration(format!("Unknown provider '{}' in {}", value, variable))
+    })
+}
+
+fn set_config_value(config: &mut serde_json::Value, key: &str, value: String) {
+    if !config.is_object() {
+        *config = serde_json::json!({});
+    }
+    if let Some(map) = config.as_object_mut() {
+        map.insert(key.to_string(), serde_json::Value::String(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_defaults_keep_files_in_data_dir() {
+        let dir = Path::new("/data/work");
+        let config = memory_config(None, dir, &ProfileConfig::default(), env(&[])).unwrap();
+
+        assert_eq!(config.llm.provider, LlmProvider::OpenAI);
+        assert_eq!(config.vector_store.provider, VectorStoreProvider::SqliteVec);
+        assert_eq!(
+            config.vector_store.config["path"],
+            serde_json::json!("/data/work/vectors.db")
+        );
+        assert_eq!(config.history_db_path, dir.join("history.db"));
+        assert_eq!(config.vector_store.embedding_model_dims, 1536);
+    }
+
+    #[test]
+    fn test_env_selects_local_providers() {
+        let config = memory_config(
+            None,
+            Path::new("/data"),
+            &ProfileConfig::default(),
+            env(&[
+                ("ROOK_LLM_PROVIDER", "ollama"),
+                ("ROOK_EMBEDDER_PROVIDER", "Ollama"),
+                ("ROOK_GRAPH_STORE_PROVIDER", "embedded"),
+                ("ROOK_CONTENT_KEY_FILE", "/keys/content.key"),
+                ("ROOK_COGNITIVE", "true"),
+                ("ROOK_ARCHIVE", "true"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.llm.provider, LlmProvider::Ollama);
+        assert_eq!(config.llm.config.model, "llama3.2");
+        assert_eq!(config.embedder.provider, EmbedderProvider::Ollama);
+        assert_eq!(config.embedder.config.model, "nomic-embed-text");
+        assert_eq!(config.vector_store.embedding_model_dims, 768);
+        assert!(config.cognitive.enabled);
+        assert_eq!(
+            config.archive_vector_store().unwrap().collection_name,
+            format!("{}_archive", config.vector_store.collection_name)
+        );
+        assert_eq!(config.graph_store.unwrap().url, "/data/graph.db");
+        let key = &config.content_encryption.unwrap().keys[0];
+        assert_eq!(key.id, "default");
+        assert_eq!(key.key_file.as_deref(), Some(Path::new("/keys/content.key")));
+    }
+
+    #[test]
+    fn test_file_config_with_profile_overrides() {
+        let mut base = MemoryConfig::default();
+        base.vector_store.provider = VectorStoreProvider::Qdrant;
+        base.vector_store.config = serde_json::json!({"url": "http://qdrant:6334"});
+        let profile = ProfileConfig {
+            embedding_model: Some("text-embedding-3-large".to_string()),
+            embedding_dims: Some(3072),
+            ..Default::default()
+        };
+
+        let config = memory_config(Some(&base), Path::new("/data"), &profile, env(&[])).unwrap();
+        assert_eq!(config.vector_store.provider, VectorStoreProvider::Qdrant);
+        assert!(config.vector_store.config.get("path").is_none());
+        assert_eq!(config.embedder.config.model, "text-embedding-3-large");
+        assert_eq!(config.vector_store.embedding_model_dims, 3072);
+    }
+
+    #[test]
+    fn test_unknown_provider_is_rejected() {
+        let result = memory_config(
+            None,
+            Path::new("/data"),
+            &ProfileConfig::default(),
+            env(&[("ROOK_LLM_PROVIDER", "nonsense")]),
+        );
+        assert!(result.is_err());
+    }
+}
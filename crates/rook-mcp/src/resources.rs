@@ -0,0 +1,239 @@
+//! MCP resources for attaching memories as context.
+//!
+//! Clients can read memories as resources instead of calling tools:
+//!
+//! - `rook://categories` - Category names memories are classified into
+//! - `rook://memories/{user_id}/recent` - A user's most recent memories
+//! - `rook://categories/{name}/{user_id}` - A user's memories in a category
+//!
+//! Append `?profile=<name>` to read from a profile other than the default,
+//! and `?limit=<n>` to change how many memories are returned. Clients that
+//! subscribe to a memory resource are notified when a tool call changes the
+//! user's memories.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate,
+    ResourceUpdatedNotificationParam,
+};
+use rmcp::{Peer, RoleServer};
+
+/// URI scheme of Rook resources.
+pub const SCHEME: &str = "rook://";
+
+/// Memories returned by a memory resource when no limit is given.
+pub const DEFAULT_RESOURCE_LIMIT: usize = 20;
+
+/// What a resource URI refers to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceKind {
+    /// Category names.
+    Categories,
+    /// A user's most recent memories.
+    Recent { user_id: String },
+    /// A user's memories in one category.
+    Category { name: String, user_id: String },
+}
+
+/// A parsed `rook://` resource URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceUri {
+    pub kind: ResourceKind,
+    /// Profile to read from; the default profile if `None`.
+    pub profile: Option<String>,
+    /// Maximum memories to return.
+    pub limit: usize,
+}
+
+impl ResourceUri {
+    /// Parse a resource URI.
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let unknown = || format!("Unknown resource '{}'", uri);
+        let rest = uri.strip_prefix(SCHEME).ok_or_else(unknown)?;
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+
+        let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+        let kind = match segments.as_slice() {
+            ["categories"] => ResourceKind::Categories,
+            ["memories", user_id, "recent"] if !user_id.is_empty() => ResourceKind::Recent {
+                user_id: user_id.to_string(),
+            },
+            ["categories", name, user_id] if !name.is_empty() && !user_id.is_empty() => {
+                ResourceKind::Category {
+                    name: name.to_string(),
+                    user_id: user_id.to_string(),
+                }
+            }
+            _ => return Err(unknown()),
+        };
+
+        let mut profile = None;
+        let mut limit = DEFAULT_RESOURCE_LIMIT;
+        for pair in query.into_iter().flat_map(|q| q.split('&')) {
+            match pair.split_once('=') {
+                Some(("profile", value)) if !value.is_empty() => {
+                    profile = Some(value.to_string())
+                }
+                Some(("limit", value)) => {
+                    limit = value
+                        .parse()
+                        .map_err(|_| format!("Invalid limit '{}' in '{}'", value, uri))?
+                }
+                _ => return Err(format!("Unknown parameter '{}' in '{}'", pair, uri)),
+            }
+        }
+
+        Ok(Self {
+            kind,
+            profile,
+            limit,
+        })
+    }
+
+    /// User whose memories the resource shows.
+    pub fn user_id(&self) -> Option<&str> {
+        match self.kind {
+            ResourceKind::Categories => None,
+            ResourceKind::Recent { ref user_id } | ResourceKind::Category { ref user_id, .. } => {
+                Some(user_id)
+            }
+        }
+    }
+}
+
+/// Resources with fixed URIs.
+pub fn static_resources() -> Vec<Resource> {
+    let mut categories = RawResource::new("rook://categories", "categories");
+    categories.description = Some("Category names memories are classified into".to_string());
+    categories.mime_type = Some("application/json".to_string());
+    vec![categories.no_annotation()]
+}
+
+/// Templates for the memory resources.
+pub fn resource_templates() -> Vec<ResourceTemplate> {
+    let template = |uri_template: &str, name: &str, description: &str| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some("application/json".to_string()),
+            icons: None,
+        }
+        .no_annotation()
+    };
+    vec![
+        template(
+            "rook://memories/{user_id}/recent",
+            "recent_memories",
+            "A user's most recent memories, newest first",
+        ),
+        template(
+            "rook://categories/{name}/{user_id}",
+            "category_memories",
+            "A user's memories in one category, e.g. preferences or projects",
+        ),
+    ]
+}
+
+/// Clients subscribed to resource updates, by URI.
+#[derive(Default)]
+pub struct Subscriptions {
+    peers: Mutex<HashMap<String, Vec<Peer<RoleServer>>>>,
+}
+
+impl Subscriptions {
+    pub fn subscribe(&self, uri: String, peer: Peer<RoleServer>) {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.entry(uri).or_default().push(peer);
+    }
+
+    pub fn unsubscribe(&self, uri: &str) {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.remove(uri);
+    }
+
+    /// Notify subscribers of memory resources in a profile that a user's
+    /// memories changed. A `None` user notifies every memory resource.
+    pub async fn notify_changed(&self, profile: &str, user_id: Option<&str>, default: &str) {
+        let targets: Vec<(String, Peer<RoleServer>)> = {
+            let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+            peers.retain(|_, subscribed| {
+                subscribed.retain(|peer| !peer.is_transport_closed());
+                !subscribed.is_empty()
+            });
+            peers
+                .iter()
+                .filter(|(uri, _)| {
+                    ResourceUri::parse(uri).is_ok_and(|resource| {
+                        resource.profile.as_deref().unwrap_or(default) == profile
+                            && resource.user_id().is_some()
+                            && (user_id.is_none() || resource.user_id() == user_id)
+                    })
+                })
+                .flat_map(|(uri, subscribed)| {
+                    subscribed.iter().map(|peer| (uri.clone(), peer.clone()))
+                })
+                .collect()
+        };
+
+        for (uri, peer) in targets {
+            if let Err(e) = peer
+                .notify_resource_updated(ResourceUpdatedNotificationParam { uri: uri.clone() })
+                .await
+            {
+                tracing::debug!(uri = %uri, error = %e, "Failed to notify resource subscriber");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_resources() {
+        let recent = ResourceUri::parse("rook://memories/alice/recent").unwrap();
+        assert_eq!(
+            recent.kind,
+            ResourceKind::Recent {
+                user_id: "alice".to_string()
+            }
+        );
+        assert_eq!(recent.limit, DEFAULT_RESOURCE_LIMIT);
+        assert!(recent.profile.is_none());
+
+        let category =
+            ResourceUri::parse("rook://categories/preferences/alice?profile=work&limit=5")
+                .unwrap();
+        assert_eq!(
+            category.kind,
+            ResourceKind::Category {
+                name: "preferences".to_string(),
+                user_id: "alice".to_string()
+            }
+        );
+        assert_eq!(category.profile.as_deref(), Some("work"));
+        assert_eq!(category.limit, 5);
+        assert_eq!(category.user_id(), Some("alice"));
+
+        let categories = ResourceUri::parse("rook://categories").unwrap();
+        assert_eq!(categories.kind, ResourceKind::Categories);
+        assert!(categories.user_id().is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_resources() {
+        assert!(ResourceUri::parse("file:///etc/passwd").is_err());
+        assert!(ResourceUri::parse("rook://memories/alice").is_err());
+        assert!(ResourceUri::parse("rook://memories//recent").is_err());
+        assert!(ResourceUri::parse("rook://categories?limit=many").is_err());
+        assert!(ResourceUri::parse("rook://categories?sort=asc").is_err());
+    }
+}
@@ -1,37 +1,275 @@
 //! MCP server implementation for Rook memory system.
 //!
-//! Uses the rmcp SDK's macro-based approach for defining tools.
+//! Uses the rmcp SDK's macro-based approach for defining tools and prompts.
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use rmcp::{
-    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    handler::server::{
+        router::{prompt::PromptRouter, tool::ToolRouter},
+        wrapper::Parameters,
+    },
     model::*,
-    schemars, tool, tool_handler, tool_router,
-    ErrorData as McpError, RoleServer, ServerHandler,
+    prompt, prompt_handler, prompt_router,
+    service::{NotificationContext, RequestContext},
+    tool, tool_handler, tool_router,
+    ErrorData as McpError, Peer, RoleServer, ServerHandler,
 };
 
+use rook_core::authz::{AllowAll, Authorizer, AuthzAction, AuthzRequest};
+use rook_core::ingestion::{DetectionLayer, IngestDecision};
+use rook_core::intentions::{ActionExecutor, IntentionNotifier, IntentionStore};
+use rook_core::memory::{
+    expiry_after, expiry_from_ttl, MergeStrategy, SessionScope, EXPIRES_AT_FIELD, EXPLAIN_FILTER,
+    INCLUDE_ARCHIVED_FILTER, MMR_LAMBDA_FILTER, QUERY_EXPANSION_FILTER, RECENCY_HALF_LIFE_FILTER,
+    RETRIEVAL_MODE_FILTER, SINCE_FILTER, UNTIL_FILTER,
+};
+use rook_core::types::{Cursor, FieldSelection, GraphRelation, PageRequest, TAGS_FIELD};
 use tokio::sync::RwLock;
 
+use crate::intentions;
+use crate::notifications::ClientNotifier;
+use crate::profiles::Profiles;
+use crate::projects::{self, ProjectIds, ProjectScope};
+use crate::prompts::{self, RecallContextArgs, SessionSummaryArgs};
+use crate::resources::{self, ResourceKind, ResourceUri, Subscriptions};
 use crate::tools::*;
 
+/// Subject reported to the authorizer when none is configured.
+pub const DEFAULT_SUBJECT: &str = "mcp";
+
 /// MCP server for Rook memory operations.
 ///
-/// Wraps a `rook_core::Memory` instance and exposes it as MCP tools.
+/// Wraps one `rook_core::Memory` instance per profile and exposes them as MCP
+/// tools. Each tool takes an optional `profile` argument, and tools that
+/// store or look up memories by scope also take a `project` (see
+/// [`projects`](crate::projects)).
 #[derive(Clone)]
 pub struct MemoryServer {
-    memory: Arc<RwLock<rook_core::Memory>>,
+    profiles: Arc<Profiles>,
+    authorizer: Arc<dyn Authorizer>,
+    subject: String,
+    project_scope: ProjectScope,
+    subscriptions: Arc<Subscriptions>,
+    notifier: Arc<ClientNotifier>,
     tool_router: ToolRouter<MemoryServer>,
+    prompt_router: PromptRouter<MemoryServer>,
+}
+
+impl MemoryServer {
+    /// Set the authorization hook and the subject tool calls run as.
+    pub fn with_authorizer(
+        mut self,
+        authorizer: Arc<dyn Authorizer>,
+        subject: impl Into<String>,
+    ) -> Self {
+        self.authorizer = authorizer;
+        self.subject = subject.into();
+        self
+    }
+
+    /// Set which memory scope projects map to.
+    pub fn with_project_scope(mut self, project_scope: ProjectScope) -> Self {
+        self.project_scope = project_scope;
+        self
+    }
+
+    /// Notifier delivering `notify_mcp` intention actions to the clients
+    /// connected to this server (see [`notifications`](crate::notifications)).
+    pub fn intention_notifier(&self) -> Arc<dyn IntentionNotifier> {
+        self.notifier.clone()
+    }
+
+    /// Executor for the actions of fired intentions, sending `notify_mcp`
+    /// actions to the clients connected to this server.
+    pub fn intention_executor(&self) -> ActionExecutor {
+        ActionExecutor::new().with_notifier(self.intention_notifier())
+    }
+
+    /// Run the time-based intentions of `store` as they fire, with the
+    /// actions executed by [`MemoryServer::intention_executor`] (see
+    /// [`intentions`](crate::intentions)). Returns the number scheduled.
+    pub async fn start_intentions(
+        &self,
+        store: Arc<dyn IntentionStore>,
+    ) -> rook_core::RookResult<usize> {
+        intentions::start(self.intention_executor(), store).await
+    }
+
+    /// Soft-delete expired memories in every profile every `interval`.
+    pub fn start_memory_expiry(&self, interval: Duration) {
+        let profiles = self.profiles.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for name in profiles.names() {
+                    let Ok(memory) = profiles.get(Some(name)) else {
+                        continue;
+                    };
+                    match memory.read().await.expire_memories().await {
+                        Ok(0) => {}
+                        Ok(n) => tracing::info!(profile = name, "Deleted {} expired memories", n),
+                        Err(e) => tracing::warn!(profile = name, error = %e, "Failed to expire memories"),
+                    }
+                }
+            }
+        });
+    }
+
+    /// Archive decayed memories in every profile every `interval`.
+    pub fn start_memory_archive(&self, interval: Duration) {
+        let profiles = self.profiles.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                for name in profiles.names() {
+                    let Ok(memory) = profiles.get(Some(name)) else {
+                        continue;
+                    };
+                    match memory.read().await.archive_decayed().await {
+                        Ok(0) => {}
+                        Ok(n) => tracing::info!(profile = name, "Archived {} decayed memories", n),
+                        Err(e) => tracing::warn!(profile = name, error = %e, "Failed to archive memories"),
+                    }
+                }
+            }
+        });
+    }
+
+    /// Memory for a profile, or the default profile.
+    fn memory(&self, profile: Option<&str>) -> Result<Arc<RwLock<rook_core::Memory>>, McpError> {
+        self.profiles
+            .get(profile)
+            .cloned()
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))
+    }
+
+    fn authz_request(&self, action: AuthzAction) -> AuthzRequest {
+        AuthzRequest::new(self.subject.clone(), action)
+    }
+
+    async fn authorize(&self, request: AuthzRequest) -> Result<(), McpError> {
+        self.authorizer
+            .enforce(&request)
+            .await
+            .map_err(|e| McpError::invalid_request(e.to_string(), None))
+    }
+
+    /// `agent_id`/`run_id` for a call's project, falling back to the
+    /// client's first workspace root.
+    async fn project_ids(&self, project: Option<&str>, peer: &Peer<RoleServer>) -> ProjectIds {
+        if project.is_some() || self.project_scope == ProjectScope::Off {
+            return self.project_scope.ids(project);
+        }
+
+        let supports_roots = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.roots.is_some());
+        if !supports_roots {
+            return ProjectIds::default();
+        }
+        match peer.list_roots().await {
+            Ok(result) => {
+                let project = result
+                    .roots
+                    .first()
+                    .and_then(|root| projects::project_from_root(&root.uri));
+                self.project_scope.ids(project.as_deref())
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Failed to list client roots");
+                ProjectIds::default()
+            }
+        }
+    }
+
+    /// Tell resource subscribers that a user's memories changed.
+    async fn notify_changed(&self, profile: Option<&str>, user_id: Option<&str>) {
+        let default = self.profiles.default_name();
+        self.subscriptions
+            .notify_changed(profile.unwrap_or(default), user_id, default)
+            .await;
+    }
+
+    /// Read a memory resource as JSON.
+    async fn read_memory_resource(&self, resource: &ResourceUri) -> Result<String, McpError> {
+        let memory = self.memory(resource.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let json = match resource.kind {
+            ResourceKind::Categories => serde_json::to_string_pretty(&memory.categories()),
+            ResourceKind::Recent { ref user_id } | ResourceKind::Category { ref user_id, .. } => {
+                self.authorize(self.authz_request(AuthzAction::List).with_scope(
+                    Some(user_id.clone()),
+                    None,
+                    None,
+                ))
+                .await?;
+
+                let items = match resource.kind {
+                    ResourceKind::Category { ref name, .. } => {
+                        memory
+                            .get_category(
+                                name,
+                                Some(user_id.clone()),
+                                None,
+                                None,
+                                Some(resource.limit),
+                            )
+                            .await
+                    }
+                    _ => {
+                        memory
+                            .get_all_ordered(
+                                Some(user_id.clone()),
+                                None,
+                                None,
+                                Some("created_at:desc"),
+                                Some(resource.limit),
+                            )
+                            .await
+                    }
+                }
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+                let output: Vec<GetMemoryResult> = items
+                    .into_iter()
+                    .map(|mem| GetMemoryResult {
+                        id: mem.id,
+                        memory: mem.memory,
+                        metadata: mem.metadata.and_then(|m| serde_json::to_value(m).ok()),
+                        created_at: mem.created_at,
+                    })
+                    .collect();
+                serde_json::to_string_pretty(&output)
+            }
+        };
+        json.map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
 }
 
 #[tool_router]
 impl MemoryServer {
     /// Create a new MemoryServer wrapping the given Memory instance.
     pub fn new(memory: Arc<RwLock<rook_core::Memory>>) -> Self {
+        Self::with_profiles(Profiles::single(memory))
+    }
+
+    /// Create a MemoryServer serving several named profiles.
+    pub fn with_profiles(profiles: Profiles) -> Self {
         Self {
-            memory,
+            profiles: Arc::new(profiles),
+            authorizer: Arc::new(AllowAll),
+            subject: DEFAULT_SUBJECT.to_string(),
+            project_scope: ProjectScope::default(),
+            subscriptions: Arc::new(Subscriptions::default()),
+            notifier: Arc::new(ClientNotifier::default()),
             tool_router: Self::tool_router(),
+            prompt_router: Self::prompt_router(),
         }
     }
 
@@ -46,28 +284,47 @@ impl MemoryServer {
     async fn memory_add(
         &self,
         Parameters(input): Parameters<AddMemoryInput>,
+        peer: Peer<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        let memory = self.memory.read().await;
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
 
         // Convert metadata to HashMap if provided
-        let metadata: Option<HashMap<String, serde_json::Value>> =
+        let mut metadata: Option<HashMap<String, serde_json::Value>> =
             input.metadata.and_then(|v| {
                 v.as_object()
                     .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
             });
+        if let Some(ttl_secs) = input.ttl_secs {
+            metadata
+                .get_or_insert_with(HashMap::new)
+                .insert(EXPIRES_AT_FIELD.to_string(), expiry_from_ttl(ttl_secs));
+        }
+
+        self.authorize(
+            self.authz_request(AuthzAction::Add)
+                .with_scope(input.user_id.clone(), ids.agent_id.clone(), ids.run_id.clone())
+                .with_metadata(metadata.clone().unwrap_or_default()),
+        )
+        .await?;
 
         let result = memory
             .add(
                 input.content.as_str(),
-                input.user_id,
-                None, // agent_id
-                None, // run_id
+                input.user_id.clone(),
+                ids.agent_id,
+                ids.run_id,
                 metadata,
                 true, // infer facts
                 None, // memory_type
             )
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if !result.results.is_empty() {
+            self.notify_changed(input.profile.as_deref(), input.user_id.as_deref())
+                .await;
+        }
 
         // Return first result (add typically returns one memory)
         if let Some(mem_result) = result.results.first() {
@@ -86,6 +343,66 @@ impl MemoryServer {
         }
     }
 
+    /// Decide whether content is worth storing, and store it if so.
+    ///
+    /// Unlike `memory_add`, the content is compared against existing
+    /// memories and may be skipped as a duplicate or replace a memory it
+    /// refines or contradicts. The decision is returned with its reasoning.
+    #[tool(
+        name = "memory_smart_ingest",
+        description = "Store content only if it is new: it is skipped if it duplicates an existing memory, updates a memory it refines, or supersedes one it contradicts. Returns the decision, a surprise score and the reasoning."
+    )]
+    async fn memory_smart_ingest(
+        &self,
+        Parameters(input): Parameters<SmartIngestInput>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let metadata: Option<HashMap<String, serde_json::Value>> =
+            input.metadata.and_then(|v| {
+                v.as_object()
+                    .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            });
+
+        self.authorize(
+            self.authz_request(AuthzAction::Add)
+                .with_scope(input.user_id.clone(), ids.agent_id.clone(), ids.run_id.clone())
+                .with_metadata(metadata.clone().unwrap_or_default()),
+        )
+        .await?;
+
+        let result = memory
+            .smart_ingest(
+                &input.content,
+                input.user_id.clone(),
+                ids.agent_id,
+                ids.run_id,
+                metadata,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if result.decision != IngestDecision::Skip {
+            self.notify_changed(input.profile.as_deref(), input.user_id.as_deref())
+                .await;
+        }
+
+        let output = SmartIngestResult {
+            decision: decision_name(result.decision).to_string(),
+            memory_id: result.memory_id,
+            related_memory_id: result.related_memory_id,
+            previous_content: result.previous_content,
+            surprise: result.surprise,
+            decided_at_layer: layer_name(result.decided_at_layer).to_string(),
+            reason: result.reason,
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
     /// Search memories by semantic similarity.
     ///
     /// Returns the most relevant memories for the given query, ranked by
@@ -97,24 +414,80 @@ impl MemoryServer {
     async fn memory_search(
         &self,
         Parameters(input): Parameters<SearchMemoryInput>,
+        peer: Peer<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
-        let memory = self.memory.read().await;
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        self.authorize(
+            self.authz_request(AuthzAction::Search).with_scope(
+                input.user_id.clone(),
+                ids.agent_id.clone(),
+                ids.run_id.clone(),
+            ),
+        )
+        .await?;
+
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
 
+        let mut filters = input.tags.map(|tags| {
+            HashMap::from([(TAGS_FIELD.to_string(), serde_json::json!(tags))])
+        });
+        if input.include_archived {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(INCLUDE_ARCHIVED_FILTER.to_string(), serde_json::json!(true));
+        }
+        if let Some(mode) = input.retrieval_mode {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(RETRIEVAL_MODE_FILTER.to_string(), serde_json::json!(mode));
+        }
+        if let Some(lambda) = input.mmr_lambda {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(MMR_LAMBDA_FILTER.to_string(), serde_json::json!(lambda));
+        }
+        if let Some(expansion) = input.query_expansion {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(QUERY_EXPANSION_FILTER.to_string(), serde_json::json!(expansion));
+        }
+        if let Some(since) = input.since {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(SINCE_FILTER.to_string(), serde_json::json!(since));
+        }
+        if let Some(until) = input.until {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(UNTIL_FILTER.to_string(), serde_json::json!(until));
+        }
+        if let Some(half_life) = input.recency_half_life_days {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(RECENCY_HALF_LIFE_FILTER.to_string(), serde_json::json!(half_life));
+        }
+        if input.explain {
+            filters
+                .get_or_insert_with(HashMap::new)
+                .insert(EXPLAIN_FILTER.to_string(), serde_json::json!(true));
+        }
         let results = memory
             .search(
                 &input.query,
                 input.user_id,
-                None, // agent_id
-                None, // run_id
+                ids.agent_id,
+                ids.run_id,
                 input.limit,
-                None,  // filters
+                filters,
                 None,  // threshold
                 false, // rerank
             )
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
-        let output: Vec<MemorySearchResult> = results
+        let fields = FieldSelection::from_fields(input.fields.unwrap_or_default());
+        let output: Vec<serde_json::Value> = results
             .results
             .into_iter()
             .map(|r| MemorySearchResult {
@@ -122,14 +495,59 @@ impl MemoryServer {
                 memory: r.memory,
                 score: r.score.unwrap_or(0.0),
                 metadata: r.metadata.and_then(|m| serde_json::to_value(m).ok()),
+                explanation: r.explanation.and_then(|e| serde_json::to_value(e).ok()),
             })
-            .collect();
+            .map(|r| fields.project(&r))
+            .collect::<Result<_, _>>()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
         Ok(CallToolResult::success(vec![Content::text(
             serde_json::to_string_pretty(&output).unwrap_or_default(),
         )]))
     }
 
+    /// Find relations in the knowledge graph related to a query.
+    ///
+    /// Returns the entities involved and one `source -[relationship]-> target`
+    /// line per relation.
+    #[tool(
+        name = "memory_graph_search",
+        description = "Search the knowledge graph for entities and relationships related to a query. Returns the entities involved and one 'source -[relationship]-> target' line per relation."
+    )]
+    async fn memory_graph_search(
+        &self,
+        Parameters(input): Parameters<GraphSearchInput>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        self.authorize(
+            self.authz_request(AuthzAction::Search).with_scope(
+                input.user_id.clone(),
+                ids.agent_id.clone(),
+                ids.run_id.clone(),
+            ),
+        )
+        .await?;
+
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let relations = memory
+            .graph_search(
+                &input.query,
+                input.user_id,
+                ids.agent_id,
+                ids.run_id,
+                input.limit,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(format_relations(
+            &relations,
+        ))]))
+    }
+
     /// Get a specific memory by its ID.
     #[tool(
         name = "memory_get",
@@ -139,21 +557,33 @@ impl MemoryServer {
         &self,
         Parameters(input): Parameters<GetMemoryInput>,
     ) -> Result<CallToolResult, McpError> {
-        let memory = self.memory.read().await;
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
 
         let result = memory
             .get(&input.id)
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
+        if let Some(ref mem) = result {
+            self.authorize(
+                self.authz_request(AuthzAction::Get)
+                    .with_memory_id(&input.id)
+                    .with_metadata(mem.metadata.clone().unwrap_or_default()),
+            )
+            .await?;
+        }
+
         match result {
             Some(mem) => {
-                let output = GetMemoryResult {
-                    id: mem.id,
-                    memory: mem.memory,
-                    metadata: mem.metadata.and_then(|m| serde_json::to_value(m).ok()),
-                    created_at: mem.created_at,
-                };
+                let output = FieldSelection::from_fields(input.fields.unwrap_or_default())
+                    .project(&GetMemoryResult {
+                        id: mem.id,
+                        memory: mem.memory,
+                        metadata: mem.metadata.and_then(|m| serde_json::to_value(m).ok()),
+                        created_at: mem.created_at,
+                    })
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?;
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&output).unwrap_or_default(),
                 )]))
@@ -165,6 +595,438 @@ impl MemoryServer {
         }
     }
 
+    /// Replace the content of a memory by its ID.
+    ///
+    /// The memory keeps its ID and metadata; the old content stays in its
+    /// history.
+    #[tool(
+        name = "memory_update",
+        description = "Correct a stored memory by replacing its content, keeping its ID and metadata, and/or set when it expires with `ttl_secs` (0 clears the expiry). Use this instead of deleting and re-adding a memory."
+    )]
+    async fn memory_update(
+        &self,
+        Parameters(input): Parameters<UpdateMemoryInput>,
+    ) -> Result<CallToolResult, McpError> {
+        if input.content.is_none() && input.ttl_secs.is_none() {
+            return Err(McpError::invalid_params(
+                "Provide content or ttl_secs",
+                None,
+            ));
+        }
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let existing = memory
+            .get(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Memory with id '{}' not found", input.id), None)
+            })?;
+        let metadata = existing.metadata.clone().unwrap_or_default();
+        self.authorize(
+            self.authz_request(AuthzAction::Update)
+                .with_memory_id(&input.id)
+                .with_metadata(metadata.clone()),
+        )
+        .await?;
+
+        let mut updated = existing.clone();
+        if let Some(ref content) = input.content {
+            updated = memory
+                .update(&input.id, content)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        }
+        if let Some(ttl_secs) = input.ttl_secs {
+            let expires_at = (ttl_secs > 0).then(|| expiry_after(ttl_secs));
+            updated = memory
+                .set_expiry(&input.id, expires_at)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        }
+        self.notify_changed(
+            input.profile.as_deref(),
+            metadata.get("user_id").and_then(|v| v.as_str()),
+        )
+        .await;
+
+        let output = UpdateMemoryResult {
+            id: updated.id,
+            memory: updated.memory,
+            previous_memory: Some(existing.memory),
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// Add or remove free-form tags on a memory.
+    #[tool(
+        name = "memory_tag",
+        description = "Add or remove free-form tags on a memory, e.g. to label it by topic or project. A memory can carry any number of tags; search with `tags` to only return memories carrying them."
+    )]
+    async fn memory_tag(
+        &self,
+        Parameters(input): Parameters<TagMemoryInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let metadata = memory
+            .get(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Memory with id '{}' not found", input.id), None)
+            })?
+            .metadata
+            .unwrap_or_default();
+        self.authorize(
+            self.authz_request(AuthzAction::Update)
+                .with_memory_id(&input.id)
+                .with_metadata(metadata),
+        )
+        .await?;
+
+        let mut item = memory
+            .add_tags(&input.id, &input.add)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if !input.remove.is_empty() {
+            item = memory
+                .remove_tags(&input.id, &input.remove)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        }
+
+        let output = TagMemoryResult {
+            tags: item.tags(),
+            id: item.id,
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// Merge overlapping memories into one.
+    #[tool(
+        name = "memory_merge",
+        description = "Combine several overlapping or near-duplicate memories of the same user into one consolidated memory. The originals are superseded (default) or deleted; the merged memory links back to them."
+    )]
+    async fn memory_merge(
+        &self,
+        Parameters(input): Parameters<MergeMemoryInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let strategy: MergeStrategy = match input.strategy.as_deref() {
+            Some(strategy) => strategy
+                .parse()
+                .map_err(|e: rook_core::RookError| McpError::invalid_params(e.to_string(), None))?,
+            None => MergeStrategy::default(),
+        };
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let mut user_id = None;
+        for id in &input.ids {
+            let metadata = memory
+                .get(id)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?
+                .ok_or_else(|| {
+                    McpError::invalid_params(format!("Memory with id '{}' not found", id), None)
+                })?
+                .metadata
+                .unwrap_or_default();
+            user_id = metadata.get("user_id").and_then(|v| v.as_str()).map(str::to_string);
+            self.authorize(
+                self.authz_request(AuthzAction::Delete)
+                    .with_memory_id(id)
+                    .with_metadata(metadata),
+            )
+            .await?;
+        }
+
+        let merged = memory
+            .merge(&input.ids, strategy)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.notify_changed(input.profile.as_deref(), user_id.as_deref()).await;
+
+        let output = MergeMemoryResult {
+            id: merged.id,
+            memory: merged.memory,
+            merged_from: input.ids,
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// List the tags used in a scope, most used first.
+    #[tool(
+        name = "memory_tags",
+        description = "List the tags used by a user's memories, with how many memories carry each, most used first."
+    )]
+    async fn memory_tags(
+        &self,
+        Parameters(input): Parameters<ListTagsInput>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        self.authorize(self.authz_request(AuthzAction::List).with_scope(
+            input.user_id.clone(),
+            ids.agent_id.clone(),
+            ids.run_id.clone(),
+        ))
+        .await?;
+
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let tags = memory
+            .tags(input.user_id, ids.agent_id, ids.run_id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&tags).unwrap_or_default(),
+        )]))
+    }
+
+    /// Show how a memory changed over time.
+    ///
+    /// Returns the recorded changes and, when versioning is enabled, every
+    /// version of the memory, so an agent can tell what it previously
+    /// believed and when that changed. Deleted memories keep their history.
+    #[tool(
+        name = "memory_history",
+        description = "Show how a memory changed over time: its recorded changes and, when versioning is enabled, every earlier version with timestamps. Use this to answer what was previously believed and when it changed."
+    )]
+    async fn memory_history(
+        &self,
+        Parameters(input): Parameters<HistoryMemoryInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let current = memory
+            .get(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let versions = if memory.versioning_enabled() {
+            Some(
+                memory
+                    .versions(&input.id)
+                    .await
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+            )
+        } else {
+            None
+        };
+
+        // Deleted memories are authorized against their last known metadata.
+        let metadata = match current {
+            Some(ref mem) => mem.metadata.clone().unwrap_or_default(),
+            None => versions
+                .as_ref()
+                .and_then(|versions| versions.last())
+                .map(|latest| latest.metadata.clone())
+                .unwrap_or_default(),
+        };
+        self.authorize(
+            self.authz_request(AuthzAction::History)
+                .with_memory_id(&input.id)
+                .with_metadata(metadata),
+        )
+        .await?;
+
+        let history: Vec<serde_json::Value> = memory
+            .history(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .into_iter()
+            .map(|h| serde_json::to_value(h).unwrap_or_default())
+            .collect();
+        if current.is_none()
+            && history.is_empty()
+            && versions.as_deref().unwrap_or_default().is_empty()
+        {
+            return Err(McpError::invalid_params(
+                format!("Memory with id '{}' not found", input.id),
+                None,
+            ));
+        }
+
+        let output = MemoryHistoryResult {
+            id: input.id,
+            memory: current.map(|mem| mem.memory),
+            history,
+            versions: versions.map(|versions| {
+                versions
+                    .into_iter()
+                    .map(|v| MemoryVersionResult {
+                        version: v.version_number,
+                        content: v.content,
+                        event: v.event_type.as_str().to_string(),
+                        created_at: v.created_at.to_rfc3339(),
+                        change_description: v.change_description,
+                    })
+                    .collect()
+            }),
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// List stored memories for a scope, a page at a time.
+    #[tool(
+        name = "memory_list",
+        description = "List stored memories, oldest first, a page at a time. Use this to review what is stored; pass the returned next_cursor to get the next page."
+    )]
+    async fn memory_list(
+        &self,
+        Parameters(input): Parameters<ListMemoryInput>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        self.authorize(
+            self.authz_request(AuthzAction::List).with_scope(
+                input.user_id.clone(),
+                ids.agent_id.clone(),
+                ids.run_id.clone(),
+            ),
+        )
+        .await?;
+
+        let mut page = PageRequest::new()
+            .with_limit(input.limit)
+            .with_offset(input.offset);
+        if let Some(ref cursor) = input.cursor {
+            let cursor = Cursor::decode(cursor)
+                .map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+            page = page.with_cursor(cursor);
+        }
+
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let page = memory
+            .get_page(input.user_id, ids.agent_id, ids.run_id, None, &page)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let fields = FieldSelection::from_fields(input.fields.unwrap_or_default());
+        let memories: Vec<serde_json::Value> = page
+            .items
+            .into_iter()
+            .map(|mem| GetMemoryResult {
+                id: mem.id,
+                memory: mem.memory,
+                metadata: mem.metadata.and_then(|m| serde_json::to_value(m).ok()),
+                created_at: mem.created_at,
+            })
+            .map(|r| fields.project(&r))
+            .collect::<Result<_, _>>()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let output = ListMemoryResult {
+            memories,
+            total: page.total,
+            next_cursor: page.next_cursor,
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// List memories whose recall has decayed and need reviewing.
+    #[tool(
+        name = "memory_due_for_review",
+        description = "List memories whose estimated recall has decayed enough that they should be checked, least likely to be recalled first. Confirm or correct each with the user and record the outcome with memory_review. Needs cognitive tracking (ROOK_COGNITIVE=true)."
+    )]
+    async fn memory_due_for_review(
+        &self,
+        Parameters(input): Parameters<DueForReviewInput>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let ids = self.project_ids(input.project.as_deref(), &peer).await;
+        self.authorize(
+            self.authz_request(AuthzAction::List).with_scope(
+                input.user_id.clone(),
+                ids.agent_id.clone(),
+                ids.run_id.clone(),
+            ),
+        )
+        .await?;
+
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let due = memory
+            .due_for_review(
+                SessionScope::new(input.user_id, ids.agent_id, ids.run_id),
+                input.limit,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let output: Vec<ReviewItem> = due
+            .into_iter()
+            .map(|mem| ReviewItem {
+                retrievability: mem.retrievability.unwrap_or(0.0),
+                id: mem.id,
+                memory: mem.memory,
+            })
+            .collect();
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
+    /// Record how well a memory held up and reschedule its next review.
+    #[tool(
+        name = "memory_review",
+        description = "Record how well a memory held up when checked: \"again\" if it was wrong or forgotten, \"hard\", \"good\" or \"easy\". Schedules when it next needs reviewing."
+    )]
+    async fn memory_review(
+        &self,
+        Parameters(input): Parameters<ReviewMemoryInput>,
+    ) -> Result<CallToolResult, McpError> {
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let metadata = memory
+            .get(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Memory with id '{}' not found", input.id), None)
+            })?
+            .metadata
+            .unwrap_or_default();
+        self.authorize(
+            self.authz_request(AuthzAction::Update)
+                .with_memory_id(&input.id)
+                .with_metadata(metadata),
+        )
+        .await?;
+
+        let item = memory
+            .review(&input.id, input.grade.into())
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let output = ReviewItem {
+            retrievability: item.retrievability.unwrap_or(0.0),
+            id: item.id,
+            memory: item.memory,
+        };
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        )]))
+    }
+
     /// Delete a memory by its ID.
     #[tool(
         name = "memory_delete",
@@ -174,12 +1036,31 @@ impl MemoryServer {
         &self,
         Parameters(input): Parameters<DeleteMemoryInput>,
     ) -> Result<CallToolResult, McpError> {
-        let memory = self.memory.read().await;
+        let memory = self.memory(input.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let metadata = memory
+            .get(&input.id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .and_then(|m| m.metadata)
+            .unwrap_or_default();
+        self.authorize(
+            self.authz_request(AuthzAction::Delete)
+                .with_memory_id(&input.id)
+                .with_metadata(metadata.clone()),
+        )
+        .await?;
 
         memory
             .delete(&input.id)
             .await
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        self.notify_changed(
+            input.profile.as_deref(),
+            metadata.get("user_id").and_then(|v| v.as_str()),
+        )
+        .await;
 
         let output = DeleteMemoryResult {
             deleted: input.id,
@@ -190,20 +1071,299 @@ impl MemoryServer {
     }
 }
 
+/// Compact relation list: the entities involved, then one line per relation.
+fn format_relations(relations: &[GraphRelation]) -> String {
+    if relations.is_empty() {
+        return "No related entities found".to_string();
+    }
+
+    let mut entities: Vec<&str> = Vec::new();
+    for relation in relations {
+        for entity in [relation.source.as_str(), relation.target.as_str()] {
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+    }
+
+    let mut output = format!("Entities: {}\nRelations:", entities.join(", "));
+    for relation in relations {
+        output.push_str(&format!(
+            "\n{} -[{}]-> {}",
+            relation.source, relation.relationship, relation.target
+        ));
+    }
+    output
+}
+
+#[prompt_router]
+impl MemoryServer {
+    /// Recall memories about a topic as a system preamble.
+    #[prompt(
+        name = "recall_context",
+        description = "Search memories for the current topic and format them as a system preamble, so the conversation starts with what is already known."
+    )]
+    async fn recall_context(
+        &self,
+        Parameters(args): Parameters<RecallContextArgs>,
+        peer: Peer<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let limit = args
+            .limit()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let ids = self.project_ids(args.project.as_deref(), &peer).await;
+        self.authorize(self.authz_request(AuthzAction::Search).with_scope(
+            args.user_id.clone(),
+            ids.agent_id.clone(),
+            ids.run_id.clone(),
+        ))
+        .await?;
+
+        let memory = self.memory(args.profile.as_deref())?;
+        let memory = memory.read().await;
+
+        let results = memory
+            .search(
+                &args.topic,
+                args.user_id,
+                ids.agent_id,
+                ids.run_id,
+                limit,
+                None,  // filters
+                None,  // threshold
+                false, // rerank
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let memories: Vec<String> = results.results.into_iter().map(|r| r.memory).collect();
+
+        Ok(GetPromptResult {
+            description: Some(format!("Memories about \"{}\"", args.topic)),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompts::recall_preamble(&args.topic, &memories),
+            )],
+        })
+    }
+
+    /// Ask for memories worth storing from the session.
+    #[prompt(
+        name = "end_of_session_summary",
+        description = "Review the session and propose memories worth storing, skipping what is already known, then store them with memory_smart_ingest."
+    )]
+    async fn end_of_session_summary(
+        &self,
+        Parameters(args): Parameters<SessionSummaryArgs>,
+        peer: Peer<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let ids = self.project_ids(args.project.as_deref(), &peer).await;
+        let known = match args.user_id {
+            Some(ref user_id) => {
+                self.authorize(self.authz_request(AuthzAction::List).with_scope(
+                    Some(user_id.clone()),
+                    ids.agent_id.clone(),
+                    ids.run_id.clone(),
+                ))
+                .await?;
+
+                let memory = self.memory(args.profile.as_deref())?;
+                let memory = memory.read().await;
+                memory
+                    .get_all_ordered(
+                        Some(user_id.clone()),
+                        ids.agent_id.clone(),
+                        ids.run_id.clone(),
+                        Some("created_at:desc"),
+                        Some(prompts::SUMMARY_KNOWN_LIMIT),
+                    )
+                    .await
+                    .map_err(|e| McpError::internal_error(e.to_string(), None))?
+                    .into_iter()
+                    .map(|mem| mem.memory)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        Ok(GetPromptResult {
+            description: Some("Propose memories to store from this session".to_string()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompts::session_summary_request(
+                    args.user_id.as_deref(),
+                    args.project.as_deref().filter(|p| !p.is_empty()),
+                    &known,
+                ),
+            )],
+        })
+    }
+}
+
+fn decision_name(decision: IngestDecision) -> &'static str {
+    match decision {
+        IngestDecision::Skip => "skip",
+        IngestDecision::Create => "create",
+        IngestDecision::Update => "update",
+        IngestDecision::Supersede => "supersede",
+    }
+}
+
+fn layer_name(layer: DetectionLayer) -> &'static str {
+    match layer {
+        DetectionLayer::EmbeddingSimilarity => "embedding_similarity",
+        DetectionLayer::KeywordPattern => "keyword_pattern",
+        DetectionLayer::TemporalConflict => "temporal_conflict",
+        DetectionLayer::SemanticLlm => "semantic_llm",
+        DetectionLayer::Default => "default",
+    }
+}
+
 #[tool_handler]
+#[prompt_handler]
 impl ServerHandler for MemoryServer {
     fn get_info(&self) -> ServerInfo {
+        let mut instructions = "Rook Memory Server - A persistent memory layer for AI assistants. \
+             Use memory_add to store new memories, memory_smart_ingest to store \
+             content only if it is new, memory_search to find relevant \
+             memories based on a query, memory_get to retrieve a specific memory, \
+             memory_graph_search to explore how people, projects and other \
+             entities relate, \
+             memory_merge to combine overlapping memories, \
+             memory_update to correct a memory or change when it expires \
+             (memory_add also takes ttl_secs for temporary facts), memory_tag and memory_tags to \
+             label memories and list labels, memory_history to see how a \
+             memory changed over time, memory_list to review stored memories, \
+             memory_due_for_review and memory_review to check memories whose \
+             recall has decayed, \
+             and memory_delete to remove memories. Memories can also be \
+             attached as rook:// resources. The recall_context and \
+             end_of_session_summary prompts load relevant memories at the start \
+             of a session and propose new ones at the end."
+            .to_string();
+        if self.profiles.is_multi() {
+            instructions.push_str(&format!(
+                " Memories are kept in separate profiles; pass `profile` to choose one \
+                 (default: {}). Profiles:",
+                self.profiles.default_name()
+            ));
+            for name in self.profiles.names() {
+                match self.profiles.description(name) {
+                    Some(description) => {
+                        instructions.push_str(&format!(" {} ({});", name, description))
+                    }
+                    None => instructions.push_str(&format!(" {};", name)),
+                }
+            }
+        }
+
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .enable_logging()
+                .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some(
-                "Rook Memory Server - A persistent memory layer for AI assistants. \
-                 Use memory_add to store new memories, memory_search to find relevant \
-                 memories based on a query, memory_get to retrieve a specific memory, \
-                 and memory_delete to remove memories."
-                    .to_string(),
-            ),
+            instructions: Some(instructions),
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: resources::static_resources(),
+            ..Default::default()
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: resources::resource_templates(),
+            ..Default::default()
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let resource = ResourceUri::parse(&request.uri)
+            .map_err(|e| McpError::resource_not_found(e, None))?;
+        let json = self.read_memory_resource(&resource).await?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some("application/json".to_string()),
+                text: json,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParams,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        ResourceUri::parse(&request.uri).map_err(|e| McpError::resource_not_found(e, None))?;
+        self.subscriptions.subscribe(request.uri, context.peer);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.unsubscribe(&request.uri);
+        Ok(())
+    }
+
+    /// Log messages are only sent for intention notifications, so the level
+    /// is accepted but not applied.
+    async fn set_level(
+        &self,
+        _request: SetLevelRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        Ok(())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.notifier.register(context.peer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relations() {
+        let relation = |source: &str, relationship: &str, target: &str| GraphRelation {
+            source: source.to_string(),
+            relationship: relationship.to_string(),
+            target: target.to_string(),
+        };
+        let output = format_relations(&[
+            relation("Alice", "works_at", "Acme"),
+            relation("Bob", "works_at", "Acme"),
+        ]);
+        assert_eq!(
+            output,
+            "Entities: Alice, Acme, Bob\nRelations:\nAlice -[works_at]-> Acme\nBob -[works_at]-> Acme"
+        );
+        assert_eq!(format_relations(&[]), "No related entities found");
+    }
 }
@@ -21,6 +21,47 @@ pub struct AddMemoryInput {
     /// Can include arbitrary key-value pairs for filtering and context.
     #[serde(default)]
     pub metadata: Option<serde_json::Value>,
+
+    /// Delete the memory this many seconds from now, for information that
+    /// is only temporarily true.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_smart_ingest tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SmartIngestInput {
+    /// The content to consider storing.
+    pub content: String,
+
+    /// User ID for scoping the memory.
+    /// The content is compared against this user's existing memories.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Optional metadata as a JSON object, stored with a created memory.
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 /// Input for memory_search tool.
@@ -38,17 +79,187 @@ pub struct SearchMemoryInput {
     /// Maximum results to return.
     #[serde(default = "default_limit")]
     pub limit: usize,
+
+    /// Only return memories carrying all of these tags.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+
+    /// Also search archived memories, which have decayed from disuse.
+    /// Archived memories found are restored.
+    #[serde(default)]
+    pub include_archived: bool,
+
+    /// Rank results with the retrieval engine: "quick", "standard",
+    /// "precise" or "cognitive" (spreading activation and memory strength).
+    /// Omit for plain similarity search.
+    #[serde(default)]
+    pub retrieval_mode: Option<String>,
+
+    /// Diversify results so near-identical memories don't fill the list,
+    /// from 0 (most diverse) to 1 (most relevant); 0.7 is a good start.
+    #[serde(default)]
+    pub mmr_lambda: Option<f32>,
+
+    /// Have the LLM rewrite the query before searching: "paraphrase" for a
+    /// few rephrasings, "hyde" for a hypothetical matching memory. Helps
+    /// short or vague queries.
+    #[serde(default)]
+    pub query_expansion: Option<String>,
+
+    /// Only return memories created at or after this time, as an RFC 3339
+    /// timestamp like "2025-06-02T00:00:00Z".
+    #[serde(default)]
+    pub since: Option<String>,
+
+    /// Only return memories created before this time (RFC 3339).
+    #[serde(default)]
+    pub until: Option<String>,
+
+    /// Favor recent memories: a memory's score halves for every this many
+    /// days of age, e.g. 7 for "this week" questions.
+    #[serde(default)]
+    pub recency_half_life_days: Option<f32>,
+
+    /// Return a breakdown of each result's score: vector similarity,
+    /// activation, memory strength, reranking and boosts, and whether it
+    /// was injected as a key or pinned memory. For debugging why a memory
+    /// surfaced.
+    #[serde(default)]
+    pub explain: bool,
+
+    /// Fields to return per result, e.g. ["memory", "metadata.user_id"].
+    /// `id` is always included. Omit to return every field.
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 fn default_limit() -> usize {
     10
 }
 
+/// Input for memory_graph_search tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GraphSearchInput {
+    /// Entity names or a question about them, e.g. "Who does Alice work with?".
+    pub query: String,
+
+    /// User ID to scope the graph.
+    /// Only returns relations learned from this user's memories.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Maximum relations to return.
+    #[serde(default = "default_relation_limit")]
+    pub limit: usize,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+fn default_relation_limit() -> usize {
+    20
+}
+
 /// Input for memory_get tool.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetMemoryInput {
     /// The memory ID to retrieve.
     pub id: String,
+
+    /// Fields to return, e.g. ["memory", "created_at"].
+    /// `id` is always included. Omit to return every field.
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_tag tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TagMemoryInput {
+    /// The memory ID to tag.
+    pub id: String,
+
+    /// Tags to add, e.g. ["rust", "work"].
+    #[serde(default)]
+    pub add: Vec<String>,
+
+    /// Tags to remove.
+    #[serde(default)]
+    pub remove: Vec<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_merge tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MergeMemoryInput {
+    /// IDs of the memories to merge; at least two, belonging to the same
+    /// user and project.
+    pub ids: Vec<String>,
+
+    /// What happens to the originals: "supersede" (default) keeps them in
+    /// the version history as superseded, "delete" deletes them.
+    #[serde(default)]
+    pub strategy: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_tags tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListTagsInput {
+    /// User whose tags to list.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_history tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct HistoryMemoryInput {
+    /// The memory ID to inspect.
+    pub id: String,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 /// Input for memory_delete tool.
@@ -56,6 +267,139 @@ pub struct GetMemoryInput {
 pub struct DeleteMemoryInput {
     /// The memory ID to delete.
     pub id: String,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_update tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateMemoryInput {
+    /// The memory ID to update.
+    pub id: String,
+
+    /// The corrected memory content, replacing the current text.
+    #[serde(default)]
+    pub content: Option<String>,
+
+    /// Delete the memory this many seconds from now; 0 clears its expiry.
+    /// At least one of `content` and `ttl_secs` is required.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Input for memory_list tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListMemoryInput {
+    /// User ID to scope the listing.
+    /// Only returns memories belonging to this user.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Maximum memories to return.
+    #[serde(default = "default_list_limit")]
+    pub limit: usize,
+
+    /// Memories to skip, for paging without a cursor.
+    #[serde(default)]
+    pub offset: usize,
+
+    /// `next_cursor` from the previous page, to continue after it.
+    #[serde(default)]
+    pub cursor: Option<String>,
+
+    /// Fields to return per memory, e.g. ["memory", "created_at"].
+    /// `id` is always included. Omit to return every field.
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+fn default_list_limit() -> usize {
+    50
+}
+
+/// Input for memory_due_for_review tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DueForReviewInput {
+    /// User whose memories to check.
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Maximum memories to return.
+    #[serde(default = "default_review_limit")]
+    pub limit: usize,
+
+    /// Project to scope memories to, e.g. the repository name.
+    /// Defaults to the client's workspace root; pass "" to skip project scoping.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+fn default_review_limit() -> usize {
+    10
+}
+
+/// How well a memory was recalled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewGrade {
+    /// Forgotten or wrong.
+    Again,
+    /// Recalled with difficulty.
+    Hard,
+    /// Recalled.
+    Good,
+    /// Recalled effortlessly.
+    Easy,
+}
+
+impl From<ReviewGrade> for rook_core::Grade {
+    fn from(grade: ReviewGrade) -> Self {
+        match grade {
+            ReviewGrade::Again => rook_core::Grade::Again,
+            ReviewGrade::Hard => rook_core::Grade::Hard,
+            ReviewGrade::Good => rook_core::Grade::Good,
+            ReviewGrade::Easy => rook_core::Grade::Easy,
+        }
+    }
+}
+
+/// Input for memory_review tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReviewMemoryInput {
+    /// The memory ID to review.
+    pub id: String,
+
+    /// How well the memory held up: "again" if it was wrong or forgotten,
+    /// "hard", "good" or "easy".
+    pub grade: ReviewGrade,
+
+    /// Memory profile to use, e.g. "work" or "personal".
+    /// Omit to use the default profile.
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 /// A single memory search result.
@@ -73,6 +417,10 @@ pub struct MemorySearchResult {
     /// Optional metadata associated with the memory.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<serde_json::Value>,
+
+    /// Score breakdown, when the search set `explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<serde_json::Value>,
 }
 
 /// Result of adding a memory.
@@ -88,6 +436,35 @@ pub struct AddMemoryResult {
     pub event: String,
 }
 
+/// Result of smart ingest, explaining what happened to the content.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SmartIngestResult {
+    /// Decision taken: skip, create, update or supersede.
+    pub decision: String,
+
+    /// The new or updated memory, or the existing duplicate when skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<String>,
+
+    /// The memory that was updated or superseded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_memory_id: Option<String>,
+
+    /// Content replaced by an update or supersede.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_content: Option<String>,
+
+    /// How unexpected the content was (0.0 duplicate to 1.0 novel).
+    pub surprise: f32,
+
+    /// Detection layer that made the decision.
+    pub decided_at_layer: String,
+
+    /// Why the decision was made.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
 /// Result of getting a memory.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct GetMemoryResult {
@@ -106,6 +483,107 @@ pub struct GetMemoryResult {
     pub created_at: Option<String>,
 }
 
+/// Result of updating a memory.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct UpdateMemoryResult {
+    /// The ID of the updated memory.
+    pub id: String,
+
+    /// The new memory content.
+    pub memory: String,
+
+    /// The content before the update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_memory: Option<String>,
+}
+
+/// One recorded version of a memory.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryVersionResult {
+    /// Version number, starting at 1.
+    pub version: u32,
+
+    /// Content at this version.
+    pub content: String,
+
+    /// What changed, e.g. "created", "content_updated" or "deleted".
+    pub event: String,
+
+    /// When this version was recorded.
+    pub created_at: String,
+
+    /// Why the memory changed, if recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_description: Option<String>,
+}
+
+/// How a memory changed over time.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryHistoryResult {
+    /// The memory ID.
+    pub id: String,
+
+    /// Current content, or `None` if the memory was deleted.
+    pub memory: Option<String>,
+
+    /// Recorded changes (add, update, delete), oldest first.
+    pub history: Vec<serde_json::Value>,
+
+    /// Versions, oldest first. Only present when versioning is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<Vec<MemoryVersionResult>>,
+}
+
+/// One page of memories.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ListMemoryResult {
+    /// Memories in the page, oldest first.
+    pub memories: Vec<serde_json::Value>,
+
+    /// Memories in the whole listing.
+    pub total: usize,
+
+    /// Cursor for the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// A memory due for review.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ReviewItem {
+    /// The memory ID.
+    pub id: String,
+
+    /// The memory content.
+    pub memory: String,
+
+    /// Probability the memory is still accurate and recalled, from 0 to 1.
+    pub retrievability: f32,
+}
+
+/// Tags of a memory after tagging it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TagMemoryResult {
+    /// The memory ID.
+    pub id: String,
+
+    /// The memory's tags.
+    pub tags: Vec<String>,
+}
+
+/// The memory created by merging others.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MergeMemoryResult {
+    /// The merged memory's ID.
+    pub id: String,
+
+    /// The merged memory content.
+    pub memory: String,
+
+    /// IDs of the memories merged into it.
+    pub merged_from: Vec<String>,
+}
+
 /// Result of deleting a memory.
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DeleteMemoryResult {
@@ -138,4 +616,57 @@ mod tests {
             serde_json::from_str(r#"{"query": "test", "limit": 5}"#).unwrap();
         assert_eq!(input.limit, 5);
     }
+
+    #[test]
+    fn test_tag_memory_input_defaults() {
+        let input: TagMemoryInput =
+            serde_json::from_str(r#"{"id": "m1", "add": ["rust"]}"#).unwrap();
+        assert_eq!(input.add, vec!["rust".to_string()]);
+        assert!(input.remove.is_empty());
+    }
+
+    #[test]
+    fn test_review_memory_input_grade() {
+        let input: ReviewMemoryInput =
+            serde_json::from_str(r#"{"id": "m1", "grade": "hard"}"#).unwrap();
+        assert_eq!(input.grade, ReviewGrade::Hard);
+        assert!(serde_json::from_str::<ReviewMemoryInput>(r#"{"id": "m1", "grade": "ok"}"#).is_err());
+
+        let input: DueForReviewInput = serde_json::from_str(r#"{}"#).unwrap();
+        assert_eq!(input.limit, 10);
+    }
+
+    #[test]
+    fn test_list_memory_input_defaults() {
+        let input: ListMemoryInput = serde_json::from_str(r#"{}"#).unwrap();
+        assert_eq!(input.limit, 50);
+        assert_eq!(input.offset, 0);
+        assert!(input.cursor.is_none());
+    }
+
+    #[test]
+    fn test_graph_search_input_default_limit() {
+        let input: GraphSearchInput = serde_json::from_str(r#"{"query": "Alice"}"#).unwrap();
+        assert_eq!(input.limit, 20);
+    }
+
+    #[test]
+    fn test_get_memory_input_fields() {
+        let input: GetMemoryInput =
+            serde_json::from_str(r#"{"id": "m1", "fields": ["memory"]}"#).unwrap();
+        assert_eq!(input.fields, Some(vec!["memory".to_string()]));
+
+        let input: GetMemoryInput = serde_json::from_str(r#"{"id": "m1"}"#).unwrap();
+        assert!(input.fields.is_none());
+    }
+
+    #[test]
+    fn test_profile_is_optional() {
+        let input: DeleteMemoryInput = serde_json::from_str(r#"{"id": "m1"}"#).unwrap();
+        assert!(input.profile.is_none());
+
+        let input: AddMemoryInput =
+            serde_json::from_str(r#"{"content": "likes tea", "profile": "personal"}"#).unwrap();
+        assert_eq!(input.profile.as_deref(), Some("personal"));
+    }
 }
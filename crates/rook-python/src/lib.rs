@@ -6,10 +6,11 @@
 use pyo3::prelude::*;
 
 mod memory;
+mod transfer;
 mod types;
 
 use memory::Memory;
-use types::{AddResult, MemoryItem, SearchResult};
+use types::{AddResult, ExportStats, ImportStats, MemoryItem, MigrationStats, SearchResult};
 
 /// Rook memory system for AI assistants.
 ///
@@ -22,5 +23,8 @@ fn rook_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<MemoryItem>()?;
     m.add_class::<SearchResult>()?;
     m.add_class::<AddResult>()?;
+    m.add_class::<ExportStats>()?;
+    m.add_class::<ImportStats>()?;
+    m.add_class::<MigrationStats>()?;
     Ok(())
 }
@@ -2,9 +2,18 @@
 
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+use rook_core::{ExportableMemory, ImportDedup, ImportableMemory};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use crate::types::{python_dict_to_metadata, AddResult, MemoryItem, SearchResult};
+use crate::transfer::{open_reader, open_writer, parse_timestamp, Progress};
+use crate::types::{
+    python_dict_to_metadata, AddResult, ExportStats, ImportStats, MemoryItem, MigrationStats,
+    SearchResult,
+};
+
+/// Memories exported between calls of an export's progress callback.
+const PROGRESS_INTERVAL: usize = 100;
 
 /// Memory class - the main interface to Rook's memory system.
 ///
@@ -264,23 +273,30 @@ impl Memory {
     ///     user_id: Optional user identifier
     ///     agent_id: Optional agent identifier
     ///     limit: Optional maximum number of results
+    ///     order_by: Optional metadata field to order by, as "field" or
+    ///         "field:desc" (created_at, updated_at, importance or a field
+    ///         declared in the metadata schema)
     ///
     /// Returns:
     ///     List of MemoryItem objects
     ///
     /// Example:
-    ///     items = memory.get_all(user_id="user123", limit=100)
-    #[pyo3(signature = (user_id=None, agent_id=None, limit=None))]
+    ///     items = memory.get_all(user_id="user123", limit=100, order_by="created_at:desc")
+    #[pyo3(signature = (user_id=None, agent_id=None, limit=None, order_by=None))]
     pub fn get_all(
         &self,
         py: Python<'_>,
         user_id: Option<String>,
         agent_id: Option<String>,
         limit: Option<usize>,
+        order_by: Option<String>,
     ) -> PyResult<Vec<MemoryItem>> {
         let result = py.allow_threads(|| {
-            self.runtime
-                .block_on(async { self.inner.get_all(user_id, agent_id, None, limit).await })
+            self.runtime.block_on(async {
+                self.inner
+                    .get_all_ordered(user_id, agent_id, None, order_by.as_deref(), limit)
+                    .await
+            })
         });
 
         match result {
@@ -320,6 +336,208 @@ impl Memory {
             )),
         }
     }
+
+    /// Export memories to JSON Lines, one memory per line.
+    ///
+    /// Args:
+    ///     dest: Path to write, or a file object opened for writing (text
+    ///         or binary)
+    ///     user_id: Optional user identifier for scoping
+    ///     agent_id: Optional agent identifier for scoping
+    ///     updated_since: Optional ISO 8601 string or timezone-aware
+    ///         datetime; only memories changed after it are exported, plus
+    ///         tombstones for memories deleted after it (requires versioning)
+    ///     progress: Optional callable, called as progress(done, total)
+    ///         with the number of memories processed
+    ///
+    /// Returns:
+    ///     ExportStats; pass its high_water_mark as updated_since for the
+    ///     next incremental export
+    ///
+    /// Example:
+    ///     stats = memory.export_jsonl("memories.jsonl", user_id="user123")
+    ///     with open("changes.jsonl", "w") as f:
+    ///         memory.export_jsonl(f, updated_since=stats.high_water_mark)
+    #[pyo3(signature = (dest, user_id=None, agent_id=None, updated_since=None, progress=None))]
+    pub fn export_jsonl(
+        &self,
+        py: Python<'_>,
+        dest: &Bound<'_, PyAny>,
+        user_id: Option<String>,
+        agent_id: Option<String>,
+        updated_since: Option<&Bound<'_, PyAny>>,
+        progress: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<ExportStats> {
+        let since = updated_since.map(parse_timestamp).transpose()?;
+        let progress = Progress::new(progress)?;
+        let mut writer = open_writer(dest)?;
+
+        let result = py.allow_threads(|| {
+            self.runtime.block_on(async {
+                let tombstones = match since {
+                    Some(since) => self.inner.tombstones_since(
+                        user_id.as_deref(),
+                        agent_id.as_deref(),
+                        None,
+                        Some(since),
+                    )?,
+                    None => Vec::new(),
+                };
+                let memories = self.inner.get_all(user_id, agent_id, None, None).await?;
+
+                let total = memories.len() as u64;
+                let memories = memories.into_iter().enumerate().map_while(|(i, memory)| {
+                    if i % PROGRESS_INTERVAL == 0 {
+                        progress.report(i as u64, Some(total));
+                    }
+                    (!progress.is_cancelled()).then_some(memory)
+                });
+                let stats = rook_core::export_jsonl_since(
+                    futures::stream::iter(memories),
+                    tombstones,
+                    since,
+                    &mut writer,
+                )
+                .await?;
+                progress.report(total, Some(total));
+                Ok::<_, rook_core::RookError>(stats)
+            })
+        });
+
+        match result {
+            Ok(stats) => progress.finish(ExportStats::from_core(stats)),
+            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                e.to_string(),
+            )),
+        }
+    }
+
+    /// Import memories from JSON Lines written by export_jsonl.
+    ///
+    /// Memories keep their IDs and timestamps and are re-embedded. Memories
+    /// whose ID already exists are skipped; malformed lines are reported in
+    /// the stats rather than aborting the import.
+    ///
+    /// Args:
+    ///     source: Path to read, or a file object opened for reading (text
+    ///         or binary)
+    ///     batch_size: Memories embedded and stored per batch (default: 100)
+    ///     dedup: Optional duplicate check: "id" (default), "hash",
+    ///         "embedding" or "smart_ingest"
+    ///     dedup_threshold: Similarity for "embedding" dedup (default: 0.95)
+    ///     progress: Optional callable, called as progress(done, None) after
+    ///         each batch with the number of memories processed
+    ///
+    /// Returns:
+    ///     ImportStats with imported, skipped and error counts
+    ///
+    /// Raises:
+    ///     ValueError: If the dedup mode or threshold is invalid
+    ///
+    /// Example:
+    ///     stats = memory.import_jsonl(
+    ///         "memories.jsonl",
+    ///         dedup="hash",
+    ///         progress=lambda done, total: print(f"{done} memories"),
+    ///     )
+    #[pyo3(signature = (source, batch_size=100, dedup=None, dedup_threshold=None, progress=None))]
+    pub fn import_jsonl(
+        &self,
+        py: Python<'_>,
+        source: &Bound<'_, PyAny>,
+        batch_size: usize,
+        dedup: Option<String>,
+        dedup_threshold: Option<f32>,
+        progress: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<ImportStats> {
+        let dedup = match dedup.as_deref() {
+            Some(mode) => ImportDedup::parse(mode, dedup_threshold)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?,
+            None => ImportDedup::Id,
+        };
+        let progress = Progress::new(progress)?;
+        let reader = tokio::io::BufReader::new(open_reader(source)?);
+        let done = AtomicU64::new(0);
+
+        let result = py.allow_threads(|| {
+            self.runtime
+                .block_on(rook_core::import_jsonl(reader, batch_size, |batch| {
+                    let (progress, done) = (&progress, &done);
+                    async move {
+                        progress.check()?;
+                        let count = batch.len() as u64;
+                        let imported = self.inner.import_batch_with(batch, dedup).await;
+                        progress.report(done.fetch_add(count, Ordering::Relaxed) + count, None);
+                        imported
+                    }
+                }))
+        });
+
+        match result {
+            Ok(stats) => progress.finish(ImportStats::from_core(stats)),
+            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                e.to_string(),
+            )),
+        }
+    }
+
+    /// Migrate memories from a mem0 JSON Lines export.
+    ///
+    /// mem0's user_id, agent_id and categories are kept in the metadata, so
+    /// migrated memories stay in their scopes. Memories whose ID already
+    /// exists are skipped, so an interrupted migration can be rerun.
+    ///
+    /// Args:
+    ///     source: Path to read, or a file object opened for reading (text
+    ///         or binary)
+    ///     batch_size: Memories embedded and stored per batch (default: 100)
+    ///     progress: Optional callable, called as progress(done, None) after
+    ///         each batch with the number of memories processed
+    ///
+    /// Returns:
+    ///     MigrationStats with migrated, skipped and error counts
+    ///
+    /// Example:
+    ///     with open("mem0_export.jsonl") as f:
+    ///         stats = memory.migrate_from_mem0(f)
+    ///     print(f"Migrated {stats.migrated}/{stats.total} memories")
+    #[pyo3(signature = (source, batch_size=100, progress=None))]
+    pub fn migrate_from_mem0(
+        &self,
+        py: Python<'_>,
+        source: &Bound<'_, PyAny>,
+        batch_size: usize,
+        progress: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<MigrationStats> {
+        let progress = Progress::new(progress)?;
+        let reader = tokio::io::BufReader::new(open_reader(source)?);
+        let done = AtomicU64::new(0);
+
+        let result = py.allow_threads(|| {
+            self.runtime
+                .block_on(rook_core::migrate_from_mem0(reader, batch_size, |batch| {
+                    let (progress, done) = (&progress, &done);
+                    async move {
+                        progress.check()?;
+                        let count = batch.len() as u64;
+                        let batch = batch
+                            .into_iter()
+                            .map(|item| ImportableMemory::from(ExportableMemory::from(item)))
+                            .collect();
+                        let migrated = self.inner.import_batch(batch).await;
+                        progress.report(done.fetch_add(count, Ordering::Relaxed) + count, None);
+                        migrated
+                    }
+                }))
+        });
+
+        match result {
+            Ok(stats) => progress.finish(MigrationStats::from_core(stats)),
+            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                e.to_string(),
+            )),
+        }
+    }
 }
 
 /// Parse Python config dict into Rust MemoryConfig.
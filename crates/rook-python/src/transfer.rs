@@ -0,0 +1,235 @@
+//! Python file objects and progress callbacks for export, import and migration.
+//!
+//! Sources and destinations are either paths or file objects. File objects
+//! are read and written in chunks, taking the GIL for each call, so large
+//! exports never have to fit in memory. Text and binary files both work.
+
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// Bytes (or characters, for text files) requested per `read()` call.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Open an import source: a path, or a file object with a `read()` method.
+pub fn open_reader(source: &Bound<'_, PyAny>) -> PyResult<Box<dyn AsyncRead + Unpin + Send>> {
+    if source.hasattr("read")? {
+        return Ok(Box::new(PyReader {
+            file: source.clone().unbind(),
+            buffer: Vec::new(),
+            pos: 0,
+        }));
+    }
+    let path: PathBuf = source.extract().map_err(|_| {
+        PyTypeError::new_err("source must be a path or a file object with a read() method")
+    })?;
+    let file = std::fs::File::open(path)?;
+    Ok(Box::new(tokio::fs::File::from_std(file)))
+}
+
+/// Open an export destination: a path, or a file object with a `write()`
+/// method. Paths are created or truncated.
+pub fn open_writer(dest: &Bound<'_, PyAny>) -> PyResult<Box<dyn AsyncWrite + Unpin + Send>> {
+    if dest.hasattr("write")? {
+        let text_io = dest.py().import("io")?.getattr("TextIOBase")?;
+        return Ok(Box::new(PyWriter {
+            file: dest.clone().unbind(),
+            text: dest.is_instance(&text_io)?,
+            pending: Vec::new(),
+        }));
+    }
+    let path: PathBuf = dest.extract().map_err(|_| {
+        PyTypeError::new_err("dest must be a path or a file object with a write() method")
+    })?;
+    let file = std::fs::File::create(path)?;
+    Ok(Box::new(tokio::fs::File::from_std(file)))
+}
+
+/// Parse a cursor given as an ISO 8601 string or a timezone-aware datetime.
+pub fn parse_timestamp(value: &Bound<'_, PyAny>) -> PyResult<DateTime<Utc>> {
+    let text: String = if value.hasattr("isoformat")? {
+        value.call_method0("isoformat")?.extract()?
+    } else {
+        value.extract()?
+    };
+    DateTime::parse_from_rfc3339(&text)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|e| PyValueError::new_err(format!("Invalid timestamp '{}': {}", text, e)))
+}
+
+/// A Python file object read as an async byte stream.
+struct PyReader {
+    file: Py<PyAny>,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl PyReader {
+    /// Replace the buffer with the next chunk; empty at end of file.
+    fn fill(&mut self) -> io::Result<()> {
+        let chunk = Python::with_gil(|py| -> PyResult<Vec<u8>> {
+            let chunk = self
+                .file
+                .bind(py)
+                .call_method1("read", (READ_CHUNK_SIZE,))?;
+            match chunk.downcast::<PyBytes>() {
+                Ok(bytes) => Ok(bytes.as_bytes().to_vec()),
+                Err(_) => Ok(chunk.extract::<String>()?.into_bytes()),
+            }
+        })?;
+        self.buffer = chunk;
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl AsyncRead for PyReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.pos == this.buffer.len() {
+            if let Err(e) = this.fill() {
+                return Poll::Ready(Err(e));
+            }
+        }
+        let n = buf.remaining().min(this.buffer.len() - this.pos);
+        buf.put_slice(&this.buffer[this.pos..this.pos + n]);
+        this.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A Python file object written as an async byte sink.
+struct PyWriter {
+    file: Py<PyAny>,
+    /// Whether the file takes `str` rather than `bytes`.
+    text: bool,
+    /// Trailing bytes of a character split across writes, for text files.
+    pending: Vec<u8>,
+}
+
+impl PyWriter {
+    fn write_chunk(&mut self, buf: &[u8]) -> io::Result<()> {
+        Python::with_gil(|py| -> PyResult<()> {
+            let file = self.file.bind(py);
+            if !self.text {
+                file.call_method1("write", (PyBytes::new(py, buf),))?;
+                return Ok(());
+            }
+            self.pending.extend_from_slice(buf);
+            let valid = match std::str::from_utf8(&self.pending) {
+                Ok(text) => text.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            if valid > 0 {
+                let text = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+                file.call_method1("write", (text,))?;
+                self.pending.drain(..valid);
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn flush_file(&self) -> io::Result<()> {
+        Python::with_gil(|py| self.file.bind(py).call_method0("flush").map(drop))?;
+        Ok(())
+    }
+}
+
+impl AsyncWrite for PyWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().write_chunk(buf).map(|()| buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.flush_file())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // The caller owns the file object and closes it
+        Poll::Ready(self.flush_file())
+    }
+}
+
+/// Reports progress to an optional Python callable as `progress(done, total)`,
+/// with `total` of `None` when it is not known up front.
+///
+/// An exception raised by the callback is kept, stops the operation at the
+/// next batch, and is raised by [`Progress::finish`].
+pub struct Progress {
+    callback: Option<Py<PyAny>>,
+    error: Mutex<Option<PyErr>>,
+}
+
+impl Progress {
+    pub fn new(callback: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let callback = match callback {
+            Some(callback) if !callback.is_none() => {
+                if !callback.is_callable() {
+                    return Err(PyTypeError::new_err("progress must be callable"));
+                }
+                Some(callback.clone().unbind())
+            }
+            _ => None,
+        };
+        Ok(Self {
+            callback,
+            error: Mutex::new(None),
+        })
+    }
+
+    /// Call the callback, unless an earlier call raised.
+    pub fn report(&self, done: u64, total: Option<u64>) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        let mut error = self.error.lock().unwrap_or_else(|e| e.into_inner());
+        if error.is_none() {
+            if let Err(e) = Python::with_gil(|py| callback.call1(py, (done, total))) {
+                *error = Some(e);
+            }
+        }
+    }
+
+    /// Whether the callback raised, so the operation should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some()
+    }
+
+    /// Error for batches skipped after the callback raised.
+    pub fn check(&self) -> rook_core::RookResult<()> {
+        if self.is_cancelled() {
+            return Err(rook_core::RookError::internal(
+                "Stopped by an exception in the progress callback",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return `value`, or raise the exception of the callback.
+    pub fn finish<T>(self, value: T) -> PyResult<T> {
+        match self.error.into_inner().unwrap_or_else(|e| e.into_inner()) {
+            Some(e) => Err(e),
+            None => Ok(value),
+        }
+    }
+}
@@ -194,6 +194,154 @@ impl AddResult {
     }
 }
 
+/// Statistics from an export.
+#[pyclass]
+#[derive(Clone)]
+pub struct ExportStats {
+    /// Memories written or attempted.
+    #[pyo3(get)]
+    pub total: u64,
+    /// Memories written successfully.
+    #[pyo3(get)]
+    pub exported: u64,
+    /// Tombstones written for memories deleted since the cursor.
+    #[pyo3(get)]
+    pub tombstones: u64,
+    /// Error messages for memories that could not be written.
+    #[pyo3(get)]
+    pub errors: Vec<String>,
+    /// Latest change covered, to pass as `updated_since` next time.
+    #[pyo3(get)]
+    pub high_water_mark: Option<String>,
+}
+
+#[pymethods]
+impl ExportStats {
+    /// Whether every memory was exported without errors.
+    fn is_success(&self) -> bool {
+        self.errors.is_empty() && self.total == self.exported
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ExportStats(exported={}, total={}, tombstones={}, errors={})",
+            self.exported,
+            self.total,
+            self.tombstones,
+            self.errors.len()
+        )
+    }
+}
+
+impl ExportStats {
+    /// Create ExportStats from rook_core::ExportStats.
+    pub fn from_core(stats: rook_core::ExportStats) -> Self {
+        Self {
+            total: stats.total,
+            exported: stats.exported,
+            tombstones: stats.tombstones,
+            errors: stats.errors,
+            high_water_mark: stats.high_water_mark.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Statistics from an import.
+#[pyclass]
+#[derive(Clone)]
+pub struct ImportStats {
+    /// Lines processed.
+    #[pyo3(get)]
+    pub total: u64,
+    /// Memories imported.
+    #[pyo3(get)]
+    pub imported: u64,
+    /// Memories skipped as duplicates.
+    #[pyo3(get)]
+    pub skipped: u64,
+    /// Error messages for lines or batches that failed.
+    #[pyo3(get)]
+    pub errors: Vec<String>,
+}
+
+#[pymethods]
+impl ImportStats {
+    /// Whether the import completed without errors.
+    fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ImportStats(imported={}, skipped={}, total={}, errors={})",
+            self.imported,
+            self.skipped,
+            self.total,
+            self.errors.len()
+        )
+    }
+}
+
+impl ImportStats {
+    /// Create ImportStats from rook_core::ImportStats.
+    pub fn from_core(stats: rook_core::ImportStats) -> Self {
+        Self {
+            total: stats.total,
+            imported: stats.imported,
+            skipped: stats.skipped,
+            errors: stats.errors,
+        }
+    }
+}
+
+/// Statistics from a migration.
+#[pyclass]
+#[derive(Clone)]
+pub struct MigrationStats {
+    /// Lines processed.
+    #[pyo3(get)]
+    pub total: u64,
+    /// Memories migrated.
+    #[pyo3(get)]
+    pub migrated: u64,
+    /// Lines skipped because they could not be parsed.
+    #[pyo3(get)]
+    pub skipped: u64,
+    /// Error messages for lines or batches that failed.
+    #[pyo3(get)]
+    pub errors: Vec<String>,
+}
+
+#[pymethods]
+impl MigrationStats {
+    /// Whether the migration completed without errors.
+    fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MigrationStats(migrated={}, skipped={}, total={}, errors={})",
+            self.migrated,
+            self.skipped,
+            self.total,
+            self.errors.len()
+        )
+    }
+}
+
+impl MigrationStats {
+    /// Create MigrationStats from rook_core::MigrationStats.
+    pub fn from_core(stats: rook_core::MigrationStats) -> Self {
+        Self {
+            total: stats.total,
+            migrated: stats.migrated,
+            skipped: stats.skipped,
+            errors: stats.errors,
+        }
+    }
+}
+
 /// Convert serde_json::Value to a Python object.
 pub fn json_to_python(py: Python<'_>, value: &serde_json::Value) -> PyResult<PyObject> {
     use pyo3::conversion::IntoPyObject;
@@ -208,3 +208,89 @@ def test_metadata_handling():
 
         # Cleanup
         memory.delete(memory_id)
+
+
+def test_transfer_stats_classes():
+    """Test export, import and migration stats classes exist."""
+    import rook_rs
+    assert hasattr(rook_rs, 'ExportStats')
+    assert hasattr(rook_rs, 'ImportStats')
+    assert hasattr(rook_rs, 'MigrationStats')
+
+
+def test_memory_transfer_methods():
+    """Test Memory exposes export, import and migration."""
+    import rook_rs
+    assert callable(getattr(rook_rs.Memory, 'export_jsonl', None))
+    assert callable(getattr(rook_rs.Memory, 'import_jsonl', None))
+    assert callable(getattr(rook_rs.Memory, 'migrate_from_mem0', None))
+
+
+@pytest.mark.skip(reason="Requires running Qdrant and OpenAI API keys")
+def test_export_import_roundtrip_integration(tmp_path):
+    """Integration test for export to a path and import from a file object."""
+    import io
+    import rook_rs
+    memory = rook_rs.Memory()
+
+    memory.add(content="Memory to export", user_id="test_user_export", infer=False)
+
+    calls = []
+    path = tmp_path / "memories.jsonl"
+    stats = memory.export_jsonl(
+        path,
+        user_id="test_user_export",
+        progress=lambda done, total: calls.append((done, total)),
+    )
+    assert stats.is_success()
+    assert stats.exported >= 1
+    assert calls[-1] == (stats.total, stats.total)
+
+    # Re-importing skips memories whose ID already exists
+    buffer = io.StringIO(path.read_text())
+    stats = memory.import_jsonl(buffer, batch_size=10)
+    assert stats.imported == 0
+    assert stats.skipped == stats.total
+
+    # Cleanup
+    memory.delete_all(user_id="test_user_export")
+
+
+@pytest.mark.skip(reason="Requires running Qdrant and OpenAI API keys")
+def test_migrate_from_mem0_integration():
+    """Integration test for migrating a mem0 export."""
+    import io
+    import rook_rs
+    memory = rook_rs.Memory()
+
+    mem0_export = io.BytesIO(
+        b'{"id": "mem0-migration-test", "memory": "Likes tea", "user_id": "test_user_mem0"}\n'
+        b'not json\n'
+    )
+    stats = memory.migrate_from_mem0(mem0_export)
+    assert stats.total == 2
+    assert stats.migrated == 1
+    assert stats.skipped == 1
+    assert not stats.is_success()
+
+    item = memory.get("mem0-migration-test")
+    assert item is not None
+    assert item.metadata["migrated_from"] == "mem0"
+
+    # Cleanup
+    memory.delete_all(user_id="test_user_mem0")
+
+
+def test_progress_callback_exception_propagates():
+    """Test an exception in the progress callback is raised to the caller."""
+    import rook_rs
+    try:
+        memory = rook_rs.Memory()
+    except RuntimeError:
+        pytest.skip("Requires running Qdrant and OpenAI API keys")
+
+    def progress(done, total):
+        raise KeyboardInterrupt
+
+    with pytest.raises(KeyboardInterrupt):
+        memory.export_jsonl("/dev/null", user_id="test_user_progress", progress=progress)
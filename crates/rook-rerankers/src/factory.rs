@@ -21,6 +21,18 @@ impl RerankerFactory {
                 Ok(Arc::new(reranker))
             }
 
+            #[cfg(feature = "jina")]
+            RerankerProvider::Jina => {
+                let reranker = crate::jina::JinaReranker::new(config)?;
+                Ok(Arc::new(reranker))
+            }
+
+            #[cfg(feature = "voyage")]
+            RerankerProvider::Voyage => {
+                let reranker = crate::voyage::VoyageReranker::new(config)?;
+                Ok(Arc::new(reranker))
+            }
+
             #[cfg(feature = "llm")]
             RerankerProvider::Llm => {
                 let reranker = crate::llm_reranker::LlmReranker::new(config)?;
@@ -45,4 +57,30 @@ impl RerankerFactory {
         let reranker = crate::cohere::CohereReranker::new(config)?;
         Ok(Arc::new(reranker))
     }
+
+    /// Create a Jina reranker.
+    #[cfg(feature = "jina")]
+    pub fn jina(api_key: &str) -> RookResult<Arc<dyn Reranker>> {
+        let config = RerankerConfig {
+            provider: RerankerProvider::Jina,
+            api_key: Some(api_key.to_string()),
+            model: "jina-reranker-v2-base-multilingual".to_string(),
+            ..Default::default()
+        };
+        let reranker = crate::jina::JinaReranker::new(config)?;
+        Ok(Arc::new(reranker))
+    }
+
+    /// Create a Voyage reranker.
+    #[cfg(feature = "voyage")]
+    pub fn voyage(api_key: &str) -> RookResult<Arc<dyn Reranker>> {
+        let config = RerankerConfig {
+            provider: RerankerProvider::Voyage,
+            api_key: Some(api_key.to_string()),
+            model: "rerank-2".to_string(),
+            ..Default::default()
+        };
+        let reranker = crate::voyage::VoyageReranker::new(config)?;
+        Ok(Arc::new(reranker))
+    }
 }
@@ -6,6 +6,8 @@
 //! # Supported Backends
 //!
 //! - **Cohere** (feature: `cohere`) - Cohere Rerank API
+//! - **Jina** (feature: `jina`) - Jina Reranker API
+//! - **Voyage** (feature: `voyage`) - Voyage AI rerank API
 //! - **LLM** (feature: `llm`) - LLM-based reranking
 
 mod factory;
@@ -13,14 +15,29 @@ mod factory;
 #[cfg(feature = "cohere")]
 mod cohere;
 
+#[cfg(feature = "jina")]
+mod jina;
+
+#[cfg(feature = "voyage")]
+mod voyage;
+
 #[cfg(feature = "llm")]
 mod llm_reranker;
 
+#[cfg(any(feature = "jina", feature = "voyage"))]
+mod normalize;
+
 pub use factory::RerankerFactory;
 
 #[cfg(feature = "cohere")]
 pub use cohere::CohereReranker;
 
+#[cfg(feature = "jina")]
+pub use jina::JinaReranker;
+
+#[cfg(feature = "voyage")]
+pub use voyage::VoyageReranker;
+
 #[cfg(feature = "llm")]
 pub use llm_reranker::LlmReranker;
 
@@ -0,0 +1,39 @@
+//! Relevance score normalization.
+//!
+//! Fusion and reranking stages expect relevance scores in 0-1. Some rerank
+//! APIs return raw logits instead, depending on the model, so a response
+//! with any score outside 0-1 is mapped through the logistic function.
+
+/// Normalize a response's relevance scores to 0-1, in place.
+///
+/// Scores already in 0-1 are kept, so probabilities pass through unchanged.
+/// Otherwise every score is treated as a logit, which keeps the order.
+pub(crate) fn normalize_scores(scores: &mut [f32]) {
+    if scores.iter().all(|s| (0.0..=1.0).contains(s)) {
+        return;
+    }
+    for score in scores.iter_mut() {
+        *score = 1.0 / (1.0 + (-*score).exp());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probabilities_pass_through() {
+        let mut scores = vec![0.92, 0.4, 0.0];
+        normalize_scores(&mut scores);
+        assert_eq!(scores, vec![0.92, 0.4, 0.0]);
+    }
+
+    #[test]
+    fn test_logits_are_squashed() {
+        let mut scores = vec![3.2, 0.5, -1.7];
+        normalize_scores(&mut scores);
+        assert!(scores.iter().all(|s| (0.0..=1.0).contains(s)));
+        assert!(scores[0] > scores[1] && scores[1] > scores[2]);
+        assert!((scores[1] - 0.6225).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,24 @@
+fn main() {
+    // The gRPC service is generated only with the `grpc` feature
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto");
+
+    // Use the vendored protoc unless one is configured
+    if std::env::var_os("PROTOC").is_none() {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+    }
+    let well_known = protoc_bin_vendored::include_path().expect("vendored protoc includes");
+
+    tonic_build::configure()
+        .compile_protos(
+            &["proto/rook/v1/memory.proto"],
+            &[std::path::PathBuf::from("proto"), well_known],
+        )
+        .expect("compile rook.v1 protos");
+}
@@ -0,0 +1,130 @@
+//! Request subject and tenant extraction for authorization hooks.
+
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, Extensions, HeaderMap},
+};
+use rook_core::authz::ApiKey;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Default header carrying the authenticated subject.
+pub const DEFAULT_SUBJECT_HEADER: &str = "x-rook-subject";
+
+/// Subject used when the request carries no subject header.
+pub const ANONYMOUS_SUBJECT: &str = "anonymous";
+
+/// The caller on whose behalf an operation runs.
+///
+/// Read from the `X-Rook-Subject` header (override the header name with
+/// `ROOK_AUTHZ_SUBJECT_HEADER`). The header is expected to be set by a
+/// trusted gateway in front of the server. Requests authenticated with a
+/// stored API key run as the key's subject (`key:<id>`) and the header is
+/// ignored.
+#[derive(Debug, Clone)]
+pub struct Subject(pub String);
+
+impl Subject {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The subject of a request with these headers and extensions.
+    pub fn resolve(headers: &HeaderMap, extensions: &Extensions) -> Self {
+        if let Some(key) = extensions.get::<ApiKey>() {
+            return Subject(key.subject());
+        }
+
+        let header = std::env::var("ROOK_AUTHZ_SUBJECT_HEADER")
+            .unwrap_or_else(|_| DEFAULT_SUBJECT_HEADER.to_string());
+
+        let subject = headers
+            .get(header.as_str())
+            .and_then(|v| v.to_str().ok())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(ANONYMOUS_SUBJECT);
+
+        Subject(subject.to_string())
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Subject
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Subject::resolve(&parts.headers, &parts.extensions))
+    }
+}
+
+/// The stored API key a request was authenticated with, if any.
+#[derive(Debug, Clone, Default)]
+pub struct CallerKey(pub Option<ApiKey>);
+
+impl CallerKey {
+    /// Whether the caller may only see aggregated analytics.
+    pub fn is_aggregate_only(&self) -> bool {
+        self.0.as_ref().is_some_and(|key| key.role.is_aggregate_only())
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CallerKey
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(CallerKey(parts.extensions.get::<ApiKey>().cloned()))
+    }
+}
+
+/// The organization a request runs for, from its API key.
+///
+/// `None` means the server's own memory instance.
+#[derive(Debug, Clone, Default)]
+pub struct Tenant(pub Option<String>);
+
+impl Tenant {
+    pub fn org_id(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    /// The tenant of a request with these extensions.
+    pub fn resolve(extensions: &Extensions) -> Self {
+        Tenant(
+            extensions
+                .get::<ApiKey>()
+                .and_then(|key| key.org_id.clone()),
+        )
+    }
+
+    /// Reject organization requests to state that is shared by the whole
+    /// server rather than isolated per organization.
+    pub fn require_shared(&self) -> ApiResult<()> {
+        match self.0 {
+            Some(ref org_id) => Err(ApiError::forbidden(format!(
+                "Not available to organization '{}': this data is shared across organizations",
+                org_id
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Tenant
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Tenant::resolve(&parts.extensions))
+    }
+}
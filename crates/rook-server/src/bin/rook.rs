@@ -0,0 +1,226 @@
+//! rook - command-line tool for operating rook servers.
+//!
+//! ```text
+//! rook sync <remote> [--local <url>]
+//! rook backup <archive> [--data-dir <dir>]
+//! rook restore <archive> [--data-dir <dir>] [--overwrite]
+//! ```
+//!
+//! `--local` defaults to `ROOK_URL` or `http://localhost:8080`. `ROOK_API_KEY`
+//! is sent as a bearer token and `ROOK_SUBJECT` as the subject header.
+//!
+//! `backup` and `restore` work on the data directory directly, `--data-dir`
+//! defaulting to `ROOK_DATA_DIR`. Stop the server before restoring.
+
+use std::path::PathBuf;
+
+use rook_core::backup::{self, RestoreOptions};
+use rook_core::sync::{Changeset, SyncReport, SyncStatus};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Changes requested per page.
+const PAGE_SIZE: usize = 100;
+
+const USAGE: &str = "usage: rook sync <remote> [--local <url>]
+       rook backup <archive> [--data-dir <dir>]
+       rook restore <archive> [--data-dir <dir>] [--overwrite]";
+
+/// HTTP client for one rook server.
+struct Server {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl Server {
+    fn new(client: reqwest::Client, url: &str) -> Self {
+        Self {
+            client,
+            base_url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path));
+        if let Ok(key) = std::env::var("ROOK_API_KEY") {
+            request = request.bearer_auth(key);
+        }
+        if let Ok(subject) = std::env::var("ROOK_SUBJECT") {
+            request = request.header("X-Rook-Subject", subject);
+        }
+        request
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> CliResult<T> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("{} returned {}: {}", self.base_url, status, body).into());
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn status(&self) -> CliResult<SyncStatus> {
+        self.send(self.request(reqwest::Method::GET, "/sync/status")).await
+    }
+
+    async fn pull(&self, since: u64) -> CliResult<Changeset> {
+        let request = self
+            .request(reqwest::Method::GET, "/sync/changes")
+            .query(&[("since", since.to_string()), ("limit", PAGE_SIZE.to_string())]);
+        self.send(request).await
+    }
+
+    async fn push(&self, changeset: &Changeset) -> CliResult<SyncReport> {
+        let request = self.request(reqwest::Method::POST, "/sync/changes").json(changeset);
+        self.send(request).await
+    }
+}
+
+/// Copy every change `from` has made since `to` last heard from it.
+async fn transfer(from: &Server, to: &Server) -> CliResult<SyncReport> {
+    let from_status = from.status().await?;
+    let to_status = to.status().await?;
+    let mut since = to_status.peers.get(&from_status.node_id).copied().unwrap_or(0);
+
+    let mut total = SyncReport::default();
+    loop {
+        let changeset = from.pull(since).await?;
+        let report = to.push(&changeset).await?;
+        total.applied += report.applied;
+        total.skipped += report.skipped;
+        total.conflicts += report.conflicts;
+
+        if !changeset.has_more || changeset.next_seq == since {
+            break;
+        }
+        since = changeset.next_seq;
+    }
+    Ok(total)
+}
+
+async fn sync(remote_url: &str, local_url: &str) -> CliResult<()> {
+    let client = reqwest::Client::new();
+    let local = Server::new(client.clone(), local_url);
+    let remote = Server::new(client, remote_url);
+
+    let pulled = transfer(&remote, &local).await?;
+    println!(
+        "pulled from {}: {} applied, {} skipped, {} conflicts",
+        remote.base_url, pulled.applied, pulled.skipped, pulled.conflicts
+    );
+
+    let pushed = transfer(&local, &remote).await?;
+    println!(
+        "pushed to {}: {} applied, {} skipped, {} conflicts",
+        remote.base_url, pushed.applied, pushed.skipped, pushed.conflicts
+    );
+    Ok(())
+}
+
+fn create_backup(archive: &str, data_dir: PathBuf) -> CliResult<()> {
+    let manifest = backup::create_snapshot(&data_dir, archive)?;
+    println!(
+        "backed up {} to {}: {} files, {} bytes",
+        data_dir.display(),
+        archive,
+        manifest.entries.len(),
+        manifest.total_bytes()
+    );
+    Ok(())
+}
+
+fn restore_backup(archive: &str, data_dir: PathBuf, options: RestoreOptions) -> CliResult<()> {
+    let report = backup::restore_snapshot(archive, &data_dir, &options)?;
+    println!(
+        "restored {} files from {} (created {}) to {}",
+        report.manifest.entries.len(),
+        archive,
+        report.manifest.created_at.to_rfc3339(),
+        data_dir.display()
+    );
+    if let Some(previous) = report.previous {
+        println!("previous contents moved to {}", previous.display());
+    }
+    Ok(())
+}
+
+/// Parse `<archive> [--data-dir <dir>] [--overwrite]`.
+fn snapshot_args(args: &[String], allow_overwrite: bool) -> (String, PathBuf, RestoreOptions) {
+    let mut archive = None;
+    let mut data_dir = std::env::var("ROOK_DATA_DIR").ok().map(PathBuf::from);
+    let mut options = RestoreOptions::default();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--data-dir" => match rest.next() {
+                Some(dir) => data_dir = Some(PathBuf::from(dir)),
+                None => exit_usage(),
+            },
+            "--overwrite" if allow_overwrite => options = options.overwrite(),
+            path if archive.is_none() && !path.starts_with("--") => {
+                archive = Some(path.to_string())
+            }
+            _ => exit_usage(),
+        }
+    }
+    match (archive, data_dir) {
+        (Some(archive), Some(data_dir)) => (archive, data_dir, options),
+        _ => exit_usage(),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("sync") => {
+            let mut remote = None;
+            let mut local = std::env::var("ROOK_URL")
+                .unwrap_or_else(|_| "http://localhost:8080".to_string());
+            let mut rest = args[1..].iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--local" => match rest.next() {
+                        Some(url) => local = url.clone(),
+                        None => exit_usage(),
+                    },
+                    url if remote.is_none() && !url.starts_with("--") => {
+                        remote = Some(url.to_string())
+                    }
+                    _ => exit_usage(),
+                }
+            }
+            match remote {
+                Some(remote) => sync(&remote, &local).await,
+                None => exit_usage(),
+            }
+        }
+        Some("backup") => {
+            let (archive, data_dir, _) = snapshot_args(&args[1..], false);
+            create_backup(&archive, data_dir)
+        }
+        Some("restore") => {
+            let (archive, data_dir, options) = snapshot_args(&args[1..], true);
+            restore_backup(&archive, data_dir, options)
+        }
+        _ => exit_usage(),
+    };
+
+    if let Err(e) = result {
+        eprintln!("rook: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn exit_usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
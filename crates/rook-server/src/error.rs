@@ -7,6 +7,7 @@ use axum::{
 };
 use serde::Serialize;
 use std::fmt;
+use utoipa::ToSchema;
 
 /// API error type.
 #[derive(Debug)]
@@ -45,6 +46,10 @@ impl ApiError {
         Self::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", message)
     }
 
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "FORBIDDEN", message)
+    }
+
     pub fn internal(message: impl Into<String>) -> Self {
         Self::new(StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", message)
     }
@@ -71,12 +76,12 @@ impl fmt::Display for ApiError {
 impl std::error::Error for ApiError {}
 
 /// Error response body.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: ErrorBody,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorBody {
     pub code: String,
     pub message: String,
@@ -101,10 +106,15 @@ impl IntoResponse for ApiError {
 // Convert from rook-core errors
 impl From<rook_core::error::RookError> for ApiError {
     fn from(err: rook_core::error::RookError) -> Self {
-        use rook_core::error::RookError;
+        use rook_core::error::{ErrorCode, RookError};
 
         match err {
             RookError::Configuration(msg) => ApiError::bad_request(msg),
+            RookError::Authentication {
+                message,
+                code: ErrorCode::AuthForbidden,
+                ..
+            } => ApiError::forbidden(message),
             RookError::Authentication { message, .. } => ApiError::unauthorized(message),
             RookError::NotFound { message, .. } => ApiError::not_found(message),
             RookError::Validation { message, .. } => ApiError::validation(message),
@@ -5,9 +5,10 @@ use std::sync::Arc;
 use rook_core::config::{LlmProvider, MemoryConfig};
 use rook_core::error::{RookError, RookResult};
 use rook_core::memory::Memory;
+use rook_core::retrieval::ActivationGraph;
 use rook_core::traits::{
     Embedder, EmbedderProvider, GraphStore, GraphStoreConfig, GraphStoreProvider, Llm, Reranker,
-    RerankerConfig, RerankerProvider, VectorStore, VectorStoreProvider,
+    RerankerConfig, RerankerProvider, VectorStore, VectorStoreConfig, VectorStoreProvider,
 };
 
 use rook_embeddings::{OllamaEmbedder, OpenAIEmbedder};
@@ -29,13 +30,20 @@ pub async fn create_memory(config: MemoryConfig) -> RookResult<Memory> {
     let embedder = create_embedder(&config)?;
 
     // Create vector store
-    let vector_store = create_vector_store(&config).await?;
+    let vector_store = create_vector_store(&config.vector_store).await?;
 
-    // Create graph store (optional)
-    let graph_store = if let Some(ref gs_config) = config.graph_store {
-        Some(create_graph_store(gs_config).await?)
+    // Create archive store (optional)
+    let archive_store = match config.archive_vector_store() {
+        Some(ref archive) => Some(create_vector_store(archive).await?),
+        None => None,
+    };
+
+    // Create graph store (optional), which may also carry spreading activation
+    let (graph_store, activation_graph) = if let Some(ref gs_config) = config.graph_store {
+        let (store, activation) = create_graph_store(gs_config).await?;
+        (Some(store), activation)
     } else {
-        None
+        (None, None)
     };
 
     // Create reranker (optional)
@@ -45,7 +53,14 @@ pub async fn create_memory(config: MemoryConfig) -> RookResult<Memory> {
         None
     };
 
-    Memory::new(config, llm, embedder, vector_store, graph_store, reranker)
+    let mut memory = Memory::new(config, llm, embedder, vector_store, graph_store, reranker)?;
+    if let Some(graph) = activation_graph {
+        memory = memory.with_activation_graph(graph);
+    }
+    match archive_store {
+        Some(store) => memory.with_archive_store(store),
+        None => Ok(memory),
+    }
 }
 
 fn create_llm(config: &MemoryConfig) -> RookResult<Arc<dyn Llm>> {
@@ -86,8 +101,7 @@ fn create_embedder(config: &MemoryConfig) -> RookResult<Arc<dyn Embedder>> {
     }
 }
 
-async fn create_vector_store(config: &MemoryConfig) -> RookResult<Arc<dyn VectorStore>> {
-    let vs_config = &config.vector_store;
+async fn create_vector_store(vs_config: &VectorStoreConfig) -> RookResult<Arc<dyn VectorStore>> {
 
     match vs_config.provider {
         VectorStoreProvider::Qdrant => {
@@ -102,11 +116,13 @@ async fn create_vector_store(config: &MemoryConfig) -> RookResult<Arc<dyn Vector
     }
 }
 
-async fn create_graph_store(config: &GraphStoreConfig) -> RookResult<Arc<dyn GraphStore>> {
+async fn create_graph_store(
+    config: &GraphStoreConfig,
+) -> RookResult<(Arc<dyn GraphStore>, Option<Arc<dyn ActivationGraph>>)> {
     match config.provider {
         GraphStoreProvider::Embedded => {
-            let store = EmbeddedGraphStore::from_config(config).await?;
-            Ok(Arc::new(store))
+            let store = Arc::new(EmbeddedGraphStore::from_config(config).await?);
+            Ok((store.clone(), Some(store)))
         }
         _ => Err(RookError::Configuration(format!(
             "Graph store provider {:?} is not enabled. Enable the corresponding feature.",
@@ -121,6 +137,16 @@ fn create_reranker(config: &RerankerConfig) -> RookResult<Arc<dyn Reranker>> {
             let reranker = CohereReranker::new(config.clone())?;
             Ok(Arc::new(reranker))
         }
+        #[cfg(feature = "jina")]
+        RerankerProvider::Jina => {
+            let reranker = rook_rerankers::JinaReranker::new(config.clone())?;
+            Ok(Arc::new(reranker))
+        }
+        #[cfg(feature = "voyage")]
+        RerankerProvider::Voyage => {
+            let reranker = rook_rerankers::VoyageReranker::new(config.clone())?;
+            Ok(Arc::new(reranker))
+        }
         // LLM reranker requires the llm feature
         _ => Err(RookError::Configuration(format!(
             "Unsupported reranker provider: {:?}",
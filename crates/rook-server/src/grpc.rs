@@ -0,0 +1,418 @@
+//! gRPC MemoryService, served alongside the REST API.
+//!
+//! The service is defined in `proto/rook/v1/memory.proto`. Each RPC mirrors
+//! the REST endpoint of the same name and shares its [`AppState`]: API keys,
+//! authorization hooks and per-organization memories apply the same way.
+//! Subjects and API keys are read from request metadata exactly as from HTTP
+//! headers (`authorization`, `x-rook-subject`).
+
+use std::collections::HashMap;
+use std::pin::Pin;
+
+use axum::http::StatusCode;
+use axum::{middleware as axum_middleware, Router};
+use prost_types::value::Kind;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::middleware;
+use crate::routes::{
+    audit_ingest, audit_results, authorize_memory, decision_to_string, layer_to_string,
+};
+use crate::state::{AppState, MemoryRef};
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::types::{MemoryEvent, MemoryItem, MemoryResult as CoreMemoryResult};
+
+/// Types generated from the `rook.v1` protos.
+pub mod proto {
+    tonic::include_proto!("rook.v1");
+}
+
+use proto::memory_service_server::{MemoryService, MemoryServiceServer};
+
+/// Create the gRPC server.
+///
+/// Requests pass through the same authentication middleware as the REST
+/// API, which is a no-op unless `ROOK_REQUIRE_AUTH` is set.
+pub fn create_grpc_server(state: AppState) -> Router {
+    tonic::service::Routes::new(MemoryServiceServer::new(GrpcMemoryService::new(state.clone())))
+        .into_axum_router()
+        .layer(axum_middleware::from_fn_with_state(
+            state,
+            middleware::auth_middleware,
+        ))
+}
+
+/// [`MemoryService`] backed by the server's [`AppState`].
+#[derive(Clone)]
+pub struct GrpcMemoryService {
+    state: AppState,
+}
+
+impl GrpcMemoryService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    async fn require_configured(&self) -> Result<(), Status> {
+        if !self.state.is_configured().await {
+            return Err(Status::failed_precondition(
+                "Memory not configured. Call /configure first.",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn memory(&self, tenant: &Tenant) -> ApiResult<MemoryRef> {
+        self.state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))
+    }
+}
+
+/// The subject and tenant a gRPC request runs as.
+fn caller<T>(request: &Request<T>) -> (Subject, Tenant) {
+    let headers = request.metadata().clone().into_headers();
+    (
+        Subject::resolve(&headers, request.extensions()),
+        Tenant::resolve(request.extensions()),
+    )
+}
+
+type ExportStream = Pin<Box<dyn Stream<Item = Result<proto::Memory, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl MemoryService for GrpcMemoryService {
+    async fn add(
+        &self,
+        request: Request<proto::AddRequest>,
+    ) -> Result<Response<proto::AddResponse>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let request = request.into_inner();
+
+        let messages = request
+            .messages
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let metadata = request.metadata.map(struct_to_map);
+
+        self.state
+            .authorize(
+                AuthzRequest::new(subject.as_str(), AuthzAction::Add)
+                    .with_scope(
+                        request.user_id.clone(),
+                        request.agent_id.clone(),
+                        request.run_id.clone(),
+                    )
+                    .with_metadata(metadata.clone().unwrap_or_default()),
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let memory = self.memory(&tenant).await?;
+        let result = memory
+            .add(
+                messages,
+                request.user_id,
+                request.agent_id,
+                request.run_id,
+                metadata,
+                request.infer.unwrap_or(true),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+        audit_results(&self.state, &subject, &tenant, &result.results);
+
+        Ok(Response::new(proto::AddResponse {
+            results: result.results.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn search(
+        &self,
+        request: Request<proto::SearchRequest>,
+    ) -> Result<Response<proto::SearchResponse>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let request = request.into_inner();
+        let filters = request.filters.map(struct_to_map);
+
+        self.state
+            .authorize(
+                AuthzRequest::new(subject.0, AuthzAction::Search)
+                    .with_scope(
+                        request.user_id.clone(),
+                        request.agent_id.clone(),
+                        request.run_id.clone(),
+                    )
+                    .with_metadata(filters.clone().unwrap_or_default()),
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let memory = self.memory(&tenant).await?;
+        let results = memory
+            .search(
+                &request.query,
+                request.user_id,
+                request.agent_id,
+                request.run_id,
+                request.limit.map_or(10, |limit| limit as usize),
+                filters,
+                request.threshold,
+                request.rerank,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(Response::new(proto::SearchResponse {
+            results: results.results.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get(
+        &self,
+        request: Request<proto::GetRequest>,
+    ) -> Result<Response<proto::Memory>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let memory_id = request.into_inner().id;
+
+        let memory = self.memory(&tenant).await?;
+        let item = memory
+            .get(&memory_id)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| {
+                ApiError::not_found(format!("Memory with id '{}' not found", memory_id))
+            })?;
+        self.state
+            .authorize(
+                AuthzRequest::new(subject.0, AuthzAction::Get)
+                    .with_memory_id(&memory_id)
+                    .with_metadata(item.metadata.clone().unwrap_or_default()),
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(Response::new(item.into()))
+    }
+
+    async fn delete(
+        &self,
+        request: Request<proto::DeleteRequest>,
+    ) -> Result<Response<proto::DeleteResponse>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let memory_id = request.into_inner().id;
+
+        let memory = self.memory(&tenant).await?;
+        let previous =
+            authorize_memory(&self.state, &memory, subject.clone(), AuthzAction::Delete, &memory_id)
+                .await?;
+        memory.delete(&memory_id).await.map_err(ApiError::from)?;
+        self.state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::Delete, "memory")
+                .with_resource_id(&memory_id)
+                .with_org_id(tenant.0.clone())
+                .with_content(previous.as_ref().map(|item| item.memory.as_str()), None),
+        );
+
+        Ok(Response::new(proto::DeleteResponse {}))
+    }
+
+    async fn smart_ingest(
+        &self,
+        request: Request<proto::SmartIngestRequest>,
+    ) -> Result<Response<proto::SmartIngestResponse>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let request = request.into_inner();
+        if request.content.trim().is_empty() {
+            return Err(Status::invalid_argument("content must not be empty"));
+        }
+        let metadata = request.metadata.map(struct_to_map);
+
+        self.state
+            .authorize(
+                AuthzRequest::new(subject.as_str(), AuthzAction::Add)
+                    .with_scope(
+                        request.user_id.clone(),
+                        request.agent_id.clone(),
+                        request.run_id.clone(),
+                    )
+                    .with_metadata(metadata.clone().unwrap_or_default()),
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let memory = self.memory(&tenant).await?;
+        let result = memory
+            .smart_ingest(
+                &request.content,
+                request.user_id,
+                request.agent_id,
+                request.run_id,
+                metadata,
+            )
+            .await
+            .map_err(ApiError::from)?;
+        audit_ingest(&self.state, &subject, &tenant, &request.content, &result);
+
+        Ok(Response::new(proto::SmartIngestResponse {
+            decision: decision_to_string(result.decision),
+            surprise: result.surprise,
+            layer: layer_to_string(result.decided_at_layer),
+            memory_id: result.memory_id,
+            related_memory_id: result.related_memory_id,
+            previous_content: result.previous_content,
+            reason: result.reason,
+        }))
+    }
+
+    type ExportStream = ExportStream;
+
+    async fn export(
+        &self,
+        request: Request<proto::ExportRequest>,
+    ) -> Result<Response<Self::ExportStream>, Status> {
+        self.require_configured().await?;
+        let (subject, tenant) = caller(&request);
+        let request = request.into_inner();
+
+        self.state
+            .authorize(AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+                request.user_id.clone(),
+                request.agent_id.clone(),
+                request.run_id.clone(),
+            ))
+            .await
+            .map_err(ApiError::from)?;
+
+        let memory = self.memory(&tenant).await?;
+        let memories = memory
+            .get_all(request.user_id, request.agent_id, request.run_id, None)
+            .await
+            .map_err(ApiError::from)?;
+
+        let stream = tokio_stream::iter(memories.into_iter().map(proto::Memory::from).map(Ok));
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+impl From<ApiError> for Status {
+    fn from(err: ApiError) -> Self {
+        let message = err.message;
+        match err.status {
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                Status::invalid_argument(message)
+            }
+            StatusCode::UNAUTHORIZED => Status::unauthenticated(message),
+            StatusCode::FORBIDDEN => Status::permission_denied(message),
+            StatusCode::NOT_FOUND => Status::not_found(message),
+            StatusCode::CONFLICT => Status::aborted(message),
+            StatusCode::TOO_MANY_REQUESTS => Status::resource_exhausted(message),
+            StatusCode::SERVICE_UNAVAILABLE => Status::unavailable(message),
+            _ => Status::internal(message),
+        }
+    }
+}
+
+impl From<MemoryItem> for proto::Memory {
+    fn from(item: MemoryItem) -> Self {
+        Self {
+            id: item.id,
+            memory: item.memory,
+            score: item.score,
+            metadata: item.metadata.map(map_to_struct),
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+            category: item.category,
+            is_key: item.is_key,
+            pinned: item.pinned,
+        }
+    }
+}
+
+impl From<CoreMemoryResult> for proto::MemoryResult {
+    fn from(r: CoreMemoryResult) -> Self {
+        Self {
+            id: r.id,
+            memory: r.memory,
+            event: match r.event {
+                MemoryEvent::Add => "ADD",
+                MemoryEvent::Update => "UPDATE",
+                MemoryEvent::Delete => "DELETE",
+                MemoryEvent::None => "NONE",
+            }
+            .to_string(),
+            previous_memory: r.previous_memory,
+        }
+    }
+}
+
+fn struct_to_map(value: prost_types::Struct) -> HashMap<String, serde_json::Value> {
+    value
+        .fields
+        .into_iter()
+        .map(|(key, value)| (key, value_to_json(value)))
+        .collect()
+}
+
+fn map_to_struct(map: HashMap<String, serde_json::Value>) -> prost_types::Struct {
+    prost_types::Struct {
+        fields: map
+            .into_iter()
+            .map(|(key, value)| (key, json_to_value(value)))
+            .collect(),
+    }
+}
+
+fn value_to_json(value: prost_types::Value) -> serde_json::Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+        Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+        // Protobuf numbers are doubles; keep whole numbers integral
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            serde_json::Value::from(n as i64)
+        }
+        Some(Kind::NumberValue(n)) => serde_json::Value::from(n),
+        Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+        Some(Kind::ListValue(list)) => {
+            serde_json::Value::Array(list.values.into_iter().map(value_to_json).collect())
+        }
+        Some(Kind::StructValue(s)) => serde_json::Value::Object(
+            s.fields
+                .into_iter()
+                .map(|(key, value)| (key, value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn json_to_value(value: serde_json::Value) -> prost_types::Value {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(0),
+        serde_json::Value::Bool(b) => Kind::BoolValue(b),
+        serde_json::Value::Number(n) => Kind::NumberValue(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => Kind::StringValue(s),
+        serde_json::Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.into_iter().map(json_to_value).collect(),
+        }),
+        serde_json::Value::Object(fields) => Kind::StructValue(prost_types::Struct {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| (key, json_to_value(value)))
+                .collect(),
+        }),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
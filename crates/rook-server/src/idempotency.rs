@@ -0,0 +1,164 @@
+//! Replay protection for writes sent with an `Idempotency-Key` header.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use axum::http::StatusCode;
+
+/// Header naming the idempotency key of a request.
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+/// How long a completed write is remembered.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A successful response kept for replay.
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub status: StatusCode,
+    pub content_type: Option<String>,
+    pub body: Bytes,
+}
+
+/// Outcome of [`IdempotencyCache::begin`].
+#[derive(Debug)]
+pub enum Begin {
+    /// The key is new; the request should run and be completed or abandoned.
+    Run,
+    /// The same request already succeeded; send its response again.
+    Replay(StoredResponse),
+    /// The same request is still running.
+    InFlight,
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+enum Entry {
+    InFlight {
+        fingerprint: u64,
+    },
+    Done {
+        fingerprint: u64,
+        response: StoredResponse,
+        at: Instant,
+    },
+}
+
+/// Responses of writes by idempotency key.
+///
+/// Keys are scoped by the caller, so two clients picking the same key don't
+/// see each other's responses. Only successful responses are kept: a failed
+/// write can be retried with the same key.
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+impl IdempotencyCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// Claim `key` for a request with the given body.
+    pub fn begin(&self, key: &str, body: &[u8]) -> Begin {
+        let fingerprint = fingerprint(body);
+        let mut entries = self.entries.lock().unwrap();
+        let ttl = self.ttl;
+        entries.retain(|_, entry| match entry {
+            Entry::InFlight { .. } => true,
+            Entry::Done { at, .. } => at.elapsed() < ttl,
+        });
+        match entries.get(key) {
+            Some(Entry::InFlight { fingerprint: f }) if *f == fingerprint => Begin::InFlight,
+            Some(Entry::Done {
+                fingerprint: f,
+                response,
+                ..
+            }) if *f == fingerprint => Begin::Replay(response.clone()),
+            Some(_) => Begin::Mismatch,
+            None => {
+                entries.insert(key.to_string(), Entry::InFlight { fingerprint });
+                Begin::Run
+            }
+        }
+    }
+
+    /// Keep the successful response of a request begun with `key`.
+    pub fn complete(&self, key: &str, response: StoredResponse) {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(Entry::InFlight { fingerprint }) = entries.get(key) {
+            let fingerprint = *fingerprint;
+            entries.insert(
+                key.to_string(),
+                Entry::Done {
+                    fingerprint,
+                    response,
+                    at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Release `key` after the request failed, so it can be retried.
+    pub fn abandon(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        if matches!(entries.get(key), Some(Entry::InFlight { .. })) {
+            entries.remove(key);
+        }
+    }
+}
+
+fn fingerprint(body: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> StoredResponse {
+        StoredResponse {
+            status: StatusCode::OK,
+            content_type: None,
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_completed_request_is_replayed() {
+        let cache = IdempotencyCache::default();
+        assert!(matches!(cache.begin("k", b"add"), Begin::Run));
+        assert!(matches!(cache.begin("k", b"add"), Begin::InFlight));
+        cache.complete("k", response("done"));
+
+        match cache.begin("k", b"add") {
+            Begin::Replay(stored) => assert_eq!(stored.body, "done"),
+            other => panic!("expected a replay, got {other:?}"),
+        }
+        assert!(matches!(cache.begin("k", b"other"), Begin::Mismatch));
+    }
+
+    #[test]
+    fn test_abandoned_and_expired_keys_run_again() {
+        let cache = IdempotencyCache::new(Duration::ZERO);
+        assert!(matches!(cache.begin("k", b"add"), Begin::Run));
+        cache.abandon("k");
+        assert!(matches!(cache.begin("k", b"add"), Begin::Run));
+        cache.complete("k", response("done"));
+        assert!(matches!(cache.begin("k", b"add"), Begin::Run));
+    }
+}
@@ -17,11 +17,18 @@
 //! }
 //! ```
 
+pub mod authz;
 pub mod error;
 pub mod factory;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod idempotency;
 pub mod middleware;
+pub mod openapi;
+pub mod rate_limit;
 pub mod routes;
 pub mod state;
+pub mod tenants;
 
 pub use error::{ApiError, ApiResult};
 pub use factory::create_memory;
@@ -33,16 +40,23 @@ use tower_http::trace::TraceLayer;
 /// Create the server with all routes and middleware.
 pub fn create_server(state: AppState) -> Router {
     routes::create_router(state)
-        .layer(TraceLayer::new_for_http())
+        .layer(TraceLayer::new_for_http().make_span_with(middleware::make_span))
         .layer(middleware::cors_layer())
         .layer(axum_middleware::from_fn(middleware::logging_middleware))
+        .layer(axum_middleware::from_fn(middleware::sampling_middleware))
+        .layer(axum_middleware::from_fn(middleware::metrics_middleware))
 }
 
 /// Create the server with authentication middleware.
 pub fn create_server_with_auth(state: AppState) -> Router {
-    routes::create_router(state)
-        .layer(TraceLayer::new_for_http())
+    routes::create_router(state.clone())
+        .layer(TraceLayer::new_for_http().make_span_with(middleware::make_span))
         .layer(middleware::cors_layer())
-        .layer(axum_middleware::from_fn(middleware::auth_middleware))
+        .layer(axum_middleware::from_fn_with_state(
+            state,
+            middleware::auth_middleware,
+        ))
         .layer(axum_middleware::from_fn(middleware::logging_middleware))
+        .layer(axum_middleware::from_fn(middleware::sampling_middleware))
+        .layer(axum_middleware::from_fn(middleware::metrics_middleware))
 }
@@ -1,13 +1,75 @@
 //! rook-server - REST API server binary.
 
 use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
-use rook_core::{BackgroundRuntime, RuntimeConfig};
+use rook_core::analytics::AggregationPolicy;
+use rook_core::events::{DiskSpaceConfig, DiskSpaceMonitor, WebhookDeliveryStore, WebhookManager};
+use rook_core::intentions::{ActionExecutor, ActionResult, FiredIntentionReceiver};
+use rook_core::observability::metrics;
+use rook_core::retrieval::TantivySearcher;
+use rook_core::{
+    AlertSubscriber, AlertSubscriberConfig, ApiKeyAuthorizer, ApiKeyStore, AuditLog, AuthzConfig,
+    BackgroundRuntime, DataDirConfig, DataDirMonitor, EventBus, RuntimeConfig,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use rook_server::tenants::TenantConfig;
 use rook_server::{create_server, create_server_with_auth, AppState};
 use tokio::signal;
-use tracing::{info, Level};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn, Level};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+/// Execute the actions of time-based intentions as they fire and record the
+/// fires for `GET /intentions/fired`.
+///
+/// Fires of intentions that were deactivated, expired or used up are dropped
+/// and their schedule removed.
+fn record_fired_intentions(
+    mut fired_rx: FiredIntentionReceiver,
+    runtime: Arc<RwLock<BackgroundRuntime>>,
+) {
+    tokio::spawn(async move {
+        let surfaced = runtime.read().await.surfaced_memories();
+        let executor = ActionExecutor::new().with_surfaced_memories(surfaced);
+        while let Some(mut fired) = fired_rx.recv().await {
+            let loaded = runtime.read().await.intention_store().get(fired.intention_id);
+            let exhausted = match loaded {
+                Ok(Some(mut intention)) if intention.can_fire() => {
+                    // Run the action without holding the runtime, as webhook
+                    // retries can take a while
+                    fired.action_result = executor.execute(&intention, &fired.reason).await;
+                    if let ActionResult::Failed { ref error } = fired.action_result {
+                        warn!("Action of intention {} failed: {}", intention.id, error);
+                    }
+
+                    let store = runtime.read().await.intention_store();
+                    if let Err(e) = store.record_fire(intention.id, &fired) {
+                        error!("Failed to record fire of intention {}: {}", intention.id, e);
+                    }
+                    intention.fire_count += 1;
+                    !intention.can_fire()
+                }
+                Ok(_) => true,
+                Err(e) => {
+                    error!("Failed to load intention {}: {}", fired.intention_id, e);
+                    false
+                }
+            };
+            if exhausted {
+                let runtime = runtime.read().await;
+                if let Some(scheduler) = runtime.intention_scheduler() {
+                    if let Err(e) = scheduler.unschedule(fired.intention_id).await {
+                        error!("Failed to unschedule intention {}: {}", fired.intention_id, e);
+                    }
+                }
+            }
+        }
+    });
+}
+
 /// Wait for shutdown signal (Ctrl+C or SIGTERM).
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -56,20 +118,147 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("ROOK_PORT must be a valid port number");
     let require_auth = std::env::var("ROOK_REQUIRE_AUTH").is_ok();
 
+    // Prometheus recorder for /metrics
+    let metrics = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Suffix("_seconds".to_string()),
+            metrics::LATENCY_BUCKETS,
+        )?
+        .install_recorder()?;
+    metrics::describe();
+
+    // Event bus shared by memory, alerts and webhooks
+    let event_bus = EventBus::new();
+
+    // Operational alerts (Slack or generic webhook URL, if configured)
+    if let Some(alert_config) = AlertSubscriberConfig::from_env() {
+        info!("Forwarding alerts to {}", alert_config.url);
+        AlertSubscriber::new(alert_config).start(&event_bus);
+        if let Some(disk_config) = DiskSpaceConfig::from_env() {
+            info!("Watching disk space for {}", disk_config.path.display());
+            DiskSpaceMonitor::new(disk_config, event_bus.clone()).start();
+        }
+    }
+
+    // Webhooks registered through /webhooks, kept in SQLite
+    let webhook_db = std::env::var("ROOK_WEBHOOK_DB_PATH").unwrap_or_else(|_| ":memory:".into());
+    let webhooks = Arc::new(
+        WebhookManager::new(event_bus.clone())
+            .with_store(Arc::new(WebhookDeliveryStore::new(&webhook_db)?)),
+    );
+    let loaded = webhooks.load_webhooks().await?;
+    info!("Webhook store: {} ({} webhooks)", webhook_db, loaded);
+    webhooks.start();
+
+    // Data directory budget (usage is reported by /stats even without a budget)
+    let storage_monitor = DataDirConfig::from_env().map(|config| {
+        let monitor = DataDirMonitor::new(config)
+            .with_default_jobs()
+            .with_event_bus(event_bus.clone());
+        let monitor = Arc::new(monitor);
+        if let Some(budget) = monitor.config().budget_bytes {
+            info!(
+                "Data directory budget: {} bytes for {}",
+                budget,
+                monitor.config().path.display()
+            );
+            monitor.clone().start();
+        }
+        monitor
+    });
+
     // Create BackgroundRuntime with config from environment
     let runtime_config = RuntimeConfig::from_env();
-    let runtime = BackgroundRuntime::new(runtime_config).await?;
+    let mut runtime = BackgroundRuntime::new(runtime_config)
+        .await?
+        .with_event_bus(event_bus.clone());
+
+    let fired_intentions_rx = runtime.take_fired_intentions_rx();
 
     // Start background schedulers
     runtime.start().await?;
     info!("Background schedulers started (consolidation + intentions)");
 
+    // Authorization hook (policy file or OPA endpoint, if configured)
+    let authz_config = AuthzConfig::from_env();
+    let authorizer = authz_config.build()?;
+    match &authz_config {
+        AuthzConfig::None => info!("Authorization hooks disabled"),
+        AuthzConfig::Static { path } => info!("Authorization policy file: {}", path.display()),
+        AuthzConfig::Opa { url, .. } => info!("Authorization policy endpoint: {}", url),
+    }
+
+    // API keys managed through /keys, kept in SQLite; their roles and user
+    // bindings are checked before the authorization hook
+    let api_key_db = std::env::var("ROOK_API_KEY_DB_PATH").unwrap_or_else(|_| ":memory:".into());
+    let api_keys = Arc::new(ApiKeyStore::new(&api_key_db)?);
+    info!("API key store: {}", api_key_db);
+    let authorizer = Arc::new(ApiKeyAuthorizer::new(authorizer, api_keys.clone()));
+
     // Create application state with runtime
-    let state = AppState::new_with_runtime(runtime);
+    let mut state = AppState::new_with_runtime(runtime)
+        .with_authorizer(authorizer)
+        .with_event_bus(event_bus)
+        .with_webhooks(webhooks)
+        .with_metrics(metrics)
+        .with_api_keys(api_keys)
+        .with_analytics_policy(AggregationPolicy::from_env())
+        .with_tenants(TenantConfig::from_env());
+    if let Ok(path) = std::env::var("ROOK_AUDIT_DB_PATH") {
+        info!("Audit log: {}", path);
+        state = state.with_audit_log(Arc::new(AuditLog::new(&path)?));
+    }
+    let tenant_config = state.tenants().config();
+    info!(
+        "Organization instances: up to {}, dropped after {}s idle",
+        tenant_config.max_tenants,
+        tenant_config.idle_timeout.as_secs()
+    );
+    state.tenants().start();
+    let run_expiry_interval = std::env::var("ROOK_RUN_EXPIRY_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&secs: &u64| secs > 0)
+        .unwrap_or(300);
+    state.start_run_expiry(Duration::from_secs(run_expiry_interval));
+    let memory_expiry_interval = std::env::var("ROOK_MEMORY_EXPIRY_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1);
+    state.start_memory_expiry(memory_expiry_interval).await;
+    let memory_archive_interval = std::env::var("ROOK_MEMORY_ARCHIVE_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    state.start_memory_archive(memory_archive_interval).await;
+    let semantic_promotion_interval = std::env::var("ROOK_SEMANTIC_PROMOTION_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    state.start_semantic_promotion(semantic_promotion_interval).await;
+    let memory_replay_interval = std::env::var("ROOK_MEMORY_REPLAY_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    state.start_memory_replay(memory_replay_interval).await;
+    state.start_strength_updates().await;
+    if let (Some(fired_rx), Some(runtime)) = (fired_intentions_rx, state.runtime()) {
+        record_fired_intentions(fired_rx, runtime);
+    }
+    if let Some(monitor) = storage_monitor {
+        state = state.with_storage_monitor(monitor);
+    }
+    if let Ok(path) = std::env::var("ROOK_TEXT_INDEX_PATH") {
+        info!("Full-text index: {}", path);
+        state = state.with_text_index(Arc::new(TantivySearcher::new(Path::new(&path))?));
+    }
 
     // Create server with or without auth
     let app = if require_auth {
         info!("Authentication enabled");
+        if std::env::var("ROOK_API_KEY").map_or(true, |key| key.is_empty()) {
+            warn!("ROOK_API_KEY is not set; only stored API keys are accepted");
+        }
         create_server_with_auth(state.clone())
     } else {
         info!("Authentication disabled");
@@ -82,6 +271,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
+    // gRPC MemoryService on its own port
+    #[cfg(feature = "grpc")]
+    {
+        let grpc_port: u16 = std::env::var("ROOK_GRPC_PORT")
+            .unwrap_or_else(|_| "50051".to_string())
+            .parse()
+            .expect("ROOK_GRPC_PORT must be a valid port number");
+        let grpc_addr: SocketAddr = format!("{}:{}", host, grpc_port).parse()?;
+        info!("Starting gRPC MemoryService on {}", grpc_addr);
+
+        let grpc_listener = tokio::net::TcpListener::bind(grpc_addr).await?;
+        let grpc_app = rook_server::grpc::create_grpc_server(state.clone());
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(grpc_listener, grpc_app)
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+            {
+                tracing::error!("gRPC server failed: {}", e);
+            }
+        });
+    }
+
     // Serve with graceful shutdown
     axum::serve(listener, app)
         .with_graceful_shutdown(async {
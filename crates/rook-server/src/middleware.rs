@@ -1,13 +1,31 @@
 //! Middleware for the REST API server.
 
+use std::num::NonZeroU32;
+
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{
+        header::{CONTENT_TYPE, RETRY_AFTER},
+        HeaderValue, StatusCode,
+    },
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use rook_core::authz::ApiKey;
+use rook_core::observability::metrics;
+use rook_core::observability::sampling::{self, SamplingDecision};
 use tower_http::cors::{Any, CorsLayer};
-use tracing::info;
+use tower_http::trace::{DefaultMakeSpan, MakeSpan};
+use tracing::{info, warn, Instrument, Span};
+
+use crate::error::ApiError;
+use crate::idempotency::{Begin, StoredResponse, IDEMPOTENCY_HEADER};
+use crate::state::AppState;
+
+/// Largest request body buffered for idempotency checks, matching the
+/// default limit of the JSON extractor.
+const MAX_IDEMPOTENT_BODY: usize = 2 * 1024 * 1024;
 
 /// Create CORS middleware.
 pub fn cors_layer() -> CorsLayer {
@@ -17,6 +35,80 @@ pub fn cors_layer() -> CorsLayer {
         .allow_headers(Any)
 }
 
+/// Head-based trace sampling middleware.
+///
+/// Decides once per request, keyed by `"{METHOD} {route}"`, and runs the rest
+/// of the stack under that decision so child spans follow it. Unsampled
+/// requests that fail with a server error are still logged when
+/// `always_sample_errors` is enabled.
+pub async fn sampling_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let operation = format!("{} {}", method, route);
+
+    let sampler = sampling::global();
+    let decision = sampler.decide(&operation);
+    let span = if decision.is_sampled() {
+        tracing::info_span!("http.request", operation = %operation)
+    } else {
+        Span::none()
+    };
+
+    let start = std::time::Instant::now();
+    let response = sampling::with_decision(decision, next.run(request).instrument(span)).await;
+
+    if !decision.is_sampled()
+        && response.status().is_server_error()
+        && sampler.always_sample_errors()
+    {
+        warn!(
+            operation = %operation,
+            status = %response.status().as_u16(),
+            duration_ms = %start.elapsed().as_millis(),
+            "Unsampled request failed"
+        );
+    }
+
+    response
+}
+
+/// Request metrics middleware.
+///
+/// Records request counts and latency keyed by the matched route template,
+/// so path parameters do not create new series.
+pub async fn metrics_middleware(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let start = std::time::Instant::now();
+
+    let response = next.run(request).await;
+
+    metrics::record_http_request(
+        method.as_str(),
+        &route,
+        response.status().as_u16(),
+        start.elapsed(),
+    );
+
+    response
+}
+
+/// Span factory for `TraceLayer` that skips unsampled requests.
+pub fn make_span(request: &Request) -> Span {
+    match sampling::current_decision() {
+        Some(SamplingDecision::NotSampled) => Span::none(),
+        _ => DefaultMakeSpan::new().make_span(request),
+    }
+}
+
 /// Request logging middleware.
 pub async fn logging_middleware(request: Request, next: Next) -> Response {
     let method = request.method().clone();
@@ -28,6 +120,11 @@ pub async fn logging_middleware(request: Request, next: Next) -> Response {
     let duration = start.elapsed();
     let status = response.status();
 
+    // Unsampled failures are reported by the sampling middleware.
+    if sampling::current_decision() == Some(SamplingDecision::NotSampled) {
+        return response;
+    }
+
     info!(
         method = %method,
         uri = %uri,
@@ -40,35 +137,175 @@ pub async fn logging_middleware(request: Request, next: Next) -> Response {
 }
 
 /// API key authentication middleware (optional).
+///
+/// When `ROOK_REQUIRE_AUTH` is set, requests must carry a `Bearer` or
+/// `Token` authorization header with either the master key (`ROOK_API_KEY`)
+/// or an active key from the state's key store. Requests with a stored key
+/// count against its rate limit and carry the
+/// [`ApiKey`](rook_core::authz::ApiKey) as an extension, so they run as the
+/// key's subject. Without a master key or a key store, every request is let
+/// through.
 pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if std::env::var("ROOK_REQUIRE_AUTH").is_err() {
+        return next.run(request).await;
+    }
+
+    let master_key = std::env::var("ROOK_API_KEY").unwrap_or_default();
+    let store = state.api_keys();
+    if master_key.is_empty() && store.is_none() {
+        return next.run(request).await;
+    }
+
+    let token = request
+        .headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|header| {
+            header
+                .strip_prefix("Bearer ")
+                .or_else(|| header.strip_prefix("Token "))
+        })
+        .map(str::to_string);
+    let Some(token) = token else {
+        return ApiError::unauthorized("Missing API key").into_response();
+    };
+
+    if !master_key.is_empty() && token == master_key {
+        return next.run(request).await;
+    }
+
+    let key = match store.map(|store| store.authenticate(&token)).transpose() {
+        Ok(Some(Some(key))) => key,
+        Ok(_) => return ApiError::unauthorized("Invalid API key").into_response(),
+        Err(e) => return ApiError::from(e).into_response(),
+    };
+
+    if let Some(per_minute) = key.rate_limit_per_minute.and_then(NonZeroU32::new) {
+        if let Err(wait) = state.rate_limiter().check(&key.id, per_minute) {
+            let retry_after = wait.as_secs().max(1);
+            let mut response = ApiError::rate_limit(format!(
+                "API key {} is limited to {} requests per minute",
+                key.id, per_minute
+            ))
+            .into_response();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+            return response;
+        }
+    }
+
+    request.extensions_mut().insert(key);
+    next.run(request).await
+}
+
+/// Idempotency-Key middleware for memory writes.
+///
+/// A request carrying the header is run once per caller and key: sending it
+/// again returns the stored response instead of writing a second time, so
+/// clients can safely retry after a timeout. Reusing a key for a different
+/// body is rejected, as is a retry while the first request still runs.
+/// Failed requests are not stored and can be retried with the same key.
+pub async fn idempotency_middleware(
+    State(state): State<AppState>,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    // Check for API key in header if ROOK_REQUIRE_AUTH is set
-    if std::env::var("ROOK_REQUIRE_AUTH").is_ok() {
-        let expected_key = std::env::var("ROOK_API_KEY").unwrap_or_default();
-
-        if !expected_key.is_empty() {
-            let auth_header = request
-                .headers()
-                .get("Authorization")
-                .and_then(|v| v.to_str().ok());
-
-            match auth_header {
-                Some(header) if header.starts_with("Bearer ") || header.starts_with("Token ") => {
-                    let token = header
-                        .strip_prefix("Bearer ")
-                        .or_else(|| header.strip_prefix("Token "))
-                        .unwrap_or("");
-
-                    if token != expected_key {
-                        return Err(StatusCode::UNAUTHORIZED);
-                    }
-                }
-                _ => return Err(StatusCode::UNAUTHORIZED),
+) -> Response {
+    let Some(key) = request
+        .headers()
+        .get(IDEMPOTENCY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+    else {
+        return next.run(request).await;
+    };
+
+    let caller = request
+        .extensions()
+        .get::<ApiKey>()
+        .map(|key| key.id.clone())
+        .unwrap_or_default();
+    let scoped_key = format!(
+        "{}\n{} {}\n{}",
+        caller,
+        request.method(),
+        request.uri().path(),
+        key
+    );
+
+    let (parts, body) = request.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_IDEMPOTENT_BODY).await {
+        Ok(body) => body,
+        Err(_) => {
+            return ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "PAYLOAD_TOO_LARGE",
+                "Request body is too large",
+            )
+            .into_response()
+        }
+    };
+
+    let cache = state.idempotency();
+    match cache.begin(&scoped_key, &body) {
+        Begin::Run => {}
+        Begin::Replay(stored) => {
+            let mut response = (stored.status, stored.body).into_response();
+            if let Some(value) = stored
+                .content_type
+                .and_then(|v| HeaderValue::from_str(&v).ok())
+            {
+                response.headers_mut().insert(CONTENT_TYPE, value);
             }
+            return response;
+        }
+        Begin::InFlight => {
+            return ApiError::conflict(format!(
+                "A request with idempotency key {} is still in progress",
+                key
+            ))
+            .into_response()
         }
+        Begin::Mismatch => {
+            return ApiError::validation(format!(
+                "Idempotency key {} was used for a different request",
+                key
+            ))
+            .into_response()
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if !response.status().is_success() {
+        cache.abandon(&scoped_key);
+        return response;
     }
 
-    Ok(next.run(request).await)
+    let (parts, body) = response.into_parts();
+    match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => {
+            cache.complete(
+                &scoped_key,
+                StoredResponse {
+                    status: parts.status,
+                    content_type: parts
+                        .headers
+                        .get(CONTENT_TYPE)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string),
+                    body: body.clone(),
+                },
+            );
+            Response::from_parts(parts, Body::from(body))
+        }
+        Err(e) => {
+            cache.abandon(&scoped_key);
+            warn!("Failed to buffer response for key {}: {}", key, e);
+            ApiError::internal("Failed to read response").into_response()
+        }
+    }
 }
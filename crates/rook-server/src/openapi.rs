@@ -0,0 +1,195 @@
+//! OpenAPI document for the REST API.
+//!
+//! Handlers and their request and response types carry utoipa annotations;
+//! [`ApiDoc`] collects them into the OpenAPI 3.1 document served at
+//! `GET /openapi.json`. Types owned by rook-core (memories, intentions,
+//! graph entities, ...) are described as plain JSON objects.
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, Response, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+use crate::error::{ErrorBody, ErrorResponse};
+use crate::routes;
+
+/// The OpenAPI document for every documented route.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "rook", description = "REST API for the rook memory layer."),
+    paths(
+        routes::health_check,
+        routes::add_memory,
+        routes::get_all_memories,
+        routes::delete_all_memories,
+        routes::get_memory,
+        routes::update_memory,
+        routes::delete_memory,
+        routes::get_document,
+        routes::get_memory_history,
+        routes::get_memory_versions,
+        routes::get_memory_version,
+        routes::rollback_memory,
+        routes::add_memory_tags,
+        routes::remove_memory_tags,
+        routes::merge_memories,
+        routes::get_tags,
+        routes::due_for_review,
+        routes::review_memory,
+        routes::smart_ingest,
+        routes::start_run,
+        routes::get_run,
+        routes::end_run,
+        routes::summarize_run,
+        routes::list_run_pins,
+        routes::pin_memory,
+        routes::clear_run_pins,
+        routes::unpin_memory,
+        routes::get_global_memories,
+        routes::propose_promotion,
+        routes::list_promotions,
+        routes::review_promotion,
+        routes::search_memories,
+        routes::list_entities,
+        routes::add_entity,
+        routes::delete_entities,
+        routes::add_relationship,
+        routes::delete_relationship,
+        routes::get_neighbors,
+        routes::search_graph,
+        routes::list_intentions,
+        routes::create_intention,
+        routes::list_fired_intentions,
+        routes::list_upcoming_fires,
+        routes::get_intention,
+        routes::update_intention,
+        routes::delete_intention,
+        routes::list_webhooks,
+        routes::create_webhook,
+        routes::get_webhook,
+        routes::update_webhook,
+        routes::delete_webhook,
+        routes::list_api_keys,
+        routes::create_api_key,
+        routes::revoke_api_key,
+        routes::process_signals,
+        routes::apply_updates,
+        routes::sync_status,
+        routes::pull_changes,
+        routes::push_changes,
+        routes::list_conflicts,
+        routes::configure,
+        routes::reset,
+        routes::reload,
+        routes::get_sampling,
+        routes::calibrate,
+        routes::get_calibration,
+        routes::rebuild,
+        routes::rebuild_status,
+        routes::reassign_scope,
+        routes::rotate_content_encryption,
+        routes::backup,
+        routes::restore,
+        routes::export_memories,
+        routes::import_memories,
+        routes::erase_user,
+        routes::export_user,
+        routes::stream_events,
+        routes::get_stats,
+        routes::get_analytics,
+        routes::get_audit_log,
+        routes::get_metrics,
+    ),
+    components(schemas(ErrorResponse, ErrorBody, routes::SearchResultItem)),
+    modifiers(&Conventions),
+    tags(
+        (name = "health", description = "Liveness"),
+        (name = "memories", description = "Memory CRUD, history and versions"),
+        (name = "runs", description = "Run lifecycle and memories pinned to runs"),
+        (name = "global", description = "Global knowledge base and promotions"),
+        (name = "review", description = "Spaced review of memories whose recall has decayed"),
+        (name = "search", description = "Memory search"),
+        (name = "graph", description = "Knowledge graph"),
+        (name = "intentions", description = "Intentions and their fires"),
+        (name = "webhooks", description = "Webhook subscriptions"),
+        (name = "keys", description = "API keys"),
+        (name = "signals", description = "Strength signals"),
+        (name = "sync", description = "Replicated sync"),
+        (name = "config", description = "Memory configuration"),
+        (name = "admin", description = "Runtime settings, calibration, rebuilds, scope reassignment and backups"),
+        (name = "transfer", description = "Export and import"),
+        (name = "users", description = "Erasure and export of a user's data"),
+        (name = "events", description = "Live memory events"),
+        (name = "stats", description = "Server statistics"),
+        (name = "analytics", description = "Aggregate memory analytics"),
+        (name = "audit", description = "Audit log of changes made through the API"),
+        (name = "metrics", description = "Prometheus metrics"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Conventions shared by every route, applied once instead of per handler.
+struct Conventions;
+
+impl Modify for Conventions {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        // Bearer auth (master or stored API key) applies only when
+        // ROOK_REQUIRE_AUTH is set, so it is optional
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        openapi.security = Some(vec![
+            SecurityRequirement::new("bearer", Vec::<String>::new()),
+            SecurityRequirement::default(),
+        ]);
+
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                // Handler docs name their route on the second line
+                if let Some(ref mut summary) = operation.summary {
+                    *summary = without_route_line(summary);
+                }
+                let responses = &mut operation.responses.responses;
+                for (status, description) in [("4XX", "Request rejected"), ("5XX", "Server error")] {
+                    responses
+                        .entry(status.to_string())
+                        .or_insert_with(|| error_response(description).into());
+                }
+            }
+        }
+    }
+}
+
+/// A response carrying an [`ErrorResponse`].
+fn error_response(description: &str) -> Response {
+    ResponseBuilder::new()
+        .description(description)
+        .content(
+            "application/json",
+            ContentBuilder::new()
+                .schema(Some(Ref::from_schema_name("ErrorResponse")))
+                .build(),
+        )
+        .build()
+}
+
+/// `summary` without `METHOD /path` lines.
+fn without_route_line(summary: &str) -> String {
+    summary
+        .lines()
+        .filter(|line| {
+            !line.split_once(' ').is_some_and(|(method, path)| {
+                path.starts_with('/') && method.chars().all(|c| c.is_ascii_uppercase())
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
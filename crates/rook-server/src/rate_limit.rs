@@ -0,0 +1,44 @@
+//! Per-key request rate limiting.
+
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+
+/// Rate limiters for API keys, created on first use.
+///
+/// Each key gets its own limiter with the key's per-minute quota, so a busy
+/// key cannot use up another key's budget.
+#[derive(Default)]
+pub struct KeyRateLimiter {
+    limiters: Mutex<HashMap<String, Arc<DefaultDirectRateLimiter>>>,
+}
+
+impl KeyRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request by `key_id`. Returns how long to wait before
+    /// retrying if the key is over `per_minute`.
+    pub fn check(&self, key_id: &str, per_minute: NonZeroU32) -> Result<(), Duration> {
+        let limiter = self
+            .limiters
+            .lock()
+            .unwrap()
+            .entry(key_id.to_string())
+            .or_insert_with(|| Arc::new(RateLimiter::direct(Quota::per_minute(per_minute))))
+            .clone();
+        limiter
+            .check()
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    /// Drop the limiter of a key, e.g. once it is revoked.
+    pub fn remove(&self, key_id: &str) {
+        self.limiters.lock().unwrap().remove(key_id);
+    }
+}
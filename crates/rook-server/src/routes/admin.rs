@@ -0,0 +1,754 @@
+//! Admin endpoints.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::backup::{
+    create_snapshot, restore_snapshot, RestoreOptions, SnapshotManifest, BACKUP_DIR,
+};
+use rook_core::memory::{ScopeReassignment, SessionScope};
+use rook_core::observability::sampling;
+use rook_core::observability::SamplingConfig;
+use rook_core::retrieval::ThresholdCalibration;
+use rook_core::storage::{
+    FsrsRebuilder, RebuildOptions, RebuildProgress, RebuildTarget, Rebuilder, TextIndexRebuilder,
+};
+
+/// Request body for reloading runtime settings.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ReloadRequest {
+    /// New trace sampling config. When omitted, it is re-read from the environment.
+    #[schema(value_type = Option<Object>)]
+    pub sampling: Option<SamplingConfig>,
+}
+
+/// Response for reload.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReloadResponse {
+    pub message: String,
+    #[schema(value_type = Object)]
+    pub sampling: SamplingConfig,
+}
+
+/// Reload runtime settings.
+/// POST /admin/reload
+#[utoipa::path(
+    post,
+    path = "/admin/reload",
+    tag = "admin",
+    request_body = ReloadRequest,
+    responses(
+        (status = 200, description = "Settings reloaded", body = ReloadResponse),
+    )
+)]
+pub async fn reload(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    request: Option<Json<ReloadRequest>>,
+) -> ApiResult<Json<ReloadResponse>> {
+    // Sampling is global, and calibration and rebuilds use the server's own memory
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let config = match request.sampling {
+        Some(config) => config,
+        None => SamplingConfig::from_env()?,
+    };
+
+    sampling::global().reload(config)?;
+    tracing::info!("Trace sampling config reloaded");
+
+    Ok(Json(ReloadResponse {
+        message: "Configuration reloaded".to_string(),
+        sampling: sampling::global().config(),
+    }))
+}
+
+/// Get the active trace sampling config.
+/// GET /admin/sampling
+#[utoipa::path(
+    get,
+    path = "/admin/sampling",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Active trace sampling config", body = Object),
+    )
+)]
+pub async fn get_sampling(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<SamplingConfig>> {
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    Ok(Json(sampling::global().config()))
+}
+
+/// Request body for threshold calibration.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CalibrateRequest {
+    /// Number of stored vectors to sample (default: 200).
+    pub sample_size: Option<usize>,
+}
+
+/// Calibrate relative score thresholds for the configured embedder.
+/// POST /admin/calibration
+#[utoipa::path(
+    post,
+    path = "/admin/calibration",
+    tag = "admin",
+    request_body = CalibrateRequest,
+    responses(
+        (status = 200, description = "The new calibration", body = Object),
+    )
+)]
+pub async fn calibrate(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    request: Option<Json<CalibrateRequest>>,
+) -> ApiResult<Json<ThresholdCalibration>> {
+    tenant.require_shared()?;
+
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let sample_size = request.and_then(|Json(r)| r.sample_size).unwrap_or(200);
+    let guard = state.inner.read().await;
+    let memory = guard
+        .memory
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let calibration = memory.calibrate_thresholds(sample_size).await?;
+    tracing::info!(
+        embedder = %calibration.embedder,
+        model = %calibration.model,
+        "Score thresholds calibrated"
+    );
+    Ok(Json(calibration))
+}
+
+/// Get the threshold calibration for the configured embedder.
+/// GET /admin/calibration
+#[utoipa::path(
+    get,
+    path = "/admin/calibration",
+    tag = "admin",
+    responses(
+        (status = 200, description = "The current calibration", body = Object),
+    )
+)]
+pub async fn get_calibration(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<ThresholdCalibration>> {
+    tenant.require_shared()?;
+
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let guard = state.inner.read().await;
+    let memory = guard
+        .memory
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    memory
+        .threshold_calibration()
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Embedder has not been calibrated"))
+}
+
+/// Query parameters for rebuilding a derived store.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RebuildQuery {
+    /// Store to rebuild: `fsrs`, `text-index` or `graph`.
+    #[param(value_type = String)]
+    pub target: RebuildTarget,
+    /// Continue an unfinished rebuild from its checkpoint.
+    #[serde(default)]
+    pub resume: bool,
+    /// Records processed between checkpoints.
+    pub batch_size: Option<usize>,
+}
+
+/// Query parameters for rebuild progress.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RebuildStatusQuery {
+    #[param(value_type = String)]
+    pub target: RebuildTarget,
+}
+
+/// Response for starting a rebuild.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RebuildResponse {
+    pub message: String,
+    #[schema(value_type = String)]
+    pub target: RebuildTarget,
+    pub resume: bool,
+}
+
+/// Start rebuilding a derived store from the vector store.
+/// POST /admin/rebuild?target=fsrs|text-index|graph
+///
+/// The rebuild runs in the background; poll `GET /admin/rebuild` for progress.
+#[utoipa::path(
+    post,
+    path = "/admin/rebuild",
+    tag = "admin",
+    params(RebuildQuery),
+    responses(
+        (status = 202, description = "Rebuild started", body = RebuildResponse),
+    )
+)]
+pub async fn rebuild(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<RebuildQuery>,
+) -> ApiResult<(StatusCode, Json<RebuildResponse>)> {
+    tenant.require_shared()?;
+
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let rebuilder: Option<Box<dyn Rebuilder>> = match query.target {
+        RebuildTarget::Fsrs => {
+            let runtime = state
+                .runtime()
+                .ok_or_else(|| ApiError::bad_request("No cognitive store configured"))?;
+            let store = runtime.read().await.cognitive_store();
+            Some(Box::new(FsrsRebuilder::new(store)))
+        }
+        RebuildTarget::TextIndex => {
+            let index = state.text_index().ok_or_else(|| {
+                ApiError::bad_request("No text index configured. Set ROOK_TEXT_INDEX_PATH.")
+            })?;
+            Some(Box::new(TextIndexRebuilder::new(index)))
+        }
+        RebuildTarget::Graph => None,
+    };
+
+    let mut options = RebuildOptions::default();
+    if query.resume {
+        options = options.resume();
+    }
+    if let Some(batch_size) = query.batch_size {
+        options = options.with_batch_size(batch_size);
+    }
+
+    let target = query.target;
+    if !state.begin_rebuild(target) {
+        return Err(ApiError::conflict(format!("A {} rebuild is already running", target)));
+    }
+
+    let task_state = state.clone();
+    tokio::spawn(async move {
+        let result = {
+            let guard = task_state.inner.read().await;
+            match guard.memory.as_ref() {
+                Some(memory) => match rebuilder {
+                    Some(ref rebuilder) => memory.rebuild(rebuilder.as_ref(), &options).await,
+                    None => memory.rebuild_graph(&options).await,
+                },
+                None => Err(rook_core::RookError::Configuration(
+                    "Memory not configured".to_string(),
+                )),
+            }
+        };
+        task_state.end_rebuild(target);
+
+        match result {
+            Ok(progress) => tracing::info!(
+                store = %target,
+                processed = progress.processed,
+                rebuilt = progress.rebuilt,
+                failed = progress.failed,
+                "Rebuild completed"
+            ),
+            Err(e) => tracing::error!(store = %target, "Rebuild failed: {}", e),
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RebuildResponse {
+            message: format!("Rebuild of {} started", target),
+            target,
+            resume: query.resume,
+        }),
+    ))
+}
+
+/// Get the progress of the latest rebuild of a derived store.
+/// GET /admin/rebuild?target=fsrs|text-index|graph
+#[utoipa::path(
+    get,
+    path = "/admin/rebuild",
+    tag = "admin",
+    params(RebuildStatusQuery),
+    responses(
+        (status = 200, description = "Progress of the latest rebuild", body = Object),
+    )
+)]
+pub async fn rebuild_status(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<RebuildStatusQuery>,
+) -> ApiResult<Json<RebuildProgress>> {
+    tenant.require_shared()?;
+
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let guard = state.inner.read().await;
+    let memory = guard
+        .memory
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    memory
+        .rebuild_progress(query.target)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No {} rebuild has run", query.target)))
+}
+
+/// Response for re-encrypting memory content.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateEncryptionResponse {
+    /// Records re-encrypted under the active key.
+    pub rotated: usize,
+}
+
+/// Re-encrypt memory content under the active content encryption key.
+/// POST /admin/encryption/rotate
+#[utoipa::path(
+    post,
+    path = "/admin/encryption/rotate",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Content re-encrypted", body = RotateEncryptionResponse),
+    )
+)]
+pub async fn rotate_content_encryption(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<RotateEncryptionResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Admin))
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+    let rotated = memory.rotate_content_encryption().await?;
+    tracing::info!(rotated, "Memory content re-encrypted");
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Configure, "encryption")
+            .with_org_id(tenant.0.clone())
+            .with_details(serde_json::json!({ "rotated": rotated })),
+    );
+
+    Ok(Json(RotateEncryptionResponse { rotated }))
+}
+
+/// Request body for reassigning a scope.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReassignScopeRequest {
+    /// Scope to move, e.g. `{"user_id": "alice-2"}`.
+    #[schema(value_type = Object)]
+    pub from: SessionScope,
+    /// IDs replacing those of the moved records, e.g. `{"user_id": "alice"}`.
+    #[schema(value_type = Object)]
+    pub to: SessionScope,
+}
+
+/// Move the memories, graph entities and runs of one scope to another.
+/// POST /admin/reassign
+#[utoipa::path(
+    post,
+    path = "/admin/reassign",
+    tag = "admin",
+    request_body = ReassignScopeRequest,
+    responses(
+        (status = 200, description = "The records moved", body = Object),
+    )
+)]
+pub async fn reassign_scope(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<ReassignScopeRequest>,
+) -> ApiResult<Json<ScopeReassignment>> {
+    tenant.require_shared()?;
+
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Admin))
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+    let reassignment = memory
+        .reassign_scope(request.from.clone(), request.to.clone())
+        .await?;
+
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Update, "scopes")
+            .with_org_id(tenant.0.clone())
+            .with_details(serde_json::json!({
+                "from": request.from,
+                "to": request.to,
+                "memories": reassignment.memory_ids.len(),
+                "entities": reassignment.entities,
+                "runs": reassignment.runs,
+                "promotions": reassignment.promotions,
+            })),
+    );
+
+    Ok(Json(reassignment))
+}
+
+/// Request body for snapshotting the data directory.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct BackupRequest {
+    /// Archive to write, relative to the backup directory (`ROOK_BACKUP_DIR`,
+    /// default: the data directory's `backups/`). Defaults to a timestamped
+    /// `.tar.zst` in it.
+    #[serde(default)]
+    pub destination: Option<String>,
+}
+
+/// Response for a snapshot.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BackupResponse {
+    /// The archive written.
+    pub path: String,
+    #[schema(value_type = Object)]
+    pub manifest: SnapshotManifest,
+}
+
+/// Snapshot the data directory into a `.tar.zst` archive.
+/// POST /admin/backup
+#[utoipa::path(
+    post,
+    path = "/admin/backup",
+    tag = "admin",
+    request_body = BackupRequest,
+    responses(
+        (status = 200, description = "Snapshot written", body = BackupResponse),
+    )
+)]
+pub async fn backup(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<BackupRequest>,
+) -> ApiResult<Json<BackupResponse>> {
+    // The data directory holds every organization's databases
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Admin))
+        .await?;
+
+    let data_dir = data_dir(&state)?;
+    let backups = backup_dir(&state)?;
+    let archive = match request.destination {
+        Some(destination) => within(&backups, &destination)?,
+        None => backups.join(format!(
+            "rook-snapshot-{}.tar.zst",
+            chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+        )),
+    };
+
+    let path = archive.clone();
+    let manifest = tokio::task::spawn_blocking(move || create_snapshot(&data_dir, &path))
+        .await
+        .map_err(|e| ApiError::internal(format!("Snapshot task failed: {}", e)))??;
+
+    tracing::info!(
+        path = %archive.display(),
+        entries = manifest.entries.len(),
+        bytes = manifest.total_bytes(),
+        "Data directory snapshot written"
+    );
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Create, "backup").with_details(
+            serde_json::json!({
+                "path": archive.display().to_string(),
+                "entries": manifest.entries.len(),
+            }),
+        ),
+    );
+
+    Ok(Json(BackupResponse {
+        path: archive.display().to_string(),
+        manifest,
+    }))
+}
+
+/// Request body for restoring a snapshot.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RestoreRequest {
+    /// Archive to restore, relative to the backup directory.
+    pub source: String,
+    /// Directory to restore into, relative to the backup directory. The
+    /// server's own data directory can't be restored over: restore it with
+    /// `rook restore` while the server is stopped.
+    pub destination: String,
+    /// Move an existing, non-empty destination aside instead of failing.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Response for a restore.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RestoreResponse {
+    #[schema(value_type = Object)]
+    pub manifest: SnapshotManifest,
+    /// Where the previous contents of the destination were moved.
+    pub previous: Option<String>,
+}
+
+/// Restore a snapshot archive into a directory other than the live one.
+/// POST /admin/restore
+#[utoipa::path(
+    post,
+    path = "/admin/restore",
+    tag = "admin",
+    request_body = RestoreRequest,
+    responses(
+        (status = 200, description = "Snapshot restored", body = RestoreResponse),
+    )
+)]
+pub async fn restore(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<RestoreRequest>,
+) -> ApiResult<Json<RestoreResponse>> {
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Admin))
+        .await?;
+
+    let backups = backup_dir(&state)?;
+    let source = within(&backups, &request.source)?;
+    let destination = within(&backups, &request.destination)?;
+    if let Ok(data_dir) = data_dir(&state) {
+        if same_dir(&destination, &data_dir) {
+            return Err(ApiError::bad_request(
+                "Cannot restore over the live data directory; stop the server and run `rook restore`",
+            ));
+        }
+    }
+
+    let target = destination.clone();
+    let options = RestoreOptions {
+        overwrite: request.overwrite,
+    };
+    let report = tokio::task::spawn_blocking(move || restore_snapshot(&source, &target, &options))
+        .await
+        .map_err(|e| ApiError::internal(format!("Restore task failed: {}", e)))??;
+
+    tracing::info!(
+        source = %request.source,
+        destination = %destination.display(),
+        entries = report.manifest.entries.len(),
+        "Snapshot restored"
+    );
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Import, "backup").with_details(
+            serde_json::json!({
+                "source": request.source,
+                "destination": request.destination,
+            }),
+        ),
+    );
+
+    Ok(Json(RestoreResponse {
+        manifest: report.manifest,
+        previous: report.previous.map(|p| p.display().to_string()),
+    }))
+}
+
+/// The server's data directory, from the storage monitor or `ROOK_DATA_DIR`.
+fn data_dir(state: &AppState) -> ApiResult<PathBuf> {
+    state
+        .storage_monitor()
+        .map(|monitor| monitor.config().path.clone())
+        .or_else(|| std::env::var_os("ROOK_DATA_DIR").map(PathBuf::from))
+        .ok_or_else(|| ApiError::bad_request("No data directory configured (set ROOK_DATA_DIR)"))
+}
+
+/// The directory backups are written to and restored from, created if
+/// missing: `ROOK_BACKUP_DIR`, or the data directory's `backups/`.
+fn backup_dir(state: &AppState) -> ApiResult<PathBuf> {
+    let dir = match std::env::var_os("ROOK_BACKUP_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => data_dir(state)?.join(BACKUP_DIR),
+    };
+    std::fs::create_dir_all(&dir)
+        .and_then(|_| dir.canonicalize())
+        .map_err(|e| ApiError::internal(format!("Failed to create {}: {}", dir.display(), e)))
+}
+
+/// Resolve a requested path against the backup directory `dir`, rejecting
+/// `..` and paths that resolve (symlinks followed) outside it.
+fn within(dir: &Path, requested: &str) -> ApiResult<PathBuf> {
+    let outside = || {
+        ApiError::bad_request(format!(
+            "Path '{}' is outside the backup directory",
+            requested
+        ))
+    };
+    let requested_path = Path::new(requested);
+    if requested.is_empty()
+        || requested_path
+            .components()
+            .any(|c| matches!(c, Component::ParentDir))
+    {
+        return Err(outside());
+    }
+
+    // The path may not exist yet; canonicalize its deepest existing ancestor
+    let path = dir.join(requested_path);
+    let mut existing = path.as_path();
+    let mut rest = Vec::new();
+    let resolved = loop {
+        match existing.canonicalize() {
+            Ok(resolved) => break resolved,
+            Err(_) => {
+                rest.push(existing.file_name().ok_or_else(outside)?);
+                existing = existing.parent().ok_or_else(outside)?;
+            }
+        }
+    };
+    let resolved = rest
+        .into_iter()
+        .rev()
+        .fold(resolved, |path, name| path.join(name));
+    if resolved.starts_with(dir) && resolved != dir {
+        Ok(resolved)
+    } else {
+        Err(outside())
+    }
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_paths_stay_within_backup_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("backups");
+        std::fs::create_dir_all(dir.join("nightly")).unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        assert_eq!(within(&dir, "a.tar.zst").unwrap(), dir.join("a.tar.zst"));
+        assert_eq!(
+            within(&dir, "nightly/restored/x").unwrap(),
+            dir.join("nightly/restored/x")
+        );
+        let absolute = dir.join("b.tar.zst");
+        assert_eq!(within(&dir, absolute.to_str().unwrap()).unwrap(), absolute);
+
+        for requested in ["", ".", "../a.tar.zst", "nightly/../../a", "/etc/passwd"] {
+            assert!(within(&dir, requested).is_err(), "{}", requested);
+        }
+        let outside = root.path().join("a.tar.zst");
+        assert!(within(&dir, outside.to_str().unwrap()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_backup_paths_reject_symlinks_out() {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("backups");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::os::unix::fs::symlink(root.path(), dir.join("escape")).unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        assert!(within(&dir, "escape/a.tar.zst").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+//! Aggregate analytics endpoint.
+//!
+//! Counts memories across tenants. Callers with an `analytics` API key only
+//! get what the server's aggregation policy releases: groups smaller than k
+//! are suppressed and counts may carry noise.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::authz::{CallerKey, Subject};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::analytics::{count_by, AnalyticsDimension, AnalyticsReport};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+
+/// Query parameters for analytics.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnalyticsQuery {
+    /// `user_id`, `agent_id`, `run_id`, `category` (default) or `created_day`.
+    pub group_by: Option<String>,
+}
+
+/// Count memories grouped by a field.
+/// GET /analytics
+#[utoipa::path(
+    get,
+    path = "/analytics",
+    tag = "analytics",
+    params(AnalyticsQuery),
+    responses(
+        (status = 200, description = "Memory counts per group", body = Object),
+    )
+)]
+pub async fn get_analytics(
+    State(state): State<AppState>,
+    subject: Subject,
+    key: CallerKey,
+    Query(query): Query<AnalyticsQuery>,
+) -> ApiResult<Json<AnalyticsReport>> {
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Analytics))
+        .await?;
+
+    let dimension = match query.group_by {
+        Some(ref group_by) => AnalyticsDimension::parse(group_by)?,
+        None => AnalyticsDimension::Category,
+    };
+
+    let store = state
+        .with_memory(|m| m.vector_store())
+        .await
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+    let records = store.list(None, None).await?;
+    let counts = count_by(records.iter().map(|r| &r.payload), dimension);
+
+    let report = if key.is_aggregate_only() {
+        AnalyticsReport::aggregated(dimension, counts, state.analytics_policy())
+    } else {
+        AnalyticsReport::exact(dimension, counts)
+    };
+    Ok(Json(report))
+}
@@ -0,0 +1,121 @@
+//! Audit log endpoint.
+//!
+//! Changes made through the API are recorded when the server runs with
+//! `ROOK_AUDIT_DB_PATH`. Organization callers only see their organization's
+//! entries.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, AuditEntry, AuditQuery, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::types::{MemoryEvent, MemoryResult};
+
+/// Query parameters for reading the audit log.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditLogQuery {
+    /// Subject that made the change.
+    pub actor: Option<String>,
+    /// `create`, `update`, `delete`, `delete_all`, `rollback`, `import`,
+    /// `configure`, `reset`, `key_create` or `key_revoke`.
+    pub action: Option<String>,
+    pub resource_id: Option<String>,
+    /// Entries recorded at or after this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub since: Option<DateTime<Utc>>,
+    /// Entries recorded before this time (RFC 3339).
+    #[param(value_type = Option<String>)]
+    pub until: Option<DateTime<Utc>>,
+    /// Entries after this sequence number; pass the previous page's
+    /// `next_seq` to page through the log.
+    pub after_seq: Option<i64>,
+    /// Maximum entries to return (default 100).
+    pub limit: Option<usize>,
+}
+
+/// Response for reading the audit log.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    /// Matching entries, oldest first.
+    #[schema(value_type = Vec<Object>)]
+    pub entries: Vec<AuditEntry>,
+    /// Sequence number of the last entry, to continue from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_seq: Option<i64>,
+}
+
+/// Read the audit log.
+/// GET /audit
+#[utoipa::path(
+    get,
+    path = "/audit",
+    tag = "audit",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Recorded changes", body = AuditLogResponse),
+    )
+)]
+pub async fn get_audit_log(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<AuditLogQuery>,
+) -> ApiResult<Json<AuditLogResponse>> {
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+    let log = state
+        .audit_log()
+        .ok_or_else(|| ApiError::bad_request("The audit log is not enabled on this server"))?;
+
+    let query = AuditQuery {
+        actor: query.actor,
+        action: query.action.as_deref().map(AuditAction::parse).transpose()?,
+        resource_id: query.resource_id,
+        org_id: tenant.0,
+        since: query.since,
+        until: query.until,
+        after_seq: query.after_seq,
+        limit: query.limit,
+    };
+    let entries = log.query(&query)?;
+    let next_seq = entries.last().map(|entry| entry.seq);
+
+    Ok(Json(AuditLogResponse { entries, next_seq }))
+}
+
+/// Record the memories changed by an add.
+pub(crate) fn audit_results(
+    state: &AppState,
+    subject: &Subject,
+    tenant: &Tenant,
+    results: &[MemoryResult],
+) {
+    for result in results {
+        let (action, old, new) = match result.event {
+            MemoryEvent::Add => (AuditAction::Create, None, Some(&result.memory)),
+            MemoryEvent::Update => (
+                AuditAction::Update,
+                result.previous_memory.as_ref(),
+                Some(&result.memory),
+            ),
+            MemoryEvent::Delete => (AuditAction::Delete, Some(&result.memory), None),
+            MemoryEvent::None => continue,
+        };
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), action, "memory")
+                .with_resource_id(&result.id)
+                .with_org_id(tenant.0.clone())
+                .with_content(old.map(String::as_str), new.map(String::as_str)),
+        );
+    }
+}
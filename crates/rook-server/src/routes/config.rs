@@ -2,20 +2,32 @@
 
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::path::PathBuf;
 
+use crate::authz::{Subject, Tenant};
 use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::cognitive::CognitiveConfig;
 use rook_core::config::{
     EmbedderProviderConfig, LlmProvider, LlmProviderConfig, MemoryConfig,
 };
+use rook_core::encryption::{BlindIndexConfig, ContentEncryptionConfig};
+use rook_core::memory::{ArchiveConfig, GlobalKnowledgeConfig, ReplayConfig, SemanticPromotionConfig};
+use rook_core::retrieval::RerankCacheConfig;
+use rook_core::sync::SyncConfig;
+use rook_core::versioning::VersioningConfig;
+use rook_core::types::MetadataSchema;
 use rook_core::traits::{
-    EmbedderConfig, EmbedderProvider, GraphStoreConfig, GraphStoreProvider, LlmConfig,
-    RerankerConfig, RerankerProvider, VectorStoreConfig, VectorStoreProvider,
+    EmbedderConfig, EmbedderProvider, GraphSearchConfig, GraphStoreConfig, GraphStoreProvider,
+    LlmConfig, QuantizationConfig, RerankerConfig, RerankerProvider, VectorStoreConfig,
+    VectorStoreProvider,
 };
 
 /// Request body for configuring memory.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ConfigureRequest {
     /// LLM configuration.
     pub llm: Option<LlmConfigInput>,
@@ -25,15 +37,48 @@ pub struct ConfigureRequest {
     pub vector_store: Option<VectorStoreConfigInput>,
     /// Graph store configuration.
     pub graph_store: Option<GraphStoreConfigInput>,
+    /// Graph-aware search settings.
+    #[schema(value_type = Option<Object>)]
+    pub graph_search: Option<GraphSearchConfig>,
     /// Reranker configuration.
     pub reranker: Option<RerankerConfigInput>,
     /// Collection name.
     pub collection_name: Option<String>,
     /// Embedding dimension.
     pub embedding_dims: Option<usize>,
+    /// Blind indexing of filterable fields.
+    #[schema(value_type = Option<Object>)]
+    pub blind_index: Option<BlindIndexConfig>,
+    /// AES-256-GCM encryption of memory content at rest.
+    #[schema(value_type = Option<Object>)]
+    pub content_encryption: Option<ContentEncryptionConfig>,
+    /// Global knowledge base settings.
+    #[schema(value_type = Option<Object>)]
+    pub global_knowledge: Option<GlobalKnowledgeConfig>,
+    /// Replicated sync settings.
+    #[schema(value_type = Option<Object>)]
+    pub sync: Option<SyncConfig>,
+    /// Memory version snapshots.
+    #[schema(value_type = Option<Object>)]
+    pub versioning: Option<VersioningConfig>,
+    /// FSRS tracking of memories on add and access.
+    #[schema(value_type = Option<Object>)]
+    pub cognitive: Option<CognitiveConfig>,
+    /// Cold-storage archive for decayed memories.
+    #[schema(value_type = Option<Object>)]
+    pub archive: Option<ArchiveConfig>,
+    /// Restatement of consolidated memories as semantic memories.
+    #[schema(value_type = Option<Object>)]
+    pub semantic_promotion: Option<SemanticPromotionConfig>,
+    /// Replay of recent memories against older related ones.
+    #[schema(value_type = Option<Object>)]
+    pub replay: Option<ReplayConfig>,
+    /// Metadata field types for `order_by`.
+    #[schema(value_type = Option<Object>)]
+    pub metadata_schema: Option<MetadataSchema>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LlmConfigInput {
     pub provider: String,
     pub model: Option<String>,
@@ -43,7 +88,7 @@ pub struct LlmConfigInput {
     pub max_tokens: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct EmbedderConfigInput {
     pub provider: String,
     pub model: Option<String>,
@@ -51,16 +96,18 @@ pub struct EmbedderConfigInput {
     pub base_url: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct VectorStoreConfigInput {
     pub provider: String,
     pub url: Option<String>,
     pub api_key: Option<String>,
     pub collection_name: Option<String>,
     pub embedding_dims: Option<usize>,
+    #[schema(value_type = Option<Object>)]
+    pub quantization: Option<QuantizationConfig>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct GraphStoreConfigInput {
     pub provider: String,
     pub url: Option<String>,
@@ -68,15 +115,19 @@ pub struct GraphStoreConfigInput {
     pub password: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RerankerConfigInput {
     pub provider: String,
     pub model: Option<String>,
     pub api_key: Option<String>,
+    /// Reuse rankings of the same query and candidates for this many seconds.
+    pub cache_ttl_secs: Option<u64>,
+    /// Keep the vector order if reranking takes longer than this.
+    pub timeout_ms: Option<u64>,
 }
 
 /// Response for configuration.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ConfigureResponse {
     pub message: String,
     pub configured: bool,
@@ -84,10 +135,28 @@ pub struct ConfigureResponse {
 
 /// Configure memory.
 /// POST /configure
+#[utoipa::path(
+    post,
+    path = "/configure",
+    tag = "config",
+    request_body = ConfigureRequest,
+    responses(
+        (status = 200, description = "Memory configured", body = ConfigureResponse),
+    )
+)]
 pub async fn configure(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<ConfigureRequest>,
 ) -> ApiResult<Json<ConfigureResponse>> {
+    // The configuration is shared by every organization
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Configure))
+        .await?;
+
     // Build LLM config
     let llm_config = if let Some(llm) = request.llm {
         let provider = parse_llm_provider(&llm.provider)?;
@@ -144,6 +213,7 @@ pub async fn configure(
             collection_name: vs.collection_name.unwrap_or(collection_name.clone()),
             embedding_model_dims: vs.embedding_dims.unwrap_or(embedding_dims),
             pool: None,
+            quantization: vs.quantization,
             config: config_json,
         }
     } else {
@@ -171,10 +241,16 @@ pub async fn configure(
     // Build reranker config (optional)
     let reranker_config = if let Some(rr) = request.reranker {
         let provider = parse_reranker_provider(&rr.provider)?;
+        let cache = RerankCacheConfig {
+            ttl_secs: rr.cache_ttl_secs.unwrap_or_default(),
+            timeout_ms: rr.timeout_ms,
+            ..Default::default()
+        };
         Some(RerankerConfig {
             provider,
             model: rr.model.unwrap_or_default(),
             api_key: rr.api_key,
+            cache,
             ..Default::default()
         })
     } else {
@@ -187,13 +263,32 @@ pub async fn configure(
         embedder: embedder_config,
         vector_store: vector_store_config,
         graph_store: graph_store_config,
+        graph_search: request.graph_search.unwrap_or_default(),
         reranker: reranker_config,
         history_db_path: PathBuf::from(".rook/history.db"),
+        blind_index: request.blind_index,
+        content_encryption: request.content_encryption,
+        global_knowledge: request.global_knowledge.unwrap_or_default(),
+        sync: request.sync.unwrap_or_default(),
+        versioning: request.versioning.unwrap_or_default(),
+        cognitive: request.cognitive.unwrap_or_default(),
+        archive: request.archive.unwrap_or_default(),
+        semantic_promotion: request.semantic_promotion.unwrap_or_default(),
+        replay: request.replay.unwrap_or_default(),
+        metadata_schema: request.metadata_schema.unwrap_or_default(),
         ..Default::default()
     };
 
     // Configure memory
     state.configure(config).await?;
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Configure, "config").with_details(
+            serde_json::json!({
+                "collection_name": collection_name,
+                "embedding_dims": embedding_dims,
+            }),
+        ),
+    );
 
     Ok(Json(ConfigureResponse {
         message: "Memory configured successfully".to_string(),
@@ -202,15 +297,34 @@ pub async fn configure(
 }
 
 /// Response for reset.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ResetResponse {
     pub message: String,
 }
 
 /// Reset memory.
 /// POST /reset
-pub async fn reset(State(state): State<AppState>) -> ApiResult<Json<ResetResponse>> {
+#[utoipa::path(
+    post,
+    path = "/reset",
+    tag = "config",
+    responses(
+        (status = 200, description = "Memory reset", body = ResetResponse),
+    )
+)]
+pub async fn reset(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<ResetResponse>> {
+    tenant.require_shared()?;
+
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Reset))
+        .await?;
+
     state.reset().await?;
+    state.audit(NewAuditEntry::new(subject.as_str(), AuditAction::Reset, "config"));
 
     Ok(Json(ResetResponse {
         message: "Memory reset successfully".to_string(),
@@ -266,6 +380,8 @@ fn parse_vector_store_provider(provider: &str) -> ApiResult<VectorStoreProvider>
         "chroma" => Ok(VectorStoreProvider::Chroma),
         "milvus" => Ok(VectorStoreProvider::Milvus),
         "upstash" => Ok(VectorStoreProvider::UpstashVector),
+        "turbopuffer" => Ok(VectorStoreProvider::Turbopuffer),
+        "typesense" => Ok(VectorStoreProvider::Typesense),
         "azure" | "azure_ai_search" => Ok(VectorStoreProvider::AzureAiSearch),
         "vertex" | "vertex_ai" => Ok(VectorStoreProvider::VertexAiVectorSearch),
         "supabase" => Ok(VectorStoreProvider::Supabase),
@@ -301,8 +417,10 @@ fn parse_reranker_provider(provider: &str) -> ApiResult<RerankerProvider> {
         "llm" => Ok(RerankerProvider::Llm),
         "huggingface" => Ok(RerankerProvider::HuggingFace),
         "sentence_transformer" => Ok(RerankerProvider::SentenceTransformer),
+        "jina" => Ok(RerankerProvider::Jina),
+        "voyage" => Ok(RerankerProvider::Voyage),
         _ => Err(ApiError::validation(format!(
-            "Unknown reranker provider: {}. Supported: cohere, llm, huggingface, sentence_transformer",
+            "Unknown reranker provider: {}. Supported: cohere, llm, huggingface, sentence_transformer, jina, voyage",
             provider
         ))),
     }
@@ -0,0 +1,227 @@
+//! Live memory lifecycle event stream.
+
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::Stream;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::events::MemoryLifecycleEvent;
+
+/// Query parameters for the event stream.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventStreamQuery {
+    /// Only events about this user's memories.
+    pub user_id: Option<String>,
+    /// Comma-separated event types (e.g. `memory.created,memory.deleted`).
+    /// All memory events when omitted.
+    pub events: Option<String>,
+}
+
+/// Which events a stream delivers.
+#[derive(Debug, Clone)]
+struct EventFilter {
+    user_id: Option<String>,
+    event_types: HashSet<String>,
+}
+
+impl EventFilter {
+    fn matches(&self, event: &MemoryLifecycleEvent) -> bool {
+        // Operational alerts are not memory events
+        if matches!(event, MemoryLifecycleEvent::Alert(_)) {
+            return false;
+        }
+        if let Some(ref user_id) = self.user_id {
+            if event.user_id() != Some(user_id.as_str()) {
+                return false;
+            }
+        }
+        self.event_types.is_empty() || self.event_types.contains(event.event_type())
+    }
+}
+
+/// Stream memory lifecycle events as Server-Sent Events.
+/// GET /events/stream
+///
+/// Each SSE event is named after the event type (e.g. `memory.created`) and
+/// carries the event as JSON. Events emitted while the client is
+/// disconnected are not replayed.
+#[utoipa::path(
+    get,
+    path = "/events/stream",
+    tag = "events",
+    params(EventStreamQuery),
+    responses(
+        (status = 200, description = "Server-Sent Events stream", body = String, content_type = "text/event-stream"),
+    )
+)]
+pub async fn stream_events(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<EventStreamQuery>,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    // Only the server's own memory publishes on the event bus
+    tenant.require_shared()?;
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+                query.user_id.clone(),
+                None,
+                None,
+            ),
+        )
+        .await?;
+
+    let event_bus = state
+        .event_bus()
+        .ok_or_else(|| ApiError::bad_request("Event streaming is not enabled on this server"))?;
+
+    let filter = EventFilter {
+        user_id: query.user_id,
+        event_types: query
+            .events
+            .iter()
+            .flat_map(|events| events.split(','))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+    };
+
+    let stream = futures::stream::unfold(event_bus.subscribe(), move |mut subscriber| {
+        let filter = filter.clone();
+        async move {
+            loop {
+                let event = subscriber.recv().await?;
+                if filter.matches(&event) {
+                    let sse = Event::default()
+                        .event(event.event_type())
+                        .json_data(&event);
+                    return Some((sse, subscriber));
+                }
+            }
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use futures::StreamExt;
+    use rook_core::events::{
+        AlertEvent, AlertKind, AlertSeverity, EventBus, MemoryCreatedEvent, MemoryDeletedEvent,
+    };
+
+    use super::*;
+
+    fn created(memory_id: &str, user_id: &str) -> MemoryLifecycleEvent {
+        MemoryLifecycleEvent::Created(MemoryCreatedEvent::new(memory_id, "text").with_user(user_id))
+    }
+
+    fn deleted(memory_id: &str, user_id: &str) -> MemoryLifecycleEvent {
+        MemoryLifecycleEvent::Deleted(MemoryDeletedEvent::new(memory_id, false).with_user(user_id))
+    }
+
+    fn alert() -> MemoryLifecycleEvent {
+        MemoryLifecycleEvent::Alert(AlertEvent::new(
+            AlertSeverity::Critical,
+            AlertKind::ProviderErrorRate {
+                provider: "llm".to_string(),
+                error_rate: 0.5,
+                threshold: 0.1,
+                samples: 10,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_filter_matches_user_and_event_types() {
+        let all = EventFilter {
+            user_id: None,
+            event_types: HashSet::new(),
+        };
+        assert!(all.matches(&created("m1", "alice")));
+        assert!(all.matches(&deleted("m1", "bob")));
+        assert!(!all.matches(&alert()));
+
+        let filter = EventFilter {
+            user_id: Some("alice".to_string()),
+            event_types: HashSet::from(["memory.created".to_string()]),
+        };
+        assert!(filter.matches(&created("m1", "alice")));
+        assert!(!filter.matches(&created("m1", "bob")));
+        assert!(!filter.matches(&deleted("m1", "alice")));
+        assert!(!filter.matches(&alert()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_matching_events() {
+        let bus = EventBus::new();
+        let state = AppState::new().with_event_bus(bus.clone());
+        let query = EventStreamQuery {
+            user_id: Some("alice".to_string()),
+            events: Some("memory.created, memory.updated".to_string()),
+        };
+        let sse = stream_events(
+            State(state),
+            Subject("admin".to_string()),
+            Tenant(None),
+            Query(query),
+        )
+        .await
+        .unwrap();
+        let mut body = sse.into_response().into_body().into_data_stream();
+
+        bus.emit(created("m1", "bob"));
+        bus.emit(deleted("m2", "alice"));
+        bus.emit(created("m3", "alice"));
+
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(chunk.to_vec()).unwrap();
+        assert!(text.starts_with("event: memory.created\n"), "{}", text);
+        assert!(text.contains(r#""memory_id":"m3""#), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_stream_needs_a_shared_event_bus() {
+        let open = |state: AppState, tenant: Tenant| {
+            let query = EventStreamQuery {
+                user_id: None,
+                events: None,
+            };
+            stream_events(
+                State(state),
+                Subject("admin".to_string()),
+                tenant,
+                Query(query),
+            )
+        };
+
+        let result = open(AppState::new(), Tenant(None)).await;
+        assert!(matches!(result, Err(e) if e.status == StatusCode::BAD_REQUEST));
+
+        let state = AppState::new().with_event_bus(EventBus::new());
+        let result = open(state, Tenant(Some("acme".to_string()))).await;
+        assert!(matches!(result, Err(e) if e.status == StatusCode::FORBIDDEN));
+    }
+}
@@ -0,0 +1,235 @@
+//! Global knowledge base endpoints.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::{PromotionRecord, PromotionStatus};
+use rook_core::types::MemoryItem;
+
+use super::memories::authorize_memory;
+
+/// Request body for proposing a promotion.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProposePromotionRequest {
+    /// Memory to promote.
+    pub memory_id: String,
+    /// Why the memory should be global.
+    pub note: Option<String>,
+}
+
+/// Propose promoting a memory into the global knowledge base.
+/// POST /global/promotions
+#[utoipa::path(
+    post,
+    path = "/global/promotions",
+    tag = "global",
+    request_body = ProposePromotionRequest,
+    responses(
+        (status = 200, description = "The pending promotion", body = Object),
+    )
+)]
+pub async fn propose_promotion(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<ProposePromotionRequest>,
+) -> ApiResult<Json<PromotionRecord>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let requested_by = subject.as_str().to_string();
+    let record = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        authorize_memory(&state, &memory, subject, AuthzAction::Get, &request.memory_id).await?;
+
+        memory
+            .propose_promotion(&request.memory_id, &requested_by, request.note)
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(record))
+}
+
+/// Query parameters for listing promotions.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListPromotionsQuery {
+    /// `pending`, `approved` or `rejected`.
+    pub status: Option<String>,
+}
+
+/// Response for listing promotions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListPromotionsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub promotions: Vec<PromotionRecord>,
+}
+
+/// List promotions, newest first.
+/// GET /global/promotions
+#[utoipa::path(
+    get,
+    path = "/global/promotions",
+    tag = "global",
+    params(ListPromotionsQuery),
+    responses(
+        (status = 200, description = "Promotions, newest first", body = ListPromotionsResponse),
+    )
+)]
+pub async fn list_promotions(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ListPromotionsQuery>,
+) -> ApiResult<Json<ListPromotionsResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let status = query
+        .status
+        .as_deref()
+        .map(PromotionStatus::parse)
+        .transpose()
+        .map_err(ApiError::from)?;
+
+    let promotions = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory.list_promotions(status).await.map_err(ApiError::from)?
+    };
+
+    Ok(Json(ListPromotionsResponse { promotions }))
+}
+
+/// Request body for reviewing a promotion.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewPromotionRequest {
+    /// Approve (true) or reject (false).
+    pub approve: bool,
+    /// Reviewer's comment.
+    pub note: Option<String>,
+}
+
+/// Approve or reject a pending promotion.
+/// POST /global/promotions/:id/review
+#[utoipa::path(
+    post,
+    path = "/global/promotions/{id}/review",
+    tag = "global",
+    params(("id" = String, Path, description = "Promotion ID")),
+    request_body = ReviewPromotionRequest,
+    responses(
+        (status = 200, description = "The reviewed promotion", body = Object),
+    )
+)]
+pub async fn review_promotion(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(promotion_id): Path<String>,
+    Json(request): Json<ReviewPromotionRequest>,
+) -> ApiResult<Json<PromotionRecord>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let reviewer = subject.as_str().to_string();
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let record = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory
+            .review_promotion(&promotion_id, &reviewer, request.approve, request.note)
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(record))
+}
+
+/// Query parameters for listing global memories.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetGlobalQuery {
+    pub limit: Option<usize>,
+}
+
+/// Response for listing global memories.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GetGlobalResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub results: Vec<MemoryItem>,
+}
+
+/// List memories in the global knowledge base.
+/// GET /global/memories
+#[utoipa::path(
+    get,
+    path = "/global/memories",
+    tag = "global",
+    params(GetGlobalQuery),
+    responses(
+        (status = 200, description = "Global memories", body = GetGlobalResponse),
+    )
+)]
+pub async fn get_global_memories(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<GetGlobalQuery>,
+) -> ApiResult<Json<GetGlobalResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::List))
+        .await?;
+
+    let results = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory.get_global(query.limit).await.map_err(ApiError::from)?
+    };
+
+    Ok(Json(GetGlobalResponse { results }))
+}
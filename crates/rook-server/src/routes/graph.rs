@@ -0,0 +1,389 @@
+//! Knowledge graph endpoints.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::SessionScope;
+use rook_core::traits::{Entity, GraphFilters, GraphPath, GraphStore};
+use rook_core::types::GraphRelation;
+
+/// Scope query parameters shared by graph routes.
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScopeQuery {
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+impl ScopeQuery {
+    fn authz(&self, subject: String, action: AuthzAction) -> AuthzRequest {
+        AuthzRequest::new(subject, action).with_scope(
+            self.user_id.clone(),
+            self.agent_id.clone(),
+            self.run_id.clone(),
+        )
+    }
+
+    /// Graph filters for the scope. At least one ID is required.
+    fn filters(&self) -> ApiResult<GraphFilters> {
+        SessionScope::new(self.user_id.clone(), self.agent_id.clone(), self.run_id.clone())
+            .validate()?;
+        Ok(GraphFilters {
+            user_id: self.user_id.clone(),
+            agent_id: self.agent_id.clone(),
+            run_id: self.run_id.clone(),
+        })
+    }
+}
+
+/// Response listing entities.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EntitiesResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub entities: Vec<Entity>,
+}
+
+/// Request body for adding an entity.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddEntityRequest {
+    pub name: String,
+    pub entity_type: String,
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub scope: ScopeQuery,
+}
+
+/// Response for adding an entity or relationship.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedResponse {
+    pub id: i64,
+}
+
+/// Request body for adding a relationship.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddRelationshipRequest {
+    pub source: String,
+    pub target: String,
+    pub relationship_type: String,
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+    /// Replace other current relationships of this type from `source`.
+    #[serde(default)]
+    pub supersede: bool,
+    #[serde(flatten)]
+    pub scope: ScopeQuery,
+}
+
+/// Query parameters for invalidating a relationship.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeleteRelationshipQuery {
+    pub source: String,
+    pub target: String,
+    pub relationship_type: String,
+    #[serde(flatten)]
+    #[param(ignore)]
+    pub scope: ScopeQuery,
+}
+
+/// Response for invalidating a relationship.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteRelationshipResponse {
+    /// Whether a currently valid relationship was found.
+    pub invalidated: bool,
+}
+
+/// Query parameters for neighbor traversal.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NeighborsQuery {
+    /// Hops to follow (default: 1).
+    pub depth: Option<usize>,
+    /// Comma-separated relationship types to follow (default: all).
+    pub relationships: Option<String>,
+    // Scope fields are listed here rather than flattened: flattened query
+    // strings cannot carry the numeric `depth`.
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// Response for neighbor traversal.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NeighborsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub paths: Vec<GraphPath>,
+}
+
+/// Request body for graph search.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GraphSearchRequest {
+    pub query: String,
+    /// Maximum number of relations (default: 20).
+    pub limit: Option<usize>,
+    #[serde(flatten)]
+    pub scope: ScopeQuery,
+}
+
+/// Response for graph search.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GraphSearchResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub relations: Vec<GraphRelation>,
+}
+
+/// The graph store of the tenant's memory.
+async fn graph_store(state: &AppState, tenant: &Tenant) -> ApiResult<Arc<dyn GraphStore>> {
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured. Call /configure first."))?;
+    memory
+        .graph_store()
+        .ok_or_else(|| ApiError::bad_request("No graph store configured"))
+}
+
+/// List entities in a scope.
+/// GET /graph/entities
+#[utoipa::path(
+    get,
+    path = "/graph/entities",
+    tag = "graph",
+    params(ScopeQuery),
+    responses(
+        (status = 200, description = "Entities in the scope", body = EntitiesResponse),
+    )
+)]
+pub async fn list_entities(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ScopeQuery>,
+) -> ApiResult<Json<EntitiesResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(query.authz(subject.0, AuthzAction::List)).await?;
+
+    let entities = graph.get_all(&query.filters()?).await?;
+    Ok(Json(EntitiesResponse { entities }))
+}
+
+/// Add an entity.
+/// POST /graph/entities
+#[utoipa::path(
+    post,
+    path = "/graph/entities",
+    tag = "graph",
+    request_body = AddEntityRequest,
+    responses(
+        (status = 200, description = "The entity ID", body = CreatedResponse),
+    )
+)]
+pub async fn add_entity(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<AddEntityRequest>,
+) -> ApiResult<Json<CreatedResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(request.scope.authz(subject.0, AuthzAction::Add)).await?;
+
+    if request.name.trim().is_empty() {
+        return Err(ApiError::validation("Entity name must not be empty"));
+    }
+    let properties = request.properties.unwrap_or_else(|| serde_json::json!({}));
+    let id = graph
+        .add_entity(&request.name, &request.entity_type, &properties, &request.scope.filters()?)
+        .await?;
+    Ok(Json(CreatedResponse { id }))
+}
+
+/// Delete every entity and relationship in a scope.
+/// DELETE /graph/entities
+#[utoipa::path(
+    delete,
+    path = "/graph/entities",
+    tag = "graph",
+    params(ScopeQuery),
+    responses(
+        (status = 200, description = "Graph data deleted", body = Object),
+    )
+)]
+pub async fn delete_entities(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ScopeQuery>,
+) -> ApiResult<Json<serde_json::Value>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(query.authz(subject.0, AuthzAction::DeleteAll)).await?;
+
+    graph.delete_all(&query.filters()?).await?;
+    Ok(Json(serde_json::json!({
+        "message": "Graph data deleted successfully"
+    })))
+}
+
+/// Add a relationship, creating its entities if needed.
+/// POST /graph/relationships
+#[utoipa::path(
+    post,
+    path = "/graph/relationships",
+    tag = "graph",
+    request_body = AddRelationshipRequest,
+    responses(
+        (status = 200, description = "The relationship ID", body = CreatedResponse),
+    )
+)]
+pub async fn add_relationship(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<AddRelationshipRequest>,
+) -> ApiResult<Json<CreatedResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(request.scope.authz(subject.0, AuthzAction::Add)).await?;
+
+    let filters = request.scope.filters()?;
+    let properties = request.properties.unwrap_or_else(|| serde_json::json!({}));
+    let id = if request.supersede {
+        graph
+            .supersede_relationship(
+                &request.source,
+                &request.target,
+                &request.relationship_type,
+                &properties,
+                &filters,
+            )
+            .await?
+    } else {
+        graph
+            .add_relationship(
+                &request.source,
+                &request.target,
+                &request.relationship_type,
+                &properties,
+                &filters,
+            )
+            .await?
+    };
+    Ok(Json(CreatedResponse { id }))
+}
+
+/// Mark a relationship as no longer valid.
+/// DELETE /graph/relationships
+#[utoipa::path(
+    delete,
+    path = "/graph/relationships",
+    tag = "graph",
+    params(
+        DeleteRelationshipQuery,
+        ScopeQuery,
+    ),
+    responses(
+        (status = 200, description = "Whether a relationship was invalidated", body = DeleteRelationshipResponse),
+    )
+)]
+pub async fn delete_relationship(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<DeleteRelationshipQuery>,
+) -> ApiResult<Json<DeleteRelationshipResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(query.scope.authz(subject.0, AuthzAction::Delete)).await?;
+
+    let invalidated = graph
+        .invalidate_relationship(
+            &query.source,
+            &query.target,
+            &query.relationship_type,
+            &query.scope.filters()?,
+        )
+        .await?;
+    Ok(Json(DeleteRelationshipResponse { invalidated }))
+}
+
+/// Entities reachable from an entity.
+/// GET /graph/neighbors/:name
+#[utoipa::path(
+    get,
+    path = "/graph/neighbors/{name}",
+    tag = "graph",
+    params(
+        ("name" = String, Path, description = "Entity name"),
+        NeighborsQuery,
+    ),
+    responses(
+        (status = 200, description = "Paths to reachable entities", body = NeighborsResponse),
+    )
+)]
+pub async fn get_neighbors(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(name): Path<String>,
+    Query(query): Query<NeighborsQuery>,
+) -> ApiResult<Json<NeighborsResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    let scope = ScopeQuery {
+        user_id: query.user_id,
+        agent_id: query.agent_id,
+        run_id: query.run_id,
+    };
+    state.authorize(scope.authz(subject.0, AuthzAction::Get)).await?;
+
+    let relationships: Option<Vec<String>> = query.relationships.as_deref().map(|s| {
+        s.split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+    let paths = graph
+        .traverse(
+            &name,
+            query.depth.unwrap_or(1),
+            relationships.as_deref(),
+            &scope.filters()?,
+        )
+        .await?;
+    Ok(Json(NeighborsResponse { paths }))
+}
+
+/// Search relations connected to entities named in a query.
+/// POST /graph/search
+#[utoipa::path(
+    post,
+    path = "/graph/search",
+    tag = "graph",
+    request_body = GraphSearchRequest,
+    responses(
+        (status = 200, description = "Matching relations", body = GraphSearchResponse),
+    )
+)]
+pub async fn search_graph(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<GraphSearchRequest>,
+) -> ApiResult<Json<GraphSearchResponse>> {
+    let graph = graph_store(&state, &tenant).await?;
+    state.authorize(request.scope.authz(subject.0, AuthzAction::Search)).await?;
+
+    let relations = graph
+        .search(&request.query, &request.scope.filters()?, request.limit.unwrap_or(20))
+        .await?;
+    Ok(Json(GraphSearchResponse { relations }))
+}
@@ -0,0 +1,166 @@
+//! Smart ingest endpoint.
+//!
+//! Adds content through prediction-error gating: duplicates are skipped,
+//! refinements update and contradictions supersede the memory they relate
+//! to, and only novel content creates a new memory.
+
+use std::collections::HashMap;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::{DetectionLayer, IngestDecision, IngestResult};
+
+/// Request body for smart ingest.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SmartIngestRequest {
+    /// The content to ingest.
+    pub content: String,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Response for smart ingest.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SmartIngestResponse {
+    /// `skip`, `create`, `update` or `supersede`.
+    pub decision: String,
+    /// Prediction error, from 0.0 (fully expected) to 1.0 (novel).
+    pub surprise: f32,
+    /// Detection layer that decided: `embedding_similarity`,
+    /// `keyword_pattern`, `temporal_conflict`, `semantic_llm` or `default`.
+    pub layer: String,
+    /// Memory created or updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_id: Option<String>,
+    /// Existing memory the content duplicates, refines or supersedes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_memory_id: Option<String>,
+    /// Content of the memory before an update or supersede.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Ingest content with prediction-error gating.
+/// POST /smart_ingest
+#[utoipa::path(
+    post,
+    path = "/smart_ingest",
+    tag = "memories",
+    request_body = SmartIngestRequest,
+    responses(
+        (status = 200, description = "The gating decision", body = SmartIngestResponse),
+    )
+)]
+pub async fn smart_ingest(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<SmartIngestRequest>,
+) -> ApiResult<Json<SmartIngestResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+    if request.content.trim().is_empty() {
+        return Err(ApiError::bad_request("content must not be empty"));
+    }
+
+    // Updates and supersedes only touch memories in the same scope
+    state
+        .authorize(
+            AuthzRequest::new(subject.as_str(), AuthzAction::Add)
+                .with_scope(
+                    request.user_id.clone(),
+                    request.agent_id.clone(),
+                    request.run_id.clone(),
+                )
+                .with_metadata(request.metadata.clone().unwrap_or_default()),
+        )
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let result = memory
+        .smart_ingest(
+            &request.content,
+            request.user_id,
+            request.agent_id,
+            request.run_id,
+            request.metadata,
+        )
+        .await?;
+    audit_ingest(&state, &subject, &tenant, &request.content, &result);
+
+    Ok(Json(SmartIngestResponse {
+        decision: decision_to_string(result.decision),
+        surprise: result.surprise,
+        layer: layer_to_string(result.decided_at_layer),
+        memory_id: result.memory_id,
+        related_memory_id: result.related_memory_id,
+        previous_content: result.previous_content,
+        reason: result.reason,
+    }))
+}
+
+/// Record the memory created or changed by an ingest.
+pub(crate) fn audit_ingest(
+    state: &AppState,
+    subject: &Subject,
+    tenant: &Tenant,
+    content: &str,
+    result: &IngestResult,
+) {
+    let action = match result.decision {
+        IngestDecision::Skip => return,
+        IngestDecision::Create | IngestDecision::Supersede => AuditAction::Create,
+        IngestDecision::Update => AuditAction::Update,
+    };
+    let Some(ref memory_id) = result.memory_id else {
+        return;
+    };
+
+    let mut entry = NewAuditEntry::new(subject.as_str(), action, "memory")
+        .with_resource_id(memory_id)
+        .with_org_id(tenant.0.clone())
+        .with_content(result.previous_content.as_deref(), Some(content));
+    if result.decision == IngestDecision::Supersede {
+        entry = entry.with_details(serde_json::json!({
+            "supersedes": result.related_memory_id,
+        }));
+    }
+    state.audit(entry);
+}
+
+pub(crate) fn decision_to_string(decision: IngestDecision) -> String {
+    match decision {
+        IngestDecision::Skip => "skip".to_string(),
+        IngestDecision::Create => "create".to_string(),
+        IngestDecision::Update => "update".to_string(),
+        IngestDecision::Supersede => "supersede".to_string(),
+    }
+}
+
+pub(crate) fn layer_to_string(layer: DetectionLayer) -> String {
+    match layer {
+        DetectionLayer::EmbeddingSimilarity => "embedding_similarity".to_string(),
+        DetectionLayer::KeywordPattern => "keyword_pattern".to_string(),
+        DetectionLayer::TemporalConflict => "temporal_conflict".to_string(),
+        DetectionLayer::SemanticLlm => "semantic_llm".to_string(),
+        DetectionLayer::Default => "default".to_string(),
+    }
+}
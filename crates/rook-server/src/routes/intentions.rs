@@ -0,0 +1,483 @@
+//! Intention management endpoints.
+//!
+//! Intentions live in the background runtime's intention store. Time-based
+//! triggers are scheduled on the runtime's intention scheduler as they are
+//! created, updated or deleted.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::intentions::{
+    upcoming_fires, FiredIntention, Intention, IntentionAction, IntentionStore, TriggerCondition,
+    UpcomingFire,
+};
+use rook_core::BackgroundRuntime;
+
+/// Request body for creating an intention.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateIntentionRequest {
+    pub name: String,
+    #[schema(value_type = Object)]
+    pub trigger: TriggerCondition,
+    /// Defaults to surfacing the associated memory.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub action: IntentionAction,
+    pub user_id: Option<String>,
+    pub memory_id: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_fires: Option<u32>,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Request body for updating an intention. Omitted fields are unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateIntentionRequest {
+    pub name: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub trigger: Option<TriggerCondition>,
+    #[schema(value_type = Option<Object>)]
+    pub action: Option<IntentionAction>,
+    pub active: Option<bool>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_fires: Option<u32>,
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+}
+
+/// Query parameters for listing intentions.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListIntentionsQuery {
+    pub user_id: Option<String>,
+    pub memory_id: Option<String>,
+    /// Only intentions that are (or are not) active.
+    pub active: Option<bool>,
+}
+
+/// Response for listing intentions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntentionsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub intentions: Vec<Intention>,
+}
+
+/// Query parameters for the fired intentions feed.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FiredIntentionsQuery {
+    /// Fires of one intention.
+    pub intention_id: Option<Uuid>,
+    /// Fires of one user's intentions.
+    pub user_id: Option<String>,
+    /// Only fires after this time, for polling.
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum fires to return (default: 50).
+    pub limit: Option<usize>,
+}
+
+/// Response for the fired intentions feed.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FiredIntentionsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub fired: Vec<FiredIntention>,
+}
+
+/// Query parameters for upcoming fires.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UpcomingFiresQuery {
+    /// Fires of one user's intentions.
+    pub user_id: Option<String>,
+    /// Maximum fires to return (default: 20).
+    pub limit: Option<usize>,
+}
+
+/// Response for upcoming fires.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UpcomingFiresResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub upcoming: Vec<UpcomingFire>,
+}
+
+/// Response for deleting an intention.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteIntentionResponse {
+    pub message: String,
+}
+
+/// Create an intention.
+/// POST /intentions
+#[utoipa::path(
+    post,
+    path = "/intentions",
+    tag = "intentions",
+    request_body = CreateIntentionRequest,
+    responses(
+        (status = 200, description = "The created intention", body = Object),
+    )
+)]
+pub async fn create_intention(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<CreateIntentionRequest>,
+) -> ApiResult<Json<Intention>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::Add).with_scope(
+                request.user_id.clone(),
+                None,
+                None,
+            ),
+        )
+        .await?;
+
+    if request.name.trim().is_empty() {
+        return Err(ApiError::bad_request("Intention name is required"));
+    }
+    request.trigger.validate()?;
+    request.action.validate()?;
+
+    let mut intention = Intention::new(request.name, request.trigger, request.action);
+    intention.user_id = request.user_id;
+    intention.memory_id = request.memory_id;
+    intention.expires_at = request.expires_at;
+    intention.max_fires = request.max_fires;
+    intention.metadata = request.metadata;
+
+    let runtime = runtime.read().await;
+    let store = runtime.intention_store();
+    store.add(&intention)?;
+    if let Some(scheduler) = runtime.intention_scheduler() {
+        // Don't keep an intention whose schedule was rejected (e.g. bad cron)
+        if let Err(e) = scheduler.schedule(&intention).await {
+            store.delete(intention.id)?;
+            return Err(ApiError::bad_request(e.to_string()));
+        }
+    }
+
+    Ok(Json(intention))
+}
+
+/// List intentions.
+/// GET /intentions
+///
+/// Without a `user_id` or `memory_id` filter only active intentions are
+/// listed.
+#[utoipa::path(
+    get,
+    path = "/intentions",
+    tag = "intentions",
+    params(ListIntentionsQuery),
+    responses(
+        (status = 200, description = "Matching intentions", body = IntentionsResponse),
+    )
+)]
+pub async fn list_intentions(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ListIntentionsQuery>,
+) -> ApiResult<Json<IntentionsResponse>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+                query.user_id.clone(),
+                None,
+                None,
+            ),
+        )
+        .await?;
+
+    let store = runtime.read().await.intention_store();
+    let mut intentions = match (&query.user_id, &query.memory_id) {
+        (Some(user_id), _) => store.get_for_user(user_id)?,
+        (None, Some(memory_id)) => store.get_for_memory(memory_id)?,
+        (None, None) => store.get_active()?,
+    };
+    if let (Some(_), Some(memory_id)) = (&query.user_id, &query.memory_id) {
+        intentions.retain(|i| i.memory_id.as_ref() == Some(memory_id));
+    }
+    if let Some(active) = query.active {
+        intentions.retain(|i| i.active == active);
+    }
+
+    Ok(Json(IntentionsResponse { intentions }))
+}
+
+/// Get an intention by ID.
+/// GET /intentions/:id
+#[utoipa::path(
+    get,
+    path = "/intentions/{id}",
+    tag = "intentions",
+    params(("id" = String, Path, description = "Intention ID")),
+    responses(
+        (status = 200, description = "The intention", body = Object),
+    )
+)]
+pub async fn get_intention(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Intention>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+    let store = runtime.read().await.intention_store();
+    let intention = load_intention(&state, subject, AuthzAction::Get, store.as_ref(), &id).await?;
+    Ok(Json(intention))
+}
+
+/// Update an intention.
+/// PUT /intentions/:id
+///
+/// A changed trigger is rescheduled; deactivating an intention unschedules
+/// it.
+#[utoipa::path(
+    put,
+    path = "/intentions/{id}",
+    tag = "intentions",
+    params(("id" = String, Path, description = "Intention ID")),
+    request_body = UpdateIntentionRequest,
+    responses(
+        (status = 200, description = "The updated intention", body = Object),
+    )
+)]
+pub async fn update_intention(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateIntentionRequest>,
+) -> ApiResult<Json<Intention>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+    let runtime = runtime.read().await;
+    let store = runtime.intention_store();
+    let mut intention =
+        load_intention(&state, subject, AuthzAction::Update, store.as_ref(), &id).await?;
+
+    if let Some(name) = request.name {
+        if name.trim().is_empty() {
+            return Err(ApiError::bad_request("Intention name is required"));
+        }
+        intention.name = name;
+    }
+    if let Some(trigger) = request.trigger {
+        trigger.validate()?;
+        intention.trigger = trigger;
+    }
+    if let Some(action) = request.action {
+        action.validate()?;
+        intention.action = action;
+    }
+    if let Some(active) = request.active {
+        intention.active = active;
+    }
+    if request.expires_at.is_some() {
+        intention.expires_at = request.expires_at;
+    }
+    if request.max_fires.is_some() {
+        intention.max_fires = request.max_fires;
+    }
+    if let Some(metadata) = request.metadata {
+        intention.metadata = metadata;
+    }
+
+    if let Some(scheduler) = runtime.intention_scheduler() {
+        scheduler.unschedule(intention.id).await?;
+        if intention.can_fire() {
+            scheduler
+                .schedule(&intention)
+                .await
+                .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        }
+    }
+    store.update(&intention)?;
+
+    Ok(Json(intention))
+}
+
+/// Delete an intention.
+/// DELETE /intentions/:id
+#[utoipa::path(
+    delete,
+    path = "/intentions/{id}",
+    tag = "intentions",
+    params(("id" = String, Path, description = "Intention ID")),
+    responses(
+        (status = 200, description = "Intention deleted", body = DeleteIntentionResponse),
+    )
+)]
+pub async fn delete_intention(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<DeleteIntentionResponse>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+    let runtime = runtime.read().await;
+    let store = runtime.intention_store();
+    let intention =
+        load_intention(&state, subject, AuthzAction::Delete, store.as_ref(), &id).await?;
+
+    if let Some(scheduler) = runtime.intention_scheduler() {
+        scheduler.unschedule(intention.id).await?;
+    }
+    store.delete(intention.id)?;
+
+    Ok(Json(DeleteIntentionResponse {
+        message: format!("Intention {} deleted", intention.id),
+    }))
+}
+
+/// Recently fired intentions, newest first.
+/// GET /intentions/fired
+#[utoipa::path(
+    get,
+    path = "/intentions/fired",
+    tag = "intentions",
+    params(FiredIntentionsQuery),
+    responses(
+        (status = 200, description = "Fired intentions, newest first", body = FiredIntentionsResponse),
+    )
+)]
+pub async fn list_fired_intentions(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<FiredIntentionsQuery>,
+) -> ApiResult<Json<FiredIntentionsResponse>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+    let store = runtime.read().await.intention_store();
+    let limit = query.limit.unwrap_or(50);
+
+    let fired = match query.intention_id {
+        Some(id) => {
+            let intention = load_intention(
+                &state,
+                subject,
+                AuthzAction::History,
+                store.as_ref(),
+                &id.to_string(),
+            )
+            .await?;
+            let mut fired = store.get_fire_history(intention.id, limit)?;
+            if let Some(since) = query.since {
+                fired.retain(|f| f.fired_at > since);
+            }
+            fired
+        }
+        None => {
+            state
+                .authorize(
+                    AuthzRequest::new(subject.0, AuthzAction::History).with_scope(
+                        query.user_id.clone(),
+                        None,
+                        None,
+                    ),
+                )
+                .await?;
+            store.get_recent_fires(query.user_id.as_deref(), query.since, limit)?
+        }
+    };
+
+    Ok(Json(FiredIntentionsResponse { fired }))
+}
+
+/// Upcoming fires of time-based intentions, soonest first.
+/// GET /intentions/upcoming
+///
+/// Covers active scheduled-time (including cron) and time-elapsed
+/// intentions.
+#[utoipa::path(
+    get,
+    path = "/intentions/upcoming",
+    tag = "intentions",
+    params(UpcomingFiresQuery),
+    responses(
+        (status = 200, description = "Upcoming fires, soonest first", body = UpcomingFiresResponse),
+    )
+)]
+pub async fn list_upcoming_fires(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<UpcomingFiresQuery>,
+) -> ApiResult<Json<UpcomingFiresResponse>> {
+    let runtime = intention_runtime(&state, &tenant)?;
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+                query.user_id.clone(),
+                None,
+                None,
+            ),
+        )
+        .await?;
+
+    let store = runtime.read().await.intention_store();
+    let mut intentions = store.get_by_trigger_type("scheduled_time")?;
+    intentions.extend(store.get_by_trigger_type("time_elapsed")?);
+    if let Some(ref user_id) = query.user_id {
+        intentions.retain(|i| i.user_id.as_ref() == Some(user_id));
+    }
+
+    let upcoming = upcoming_fires(&intentions, Utc::now(), query.limit.unwrap_or(20));
+    Ok(Json(UpcomingFiresResponse { upcoming }))
+}
+
+/// The background runtime holding the intention store. The store is shared
+/// by the whole server, so organizations cannot use it.
+fn intention_runtime(
+    state: &AppState,
+    tenant: &Tenant,
+) -> ApiResult<Arc<RwLock<BackgroundRuntime>>> {
+    tenant.require_shared()?;
+    state
+        .runtime()
+        .ok_or_else(|| ApiError::bad_request("Intentions require the background runtime"))
+}
+
+/// Load an intention and authorize `action` against its user scope.
+async fn load_intention(
+    state: &AppState,
+    subject: Subject,
+    action: AuthzAction,
+    store: &dyn IntentionStore,
+    id: &str,
+) -> ApiResult<Intention> {
+    let intention_id =
+        Uuid::parse_str(id).map_err(|_| ApiError::bad_request("Invalid intention ID"))?;
+    let intention = store
+        .get(intention_id)?
+        .ok_or_else(|| ApiError::not_found(format!("Intention {} not found", id)))?;
+
+    let mut request =
+        AuthzRequest::new(subject.0, action).with_scope(intention.user_id.clone(), None, None);
+    if let Some(ref memory_id) = intention.memory_id {
+        request = request.with_memory_id(memory_id.clone());
+    }
+    state.authorize(request).await?;
+
+    Ok(intention)
+}
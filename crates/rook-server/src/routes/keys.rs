@@ -0,0 +1,180 @@
+//! API key management endpoints.
+//!
+//! Keys created here authenticate requests when the server runs with
+//! `ROOK_REQUIRE_AUTH`. Each key has a role, and may be bound to a user and
+//! limited to a number of requests per minute.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{ApiKey, ApiKeyStore, AuthzAction, AuthzRequest, KeyRole, NewApiKey};
+
+/// Request body for creating an API key.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    /// `read_only`, `read_write`, `analytics` or `admin`.
+    #[schema(value_type = String)]
+    pub role: KeyRole,
+    /// Requests made with the key must be scoped to this user.
+    pub user_id: Option<String>,
+    /// Organization whose isolated memories the key works on. Organization
+    /// keys cannot have the `admin` or `analytics` role. Callers belonging
+    /// to an organization can only create keys for it.
+    pub org_id: Option<String>,
+    /// Requests allowed per minute. Unlimited when omitted.
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+/// Response for creating an API key.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    #[schema(value_type = Object)]
+    pub key: ApiKey,
+    /// The key's secret. It is only returned here.
+    pub secret: String,
+}
+
+/// Response for listing API keys.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeysResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub keys: Vec<ApiKey>,
+}
+
+/// Response for revoking an API key.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevokeApiKeyResponse {
+    pub message: String,
+}
+
+/// Create an API key.
+/// POST /keys
+#[utoipa::path(
+    post,
+    path = "/keys",
+    tag = "keys",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "The key and its secret", body = CreateApiKeyResponse),
+    )
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> ApiResult<Json<CreateApiKeyResponse>> {
+    let org_id = key_org_id(&tenant, request.org_id)?;
+    let store = authorized_store(&state, subject.clone()).await?;
+    let (key, secret) = store.create(NewApiKey {
+        name: request.name,
+        role: request.role,
+        user_id: request.user_id.filter(|s| !s.is_empty()),
+        org_id,
+        rate_limit_per_minute: request.rate_limit_per_minute,
+    })?;
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::KeyCreate, "api_key")
+            .with_resource_id(&key.id)
+            .with_org_id(key.org_id.clone())
+            .with_details(serde_json::json!({ "role": key.role, "user_id": key.user_id })),
+    );
+    Ok(Json(CreateApiKeyResponse { key, secret }))
+}
+
+/// List API keys, newest first. Revoked keys are included.
+/// GET /keys
+#[utoipa::path(
+    get,
+    path = "/keys",
+    tag = "keys",
+    responses(
+        (status = 200, description = "API keys", body = ApiKeysResponse),
+    )
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<ApiKeysResponse>> {
+    let store = key_store(&state, subject, &tenant).await?;
+    Ok(Json(ApiKeysResponse { keys: store.list()? }))
+}
+
+/// Revoke an API key.
+/// DELETE /keys/:id
+#[utoipa::path(
+    delete,
+    path = "/keys/{id}",
+    tag = "keys",
+    params(("id" = String, Path, description = "API key ID")),
+    responses(
+        (status = 200, description = "Key revoked", body = RevokeApiKeyResponse),
+    )
+)]
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<RevokeApiKeyResponse>> {
+    let store = key_store(&state, subject.clone(), &tenant).await?;
+    if !store.revoke(&id)? {
+        return Err(ApiError::not_found(format!("Active API key {} not found", id)));
+    }
+    state.forget_rate_limit(&id);
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::KeyRevoke, "api_key").with_resource_id(&id),
+    );
+
+    Ok(Json(RevokeApiKeyResponse {
+        message: format!("API key {} revoked", id),
+    }))
+}
+
+/// The organization a new key is for. Callers belonging to an organization
+/// get keys for it; asking for another organization is refused.
+fn key_org_id(tenant: &Tenant, requested: Option<String>) -> ApiResult<Option<String>> {
+    let requested = requested.filter(|s| !s.is_empty());
+    match tenant.org_id() {
+        Some(org_id) if requested.as_deref().is_some_and(|id| id != org_id) => {
+            Err(ApiError::forbidden(format!(
+                "Organization '{}' can only create keys for itself",
+                org_id
+            )))
+        }
+        Some(org_id) => Ok(Some(org_id.to_string())),
+        None => Ok(requested),
+    }
+}
+
+/// The key store, for listing and revoking the keys of every organization.
+async fn key_store(
+    state: &AppState,
+    subject: Subject,
+    tenant: &Tenant,
+) -> ApiResult<Arc<ApiKeyStore>> {
+    tenant.require_shared()?;
+    authorized_store(state, subject).await
+}
+
+/// The key store, once the subject is authorized to manage keys.
+async fn authorized_store(state: &AppState, subject: Subject) -> ApiResult<Arc<ApiKeyStore>> {
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+    state
+        .api_keys()
+        .ok_or_else(|| ApiError::bad_request("API keys are not enabled on this server"))
+}
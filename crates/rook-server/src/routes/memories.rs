@@ -7,13 +7,26 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
+use crate::authz::{Subject, Tenant};
 use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
-use rook_core::types::{MemoryEvent, MemoryItem, MemoryResult as CoreMemoryResult};
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::{
+    expiry_after, expiry_from_ttl, is_global, Memory, MergeStrategy, EXPIRES_AT_FIELD,
+};
+use rook_core::types::{
+    Cursor, FieldSelection, MemoryEvent, MemoryItem, MemoryResult as CoreMemoryResult,
+    PageRequest, TagCount,
+};
+use rook_core::versioning::MemoryVersion;
+
+use super::audit::audit_results;
 
 /// Request body for adding a memory.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AddMemoryRequest {
     /// The messages to process.
     pub messages: Vec<MessageInput>,
@@ -29,21 +42,38 @@ pub struct AddMemoryRequest {
     pub filters: Option<HashMap<String, serde_json::Value>>,
     /// Whether to include fact extraction.
     pub infer: Option<bool>,
+    /// Report existing memories at least this similar (0.0-1.0) as
+    /// `possible_duplicates`. The memory is added either way.
+    pub duplicate_threshold: Option<f32>,
+    /// Delete the added memories this many seconds from now (sets the
+    /// `expires_at` metadata field).
+    pub ttl_secs: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MessageInput {
     pub role: String,
     pub content: String,
 }
 
 /// Response for adding a memory.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AddMemoryResponse {
     pub results: Vec<MemoryResultItem>,
+    /// Existing memories similar to the added content, found before adding.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub possible_duplicates: Vec<DuplicateItem>,
+}
+
+/// An existing memory similar to added content.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateItem {
+    pub id: String,
+    pub memory: String,
+    pub score: f32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MemoryResultItem {
     pub id: String,
     pub memory: String,
@@ -68,8 +98,19 @@ impl From<CoreMemoryResult> for MemoryResultItem {
 
 /// Add a memory.
 /// POST /memories
+#[utoipa::path(
+    post,
+    path = "/memories",
+    tag = "memories",
+    request_body = AddMemoryRequest,
+    responses(
+        (status = 200, description = "Memories changed by the add", body = AddMemoryResponse),
+    )
+)]
 pub async fn add_memory(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<AddMemoryRequest>,
 ) -> ApiResult<Json<AddMemoryResponse>> {
     if !state.is_configured().await {
@@ -89,47 +130,132 @@ pub async fn add_memory(
     let user_id = request.user_id.clone();
     let agent_id = request.agent_id.clone();
     let run_id = request.run_id.clone();
-    let metadata = request.metadata.clone();
+    let mut metadata = request.metadata.clone();
+    if let Some(ttl_secs) = request.ttl_secs {
+        metadata
+            .get_or_insert_with(HashMap::new)
+            .insert(EXPIRES_AT_FIELD.to_string(), expiry_from_ttl(ttl_secs));
+    }
     let infer = request.infer.unwrap_or(true);
+    if let Some(threshold) = request.duplicate_threshold {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(ApiError::bad_request(
+                "duplicate_threshold must be between 0.0 and 1.0",
+            ));
+        }
+    }
 
-    let result = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+    state
+        .authorize(
+            AuthzRequest::new(subject.as_str(), AuthzAction::Add)
+                .with_scope(user_id.clone(), agent_id.clone(), run_id.clone())
+                .with_metadata(metadata.clone().unwrap_or_default()),
+        )
+        .await?;
+
+    let (result, duplicates) = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
-        memory
+        // Checked before adding so the new memory doesn't match itself
+        let duplicates = match request.duplicate_threshold {
+            Some(threshold) => {
+                memory
+                    .find_duplicates(
+                        &messages_str,
+                        user_id.clone(),
+                        agent_id.clone(),
+                        run_id.clone(),
+                        threshold,
+                    )
+                    .await?
+            }
+            None => Vec::new(),
+        };
+
+        let result = memory
             .add(messages_str, user_id, agent_id, run_id, metadata, infer, None)
             .await
-            .map_err(ApiError::from)?
+            .map_err(ApiError::from)?;
+        (result, duplicates)
     };
+    audit_results(&state, &subject, &tenant, &result.results);
 
     let response = AddMemoryResponse {
         results: result.results.into_iter().map(Into::into).collect(),
+        possible_duplicates: duplicates
+            .into_iter()
+            .map(|item| DuplicateItem {
+                id: item.id,
+                memory: item.memory,
+                score: item.score.unwrap_or(0.0),
+            })
+            .collect(),
     };
 
     Ok(Json(response))
 }
 
 /// Query parameters for getting memories.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetMemoriesQuery {
     pub user_id: Option<String>,
     pub agent_id: Option<String>,
     pub run_id: Option<String>,
+    /// Comma-separated fields to return per memory (`id` is always included).
+    #[param(value_type = Option<String>)]
+    pub fields: Option<FieldSelection>,
+    /// Order by a typed metadata field: `field` or `field:asc|desc`.
+    pub order_by: Option<String>,
+    /// Maximum memories to return, applied after ordering.
+    pub limit: Option<usize>,
+    /// Memories to skip, after the cursor if one is given.
+    pub offset: Option<usize>,
+    /// `next_cursor` of the previous page. Pages follow `(created_at, id)`
+    /// order, so cursors cannot be combined with `order_by`.
+    pub cursor: Option<String>,
+}
+
+/// Query parameters for getting a single memory.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetMemoryQuery {
+    /// Comma-separated fields to return (`id` is always included).
+    #[param(value_type = Option<String>)]
+    pub fields: Option<FieldSelection>,
 }
 
 /// Response for getting memories.
-#[derive(Debug, Serialize)]
+///
+/// Results are [`MemoryItem`]s, trimmed to the requested fields.
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GetMemoriesResponse {
-    pub results: Vec<MemoryItem>,
+    pub results: Vec<serde_json::Value>,
+    /// Memories in the scope, across all pages.
+    pub total: usize,
+    /// Cursor for the next page, if there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Get all memories.
 /// GET /memories
+#[utoipa::path(
+    get,
+    path = "/memories",
+    tag = "memories",
+    params(GetMemoriesQuery),
+    responses(
+        (status = 200, description = "Memories in the scope", body = GetMemoriesResponse),
+    )
+)]
 pub async fn get_all_memories(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Query(query): Query<GetMemoriesQuery>,
 ) -> ApiResult<Json<GetMemoriesResponse>> {
     if !state.is_configured().await {
@@ -138,28 +264,76 @@ pub async fn get_all_memories(
         ));
     }
 
-    let results = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+            query.user_id.clone(),
+            query.agent_id.clone(),
+            query.run_id.clone(),
+        ))
+        .await?;
+
+    let mut page = PageRequest::new().with_offset(query.offset.unwrap_or(0));
+    if let Some(limit) = query.limit {
+        page = page.with_limit(limit);
+    }
+    if let Some(ref cursor) = query.cursor {
+        page = page.with_cursor(Cursor::decode(cursor)?);
+    }
+
+    let page = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
         memory
-            .get_all(query.user_id, query.agent_id, query.run_id, None)
+            .get_page(
+                query.user_id,
+                query.agent_id,
+                query.run_id,
+                query.order_by.as_deref(),
+                &page,
+            )
             .await
             .map_err(ApiError::from)?
     };
 
-    Ok(Json(GetMemoriesResponse { results }))
+    let fields = query.fields.unwrap_or_default();
+    let results = page
+        .items
+        .iter()
+        .map(|item| fields.project(item))
+        .collect::<Result<_, _>>()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(GetMemoriesResponse {
+        results,
+        total: page.total,
+        next_cursor: page.next_cursor,
+    }))
 }
 
 /// Get a specific memory by ID.
 /// GET /memories/:id
+#[utoipa::path(
+    get,
+    path = "/memories/{id}",
+    tag = "memories",
+    params(
+        ("id" = String, Path, description = "Memory ID"),
+        GetMemoryQuery,
+    ),
+    responses(
+        (status = 200, description = "The memory, trimmed to the requested fields", body = Object),
+    )
+)]
 pub async fn get_memory(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Path(memory_id): Path<String>,
-) -> ApiResult<Json<MemoryItem>> {
+    Query(query): Query<GetMemoryQuery>,
+) -> ApiResult<Json<serde_json::Value>> {
     if !state.is_configured().await {
         return Err(ApiError::bad_request(
             "Memory not configured. Call /configure first.",
@@ -167,17 +341,31 @@ pub async fn get_memory(
     }
 
     let result = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
-        memory.get(&memory_id).await.map_err(ApiError::from)?
+        let item = memory.get(&memory_id).await.map_err(ApiError::from)?;
+        if let Some(ref item) = item {
+            state
+                .authorize(
+                    AuthzRequest::new(subject.0, AuthzAction::Get)
+                        .with_memory_id(&memory_id)
+                        .with_metadata(item.metadata.clone().unwrap_or_default()),
+                )
+                .await?;
+        }
+        item
     };
 
     match result {
-        Some(item) => Ok(Json(item)),
+        Some(item) => query
+            .fields
+            .unwrap_or_default()
+            .project(&item)
+            .map(Json)
+            .map_err(|e| ApiError::internal(e.to_string())),
         None => Err(ApiError::not_found(format!(
             "Memory with id '{}' not found",
             memory_id
@@ -185,16 +373,90 @@ pub async fn get_memory(
     }
 }
 
-/// Request body for updating a memory.
-#[derive(Debug, Deserialize)]
+/// Response of a document record with its chunks.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentResponse {
+    /// The document record.
+    #[schema(value_type = Object)]
+    pub document: MemoryItem,
+    /// The document's chunks, in document order.
+    #[schema(value_type = Vec<Object>)]
+    pub chunks: Vec<MemoryItem>,
+}
+
+/// Get a document record and its chunks.
+/// GET /memories/:id/document
+#[utoipa::path(
+    get,
+    path = "/memories/{id}/document",
+    tag = "memories",
+    params(("id" = String, Path, description = "Document record ID")),
+    responses(
+        (status = 200, description = "The document record and its chunks", body = DocumentResponse),
+    )
+)]
+pub async fn get_document(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(document_id): Path<String>,
+) -> ApiResult<Json<DocumentResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let document = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+        memory.get_document(&document_id).await.map_err(ApiError::from)?
+    };
+    let document = document.ok_or_else(|| {
+        ApiError::not_found(format!("Document with id '{}' not found", document_id))
+    })?;
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::Get)
+                .with_memory_id(&document_id)
+                .with_metadata(document.document.metadata.clone().unwrap_or_default()),
+        )
+        .await?;
+
+    Ok(Json(DocumentResponse {
+        document: document.document,
+        chunks: document.chunks,
+    }))
+}
+
+/// Request body for updating a memory. At least one field is required.
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateMemoryRequest {
-    pub text: String,
+    /// New memory text.
+    pub text: Option<String>,
+    /// Delete the memory this many seconds from now; 0 clears its expiry.
+    pub ttl_secs: Option<u64>,
 }
 
 /// Update a memory.
 /// PUT /memories/:id
+#[utoipa::path(
+    put,
+    path = "/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = UpdateMemoryRequest,
+    responses(
+        (status = 200, description = "The updated memory", body = Object),
+    )
+)]
 pub async fn update_memory(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Path(memory_id): Path<String>,
     Json(request): Json<UpdateMemoryRequest>,
 ) -> ApiResult<Json<MemoryItem>> {
@@ -205,16 +467,34 @@ pub async fn update_memory(
     }
 
     let result = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
-        memory
-            .update(&memory_id, &request.text)
-            .await
-            .map_err(ApiError::from)?
+        let previous =
+            authorize_memory(&state, &memory, subject.clone(), AuthzAction::Update, &memory_id)
+                .await?;
+
+        let mut result = match request.text {
+            Some(ref text) => Some(memory.update(&memory_id, text).await?),
+            None => None,
+        };
+        if let Some(ttl_secs) = request.ttl_secs {
+            let expires_at = (ttl_secs > 0).then(|| expiry_after(ttl_secs));
+            result = Some(memory.set_expiry(&memory_id, expires_at).await?);
+        }
+        let result = result.ok_or_else(|| ApiError::bad_request("Provide text or ttl_secs"))?;
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::Update, "memory")
+                .with_resource_id(&memory_id)
+                .with_org_id(tenant.0.clone())
+                .with_content(
+                    previous.as_ref().map(|item| item.memory.as_str()),
+                    Some(&result.memory),
+                ),
+        );
+        result
     };
 
     Ok(Json(result))
@@ -222,8 +502,19 @@ pub async fn update_memory(
 
 /// Delete a memory.
 /// DELETE /memories/:id
+#[utoipa::path(
+    delete,
+    path = "/memories/{id}",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    responses(
+        (status = 200, description = "Memory deleted", body = Object),
+    )
+)]
 pub async fn delete_memory(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Path(memory_id): Path<String>,
 ) -> ApiResult<Json<serde_json::Value>> {
     if !state.is_configured().await {
@@ -233,13 +524,22 @@ pub async fn delete_memory(
     }
 
     {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
+        let previous =
+            authorize_memory(&state, &memory, subject.clone(), AuthzAction::Delete, &memory_id)
+                .await?;
+
         memory.delete(&memory_id).await.map_err(ApiError::from)?;
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::Delete, "memory")
+                .with_resource_id(&memory_id)
+                .with_org_id(tenant.0.clone())
+                .with_content(previous.as_ref().map(|item| item.memory.as_str()), None),
+        );
     }
 
     Ok(Json(serde_json::json!({
@@ -248,7 +548,7 @@ pub async fn delete_memory(
 }
 
 /// Request body for deleting all memories.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct DeleteAllMemoriesRequest {
     pub user_id: Option<String>,
     pub agent_id: Option<String>,
@@ -257,8 +557,19 @@ pub struct DeleteAllMemoriesRequest {
 
 /// Delete all memories.
 /// DELETE /memories
+#[utoipa::path(
+    delete,
+    path = "/memories",
+    tag = "memories",
+    request_body = DeleteAllMemoriesRequest,
+    responses(
+        (status = 200, description = "Memories deleted", body = Object),
+    )
+)]
 pub async fn delete_all_memories(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<DeleteAllMemoriesRequest>,
 ) -> ApiResult<Json<serde_json::Value>> {
     if !state.is_configured().await {
@@ -267,17 +578,34 @@ pub async fn delete_all_memories(
         ));
     }
 
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::DeleteAll).with_scope(
+            request.user_id.clone(),
+            request.agent_id.clone(),
+            request.run_id.clone(),
+        ))
+        .await?;
+
     {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
+        let details = serde_json::json!({
+            "user_id": request.user_id,
+            "agent_id": request.agent_id,
+            "run_id": request.run_id,
+        });
         memory
             .delete_all(request.user_id, request.agent_id, request.run_id)
             .await
             .map_err(ApiError::from)?;
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::DeleteAll, "memories")
+                .with_org_id(tenant.0.clone())
+                .with_details(details),
+        );
     }
 
     Ok(Json(serde_json::json!({
@@ -286,15 +614,26 @@ pub async fn delete_all_memories(
 }
 
 /// Response for memory history.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MemoryHistoryResponse {
     pub history: Vec<serde_json::Value>,
 }
 
 /// Get memory history.
 /// GET /memories/:id/history
+#[utoipa::path(
+    get,
+    path = "/memories/{id}/history",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    responses(
+        (status = 200, description = "Changes to the memory", body = MemoryHistoryResponse),
+    )
+)]
 pub async fn get_memory_history(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Path(memory_id): Path<String>,
 ) -> ApiResult<Json<MemoryHistoryResponse>> {
     if !state.is_configured().await {
@@ -304,12 +643,13 @@ pub async fn get_memory_history(
     }
 
     let history = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
+        authorize_memory(&state, &memory, subject, AuthzAction::History, &memory_id).await?;
+
         memory
             .history(&memory_id)
             .await
@@ -320,4 +660,520 @@ pub async fn get_memory_history(
     };
 
     Ok(Json(MemoryHistoryResponse { history }))
-}
\ No newline at end of file
+}
+/// Response listing memory versions.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MemoryVersionsResponse {
+    /// Versions, oldest first.
+    #[schema(value_type = Vec<Object>)]
+    pub versions: Vec<MemoryVersion>,
+}
+
+/// Request body for rolling back a memory.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RollbackRequest {
+    /// Version number to restore.
+    pub version: u32,
+}
+
+/// List the versions of a memory.
+/// GET /memories/:id/versions
+#[utoipa::path(
+    get,
+    path = "/memories/{id}/versions",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    responses(
+        (status = 200, description = "Versions of the memory", body = MemoryVersionsResponse),
+    )
+)]
+pub async fn get_memory_versions(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(memory_id): Path<String>,
+) -> ApiResult<Json<MemoryVersionsResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let versions = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        let versions = memory.versions(&memory_id).await.map_err(ApiError::from)?;
+        authorize_versioned(&state, &memory, subject, AuthzAction::History, &memory_id, &versions)
+            .await?;
+        versions
+    };
+
+    Ok(Json(MemoryVersionsResponse { versions }))
+}
+
+/// Get one version of a memory.
+/// GET /memories/:id/versions/:version
+#[utoipa::path(
+    get,
+    path = "/memories/{id}/versions/{version}",
+    tag = "memories",
+    params(
+        ("id" = String, Path, description = "Memory ID"),
+        ("version" = u32, Path, description = "Version number"),
+    ),
+    responses(
+        (status = 200, description = "The memory version", body = Object),
+    )
+)]
+pub async fn get_memory_version(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path((memory_id, version)): Path<(String, u32)>,
+) -> ApiResult<Json<MemoryVersion>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let versions = memory.versions(&memory_id).await.map_err(ApiError::from)?;
+    authorize_versioned(&state, &memory, subject, AuthzAction::History, &memory_id, &versions)
+        .await?;
+
+    versions
+        .into_iter()
+        .find(|v| v.version_number == version)
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Version {} of memory {} not found", version, memory_id))
+        })
+}
+
+/// Restore a memory to an earlier version, recreating it if it was deleted.
+/// POST /memories/:id/rollback
+#[utoipa::path(
+    post,
+    path = "/memories/{id}/rollback",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = RollbackRequest,
+    responses(
+        (status = 200, description = "The restored memory", body = Object),
+    )
+)]
+pub async fn rollback_memory(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(memory_id): Path<String>,
+    Json(request): Json<RollbackRequest>,
+) -> ApiResult<Json<MemoryItem>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let result = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        let versions = memory.versions(&memory_id).await.map_err(ApiError::from)?;
+        let previous = authorize_versioned(
+            &state,
+            &memory,
+            subject.clone(),
+            AuthzAction::Update,
+            &memory_id,
+            &versions,
+        )
+        .await?;
+
+        let result = memory
+            .rollback(&memory_id, request.version)
+            .await
+            .map_err(ApiError::from)?;
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::Rollback, "memory")
+                .with_resource_id(&memory_id)
+                .with_org_id(tenant.0.clone())
+                .with_content(
+                    previous.as_ref().map(|item| item.memory.as_str()),
+                    Some(&result.memory),
+                )
+                .with_details(serde_json::json!({ "version": request.version })),
+        );
+        result
+    };
+
+    Ok(Json(result))
+}
+
+/// Request body for adding or removing tags.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagsRequest {
+    /// Tags to add or remove. Tags are trimmed and lowercased.
+    pub tags: Vec<String>,
+}
+
+/// Add tags to a memory.
+/// POST /memories/:id/tags
+#[utoipa::path(
+    post,
+    path = "/memories/{id}/tags",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = TagsRequest,
+    responses(
+        (status = 200, description = "The tagged memory", body = Object),
+    )
+)]
+pub async fn add_memory_tags(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(memory_id): Path<String>,
+    Json(request): Json<TagsRequest>,
+) -> ApiResult<Json<MemoryItem>> {
+    change_memory_tags(state, subject, tenant, memory_id, request.tags, true).await
+}
+
+/// Remove tags from a memory.
+/// DELETE /memories/:id/tags
+#[utoipa::path(
+    delete,
+    path = "/memories/{id}/tags",
+    tag = "memories",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = TagsRequest,
+    responses(
+        (status = 200, description = "The memory without the removed tags", body = Object),
+    )
+)]
+pub async fn remove_memory_tags(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(memory_id): Path<String>,
+    Json(request): Json<TagsRequest>,
+) -> ApiResult<Json<MemoryItem>> {
+    change_memory_tags(state, subject, tenant, memory_id, request.tags, false).await
+}
+
+async fn change_memory_tags(
+    state: AppState,
+    subject: Subject,
+    tenant: Tenant,
+    memory_id: String,
+    tags: Vec<String>,
+    add: bool,
+) -> ApiResult<Json<MemoryItem>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    authorize_memory(&state, &memory, subject.clone(), AuthzAction::Update, &memory_id).await?;
+
+    let result = if add {
+        memory.add_tags(&memory_id, &tags).await
+    } else {
+        memory.remove_tags(&memory_id, &tags).await
+    }
+    .map_err(ApiError::from)?;
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Update, "memory")
+            .with_resource_id(&memory_id)
+            .with_org_id(tenant.0.clone())
+            .with_details(serde_json::json!({ "tags": result.tags() })),
+    );
+
+    Ok(Json(result))
+}
+
+/// Request body for merging memories.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeMemoriesRequest {
+    /// Memories to merge; at least two, sharing the same scope.
+    pub ids: Vec<String>,
+    /// `supersede` (default) soft-deletes the originals after recording
+    /// them as superseded; `delete` deletes them.
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub strategy: MergeStrategy,
+}
+
+/// Merge overlapping memories into one.
+/// POST /memories/merge
+#[utoipa::path(
+    post,
+    path = "/memories/merge",
+    tag = "memories",
+    request_body = MergeMemoriesRequest,
+    responses(
+        (status = 200, description = "The merged memory", body = Object),
+    )
+)]
+pub async fn merge_memories(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<MergeMemoriesRequest>,
+) -> ApiResult<Json<MemoryItem>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let mut sources = Vec::new();
+    for id in &request.ids {
+        let item =
+            authorize_memory(&state, &memory, subject.clone(), AuthzAction::Delete, id).await?;
+        sources.extend(item);
+    }
+    if let Some(metadata) = sources.first().and_then(|item| item.metadata.clone()) {
+        state
+            .authorize(
+                AuthzRequest::new(subject.as_str(), AuthzAction::Add).with_metadata(metadata),
+            )
+            .await?;
+    }
+
+    let merged = memory.merge(&request.ids, request.strategy).await?;
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Create, "memory")
+            .with_resource_id(&merged.id)
+            .with_org_id(tenant.0.clone())
+            .with_content(None, Some(&merged.memory))
+            .with_details(serde_json::json!({ "merged_from": request.ids })),
+    );
+    for source in &sources {
+        state.audit(
+            NewAuditEntry::new(subject.as_str(), AuditAction::Delete, "memory")
+                .with_resource_id(&source.id)
+                .with_org_id(tenant.0.clone())
+                .with_content(Some(&source.memory), None)
+                .with_details(serde_json::json!({ "merged_into": merged.id })),
+        );
+    }
+
+    Ok(Json(merged))
+}
+
+/// Query parameters for listing tags.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetTagsQuery {
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// Response listing the tags used in a scope.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagsResponse {
+    /// Tags with the number of memories carrying each, most used first.
+    #[schema(value_type = Vec<Object>)]
+    pub tags: Vec<TagCount>,
+}
+
+/// List the tags used in a scope.
+/// GET /tags
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "memories",
+    params(GetTagsQuery),
+    responses(
+        (status = 200, description = "Tags used in the scope", body = TagsResponse),
+    )
+)]
+pub async fn get_tags(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<GetTagsQuery>,
+) -> ApiResult<Json<TagsResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+            query.user_id.clone(),
+            query.agent_id.clone(),
+            query.run_id.clone(),
+        ))
+        .await?;
+
+    let tags = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory
+            .tags(query.user_id, query.agent_id, query.run_id)
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(TagsResponse { tags }))
+}
+
+/// Authorize an operation on a memory that may have been deleted.
+///
+/// Deleted memories are checked against the metadata of their latest version.
+/// Returns the memory if it exists.
+async fn authorize_versioned(
+    state: &AppState,
+    memory: &Memory,
+    subject: Subject,
+    action: AuthzAction,
+    memory_id: &str,
+    versions: &[MemoryVersion],
+) -> ApiResult<Option<MemoryItem>> {
+    let exists = memory.get(memory_id).await.map_err(ApiError::from)?.is_some();
+    match versions.last() {
+        Some(latest) if !exists => {
+            let action = match action {
+                AuthzAction::Update if is_global(&latest.metadata) => AuthzAction::Admin,
+                action => action,
+            };
+            state
+                .authorize(
+                    AuthzRequest::new(subject.0, action)
+                        .with_memory_id(memory_id)
+                        .with_metadata(latest.metadata.clone()),
+                )
+                .await?;
+            Ok(None)
+        }
+        _ => authorize_memory(state, memory, subject, action, memory_id).await,
+    }
+}
+
+/// Authorize an operation on an existing memory using its stored metadata.
+///
+/// Missing memories are passed through so the operation reports not-found.
+/// Returns the memory as it was checked.
+pub(crate) async fn authorize_memory(
+    state: &AppState,
+    memory: &Memory,
+    subject: Subject,
+    action: AuthzAction,
+    memory_id: &str,
+) -> ApiResult<Option<MemoryItem>> {
+    let item = memory.get(memory_id).await.map_err(ApiError::from)?;
+    let metadata = item
+        .as_ref()
+        .and_then(|item| item.metadata.clone())
+        .unwrap_or_default();
+
+    // Global memories are shared, so only admins may change them
+    let action = match action {
+        AuthzAction::Update | AuthzAction::Delete if is_global(&metadata) => AuthzAction::Admin,
+        action => action,
+    };
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, action)
+                .with_memory_id(memory_id)
+                .with_metadata(metadata),
+        )
+        .await?;
+
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use rook_core::memory::testing::{test_config, test_memory};
+    use serde_json::json;
+
+    use super::*;
+
+    async fn add(state: &AppState, content: &str, threshold: Option<f32>) -> AddMemoryResponse {
+        let request = serde_json::from_value(json!({
+            "messages": [{"role": "user", "content": content}],
+            "user_id": "alice",
+            "infer": false,
+            "duplicate_threshold": threshold,
+        }))
+        .unwrap();
+        let Json(response) = add_memory(
+            State(state.clone()),
+            Subject("alice".to_string()),
+            Tenant(None),
+            Json(request),
+        )
+        .await
+        .unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_add_reports_possible_duplicates() {
+        let (memory, store) = test_memory(test_config());
+        let state = AppState::new_with_memory(memory, test_config());
+        let first = add(&state, "I like green tea", None).await;
+        assert!(first.possible_duplicates.is_empty());
+
+        let second = add(&state, "I like green tea", Some(0.9)).await;
+        assert_eq!(second.possible_duplicates.len(), 1);
+        let duplicate = &second.possible_duplicates[0];
+        assert_eq!(duplicate.id, first.results[0].id);
+        assert!(duplicate.score >= 0.9);
+        // Reported, but added all the same
+        assert_eq!(store.records().len(), 2);
+
+        let unrelated = add(&state, "Deploys go out on Tuesdays", Some(0.9)).await;
+        assert!(unrelated.possible_duplicates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_out_of_range_duplicate_threshold() {
+        let (memory, _) = test_memory(test_config());
+        let state = AppState::new_with_memory(memory, test_config());
+        let request = serde_json::from_value(json!({
+            "messages": [{"role": "user", "content": "I like green tea"}],
+            "duplicate_threshold": 1.5,
+        }))
+        .unwrap();
+        let result = add_memory(
+            State(state),
+            Subject("alice".to_string()),
+            Tenant(None),
+            Json(request),
+        )
+        .await;
+        assert_eq!(result.unwrap_err().status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,37 @@
+//! Prometheus metrics endpoint.
+
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::observability::metrics;
+
+/// Content type of the Prometheus text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Render metrics in Prometheus format.
+/// GET /metrics
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn get_metrics(State(state): State<AppState>) -> ApiResult<impl IntoResponse> {
+    let handle = state
+        .metrics()
+        .ok_or_else(|| ApiError::not_found("Metrics are not enabled"))?;
+
+    // Memory count is sampled at scrape time rather than tracked per write.
+    if let Some(store) = state.with_memory(|m| m.vector_store()).await {
+        match store.collection_info(store.collection_name()).await {
+            Ok(info) => metrics::set_memory_count(info.vector_count),
+            Err(e) => tracing::warn!("Failed to count memories for metrics: {}", e),
+        }
+    }
+
+    handle.run_upkeep();
+    Ok(([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], handle.render()))
+}
@@ -1,45 +1,335 @@
 //! Route definitions for the REST API.
 
+mod admin;
+mod analytics;
+mod audit;
 mod config;
+mod events;
+mod global;
+mod graph;
 mod health;
+mod ingest;
+mod intentions;
+mod keys;
 mod memories;
+mod metrics;
+mod openapi;
+mod review;
+mod runs;
 mod search;
 mod signals;
+mod stats;
+mod sync;
+mod transfer;
+mod users;
+mod webhooks;
 
 use axum::{
+    middleware::from_fn_with_state,
     routing::{delete, get, post, put},
     Router,
 };
 
+use crate::middleware::idempotency_middleware;
 use crate::state::AppState;
 
 /// Create the main application router.
 pub fn create_router(state: AppState) -> Router {
-    Router::new()
+    // Writes that clients retry with an Idempotency-Key
+    let idempotent = from_fn_with_state(state.clone(), idempotency_middleware);
+    let router = Router::new()
         // Health check
         .route("/health", get(health::health_check))
         // Memory operations
-        .route("/memories", post(memories::add_memory))
+        .route(
+            "/memories",
+            post(memories::add_memory).layer(idempotent.clone()),
+        )
         .route("/memories", get(memories::get_all_memories))
         .route("/memories", delete(memories::delete_all_memories))
+        .route("/memories/merge", post(memories::merge_memories))
         .route("/memories/:id", get(memories::get_memory))
-        .route("/memories/:id", put(memories::update_memory))
+        .route("/memories/:id", put(memories::update_memory).layer(idempotent))
         .route("/memories/:id", delete(memories::delete_memory))
+        .route("/memories/:id/document", get(memories::get_document))
         .route("/memories/:id/history", get(memories::get_memory_history))
+        .route("/memories/:id/versions", get(memories::get_memory_versions))
+        .route("/memories/:id/versions/:version", get(memories::get_memory_version))
+        .route("/memories/:id/rollback", post(memories::rollback_memory))
+        .route("/memories/:id/tags", post(memories::add_memory_tags))
+        .route("/memories/:id/tags", delete(memories::remove_memory_tags))
+        .route("/memories/:id/review", post(review::review_memory))
+        .route("/tags", get(memories::get_tags))
+        .route("/smart_ingest", post(ingest::smart_ingest))
+        // Spaced review
+        .route("/review", get(review::due_for_review))
+        // Runs
+        .route("/runs", post(runs::start_run))
+        .route("/runs/:run_id", get(runs::get_run))
+        .route("/runs/:run_id/end", post(runs::end_run))
+        .route("/runs/:run_id/summarize", post(runs::summarize_run))
+        .route("/runs/:run_id/pins", get(runs::list_run_pins))
+        .route("/runs/:run_id/pins", post(runs::pin_memory))
+        .route("/runs/:run_id/pins", delete(runs::clear_run_pins))
+        .route("/runs/:run_id/pins/:memory_id", delete(runs::unpin_memory))
+        // Global knowledge
+        .route("/global/memories", get(global::get_global_memories))
+        .route("/global/promotions", post(global::propose_promotion))
+        .route("/global/promotions", get(global::list_promotions))
+        .route("/global/promotions/:id/review", post(global::review_promotion))
         // Search
         .route("/search", post(search::search_memories))
+        // Knowledge graph
+        .route("/graph/entities", get(graph::list_entities))
+        .route("/graph/entities", post(graph::add_entity))
+        .route("/graph/entities", delete(graph::delete_entities))
+        .route("/graph/relationships", post(graph::add_relationship))
+        .route("/graph/relationships", delete(graph::delete_relationship))
+        .route("/graph/neighbors/:name", get(graph::get_neighbors))
+        .route("/graph/search", post(graph::search_graph))
+        // Intentions
+        .route("/intentions", get(intentions::list_intentions))
+        .route("/intentions", post(intentions::create_intention))
+        .route("/intentions/fired", get(intentions::list_fired_intentions))
+        .route("/intentions/upcoming", get(intentions::list_upcoming_fires))
+        .route("/intentions/:id", get(intentions::get_intention))
+        .route("/intentions/:id", put(intentions::update_intention))
+        .route("/intentions/:id", delete(intentions::delete_intention))
+        // Webhooks
+        .route("/webhooks", get(webhooks::list_webhooks))
+        .route("/webhooks", post(webhooks::create_webhook))
+        .route("/webhooks/:id", get(webhooks::get_webhook))
+        .route("/webhooks/:id", put(webhooks::update_webhook))
+        .route("/webhooks/:id", delete(webhooks::delete_webhook))
+        // API keys
+        .route("/keys", get(keys::list_api_keys))
+        .route("/keys", post(keys::create_api_key))
+        .route("/keys/:id", delete(keys::revoke_api_key))
         // Strength signals
         .route("/signals", post(signals::process_signals))
         .route("/signals/apply", post(signals::apply_updates))
+        // Replicated sync
+        .route("/sync/status", get(sync::sync_status))
+        .route("/sync/changes", get(sync::pull_changes))
+        .route("/sync/changes", post(sync::push_changes))
+        .route("/sync/conflicts", get(sync::list_conflicts))
         // Configuration
         .route("/configure", post(config::configure))
         .route("/reset", post(config::reset))
-        // Attach state
-        .with_state(state)
+        // Admin
+        .route("/admin/reload", post(admin::reload))
+        .route("/admin/sampling", get(admin::get_sampling))
+        .route("/admin/calibration", post(admin::calibrate))
+        .route("/admin/calibration", get(admin::get_calibration))
+        .route("/admin/rebuild", post(admin::rebuild))
+        .route("/admin/rebuild", get(admin::rebuild_status))
+        .route("/admin/reassign", post(admin::reassign_scope))
+        .route("/admin/encryption/rotate", post(admin::rotate_content_encryption))
+        .route("/admin/backup", post(admin::backup))
+        .route("/admin/restore", post(admin::restore))
+        // Export and import
+        .route("/export", post(transfer::export_memories))
+        .route("/import", post(transfer::import_memories))
+        // User data
+        .route("/users/:user_id", delete(users::erase_user))
+        .route("/users/:user_id/export", get(users::export_user))
+        // Live events
+        .route("/events/stream", get(events::stream_events))
+        // Stats
+        .route("/stats", get(stats::get_stats))
+        .route("/analytics", get(analytics::get_analytics))
+        // Audit log
+        .route("/audit", get(audit::get_audit_log))
+        // Prometheus metrics
+        .route("/metrics", get(metrics::get_metrics))
+        // API description
+        .route("/openapi.json", get(openapi::get_openapi));
+
+    #[cfg(feature = "swagger-ui")]
+    let router = router.merge(
+        utoipa_swagger_ui::SwaggerUi::new("/docs")
+            .config(utoipa_swagger_ui::Config::from("/openapi.json")),
+    );
+
+    // Attach state
+    router.with_state(state)
 }
 
+pub use admin::*;
+pub use analytics::*;
+pub use audit::*;
 pub use config::*;
+pub use events::*;
+pub use global::*;
+pub use graph::*;
 pub use health::*;
+pub use ingest::*;
+pub use intentions::*;
+pub use keys::*;
 pub use memories::*;
+pub use metrics::*;
+pub use openapi::*;
+pub use review::*;
+pub use runs::*;
 pub use search::*;
 pub use signals::*;
+pub use stats::*;
+pub use sync::*;
+pub use transfer::*;
+pub use users::*;
+pub use webhooks::*;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::body::Body;
+    use axum::http::{Method, Request, StatusCode};
+    use rook_core::authz::{ApiKey, ApiKeyStore, KeyRole, NewApiKey};
+    use rook_core::memory::testing::{test_config, test_memory};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// A state with a key store holding a key of the `acme` organization.
+    fn org_state() -> (AppState, ApiKey) {
+        let store = Arc::new(ApiKeyStore::in_memory().unwrap());
+        let (key, _) = store
+            .create(NewApiKey::new("acme-bot", KeyRole::ReadWrite).with_org_id("acme"))
+            .unwrap();
+        (AppState::new().with_api_keys(store), key)
+    }
+
+    /// Send a request as the auth middleware would pass it on, authenticated
+    /// with `key` if given.
+    async fn send(
+        state: &AppState,
+        key: Option<&ApiKey>,
+        method: Method,
+        uri: &str,
+        body: Value,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        if let Some(key) = key {
+            request.extensions_mut().insert(key.clone());
+        }
+        respond(state, request).await
+    }
+
+    async fn respond(state: &AppState, request: Request<Body>) -> (StatusCode, Value) {
+        let response = create_router(state.clone()).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn test_org_keys_cannot_use_server_wide_routes() {
+        let (state, key) = org_state();
+        let routes = [
+            (Method::POST, "/configure", json!({})),
+            (Method::POST, "/reset", json!({})),
+            (Method::POST, "/admin/reload", json!({})),
+            (Method::GET, "/admin/sampling", Value::Null),
+            (Method::POST, "/admin/calibration", json!({})),
+            (Method::GET, "/admin/calibration", Value::Null),
+            (Method::POST, "/admin/rebuild?target=fsrs", Value::Null),
+            (Method::GET, "/admin/rebuild?target=fsrs", Value::Null),
+            (
+                Method::POST,
+                "/admin/reassign",
+                json!({"from": {"user_id": "a"}, "to": {"user_id": "b"}}),
+            ),
+            (Method::POST, "/admin/backup", json!({})),
+            (
+                Method::POST,
+                "/admin/restore",
+                json!({"source": "a.tar.zst", "destination": "restored"}),
+            ),
+            (Method::GET, "/webhooks", Value::Null),
+            (Method::POST, "/webhooks", json!({"url": "https://example.com/hook"})),
+            (Method::GET, "/webhooks/w1", Value::Null),
+            (Method::PUT, "/webhooks/w1", json!({})),
+            (Method::DELETE, "/webhooks/w1", Value::Null),
+            (Method::GET, "/keys", Value::Null),
+            (Method::DELETE, "/keys/k1", Value::Null),
+        ];
+        for (method, uri, body) in routes {
+            let (status, body) = send(&state, Some(&key), method.clone(), uri, body).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{} {}: {}", method, uri, body);
+        }
+
+        // The server's own callers get through
+        let (status, _) = send(&state, None, Method::GET, "/keys", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(&state, None, Method::GET, "/admin/sampling", Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_org_keys_create_keys_for_their_org() {
+        let (state, key) = org_state();
+
+        let request = json!({"name": "reader", "role": "read_only"});
+        let (status, body) = send(&state, Some(&key), Method::POST, "/keys", request).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body["key"]["org_id"], "acme");
+
+        let request = json!({"name": "reader", "role": "read_only", "org_id": "acme"});
+        let (status, body) = send(&state, Some(&key), Method::POST, "/keys", request).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body["key"]["org_id"], "acme");
+
+        let request = json!({"name": "reader", "role": "read_only", "org_id": "globex"});
+        let (status, _) = send(&state, Some(&key), Method::POST, "/keys", request).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // The server's own callers pick the organization
+        let request = json!({"name": "reader", "role": "read_only", "org_id": "globex"});
+        let (status, body) = send(&state, None, Method::POST, "/keys", request).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(body["key"]["org_id"], "globex");
+    }
+
+    #[tokio::test]
+    async fn test_replayed_add_is_applied_once() {
+        let (memory, store) = test_memory(test_config());
+        let state = AppState::new_with_memory(memory, test_config());
+        let add = |key: &str, content: &str| {
+            let body = json!({
+                "messages": [{"role": "user", "content": content}],
+                "user_id": "alice",
+                "infer": false,
+            });
+            Request::builder()
+                .method(Method::POST)
+                .uri("/memories")
+                .header("content-type", "application/json")
+                .header("idempotency-key", key)
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let (status, first) = respond(&state, add("add-1", "I like tea")).await;
+        assert_eq!(status, StatusCode::OK, "{}", first);
+        let (status, replayed) = respond(&state, add("add-1", "I like tea")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replayed, first);
+        assert_eq!(store.records().len(), 1);
+
+        // A key names one request
+        let (status, _) = respond(&state, add("add-1", "I like coffee")).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, _) = respond(&state, add("add-2", "I like coffee")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(store.records().len(), 2);
+    }
+}
@@ -0,0 +1,12 @@
+//! OpenAPI document endpoint.
+
+use axum::Json;
+use utoipa::OpenApi;
+
+use crate::openapi::ApiDoc;
+
+/// Get the OpenAPI document describing this API.
+/// GET /openapi.json
+pub async fn get_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -0,0 +1,171 @@
+//! Spaced review endpoints.
+//!
+//! Memories whose FSRS retrievability has decayed below the configured
+//! threshold are listed for review, and review grades reschedule them.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::SessionScope;
+use rook_core::types::MemoryItem;
+use rook_core::Grade;
+
+use super::memories::authorize_memory;
+
+/// Query parameters for listing memories due for review.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DueForReviewQuery {
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    /// Maximum number of memories (default 10).
+    pub limit: Option<usize>,
+}
+
+/// Response listing memories due for review.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DueForReviewResponse {
+    /// Memories with their FSRS state and retrievability, least
+    /// retrievable first.
+    #[schema(value_type = Vec<Object>)]
+    pub memories: Vec<MemoryItem>,
+}
+
+/// List memories due for review.
+/// GET /review
+///
+/// Needs `cognitive.enabled`. A memory is due once its retrievability falls
+/// below the desired retention of its category (`cognitive.fsrs`).
+#[utoipa::path(
+    get,
+    path = "/review",
+    tag = "review",
+    params(DueForReviewQuery),
+    responses(
+        (status = 200, description = "Memories due for review", body = DueForReviewResponse),
+    )
+)]
+pub async fn due_for_review(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<DueForReviewQuery>,
+) -> ApiResult<Json<DueForReviewResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+            query.user_id.clone(),
+            query.agent_id.clone(),
+            query.run_id.clone(),
+        ))
+        .await?;
+
+    let memories = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory
+            .due_for_review(
+                SessionScope::new(query.user_id, query.agent_id, query.run_id),
+                query.limit.unwrap_or(10),
+            )
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(DueForReviewResponse { memories }))
+}
+
+/// How well a memory was recalled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewGrade {
+    Again,
+    Hard,
+    Good,
+    Easy,
+}
+
+impl From<ReviewGrade> for Grade {
+    fn from(grade: ReviewGrade) -> Self {
+        match grade {
+            ReviewGrade::Again => Grade::Again,
+            ReviewGrade::Hard => Grade::Hard,
+            ReviewGrade::Good => Grade::Good,
+            ReviewGrade::Easy => Grade::Easy,
+        }
+    }
+}
+
+/// Request body for reviewing a memory.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewMemoryRequest {
+    /// `again`, `hard`, `good` or `easy`.
+    pub grade: ReviewGrade,
+}
+
+/// Record a review of a memory and reschedule it.
+/// POST /memories/:id/review
+#[utoipa::path(
+    post,
+    path = "/memories/{id}/review",
+    tag = "review",
+    params(("id" = String, Path, description = "Memory ID")),
+    request_body = ReviewMemoryRequest,
+    responses(
+        (status = 200, description = "The memory with its updated FSRS state", body = Object),
+    )
+)]
+pub async fn review_memory(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(memory_id): Path<String>,
+    Json(request): Json<ReviewMemoryRequest>,
+) -> ApiResult<Json<MemoryItem>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    authorize_memory(&state, &memory, subject.clone(), AuthzAction::Update, &memory_id).await?;
+
+    let result = memory
+        .review(&memory_id, request.grade.into())
+        .await
+        .map_err(ApiError::from)?;
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Update, "memory")
+            .with_resource_id(&memory_id)
+            .with_org_id(tenant.0.clone())
+            .with_details(serde_json::json!({
+                "review": request.grade,
+                "retrievability": result.retrievability,
+            })),
+    );
+
+    Ok(Json(result))
+}
@@ -0,0 +1,406 @@
+//! Run endpoints.
+//!
+//! Runs are started and ended explicitly to get end-of-run summarization,
+//! promotion of salient memories to the user and cleanup of the rest.
+//! Memories pinned to a run are returned by every search in the run.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::{RunEndOptions, RunPin, RunRecord, SessionSummary};
+
+use super::memories::authorize_memory;
+
+/// Request body for starting a run.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartRunRequest {
+    /// Run ID. Generated when omitted.
+    pub run_id: Option<String>,
+    /// User whose scope salient memories are promoted to when the run ends.
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Request body for ending a run. Unset fields use the server's `runs`
+/// configuration.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct EndRunRequest {
+    /// Summarize the run's memories.
+    pub summarize: Option<bool>,
+    /// Copy salient memories into the user's scope.
+    pub promote: Option<bool>,
+    /// Seconds to keep the run's memories; 0 deletes them now.
+    pub retention_secs: Option<u64>,
+}
+
+/// Request body for summarizing a run's memories.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SummarizeRunRequest {
+    /// Soft-delete the summarized memories (default: false).
+    #[serde(default)]
+    pub archive: bool,
+}
+
+/// Request body for pinning a memory to a run.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PinMemoryRequest {
+    pub memory_id: String,
+    /// When the pin lapses. Without it, the pin lasts until removed.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response for listing a run's pins.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RunPinsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub pins: Vec<RunPin>,
+}
+
+/// Response for removing pins.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnpinResponse {
+    pub removed: usize,
+}
+
+/// Start a run.
+/// POST /runs
+#[utoipa::path(
+    post,
+    path = "/runs",
+    tag = "runs",
+    request_body = StartRunRequest,
+    responses(
+        (status = 200, description = "The run", body = Object),
+    )
+)]
+pub async fn start_run(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<StartRunRequest>,
+) -> ApiResult<Json<RunRecord>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+    let run_id = request
+        .run_id
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Add).with_scope(
+            request.user_id.clone(),
+            request.agent_id.clone(),
+            Some(run_id.clone()),
+        ))
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let run = memory
+        .start_run(&run_id, request.user_id, request.agent_id, request.metadata)
+        .await?;
+    Ok(Json(run))
+}
+
+/// Get a run.
+/// GET /runs/:run_id
+#[utoipa::path(
+    get,
+    path = "/runs/{run_id}",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    responses(
+        (status = 200, description = "The run", body = Object),
+    )
+)]
+pub async fn get_run(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+) -> ApiResult<Json<RunRecord>> {
+    authorize_run(&state, subject, AuthzAction::Get, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let run = memory
+        .get_run(&run_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Run {} not found", run_id)))?;
+    Ok(Json(run))
+}
+
+/// End a run.
+/// POST /runs/:run_id/end
+///
+/// Summarizes the run, promotes its salient memories to the run's user and
+/// schedules the rest for deletion after the retention period.
+#[utoipa::path(
+    post,
+    path = "/runs/{run_id}/end",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    request_body = EndRunRequest,
+    responses(
+        (status = 200, description = "The ended run", body = Object),
+    )
+)]
+pub async fn end_run(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+    request: Option<Json<EndRunRequest>>,
+) -> ApiResult<Json<RunRecord>> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    authorize_run(&state, subject.clone(), AuthzAction::Update, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let run = memory
+        .get_run(&run_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Run {} not found", run_id)))?;
+    // Promotion and the summary write to the user's scope
+    if run.user_id.is_some() || run.agent_id.is_some() {
+        state
+            .authorize(AuthzRequest::new(subject.0, AuthzAction::Add).with_scope(
+                run.user_id,
+                run.agent_id,
+                None,
+            ))
+            .await?;
+    }
+
+    let options = RunEndOptions {
+        summarize: request.summarize,
+        promote: request.promote,
+        retention_secs: request.retention_secs,
+    };
+    let run = memory.end_run(&run_id, options).await?;
+    Ok(Json(run))
+}
+
+/// Summarize a run into an episodic memory.
+/// POST /runs/:run_id/summarize
+///
+/// Stores an LLM summary of the run's memories, linked to them, in the scope
+/// of the run's user or agent; the run itself is left running.
+#[utoipa::path(
+    post,
+    path = "/runs/{run_id}/summarize",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    request_body = SummarizeRunRequest,
+    responses(
+        (status = 200, description = "The stored summary", body = Object),
+    )
+)]
+pub async fn summarize_run(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+    request: Option<Json<SummarizeRunRequest>>,
+) -> ApiResult<Json<SessionSummary>> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let action = if request.archive {
+        AuthzAction::Delete
+    } else {
+        AuthzAction::Update
+    };
+    authorize_run(&state, subject.clone(), action, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    // The summary is written to the user's scope
+    if let Some(run) = memory.get_run(&run_id).await? {
+        if run.user_id.is_some() || run.agent_id.is_some() {
+            state
+                .authorize(AuthzRequest::new(subject.0, AuthzAction::Add).with_scope(
+                    run.user_id,
+                    run.agent_id,
+                    None,
+                ))
+                .await?;
+        }
+    }
+
+    let summary = memory.summarize_session(&run_id, request.archive).await?;
+    Ok(Json(summary))
+}
+
+/// Pin a memory to a run.
+/// POST /runs/:run_id/pins
+#[utoipa::path(
+    post,
+    path = "/runs/{run_id}/pins",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    request_body = PinMemoryRequest,
+    responses(
+        (status = 200, description = "The pin", body = Object),
+    )
+)]
+pub async fn pin_memory(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+    Json(request): Json<PinMemoryRequest>,
+) -> ApiResult<Json<RunPin>> {
+    authorize_run(&state, subject.clone(), AuthzAction::Update, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    authorize_memory(&state, &memory, subject, AuthzAction::Get, &request.memory_id).await?;
+
+    let pin = memory
+        .pin_to_run(&request.memory_id, &run_id, request.expires_at)
+        .await?;
+    Ok(Json(pin))
+}
+
+/// List the memories pinned to a run.
+/// GET /runs/:run_id/pins
+#[utoipa::path(
+    get,
+    path = "/runs/{run_id}/pins",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    responses(
+        (status = 200, description = "Memories pinned to the run", body = RunPinsResponse),
+    )
+)]
+pub async fn list_run_pins(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+) -> ApiResult<Json<RunPinsResponse>> {
+    authorize_run(&state, subject, AuthzAction::List, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let pins = memory.run_pins(&run_id).await?;
+    Ok(Json(RunPinsResponse { pins }))
+}
+
+/// Unpin a memory from a run.
+/// DELETE /runs/:run_id/pins/:memory_id
+#[utoipa::path(
+    delete,
+    path = "/runs/{run_id}/pins/{memory_id}",
+    tag = "runs",
+    params(
+        ("run_id" = String, Path, description = "Run ID"),
+        ("memory_id" = String, Path, description = "Memory ID"),
+    ),
+    responses(
+        (status = 200, description = "Memory unpinned", body = UnpinResponse),
+    )
+)]
+pub async fn unpin_memory(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path((run_id, memory_id)): Path<(String, String)>,
+) -> ApiResult<Json<UnpinResponse>> {
+    authorize_run(&state, subject, AuthzAction::Update, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    if !memory.unpin_from_run(&memory_id, &run_id).await? {
+        return Err(ApiError::not_found(format!(
+            "Memory {} is not pinned to run {}",
+            memory_id, run_id
+        )));
+    }
+    Ok(Json(UnpinResponse { removed: 1 }))
+}
+
+/// Remove every pin of a run.
+/// DELETE /runs/:run_id/pins
+#[utoipa::path(
+    delete,
+    path = "/runs/{run_id}/pins",
+    tag = "runs",
+    params(("run_id" = String, Path, description = "Run ID")),
+    responses(
+        (status = 200, description = "Pins removed", body = UnpinResponse),
+    )
+)]
+pub async fn clear_run_pins(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(run_id): Path<String>,
+) -> ApiResult<Json<UnpinResponse>> {
+    authorize_run(&state, subject, AuthzAction::Update, &run_id).await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+    let removed = memory.clear_run_pins(&run_id).await?;
+    Ok(Json(UnpinResponse { removed }))
+}
+
+/// Authorize `action` against a run's scope.
+async fn authorize_run(
+    state: &AppState,
+    subject: Subject,
+    action: AuthzAction,
+    run_id: &str,
+) -> ApiResult<()> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+    state
+        .authorize(AuthzRequest::new(subject.0, action).with_scope(
+            None,
+            None,
+            Some(run_id.to_string()),
+        ))
+        .await?;
+    Ok(())
+}
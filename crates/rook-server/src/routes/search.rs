@@ -3,14 +3,23 @@
 use std::collections::HashMap;
 
 use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+use crate::authz::{Subject, Tenant};
 use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
-use rook_core::types::MemoryItem;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::{
+    QueryExpansion, SessionScope, INCLUDE_ARCHIVED_FILTER, MMR_LAMBDA_FILTER, QUERY_EXPANSION_FILTER,
+    EXPLAIN_FILTER, RECENCY_HALF_LIFE_FILTER, RETRIEVAL_MODE_FILTER, SINCE_FILTER, UNTIL_FILTER,
+};
+use rook_core::retrieval::{RetrievalMode, ThresholdSpec};
+use rook_core::types::{FieldSelection, MemoryItem, RetrievalExplanation, TAGS_FIELD};
 
 /// Request body for searching memories.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SearchRequest {
     /// The search query.
     pub query: String,
@@ -24,19 +33,54 @@ pub struct SearchRequest {
     pub limit: Option<usize>,
     /// Optional filters.
     pub filters: Option<HashMap<String, serde_json::Value>>,
-    /// Score threshold.
-    pub threshold: Option<f32>,
+    /// Only return memories carrying all of these tags.
+    pub tags: Option<Vec<String>>,
+    /// Search archived memories too.
+    pub include_archived: Option<bool>,
+    /// Rank with the retrieval engine: `quick`, `standard`, `precise` or
+    /// `cognitive`. Plain similarity search if omitted.
+    #[schema(value_type = Option<String>)]
+    pub retrieval_mode: Option<RetrievalMode>,
+    /// Diversify results by maximal marginal relevance, from 0 (diversity
+    /// only) to 1 (relevance only).
+    pub mmr_lambda: Option<f32>,
+    /// Have the LLM rewrite the query before searching: `paraphrase` or
+    /// `hyde`. The query is searched as is if omitted.
+    #[schema(value_type = Option<String>)]
+    pub query_expansion: Option<QueryExpansion>,
+    /// Only return memories created at or after this time (RFC 3339).
+    pub since: Option<DateTime<Utc>>,
+    /// Only return memories created before this time (RFC 3339).
+    pub until: Option<DateTime<Utc>>,
+    /// Boost recent memories, halving a memory's score for every this many
+    /// days of age.
+    pub recency_half_life_days: Option<f32>,
+    /// Score threshold: a number, or `strict`, `default` or `loose` for the
+    /// embedder's calibrated thresholds.
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub threshold: Option<ThresholdSpec>,
     /// Whether to rerank results.
     pub rerank: Option<bool>,
+    /// Return each result's score breakdown in `explanation`.
+    pub explain: Option<bool>,
+    /// Search memories as they were at this time (RFC 3339), from their
+    /// recorded versions. Needs versioning; filters, tags and the other
+    /// search options don't apply.
+    pub as_of: Option<DateTime<Utc>>,
+    /// Fields to return per result (`id` is always included).
+    #[schema(value_type = Option<Vec<String>>)]
+    pub fields: Option<FieldSelection>,
 }
 
 /// Response for searching memories.
-#[derive(Debug, Serialize)]
+///
+/// Results are [`SearchResultItem`]s, trimmed to the requested fields.
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SearchResponse {
-    pub results: Vec<SearchResultItem>,
+    pub results: Vec<serde_json::Value>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SearchResultItem {
     pub id: String,
     pub memory: String,
@@ -49,6 +93,16 @@ pub struct SearchResultItem {
     pub run_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
+    /// Included because it is pinned to the searched run.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// ID of the intention whose fired reminder surfaced the memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surfaced_by: Option<String>,
+    /// Score breakdown, when the search set `explain`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub explanation: Option<RetrievalExplanation>,
 }
 
 impl From<MemoryItem> for SearchResultItem {
@@ -76,14 +130,28 @@ impl From<MemoryItem> for SearchResultItem {
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
             metadata: item.metadata,
+            pinned: item.pinned,
+            surfaced_by: item.surfaced_by,
+            explanation: item.explanation,
         }
     }
 }
 
 /// Search memories.
 /// POST /search
+#[utoipa::path(
+    post,
+    path = "/search",
+    tag = "search",
+    request_body = SearchRequest,
+    responses(
+        (status = 200, description = "Matching memories, trimmed to the requested fields", body = SearchResponse),
+    )
+)]
 pub async fn search_memories(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<SearchRequest>,
 ) -> ApiResult<Json<SearchResponse>> {
     if !state.is_configured().await {
@@ -92,34 +160,112 @@ pub async fn search_memories(
         ));
     }
 
+    let mut filters = request.filters;
+    if let Some(tags) = request.tags {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(TAGS_FIELD.to_string(), serde_json::json!(tags));
+    }
+    if request.include_archived == Some(true) {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(INCLUDE_ARCHIVED_FILTER.to_string(), serde_json::json!(true));
+    }
+    if let Some(mode) = request.retrieval_mode {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(RETRIEVAL_MODE_FILTER.to_string(), serde_json::json!(mode.as_str()));
+    }
+    if let Some(lambda) = request.mmr_lambda {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(MMR_LAMBDA_FILTER.to_string(), serde_json::json!(lambda));
+    }
+    if let Some(expansion) = request.query_expansion {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(QUERY_EXPANSION_FILTER.to_string(), serde_json::json!(expansion.as_str()));
+    }
+    if let Some(since) = request.since {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(SINCE_FILTER.to_string(), serde_json::json!(since.to_rfc3339()));
+    }
+    if let Some(until) = request.until {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(UNTIL_FILTER.to_string(), serde_json::json!(until.to_rfc3339()));
+    }
+    if let Some(half_life) = request.recency_half_life_days {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(RECENCY_HALF_LIFE_FILTER.to_string(), serde_json::json!(half_life));
+    }
+    if request.explain == Some(true) {
+        filters
+            .get_or_insert_with(HashMap::new)
+            .insert(EXPLAIN_FILTER.to_string(), serde_json::json!(true));
+    }
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::Search)
+                .with_scope(
+                    request.user_id.clone(),
+                    request.agent_id.clone(),
+                    request.run_id.clone(),
+                )
+                .with_metadata(filters.clone().unwrap_or_default()),
+        )
+        .await?;
+
     let limit = request.limit.unwrap_or(10);
     let rerank = request.rerank.unwrap_or(false);
 
     let results = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
-        memory
-            .search(
-                &request.query,
-                request.user_id,
-                request.agent_id,
-                request.run_id,
-                limit,
-                request.filters,
-                request.threshold,
-                rerank,
-            )
-            .await
-            .map_err(ApiError::from)?
-    };
+        let threshold = match request.threshold {
+            Some(spec) => Some(memory.resolve_threshold(spec).await?),
+            None => None,
+        };
 
-    let response = SearchResponse {
-        results: results.results.into_iter().map(Into::into).collect(),
+        match request.as_of {
+            Some(as_of) => {
+                let scope = SessionScope::new(request.user_id, request.agent_id, request.run_id);
+                memory
+                    .search_as_of(&request.query, as_of, scope, limit, threshold)
+                    .await
+                    .map_err(ApiError::from)?
+            }
+            None => {
+                memory
+                    .search(
+                        &request.query,
+                        request.user_id,
+                        request.agent_id,
+                        request.run_id,
+                        limit,
+                        filters,
+                        threshold,
+                        rerank,
+                    )
+                    .await
+                    .map_err(ApiError::from)?
+                    .results
+            }
+        }
     };
 
-    Ok(Json(response))
+    let fields = request.fields.unwrap_or_default();
+    let results = results
+        .into_iter()
+        .map(|item| fields.project(&SearchResultItem::from(item)))
+        .collect::<Result<_, _>>()
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(SearchResponse { results }))
 }
\ No newline at end of file
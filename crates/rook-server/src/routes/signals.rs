@@ -2,20 +2,41 @@
 
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+use crate::authz::{Subject, Tenant};
 use crate::error::{ApiError, ApiResult};
 use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
 use rook_core::{Grade, StrengthSignal};
 
-/// Request body for processing strength signals.
-#[derive(Debug, Deserialize)]
-pub struct ProcessSignalsRequest {
-    /// The signals to process.
-    pub signals: Vec<SignalInput>,
+/// Request body for processing strength signals: `{"signals": [...]}`, a
+/// bare array of signals, or a single signal.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ProcessSignalsRequest {
+    Wrapped {
+        /// The signals to process.
+        signals: Vec<SignalInput>,
+    },
+    Batch(Vec<SignalInput>),
+    Single(SignalInput),
+}
+
+impl ProcessSignalsRequest {
+    /// The signals in the request, in order.
+    pub fn into_signals(self) -> Vec<SignalInput> {
+        match self {
+            ProcessSignalsRequest::Wrapped { signals } | ProcessSignalsRequest::Batch(signals) => {
+                signals
+            }
+            ProcessSignalsRequest::Single(signal) => vec![signal],
+        }
+    }
 }
 
 /// A strength signal input from the API.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SignalInput {
     /// Memory was used in generating a response.
@@ -82,7 +103,7 @@ impl From<SignalInput> for StrengthSignal {
 }
 
 /// Response for processing signals.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ProcessSignalsResponse {
     /// Number of signals processed.
     pub processed: usize,
@@ -93,7 +114,7 @@ pub struct ProcessSignalsResponse {
 }
 
 /// A pending grade update.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PendingUpdate {
     pub memory_id: String,
     pub grade: String,
@@ -101,8 +122,22 @@ pub struct PendingUpdate {
 
 /// Process strength signals.
 /// POST /signals
+///
+/// Accepts a batch of signals, applied in order, so hosts can report all
+/// feedback from a turn in one request.
+#[utoipa::path(
+    post,
+    path = "/signals",
+    tag = "signals",
+    request_body = ProcessSignalsRequest,
+    responses(
+        (status = 200, description = "Signals processed", body = ProcessSignalsResponse),
+    )
+)]
 pub async fn process_signals(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<ProcessSignalsRequest>,
 ) -> ApiResult<Json<ProcessSignalsResponse>> {
     if !state.is_configured().await {
@@ -111,17 +146,21 @@ pub async fn process_signals(
         ));
     }
 
-    let signal_count = request.signals.len();
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Signal))
+        .await?;
+
+    let signals = request.into_signals();
+    let signal_count = signals.len();
 
     // Process each signal
     {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
-        for signal_input in request.signals {
+        for signal_input in signals {
             let signal: StrengthSignal = signal_input.into();
             memory.process_strength_signal(signal);
         }
@@ -129,10 +168,9 @@ pub async fn process_signals(
 
     // Get pending updates
     let pending_updates = {
-        let guard = state.inner.read().await;
-        let memory = guard
-            .memory
-            .as_ref()
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
             .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
         memory
@@ -162,7 +200,7 @@ fn grade_to_string(grade: Grade) -> String {
 }
 
 /// Request to apply pending updates (clear the processor).
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct ApplyUpdatesRequest {
     /// Whether to clear pending updates after returning them.
     #[serde(default = "default_clear")]
@@ -174,7 +212,7 @@ fn default_clear() -> bool {
 }
 
 /// Response with applied updates.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApplyUpdatesResponse {
     /// Updates that were applied.
     pub updates: Vec<PendingUpdate>,
@@ -184,8 +222,19 @@ pub struct ApplyUpdatesResponse {
 
 /// Get and optionally clear pending strength updates.
 /// POST /signals/apply
+#[utoipa::path(
+    post,
+    path = "/signals/apply",
+    tag = "signals",
+    request_body = ApplyUpdatesRequest,
+    responses(
+        (status = 200, description = "Pending grade updates", body = ApplyUpdatesResponse),
+    )
+)]
 pub async fn apply_updates(
     State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
     Json(request): Json<ApplyUpdatesRequest>,
 ) -> ApiResult<Json<ApplyUpdatesResponse>> {
     if !state.is_configured().await {
@@ -194,10 +243,13 @@ pub async fn apply_updates(
         ));
     }
 
-    let guard = state.inner.read().await;
-    let memory = guard
-        .memory
-        .as_ref()
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Signal))
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
         .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
 
     let updates: Vec<PendingUpdate> = memory
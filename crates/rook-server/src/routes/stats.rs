@@ -0,0 +1,97 @@
+//! Server statistics endpoint.
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::authz::{CallerKey, Subject};
+use crate::error::ApiResult;
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::ClassificationCacheStats;
+use rook_core::storage::DataUsage;
+
+/// Response for stats.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    pub configured: bool,
+    /// Memories in the vector store. Omitted for aggregate-only callers when
+    /// there are too few to release.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memories: Option<u64>,
+    /// Data directory usage, when a data directory is monitored. Never
+    /// returned to aggregate-only callers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub storage: Option<DataUsage>,
+    /// Classification cache hits and misses, when the cache is enabled.
+    /// Never returned to aggregate-only callers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub classification_cache: Option<ClassificationCacheStats>,
+}
+
+/// Get server statistics.
+/// GET /stats
+///
+/// Requires admin access, or an `analytics` API key, which gets the memory
+/// count under the server's aggregation policy and nothing else.
+#[utoipa::path(
+    get,
+    path = "/stats",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Server statistics", body = StatsResponse),
+    )
+)]
+pub async fn get_stats(
+    State(state): State<AppState>,
+    subject: Subject,
+    key: CallerKey,
+) -> ApiResult<Json<StatsResponse>> {
+    let aggregate_only = key.is_aggregate_only();
+    let action = if aggregate_only {
+        AuthzAction::Analytics
+    } else {
+        AuthzAction::Admin
+    };
+    state.authorize(AuthzRequest::new(subject.0, action)).await?;
+
+    let (store, cache) = match state
+        .with_memory(|m| (m.vector_store(), m.classification_cache()))
+        .await
+    {
+        Some((store, cache)) => (Some(store), cache),
+        None => (None, None),
+    };
+
+    let memories = match store {
+        Some(store) => {
+            let count = store
+                .collection_info(store.collection_name())
+                .await?
+                .vector_count;
+            if aggregate_only {
+                state.analytics_policy().release(count)
+            } else {
+                Some(count)
+            }
+        }
+        None => None,
+    };
+
+    let storage = match state.storage_monitor() {
+        Some(monitor) if !aggregate_only => Some(monitor.usage()?),
+        _ => None,
+    };
+    let classification_cache = cache
+        .filter(|_| !aggregate_only)
+        .map(|cache| cache.stats());
+
+    Ok(Json(StatsResponse {
+        configured: state.is_configured().await,
+        memories,
+        storage,
+        classification_cache,
+    }))
+}
@@ -0,0 +1,198 @@
+//! Replicated sync endpoints.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::sync::{Changeset, SupersedeRecord, SyncReport, SyncStatus};
+
+/// Get this replica's node ID, change sequence and peer cursors.
+/// GET /sync/status
+#[utoipa::path(
+    get,
+    path = "/sync/status",
+    tag = "sync",
+    responses(
+        (status = 200, description = "Replica status", body = Object),
+    )
+)]
+pub async fn sync_status(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<SyncStatus>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let status = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory.sync_status().await.map_err(ApiError::from)?
+    };
+
+    Ok(Json(status))
+}
+
+/// Query parameters for pulling changes.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PullChangesQuery {
+    /// Sequence number to pull changes after (default: 0).
+    pub since: Option<u64>,
+    /// Maximum changes per page (default: 100).
+    pub limit: Option<usize>,
+}
+
+/// Pull changes made on this replica.
+/// GET /sync/changes
+#[utoipa::path(
+    get,
+    path = "/sync/changes",
+    tag = "sync",
+    params(PullChangesQuery),
+    responses(
+        (status = 200, description = "A page of changes", body = Object),
+    )
+)]
+pub async fn pull_changes(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<PullChangesQuery>,
+) -> ApiResult<Json<Changeset>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let changeset = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory
+            .sync_changes(query.since.unwrap_or(0), query.limit.unwrap_or(100))
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(changeset))
+}
+
+/// Push a peer's changes to this replica.
+/// POST /sync/changes
+#[utoipa::path(
+    post,
+    path = "/sync/changes",
+    tag = "sync",
+    request_body = Object,
+    responses(
+        (status = 200, description = "How the changes were applied", body = Object),
+    )
+)]
+pub async fn push_changes(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(changeset): Json<Changeset>,
+) -> ApiResult<Json<SyncReport>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let report = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory.apply_changeset(&changeset).await.map_err(ApiError::from)?
+    };
+
+    Ok(Json(report))
+}
+
+/// Query parameters for listing conflicts.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListConflictsQuery {
+    pub limit: Option<usize>,
+}
+
+/// Response for listing conflicts.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListConflictsResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub conflicts: Vec<SupersedeRecord>,
+}
+
+/// List concurrent versions discarded by conflict resolution.
+/// GET /sync/conflicts
+#[utoipa::path(
+    get,
+    path = "/sync/conflicts",
+    tag = "sync",
+    params(ListConflictsQuery),
+    responses(
+        (status = 200, description = "Discarded concurrent versions", body = ListConflictsResponse),
+    )
+)]
+pub async fn list_conflicts(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ListConflictsQuery>,
+) -> ApiResult<Json<ListConflictsResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+
+    let conflicts = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        memory
+            .sync_conflicts(query.limit.unwrap_or(100))
+            .await
+            .map_err(ApiError::from)?
+    };
+
+    Ok(Json(ListConflictsResponse { conflicts }))
+}
@@ -0,0 +1,427 @@
+//! Export and import endpoints.
+
+use axum::{
+    body::Body,
+    extract::{FromRequest, Multipart, Query, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures::{Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use tokio::io::BufReader;
+use tokio_util::io::StreamReader;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::export::BundleHeader;
+use rook_core::{
+    export_bundle_jsonl, export_jsonl_since, import_bundle_jsonl, ExportBundle, ExportSection,
+    ExportStats, ImportDedup, ImportStats, RestoreStats,
+};
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// JSON Lines, one memory per line.
+    #[default]
+    Jsonl,
+    /// Apache Parquet. Requires the `parquet` feature.
+    Parquet,
+}
+
+/// Request body for exporting memories.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExportRequest {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub user_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    /// State to bundle with the memories (`cognitive`, `intentions`,
+    /// `graph`). Any section turns the export into a versioned JSON Lines
+    /// bundle.
+    #[serde(default)]
+    #[schema(value_type = Vec<String>)]
+    pub include: Vec<ExportSection>,
+    /// Cursor of an incremental export: only memories changed after it are
+    /// exported, followed by tombstones for the memories deleted since.
+    /// Pass the previous export's `x-rook-export-high-water-mark`. JSON Lines
+    /// only; needs versioning enabled to track deletions.
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for importing memories.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Memories written per batch (default: 100).
+    pub batch_size: Option<usize>,
+    /// How duplicates of stored memories are skipped: `id` (default),
+    /// `hash`, `embedding` or `smart_ingest`.
+    pub dedup: Option<String>,
+    /// Similarity at which `embedding` dedup skips a memory (default: 0.95).
+    pub dedup_threshold: Option<f32>,
+}
+
+/// Response for importing memories.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportResponse {
+    #[serde(flatten)]
+    #[schema(value_type = Object)]
+    pub stats: ImportStats,
+    /// Bundled state restored after the memories, for bundle imports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub restored: Option<RestoreStats>,
+    /// Memories deleted for the tombstones of an incremental export.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted: Option<u64>,
+}
+
+/// Export the memories in a scope as a file download.
+/// POST /export
+///
+/// The file is assembled before it is sent so the export statistics can be
+/// returned in `x-rook-export-*` headers. With `include`, cognitive state,
+/// intentions and the graph of the scope are bundled too. With
+/// `updated_since`, only the changes and deletions since the cursor are
+/// exported.
+#[utoipa::path(
+    post,
+    path = "/export",
+    tag = "transfer",
+    request_body = ExportRequest,
+    responses(
+        (status = 200, description = "The export file", body = String, content_type = "application/x-ndjson", headers(
+            ("x-rook-export-total" = u64, description = "Memories in the scope"),
+            ("x-rook-export-exported" = u64, description = "Memories written"),
+            ("x-rook-export-errors" = u64, description = "Records that failed to export"),
+            ("x-rook-export-records" = u64, description = "Records written, including bundled state"),
+            ("x-rook-export-tombstones" = u64, description = "Tombstones written for deleted memories"),
+            ("x-rook-export-high-water-mark" = String, description = "Cursor for the next incremental export"),
+        )),
+    )
+)]
+pub async fn export_memories(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<ExportRequest>,
+) -> ApiResult<Response> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.0, AuthzAction::List).with_scope(
+                request.user_id.clone(),
+                request.agent_id.clone(),
+                request.run_id.clone(),
+            ),
+        )
+        .await?;
+
+    let sections: Vec<ExportSection> = request
+        .include
+        .iter()
+        .copied()
+        .filter(|section| *section != ExportSection::Memories)
+        .collect();
+    if !sections.is_empty() && !matches!(request.format, ExportFormat::Jsonl) {
+        return Err(ApiError::bad_request(
+            "Bundled sections are only supported for JSON Lines exports",
+        ));
+    }
+    if request.updated_since.is_some()
+        && (!sections.is_empty() || !matches!(request.format, ExportFormat::Jsonl))
+    {
+        return Err(ApiError::bad_request(
+            "Incremental exports are only supported for plain JSON Lines exports",
+        ));
+    }
+    if sections.contains(&ExportSection::History) {
+        return Err(ApiError::bad_request(
+            "History is only exported per user, see GET /users/{user_id}/export",
+        ));
+    }
+    if sections.contains(&ExportSection::Intentions) {
+        // The intention store is shared by every organization
+        tenant.require_shared()?;
+    }
+
+    let (memories, tombstones, graph_store) = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+
+        let tombstones = match request.updated_since {
+            Some(since) => memory.tombstones_since(
+                request.user_id.as_deref(),
+                request.agent_id.as_deref(),
+                request.run_id.as_deref(),
+                Some(since),
+            )?,
+            None => Vec::new(),
+        };
+
+        let memories = memory
+            .get_all(
+                request.user_id.clone(),
+                request.agent_id.clone(),
+                request.run_id.clone(),
+                None,
+            )
+            .await?;
+        (memories, tombstones, memory.graph_store())
+    };
+
+    let mut body = Vec::new();
+    let (stats, content_type, filename) = match request.format {
+        ExportFormat::Jsonl if !sections.is_empty() => {
+            let header = BundleHeader::new(request.user_id, request.agent_id, request.run_id);
+            let mut bundle = ExportBundle::new(header, memories);
+            for section in sections {
+                match section {
+                    ExportSection::Cognitive => {
+                        let runtime = bundle_runtime(&state, section)?;
+                        let store = runtime.read().await.cognitive_store();
+                        bundle.collect_cognitive(&store)?;
+                    }
+                    ExportSection::Intentions => {
+                        let runtime = bundle_runtime(&state, section)?;
+                        let store = runtime.read().await.intention_store();
+                        bundle.collect_intentions(store.as_ref())?;
+                    }
+                    ExportSection::Graph => {
+                        let store = graph_store.as_ref().ok_or_else(|| {
+                            ApiError::bad_request("Graph store not configured")
+                        })?;
+                        bundle.collect_graph(store.as_ref()).await?;
+                    }
+                    ExportSection::History | ExportSection::Memories => {}
+                }
+            }
+            let stats = export_bundle_jsonl(bundle, &mut body).await?;
+            (stats, "application/x-ndjson", "rook-bundle.jsonl")
+        }
+        ExportFormat::Jsonl => {
+            let stats = export_jsonl_since(
+                futures::stream::iter(memories),
+                tombstones,
+                request.updated_since,
+                &mut body,
+            )
+            .await?;
+            (stats, "application/x-ndjson", "memories.jsonl")
+        }
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => {
+            let stats = rook_core::export_parquet(memories, &mut body, None).await?;
+            (stats, "application/vnd.apache.parquet", "memories.parquet")
+        }
+        #[cfg(not(feature = "parquet"))]
+        ExportFormat::Parquet => {
+            return Err(ApiError::bad_request(
+                "Parquet export is not enabled on this server",
+            ));
+        }
+    };
+
+    let mut headers = export_headers(&stats);
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+            .map_err(|e| ApiError::internal(e.to_string()))?,
+    );
+
+    Ok((headers, Body::from(body)).into_response())
+}
+
+/// Background runtime holding the cognitive and intention stores.
+fn bundle_runtime(
+    state: &AppState,
+    section: ExportSection,
+) -> ApiResult<std::sync::Arc<tokio::sync::RwLock<rook_core::BackgroundRuntime>>> {
+    state.runtime().ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "Cannot export {:?} state: background runtime not configured",
+            section
+        ))
+    })
+}
+
+/// Export statistics as response headers.
+pub(crate) fn export_headers(stats: &ExportStats) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in [
+        ("x-rook-export-total", stats.total),
+        ("x-rook-export-exported", stats.exported),
+        ("x-rook-export-errors", stats.errors.len() as u64),
+        (
+            "x-rook-export-records",
+            stats.exported + stats.tombstones + stats.sections.values().sum::<u64>(),
+        ),
+        ("x-rook-export-tombstones", stats.tombstones),
+    ] {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    }
+    if let Some(mark) = stats.high_water_mark {
+        if let Ok(value) = HeaderValue::from_str(&mark.to_rfc3339()) {
+            headers.insert(
+                HeaderName::from_static("x-rook-export-high-water-mark"),
+                value,
+            );
+        }
+    }
+    headers
+}
+
+/// Import memories from JSON Lines.
+/// POST /import
+///
+/// Accepts the JSONL file as the raw request body or as the first file of a
+/// `multipart/form-data` upload. The body is read as a stream and written in
+/// batches, so files larger than memory can be imported. Existing memory IDs
+/// are skipped. State carried in an export bundle is restored after the
+/// memories, with cognitive state and intentions each in one transaction.
+/// Tombstones of an incremental export delete the memories they name.
+#[utoipa::path(
+    post,
+    path = "/import",
+    tag = "transfer",
+    params(ImportQuery),
+    request_body(content = String, description = "JSON Lines file, as the raw body or a multipart upload", content_type = "application/x-ndjson"),
+    responses(
+        (status = 200, description = "Import statistics", body = ImportResponse),
+    )
+)]
+pub async fn import_memories(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Query(query): Query<ImportQuery>,
+    request: Request,
+) -> ApiResult<Json<ImportResponse>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    // Imported memories carry their own scopes
+    state
+        .authorize(AuthzRequest::new(subject.as_str(), AuthzAction::Admin))
+        .await?;
+
+    let batch_size = query.batch_size.unwrap_or(100).max(1);
+    let dedup = match query.dedup.as_deref() {
+        Some(mode) => ImportDedup::parse(mode, query.dedup_threshold)?,
+        None => ImportDedup::default(),
+    };
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+    let import_batch = |batch| memory.import_batch_with(batch, dedup);
+
+    let (mut stats, contents) = if is_multipart {
+        let mut multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        let field = multipart
+            .next_field()
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))?
+            .ok_or_else(|| ApiError::bad_request("Multipart upload contains no file"))?;
+        import_bundle_jsonl(reader(field), batch_size, import_batch).await?
+    } else {
+        let body = request.into_body().into_data_stream();
+        import_bundle_jsonl(reader(body), batch_size, import_batch).await?
+    };
+
+    let restored = if contents.is_empty() && contents.invalid == 0 {
+        None
+    } else {
+        let (cognitive, intentions) = match state.runtime() {
+            Some(runtime) => {
+                let runtime = runtime.read().await;
+                (Some(runtime.cognitive_store()), Some(runtime.intention_store()))
+            }
+            None => (None, None),
+        };
+        let graph = memory.graph_store();
+        Some(
+            contents
+                .restore(cognitive.as_deref(), intentions.as_deref(), graph.as_deref())
+                .await,
+        )
+    };
+
+    let deleted = if contents.tombstones.is_empty() {
+        None
+    } else {
+        let mut deleted = 0;
+        for tombstone in &contents.tombstones {
+            let result = match memory.get(&tombstone.id).await {
+                Ok(Some(_)) => memory.delete(&tombstone.id).await.map(|_| 1),
+                Ok(None) => Ok(0),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(n) => deleted += n,
+                Err(e) => {
+                    stats
+                        .errors
+                        .push(format!("Tombstone for {} not applied: {}", tombstone.id, e));
+                }
+            }
+        }
+        Some(deleted)
+    };
+
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::Import, "memories")
+            .with_org_id(tenant.0.clone())
+            .with_details(serde_json::json!({
+                "imported": stats.imported,
+                "skipped": stats.skipped,
+                "deleted": deleted,
+                "failed": stats.errors.len(),
+                "dedup": dedup,
+            })),
+    );
+
+    Ok(Json(ImportResponse {
+        stats,
+        restored,
+        deleted,
+    }))
+}
+
+/// Buffered reader over a stream of body chunks.
+fn reader<S, E>(stream: S) -> BufReader<impl tokio::io::AsyncRead + Unpin>
+where
+    S: Stream<Item = Result<axum::body::Bytes, E>> + Unpin,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let stream = stream.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+    BufReader::new(StreamReader::new(stream))
+}
@@ -0,0 +1,137 @@
+//! User data endpoints.
+//!
+//! Erasure and export of everything stored about one user, for data
+//! subject requests.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderValue},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::audit::{AuditAction, NewAuditEntry};
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::memory::ErasureReport;
+
+use super::transfer::export_headers;
+
+/// Erase everything stored about a user.
+/// DELETE /users/{user_id}
+///
+/// Deletes the user's memories in every scope with their graph entities,
+/// cognitive state, versions and history, and the user's runs, promotion
+/// records and intentions. The report lists what was deleted and what each
+/// store still holds afterwards; `verified` is true when nothing is left.
+/// Organization instances share the intention store with other
+/// organizations and leave intentions alone.
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The erasure report", body = Object),
+    )
+)]
+pub async fn erase_user(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(user_id): Path<String>,
+) -> ApiResult<Json<ErasureReport>> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.as_str(), AuthzAction::DeleteAll)
+                .with_scope(Some(user_id.clone()), None, None),
+        )
+        .await?;
+
+    let memory = state
+        .memory(tenant.org_id())
+        .await?
+        .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+    let report = memory.erase_user(&user_id).await?;
+
+    state.audit(
+        NewAuditEntry::new(subject.as_str(), AuditAction::DeleteAll, "user")
+            .with_org_id(tenant.0.clone())
+            .with_resource_id(user_id)
+            .with_details(serde_json::json!({
+                "erased": report.erased,
+                "remaining": report.remaining,
+                "skipped": report.skipped,
+                "verified": report.verified,
+            })),
+    );
+
+    Ok(Json(report))
+}
+
+/// Export everything stored about a user as a JSON Lines bundle.
+/// GET /users/{user_id}/export
+///
+/// The bundle holds the user's memories in every scope, their cognitive
+/// state, intentions and graph, and a `history` section with versions,
+/// history records, runs and promotion records. Export statistics are
+/// returned in `x-rook-export-*` headers as for `POST /export`.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/export",
+    tag = "users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The export bundle", body = String, content_type = "application/x-ndjson"),
+    )
+)]
+pub async fn export_user(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(user_id): Path<String>,
+) -> ApiResult<Response> {
+    if !state.is_configured().await {
+        return Err(ApiError::bad_request(
+            "Memory not configured. Call /configure first.",
+        ));
+    }
+
+    state
+        .authorize(
+            AuthzRequest::new(subject.as_str(), AuthzAction::List)
+                .with_scope(Some(user_id.clone()), None, None),
+        )
+        .await?;
+
+    let mut body = Vec::new();
+    let stats = {
+        let memory = state
+            .memory(tenant.org_id())
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Memory not configured"))?;
+        memory.export_user(&user_id, &mut body).await?
+    };
+
+    let mut headers = export_headers(&stats);
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"rook-user-{}.jsonl\"", user_id))
+            .map_err(|e| ApiError::internal(e.to_string()))?,
+    );
+
+    Ok((headers, Body::from(body)).into_response())
+}
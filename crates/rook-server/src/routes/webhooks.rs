@@ -0,0 +1,283 @@
+//! Webhook subscription endpoints.
+//!
+//! Webhooks registered here receive memory lifecycle events from the
+//! server's event bus and are persisted in the webhook store, so they survive
+//! a restart.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::authz::{Subject, Tenant};
+use crate::error::{ApiError, ApiResult};
+use crate::state::AppState;
+use rook_core::authz::{AuthzAction, AuthzRequest};
+use rook_core::events::{CircuitBreakerConfig, RetryPolicy, WebhookConfig, WebhookManager};
+
+/// Request body for registering a webhook.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Secret for HMAC-SHA256 signing of payloads.
+    pub secret: Option<String>,
+    /// Event types to deliver (e.g. `memory.created`). Empty means all.
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[schema(value_type = Option<Object>)]
+    pub retry_policy: Option<RetryPolicy>,
+    #[schema(value_type = Option<Object>)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Request timeout in seconds (default: 30).
+    pub timeout_secs: Option<u64>,
+    /// Defaults to true.
+    pub enabled: Option<bool>,
+}
+
+/// Request body for updating a webhook. Omitted fields are unchanged.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    /// New signing secret; an empty string removes it.
+    pub secret: Option<String>,
+    pub events: Option<Vec<String>>,
+    #[schema(value_type = Option<Object>)]
+    pub retry_policy: Option<RetryPolicy>,
+    #[schema(value_type = Option<Object>)]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub timeout_secs: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+/// A registered webhook. The signing secret is never returned.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub has_secret: bool,
+    pub events: Vec<String>,
+    #[schema(value_type = Object)]
+    pub retry_policy: RetryPolicy,
+    #[schema(value_type = Object)]
+    pub circuit_breaker: CircuitBreakerConfig,
+    pub timeout_secs: u64,
+    pub enabled: bool,
+}
+
+impl From<WebhookConfig> for WebhookResponse {
+    fn from(config: WebhookConfig) -> Self {
+        let mut events: Vec<String> = config.events.into_iter().collect();
+        events.sort();
+        Self {
+            id: config.id,
+            url: config.url,
+            has_secret: config.secret.is_some(),
+            events,
+            retry_policy: config.retry_policy,
+            circuit_breaker: config.circuit_breaker,
+            timeout_secs: config.timeout_secs,
+            enabled: config.enabled,
+        }
+    }
+}
+
+/// Response for listing webhooks.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhooksResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// Response for deleting a webhook.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteWebhookResponse {
+    pub message: String,
+}
+
+/// Register a webhook.
+/// POST /webhooks
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "The registered webhook", body = WebhookResponse),
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Json(request): Json<CreateWebhookRequest>,
+) -> ApiResult<Json<WebhookResponse>> {
+    let webhooks = webhook_manager(&state, subject, tenant).await?;
+
+    let mut config = WebhookConfig::new(request.url);
+    config.secret = request.secret.filter(|s| !s.is_empty());
+    config.events = request.events.into_iter().collect();
+    if let Some(policy) = request.retry_policy {
+        config.retry_policy = policy;
+    }
+    if let Some(breaker) = request.circuit_breaker {
+        config.circuit_breaker = breaker;
+    }
+    if let Some(timeout_secs) = request.timeout_secs {
+        config.timeout_secs = timeout_secs;
+    }
+    if let Some(enabled) = request.enabled {
+        config.enabled = enabled;
+    }
+
+    webhooks.save_webhook(config.clone()).await?;
+    Ok(Json(config.into()))
+}
+
+/// List registered webhooks.
+/// GET /webhooks
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Registered webhooks", body = WebhooksResponse),
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Json<WebhooksResponse>> {
+    let webhooks = webhook_manager(&state, subject, tenant).await?;
+    let webhooks = webhooks.list_webhooks().await;
+    Ok(Json(WebhooksResponse {
+        webhooks: webhooks.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Get a webhook by ID.
+/// GET /webhooks/:id
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = String, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "The webhook", body = WebhookResponse),
+    )
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<WebhookResponse>> {
+    let webhooks = webhook_manager(&state, subject, tenant).await?;
+    let config = load_webhook(&webhooks, &id).await?;
+    Ok(Json(config.into()))
+}
+
+/// Update a webhook.
+/// PUT /webhooks/:id
+///
+/// The webhook's circuit and delivery metrics start over.
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = String, Path, description = "Webhook ID")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "The updated webhook", body = WebhookResponse),
+    )
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> ApiResult<Json<WebhookResponse>> {
+    let webhooks = webhook_manager(&state, subject, tenant).await?;
+    let mut config = load_webhook(&webhooks, &id).await?;
+
+    if let Some(url) = request.url {
+        config.url = url;
+    }
+    if let Some(secret) = request.secret {
+        config.secret = Some(secret).filter(|s| !s.is_empty());
+    }
+    if let Some(events) = request.events {
+        config.events = events.into_iter().collect();
+    }
+    if let Some(policy) = request.retry_policy {
+        config.retry_policy = policy;
+    }
+    if let Some(breaker) = request.circuit_breaker {
+        config.circuit_breaker = breaker;
+    }
+    if let Some(timeout_secs) = request.timeout_secs {
+        config.timeout_secs = timeout_secs;
+    }
+    if let Some(enabled) = request.enabled {
+        config.enabled = enabled;
+    }
+
+    webhooks.save_webhook(config.clone()).await?;
+    Ok(Json(config.into()))
+}
+
+/// Unregister a webhook.
+/// DELETE /webhooks/:id
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = String, Path, description = "Webhook ID")),
+    responses(
+        (status = 200, description = "Webhook deleted", body = DeleteWebhookResponse),
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    subject: Subject,
+    tenant: Tenant,
+    Path(id): Path<String>,
+) -> ApiResult<Json<DeleteWebhookResponse>> {
+    let webhooks = webhook_manager(&state, subject, tenant).await?;
+    if !webhooks.delete_webhook(&id).await? {
+        return Err(ApiError::not_found(format!("Webhook {} not found", id)));
+    }
+
+    Ok(Json(DeleteWebhookResponse {
+        message: format!("Webhook {} deleted", id),
+    }))
+}
+
+/// The webhook manager, once the subject is authorized to manage webhooks.
+///
+/// Webhooks receive the server's own events, so organizations can't manage
+/// them.
+async fn webhook_manager(
+    state: &AppState,
+    subject: Subject,
+    tenant: Tenant,
+) -> ApiResult<Arc<WebhookManager>> {
+    tenant.require_shared()?;
+    state
+        .authorize(AuthzRequest::new(subject.0, AuthzAction::Admin))
+        .await?;
+    state
+        .webhooks()
+        .ok_or_else(|| ApiError::bad_request("Webhooks are not enabled on this server"))
+}
+
+async fn load_webhook(webhooks: &WebhookManager, id: &str) -> ApiResult<WebhookConfig> {
+    webhooks
+        .get_webhook(id)
+        .await
+        .ok_or_else(|| ApiError::not_found(format!("Webhook {} not found", id)))
+}
@@ -1,14 +1,30 @@
 //! Server state management.
 
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Duration;
 
+use rook_core::analytics::AggregationPolicy;
+use rook_core::audit::{AuditLog, NewAuditEntry};
+use rook_core::authz::{AllowAll, ApiKeyStore, Authorizer, AuthzRequest};
 use rook_core::config::MemoryConfig;
 use rook_core::error::RookResult;
+use rook_core::events::{ErrorRateConfig, ErrorRateMonitor, EventBus, WebhookManager};
 use rook_core::memory::Memory;
-use rook_core::BackgroundRuntime;
-use tokio::sync::RwLock;
+use rook_core::retrieval::TantivySearcher;
+use rook_core::storage::{ArchivalJob, DataDirMonitor, RebuildTarget};
+use rook_core::types::ArchivalConfig;
+use rook_core::{BackgroundRuntime, MaintenanceJob};
+use metrics_exporter_prometheus::PrometheusHandle;
+use tokio::sync::{OwnedRwLockReadGuard, RwLock};
+use tracing::{info, warn};
 
 use crate::factory::create_memory;
+use crate::idempotency::IdempotencyCache;
+use crate::rate_limit::KeyRateLimiter;
+use crate::tenants::{TenantConfig, TenantRegistry};
 
 /// Shared application state.
 #[derive(Clone)]
@@ -16,6 +32,34 @@ pub struct AppState {
     pub inner: Arc<RwLock<AppStateInner>>,
     /// Background runtime for schedulers (managed separately for lifecycle).
     runtime: Option<Arc<RwLock<BackgroundRuntime>>>,
+    /// Authorization hook consulted before each memory operation.
+    authorizer: Arc<dyn Authorizer>,
+    /// Event bus attached to configured memory instances (for alerts).
+    event_bus: Option<EventBus>,
+    /// Provider error-rate tracking shared across reconfigurations.
+    error_monitor: Option<Arc<ErrorRateMonitor>>,
+    /// Data directory budget enforcement.
+    storage_monitor: Option<Arc<DataDirMonitor>>,
+    /// Full-text index, if the server keeps one.
+    text_index: Option<Arc<TantivySearcher>>,
+    /// Webhooks receiving events from the event bus.
+    webhooks: Option<Arc<WebhookManager>>,
+    /// Derived stores currently being rebuilt.
+    rebuilds: Arc<std::sync::Mutex<HashSet<RebuildTarget>>>,
+    /// Prometheus recorder rendered at `/metrics`.
+    metrics: Option<PrometheusHandle>,
+    /// API keys accepted by the auth middleware.
+    api_keys: Option<Arc<ApiKeyStore>>,
+    /// Per-key rate limits.
+    rate_limiter: Arc<KeyRateLimiter>,
+    /// Responses of writes sent with an idempotency key.
+    idempotency: Arc<IdempotencyCache>,
+    /// Protection applied to analytics for aggregate-only callers.
+    analytics_policy: AggregationPolicy,
+    /// Memory instances of organizations.
+    tenants: Arc<TenantRegistry>,
+    /// Append-only log of changes made through the API.
+    audit_log: Option<Arc<AuditLog>>,
 }
 
 pub struct AppStateInner {
@@ -23,6 +67,25 @@ pub struct AppStateInner {
     pub config: Option<MemoryConfig>,
 }
 
+/// A memory instance held for the duration of a request.
+pub enum MemoryRef {
+    /// The server's own instance, read-locked against reconfiguration.
+    Shared(OwnedRwLockReadGuard<AppStateInner, Memory>),
+    /// An organization's instance.
+    Tenant(Arc<Memory>),
+}
+
+impl Deref for MemoryRef {
+    type Target = Memory;
+
+    fn deref(&self) -> &Memory {
+        match self {
+            Self::Shared(guard) => guard,
+            Self::Tenant(memory) => memory,
+        }
+    }
+}
+
 impl AppState {
     /// Create a new application state.
     pub fn new() -> Self {
@@ -32,6 +95,20 @@ impl AppState {
                 config: None,
             })),
             runtime: None,
+            authorizer: Arc::new(AllowAll),
+            event_bus: None,
+            error_monitor: None,
+            storage_monitor: None,
+            text_index: None,
+            webhooks: None,
+            rebuilds: Arc::default(),
+            metrics: None,
+            api_keys: None,
+            rate_limiter: Arc::default(),
+            idempotency: Arc::default(),
+            analytics_policy: AggregationPolicy::default(),
+            tenants: Arc::default(),
+            audit_log: None,
         }
     }
 
@@ -43,6 +120,20 @@ impl AppState {
                 config: Some(config),
             })),
             runtime: None,
+            authorizer: Arc::new(AllowAll),
+            event_bus: None,
+            error_monitor: None,
+            storage_monitor: None,
+            text_index: None,
+            webhooks: None,
+            rebuilds: Arc::default(),
+            metrics: None,
+            api_keys: None,
+            rate_limiter: Arc::default(),
+            idempotency: Arc::default(),
+            analytics_policy: AggregationPolicy::default(),
+            tenants: Arc::default(),
+            audit_log: None,
         }
     }
 
@@ -54,9 +145,176 @@ impl AppState {
                 config: None,
             })),
             runtime: Some(Arc::new(RwLock::new(runtime))),
+            authorizer: Arc::new(AllowAll),
+            event_bus: None,
+            error_monitor: None,
+            storage_monitor: None,
+            text_index: None,
+            webhooks: None,
+            rebuilds: Arc::default(),
+            metrics: None,
+            api_keys: None,
+            rate_limiter: Arc::default(),
+            idempotency: Arc::default(),
+            analytics_policy: AggregationPolicy::default(),
+            tenants: Arc::default(),
+            audit_log: None,
+        }
+    }
+
+    /// Set the authorization hook.
+    pub fn with_authorizer(mut self, authorizer: Arc<dyn Authorizer>) -> Self {
+        self.authorizer = authorizer;
+        self
+    }
+
+    /// Attach an event bus to memory instances and raise provider
+    /// error-rate alerts on it.
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.error_monitor = Some(Arc::new(ErrorRateMonitor::new(
+            ErrorRateConfig::default(),
+            event_bus.clone(),
+        )));
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Enforce a data directory budget. Once memory is configured, the
+    /// monitor may also archive decayed memories if its config allows it.
+    pub fn with_storage_monitor(mut self, monitor: Arc<DataDirMonitor>) -> Self {
+        self.storage_monitor = Some(monitor);
+        self
+    }
+
+    /// Keep a full-text index that can be rebuilt through the admin API.
+    pub fn with_text_index(mut self, index: Arc<TantivySearcher>) -> Self {
+        self.text_index = Some(index);
+        self
+    }
+
+    /// Get the full-text index.
+    pub fn text_index(&self) -> Option<Arc<TantivySearcher>> {
+        self.text_index.clone()
+    }
+
+    /// Get the event bus.
+    pub fn event_bus(&self) -> Option<EventBus> {
+        self.event_bus.clone()
+    }
+
+    /// Manage webhooks through the API.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookManager>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Get the webhook manager.
+    pub fn webhooks(&self) -> Option<Arc<WebhookManager>> {
+        self.webhooks.clone()
+    }
+
+    /// Serve metrics from an installed Prometheus recorder at `/metrics`.
+    pub fn with_metrics(mut self, handle: PrometheusHandle) -> Self {
+        self.metrics = Some(handle);
+        self
+    }
+
+    /// Get the Prometheus recorder handle.
+    pub fn metrics(&self) -> Option<PrometheusHandle> {
+        self.metrics.clone()
+    }
+
+    /// Accept API keys from this store when authentication is required.
+    pub fn with_api_keys(mut self, store: Arc<ApiKeyStore>) -> Self {
+        self.api_keys = Some(store);
+        self
+    }
+
+    /// Get the API key store.
+    pub fn api_keys(&self) -> Option<Arc<ApiKeyStore>> {
+        self.api_keys.clone()
+    }
+
+    /// Record changes made through the API in an audit log.
+    pub fn with_audit_log(mut self, log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(log);
+        self
+    }
+
+    /// Get the audit log.
+    pub fn audit_log(&self) -> Option<Arc<AuditLog>> {
+        self.audit_log.clone()
+    }
+
+    /// Record a change in the audit log, if there is one.
+    ///
+    /// Called after the change succeeded, so a failure to record is logged
+    /// rather than returned to the caller.
+    pub fn audit(&self, entry: NewAuditEntry) {
+        if let Some(ref log) = self.audit_log {
+            if let Err(e) = log.record(entry) {
+                warn!("Failed to record audit entry: {}", e);
+            }
         }
     }
 
+    /// Get the per-key rate limiter.
+    pub fn rate_limiter(&self) -> Arc<KeyRateLimiter> {
+        self.rate_limiter.clone()
+    }
+
+    /// Get the cache of idempotent write responses.
+    pub fn idempotency(&self) -> Arc<IdempotencyCache> {
+        self.idempotency.clone()
+    }
+
+    /// Drop the rate limit state of a key.
+    pub fn forget_rate_limit(&self, key_id: &str) {
+        self.rate_limiter.remove(key_id);
+    }
+
+    /// Set the k-anonymity threshold and noise for aggregate-only analytics.
+    pub fn with_analytics_policy(mut self, policy: AggregationPolicy) -> Self {
+        self.analytics_policy = policy;
+        self
+    }
+
+    /// Get the aggregate-only analytics policy.
+    pub fn analytics_policy(&self) -> AggregationPolicy {
+        self.analytics_policy
+    }
+
+    /// Set how many organization instances are kept and for how long.
+    pub fn with_tenants(mut self, config: TenantConfig) -> Self {
+        self.tenants = Arc::new(TenantRegistry::new(config));
+        self
+    }
+
+    /// Get the organization instances.
+    pub fn tenants(&self) -> Arc<TenantRegistry> {
+        self.tenants.clone()
+    }
+
+    /// Mark a rebuild as started. Returns false if one is already running.
+    pub fn begin_rebuild(&self, target: RebuildTarget) -> bool {
+        self.rebuilds.lock().unwrap().insert(target)
+    }
+
+    /// Mark a rebuild as finished.
+    pub fn end_rebuild(&self, target: RebuildTarget) {
+        self.rebuilds.lock().unwrap().remove(&target);
+    }
+
+    /// Get the data directory monitor.
+    pub fn storage_monitor(&self) -> Option<Arc<DataDirMonitor>> {
+        self.storage_monitor.clone()
+    }
+
+    /// Check a request against the authorization hook.
+    pub async fn authorize(&self, request: AuthzRequest) -> RookResult<()> {
+        self.authorizer.enforce(&request).await
+    }
+
     /// Get a reference to the runtime.
     pub fn runtime(&self) -> Option<Arc<RwLock<BackgroundRuntime>>> {
         self.runtime.clone()
@@ -96,12 +354,278 @@ impl AppState {
         }
     }
 
+    /// The memory instance for an organization, or the server's own instance
+    /// when `org_id` is `None`. Returns `None` until memory is configured.
+    ///
+    /// Organization instances are created on first use from the server's
+    /// configuration. They report provider errors like the server's own
+    /// instance and share its classification cache, but do not publish on
+    /// its event bus, which is not isolated.
+    pub async fn memory(&self, org_id: Option<&str>) -> RookResult<Option<MemoryRef>> {
+        let guard = self.inner.clone().read_owned().await;
+        let Some(org_id) = org_id else {
+            return Ok(OwnedRwLockReadGuard::try_map(guard, |inner| inner.memory.as_ref())
+                .ok()
+                .map(MemoryRef::Shared));
+        };
+        let Some(base) = guard.config.clone() else {
+            return Ok(None);
+        };
+        let classification_cache = guard
+            .memory
+            .as_ref()
+            .and_then(|memory| memory.classification_cache());
+        drop(guard);
+
+        let error_monitor = self.error_monitor.clone();
+        // Cognitive state is keyed by memory ID; intentions are keyed by
+        // user and stay with the server's own instance
+        let cognitive_store = match self.runtime {
+            Some(ref runtime) => Some(runtime.read().await.cognitive_store()),
+            None => None,
+        };
+        let memory = self
+            .tenants
+            .get_or_init(org_id, &base, |config| async move {
+                let mut memory = create_memory(config).await?;
+                if let Some(monitor) = error_monitor {
+                    memory = memory.with_error_monitor(monitor);
+                }
+                if let Some(cache) = classification_cache {
+                    memory = memory.with_classification_cache(cache);
+                }
+                if let Some(store) = cognitive_store {
+                    memory = memory.with_cognitive_store(store);
+                }
+                Ok(memory)
+            })
+            .await?;
+        Ok(Some(MemoryRef::Tenant(memory)))
+    }
+
+    /// Delete the memories of ended runs past their retention period, in
+    /// the server's memory and in loaded organization instances. Returns the
+    /// number of memories deleted.
+    ///
+    /// Organization instances that are not loaded are swept the next time
+    /// they are.
+    pub async fn expire_runs(&self) -> usize {
+        let mut deleted = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.expire_runs().await {
+                Ok(n) => deleted += n,
+                Err(e) => warn!("Failed to expire runs: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.expire_runs().await {
+                Ok(n) => deleted += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to expire runs: {}", e),
+            }
+        }
+        deleted
+    }
+
+    /// Expire ended runs every `interval`.
+    pub fn start_run_expiry(&self, interval: Duration) {
+        let state = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let deleted = state.expire_runs().await;
+                if deleted > 0 {
+                    info!("Deleted {} memories of expired runs", deleted);
+                }
+            }
+        });
+    }
+
+    /// Soft-delete expired memories of the server and loaded organization
+    /// instances. Returns the number of memories deleted.
+    pub async fn expire_memories(&self) -> usize {
+        let mut deleted = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.expire_memories().await {
+                Ok(n) => deleted += n,
+                Err(e) => warn!("Failed to expire memories: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.expire_memories().await {
+                Ok(n) => deleted += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to expire memories: {}", e),
+            }
+        }
+        deleted
+    }
+
+    /// Expire memories every `interval_minutes` as a background runtime
+    /// maintenance job. Does nothing without a runtime.
+    pub async fn start_memory_expiry(&self, interval_minutes: u64) {
+        if let Some(ref runtime) = self.runtime {
+            let job = Arc::new(MemoryExpirySweep {
+                state: self.clone(),
+            });
+            runtime.read().await.spawn_maintenance_job(job, interval_minutes);
+        }
+    }
+
+    /// Archive decayed memories of the server and loaded organization
+    /// instances. Returns the number of memories archived.
+    pub async fn archive_memories(&self) -> usize {
+        let mut archived = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.archive_decayed().await {
+                Ok(n) => archived += n,
+                Err(e) => warn!("Failed to archive memories: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.archive_decayed().await {
+                Ok(n) => archived += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to archive memories: {}", e),
+            }
+        }
+        archived
+    }
+
+    /// Archive decayed memories every `interval_minutes` as a background
+    /// runtime maintenance job. Does nothing without a runtime.
+    pub async fn start_memory_archive(&self, interval_minutes: u64) {
+        if let Some(ref runtime) = self.runtime {
+            let job = Arc::new(MemoryArchiveSweep {
+                state: self.clone(),
+            });
+            runtime.read().await.spawn_maintenance_job(job, interval_minutes);
+        }
+    }
+
+    /// Promote consolidated memories of the server and loaded organization
+    /// instances to semantic memory. Returns the number of semantic
+    /// memories created.
+    pub async fn promote_memories(&self) -> usize {
+        let mut created = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.promote_consolidated().await {
+                Ok(n) => created += n,
+                Err(e) => warn!("Failed to promote consolidated memories: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.promote_consolidated().await {
+                Ok(n) => created += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to promote consolidated memories: {}", e),
+            }
+        }
+        created
+    }
+
+    /// Promote consolidated memories to semantic memory every
+    /// `interval_minutes` as a background runtime maintenance job. Does
+    /// nothing without a runtime.
+    pub async fn start_semantic_promotion(&self, interval_minutes: u64) {
+        if let Some(ref runtime) = self.runtime {
+            let job = Arc::new(SemanticPromotionSweep {
+                state: self.clone(),
+            });
+            runtime.read().await.spawn_maintenance_job(job, interval_minutes);
+        }
+    }
+
+    /// Replay recent memories of the server and loaded organization
+    /// instances. Returns the number of older memories strengthened.
+    pub async fn replay_memories(&self) -> usize {
+        let mut strengthened = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.replay().await {
+                Ok(n) => strengthened += n,
+                Err(e) => warn!("Failed to replay memories: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.replay().await {
+                Ok(n) => strengthened += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to replay memories: {}", e),
+            }
+        }
+        strengthened
+    }
+
+    /// Replay recent memories every `interval_minutes` as a background
+    /// runtime maintenance job. Does nothing without a runtime.
+    pub async fn start_memory_replay(&self, interval_minutes: u64) {
+        if let Some(ref runtime) = self.runtime {
+            let job = Arc::new(MemoryReplaySweep {
+                state: self.clone(),
+            });
+            runtime.read().await.spawn_maintenance_job(job, interval_minutes);
+        }
+    }
+
+    /// Apply pending strength signal grades of the server and loaded
+    /// organization instances. Returns the number of memories reviewed.
+    pub async fn apply_strength_updates(&self) -> usize {
+        let mut applied = 0;
+        if let Ok(Some(memory)) = self.memory(None).await {
+            match memory.apply_strength_updates().await {
+                Ok(n) => applied += n,
+                Err(e) => warn!("Failed to apply strength updates: {}", e),
+            }
+        }
+        for (org_id, memory) in self.tenants.loaded() {
+            match memory.apply_strength_updates().await {
+                Ok(n) => applied += n,
+                Err(e) => warn!(org_id = %org_id, "Failed to apply strength updates: {}", e),
+            }
+        }
+        applied
+    }
+
+    /// Apply pending strength signal grades on the runtime's strength
+    /// update interval. Does nothing without a runtime or when the runtime
+    /// has strength updates disabled.
+    pub async fn start_strength_updates(&self) {
+        if let Some(ref runtime) = self.runtime {
+            let job = Arc::new(StrengthUpdateSweep {
+                state: self.clone(),
+            });
+            runtime.read().await.spawn_strength_updates(job);
+        }
+    }
+
     /// Configure the memory instance.
+    ///
+    /// Organization instances built from the previous configuration are
+    /// dropped and rebuilt on their next request.
     pub async fn configure(&self, config: MemoryConfig) -> RookResult<()> {
-        let memory = create_memory(config.clone()).await?;
+        let mut memory = create_memory(config.clone()).await?;
+        if let Some(ref event_bus) = self.event_bus {
+            memory = memory.with_event_bus(event_bus.clone());
+        }
+        if let Some(ref monitor) = self.error_monitor {
+            memory = memory.with_error_monitor(monitor.clone());
+        }
+        if let Some(ref runtime) = self.runtime {
+            let runtime = runtime.read().await;
+            memory = memory
+                .with_cognitive_store(runtime.cognitive_store())
+                .with_intention_store(runtime.intention_store())
+                .with_surfaced_memories(runtime.surfaced_memories());
+        }
+        if let (Some(monitor), Some(runtime)) = (&self.storage_monitor, &self.runtime) {
+            if monitor.config().archive_over_budget {
+                monitor.register(Arc::new(ArchivalJob::new(
+                    runtime.read().await.cognitive_store(),
+                    memory.vector_store(),
+                    ArchivalConfig::default(),
+                )));
+            }
+        }
         let mut guard = self.inner.write().await;
         guard.memory = Some(memory);
         guard.config = Some(config);
+        self.tenants.clear();
         Ok(())
     }
 
@@ -119,4 +643,88 @@ impl Default for AppState {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Maintenance job expiring memories across the server's memory instances.
+struct MemoryExpirySweep {
+    state: AppState,
+}
+
+#[async_trait]
+impl MaintenanceJob for MemoryExpirySweep {
+    fn name(&self) -> &str {
+        "memory_expiry"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        Ok(self.state.expire_memories().await)
+    }
+}
+
+/// Maintenance job archiving decayed memories across the server's memory
+/// instances.
+struct MemoryArchiveSweep {
+    state: AppState,
+}
+
+#[async_trait]
+impl MaintenanceJob for MemoryArchiveSweep {
+    fn name(&self) -> &str {
+        "memory_archive"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        Ok(self.state.archive_memories().await)
+    }
+}
+
+/// Maintenance job promoting consolidated memories to semantic memory
+/// across the server's memory instances.
+struct SemanticPromotionSweep {
+    state: AppState,
+}
+
+#[async_trait]
+impl MaintenanceJob for SemanticPromotionSweep {
+    fn name(&self) -> &str {
+        "semantic_promotion"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        Ok(self.state.promote_memories().await)
+    }
+}
+
+/// Maintenance job applying pending strength signal grades across the
+/// server's memory instances.
+struct StrengthUpdateSweep {
+    state: AppState,
+}
+
+#[async_trait]
+impl MaintenanceJob for StrengthUpdateSweep {
+    fn name(&self) -> &str {
+        "strength_updates"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        Ok(self.state.apply_strength_updates().await)
+    }
+}
+
+/// Maintenance job replaying recent memories across the server's memory
+/// instances.
+struct MemoryReplaySweep {
+    state: AppState,
+}
+
+#[async_trait]
+impl MaintenanceJob for MemoryReplaySweep {
+    fn name(&self) -> &str {
+        "memory_replay"
+    }
+
+    async fn run(&self) -> RookResult<usize> {
+        Ok(self.state.replay_memories().await)
+    }
+}
@@ -0,0 +1,295 @@
+//! Per-organization memory instances.
+//!
+//! Requests authenticated with an organization's API key run against that
+//! organization's own [`Memory`], built from the server's configuration with
+//! [`MemoryConfig::for_tenant`] so its collection and databases are isolated.
+//! Instances are created on first use and dropped when idle or when the
+//! registry is full, least recently used first.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use rook_core::config::MemoryConfig;
+use rook_core::error::RookResult;
+use rook_core::memory::Memory;
+use tokio::sync::OnceCell;
+use tracing::{debug, info};
+
+/// Default number of tenant instances kept at once.
+pub const DEFAULT_MAX_TENANTS: usize = 64;
+
+/// Default time after which an unused tenant instance is dropped.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Limits for the tenant registry.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantConfig {
+    /// Instances kept at once; the least recently used is dropped beyond this.
+    pub max_tenants: usize,
+    /// Instances unused for this long are dropped.
+    pub idle_timeout: Duration,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            max_tenants: DEFAULT_MAX_TENANTS,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+        }
+    }
+}
+
+impl TenantConfig {
+    /// Load from `ROOK_TENANT_MAX` and `ROOK_TENANT_IDLE_SECS`.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(max) = std::env::var("ROOK_TENANT_MAX")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&max: &usize| max > 0)
+        {
+            config.max_tenants = max;
+        }
+        if let Some(secs) = std::env::var("ROOK_TENANT_IDLE_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.idle_timeout = Duration::from_secs(secs);
+        }
+        config
+    }
+}
+
+struct TenantSlot {
+    memory: OnceCell<Arc<Memory>>,
+    last_used: Mutex<Instant>,
+}
+
+impl TenantSlot {
+    fn touch(&self) {
+        *self.last_used.lock().unwrap() = Instant::now();
+    }
+
+    fn last_used(&self) -> Instant {
+        *self.last_used.lock().unwrap()
+    }
+}
+
+/// Lazily created memory instances, one per organization.
+pub struct TenantRegistry {
+    config: TenantConfig,
+    slots: Mutex<HashMap<String, Arc<TenantSlot>>>,
+}
+
+impl TenantRegistry {
+    pub fn new(config: TenantConfig) -> Self {
+        Self {
+            config,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the limits.
+    pub fn config(&self) -> TenantConfig {
+        self.config
+    }
+
+    /// The organization's memory, created from `base` with `build` on first
+    /// use.
+    ///
+    /// Concurrent first requests for the same organization share one
+    /// initialization; other organizations are not blocked by it.
+    pub async fn get_or_init<F, Fut>(
+        &self,
+        org_id: &str,
+        base: &MemoryConfig,
+        build: F,
+    ) -> RookResult<Arc<Memory>>
+    where
+        F: FnOnce(MemoryConfig) -> Fut,
+        Fut: Future<Output = RookResult<Memory>>,
+    {
+        let slot = self.slot(org_id);
+        let memory = slot
+            .memory
+            .get_or_try_init(|| async {
+                let config = base.for_tenant(org_id)?;
+                info!(org_id, collection = %config.vector_store.collection_name, "Initializing tenant");
+                build(config).await.map(Arc::new)
+            })
+            .await;
+        if memory.is_err() {
+            // Let the next request retry instead of caching the failure
+            self.remove(org_id);
+        }
+        memory.cloned()
+    }
+
+    /// Drop instances unused for longer than the idle timeout. Returns how
+    /// many were dropped.
+    pub fn evict_idle(&self) -> usize {
+        let timeout = self.config.idle_timeout;
+        let mut slots = self.slots.lock().unwrap();
+        let before = slots.len();
+        slots.retain(|org_id, slot| {
+            let keep = slot.last_used().elapsed() < timeout;
+            if !keep {
+                debug!(org_id = %org_id, "Dropping idle tenant");
+            }
+            keep
+        });
+        before - slots.len()
+    }
+
+    /// Drop an organization's instance. In-flight requests keep using it.
+    pub fn remove(&self, org_id: &str) -> bool {
+        self.slots.lock().unwrap().remove(org_id).is_some()
+    }
+
+    /// Drop every instance, e.g. after the base configuration changed.
+    pub fn clear(&self) {
+        self.slots.lock().unwrap().clear();
+    }
+
+    /// Number of instances currently kept.
+    pub fn len(&self) -> usize {
+        self.slots.lock().unwrap().len()
+    }
+
+    /// Whether no instances are kept.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Instances currently initialized, without marking them used.
+    pub fn loaded(&self) -> Vec<(String, Arc<Memory>)> {
+        self.slots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|(org_id, slot)| Some((org_id.clone(), slot.memory.get()?.clone())))
+            .collect()
+    }
+
+    /// Drop idle instances periodically.
+    pub fn start(self: Arc<Self>) {
+        let interval = (self.config.idle_timeout / 4).max(Duration::from_secs(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let evicted = self.evict_idle();
+                if evicted > 0 {
+                    info!("Dropped {} idle tenants", evicted);
+                }
+            }
+        });
+    }
+
+    fn slot(&self, org_id: &str) -> Arc<TenantSlot> {
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(slot) = slots.get(org_id) {
+            slot.touch();
+            return slot.clone();
+        }
+
+        while slots.len() >= self.config.max_tenants {
+            let Some(lru) = slots
+                .iter()
+                .min_by_key(|(_, slot)| slot.last_used())
+                .map(|(org_id, _)| org_id.clone())
+            else {
+                break;
+            };
+            debug!(org_id = %lru, "Evicting least recently used tenant");
+            slots.remove(&lru);
+        }
+
+        let slot = Arc::new(TenantSlot {
+            memory: OnceCell::new(),
+            last_used: Mutex::new(Instant::now()),
+        });
+        slots.insert(org_id.to_string(), slot.clone());
+        slot
+    }
+}
+
+impl Default for TenantRegistry {
+    fn default() -> Self {
+        Self::new(TenantConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rook_core::error::RookError;
+
+    fn registry(max_tenants: usize, idle_timeout: Duration) -> TenantRegistry {
+        TenantRegistry::new(TenantConfig {
+            max_tenants,
+            idle_timeout,
+        })
+    }
+
+    /// Mark an organization's slot as last used `secs` ago.
+    fn age(registry: &TenantRegistry, org_id: &str, secs: u64) {
+        let slots = registry.slots.lock().unwrap();
+        let ago = Instant::now()
+            .checked_sub(Duration::from_secs(secs))
+            .unwrap();
+        *slots[org_id].last_used.lock().unwrap() = ago;
+    }
+
+    fn org_ids(registry: &TenantRegistry) -> Vec<String> {
+        let mut org_ids: Vec<String> = registry.slots.lock().unwrap().keys().cloned().collect();
+        org_ids.sort();
+        org_ids
+    }
+
+    #[test]
+    fn test_full_registry_evicts_least_recently_used() {
+        let registry = registry(2, DEFAULT_IDLE_TIMEOUT);
+        registry.slot("acme");
+        registry.slot("globex");
+        age(&registry, "acme", 20);
+        age(&registry, "globex", 10);
+
+        // Using acme again leaves globex the least recently used
+        registry.slot("acme");
+        registry.slot("initech");
+        assert_eq!(org_ids(&registry), vec!["acme", "initech"]);
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[test]
+    fn test_idle_tenants_are_evicted() {
+        let registry = registry(DEFAULT_MAX_TENANTS, Duration::from_secs(60));
+        registry.slot("acme");
+        registry.slot("globex");
+        age(&registry, "acme", 120);
+
+        assert_eq!(registry.evict_idle(), 1);
+        assert_eq!(org_ids(&registry), vec!["globex"]);
+        assert_eq!(registry.evict_idle(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_init_is_retried() {
+        let registry = TenantRegistry::default();
+        let base = MemoryConfig::default();
+
+        for _ in 0..2 {
+            let result = registry
+                .get_or_init("acme", &base, |config| async move {
+                    assert!(config.vector_store.collection_name.ends_with("_acme"));
+                    Err(RookError::internal("provider unavailable"))
+                })
+                .await;
+            assert!(result.is_err());
+            assert!(registry.is_empty());
+        }
+    }
+}
@@ -102,6 +102,18 @@ impl VectorStoreFactory {
                 Ok(Arc::new(store))
             }
 
+            #[cfg(feature = "turbopuffer")]
+            VectorStoreProvider::Turbopuffer => {
+                let store = crate::turbopuffer::TurbopufferVectorStore::new(config).await?;
+                Ok(Arc::new(store))
+            }
+
+            #[cfg(feature = "typesense")]
+            VectorStoreProvider::Typesense => {
+                let store = crate::typesense::TypesenseVectorStore::new(config).await?;
+                Ok(Arc::new(store))
+            }
+
             #[cfg(feature = "azure-ai-search")]
             VectorStoreProvider::AzureAiSearch => {
                 let store = crate::azure_ai_search::AzureAISearchVectorStore::new(config).await?;
@@ -157,11 +169,19 @@ impl VectorStoreFactory {
                     .get("path")
                     .and_then(|v| v.as_str())
                     .unwrap_or(":memory:");
-                let store = crate::sqlite_vec::SqliteVecStore::new(
-                    db_path,
-                    &config.collection_name,
-                    config.embedding_model_dims,
-                )?;
+                let store = match config.quantization {
+                    Some(quantization) => crate::sqlite_vec::SqliteVecStore::with_quantization(
+                        db_path,
+                        &config.collection_name,
+                        config.embedding_model_dims,
+                        quantization,
+                    )?,
+                    None => crate::sqlite_vec::SqliteVecStore::new(
+                        db_path,
+                        &config.collection_name,
+                        config.embedding_model_dims,
+                    )?,
+                };
                 Ok(Arc::new(store))
             }
 
@@ -180,6 +200,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({}),
         };
         Self::create(VectorStoreProvider::Qdrant, config).await
@@ -196,6 +217,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({
                 "url": url
             }),
@@ -211,6 +233,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({
                 "url": url
             }),
@@ -230,6 +253,7 @@ impl VectorStoreFactory {
             collection_name: index_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({
                 "api_key": api_key,
                 "environment": environment
@@ -252,6 +276,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({
                 "url": connection_string
             }),
@@ -287,6 +312,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: Some(PostgresPoolConfig::default()),
+            quantization: None,
             config: json!({
                 "url": connection_string
             }),
@@ -330,6 +356,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: Some(pool_config),
+            quantization: None,
             config: json!({
                 "url": connection_string
             }),
@@ -345,6 +372,7 @@ impl VectorStoreFactory {
             collection_name: collection_name.to_string(),
             embedding_model_dims: 1536,
             pool: None,
+            quantization: None,
             config: json!({
                 "url": url
             }),
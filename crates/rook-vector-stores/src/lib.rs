@@ -23,6 +23,8 @@
 //! - **Chroma** (feature: `chroma`) - Chroma embedding database
 //! - **Milvus** (feature: `milvus`) - Milvus vector database
 //! - **Upstash** (feature: `upstash`) - Upstash Vector
+//! - **Turbopuffer** (feature: `turbopuffer`) - Turbopuffer, one namespace per user
+//! - **Typesense** (feature: `typesense`) - Typesense, one collection per user
 //!
 //! ## Tier 4 - Cloud Backends
 //! - **Azure AI Search** (feature: `azure-ai-search`)
@@ -83,6 +85,15 @@ mod milvus;
 #[cfg(feature = "upstash")]
 mod upstash;
 
+#[cfg(any(feature = "turbopuffer", feature = "typesense"))]
+mod namespace;
+
+#[cfg(feature = "turbopuffer")]
+mod turbopuffer;
+
+#[cfg(feature = "typesense")]
+mod typesense;
+
 #[cfg(feature = "azure-ai-search")]
 mod azure_ai_search;
 
@@ -164,6 +175,12 @@ pub use milvus::MilvusVectorStore;
 #[cfg(feature = "upstash")]
 pub use upstash::UpstashVectorStore;
 
+#[cfg(feature = "turbopuffer")]
+pub use turbopuffer::TurbopufferVectorStore;
+
+#[cfg(feature = "typesense")]
+pub use typesense::TypesenseVectorStore;
+
 #[cfg(feature = "azure-ai-search")]
 pub use azure_ai_search::AzureAISearchVectorStore;
 
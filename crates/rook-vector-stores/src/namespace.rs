@@ -0,0 +1,229 @@
+//! Namespace mapping for backends that scope data by namespace or collection.
+//!
+//! Records are routed to `{base}__{scope}` where `scope` is the value of the
+//! scoping field (default `user_id`), and to `{base}` when the field is
+//! absent. Filters with an equality or in-list condition on the scoping field
+//! narrow searches to the matching namespaces; other searches fan out to
+//! every namespace of the collection.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use rook_core::traits::VectorStoreConfig;
+use rook_core::types::{Filter, FilterOperator};
+
+/// Default payload field used for namespace scoping.
+pub(crate) const DEFAULT_NAMESPACE_FIELD: &str = "user_id";
+
+const SEPARATOR: &str = "__";
+
+/// Namespaces a request should touch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NamespaceScope {
+    /// Only these namespaces.
+    Only(Vec<String>),
+    /// Every namespace of the collection.
+    All,
+}
+
+/// Maps scope values to namespaces and remembers where ids live.
+pub(crate) struct NamespaceMapper {
+    base: String,
+    field: Option<String>,
+    locations: RwLock<HashMap<String, String>>,
+}
+
+impl NamespaceMapper {
+    /// Build from store config.
+    ///
+    /// Reads `namespace_field` from the provider config; an empty string
+    /// disables mapping so everything lives in the base namespace.
+    pub(crate) fn from_config(config: &VectorStoreConfig) -> Self {
+        let field = match config.config.get("namespace_field").and_then(|v| v.as_str()) {
+            Some("") => None,
+            Some(field) => Some(field.to_string()),
+            None => Some(DEFAULT_NAMESPACE_FIELD.to_string()),
+        };
+        Self::new(&config.collection_name, field)
+    }
+
+    pub(crate) fn new(base: impl Into<String>, field: Option<String>) -> Self {
+        Self {
+            base: base.into(),
+            field,
+            locations: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The collection's base namespace.
+    pub(crate) fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Namespace for a scope value.
+    pub(crate) fn namespace_for(&self, value: &serde_json::Value) -> String {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        format!("{}{}{}", self.base, SEPARATOR, encode(&value))
+    }
+
+    /// Namespace a record with this payload belongs to.
+    pub(crate) fn namespace_for_payload(
+        &self,
+        payload: &HashMap<String, serde_json::Value>,
+    ) -> String {
+        match self.field.as_ref().and_then(|f| payload.get(f)) {
+            Some(value) if !value.is_null() => self.namespace_for(value),
+            _ => self.base.clone(),
+        }
+    }
+
+    /// Whether a namespace belongs to this collection.
+    pub(crate) fn owns(&self, namespace: &str) -> bool {
+        namespace == self.base
+            || namespace
+                .strip_prefix(self.base.as_str())
+                .is_some_and(|rest| rest.starts_with(SEPARATOR))
+    }
+
+    /// Namespaces a filter can match.
+    pub(crate) fn scope(&self, filter: Option<&Filter>) -> NamespaceScope {
+        let Some(field) = self.field.as_deref() else {
+            return NamespaceScope::Only(vec![self.base.clone()]);
+        };
+        match filter.and_then(|f| scope_values(f, field)) {
+            Some(values) => {
+                NamespaceScope::Only(values.iter().map(|v| self.namespace_for(v)).collect())
+            }
+            None => NamespaceScope::All,
+        }
+    }
+
+    /// Remember which namespace holds an id.
+    pub(crate) fn remember(&self, id: &str, namespace: &str) {
+        self.locations
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.to_string(), namespace.to_string());
+    }
+
+    /// Forget an id's namespace.
+    pub(crate) fn forget(&self, id: &str) {
+        self.locations
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id);
+    }
+
+    /// Forget every remembered location.
+    pub(crate) fn clear(&self) {
+        self.locations
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// Namespace an id was last seen in.
+    pub(crate) fn location(&self, id: &str) -> Option<String> {
+        self.locations
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+            .cloned()
+    }
+}
+
+/// Values of the scoping field required by the filter, if it pins them.
+fn scope_values(filter: &Filter, field: &str) -> Option<Vec<serde_json::Value>> {
+    match filter {
+        Filter::Condition(cond) if cond.field == field => match &cond.operator {
+            FilterOperator::Eq(value) => Some(vec![value.clone()]),
+            FilterOperator::In(values) => Some(values.clone()),
+            _ => None,
+        },
+        Filter::And(filters) => filters.iter().find_map(|f| scope_values(f, field)),
+        _ => None,
+    }
+}
+
+/// Encode a scope value into namespace-safe characters.
+///
+/// Alphanumerics and `_` pass through; every other byte becomes `-xx`, so
+/// distinct values never collide.
+fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("-{:02x}", byte));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rook_core::types::FilterCondition;
+
+    fn mapper() -> NamespaceMapper {
+        NamespaceMapper::new("memories", Some("user_id".to_string()))
+    }
+
+    #[test]
+    fn test_namespace_encoding_is_collision_free() {
+        let mapper = mapper();
+        assert_eq!(mapper.namespace_for(&"alice".into()), "memories__alice");
+        assert_eq!(mapper.namespace_for(&"a:b".into()), "memories__a-3ab");
+        assert_ne!(
+            mapper.namespace_for(&"a-3ab".into()),
+            mapper.namespace_for(&"a:b".into())
+        );
+        assert!(mapper.owns("memories__alice"));
+        assert!(mapper.owns("memories"));
+        assert!(!mapper.owns("memories_archive"));
+    }
+
+    #[test]
+    fn test_scope_from_filter() {
+        let mapper = mapper();
+        let filter = Filter::and(vec![
+            Filter::eq("agent_id", "bot"),
+            Filter::eq("user_id", "alice"),
+        ]);
+        assert_eq!(
+            mapper.scope(Some(&filter)),
+            NamespaceScope::Only(vec!["memories__alice".to_string()])
+        );
+
+        let filter = Filter::Condition(FilterCondition::in_list(
+            "user_id",
+            vec!["a".into(), "b".into()],
+        ));
+        assert_eq!(
+            mapper.scope(Some(&filter)),
+            NamespaceScope::Only(vec!["memories__a".to_string(), "memories__b".to_string()])
+        );
+
+        let filter = Filter::or(vec![Filter::eq("user_id", "a"), Filter::eq("user_id", "b")]);
+        assert_eq!(mapper.scope(Some(&filter)), NamespaceScope::All);
+        assert_eq!(mapper.scope(None), NamespaceScope::All);
+
+        let unscoped = NamespaceMapper::new("memories", None);
+        assert_eq!(
+            unscoped.scope(Some(&Filter::eq("user_id", "alice"))),
+            NamespaceScope::Only(vec!["memories".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_payload_routing() {
+        let mapper = mapper();
+        let payload = HashMap::from([("user_id".to_string(), serde_json::json!("alice"))]);
+        assert_eq!(mapper.namespace_for_payload(&payload), "memories__alice");
+        assert_eq!(mapper.namespace_for_payload(&HashMap::new()), "memories");
+    }
+}
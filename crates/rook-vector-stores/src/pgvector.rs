@@ -8,7 +8,9 @@ use rook_core::traits::{
     CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore,
     VectorStoreConfig,
 };
-use rook_core::types::{Filter, FilterCondition, FilterOperator};
+use rook_core::types::{
+    Filter, FilterCondition, FilterOperator, MetadataFieldType, OrderBy, SortDirection,
+};
 
 use pgvector::Vector;
 use sqlx::{postgres::PgPoolOptions, PgPool, Row};
@@ -319,54 +321,16 @@ impl VectorStore for PgVectorStore {
         filters: Option<Filter>,
         limit: Option<usize>,
     ) -> RookResult<Vec<VectorRecord>> {
-        let collection_name = self.collection_name();
-        let (where_clause, params) = if let Some(f) = filters {
-            Self::build_filter(&f)
-        } else {
-            (String::new(), vec![])
-        };
-
-        let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
-
-        let query = format!(
-            r#"SELECT id, vector, payload FROM "{}"{}{}"#,
-            collection_name,
-            if where_clause.is_empty() { String::new() } else { format!(" WHERE {}", where_clause) },
-            limit_clause
-        );
-
-        let mut query_builder = sqlx::query(&query);
-
-        for param in &params {
-            query_builder = query_builder.bind(param);
-        }
-
-        let rows = query_builder
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| RookError::vector_store(format!("Failed to list vectors: {}", e)))?;
-
-        let records = rows
-            .into_iter()
-            .map(|r| {
-                let vector: Vector = r.get("vector");
-                let payload_value: serde_json::Value = r.get("payload");
-
-                let payload: HashMap<String, serde_json::Value> = payload_value
-                    .as_object()
-                    .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
-                    .unwrap_or_default();
-
-                VectorRecord {
-                    id: r.get("id"),
-                    vector: vector.to_vec(),
-                    payload,
-                    score: None,
-                }
-            })
-            .collect();
+        self.list_records(filters, None, limit).await
+    }
 
-        Ok(records)
+    async fn list_ordered(
+        &self,
+        filters: Option<Filter>,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        self.list_records(filters, Some(order_by), limit).await
     }
 
     async fn list_collections(&self) -> RookResult<Vec<String>> {
@@ -423,6 +387,64 @@ impl VectorStore for PgVectorStore {
 }
 
 impl PgVectorStore {
+    /// List records, optionally ordered by a typed payload field.
+    async fn list_records(
+        &self,
+        filters: Option<Filter>,
+        order_by: Option<&OrderBy>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        let collection_name = self.collection_name();
+        let (where_clause, params) = if let Some(f) = filters {
+            Self::build_filter(&f)
+        } else {
+            (String::new(), vec![])
+        };
+
+        let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+
+        let query = format!(
+            r#"SELECT id, vector, payload FROM "{}"{}{}{}"#,
+            collection_name,
+            if where_clause.is_empty() { String::new() } else { format!(" WHERE {}", where_clause) },
+            order_by.map(order_clause).unwrap_or_default(),
+            limit_clause
+        );
+
+        let mut query_builder = sqlx::query(&query);
+
+        for param in &params {
+            query_builder = query_builder.bind(param);
+        }
+
+        let rows = query_builder
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to list vectors: {}", e)))?;
+
+        let records = rows
+            .into_iter()
+            .map(|r| {
+                let vector: Vector = r.get("vector");
+                let payload_value: serde_json::Value = r.get("payload");
+
+                let payload: HashMap<String, serde_json::Value> = payload_value
+                    .as_object()
+                    .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+                    .unwrap_or_default();
+
+                VectorRecord {
+                    id: r.get("id"),
+                    vector: vector.to_vec(),
+                    payload,
+                    score: None,
+                }
+            })
+            .collect();
+
+        Ok(records)
+    }
+
     fn build_filter(filter: &Filter) -> (String, Vec<String>) {
         match filter {
             Filter::Condition(cond) => Self::build_condition(cond, 2),
@@ -603,3 +625,33 @@ impl PgVectorStore {
         }
     }
 }
+
+/// `ORDER BY` clause for a typed payload field. Values that are missing or
+/// of another type sort last.
+pub(crate) fn order_clause(order_by: &OrderBy) -> String {
+    let value = format!("payload->'{}'", order_by.field());
+    let text = format!("payload->>'{}'", order_by.field());
+    let key = match order_by.field_type() {
+        MetadataFieldType::String => {
+            format!("CASE WHEN jsonb_typeof({}) = 'string' THEN {} END", value, text)
+        }
+        MetadataFieldType::Number => format!(
+            "CASE WHEN jsonb_typeof({}) = 'number' THEN ({})::double precision END",
+            value, text
+        ),
+        MetadataFieldType::Date => format!(
+            "CASE WHEN jsonb_typeof({}) = 'string' AND {} ~ '^\\d{{4}}-\\d{{2}}-\\d{{2}}' \
+             THEN ({})::timestamptz END",
+            value, text, text
+        ),
+        MetadataFieldType::Boolean => format!(
+            "CASE WHEN jsonb_typeof({}) = 'boolean' THEN ({})::boolean END",
+            value, text
+        ),
+    };
+    let direction = match order_by.direction() {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+    format!(" ORDER BY {} {} NULLS LAST", key, direction)
+}
@@ -8,13 +8,15 @@ use rook_core::traits::{
     CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore,
     VectorStoreConfig,
 };
-use rook_core::types::Filter;
+use rook_core::types::{Cursor, Filter, Page, PageRequest};
 
 use qdrant_client::qdrant::{
-    vectors_config::Config, Condition, CreateCollectionBuilder, DeletePointsBuilder, Distance,
-    FieldCondition, Filter as QdrantFilter, GetPointsBuilder, Match, PointId, PointStruct,
-    Range, ScrollPointsBuilder, SearchPointsBuilder, UpsertPointsBuilder, Value,
-    VectorParamsBuilder,
+    vectors_config::Config, vectors_output::VectorsOptions, Condition, CountPointsBuilder,
+    CreateCollectionBuilder, CreateFieldIndexCollectionBuilder, DatetimeRange,
+    DeletePointsBuilder, Distance, FieldCondition, FieldType, Filter as QdrantFilter,
+    GetPointsBuilder, Match, PayloadIncludeSelector, PointId, PointStruct, Range,
+    ScrollPointsBuilder, SearchPointsBuilder, Timestamp, UpsertPointsBuilder, Value,
+    VectorParamsBuilder, VectorsOutput,
 };
 use qdrant_client::Qdrant;
 
@@ -297,6 +299,42 @@ impl QdrantVectorStore {
         }
     }
 
+    fn extract_vector(vectors: Option<VectorsOutput>) -> Vec<f32> {
+        match vectors.and_then(|v| v.vectors_options) {
+            Some(VectorsOptions::Vector(v)) => v.data,
+            _ => vec![],
+        }
+    }
+
+    /// Restrict a filter to points created at or after a cursor, so paging
+    /// past it doesn't rescan earlier points.
+    fn page_filter(filters: Option<&Filter>, cursor: Option<&Cursor>) -> Option<QdrantFilter> {
+        let mut filter = filters.map(Self::convert_filter);
+        let since = cursor
+            .and_then(|c| c.created_at())
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+        if let Some(since) = since {
+            let range = Condition::datetime_range(
+                "created_at",
+                DatetimeRange {
+                    gte: Some(Timestamp {
+                        seconds: since.timestamp(),
+                        nanos: since.timestamp_subsec_nanos() as i32,
+                    }),
+                    ..Default::default()
+                },
+            );
+            filter = Some(QdrantFilter {
+                must: match filter {
+                    Some(inner) => vec![Condition::from(inner), range],
+                    None => vec![range],
+                },
+                ..Default::default()
+            });
+        }
+        filter
+    }
+
     fn extract_point_id(point_id: Option<PointId>) -> String {
         match point_id {
             Some(PointId {
@@ -328,6 +366,12 @@ impl VectorStore for QdrantVectorStore {
             .await
             .map_err(|e| RookError::vector_store(format!("Failed to create collection: {}", e)))?;
 
+        // Paged listings resume from a `created_at` range
+        let index = CreateFieldIndexCollectionBuilder::new(name, "created_at", FieldType::Datetime);
+        self.client.create_field_index(index).await.map_err(|e| {
+            RookError::vector_store(format!("Failed to create payload index: {}", e))
+        })?;
+
         Ok(())
     }
 
@@ -402,23 +446,11 @@ impl VectorStore for QdrantVectorStore {
             .await
             .map_err(|e| RookError::vector_store(format!("Failed to get vector: {}", e)))?;
 
-        let record = result.result.into_iter().next().map(|point| {
-            let vector = match point.vectors {
-                Some(vectors) => match vectors.vectors_options {
-                    Some(
-                        qdrant_client::qdrant::vectors_output::VectorsOptions::Vector(v),
-                    ) => v.data,
-                    _ => vec![],
-                },
-                None => vec![],
-            };
-
-            VectorRecord {
-                id: id.to_string(),
-                vector,
-                payload: Self::payload_to_hashmap(point.payload),
-                score: None,
-            }
+        let record = result.result.into_iter().next().map(|point| VectorRecord {
+            id: id.to_string(),
+            vector: Self::extract_vector(point.vectors),
+            payload: Self::payload_to_hashmap(point.payload),
+            score: None,
         });
 
         Ok(record)
@@ -493,30 +525,115 @@ impl VectorStore for QdrantVectorStore {
         let records = scroll_result
             .result
             .into_iter()
-            .map(|point| {
-                let id = Self::extract_point_id(point.id);
-                let vector = match point.vectors {
-                    Some(vectors) => match vectors.vectors_options {
-                        Some(
-                            qdrant_client::qdrant::vectors_output::VectorsOptions::Vector(v),
-                        ) => v.data,
-                        _ => vec![],
-                    },
-                    None => vec![],
-                };
-
-                VectorRecord {
-                    id,
-                    vector,
-                    payload: Self::payload_to_hashmap(point.payload),
-                    score: None,
-                }
+            .map(|point| VectorRecord {
+                id: Self::extract_point_id(point.id),
+                vector: Self::extract_vector(point.vectors),
+                payload: Self::payload_to_hashmap(point.payload),
+                score: None,
             })
             .collect();
 
         Ok(records)
     }
 
+    /// Counts matches exactly, then scrolls only the `created_at` of points
+    /// at or after the cursor to find the page, and fetches the page's
+    /// points in full.
+    async fn list_page(
+        &self,
+        filters: Option<Filter>,
+        page: &PageRequest,
+    ) -> RookResult<Page<VectorRecord>> {
+        let mut count = CountPointsBuilder::new(self.collection_name()).exact(true);
+        if let Some(ref f) = filters {
+            count = count.filter(Self::convert_filter(f));
+        }
+        let total = self
+            .client
+            .count(count)
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to count vectors: {}", e)))?
+            .result
+            .map_or(0, |r| r.count as usize);
+
+        let filter = Self::page_filter(filters.as_ref(), page.cursor.as_ref());
+        let mut positions = Vec::new();
+        let mut offset: Option<PointId> = None;
+        loop {
+            let mut request = ScrollPointsBuilder::new(self.collection_name())
+                .with_payload(PayloadIncludeSelector::new(vec!["created_at".to_string()]))
+                .with_vectors(false)
+                .limit(1000);
+            if let Some(ref f) = filter {
+                request = request.filter(f.clone());
+            }
+            if let Some(id) = offset.take() {
+                request = request.offset(id);
+            }
+            let response = self
+                .client
+                .scroll(request)
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to list vectors: {}", e)))?;
+
+            for point in response.result {
+                let id = Self::extract_point_id(point.id);
+                let position = Cursor::of_record(&id, &Self::payload_to_hashmap(point.payload));
+                if page.cursor.as_ref().map_or(true, |c| position.compare(c).is_gt()) {
+                    positions.push((position, id));
+                }
+            }
+            match response.next_page_offset {
+                Some(next) => offset = Some(next),
+                None => break,
+            }
+        }
+        positions.sort_by(|a, b| a.0.compare(&b.0));
+
+        let start = page.offset.min(positions.len());
+        let end = match page.limit {
+            Some(limit) => start.saturating_add(limit).min(positions.len()),
+            None => positions.len(),
+        };
+        let next_cursor =
+            (end > start && end < positions.len()).then(|| positions[end - 1].0.encode());
+        let ids: Vec<String> = positions.drain(start..end).map(|(_, id)| id).collect();
+
+        let mut items = Vec::with_capacity(ids.len());
+        if !ids.is_empty() {
+            let points: Vec<PointId> = ids.iter().map(|id| id.as_str().into()).collect();
+            let request = GetPointsBuilder::new(self.collection_name(), points)
+                .with_payload(true)
+                .with_vectors(true);
+            let mut found: HashMap<String, VectorRecord> = self
+                .client
+                .get_points(request)
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to get vectors: {}", e)))?
+                .result
+                .into_iter()
+                .map(|point| {
+                    let id = Self::extract_point_id(point.id);
+                    let record = VectorRecord {
+                        id: id.clone(),
+                        vector: Self::extract_vector(point.vectors),
+                        payload: Self::payload_to_hashmap(point.payload),
+                        score: None,
+                    };
+                    (id, record)
+                })
+                .collect();
+            // Points deleted since the scroll are left out
+            items.extend(ids.iter().filter_map(|id| found.remove(id)));
+        }
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+        })
+    }
+
     async fn list_collections(&self) -> RookResult<Vec<String>> {
         let collections = self
             .client
@@ -9,6 +9,7 @@
 //! - Zero-copy vector serialization with zerocopy
 //! - Support for L2 distance (Euclidean)
 //! - Metadata storage as JSON
+//! - Optional int8/binary quantization with exact rescoring
 //!
 //! # Example
 //!
@@ -17,6 +18,26 @@
 //!
 //! let store = SqliteVecStore::new(":memory:", "embeddings", 1536)?;
 //! ```
+//!
+//! # Quantization
+//!
+//! With quantization enabled, the vec0 index holds int8 or binary vectors and
+//! the float32 vectors move to an auxiliary column. Search scans the compact
+//! index for `limit * rescore_multiplier` candidates and rescores them with
+//! exact L2 distance. Int8 quantization assumes normalized embeddings with
+//! components in `[-1, 1]`. Opening an existing collection with a different
+//! quantization setting rebuilds its index in place.
+//!
+//! ```ignore
+//! use rook_core::traits::{QuantizationConfig, QuantizationType};
+//!
+//! let store = SqliteVecStore::with_quantization(
+//!     "vectors.db",
+//!     "embeddings",
+//!     1536,
+//!     QuantizationConfig::new(QuantizationType::Binary),
+//! )?;
+//! ```
 
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -28,9 +49,12 @@ use zerocopy::IntoBytes;
 
 use rook_core::error::{RookError, RookResult};
 use rook_core::traits::{
-    CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore,
+    CollectionInfo, DistanceMetric, QuantizationConfig, QuantizationType, VectorRecord,
+    VectorSearchResult, VectorStore,
+};
+use rook_core::types::{
+    Cursor, Filter, MetadataFieldType, OrderBy, Page, PageRequest, SortDirection,
 };
-use rook_core::types::Filter;
 
 /// SQLite vector store using sqlite-vec extension.
 ///
@@ -43,6 +67,8 @@ pub struct SqliteVecStore {
     collection_name: String,
     /// Vector dimension.
     dimension: usize,
+    /// Quantization of the vec0 index (None = float32).
+    quantization: Option<QuantizationConfig>,
 }
 
 impl SqliteVecStore {
@@ -60,6 +86,37 @@ impl SqliteVecStore {
     /// let store = SqliteVecStore::new(":memory:", "embeddings", 1536)?;
     /// ```
     pub fn new(db_path: &str, collection_name: &str, dimension: usize) -> RookResult<Self> {
+        Self::open(db_path, collection_name, dimension, None)
+    }
+
+    /// Create a SqliteVecStore with a quantized index.
+    ///
+    /// Binary quantization requires `dimension` to be a multiple of 8.
+    pub fn with_quantization(
+        db_path: &str,
+        collection_name: &str,
+        dimension: usize,
+        quantization: QuantizationConfig,
+    ) -> RookResult<Self> {
+        Self::open(db_path, collection_name, dimension, Some(quantization))
+    }
+
+    fn open(
+        db_path: &str,
+        collection_name: &str,
+        dimension: usize,
+        quantization: Option<QuantizationConfig>,
+    ) -> RookResult<Self> {
+        let quantization = quantization.filter(|q| q.is_enabled());
+        if let Some(ref q) = quantization {
+            if q.kind == QuantizationType::Binary && dimension % 8 != 0 {
+                return Err(RookError::Configuration(format!(
+                    "Binary quantization requires a dimension divisible by 8, got {}",
+                    dimension
+                )));
+            }
+        }
+
         // Register sqlite-vec extension before opening connection.
         // SAFETY: sqlite3_auto_extension requires a function pointer cast.
         // This is the documented way to register sqlite-vec with rusqlite.
@@ -86,26 +143,186 @@ impl SqliteVecStore {
 
         tracing::debug!("sqlite-vec version: {}", version);
 
-        Ok(Self {
+        let store = Self {
             conn: Mutex::new(conn),
             collection_name: collection_name.to_string(),
             dimension,
-        })
+            quantization,
+        };
+
+        {
+            let conn = store.conn.lock().map_err(|e| {
+                RookError::vector_store(format!("Failed to acquire lock: {}", e))
+            })?;
+            store.sync_schema(&conn)?;
+        }
+
+        Ok(store)
     }
 
-    /// Create the vec0 virtual table for this collection.
-    fn create_table(&self, conn: &Connection) -> RookResult<()> {
-        // Create vec0 virtual table with embedding column and metadata.
+    fn quantization_type(&self) -> QuantizationType {
+        self.quantization
+            .as_ref()
+            .map(|q| q.kind)
+            .unwrap_or_default()
+    }
+
+    /// Column holding full-precision vectors.
+    fn vector_column(&self) -> &'static str {
+        match self.quantization_type() {
+            QuantizationType::None => "embedding",
+            _ => "full_embedding",
+        }
+    }
+
+    /// SQL expression converting a float32 blob into the index's element type.
+    fn index_expr(kind: QuantizationType, value: &str) -> String {
+        match kind {
+            QuantizationType::None => value.to_string(),
+            QuantizationType::Int8 => format!("vec_quantize_int8({}, 'unit')", value),
+            QuantizationType::Binary => format!("vec_quantize_binary({})", value),
+        }
+    }
+
+    /// vec0 table definition for the current quantization setting.
+    fn table_sql(&self, name: &str, dimension: usize) -> String {
         // The + prefix on columns makes them auxiliary (stored but not indexed).
-        let sql = format!(
-            r#"CREATE VIRTUAL TABLE IF NOT EXISTS "{}" USING vec0(
+        match self.quantization_type() {
+            QuantizationType::None => format!(
+                r#"CREATE VIRTUAL TABLE IF NOT EXISTS "{}" USING vec0(
                 embedding float[{}],
                 +id TEXT PRIMARY KEY,
                 +payload TEXT
             )"#,
-            self.collection_name, self.dimension
+                name, dimension
+            ),
+            kind => format!(
+                r#"CREATE VIRTUAL TABLE IF NOT EXISTS "{}" USING vec0(
+                embedding {}[{}],
+                +id TEXT PRIMARY KEY,
+                +payload TEXT,
+                +full_embedding BLOB
+            )"#,
+                name,
+                if kind == QuantizationType::Int8 { "int8" } else { "bit" },
+                dimension
+            ),
+        }
+    }
+
+    /// Rebuild the collection if it was created with a different quantization.
+    fn sync_schema(&self, conn: &Connection) -> RookResult<()> {
+        let existing: Option<String> = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+                [&self.collection_name],
+                |row| row.get(0),
+            )
+            .ok();
+        let Some(existing) = existing else {
+            return Ok(());
+        };
+
+        let existing_kind = if existing.contains("int8[") {
+            QuantizationType::Int8
+        } else if existing.contains("bit[") {
+            QuantizationType::Binary
+        } else {
+            QuantizationType::None
+        };
+        let target_kind = self.quantization_type();
+        if existing_kind == target_kind {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Rebuilding collection '{}' index: {:?} -> {:?}",
+            self.collection_name,
+            existing_kind,
+            target_kind
         );
 
+        let source_column = match existing_kind {
+            QuantizationType::None => "embedding",
+            _ => "full_embedding",
+        };
+        let staging = format!("{}__rebuild", self.collection_name);
+        let insert_columns = match target_kind {
+            QuantizationType::None => "embedding, id, payload",
+            _ => "embedding, id, payload, full_embedding",
+        };
+        let select_columns = match target_kind {
+            QuantizationType::None => format!("{}, id, payload", source_column),
+            kind => format!(
+                "{}, id, payload, {}",
+                Self::index_expr(kind, source_column),
+                source_column
+            ),
+        };
+
+        let map_err = |e: rusqlite::Error| RookError::VectorStore {
+            message: format!(
+                "Failed to rebuild collection '{}': {}",
+                self.collection_name, e
+            ),
+            code: rook_core::error::ErrorCode::VecOperationFailed,
+            source: Some(Box::new(e)),
+        };
+
+        let copy = |from: &str, to: &str, columns: &str, select: &str| {
+            conn.execute(
+                &format!(
+                    r#"INSERT INTO "{}" ({}) SELECT {} FROM "{}""#,
+                    to, columns, select, from
+                ),
+                [],
+            )
+        };
+
+        conn.execute_batch("BEGIN").map_err(map_err)?;
+        let result: rusqlite::Result<()> = (|| {
+            conn.execute(&format!(r#"DROP TABLE IF EXISTS "{}""#, staging), [])?;
+            conn.execute(&self.table_sql(&staging, self.dimension), [])?;
+            copy(
+                &self.collection_name,
+                &staging,
+                insert_columns,
+                &select_columns,
+            )?;
+            conn.execute(&format!(r#"DROP TABLE "{}""#, self.collection_name), [])?;
+            conn.execute(&self.table_sql(&self.collection_name, self.dimension), [])?;
+            copy(
+                &staging,
+                &self.collection_name,
+                insert_columns,
+                insert_columns,
+            )?;
+            conn.execute(&format!(r#"DROP TABLE "{}""#, staging), [])?;
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => conn.execute_batch("COMMIT").map_err(map_err),
+            Err(e) => {
+                let _ = conn.execute_batch("ROLLBACK");
+                Err(map_err(e))
+            }
+        }
+    }
+
+    /// L2 distance between two float vectors.
+    fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y) * (x - y))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Create the vec0 virtual table for this collection.
+    fn create_table(&self, conn: &Connection) -> RookResult<()> {
+        let sql = self.table_sql(&self.collection_name, self.dimension);
+
         conn.execute(&sql, []).map_err(|e| RookError::VectorStore {
             message: format!("Failed to create vec0 table: {}", e),
             code: rook_core::error::ErrorCode::VecOperationFailed,
@@ -128,6 +345,220 @@ impl SqliteVecStore {
             .collect()
     }
 
+    /// List records, optionally ordered by a typed payload field.
+    fn list_records(
+        &self,
+        filters: Option<Filter>,
+        order_by: Option<&OrderBy>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        let conn = self.conn.lock().map_err(|e| {
+            RookError::vector_store(format!("Failed to acquire lock: {}", e))
+        })?;
+
+        // Fetch more if filtering is needed. Ordered results are filtered
+        // after sorting, so any match may belong in the first `limit`.
+        let fetch_limit = match (filters.as_ref(), limit) {
+            (Some(_), Some(_)) if order_by.is_some() => 10000,
+            (Some(_), Some(l)) => l * 10,
+            (None, Some(l)) => l,
+            (_, None) => 10000, // Reasonable max
+        };
+
+        let sql = format!(
+            r#"SELECT {}, id, payload FROM "{}"{} LIMIT ?"#,
+            self.vector_column(),
+            self.collection_name,
+            order_by.map(Self::order_clause).unwrap_or_default()
+        );
+
+        let mut stmt = conn.prepare(&sql).map_err(|e| RookError::VectorStore {
+            message: format!("Failed to prepare list statement: {}", e),
+            code: rook_core::error::ErrorCode::VecOperationFailed,
+            source: Some(Box::new(e)),
+        })?;
+
+        let rows = stmt
+            .query_map([fetch_limit], |row| {
+                let embedding_bytes: Vec<u8> = row.get(0)?;
+                let id: String = row.get(1)?;
+                let payload_str: String = row.get(2)?;
+                Ok((embedding_bytes, id, payload_str))
+            })
+            .map_err(|e| RookError::VectorStore {
+                message: format!("Failed to execute list: {}", e),
+                code: rook_core::error::ErrorCode::VecOperationFailed,
+                source: Some(Box::new(e)),
+            })?;
+
+        let mut records = Vec::new();
+        for row in rows {
+            let (embedding_bytes, id, payload_str) = row.map_err(|e| RookError::VectorStore {
+                message: format!("Failed to read list result: {}", e),
+                code: rook_core::error::ErrorCode::VecOperationFailed,
+                source: Some(Box::new(e)),
+            })?;
+
+            let vector = Self::bytes_to_vector(&embedding_bytes);
+            let payload: HashMap<String, Value> =
+                serde_json::from_str(&payload_str).unwrap_or_default();
+
+            records.push(VectorRecord {
+                id,
+                vector,
+                payload,
+                score: None,
+            });
+        }
+
+        // Apply post-filter if provided.
+        let records = if let Some(filter) = filters {
+            records
+                .into_iter()
+                .filter(|r| Self::matches_filter(&r.payload, &filter))
+                .collect::<Vec<_>>()
+        } else {
+            records
+        };
+
+        // Apply limit.
+        let records = match limit {
+            Some(l) => records.into_iter().take(l).collect(),
+            None => records,
+        };
+
+        Ok(records)
+    }
+
+    /// List one page of records in `(created_at, id)` order.
+    ///
+    /// Payloads are scanned in query order without their vectors, to count
+    /// the matches and find the window; only the page's vectors are read.
+    fn list_page_records(
+        &self,
+        filters: Option<Filter>,
+        page: &PageRequest,
+    ) -> RookResult<Page<VectorRecord>> {
+        let conn = self.conn.lock().map_err(|e| {
+            RookError::vector_store(format!("Failed to acquire lock: {}", e))
+        })?;
+
+        // Missing or non-text timestamps are NULL, which sorts first, as in
+        // `Cursor::compare`.
+        let sql = format!(
+            r#"SELECT id, payload FROM "{}"
+               ORDER BY CASE WHEN json_type(payload, '$.created_at') = 'text'
+                        THEN julianday(json_extract(payload, '$.created_at')) END, id"#,
+            self.collection_name
+        );
+        let mut stmt = conn.prepare(&sql).map_err(|e| RookError::VectorStore {
+            message: format!("Failed to prepare list statement: {}", e),
+            code: rook_core::error::ErrorCode::VecOperationFailed,
+            source: Some(Box::new(e)),
+        })?;
+        let mut rows = stmt.query([]).map_err(|e| RookError::VectorStore {
+            message: format!("Failed to execute list: {}", e),
+            code: rook_core::error::ErrorCode::VecOperationFailed,
+            source: Some(Box::new(e)),
+        })?;
+
+        let mut total = 0;
+        let mut skipped = 0;
+        let mut items = Vec::new();
+        let mut last = None;
+        let mut more = false;
+        while let Some(row) = rows.next().map_err(|e| RookError::VectorStore {
+            message: format!("Failed to read list result: {}", e),
+            code: rook_core::error::ErrorCode::VecOperationFailed,
+            source: Some(Box::new(e)),
+        })? {
+            let id: String = row.get(0).map_err(|e| RookError::vector_store(e.to_string()))?;
+            let payload_str: String =
+                row.get(1).map_err(|e| RookError::vector_store(e.to_string()))?;
+            let payload: HashMap<String, Value> =
+                serde_json::from_str(&payload_str).unwrap_or_default();
+            if !filters.as_ref().map_or(true, |f| Self::matches_filter(&payload, f)) {
+                continue;
+            }
+            total += 1;
+
+            let position = Cursor::of_record(&id, &payload);
+            if page.cursor.as_ref().is_some_and(|c| position.compare(c).is_le()) {
+                continue;
+            }
+            if skipped < page.offset {
+                skipped += 1;
+                continue;
+            }
+            if page.limit.is_some_and(|limit| items.len() >= limit) {
+                more = true;
+            } else {
+                last = Some(position);
+                items.push(VectorRecord {
+                    id,
+                    vector: Vec::new(),
+                    payload,
+                    score: None,
+                });
+            }
+        }
+        drop(rows);
+        drop(stmt);
+
+        let sql = format!(
+            r#"SELECT {} FROM "{}" WHERE id = ?"#,
+            self.vector_column(),
+            self.collection_name
+        );
+        for record in &mut items {
+            let bytes: Vec<u8> = conn
+                .query_row(&sql, [&record.id], |row| row.get(0))
+                .map_err(|e| RookError::VectorStore {
+                    message: format!("Failed to get record '{}': {}", record.id, e),
+                    code: rook_core::error::ErrorCode::VecOperationFailed,
+                    source: Some(Box::new(e)),
+                })?;
+            record.vector = Self::bytes_to_vector(&bytes);
+        }
+
+        // A cursor is only useful when matches follow the page
+        let next_cursor = last.filter(|_| more).map(|cursor| cursor.encode());
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+        })
+    }
+
+    /// `ORDER BY` clause for a typed payload field. Values that are missing
+    /// or of another type sort last.
+    fn order_clause(order_by: &OrderBy) -> String {
+        let path = format!("'$.{}'", order_by.field());
+        let value = format!("json_extract(payload, {})", path);
+        let json_type = format!("json_type(payload, {})", path);
+        let key = match order_by.field_type() {
+            MetadataFieldType::String => {
+                format!("CASE WHEN {} = 'text' THEN {} END", json_type, value)
+            }
+            MetadataFieldType::Number => format!(
+                "CASE WHEN {} IN ('integer', 'real') THEN {} END",
+                json_type, value
+            ),
+            MetadataFieldType::Date => {
+                format!("CASE WHEN {} = 'text' THEN julianday({}) END", json_type, value)
+            }
+            MetadataFieldType::Boolean => {
+                format!("CASE WHEN {} IN ('true', 'false') THEN {} END", json_type, value)
+            }
+        };
+        let direction = match order_by.direction() {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+        format!(" ORDER BY ({0}) IS NULL, {0} {1}", key, direction)
+    }
+
     /// Apply filter to records in memory (post-filter).
     /// sqlite-vec doesn't support native filtering, so we filter after retrieval.
     fn apply_filter(records: Vec<VectorSearchResult>, filter: &Filter) -> Vec<VectorSearchResult> {
@@ -189,6 +620,8 @@ impl SqliteVecStore {
     }
 
     /// Compare two JSON values using a comparison function.
+    ///
+    /// Numbers compare by value and RFC 3339 timestamps by time.
     fn compare_values<F>(field_value: Option<&Value>, compare_to: &Value, cmp: F) -> bool
     where
         F: Fn(f64, f64) -> bool,
@@ -200,6 +633,17 @@ impl SqliteVecStore {
                     _ => false,
                 }
             }
+            (Some(Value::String(a)), Value::String(b)) => {
+                let timestamp = |s: &str| {
+                    chrono::DateTime::parse_from_rfc3339(s)
+                        .ok()
+                        .map(|t| t.timestamp_micros() as f64)
+                };
+                match (timestamp(a), timestamp(b)) {
+                    (Some(av), Some(bv)) => cmp(av, bv),
+                    _ => false,
+                }
+            }
             _ => false,
         }
     }
@@ -219,14 +663,7 @@ impl VectorStore for SqliteVecStore {
 
         // Create vec0 virtual table.
         // Note: sqlite-vec uses L2 distance by default, we ignore the distance parameter.
-        let sql = format!(
-            r#"CREATE VIRTUAL TABLE IF NOT EXISTS "{}" USING vec0(
-                embedding float[{}],
-                +id TEXT PRIMARY KEY,
-                +payload TEXT
-            )"#,
-            name, dimension
-        );
+        let sql = self.table_sql(name, dimension);
 
         conn.execute(&sql, []).map_err(|e| RookError::VectorStore {
             message: format!("Failed to create collection '{}': {}", name, e),
@@ -247,10 +684,18 @@ impl VectorStore for SqliteVecStore {
         self.create_table(&conn)?;
 
         // Insert each record.
-        let sql = format!(
-            r#"INSERT OR REPLACE INTO "{}" (embedding, id, payload) VALUES (?, ?, ?)"#,
-            self.collection_name
-        );
+        let sql = match self.quantization_type() {
+            QuantizationType::None => format!(
+                r#"INSERT OR REPLACE INTO "{}" (embedding, id, payload) VALUES (?1, ?2, ?3)"#,
+                self.collection_name
+            ),
+            kind => format!(
+                r#"INSERT OR REPLACE INTO "{}" (embedding, id, payload, full_embedding)
+                   VALUES ({}, ?2, ?3, ?1)"#,
+                self.collection_name,
+                Self::index_expr(kind, "?1")
+            ),
+        };
 
         let mut stmt = conn.prepare(&sql).map_err(|e| RookError::VectorStore {
             message: format!("Failed to prepare insert statement: {}", e),
@@ -291,16 +736,28 @@ impl VectorStore for SqliteVecStore {
         })?;
 
         // Use MATCH operator for KNN search.
-        // We fetch more results if filtering is needed.
+        // We fetch more results if filtering is needed, and oversample
+        // candidates for rescoring when the index is quantized.
         let fetch_limit = if filters.is_some() { limit * 10 } else { limit };
+        let fetch_limit = match self.quantization {
+            Some(ref q) => fetch_limit * q.rescore_multiplier(),
+            None => fetch_limit,
+        };
+        let kind = self.quantization_type();
 
         let sql = format!(
-            r#"SELECT id, distance, payload
+            r#"SELECT id, distance, payload{}
                FROM "{}"
-               WHERE embedding MATCH ?
+               WHERE embedding MATCH {}
                ORDER BY distance
-               LIMIT ?"#,
-            self.collection_name
+               LIMIT ?2"#,
+            if kind == QuantizationType::None {
+                ""
+            } else {
+                ", full_embedding"
+            },
+            self.collection_name,
+            Self::index_expr(kind, "?1")
         );
 
         let query_bytes = Self::vector_to_bytes(query_vector);
@@ -313,8 +770,13 @@ impl VectorStore for SqliteVecStore {
         let rows = stmt
             .query_map(rusqlite::params![query_bytes, fetch_limit], |row| {
                 let id: String = row.get(0)?;
-                let distance: f32 = row.get(1)?;
+                let mut distance: f32 = row.get(1)?;
                 let payload_str: String = row.get(2)?;
+                if kind != QuantizationType::None {
+                    // Rescore with the exact distance.
+                    let full: Vec<u8> = row.get(3)?;
+                    distance = Self::l2_distance(query_vector, &Self::bytes_to_vector(&full));
+                }
                 Ok((id, distance, payload_str))
             })
             .map_err(|e| RookError::VectorStore {
@@ -341,12 +803,20 @@ impl VectorStore for SqliteVecStore {
             results.push(VectorSearchResult { id, score, payload });
         }
 
+        if kind != QuantizationType::None {
+            results.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+
         // Apply post-filter if provided.
-        let results = if let Some(filter) = filters {
+        let results: Vec<_> = if let Some(filter) = filters {
             let filtered = Self::apply_filter(results, &filter);
             filtered.into_iter().take(limit).collect()
         } else {
-            results
+            results.into_iter().take(limit).collect()
         };
 
         tracing::debug!(
@@ -364,7 +834,8 @@ impl VectorStore for SqliteVecStore {
         })?;
 
         let sql = format!(
-            r#"SELECT embedding, id, payload FROM "{}" WHERE id = ?"#,
+            r#"SELECT {}, id, payload FROM "{}" WHERE id = ?"#,
+            self.vector_column(),
             self.collection_name
         );
 
@@ -454,78 +925,24 @@ impl VectorStore for SqliteVecStore {
         filters: Option<Filter>,
         limit: Option<usize>,
     ) -> RookResult<Vec<VectorRecord>> {
-        let conn = self.conn.lock().map_err(|e| {
-            RookError::vector_store(format!("Failed to acquire lock: {}", e))
-        })?;
-
-        // Fetch more if filtering is needed.
-        let fetch_limit = match (filters.as_ref(), limit) {
-            (Some(_), Some(l)) => l * 10,
-            (None, Some(l)) => l,
-            (_, None) => 10000, // Reasonable max
-        };
-
-        let sql = format!(
-            r#"SELECT embedding, id, payload FROM "{}" LIMIT ?"#,
-            self.collection_name
-        );
-
-        let mut stmt = conn.prepare(&sql).map_err(|e| RookError::VectorStore {
-            message: format!("Failed to prepare list statement: {}", e),
-            code: rook_core::error::ErrorCode::VecOperationFailed,
-            source: Some(Box::new(e)),
-        })?;
-
-        let rows = stmt
-            .query_map([fetch_limit], |row| {
-                let embedding_bytes: Vec<u8> = row.get(0)?;
-                let id: String = row.get(1)?;
-                let payload_str: String = row.get(2)?;
-                Ok((embedding_bytes, id, payload_str))
-            })
-            .map_err(|e| RookError::VectorStore {
-                message: format!("Failed to execute list: {}", e),
-                code: rook_core::error::ErrorCode::VecOperationFailed,
-                source: Some(Box::new(e)),
-            })?;
-
-        let mut records = Vec::new();
-        for row in rows {
-            let (embedding_bytes, id, payload_str) = row.map_err(|e| RookError::VectorStore {
-                message: format!("Failed to read list result: {}", e),
-                code: rook_core::error::ErrorCode::VecOperationFailed,
-                source: Some(Box::new(e)),
-            })?;
-
-            let vector = Self::bytes_to_vector(&embedding_bytes);
-            let payload: HashMap<String, Value> =
-                serde_json::from_str(&payload_str).unwrap_or_default();
-
-            records.push(VectorRecord {
-                id,
-                vector,
-                payload,
-                score: None,
-            });
-        }
-
-        // Apply post-filter if provided.
-        let records = if let Some(filter) = filters {
-            records
-                .into_iter()
-                .filter(|r| Self::matches_filter(&r.payload, &filter))
-                .collect::<Vec<_>>()
-        } else {
-            records
-        };
+        self.list_records(filters, None, limit)
+    }
 
-        // Apply limit.
-        let records = match limit {
-            Some(l) => records.into_iter().take(l).collect(),
-            None => records,
-        };
+    async fn list_ordered(
+        &self,
+        filters: Option<Filter>,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        self.list_records(filters, Some(order_by), limit)
+    }
 
-        Ok(records)
+    async fn list_page(
+        &self,
+        filters: Option<Filter>,
+        page: &PageRequest,
+    ) -> RookResult<Page<VectorRecord>> {
+        self.list_page_records(filters, page)
     }
 
     async fn list_collections(&self) -> RookResult<Vec<String>> {
@@ -619,6 +1036,10 @@ impl VectorStore for SqliteVecStore {
     fn collection_name(&self) -> &str {
         &self.collection_name
     }
+
+    fn supports_timestamp_filters(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -681,6 +1102,95 @@ mod tests {
         assert!(results[0].score >= results[1].score);
     }
 
+    #[tokio::test]
+    async fn test_list_ordered() {
+        let store = create_test_store();
+
+        let mut records = Vec::new();
+        for (id, importance, created_at) in [
+            ("a", Value::from(9), "2024-01-01T12:00:00+00:00"),
+            ("b", Value::from(10), "2024-01-01T13:00:00+02:00"),
+            ("c", Value::from("high"), "2024-01-02T00:00:00+00:00"),
+        ] {
+            let mut record = create_test_vector(id, [1.0, 0.0, 0.0, 0.0]);
+            record.payload.insert("importance".to_string(), importance);
+            record.payload.insert("created_at".to_string(), Value::from(created_at));
+            records.push(record);
+        }
+        store.insert(records).await.unwrap();
+
+        let ids = |records: Vec<VectorRecord>| -> Vec<String> {
+            records.into_iter().map(|r| r.id).collect()
+        };
+
+        // Numbers compare numerically; non-numbers sort last
+        let by_importance =
+            OrderBy::new("importance", MetadataFieldType::Number, SortDirection::Desc).unwrap();
+        let listed = store.list_ordered(None, &by_importance, None).await.unwrap();
+        assert_eq!(ids(listed), vec!["b", "a", "c"]);
+
+        // Dates compare as instants, and the limit applies after ordering
+        let by_created =
+            OrderBy::new("created_at", MetadataFieldType::Date, SortDirection::Asc).unwrap();
+        let filter = Filter::eq("category", "test");
+        let listed = store.list_ordered(Some(filter), &by_created, Some(2)).await.unwrap();
+        assert_eq!(ids(listed), vec!["b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_list_page() {
+        let store = create_test_store();
+
+        let mut records = Vec::new();
+        for (id, created_at, category) in [
+            ("a", Some("2024-01-03T00:00:00+00:00"), "test"),
+            ("b", Some("2024-01-01T12:00:00+02:00"), "test"),
+            ("c", Some("2024-01-01T11:00:00+00:00"), "other"),
+            ("d", None, "test"),
+            ("e", Some("2024-01-03T00:00:00+00:00"), "test"),
+        ] {
+            let mut record = create_test_vector(id, [1.0, 2.0, 3.0, 4.0]);
+            record.payload.insert("category".to_string(), Value::from(category));
+            if let Some(created_at) = created_at {
+                record.payload.insert("created_at".to_string(), Value::from(created_at));
+            }
+            records.push(record);
+        }
+        store.insert(records).await.unwrap();
+
+        let ids = |page: &Page<VectorRecord>| -> Vec<String> {
+            page.items.iter().map(|r| r.id.clone()).collect()
+        };
+        let filter = || Some(Filter::eq("category", "test"));
+
+        // Undated first, then by instant and ID, counting only matches
+        let first = store
+            .list_page(filter(), &PageRequest::new().with_limit(2))
+            .await
+            .unwrap();
+        assert_eq!(ids(&first), vec!["d", "b"]);
+        assert_eq!(first.total, 4);
+        assert_eq!(first.items[1].vector, vec![1.0, 2.0, 3.0, 4.0]);
+
+        let cursor = Cursor::decode(first.next_cursor.as_deref().unwrap()).unwrap();
+        let second = store
+            .list_page(filter(), &PageRequest::new().with_limit(2).with_cursor(cursor))
+            .await
+            .unwrap();
+        assert_eq!(ids(&second), vec!["a", "e"]);
+        assert_eq!(second.total, 4);
+        assert!(second.next_cursor.is_none());
+
+        // Offsets skip matches, and agree with the in-memory default
+        let request = PageRequest::new().with_offset(1).with_limit(2);
+        let offset = store.list_page(filter(), &request).await.unwrap();
+        assert_eq!(ids(&offset), vec!["b", "a"]);
+        let all = store.list(filter(), None).await.unwrap();
+        let expected = request.apply(all, |r| Cursor::of_record(&r.id, &r.payload));
+        assert_eq!(ids(&offset), ids(&expected));
+        assert_eq!(offset.next_cursor, expected.next_cursor);
+    }
+
     #[tokio::test]
     async fn test_get_and_delete() {
         let store = create_test_store();
@@ -775,6 +1285,42 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_list_with_timestamp_filter() {
+        let store = create_test_store();
+
+        let mut records = Vec::new();
+        for (i, created_at) in [
+            "2024-01-01T00:00:00+00:00",
+            "2024-02-01T12:00:00Z",
+            "2024-03-01T00:00:00+02:00",
+        ]
+        .iter()
+        .enumerate()
+        {
+            let mut payload = HashMap::new();
+            payload.insert("created_at".to_string(), Value::String(created_at.to_string()));
+            records.push(VectorRecord {
+                id: format!("record-{}", i),
+                vector: vec![i as f32, 0.0, 0.0, 0.0],
+                payload,
+                score: None,
+            });
+        }
+        store.insert(records).await.unwrap();
+
+        let filter = Filter::and(vec![
+            Filter::gte("created_at", "2024-01-15T00:00:00Z"),
+            Filter::lt("created_at", "2024-03-01T00:00:00Z"),
+        ]);
+        let results = store.list(Some(filter), None).await.unwrap();
+
+        // The March record is 2024-02-29T22:00Z once offsets are applied.
+        let mut ids: Vec<_> = results.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["record-1", "record-2"]);
+    }
+
     #[tokio::test]
     async fn test_reset() {
         let store = create_test_store();
@@ -802,4 +1348,92 @@ mod tests {
         let info = store.collection_info("test_collection").await.unwrap();
         assert_eq!(info.vector_count, 1);
     }
+
+    fn unit_vectors() -> Vec<VectorRecord> {
+        let mut records = Vec::new();
+        for i in 0..8 {
+            let mut values = [0.0f32; 8];
+            values[i] = 0.8;
+            values[(i + 1) % 8] = 0.6;
+            records.push(VectorRecord::new(
+                format!("v{}", i),
+                values.to_vec(),
+                HashMap::from([("slot".to_string(), Value::from(i))]),
+            ));
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn test_quantized_search_rescores_exactly() {
+        for kind in [QuantizationType::Int8, QuantizationType::Binary] {
+            let store = SqliteVecStore::with_quantization(
+                ":memory:",
+                "quantized",
+                8,
+                QuantizationConfig::new(kind),
+            )
+            .unwrap();
+            store.insert(unit_vectors()).await.unwrap();
+
+            let query = vec![0.8, 0.6, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+            let results = store.search(&query, 2, None).await.unwrap();
+            assert_eq!(results.len(), 2, "{:?}", kind);
+            assert_eq!(results[0].id, "v0", "{:?}", kind);
+            // Exact rescoring: identical vector has distance 0.
+            assert!((results[0].score - 1.0).abs() < 1e-6, "{:?}", kind);
+
+            // Full-precision vectors are returned by get().
+            let record = store.get("v3").await.unwrap().unwrap();
+            assert_eq!(record.vector[3], 0.8);
+            assert_eq!(record.vector[4], 0.6);
+        }
+    }
+
+    #[test]
+    fn test_binary_quantization_requires_byte_aligned_dimension() {
+        let result = SqliteVecStore::with_quantization(
+            ":memory:",
+            "quantized",
+            4,
+            QuantizationConfig::new(QuantizationType::Binary),
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_existing_collection_is_rebuilt_on_quantization_change() {
+        let path = std::env::temp_dir().join(format!(
+            "rook-sqlite-vec-quantize-{}.db",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let db_path = path.to_string_lossy().to_string();
+
+        {
+            let store = SqliteVecStore::new(&db_path, "memories", 8).unwrap();
+            store.insert(unit_vectors()).await.unwrap();
+        }
+
+        {
+            let store = SqliteVecStore::with_quantization(
+                &db_path,
+                "memories",
+                8,
+                QuantizationConfig::new(QuantizationType::Int8),
+            )
+            .unwrap();
+            assert_eq!(store.list(None, None).await.unwrap().len(), 8);
+            let query = vec![0.0, 0.0, 0.8, 0.6, 0.0, 0.0, 0.0, 0.0];
+            assert_eq!(store.search(&query, 1, None).await.unwrap()[0].id, "v2");
+        }
+
+        // And back to float32.
+        let store = SqliteVecStore::new(&db_path, "memories", 8).unwrap();
+        let record = store.get("v5").await.unwrap().unwrap();
+        assert_eq!(record.vector[5], 0.8);
+
+        drop(store);
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -0,0 +1,706 @@
+//! Turbopuffer vector store implementation.
+//!
+//! Each collection maps to a family of Turbopuffer namespaces: records are
+//! routed to `{collection}__{user_id}` so per-user queries only touch that
+//! user's data (see [`crate::namespace`]). Scalar payload fields are stored
+//! as filterable attributes; the full payload is kept as JSON in `_payload`.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::{
+    CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore,
+    VectorStoreConfig,
+};
+use rook_core::types::{Filter, FilterCondition, FilterOperator};
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::namespace::{NamespaceMapper, NamespaceScope};
+
+/// Attribute holding the full JSON payload.
+const PAYLOAD_ATTRIBUTE: &str = "_payload";
+
+/// Page size used when listing namespaces and rows.
+const PAGE_SIZE: usize = 1000;
+
+/// Turbopuffer vector store implementation.
+pub struct TurbopufferVectorStore {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    distance: RwLock<DistanceMetric>,
+    namespaces: NamespaceMapper,
+    config: VectorStoreConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct TurbopufferQueryResponse {
+    #[serde(default)]
+    rows: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TurbopufferNamespace {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TurbopufferNamespaceList {
+    #[serde(default)]
+    namespaces: Vec<TurbopufferNamespace>,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TurbopufferMetadata {
+    #[serde(default)]
+    approx_row_count: u64,
+}
+
+impl TurbopufferVectorStore {
+    /// Create a new Turbopuffer vector store.
+    ///
+    /// Provider config keys:
+    /// - `api_key` (or `TURBOPUFFER_API_KEY`)
+    /// - `url` - API base URL (default: `https://api.turbopuffer.com`)
+    /// - `distance_metric` - `cosine` (default) or `euclidean`
+    /// - `namespace_field` - payload field used for namespace scoping
+    ///   (default: `user_id`, empty string disables)
+    pub async fn new(config: VectorStoreConfig) -> RookResult<Self> {
+        let api_key = config
+            .config
+            .get("api_key")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or_else(|| std::env::var("TURBOPUFFER_API_KEY").ok())
+            .ok_or_else(|| {
+                RookError::Configuration(
+                    "Turbopuffer API key required. Set TURBOPUFFER_API_KEY or provide api_key."
+                        .to_string(),
+                )
+            })?;
+
+        let base_url = config
+            .config
+            .get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("https://api.turbopuffer.com")
+            .trim_end_matches('/')
+            .to_string();
+
+        let distance = match config.config.get("distance_metric").and_then(|v| v.as_str()) {
+            Some("euclidean") => DistanceMetric::Euclidean,
+            _ => DistanceMetric::Cosine,
+        };
+
+        Ok(Self {
+            client: Client::new(),
+            api_key,
+            base_url,
+            distance: RwLock::new(distance),
+            namespaces: NamespaceMapper::from_config(&config),
+            config,
+        })
+    }
+
+    fn distance(&self) -> DistanceMetric {
+        *self.distance.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn distance_name(&self) -> &'static str {
+        match self.distance() {
+            DistanceMetric::Euclidean => "euclidean_squared",
+            _ => "cosine_distance",
+        }
+    }
+
+    /// Convert a Turbopuffer distance to a similarity score (higher is better).
+    fn distance_to_score(&self, distance: f32) -> f32 {
+        match self.distance() {
+            DistanceMetric::Euclidean => 1.0 / (1.0 + distance),
+            _ => 1.0 - distance,
+        }
+    }
+
+    fn namespace_url(&self, namespace: &str) -> String {
+        format!("{}/v2/namespaces/{}", self.base_url, namespace)
+    }
+
+    async fn post(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+        action: &str,
+    ) -> RookResult<reqwest::Response> {
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to {}: {}", action, e)))?;
+
+        if !response.status().is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(RookError::vector_store(format!("Failed to {}: {}", action, error)));
+        }
+
+        Ok(response)
+    }
+
+    /// Write rows and deletes to one namespace.
+    async fn write(
+        &self,
+        namespace: &str,
+        rows: Vec<serde_json::Value>,
+        deletes: Vec<String>,
+    ) -> RookResult<()> {
+        let mut body = json!({ "distance_metric": self.distance_name() });
+        if !rows.is_empty() {
+            body["upsert_rows"] = json!(rows);
+        }
+        if !deletes.is_empty() {
+            body["deletes"] = json!(deletes);
+        }
+        self.post(&self.namespace_url(namespace), &body, "write vectors").await?;
+        Ok(())
+    }
+
+    /// Run a query against one namespace. Missing namespaces return no rows.
+    async fn query(
+        &self,
+        namespace: &str,
+        body: serde_json::Value,
+    ) -> RookResult<Vec<serde_json::Map<String, serde_json::Value>>> {
+        let url = format!("{}/query", self.namespace_url(namespace));
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to query: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(RookError::vector_store(format!("Failed to query: {}", error)));
+        }
+
+        let result: TurbopufferQueryResponse = response
+            .json()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+        Ok(result.rows)
+    }
+
+    /// List every namespace that belongs to this collection.
+    async fn collection_namespaces(&self) -> RookResult<Vec<String>> {
+        let mut namespaces = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(format!("{}/v1/namespaces", self.base_url))
+                .bearer_auth(&self.api_key)
+                .query(&[
+                    ("prefix", self.namespaces.base()),
+                    ("page_size", &PAGE_SIZE.to_string()),
+                ]);
+            if let Some(ref c) = cursor {
+                request = request.query(&[("cursor", c)]);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to list namespaces: {}", e)))?;
+            if !response.status().is_success() {
+                let error = response.text().await.unwrap_or_default();
+                return Err(RookError::vector_store(format!(
+                    "Failed to list namespaces: {}",
+                    error
+                )));
+            }
+
+            let page: TurbopufferNamespaceList = response
+                .json()
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+
+            namespaces.extend(page.namespaces.into_iter().map(|n| n.id));
+            match page.next_cursor {
+                Some(next) if !next.is_empty() => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(namespaces)
+    }
+
+    async fn scoped_namespaces(&self, filter: Option<&Filter>) -> RookResult<Vec<String>> {
+        match self.namespaces.scope(filter) {
+            NamespaceScope::Only(namespaces) => Ok(namespaces),
+            NamespaceScope::All => Ok(self
+                .collection_namespaces()
+                .await?
+                .into_iter()
+                .filter(|n| self.namespaces.owns(n))
+                .collect()),
+        }
+    }
+
+    /// Find an id, trying its remembered namespace first.
+    async fn locate(&self, id: &str) -> RookResult<Option<(String, VectorRecord)>> {
+        let id_filter = json!(["id", "Eq", id]);
+        let body = json!({
+            "rank_by": ["id", "asc"],
+            "top_k": 1,
+            "filters": id_filter,
+            "include_attributes": true,
+        });
+
+        let mut candidates = Vec::new();
+        if let Some(namespace) = self.namespaces.location(id) {
+            candidates.push(namespace);
+        }
+        for namespace in self.scoped_namespaces(None).await? {
+            if !candidates.contains(&namespace) {
+                candidates.push(namespace);
+            }
+        }
+
+        for namespace in candidates {
+            if let Some(row) = self.query(&namespace, body.clone()).await?.into_iter().next() {
+                self.namespaces.remember(id, &namespace);
+                return Ok(Some((namespace, Self::row_to_record(row))));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn record_to_row(record: &VectorRecord) -> serde_json::Value {
+        let mut row = serde_json::Map::new();
+        for (key, value) in &record.payload {
+            if key != "id" && key != "vector" && Self::is_attribute_value(value) {
+                row.insert(key.clone(), value.clone());
+            }
+        }
+        row.insert("id".to_string(), json!(record.id));
+        row.insert("vector".to_string(), json!(record.vector));
+        row.insert(
+            PAYLOAD_ATTRIBUTE.to_string(),
+            json!(serde_json::to_string(&record.payload).unwrap_or_default()),
+        );
+        serde_json::Value::Object(row)
+    }
+
+    /// Turbopuffer attributes are scalars or arrays of scalars.
+    fn is_attribute_value(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Object(_) => false,
+            serde_json::Value::Array(items) => items
+                .iter()
+                .all(|v| !v.is_object() && !v.is_array()),
+            _ => true,
+        }
+    }
+
+    fn row_payload(
+        row: &serde_json::Map<String, serde_json::Value>,
+    ) -> HashMap<String, serde_json::Value> {
+        row.get(PAYLOAD_ATTRIBUTE)
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default()
+    }
+
+    fn row_to_record(row: serde_json::Map<String, serde_json::Value>) -> VectorRecord {
+        let id = row
+            .get("id")
+            .map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default();
+        let vector = row
+            .get("vector")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_f64().map(|f| f as f32)).collect())
+            .unwrap_or_default();
+
+        VectorRecord {
+            id,
+            vector,
+            payload: Self::row_payload(&row),
+            score: None,
+        }
+    }
+
+    /// Translate a filter into Turbopuffer's filter expression syntax.
+    fn convert_filter(filter: &Filter) -> Option<serde_json::Value> {
+        match filter {
+            Filter::Condition(cond) => Self::convert_condition(cond),
+            Filter::And(filters) => {
+                let parts: Vec<_> = filters.iter().filter_map(Self::convert_filter).collect();
+                match parts.len() {
+                    0 => None,
+                    1 => parts.into_iter().next(),
+                    _ => Some(json!(["And", parts])),
+                }
+            }
+            Filter::Or(filters) => {
+                let parts: Vec<_> = filters.iter().filter_map(Self::convert_filter).collect();
+                // An OR with an unconstrained branch matches everything
+                if parts.len() < filters.len() {
+                    return None;
+                }
+                match parts.len() {
+                    0 => None,
+                    1 => parts.into_iter().next(),
+                    _ => Some(json!(["Or", parts])),
+                }
+            }
+            Filter::Not(inner) => Self::convert_filter(inner).map(|f| json!(["Not", f])),
+        }
+    }
+
+    fn convert_condition(cond: &FilterCondition) -> Option<serde_json::Value> {
+        let field = cond.field.as_str();
+        let expr = match &cond.operator {
+            FilterOperator::Eq(value) => json!([field, "Eq", value]),
+            FilterOperator::Ne(value) => json!([field, "NotEq", value]),
+            FilterOperator::Gt(value) => json!([field, "Gt", value]),
+            FilterOperator::Gte(value) => json!([field, "Gte", value]),
+            FilterOperator::Lt(value) => json!([field, "Lt", value]),
+            FilterOperator::Lte(value) => json!([field, "Lte", value]),
+            FilterOperator::In(values) => json!([field, "In", values]),
+            FilterOperator::Nin(values) => json!([field, "NotIn", values]),
+            FilterOperator::Contains(text) => json!([field, "Glob", format!("*{}*", text)]),
+            FilterOperator::Icontains(text) => json!([field, "IGlob", format!("*{}*", text)]),
+            FilterOperator::Between { min, max } => {
+                json!(["And", [[field, "Gte", min], [field, "Lte", max]]])
+            }
+            FilterOperator::IsNull | FilterOperator::NotExists => json!([field, "Eq", null]),
+            FilterOperator::IsNotNull | FilterOperator::Exists => json!([field, "NotEq", null]),
+            FilterOperator::Wildcard => return None,
+        };
+        Some(expr)
+    }
+}
+
+#[async_trait]
+impl VectorStore for TurbopufferVectorStore {
+    async fn create_collection(
+        &self,
+        _name: &str,
+        _dimension: usize,
+        distance: DistanceMetric,
+    ) -> RookResult<()> {
+        // Namespaces are created implicitly on first write
+        *self.distance.write().unwrap_or_else(|e| e.into_inner()) = distance;
+        Ok(())
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> RookResult<()> {
+        let mut by_namespace: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+        for record in &records {
+            let namespace = self.namespaces.namespace_for_payload(&record.payload);
+            by_namespace
+                .entry(namespace)
+                .or_default()
+                .push(Self::record_to_row(record));
+        }
+
+        for (namespace, rows) in by_namespace {
+            self.write(&namespace, rows, Vec::new()).await?;
+        }
+        for record in &records {
+            self.namespaces.remember(
+                &record.id,
+                &self.namespaces.namespace_for_payload(&record.payload),
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        query_vector: &[f32],
+        limit: usize,
+        filters: Option<Filter>,
+    ) -> RookResult<Vec<VectorSearchResult>> {
+        let mut body = json!({
+            "rank_by": ["vector", "ANN", query_vector],
+            "top_k": limit,
+            "include_attributes": [PAYLOAD_ATTRIBUTE],
+        });
+        if let Some(f) = filters.as_ref().and_then(Self::convert_filter) {
+            body["filters"] = f;
+        }
+
+        let mut results = Vec::new();
+        for namespace in self.scoped_namespaces(filters.as_ref()).await? {
+            for row in self.query(&namespace, body.clone()).await? {
+                let distance = row.get("$dist").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                let record = Self::row_to_record(row);
+                self.namespaces.remember(&record.id, &namespace);
+                results.push(VectorSearchResult {
+                    id: record.id,
+                    score: self.distance_to_score(distance),
+                    payload: record.payload,
+                });
+            }
+        }
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get(&self, id: &str) -> RookResult<Option<VectorRecord>> {
+        Ok(self.locate(id).await?.map(|(_, record)| record))
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        vector: Option<Vec<f32>>,
+        payload: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<()> {
+        let (namespace, existing) = self
+            .locate(id)
+            .await?
+            .ok_or_else(|| RookError::not_found(id))?;
+
+        let new_payload = if let Some(p) = payload {
+            let mut merged = existing.payload;
+            merged.extend(p);
+            merged
+        } else {
+            existing.payload
+        };
+
+        let record = VectorRecord {
+            id: id.to_string(),
+            vector: vector.unwrap_or(existing.vector),
+            payload: new_payload,
+            score: None,
+        };
+
+        // A changed scope field moves the record to another namespace
+        let target = self.namespaces.namespace_for_payload(&record.payload);
+        if target != namespace {
+            self.write(&namespace, Vec::new(), vec![id.to_string()]).await?;
+        }
+
+        self.insert(vec![record]).await
+    }
+
+    async fn delete(&self, id: &str) -> RookResult<()> {
+        if let Some((namespace, _)) = self.locate(id).await? {
+            self.write(&namespace, Vec::new(), vec![id.to_string()]).await?;
+        }
+        self.namespaces.forget(id);
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        filters: Option<Filter>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        let limit = limit.unwrap_or(PAGE_SIZE);
+        let mut body = json!({
+            "rank_by": ["id", "asc"],
+            "top_k": limit,
+            "include_attributes": true,
+        });
+        if let Some(f) = filters.as_ref().and_then(Self::convert_filter) {
+            body["filters"] = f;
+        }
+
+        let mut records = Vec::new();
+        for namespace in self.scoped_namespaces(filters.as_ref()).await? {
+            for row in self.query(&namespace, body.clone()).await? {
+                let record = Self::row_to_record(row);
+                self.namespaces.remember(&record.id, &namespace);
+                records.push(record);
+            }
+            if records.len() >= limit {
+                break;
+            }
+        }
+
+        records.truncate(limit);
+        Ok(records)
+    }
+
+    async fn list_collections(&self) -> RookResult<Vec<String>> {
+        let mut collections: Vec<String> = self
+            .collection_namespaces()
+            .await?
+            .into_iter()
+            .filter(|n| self.namespaces.owns(n))
+            .collect();
+        collections.sort();
+        Ok(collections)
+    }
+
+    async fn delete_collection(&self, name: &str) -> RookResult<()> {
+        let namespaces = if name == self.namespaces.base() {
+            self.list_collections().await?
+        } else {
+            vec![name.to_string()]
+        };
+
+        for namespace in namespaces {
+            let response = self
+                .client
+                .delete(self.namespace_url(&namespace))
+                .bearer_auth(&self.api_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    RookError::vector_store(format!("Failed to delete namespace: {}", e))
+                })?;
+
+            if !response.status().is_success()
+                && response.status() != reqwest::StatusCode::NOT_FOUND
+            {
+                let error = response.text().await.unwrap_or_default();
+                return Err(RookError::vector_store(format!(
+                    "Failed to delete namespace {}: {}",
+                    namespace, error
+                )));
+            }
+        }
+
+        self.namespaces.clear();
+        Ok(())
+    }
+
+    async fn collection_info(&self, name: &str) -> RookResult<CollectionInfo> {
+        let namespaces = if name == self.namespaces.base() {
+            self.list_collections().await?
+        } else {
+            vec![name.to_string()]
+        };
+
+        let mut vector_count = 0;
+        for namespace in namespaces {
+            let response = self
+                .client
+                .get(format!("{}/v1/namespaces/{}/metadata", self.base_url, namespace))
+                .bearer_auth(&self.api_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    RookError::vector_store(format!("Failed to get namespace metadata: {}", e))
+                })?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                continue;
+            }
+            if !response.status().is_success() {
+                let error = response.text().await.unwrap_or_default();
+                return Err(RookError::vector_store(format!(
+                    "Failed to get namespace metadata: {}",
+                    error
+                )));
+            }
+
+            let metadata: TurbopufferMetadata = response
+                .json()
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+            vector_count += metadata.approx_row_count;
+        }
+
+        Ok(CollectionInfo {
+            name: name.to_string(),
+            vector_count,
+            dimension: self.config.embedding_model_dims,
+            distance: self.distance(),
+        })
+    }
+
+    async fn reset(&self) -> RookResult<()> {
+        self.delete_collection(self.namespaces.base()).await
+    }
+
+    fn collection_name(&self) -> &str {
+        &self.config.collection_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_translation() {
+        let filter = Filter::and(vec![
+            Filter::eq("user_id", "alice"),
+            Filter::Condition(FilterCondition::gte("importance", 0.5)),
+            Filter::not(Filter::Condition(FilterCondition::contains("data", "secret"))),
+        ]);
+
+        assert_eq!(
+            TurbopufferVectorStore::convert_filter(&filter).unwrap(),
+            json!([
+                "And",
+                [
+                    ["user_id", "Eq", "alice"],
+                    ["importance", "Gte", 0.5],
+                    ["Not", ["data", "Glob", "*secret*"]]
+                ]
+            ])
+        );
+
+        let or_with_wildcard = Filter::or(vec![
+            Filter::eq("agent_id", "bot"),
+            Filter::Condition(FilterCondition {
+                field: "agent_id".to_string(),
+                operator: FilterOperator::Wildcard,
+            }),
+        ]);
+        assert!(TurbopufferVectorStore::convert_filter(&or_with_wildcard).is_none());
+    }
+
+    #[test]
+    fn test_row_roundtrip_keeps_nested_payload() {
+        let record = VectorRecord::new(
+            "m1",
+            vec![0.1, 0.2],
+            HashMap::from([
+                ("user_id".to_string(), json!("alice")),
+                ("tags".to_string(), json!(["a", "b"])),
+                ("extra".to_string(), json!({"nested": true})),
+            ]),
+        );
+
+        let row = TurbopufferVectorStore::record_to_row(&record);
+        assert_eq!(row["user_id"], "alice");
+        assert_eq!(row["tags"], json!(["a", "b"]));
+        assert!(row.get("extra").is_none());
+
+        let restored = TurbopufferVectorStore::row_to_record(row.as_object().unwrap().clone());
+        assert_eq!(restored.id, "m1");
+        assert_eq!(restored.vector, vec![0.1, 0.2]);
+        assert_eq!(restored.payload, record.payload);
+    }
+}
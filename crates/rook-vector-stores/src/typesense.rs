@@ -0,0 +1,810 @@
+//! Typesense vector store implementation.
+//!
+//! Each collection maps to a family of Typesense collections: records are
+//! routed to `{collection}__{user_id}` so per-user queries only touch that
+//! user's data (see [`crate::namespace`]). Scalar payload fields are indexed
+//! through an auto-typed wildcard field; the full payload is kept as JSON in
+//! `_payload`.
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+
+use rook_core::error::{RookError, RookResult};
+use rook_core::traits::{
+    CollectionInfo, DistanceMetric, VectorRecord, VectorSearchResult, VectorStore,
+    VectorStoreConfig,
+};
+use rook_core::types::{Filter, FilterCondition, FilterOperator};
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::namespace::{NamespaceMapper, NamespaceScope};
+
+/// Field holding the embedding.
+const VECTOR_FIELD: &str = "embedding";
+
+/// Field holding the full JSON payload.
+const PAYLOAD_FIELD: &str = "_payload";
+
+/// Maximum page size accepted by Typesense.
+const PAGE_SIZE: usize = 250;
+
+/// Typesense vector store implementation.
+pub struct TypesenseVectorStore {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    dimension: RwLock<usize>,
+    distance: RwLock<DistanceMetric>,
+    /// Collections known to exist, so writes only create each one once.
+    created: RwLock<HashSet<String>>,
+    namespaces: NamespaceMapper,
+    config: VectorStoreConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypesenseCollection {
+    name: String,
+    #[serde(default)]
+    num_documents: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypesenseSearchResponse {
+    #[serde(default)]
+    hits: Vec<TypesenseHit>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypesenseHit {
+    document: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    vector_distance: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TypesenseMultiSearchResponse {
+    results: Vec<TypesenseSearchResponse>,
+}
+
+impl TypesenseVectorStore {
+    /// Create a new Typesense vector store.
+    ///
+    /// Provider config keys:
+    /// - `api_key` (or `TYPESENSE_API_KEY`)
+    /// - `url` - server URL (default: `http://localhost:8108`)
+    /// - `distance_metric` - `cosine` (default) or `inner_product`
+    /// - `namespace_field` - payload field used for collection scoping
+    ///   (default: `user_id`, empty string disables)
+    pub async fn new(config: VectorStoreConfig) -> RookResult<Self> {
+        let api_key = config
+            .config
+            .get("api_key")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or_else(|| std::env::var("TYPESENSE_API_KEY").ok())
+            .ok_or_else(|| {
+                RookError::Configuration(
+                    "Typesense API key required. Set TYPESENSE_API_KEY or provide api_key."
+                        .to_string(),
+                )
+            })?;
+
+        let base_url = config
+            .config
+            .get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("http://localhost:8108")
+            .trim_end_matches('/')
+            .to_string();
+
+        let distance = match config.config.get("distance_metric").and_then(|v| v.as_str()) {
+            Some("inner_product" | "dot") => DistanceMetric::DotProduct,
+            _ => DistanceMetric::Cosine,
+        };
+
+        Ok(Self {
+            client: Client::new(),
+            api_key,
+            base_url,
+            dimension: RwLock::new(config.embedding_model_dims),
+            distance: RwLock::new(distance),
+            created: RwLock::new(HashSet::new()),
+            namespaces: NamespaceMapper::from_config(&config),
+            config,
+        })
+    }
+
+    fn distance(&self) -> DistanceMetric {
+        *self.distance.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("X-TYPESENSE-API-KEY", &self.api_key)
+    }
+
+    async fn check(response: reqwest::Response, action: &str) -> RookResult<reqwest::Response> {
+        if !response.status().is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(RookError::vector_store(format!("Failed to {}: {}", action, error)));
+        }
+        Ok(response)
+    }
+
+    /// Create a collection for a namespace unless it already exists.
+    async fn ensure_collection(&self, name: &str) -> RookResult<()> {
+        if self
+            .created
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(name)
+        {
+            return Ok(());
+        }
+
+        let vector_field = match self.distance() {
+            DistanceMetric::DotProduct => json!({
+                "name": VECTOR_FIELD,
+                "type": "float[]",
+                "num_dim": *self.dimension.read().unwrap_or_else(|e| e.into_inner()),
+                "vec_dist": "ip",
+            }),
+            _ => json!({
+                "name": VECTOR_FIELD,
+                "type": "float[]",
+                "num_dim": *self.dimension.read().unwrap_or_else(|e| e.into_inner()),
+            }),
+        };
+
+        let schema = json!({
+            "name": name,
+            "fields": [
+                vector_field,
+                { "name": PAYLOAD_FIELD, "type": "string", "index": false, "optional": true },
+                { "name": ".*", "type": "auto", "optional": true },
+            ],
+        });
+
+        let response = self
+            .request(reqwest::Method::POST, "/collections")
+            .json(&schema)
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to create collection: {}", e)))?;
+
+        // 409 means the collection already exists
+        if response.status() != StatusCode::CONFLICT {
+            Self::check(response, "create collection").await?;
+        }
+
+        self.created
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string());
+        Ok(())
+    }
+
+    /// List every Typesense collection that belongs to this store.
+    async fn owned_collections(&self) -> RookResult<Vec<TypesenseCollection>> {
+        let response = self
+            .request(reqwest::Method::GET, "/collections")
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to list collections: {}", e)))?;
+        let response = Self::check(response, "list collections").await?;
+
+        let collections: Vec<TypesenseCollection> = response
+            .json()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+
+        Ok(collections
+            .into_iter()
+            .filter(|c| self.namespaces.owns(&c.name))
+            .collect())
+    }
+
+    async fn scoped_collections(&self, filter: Option<&Filter>) -> RookResult<Vec<String>> {
+        match self.namespaces.scope(filter) {
+            NamespaceScope::Only(collections) => Ok(collections),
+            NamespaceScope::All => Ok(self
+                .owned_collections()
+                .await?
+                .into_iter()
+                .map(|c| c.name)
+                .collect()),
+        }
+    }
+
+    /// Fetch a document from one collection. Missing documents or
+    /// collections return `None`.
+    async fn fetch(&self, collection: &str, id: &str) -> RookResult<Option<VectorRecord>> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/collections/{}/documents/{}", collection, id),
+            )
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to get document: {}", e)))?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = Self::check(response, "get document").await?;
+
+        let document: serde_json::Map<String, serde_json::Value> = response
+            .json()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+        Ok(Some(Self::document_to_record(document)))
+    }
+
+    /// Find an id, trying its remembered collection first.
+    async fn locate(&self, id: &str) -> RookResult<Option<(String, VectorRecord)>> {
+        let mut candidates = Vec::new();
+        if let Some(collection) = self.namespaces.location(id) {
+            candidates.push(collection);
+        }
+        for collection in self.scoped_collections(None).await? {
+            if !candidates.contains(&collection) {
+                candidates.push(collection);
+            }
+        }
+
+        for collection in candidates {
+            if let Some(record) = self.fetch(&collection, id).await? {
+                self.namespaces.remember(id, &collection);
+                return Ok(Some((collection, record)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn delete_document(&self, collection: &str, id: &str) -> RookResult<()> {
+        let response = self
+            .request(
+                reqwest::Method::DELETE,
+                &format!("/collections/{}/documents/{}", collection, id),
+            )
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to delete document: {}", e)))?;
+
+        if response.status() != StatusCode::NOT_FOUND {
+            Self::check(response, "delete document").await?;
+        }
+        Ok(())
+    }
+
+    fn record_to_document(record: &VectorRecord) -> serde_json::Value {
+        let mut document = serde_json::Map::new();
+        for (key, value) in &record.payload {
+            if key != "id" && key != VECTOR_FIELD && Self::is_indexable(value) {
+                document.insert(key.clone(), value.clone());
+            }
+        }
+        document.insert("id".to_string(), json!(record.id));
+        document.insert(VECTOR_FIELD.to_string(), json!(record.vector));
+        document.insert(
+            PAYLOAD_FIELD.to_string(),
+            json!(serde_json::to_string(&record.payload).unwrap_or_default()),
+        );
+        serde_json::Value::Object(document)
+    }
+
+    /// Auto-typed fields must be scalars or arrays of scalars.
+    fn is_indexable(value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::Null | serde_json::Value::Object(_) => false,
+            serde_json::Value::Array(items) => items
+                .iter()
+                .all(|v| !v.is_object() && !v.is_array() && !v.is_null()),
+            _ => true,
+        }
+    }
+
+    fn document_to_record(document: serde_json::Map<String, serde_json::Value>) -> VectorRecord {
+        let id = document
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let vector = document
+            .get(VECTOR_FIELD)
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_f64().map(|f| f as f32)).collect())
+            .unwrap_or_default();
+        let payload = document
+            .get(PAYLOAD_FIELD)
+            .and_then(|v| v.as_str())
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default();
+
+        VectorRecord {
+            id,
+            vector,
+            payload,
+            score: None,
+        }
+    }
+
+    /// Translate a filter into a Typesense `filter_by` expression.
+    fn convert_filter(filter: &Filter) -> RookResult<Option<String>> {
+        match filter {
+            Filter::Condition(cond) => Self::convert_condition(cond),
+            Filter::And(filters) => {
+                let mut parts = Vec::new();
+                for f in filters {
+                    if let Some(part) = Self::convert_filter(f)? {
+                        parts.push(part);
+                    }
+                }
+                Ok(Self::join(parts, " && "))
+            }
+            Filter::Or(filters) => {
+                let mut parts = Vec::new();
+                for f in filters {
+                    match Self::convert_filter(f)? {
+                        Some(part) => parts.push(part),
+                        // An OR with an unconstrained branch matches everything
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Self::join(parts, " || "))
+            }
+            Filter::Not(inner) => match inner.as_ref() {
+                Filter::Condition(cond) => Self::negate_condition(cond),
+                Filter::Not(inner) => Self::convert_filter(inner),
+                _ => Err(RookError::validation(
+                    "Typesense filters only support negating a single condition",
+                )),
+            },
+        }
+    }
+
+    fn join(parts: Vec<String>, separator: &str) -> Option<String> {
+        match parts.len() {
+            0 => None,
+            1 => parts.into_iter().next(),
+            _ => Some(format!("({})", parts.join(separator))),
+        }
+    }
+
+    fn convert_condition(cond: &FilterCondition) -> RookResult<Option<String>> {
+        let field = cond.field.as_str();
+        let expr = match &cond.operator {
+            FilterOperator::Eq(value) => format!("{}:={}", field, Self::literal(value)?),
+            FilterOperator::Ne(value) => format!("{}:!={}", field, Self::literal(value)?),
+            FilterOperator::Gt(value) => format!("{}:>{}", field, Self::literal(value)?),
+            FilterOperator::Gte(value) => format!("{}:>={}", field, Self::literal(value)?),
+            FilterOperator::Lt(value) => format!("{}:<{}", field, Self::literal(value)?),
+            FilterOperator::Lte(value) => format!("{}:<={}", field, Self::literal(value)?),
+            FilterOperator::In(values) => format!("{}:={}", field, Self::literal_list(values)?),
+            FilterOperator::Nin(values) => format!("{}:!={}", field, Self::literal_list(values)?),
+            FilterOperator::Between { min, max } => format!(
+                "{}:[{}..{}]",
+                field,
+                Self::literal(min)?,
+                Self::literal(max)?
+            ),
+            FilterOperator::Wildcard => return Ok(None),
+            other => {
+                return Err(RookError::validation(format!(
+                    "Typesense does not support the {:?} filter operator",
+                    other
+                )))
+            }
+        };
+        Ok(Some(expr))
+    }
+
+    fn negate_condition(cond: &FilterCondition) -> RookResult<Option<String>> {
+        let negated = match &cond.operator {
+            FilterOperator::Eq(value) => FilterOperator::Ne(value.clone()),
+            FilterOperator::Ne(value) => FilterOperator::Eq(value.clone()),
+            FilterOperator::Gt(value) => FilterOperator::Lte(value.clone()),
+            FilterOperator::Gte(value) => FilterOperator::Lt(value.clone()),
+            FilterOperator::Lt(value) => FilterOperator::Gte(value.clone()),
+            FilterOperator::Lte(value) => FilterOperator::Gt(value.clone()),
+            FilterOperator::In(values) => FilterOperator::Nin(values.clone()),
+            FilterOperator::Nin(values) => FilterOperator::In(values.clone()),
+            other => {
+                return Err(RookError::validation(format!(
+                    "Typesense does not support negating the {:?} filter operator",
+                    other
+                )))
+            }
+        };
+        Self::convert_condition(&FilterCondition {
+            field: cond.field.clone(),
+            operator: negated,
+        })
+    }
+
+    fn literal(value: &serde_json::Value) -> RookResult<String> {
+        match value {
+            serde_json::Value::String(s) => Ok(format!("`{}`", s.replace('`', "\\`"))),
+            serde_json::Value::Number(n) => Ok(n.to_string()),
+            serde_json::Value::Bool(b) => Ok(b.to_string()),
+            other => Err(RookError::validation(format!(
+                "Typesense filters do not support the value {}",
+                other
+            ))),
+        }
+    }
+
+    fn literal_list(values: &[serde_json::Value]) -> RookResult<String> {
+        let literals = values
+            .iter()
+            .map(Self::literal)
+            .collect::<RookResult<Vec<_>>>()?;
+        Ok(format!("[{}]", literals.join(",")))
+    }
+
+    /// Convert a Typesense vector distance to a similarity score.
+    fn distance_to_score(distance: f32) -> f32 {
+        1.0 - distance
+    }
+}
+
+#[async_trait]
+impl VectorStore for TypesenseVectorStore {
+    async fn create_collection(
+        &self,
+        _name: &str,
+        dimension: usize,
+        distance: DistanceMetric,
+    ) -> RookResult<()> {
+        // Per-user collections are created lazily on first write
+        *self.dimension.write().unwrap_or_else(|e| e.into_inner()) = dimension;
+        *self.distance.write().unwrap_or_else(|e| e.into_inner()) = distance;
+        Ok(())
+    }
+
+    async fn insert(&self, records: Vec<VectorRecord>) -> RookResult<()> {
+        let mut by_collection: HashMap<String, Vec<&VectorRecord>> = HashMap::new();
+        for record in &records {
+            by_collection
+                .entry(self.namespaces.namespace_for_payload(&record.payload))
+                .or_default()
+                .push(record);
+        }
+
+        for (collection, records) in by_collection {
+            self.ensure_collection(&collection).await?;
+
+            let body = records
+                .iter()
+                .map(|r| Self::record_to_document(r).to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let response = self
+                .request(
+                    reqwest::Method::POST,
+                    &format!("/collections/{}/documents/import?action=upsert", collection),
+                )
+                .header("Content-Type", "text/plain")
+                .body(body)
+                .send()
+                .await
+                .map_err(|e| RookError::vector_store(format!("Failed to upsert: {}", e)))?;
+            let response = Self::check(response, "upsert documents").await?;
+
+            // Import reports per-document results as JSON lines
+            let text = response.text().await.unwrap_or_default();
+            for line in text.lines() {
+                let result: serde_json::Value = serde_json::from_str(line).unwrap_or_default();
+                if result.get("success").and_then(|v| v.as_bool()) == Some(false) {
+                    return Err(RookError::vector_store(format!(
+                        "Failed to upsert document: {}",
+                        result.get("error").and_then(|v| v.as_str()).unwrap_or(line)
+                    )));
+                }
+            }
+
+            for record in records {
+                self.namespaces.remember(&record.id, &collection);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        query_vector: &[f32],
+        limit: usize,
+        filters: Option<Filter>,
+    ) -> RookResult<Vec<VectorSearchResult>> {
+        let filter_by = match filters.as_ref() {
+            Some(f) => Self::convert_filter(f)?,
+            None => None,
+        };
+        let vector = query_vector
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let searches: Vec<_> = self
+            .scoped_collections(filters.as_ref())
+            .await?
+            .into_iter()
+            .map(|collection| {
+                let mut search = json!({
+                    "collection": collection,
+                    "q": "*",
+                    "vector_query": format!("{}:([{}], k:{})", VECTOR_FIELD, vector, limit),
+                    "per_page": limit,
+                    "exclude_fields": VECTOR_FIELD,
+                });
+                if let Some(ref f) = filter_by {
+                    search["filter_by"] = json!(f);
+                }
+                search
+            })
+            .collect();
+
+        if searches.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self
+            .request(reqwest::Method::POST, "/multi_search")
+            .json(&json!({ "searches": searches }))
+            .send()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to search: {}", e)))?;
+        let response = Self::check(response, "search").await?;
+
+        let result: TypesenseMultiSearchResponse = response
+            .json()
+            .await
+            .map_err(|e| RookError::vector_store(format!("Failed to parse response: {}", e)))?;
+
+        let mut results = Vec::new();
+        for search in result.results {
+            if let Some(error) = search.error {
+                // Collections that have not been written to yet return 404s
+                if error.contains("not found") {
+                    continue;
+                }
+                return Err(RookError::vector_store(format!("Failed to search: {}", error)));
+            }
+            for hit in search.hits {
+                let score = Self::distance_to_score(hit.vector_distance.unwrap_or(1.0));
+                let record = Self::document_to_record(hit.document);
+                results.push(VectorSearchResult {
+                    id: record.id,
+                    score,
+                    payload: record.payload,
+                });
+            }
+        }
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get(&self, id: &str) -> RookResult<Option<VectorRecord>> {
+        Ok(self.locate(id).await?.map(|(_, record)| record))
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        vector: Option<Vec<f32>>,
+        payload: Option<HashMap<String, serde_json::Value>>,
+    ) -> RookResult<()> {
+        let (collection, existing) = self
+            .locate(id)
+            .await?
+            .ok_or_else(|| RookError::not_found(id))?;
+
+        let new_payload = if let Some(p) = payload {
+            let mut merged = existing.payload;
+            merged.extend(p);
+            merged
+        } else {
+            existing.payload
+        };
+
+        let record = VectorRecord {
+            id: id.to_string(),
+            vector: vector.unwrap_or(existing.vector),
+            payload: new_payload,
+            score: None,
+        };
+
+        // A changed scope field moves the record to another collection
+        if self.namespaces.namespace_for_payload(&record.payload) != collection {
+            self.delete_document(&collection, id).await?;
+        }
+
+        self.insert(vec![record]).await
+    }
+
+    async fn delete(&self, id: &str) -> RookResult<()> {
+        if let Some((collection, _)) = self.locate(id).await? {
+            self.delete_document(&collection, id).await?;
+        }
+        self.namespaces.forget(id);
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        filters: Option<Filter>,
+        limit: Option<usize>,
+    ) -> RookResult<Vec<VectorRecord>> {
+        let limit = limit.unwrap_or(PAGE_SIZE);
+        let filter_by = match filters.as_ref() {
+            Some(f) => Self::convert_filter(f)?,
+            None => None,
+        };
+
+        let mut records = Vec::new();
+        for collection in self.scoped_collections(filters.as_ref()).await? {
+            let mut page = 1;
+            while records.len() < limit {
+                let per_page = (limit - records.len()).min(PAGE_SIZE);
+                let mut query = vec![
+                    ("q", "*".to_string()),
+                    ("query_by", PAYLOAD_FIELD.to_string()),
+                    ("per_page", per_page.to_string()),
+                    ("page", page.to_string()),
+                ];
+                if let Some(ref f) = filter_by {
+                    query.push(("filter_by", f.clone()));
+                }
+
+                let response = self
+                    .request(
+                        reqwest::Method::GET,
+                        &format!("/collections/{}/documents/search", collection),
+                    )
+                    .query(&query)
+                    .send()
+                    .await
+                    .map_err(|e| RookError::vector_store(format!("Failed to list: {}", e)))?;
+
+                if response.status() == StatusCode::NOT_FOUND {
+                    break;
+                }
+                let response = Self::check(response, "list documents").await?;
+
+                let result: TypesenseSearchResponse = response.json().await.map_err(|e| {
+                    RookError::vector_store(format!("Failed to parse response: {}", e))
+                })?;
+
+                let count = result.hits.len();
+                for hit in result.hits {
+                    let record = Self::document_to_record(hit.document);
+                    self.namespaces.remember(&record.id, &collection);
+                    records.push(record);
+                }
+                if count < per_page {
+                    break;
+                }
+                page += 1;
+            }
+            if records.len() >= limit {
+                break;
+            }
+        }
+
+        records.truncate(limit);
+        Ok(records)
+    }
+
+    async fn list_collections(&self) -> RookResult<Vec<String>> {
+        let mut collections: Vec<String> = self
+            .owned_collections()
+            .await?
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        collections.sort();
+        Ok(collections)
+    }
+
+    async fn delete_collection(&self, name: &str) -> RookResult<()> {
+        let collections = if name == self.namespaces.base() {
+            self.list_collections().await?
+        } else {
+            vec![name.to_string()]
+        };
+
+        for collection in collections {
+            let response = self
+                .request(reqwest::Method::DELETE, &format!("/collections/{}", collection))
+                .send()
+                .await
+                .map_err(|e| {
+                    RookError::vector_store(format!("Failed to delete collection: {}", e))
+                })?;
+
+            if response.status() != StatusCode::NOT_FOUND {
+                Self::check(response, "delete collection").await?;
+            }
+            self.created
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&collection);
+        }
+
+        self.namespaces.clear();
+        Ok(())
+    }
+
+    async fn collection_info(&self, name: &str) -> RookResult<CollectionInfo> {
+        let vector_count = self
+            .owned_collections()
+            .await?
+            .into_iter()
+            .filter(|c| name == self.namespaces.base() || c.name == name)
+            .map(|c| c.num_documents)
+            .sum();
+
+        Ok(CollectionInfo {
+            name: name.to_string(),
+            vector_count,
+            dimension: *self.dimension.read().unwrap_or_else(|e| e.into_inner()),
+            distance: self.distance(),
+        })
+    }
+
+    async fn reset(&self) -> RookResult<()> {
+        self.delete_collection(self.namespaces.base()).await
+    }
+
+    fn collection_name(&self) -> &str {
+        &self.config.collection_name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_translation() {
+        let filter = Filter::and(vec![
+            Filter::eq("user_id", "o`brien"),
+            Filter::between("importance", 0.2, 0.8),
+            Filter::or(vec![
+                Filter::in_list("category", vec!["work".into(), "travel".into()]),
+                Filter::not(Filter::Condition(FilterCondition::gte("age", 30))),
+            ]),
+        ]);
+
+        assert_eq!(
+            TypesenseVectorStore::convert_filter(&filter).unwrap().unwrap(),
+            "(user_id:=`o\\`brien` && importance:[0.2..0.8] && \
+             (category:=[`work`,`travel`] || age:<30))"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_filters_are_rejected() {
+        let contains = Filter::contains("data", "coffee");
+        assert!(TypesenseVectorStore::convert_filter(&contains).is_err());
+
+        let negated_group = Filter::not(Filter::and(vec![
+            Filter::eq("a", 1),
+            Filter::eq("b", 2),
+        ]));
+        assert!(TypesenseVectorStore::convert_filter(&negated_group).is_err());
+    }
+}
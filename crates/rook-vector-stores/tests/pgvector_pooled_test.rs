@@ -31,6 +31,7 @@ fn create_test_config(collection_name: &str, pool_config: Option<PostgresPoolCon
         collection_name: collection_name.to_string(),
         embedding_model_dims: 384,
         pool: pool_config,
+        quantization: None,
         config: serde_json::json!({
             "url": get_test_url().expect("DATABASE_URL must be set")
         }),
@@ -63,6 +64,7 @@ async fn test_pool_init_default_config() -> RookResult<()> {
         collection_name: "test_pool_default".to_string(),
         embedding_model_dims: 384,
         pool: None, // Use defaults
+        quantization: None,
         config: serde_json::json!({ "url": url }),
     };
 
@@ -101,6 +103,7 @@ async fn test_pool_init_custom_config() -> RookResult<()> {
         collection_name: "test_pool_custom".to_string(),
         embedding_model_dims: 384,
         pool: Some(pool_config),
+        quantization: None,
         config: serde_json::json!({ "url": url }),
     };
 